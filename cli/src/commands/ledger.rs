@@ -905,7 +905,7 @@ fn sign_and_submit_action(wallet_handle: WalletHandle, pool_handle: PoolHandle,
     let nodes = match nodes {
         Some(n) =>
             Some(serde_json::to_string(&n)
-                .map_err(|err| IndyError { error_code: ErrorCode::CommonInvalidStructure, message: err.to_string(), indy_backtrace: None })?),
+                .map_err(|err| IndyError { error_code: ErrorCode::CommonInvalidStructure, message: err.to_string(), indy_backtrace: None, indy_context: Vec::new(), vendor_code: None })?),
         None => None
     };
 
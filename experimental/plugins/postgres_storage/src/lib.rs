@@ -37,7 +37,7 @@ use libindy::ErrorCode;
 use utils::sequence::SequenceUtils;
 use utils::crypto::base64 as util_base64;
 use utils::ctypes;
-use wql::storage::{WalletStorage, StorageRecord, StorageIterator, Tag, TagName, EncryptedValue};
+use wql::storage::{WalletStorage, StorageOperation, StorageRecord, StorageIterator, Tag, TagName, EncryptedValue};
 use wql::language;
 use errors::wallet::WalletStorageError;
 use postgres_storage::WalletStorageType;
@@ -60,7 +60,7 @@ pub extern fn postgresstorage_init() -> libindy::ErrorCode {
         return err;
     }
 
-    libindy::wallet::register_wallet_storage(
+    let err = libindy::wallet::register_wallet_storage(
         postgres_storage_name.as_ptr(),
         PostgresWallet::create,
         PostgresWallet::open,
@@ -86,6 +86,25 @@ pub extern fn postgresstorage_init() -> libindy::ErrorCode {
         PostgresWallet::get_search_total_count,
         PostgresWallet::fetch_search_next_record,
         PostgresWallet::free_search,
+    );
+
+    if err != ErrorCode::Success {
+        return err;
+    }
+
+    let err = libindy::wallet::register_wallet_storage_batch(
+        postgres_storage_name.as_ptr(),
+        PostgresWallet::batch_records,
+    );
+
+    if err != ErrorCode::Success {
+        return err;
+    }
+
+    libindy::wallet::register_wallet_storage_search_chunk(
+        postgres_storage_name.as_ptr(),
+        PostgresWallet::fetch_search_next_records,
+        PostgresWallet::free_search_records,
     )
 }
 
@@ -94,6 +113,16 @@ pub extern fn init_storagetype(config: *const c_char, credentials: *const c_char
     return PostgresWallet::init(config, credentials);
 }
 
+#[no_mangle]
+pub extern fn snapshot_storage(id: *const c_char, config: *const c_char, credentials: *const c_char, snapshot_id: *mut i64) -> libindy::ErrorCode {
+    return PostgresWallet::snapshot(id, config, credentials, snapshot_id);
+}
+
+#[no_mangle]
+pub extern fn restore_storage(id: *const c_char, config: *const c_char, credentials: *const c_char, snapshot_id: i64) -> libindy::ErrorCode {
+    return PostgresWallet::restore(id, config, credentials, snapshot_id);
+}
+
 struct PostgresStorageContext {
     // TODO save handle, config and credentials in case we need to re-connect to database
     _xhandle: i32,        // reference returned to client to track open wallet connection
@@ -111,6 +140,27 @@ struct PostgresWalletRecord {
     tags: CString
 }
 
+#[derive(Debug, Deserialize)]
+struct PostgresStorageOperation {
+    op: String,
+    #[serde(rename = "type")]
+    type_: String,
+    id: String,
+    value: Option<String>,
+    tags: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PostgresSearchRecord {
+    id: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    type_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Clone)]
 struct PostgresWalletRecordSet {
     idx: usize,
@@ -140,6 +190,11 @@ lazy_static! {
     static ref POSTGRES_ACTIVE_SEARCHES: Mutex<HashMap<i32, PostgresWalletRecordSet>> = Default::default();
 }
 
+lazy_static! {
+    // cache of records json fetched by chunks
+    static ref POSTGRES_ACTIVE_SEARCH_RECORDS: Mutex<HashMap<i32, CString>> = Default::default();
+}
+
 pub struct PostgresWallet {}
 
 impl PostgresWallet {
@@ -626,6 +681,45 @@ impl PostgresWallet {
     }
 
 
+    pub extern fn batch_records(xhandle: i32, operations_json: *const c_char) -> ErrorCode {
+        check_useful_c_str!(operations_json, ErrorCode::CommonInvalidState);
+
+        let handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
+
+        if !handles.contains_key(&xhandle) {
+            return ErrorCode::CommonInvalidState;
+        }
+
+        let operations = match _operations_from_json(&operations_json) {
+            Ok(operations) => operations,
+            Err(err) => {
+                error!("Invalid batch operations. Error details: {:?}", err);
+                return ErrorCode::CommonInvalidState;
+            }
+        };
+
+        let wallet_context = handles.get(&xhandle).unwrap();
+        let wallet_box = &wallet_context.phandle;
+        let storage = &*wallet_box;
+
+        let res = storage.batch(&operations);
+
+        match res {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => {
+                match err {
+                    WalletStorageError::ItemNotFound => ErrorCode::WalletItemNotFound,
+                    WalletStorageError::ItemAlreadyExists => ErrorCode::WalletItemAlreadyExists,
+                    _ => {
+                        error!("Error applying batch operations. Error details: {:?}", err);
+                        ErrorCode::WalletStorageError
+                    }
+                }
+            }
+        }
+    }
+
+
     pub extern fn get_storage_metadata(xhandle: i32, metadata_ptr: *mut *const c_char, metadata_handle: *mut i32) -> ErrorCode {
         let handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
 
@@ -868,6 +962,65 @@ impl PostgresWallet {
     }
 
 
+    pub extern fn fetch_search_next_records(xhandle: i32,
+                                            search_handle: i32,
+                                            count: usize,
+                                            records_json_ptr: *mut *const c_char,
+                                            records_handle: *mut i32) -> ErrorCode {
+        let handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
+
+        if !handles.contains_key(&xhandle) {
+            return ErrorCode::CommonInvalidState;
+        }
+
+        let mut searches = POSTGRES_ACTIVE_SEARCHES.lock().unwrap();
+
+        let records = match searches.get_mut(&search_handle) {
+            Some(records) => records,
+            None => return ErrorCode::CommonInvalidState
+        };
+
+        let end = ::std::cmp::min(records.idx + count, records.records.len());
+
+        let chunk = match _postgresrecords_to_json(&records.records[records.idx..end]) {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                error!("Error serializing search records. Error details: {:?}", err);
+                return ErrorCode::WalletStorageError;
+            }
+        };
+        records.idx = end;
+
+        let handle = SequenceUtils::get_next_id();
+
+        let mut handles = POSTGRES_ACTIVE_SEARCH_RECORDS.lock().unwrap();
+        handles.insert(handle, chunk);
+
+        unsafe { *records_json_ptr = handles.get(&handle).unwrap().as_ptr(); }
+        unsafe { *records_handle = handle };
+
+        ErrorCode::Success
+    }
+
+
+    pub extern fn free_search_records(xhandle: i32, records_handle: i32) -> ErrorCode {
+        let handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
+
+        if !handles.contains_key(&xhandle) {
+            return ErrorCode::CommonInvalidState;
+        }
+
+        let mut handles = POSTGRES_ACTIVE_SEARCH_RECORDS.lock().unwrap();
+
+        if !handles.contains_key(&records_handle) {
+            return ErrorCode::CommonInvalidState;
+        }
+        handles.remove(&records_handle);
+
+        ErrorCode::Success
+    }
+
+
     pub extern fn free_search(xhandle: i32, search_handle: i32) -> ErrorCode {
         let handles = POSTGRES_OPEN_WALLETS.lock().unwrap();
 
@@ -919,6 +1072,54 @@ impl PostgresWallet {
             Err(_err) => ErrorCode::WalletStorageError
         }
     }
+
+
+    /// Copies wallet data into snapshot tables, wallet doesn't need to be opened
+    pub extern fn snapshot(id: *const c_char,
+                           config: *const c_char,
+                           credentials: *const c_char,
+                           snapshot_id: *mut i64) -> ErrorCode {
+        check_useful_c_str!(id, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(config, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(credentials, ErrorCode::CommonInvalidState);
+
+        let storage_type = ::postgres_storage::PostgresStorageType::new();
+        match storage_type.snapshot_storage(&id, Some(&config), Some(&credentials)) {
+            Ok(id) => {
+                unsafe { *snapshot_id = id; }
+                ErrorCode::Success
+            }
+            Err(err) => {
+                error!("Error making wallet snapshot. Error details: {:?}", err);
+                ErrorCode::WalletStorageError
+            }
+        }
+    }
+
+
+    /// Replaces wallet data with the snapshot content, wallet should be closed by all clients
+    pub extern fn restore(id: *const c_char,
+                          config: *const c_char,
+                          credentials: *const c_char,
+                          snapshot_id: i64) -> ErrorCode {
+        check_useful_c_str!(id, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(config, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(credentials, ErrorCode::CommonInvalidState);
+
+        let storage_type = ::postgres_storage::PostgresStorageType::new();
+        match storage_type.restore_storage(&id, Some(&config), Some(&credentials), snapshot_id) {
+            Ok(_) => ErrorCode::Success,
+            Err(err) => {
+                match err {
+                    WalletStorageError::ItemNotFound => ErrorCode::WalletItemNotFound,
+                    _ => {
+                        error!("Error restoring wallet snapshot. Error details: {:?}", err);
+                        ErrorCode::WalletStorageError
+                    }
+                }
+            }
+        }
+    }
 }
 
 fn _storagerecord_to_postgresrecord(in_rec: &StorageRecord) -> Result<PostgresWalletRecord, WalletStorageError> {
@@ -944,6 +1145,27 @@ fn _storagerecord_to_postgresrecord(in_rec: &StorageRecord) -> Result<PostgresWa
     Ok(out_rec)
 }
 
+// Empty type, value or tags mean that they were not requested by search options
+fn _postgresrecords_to_json(records: &[PostgresWalletRecord]) -> Result<CString, WalletStorageError> {
+    let mut res = Vec::with_capacity(records.len());
+
+    for record in records {
+        let type_ = record.type_.to_str().map_err(|err| WalletStorageError::IOError(err.to_string()))?;
+        let tags = record.tags.to_str().map_err(|err| WalletStorageError::IOError(err.to_string()))?;
+
+        res.push(PostgresSearchRecord {
+            id: record.id.to_str().map_err(|err| WalletStorageError::IOError(err.to_string()))?.to_string(),
+            type_: if type_.is_empty() { None } else { Some(type_.to_string()) },
+            value: if record.value.is_empty() { None } else { Some(util_base64::encode(&record.value)) },
+            tags: if tags.is_empty() { None } else { Some(serde_json::from_str(tags).map_err(|err| WalletStorageError::IOError(err.to_string()))?) },
+        });
+    }
+
+    let json = serde_json::to_string(&res).map_err(|err| WalletStorageError::IOError(err.to_string()))?;
+
+    CString::new(json).map_err(|err| WalletStorageError::IOError(err.to_string()))
+}
+
 fn _iterator_to_record_set(mut iter: Box<dyn StorageIterator>) -> Result<Vec<PostgresWalletRecord>, ErrorCode> {
     let mut search_continue: bool = true;
     let mut search_records = Vec::new();
@@ -982,6 +1204,10 @@ fn _tags_to_json(tags: &[Tag]) -> Result<String, WalletStorageError> {
 
 fn _tags_from_json(json: &str) -> Result<Vec<Tag>, WalletStorageError> {
     let string_tags: HashMap<String, String> = serde_json::from_str(json).map_err(|err| WalletStorageError::IOError(err.to_string()))?;
+    _tags_from_map(string_tags)
+}
+
+fn _tags_from_map(string_tags: HashMap<String, String>) -> Result<Vec<Tag>, WalletStorageError> {
     let mut tags = Vec::new();
 
     for (k, v) in string_tags {
@@ -1006,6 +1232,43 @@ fn _tags_from_json(json: &str) -> Result<Vec<Tag>, WalletStorageError> {
     Ok(tags)
 }
 
+// Type and id are passed in the same form as to add_record and other handlers
+fn _operations_from_json(json: &str) -> Result<Vec<StorageOperation>, WalletStorageError> {
+    let operations: Vec<PostgresStorageOperation> = serde_json::from_str(json).map_err(|err| WalletStorageError::IOError(err.to_string()))?;
+
+    let mut res = Vec::with_capacity(operations.len());
+
+    for operation in operations {
+        let type_ = operation.type_.into_bytes();
+        let id = operation.id.into_bytes();
+
+        let value = match operation.value {
+            Some(value) => {
+                let value = util_base64::decode(&value).map_err(|err| WalletStorageError::IOError(err.to_string()))?;
+                Some(EncryptedValue::from_bytes(&value)?)
+            }
+            None => None
+        };
+
+        let tags = match operation.tags {
+            Some(tags) => Some(_tags_from_map(tags)?),
+            None => None
+        };
+
+        let operation = match (operation.op.as_str(), value, tags) {
+            ("add", Some(value), Some(tags)) => StorageOperation::Add(type_, id, value, tags),
+            ("update", Some(value), None) => StorageOperation::Update(type_, id, value),
+            ("update_tags", None, Some(tags)) => StorageOperation::UpdateTags(type_, id, tags),
+            ("delete", None, None) => StorageOperation::Delete(type_, id),
+            (op, _, _) => return Err(WalletStorageError::IOError(format!("Invalid batch operation: {}", op)))
+        };
+
+        res.push(operation);
+    }
+
+    Ok(res)
+}
+
 fn _tag_names_to_json(tag_names: &[TagName]) -> Result<String, WalletStorageError> {
     let mut tags: Vec<String> = Vec::new();
 
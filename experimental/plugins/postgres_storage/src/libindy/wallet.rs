@@ -324,6 +324,43 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: IndyHandle,
 pub type WalletFreeSearch = extern fn(storage_handle: IndyHandle,
                                       search_handle: IndyHandle) -> ErrorCode;
 
+/// Apply a batch of record operations atomically (in a single storage transaction)
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// operations_json: json array of operations applied in the given order:
+///   [
+///     {"op": "add", "type": <type>, "id": <id>, "value": <base64 value>, "tags": <tags json>},
+///     {"op": "update", "type": <type>, "id": <id>, "value": <base64 value>},
+///     {"op": "update_tags", "type": <type>, "id": <id>, "tags": <tags json>},
+///     {"op": "delete", "type": <type>, "id": <id>},
+///   ]
+pub type WalletBatchRecords = extern fn(storage_handle: IndyHandle,
+                                        operations_json: *const c_char) -> ErrorCode;
+
+/// Get the next chunk of records retrieved by this wallet search as json array:
+///   [{"id": <id>, "type": <type>, "value": <base64 value>, "tags": <tags json>}]
+///   type, value and tags are present only if requested in search options
+///   Note if no more records empty array will be returned
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// search_handle: wallet search handle (See search_records handler)
+/// count: max count of records to return
+pub type WalletFetchSearchNextRecords = extern fn(storage_handle: IndyHandle,
+                                                  search_handle: IndyHandle,
+                                                  count: usize,
+                                                  records_json_p: *mut *const c_char,
+                                                  records_handle_p: *mut IndyHandle) -> ErrorCode;
+
+/// Free records json returned by fetch_search_next_records handler
+///
+/// #Params
+/// storage_handle: opened storage handle (See open handler)
+/// records_handle: records handle (See fetch_search_next_records handler)
+pub type WalletFreeSearchRecords = extern fn(storage_handle: IndyHandle,
+                                             records_handle: IndyHandle) -> ErrorCode;
+
 pub fn register_wallet_storage(
     wallet_storage_name: *const c_char,
     create: WalletCreate,
@@ -394,6 +431,41 @@ pub fn register_wallet_storage(
     receiver.recv().unwrap()
 }
 
+pub fn register_wallet_storage_batch(wallet_storage_name: *const c_char,
+                                     batch_records: WalletBatchRecords) -> ErrorCode {
+    let (sender, receiver) = channel();
+
+    let closure: Box<dyn FnMut(ErrorCode) + Send> = Box::new(move |err| {
+        sender.send(err).unwrap();
+    });
+
+    let (cmd_handle, cb) = callbacks::closure_to_cb_ec(closure);
+
+    unsafe {
+        indy_register_wallet_storage_batch(cmd_handle, wallet_storage_name, Some(batch_records), cb);
+    }
+
+    receiver.recv().unwrap()
+}
+
+pub fn register_wallet_storage_search_chunk(wallet_storage_name: *const c_char,
+                                            fetch_search_next_records: WalletFetchSearchNextRecords,
+                                            free_search_records: WalletFreeSearchRecords) -> ErrorCode {
+    let (sender, receiver) = channel();
+
+    let closure: Box<dyn FnMut(ErrorCode) + Send> = Box::new(move |err| {
+        sender.send(err).unwrap();
+    });
+
+    let (cmd_handle, cb) = callbacks::closure_to_cb_ec(closure);
+
+    unsafe {
+        indy_register_wallet_storage_search_chunk(cmd_handle, wallet_storage_name, Some(fetch_search_next_records), Some(free_search_records), cb);
+    }
+
+    receiver.recv().unwrap()
+}
+
 extern {
     #[no_mangle]
     pub fn indy_register_wallet_storage(command_handle: IndyHandle,
@@ -424,6 +496,21 @@ extern {
                                             free_search: Option<WalletFreeSearch>,
                                             cb: Option<extern fn(command_handle_: IndyHandle,
                                                                     err: ErrorCode)>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_batch(command_handle: IndyHandle,
+                                              type_: *const c_char,
+                                              batch_records: Option<WalletBatchRecords>,
+                                              cb: Option<extern fn(command_handle_: IndyHandle,
+                                                                   err: ErrorCode)>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_search_chunk(command_handle: IndyHandle,
+                                                     type_: *const c_char,
+                                                     fetch_search_next_records: Option<WalletFetchSearchNextRecords>,
+                                                     free_search_records: Option<WalletFreeSearchRecords>,
+                                                     cb: Option<extern fn(command_handle_: IndyHandle,
+                                                                          err: ErrorCode)>) -> ErrorCode;
 }
 
 
@@ -27,7 +27,7 @@ use wql::language;
 use wql::query;
 use wql::transaction;
 
-use wql::storage::{StorageIterator, WalletStorage, StorageRecord, EncryptedValue, Tag, TagName};
+use wql::storage::{StorageIterator, WalletStorage, StorageOperation, StorageRecord, EncryptedValue, Tag, TagName};
 use self::r2d2_postgres::r2d2::Pool;
 use errors::wallet::WalletStorageError::{ConfigError};
 
@@ -128,6 +128,8 @@ const _PLAIN_TAGS_QUERY_MULTI: &str = "SELECT name, value from tags_plaintext wh
 const _ENCRYPTED_TAGS_QUERY_MULTI: &str = "SELECT name, value from tags_encrypted where item_id = $1 and wallet_id = $2";
 const _CREATE_WALLET_DATABASE: &str = "CREATE DATABASE \"$1\"";
 const _CREATE_WALLETS_DATABASE: &str = "CREATE DATABASE wallets";
+const _SCHEMA_EXISTS: &str = "SELECT 1 FROM pg_namespace WHERE nspname = $1";
+const _CREATE_DB_SCHEMA: &str = "CREATE SCHEMA \"$1\"";
 // Note: wallet id length was constrained before by postgres database name length to 64 characters, keeping the same restrictions
 const _CREATE_SCHEMA: [&str; 12] = [
     "CREATE TABLE IF NOT EXISTS metadata (
@@ -218,14 +220,101 @@ const _CREATE_SCHEMA_MULTI: [&str; 14] = [
     "CREATE INDEX IF NOT EXISTS ix_tags_plaintext_value ON tags_plaintext(wallet_id, value)",
     "CREATE INDEX IF NOT EXISTS ix_tags_plaintext_wallet_id_item_id ON tags_plaintext(wallet_id, item_id)"
 ];
+// Snapshot tables are created on first snapshot, so existing wallet databases don't need a migration
+const _CREATE_SNAPSHOT_SCHEMA: [&str; 4] = [
+    "CREATE TABLE IF NOT EXISTS snapshots(
+        id BIGSERIAL PRIMARY KEY,
+        metadata BYTEA NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now()
+    )",
+    "CREATE TABLE IF NOT EXISTS snapshot_items(
+        snapshot_id BIGINT NOT NULL,
+        id BIGINT NOT NULL,
+        type BYTEA NOT NULL,
+        name BYTEA NOT NULL,
+        value BYTEA NOT NULL,
+        key BYTEA NOT NULL,
+        PRIMARY KEY(snapshot_id, id),
+        FOREIGN KEY(snapshot_id)
+            REFERENCES snapshots(id)
+            ON DELETE CASCADE
+    )",
+    "CREATE TABLE IF NOT EXISTS snapshot_tags_encrypted(
+        snapshot_id BIGINT NOT NULL,
+        name BYTEA NOT NULL,
+        value BYTEA NOT NULL,
+        item_id BIGINT NOT NULL,
+        PRIMARY KEY(snapshot_id, name, item_id),
+        FOREIGN KEY(snapshot_id)
+            REFERENCES snapshots(id)
+            ON DELETE CASCADE
+    )",
+    "CREATE TABLE IF NOT EXISTS snapshot_tags_plaintext(
+        snapshot_id BIGINT NOT NULL,
+        name BYTEA NOT NULL,
+        value TEXT NOT NULL,
+        item_id BIGINT NOT NULL,
+        PRIMARY KEY(snapshot_id, name, item_id),
+        FOREIGN KEY(snapshot_id)
+            REFERENCES snapshots(id)
+            ON DELETE CASCADE
+    )"
+];
+const _CREATE_SNAPSHOT_SCHEMA_MULTI: [&str; 5] = [
+    "CREATE TABLE IF NOT EXISTS snapshots(
+        id BIGSERIAL PRIMARY KEY,
+        wallet_id VARCHAR(64) NOT NULL,
+        metadata BYTEA NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT now()
+    )",
+    "CREATE INDEX IF NOT EXISTS ix_snapshots_wallet_id ON snapshots(wallet_id)",
+    "CREATE TABLE IF NOT EXISTS snapshot_items(
+        snapshot_id BIGINT NOT NULL,
+        id BIGINT NOT NULL,
+        type BYTEA NOT NULL,
+        name BYTEA NOT NULL,
+        value BYTEA NOT NULL,
+        key BYTEA NOT NULL,
+        PRIMARY KEY(snapshot_id, id),
+        FOREIGN KEY(snapshot_id)
+            REFERENCES snapshots(id)
+            ON DELETE CASCADE
+    )",
+    "CREATE TABLE IF NOT EXISTS snapshot_tags_encrypted(
+        snapshot_id BIGINT NOT NULL,
+        name BYTEA NOT NULL,
+        value BYTEA NOT NULL,
+        item_id BIGINT NOT NULL,
+        PRIMARY KEY(snapshot_id, name, item_id),
+        FOREIGN KEY(snapshot_id)
+            REFERENCES snapshots(id)
+            ON DELETE CASCADE
+    )",
+    "CREATE TABLE IF NOT EXISTS snapshot_tags_plaintext(
+        snapshot_id BIGINT NOT NULL,
+        name BYTEA NOT NULL,
+        value TEXT NOT NULL,
+        item_id BIGINT NOT NULL,
+        PRIMARY KEY(snapshot_id, name, item_id),
+        FOREIGN KEY(snapshot_id)
+            REFERENCES snapshots(id)
+            ON DELETE CASCADE
+    )"
+];
 const _DROP_WALLET_DATABASE: &str = "DROP DATABASE \"$1\"";
-const _DROP_SCHEMA: [&str; 4] = [
+const _DROP_SCHEMA: [&str; 8] = [
+    "DROP TABLE IF EXISTS snapshot_tags_plaintext",
+    "DROP TABLE IF EXISTS snapshot_tags_encrypted",
+    "DROP TABLE IF EXISTS snapshot_items",
+    "DROP TABLE IF EXISTS snapshots",
     "DROP TABLE tags_plaintext",
     "DROP TABLE tags_encrypted",
     "DROP TABLE items",
     "DROP TABLE metadata"
 ];
-const _DELETE_WALLET_MULTI: [&str; 4] = [
+// Snapshot rows go first: only the result of the last statement is reported
+const _DELETE_WALLET_MULTI: [&str; 5] = [
+    "DELETE FROM snapshots WHERE wallet_id = $1",
     "DELETE FROM tags_plaintext WHERE wallet_id = $1",
     "DELETE FROM tags_encrypted WHERE wallet_id = $1",
     "DELETE FROM items WHERE wallet_id = $1",
@@ -390,6 +479,7 @@ pub struct PostgresConfig {
     // default 5
     wallet_scheme: Option<WalletScheme>,   // default DatabasePerWallet
     database_name: Option<String>,   // default _WALLET_DB
+    schema_name: Option<String>,   // default public
     
     // For TLS
     #[serde(skip)]
@@ -473,6 +563,22 @@ impl PostgresConfig {
             None => 5
         }
     }
+    /// Postgres schema holding the wallet tables, None means the default search_path (public).
+    fn schema_name(&self) -> Result<Option<&str>, WalletStorageError> {
+        match self.schema_name {
+            Some(ref schema_name) => {
+                let valid = !schema_name.is_empty() && schema_name.len() <= 63
+                    && !schema_name.starts_with(|c: char| c.is_ascii_digit())
+                    && schema_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if !valid {
+                    error!("Invalid schema_name {:?}: only letters, digits and underscores are allowed", schema_name);
+                    return Err(ConfigError);
+                }
+                Ok(Some(schema_name))
+            }
+            None => Ok(None)
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -494,6 +600,8 @@ pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), WalletStorageError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<PostgresStorage>, WalletStorageError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), WalletStorageError>;
+    fn snapshot_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<i64, WalletStorageError>;
+    fn restore_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, snapshot_id: i64) -> Result<(), WalletStorageError>;
 }
 
 #[derive(Deserialize, Debug)]
@@ -552,7 +660,7 @@ impl WalletStrategy for MultiWalletSingleTableStrategySharedPool {
             if error.code() != Some(&postgres::error::DUPLICATE_DATABASE) {
                 conn.finish()?;
                 return Err(WalletStorageError::IOError(format!("Error occurred while creating the database: {}", error)));
-            } else {
+            } else if config.schema_name.is_none() {
                 // if database already exists, assume tables are created already and return
                 conn.finish()?;
                 return Ok(());
@@ -567,6 +675,11 @@ impl WalletStrategy for MultiWalletSingleTableStrategySharedPool {
             }
         };
 
+        if let Err(error) = create_db_schema(&conn, config) {
+            conn.finish()?;
+            return Err(error);
+        }
+
         for sql in &_CREATE_SCHEMA_MULTI {
             if let Err(error) = conn.execute(sql, &[]) {
                 conn.finish()?;
@@ -716,6 +829,11 @@ impl WalletStrategy for DatabasePerWalletStrategy {
             }
         };
 
+        if let Err(error) = create_db_schema(&conn, config) {
+            conn.finish()?;
+            return Err(error);
+        }
+
         // select metadata for this wallet to ensure it DOESN'T exist
         let mut schema_result = {
             let rows = conn.query(
@@ -856,6 +974,28 @@ fn get_multi_database_name(config: &PostgresConfig) -> &str {
     return name;
 }
 
+// create configured schema unless it exists already (it may be pre-created by the DBA),
+// CREATE SCHEMA IF NOT EXISTS would still require CREATE privilege on the database
+fn create_db_schema(conn: &postgres::Connection, config: &PostgresConfig) -> Result<(), WalletStorageError> {
+    let schema_name = match config.schema_name()? {
+        Some(schema_name) => schema_name,
+        None => return Ok(())
+    };
+
+    let exists = match conn.query(_SCHEMA_EXISTS, &[&schema_name]) {
+        Ok(rows) => !rows.is_empty(),
+        Err(error) => return Err(WalletStorageError::IOError(format!("Error occurred while looking up database schema: {}", error)))
+    };
+
+    if !exists {
+        let create_schema_sql = str::replace(_CREATE_DB_SCHEMA, "$1", schema_name);
+        if let Err(error) = conn.execute(&create_schema_sql, &[]) {
+            return Err(WalletStorageError::IOError(format!("Error occurred while creating database schema: {}", error)));
+        }
+    }
+    Ok(())
+}
+
 impl WalletStrategy for MultiWalletSingleTableStrategy {
     // initialize storage based on wallet storage strategy
     fn init_storage(&self, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<(), WalletStorageError> {
@@ -885,12 +1025,13 @@ impl WalletStrategy for MultiWalletSingleTableStrategy {
                 debug!("error creating database, Error: {}", error);
                 conn.finish()?;
                 return Err(WalletStorageError::IOError(format!("Error occurred while creating the database: {}", error)));
-            } else {
+            } else if config.schema_name.is_none() {
                 // if database already exists, assume tables are created already and return
                 debug!("database already exists");
                 conn.finish()?;
                 return Ok(());
             }
+            // tables may be missing in configured schema of existing database, they are created with IF NOT EXISTS
         }
         conn.finish()?;
 
@@ -904,6 +1045,12 @@ impl WalletStrategy for MultiWalletSingleTableStrategy {
         };
 
         debug!("setting up multi schema");
+        if let Err(error) = create_db_schema(&conn, config) {
+            debug!("error creating database schema, Error: {}", error);
+            conn.finish()?;
+            return Err(error);
+        }
+
         for sql in &_CREATE_SCHEMA_MULTI {
             if let Err(error) = conn.execute(sql, &[]) {
                 debug!("error creating wallet schema, Error: {}", error);
@@ -1115,6 +1262,11 @@ impl PostgresStorageType {
         let mut url_base = PostgresStorageType::_base_postgres_url(config, credentials);
         url_base.push_str("/");
         url_base.push_str(id);
+        // all statements use unqualified table names, so the schema is selected for the whole session
+        if let Some(ref schema_name) = config.schema_name {
+            url_base.push_str("?search_path=");
+            url_base.push_str(&utf8_percent_encode(&schema_name[..], &NON_ALPHANUMERIC).to_string());
+        }
         url_base
     }
 }
@@ -1259,89 +1411,9 @@ impl WalletStorage for PostgresStorage {
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> Result<(), WalletStorageError> {
         let pool = self.pool.clone();
         let conn = pool.get().unwrap();
-        let query_qualifier = get_wallet_strategy_qualifier();
         let tx: transaction::Transaction = transaction::Transaction::new(&conn)?;
-        let res = match query_qualifier {
-            Some(_) => tx.prepare_cached("INSERT INTO items (type, name, value, key, wallet_id) VALUES ($1, $2, $3, $4, $5) RETURNING id")?
-                .query(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key, &self.wallet_id]),
-            None => tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES ($1, $2, $3, $4) RETURNING id")?
-                .query(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key])
-        };
 
-        let item_id = match res {
-            Ok(rows) => {
-                let res = match rows.iter().next() {
-                    Some(row) => Ok(row.get(0)),
-                    None => Err(WalletStorageError::ItemNotFound)
-                };
-                let item_id: i64 = match res {
-                    Err(WalletStorageError::ItemNotFound) => return Err(WalletStorageError::ItemNotFound),
-                    Err(err) => return Err(WalletStorageError::from(err)),
-                    Ok(id) => id
-                };
-                item_id
-            }
-            Err(err) => {
-                if err.code() == Some(&postgres::error::UNIQUE_VIOLATION) ||
-                    err.code() == Some(&postgres::error::INTEGRITY_CONSTRAINT_VIOLATION) {
-                    return Err(WalletStorageError::ItemAlreadyExists);
-                } else {
-                    return Err(WalletStorageError::from(err));
-                }
-            }
-        };
-
-        let item_id = item_id as i64;
-
-        if !tags.is_empty() {
-            let stmt_e = match query_qualifier {
-                Some(_) => tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
-                None => tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES ($1, $2, $3)")?
-            };
-            let stmt_p = match query_qualifier {
-                Some(_) => tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
-                None => tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES ($1, $2, $3)")?
-            };
-
-            for tag in tags {
-                match tag {
-                    &Tag::Encrypted(ref tag_name, ref tag_data) => {
-                        let res = match query_qualifier {
-                            Some(_) => stmt_e.execute(&[&item_id, tag_name, tag_data, &self.wallet_id]),
-                            None => stmt_e.execute(&[&item_id, tag_name, tag_data])
-                        };
-                        match res {
-                            Ok(_) => (),
-                            Err(err) => {
-                                if err.code() == Some(&postgres::error::UNIQUE_VIOLATION) ||
-                                    err.code() == Some(&postgres::error::INTEGRITY_CONSTRAINT_VIOLATION) {
-                                    return Err(WalletStorageError::ItemAlreadyExists);
-                                } else {
-                                    return Err(WalletStorageError::from(err));
-                                }
-                            }
-                        }
-                    }
-                    &Tag::PlainText(ref tag_name, ref tag_data) => {
-                        let res = match query_qualifier {
-                            Some(_) => stmt_p.execute(&[&item_id, tag_name, tag_data, &self.wallet_id]),
-                            None => stmt_p.execute(&[&item_id, tag_name, tag_data])
-                        };
-                        match res {
-                            Ok(_) => (),
-                            Err(err) => {
-                                if err.code() == Some(&postgres::error::UNIQUE_VIOLATION) ||
-                                    err.code() == Some(&postgres::error::INTEGRITY_CONSTRAINT_VIOLATION) {
-                                    return Err(WalletStorageError::ItemAlreadyExists);
-                                } else {
-                                    return Err(WalletStorageError::from(err));
-                                }
-                            }
-                        }
-                    }
-                };
-            }
-        }
+        self._add_item(&tx, type_, id, value, tags)?;
 
         tx.commit()?;
 
@@ -1351,20 +1423,8 @@ impl WalletStorage for PostgresStorage {
     fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> Result<(), WalletStorageError> {
         let pool = self.pool.clone();
         let conn = pool.get().unwrap();
-        let query_qualifier = get_wallet_strategy_qualifier();
-        let res = match query_qualifier {
-            Some(_) => conn.prepare_cached("UPDATE items SET value = $1, key = $2 WHERE type = $3 AND name = $4 AND wallet_id = $5")?
-                .execute(&[&value.data, &value.key, &type_.to_vec(), &id.to_vec(), &self.wallet_id]),
-            None => conn.prepare_cached("UPDATE items SET value = $1, key = $2 WHERE type = $3 AND name = $4")?
-                .execute(&[&value.data, &value.key, &type_.to_vec(), &id.to_vec()])
-        };
 
-        match res {
-            Ok(1) => Ok(()),
-            Ok(0) => Err(WalletStorageError::ItemNotFound),
-            Ok(count) => Err(WalletStorageError::CommonError(CommonError::InvalidState(format!("Postgres returned update row count: {}", count)))),
-            Err(err) => Err(WalletStorageError::from(err)),
-        }
+        self._update_item(&conn, type_, id, value)
     }
 
     fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), WalletStorageError> {
@@ -1459,72 +1519,10 @@ impl WalletStorage for PostgresStorage {
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), WalletStorageError> {
         let pool = self.pool.clone();
         let conn = pool.get().unwrap();
-        let query_qualifier = get_wallet_strategy_qualifier();
         let tx: transaction::Transaction = transaction::Transaction::new(&conn)?;
 
-        let res = match query_qualifier {
-            Some(_) => {
-                let mut rows = tx.prepare_cached("SELECT id FROM items WHERE type = $1 AND name = $2 AND wallet_id = $3")?
-                    .query(&[&type_.to_vec(), &id.to_vec(), &self.wallet_id]);
-                match rows.as_mut().unwrap().iter().next() {
-                    Some(row) => Ok(row.get(0)),
-                    None => Err(WalletStorageError::ItemNotFound)
-                }
-            }
-            None => {
-                let mut rows = tx.prepare_cached("SELECT id FROM items WHERE type = $1 AND name = $2")?
-                    .query(&[&type_.to_vec(), &id.to_vec()]);
-                match rows.as_mut().unwrap().iter().next() {
-                    Some(row) => Ok(row.get(0)),
-                    None => Err(WalletStorageError::ItemNotFound)
-                }
-            }
-        };
-
-        let item_id: i64 = match res {
-            Err(WalletStorageError::ItemNotFound) => return Err(WalletStorageError::ItemNotFound),
-            Err(err) => return Err(WalletStorageError::from(err)),
-            Ok(id) => id
-        };
-
-        match query_qualifier {
-            Some(_) => {
-                tx.execute("DELETE FROM tags_encrypted WHERE item_id = $1 AND wallet_id = $2", &[&item_id, &self.wallet_id])?;
-                tx.execute("DELETE FROM tags_plaintext WHERE item_id = $1 AND wallet_id = $2", &[&item_id, &self.wallet_id])?;
-            }
-            None => {
-                tx.execute("DELETE FROM tags_encrypted WHERE item_id = $1", &[&item_id])?;
-                tx.execute("DELETE FROM tags_plaintext WHERE item_id = $1", &[&item_id])?;
-            }
-        };
-
-        if !tags.is_empty() {
-            let enc_tag_insert_stmt = match query_qualifier {
-                Some(_) => tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
-                None => tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES ($1, $2, $3)")?
-            };
-            let plain_tag_insert_stmt = match query_qualifier {
-                Some(_) => tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
-                None => tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES ($1, $2, $3)")?
-            };
+        self._update_item_tags(&tx, type_, id, tags)?;
 
-            for tag in tags {
-                match query_qualifier {
-                    Some(_) => {
-                        match tag {
-                            &Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data, &self.wallet_id])?,
-                            &Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data, &self.wallet_id])?
-                        }
-                    }
-                    None => {
-                        match tag {
-                            &Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?,
-                            &Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?
-                        }
-                    }
-                };
-            }
-        }
         tx.commit()?;
 
         Ok(())
@@ -1619,22 +1617,8 @@ impl WalletStorage for PostgresStorage {
     fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), WalletStorageError> {
         let pool = self.pool.clone();
         let conn = pool.get().unwrap();
-        let query_qualifier = get_wallet_strategy_qualifier();
-        let row_count = match query_qualifier {
-            Some(_) => conn.execute(
-                "DELETE FROM items where type = $1 AND name = $2 AND wallet_id = $3",
-                &[&type_.to_vec(), &id.to_vec(), &self.wallet_id],
-            )?,
-            None => conn.execute(
-                "DELETE FROM items where type = $1 AND name = $2",
-                &[&type_.to_vec(), &id.to_vec()],
-            )?
-        };
-        if row_count == 1 {
-            Ok(())
-        } else {
-            Err(WalletStorageError::ItemNotFound)
-        }
+
+        self._delete_item(&conn, type_, id)
     }
 
     fn get_storage_metadata(&self) -> Result<Vec<u8>, WalletStorageError> {
@@ -1809,6 +1793,34 @@ impl WalletStorage for PostgresStorage {
         }
     }
 
+    ///
+    /// applies all operations inside of one transaction.
+    /// Either all operations are applied or storage is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Error of the first failed operation (`WalletStorageError::ItemNotFound`,
+    /// `WalletStorageError::ItemAlreadyExists`, ...)
+    ///
+    fn batch(&self, operations: &[StorageOperation]) -> Result<(), WalletStorageError> {
+        let pool = self.pool.clone();
+        let conn = pool.get().unwrap();
+        let tx: transaction::Transaction = transaction::Transaction::new(&conn)?;
+
+        for operation in operations {
+            match operation {
+                StorageOperation::Add(type_, id, value, tags) => self._add_item(&tx, type_, id, value, tags)?,
+                StorageOperation::Update(type_, id, value) => self._update_item(&tx, type_, id, value)?,
+                StorageOperation::UpdateTags(type_, id, tags) => self._update_item_tags(&tx, type_, id, tags)?,
+                StorageOperation::Delete(type_, id) => self._delete_item(&tx, type_, id)?,
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
     fn close(&mut self) -> Result<(), WalletStorageError> {
         // TODO throws a borrow error if we try to close the connection here; temporary workaround is to rely on idle connection timeout
         Ok(())
@@ -1816,19 +1828,326 @@ impl WalletStorage for PostgresStorage {
 }
 
 impl PostgresStorage {
-    fn _prepare_statement(&self, sql: &str) -> Result<
-        OwningHandle<Rc<r2d2::PooledConnection<PostgresConnectionManager>>, Box<postgres::stmt::Statement<'static>>>,
-        WalletStorageError> {
+    ///
+    /// Copies all items, tags and metadata of the wallet into snapshot tables inside of one transaction.
+    /// Returns id of the created snapshot that can be passed to `restore`.
+    ///
+    /// # Errors
+    ///
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    pub fn snapshot(&self) -> Result<i64, WalletStorageError> {
         let pool = self.pool.clone();
-        OwningHandle::try_new(Rc::new(pool.get().unwrap()).clone(), |conn| {
-            unsafe { (*conn).prepare(sql) }.map(Box::new).map_err(WalletStorageError::from)
-        })
-    }
-}
+        let conn = pool.get().unwrap();
+        let query_qualifier = get_wallet_strategy_qualifier();
 
-fn create_connection_pool(config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Pool<PostgresConnectionManager>, WalletStorageError> {
-    let _url_base = PostgresStorageType::_admin_postgres_url(&config, &credentials);
-    let url = PostgresStorageType::_postgres_url(_WALLETS_DB, &config, &credentials);
+        let create_schema: &[&str] = match query_qualifier {
+            Some(_) => &_CREATE_SNAPSHOT_SCHEMA_MULTI,
+            None => &_CREATE_SNAPSHOT_SCHEMA
+        };
+        for sql in create_schema {
+            conn.execute(sql, &[])?;
+        }
+
+        let tx: transaction::Transaction = transaction::Transaction::new(&conn)?;
+
+        let rows = match query_qualifier {
+            Some(_) => tx.query("INSERT INTO snapshots (wallet_id, metadata) SELECT wallet_id, value FROM metadata WHERE wallet_id = $1 RETURNING id",
+                                &[&self.wallet_id])?,
+            None => tx.query("INSERT INTO snapshots (metadata) SELECT value FROM metadata RETURNING id", &[])?
+        };
+        let snapshot_id: i64 = match rows.iter().next() {
+            Some(row) => row.get(0),
+            None => return Err(WalletStorageError::ItemNotFound)
+        };
+
+        match query_qualifier {
+            Some(_) => {
+                tx.execute("INSERT INTO snapshot_items (snapshot_id, id, type, name, value, key) SELECT $1, id, type, name, value, key FROM items WHERE wallet_id = $2",
+                           &[&snapshot_id, &self.wallet_id])?;
+                tx.execute("INSERT INTO snapshot_tags_encrypted (snapshot_id, name, value, item_id) SELECT $1, name, value, item_id FROM tags_encrypted WHERE wallet_id = $2",
+                           &[&snapshot_id, &self.wallet_id])?;
+                tx.execute("INSERT INTO snapshot_tags_plaintext (snapshot_id, name, value, item_id) SELECT $1, name, value, item_id FROM tags_plaintext WHERE wallet_id = $2",
+                           &[&snapshot_id, &self.wallet_id])?;
+            }
+            None => {
+                tx.execute("INSERT INTO snapshot_items (snapshot_id, id, type, name, value, key) SELECT $1, id, type, name, value, key FROM items",
+                           &[&snapshot_id])?;
+                tx.execute("INSERT INTO snapshot_tags_encrypted (snapshot_id, name, value, item_id) SELECT $1, name, value, item_id FROM tags_encrypted",
+                           &[&snapshot_id])?;
+                tx.execute("INSERT INTO snapshot_tags_plaintext (snapshot_id, name, value, item_id) SELECT $1, name, value, item_id FROM tags_plaintext",
+                           &[&snapshot_id])?;
+            }
+        };
+
+        tx.commit()?;
+        Ok(snapshot_id)
+    }
+
+    ///
+    /// Replaces all items, tags and metadata of the wallet with the content of the snapshot
+    /// inside of one transaction. The snapshot is kept and can be restored again.
+    ///
+    /// # Errors
+    ///
+    ///  * `WalletStorageError::ItemNotFound` - Snapshot doesn't exist or belongs to another wallet
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    pub fn restore(&self, snapshot_id: i64) -> Result<(), WalletStorageError> {
+        let pool = self.pool.clone();
+        let conn = pool.get().unwrap();
+        let query_qualifier = get_wallet_strategy_qualifier();
+
+        let tx: transaction::Transaction = transaction::Transaction::new(&conn)?;
+
+        let rows = match query_qualifier {
+            Some(_) => tx.query("SELECT metadata FROM snapshots WHERE id = $1 AND wallet_id = $2", &[&snapshot_id, &self.wallet_id]),
+            None => tx.query("SELECT metadata FROM snapshots WHERE id = $1", &[&snapshot_id])
+        };
+        // snapshot table doesn't exist until the first snapshot is made
+        let metadata: Vec<u8> = match rows {
+            Ok(ref rows) => match rows.iter().next() {
+                Some(row) => row.get(0),
+                None => return Err(WalletStorageError::ItemNotFound)
+            },
+            Err(ref err) if err.code() == Some(&postgres::error::UNDEFINED_TABLE) => return Err(WalletStorageError::ItemNotFound),
+            Err(err) => return Err(WalletStorageError::from(err))
+        };
+
+        match query_qualifier {
+            Some(_) => {
+                // tags are removed by ON DELETE CASCADE
+                tx.execute("DELETE FROM items WHERE wallet_id = $1", &[&self.wallet_id])?;
+                tx.execute("INSERT INTO items (wallet_id, id, type, name, value, key) SELECT $2, id, type, name, value, key FROM snapshot_items WHERE snapshot_id = $1",
+                           &[&snapshot_id, &self.wallet_id])?;
+                tx.execute("INSERT INTO tags_encrypted (wallet_id, name, value, item_id) SELECT $2, name, value, item_id FROM snapshot_tags_encrypted WHERE snapshot_id = $1",
+                           &[&snapshot_id, &self.wallet_id])?;
+                tx.execute("INSERT INTO tags_plaintext (wallet_id, name, value, item_id) SELECT $2, name, value, item_id FROM snapshot_tags_plaintext WHERE snapshot_id = $1",
+                           &[&snapshot_id, &self.wallet_id])?;
+                tx.execute("UPDATE metadata SET value = $1 WHERE wallet_id = $2", &[&metadata, &self.wallet_id])?;
+            }
+            None => {
+                tx.execute("DELETE FROM items", &[])?;
+                tx.execute("INSERT INTO items (id, type, name, value, key) SELECT id, type, name, value, key FROM snapshot_items WHERE snapshot_id = $1",
+                           &[&snapshot_id])?;
+                tx.execute("INSERT INTO tags_encrypted (name, value, item_id) SELECT name, value, item_id FROM snapshot_tags_encrypted WHERE snapshot_id = $1",
+                           &[&snapshot_id])?;
+                tx.execute("INSERT INTO tags_plaintext (name, value, item_id) SELECT name, value, item_id FROM snapshot_tags_plaintext WHERE snapshot_id = $1",
+                           &[&snapshot_id])?;
+                tx.execute("UPDATE metadata SET value = $1", &[&metadata])?;
+            }
+        };
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn _add_item(&self, conn: &postgres::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> Result<(), WalletStorageError> {
+        let query_qualifier = get_wallet_strategy_qualifier();
+        let res = match query_qualifier {
+            Some(_) => conn.prepare_cached("INSERT INTO items (type, name, value, key, wallet_id) VALUES ($1, $2, $3, $4, $5) RETURNING id")?
+                .query(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key, &self.wallet_id]),
+            None => conn.prepare_cached("INSERT INTO items (type, name, value, key) VALUES ($1, $2, $3, $4) RETURNING id")?
+                .query(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key])
+        };
+
+        let item_id = match res {
+            Ok(rows) => {
+                let res = match rows.iter().next() {
+                    Some(row) => Ok(row.get(0)),
+                    None => Err(WalletStorageError::ItemNotFound)
+                };
+                let item_id: i64 = match res {
+                    Err(WalletStorageError::ItemNotFound) => return Err(WalletStorageError::ItemNotFound),
+                    Err(err) => return Err(WalletStorageError::from(err)),
+                    Ok(id) => id
+                };
+                item_id
+            }
+            Err(err) => {
+                if err.code() == Some(&postgres::error::UNIQUE_VIOLATION) ||
+                    err.code() == Some(&postgres::error::INTEGRITY_CONSTRAINT_VIOLATION) {
+                    return Err(WalletStorageError::ItemAlreadyExists);
+                } else {
+                    return Err(WalletStorageError::from(err));
+                }
+            }
+        };
+
+        let item_id = item_id as i64;
+
+        if !tags.is_empty() {
+            let stmt_e = match query_qualifier {
+                Some(_) => conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
+                None => conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES ($1, $2, $3)")?
+            };
+            let stmt_p = match query_qualifier {
+                Some(_) => conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
+                None => conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES ($1, $2, $3)")?
+            };
+
+            for tag in tags {
+                match tag {
+                    &Tag::Encrypted(ref tag_name, ref tag_data) => {
+                        let res = match query_qualifier {
+                            Some(_) => stmt_e.execute(&[&item_id, tag_name, tag_data, &self.wallet_id]),
+                            None => stmt_e.execute(&[&item_id, tag_name, tag_data])
+                        };
+                        match res {
+                            Ok(_) => (),
+                            Err(err) => {
+                                if err.code() == Some(&postgres::error::UNIQUE_VIOLATION) ||
+                                    err.code() == Some(&postgres::error::INTEGRITY_CONSTRAINT_VIOLATION) {
+                                    return Err(WalletStorageError::ItemAlreadyExists);
+                                } else {
+                                    return Err(WalletStorageError::from(err));
+                                }
+                            }
+                        }
+                    }
+                    &Tag::PlainText(ref tag_name, ref tag_data) => {
+                        let res = match query_qualifier {
+                            Some(_) => stmt_p.execute(&[&item_id, tag_name, tag_data, &self.wallet_id]),
+                            None => stmt_p.execute(&[&item_id, tag_name, tag_data])
+                        };
+                        match res {
+                            Ok(_) => (),
+                            Err(err) => {
+                                if err.code() == Some(&postgres::error::UNIQUE_VIOLATION) ||
+                                    err.code() == Some(&postgres::error::INTEGRITY_CONSTRAINT_VIOLATION) {
+                                    return Err(WalletStorageError::ItemAlreadyExists);
+                                } else {
+                                    return Err(WalletStorageError::from(err));
+                                }
+                            }
+                        }
+                    }
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _update_item(&self, conn: &postgres::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue) -> Result<(), WalletStorageError> {
+        let query_qualifier = get_wallet_strategy_qualifier();
+        let res = match query_qualifier {
+            Some(_) => conn.prepare_cached("UPDATE items SET value = $1, key = $2 WHERE type = $3 AND name = $4 AND wallet_id = $5")?
+                .execute(&[&value.data, &value.key, &type_.to_vec(), &id.to_vec(), &self.wallet_id]),
+            None => conn.prepare_cached("UPDATE items SET value = $1, key = $2 WHERE type = $3 AND name = $4")?
+                .execute(&[&value.data, &value.key, &type_.to_vec(), &id.to_vec()])
+        };
+
+        match res {
+            Ok(1) => Ok(()),
+            Ok(0) => Err(WalletStorageError::ItemNotFound),
+            Ok(count) => Err(WalletStorageError::CommonError(CommonError::InvalidState(format!("Postgres returned update row count: {}", count)))),
+            Err(err) => Err(WalletStorageError::from(err)),
+        }
+    }
+
+    fn _update_item_tags(&self, conn: &postgres::Connection, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), WalletStorageError> {
+        let query_qualifier = get_wallet_strategy_qualifier();
+
+        let res = match query_qualifier {
+            Some(_) => {
+                let mut rows = conn.prepare_cached("SELECT id FROM items WHERE type = $1 AND name = $2 AND wallet_id = $3")?
+                    .query(&[&type_.to_vec(), &id.to_vec(), &self.wallet_id]);
+                match rows.as_mut().unwrap().iter().next() {
+                    Some(row) => Ok(row.get(0)),
+                    None => Err(WalletStorageError::ItemNotFound)
+                }
+            }
+            None => {
+                let mut rows = conn.prepare_cached("SELECT id FROM items WHERE type = $1 AND name = $2")?
+                    .query(&[&type_.to_vec(), &id.to_vec()]);
+                match rows.as_mut().unwrap().iter().next() {
+                    Some(row) => Ok(row.get(0)),
+                    None => Err(WalletStorageError::ItemNotFound)
+                }
+            }
+        };
+
+        let item_id: i64 = match res {
+            Err(WalletStorageError::ItemNotFound) => return Err(WalletStorageError::ItemNotFound),
+            Err(err) => return Err(WalletStorageError::from(err)),
+            Ok(id) => id
+        };
+
+        match query_qualifier {
+            Some(_) => {
+                conn.execute("DELETE FROM tags_encrypted WHERE item_id = $1 AND wallet_id = $2", &[&item_id, &self.wallet_id])?;
+                conn.execute("DELETE FROM tags_plaintext WHERE item_id = $1 AND wallet_id = $2", &[&item_id, &self.wallet_id])?;
+            }
+            None => {
+                conn.execute("DELETE FROM tags_encrypted WHERE item_id = $1", &[&item_id])?;
+                conn.execute("DELETE FROM tags_plaintext WHERE item_id = $1", &[&item_id])?;
+            }
+        };
+
+        if !tags.is_empty() {
+            let enc_tag_insert_stmt = match query_qualifier {
+                Some(_) => conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
+                None => conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES ($1, $2, $3)")?
+            };
+            let plain_tag_insert_stmt = match query_qualifier {
+                Some(_) => conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value, wallet_id) VALUES ($1, $2, $3, $4)")?,
+                None => conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES ($1, $2, $3)")?
+            };
+
+            for tag in tags {
+                match query_qualifier {
+                    Some(_) => {
+                        match tag {
+                            &Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data, &self.wallet_id])?,
+                            &Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data, &self.wallet_id])?
+                        }
+                    }
+                    None => {
+                        match tag {
+                            &Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?,
+                            &Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(&[&item_id, tag_name, tag_data])?
+                        }
+                    }
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _delete_item(&self, conn: &postgres::Connection, type_: &[u8], id: &[u8]) -> Result<(), WalletStorageError> {
+        let query_qualifier = get_wallet_strategy_qualifier();
+        let row_count = match query_qualifier {
+            Some(_) => conn.execute(
+                "DELETE FROM items where type = $1 AND name = $2 AND wallet_id = $3",
+                &[&type_.to_vec(), &id.to_vec(), &self.wallet_id],
+            )?,
+            None => conn.execute(
+                "DELETE FROM items where type = $1 AND name = $2",
+                &[&type_.to_vec(), &id.to_vec()],
+            )?
+        };
+        if row_count == 1 {
+            Ok(())
+        } else {
+            Err(WalletStorageError::ItemNotFound)
+        }
+    }
+
+    fn _prepare_statement(&self, sql: &str) -> Result<
+        OwningHandle<Rc<r2d2::PooledConnection<PostgresConnectionManager>>, Box<postgres::stmt::Statement<'static>>>,
+        WalletStorageError> {
+        let pool = self.pool.clone();
+        OwningHandle::try_new(Rc::new(pool.get().unwrap()).clone(), |conn| {
+            unsafe { (*conn).prepare(sql) }.map(Box::new).map_err(WalletStorageError::from)
+        })
+    }
+}
+
+fn create_connection_pool(config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Pool<PostgresConnectionManager>, WalletStorageError> {
+    let _url_base = PostgresStorageType::_admin_postgres_url(&config, &credentials);
+    let url = PostgresStorageType::_postgres_url(_WALLETS_DB, &config, &credentials);
 
     let manager = match PostgresConnectionManager::new(&url[..], config.r2d2_tls()) {
         Ok(manager) => manager,
@@ -2096,6 +2415,46 @@ impl WalletStorageType for PostgresStorageType {
         let r1 = SELECTED_STRATEGY.read().unwrap();
         r1.as_ref().open_wallet(id, &config, &credentials)
     }
+
+    ///
+    /// Makes a point-in-time copy of the wallet with the provided id.
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - id of the wallet
+    ///  * `config` - config containing the location of Postgres DB
+    ///  * `credentials` - DB credentials
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `i64` - id of the created snapshot
+    ///  * `WalletStorageError`
+    ///
+    fn snapshot_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<i64, WalletStorageError> {
+        let storage = self.open_storage(id, config, credentials)?;
+        storage.snapshot()
+    }
+
+    ///
+    /// Rolls the wallet with the provided id back to the state captured by `snapshot_storage`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - id of the wallet
+    ///  * `config` - config containing the location of Postgres DB
+    ///  * `credentials` - DB credentials
+    ///  * `snapshot_id` - id returned by `snapshot_storage`
+    ///
+    /// # Errors
+    ///
+    ///  * `WalletStorageError::ItemNotFound` - Snapshot with the provided id doesn't exist for the wallet
+    ///
+    fn restore_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, snapshot_id: i64) -> Result<(), WalletStorageError> {
+        let storage = self.open_storage(id, config, credentials)?;
+        storage.restore(snapshot_id)
+    }
 }
 
 
@@ -2159,6 +2518,38 @@ mod tests {
         storage_type.delete_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
     }
 
+    #[test]
+    fn postgres_storage_type_create_works_for_schema() {
+        _cleanup();
+
+        let storage_type = PostgresStorageType::new();
+        let config = _wallet_config_with_schema();
+        storage_type.init_storage(Some(&config[..]), Some(&_wallet_credentials()[..])).unwrap();
+        storage_type.create_storage(_wallet_id(), Some(&config[..]), Some(&_wallet_credentials()[..]), &_metadata()).unwrap();
+
+        let storage = storage_type.open_storage(_wallet_id(), Some(&config[..]), Some(&_wallet_credentials()[..])).unwrap();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+
+        storage_type.delete_storage(_wallet_id(), Some(&config[..]), Some(&_wallet_credentials()[..])).unwrap();
+    }
+
+    #[test]
+    fn postgres_config_schema_name_works_for_invalid_name() {
+        let config: PostgresConfig = serde_json::from_value(json!({
+            "url": "localhost:5432",
+            "schema_name": "indy\"; DROP TABLE items; --"
+        })).unwrap();
+        assert_match!(Err(WalletStorageError::ConfigError), config.schema_name());
+
+        let config: PostgresConfig = serde_json::from_value(json!({
+            "url": "localhost:5432",
+            "schema_name": "indy_wallets"
+        })).unwrap();
+        assert_eq!(Some("indy_wallets"), config.schema_name().unwrap());
+    }
+
     #[test]
     fn postgres_storage_type_open_works() {
         _cleanup();
@@ -2266,6 +2657,82 @@ mod tests {
         assert_match!(Err(WalletStorageError::ItemNotFound), res);
     }
 
+    #[test]
+    fn postgres_storage_batch_works() {
+        _cleanup();
+
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        storage.batch(&[
+            StorageOperation::Add(_type1(), _id2(), _value2(), _tags()),
+            StorageOperation::Update(_type1(), _id1(), _value2()),
+            StorageOperation::UpdateTags(_type1(), _id1(), _new_tags()),
+        ]).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value2());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+        storage.batch(&[StorageOperation::Delete(_type1(), _id2())]).unwrap();
+        let res = storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
+
+    #[test]
+    fn postgres_storage_batch_rolls_back_on_failure() {
+        _cleanup();
+
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.batch(&[
+            StorageOperation::Update(_type1(), _id1(), _value2()),
+            StorageOperation::Add(_type1(), _id1(), _value2(), _tags()),
+        ]);
+        assert_match!(Err(WalletStorageError::ItemAlreadyExists), res);
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+    }
+
+    #[test]
+    fn postgres_storage_snapshot_restore_works() {
+        _cleanup();
+
+        let storage = _storage();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let storage_type = PostgresStorageType::new();
+        let snapshot_id = storage_type.snapshot_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
+
+        storage.update(&_type1(), &_id1(), &_value2()).unwrap();
+        storage.add(&_type2(), &_id2(), &_value2(), &_new_tags()).unwrap();
+        storage.set_storage_metadata(&[1, 2, 3]).unwrap();
+
+        storage_type.restore_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..]), snapshot_id).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+        let res = storage.get(&_type2(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+
+        assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+    }
+
+    #[test]
+    fn postgres_storage_restore_works_for_unknown_snapshot() {
+        _cleanup();
+
+        let _storage = _storage();
+
+        let storage_type = PostgresStorageType::new();
+        let res = storage_type.restore_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..]), -1);
+        assert_match!(Err(WalletStorageError::ItemNotFound), res);
+    }
+
     #[test]
     fn postgres_storage_delete_works_for_non_existing() {
         _cleanup();
@@ -2631,6 +3098,12 @@ mod tests {
         config
     }
 
+    fn _wallet_config_with_schema() -> String {
+        let mut config: serde_json::Value = serde_json::from_str(&_wallet_config()).unwrap();
+        config["schema_name"] = json!("indy_wallets");
+        config.to_string()
+    }
+
     fn _wallet_config_db_pool() -> String {
         let config = json!({
             "url": "localhost:5432".to_owned(),
@@ -71,6 +71,15 @@ impl StorageRecord {
     }
 }
 
+// Operation of the storage batch. All operations of the batch are applied atomically.
+#[derive(Debug)]
+pub enum StorageOperation {
+    Add(Vec<u8>, Vec<u8>, EncryptedValue, Vec<Tag>),
+    Update(Vec<u8>, Vec<u8>, EncryptedValue),
+    UpdateTags(Vec<u8>, Vec<u8>, Vec<Tag>),
+    Delete(Vec<u8>, Vec<u8>),
+}
+
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageRecord>, WalletStorageError>;
     fn get_total_count(&self) -> Result<Option<usize>, WalletStorageError>;
@@ -88,5 +97,6 @@ pub trait WalletStorage {
     fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), WalletStorageError>;
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, WalletStorageError>;
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> Result<Box<dyn StorageIterator>, WalletStorageError>;
+    fn batch(&self, operations: &[StorageOperation]) -> Result<(), WalletStorageError>;
     fn close(&mut self) -> Result<(), WalletStorageError>;
 }
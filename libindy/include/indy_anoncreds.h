@@ -31,6 +31,23 @@ extern "C" {
                                                                                          const char*   cred_def_json)
                                                                     );
 
+    extern indy_error_t indy_issuer_create_and_store_credential_def_with_progress(indy_handle_t command_handle,
+                                                                                  indy_handle_t wallet_handle,
+                                                                                  const char *  issuer_did,
+                                                                                  const char *  schema_json,
+                                                                                  const char *  tag,
+                                                                                  const char *  signature_type,
+                                                                                  const char *  config_json,
+
+                                                                                  void           (*progress_cb)(indy_handle_t command_handle_,
+                                                                                                                const char*   stage),
+
+                                                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                                                       indy_error_t  err,
+                                                                                                       const char*   cred_def_id,
+                                                                                                       const char*   cred_def_json)
+                                                                                  );
+
     extern indy_error_t indy_issuer_rotate_credential_def_start(indy_handle_t command_handle,
                                                                 indy_handle_t wallet_handle,
                                                                 const char *  cred_def_id,
@@ -88,6 +105,19 @@ extern "C" {
                                                                            const char*   cred_revoc_id,
                                                                            const char*   revoc_reg_delta_json)
                                                       );
+
+    extern indy_error_t indy_issuer_create_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  cred_offer_json,
+                                                       const char *  cred_reqs_json,
+                                                       const char *  rev_reg_id,
+                                                       indy_handle_t blob_storage_reader_handle,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   creds_json,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
     
     extern indy_error_t indy_issuer_revoke_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
@@ -165,6 +195,16 @@ extern "C" {
                                                                           indy_error_t        err)
                                                       );
 
+    extern indy_error_t indy_prover_delete_credential_with_related(indy_handle_t command_handle,
+                                                                   indy_handle_t wallet_handle,
+                                                                   const char *  cred_id,
+                                                                   const char *  related_records_json,
+
+                                                                   void          (*cb)(indy_handle_t command_handle_,
+                                                                                       indy_error_t  err,
+                                                                                       const char*   deleted_credential_json)
+                                                                   );
+
     extern indy_error_t indy_prover_get_credentials(indy_handle_t command_handle,
                                                     indy_handle_t wallet_handle,
                                                     const char *  filter_json,
@@ -183,6 +223,22 @@ extern "C" {
                                                                         const char*   credential_json)
                                                    );
 
+    extern indy_error_t indy_prover_credential_to_w3c(indy_handle_t command_handle,
+                                                      const char *  cred_json,
+
+                                                      void           (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           const char*   w3c_cred_json)
+                                                      );
+
+    extern indy_error_t indy_prover_credential_from_w3c(indy_handle_t command_handle,
+                                                        const char *  w3c_cred_json,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   cred_json)
+                                                        );
+
     extern indy_error_t indy_prover_search_credentials(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        const char *  query_json,
@@ -260,6 +316,33 @@ extern "C" {
                                                                       const char*   proof_json)
                                                  );
 
+    extern indy_error_t indy_prover_create_proof_with_details(indy_handle_t command_handle,
+                                                              indy_handle_t wallet_handle,
+                                                              const char *  proof_req_json,
+                                                              const char *  requested_credentials_json,
+                                                              const char *  master_secret_name,
+                                                              const char *  schemas_json,
+                                                              const char *  credential_defs_json,
+                                                              const char *  rev_states_json,
+
+                                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                                   indy_error_t  err,
+                                                                                   const char*   result_json)
+                                                              );
+
+    extern indy_error_t indy_prover_create_proofs(indy_handle_t command_handle,
+                                                  indy_handle_t wallet_handle,
+                                                  const char *  proof_requests_json,
+                                                  const char *  master_secret_name,
+                                                  const char *  schemas_json,
+                                                  const char *  credential_defs_json,
+                                                  const char *  rev_states_json,
+
+                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       const char*   proofs_json)
+                                                  );
+
 
     extern indy_error_t indy_verifier_verify_proof(indy_handle_t command_handle,
                                                    const char *  proof_request_json,
@@ -275,6 +358,21 @@ extern "C" {
                                                    );
 
 
+    extern indy_error_t indy_verifier_verify_proof_with_details(indy_handle_t command_handle,
+                                                                const char *  proof_request_json,
+                                                                const char *  proof_json,
+                                                                const char *  schemas_json,
+                                                                const char *  credential_defs_jsons,
+                                                                const char *  rev_reg_defs_json,
+                                                                const char *  rev_regs_json,
+                                                                const char *  options_json,
+
+                                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                                     indy_error_t  err,
+                                                                                     const char*   result_json)
+                                                                );
+
+
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
                                                      const char *  rev_reg_def_json,
@@ -308,6 +406,21 @@ extern "C" {
                                                                  const char*   nonce)
                                             );
 
+    extern indy_error_t indy_verifier_create_proof_request_from_presentation_definition(indy_handle_t command_handle,
+                                                                                        const char *  presentation_definition_json,
+                                                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                                                             indy_error_t  err,
+                                                                                                             const char*   proof_request_json)
+                                                                                        );
+
+    extern indy_error_t indy_verifier_create_presentation_submission(indy_handle_t command_handle,
+                                                                     const char *  presentation_definition_json,
+                                                                     const char *  proof_json,
+                                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                                          indy_error_t  err,
+                                                                                          const char*   presentation_submission_json)
+                                                                     );
+
     extern indy_error_t indy_to_unqualified(indy_handle_t command_handle,
                                             const char *  entity,
                                             void           (*cb)(indy_handle_t command_handle_,
@@ -315,6 +428,19 @@ extern "C" {
                                                                  const char*   res)
                                             );
 
+    extern indy_error_t indy_encode_credential_attribute(indy_handle_t command_handle,
+                                                         const char *  raw_value,
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err,
+                                                                              const char*   encoded)
+                                                         );
+
+    extern indy_error_t indy_validate_credential_values(indy_handle_t command_handle,
+                                                        const char *  cred_values_json,
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err)
+                                                        );
+
 #ifdef __cplusplus
 }
 #endif
@@ -22,7 +22,7 @@ extern "C" {
     ///    noCache: (bool, optional, false by default) Skip usage of cache,
     ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
     ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
-    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
     ///  }
     /// #Returns
     /// Schema json:
@@ -61,7 +61,7 @@ extern "C" {
     ///    noCache: (bool, optional, false by default) Skip usage of cache,
     ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
     ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
-    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
     ///  }
     ///
     /// #Returns
@@ -88,6 +88,36 @@ extern "C" {
                                                               const char*   cred_def_json)
                                          );
 
+    /// Gets revocation registry definition json data for specified revocation registry definition id.
+    /// If data is present inside of cache, cached data is returned.
+    /// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// id: identifier of revocation registry definition.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    extern indy_error_t indy_get_rev_reg_def(indy_handle_t command_handle,
+                                             indy_handle_t pool_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *  submitter_did,
+                                             const char *  id,
+                                             const char *  options_json,
+                                             void          (*cb)(indy_handle_t command_handle_,
+                                                                 indy_error_t  err,
+                                                                 const char*   rev_reg_def_json)
+                                            );
+
     /// Purge schema cache.
     ///
     /// EXPERIMENTAL
@@ -106,6 +136,55 @@ extern "C" {
                                                                     indy_error_t  err)
                                                );
 
+    /// Gets json data of an object of the rich schema family for specified object id.
+    /// If data is present inside of cache, cached data is returned.
+    /// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// id: DID URL identifying the object.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    /// cb: Callback that takes command result as parameter.
+    extern indy_error_t indy_get_rich_schema_object(indy_handle_t command_handle,
+                                                    indy_handle_t pool_handle,
+                                                    indy_handle_t wallet_handle,
+                                                    const char *  submitter_did,
+                                                    const char *  id,
+                                                    const char *  options_json,
+                                                    void          (*cb)(indy_handle_t command_handle_,
+                                                                        indy_error_t  err,
+                                                                        const char*   rich_schema_json)
+                                                   );
+
+    /// Purge cache of rich schema family objects.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_rich_schema_cache(indy_handle_t command_handle,
+                                                     indy_handle_t wallet_handle,
+                                                     const char *  options_json,
+                                                     void          (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err)
+                                                    );
+
     /// Purge credential definition cache.
     ///
     /// EXPERIMENTAL
@@ -123,6 +202,85 @@ extern "C" {
                                                   void          (*cb)(indy_handle_t command_handle_,
                                                                       indy_error_t  err)
                                                  );
+
+    /// Purge revocation registry definition cache.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_rev_reg_def_cache(indy_handle_t command_handle,
+                                                     indy_handle_t wallet_handle,
+                                                     const char *  options_json,
+                                                     void          (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err)
+                                                    );
+
+    /// Collects all ledger artifacts needed to create or verify a proof for the proof request
+    /// and fetches them from the ledger concurrently in one pass.
+    /// Schemas, credential definitions and revocation registry definitions are taken from the cache if present
+    /// and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// proof_request_json: proof request json (see indy_prover_create_proof).
+    /// identifiers_json: list of identifiers of credentials selected for the proof (prover) or of the proof (verifier)
+    ///  [{
+    ///     "schema_id": string,
+    ///     "cred_def_id": string,
+    ///     "rev_reg_id": Optional<string>,
+    ///     "timestamp": Optional<number>
+    ///  }]
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    ///  Options are applied to schemas, credential definitions and revocation registry definitions.
+    ///  States and deltas of revocation registries are always fetched from the ledger.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// artifacts_json:
+    /// {
+    ///     "schemas": { <schema_id>: <schema_json>, ... },
+    ///     "cred_defs": { <cred_def_id>: <cred_def_json>, ... },
+    ///     "rev_reg_defs": { <rev_reg_id>: <rev_reg_def_json>, ... },
+    ///     "rev_regs": { <rev_reg_id>: { <timestamp>: <rev_reg_json>, ... }, ... },
+    ///     "rev_reg_deltas": [{
+    ///         "rev_reg_id": revocation registry id,
+    ///         "from": (optional) start of the requested interval,
+    ///         "to": end of the requested interval,
+    ///         "timestamp": time of the delta as returned by the ledger,
+    ///         "delta": <rev_reg_delta_json>
+    ///     }, ...]
+    /// }
+    /// schemas, cred_defs and rev_regs can be passed to indy_verifier_verify_proof as is,
+    /// schemas and cred_defs to indy_prover_create_proof, rev_reg_defs and rev_reg_deltas are
+    /// the inputs for indy_create_revocation_state.
+    extern indy_error_t indy_prefetch_proof_artifacts(indy_handle_t command_handle,
+                                                      indy_handle_t pool_handle,
+                                                      indy_handle_t wallet_handle,
+                                                      const char *  submitter_did,
+                                                      const char *  proof_request_json,
+                                                      const char *  identifiers_json,
+                                                      const char *  options_json,
+                                                      void          (*cb)(indy_handle_t command_handle_,
+                                                                          indy_error_t  err,
+                                                                          const char*   artifacts_json)
+                                                     );
 #ifdef __cplusplus
 }
 #endif
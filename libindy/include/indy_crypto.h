@@ -17,6 +17,9 @@ extern "C" {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
     ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+    ///     "kms": string, (optional) Name of key management registered with indy_register_key_management.
+    ///                    The private key is created and kept in external KMS, the wallet stores only its identifier.
+    ///                    Such keys can be used for signing only. "wallet" (default) keeps the private key in the wallet.
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -445,6 +448,248 @@ extern "C" {
                                                                  const indy_u8_t*  res_json_raw,
                                                                  indy_u32_t        res_json_len)
                                             );
+
+    /// Signs a payload and returns RFC 7515 JSON Web Signature.
+    /// Algorithm is selected by the key crypto type: `EdDSA` for Ed25519 keys, `ES256K` for secp256k1 ones.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// signer_vks_json: list of verkeys (key ids) of the signers
+    /// payload_raw: a pointer to first byte of payload to be signed
+    /// payload_len: a payload length
+    /// options_json: (optional) {"format": `compact` (default, single signer only) or `general`}
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// jws: JWS in the requested serialization
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_sign_jws(indy_handle_t      command_handle,
+                                             indy_handle_t      wallet_handle,
+                                             const char *       signer_vks_json,
+                                             const indy_u8_t *  payload_raw,
+                                             indy_u32_t         payload_len,
+                                             const char *       options_json,
+
+                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                  indy_error_t  err,
+                                                                  const char *  jws)
+                                            );
+
+    /// Verifies RFC 7515 JSON Web Signature in compact, general or flattened JSON serialization.
+    /// All the signatures must be valid.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// jws: JWS to verify
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// signers_json: list of verkeys of the signers
+    /// payload_raw: a pointer to first byte of the decoded payload
+    /// payload_len: a payload length
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_crypto_verify_jws(indy_handle_t      command_handle,
+                                               const char *       jws,
+
+                                               void           (*cb)(indy_handle_t     command_handle_,
+                                                                    indy_error_t      err,
+                                                                    const char *      signers_json,
+                                                                    const indy_u8_t*  payload_raw,
+                                                                    indy_u32_t        payload_len)
+                                              );
+
+    /// Converts Ed25519 verkey to X25519 public key of the same key pair.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// verkey: full Ed25519 verkey
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// x25519_key: base58 encoded X25519 public key
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_crypto_ed25519_to_x25519(indy_handle_t      command_handle,
+                                                      const char *       verkey,
+
+                                                      void           (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           const char *  x25519_key)
+                                                     );
+
+    /// Computes X25519 Diffie-Hellman shared secret of the X25519 form of a wallet key and a public X25519 key.
+    /// The secret key never leaves the wallet.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// my_vk: id (verkey) of Ed25519 key in the wallet
+    /// their_x25519_key: base58 encoded X25519 public key of the other party
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// shared_secret_raw: a pointer to first byte of the raw shared secret
+    /// shared_secret_len: a shared secret length
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_x25519_diffie_hellman(indy_handle_t      command_handle,
+                                                          indy_handle_t      wallet_handle,
+                                                          const char *       my_vk,
+                                                          const char *       their_x25519_key,
+
+                                                          void           (*cb)(indy_handle_t     command_handle_,
+                                                                               indy_error_t      err,
+                                                                               const indy_u8_t*  shared_secret_raw,
+                                                                               indy_u32_t        shared_secret_len)
+                                                         );
+
+    /// Generates BLS12-381 key pair usable for signing and verification of ledger state proofs multi-signatures.
+    /// Keys are not stored in the wallet.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// seed: (optional) Seed that allows deterministic key creation (if not set random one will be created).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// keys_json: {"sign_key": string, "ver_key": string, "pop": string} - base58 encoded keys and proof of possession
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_bls_create_keys(indy_handle_t      command_handle,
+                                             const char *       seed,
+
+                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                  indy_error_t  err,
+                                                                  const char *  keys_json)
+                                            );
+
+    /// Signs a message with BLS sign key.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// sign_key: base58 encoded BLS sign key
+    /// message_raw: a pointer to first byte of message to be signed
+    /// message_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// signature: base58 encoded BLS signature
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_bls_sign(indy_handle_t      command_handle,
+                                      const char *       sign_key,
+                                      const indy_u8_t *  message_raw,
+                                      indy_u32_t         message_len,
+
+                                      void           (*cb)(indy_handle_t command_handle_,
+                                                           indy_error_t  err,
+                                                           const char *  signature)
+                                     );
+
+    /// Aggregates BLS signatures of the same message into multi-signature.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// signatures_json: list of base58 encoded BLS signatures
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// multi_sig: base58 encoded BLS multi-signature
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_bls_aggregate_signatures(indy_handle_t      command_handle,
+                                                      const char *       signatures_json,
+
+                                                      void           (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           const char *  multi_sig)
+                                                     );
+
+    /// Verifies BLS multi-signature of a message, e.g. the one of ledger state proof.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// multi_sig: base58 encoded BLS multi-signature
+    /// message_raw: a pointer to first byte of message that has been signed
+    /// message_len: a message length
+    /// ver_keys_json: list of base58 encoded BLS verkeys of all the signers
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if multi-signature is valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_bls_verify_multi_sig(indy_handle_t      command_handle,
+                                                  const char *       multi_sig,
+                                                  const indy_u8_t *  message_raw,
+                                                  indy_u32_t         message_len,
+                                                  const char *       ver_keys_json,
+
+                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       indy_bool_t   valid)
+                                                 );
+
+    /// Register key management implementation.
+    ///
+    /// Key management keeps private keys in external KMS (PKCS#11 HSM or mobile secure enclave for example)
+    /// instead of the wallet. Keys are created in KMS with `kms` param of indy_create_key call,
+    /// signing with such keys is delegated to KMS.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// name: Key management name.
+    /// createKeyFn: KeyManagement create key operation handler
+    /// signFn: KeyManagement sign operation handler
+    /// freeFn: Handler that allows to de-allocate data allocated in caller code
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_register_key_management(indy_handle_t  command_handle,
+                                                     const char*    name,
+                                                     indy_error_t (*createKeyFn)(const char* crypto_type,
+                                                                                 const char** key_id_p,
+                                                                                 const char** verkey_p,
+                                                                                 indy_handle_t* key_handle_p),
+
+                                                     indy_error_t (*signFn)(const char* key_id,
+                                                                            const char* crypto_type,
+                                                                            const indy_u8_t* message_raw,
+                                                                            indy_u32_t message_len,
+                                                                            const indy_u8_t** signature_raw_p,
+                                                                            indy_u32_t* signature_len_p,
+                                                                            indy_handle_t* signature_handle_p),
+
+                                                     indy_error_t (*freeFn)(indy_handle_t handle),
+
+                                                     void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                     );
+
 #ifdef __cplusplus
 }
 #endif
@@ -22,10 +22,20 @@ extern "C" {
     ///             if provided, then keys will be replaced - key rotation use case)
     ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
+    ///     "mnemonic": object, (optional) BIP39 mnemonic for deterministic did creation instead of seed.
+    ///                 Seed is derived with SLIP-0010 Ed25519 path m/1093'/1'/index', so the same DIDs can be re-created on another device.
+    ///         {
+    ///             "phrase": string, mnemonic generated by indy_generate_mnemonic
+    ///             "passphrase": string, (optional) BIP39 passphrase, empty by default
+    ///             "index": int, (optional) index of the DID, 0 by default
+    ///         }
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
     ///               currently only 'ed25519' value is supported for this field)
     ///     "cid": bool, (optional; if not set then false is used;)
     ///     "method_name": string, method name to create fully qualified did (Example:  `did:method_name:NcYxiDXkpYi6ov5FcYDi1e`).
+    ///                    Use `indy:<namespace>` to create a DID bound to the network with this namespace (`did:indy:<namespace>:<id>`).
+    ///     "kms": string, (optional) Name of key management registered with indy_register_key_management.
+    ///            The DID key is created and kept in external KMS and can be used for signing only. Seed and mnemonic can't be used then.
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -117,6 +127,54 @@ extern "C" {
                                                                      indy_error_t  err)
                                                );
 
+    /// Rotates keys of an existing DID (owned by the caller of the library) in the wallet and on the ledger at once.
+    ///
+    /// Generates temporary keys for the DID, sends NYM transaction with the new verkey
+    /// signed by the current key of the DID, and applies the new keys in the wallet when the transaction is written.
+    /// Their DIDs and endpoints stored in the wallet with the old verkey are updated to the new one.
+    ///
+    /// If the ledger rejects the transaction the temporary keys are removed and the wallet stays unchanged.
+    /// If the ledger doesn't reply in time (PoolLedgerTimeout) the temporary keys are kept
+    /// so they can be applied with indy_replace_keys_apply once the NYM is found on the ledger.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// did: target did to rotate keys.
+    /// key_info: key information as json. Example:
+    /// {
+    ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
+    ///                                Can be UTF-8, base64 or hex string.
+    ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+    ///               currently only 'ed25519' value is supported for this field)
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   verkey: The new verification key of the DID
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+
+    extern indy_error_t indy_rotate_did_key(indy_handle_t command_handle,
+                                            indy_handle_t wallet_handle,
+                                            indy_handle_t pool_handle,
+                                            const char *  did,
+                                            const char *  key_info,
+
+                                            void           (*cb)(indy_handle_t command_handle_,
+                                                                 indy_error_t  err,
+                                                                 const char *const   verkey)
+                                           );
+
     /// Saves their DID for a pairwise connection in a secured Wallet,
     /// so that it can be used to verify transaction.
     ///
@@ -17,6 +17,7 @@ extern "C" {
     /// #Params
     /// command_handle: command handle to map callback to caller context.
     /// pool_handle: pool handle (created by open_pool_ledger).
+    ///             Pass 0 to route the request to the pool opened for the network namespace of `did:indy:<namespace>:<id>` submitter DID.
     /// wallet_handle: wallet handle (created by open_wallet).
     /// submitter_did: Id of Identity stored in secured Wallet.
     /// request_json: Request data json.
@@ -104,6 +105,51 @@ extern "C" {
                                                                 const char*   request_result_json)
                                            );
 
+    /// Send read request to validator pool collecting matching replies of several nodes.
+    ///
+    /// The request is sent to `fan_out` nodes at once and the result is returned as soon as `quorum` of them
+    /// replied with the same data or one of them replied with valid state proof.
+    /// If pending nodes can't make up the quorum anymore (a node failed or timed out) the request is sent
+    /// to as many further nodes as needed.
+    ///
+    /// Supported requests are those having state proofs:
+    ///     GET_NYM, GET_ATTR, GET_SCHEMA, GET_CRED_DEF, GET_REVOC_REG_DEF, GET_REVOC_REG, GET_REVOC_REG_DELTA,
+    ///     GET_AUTH_RULE, GET_TXN, GET_TXN_AUTHR_AGRMT, GET_TXN_AUTHR_AGRMT_AML
+    ///     and requests for which a state proof parser is registered (see indy_register_transaction_parser_for_sp)
+    ///
+    /// The request is sent to the nodes as is. It's assumed that it's already prepared.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// request_json: Request data json.
+    /// options_json: (Optional) Options of the read:
+    ///     {
+    ///         "quorum": Optional<int> - number of matching replies required to accept the result (f+1 by default),
+    ///                                       less than f+1 is accepted only with "require_state_proof",
+    ///         "fan_out": Optional<int> - number of nodes the request is sent to at once (`number_read_nodes` of the pool by default),
+    ///         "timeout": Optional<int> - time to wait for acknowledgement of a node in seconds (`timeout` of the pool by default),
+    ///         "require_state_proof": Optional<bool> - accept only a reply with valid state proof, matching replies without it are not enough (false by default)
+    ///     }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_submit_read_request(indy_handle_t command_handle,
+                                                 indy_handle_t pool_handle,
+                                                 const char *  request_json,
+                                                 const char *  options_json,
+
+                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                      indy_error_t  err,
+                                                                      const char*   request_result_json)
+                                                 );
+
     /// Signs request message.
     ///
     /// Adds submitter information to passed request json, signs it with submitter
@@ -567,6 +613,34 @@ extern "C" {
                                                                             const char*   request_json)
                                                        );
 
+    /// Parse a GET_VALIDATOR_INFO action response to get validator info of every node of the pool.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_validator_info_response: response of GET_VALIDATOR_INFO action (result of `indy_submit_action`).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Results of the action aggregated per node as json.
+    /// {
+    ///     <node_alias>: {
+    ///         "status": String - "REPLY", "REQNACK", "REJECT" or "timeout",
+    ///         "data": Optional<object> - validator info reported by the node (present for "REPLY" status only),
+    ///         "reason": Optional<string> - reason of request rejection (present for "REQNACK" and "REJECT" statuses only),
+    ///     },
+    ///     ...
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_parse_get_validator_info_response(indy_handle_t command_handle,
+                                                               const char *  get_validator_info_response,
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   nodes_results_json)
+                                                               );
 
     /// Builds a GET_TXN request. Request to get any transaction by its seq_no.
     ///
@@ -650,6 +724,35 @@ extern "C" {
                                                                              const char*   request_json)
                                                         );
 
+    /// Parse a POOL_RESTART action response to get the result of the restart command for every node of the pool.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_restart_response: response of POOL_RESTART action (result of `indy_submit_action`).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Results of the action aggregated per node as json.
+    /// {
+    ///     <node_alias>: {
+    ///         "status": String - "REPLY", "REQNACK", "REJECT" or "timeout",
+    ///         "data": Optional<object> - restart command result reported by the node (present for "REPLY" status only),
+    ///         "reason": Optional<string> - reason of request rejection (present for "REQNACK" and "REJECT" statuses only),
+    ///     },
+    ///     ...
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_parse_pool_restart_response(indy_handle_t command_handle,
+                                                         const char *  pool_restart_response,
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err,
+                                                                              const char*   nodes_results_json)
+                                                         );
+
     /// Builds a POOL_UPGRADE request. Request to upgrade the Pool (sent by Trustee).
     /// It upgrades the specified Nodes (either all nodes in the Pool, or some specific ones).
     ///
@@ -1307,6 +1410,136 @@ extern "C" {
                                                                                       const char*   request_json)
                                                                 );
 
+    /// Parse a GET_FROZEN_LEDGERS response to get the list of frozen ledgers.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_frozen_ledgers_response: response of GET_FROZEN_LEDGERS request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Frozen ledgers as json (empty object if there are no frozen ledgers).
+    /// {
+    ///     <ledger_id>: {
+    ///         "ledger": String - Ledger root hash,
+    ///         "state": String - State root hash,
+    ///         "seq_no": u64 - the latest transaction seqNo for particular Node,
+    ///     },
+    ///     ...
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_parse_get_frozen_ledgers_response(indy_handle_t command_handle,
+                                                               const char *  get_frozen_ledgers_response,
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   frozen_ledgers_json)
+                                                               );
+
+    /// Builds a request to add an object of the rich schema family:
+    /// JSON-LD context (SET_CONTEXT), rich schema (RICH_SCHEMA), mapping (MAPPING) or encoding (ENCODING).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: Identifier (DID) of the transaction author as base58-encoded string.
+    /// data: rich schema object json
+    /// {
+    ///     id: string - DID URL identifying the object
+    ///     content: string - JSON-LD content of the object serialized as string.
+    ///     rsName: string - name of the object
+    ///     rsVersion: string - version of the object
+    ///     rsType: string - type of the object: "ctx", "sch", "map" or "enc"
+    ///     ver: string - version of the object json
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_build_rich_schema_request(indy_handle_t command_handle,
+                                                       const char *  submitter_did,
+                                                       const char *  data,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   request_json)
+                                                      );
+
+    /// Builds a GET_RICH_SCHEMA_OBJECT_BY_ID request. Request to get an object of the rich schema family by its id.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+    /// id: DID URL identifying the object
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_build_get_rich_schema_object_by_id_request(indy_handle_t command_handle,
+                                                                        const char *  submitter_did,
+                                                                        const char *  id,
+
+                                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                                             indy_error_t  err,
+                                                                                             const char*   request_json)
+                                                                       );
+
+    /// Builds a GET_RICH_SCHEMA_OBJECT_BY_METADATA request. Request to get an object of the rich schema family
+    /// by its type, name and version.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+    /// rs_type: type of the object: "ctx", "sch", "map" or "enc"
+    /// rs_name: name of the object
+    /// rs_version: version of the object
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_build_get_rich_schema_object_by_metadata_request(indy_handle_t command_handle,
+                                                                              const char *  submitter_did,
+                                                                              const char *  rs_type,
+                                                                              const char *  rs_name,
+                                                                              const char *  rs_version,
+
+                                                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                                                   indy_error_t  err,
+                                                                                                   const char*   request_json)
+                                                                             );
+
+    /// Parse a GET_RICH_SCHEMA_OBJECT_BY_ID or GET_RICH_SCHEMA_OBJECT_BY_METADATA response.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_rich_schema_object_response: response of GET_RICH_SCHEMA_OBJECT_BY_ID or GET_RICH_SCHEMA_OBJECT_BY_METADATA request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Object id and object json (see `indy_build_rich_schema_request`).
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_parse_get_rich_schema_object_response(indy_handle_t command_handle,
+                                                                   const char *  get_rich_schema_object_response,
+                                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                                        indy_error_t  err,
+                                                                                        const char*   id,
+                                                                                        const char*   rich_schema_json)
+                                                                  );
+
     /// Builds a GET_TXN_AUTHR_AGRMT_AML request. Request to get a list of  acceptance mechanisms from the ledger
     /// valid for specified time or the latest one.
     ///
@@ -1377,6 +1610,83 @@ extern "C" {
                                                                                                     const char*   request_with_meta_json)
                                                                                );
 
+    /// Record transaction author agreement acceptance for the requests built by libindy.
+    /// Once recorded, acceptance data is appended to every domain ledger write request
+    /// (NYM, ATTRIB, SCHEMA, CRED_DEF, REVOC_REG_DEF, REVOC_REG_ENTRY) built by `indy_build_*_request` functions,
+    /// so `indy_append_txn_author_agreement_acceptance_to_request` doesn't have to be called for them.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// Digest and time are prepared the same way as in `indy_append_txn_author_agreement_acceptance_to_request`.
+    /// Use `indy_get_active_txn_author_agreement` to fetch text, version and acceptance mechanisms set on the Ledger.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// text and version - (optional) raw data about TAA from ledger.
+    ///     These parameters should be passed together.
+    ///     These parameters are required if taa_digest parameter is omitted.
+    /// taa_digest - (optional) digest on text and version.
+    ///     Digest is sha256 hash calculated on concatenated strings: version || text.
+    ///     This parameter is required if text and version parameters are omitted.
+    /// mechanism - (optional) mechanism how user has accepted the TAA.
+    ///     Pass null to remove recorded acceptance, other parameters are ignored in this case.
+    /// time - UTC timestamp when user has accepted the TAA. Note that the time portion will be discarded to avoid a privacy risk.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_set_txn_author_agreement_acceptance(indy_handle_t command_handle,
+                                                                 const char *  text,
+                                                                 const char *  version,
+                                                                 const char *  taa_digest,
+                                                                 const char *  mechanism,
+                                                                 indy_u64_t    time,
+
+                                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                                      indy_error_t  err)
+                                                                 );
+
+    /// Get transaction author agreement and acceptance mechanisms which are active on the Ledger.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// Sends GET_TXN_AUTHR_AGRMT and GET_TXN_AUTHR_AGRMT_AML requests to the pool and combines their replies.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// {
+    ///     "taa": Optional<object> - active transaction author agreement, null if it isn't set on the Ledger
+    ///     {
+    ///         "text": string - text of TAA,
+    ///         "version": string - version of TAA,
+    ///         "digest": string - digest to use for acceptance,
+    ///         "ratification_ts": Optional<int> - time of TAA ratification
+    ///     },
+    ///     "aml": Optional<object> - latest acceptance mechanisms, null if they aren't set on the Ledger
+    ///     {
+    ///         "aml": {<label>: <description>} - acceptance mechanisms allowed for TAA acceptance,
+    ///         "version": string - version of acceptance mechanisms,
+    ///         "amlContext": Optional<string> - context information about acceptance mechanisms
+    ///     }
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+    extern indy_error_t indy_get_active_txn_author_agreement(indy_handle_t command_handle,
+                                                             indy_handle_t pool_handle,
+                                                             const char *  submitter_did,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   active_taa_json)
+                                                             );
+
     /// Append Endorser to an existing request.
     ///
     /// An author of request still is a `DID` used as a `submitter_did` parameter for the building of the request.
@@ -1405,6 +1715,74 @@ extern "C" {
                                                                           const char*   out_request_json)
                                                      );
 
+    /// Sign request by its author for the Endorser.
+    ///
+    /// Appends the Endorser to the request and multi signs it with the author key (see `indy_multi_sign_request`),
+    /// so the output can be transported to the Endorser who adds their signature and submits it
+    /// (see `indy_endorse_and_submit_request`).
+    ///
+    /// More about Transaction Endorser: https://github.com/hyperledger/indy-node/blob/master/design/transaction_endorser.md
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// author_did: DID of the transaction author used to build the request. Must be stored in the wallet.
+    /// request_json: original request data json.
+    /// endorser_did: DID of the Endorser that will submit the transaction.
+    ///               The Endorser's DID must be present on the ledger.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request json signed by the author and ready to be passed to the Endorser.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_sign_request_for_endorser(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  author_did,
+                                                       const char *  request_json,
+                                                       const char *  endorser_did,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   signed_request_json)
+                                                       );
+
+    /// Endorse request signed by its author and send it to validator pool.
+    ///
+    /// Checks that the request names the Endorser and is multi signed by its author (see `indy_sign_request_for_endorser`),
+    /// adds the Endorser signature and sends the request to validator pool.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    ///             Pass 0 to route the request to the pool opened for the network namespace of `did:indy:<namespace>:<id>` endorser DID.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// endorser_did: DID of the Endorser named in the request. Must be stored in the wallet.
+    /// request_json: request json signed by the author.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+    extern indy_error_t indy_endorse_and_submit_request(indy_handle_t command_handle,
+                                                        indy_handle_t pool_handle,
+                                                        indy_handle_t wallet_handle,
+                                                        const char *  endorser_did,
+                                                        const char *  request_json,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   request_result_json)
+                                                        );
+
 #ifdef __cplusplus
 }
 #endif
@@ -77,6 +77,22 @@ extern "C" {
                                         void (**flushFn)(const void*  context)
                                                   );
 
+    /// Set correlation id for the subsequent calls from the current thread.
+    ///
+    /// Every command started by libindy is tagged with correlation id that prefixes its log messages
+    /// including messages of the service calls, pool requests and callbacks of the command.
+    /// By default libindy generates unique id for every call, this function allows application
+    /// to use its own ids (request id of the application for example) to trace the calls.
+    ///
+    /// #Params
+    /// correlation_id: (optional) correlation id to use for the calls from the current thread.
+    ///                 Pass null to restore generation of ids.
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_set_correlation_id(const char *  correlation_id);
+
 #ifdef __cplusplus
 }
 #endif
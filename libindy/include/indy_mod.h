@@ -54,6 +54,12 @@ typedef enum
     // IO Error
     CommonIOError = 114,
 
+    // Command queue of the library is full. The call can be retried later
+    CommonBusy = 130,
+
+    // Command was cancelled with indy_cancel_command or its timeout expired
+    CommonCancelled = 131,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -183,6 +189,12 @@ extern "C" {
     /// #Params
     /// config: {
     ///     "crypto_thread_pool_size": Optional<int> - size of thread pool for the most expensive crypto operations. (4 by default)
+    ///     "cred_def_thread_pool_size": Optional<int> - size of dedicated thread pool for credential definition keys generation. (2 by default)
+    ///     "command_queue_size": Optional<int> - max number of commands waiting for execution. (0 by default - the queue is unbounded)
+    ///         Calls made when the queue is full are rejected with CommonBusy error and can be retried later.
+    ///     "command_workers": Optional<int> - number of threads executing commands. (1 by default, at most 3 are used)
+    ///         Wallet dependent commands always run on the same thread, pool and blob storage commands get own threads if available.
+    ///         NOTE: must be set before invocation of any other API functions.
     ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
     ///         Capturing of backtrace can affect library performance.
     ///         NOTE: must be set before invocation of any other API functions.
@@ -192,6 +204,39 @@ extern "C" {
     /// Common*
     extern indy_error_t indy_set_runtime_config(const char * config);
 
+    /// Cancel long-running command.
+    ///
+    /// Callback of the command is called with CommonCancelled error and the result of the command is dropped when it's ready.
+    /// Supported commands: `indy_submit_request`, `indy_sign_and_submit_request`, `indy_submit_action`,
+    /// `indy_prover_create_proof`, `indy_prover_create_proof_with_details`, `indy_prover_fetch_credentials_for_proof_req`,
+    /// `indy_fetch_wallet_search_next_records`.
+    ///
+    /// Cancellation doesn't stop the work of the command: proof generation keeps running on the thread pool
+    /// and a pool request is still sent to the ledger, only the result isn't returned.
+    /// 
+    /// NOTE: commands are identified by command handle passed to the function call,
+    /// so the call of a cancellable command fails with CommonInvalidParam1 while another one with the same handle is pending.
+    ///
+    /// #Params
+    /// command_handle: command handle passed to the call of the command to cancel.
+    ///
+    /// #Errors
+    /// CommonInvalidParam1 - there is no pending cancellable command with the handle
+    extern indy_error_t indy_cancel_command(indy_handle_t command_handle);
+
+    /// Set timeout of long-running command.
+    ///
+    /// The command is cancelled (see `indy_cancel_command`) if it isn't completed in `timeout_ms` milliseconds.
+    /// Should be called right after the call of the command.
+    ///
+    /// #Params
+    /// command_handle: command handle passed to the call of the command.
+    /// timeout_ms: timeout in milliseconds.
+    ///
+    /// #Errors
+    /// CommonInvalidParam1 - there is no pending cancellable command with the handle
+    extern indy_error_t indy_set_command_timeout(indy_handle_t command_handle, indy_u32_t timeout_ms);
+
     /// Get details for last occurred error.
     ///
     /// This function should be called in two places to handle both cases of error occurrence:
@@ -205,15 +250,38 @@ extern "C" {
     /// * `error_json_p` - Reference that will contain error details (if any error has occurred before)
     ///  in the format:
     /// {
+    ///     "code": int - error code returned by the function
     ///     "backtrace": Optional<str> - error backtrace.
     ///         Collecting of backtrace can be enabled by:
     ///             1) setting environment variable `RUST_BACKTRACE=1`
     ///             2) calling `indy_set_runtime_config` API function with `collect_backtrace: true`
     ///     "message": str - human-readable error description
+    ///     "extra": {
+    ///         "context": [str] - messages of the error context chain from the outermost one to the root cause
+    ///                            (names the invalid parameter for CommonInvalidParam errors for example)
+    ///         "vendor_code": Optional<int> - original error code returned by plugin (payment method, wallet storage, key management)
+    ///     }
     /// }
     ///
     extern void indy_get_current_error(const char ** error_json_p);
 
+    /// Get optional subsystems of libindy: whether they are compiled in and enabled at the moment of the call.
+    ///
+    /// NOTE: Returning pointer is valid until the next call of this function in the same execution thread.
+    ///
+    /// #Returns
+    /// Map in the JSON format. Where keys are names of subsystems:
+    /// {
+    ///     "revocation": {"compiled": bool, "enabled": bool},
+    ///     "payments": {"compiled": bool, "enabled": bool},
+    ///     "postgres_storage": {"compiled": bool, "enabled": bool} - always false as postgres storage is a separate plugin
+    ///         that has to be registered with `indy_register_wallet_storage`,
+    ///     "didcomm_v2": {"compiled": bool, "enabled": bool},
+    ///     "experimental_protocols": {"compiled": bool, "enabled": bool},
+    ///     "anoncreds_telemetry": {"compiled": bool, "enabled": bool} - enabled by `indy_set_runtime_config`
+    /// }
+    extern const char * indy_get_features();
+
 #ifdef __cplusplus
 }
 #endif
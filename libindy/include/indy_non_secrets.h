@@ -144,6 +144,74 @@ extern "C" {
                                                                        indy_error_t err)
                                                  );
 
+    /// Create a list of new non-secret records in the wallet atomically.
+    /// Either all records are added or none of them (if any record already exists for example).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// records_json: json array of records to add:
+    ///   [
+    ///     {
+    ///       "type": <str>, // allows to separate different record types collections
+    ///       "id": <str>, // the id of record
+    ///       "value": <str>, // the value of record
+    ///       "tags": (optional) <object>, // the record tags in the same format as for indy_add_wallet_record
+    ///     }
+    ///   ]
+
+    extern indy_error_t indy_add_wallet_records(indy_handle_t  command_handle,
+                                                indy_handle_t  wallet_handle,
+                                                const char*    records_json,
+                                                void           (*fn)(indy_handle_t command_handle_,
+                                                                     indy_error_t err)
+                                               );
+
+    /// Delete a list of existing wallet records atomically.
+    /// Either all records are deleted or none of them (if any record doesn't exist for example).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// record_ids_json: json array of ids of records to delete:
+    ///   [
+    ///     {
+    ///       "type": <str>, // record type
+    ///       "id": <str>, // the id of record
+    ///     }
+    ///   ]
+
+    extern indy_error_t indy_delete_wallet_records(indy_handle_t  command_handle,
+                                                   indy_handle_t  wallet_handle,
+                                                   const char*    record_ids_json,
+                                                   void           (*fn)(indy_handle_t command_handle_,
+                                                                        indy_error_t err)
+                                                  );
+
+    /// Apply a list of add/update/delete operations to wallet records atomically (in a single storage transaction).
+    /// Either all operations are applied or wallet is left unchanged.
+    ///
+    /// Note: custom wallet storage must register batch handler (see indy_register_wallet_storage_batch).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// operations_json: json array of operations applied in the given order:
+    ///   [
+    ///     {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": (optional) <object>},
+    ///     {"op": "update", "type": <str>, "id": <str>, "value": (optional) <str>, "tags": (optional) <object>},
+    ///     {"op": "delete", "type": <str>, "id": <str>},
+    ///   ]
+    ///   "update" operation replaces record value and/or all record tags, at least one of them must be set.
+    ///   Tags have the same format as for indy_add_wallet_record.
+
+    extern indy_error_t indy_with_wallet_records(indy_handle_t  command_handle,
+                                                 indy_handle_t  wallet_handle,
+                                                 const char*    operations_json,
+                                                 void           (*fn)(indy_handle_t command_handle_,
+                                                                      indy_error_t err)
+                                                );
+
     /// Get an wallet record by id
     ///
     /// #Params
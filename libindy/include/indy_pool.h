@@ -43,6 +43,23 @@ extern "C" {
                                                   indy_u64_t    protocol_version,
                                                   void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
                                                   );
+
+    extern indy_error_t indy_get_pool_handle_for_network(indy_handle_t command_handle,
+                                                         const char *  network,
+                                                         void          (*cb)(indy_handle_t command_handle_, indy_error_t err, indy_handle_t pool_handle)
+                                                         );
+
+    extern indy_error_t indy_validate_pool_genesis_txns(indy_handle_t command_handle,
+                                                        const char *  genesis_txn,
+                                                        const char *  options_json,
+                                                        void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char *const report_json)
+                                                        );
+
+    extern indy_error_t indy_diff_pool_genesis_txns(indy_handle_t command_handle,
+                                                    const char *  config_name,
+                                                    const char *  genesis_txn,
+                                                    void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char *const diff_json)
+                                                    );
 #ifdef __cplusplus
 }
 #endif
@@ -144,6 +144,97 @@ extern "C" {
                                                   void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                                   );
 
+    /// Registers wallet key wrapper implementation.
+    ///
+    /// Key wrapper allows to seal wallet export with the transit key of external KMS (HashiCorp Vault transit engine for example)
+    /// instead of passphrase. See `sealed` param of indy_export_wallet call.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Key wrapper name.
+    /// wrapKeyFn: KeyWrapper wrap key operation handler
+    /// unwrapKeyFn: KeyWrapper unwrap key operation handler
+    /// freeKeyFn: Handler that allows to de-allocate keys allocated in caller code
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_key_wrapper(indy_handle_t  command_handle,
+                                                         const char*    type_,
+                                                         indy_error_t (*wrapKeyFn)(const char* key_id,
+                                                                                   const char* key,
+                                                                                   const char** wrapped_key_p,
+                                                                                   indy_handle_t* wrapped_key_handle_p),
+
+                                                         indy_error_t (*unwrapKeyFn)(const char* key_id,
+                                                                                     const char* wrapped_key,
+                                                                                     const char** key_p,
+                                                                                     indy_handle_t* key_handle_p),
+
+                                                         indy_error_t (*freeKeyFn)(indy_handle_t key_handle),
+
+                                                         void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
+    /// Registers atomic batch handler for the custom wallet storage registered with indy_register_wallet_storage.
+    ///
+    /// Batch handler allows to apply atomic record operations (see indy_with_wallet_records)
+    /// in a single storage transaction. Without it such operations fail for the custom storage.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Storage type name (must be registered already).
+    /// batchRecordsFn: WalletType batch records operation handler.
+    ///   operations_json: json array of operations applied in the given order:
+    ///   [
+    ///     {"op": "add", "type": <base64 type>, "id": <base64 id>, "value": <base64 value>, "tags": <tags json>},
+    ///     {"op": "update", "type": <base64 type>, "id": <base64 id>, "value": <base64 value>},
+    ///     {"op": "update_tags", "type": <base64 type>, "id": <base64 id>, "tags": <tags json>},
+    ///     {"op": "delete", "type": <base64 type>, "id": <base64 id>},
+    ///   ]
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_storage_batch(indy_handle_t  command_handle,
+                                                           const char*    type_,
+                                                           indy_error_t (*batchRecordsFn)(indy_handle_t handle,
+                                                                                          const char* operations_json),
+
+                                                           void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                           );
+
+    /// Registers chunked search handlers for the custom wallet storage registered with indy_register_wallet_storage.
+    ///
+    /// Chunked search handlers allow libindy to fetch search results by chunks of records
+    /// with a single handler call instead of fetchSearchNextRecordFn and getRecord*Fn calls for every record.
+    /// Storages without these handlers are searched record by record.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Storage type name (must be registered already).
+    /// fetchSearchNextRecordsFn: WalletType fetch search next records operation handler.
+    ///   Returns up to count records as json array (empty array if no more records):
+    ///   [{"id": <base64 id>, "type": <base64 type>, "value": <base64 value>, "tags": <tags json>}]
+    ///   type, value and tags are present only if requested in search options.
+    /// freeSearchRecordsFn: WalletType free search records operation handler.
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_storage_search_chunk(indy_handle_t  command_handle,
+                                                                  const char*    type_,
+                                                                  indy_error_t (*fetchSearchNextRecordsFn)(indy_handle_t handle,
+                                                                                                           indy_handle_t search_handle,
+                                                                                                           indy_u32_t count,
+                                                                                                           const char** records_json_p,
+                                                                                                           indy_handle_t* records_handle_p),
+                                                                  indy_error_t (*freeSearchRecordsFn)(indy_handle_t handle,
+                                                                                                      indy_handle_t records_handle),
+
+                                                                  void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                                  );
+
     /// Create a new secure wallet.
     ///
     /// #Params
@@ -217,6 +308,8 @@ extern "C" {
     ///       "key": string, Key or passphrase used for wallet key derivation.
     ///                      Look to key_derivation_method param for information about supported key derivation methods.
     ///       "rekey": optional<string>, If present than wallet master key will be rotated to a new one.
+    ///       "key_shares": optional<array<string>>, Shares of the key returned by indy_split_wallet_key and decrypted by their holders.
+    ///                     If present than key is reconstructed from them and "key" must be omitted.
     ///       "storage_credentials": optional<object> Credentials for wallet storage. Storage type defines set of supported keys.
     ///                              Can be optional if storage supports default configuration.
     ///                              For 'default' storage type should be empty.
@@ -248,6 +341,9 @@ extern "C" {
 
     /// Exports opened wallet
     ///
+    /// With "chunked" option records are exported in chunks sealed separately, so the export file is verified
+    /// and imported chunk by chunk without loading the whole file into memory.
+    ///
     /// #Params:
     /// wallet_handle: wallet handle returned by indy_open_wallet
     /// export_config: JSON containing settings for input operation.
@@ -260,6 +356,20 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "sealed": optional<object> Seal export with the transit key of external KMS instead of key.
+    ///               Random export key is generated and stored in the export file wrapped with the transit key.
+    ///         {
+    ///             "key_wrapper": <string>, Name of key wrapper registered with indy_register_wallet_key_wrapper call
+    ///             "key_id": <string>, Identifier of the transit key in KMS
+    ///         }
+    ///     "filter": optional<object> Export only matching records instead of the whole wallet.
+    ///         {
+    ///             "types": <array<string>>, Types of exported records. Records created by libindy have "Indy::" prefix
+    ///                                       (for example "Indy::Credential").
+    ///             "query": optional<object>, WQL query records of the listed types must match (see indy_open_wallet_search)
+    ///         }
+    ///     "chunked": optional<bool> Export in separately sealed chunks (false by default).
+    ///                Chunked export files can't be imported by libindy versions before chunked export.
     ///   }
     ///
     /// #Returns
@@ -275,6 +385,94 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Starts re-encryption of wallet records under a new master key.
+    ///
+    /// Unlike rekey on wallet opening, that re-encrypts only the wallet keys, all records are re-encrypted
+    /// with newly generated keys. Records are re-encrypted in batches by indy_reencrypt_wallet_records call,
+    /// the wallet remains available for other operations between batches.
+    /// Re-encryption is continued after reopening of the wallet with the current key.
+    /// The new key has to be used for opening of the wallet after re-encryption is finished.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// reencryption_config: Re-encryption configuration json.
+    ///   {
+    ///       "key": string, Current key or passphrase of the wallet.
+    ///       "new_key": string, Key or passphrase used for derivation of the new wallet master key.
+    ///       "key_derivation_method": optional<string> Algorithm used for current key derivation:
+    ///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+    ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+    ///                          RAW - raw wallet key master provided (skip derivation).
+    ///       "new_key_derivation_method": optional<string> Algorithm to use for new key derivation:
+    ///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+    ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+    ///                          RAW - raw wallet key master provided (skip derivation).
+    ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///   }
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_start_wallet_reencryption(indy_handle_t  command_handle,
+                                                       indy_handle_t  wallet_handle,
+                                                       const char*    reencryption_config,
+                                                       void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                       );
+
+    /// Re-encrypts the next batch of wallet records under the new master key.
+    /// Re-encryption has to be started with indy_start_wallet_reencryption call.
+    ///
+    /// Call it repeatedly (with pauses to limit the load of the storage) until `finished` is true.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// batch_size: max number of records to re-encrypt
+    ///
+    /// #Returns
+    /// Error code
+    /// progress_json: {
+    ///     "reencrypted": int, Number of records re-encrypted by the batch
+    ///     "finished": bool, All records are re-encrypted and the wallet uses the new master key since now
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_reencrypt_wallet_records(indy_handle_t  command_handle,
+                                                      indy_handle_t  wallet_handle,
+                                                      indy_u32_t     batch_size,
+                                                      void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* progress_json)
+                                                      );
+
+    /// Verifies wallet records can be decrypted and reports progress of re-encryption.
+    /// Each record of the wallet is read, so it can take a while for large wallets.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    ///
+    /// #Returns
+    /// Error code
+    /// status_json: {
+    ///     "inProgress": bool, Re-encryption is started and not finished yet
+    ///     "total": int, Number of records in the wallet
+    ///     "remaining": int, Number of records still encrypted with the current master key
+    ///     "undecryptable": int, Number of records that can't be decrypted
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_wallet_reencryption_status(indy_handle_t  command_handle,
+                                                            indy_handle_t  wallet_handle,
+                                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* status_json)
+                                                            );
+
 
     /// Creates a new secure wallet and then imports its content
     /// according to fields provided in import_config
@@ -314,6 +512,14 @@ extern "C" {
     /// {
     ///   "path": <string>, path of the file that contains exported wallet content
     ///   "key": <string>, key used for export of the wallet
+    ///                    Can be omitted for sealed export. Its key is unwrapped with the key wrapper used for export,
+    ///                    so the same key wrapper must be registered with indy_register_wallet_key_wrapper call.
+    ///   "key_shares": optional<array<string>>, Shares of the export key returned by indy_split_wallet_key and decrypted by their holders.
+    ///                 If present than key is reconstructed from them and "key" must be omitted.
+    ///   "resumable": optional<bool>, Keep partially imported wallet if import fails (false by default).
+    ///                Calling import with the same config, credentials and import_config again
+    ///                continues import from the first not imported chunk.
+    ///                Supported only for export files created with "chunked" option.
     /// }
     ///
     /// #Returns
@@ -330,6 +536,44 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Imports records of exported wallet into opened wallet
+    ///
+    /// Whole export file is verified before the wallet is changed.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// import_config: Import settings json.
+    /// {
+    ///   "path": <string>, path of the file that contains exported wallet content
+    ///   "key": <string>, key used for export of the wallet
+    ///                    Can be omitted for sealed export. Its key is unwrapped with the key wrapper used for export,
+    ///                    so the same key wrapper must be registered with indy_register_wallet_key_wrapper call.
+    ///   "key_shares": optional<array<string>>, Shares of the export key returned by indy_split_wallet_key and decrypted by their holders.
+    ///                 If present than key is reconstructed from them and "key" must be omitted.
+    ///   "conflict_policy": optional<string>, What to do with records already present in the wallet:
+    ///                      skip - keep existing record
+    ///                      overwrite - replace value and tags of existing record
+    ///                      fail - fail without changes in the wallet (used by default)
+    /// }
+    ///
+    /// #Returns
+    /// Error code
+    /// import_result_json: {
+    ///     "added": int, Number of added records
+    ///     "skipped": int, Number of records skipped as already present
+    ///     "overwritten": int, Number of overwritten records
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_records(indy_handle_t  command_handle,
+                                                   indy_handle_t  wallet_handle,
+                                                   const char*    import_config_json,
+                                                   void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* import_result_json)
+                                                   );
+
     /// Closes opened wallet and frees allocated resources.
     ///
     /// #Params
@@ -402,6 +646,13 @@ extern "C" {
     /// {
     ///   "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                              Can be UTF-8, base64 or hex string.
+    ///   "mnemonic": object, (optional) BIP39 mnemonic for deterministic key creation instead of seed.
+    ///               Key is derived with SLIP-0010 Ed25519 path m/1093'/0'/index'.
+    ///       {
+    ///           "phrase": string, mnemonic generated by indy_generate_mnemonic
+    ///           "passphrase": string, (optional) BIP39 passphrase, empty by default
+    ///           "index": int, (optional) index of the key, 0 by default
+    ///       }
     /// }
     ///
     /// #Returns
@@ -418,6 +669,65 @@ extern "C" {
                                                                          const char *const key)
                                                 );
 
+    /// Generate BIP39 mnemonic.
+    /// Mnemonic is a human readable backup of keys derived from it: it can be passed to indy_generate_wallet_key
+    /// for derivation of the wallet key and to indy_create_and_store_my_did for derivation of DIDs,
+    /// so the same wallet key and DIDs can be re-derived on another device.
+    ///
+    /// #Params
+    /// config: (optional) mnemonic configuration json.
+    /// {
+    ///   "word_count": int, (optional) Number of words: 12, 15, 18, 21 or 24 (default).
+    /// }
+    ///
+    /// #Returns
+    /// err: Error code
+    /// mnemonic: mnemonic phrase of English words separated by spaces
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_generate_mnemonic(indy_handle_t     command_handle,
+                                               const char *const config,
+
+                                               void              (*cb)(indy_handle_t     command_handle,
+                                                                       indy_error_t      err,
+                                                                       const char *const mnemonic)
+                                              );
+
+    /// Split wallet key into shares for social recovery of the wallet.
+    ///
+    /// Key is split with Shamir's secret sharing scheme into a share for each recipient, any `threshold`
+    /// of shares are enough to reconstruct the key. Each share is encrypted for its recipient as in indy_crypto_anon_crypt.
+    /// Recipient decrypts the share with indy_crypto_anon_decrypt and the decrypted shares can be passed
+    /// as "key_shares" of credentials to indy_open_wallet and indy_import_wallet or of import config to indy_import_wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// key: wallet key, passphrase or export key to split.
+    /// config: key shares configuration json.
+    /// {
+    ///   "threshold": int, Number of shares required to reconstruct the key. Must be at least 2.
+    ///   "recipients": array<string>, Verkeys of share holders. Number of shares is equal to the number of recipients.
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// err: Error code
+    /// shares: json array of base58 encrypted shares in the order of recipients.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_split_wallet_key(indy_handle_t     command_handle,
+                                              const char *const key,
+                                              const char *const config,
+
+                                              void              (*cb)(indy_handle_t     command_handle,
+                                                                      indy_error_t      err,
+                                                                      const char *const shares)
+                                             );
+
 #ifdef __cplusplus
 }
 #endif
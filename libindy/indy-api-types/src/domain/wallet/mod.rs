@@ -8,11 +8,24 @@ pub struct Config {
     pub id: String,
     pub storage_type: Option<String>,
     pub storage_config: Option<Value>,
+    pub range_tags: Option<HashMap<String, RangeTagConfig>>,
+}
+
+// Encrypted tag that additionally gets an order-revealing bucket tag,
+// so that $gt/$gte/$lt/$lte queries can be answered for it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RangeTagConfig {
+    // Width of a bucket. Only the bucket index of a value is revealed to the storage.
+    pub bucket_size: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Credentials {
+    #[serde(default)]
     pub key: String,
+    // Shamir shares of the key produced by indy_split_wallet_key. Key is reconstructed from them if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_shares: Option<Vec<String>>,
     pub rekey: Option<String>,
     pub storage_credentials: Option<Value>,
     #[serde(default = "default_key_derivation_method")]
@@ -35,15 +48,120 @@ fn default_key_derivation_method() -> KeyDerivationMethod {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExportConfig {
+    #[serde(default)]
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
-    pub key_derivation_method: KeyDerivationMethod
+    pub key_derivation_method: KeyDerivationMethod,
+    pub sealed: Option<SealedExportConfig>,
+    // Shamir shares of the export key. Key is reconstructed from them on import if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_shares: Option<Vec<String>>,
+    // Keeps partially imported wallet on failure, so import into it can be continued by the next call
+    #[serde(default)]
+    pub resumable: bool,
+    // Exports only matching records instead of the whole wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ExportFilter>,
+    // Resolves conflicts with records already present in the wallet on import of records into opened wallet
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+    // Exports in separately sealed chunks (version 1). Default is version 0 readable by older libindy
+    #[serde(default)]
+    pub chunked: bool,
+}
+
+// Records of the listed types matching optional WQL query.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportFilter {
+    pub types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<Value>,
+}
+
+impl Validatable for ExportFilter {
+    fn validate(&self) -> Result<(), String> {
+        if self.types.is_empty() || self.types.iter().any(String::is_empty) {
+            return Err("Export filter must contain non empty record types".to_string());
+        }
+
+        if self.types.iter().enumerate().any(|(i, type_)| self.types[..i].contains(type_)) {
+            return Err("Export filter contains duplicated record types".to_string());
+        }
+
+        match self.query {
+            Some(ref query) if !query.is_object() => Err("Export filter query must be WQL object".to_string()),
+            _ => Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    // Keeps existing record
+    Skip,
+    // Replaces value and tags of existing record
+    Overwrite,
+    // Fails without changes in the wallet if any record already exists
+    Fail,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        ConflictPolicy::Fail
+    }
+}
+
+// Export key is generated randomly and wrapped with the transit key of external KMS instead of derivation from passphrase.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SealedExportConfig {
+    // Name of key wrapper registered with indy_register_wallet_key_wrapper
+    pub key_wrapper: String,
+    // Identifier of the transit key in KMS
+    pub key_id: String,
+}
+
+// Passphrases for re-encryption of wallet records under a new master key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReencryptionConfig {
+    pub key: String,
+    pub new_key: String,
+    #[serde(default = "default_key_derivation_method")]
+    pub key_derivation_method: KeyDerivationMethod,
+    #[serde(default = "default_key_derivation_method")]
+    pub new_key_derivation_method: KeyDerivationMethod,
+}
+
+// Social recovery of the wallet key: key is split into a share for each recipient,
+// any `threshold` of them are enough to reconstruct the key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeySharesConfig {
+    pub threshold: usize,
+    // Verkeys of share holders. Each share is anoncrypted for its holder.
+    pub recipients: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct KeyConfig {
-    pub seed: Option<String>
+    pub seed: Option<String>,
+    pub mnemonic: Option<MnemonicSeed>,
+}
+
+// BIP39 mnemonic used for deterministic derivation of the wallet key or DID seed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MnemonicSeed {
+    pub phrase: String,
+    #[serde(default)]
+    pub passphrase: String,
+    // Index of the key in the derivation path
+    #[serde(default)]
+    pub index: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MnemonicConfig {
+    pub word_count: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,16 +174,88 @@ pub struct Record {
     // Wallet record value
     pub value: String,
     // Wallet record tags
+    #[serde(default)]
     pub tags: HashMap<String, String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordId {
+    // Wallet record type
+    #[serde(rename = "type")]
+    pub type_: String,
+    // Wallet record id
+    pub id: String,
+}
+
+// Operation of the atomic wallet records transaction
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum RecordOperation {
+    Add {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        value: String,
+        #[serde(default)]
+        tags: Tags,
+    },
+    // Replaces value and/or all tags of the record
+    Update {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+        value: Option<String>,
+        tags: Option<Tags>,
+    },
+    Delete {
+        #[serde(rename = "type")]
+        type_: String,
+        id: String,
+    },
+}
+
+impl RecordOperation {
+    pub fn type_(&self) -> &str {
+        match self {
+            RecordOperation::Add { type_, .. } |
+            RecordOperation::Update { type_, .. } |
+            RecordOperation::Delete { type_, .. } => type_
+        }
+    }
+}
+
 pub type Tags = HashMap<String, String>;
 
+impl Validatable for RecordOperation {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            RecordOperation::Add { type_, id, .. } |
+            RecordOperation::Delete { type_, id } if type_.is_empty() || id.is_empty() =>
+                Err("Record type and id must be non empty".to_string()),
+            RecordOperation::Update { type_, id, .. } if type_.is_empty() || id.is_empty() =>
+                Err("Record type and id must be non empty".to_string()),
+            RecordOperation::Update { value: None, tags: None, .. } =>
+                Err("Update operation must contain value or tags".to_string()),
+            _ => Ok(())
+        }
+    }
+}
+
 impl Validatable for Config {
     fn validate(&self) -> Result<(), String> {
         if self.id.is_empty() {
             return Err("Wallet id is empty".to_string());
         }
+        if let Some(ref range_tags) = self.range_tags {
+            for (name, config) in range_tags {
+                if name.is_empty() || name.starts_with('~') {
+                    return Err(format!("Range indexed tag must be a non empty encrypted tag name: {:?}", name));
+                }
+                if config.bucket_size == 0 {
+                    return Err(format!("Bucket size for range indexed tag {:?} must be greater than 0", name));
+                }
+            }
+        }
         Ok(())
     }
 }
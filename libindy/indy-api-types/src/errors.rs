@@ -33,6 +33,10 @@ pub enum IndyErrorKind {
     InvalidParam(u32),
     #[fail(display = "IO error")]
     IOError,
+    #[fail(display = "Library is busy")]
+    Busy,
+    #[fail(display = "Command cancelled")]
+    Cancelled,
     // Anoncreds errors
     #[fail(display = "Duplicated master secret")]
     MasterSecretDuplicateName,
@@ -120,7 +124,9 @@ pub enum IndyErrorKind {
 pub struct IndyError {
     // FIXME: We have to use Arc as for now we clone messages in pool service
     // FIXME: In theory we can avoid sync by refactoring of pool service
-    inner: Arc<Context<IndyErrorKind>>
+    inner: Arc<Context<IndyErrorKind>>,
+    // Original error code returned by plugin (payment method, wallet storage or key management)
+    vendor_code: Option<i32>,
 }
 
 impl Fail for IndyError {
@@ -153,7 +159,7 @@ impl fmt::Display for IndyError {
 impl IndyError {
     pub fn from_msg<D>(kind: IndyErrorKind, msg: D) -> IndyError
         where D: fmt::Display + fmt::Debug + Send + Sync + 'static {
-        IndyError { inner: Arc::new(Context::new(msg).context(kind)) }
+        IndyError { inner: Arc::new(Context::new(msg).context(kind)), vendor_code: None }
     }
 
     pub fn kind(&self) -> IndyErrorKind {
@@ -164,13 +170,40 @@ impl IndyError {
         where D: fmt::Display + fmt::Debug + Send + Sync + 'static {
         let kind = self.kind();
         let inner = Arc::try_unwrap(self.inner).unwrap();
-        IndyError { inner: Arc::new(inner.map(|_| msg).context(kind)) }
+        IndyError { inner: Arc::new(inner.map(|_| msg).context(kind)), vendor_code: self.vendor_code }
     }
 
     pub fn map<D>(self, kind: IndyErrorKind, msg: D) -> IndyError
         where D: fmt::Display + fmt::Debug + Send + Sync + 'static {
         let inner = Arc::try_unwrap(self.inner).unwrap();
-        IndyError { inner: Arc::new(inner.map(|_| msg).context(kind)) }
+        IndyError { inner: Arc::new(inner.map(|_| msg).context(kind)), vendor_code: self.vendor_code }
+    }
+
+    pub fn with_vendor_code(self, vendor_code: i32) -> IndyError {
+        IndyError { vendor_code: Some(vendor_code), ..self }
+    }
+
+    pub fn vendor_code(&self) -> Option<i32> {
+        self.vendor_code
+    }
+
+    // Messages of the error chain from the outermost context to the root cause
+    pub fn context_chain(&self) -> Vec<String> {
+        <dyn Fail>::iter_chain(self.inner.as_ref())
+            .map(|cause| cause.to_string())
+            .collect()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "code": ErrorCode::from(self.kind()) as i32,
+            "message": self.to_string(),
+            "backtrace": self.backtrace().map(|bt| bt.to_string()),
+            "extra": {
+                "context": self.context_chain(),
+                "vendor_code": self.vendor_code,
+            }
+        })
     }
 }
 
@@ -181,13 +214,13 @@ pub fn err_msg<D>(kind: IndyErrorKind, msg: D) -> IndyError
 
 impl From<IndyErrorKind> for IndyError {
     fn from(kind: IndyErrorKind) -> IndyError {
-        IndyError { inner: Arc::new(Context::new(kind)) }
+        IndyError { inner: Arc::new(Context::new(kind)), vendor_code: None }
     }
 }
 
 impl From<Context<IndyErrorKind>> for IndyError {
     fn from(inner: Context<IndyErrorKind>) -> IndyError {
-        IndyError { inner: Arc::new(inner) }
+        IndyError { inner: Arc::new(inner), vendor_code: None }
     }
 }
 
@@ -329,6 +362,8 @@ impl From<IndyErrorKind> for ErrorCode {
                     _ => ErrorCode::CommonInvalidState
                 },
             IndyErrorKind::IOError => ErrorCode::CommonIOError,
+            IndyErrorKind::Busy => ErrorCode::CommonBusy,
+            IndyErrorKind::Cancelled => ErrorCode::CommonCancelled,
             IndyErrorKind::MasterSecretDuplicateName => ErrorCode::AnoncredsMasterSecretDuplicateNameError,
             IndyErrorKind::ProofRejected => ErrorCode::AnoncredsProofRejected,
             IndyErrorKind::RevocationRegistryFull => ErrorCode::AnoncredsRevocationRegistryFullError,
@@ -382,7 +417,8 @@ impl From<ErrorCode> for IndyResult<()> {
 
 impl From<ErrorCode> for IndyError {
     fn from(err: ErrorCode) -> IndyError {
-        err_msg(err.into(), "Plugin returned error".to_string())
+        err_msg(err.into(), format!("Plugin returned error {}", err as i32))
+            .with_vendor_code(err as i32)
     }
 }
 
@@ -419,6 +455,8 @@ impl From<ErrorCode> for IndyErrorKind {
             ErrorCode::CommonInvalidParam26 => IndyErrorKind::InvalidParam(26),
             ErrorCode::CommonInvalidParam27 => IndyErrorKind::InvalidParam(27),
             ErrorCode::CommonIOError => IndyErrorKind::IOError,
+            ErrorCode::CommonBusy => IndyErrorKind::Busy,
+            ErrorCode::CommonCancelled => IndyErrorKind::Cancelled,
             ErrorCode::AnoncredsMasterSecretDuplicateNameError => IndyErrorKind::MasterSecretDuplicateName,
             ErrorCode::AnoncredsProofRejected => IndyErrorKind::ProofRejected,
             ErrorCode::AnoncredsRevocationRegistryFullError => IndyErrorKind::RevocationRegistryFull,
@@ -493,10 +531,7 @@ thread_local! {
 
 pub fn set_current_error(err: &IndyError) {
     CURRENT_ERROR_C_JSON.try_with(|error| {
-        let error_json = json!({
-            "message": err.to_string(),
-            "backtrace": err.backtrace().map(|bt| bt.to_string())
-        }).to_string();
+        let error_json = err.to_json().to_string();
         error.replace(Some(string_to_cstring(error_json)));
     })
         .map_err(|err| error!("Thread local variable access failed with: {:?}", err)).ok();
@@ -144,6 +144,12 @@ pub enum ErrorCode
     // Caller passed invalid value as param 27 (null, invalid json and etc..)
     CommonInvalidParam27 = 129,
 
+    // Command queue of the library is full. The call can be retried later
+    CommonBusy = 130,
+
+    // Command was cancelled with indy_cancel_command or its timeout expired
+    CommonCancelled = 131,
+
     // Wallet errors
     // Caller passed invalid wallet handle
     WalletInvalidHandle = 200,
@@ -586,4 +592,128 @@ pub mod wallet {
     pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                           search_handle: i32) -> ErrorCode;
 
-}
\ No newline at end of file
+    /// Apply a batch of record operations atomically (in a single storage transaction)
+    /// Either all operations are applied or storage is left unchanged.
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    /// operations_json: json array of operations applied in the given order:
+    ///   [
+    ///     {"op": "add", "type": <base64 type>, "id": <base64 id>, "value": <base64 value>, "tags": <tags json>},
+    ///     {"op": "update", "type": <base64 type>, "id": <base64 id>, "value": <base64 value>},
+    ///     {"op": "update_tags", "type": <base64 type>, "id": <base64 id>, "tags": <tags json>},
+    ///     {"op": "delete", "type": <base64 type>, "id": <base64 id>},
+    ///   ]
+    ///   tags json has the same format as in add_record handler
+    ///
+    /// #Returns
+    /// Error code of the first failed operation (WalletItemNotFound, WalletItemAlreadyExists, ...)
+    pub type WalletBatchRecords = extern fn(storage_handle: StorageHandle,
+                                            operations_json: *const c_char) -> ErrorCode;
+
+    /// Get the next chunk of wallet storage records retrieved by this wallet search.
+    /// Replaces fetch_search_next_record and get_record_* calls for every record with a single call.
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    /// search_handle: wallet search handle (See search_records handler)
+    /// count: max count of records to return
+    ///
+    /// #Returns
+    /// records_json_p: json array of records:
+    ///   [{
+    ///     "id": <base64 id>,
+    ///     "type": <base64 type>, // present only if retrieveType set to true
+    ///     "value": <base64 value>, // present only if retrieveValue set to true
+    ///     "tags": <tags json>, // present only if retrieveTags set to true
+    ///   }]
+    ///   tags json has the same format as in add_record handler
+    ///   Note if no more records empty array will be returned
+    /// records_handle_p: handle of records json (See free_search_records handler)
+    pub type WalletFetchSearchNextRecords = extern fn(storage_handle: StorageHandle,
+                                                      search_handle: i32,
+                                                      count: usize,
+                                                      records_json_p: *mut *const c_char,
+                                                      records_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free records json returned by fetch_search_next_records handler
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    /// records_handle: records handle (See fetch_search_next_records handler)
+    pub type WalletFreeSearchRecords = extern fn(storage_handle: StorageHandle,
+                                                 records_handle: IndyHandle) -> ErrorCode;
+
+    /// Wrap wallet export key with the transit key of external KMS
+    ///
+    /// #Params
+    /// key_id: identifier of the transit key in KMS
+    /// key: base64 value of wallet export key
+    /// wrapped_key_p: pointer to store wrapped key (For example, "vault:v1:...")
+    /// wrapped_key_handle_p: pointer to store handle of wrapped key (See free_key handler)
+    pub type WalletKeyWrap = extern fn(key_id: *const c_char,
+                                       key: *const c_char,
+                                       wrapped_key_p: *mut *const c_char,
+                                       wrapped_key_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Unwrap wallet export key with the transit key of external KMS
+    ///
+    /// #Params
+    /// key_id: identifier of the transit key in KMS
+    /// wrapped_key: wrapped key returned by wrap_key handler
+    /// key_p: pointer to store base64 value of wallet export key
+    /// key_handle_p: pointer to store handle of key (See free_key handler)
+    pub type WalletKeyUnwrap = extern fn(key_id: *const c_char,
+                                         wrapped_key: *const c_char,
+                                         key_p: *mut *const c_char,
+                                         key_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free key returned by wrap_key or unwrap_key handler (make key handle invalid)
+    ///
+    /// #Params
+    /// key_handle: key handle (See wrap_key and unwrap_key handlers)
+    pub type WalletKeyFree = extern fn(key_handle: IndyHandle) -> ErrorCode;
+
+}
+
+pub mod crypto {
+    use super::*;
+    use libc::c_char;
+
+    /// Create a key in external KMS (PKCS#11 HSM or mobile secure enclave for example).
+    /// Private key must never leave KMS.
+    ///
+    /// #Params
+    /// crypto_type: crypto type of the key: "ed25519" or "secp256k1"
+    /// key_id_p: pointer to store identifier of the private key in KMS
+    /// verkey_p: pointer to store base58 encoded public key
+    /// key_handle_p: pointer to store handle of returned key_id and verkey (See free handler)
+    pub type KeyManagementCreateKey = extern fn(crypto_type: *const c_char,
+                                                key_id_p: *mut *const c_char,
+                                                verkey_p: *mut *const c_char,
+                                                key_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Sign a message with the private key stored in external KMS
+    ///
+    /// #Params
+    /// key_id: identifier of the private key returned by create_key handler
+    /// crypto_type: crypto type of the key: "ed25519" or "secp256k1"
+    /// message_raw: a pointer to first byte of message to be signed
+    /// message_len: a message length
+    /// signature_raw_p: pointer to store a pointer to first byte of signature
+    /// signature_len_p: pointer to store a signature length
+    /// signature_handle_p: pointer to store handle of signature (See free handler)
+    pub type KeyManagementSign = extern fn(key_id: *const c_char,
+                                           crypto_type: *const c_char,
+                                           message_raw: *const u8,
+                                           message_len: u32,
+                                           signature_raw_p: *mut *const u8,
+                                           signature_len_p: *mut u32,
+                                           signature_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free data returned by create_key or sign handler (make handle invalid)
+    ///
+    /// #Params
+    /// handle: handle returned by create_key or sign handler
+    pub type KeyManagementFree = extern fn(handle: IndyHandle) -> ErrorCode;
+}
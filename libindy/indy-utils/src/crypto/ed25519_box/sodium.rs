@@ -1,6 +1,7 @@
 extern crate sodiumoxide;
 
 use indy_api_types::errors::prelude::*;
+use libc::c_int;
 use self::sodiumoxide::crypto::box_;
 
 
@@ -12,6 +13,17 @@ sodium_type!(Nonce, box_::Nonce, NONCEBYTES);
 sodium_type!(PublicKey, box_::PublicKey, PUBLICKEYBYTES);
 sodium_type!(SecretKey, box_::SecretKey, SECRETKEYBYTES);
 
+pub const SHAREDSECRETBYTES: usize = 32;
+
+extern {
+    // this function isn't included to sodiumoxide 0.0.16 rust wrappers as a fallible call,
+    // local binding is used to get libsodium result code for low order points
+    fn crypto_scalarmult_curve25519(
+        q: *mut [u8; SHAREDSECRETBYTES],
+        n: *const [u8; SECRETKEYBYTES],
+        p: *const [u8; PUBLICKEYBYTES]) -> c_int;
+}
+
 pub fn encrypt(secret_key: &SecretKey, public_key: &PublicKey, doc: &[u8], nonce: &Nonce) -> Result<Vec<u8>, IndyError> {
     Ok(box_::seal(
         doc,
@@ -35,6 +47,20 @@ pub fn gen_nonce() -> Nonce {
     Nonce(box_::gen_nonce())
 }
 
+pub fn diffie_hellman(secret_key: &SecretKey, public_key: &PublicKey) -> Result<Vec<u8>, IndyError> {
+    let mut shared: [u8; SHAREDSECRETBYTES] = [0; SHAREDSECRETBYTES];
+
+    let res = unsafe {
+        crypto_scalarmult_curve25519(&mut shared, &(secret_key.0).0, &(public_key.0).0)
+    };
+
+    if res != 0 || shared.iter().fold(0u8, |acc, b| acc | b) == 0 {
+        return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "X25519 key agreement with low order public key"));
+    }
+
+    Ok(shared.to_vec())
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -66,4 +92,25 @@ mod tests {
         assert!(alice_decrypted_text.is_ok());
         assert_eq!(text, alice_decrypted_text.unwrap());
     }
+
+    #[test]
+    fn diffie_hellman_works() {
+        let (alice_ver_key, alice_sign_key) = ed25519_sign::create_key_pair_for_signature(None).unwrap();
+        let (bob_ver_key, bob_sign_key) = ed25519_sign::create_key_pair_for_signature(None).unwrap();
+
+        let alice_shared = diffie_hellman(&ed25519_sign::sk_to_curve25519(&alice_sign_key).unwrap(),
+                                          &ed25519_sign::vk_to_curve25519(&bob_ver_key).unwrap()).unwrap();
+        let bob_shared = diffie_hellman(&ed25519_sign::sk_to_curve25519(&bob_sign_key).unwrap(),
+                                        &ed25519_sign::vk_to_curve25519(&alice_ver_key).unwrap()).unwrap();
+
+        assert_eq!(alice_shared, bob_shared);
+    }
+
+    #[test]
+    fn diffie_hellman_rejects_low_order_point() {
+        let (_, sign_key) = ed25519_sign::create_key_pair_for_signature(None).unwrap();
+        let zero = PublicKey::from_slice(&[0; PUBLICKEYBYTES]).unwrap();
+
+        assert!(diffie_hellman(&ed25519_sign::sk_to_curve25519(&sign_key).unwrap(), &zero).is_err());
+    }
 }
@@ -33,12 +33,12 @@ macro_rules! check_useful_c_str {
         let $x = match ctypes::c_str_to_string($x) {
             Ok(Some(val)) => val.to_string(),
             _ => {
-                return err_msg($e.into(), "Invalid pointer has been passed").into()
+                return err_msg($e.into(), format!("Invalid pointer has been passed for `{}`", stringify!($x))).into()
             }
         };
 
         if $x.is_empty() {
-            return err_msg($e.into(), "Empty string has been passed").into()
+            return err_msg($e.into(), format!("Empty string has been passed for `{}`", stringify!($x))).into()
         }
     }
 }
@@ -50,14 +50,14 @@ macro_rules! check_useful_opt_json {
             Ok(Some(val)) => Some(val),
             Ok(None) => None,
             _ => {
-                return err_msg($e.into(), "Invalid pointer has been passed").into()
+                return err_msg($e.into(), format!("Invalid pointer has been passed for `{}`", stringify!($x))).into()
             },
         };
 
         let $x: Option<$t>  = match $x {
-            Some(val) => {
-                parse_json!(val, $e, $t);
-                Some(val)
+            Some($x) => {
+                parse_json!($x, $e, $t);
+                Some($x)
             },
             None => None
         };
@@ -70,7 +70,7 @@ macro_rules! check_useful_json {
         let $x = match ctypes::c_str_to_string($x) {
             Ok(Some(val)) => val,
             _ => {
-                return err_msg($e.into(), "Invalid pointer has been passed").into()
+                return err_msg($e.into(), format!("Invalid pointer has been passed for `{}`", stringify!($x))).into()
             },
         };
 
@@ -86,7 +86,7 @@ macro_rules! check_useful_validatable_json {
         match $x.validate() {
             Ok(ok) => ok,
             Err(err) => {
-                return err_msg(IndyErrorKind::InvalidStructure, err).into()
+                return err_msg(IndyErrorKind::InvalidStructure, format!("Invalid `{}`: {}", stringify!($x), err)).into()
             }
         };
     }
@@ -99,20 +99,20 @@ macro_rules! check_useful_opt_validatable_json {
             Ok(Some(val)) => Some(val),
             Ok(None) => None,
             _ => {
-                return err_msg($e.into(), "Invalid pointer has been passed").into()
+                return err_msg($e.into(), format!("Invalid pointer has been passed for `{}`", stringify!($x))).into()
             },
         };
 
         let $x: Option<$t>  = match $x {
-            Some(val) => {
-                parse_json!(val, $e, $t);
-                match val.validate() {
+            Some($x) => {
+                parse_json!($x, $e, $t);
+                match $x.validate() {
                     Ok(ok) => ok,
                     Err(err) => {
-                        return err_msg($e.into(), err).into()
+                        return err_msg($e.into(), format!("Invalid `{}`: {}", stringify!($x), err)).into()
                     }
                 };
-                Some(val)
+                Some($x)
             },
             None => None
         };
@@ -129,7 +129,7 @@ macro_rules! check_useful_validatable_string {
         match $x.validate() {
             Ok(ok) => ok,
             Err(err) => {
-                return err_msg(IndyErrorKind::InvalidStructure, err).into()
+                return err_msg(IndyErrorKind::InvalidStructure, format!("Invalid `{}`: {}", stringify!($x), err)).into()
             }
         };
     }
@@ -147,7 +147,7 @@ macro_rules! check_useful_validatable_opt_string {
                 match $x.validate() {
                     Ok(ok) => ok,
                     Err(err) => {
-                        return err_msg(IndyErrorKind::InvalidStructure, err).into()
+                        return err_msg(IndyErrorKind::InvalidStructure, format!("Invalid `{}`: {}", stringify!($x), err)).into()
                     }
                 };
                 Some($x)
@@ -161,11 +161,11 @@ macro_rules! check_useful_validatable_opt_string {
 macro_rules! parse_json {
     ($x:ident, $e:expr, $t:ty) => {
         if $x.is_empty() {
-           return err_msg($e.into(), "Empty string has been passed").into()
+           return err_msg($e.into(), format!("Empty string has been passed for `{}`", stringify!($x))).into()
         }
 
         let r = serde_json::from_str::<$t>($x)
-                    .to_indy(indy_api_types::errors::IndyErrorKind::InvalidStructure, format!("Invalid {} json has been passed", stringify!($t)));
+                    .to_indy(indy_api_types::errors::IndyErrorKind::InvalidStructure, format!("Invalid {} json has been passed for `{}`", stringify!($t), stringify!($x)));
 
         let $x: $t = match r {
             Ok(ok) => ok,
@@ -182,7 +182,7 @@ macro_rules! check_useful_c_str_empty_accepted {
         let $x = match ctypes::c_str_to_string($x) {
             Ok(Some(val)) => val.to_string(),
             _ => {
-                return err_msg($e.into(), "Invalid pointer has been passed").into()
+                return err_msg($e.into(), format!("Invalid pointer has been passed for `{}`", stringify!($x))).into()
             }
         };
     }
@@ -195,7 +195,7 @@ macro_rules! check_useful_opt_c_str {
         let $x = match ctypes::c_str_to_string($x) {
             Ok(opt_val) => opt_val.map(String::from),
             Err(_) => {
-                return err_msg($e.into(), "Invalid pointer has been passed").into()
+                return err_msg($e.into(), format!("Invalid pointer has been passed for `{}`", stringify!($x))).into()
             }
         };
     }
@@ -206,11 +206,11 @@ macro_rules! check_useful_opt_c_str {
 macro_rules! check_useful_c_byte_array {
     ($ptr:ident, $len:expr, $err1:expr, $err2:expr) => {
         if $ptr.is_null() {
-            return err_msg($err1.into(), "Invalid pointer has been passed").into();
+            return err_msg($err1.into(), format!("Invalid pointer has been passed for `{}`", stringify!($ptr))).into();
         }
 
         if $len <= 0 {
-            return err_msg($err2.into(), "Array length must be greater than 0").into();
+            return err_msg($err2.into(), format!("Array length must be greater than 0 for `{}`", stringify!($ptr))).into();
         }
 
         let $ptr = unsafe { ::std::slice::from_raw_parts($ptr, $len as usize) };
@@ -244,7 +244,7 @@ macro_rules! check_useful_opt_u64 {
         } else if $x == -1 {
             None
         } else {
-            return err_msg($e.into(), "Invalid integer has been passed (should be non-negative or -1").into()
+            return err_msg($e.into(), format!("Invalid integer has been passed for `{}` (should be non-negative or -1)", stringify!($x))).into()
         };
     }
 }
@@ -255,7 +255,7 @@ macro_rules! check_u32_less_or_eq {
         let $x: u32 = if $x <= $lim {
             $x
         } else {
-            return err_msg($e.into(), format!("Invalid integer has been passed (should be non-negative and less or equal to {}", $lim)).into()
+            return err_msg($e.into(), format!("Invalid integer has been passed for `{}` (should be non-negative and less or equal to {})", stringify!($x), $lim)).into()
         };
     }
 }
@@ -166,6 +166,21 @@ impl string::ToString for Query {
     }
 }
 
+// Matches the value against `$like` pattern with SQL LIKE semantic as in wallet search:
+// '%' matches any sequence, '_' matches any single character.
+pub fn like(pattern: &str, value: &str) -> bool {
+    _like(pattern.as_bytes(), value.as_bytes())
+}
+
+fn _like(pattern: &[u8], value: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((b'%', rest)) => (0..=value.len()).any(|i| _like(rest, &value[i..])),
+        Some((b'_', rest)) => !value.is_empty() && _like(rest, &value[1..]),
+        Some((c, rest)) => value.first() == Some(c) && _like(rest, &value[1..]),
+    }
+}
+
 fn parse_query(map: serde_json::Map<String, serde_json::Value>) -> Result<Query, &'static str> {
     let mut operators: Vec<Query> = Vec::new();
 
@@ -419,6 +434,17 @@ mod tests {
         assert_eq!(query, expected);
     }
 
+    #[test]
+    fn test_like_works() {
+        assert!(like("Al%", "Alice"));
+        assert!(like("%ice", "Alice"));
+        assert!(like("A_ice", "Alice"));
+        assert!(like("%", ""));
+        assert!(!like("Al_", "Alice"));
+        assert!(!like("Bob%", "Alice"));
+        assert!(!like("_", ""));
+    }
+
     #[test]
     fn test_simple_operator_in_plaintext_parse() {
         let name1 = _random_string(10);
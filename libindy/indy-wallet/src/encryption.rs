@@ -206,6 +206,10 @@ pub(super) fn decrypt_storage_record(record: &StorageRecord, keys: &Keys) -> Ind
     Ok(WalletRecord::new(decrypted_name, decrypted_type, decrypted_value, decrypted_tags))
 }
 
+// Name of each record is encrypted, so it identifies keys the record is encrypted with
+pub(super) fn is_encrypted_with(record: &StorageRecord, keys: &Keys) -> bool {
+    decrypt_merged(&record.id, &keys.name_key).is_ok()
+}
 
 #[cfg(test)]
 mod tests {
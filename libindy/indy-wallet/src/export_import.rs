@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -5,16 +6,23 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use rmp_serde;
 
-use indy_api_types::domain::wallet::Record;
+use indy_api_types::domain::wallet::{ConflictPolicy, ExportFilter, Record, Tags};
 use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
 use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
 use indy_utils::crypto::hash::{hash, HASHBYTES};
+use rust_base58::ToBase58;
 
-use super::{Wallet, WalletRecord};
+use crate::key_wrapper::KeyWrapper;
+use crate::iterator::WalletIterator;
+use super::{RecordOptions, SearchOptions, Wallet, WalletRecord};
 
 const CHUNK_SIZE: usize = 1024;
+const RECORDS_PER_CHUNK: usize = 100;
+
+const IMPORT_PROGRESS_TYPE: &str = "Indy::ImportProgress";
+const IMPORT_PROGRESS_ID: &str = "progress";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EncryptionMethod {
@@ -24,7 +32,7 @@ pub enum EncryptionMethod {
         salt: Vec<u8>,
         // chacha20poly1305_ietf::Nonce as bytes. Random start nonce. We increment nonce for each chunk to be sure in export file consistency
         nonce: Vec<u8>,
-        // size of encrypted chunk
+        // size of encrypted chunk: in bytes for version 0, in records for version 1
         chunk_size: usize,
     },
     // **ChaCha20-Poly1305-IETF interactive key derivation** cypher in blocks per chunk_size bytes
@@ -33,18 +41,39 @@ pub enum EncryptionMethod {
         salt: Vec<u8>,
         // chacha20poly1305_ietf::Nonce as bytes. Random start nonce. We increment nonce for each chunk to be sure in export file consistency
         nonce: Vec<u8>,
-        // size of encrypted chunk
+        // size of encrypted chunk: in bytes for version 0, in records for version 1
         chunk_size: usize,
     },
     // **ChaCha20-Poly1305-IETF raw key** cypher in blocks per chunk_size bytes
     ChaCha20Poly1305IETFRaw {
         // chacha20poly1305_ietf::Nonce as bytes. Random start nonce. We increment nonce for each chunk to be sure in export file consistency
         nonce: Vec<u8>,
-        // size of encrypted chunk
+        // size of encrypted chunk: in bytes for version 0, in records for version 1
+        chunk_size: usize,
+    },
+    // **ChaCha20-Poly1305-IETF sealed key** cypher in blocks per chunk_size bytes
+    ChaCha20Poly1305IETFSealed {
+        // Name of key wrapper used to wrap random export key
+        key_wrapper: String,
+        // Identifier of the transit key in KMS
+        key_id: String,
+        // Export key wrapped with the transit key
+        wrapped_key: String,
+        // chacha20poly1305_ietf::Nonce as bytes. Random start nonce. We increment nonce for each chunk to be sure in export file consistency
+        nonce: Vec<u8>,
+        // size of encrypted chunk: in bytes for version 0, in records for version 1
         chunk_size: usize,
     },
 }
 
+// Export key wrapped with the transit key of external KMS
+#[derive(Debug)]
+pub struct SealedKey {
+    pub key_wrapper: String,
+    pub key_id: String,
+    pub wrapped_key: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Header {
     // Method of encryption for encrypted stream
@@ -52,7 +81,24 @@ pub struct Header {
     // Export time in seconds from UNIX Epoch
     pub time: u64,
     // Version of header
-    pub version: u32
+    pub version: u32,
+    // Number of sealed chunks following the header. Used since version 1
+    #[serde(default)]
+    pub chunk_count: u64,
+    // Number of exported records. Used since version 1
+    #[serde(default)]
+    pub record_count: u64,
+}
+
+// Import progress kept in the imported wallet until all chunks are imported
+#[derive(Debug, Serialize, Deserialize)]
+struct ImportProgress {
+    // Base58 hash of the header of export file being imported
+    header_hash: String,
+    // Number of imported chunks
+    chunks: u64,
+    // Number of imported records
+    records: u64,
 }
 
 // Note that we use externally tagged enum serialization and header will be represented as:
@@ -67,24 +113,61 @@ pub struct Header {
 //   },
 //   "time": ..,
 //   "version": ..,
+//   "chunk_count": ..,
+//   "record_count": ..,
 // }
+//
+// Version 0 export file is the plain header followed by single chacha20poly1305_ietf stream
+// encrypted in chunks of chunk_size bytes.
+//
+// Version 1 export file is the plain header followed by chunk_count chunks. Every chunk contains
+// up to chunk_size records and is sealed separately with the incremented nonce. Header hash and chunk index
+// are used as associated data, so chunks can't be reordered or moved to another export file.
+// Chunks are verified and imported one by one, so interrupted import can be resumed.
 
-pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, sealed_key: Option<SealedKey>, filter: Option<&ExportFilter>) -> IndyResult<()> {
     let nonce = chacha20poly1305_ietf::gen_nonce();
-    let chunk_size = CHUNK_SIZE;
 
-    let encryption_method = match key_data {
-        KeyDerivationData::Argon2iMod(_, salt) => EncryptionMethod::ChaCha20Poly1305IETF {
+    let (chunk_size, chunk_count, record_count) = match version {
+        0 => (CHUNK_SIZE, 0, 0),
+        1 => {
+            let record_count = match filter {
+                Some(_) => {
+                    let mut records = ExportedRecords::new(wallet, filter)?;
+                    let mut count = 0;
+                    while records.next()?.is_some() {
+                        count += 1;
+                    }
+                    count
+                }
+                None => wallet.count_all()?
+            };
+            // Export always contains at least one chunk, so key and header are verified even for empty wallet
+            let chunk_count = ::std::cmp::max(1, (record_count + RECORDS_PER_CHUNK - 1) / RECORDS_PER_CHUNK);
+            (RECORDS_PER_CHUNK, chunk_count as u64, record_count as u64)
+        }
+        _ => return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"))
+    };
+
+    let encryption_method = match (key_data, sealed_key) {
+        (_, Some(SealedKey { key_wrapper, key_id, wrapped_key })) => EncryptionMethod::ChaCha20Poly1305IETFSealed {
+            key_wrapper,
+            key_id,
+            wrapped_key,
+            nonce: nonce[..].to_vec(),
+            chunk_size,
+        },
+        (KeyDerivationData::Argon2iMod(_, salt), None) => EncryptionMethod::ChaCha20Poly1305IETF {
             salt: salt[..].to_vec(),
             nonce: nonce[..].to_vec(),
             chunk_size,
         },
-        KeyDerivationData::Argon2iInt(_, salt) => EncryptionMethod::ChaCha20Poly1305IETFInteractive {
+        (KeyDerivationData::Argon2iInt(_, salt), None) => EncryptionMethod::ChaCha20Poly1305IETFInteractive {
             salt: salt[..].to_vec(),
             nonce: nonce[..].to_vec(),
             chunk_size,
         },
-        KeyDerivationData::Raw(_) => EncryptionMethod::ChaCha20Poly1305IETFRaw {
+        (KeyDerivationData::Raw(_), None) => EncryptionMethod::ChaCha20Poly1305IETFRaw {
             nonce: nonce[..].to_vec(),
             chunk_size,
         }
@@ -93,7 +176,9 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
     let header = Header {
         encryption_method,
         time: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
-        version
+        version,
+        chunk_count,
+        record_count,
     };
 
     let header = rmp_serde::to_vec(&header)
@@ -104,44 +189,161 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
     writer.write_u32::<LittleEndian>(header.len() as u32)?;
     writer.write_all(&header)?;
 
+    match version {
+        0 => _export_stream(ExportedRecords::new(wallet, filter)?, writer, key, nonce, chunk_size, &header),
+        _ => _export_chunks(ExportedRecords::new(wallet, filter)?, writer, key, nonce, chunk_size, chunk_count, &header)
+    }
+}
+
+// Iterates over all wallet records or only over records matching export filter
+struct ExportedRecords<'a> {
+    wallet: &'a Wallet,
+    filter: Option<&'a ExportFilter>,
+    next_type: usize,
+    records: Option<WalletIterator>,
+}
+
+impl<'a> ExportedRecords<'a> {
+    fn new(wallet: &'a Wallet, filter: Option<&'a ExportFilter>) -> IndyResult<ExportedRecords<'a>> {
+        let records = match filter {
+            Some(_) => None,
+            None => Some(wallet.get_all()?)
+        };
+
+        Ok(ExportedRecords { wallet, filter, next_type: 0, records })
+    }
+
+    fn next(&mut self) -> IndyResult<Option<WalletRecord>> {
+        loop {
+            if let Some(ref mut records) = self.records {
+                if let Some(record) = records.next()? {
+                    return Ok(Some(record));
+                }
+            }
+
+            let filter = match self.filter {
+                Some(filter) => filter,
+                None => return Ok(None)
+            };
+
+            let type_ = match filter.types.get(self.next_type) {
+                Some(type_) => type_,
+                None => return Ok(None)
+            };
+
+            self.next_type += 1;
+
+            let query = filter.query.as_ref()
+                .map(|query| query.to_string())
+                .unwrap_or_else(|| "{}".to_string());
+
+            self.records = Some(self.wallet.search(type_, &query, Some(&SearchOptions::full()))?);
+        }
+    }
+}
+
+fn _export_stream<W: Write>(mut records: ExportedRecords, writer: W, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header: &[u8]) -> IndyResult<()> {
     // Write ecnrypted
     let mut writer = chacha20poly1305_ietf::Writer::new(writer,
                                                         key,
                                                         nonce,
                                                         chunk_size);
 
-    writer.write_all(&hash(&header)?)?;
+    writer.write_all(&hash(header)?)?;
 
-    let mut records = wallet.get_all()?;
+    while let Some(record) = records.next()? {
+        _write_record(&mut writer, record)?;
+    }
 
-    while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
-        let record = Record {
-            type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
-            id,
-            value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
-            tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
-        };
+    writer.write_u32::<LittleEndian>(0)?; // END message
+    writer.flush()?;
+    Ok(())
+}
+
+fn _export_chunks<W: Write>(mut records: ExportedRecords, mut writer: W, key: chacha20poly1305_ietf::Key, mut nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, chunk_count: u64, header: &[u8]) -> IndyResult<()> {
+    let header_hash = hash(header)?;
 
-        let record = rmp_serde::to_vec(&record)
-            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+    let mut written_chunks = 0;
 
-        writer.write_u32::<LittleEndian>(record.len() as u32)?;
-        writer.write_all(&record)?;
+    loop {
+        let mut chunk: Vec<u8> = Vec::new();
+        let mut chunk_records = 0;
+
+        while chunk_records < chunk_size {
+            match records.next()? {
+                Some(record) => _write_record(&mut chunk, record)?,
+                None => break
+            }
+            chunk_records += 1;
+        }
+
+        if chunk_records == 0 && written_chunks > 0 {
+            break;
+        }
+
+        if written_chunks == chunk_count {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Wallet has been modified during export"));
+        }
+
+        let chunk = chacha20poly1305_ietf::encrypt_with_ad(&chunk, &_chunk_ad(&header_hash, written_chunks), &key, &nonce);
+
+        writer.write_u32::<LittleEndian>(chunk.len() as u32)?;
+        writer.write_all(&chunk)?;
+
+        nonce.increment();
+        written_chunks += 1;
+
+        if chunk_records < chunk_size {
+            break;
+        }
+    }
+
+    if written_chunks != chunk_count {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Wallet has been modified during export"));
     }
 
-    writer.write_u32::<LittleEndian>(0)?; // END message
     writer.flush()?;
     Ok(())
 }
 
+fn _write_record<W: Write>(writer: &mut W, record: WalletRecord) -> IndyResult<()> {
+    let WalletRecord { type_, id, value, tags } = record;
+
+    let record = Record {
+        type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
+        id,
+        value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
+        tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
+    };
+
+    let record = rmp_serde::to_vec(&record)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+
+    writer.write_u32::<LittleEndian>(record.len() as u32)?;
+    writer.write_all(&record)?;
+    Ok(())
+}
+
+// Associated data binds the sealed chunk to its position in the particular export file
+fn _chunk_ad(header_hash: &[u8], chunk_index: u64) -> Vec<u8> {
+    let mut ad = header_hash.to_vec();
+    ad.extend_from_slice(&chunk_index.to_le_bytes());
+    ad
+}
+
 #[cfg(test)]
 fn import<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> where T: Read {
-    let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase)?;
+    import_with_key_wrappers(wallet, reader, passphrase, &HashMap::new())
+}
+
+#[cfg(test)]
+fn import_with_key_wrappers<T>(wallet: &Wallet, reader: T, passphrase: &str, key_wrappers: &HashMap<String, Box<dyn KeyWrapper>>) -> IndyResult<()> where T: Read {
+    let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase, key_wrappers)?;
     let import_key = import_key_derivation_data.calc_master_key()?;
-    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes)
+    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes, false)
 }
 
-pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyResult<(BufReader<T>, KeyDerivationData, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)> where T: Read {
+pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str, key_wrappers: &HashMap<String, Box<dyn KeyWrapper>>) -> IndyResult<(BufReader<T>, KeyDerivationData, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)> where T: Read {
     // Reads plain
     let mut reader = BufReader::new(reader);
 
@@ -157,14 +359,14 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     let header: Header = rmp_serde::from_slice(&header_bytes)
         .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
 
-    if header.version != 0 {
+    if header.version > 1 {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported version"));
     }
 
     let key_derivation_method = match header.encryption_method {
         EncryptionMethod::ChaCha20Poly1305IETF { .. } => KeyDerivationMethod::ARGON2I_MOD,
         EncryptionMethod::ChaCha20Poly1305IETFInteractive { .. } => KeyDerivationMethod::ARGON2I_INT,
-        EncryptionMethod::ChaCha20Poly1305IETFRaw { .. } => KeyDerivationMethod::RAW,
+        EncryptionMethod::ChaCha20Poly1305IETFRaw { .. } | EncryptionMethod::ChaCha20Poly1305IETFSealed { .. } => KeyDerivationMethod::RAW,
     };
 
     let (import_key_derivation_data, nonce, chunk_size) = match header.encryption_method {
@@ -193,6 +395,20 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
 
             let key_data = KeyDerivationData::Raw(passphrase.to_owned());
 
+            (key_data, nonce, chunk_size)
+        }
+        EncryptionMethod::ChaCha20Poly1305IETFSealed { key_wrapper, key_id, wrapped_key, nonce, chunk_size } => {
+            let nonce = chacha20poly1305_ietf::Nonce::from_slice(&nonce)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid nonce")?;
+
+            let key = key_wrappers
+                .get(&key_wrapper)
+                .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Unknown wallet key wrapper: {}", key_wrapper)))?
+                .unwrap(&key_id, &wrapped_key)?;
+
+            // Unwrapped export key is used as raw key, so no derivation is needed
+            let key_data = KeyDerivationData::Raw(key.to_base58());
+
             (key_data, nonce, chunk_size)
         }
     };
@@ -200,36 +416,275 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     Ok((reader, import_key_derivation_data, nonce, chunk_size, header_bytes))
 }
 
-pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>) -> IndyResult<()> where T: Read {
+// Only chunked exports can be imported with resumption
+pub(super) fn is_resumable(header_bytes: &[u8]) -> IndyResult<bool> {
+    let header: Header = rmp_serde::from_slice(header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
+
+    Ok(header.version >= 1)
+}
+
+pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>, resume: bool) -> IndyResult<()> where T: Read {
+    let header: Header = rmp_serde::from_slice(&header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
+
+    match header.version {
+        0 if resume => Err(err_msg(IndyErrorKind::InvalidStructure, "Import of version 0 export file can't be resumed")),
+        0 => _import_stream(wallet, reader, key, nonce, chunk_size, &header_bytes),
+        _ => _import_chunks(wallet, reader, key, nonce, &header, &header_bytes, resume)
+    }
+}
+
+fn _import_stream<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: &[u8]) -> IndyResult<()> where T: Read {
+    _for_each_stream_record(reader, key, nonce, chunk_size, header_bytes, |record| {
+        wallet.add(&record.type_, &record.id, &record.value, &record.tags)
+    })
+}
+
+fn _for_each_stream_record<T, F>(reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: &[u8], mut f: F) -> IndyResult<()> where T: Read, F: FnMut(Record) -> IndyResult<()> {
     // Reads encrypted
     let mut reader = chacha20poly1305_ietf::Reader::new(reader, key, nonce, chunk_size);
 
     let mut header_hash = vec![0u8; HASHBYTES];
     reader.read_exact(&mut header_hash).map_err(_map_io_err)?;
 
-    if hash(&header_bytes)? != header_hash {
+    if hash(header_bytes)? != header_hash {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header hash"));
     }
 
-    loop {
-        let record_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
+    while let Some(record) = _read_record(&mut reader)? {
+        f(record)?;
+    }
 
-        if record_len == 0 {
-            break;
+    Ok(())
+}
+
+fn _import_chunks<T>(wallet: &Wallet, mut reader: BufReader<T>, key: chacha20poly1305_ietf::Key, mut nonce: chacha20poly1305_ietf::Nonce, header: &Header, header_bytes: &[u8], resume: bool) -> IndyResult<()> where T: Read {
+    let header_hash = hash(header_bytes)?;
+
+    let mut progress = if resume {
+        _get_import_progress(wallet, &header_hash)?
+    } else {
+        let progress = ImportProgress { header_hash: header_hash.to_base58(), chunks: 0, records: 0 };
+        wallet.add(IMPORT_PROGRESS_TYPE, IMPORT_PROGRESS_ID, &_serialize_import_progress(&progress)?, &Tags::new())?;
+        progress
+    };
+
+    // Records of the chunk interrupted on previous attempt may be already added
+    let interrupted_chunk = if resume { Some(progress.chunks) } else { None };
+
+    for chunk_index in 0..header.chunk_count {
+        let chunk = _read_sealed_chunk(&mut reader)?;
+
+        if chunk_index >= progress.chunks {
+            for record in _open_chunk(&chunk, &key, &nonce, &header_hash, chunk_index)? {
+                match wallet.add(&record.type_, &record.id, &record.value, &record.tags) {
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists && interrupted_chunk == Some(chunk_index) => {}
+                    res => res?
+                }
+
+                progress.records += 1;
+            }
+
+            progress.chunks += 1;
+            wallet.update(IMPORT_PROGRESS_TYPE, IMPORT_PROGRESS_ID, &_serialize_import_progress(&progress)?)?;
         }
 
-        let mut record = vec![0u8; record_len];
-        reader.read_exact(&mut record).map_err(_map_io_err)?;
+        nonce.increment();
+    }
+
+    if progress.records != header.record_count {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Export file contains unexpected number of records"));
+    }
+
+    _check_end(&mut reader)?;
+
+    wallet.delete(IMPORT_PROGRESS_TYPE, IMPORT_PROGRESS_ID)?;
+
+    Ok(())
+}
+
+fn _read_sealed_chunk<R: Read>(reader: &mut R) -> IndyResult<Vec<u8>> {
+    let chunk_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as u64;
 
-        let record: Record = rmp_serde::from_slice(&record)
-            .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
+    let mut chunk: Vec<u8> = Vec::new();
+    reader.by_ref().take(chunk_len).read_to_end(&mut chunk).map_err(_map_io_err)?;
 
-        wallet.add(&record.type_, &record.id, &record.value, &record.tags)?;
+    if chunk.len() as u64 != chunk_len {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid export file format"));
+    }
+
+    Ok(chunk)
+}
+
+fn _open_chunk(chunk: &[u8], key: &chacha20poly1305_ietf::Key, nonce: &chacha20poly1305_ietf::Nonce, header_hash: &[u8], chunk_index: u64) -> IndyResult<Vec<Record>> {
+    let chunk = chacha20poly1305_ietf::decrypt_with_ad(chunk, &_chunk_ad(header_hash, chunk_index), key, nonce)
+        .map_err(|err| err.map(IndyErrorKind::InvalidStructure, "Export file chunk is corrupted"))?;
+
+    let mut chunk_reader = chunk.as_slice();
+    let mut records = Vec::new();
+
+    while !chunk_reader.is_empty() {
+        let record = _read_record(&mut chunk_reader)?
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid export file format"))?;
+
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+fn _check_end<R: Read>(reader: &mut R) -> IndyResult<()> {
+    if reader.read(&mut [0u8; 1]).map_err(_map_io_err)? != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid export file format"));
     }
 
     Ok(())
 }
 
+// Number of records by the way they were merged into the wallet
+#[derive(Debug, Default, Serialize)]
+pub struct MergeResult {
+    pub added: usize,
+    pub skipped: usize,
+    pub overwritten: usize,
+}
+
+// Merges exported records into existing wallet. Export file is read twice: the first pass verifies
+// integrity of the whole file (and absence of conflicts for fail policy), so the wallet isn't changed
+// if verification fails. Both readers must be positioned right after the header (see `skip_header`).
+pub(super) fn merge_import<T>(wallet: &Wallet, verify_reader: BufReader<T>, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: &[u8], conflict_policy: ConflictPolicy) -> IndyResult<MergeResult> where T: Read {
+    _for_each_record(verify_reader, &key, &nonce, chunk_size, header_bytes, |record| {
+        if conflict_policy == ConflictPolicy::Fail && _record_exists(wallet, &record)? {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, format!("Wallet item already exists with type: {}, id: {}", record.type_, record.id)));
+        }
+
+        Ok(())
+    })?;
+
+    let mut result = MergeResult::default();
+
+    _for_each_record(reader, &key, &nonce, chunk_size, header_bytes, |record| {
+        if !_record_exists(wallet, &record)? {
+            wallet.add(&record.type_, &record.id, &record.value, &record.tags)?;
+            result.added += 1;
+            return Ok(());
+        }
+
+        match conflict_policy {
+            ConflictPolicy::Skip => result.skipped += 1,
+            ConflictPolicy::Overwrite => {
+                wallet.update(&record.type_, &record.id, &record.value)?;
+                wallet.update_tags(&record.type_, &record.id, &record.tags)?;
+                result.overwritten += 1;
+            }
+            ConflictPolicy::Fail =>
+                return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, format!("Wallet item already exists with type: {}, id: {}", record.type_, record.id)))
+        }
+
+        Ok(())
+    })?;
+
+    Ok(result)
+}
+
+// Positions reader of reopened export file after the header that must be the same as preparsed one
+pub(super) fn skip_header<T>(reader: T, header_bytes: &[u8]) -> IndyResult<BufReader<T>> where T: Read {
+    let mut reader = BufReader::new(reader);
+
+    let header_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
+
+    let mut header = vec![0u8; header_len];
+    reader.read_exact(&mut header).map_err(_map_io_err)?;
+
+    if header != header_bytes {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Export file has been changed during import"));
+    }
+
+    Ok(reader)
+}
+
+fn _record_exists(wallet: &Wallet, record: &Record) -> IndyResult<bool> {
+    match wallet.get(&record.type_, &record.id, &RecordOptions::id()) {
+        Ok(_) => Ok(true),
+        Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => Ok(false),
+        Err(err) => Err(err)
+    }
+}
+
+// Reads all records of export file of any version verifying its integrity
+fn _for_each_record<T, F>(reader: BufReader<T>, key: &chacha20poly1305_ietf::Key, nonce: &chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: &[u8], mut f: F) -> IndyResult<()> where T: Read, F: FnMut(Record) -> IndyResult<()> {
+    let header: Header = rmp_serde::from_slice(header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
+
+    if header.version == 0 {
+        return _for_each_stream_record(reader, key.clone(), nonce.clone(), chunk_size, header_bytes, f);
+    }
+
+    let mut reader = reader;
+    let mut nonce = nonce.clone();
+    let header_hash = hash(header_bytes)?;
+    let mut record_count = 0;
+
+    for chunk_index in 0..header.chunk_count {
+        let chunk = _read_sealed_chunk(&mut reader)?;
+
+        for record in _open_chunk(&chunk, key, &nonce, &header_hash, chunk_index)? {
+            f(record)?;
+            record_count += 1;
+        }
+
+        nonce.increment();
+    }
+
+    if record_count != header.record_count {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Export file contains unexpected number of records"));
+    }
+
+    _check_end(&mut reader)
+}
+
+fn _get_import_progress(wallet: &Wallet, header_hash: &[u8]) -> IndyResult<ImportProgress> {
+    let progress = match wallet.get(IMPORT_PROGRESS_TYPE, IMPORT_PROGRESS_ID, &RecordOptions::id_value()) {
+        Ok(record) => record.value
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for import progress"))?,
+        Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound =>
+            return Err(err_msg(IndyErrorKind::WalletAlreadyExists, "Wallet already exists and isn't being imported")),
+        Err(err) => return Err(err)
+    };
+
+    let progress: ImportProgress = serde_json::from_str(&progress)
+        .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize import progress")?;
+
+    if progress.header_hash != header_hash.to_base58() {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Wallet is being imported from another export file"));
+    }
+
+    Ok(progress)
+}
+
+fn _serialize_import_progress(progress: &ImportProgress) -> IndyResult<String> {
+    serde_json::to_string(progress)
+        .to_indy(IndyErrorKind::InvalidState, "Cannot serialize import progress")
+}
+
+// Returns None for the END message of version 0 stream
+fn _read_record<R: Read>(reader: &mut R) -> IndyResult<Option<Record>> {
+    let record_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
+
+    if record_len == 0 {
+        return Ok(None);
+    }
+
+    let mut record = vec![0u8; record_len];
+    reader.read_exact(&mut record).map_err(_map_io_err)?;
+
+    let record: Record = rmp_serde::from_slice(&record)
+        .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
+
+    Ok(Some(record))
+}
+
 fn _map_io_err(e: io::Error) -> IndyError {
     match e {
         ref e if e.kind() == io::ErrorKind::UnexpectedEof
@@ -250,20 +705,17 @@ mod tests {
     use crate::storage::default::SQLiteStorageType;
     use crate::storage::WalletStorageType;
     use crate::wallet::{Keys, Wallet};
+    use crate::range_index::RangeIndex;
     use indy_utils::crypto::pwhash_argon2i13;
     use indy_utils::test;
 
     use super::*;
 
     fn export(wallet: &Wallet, writer: &mut dyn Write, passphrase: &str, version: u32, key_derivation_method: &KeyDerivationMethod) -> IndyResult<()> {
-        if version != 0 {
-            Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"))?;
-        }
-
         let key_data = KeyDerivationData::from_passphrase_with_new_salt(passphrase, key_derivation_method);
         let key = key_data.calc_master_key()?;
 
-        export_continue(wallet, writer, version, key, &key_data)
+        export_continue(wallet, writer, version, key, &key_data, None, None)
     }
 
     #[test]
@@ -460,6 +912,424 @@ mod tests {
         _cleanup("import_works_for_data_extended2");
     }
 
+    #[test]
+    fn export_works_for_unsupported_version() {
+        _cleanup("export_works_for_unsupported_version");
+
+        let mut output: Vec<u8> = Vec::new();
+        let res = export(&_wallet("export_works_for_unsupported_version"), &mut output, _passphrase(), 2, &KeyDerivationMethod::ARGON2I_MOD);
+        assert_eq!(IndyErrorKind::InvalidState, res.unwrap_err().kind());
+        _cleanup("export_works_for_unsupported_version");
+    }
+
+    #[test]
+    fn export_import_works_for_chunks_and_empty_wallet() {
+        _cleanup("export_import_works_for_chunks_and_empty_wallet1");
+        _cleanup("export_import_works_for_chunks_and_empty_wallet2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_wallet("export_import_works_for_chunks_and_empty_wallet1"), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let header = _header(&output);
+            assert_eq!(1, header.chunk_count);
+            assert_eq!(0, header.record_count);
+
+            let wallet = _wallet("export_import_works_for_chunks_and_empty_wallet2");
+            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            _assert_is_empty(&wallet);
+        }
+        _cleanup("export_import_works_for_chunks_and_empty_wallet1");
+        _cleanup("export_import_works_for_chunks_and_empty_wallet2");
+    }
+
+    #[test]
+    fn export_import_works_for_chunks_and_multiple_items() {
+        _cleanup("export_import_works_for_chunks_and_multiple_items1");
+        _cleanup("export_import_works_for_chunks_and_multiple_items2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_300_records(_wallet("export_import_works_for_chunks_and_multiple_items1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let header = _header(&output);
+            assert_eq!(3, header.chunk_count);
+            assert_eq!(300, header.record_count);
+
+            let wallet = _wallet("export_import_works_for_chunks_and_multiple_items2");
+            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            _assert_has_300_records(&wallet);
+            _assert_no_import_progress(&wallet);
+        }
+        _cleanup("export_import_works_for_chunks_and_multiple_items1");
+        _cleanup("export_import_works_for_chunks_and_multiple_items2");
+    }
+
+    #[test]
+    fn import_works_for_chunks_and_invalid_passphrase() {
+        _cleanup("import_works_for_chunks_and_invalid_passphrase1");
+        _cleanup("import_works_for_chunks_and_invalid_passphrase2");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_2_records(_wallet("import_works_for_chunks_and_invalid_passphrase1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        let res = import(&_wallet("import_works_for_chunks_and_invalid_passphrase2"), &mut output.as_slice(), "other_key");
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        _cleanup("import_works_for_chunks_and_invalid_passphrase1");
+        _cleanup("import_works_for_chunks_and_invalid_passphrase2");
+    }
+
+    #[test]
+    fn import_works_for_chunks_and_changed_header() {
+        _cleanup("import_works_for_chunks_and_changed_header1");
+        _cleanup("import_works_for_chunks_and_changed_header2");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_2_records(_wallet("import_works_for_chunks_and_changed_header1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        // Modifying the last byte of the header that is record_count
+        let pos = (&mut output.as_slice()).read_u32::<LittleEndian>().unwrap() as usize + 3;
+        _change_byte(&mut output, pos);
+
+        let res = import(&_wallet("import_works_for_chunks_and_changed_header2"), &mut output.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        _cleanup("import_works_for_chunks_and_changed_header1");
+        _cleanup("import_works_for_chunks_and_changed_header2");
+    }
+
+    #[test]
+    fn import_works_for_chunks_and_changed_record() {
+        _cleanup("import_works_for_chunks_and_changed_record1");
+        _cleanup("import_works_for_chunks_and_changed_record2");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_300_records(_wallet("import_works_for_chunks_and_changed_record1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        // Modifying one byte in the middle of sealed chunks
+        let pos = output.len() / 2;
+        _change_byte(&mut output, pos);
+
+        let res = import(&_wallet("import_works_for_chunks_and_changed_record2"), &mut output.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        _cleanup("import_works_for_chunks_and_changed_record1");
+        _cleanup("import_works_for_chunks_and_changed_record2");
+    }
+
+    #[test]
+    fn import_works_for_chunks_and_data_cut_or_extended() {
+        _cleanup("import_works_for_chunks_and_data_cut_or_extended1");
+        _cleanup("import_works_for_chunks_and_data_cut_or_extended2");
+        _cleanup("import_works_for_chunks_and_data_cut_or_extended3");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_2_records(_wallet("import_works_for_chunks_and_data_cut_or_extended1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        let mut cut = output.clone();
+        cut.pop().unwrap();
+
+        let res = import(&_wallet("import_works_for_chunks_and_data_cut_or_extended2"), &mut cut.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+        output.push(10);
+
+        let res = import(&_wallet("import_works_for_chunks_and_data_cut_or_extended3"), &mut output.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        _cleanup("import_works_for_chunks_and_data_cut_or_extended1");
+        _cleanup("import_works_for_chunks_and_data_cut_or_extended2");
+        _cleanup("import_works_for_chunks_and_data_cut_or_extended3");
+    }
+
+    #[test]
+    fn import_works_for_resume_after_interruption() {
+        _cleanup("import_works_for_resume_after_interruption1");
+        _cleanup("import_works_for_resume_after_interruption2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_300_records(_wallet("import_works_for_resume_after_interruption1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            // Interruption in the middle of the last chunk
+            let cut = &output[..output.len() - 10];
+
+            let wallet = _wallet("import_works_for_resume_after_interruption2");
+
+            let res = import(&wallet, &mut &cut[..], _passphrase());
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+
+            let progress = _get_import_progress(&wallet, &hash(&_header_bytes(&output)).unwrap()).unwrap();
+            assert_eq!(2, progress.chunks);
+            assert_eq!(200, progress.records);
+
+            import_resumed(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            _assert_has_300_records(&wallet);
+            _assert_no_import_progress(&wallet);
+        }
+        _cleanup("import_works_for_resume_after_interruption1");
+        _cleanup("import_works_for_resume_after_interruption2");
+    }
+
+    #[test]
+    fn import_works_for_resume_of_interrupted_chunk() {
+        _cleanup("import_works_for_resume_of_interrupted_chunk1");
+        _cleanup("import_works_for_resume_of_interrupted_chunk2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            export(&_add_300_records(_wallet("import_works_for_resume_of_interrupted_chunk1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+            let header_hash = hash(&_header_bytes(&output)).unwrap();
+
+            // Import was interrupted after some records of the first chunk are added
+            let wallet = _wallet("import_works_for_resume_of_interrupted_chunk2");
+            let progress = ImportProgress { header_hash: header_hash.to_base58(), chunks: 0, records: 0 };
+            wallet.add(IMPORT_PROGRESS_TYPE, IMPORT_PROGRESS_ID, &_serialize_import_progress(&progress).unwrap(), &Tags::new()).unwrap();
+            wallet.add(&_type(0), &_id(0), &_value(0), &_tags(0)).unwrap();
+
+            import_resumed(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            _assert_has_300_records(&wallet);
+            _assert_no_import_progress(&wallet);
+        }
+        _cleanup("import_works_for_resume_of_interrupted_chunk1");
+        _cleanup("import_works_for_resume_of_interrupted_chunk2");
+    }
+
+    #[test]
+    fn import_works_for_resume_and_wallet_not_being_imported() {
+        _cleanup("import_works_for_resume_and_wallet_not_being_imported1");
+        _cleanup("import_works_for_resume_and_wallet_not_being_imported2");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_2_records(_wallet("import_works_for_resume_and_wallet_not_being_imported1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        let res = import_resumed(&_wallet("import_works_for_resume_and_wallet_not_being_imported2"), &mut output.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::WalletAlreadyExists, res.unwrap_err().kind());
+        _cleanup("import_works_for_resume_and_wallet_not_being_imported1");
+        _cleanup("import_works_for_resume_and_wallet_not_being_imported2");
+    }
+
+    #[test]
+    fn import_works_for_resume_and_version_0() {
+        _cleanup("import_works_for_resume_and_version_01");
+        _cleanup("import_works_for_resume_and_version_02");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_2_records(_wallet("import_works_for_resume_and_version_01")), &mut output, _passphrase(), _version1(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        assert!(!is_resumable(&_header_bytes(&output)).unwrap());
+
+        let res = import_resumed(&_wallet("import_works_for_resume_and_version_02"), &mut output.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        _cleanup("import_works_for_resume_and_version_01");
+        _cleanup("import_works_for_resume_and_version_02");
+    }
+
+    #[test]
+    fn export_import_works_for_type_filter() {
+        for version in &[_version1(), _version2()] {
+            _cleanup("export_import_works_for_type_filter1");
+            _cleanup("export_import_works_for_type_filter2");
+            {
+                let mut output: Vec<u8> = Vec::new();
+                let filter = ExportFilter { types: vec![_type(1)], query: None };
+                export_filtered(&_add_300_records(_wallet("export_import_works_for_type_filter1")), &mut output, *version, &filter).unwrap();
+
+                let wallet = _wallet("export_import_works_for_type_filter2");
+                import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+
+                for i in 0..300 {
+                    let res = wallet.get(&_type(i % 3), &_id(i), _options());
+                    assert_eq!(i % 3 == 1, res.is_ok());
+                }
+            }
+            _cleanup("export_import_works_for_type_filter1");
+            _cleanup("export_import_works_for_type_filter2");
+        }
+    }
+
+    #[test]
+    fn export_import_works_for_query_filter() {
+        _cleanup("export_import_works_for_query_filter1");
+        _cleanup("export_import_works_for_query_filter2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            let filter = ExportFilter {
+                types: vec![_type(0), _type(1)],
+                query: Some(json!({"$or": [{"tag_id_1_1": "tag_value_1_1"}, {"tag_id_3_1": "tag_value_3_1"}]})),
+            };
+            export_filtered(&_add_300_records(_wallet("export_import_works_for_query_filter1")), &mut output, _version2(), &filter).unwrap();
+
+            assert_eq!(2, _header(&output).record_count);
+
+            let wallet = _wallet("export_import_works_for_query_filter2");
+            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+
+            for i in 0..300 {
+                let res = wallet.get(&_type(i % 3), &_id(i), _options());
+                assert_eq!(i == 1 || i == 3, res.is_ok());
+            }
+
+            let record = wallet.get(&_type(1), &_id(1), _options()).unwrap();
+            assert_eq!(record.value.unwrap(), _value(1));
+            assert_eq!(record.tags.unwrap(), _tags(1));
+        }
+        _cleanup("export_import_works_for_query_filter1");
+        _cleanup("export_import_works_for_query_filter2");
+    }
+
+    #[test]
+    fn merge_import_works_for_conflict_policies() {
+        for version in &[_version1(), _version2()] {
+            _cleanup("merge_import_works_for_conflict_policies1");
+            _cleanup("merge_import_works_for_conflict_policies2");
+            {
+                let mut output: Vec<u8> = Vec::new();
+                export(&_add_2_records(_wallet("merge_import_works_for_conflict_policies1")), &mut output, _passphrase(), *version, &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+                let wallet = _wallet("merge_import_works_for_conflict_policies2");
+                wallet.add(&_type1(), &_id1(), "existing", &HashMap::new()).unwrap();
+
+                // Wallet isn't changed if any record already exists
+                let res = merge(&wallet, &output, ConflictPolicy::Fail);
+                assert_eq!(IndyErrorKind::WalletItemAlreadyExists, res.unwrap_err().kind());
+                assert_eq!(IndyErrorKind::WalletItemNotFound, wallet.get(&_type2(), &_id2(), _options()).unwrap_err().kind());
+
+                let result = merge(&wallet, &output, ConflictPolicy::Skip).unwrap();
+                assert_eq!((1, 1, 0), (result.added, result.skipped, result.overwritten));
+                assert_eq!("existing", wallet.get(&_type1(), &_id1(), _options()).unwrap().value.unwrap());
+
+                let result = merge(&wallet, &output, ConflictPolicy::Overwrite).unwrap();
+                assert_eq!((0, 0, 2), (result.added, result.skipped, result.overwritten));
+                _assert_has_2_records(&wallet);
+            }
+            _cleanup("merge_import_works_for_conflict_policies1");
+            _cleanup("merge_import_works_for_conflict_policies2");
+        }
+    }
+
+    #[test]
+    fn merge_import_works_for_changed_record() {
+        _cleanup("merge_import_works_for_changed_record1");
+        _cleanup("merge_import_works_for_changed_record2");
+
+        let mut output: Vec<u8> = Vec::new();
+        export(&_add_300_records(_wallet("merge_import_works_for_changed_record1")), &mut output, _passphrase(), _version2(), &KeyDerivationMethod::ARGON2I_MOD).unwrap();
+
+        // Modifying one byte in the last chunk
+        let pos = output.len() - 10;
+        _change_byte(&mut output, pos);
+
+        let wallet = _wallet("merge_import_works_for_changed_record2");
+        let res = merge(&wallet, &output, ConflictPolicy::Skip);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        _assert_is_empty(&wallet);
+
+        _cleanup("merge_import_works_for_changed_record1");
+        _cleanup("merge_import_works_for_changed_record2");
+    }
+
+    #[test]
+    fn export_import_works_for_sealed_key() {
+        _cleanup("export_import_works_for_sealed_key1");
+        _cleanup("export_import_works_for_sealed_key2");
+        {
+            let mut output: Vec<u8> = Vec::new();
+            _export_sealed(&_add_2_records(_wallet("export_import_works_for_sealed_key1")), &mut output).unwrap();
+
+            let wallet = _wallet("export_import_works_for_sealed_key2");
+            _assert_is_empty(&wallet);
+
+            import_with_key_wrappers(&wallet, &mut output.as_slice(), "", &_key_wrappers()).unwrap();
+            _assert_has_2_records(&wallet);
+        }
+        _cleanup("export_import_works_for_sealed_key1");
+        _cleanup("export_import_works_for_sealed_key2");
+    }
+
+    #[test]
+    fn import_works_for_sealed_key_and_unknown_key_wrapper() {
+        _cleanup("import_works_for_sealed_key_and_unknown_key_wrapper1");
+        _cleanup("import_works_for_sealed_key_and_unknown_key_wrapper2");
+
+        let mut output: Vec<u8> = Vec::new();
+        _export_sealed(&_add_2_records(_wallet("import_works_for_sealed_key_and_unknown_key_wrapper1")), &mut output).unwrap();
+
+        let res = import(&_wallet("import_works_for_sealed_key_and_unknown_key_wrapper2"), &mut output.as_slice(), _passphrase());
+        assert_eq!(IndyErrorKind::UnknownWalletStorageType, res.unwrap_err().kind());
+
+        _cleanup("import_works_for_sealed_key_and_unknown_key_wrapper1");
+        _cleanup("import_works_for_sealed_key_and_unknown_key_wrapper2");
+    }
+
+    struct TestKeyWrapper;
+
+    impl KeyWrapper for TestKeyWrapper {
+        fn wrap(&self, key_id: &str, key: &[u8]) -> IndyResult<String> {
+            Ok(format!("{}:{}", key_id, key.to_base58()))
+        }
+
+        fn unwrap(&self, key_id: &str, wrapped_key: &str) -> IndyResult<Vec<u8>> {
+            use rust_base58::FromBase58;
+
+            let prefix = format!("{}:", key_id);
+
+            if !wrapped_key.starts_with(&prefix) {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Key is wrapped with another transit key"));
+            }
+
+            wrapped_key[prefix.len()..].from_base58()
+                .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Invalid wrapped key"))
+        }
+    }
+
+    fn _key_wrappers() -> HashMap<String, Box<dyn KeyWrapper>> {
+        let mut key_wrappers: HashMap<String, Box<dyn KeyWrapper>> = HashMap::new();
+        key_wrappers.insert("test".to_string(), Box::new(TestKeyWrapper));
+        key_wrappers
+    }
+
+    fn _export_sealed(wallet: &Wallet, writer: &mut dyn Write) -> IndyResult<()> {
+        let key = chacha20poly1305_ietf::gen_key();
+        let key_data = KeyDerivationData::Raw(key[..].to_base58());
+
+        let sealed_key = SealedKey {
+            key_wrapper: "test".to_string(),
+            key_id: "wallet-backup".to_string(),
+            wrapped_key: TestKeyWrapper.wrap("wallet-backup", &key[..])?,
+        };
+
+        export_continue(wallet, writer, _version1(), key, &key_data, Some(sealed_key), None)
+    }
+
+    fn export_filtered(wallet: &Wallet, writer: &mut dyn Write, version: u32, filter: &ExportFilter) -> IndyResult<()> {
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(_passphrase(), &KeyDerivationMethod::ARGON2I_MOD);
+        let key = key_data.calc_master_key()?;
+
+        export_continue(wallet, writer, version, key, &key_data, None, Some(filter))
+    }
+
+    fn merge(wallet: &Wallet, output: &[u8], conflict_policy: ConflictPolicy) -> IndyResult<MergeResult> {
+        let (verify_reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(output, _passphrase(), &HashMap::new())?;
+        let reader = skip_header(output, &header_bytes)?;
+        let import_key = import_key_derivation_data.calc_master_key()?;
+        merge_import(wallet, verify_reader, reader, import_key, nonce, chunk_size, &header_bytes, conflict_policy)
+    }
+
+    fn import_resumed<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> where T: Read {
+        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase, &HashMap::new())?;
+        let import_key = import_key_derivation_data.calc_master_key()?;
+        finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes, true)
+    }
+
+    fn _header_bytes(output: &[u8]) -> Vec<u8> {
+        let header_len = (&mut &output[..]).read_u32::<LittleEndian>().unwrap() as usize;
+        output[4..4 + header_len].to_vec()
+    }
+
+    fn _header(output: &[u8]) -> Header {
+        rmp_serde::from_slice(&_header_bytes(output)).unwrap()
+    }
+
+    fn _assert_no_import_progress(wallet: &Wallet) {
+        let res = wallet.get(IMPORT_PROGRESS_TYPE, IMPORT_PROGRESS_ID, _options());
+        assert_eq!(IndyErrorKind::WalletItemNotFound, res.unwrap_err().kind());
+    }
+
     fn _cleanup(name: &str) {
         test::cleanup_storage(name)
     }
@@ -480,6 +1350,8 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                pending_keys: None,
+                range_tags: None,
             });
 
             serde_json::to_vec(&metadata)
@@ -493,7 +1365,7 @@ mod tests {
 
         let storage = storage_type.open_storage(id, None, None).unwrap();
 
-        Wallet::new(id.to_string(), storage, Rc::new(keys))
+        Wallet::new(id.to_string(), storage, Rc::new(keys), Rc::new(RangeIndex::default()))
     }
 
     fn _assert_is_empty(wallet: &Wallet) {
@@ -568,6 +1440,10 @@ mod tests {
         0
     }
 
+    fn _version2() -> u32 {
+        1
+    }
+
     fn _id(suffix: usize) -> String {
         format!("id_{}", suffix)
     }
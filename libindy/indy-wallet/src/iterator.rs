@@ -1,15 +1,26 @@
 use std::rc::Rc;
 
 use indy_api_types::errors::IndyError;
+use indy_utils::wql::Query;
 
 use super::WalletRecord;
 use super::wallet::Keys;
+use super::range_index::RangeIndex;
 use super::storage::StorageIterator;
-use super::encryption::{decrypt_storage_record};
+use super::encryption::{decrypt_storage_record, is_encrypted_with};
+
+struct RangeFilter {
+    query: Query,
+    range_index: Rc<RangeIndex>,
+    retrieve_tags: bool,
+}
 
 pub(super) struct WalletIterator {
     storage_iterator: Box<dyn StorageIterator>,
     keys: Rc<Keys>,
+    range_filter: Option<RangeFilter>,
+    fallback_keys: Option<Rc<Keys>>,
+    chained: Option<Box<WalletIterator>>,
 }
 
 
@@ -18,19 +29,84 @@ impl WalletIterator {
         WalletIterator {
             storage_iterator: storage_iter,
             keys,
+            range_filter: None,
+            fallback_keys: None,
+            chained: None,
+        }
+    }
+
+    // Records that can't be decrypted with keys are decrypted with fallback keys.
+    pub fn with_fallback_keys(mut self, fallback_keys: Rc<Keys>) -> Self {
+        self.fallback_keys = Some(fallback_keys);
+        self
+    }
+
+    // Records of the other iterator are returned after records of this one.
+    pub fn chain(mut self, other: WalletIterator) -> Self {
+        self.chained = Some(Box::new(other));
+        self
+    }
+
+    // Skips records that don't match query after decryption.
+    // Tags are returned only if retrieve_tags is set.
+    pub fn with_range_filter(mut self, query: Query, range_index: Rc<RangeIndex>, retrieve_tags: bool) -> Self {
+        self.range_filter = Some(RangeFilter { query, range_index, retrieve_tags });
+        self
+    }
+
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.storage_iterator.set_chunk_size(chunk_size);
+
+        if let Some(ref mut chained) = self.chained {
+            chained.set_chunk_size(chunk_size);
         }
     }
 
     pub fn next(&mut self) -> Result<Option<WalletRecord>, IndyError> {
-        let next_storage_entity = self.storage_iterator.next()?;
-        if let Some(next_storage_entity) = next_storage_entity {
-            let record = decrypt_storage_record(&next_storage_entity, &self.keys)?;
-            Ok(Some(record))
-        } else { Ok(None) }
+        while let Some(next_storage_entity) = self.storage_iterator.next()? {
+            let mut record = match self.fallback_keys {
+                Some(ref fallback_keys) if !is_encrypted_with(&next_storage_entity, &self.keys) =>
+                    decrypt_storage_record(&next_storage_entity, fallback_keys)?,
+                _ => decrypt_storage_record(&next_storage_entity, &self.keys)?
+            };
+
+            if let Some(ref mut tags) = record.tags {
+                RangeIndex::strip_bucket_tags(tags);
+            }
+
+            if let Some(ref filter) = self.range_filter {
+                let matches = match record.tags {
+                    Some(ref tags) => filter.range_index.matches(&filter.query, tags),
+                    None => false
+                };
+
+                if !matches {
+                    continue;
+                }
+
+                if !filter.retrieve_tags {
+                    record.tags = None;
+                }
+            }
+
+            return Ok(Some(record));
+        }
+
+        match self.chained {
+            Some(ref mut chained) => chained.next(),
+            None => Ok(None)
+        }
     }
 
     pub fn get_total_count(&self) -> Result<Option<usize>, IndyError> {
         let total_count = self.storage_iterator.get_total_count()?;
-        Ok(total_count)
+
+        match self.chained {
+            Some(ref chained) => {
+                let chained_count = chained.get_total_count()?;
+                Ok(total_count.and_then(|count| chained_count.map(|chained_count| count + chained_count)))
+            }
+            None => Ok(total_count)
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,105 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use libc::c_char;
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::wallet::*;
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::base64;
+
+// Wraps wallet export keys with the transit key of external KMS (HashiCorp Vault transit engine for example),
+// so export file can be decrypted only by the one who has access to KMS.
+pub trait KeyWrapper {
+    fn wrap(&self, key_id: &str, key: &[u8]) -> IndyResult<String>;
+    fn unwrap(&self, key_id: &str, wrapped_key: &str) -> IndyResult<Vec<u8>>;
+}
+
+// This struct is used as a helper to free the key even in case of error.
+struct KeyGuard {
+    key_handle: IndyHandle,
+    free_handler: WalletKeyFree,
+}
+
+impl Drop for KeyGuard {
+    fn drop(&mut self) {
+        (self.free_handler)(self.key_handle);
+    }
+}
+
+pub(super) struct PluggedKeyWrapper {
+    wrap_handler: WalletKeyWrap,
+    unwrap_handler: WalletKeyUnwrap,
+    free_handler: WalletKeyFree,
+}
+
+impl PluggedKeyWrapper {
+    pub(super) fn new(wrap_handler: WalletKeyWrap,
+                      unwrap_handler: WalletKeyUnwrap,
+                      free_handler: WalletKeyFree) -> PluggedKeyWrapper {
+        PluggedKeyWrapper {
+            wrap_handler,
+            unwrap_handler,
+            free_handler,
+        }
+    }
+
+    fn _read_key(&self, key_p: *const c_char, key_handle: IndyHandle) -> IndyResult<String> {
+        let _key_guard = KeyGuard { key_handle, free_handler: self.free_handler };
+
+        if key_p.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key wrapper returned null key"));
+        }
+
+        let key = unsafe {
+            CStr::from_ptr(key_p)
+                .to_str()
+                .to_indy(IndyErrorKind::InvalidState, "Key wrapper returned non-utf8 key")?
+                .to_string()
+        };
+
+        Ok(key)
+    }
+}
+
+impl KeyWrapper for PluggedKeyWrapper {
+    fn wrap(&self, key_id: &str, key: &[u8]) -> IndyResult<String> {
+        let key_id = CString::new(key_id)?;
+        let key = CString::new(base64::encode(key))?;
+
+        let mut wrapped_key_p: *const c_char = ptr::null();
+        let mut wrapped_key_handle: IndyHandle = -1;
+
+        let err = (self.wrap_handler)(key_id.as_ptr(),
+                                      key.as_ptr(),
+                                      &mut wrapped_key_p,
+                                      &mut wrapped_key_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        self._read_key(wrapped_key_p, wrapped_key_handle)
+    }
+
+    fn unwrap(&self, key_id: &str, wrapped_key: &str) -> IndyResult<Vec<u8>> {
+        let key_id = CString::new(key_id)?;
+        let wrapped_key = CString::new(wrapped_key)?;
+
+        let mut key_p: *const c_char = ptr::null();
+        let mut key_handle: IndyHandle = -1;
+
+        let err = (self.unwrap_handler)(key_id.as_ptr(),
+                                        wrapped_key.as_ptr(),
+                                        &mut key_p,
+                                        &mut key_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let key = self._read_key(key_p, key_handle)?;
+
+        base64::decode(&key)
+    }
+}
@@ -22,34 +22,54 @@ use serde_json::Value as SValue;
 
 use indy_api_types::wallet::*;
 
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, Tags};
+use indy_api_types::domain::wallet::{ConflictPolicy, Config, Credentials, ExportConfig, RecordOperation, ReencryptionConfig, Tags};
+use indy_api_types::validation::Validatable;
 use indy_api_types::errors::prelude::*;
 pub use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 
-use self::export_import::{export_continue, finish_import, preparse_file_to_import};
+use self::export_import::{export_continue, finish_import, is_resumable, merge_import, preparse_file_to_import, skip_header, SealedKey};
+pub use self::export_import::MergeResult;
+use self::key_wrapper::{KeyWrapper, PluggedKeyWrapper};
 use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
 use self::storage::plugged::PluggedStorageType;
 use self::wallet::{Keys, Wallet};
+use self::range_index::RangeIndex;
 use indy_api_types::{WalletHandle};
 
 mod storage;
 mod encryption;
 mod query_encryption;
 mod iterator;
+mod range_index;
 // TODO: Remove query language out of wallet module
 pub mod language;
 mod export_import;
+mod key_wrapper;
 mod wallet;
 
 pub struct WalletService {
     storage_types: RefCell<HashMap<String, Box<dyn WalletStorageType>>>,
+    key_wrappers: RefCell<HashMap<String, Box<dyn KeyWrapper>>>,
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
     wallet_ids: RefCell<HashSet<String>>,
-    pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
-    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>, Option<RangeIndex>)>>,
+    pending_for_import: RefCell<HashMap<WalletHandle, PendingImport>>,
+    pending_for_merge: RefCell<HashMap<i32, (WalletHandle, String /* path */, BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, ConflictPolicy)>>,
+}
+
+struct PendingImport {
+    reader: BufReader<::std::fs::File>,
+    nonce: chacha20poly1305_ietf::Nonce,
+    chunk_size: usize,
+    header_bytes: Vec<u8>,
+    key_data: KeyDerivationData,
+    // Storage and metadata of the wallet which import is being resumed
+    resumed: Option<(Box<dyn WalletStorage>, Metadata)>,
+    // Partially imported wallet is kept on failure to resume import later
+    resumable: bool,
 }
 
 impl WalletService {
@@ -62,10 +82,12 @@ impl WalletService {
 
         WalletService {
             storage_types,
+            key_wrappers: RefCell::new(HashMap::new()),
             wallets: RefCell::new(HashMap::new()),
             wallet_ids: RefCell::new(HashSet::new()),
             pending_for_open: RefCell::new(HashMap::new()),
             pending_for_import: RefCell::new(HashMap::new()),
+            pending_for_merge: RefCell::new(HashMap::new()),
         }
     }
 
@@ -119,6 +141,52 @@ impl WalletService {
         Ok(())
     }
 
+    pub fn register_wallet_storage_batch(&self, type_: &str, batch_records: WalletBatchRecords) -> IndyResult<()> {
+        trace!("register_wallet_storage_batch >>> type_: {:?}", type_);
+
+        self.storage_types.borrow()
+            .get(type_)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Unknown wallet storage type: {}", type_)))?
+            .set_batch_handler(batch_records)?;
+
+        trace!("register_wallet_storage_batch <<<");
+        Ok(())
+    }
+
+    pub fn register_wallet_storage_search_chunk(&self,
+                                                type_: &str,
+                                                fetch_search_next_records: WalletFetchSearchNextRecords,
+                                                free_search_records: WalletFreeSearchRecords) -> IndyResult<()> {
+        trace!("register_wallet_storage_search_chunk >>> type_: {:?}", type_);
+
+        self.storage_types.borrow()
+            .get(type_)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Unknown wallet storage type: {}", type_)))?
+            .set_search_chunk_handlers(fetch_search_next_records, free_search_records)?;
+
+        trace!("register_wallet_storage_search_chunk <<<");
+        Ok(())
+    }
+
+    pub fn register_key_wrapper(&self,
+                                type_: &str,
+                                wrap_key: WalletKeyWrap,
+                                unwrap_key: WalletKeyUnwrap,
+                                free_key: WalletKeyFree) -> IndyResult<()> {
+        trace!("register_key_wrapper >>> type_: {:?}", type_);
+
+        let mut key_wrappers = self.key_wrappers.borrow_mut();
+
+        if key_wrappers.contains_key(type_) {
+            return Err(err_msg(IndyErrorKind::WalletStorageTypeAlreadyRegistered, format!("Wallet key wrapper is already registered for type: {}", type_)));
+        }
+
+        key_wrappers.insert(type_.to_string(), Box::new(PluggedKeyWrapper::new(wrap_key, unwrap_key, free_key)));
+
+        trace!("register_key_wrapper <<<");
+        Ok(())
+    }
+
     pub fn create_wallet(&self,
                          config: &Config,
                          credentials: &Credentials,
@@ -137,7 +205,7 @@ impl WalletService {
         let (storage_type, storage_config, storage_credentials) = WalletService::_get_config_and_cred_for_storage(config, credentials, &storage_types)?;
 
         let keys = Keys::new();
-        let metadata = self._prepare_metadata(master_key, key_data, &keys)?;
+        let metadata = self._prepare_metadata(master_key, key_data, &keys, RangeIndex::from_config(config).bucket_sizes())?;
 
         storage_type.create_storage(&config.id,
                                     storage_config
@@ -196,31 +264,58 @@ impl WalletService {
 
         let (storage, metadata, key_derivation_data) = self._open_storage_and_fetch_metadata(config, credentials)?;
 
+        if credentials.rekey.is_some() && metadata.get_pending_keys().is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Wallet key can't be changed while re-encryption is in progress"));
+        }
+
         let wallet_handle = indy_utils::next_wallet_handle();
 
         let rekey_data: Option<KeyDerivationData> = credentials.rekey.as_ref().map(|ref rekey|
             KeyDerivationData::from_passphrase_with_new_salt(rekey, &credentials.rekey_derivation_method));
 
-        self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone()));
+        self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone(), WalletService::_configured_range_index(config)));
 
         Ok((wallet_handle, key_derivation_data, rekey_data))
     }
 
     pub fn open_wallet_continue(&self, wallet_handle: WalletHandle, master_key: (&MasterKey, Option<&MasterKey>)) -> IndyResult<WalletHandle> {
-        let (id, storage, metadata, rekey_data) = self.pending_for_open.borrow_mut().remove(&wallet_handle)
+        let (id, storage, mut metadata, rekey_data, range_index) = self.pending_for_open.borrow_mut().remove(&wallet_handle)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Open data not found"))?;
 
         let (master_key, rekey) = master_key;
-        let keys = self._restore_keys(&metadata, &master_key)?;
+        let keys = Rc::new(self._restore_keys(&metadata, &master_key)?);
+
+        // Interrupted re-encryption is continued with the next batch
+        let pending_keys = match metadata.get_pending_keys() {
+            Some(pending_keys) => Some(Rc::new(Keys::deserialize_encrypted(&pending_keys.keys, master_key)?)),
+            None => None
+        };
+
+        // Range tags stored with the wallet are used if they are omitted in the config
+        let stored_range_index = RangeIndex::from_bucket_sizes(metadata.get_range_tags());
+        let range_index = range_index.unwrap_or_else(|| stored_range_index.clone());
+        let reindex = range_index != stored_range_index;
+
+        let mut wallet = Wallet::new(id.clone(), storage, Rc::clone(&keys), Rc::new(range_index.clone()));
+        wallet.set_pending_keys(pending_keys);
+
+        // Records written with the previous range tags configuration are re-indexed before it is replaced,
+        // so interrupted re-indexing is repeated on the next opening
+        if reindex {
+            wallet.reindex_range_tags()?;
+            metadata.set_range_tags(range_index.bucket_sizes())?;
+        }
 
         // Rotate master key
         if let (Some(rekey), Some(rekey_data)) = (rekey, rekey_data) {
-            let metadata = self._prepare_metadata(rekey, &rekey_data, &keys)?;
-            storage.set_storage_metadata(&metadata)?;
+            let metadata = self._prepare_metadata(rekey, &rekey_data, &keys, range_index.bucket_sizes())?;
+            wallet.set_storage_metadata(&metadata)?;
+        } else if reindex {
+            let metadata = serde_json::to_vec(&metadata)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet metadata")?;
+            wallet.set_storage_metadata(&metadata)?;
         }
 
-        let wallet = Wallet::new(id.clone(), storage, Rc::new(keys));
-
         let mut wallets = self.wallets.borrow_mut();
         wallets.insert(wallet_handle, Box::new(wallet));
         let mut wallet_ids = self.wallet_ids.borrow_mut();
@@ -230,6 +325,12 @@ impl WalletService {
         Ok(wallet_handle)
     }
 
+    fn _configured_range_index(config: &Config) -> Option<RangeIndex> {
+        config.range_tags
+            .as_ref()
+            .map(|_| RangeIndex::from_config(config))
+    }
+
     fn _open_storage_and_fetch_metadata(&self, config: &Config, credentials: &Credentials) -> IndyResult<(Box<dyn WalletStorage>, Metadata, KeyDerivationData)> {
         let storage = self._open_storage(config, credentials)?;
         let metadata: Metadata = {
@@ -272,6 +373,13 @@ impl WalletService {
         }
     }
 
+    pub fn batch_records(&self, wallet_handle: WalletHandle, operations: &[RecordOperation]) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.batch(operations),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn add_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str, value: &str, tags: &Tags)
                               -> IndyResult<()> where T: Sized {
         self.add_record(wallet_handle, &self.add_prefix(short_type_name::<T>()), name, value,tags)
@@ -429,6 +537,13 @@ impl WalletService {
         }
     }
 
+    pub fn is_range_indexed_tag(&self, wallet_handle: WalletHandle, tag_name: &str) -> IndyResult<bool> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => Ok(wallet.is_range_indexed(tag_name)),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn check(&self, handle: WalletHandle) -> IndyResult<()> {
         match self.wallets.borrow().get(&handle) {
             Some(_) => Ok(()),
@@ -436,13 +551,131 @@ impl WalletService {
         }
     }
 
+    pub fn start_reencryption_prepare(&self, wallet_handle: WalletHandle, config: &ReencryptionConfig) -> IndyResult<(KeyDerivationData, KeyDerivationData)> {
+        trace!("start_reencryption_prepare >>> wallet_handle: {:?}, config: {:?}", wallet_handle, secret!(config));
+
+        let metadata = self._get_wallet_metadata(wallet_handle)?;
+
+        if metadata.get_pending_keys().is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Wallet re-encryption is already in progress"));
+        }
+
+        let key_data = KeyDerivationData::from_passphrase_and_metadata(&config.key, &metadata, &config.key_derivation_method)?;
+        let new_key_data = KeyDerivationData::from_passphrase_with_new_salt(&config.new_key, &config.new_key_derivation_method);
+
+        Ok((key_data, new_key_data))
+    }
+
+    // New keys are stored in metadata encrypted with the current master key, so re-encryption can be continued
+    // after reopening of the wallet. Metadata to switch to is prepared in advance with the new master key.
+    pub fn start_reencryption_continue(&self, wallet_handle: WalletHandle, key: (&MasterKey, &KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+        let (master_key, new_key_data, new_master_key) = key;
+
+        let mut metadata = self._get_wallet_metadata(wallet_handle)?;
+
+        if metadata.get_pending_keys().is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Wallet re-encryption is already in progress"));
+        }
+
+        self._restore_keys(&metadata, master_key)?;
+
+        let new_keys = Keys::new();
+
+        metadata.set_pending_keys(Some(PendingKeys {
+            keys: new_keys.serialize_encrypted(master_key)?,
+            metadata: self._prepare_metadata(new_master_key, new_key_data, &new_keys, metadata.get_range_tags().cloned())?,
+        }));
+
+        let metadata = serde_json::to_vec(&metadata)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet metadata")?;
+
+        let mut wallets = self.wallets.borrow_mut();
+        let wallet = wallets
+            .get_mut(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        wallet.set_storage_metadata(&metadata)?;
+        wallet.set_pending_keys(Some(Rc::new(new_keys)));
+
+        trace!("start_reencryption <<<");
+        Ok(())
+    }
+
+    // Re-encrypts the next batch of records. Wallet is available for other operations between batches.
+    pub fn reencrypt_records(&self, wallet_handle: WalletHandle, batch_size: usize) -> IndyResult<ReencryptionProgress> {
+        trace!("reencrypt_records >>> wallet_handle: {:?}, batch_size: {:?}", wallet_handle, batch_size);
+
+        if batch_size == 0 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Batch size must be greater than 0"));
+        }
+
+        let metadata = self._get_wallet_metadata(wallet_handle)?;
+
+        let mut wallets = self.wallets.borrow_mut();
+        let wallet = wallets
+            .get_mut(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let reencrypted = wallet.reencrypt_records(batch_size)?;
+        let finished = reencrypted < batch_size;
+
+        if finished {
+            let pending_keys = metadata.get_pending_keys()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet re-encryption is not in progress"))?;
+
+            wallet.set_storage_metadata(&pending_keys.metadata)?;
+            wallet.finish_reencryption()?;
+        }
+
+        let res = ReencryptionProgress { reencrypted, finished };
+
+        trace!("reencrypt_records <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    // Checks each record of the wallet can be decrypted
+    pub fn get_reencryption_status(&self, wallet_handle: WalletHandle) -> IndyResult<ReencryptionStatus> {
+        trace!("get_reencryption_status >>> wallet_handle: {:?}", wallet_handle);
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let (total, remaining, undecryptable) = wallet.count_records_by_keys()?;
+
+        let res = ReencryptionStatus {
+            in_progress: wallet.is_reencryption_in_progress(),
+            total,
+            remaining,
+            undecryptable,
+        };
+
+        trace!("get_reencryption_status <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _get_wallet_metadata(&self, wallet_handle: WalletHandle) -> IndyResult<Metadata> {
+        let metadata = self.wallets.borrow()
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?
+            .get_storage_metadata()?;
+
+        serde_json::from_slice(&metadata)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")
+    }
+
     pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
         trace!("export_wallet >>> wallet_handle: {:?}, export_config: {:?}, version: {:?}", wallet_handle, secret!(export_config), version);
 
-        if version != 0 {
+        if version > 1 {
             return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
         }
 
+        if let Some(ref filter) = export_config.filter {
+            filter.validate().map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+        }
+
         let (key_data, key) = key;
 
         let wallets = self.wallets.borrow();
@@ -450,6 +683,22 @@ impl WalletService {
             .get(&wallet_handle)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
 
+        let sealed_key = match export_config.sealed {
+            Some(ref sealed) => {
+                let wrapped_key = self.key_wrappers.borrow()
+                    .get(&sealed.key_wrapper)
+                    .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Unknown wallet key wrapper: {}", sealed.key_wrapper)))?
+                    .wrap(&sealed.key_id, &key[..])?;
+
+                Some(SealedKey {
+                    key_wrapper: sealed.key_wrapper.clone(),
+                    key_id: sealed.key_id.clone(),
+                    wrapped_key,
+                })
+            }
+            None => None
+        };
+
         let path = PathBuf::from(&export_config.path);
 
         if let Some(parent_path) = path.parent() {
@@ -464,7 +713,7 @@ impl WalletService {
                 .create_new(true)
                 .open(export_config.path.clone())?;
 
-        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data);
+        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data, sealed_key, export_config.filter.as_ref());
 
         trace!("export_wallet <<<");
 
@@ -482,39 +731,75 @@ impl WalletService {
                 .read(true)
                 .open(&export_config.path)?;
 
-        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key, &self.key_wrappers.borrow())?;
+
+        if export_config.resumable && !is_resumable(&header_bytes)? {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Import of version 0 export file can't be resumed"));
+        }
+
+        let (key_data, resumed) = self._prepare_import_target(config, credentials, export_config.resumable)?;
 
         let wallet_handle = indy_utils::next_wallet_handle();
 
         let stashed_key_data = key_data.clone();
 
-        self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, stashed_key_data));
+        self.pending_for_import.borrow_mut().insert(wallet_handle, PendingImport {
+            reader,
+            nonce,
+            chunk_size,
+            header_bytes,
+            key_data: stashed_key_data,
+            resumed,
+            resumable: export_config.resumable,
+        });
 
         Ok((wallet_handle, key_data, import_key_derivation_data))
     }
 
+    // Wallet left by interrupted resumable import is opened to continue import into it
+    fn _prepare_import_target(&self, config: &Config, credentials: &Credentials, resumable: bool) -> IndyResult<(KeyDerivationData, Option<(Box<dyn WalletStorage>, Metadata)>)> {
+        if resumable {
+            match self._open_storage_and_fetch_metadata(config, credentials) {
+                Ok((storage, metadata, key_data)) => return Ok((key_data, Some((storage, metadata)))),
+                Err(ref err) if err.kind() == IndyErrorKind::WalletNotFound => {}
+                Err(err) => return Err(err)
+            }
+        }
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+        Ok((key_data, None))
+    }
+
     pub fn import_wallet_continue(&self, wallet_handle: WalletHandle, config: &Config, credentials: &Credentials, key: (MasterKey, MasterKey)) -> IndyResult<()> {
-        let (reader, nonce, chunk_size, header_bytes, key_data) = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
+        let PendingImport { reader, nonce, chunk_size, header_bytes, key_data, resumed, resumable } = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
 
         let (import_key, master_key) = key;
 
-        let keys = self._create_wallet(config, credentials, (&key_data, &master_key))?;
+        let (storage, metadata, keys, resume) = match resumed {
+            Some((storage, metadata)) => {
+                self._is_id_from_config_not_used(config)?;
+                let keys = self._restore_keys(&metadata, &master_key)?;
+                (storage, metadata, keys, true)
+            }
+            None => {
+                let keys = self._create_wallet(config, credentials, (&key_data, &master_key))?;
 
-        self._is_id_from_config_not_used(config)?;
-        let storage = self._open_storage(config, credentials)?;
-        let metadata = storage.get_storage_metadata()?;
+                self._is_id_from_config_not_used(config)?;
+                let storage = self._open_storage(config, credentials)?;
+                let metadata: Metadata = serde_json::from_slice(&storage.get_storage_metadata()?)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
+
+                (storage, metadata, keys, false)
+            }
+        };
 
         let res = {
-            let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys));
+            let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys), Rc::new(RangeIndex::from_config(config)));
 
-            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes)
+            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes, resume)
         };
 
-        if res.is_err() {
-            let metadata: Metadata = serde_json::from_slice(&metadata)
-                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
-
+        if res.is_err() && !resumable {
             self.delete_wallet_continue(config, credentials, &metadata, &master_key)?;
         }
 
@@ -524,6 +809,49 @@ impl WalletService {
         res
     }
 
+    // Starts import of exported records into opened wallet. Returns id of pending import and
+    // derivation data of the export key.
+    pub fn import_records_prepare(&self, wallet_handle: WalletHandle, export_config: &ExportConfig) -> IndyResult<(i32, KeyDerivationData)> {
+        trace!("import_records_prepare >>> wallet_handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
+
+        if !self.wallets.borrow().contains_key(&wallet_handle) {
+            return Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"));
+        }
+
+        let exported_file_to_import =
+            fs::OpenOptions::new()
+                .read(true)
+                .open(&export_config.path)?;
+
+        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key, &self.key_wrappers.borrow())?;
+
+        let merge_id = indy_utils::sequence::get_next_id();
+
+        self.pending_for_merge.borrow_mut().insert(merge_id, (wallet_handle, export_config.path.clone(), reader, nonce, chunk_size, header_bytes, export_config.conflict_policy));
+
+        trace!("import_records_prepare <<< merge_id: {:?}", merge_id);
+        Ok((merge_id, import_key_derivation_data))
+    }
+
+    pub fn import_records_continue(&self, merge_id: i32, import_key: &MasterKey) -> IndyResult<MergeResult> {
+        trace!("import_records_continue >>> merge_id: {:?}", merge_id);
+
+        let (wallet_handle, path, verify_reader, nonce, chunk_size, header_bytes, conflict_policy) = self.pending_for_merge.borrow_mut().remove(&merge_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Unknown pending import of records"))?;
+
+        let reader = skip_header(fs::OpenOptions::new().read(true).open(&path)?, &header_bytes)?;
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let res = merge_import(wallet, verify_reader, reader, import_key.clone(), nonce, chunk_size, &header_bytes, conflict_policy)?;
+
+        trace!("import_records_continue <<< res: {:?}", res);
+        Ok(res)
+    }
+
     pub fn get_wallets_count(&self) -> usize {
         self.wallets.borrow().len()
     }
@@ -583,12 +911,13 @@ impl WalletService {
         Ok(storage)
     }
 
-    fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys) -> IndyResult<Vec<u8>> {
+    fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys,
+                         range_tags: Option<HashMap<String, u64>>) -> IndyResult<Vec<u8>> {
         let encrypted_keys = keys.serialize_encrypted(master_key)?;
         let metadata = match key_data {
             KeyDerivationData::Raw(_) => {
                 Metadata::MetadataRaw(
-                    MetadataRaw { keys: encrypted_keys }
+                    MetadataRaw { keys: encrypted_keys, pending_keys: None, range_tags }
                 )
             }
             KeyDerivationData::Argon2iInt(_, salt) | KeyDerivationData::Argon2iMod(_, salt) => {
@@ -596,6 +925,8 @@ impl WalletService {
                     MetadataArgon {
                         keys: encrypted_keys,
                         master_key_salt: salt[..].to_vec(),
+                        pending_keys: None,
+                        range_tags,
                     }
                 )
             }
@@ -637,17 +968,102 @@ impl Metadata {
             Metadata::MetadataRaw(ref metadata) => &metadata.keys,
         }
     }
+
+    pub fn get_pending_keys(&self) -> Option<&PendingKeys> {
+        match *self {
+            Metadata::MetadataArgon(ref metadata) => metadata.pending_keys.as_ref(),
+            Metadata::MetadataRaw(ref metadata) => metadata.pending_keys.as_ref(),
+        }
+    }
+
+    fn set_pending_keys(&mut self, pending_keys: Option<PendingKeys>) {
+        match *self {
+            Metadata::MetadataArgon(ref mut metadata) => metadata.pending_keys = pending_keys,
+            Metadata::MetadataRaw(ref mut metadata) => metadata.pending_keys = pending_keys,
+        }
+    }
+
+    pub fn get_range_tags(&self) -> Option<&HashMap<String, u64>> {
+        match *self {
+            Metadata::MetadataArgon(ref metadata) => metadata.range_tags.as_ref(),
+            Metadata::MetadataRaw(ref metadata) => metadata.range_tags.as_ref(),
+        }
+    }
+
+    // Metadata switched to after re-encryption is updated too
+    fn set_range_tags(&mut self, range_tags: Option<HashMap<String, u64>>) -> IndyResult<()> {
+        let pending_keys = match *self {
+            Metadata::MetadataArgon(ref mut metadata) => {
+                metadata.range_tags = range_tags.clone();
+                metadata.pending_keys.as_mut()
+            }
+            Metadata::MetadataRaw(ref mut metadata) => {
+                metadata.range_tags = range_tags.clone();
+                metadata.pending_keys.as_mut()
+            }
+        };
+
+        if let Some(pending_keys) = pending_keys {
+            let mut pending_metadata: Metadata = serde_json::from_slice(&pending_keys.metadata)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize metadata")?;
+
+            pending_metadata.set_range_tags(range_tags)?;
+
+            pending_keys.metadata = serde_json::to_vec(&pending_metadata)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet metadata")?;
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetadataArgon {
     pub keys: Vec<u8>,
     pub master_key_salt: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_keys: Option<PendingKeys>,
+    // Bucket sizes of range indexed tags records are indexed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_tags: Option<HashMap<String, u64>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetadataRaw {
-    pub keys: Vec<u8>
+    pub keys: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_keys: Option<PendingKeys>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_tags: Option<HashMap<String, u64>>,
+}
+
+// State of re-encryption of wallet records under a new master key
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingKeys {
+    // New keys encrypted with the current master key
+    pub keys: Vec<u8>,
+    // Serialized metadata with new keys encrypted with the new master key
+    pub metadata: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReencryptionProgress {
+    // Number of records re-encrypted by the batch
+    pub reencrypted: usize,
+    // New master key is used for the wallet since now
+    pub finished: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReencryptionStatus {
+    pub in_progress: bool,
+    pub total: usize,
+    // Records still encrypted with the current keys
+    pub remaining: usize,
+    // Records that can't be decrypted with any of keys
+    pub undecryptable: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -735,6 +1151,16 @@ impl RecordOptions {
 
         serde_json::to_string(&options).unwrap()
     }
+
+    pub fn id_value_tags() -> String {
+        let options = RecordOptions {
+            retrieve_type: false,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
 }
 
 impl Default for RecordOptions {
@@ -759,6 +1185,22 @@ impl WalletSearch {
     pub fn fetch_next_record(&mut self) -> IndyResult<Option<WalletRecord>> {
         self.iter.next()
     }
+
+    // Fetches up to count records. Plugged storages with search chunk handlers return them in one call.
+    pub fn fetch_next_records(&mut self, count: usize) -> IndyResult<Vec<WalletRecord>> {
+        self.iter.set_chunk_size(count);
+
+        let mut records = Vec::new();
+
+        while records.len() < count {
+            match self.iter.next()? {
+                Some(record) => records.push(record),
+                None => break
+            }
+        }
+
+        Ok(records)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -788,6 +1230,18 @@ impl SearchOptions {
 
         serde_json::to_string(&options).unwrap()
     }
+
+    pub fn full() -> String {
+        let options = SearchOptions {
+            retrieve_records: true,
+            retrieve_total_count: false,
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
 }
 
 impl Default for SearchOptions {
@@ -823,7 +1277,7 @@ mod tests {
 
     use indy_api_types::INVALID_WALLET_HANDLE;
 
-    use indy_api_types::domain::wallet::KeyDerivationMethod;
+    use indy_api_types::domain::wallet::{KeyDerivationMethod, RangeTagConfig};
     use indy_utils::environment;
     use indy_utils::inmem_wallet::InmemWallet;
     use indy_utils::test;
@@ -842,7 +1296,7 @@ mod tests {
             let rekey_data: Option<KeyDerivationData> = credentials.rekey.as_ref().map(|ref rekey|
                 KeyDerivationData::from_passphrase_with_new_salt(rekey, &credentials.rekey_derivation_method));
 
-            self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone()));
+            self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone(), WalletService::_configured_range_index(config)));
 
             let key = key_derivation_data.calc_master_key()?;
 
@@ -868,19 +1322,36 @@ mod tests {
                     .read(true)
                     .open(&export_config.path)?;
 
-            let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
-            let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+            let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key, &self.key_wrappers.borrow())?;
+            let (key_data, resumed) = self._prepare_import_target(config, credentials, export_config.resumable)?;
 
             let wallet_handle = next_wallet_handle();
 
             let import_key = import_key_derivation_data.calc_master_key()?;
             let master_key = key_data.calc_master_key()?;
 
-            self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, key_data));
+            self.pending_for_import.borrow_mut().insert(wallet_handle, PendingImport {
+                reader,
+                nonce,
+                chunk_size,
+                header_bytes,
+                key_data,
+                resumed,
+                resumable: export_config.resumable,
+            });
 
             self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key))
         }
 
+        pub fn start_reencryption(&self, wallet_handle: WalletHandle, config: &ReencryptionConfig) -> IndyResult<()> {
+            let (key_data, new_key_data) = self.start_reencryption_prepare(wallet_handle, config)?;
+
+            let master_key = key_data.calc_master_key()?;
+            let new_master_key = new_key_data.calc_master_key()?;
+
+            self.start_reencryption_continue(wallet_handle, (&master_key, &new_key_data, &new_master_key))
+        }
+
         pub fn delete_wallet(&self, config: &Config, credentials: &Credentials) -> IndyResult<()> {
             if self.wallets.borrow_mut().values().any(|ref wallet| wallet.get_id() == WalletService::_get_wallet_id(config)) {
                 return Err(err_msg(IndyErrorKind::InvalidState, format!("Wallet has to be closed before deleting: {:?}", WalletService::_get_wallet_id(config))))?;
@@ -1163,6 +1634,7 @@ mod tests {
             id: String::from("same_id"),
             storage_type: None,
             storage_config: None,
+            range_tags: None,
         };
 
         wallet_service.create_wallet(&config_1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
@@ -1174,6 +1646,7 @@ mod tests {
             storage_config: Some(json!({
                 "path": _custom_path("wallet_service_open_wallet_works_for_two_wallets_with_same_ids_but_different_paths")
             })),
+            range_tags: None,
         };
 
         wallet_service.create_wallet(&config_2, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
@@ -1742,6 +2215,83 @@ mod tests {
         _cleanup("wallet_service_delete_tags_for_plugged");
     }
 
+    /**
+     * Batch records tests
+     */
+    #[test]
+    fn wallet_service_batch_records_works() {
+        test::cleanup_wallet("wallet_service_batch_records_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_batch_records_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_batch_records_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "name_1", "value_1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "name_2", "value_2", &HashMap::new()).unwrap();
+
+            let operations: Vec<RecordOperation> = serde_json::from_value(json!([
+                {"op": "add", "type": "type", "id": "name_3", "value": "value_3", "tags": {"tag_name": "tag_value"}},
+                {"op": "update", "type": "type", "id": "name_1", "value": "new_value_1", "tags": {"tag_name": "new_tag_value"}},
+                {"op": "delete", "type": "type", "id": "name_2"},
+            ])).unwrap();
+
+            wallet_service.batch_records(wallet_handle, &operations).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "name_3", &_fetch_options(false, true, true)).unwrap();
+            assert_eq!("value_3", record.get_value().unwrap());
+
+            let record = wallet_service.get_record(wallet_handle, "type", "name_1", &_fetch_options(false, true, true)).unwrap();
+            assert_eq!("new_value_1", record.get_value().unwrap());
+            assert_eq!("new_tag_value", record.get_tags().unwrap()["tag_name"]);
+
+            let res = wallet_service.get_record(wallet_handle, "type", "name_2", &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_batch_records_works");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_works_for_failed_operation() {
+        test::cleanup_wallet("wallet_service_batch_records_works_for_failed_operation");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_batch_records_works_for_failed_operation"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_batch_records_works_for_failed_operation"), &RAW_CREDENTIAL).unwrap();
+
+            let operations: Vec<RecordOperation> = serde_json::from_value(json!([
+                {"op": "add", "type": "type", "id": "name_1", "value": "value_1"},
+                {"op": "delete", "type": "type", "id": "unknown"},
+            ])).unwrap();
+
+            let res = wallet_service.batch_records(wallet_handle, &operations);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = wallet_service.get_record(wallet_handle, "type", "name_1", &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_batch_records_works_for_failed_operation");
+    }
+
+    #[test]
+    fn wallet_service_batch_records_fails_for_plugged_without_batch_handler() {
+        _cleanup("wallet_service_batch_records_fails_for_plugged_without_batch_handler");
+        {
+            let wallet_service = WalletService::new();
+            _register_inmem_wallet(&wallet_service);
+
+            wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+            let operations: Vec<RecordOperation> = serde_json::from_value(json!([
+                {"op": "add", "type": "type", "id": "name_1", "value": "value_1"},
+            ])).unwrap();
+
+            let res = wallet_service.batch_records(wallet_handle, &operations);
+            assert_kind!(IndyErrorKind::InvalidState, res);
+        }
+        _cleanup("wallet_service_batch_records_fails_for_plugged_without_batch_handler");
+    }
+
     #[test]
     fn wallet_service_search_records_works() {
         test::cleanup_wallet("wallet_service_search_records_works");
@@ -1765,6 +2315,88 @@ mod tests {
         test::cleanup_wallet("wallet_service_search_records_works");
     }
 
+    #[test]
+    fn wallet_service_is_range_indexed_tag_works() {
+        test::cleanup_wallet("wallet_service_is_range_indexed_tag_works");
+        {
+            let mut range_tags = HashMap::new();
+            range_tags.insert("age".to_string(), RangeTagConfig { bucket_size: 10 });
+
+            let mut config = _config("wallet_service_is_range_indexed_tag_works");
+            config.range_tags = Some(range_tags);
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            assert!(wallet_service.is_range_indexed_tag(wallet_handle, "age").unwrap());
+            assert!(!wallet_service.is_range_indexed_tag(wallet_handle, "name").unwrap());
+
+            let res = wallet_service.is_range_indexed_tag(INVALID_WALLET_HANDLE, "age");
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+        }
+        test::cleanup_wallet("wallet_service_is_range_indexed_tag_works");
+    }
+
+    #[test]
+    fn wallet_service_open_wallet_reindexes_records_for_changed_range_tags() {
+        test::cleanup_wallet("wallet_service_open_wallet_reindexes_records_for_changed_range_tags");
+        {
+            let config = _config("wallet_service_open_wallet_reindexes_records_for_changed_range_tags");
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("age".to_string(), "42".to_string());
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags).unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Record written before the range tags were configured is found by range query
+            let mut range_tags = HashMap::new();
+            range_tags.insert("age".to_string(), RangeTagConfig { bucket_size: 10 });
+
+            let mut range_config = config.clone();
+            range_config.range_tags = Some(range_tags);
+
+            let wallet_handle = wallet_service.open_wallet(&range_config, &RAW_CREDENTIAL).unwrap();
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", r#"{"age": {"$gt": "30"}}"#, &_fetch_options(true, true, true)).unwrap();
+            let record = search.fetch_next_record().unwrap().unwrap();
+            assert_eq!("key1", record.get_id());
+            assert!(search.fetch_next_record().unwrap().is_none());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Range tags stored with the wallet are used if they are omitted in the config
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+            assert!(wallet_service.is_range_indexed_tag(wallet_handle, "age").unwrap());
+        }
+        test::cleanup_wallet("wallet_service_open_wallet_reindexes_records_for_changed_range_tags");
+    }
+
+    #[test]
+    fn wallet_service_search_records_works_for_fetch_next_records() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_fetch_next_records");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_fetch_next_records"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_fetch_next_records"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key3", "value3", &HashMap::new()).unwrap();
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &_fetch_options(true, true, true)).unwrap();
+
+            assert_eq!(2, search.fetch_next_records(2).unwrap().len());
+            assert_eq!(1, search.fetch_next_records(2).unwrap().len());
+            assert!(search.fetch_next_records(2).unwrap().is_empty());
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_fetch_next_records");
+    }
+
     #[test]
     fn wallet_service_search_records_works_for_plugged_wallet() {
         _cleanup("wallet_service_search_records_works_for_plugged_wallet");
@@ -1896,6 +2528,120 @@ mod tests {
         test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
     }
 
+    #[test]
+    fn wallet_service_reencryption_works_for_raw_method() {
+        test::cleanup_wallet("wallet_service_reencryption_works_for_raw_method");
+        {
+            let config: &Config = &_config("wallet_service_reencryption_works_for_raw_method");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            wallet_service.start_reencryption(wallet_handle, &_reencryption_config_raw()).unwrap();
+
+            let res = wallet_service.start_reencryption(wallet_handle, &_reencryption_config_raw());
+            assert_kind!(IndyErrorKind::InvalidState, res);
+
+            let progress = wallet_service.reencrypt_records(wallet_handle, 1).unwrap();
+            assert_eq!(ReencryptionProgress { reencrypted: 1, finished: false }, progress);
+
+            // Records encrypted with both current and new keys are available
+            wallet_service.add_record(wallet_handle, "type", "key3", "value3", &HashMap::new()).unwrap();
+            wallet_service.update_record_value(wallet_handle, "type", "key1", "value1_new").unwrap();
+            wallet_service.update_record_value(wallet_handle, "type", "key2", "value2_new").unwrap();
+
+            let res = wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new());
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+            let search = wallet_service.search_records(wallet_handle, "type", "{}", &json!({"retrieveTotalCount": true}).to_string()).unwrap();
+            assert_eq!(Some(3), search.get_total_count().unwrap());
+
+            let status = wallet_service.get_reencryption_status(wallet_handle).unwrap();
+            assert_eq!(ReencryptionStatus { in_progress: true, total: 3, remaining: 1, undecryptable: 0 }, status);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Re-encryption is continued after reopening with the current key
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            let progress = wallet_service.reencrypt_records(wallet_handle, 10).unwrap();
+            assert_eq!(ReencryptionProgress { reencrypted: 1, finished: true }, progress);
+
+            let status = wallet_service.get_reencryption_status(wallet_handle).unwrap();
+            assert_eq!(ReencryptionStatus { in_progress: false, total: 3, remaining: 0, undecryptable: 0 }, status);
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key2", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value2_new", record.get_value().unwrap());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Access failed for old key
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_raw()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1_new", record.get_value().unwrap());
+            let record = wallet_service.get_record(wallet_handle, "type", "key3", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value3", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_reencryption_works_for_raw_method");
+    }
+
+    #[test]
+    fn wallet_service_reencryption_works_for_records_deleted_between_batches() {
+        test::cleanup_wallet("wallet_service_reencryption_works_for_records_deleted_between_batches");
+        {
+            let config: &Config = &_config("wallet_service_reencryption_works_for_records_deleted_between_batches");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key3", "value3", &HashMap::new()).unwrap();
+
+            wallet_service.start_reencryption(wallet_handle, &_reencryption_config_raw()).unwrap();
+
+            let progress = wallet_service.reencrypt_records(wallet_handle, 1).unwrap();
+            assert_eq!(ReencryptionProgress { reencrypted: 1, finished: false }, progress);
+
+            let status = wallet_service.get_reencryption_status(wallet_handle).unwrap();
+            assert_eq!(2, status.remaining);
+
+            // One of remaining records is deleted, so the next batch resumes with the other one
+            wallet_service.delete_record(wallet_handle, "type", "key3").unwrap();
+
+            let progress = wallet_service.reencrypt_records(wallet_handle, 2).unwrap();
+            assert_eq!(ReencryptionProgress { reencrypted: 1, finished: true }, progress);
+
+            let status = wallet_service.get_reencryption_status(wallet_handle).unwrap();
+            assert_eq!(ReencryptionStatus { in_progress: false, total: 2, remaining: 0, undecryptable: 0 }, status);
+        }
+        test::cleanup_wallet("wallet_service_reencryption_works_for_records_deleted_between_batches");
+    }
+
+    #[test]
+    fn wallet_service_open_wallet_returns_error_for_rekey_while_reencryption_in_progress() {
+        test::cleanup_wallet("wallet_service_open_wallet_returns_error_for_rekey_while_reencryption_in_progress");
+        {
+            let config: &Config = &_config("wallet_service_open_wallet_returns_error_for_rekey_while_reencryption_in_progress");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.start_reencryption(wallet_handle, &_reencryption_config_raw()).unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let res = wallet_service.open_wallet(config, &_rekey_credentials_raw());
+            assert_kind!(IndyErrorKind::InvalidState, res);
+        }
+        test::cleanup_wallet("wallet_service_open_wallet_returns_error_for_rekey_while_reencryption_in_progress");
+    }
+
     fn remove_exported_wallet(export_config: &ExportConfig) -> &Path {
         let export_path = Path::new(&export_config.path);
         if export_path.exists() {
@@ -2227,6 +2973,7 @@ mod tests {
             id: name.to_string(),
             storage_type: None,
             storage_config: None,
+            range_tags: None,
         }
     }
 
@@ -2235,6 +2982,7 @@ mod tests {
             id: name.to_string(),
             storage_type: Some("default".to_string()),
             storage_config: None,
+            range_tags: None,
         }
     }
 
@@ -2243,6 +2991,7 @@ mod tests {
             id: "w1".to_string(),
             storage_type: Some("inmem".to_string()),
             storage_config: None,
+            range_tags: None,
         }
     }
 
@@ -2251,6 +3000,7 @@ mod tests {
             id: name.to_string(),
             storage_type: Some("unknown".to_string()),
             storage_config: None,
+            range_tags: None,
         }
     }
 
@@ -2258,6 +3008,7 @@ mod tests {
     lazy_static! {
         static ref ARGON_MOD_CREDENTIAL: Credentials = Credentials {
             key: "my_key".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
@@ -2269,6 +3020,7 @@ mod tests {
     lazy_static! {
         static ref ARGON_INT_CREDENTIAL: Credentials = Credentials {
             key: "my_key".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
@@ -2281,6 +3033,7 @@ mod tests {
     lazy_static! {
         static ref RAW_CREDENTIAL: Credentials = Credentials {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
@@ -2321,6 +3074,7 @@ mod tests {
     fn _credentials_invalid_raw() -> Credentials {
         Credentials {
             key: "key".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
@@ -2331,6 +3085,7 @@ mod tests {
     fn _rekey_credentials_moderate() -> Credentials {
         Credentials {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            key_shares: None,
             rekey: Some("my_new_key".to_string()),
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
@@ -2341,6 +3096,7 @@ mod tests {
     fn _rekey_credentials_interactive() -> Credentials {
         Credentials {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            key_shares: None,
             rekey: Some("my_new_key".to_string()),
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
@@ -2351,6 +3107,7 @@ mod tests {
     fn _rekey_credentials_raw() -> Credentials {
         Credentials {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            key_shares: None,
             rekey: Some("7nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string()),
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
@@ -2361,6 +3118,7 @@ mod tests {
     fn _credentials_for_new_key_moderate() -> Credentials {
         Credentials {
             key: "my_new_key".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
@@ -2371,6 +3129,7 @@ mod tests {
     fn _credentials_for_new_key_interactive() -> Credentials {
         Credentials {
             key: "my_new_key".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
@@ -2381,6 +3140,7 @@ mod tests {
     fn _credentials_for_new_key_raw() -> Credentials {
         Credentials {
             key: "7nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            key_shares: None,
             rekey: None,
             storage_credentials: None,
             key_derivation_method: KeyDerivationMethod::RAW,
@@ -2388,6 +3148,15 @@ mod tests {
         }
     }
 
+    fn _reencryption_config_raw() -> ReencryptionConfig {
+        ReencryptionConfig {
+            key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            new_key: "7nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
+            key_derivation_method: KeyDerivationMethod::RAW,
+            new_key_derivation_method: KeyDerivationMethod::RAW,
+        }
+    }
+
     fn _export_file_path(name: &str) -> PathBuf {
         let mut path = environment::tmp_path();
         path.push(name);
@@ -2399,6 +3168,12 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            sealed: None,
+            key_shares: None,
+            resumable: false,
+            filter: None,
+            conflict_policy: Default::default(),
+            chunked: false,
         }
     }
 
@@ -2417,6 +3192,12 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            sealed: None,
+            key_shares: None,
+            resumable: false,
+            filter: None,
+            conflict_policy: Default::default(),
+            chunked: false,
         }
     }
 
@@ -2429,6 +3210,12 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            sealed: None,
+            key_shares: None,
+            resumable: false,
+            filter: None,
+            conflict_policy: Default::default(),
+            chunked: false,
         }
     }
 
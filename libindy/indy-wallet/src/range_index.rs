@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use indy_api_types::domain::wallet::{Config, Tags};
+use indy_api_types::errors::prelude::*;
+use indy_utils::wql::{self, Query};
+
+// Encrypted tags can only be compared for equality by the storage. For tags listed in
+// `range_tags` of the wallet config we additionally store a plaintext bucket tag
+// (value / bucket_size, zero padded so that string order matches numeric order).
+// Range queries are translated to bucket ranges for the storage and the exact
+// comparison is done after decryption. Only the bucket of a value is revealed.
+pub(super) const BUCKET_TAG_PREFIX: &str = "~~bucket:";
+
+const BUCKET_WIDTH: usize = 20;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(super) struct RangeIndex {
+    bucket_sizes: HashMap<String, u64>,
+}
+
+impl RangeIndex {
+    pub fn from_config(config: &Config) -> RangeIndex {
+        let bucket_sizes = config.range_tags
+            .as_ref()
+            .map(|range_tags| range_tags
+                .iter()
+                .map(|(name, config)| (name.clone(), config.bucket_size))
+                .collect())
+            .unwrap_or_default();
+
+        RangeIndex { bucket_sizes }
+    }
+
+    pub fn from_bucket_sizes(bucket_sizes: Option<&HashMap<String, u64>>) -> RangeIndex {
+        RangeIndex { bucket_sizes: bucket_sizes.cloned().unwrap_or_default() }
+    }
+
+    // Bucket sizes by tag name as they are stored in wallet metadata
+    pub fn bucket_sizes(&self) -> Option<HashMap<String, u64>> {
+        if self.bucket_sizes.is_empty() {
+            None
+        } else {
+            Some(self.bucket_sizes.clone())
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bucket_sizes.is_empty()
+    }
+
+    pub fn is_range_indexed(&self, name: &str) -> bool {
+        self.bucket_sizes.contains_key(name)
+    }
+
+    // Returns tags extended with bucket tags for range indexed tags holding an integer value.
+    pub fn index_tags(&self, tags: &Tags) -> Tags {
+        let mut indexed = tags.clone();
+
+        for (name, value) in tags {
+            if let Some(bucket) = self.bucket(name, value) {
+                indexed.insert(_bucket_tag_name(name), bucket);
+            }
+        }
+
+        indexed
+    }
+
+    // Returns names of bucket tags that must be deleted together with given tags.
+    pub fn bucket_tag_names(&self, tag_names: &[&str]) -> Vec<String> {
+        tag_names
+            .iter()
+            .filter(|name| self.bucket_sizes.contains_key(**name))
+            .map(|name| _bucket_tag_name(name))
+            .collect()
+    }
+
+    pub fn strip_bucket_tags(tags: &mut Tags) {
+        tags.retain(|name, _| !name.starts_with(BUCKET_TAG_PREFIX));
+    }
+
+    // Checks whether query contains range operators on range indexed tags.
+    pub fn is_range_query(&self, query: &Query) -> bool {
+        match *query {
+            Query::Gt(ref name, _) | Query::Gte(ref name, _) |
+            Query::Lt(ref name, _) | Query::Lte(ref name, _) => self.bucket_sizes.contains_key(name),
+            Query::And(ref queries) | Query::Or(ref queries) => queries.iter().any(|query| self.is_range_query(query)),
+            Query::Not(ref query) => self.is_range_query(query),
+            _ => false,
+        }
+    }
+
+    // Replaces range operators on range indexed tags with bucket ranges.
+    // The result selects a superset of matching records.
+    pub fn to_bucket_query(&self, query: Query) -> IndyResult<Query> {
+        match query {
+            Query::Gt(name, value) | Query::Gte(name, value) if self.bucket_sizes.contains_key(&name) => {
+                let bucket = self._query_bucket(&name, &value)?;
+                Ok(Query::Gte(_bucket_tag_name(&name), bucket))
+            }
+            Query::Lt(name, value) | Query::Lte(name, value) if self.bucket_sizes.contains_key(&name) => {
+                let bucket = self._query_bucket(&name, &value)?;
+                Ok(Query::Lte(_bucket_tag_name(&name), bucket))
+            }
+            Query::And(queries) => Ok(Query::And(self._to_bucket_queries(queries)?)),
+            Query::Or(queries) => Ok(Query::Or(self._to_bucket_queries(queries)?)),
+            Query::Not(ref query) if self.is_range_query(query) =>
+                Err(err_msg(IndyErrorKind::WalletQueryError, "Range query on range indexed tag is not supported inside of $not")),
+            query => Ok(query),
+        }
+    }
+
+    // Evaluates query against decrypted record tags.
+    pub fn matches(&self, query: &Query, tags: &Tags) -> bool {
+        match *query {
+            Query::And(ref queries) => queries.iter().all(|query| self.matches(query, tags)),
+            Query::Or(ref queries) => queries.iter().any(|query| self.matches(query, tags)),
+            Query::Not(ref query) => !self.matches(query, tags),
+            Query::Eq(ref name, ref value) => tags.get(name).map(|tag| tag == value).unwrap_or(false),
+            Query::Neq(ref name, ref value) => tags.get(name).map(|tag| tag != value).unwrap_or(false),
+            Query::Gt(ref name, ref value) => self._compare(name, value, tags, |ordering| ordering == Ordering::Greater),
+            Query::Gte(ref name, ref value) => self._compare(name, value, tags, |ordering| ordering != Ordering::Less),
+            Query::Lt(ref name, ref value) => self._compare(name, value, tags, |ordering| ordering == Ordering::Less),
+            Query::Lte(ref name, ref value) => self._compare(name, value, tags, |ordering| ordering != Ordering::Greater),
+            Query::Like(ref name, ref pattern) => tags.get(name).map(|tag| wql::like(pattern, tag)).unwrap_or(false),
+            Query::In(ref name, ref values) => tags.get(name).map(|tag| values.contains(tag)).unwrap_or(false),
+        }
+    }
+
+    fn bucket(&self, name: &str, value: &str) -> Option<String> {
+        let bucket_size = self.bucket_sizes.get(name)?;
+        let value = value.parse::<u64>().ok()?;
+        Some(format!("{:0width$}", value / bucket_size, width = BUCKET_WIDTH))
+    }
+
+    fn _query_bucket(&self, name: &str, value: &str) -> IndyResult<String> {
+        self.bucket(name, value)
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletQueryError,
+                                   format!("Range query on tag {:?} requires non negative integer value, got: {:?}", name, value)))
+    }
+
+    fn _to_bucket_queries(&self, queries: Vec<Query>) -> IndyResult<Vec<Query>> {
+        queries
+            .into_iter()
+            .map(|query| self.to_bucket_query(query))
+            .collect()
+    }
+
+    fn _compare<F>(&self, name: &str, value: &str, tags: &Tags, predicate: F) -> bool where F: Fn(Ordering) -> bool {
+        let tag = match tags.get(name) {
+            Some(tag) => tag,
+            None => return false
+        };
+
+        if self.bucket_sizes.contains_key(name) {
+            match (tag.parse::<u64>(), value.parse::<u64>()) {
+                (Ok(tag), Ok(value)) => predicate(tag.cmp(&value)),
+                _ => false
+            }
+        } else {
+            predicate(tag.as_str().cmp(value))
+        }
+    }
+}
+
+fn _bucket_tag_name(name: &str) -> String {
+    format!("{}{}", BUCKET_TAG_PREFIX, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use indy_api_types::domain::wallet::RangeTagConfig;
+
+    macro_rules! jsonmap {
+        ($($x:tt)+) => {
+            {
+                let map: ::std::collections::HashMap<String, String> = serde_json::from_value(json!($($x)+)).unwrap();
+                map
+            }
+        }
+    }
+
+    fn _range_index() -> RangeIndex {
+        let mut range_tags = HashMap::new();
+        range_tags.insert("age".to_string(), RangeTagConfig { bucket_size: 10 });
+
+        RangeIndex::from_config(&Config {
+            id: "range_index".to_string(),
+            storage_type: None,
+            storage_config: None,
+            range_tags: Some(range_tags),
+        })
+    }
+
+    fn _query(query: serde_json::Value) -> Query {
+        serde_json::from_value(query).unwrap()
+    }
+
+    #[test]
+    fn index_tags_works() {
+        let tags = _range_index().index_tags(&jsonmap!({"age": "42", "name": "Alex"}));
+
+        assert_eq!(tags, jsonmap!({
+            "age": "42",
+            "name": "Alex",
+            "~~bucket:age": "00000000000000000004"
+        }));
+    }
+
+    #[test]
+    fn index_tags_works_for_not_integer_value() {
+        let tags = _range_index().index_tags(&jsonmap!({"age": "unknown"}));
+        assert_eq!(tags, jsonmap!({"age": "unknown"}));
+    }
+
+    #[test]
+    fn strip_bucket_tags_works() {
+        let mut tags = _range_index().index_tags(&jsonmap!({"age": "42"}));
+        RangeIndex::strip_bucket_tags(&mut tags);
+        assert_eq!(tags, jsonmap!({"age": "42"}));
+    }
+
+    #[test]
+    fn to_bucket_query_works() {
+        let range_index = _range_index();
+
+        let query = _query(json!({"$and": [{"age": {"$gt": "25"}}, {"age": {"$lte": "40"}}, {"name": "Alex"}]}));
+        assert!(range_index.is_range_query(&query));
+
+        let expected = _query(json!({"$and": [
+            {"~~bucket:age": {"$gte": "00000000000000000002"}},
+            {"~~bucket:age": {"$lte": "00000000000000000004"}},
+            {"name": "Alex"}
+        ]}));
+        assert_eq!(range_index.to_bucket_query(query).unwrap(), expected);
+    }
+
+    #[test]
+    fn to_bucket_query_works_for_not_indexed_tag() {
+        let range_index = _range_index();
+
+        let query = _query(json!({"~height": {"$gt": "25"}}));
+        assert!(!range_index.is_range_query(&query));
+        assert_eq!(range_index.to_bucket_query(query.clone()).unwrap(), query);
+    }
+
+    #[test]
+    fn to_bucket_query_fails_for_not_integer_value() {
+        let res = _range_index().to_bucket_query(_query(json!({"age": {"$gt": "old"}})));
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+    }
+
+    #[test]
+    fn to_bucket_query_fails_for_not() {
+        let res = _range_index().to_bucket_query(_query(json!({"$not": {"age": {"$gt": "25"}}})));
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+    }
+
+    #[test]
+    fn matches_works() {
+        let range_index = _range_index();
+        let tags = jsonmap!({"age": "42", "name": "Alex"});
+
+        assert!(range_index.matches(&_query(json!({"age": {"$gt": "9"}})), &tags));
+        assert!(range_index.matches(&_query(json!({"age": {"$lte": "42"}})), &tags));
+        assert!(!range_index.matches(&_query(json!({"age": {"$lt": "42"}})), &tags));
+        assert!(range_index.matches(&_query(json!({"$and": [{"age": {"$gte": "40"}}, {"name": {"$like": "Al%"}}]})), &tags));
+        assert!(!range_index.matches(&_query(json!({"$or": [{"age": {"$gt": "50"}}, {"name": {"$in": ["Bob"]}}]})), &tags));
+        assert!(!range_index.matches(&_query(json!({"height": {"$gt": "1"}})), &tags));
+    }
+}
@@ -11,7 +11,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::environment;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{EncryptedValue, StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 use self::owning_ref::OwningHandle;
@@ -349,40 +349,13 @@ impl WalletStorage for SQLiteStorage {
     ///
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-        let res = tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
-            .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
-
-        let id = match res {
-            Ok(entity) => entity,
-            Err(err) => return Err(IndyError::from(err))
-        };
-
-        if !tags.is_empty() {
-            let mut stmt_e = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut stmt_p = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
-                };
-            }
-        }
-
+        _add_item(&tx, type_, id, value, tags)?;
         tx.commit()?;
         Ok(())
     }
 
     fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
-        let res = self.conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
-            .execute(rusqlite::params![&value.data, &value.key, &type_.to_vec(), &id.to_vec()]);
-
-        match res {
-            Ok(1) => Ok(()),
-            Ok(0) => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found")),
-            Ok(_) => Err(err_msg(IndyErrorKind::InvalidState, "More than one row update. Seems wallet structure is inconsistent")),
-            Err(err) => Err(err.into()),
-        }
+        _update_item(&self.conn, type_, id, value)
     }
 
     fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
@@ -409,24 +382,7 @@ impl WalletStorage for SQLiteStorage {
 
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
         let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
-
-        let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
-            .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
-
-        tx.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
-        tx.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
-
-        if !tags.is_empty() {
-            let mut enc_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-            let mut plain_tag_insert_stmt = tx.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
-
-            for tag in tags {
-                match *tag {
-                    Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
-                    Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
-                };
-            }
-        }
+        _update_item_tags(&tx, type_, id, tags)?;
         tx.commit()?;
 
         Ok(())
@@ -480,16 +436,27 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
-        let row_count = self.conn.execute(
-            "DELETE FROM items where type = ?1 AND name = ?2",
-            &[&type_.to_vec(), &id.to_vec()],
-        )?;
+        _delete_item(&self.conn, type_, id)
+    }
 
-        if row_count == 1 {
-            Ok(())
-        } else {
-            Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    ///
+    /// applies operations of the batch in a single transaction.
+    /// Either all operations are applied or none of them in case of failure.
+    ///
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+
+        for operation in operations {
+            match *operation {
+                StorageOperation::Add(ref type_, ref id, ref value, ref tags) => _add_item(&tx, type_, id, value, tags)?,
+                StorageOperation::Update(ref type_, ref id, ref value) => _update_item(&tx, type_, id, value)?,
+                StorageOperation::UpdateTags(ref type_, ref id, ref tags) => _update_item_tags(&tx, type_, id, tags)?,
+                StorageOperation::Delete(ref type_, ref id) => _delete_item(&tx, type_, id)?,
+            }
         }
+
+        tx.commit()?;
+        Ok(())
     }
 
     fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -581,6 +548,77 @@ impl SQLiteStorage {
 }
 
 
+fn _add_item(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+    let res = conn.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
+        .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
+
+    let id = match res {
+        Ok(entity) => entity,
+        Err(err) => return Err(IndyError::from(err))
+    };
+
+    if !tags.is_empty() {
+        let mut stmt_e = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut stmt_p = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_data) => stmt_e.execute(rusqlite::params![&id, tag_name, tag_data])?,
+                Tag::PlainText(ref tag_name, ref tag_data) => stmt_p.execute(rusqlite::params![&id, tag_name, tag_data])?
+            };
+        }
+    }
+
+    Ok(())
+}
+
+fn _update_item(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+    let res = conn.prepare_cached("UPDATE items SET value = ?1, key = ?2 WHERE type = ?3 AND name = ?4")?
+        .execute(rusqlite::params![&value.data, &value.key, &type_.to_vec(), &id.to_vec()]);
+
+    match res {
+        Ok(1) => Ok(()),
+        Ok(0) => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found")),
+        Ok(_) => Err(err_msg(IndyErrorKind::InvalidState, "More than one row update. Seems wallet structure is inconsistent")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn _update_item_tags(conn: &rusqlite::Connection, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+    let item_id: i64 = conn.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
+        .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
+
+    conn.execute("DELETE FROM tags_encrypted WHERE item_id = ?1", &[&item_id])?;
+    conn.execute("DELETE FROM tags_plaintext WHERE item_id = ?1", &[&item_id])?;
+
+    if !tags.is_empty() {
+        let mut enc_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_encrypted (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+        let mut plain_tag_insert_stmt = conn.prepare_cached("INSERT INTO tags_plaintext (item_id, name, value) VALUES (?1, ?2, ?3)")?;
+
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref tag_name, ref tag_data) => enc_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?,
+                Tag::PlainText(ref tag_name, ref tag_data) => plain_tag_insert_stmt.execute(rusqlite::params![&item_id, tag_name, tag_data])?
+            };
+        }
+    }
+
+    Ok(())
+}
+
+fn _delete_item(conn: &rusqlite::Connection, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+    let row_count = conn.execute(
+        "DELETE FROM items where type = ?1 AND name = ?2",
+        &[&type_.to_vec(), &id.to_vec()],
+    )?;
+
+    if row_count == 1 {
+        Ok(())
+    } else {
+        Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))
+    }
+}
+
 impl WalletStorageType for SQLiteStorageType {
     ///
     /// Deletes the SQLite database file with the provided id from the path specified in the
@@ -1313,6 +1351,59 @@ mod tests {
         _cleanup("sqlite_storage_delete_tags_works_for_non_existing_id");
     }
 
+    #[test]
+    fn sqlite_storage_batch_works() {
+        _cleanup("sqlite_storage_batch_works");
+        {
+            let storage = _storage("sqlite_storage_batch_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            storage.batch(&[
+                StorageOperation::Add(_type2(), _id2(), _value2(), _tags()),
+                StorageOperation::Update(_type1(), _id1(), _value2()),
+                StorageOperation::UpdateTags(_type2(), _id2(), _new_tags()),
+            ]).unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+
+            let record = storage.get(&_type2(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+            storage.batch(&[StorageOperation::Delete(_type1(), _id1()), StorageOperation::Delete(_type2(), _id2())]).unwrap();
+
+            let res = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+            let res = storage.get(&_type2(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sqlite_storage_batch_works");
+    }
+
+    #[test]
+    fn sqlite_storage_batch_rolls_back_on_failure() {
+        _cleanup("sqlite_storage_batch_rolls_back_on_failure");
+        {
+            let storage = _storage("sqlite_storage_batch_rolls_back_on_failure");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            let res = storage.batch(&[
+                StorageOperation::Add(_type2(), _id2(), _value2(), _tags()),
+                StorageOperation::Update(_type1(), _id1(), _value2()),
+                StorageOperation::Delete(_type2(), _id1()),
+            ]);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+
+            let res = storage.get(&_type2(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sqlite_storage_batch_rolls_back_on_failure");
+    }
+
     fn _cleanup(name: &str) {
         test::cleanup_storage(name)
     }
@@ -2,6 +2,7 @@ pub mod default;
 pub mod plugged;
 
 use indy_api_types::errors::prelude::*;
+use indy_api_types::wallet::{WalletBatchRecords, WalletFetchSearchNextRecords, WalletFreeSearchRecords};
 use crate::language;
 use crate::wallet::EncryptedValue;
 
@@ -36,9 +37,22 @@ impl StorageRecord {
     }
 }
 
+// Operation of the storage batch. All operations of the batch are applied atomically.
+#[derive(Debug)]
+pub enum StorageOperation {
+    Add(Vec<u8>, Vec<u8>, EncryptedValue, Vec<Tag>),
+    Update(Vec<u8>, Vec<u8>, EncryptedValue),
+    UpdateTags(Vec<u8>, Vec<u8>, Vec<Tag>),
+    Delete(Vec<u8>, Vec<u8>),
+}
+
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
+
+    // Hint about how many records are going to be fetched at once. Iterators that can fetch records
+    // in chunks use it as the chunk size, others ignore it.
+    fn set_chunk_size(&mut self, _chunk_size: usize) {}
 }
 
 pub trait WalletStorage {
@@ -49,6 +63,7 @@ pub trait WalletStorage {
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> Result<(), IndyError>;
     fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> Result<(), IndyError>;
     fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), IndyError>;
+    fn batch(&self, operations: &[StorageOperation]) -> Result<(), IndyError>;
     fn get_storage_metadata(&self) -> Result<Vec<u8>, IndyError>;
     fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
@@ -60,4 +75,16 @@ pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), IndyError>;
+
+    // Only plugged storages can be extended with an atomic batch handler, built-in ones implement batches natively
+    fn set_batch_handler(&self, _batch_records: WalletBatchRecords) -> Result<(), IndyError> {
+        Err(err_msg(IndyErrorKind::InvalidState, "Wallet storage type doesn't support batch handler registration"))
+    }
+
+    // Only plugged storages can be extended with chunked search handlers, built-in ones fetch records in-process
+    fn set_search_chunk_handlers(&self,
+                                 _fetch_search_next_records: WalletFetchSearchNextRecords,
+                                 _free_search_records: WalletFreeSearchRecords) -> Result<(), IndyError> {
+        Err(err_msg(IndyErrorKind::InvalidState, "Wallet storage type doesn't support search chunk handlers registration"))
+    }
 }
\ No newline at end of file
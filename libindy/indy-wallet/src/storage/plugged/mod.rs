@@ -1,5 +1,6 @@
 use std::{slice, str};
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
 use std::ffi::{CStr, CString};
 use std::ptr;
 
@@ -12,7 +13,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::crypto::base64;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{EncryptedValue, StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +27,27 @@ pub struct PluggedWalletJSONValues {
     pub values: Vec<PluggedWalletJSONValue>
 }
 
+#[derive(Debug, Deserialize)]
+struct PluggedStorageRecord {
+    id: String,
+    #[serde(rename = "type")]
+    type_: Option<String>,
+    value: Option<String>,
+    tags: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PluggedStorageOperation {
+    op: &'static str,
+    #[serde(rename = "type")]
+    type_: String,
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<HashMap<String, String>>,
+}
+
 // This struct is used as a helper to free the resource even in case of error.
 // It is workaround for Rust's lack of try/catch.
 struct ResourceGuard {
@@ -53,8 +75,10 @@ impl Drop for ResourceGuard {
     }
 }
 
+// Count of records fetched at once by chunk handler if iterator user didn't provide a hint
+const DEFAULT_SEARCH_CHUNK_SIZE: usize = 100;
 
-#[derive(PartialEq, Debug)]
+#[derive(Debug)]
 struct PluggedStorageIterator {
     storage_handle: i32,
     search_handle: SearchHandle,
@@ -67,6 +91,9 @@ struct PluggedStorageIterator {
     get_record_tags_handler: WalletGetRecordTags,
     free_record_handler: WalletFreeRecord,
     free_search_handler: WalletFreeSearch,
+    search_chunk_handlers: Option<(WalletFetchSearchNextRecords, WalletFreeSearchRecords)>,
+    chunk_size: usize,
+    fetched_records: VecDeque<StorageRecord>,
 }
 
 impl PluggedStorageIterator {
@@ -83,12 +110,92 @@ impl PluggedStorageIterator {
             get_record_tags_handler: storage.get_record_tags_handler,
             free_record_handler: storage.free_record_handler,
             free_search_handler: storage.free_search_handler,
+            search_chunk_handlers: storage.search_chunk_handlers,
+            chunk_size: DEFAULT_SEARCH_CHUNK_SIZE,
+            fetched_records: VecDeque::new(),
+        }
+    }
+
+    fn _next_from_chunk(&mut self,
+                        fetch_search_next_records_handler: WalletFetchSearchNextRecords,
+                        free_search_records_handler: WalletFreeSearchRecords) -> IndyResult<Option<StorageRecord>> {
+        if self.fetched_records.is_empty() {
+            let mut records_json_ptr: *const c_char = ptr::null();
+            let mut records_handle = -1;
+
+            let err = fetch_search_next_records_handler(self.storage_handle,
+                                                        self.search_handle.0,
+                                                        self.chunk_size,
+                                                        &mut records_json_ptr,
+                                                        &mut records_handle);
+
+            if err != ErrorCode::Success {
+                return Err(err.into());
+            }
+
+            let _records_free_helper = ResourceGuard::new(self.storage_handle, records_handle, free_search_records_handler);
+
+            if records_json_ptr.is_null() {
+                return Err(err_msg(IndyErrorKind::InvalidState, "Wallet storage plugin returned null records json"));
+            }
+
+            let records_json = unsafe {
+                CStr::from_ptr(records_json_ptr)
+                    .to_str()
+                    .to_indy(IndyErrorKind::InvalidState, "Records json contains non-utf8 symbol")?
+            };
+
+            let records: Vec<PluggedStorageRecord> = serde_json::from_str(records_json)
+                .to_indy(IndyErrorKind::InvalidState, "Unable to deserialize records from json")?;
+
+            for record in records {
+                let record = self._record_from_plugged(record)?;
+                self.fetched_records.push_back(record);
+            }
         }
+
+        Ok(self.fetched_records.pop_front())
+    }
+
+    fn _record_from_plugged(&self, record: PluggedStorageRecord) -> IndyResult<StorageRecord> {
+        let id = base64::decode(&record.id)
+            .to_indy(IndyErrorKind::InvalidState, "Unable to decode record id from base64")?;
+
+        let type_ = match record.type_ {
+            Some(ref type_) if self.options.retrieve_type =>
+                Some(base64::decode(type_).to_indy(IndyErrorKind::InvalidState, "Unable to decode record type from base64")?),
+            _ => None
+        };
+
+        let value = match record.value {
+            Some(ref value) if self.options.retrieve_value => {
+                let value = base64::decode(value)
+                    .to_indy(IndyErrorKind::InvalidState, "Unable to decode record value from base64")?;
+                Some(EncryptedValue::from_bytes(&value)?)
+            }
+            _ => None
+        };
+
+        let tags = match record.tags {
+            Some(tags) if self.options.retrieve_tags => Some(_tags_from_map(tags)?),
+            _ => None
+        };
+
+        Ok(StorageRecord {
+            type_,
+            id,
+            value,
+            tags,
+        })
     }
 }
 
 impl StorageIterator for PluggedStorageIterator {
     fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        if let Some((fetch_search_next_records_handler, free_search_records_handler)) = self.search_chunk_handlers {
+            return self._next_from_chunk(fetch_search_next_records_handler, free_search_records_handler);
+        }
+
         let mut record_handle = -1;
 
         let err = (self.fetch_search_next_record_handler)(self.storage_handle,
@@ -203,6 +310,12 @@ impl StorageIterator for PluggedStorageIterator {
             Ok(None)
         }
     }
+
+    fn set_chunk_size(&mut self, chunk_size: usize) {
+        if chunk_size > 0 {
+            self.chunk_size = chunk_size;
+        }
+    }
 }
 
 impl Drop for PluggedStorageIterator {
@@ -234,6 +347,8 @@ struct PluggedStorage {
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
+    batch_records_handler: Option<WalletBatchRecords>,
+    search_chunk_handlers: Option<(WalletFetchSearchNextRecords, WalletFreeSearchRecords)>,
     close_handler: WalletClose,
 }
 
@@ -259,6 +374,8 @@ impl PluggedStorage {
            get_search_total_count_handler: WalletGetSearchTotalCount,
            fetch_search_next_record_handler: WalletFetchSearchNextRecord,
            free_search_handler: WalletFreeSearch,
+           batch_records_handler: Option<WalletBatchRecords>,
+           search_chunk_handlers: Option<(WalletFetchSearchNextRecords, WalletFreeSearchRecords)>,
            close_handler: WalletClose) -> PluggedStorage {
         PluggedStorage {
             handle,
@@ -282,12 +399,14 @@ impl PluggedStorage {
             get_search_total_count_handler,
             fetch_search_next_record_handler,
             free_search_handler,
+            batch_records_handler,
+            search_chunk_handlers,
             close_handler,
         }
     }
 }
 
-fn _tags_to_json(tags: &[Tag]) -> IndyResult<String> {
+fn _tags_to_map(tags: &[Tag]) -> HashMap<String, String> {
     let mut string_tags = HashMap::with_capacity(tags.len());
 
     for tag in tags {
@@ -297,7 +416,11 @@ fn _tags_to_json(tags: &[Tag]) -> IndyResult<String> {
         };
     }
 
-    serde_json::to_string(&string_tags)
+    string_tags
+}
+
+fn _tags_to_json(tags: &[Tag]) -> IndyResult<String> {
+    serde_json::to_string(&_tags_to_map(tags))
         .to_indy(IndyErrorKind::InvalidState, "Unable to serialize tags as json")
 }
 
@@ -305,6 +428,10 @@ fn _tags_from_json(json: &str) -> IndyResult<Vec<Tag>> {
     let string_tags: HashMap<String, String> = serde_json::from_str(json)
         .to_indy(IndyErrorKind::InvalidState, "Unable to deserialize tags from json")?;
 
+    _tags_from_map(string_tags)
+}
+
+fn _tags_from_map(string_tags: HashMap<String, String>) -> IndyResult<Vec<Tag>> {
     let mut tags = Vec::with_capacity(string_tags.len());
 
     for (k, v) in string_tags {
@@ -340,6 +467,31 @@ fn _tags_names_to_json(tag_names: &[TagName]) -> IndyResult<String> {
         .to_indy(IndyErrorKind::InvalidState, "Unable to serialize tag names as json")
 }
 
+fn _operations_to_json(operations: &[StorageOperation]) -> IndyResult<String> {
+    let operations = operations
+        .iter()
+        .map(|operation| {
+            let (op, type_, id, value, tags) = match operation {
+                StorageOperation::Add(type_, id, value, tags) => ("add", type_, id, Some(value), Some(tags)),
+                StorageOperation::Update(type_, id, value) => ("update", type_, id, Some(value), None),
+                StorageOperation::UpdateTags(type_, id, tags) => ("update_tags", type_, id, None, Some(tags)),
+                StorageOperation::Delete(type_, id) => ("delete", type_, id, None, None),
+            };
+
+            PluggedStorageOperation {
+                op,
+                type_: base64::encode(type_),
+                id: base64::encode(id),
+                value: value.map(|value| base64::encode(&value.to_bytes())),
+                tags: tags.map(|tags| _tags_to_map(tags)),
+            }
+        })
+        .collect::<Vec<PluggedStorageOperation>>();
+
+    serde_json::to_string(&operations)
+        .to_indy(IndyErrorKind::InvalidState, "Unable to serialize storage operations as json")
+}
+
 impl WalletStorage for PluggedStorage {
     fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
         let type_cstr = CString::new(base64::encode(type_))?;
@@ -606,6 +758,23 @@ impl WalletStorage for PluggedStorage {
         ))
     }
 
+    fn batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let batch_records_handler = self.batch_records_handler
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet storage plugin doesn't support atomic batches"))?;
+
+        let operations = CString::new(_operations_to_json(operations)?)?;
+
+        let err = batch_records_handler(self.handle, operations.as_ptr());
+
+        if err == ErrorCode::WalletItemAlreadyExists {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists"));
+        } else if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
     fn close(&mut self) -> IndyResult<()> {
         let err = (self.close_handler)(self.handle);
 
@@ -655,6 +824,8 @@ pub struct PluggedStorageType {
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
+    batch_records_handler: Cell<Option<WalletBatchRecords>>,
+    search_chunk_handlers: Cell<Option<(WalletFetchSearchNextRecords, WalletFreeSearchRecords)>>,
 }
 
 
@@ -708,6 +879,8 @@ impl PluggedStorageType {
             get_search_total_count_handler,
             fetch_search_next_record_handler,
             free_search_handler,
+            batch_records_handler: Cell::new(None),
+            search_chunk_handlers: Cell::new(None),
         }
     }
 }
@@ -781,6 +954,8 @@ impl WalletStorageType for PluggedStorageType {
                 self.get_search_total_count_handler,
                 self.fetch_search_next_record_handler,
                 self.free_search_handler,
+                self.batch_records_handler.get(),
+                self.search_chunk_handlers.get(),
                 self.close_handler)))
     }
 
@@ -805,6 +980,18 @@ impl WalletStorageType for PluggedStorageType {
 
         Ok(())
     }
+
+    fn set_batch_handler(&self, batch_records: WalletBatchRecords) -> IndyResult<()> {
+        self.batch_records_handler.set(Some(batch_records));
+        Ok(())
+    }
+
+    fn set_search_chunk_handlers(&self,
+                                 fetch_search_next_records: WalletFetchSearchNextRecords,
+                                 free_search_records: WalletFreeSearchRecords) -> IndyResult<()> {
+        self.search_chunk_handlers.set(Some((fetch_search_next_records, free_search_records)));
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -867,6 +1054,8 @@ mod tests {
         GetSearchTotalCountHandler(i32, i32),
         FetchSearchNextRecordHandler(i32, i32),
         FreeSearchHandler(i32, i32),
+        FetchSearchNextRecordsHandler(i32, i32, usize),
+        FreeSearchRecordsHandler(i32, i32),
     }
 
     fn _random_vector(len: usize) -> Vec<u8> {
@@ -906,6 +1095,15 @@ mod tests {
             let tags_json = CString::new(_tags_to_json(&tags).unwrap()).unwrap();
             (tags_json, tags)
         });
+        static ref RETURN_RECORDS: RwLock<CString> = RwLock::new({
+            let record = json!({
+                "id": RETURN_ID.read().unwrap().0.to_str().unwrap(),
+                "value": base64::encode(&RETURN_VALUE.read().unwrap().0),
+                "tags": serde_json::from_str::<serde_json::Value>(RETURN_TAGS.read().unwrap().0.to_str().unwrap()).unwrap(),
+            });
+            CString::new(json!([record, record]).to_string()).unwrap()
+        });
+        static ref RETURN_NO_RECORDS: CString = CString::new("[]").unwrap();
         static ref RETURN_METADATA: RwLock<(CString, Vec<u8>)> = RwLock::new({
             let data = _random_vector(512);
             let str = CString::new(
@@ -920,6 +1118,7 @@ mod tests {
     static RETURN_SEARCH_HANDLE: i32 = 3i32;
     static RETURN_METADATA_HANDLE: i32 = 4i32;
     static RETURN_SEARCH_TOTAL_COUNT: usize = 1024;
+    static RETURN_RECORDS_HANDLE: i32 = 5i32;
 
     fn _convert_c_string(str: *const c_char) -> Option<String> {
         if str != ptr::null() {
@@ -1340,6 +1539,47 @@ mod tests {
         ErrorCode::Success
     }
 
+    extern "C" fn _mock_fetch_search_next_records_handler(storage_handle: i32,
+                                                          search_handle: i32,
+                                                          count: usize,
+                                                          records_json_p: *mut *const c_char,
+                                                          records_handle_p: *mut i32) -> ErrorCode {
+        let mut debug = DEBUG_VEC.write().unwrap();
+
+        // the first chunk contains two records, the next one is empty
+        let is_first_chunk = !debug.iter().any(|call| match *call {
+            Call::FetchSearchNextRecordsHandler(..) => true,
+            _ => false
+        });
+
+        unsafe {
+            *records_json_p = if is_first_chunk { RETURN_RECORDS.read().unwrap().as_ptr() } else { RETURN_NO_RECORDS.as_ptr() };
+            *records_handle_p = RETURN_RECORDS_HANDLE;
+        }
+
+        debug.push(
+            Call::FetchSearchNextRecordsHandler(
+                storage_handle,
+                search_handle,
+                count,
+            )
+        );
+
+        ErrorCode::Success
+    }
+
+    extern "C" fn _mock_free_search_records_handler(storage_handle: i32,
+                                                    records_handle: i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(
+            Call::FreeSearchRecordsHandler(
+                storage_handle,
+                records_handle,
+            )
+        );
+
+        ErrorCode::Success
+    }
+
     fn _create_storage_type() -> PluggedStorageType {
         PluggedStorageType::new(
             _mock_create_handler,
@@ -2185,4 +2425,68 @@ mod tests {
         assert_eq!(&expected_free_record_call, debug.get(6).unwrap());
         assert_eq!(&expected_free_search_call, debug.get(7).unwrap());
     }
+
+    #[test]
+    fn plugged_storage_search_works_for_search_chunk_handlers() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage_type = _create_storage_type();
+        storage_type.set_search_chunk_handlers(_mock_fetch_search_next_records_handler, _mock_free_search_records_handler).unwrap();
+        let storage = storage_type.open_storage("wallet1", None, Some("credentials")).unwrap();
+
+        DEBUG_VEC.write().unwrap().clear();
+
+        let type_ = _random_vector(32);
+        let query = language::Operator::Eq(
+            language::TagName::EncryptedTagName(_random_vector(32)),
+            language::TargetValue::Encrypted(_random_vector(32)),
+        );
+        let options = _search_options(true, false, true, true, false);
+
+        {
+            let mut storage_iterator = storage.search(&type_, &query, Some(&options)).unwrap();
+            storage_iterator.set_chunk_size(10);
+
+            let expected_storage_entity = StorageRecord {
+                type_: None,
+                id: RETURN_ID.read().unwrap().1.clone(),
+                value: Some(RETURN_VALUE.read().unwrap().1.clone()),
+                tags: Some(RETURN_TAGS.read().unwrap().1.clone()),
+            };
+
+            assert_eq!(expected_storage_entity, storage_iterator.next().unwrap().unwrap());
+            assert_eq!(expected_storage_entity, storage_iterator.next().unwrap().unwrap());
+            assert!(storage_iterator.next().unwrap().is_none());
+        }
+
+        let expected_search_call = Call::SearchRecordsHandler(
+            RETURN_STORAGE_HANDLE,
+            Some(base64::encode(&type_)),
+            Some(query.to_string()),
+            Some(options.to_string()),
+        );
+        let expected_fetch_next_records_call = Call::FetchSearchNextRecordsHandler(
+            RETURN_STORAGE_HANDLE,
+            RETURN_SEARCH_HANDLE,
+            10,
+        );
+        let expected_free_records_call = Call::FreeSearchRecordsHandler(
+            RETURN_STORAGE_HANDLE,
+            RETURN_RECORDS_HANDLE,
+        );
+        let expected_free_search_call = Call::FreeSearchHandler(
+            RETURN_STORAGE_HANDLE,
+            RETURN_SEARCH_HANDLE,
+        );
+
+        let debug = DEBUG_VEC.read().unwrap();
+
+        assert_eq!(debug.len(), 6);
+        assert_eq!(&expected_search_call, debug.get(0).unwrap());
+        assert_eq!(&expected_fetch_next_records_call, debug.get(1).unwrap());
+        assert_eq!(&expected_free_records_call, debug.get(2).unwrap());
+        assert_eq!(&expected_fetch_next_records_call, debug.get(3).unwrap());
+        assert_eq!(&expected_free_records_call, debug.get(4).unwrap());
+        assert_eq!(&expected_free_search_call, debug.get(5).unwrap());
+    }
 }
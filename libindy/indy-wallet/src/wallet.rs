@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
 
 use indy_utils::crypto::{hmacsha256, chacha20poly1305_ietf};
 use indy_utils::wql::Query;
 
+use indy_api_types::domain::wallet::RecordOperation;
 use indy_api_types::errors::prelude::*;
 
 use zeroize::Zeroize;
 
 use super::storage;
+use super::storage::{StorageOperation, StorageRecord};
 use super::iterator::WalletIterator;
 use super::encryption::*;
 use super::query_encryption::encrypt_query;
-use super::WalletRecord;
+use super::range_index::RangeIndex;
+use super::{RecordOptions, SearchOptions, WalletRecord};
 
 #[derive(Serialize, Deserialize)]
 pub(super) struct Keys {
@@ -116,76 +119,161 @@ pub(super) struct Wallet {
     id: String,
     storage: Box<dyn storage::WalletStorage>,
     keys: Rc<Keys>,
+    range_index: Rc<RangeIndex>,
+    // Keys records are re-encrypted with. Set while re-encryption is in progress,
+    // so records can be encrypted with either current or pending keys.
+    pending_keys: Option<Rc<Keys>>,
+    // Encrypted types and ids of records left to re-encrypt. Collected by the first batch,
+    // so next batches resume where the previous one stopped instead of scanning the storage again.
+    reencryption_queue: Option<VecDeque<(Vec<u8>, Vec<u8>)>>,
 }
 
 impl Wallet {
-    pub fn new(id: String, storage: Box<dyn storage::WalletStorage>, keys: Rc<Keys>) -> Wallet {
-        Wallet { id, storage, keys }
+    pub fn new(id: String, storage: Box<dyn storage::WalletStorage>, keys: Rc<Keys>, range_index: Rc<RangeIndex>) -> Wallet {
+        Wallet { id, storage, keys, range_index, pending_keys: None, reencryption_queue: None }
     }
 
     pub fn add(&self, type_: &str, name: &str, value: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
-        let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let evalue = EncryptedValue::encrypt(value, &self.keys.value_key);
-        let etags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
+        let keys = match self.pending_keys {
+            Some(ref pending_keys) => {
+                // Record may still be encrypted with current keys
+                let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+                let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+
+                match self.storage.get(&etype, &ename, &RecordOptions::id()) {
+                    Ok(_) => return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists")),
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {}
+                    Err(err) => return Err(err)
+                }
+
+                pending_keys
+            }
+            None => &self.keys
+        };
+
+        let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+        let ename = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+        let evalue = EncryptedValue::encrypt(value, &keys.value_key);
+        let etags = encrypt_tags(&self.range_index.index_tags(tags), &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key);
         self.storage.add(&etype, &ename, &evalue, &etags)?;
         Ok(())
     }
 
     pub fn add_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
-        let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.add_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
-        Ok(())
+        self._with_keys(|keys| {
+            let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+            let encrypted_tags = encrypt_tags(&self.range_index.index_tags(tags), &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key);
+            self.storage.add_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
+            Ok(())
+        })
     }
 
     pub fn update_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
-        let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.update_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
-        Ok(())
+        self._with_keys(|keys| {
+            let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+            let encrypted_tags = encrypt_tags(&self.range_index.index_tags(tags), &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key);
+            self.storage.update_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
+            Ok(())
+        })
     }
 
     pub fn delete_tags(&self, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
-        let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tag_names = encrypt_tag_names(tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key);
-        self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_tag_names[..])?;
-        Ok(())
+        let bucket_tag_names = self.range_index.bucket_tag_names(tag_names);
+        let tag_names = tag_names.iter().cloned().chain(bucket_tag_names.iter().map(String::as_str)).collect::<Vec<&str>>();
+
+        self._with_keys(|keys| {
+            let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+            let encrypted_tag_names = encrypt_tag_names(&tag_names, &keys.tag_name_key, &keys.tags_hmac_key);
+            self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_tag_names[..])?;
+            Ok(())
+        })
     }
 
     pub fn update(&self, type_: &str, name: &str, new_value: &str) -> IndyResult<()> {
-        let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_value = EncryptedValue::encrypt(new_value, &self.keys.value_key);
-        self.storage.update(&encrypted_type, &encrypted_name, &encrypted_value)?;
-        Ok(())
+        self._with_keys(|keys| {
+            let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let encrypted_name = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+            let encrypted_value = EncryptedValue::encrypt(new_value, &keys.value_key);
+            self.storage.update(&encrypted_type, &encrypted_name, &encrypted_value)?;
+            Ok(())
+        })
     }
 
     pub fn get(&self, type_: &str, name: &str, options: &str) -> IndyResult<WalletRecord> {
-        let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+        self._with_keys(|keys| {
+            let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let ename = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
 
-        let result = self.storage.get(&etype, &ename, options)?;
+            let result = self.storage.get(&etype, &ename, options)?;
 
-        let value = match result.value {
-            None => None,
-            Some(encrypted_value) => Some(encrypted_value.decrypt(&self.keys.value_key)?)
-        };
+            let value = match result.value {
+                None => None,
+                Some(encrypted_value) => Some(encrypted_value.decrypt(&keys.value_key)?)
+            };
 
-        let tags = decrypt_tags(&result.tags, &self.keys.tag_name_key, &self.keys.tag_value_key)?;
+            let mut tags = decrypt_tags(&result.tags, &keys.tag_name_key, &keys.tag_value_key)?;
+            if let Some(ref mut tags) = tags {
+                RangeIndex::strip_bucket_tags(tags);
+            }
 
-        Ok(WalletRecord::new(String::from(name), result.type_.map(|_| type_.to_string()), value, tags))
+            Ok(WalletRecord::new(String::from(name), result.type_.map(|_| type_.to_string()), value, tags))
+        })
     }
 
     pub fn delete(&self, type_: &str, name: &str) -> IndyResult<()> {
-        let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
+        self._with_keys(|keys| {
+            let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let ename = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
 
-        self.storage.delete(&etype, &ename)?;
-        Ok(())
+            self.storage.delete(&etype, &ename)?;
+            Ok(())
+        })
+    }
+
+    // Applies all operations atomically: either all of them succeed or wallet is left unchanged
+    pub fn batch(&self, operations: &[RecordOperation]) -> IndyResult<()> {
+        if self.pending_keys.is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Atomic record operations aren't allowed while re-encryption is in progress"));
+        }
+
+        let keys = &self.keys;
+
+        let operations = operations
+            .iter()
+            .flat_map(|operation| {
+                let (type_, name) = match operation {
+                    RecordOperation::Add { type_, id, .. } |
+                    RecordOperation::Update { type_, id, .. } |
+                    RecordOperation::Delete { type_, id } => (type_, id)
+                };
+
+                let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+                let ename = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+                let etags = |tags: &HashMap<String, String>| encrypt_tags(&self.range_index.index_tags(tags), &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key);
+
+                match operation {
+                    RecordOperation::Add { value, tags, .. } =>
+                        vec![StorageOperation::Add(etype, ename, EncryptedValue::encrypt(value, &keys.value_key), etags(tags))],
+                    RecordOperation::Update { value, tags, .. } => {
+                        let mut res = Vec::new();
+                        if let Some(value) = value {
+                            res.push(StorageOperation::Update(etype.clone(), ename.clone(), EncryptedValue::encrypt(value, &keys.value_key)));
+                        }
+                        if let Some(tags) = tags {
+                            res.push(StorageOperation::UpdateTags(etype, ename, etags(tags)));
+                        }
+                        res
+                    }
+                    RecordOperation::Delete { .. } =>
+                        vec![StorageOperation::Delete(etype, ename)]
+                }
+            })
+            .collect::<Vec<StorageOperation>>();
+
+        self.storage.batch(&operations)
     }
 
     pub fn search<'a>(&'a self, type_: &str, query: &str, options: Option<&str>) -> IndyResult<WalletIterator> {
@@ -194,13 +282,81 @@ impl Wallet {
             .optimise()
             .unwrap_or_default();
 
-        let encrypted_query = encrypt_query(parsed_query, &self.keys)?;
-        let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let wallet_iterator = self._search(&self.keys, type_, parsed_query.clone(), options)?;
+
+        match self.pending_keys {
+            Some(ref pending_keys) => Ok(wallet_iterator.chain(self._search(pending_keys, type_, parsed_query, options)?)),
+            None => Ok(wallet_iterator)
+        }
+    }
+
+    fn _search(&self, keys: &Rc<Keys>, type_: &str, query: Query, options: Option<&str>) -> IndyResult<WalletIterator> {
+        if !self.range_index.is_empty() && self.range_index.is_range_query(&query) {
+            return self._search_range(keys, type_, query, options);
+        }
+
+        let encrypted_query = encrypt_query(query, keys)?;
+        let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
         let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options)?;
-        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys));
+        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(keys));
+        Ok(wallet_iterator)
+    }
+
+    // Storage selects records by buckets of range indexed tags and exact query is checked
+    // against decrypted tags. Total count is an upper bound for such searches.
+    fn _search_range(&self, keys: &Rc<Keys>, type_: &str, query: Query, options: Option<&str>) -> IndyResult<WalletIterator> {
+        let bucket_query = self.range_index.to_bucket_query(query.clone())?;
+        let encrypted_query = encrypt_query(bucket_query, keys)?;
+        let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+
+        let mut search_options: SearchOptions = match options {
+            Some(options) => ::serde_json::from_str(options)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options are malformed")?,
+            None => SearchOptions::default()
+        };
+        let retrieve_tags = search_options.retrieve_tags;
+        search_options.retrieve_tags = true;
+
+        let options = ::serde_json::to_string(&search_options)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize search options")?;
+
+        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, Some(&options))?;
+        let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(keys))
+            .with_range_filter(query, Rc::clone(&self.range_index), retrieve_tags);
         Ok(wallet_iterator)
     }
 
+    // Range queries on encrypted tags are supported only for range indexed ones.
+    pub fn is_range_indexed(&self, tag_name: &str) -> bool {
+        self.range_index.is_range_indexed(tag_name)
+    }
+
+    // Rewrites tags of all records, so their bucket tags match the current range index
+    pub fn reindex_range_tags(&self) -> IndyResult<()> {
+        // Records are collected first as storage iterator can't be used while storage is modified
+        let mut records: Vec<(String, String, HashMap<String, String>)> = Vec::new();
+        {
+            let mut iterator = self.get_all()?;
+
+            while let Some(record) = iterator.next()? {
+                let type_ = record.get_type()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet record without type"))?;
+
+                if let Some(tags) = record.get_tags() {
+                    if !tags.is_empty() {
+                        records.push((type_.to_string(), record.get_id().to_string(), tags.clone()));
+                    }
+                }
+            }
+        }
+
+        for (type_, name, tags) in records {
+            self.update_tags(&type_, &name, &tags)?;
+        }
+
+        Ok(())
+    }
+
     pub fn close(&mut self) -> IndyResult<()> {
         self.storage.close()
             .map_err(IndyError::from)
@@ -208,12 +364,167 @@ impl Wallet {
 
     pub fn get_all(&self) -> IndyResult<WalletIterator> {
         let all_items = self.storage.get_all()?;
-        Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)))
+
+        match self.pending_keys {
+            Some(ref pending_keys) => Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)).with_fallback_keys(Rc::clone(pending_keys))),
+            None => Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)))
+        }
+    }
+
+    // Counts all records without decryption
+    pub fn count_all(&self) -> IndyResult<usize> {
+        let mut storage_iterator = self.storage.get_all()?;
+
+        let mut count = 0;
+
+        while storage_iterator.next()?.is_some() {
+            count += 1;
+        }
+
+        Ok(count)
     }
 
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
+
+    pub fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        self.storage.get_storage_metadata()
+    }
+
+    pub fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.storage.set_storage_metadata(metadata)
+    }
+
+    pub fn is_reencryption_in_progress(&self) -> bool {
+        self.pending_keys.is_some()
+    }
+
+    pub fn set_pending_keys(&mut self, pending_keys: Option<Rc<Keys>>) {
+        self.pending_keys = pending_keys;
+        self.reencryption_queue = None;
+    }
+
+    // Pending keys become current ones when all records are re-encrypted
+    pub fn finish_reencryption(&mut self) -> IndyResult<()> {
+        let pending_keys = self.pending_keys.take()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet re-encryption is not in progress"))?;
+
+        self.keys = pending_keys;
+        self.reencryption_queue = None;
+        Ok(())
+    }
+
+    // Re-encrypts up to `batch_size` records still encrypted with current keys.
+    // Returns the number of re-encrypted records, so the batch smaller than requested is the last one.
+    pub fn reencrypt_records(&mut self, batch_size: usize) -> IndyResult<usize> {
+        let res = self._reencrypt_records(batch_size);
+
+        // Records of the failed batch are already taken from the queue, so the storage is scanned again
+        if res.is_err() {
+            self.reencryption_queue = None;
+        }
+
+        res
+    }
+
+    fn _reencrypt_records(&mut self, batch_size: usize) -> IndyResult<usize> {
+        let pending_keys = self.pending_keys.clone()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet re-encryption is not in progress"))?;
+
+        if self.reencryption_queue.is_none() {
+            // Records are collected first as storage iterator can't be used while storage is modified.
+            // Records added later are encrypted with pending keys, so the single scan is enough.
+            let mut queue = VecDeque::new();
+            let mut storage_iterator = self.storage.get_all()?;
+
+            while let Some(record) = storage_iterator.next()? {
+                if is_encrypted_with(&record, &self.keys) {
+                    let type_ = record.type_
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Storage record without type"))?;
+                    queue.push_back((type_, record.id));
+                }
+            }
+
+            self.reencryption_queue = Some(queue);
+        }
+
+        let mut records: Vec<StorageRecord> = Vec::new();
+
+        while records.len() < batch_size {
+            let (type_, id) = match self.reencryption_queue.as_mut().and_then(VecDeque::pop_front) {
+                Some(key) => key,
+                None => break
+            };
+
+            // Record may be deleted after the scan
+            match self.storage.get(&type_, &id, &RecordOptions::id_value_tags()) {
+                Ok(mut record) => {
+                    record.type_ = Some(type_);
+                    records.push(record)
+                }
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {}
+                Err(err) => return Err(err)
+            }
+        }
+
+        for record in records.iter() {
+            let decrypted = decrypt_storage_record(record, &self.keys)?;
+
+            let type_ = record.type_.as_ref()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Storage record without type"))?;
+
+            let value = decrypted.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Storage record without value"))?;
+
+            // Bucket tags of range indexed tags are kept as they are
+            let tags = decrypted.get_tags().cloned().unwrap_or_default();
+
+            let etype = encrypt_as_searchable(decrypted.get_type().unwrap_or_default().as_bytes(), &pending_keys.type_key, &pending_keys.item_hmac_key);
+            let ename = encrypt_as_searchable(decrypted.get_id().as_bytes(), &pending_keys.name_key, &pending_keys.item_hmac_key);
+            let evalue = EncryptedValue::encrypt(value, &pending_keys.value_key);
+            let etags = encrypt_tags(&tags, &pending_keys.tag_name_key, &pending_keys.tag_value_key, &pending_keys.tags_hmac_key);
+
+            // Record may be already added before interruption of the previous batch
+            match self.storage.add(&etype, &ename, &evalue, &etags) {
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists => {}
+                res => res?
+            }
+
+            self.storage.delete(type_, &record.id)?;
+        }
+
+        Ok(records.len())
+    }
+
+    // Counts records by keys they are encrypted with.
+    // Returns (total, encrypted with current keys while re-encryption is in progress, undecryptable).
+    pub fn count_records_by_keys(&self) -> IndyResult<(usize, usize, usize)> {
+        let mut storage_iterator = self.storage.get_all()?;
+
+        let (mut total, mut remaining, mut undecryptable) = (0, 0, 0);
+
+        while let Some(record) = storage_iterator.next()? {
+            total += 1;
+
+            match self.pending_keys {
+                Some(ref pending_keys) if is_encrypted_with(&record, pending_keys) => {}
+                Some(_) if is_encrypted_with(&record, &self.keys) => remaining += 1,
+                None if is_encrypted_with(&record, &self.keys) => {}
+                _ => undecryptable += 1
+            }
+        }
+
+        Ok((total, remaining, undecryptable))
+    }
+
+    // Record can be encrypted with either current or pending keys while re-encryption is in progress
+    fn _with_keys<T, F>(&self, f: F) -> IndyResult<T> where F: Fn(&Rc<Keys>) -> IndyResult<T> {
+        match (f(&self.keys), self.pending_keys.as_ref()) {
+            (Err(ref err), Some(pending_keys)) if err.kind() == IndyErrorKind::WalletItemNotFound => f(pending_keys),
+            (res, _) => res
+        }
+    }
 }
 
 #[cfg(test)]
@@ -230,6 +541,7 @@ mod tests {
     use crate::storage::WalletStorageType;
     use crate::storage::default::SQLiteStorageType;
     use crate::language::*;
+    use indy_api_types::domain::wallet::{Config, RangeTagConfig};
     use indy_utils::test;
 
     macro_rules! jsonstr {
@@ -1044,6 +1356,69 @@ mod tests {
         test::cleanup_wallet("wallet_search_works_for_gt_encrypted");
     }
 
+    #[test]
+    fn wallet_search_works_for_gt_encrypted_range_indexed() {
+        test::cleanup_wallet("wallet_search_works_for_gt_encrypted_range_indexed");
+        {
+            let mut wallet = _range_indexed_wallet("wallet_search_works_for_gt_encrypted_range_indexed");
+            wallet.add(_type1(), _id1(), _value1(), &jsonmap!({"tag_name":"5"})).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &jsonmap!({"tag_name":"17"})).unwrap();
+            wallet.add(_type1(), _id3(), _value3(), &jsonmap!({"tag_name":"25"})).unwrap();
+
+            let mut iterator = wallet.search(_type1(),
+                                             &jsonstr!({"tag_name": {"$gt": "15"}}),
+                                             Some(&_search_options(true, false, false, true, true))).unwrap();
+
+            let expected_records = _sort(vec![
+                WalletRecord {
+                    type_: None,
+                    id: _id2().to_string(),
+                    value: Some(_value2().to_string()),
+                    tags: Some(jsonmap!({"tag_name":"17"})),
+                },
+                WalletRecord {
+                    type_: None,
+                    id: _id3().to_string(),
+                    value: Some(_value3().to_string()),
+                    tags: Some(jsonmap!({"tag_name":"25"})),
+                },
+            ]);
+
+            assert_eq!(_fetch_all(&mut iterator), expected_records);
+
+            // tags are used for filtering but not returned
+            let mut iterator = wallet.search(_type1(),
+                                             &jsonstr!({"$and": [{"tag_name": {"$gte": "5"}}, {"tag_name": {"$lt": "17"}}]}),
+                                             Some(&_search_options(true, false, false, true, false))).unwrap();
+
+            let expected_records = vec![
+                WalletRecord {
+                    type_: None,
+                    id: _id1().to_string(),
+                    value: Some(_value1().to_string()),
+                    tags: None,
+                },
+            ];
+
+            assert_eq!(_fetch_all(&mut iterator), expected_records);
+
+            // bucket tags are removed with original tag
+            wallet.delete_tags(_type1(), _id3(), &["tag_name"]).unwrap();
+
+            let mut iterator = wallet.search(_type1(),
+                                             &jsonstr!({"tag_name": {"$gt": "20"}}),
+                                             Some(&_search_options(true, false, false, true, false))).unwrap();
+
+            assert!(iterator.next().unwrap().is_none());
+
+            let record = wallet.get(_type1(), _id3(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.tags.unwrap(), HashMap::new());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_search_works_for_gt_encrypted_range_indexed");
+    }
+
     #[test]
     fn wallet_search_works_for_gte_plain() {
         test::cleanup_wallet("wallet_search_works_for_gte_plain");
@@ -2000,6 +2375,8 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                pending_keys: None,
+                range_tags: None,
             });
 
             serde_json::to_vec(&metadata).unwrap()
@@ -2012,7 +2389,7 @@ mod tests {
 
         let storage = storage_type.open_storage(name, None, None).unwrap();
 
-        Wallet::new(name.to_string(), storage, Rc::new(keys))
+        Wallet::new(name.to_string(), storage, Rc::new(keys), Rc::new(RangeIndex::default()))
     }
 
     fn _exists_wallet(name: &str) -> Wallet {
@@ -2027,7 +2404,7 @@ mod tests {
         let master_key = _master_key();
         let keys = Keys::deserialize_encrypted(&metadata.keys, &master_key).unwrap();
 
-        Wallet::new(name.to_string(), storage, Rc::new(keys))
+        Wallet::new(name.to_string(), storage, Rc::new(keys), Rc::new(RangeIndex::default()))
     }
 
     fn _master_key() -> chacha20poly1305_ietf::Key {
@@ -2056,6 +2433,22 @@ mod tests {
         }).to_string()
     }
 
+    fn _range_indexed_wallet(name: &str) -> Wallet {
+        let mut wallet = _wallet(name);
+
+        let mut range_tags = HashMap::new();
+        range_tags.insert("tag_name".to_string(), RangeTagConfig { bucket_size: 10 });
+
+        wallet.range_index = Rc::new(RangeIndex::from_config(&Config {
+            id: name.to_string(),
+            storage_type: None,
+            storage_config: None,
+            range_tags: Some(range_tags),
+        }));
+
+        wallet
+    }
+
     fn _search_options(records: bool, total_count: bool, type_: bool, value: bool, tags: bool) -> String {
         json!({
             "retrieveRecords": records,
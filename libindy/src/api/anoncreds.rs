@@ -2,27 +2,32 @@ use indy_api_types::{ErrorCode, IndyHandle, CommandHandle, WalletHandle, SearchH
 use indy_api_types::errors::prelude::*;
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::anoncreds::AnoncredsCommand;
-use crate::commands::anoncreds::issuer::IssuerCommand;
+use crate::commands::anoncreds::issuer::{IssuerCommand, CredentialDefinitionProgressCallback};
 use crate::commands::anoncreds::prover::ProverCommand;
 use crate::commands::anoncreds::verifier::VerifierCommand;
 use crate::domain::anoncreds::schema::{Schema, AttributeNames, Schemas};
 use crate::domain::crypto::did::DidValue;
-use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions};
+use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions, CredentialDefinitionStage};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
-use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
+use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata, CredentialIssuanceRequests};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
 use crate::domain::anoncreds::credential::{Credential, CredentialValues};
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::presentation_exchange::PresentationDefinition;
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
-use crate::domain::anoncreds::requested_credential::RequestedCredentials;
+use crate::domain::anoncreds::requested_credential::{RequestedCredentials, ProofRequestsWithCredentials};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistries;
 use crate::domain::anoncreds::revocation_state::{RevocationState, RevocationStates};
+use crate::domain::anoncreds::verification_result::VerificationOptions;
+use crate::domain::anoncreds::w3c_credential::W3CCredential;
+use indy_api_types::domain::wallet::RecordId;
 use indy_utils::ctypes;
 
 use libc::c_char;
 use std::ptr;
+use std::sync::Arc;
 
 use crate::indy_api_types::validation::Validatable;
 
@@ -87,7 +92,7 @@ pub extern fn indy_issuer_create_schema(command_handle: CommandHandle,
     trace!("indy_issuer_create_schema: entity >>> issuer_did: {:?}, name: {:?}, version: {:?}, attrs: {:?}", issuer_did, name, version, attrs);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::CreateSchema(
                     issuer_did,
@@ -195,7 +200,7 @@ pub extern fn indy_issuer_create_and_store_credential_def(command_handle: Comman
     signature_type: {:?}, config_json: {:?}", wallet_handle, issuer_did, schema_json, tag, signature_type, config_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::CreateAndStoreCredentialDefinition(
                     wallet_handle,
@@ -204,6 +209,7 @@ pub extern fn indy_issuer_create_and_store_credential_def(command_handle: Comman
                     tag,
                     signature_type,
                     config_json,
+                    None,
                     Box::new(move |result| {
                         let (err, cred_def_id, cred_def_json) = prepare_result_2!(result, String::new(), String::new());
                         trace!("indy_issuer_create_and_store_credential_def: cred_def_id: {:?}, cred_def_json: {:?}", cred_def_id, cred_def_json);
@@ -220,6 +226,96 @@ pub extern fn indy_issuer_create_and_store_credential_def(command_handle: Comman
     res
 }
 
+/// Create credential definition entity that encapsulates credentials issuer DID, credential schema, secrets used for signing credentials
+/// and secrets used for credentials revocation.
+/// The same as `indy_issuer_create_and_store_credential_def` but also reports progress of the operation.
+///
+/// Keys generation can take tens of seconds, so it is performed on a dedicated thread pool
+/// (see `cred_def_thread_pool_size` of `indy_set_runtime_config`) and other operations are not blocked meanwhile.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// issuer_did: a DID of the issuer
+/// schema_json: credential schema as a json, see `indy_issuer_create_and_store_credential_def`
+/// tag: any string that allows to distinguish between credential definitions for the same issuer and schema
+/// signature_type: credential definition type (optional, 'CL' by default) that defines credentials signature and revocation math.
+/// config_json: (optional) type-specific configuration of credential definition as json, see `indy_issuer_create_and_store_credential_def`
+/// progress_cb: Callback that is called on each stage of the operation with the stage name:
+///     "queued" - waiting for a free worker of the credential definition thread pool
+///     "generating" - keys are being generated
+///     "storing" - generated keys are being stored in the wallet
+///     Note: the callback isn't called if credential definition already exists in the wallet.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_def_id: identifier of created credential definition
+/// cred_def_json: public part of created credential definition, see `indy_issuer_create_and_store_credential_def`
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_create_and_store_credential_def_with_progress(command_handle: CommandHandle,
+                                                                        wallet_handle: WalletHandle,
+                                                                        issuer_did: *const c_char,
+                                                                        schema_json: *const c_char,
+                                                                        tag: *const c_char,
+                                                                        signature_type: *const c_char,
+                                                                        config_json: *const c_char,
+                                                                        progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                                                      stage: *const c_char)>,
+                                                                        cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                             cred_def_id: *const c_char,
+                                                                                             cred_def_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_and_store_credential_def_with_progress: >>> wallet_handle: {:?}, issuer_did: {:?}, schema_json: {:?}, tag: {:?}, \
+    signature_type: {:?}, config_json: {:?}", wallet_handle, issuer_did, schema_json, tag, signature_type, config_json);
+
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_validatable_json!(schema_json, ErrorCode::CommonInvalidParam4, Schema);
+    check_useful_c_str!(tag, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_c_str!(signature_type, ErrorCode::CommonInvalidParam6);
+    check_useful_opt_validatable_json!(config_json, ErrorCode::CommonInvalidParam7, CredentialDefinitionConfig);
+    check_useful_c_callback!(progress_cb, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_issuer_create_and_store_credential_def_with_progress: entities >>> wallet_handle: {:?}, issuer_did: {:?}, schema_json: {:?}, tag: {:?}, \
+    signature_type: {:?}, config_json: {:?}", wallet_handle, issuer_did, schema_json, tag, signature_type, config_json);
+
+    let progress: CredentialDefinitionProgressCallback = Arc::new(move |stage: CredentialDefinitionStage| {
+        trace!("indy_issuer_create_and_store_credential_def_with_progress: stage: {:?}", stage);
+        let stage = ctypes::str_to_cstring(stage.to_str());
+        progress_cb(command_handle, stage.as_ptr())
+    });
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateAndStoreCredentialDefinition(
+                    wallet_handle,
+                    issuer_did,
+                    schema_json,
+                    tag,
+                    signature_type,
+                    config_json,
+                    Some(progress),
+                    Box::new(move |result| {
+                        let (err, cred_def_id, cred_def_json) = prepare_result_2!(result, String::new(), String::new());
+                        trace!("indy_issuer_create_and_store_credential_def_with_progress: cred_def_id: {:?}, cred_def_json: {:?}", cred_def_id, cred_def_json);
+                        let cred_def_id = ctypes::string_to_cstring(cred_def_id);
+                        let cred_def_json = ctypes::string_to_cstring(cred_def_json);
+                        cb(command_handle, err, cred_def_id.as_ptr(), cred_def_json.as_ptr())
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_and_store_credential_def_with_progress: <<< res: {:?}", res);
+
+    res
+}
+
 /// Generate temporary credential definitional keys for an existing one (owned by the caller of the library).
 ///
 /// Use `indy_issuer_rotate_credential_def_apply` function to set generated temporary keys as the main.
@@ -276,7 +372,7 @@ pub extern fn indy_issuer_rotate_credential_def_start(command_handle: CommandHan
            wallet_handle, cred_def_id, config_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::RotateCredentialDefinitionStart(
                     wallet_handle,
@@ -328,7 +424,7 @@ pub extern fn indy_issuer_rotate_credential_def_apply(command_handle: CommandHan
            wallet_handle, cred_def_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::RotateCredentialDefinitionApply(
                     wallet_handle,
@@ -450,7 +546,7 @@ pub extern fn indy_issuer_create_and_store_revoc_reg(command_handle: CommandHand
     cred_def_id: {:?}, config_json: {:?}, tails_writer_handle: {:?}", wallet_handle, issuer_did, revoc_def_type, tag, cred_def_id, config_json, tails_writer_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::CreateAndStoreRevocationRegistry(
                     wallet_handle,
@@ -518,7 +614,7 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
     trace!("indy_issuer_create_credential_offer: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::CreateCredentialOffer(
                     wallet_handle,
@@ -615,7 +711,7 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), secret!(&rev_reg_id), blob_storage_reader_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::CreateCredential(
                     wallet_handle,
@@ -644,6 +740,88 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     res
 }
 
+/// Issue Credentials for a batch of Cred Requests created for the same Cred Offer.
+///
+/// Works as indy_issuer_create_credential called for each request, but the revocation registry
+/// is updated in the wallet once and deltas of all credentials are merged into one,
+/// so the whole batch requires a single REVOC_REG_ENTRY transaction.
+/// If issuance of any credential fails, no credentials are issued and the wallet stays unchanged.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer
+/// cred_reqs_json: credential requests with values of credentials
+///     [{
+///         "cred_req": <credential request created by indy_prover_create_credential_req>,
+///         "cred_values": <credential values, see indy_issuer_create_credential>
+///     }]
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// creds_json: issued credentials in the order of requests
+///     [{
+///         "cred": <credential json, see indy_issuer_create_credential>,
+///         "cred_revoc_id": Optional<string> - local id for revocation info
+///     }]
+/// revoc_reg_delta_json: Revocation registry delta json with all newly issued credentials
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cred_offer_json: *const c_char,
+                                             cred_reqs_json: *const c_char,
+                                             rev_reg_id: *const c_char,
+                                             blob_storage_reader_handle: IndyHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  creds_json: *const c_char,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credentials: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_reqs_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, cred_reqs_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_reqs_json, ErrorCode::CommonInvalidParam4, CredentialIssuanceRequests);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam5, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credentials: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_reqs_json: {:?}, rev_reg_id: {:?}, \
+    blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_reqs_json), secret!(&rev_reg_id), blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentials(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_reqs_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    Box::new(move |result| {
+                        let (err, creds_json, revoc_reg_delta_json) = prepare_result_2!(result, String::new(), None);
+                        trace!("indy_issuer_create_credentials: creds_json: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(creds_json.as_str()), revoc_reg_delta_json);
+                        let creds_json = ctypes::string_to_cstring(creds_json);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, creds_json.as_ptr(),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -694,7 +872,7 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
            wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(cred_revoc_id.as_str()));
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::RevokeCredential(
                     wallet_handle,
@@ -756,7 +934,7 @@ pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::RecoverCredential(
                     wallet_handle,
@@ -776,6 +954,10 @@ pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
 /// Merge two revocation registry deltas (returned by indy_issuer_create_credential or indy_issuer_revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
+/// Doesn't require a wallet, so it can be used by provers as well: merge consecutive deltas parsed
+/// from several GET_REVOC_REG_DELTA responses (indy_parse_get_revoc_reg_delta_response)
+/// and pass the result to indy_create_revocation_state or indy_update_revocation_state.
+///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 /// rev_reg_delta_json: revocation registry delta.
@@ -807,7 +989,6 @@ pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
 /// #Errors
 /// Anoncreds*
 /// Common*
-/// Wallet*
 #[no_mangle]
 pub extern fn indy_issuer_merge_revocation_registry_deltas(command_handle: CommandHandle,
                                                            rev_reg_delta_json: *const c_char,
@@ -825,7 +1006,7 @@ pub extern fn indy_issuer_merge_revocation_registry_deltas(command_handle: Comma
            rev_reg_delta_json, other_rev_reg_delta_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Issuer(
                 IssuerCommand::MergeRevocationRegistryDeltas(
                     rev_reg_delta_json,
@@ -869,7 +1050,7 @@ pub extern fn indy_prover_create_master_secret(command_handle: CommandHandle,
     trace!("indy_prover_create_master_secret: entities >>> wallet_handle: {:?}, master_secret_id: {:?}", wallet_handle, master_secret_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::CreateMasterSecret(
                     wallet_handle,
@@ -951,7 +1132,7 @@ pub extern fn indy_prover_create_credential_req(command_handle: CommandHandle,
            wallet_handle, prover_did, cred_offer_json, cred_def_json, master_secret_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::CreateCredentialRequest(
                     wallet_handle,
@@ -1028,7 +1209,7 @@ pub extern fn indy_prover_set_credential_attr_tag_policy(command_handle: Command
            wallet_handle, cred_def_id, tag_attrs_json, retroactive);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::SetCredentialAttrTagPolicy(
                     wallet_handle,
@@ -1082,7 +1263,7 @@ pub extern fn indy_prover_get_credential_attr_tag_policy(command_handle: Command
     trace!("indy_prover_get_credential_attr_tag_policy: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::GetCredentialAttrTagPolicy(
                     wallet_handle,
@@ -1168,7 +1349,7 @@ pub extern fn indy_prover_store_credential(command_handle: CommandHandle,
     rev_reg_def_json: {:?}", wallet_handle, cred_id, cred_req_metadata_json, cred_json, cred_def_json, rev_reg_def_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::StoreCredential(
                     wallet_handle,
@@ -1224,7 +1405,7 @@ pub extern fn indy_prover_get_credential(command_handle: CommandHandle,
     trace!("indy_prover_get_credential: entities >>> wallet_handle: {:?}, cred_id: {:?}", wallet_handle, cred_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::GetCredential(
                     wallet_handle,
@@ -1239,6 +1420,109 @@ pub extern fn indy_prover_get_credential(command_handle: CommandHandle,
     res
 }
 
+/// Wraps an issued credential as W3C Verifiable Credential.
+/// Raw attribute values become `credentialSubject`, CL signature and encoded values are kept in `proof`
+/// so the credential can be converted back with `indy_prover_credential_from_w3c`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// cred_json: credential json received from issuer (see `indy_issuer_create_credential`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// w3c_cred_json: W3C Verifiable Credential json
+///     {
+///         "@context": ["https://www.w3.org/2018/credentials/v1"],
+///         "type": ["VerifiableCredential", "IndyCredential"],
+///         "issuer": string, - fully qualified DID of the issuer
+///         "issuanceDate": string, - time of conversion as credential doesn't contain issuance time
+///         "credentialSchema": {"id": string, "type": "IndyCredentialSchema"},
+///         "credentialSubject": {"attr1" : "raw_value1", "attr2" : "raw_value2"},
+///         "proof": {
+///             "type": "CLSignature2019",
+///             "credDefId": string,
+///             "revRegId": Optional<string>,
+///             "encoding": {"attr1" : "encoded_value1", "attr2" : "encoded_value2"},
+///             "signature": <credential signature>,
+///             "signatureCorrectnessProof": <credential signature correctness proof>,
+///             "revReg": Optional<revocation registry>,
+///             "witness": Optional<witness>
+///         }
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_prover_credential_to_w3c(command_handle: CommandHandle,
+                                            cred_json: *const c_char,
+                                            cb: Option<extern fn(
+                                                command_handle_: CommandHandle, err: ErrorCode,
+                                                w3c_cred_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_credential_to_w3c: >>> cred_json: {:?}", secret!(&cred_json));
+
+    check_useful_validatable_json!(cred_json, ErrorCode::CommonInvalidParam2, Credential);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_prover_credential_to_w3c: entities >>> cred_json: {:?}", secret!(&cred_json));
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::CredentialToW3C(
+                    cred_json,
+                    boxed_callback_string!("indy_prover_credential_to_w3c", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_credential_to_w3c: <<< res: {:?}", res);
+
+    res
+}
+
+/// Converts W3C Verifiable Credential created by `indy_prover_credential_to_w3c` back to the credential.
+/// The result can be stored with `indy_prover_store_credential` and used for proof generation.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// w3c_cred_json: W3C Verifiable Credential json (see `indy_prover_credential_to_w3c`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: credential json
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_prover_credential_from_w3c(command_handle: CommandHandle,
+                                              w3c_cred_json: *const c_char,
+                                              cb: Option<extern fn(
+                                                  command_handle_: CommandHandle, err: ErrorCode,
+                                                  cred_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_credential_from_w3c: >>> w3c_cred_json: {:?}", secret!(&w3c_cred_json));
+
+    check_useful_validatable_json!(w3c_cred_json, ErrorCode::CommonInvalidParam2, W3CCredential);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_prover_credential_from_w3c: entities >>> w3c_cred_json: {:?}", secret!(&w3c_cred_json));
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::CredentialFromW3C(
+                    w3c_cred_json,
+                    boxed_callback_string!("indy_prover_credential_from_w3c", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_credential_from_w3c: <<< res: {:?}", res);
+
+    res
+}
+
 /// Deletes credential by given id.
 ///
 /// #Params
@@ -1263,7 +1547,7 @@ pub extern fn indy_prover_delete_credential(command_handle: CommandHandle,
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::DeleteCredential(
                     wallet_handle,
@@ -1282,6 +1566,72 @@ pub extern fn indy_prover_delete_credential(command_handle: CommandHandle,
     res
 }
 
+/// Deletes credential by given id together with related records of the application
+/// (revocation states, tails file references or any other non-secret records kept for the credential)
+/// in a single wallet transaction. Credential tags are deleted with the credential.
+///
+/// Note: custom wallet storage must register batch handler (see indy_register_wallet_storage_batch).
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_id: Identifier by which requested credential is stored in the wallet
+/// related_records_json: json array of non-secret records to delete with the credential.
+///     Records that don't exist are skipped.
+///     [{"type": <str>, "id": <str>}]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// deleted_credential_json: info of the deleted credential so the application can notify about the deletion
+///     and release resources shared with other credentials (tails files of the revocation registry for example).
+///     {
+///         "referent": string, // cred_id in the wallet
+///         "attrs": {"key1":"raw_value1", "key2":"raw_value2"},
+///         "schema_id": string,
+///         "cred_def_id": string,
+///         "rev_reg_id": Optional<string>,
+///         "cred_rev_id": Optional<string>
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_delete_credential_with_related(command_handle: CommandHandle,
+                                                         wallet_handle: WalletHandle,
+                                                         cred_id: *const c_char,
+                                                         related_records_json: *const c_char,
+                                                         cb: Option<extern fn(
+                                                             command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             deleted_credential_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_delete_credential_with_related: >>> wallet_handle: {:?}, cred_id: {:?}, related_records_json: {:?}",
+           wallet_handle, cred_id, related_records_json);
+
+    check_useful_c_str!(cred_id, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(related_records_json, ErrorCode::CommonInvalidParam4, Vec<RecordId>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_delete_credential_with_related: entities >>> wallet_handle: {:?}, cred_id: {:?}, related_records_json: {:?}",
+           wallet_handle, cred_id, related_records_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::DeleteCredentialWithRelated(
+                    wallet_handle,
+                    cred_id,
+                    related_records_json,
+                    boxed_callback_string!("indy_prover_delete_credential_with_related", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_delete_credential_with_related: <<< res: {:?}", res);
+
+    res
+}
+
 /// Gets human readable credentials according to the filter.
 /// If filter is NULL, then all credentials are returned.
 /// Credentials can be filtered by Issuer, credential_def and/or Schema.
@@ -1333,7 +1683,7 @@ pub extern fn indy_prover_get_credentials(command_handle: CommandHandle,
     trace!("indy_prover_get_credentials: entities >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::GetCredentials(
                     wallet_handle,
@@ -1385,7 +1735,7 @@ pub extern fn indy_prover_search_credentials(command_handle: CommandHandle,
     trace!("indy_prover_search_credentials: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::SearchCredentials(
                     wallet_handle,
@@ -1439,7 +1789,7 @@ pub  extern fn indy_prover_fetch_credentials(command_handle: CommandHandle,
     trace!("indy_prover_fetch_credentials: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::FetchCredentials(
                     search_handle,
@@ -1474,7 +1824,7 @@ pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandl
     trace!("indy_prover_close_credentials_search: entities >>> search_handle: {:?}", search_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::CloseCredentialsSearch(
                     search_handle,
@@ -1540,8 +1890,10 @@ pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandl
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": int predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": int predicate value (for all predicate types except "RANGE")
+///         "from": int inclusive lower bound (for "RANGE" only)
+///         "to": int inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<filter_json>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1605,7 +1957,7 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
            wallet_handle, proof_request_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::GetCredentialsForProofReq(
                     wallet_handle,
@@ -1668,8 +2020,10 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": predicate value (for all predicate types except "RANGE")
+///         "from": inclusive lower bound (for "RANGE" only)
+///         "to": inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1699,6 +2053,10 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 ///         "attr::<attribute name>::marker": "1", - to filter based on existence of a specific attribute
 ///         "attr::<attribute name>::value": <attribute raw value>, - to filter based on value of a specific attribute
 ///
+/// Note: predicates are evaluated by the wallet search if "attr::<attribute name>::value" tag is listed in `range_tags`
+///     of the wallet config (attribute name in lowercase without spaces), otherwise all credentials
+///     containing the attribute are fetched and checked one by one.
+///
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
@@ -1726,7 +2084,7 @@ pub extern fn indy_prover_search_credentials_for_proof_req(command_handle: Comma
            wallet_handle, proof_request_json, extra_query_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::SearchCredentialsForProofReq(
                     wallet_handle,
@@ -1783,6 +2141,8 @@ pub extern fn indy_prover_search_credentials_for_proof_req(command_handle: Comma
 /// Anoncreds*
 /// Common*
 /// Wallet*
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
 #[no_mangle]
 pub  extern fn indy_prover_fetch_credentials_for_proof_req(command_handle: CommandHandle,
                                                            search_handle: SearchHandle,
@@ -1797,16 +2157,22 @@ pub  extern fn indy_prover_fetch_credentials_for_proof_req(command_handle: Comma
 
     trace!("indy_prover_fetch_credentials_for_proof_req: entities >>> search_handle: {:?}, count: {:?}", search_handle, count);
 
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_prover_fetch_credentials_for_proof_request", cb, command_handle);
+
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::FetchCredentialForProofReq(
                     search_handle,
                     item_referent,
                     count,
-                    boxed_callback_string!("indy_prover_fetch_credentials_for_proof_request", cb, command_handle)
+                    cb
                 ))));
 
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
     let res = prepare_result!(result);
 
     trace!("indy_prover_fetch_credentials_for_proof_req: <<< res: {:?}", res);
@@ -1834,7 +2200,7 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
     trace!("indy_prover_close_credentials_search_for_proof_req: entities >>> search_handle: {:?}", search_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(
+        .submit(Command::Anoncreds(
             AnoncredsCommand::Prover(
                 ProverCommand::CloseCredentialsSearchForProofReq(
                     search_handle,
@@ -1945,8 +2311,10 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": predicate value (for all predicate types except "RANGE")
+///         "from": inclusive lower bound (for "RANGE" only)
+///         "to": inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -2017,6 +2385,8 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 /// Anoncreds*
 /// Common*
 /// Wallet*
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
 #[no_mangle]
 pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
                                        wallet_handle: WalletHandle,
@@ -2044,8 +2414,10 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
     schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
            wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
 
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_prover_create_proof", cb, command_handle);
+
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProof(
+        .submit(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProof(
             wallet_handle,
             proof_req_json,
             requested_credentials_json,
@@ -2053,9 +2425,13 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
             schemas_json,
             credential_defs_json,
             rev_states_json,
-            boxed_callback_string!("indy_prover_create_proof", cb, command_handle)
+            cb
         ))));
 
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
     let res = prepare_result!(result);
 
     trace!("indy_prover_create_proof: <<< res: {:?}", res);
@@ -2063,6 +2439,169 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
     res
 }
 
+/// Creates a proof like `indy_prover_create_proof` and returns it together with proof telemetry.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_req_json: proof request json. See `indy_prover_create_proof`.
+/// requested_credentials_json: either a credential or self-attested attribute for each requested attribute. See `indy_prover_create_proof`.
+/// master_secret_id: the id of the master secret stored in the wallet
+/// schemas_json: all schemas participating in the proof request. See `indy_prover_create_proof`.
+/// credential_defs_json: all credential definitions participating in the proof request. See `indy_prover_create_proof`.
+/// rev_states_json: all revocation states participating in the proof request. See `indy_prover_create_proof`.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// proof creation result json:
+///     {
+///         "proof": <proof>, // proof json returned by `indy_prover_create_proof`, only this part is sent to the verifier
+///         "telemetry": Optional<{ // present if "anoncreds_telemetry" runtime config flag is enabled, durations are in microseconds
+///             "proof_size": int, // size of the proof json in bytes
+///             "sub_proofs": [{
+///                 "cred_def_id": string,
+///                 "duration": int, // time of building the sub proof
+///                 "revocation_duration": Optional<int>, // time of resolving the revocation state of the sub proof,
+///                                                       // non-revocation cryptography is a part of "duration"
+///             }],
+///             "revocation_durations": Optional<{"<attr_referent or predicate_referent>": int}>, // revocation durations of
+///                                                                                                // sub proofs by referents
+///             "crypto_duration": int, // time of building the aggregated proof
+///             "total_duration": int,
+///         }>,
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
+#[no_mangle]
+pub extern fn indy_prover_create_proof_with_details(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    proof_req_json: *const c_char,
+                                                    requested_credentials_json: *const c_char,
+                                                    master_secret_id: *const c_char,
+                                                    schemas_json: *const c_char,
+                                                    credential_defs_json: *const c_char,
+                                                    rev_states_json: *const c_char,
+                                                    cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                         result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_proof_with_details: >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}, master_secret_id: {:?}, \
+    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
+           wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+
+    check_useful_validatable_json!(proof_req_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(requested_credentials_json, ErrorCode::CommonInvalidParam4, RequestedCredentials);
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam5);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam6, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam7, CredentialDefinitions);
+    check_useful_json!(rev_states_json, ErrorCode::CommonInvalidParam8, RevocationStates);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_prover_create_proof_with_details: entities >>> wallet_handle: {:?}, proof_req_json: {:?}, requested_credentials_json: {:?}, master_secret_id: {:?}, \
+    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
+           wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_prover_create_proof_with_details", cb, command_handle);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProofWithDetails(
+            wallet_handle,
+            proof_req_json,
+            requested_credentials_json,
+            master_secret_id,
+            schemas_json,
+            credential_defs_json,
+            rev_states_json,
+            cb
+        ))));
+
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_create_proof_with_details: <<< res: {:?}", res);
+
+    res
+}
+
+/// Creates proofs for several proof requests at once.
+/// The same as `indy_prover_create_proof` but master secret and credentials are read from the wallet once
+/// for all proof requests and proofs are built in parallel on the crypto thread pool
+/// (see `crypto_thread_pool_size` of `indy_set_runtime_config`).
+///
+/// #Params
+/// wallet_handle: wallet handle (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// proof_requests_json: list of proof requests with credentials to use for each of them
+///     [
+///         {
+///             "proof_request": <proof_request_json>, // see `indy_prover_create_proof`
+///             "requested_credentials": <requested_credentials_json>, // see `indy_prover_create_proof`
+///         },
+///         ...
+///     ]
+/// master_secret_id: the id of the master secret stored in the wallet
+/// schemas_json: all schemas participating in the proof requests, see `indy_prover_create_proof`
+/// credential_defs_json: all credential definitions participating in the proof requests, see `indy_prover_create_proof`
+/// rev_states_json: all revocation states participating in the proof requests, see `indy_prover_create_proof`
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// List of proof jsons in the same order as proof requests (see `indy_prover_create_proof` for format of proof).
+/// If building of any proof fails the error of the first failed proof request is returned.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_create_proofs(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        proof_requests_json: *const c_char,
+                                        master_secret_id: *const c_char,
+                                        schemas_json: *const c_char,
+                                        credential_defs_json: *const c_char,
+                                        rev_states_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                             proofs_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_create_proofs: >>> wallet_handle: {:?}, proof_requests_json: {:?}, master_secret_id: {:?}, \
+    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
+           wallet_handle, proof_requests_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+
+    check_useful_validatable_json!(proof_requests_json, ErrorCode::CommonInvalidParam3, ProofRequestsWithCredentials);
+    check_useful_c_str!(master_secret_id, ErrorCode::CommonInvalidParam4);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam5, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam6, CredentialDefinitions);
+    check_useful_json!(rev_states_json, ErrorCode::CommonInvalidParam7, RevocationStates);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_prover_create_proofs: entities >>> wallet_handle: {:?}, proof_requests_json: {:?}, master_secret_id: {:?}, \
+    schemas_json: {:?}, credential_defs_json: {:?}, rev_states_json: {:?}",
+           wallet_handle, proof_requests_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateProofs(
+            wallet_handle,
+            proof_requests_json,
+            master_secret_id,
+            schemas_json,
+            credential_defs_json,
+            rev_states_json,
+            boxed_callback_string!("indy_prover_create_proofs", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_create_proofs: <<< res: {:?}", res);
+
+    res
+}
+
 /// Verifies a proof (of multiple credential).
 /// All required schemas, public keys and revocation registries must be provided.
 ///
@@ -2177,8 +2716,10 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": predicate value (for all predicate types except "RANGE")
+///         "from": inclusive lower bound (for "RANGE" only)
+///         "to": inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -2202,6 +2743,15 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 ///         // the following keys can be used for every `attribute name` in credential.
 ///         "attr::<attribute name>::marker": "1", - to filter based on existence of a specific attribute
 ///         "attr::<attribute name>::value": <attribute raw value>, - to filter based on value of a specific attribute
+///     Restrictions are checked against identifiers of the credential used for the proof and revealed attribute values
+///     with "$eq", "$neq", "$in", "$like", "$and", "$or" and "$not" operators. Restriction on unknown key fails verification
+///     with CommonInvalidStructure error.
+/// non_revoc_interval: see `indy_prover_get_credentials_for_proof_req`.
+///     Timestamp of every revocation state used for the proof must not be after the end of requested interval ("to").
+///     "from" is ignored: the latest registry state at or before "to" is valid even if it is older than "from" (RFC 0441).
+///     Otherwise verification fails with AnoncredsProofRejected error describing the failed referent, for example:
+///     {"reason": "timestamp_after_interval", "referent": "attr1_referent", "timestamp": 120, "to": 100}
+///     Missing timestamp fails with CommonInvalidStructure error.
 /// 
 /// cb: Callback that takes command result as parameter.
 ///
@@ -2237,7 +2787,7 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProof(
+        .submit(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProof(
             proof_request_json,
             proof_json,
             schemas_json,
@@ -2259,6 +2809,117 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     res
 }
 
+/// Verifies a proof (of multiple credential) like `indy_verifier_verify_proof` but reports the result of every check
+/// instead of failing on the first one.
+/// All required schemas, public keys and revocation registries must be provided.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json. See `indy_verifier_verify_proof`.
+/// proof_json: created for request proof json. See `indy_verifier_verify_proof`.
+/// schemas_json: all schemas participating in the proof. See `indy_verifier_verify_proof`.
+/// credential_defs_json: all credential definitions participating in the proof. See `indy_verifier_verify_proof`.
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof. See `indy_verifier_verify_proof`.
+/// rev_regs_json: all revocation registries participating in the proof. See `indy_verifier_verify_proof`.
+/// options_json: (optional) verification options
+///     {
+///         "revocation_unavailable": Optional<string>, // what to do if revocation registry definition or entry
+///                                                     // used by the proof is not provided:
+///             "fail" (default) - fail with CommonInvalidStructure error like `indy_verifier_verify_proof`,
+///             "skip" - return result flagged with "revocation_unchecked". Non-revocation proofs are bound into
+///                      the challenge of the whole proof, so cryptographic verification is skipped too ("crypto_valid" is null)
+///                      and only checks of the proof against the proof request are performed.
+///                      Primary proofs can't be checked separately as they share the challenge, so "valid" is false.
+///                      Referents proven by credentials from unavailable registries are listed in "revocation_unchecked_referents".
+///                      If all registries are provided the proof is verified cryptographically as with "fail".
+///         "attribute_encoding": Optional<string>, // what to do if encoded value of a revealed attribute doesn't match
+///                                                 // canonical encoding of its raw value (see `indy_encode_credential_attribute`):
+///             "warn" (default) - log a warning and list the attribute in "non_canonical_attrs",
+///             "fail" - fail the check "attribute_encoding" for the attribute.
+///         "non_revocable_referents": Optional<[string]>, // referents for which credentials without revocation support
+///                                                        // are accepted even if the proof request demands a non-revocation interval.
+///                                                        // Credentials with revocation registry must still provide a timestamp.
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// verification result json:
+///     {
+///         "valid": bool, // true if all checks and cryptographic verification passed
+///         "crypto_valid": Optional<bool>, // result of cryptographic verification,
+///                                         // null if it was skipped because of failed checks
+///         "referents": {
+///             "<attr_referent or predicate_referent>": {
+///                 "valid": bool,
+///                 "failures": Optional<[{
+///                     "check": string, // one of "attribute_mismatch", "predicate_mismatch",
+///                                      // "revocation_interval", "restriction", "attribute_encoding"
+///                     "message": string,
+///                 }]>
+///             },
+///         },
+///         "revealed_attrs": {"<attr_referent>": <raw value>},
+///         "revealed_attr_groups": {"<attr_referent>": {"<attr_name>": <raw value>}},
+///         "revocation_unchecked": bool, // true if revocation artifacts were unavailable and "skip" policy was applied
+///         "unavailable_rev_reg_ids": Optional<[string]>, // ids of revocation registries that were not provided
+///         "revocation_unchecked_referents": Optional<[string]>, // referents proven by credentials from registries that were not provided
+///         "non_canonical_attrs": Optional<[string]>, // referents of revealed attributes with non-canonical encoding
+///         "non_revocable_accepted": Optional<[string]>, // referents proven by credentials without revocation support
+///                                                       // accepted because of "non_revocable_referents" option
+///         "telemetry": Optional<object>, // present if "anoncreds_telemetry" runtime config flag is enabled,
+///                                        // see `indy_prover_create_proof_with_details` for the format.
+///                                        // Revocation durations are times of resolving revocation registries.
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_details(command_handle: CommandHandle,
+                                                      proof_request_json: *const c_char,
+                                                      proof_json: *const c_char,
+                                                      schemas_json: *const c_char,
+                                                      credential_defs_json: *const c_char,
+                                                      rev_reg_defs_json: *const c_char,
+                                                      rev_regs_json: *const c_char,
+                                                      options_json: *const c_char,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                           result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_details: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}, options_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, options_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam8, VerificationOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    trace!("indy_verifier_verify_proof_with_details: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}, options_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithDetails(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            options_json,
+            boxed_callback_string!("indy_verifier_verify_proof_with_details", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_details: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
 /// Note that revocation delta must cover the whole registry existence time.
@@ -2312,7 +2973,7 @@ pub extern fn indy_create_revocation_state(command_handle: CommandHandle,
     cred_rev_id: {:?}", blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateRevocationState(
+        .submit(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::CreateRevocationState(
             blob_storage_reader_handle,
             rev_reg_def_json,
             rev_reg_delta_json,
@@ -2383,7 +3044,7 @@ pub extern fn indy_update_revocation_state(command_handle: CommandHandle,
     timestamp: {:?}, cred_rev_id: {:?}", blob_storage_reader_handle, rev_state_json, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::UpdateRevocationState(
+        .submit(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::UpdateRevocationState(
             blob_storage_reader_handle,
             rev_state_json,
             rev_reg_def_json,
@@ -2420,7 +3081,7 @@ pub extern fn indy_generate_nonce(command_handle: CommandHandle,
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::Verifier(
+        .submit(Command::Anoncreds(AnoncredsCommand::Verifier(
             VerifierCommand::GenerateNonce(
                 boxed_callback_string!("indy_generate_nonce", cb, command_handle)
             ))));
@@ -2432,6 +3093,128 @@ pub extern fn indy_generate_nonce(command_handle: CommandHandle,
     res
 }
 
+/// Creates a proof request from DIF Presentation Definition (Presentation Exchange).
+///
+/// Every input descriptor is requested as a group of attributes with the descriptor id as referent,
+/// so the revealed fields of the descriptor are taken from the same credential.
+/// Numeric bounds of field filters are requested as predicates with `<descriptor id>::<index>` referents.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// presentation_definition_json: DIF presentation definition json
+///     {
+///         "id": string,
+///         "name": Optional<string>,
+///         "purpose": Optional<string>,
+///         "input_descriptors": [
+///             {
+///                 "id": string,
+///                 "schema": Optional<[{"uri": <schema id>}]>, - restrict schema id of the credential
+///                 "constraints": {
+///                     "fields": [
+///                         {
+///                             "path": [string], - JSON path of the attribute, like `$.credentialSubject.age`
+///                             "filter": Optional<{
+///                                 "type": Optional<string>,
+///                                 "const": Optional<value>, - required raw value of the attribute
+///                                 "minimum": Optional<int>,
+///                                 "exclusiveMinimum": Optional<int>,
+///                                 "maximum": Optional<int>,
+///                                 "exclusiveMaximum": Optional<int>,
+///                             }>
+///                         }
+///                     ]
+///                 }
+///             }
+///         ]
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// proof_request_json: proof request json (version 2.0) with generated nonce. See `indy_prover_create_proof`.
+///
+/// #Errors
+/// Common*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_verifier_create_proof_request_from_presentation_definition(command_handle: CommandHandle,
+                                                                               presentation_definition_json: *const c_char,
+                                                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                                    proof_request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_create_proof_request_from_presentation_definition: >>> presentation_definition_json: {:?}", presentation_definition_json);
+
+    check_useful_validatable_json!(presentation_definition_json, ErrorCode::CommonInvalidParam2, PresentationDefinition);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_verifier_create_proof_request_from_presentation_definition: entities >>> presentation_definition_json: {:?}", presentation_definition_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::CreateProofRequestFromPresentationDefinition(
+            presentation_definition_json,
+            boxed_callback_string!("indy_verifier_create_proof_request_from_presentation_definition", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_create_proof_request_from_presentation_definition: <<< res: {:?}", res);
+
+    res
+}
+
+/// Maps a proof created for the proof request of `indy_verifier_create_proof_request_from_presentation_definition`
+/// back to DIF Presentation Submission.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// presentation_definition_json: DIF presentation definition json. See `indy_verifier_create_proof_request_from_presentation_definition`.
+/// proof_json: proof json. See `indy_verifier_verify_proof`.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// presentation_submission_json: DIF presentation submission json
+///     {
+///         "id": string,
+///         "definition_id": string, - id of the presentation definition
+///         "descriptor_map": [
+///             {
+///                 "id": string, - id of the input descriptor
+///                 "format": "hlindy/proof@v2.0",
+///                 "path": string, - path to identifiers of the credential in the proof, like `$.identifiers[0]`
+///             }
+///         ]
+///     }
+///
+/// #Errors
+/// Common*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_verifier_create_presentation_submission(command_handle: CommandHandle,
+                                                           presentation_definition_json: *const c_char,
+                                                           proof_json: *const c_char,
+                                                           cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                presentation_submission_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_create_presentation_submission: >>> presentation_definition_json: {:?}, proof_json: {:?}", presentation_definition_json, proof_json);
+
+    check_useful_validatable_json!(presentation_definition_json, ErrorCode::CommonInvalidParam2, PresentationDefinition);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_verifier_create_presentation_submission: entities >>> presentation_definition_json: {:?}, proof_json: {:?}", presentation_definition_json, proof_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::CreatePresentationSubmission(
+            presentation_definition_json,
+            proof_json,
+            boxed_callback_string!("indy_verifier_create_presentation_submission", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_create_presentation_submission: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get unqualified form (short form without method) of a fully qualified entity like DID.
 ///
 /// This function should be used to the proper casting of fully qualified entity to unqualified form in the following cases:
@@ -2469,7 +3252,7 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     trace!("indy_to_unqualified: entities >>> entity: {:?}", entity);
 
     let result = CommandExecutor::instance()
-        .send(Command::Anoncreds(AnoncredsCommand::ToUnqualified(
+        .submit(Command::Anoncreds(AnoncredsCommand::ToUnqualified(
             entity,
             Box::new(move |result| {
                 let (err, res) = prepare_result_1!(result, String::new());
@@ -2486,3 +3269,87 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     res
 }
 
+
+/// Encode a raw credential attribute value the canonical way.
+///
+/// Values that are 32-bit integers are encoded as themselves, so they can be used in predicates.
+/// All other values are encoded as decimal representation of big-endian SHA-256 digest of their UTF-8 bytes.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// raw_value: raw attribute value
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+///   encoded: canonical encoding of the raw value
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_encode_credential_attribute(command_handle: CommandHandle,
+                                               raw_value: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    encoded: *const c_char)>) -> ErrorCode {
+    trace!("indy_encode_credential_attribute: >>> raw_value: {:?}", raw_value);
+
+    check_useful_c_str!(raw_value, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_encode_credential_attribute: entities >>> raw_value: {:?}", raw_value);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::EncodeAttributeValue(
+            raw_value,
+            boxed_callback_string!("indy_encode_credential_attribute", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_encode_credential_attribute: <<< res: {:?}", res);
+
+    res
+}
+
+/// Check that all values of credential attributes are encoded the canonical way (see `indy_encode_credential_attribute`).
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// cred_values_json: a credential values (in the format accepted by `indy_issuer_create_credential`):
+///     {
+///         "attr1" : {"raw": "value1", "encoded": "value1_as_int" },
+///         "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// CommonInvalidStructure - some values are not encoded canonically. The error message lists them.
+/// Common*
+#[no_mangle]
+pub extern fn indy_validate_credential_values(command_handle: CommandHandle,
+                                              cred_values_json: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_validate_credential_values: >>> cred_values_json: {:?}", cred_values_json);
+
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam2, CredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_validate_credential_values: entities >>> cred_values_json: {:?}", cred_values_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Anoncreds(AnoncredsCommand::ValidateCredentialValues(
+            cred_values_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_validate_credential_values:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_validate_credential_values: <<< res: {:?}", res);
+
+    res
+}
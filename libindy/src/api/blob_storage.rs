@@ -7,6 +7,23 @@ use indy_utils::ctypes;
 
 use libc::c_char;
 
+/// Opens BlobStorage reader configuration.
+///
+/// #Params
+/// type_: Reader type:
+///   "default" - reads tails files from local directory
+///     config_json: {"base_dir": string}
+///   "http" - (requires "http_tails" build feature) fetches tails files by tails location url
+///            over HTTP(S), verifies tails hash and caches them in local directory
+///     config_json: {
+///         "cache_dir": optional<string>, Directory for cached tails files (<indy home>/tails_cache by default)
+///         "max_cache_size": optional<int>, Max total size of cached files in bytes (unlimited by default).
+///                           The least recently downloaded files are evicted first.
+///         "timeout": optional<int>, Timeout of tails file fetching in seconds (60 by default)
+///         "max_tails_size": optional<int>, Max size of single tails file in bytes (1 GiB by default).
+///                           Fetching is aborted once it is exceeded.
+///     }
+/// config_json: Reader configuration json
 #[no_mangle]
 pub extern fn indy_open_blob_storage_reader(command_handle: CommandHandle,
                                             type_: *const c_char,
@@ -23,7 +40,7 @@ pub extern fn indy_open_blob_storage_reader(command_handle: CommandHandle,
     trace!("indy_open_blob_storage_reader: entities >>> type_: {:?}, config_json: {:?}", type_, config_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::BlobStorage(BlobStorageCommand::OpenReader(
+        .submit(Command::BlobStorage(BlobStorageCommand::OpenReader(
             type_,
             config_json,
             Box::new(move |result| {
@@ -56,7 +73,7 @@ pub extern fn indy_open_blob_storage_writer(command_handle: CommandHandle,
     trace!("indy_open_blob_storage_writer: entities >>> type_: {:?}, config_json: {:?}", type_, config_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::BlobStorage(BlobStorageCommand::OpenWriter(
+        .submit(Command::BlobStorage(BlobStorageCommand::OpenWriter(
             type_,
             config_json,
             Box::new(move |result| {
@@ -6,6 +6,9 @@ use indy_utils::ctypes;
 use crate::domain::cache::{GetCacheOptions, PurgeOptions};
 use crate::domain::anoncreds::schema::SchemaId;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::proof::Identifier;
+use crate::domain::anoncreds::proof_request::ProofRequest;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
 use crate::domain::crypto::did::DidValue;
 use indy_api_types::validation::Validatable;
 use libc::c_char;
@@ -50,7 +53,7 @@ pub extern fn indy_get_cred_def(command_handle: CommandHandle,
            pool_handle, wallet_handle, submitter_did, id, options_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Cache(CacheCommand::GetCredDef(
+        .submit(Command::Cache(CacheCommand::GetCredDef(
             pool_handle,
             wallet_handle,
             submitter_did,
@@ -83,7 +86,7 @@ pub extern fn indy_get_cred_def(command_handle: CommandHandle,
 ///    noCache: (bool, optional, false by default) Skip usage of cache,
 ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
 ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
-///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
 ///  }
 /// cb: Callback that takes command result as parameter.
 #[no_mangle]
@@ -108,7 +111,7 @@ pub extern fn indy_get_schema(command_handle: CommandHandle,
            pool_handle, wallet_handle, submitter_did, id, options_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Cache(CacheCommand::GetSchema(
+        .submit(Command::Cache(CacheCommand::GetSchema(
             pool_handle,
             wallet_handle,
             submitter_did,
@@ -124,6 +127,64 @@ pub extern fn indy_get_schema(command_handle: CommandHandle,
     res
 }
 
+/// Gets revocation registry definition json data for specified revocation registry definition id.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// id: identifier of revocation registry definition.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_get_rev_reg_def(command_handle: CommandHandle,
+                                   pool_handle: PoolHandle,
+                                   wallet_handle: WalletHandle,
+                                   submitter_did: *const c_char,
+                                   id: *const c_char,
+                                   options_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        rev_reg_def_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_rev_reg_def: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, id, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(id, ErrorCode::CommonInvalidParam5, RevocationRegistryId);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam6, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_get_rev_reg_def: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, id, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Cache(CacheCommand::GetRevRegDef(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            id,
+            options_json,
+            boxed_callback_string!("indy_get_rev_reg_def", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_rev_reg_def: <<< res: {:?}", res);
+
+    res
+}
+
 /// Purge credential definition cache.
 ///
 /// EXPERIMENTAL
@@ -152,7 +213,7 @@ pub extern fn indy_purge_cred_def_cache(command_handle: CommandHandle,
            wallet_handle, options_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Cache(CacheCommand::PurgeCredDefCache(
+        .submit(Command::Cache(CacheCommand::PurgeCredDefCache(
             wallet_handle,
             options_json,
             Box::new(move |result| {
@@ -197,7 +258,7 @@ pub extern fn indy_purge_schema_cache(command_handle: CommandHandle,
            wallet_handle, options_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Cache(CacheCommand::PurgeSchemaCache(
+        .submit(Command::Cache(CacheCommand::PurgeSchemaCache(
             wallet_handle,
             options_json,
             Box::new(move |result| {
@@ -213,3 +274,248 @@ pub extern fn indy_purge_schema_cache(command_handle: CommandHandle,
 
     res
 }
+
+/// Gets json data of an object of the rich schema family (JSON-LD context, rich schema, mapping or encoding)
+/// for specified object id.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// id: DID URL identifying the object.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_get_rich_schema_object(command_handle: CommandHandle,
+                                          pool_handle: PoolHandle,
+                                          wallet_handle: WalletHandle,
+                                          submitter_did: *const c_char,
+                                          id: *const c_char,
+                                          options_json: *const c_char,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               rich_schema_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_rich_schema_object: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, id, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_str!(id, ErrorCode::CommonInvalidParam5);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam6, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_get_rich_schema_object: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, id, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Cache(CacheCommand::GetRichSchemaObject(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            id,
+            options_json,
+            boxed_callback_string!("indy_get_rich_schema_object", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_rich_schema_object: <<< res: {:?}", res);
+
+    res
+}
+
+/// Purge cache of rich schema family objects.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    minFresh: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_rich_schema_cache(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           options_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_rich_schema_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_rich_schema_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Cache(CacheCommand::PurgeRichSchemaCache(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_rich_schema_cache:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_rich_schema_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Purge revocation registry definition cache.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_rev_reg_def_cache(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           options_json: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_rev_reg_def_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_rev_reg_def_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Cache(CacheCommand::PurgeRevRegDefCache(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_rev_reg_def_cache:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_rev_reg_def_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Collects all ledger artifacts needed to create or verify a proof for the proof request
+/// and fetches them from the ledger concurrently in one pass.
+/// Schemas, credential definitions and revocation registry definitions are taken from the cache if present
+/// and stored inside of cache for future use.
+///
+/// The set of artifacts is built from:
+///  - identifiers of credentials selected for the proof (prover) or identifiers of the proof (verifier).
+///    Identifiers with timestamp require the state of revocation registry at this timestamp,
+///    identifiers without timestamp require revocation registry deltas for non-revocation intervals of the proof request.
+///  - ids listed in restrictions of the proof request by `$eq` and `$in` operators.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// proof_request_json: proof request json (see indy_prover_create_proof).
+/// identifiers_json: list of identifiers
+///  [{
+///     "schema_id": string,
+///     "cred_def_id": string,
+///     "rev_reg_id": Optional<string>,
+///     "timestamp": Optional<number>
+///  }]
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+///  Options are applied to schemas, credential definitions and revocation registry definitions.
+///  States and deltas of revocation registries are always fetched from the ledger.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// artifacts_json:
+/// {
+///     "schemas": { <schema_id>: <schema_json>, ... },
+///     "cred_defs": { <cred_def_id>: <cred_def_json>, ... },
+///     "rev_reg_defs": { <rev_reg_id>: <rev_reg_def_json>, ... },
+///     "rev_regs": { <rev_reg_id>: { <timestamp>: <rev_reg_json>, ... }, ... },
+///     "rev_reg_deltas": [{
+///         "rev_reg_id": revocation registry id,
+///         "from": (optional) start of the requested interval,
+///         "to": end of the requested interval,
+///         "timestamp": time of the delta as returned by the ledger,
+///         "delta": <rev_reg_delta_json>
+///     }, ...]
+/// }
+/// schemas, cred_defs and rev_regs can be passed to indy_verifier_verify_proof as is,
+/// schemas and cred_defs to indy_prover_create_proof, rev_reg_defs and rev_reg_deltas are
+/// the inputs for indy_create_revocation_state.
+#[no_mangle]
+pub extern fn indy_prefetch_proof_artifacts(command_handle: CommandHandle,
+                                            pool_handle: PoolHandle,
+                                            wallet_handle: WalletHandle,
+                                            submitter_did: *const c_char,
+                                            proof_request_json: *const c_char,
+                                            identifiers_json: *const c_char,
+                                            options_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 artifacts_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prefetch_proof_artifacts: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, proof_request_json: {:?}, identifiers_json: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, proof_request_json, identifiers_json, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam5, ProofRequest);
+    check_useful_json!(identifiers_json, ErrorCode::CommonInvalidParam6, Vec<Identifier>);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam7, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_prefetch_proof_artifacts: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, proof_request_json: {:?}, identifiers_json: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, proof_request_json, identifiers_json, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Cache(CacheCommand::PrefetchProofArtifacts(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            proof_request_json,
+            identifiers_json,
+            options_json,
+            boxed_callback_string!("indy_prefetch_proof_artifacts", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prefetch_proof_artifacts: <<< res: {:?}", res);
+
+    res
+}
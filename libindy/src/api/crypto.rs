@@ -4,7 +4,9 @@ use crate::commands::{Command, CommandExecutor};
 use crate::commands::crypto::CryptoCommand;
 use crate::domain::crypto::pack::JWE;
 use crate::domain::crypto::key::KeyInfo;
+use crate::domain::crypto::jws::SignJwsOptions;
 use indy_api_types::errors::prelude::*;
+use indy_api_types::crypto::{KeyManagementCreateKey, KeyManagementFree, KeyManagementSign};
 use indy_utils::ctypes;
 
 use serde_json;
@@ -20,7 +22,11 @@ use libc::c_char;
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used);
+///                            // 'ed25519' or 'secp256k1'. secp256k1 keys can be used for signing only (ECDSA, 64 bytes `r || s` signatures).
+///     "kms": string, (optional) Name of key management registered with indy_register_key_management.
+///                    The private key is created and kept in external KMS, the wallet stores only its identifier.
+///                    Such keys can be used for signing only. "wallet" (default) keeps the private key in the wallet.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -29,7 +35,8 @@ use libc::c_char;
 /// cb:
 /// - command_handle_: command handle to map callback to caller context.
 /// - err: Error code.
-/// - verkey: Ver key of generated key pair, also used as key identifier
+/// - verkey: Ver key of generated key pair, also used as key identifier.
+///           Ver keys of secp256k1 keys have `:secp256k1` suffix
 ///
 /// #Errors
 /// Common*
@@ -50,7 +57,7 @@ pub extern fn indy_create_key(command_handle: CommandHandle,
     trace!("indy_create_key: entities >>> wallet_handle: {:?}, key_json: {:?}", wallet_handle, secret!(&key_json));
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::CreateKey(
+        .submit(Command::Crypto(CryptoCommand::CreateKey(
             wallet_handle,
             key_json,
             boxed_callback_string!("indy_create_key", cb, command_handle)
@@ -98,7 +105,7 @@ pub  extern fn indy_set_key_metadata(command_handle: CommandHandle,
     trace!("indy_set_key_metadata: entities >>> wallet_handle: {:?}, verkey: {:?}, metadata: {:?}", wallet_handle, verkey, metadata);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::SetKeyMetadata(
+        .submit(Command::Crypto(CryptoCommand::SetKeyMetadata(
             wallet_handle,
             verkey,
             metadata,
@@ -150,7 +157,7 @@ pub  extern fn indy_get_key_metadata(command_handle: CommandHandle,
     trace!("indy_get_key_metadata: entities >>> wallet_handle: {:?}, verkey: {:?}", wallet_handle, verkey);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::GetKeyMetadata(
+        .submit(Command::Crypto(CryptoCommand::GetKeyMetadata(
             wallet_handle,
             verkey,
             boxed_callback_string!("indy_get_key_metadata", cb, command_handle)
@@ -204,7 +211,7 @@ pub  extern fn indy_crypto_sign(command_handle: CommandHandle,
            wallet_handle, signer_vk, message_raw, message_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::CryptoSign(
+        .submit(Command::Crypto(CryptoCommand::CryptoSign(
             wallet_handle,
             signer_vk,
             message_raw,
@@ -267,7 +274,7 @@ pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
            signer_vk, message_raw, message_len, signature_raw, signature_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::CryptoVerify(
+        .submit(Command::Crypto(CryptoCommand::CryptoVerify(
             signer_vk,
             message_raw,
             signature_raw,
@@ -337,7 +344,7 @@ pub  extern fn indy_crypto_auth_crypt(command_handle: CommandHandle,
            wallet_handle, sender_vk, recipient_vk, msg_data, msg_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::AuthenticatedEncrypt(
+        .submit(Command::Crypto(CryptoCommand::AuthenticatedEncrypt(
             wallet_handle,
             sender_vk,
             recipient_vk,
@@ -406,7 +413,7 @@ pub  extern fn indy_crypto_auth_decrypt(command_handle: CommandHandle,
            wallet_handle, recipient_vk, encrypted_msg, encrypted_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::AuthenticatedDecrypt(
+        .submit(Command::Crypto(CryptoCommand::AuthenticatedDecrypt(
             wallet_handle,
             recipient_vk,
             encrypted_msg,
@@ -470,7 +477,7 @@ pub  extern fn indy_crypto_anon_crypt(command_handle: CommandHandle,
     trace!("indy_crypto_anon_crypt: entities >>> recipient_vk: {:?}, msg_data: {:?}, msg_len: {:?}", recipient_vk, msg_data, msg_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::AnonymousEncrypt(
+        .submit(Command::Crypto(CryptoCommand::AnonymousEncrypt(
             recipient_vk,
             msg_data,
             Box::new(move |result| {
@@ -535,7 +542,7 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: CommandHandle,
            wallet_handle, recipient_vk, encrypted_msg, encrypted_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Crypto(CryptoCommand::AnonymousDecrypt(
+        .submit(Command::Crypto(CryptoCommand::AnonymousDecrypt(
             wallet_handle,
             recipient_vk,
             encrypted_msg,
@@ -654,7 +661,7 @@ pub extern fn indy_pack_message(
         return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Empty RecipientKeys has been passed").into();
     }
 
-    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::PackMessage(
+    let result = CommandExecutor::instance().submit(Command::Crypto(CryptoCommand::PackMessage(
         message,
         receiver_list,
         sender,
@@ -744,7 +751,7 @@ pub extern fn indy_unpack_message(
         Err(_) => return ErrorCode::CommonInvalidParam3
     };
 
-    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::UnpackMessage(
+    let result = CommandExecutor::instance().submit(Command::Crypto(CryptoCommand::UnpackMessage(
         jwe_struct,
         wallet_handle,
         Box::new(move |result| {
@@ -763,3 +770,490 @@ pub extern fn indy_unpack_message(
 
     res
 }
+
+/// Signs a payload and returns RFC 7515 JSON Web Signature.
+///
+/// Algorithm is selected by the key crypto type: `EdDSA` for Ed25519 keys, `ES256K` for secp256k1 ones.
+/// Protected header of each signature contains `alg` and `kid` (base58 verkey), Ed25519 keys are also set as `jwk`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// signer_vks_json: list of verkeys (key ids) of the signers. The keys must be created by calling indy_create_key or indy_create_and_store_my_did
+///     [string, ...]
+/// payload_raw: a pointer to first byte of payload to be signed
+/// payload_len: a payload length
+/// options_json: (optional) signing options
+///     {
+///         "format": string - `compact` (default, single signer only) or `general` JSON serialization
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+/// - jws: JWS in the requested serialization
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_sign_jws(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   signer_vks_json: *const c_char,
+                                   payload_raw: *const u8,
+                                   payload_len: u32,
+                                   options_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        jws: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_sign_jws: >>> wallet_handle: {:?}, signer_vks_json: {:?}, payload_raw: {:?}, payload_len: {:?}, options_json: {:?}",
+           wallet_handle, signer_vks_json, payload_raw, payload_len, options_json);
+
+    check_useful_json!(signer_vks_json, ErrorCode::CommonInvalidParam3, Vec<String>);
+    check_useful_c_byte_array!(payload_raw, payload_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam6, SignJwsOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_crypto_sign_jws: entities >>> wallet_handle: {:?}, signer_vks_json: {:?}, payload_raw: {:?}, payload_len: {:?}, options_json: {:?}",
+           wallet_handle, signer_vks_json, payload_raw, payload_len, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::SignJws(
+            wallet_handle,
+            signer_vks_json,
+            payload_raw,
+            options_json.unwrap_or_default(),
+            boxed_callback_string!("indy_crypto_sign_jws", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_sign_jws: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies RFC 7515 JSON Web Signature in compact, general or flattened JSON serialization.
+///
+/// `EdDSA` and `ES256K` algorithms are supported. Signer key is taken from `jwk` of protected header (Ed25519 only)
+/// or `kid` of protected or unprotected header, `kid` may be either base58 verkey or `did:key` DID.
+/// Caller is responsible for checking that the returned signer keys are the expected ones.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// jws: JWS to verify
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code. CommonInvalidStructure if any of the signatures is invalid.
+/// - signers_json: list of verkeys of the signers
+/// - payload_raw: a pointer to first byte of the decoded payload
+/// - payload_len: a payload length
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_verify_jws(command_handle: CommandHandle,
+                                     jws: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          signers_json: *const c_char,
+                                                          payload_raw: *const u8,
+                                                          payload_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_verify_jws: >>> jws: {:?}", jws);
+
+    check_useful_c_str!(jws, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_verify_jws: entities >>> jws: {:?}", jws);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::VerifyJws(
+            jws,
+            Box::new(move |result| {
+                let (err, signers_json, payload) = prepare_result_2!(result, String::new(), Vec::new());
+                trace!("indy_crypto_verify_jws: signers_json: {:?}, payload: {:?}", signers_json, payload);
+                let (payload_raw, payload_len) = ctypes::vec_to_pointer(&payload);
+                let signers_json = ctypes::string_to_cstring(signers_json);
+                cb(command_handle, err, signers_json.as_ptr(), payload_raw, payload_len)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_jws: <<< res: {:?}", res);
+
+    res
+}
+
+/// Converts Ed25519 verkey to X25519 public key of the same key pair.
+/// X25519 keys are used for Diffie-Hellman key agreement, e.g. in DIDComm `keyAgreement` sections.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// verkey: full Ed25519 verkey (abbreviated verkeys are not supported).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+/// - x25519_key: base58 encoded X25519 public key
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_ed25519_to_x25519(command_handle: CommandHandle,
+                                            verkey: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 x25519_key: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_ed25519_to_x25519: >>> verkey: {:?}", verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_crypto_ed25519_to_x25519: entities >>> verkey: {:?}", verkey);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::Ed25519ToX25519(
+            verkey,
+            boxed_callback_string!("indy_crypto_ed25519_to_x25519", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_ed25519_to_x25519: <<< res: {:?}", res);
+
+    res
+}
+
+/// Computes X25519 Diffie-Hellman shared secret of the X25519 form of a wallet key and a public X25519 key.
+/// The secret key never leaves the wallet.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// my_vk: id (verkey) of Ed25519 key in the wallet. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// their_x25519_key: base58 encoded X25519 public key of the other party
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code. CommonInvalidStructure for low order public key.
+/// - shared_secret_raw: a pointer to first byte of the raw shared secret
+/// - shared_secret_len: a shared secret length
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_x25519_diffie_hellman(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                my_vk: *const c_char,
+                                                their_x25519_key: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode,
+                                                                     shared_secret_raw: *const u8,
+                                                                     shared_secret_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_x25519_diffie_hellman: >>> wallet_handle: {:?}, my_vk: {:?}, their_x25519_key: {:?}",
+           wallet_handle, my_vk, their_x25519_key);
+
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(their_x25519_key, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_x25519_diffie_hellman: entities >>> wallet_handle: {:?}, my_vk: {:?}, their_x25519_key: {:?}",
+           wallet_handle, my_vk, their_x25519_key);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::X25519DiffieHellman(
+            wallet_handle,
+            my_vk,
+            their_x25519_key,
+            Box::new(move |result| {
+                let (err, shared_secret) = prepare_result_1!(result, Vec::new());
+                trace!("indy_crypto_x25519_diffie_hellman: shared_secret: {:?}", secret!(&shared_secret));
+                let (shared_secret_raw, shared_secret_len) = ctypes::vec_to_pointer(&shared_secret);
+                cb(command_handle, err, shared_secret_raw, shared_secret_len)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_x25519_diffie_hellman: <<< res: {:?}", res);
+
+    res
+}
+
+/// Generates BLS12-381 key pair usable for signing and verification of ledger state proofs multi-signatures.
+/// Keys are not stored in the wallet.
+///
+/// Default generator of Indy pools is used, so the verkey and its proof of possession can be
+/// used as `blskey` and `blskey_pop` of NODE transaction.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// seed: (optional) Seed that allows deterministic key creation (if not set random one will be created).
+///                  Can be UTF-8, base64 or hex string.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+/// - keys_json: generated keys
+///     {
+///         "sign_key": string - base58 encoded secret key
+///         "ver_key": string - base58 encoded public key
+///         "pop": string - base58 encoded proof of possession of the key
+///     }
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_bls_create_keys(command_handle: CommandHandle,
+                                   seed: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        keys_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_bls_create_keys: >>> seed: {:?}", seed);
+
+    check_useful_opt_c_str!(seed, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_bls_create_keys: entities >>> seed: {:?}", secret!(&seed));
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::BlsCreateKeys(
+            seed,
+            boxed_callback_string!("indy_bls_create_keys", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_bls_create_keys: <<< res: {:?}", res);
+
+    res
+}
+
+/// Signs a message with BLS sign key.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// sign_key: base58 encoded BLS sign key (see indy_bls_create_keys).
+/// message_raw: a pointer to first byte of message to be signed
+/// message_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+/// - signature: base58 encoded BLS signature
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_bls_sign(command_handle: CommandHandle,
+                            sign_key: *const c_char,
+                            message_raw: *const u8,
+                            message_len: u32,
+                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                 err: ErrorCode,
+                                                 signature: *const c_char)>) -> ErrorCode {
+    trace!("indy_bls_sign: >>> message_raw: {:?}, message_len: {:?}", message_raw, message_len);
+
+    check_useful_c_str!(sign_key, ErrorCode::CommonInvalidParam2);
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_bls_sign: entities >>> message_raw: {:?}, message_len: {:?}", message_raw, message_len);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::BlsSign(
+            sign_key,
+            message_raw,
+            boxed_callback_string!("indy_bls_sign", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_bls_sign: <<< res: {:?}", res);
+
+    res
+}
+
+/// Aggregates BLS signatures of the same message into multi-signature.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// signatures_json: list of base58 encoded BLS signatures
+///     [string, ...]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: command handle to map callback to caller context.
+/// - err: Error code.
+/// - multi_sig: base58 encoded BLS multi-signature
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_bls_aggregate_signatures(command_handle: CommandHandle,
+                                            signatures_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 multi_sig: *const c_char)>) -> ErrorCode {
+    trace!("indy_bls_aggregate_signatures: >>> signatures_json: {:?}", signatures_json);
+
+    check_useful_json!(signatures_json, ErrorCode::CommonInvalidParam2, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_bls_aggregate_signatures: entities >>> signatures_json: {:?}", signatures_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::BlsAggregateSignatures(
+            signatures_json,
+            boxed_callback_string!("indy_bls_aggregate_signatures", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_bls_aggregate_signatures: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies BLS multi-signature of a message, e.g. the one of ledger state proof (`multi_signature.signature`).
+/// Single signature can be verified as multi-signature with one verkey.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// multi_sig: base58 encoded BLS multi-signature
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length
+/// ver_keys_json: list of base58 encoded BLS verkeys of all the signers (e.g. `blskey` of participating nodes)
+///     [string, ...]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if multi-signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_bls_verify_multi_sig(command_handle: CommandHandle,
+                                        multi_sig: *const c_char,
+                                        message_raw: *const u8,
+                                        message_len: u32,
+                                        ver_keys_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             valid: bool)>) -> ErrorCode {
+    trace!("indy_bls_verify_multi_sig: >>> multi_sig: {:?}, message_raw: {:?}, message_len: {:?}, ver_keys_json: {:?}",
+           multi_sig, message_raw, message_len, ver_keys_json);
+
+    check_useful_c_str!(multi_sig, ErrorCode::CommonInvalidParam2);
+    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_json!(ver_keys_json, ErrorCode::CommonInvalidParam5, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_bls_verify_multi_sig: entities >>> multi_sig: {:?}, message_raw: {:?}, message_len: {:?}, ver_keys_json: {:?}",
+           multi_sig, message_raw, message_len, ver_keys_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(CryptoCommand::BlsVerifyMultiSig(
+            multi_sig,
+            message_raw,
+            ver_keys_json,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_bls_verify_multi_sig: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_bls_verify_multi_sig: <<< res: {:?}", res);
+
+    res
+}
+
+/// Register key management implementation.
+///
+/// Key management keeps private keys in external KMS (PKCS#11 HSM or mobile secure enclave for example)
+/// instead of the wallet. Keys are created in KMS with `kms` param of indy_create_key call,
+/// signing with such keys is delegated to KMS.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// name: Key management name.
+/// create_key: KeyManagement create key operation handler
+/// sign: KeyManagement sign operation handler
+/// free: Handler that allows to de-allocate data allocated in caller code
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_key_management(command_handle: CommandHandle,
+                                           name: *const c_char,
+                                           create_key: Option<KeyManagementCreateKey>,
+                                           sign: Option<KeyManagementSign>,
+                                           free: Option<KeyManagementFree>,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_key_management: >>> command_handle: {:?}, name: {:?}, cb: {:?}",
+           command_handle, name, cb);
+
+    check_useful_c_str!(name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(create_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(free, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_register_key_management: params name: {:?}", name);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Crypto(
+            CryptoCommand::RegisterKeyManagement(
+                name,
+                create_key,
+                sign,
+                free,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_key_management: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_key_management: <<< res: {:?}", res);
+    res
+}
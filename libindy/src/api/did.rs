@@ -32,10 +32,20 @@ use crate::domain::ledger::attrib::Endpoint;
 ///             if provided, then keys will be replaced - key rotation use case)
 ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
+///     "mnemonic": object, (optional) BIP39 mnemonic for deterministic did creation instead of seed.
+///                 Seed is derived with SLIP-0010 Ed25519 path m/1093'/1'/index', so the same DIDs can be re-created on another device.
+///         {
+///             "phrase": string, mnemonic generated by indy_generate_mnemonic
+///             "passphrase": string, (optional) BIP39 passphrase, empty by default
+///             "index": int, (optional) index of the DID, 0 by default
+///         }
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
 ///               currently only 'ed25519' value is supported for this field)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///                    Use `indy:<namespace>` to create a DID bound to the network with this namespace (`did:indy:<namespace>:<id>`).
+///     "kms": string, (optional) Name of key management registered with indy_register_key_management.
+///            The DID key is created and kept in external KMS and can be used for signing only. Seed and mnemonic can't be used then.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -67,7 +77,7 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
     trace!("indy_create_and_store_my_did: entities >>> wallet_handle: {:?}, did_json: {:?}", wallet_handle, secret!(&did_info));
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::CreateAndStoreMyDid(
+        .submit(Command::Did(DidCommand::CreateAndStoreMyDid(
             wallet_handle,
             did_info,
             Box::new(move |result| {
@@ -131,7 +141,7 @@ pub  extern fn indy_replace_keys_start(command_handle: CommandHandle,
     trace!("indy_replace_keys_start: entities>>> wallet_handle: {:?}, did: {:?}, key_info: {:?}", wallet_handle, did, secret!(&key_info));
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::ReplaceKeysStart(
+        .submit(Command::Did(DidCommand::ReplaceKeysStart(
             wallet_handle,
             key_info,
             did,
@@ -177,7 +187,7 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
     trace!("indy_replace_keys_apply: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::ReplaceKeysApply(
+        .submit(Command::Did(DidCommand::ReplaceKeysApply(
             wallet_handle,
             did,
             Box::new(move |result| {
@@ -194,6 +204,75 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
     res
 }
 
+/// Rotates keys of an existing DID (owned by the caller of the library) in the wallet and on the ledger at once.
+///
+/// Generates temporary keys for the DID (as indy_replace_keys_start does), sends NYM transaction with the new verkey
+/// signed by the current key of the DID, and applies the new keys in the wallet (as indy_replace_keys_apply does)
+/// when the transaction is written. Their DIDs and endpoints stored in the wallet with the old verkey are updated to the new one.
+///
+/// If the ledger rejects the transaction the temporary keys are removed and the wallet stays unchanged.
+/// If the ledger doesn't reply in time (PoolLedgerTimeout) the temporary keys are kept
+/// so they can be applied with indy_replace_keys_apply once the NYM is found on the ledger.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// did: target did to rotate keys.
+/// key_info: key information as json. Example:
+/// {
+///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
+///                                Can be UTF-8, base64 or hex string.
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               currently only 'ed25519' value is supported for this field)
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   verkey: The new verification key of the DID
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_rotate_did_key(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  pool_handle: PoolHandle,
+                                  did: *const c_char,
+                                  key_info: *const c_char,
+                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                       err: ErrorCode,
+                                                       verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_rotate_did_key: >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, key_info: {:?}", wallet_handle, pool_handle, did, key_info);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_json!(key_info, ErrorCode::CommonInvalidParam5, KeyInfo);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_rotate_did_key: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}, key_info: {:?}", wallet_handle, pool_handle, did, secret!(&key_info));
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Did(DidCommand::RotateKey(
+            pool_handle,
+            wallet_handle,
+            key_info,
+            did,
+            boxed_callback_string!("indy_rotate_did_key", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_rotate_did_key: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
 /// Updates DID associated verkey in case DID already exists in the Wallet.
@@ -234,7 +313,7 @@ pub  extern fn indy_store_their_did(command_handle: CommandHandle,
     trace!("indy_store_their_did: entities >>> wallet_handle: {:?}, identity_json: {:?}", wallet_handle, identity_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::StoreTheirDid(
+        .submit(Command::Did(DidCommand::StoreTheirDid(
             wallet_handle,
             identity_json,
             Box::new(move |result| {
@@ -298,7 +377,7 @@ pub extern fn indy_key_for_did(command_handle: CommandHandle,
     trace!("indy_key_for_did: entities >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::KeyForDid(
+        .submit(Command::Did(DidCommand::KeyForDid(
             pool_handle,
             wallet_handle,
             did,
@@ -355,7 +434,7 @@ pub extern fn indy_key_for_local_did(command_handle: CommandHandle,
     trace!("indy_key_for_local_did: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::KeyForLocalDid(
+        .submit(Command::Did(DidCommand::KeyForLocalDid(
             wallet_handle,
             did,
             boxed_callback_string!("indy_key_for_local_did", cb, command_handle)
@@ -409,7 +488,7 @@ pub extern fn indy_set_endpoint_for_did(command_handle: CommandHandle,
     let endpoint = Endpoint::new(address, Some(transport_key));
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::SetEndpointForDid(
+        .submit(Command::Did(DidCommand::SetEndpointForDid(
             wallet_handle,
             did,
             endpoint,
@@ -464,7 +543,7 @@ pub extern fn indy_get_endpoint_for_did(command_handle: CommandHandle,
     trace!("indy_get_endpoint_for_did: entities >>> wallet_handle: {:?}, pool_handle: {:?}, did: {:?}", wallet_handle, pool_handle, did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::GetEndpointForDid(
+        .submit(Command::Did(DidCommand::GetEndpointForDid(
             wallet_handle,
             pool_handle,
             did,
@@ -520,7 +599,7 @@ pub extern fn indy_set_did_metadata(command_handle: CommandHandle,
     trace!("indy_set_did_metadata: entities >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::SetDidMetadata(
+        .submit(Command::Did(DidCommand::SetDidMetadata(
             wallet_handle,
             did,
             metadata,
@@ -572,7 +651,7 @@ pub extern fn indy_get_did_metadata(command_handle: CommandHandle,
     trace!("indy_get_did_metadata: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::GetDidMetadata(
+        .submit(Command::Did(DidCommand::GetDidMetadata(
             wallet_handle,
             did,
             boxed_callback_string!("indy_get_did_metadata", cb, command_handle))));
@@ -624,7 +703,7 @@ pub extern fn indy_get_my_did_with_meta(command_handle: CommandHandle,
     trace!("indy_get_my_did_with_meta: entities >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::GetMyDidWithMeta(
+        .submit(Command::Did(DidCommand::GetMyDidWithMeta(
             wallet_handle,
             my_did,
             boxed_callback_string!("indy_get_my_did_with_meta", cb, command_handle)
@@ -672,7 +751,7 @@ pub extern fn indy_list_my_dids_with_meta(command_handle: CommandHandle,
     trace!("indy_list_my_dids_with_meta: entities >>> wallet_handle: {:?}", wallet_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::ListMyDidsWithMeta(
+        .submit(Command::Did(DidCommand::ListMyDidsWithMeta(
             wallet_handle,
             boxed_callback_string!("indy_list_my_dids_with_meta", cb, command_handle)
         )));
@@ -718,7 +797,7 @@ pub  extern fn indy_abbreviate_verkey(command_handle: CommandHandle,
     trace!("indy_abbreviate_verkey: entities >>> did: {:?}, full_verkey: {:?}", did, full_verkey);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::AbbreviateVerkey(
+        .submit(Command::Did(DidCommand::AbbreviateVerkey(
             did,
             full_verkey,
             boxed_callback_string!("indy_abbreviate_verkey", cb, command_handle)
@@ -770,7 +849,7 @@ pub extern fn indy_qualify_did(command_handle: CommandHandle,
     trace!("indy_qualify_did: entities >>> wallet_handle: {:?}, did: {:?}, method: {:?}", wallet_handle, did, method);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::QualifyDid(
+        .submit(Command::Did(DidCommand::QualifyDid(
             wallet_handle,
             did,
             method,
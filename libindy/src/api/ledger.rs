@@ -16,6 +16,8 @@ use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
 use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
 use crate::domain::ledger::node::NodeOperationData;
 use crate::domain::ledger::pool::Schedule;
+use crate::domain::ledger::rich_schema::{RichSchema, RichSchemaType};
+use crate::domain::pool::ReadRequestOptions;
 
 /// Signs and submits request message to validator pool.
 ///
@@ -26,6 +28,7 @@ use crate::domain::ledger::pool::Schedule;
 /// #Params
 /// command_handle: command handle to map callback to caller context.
 /// pool_handle: pool handle (created by open_pool_ledger).
+///             Pass 0 to route the request to the pool opened for the network namespace of `did:indy:<namespace>:<id>` submitter DID.
 /// wallet_handle: wallet handle (created by open_wallet).
 /// submitter_did: Id of Identity stored in secured Wallet.
 /// request_json: Request data json.
@@ -39,6 +42,8 @@ use crate::domain::ledger::pool::Schedule;
 /// Wallet*
 /// Ledger*
 /// Crypto*
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
 #[no_mangle]
 pub extern fn indy_sign_and_submit_request(command_handle: CommandHandle,
                                            pool_handle: PoolHandle,
@@ -58,15 +63,21 @@ pub extern fn indy_sign_and_submit_request(command_handle: CommandHandle,
     trace!("indy_sign_and_submit_request: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}",
            pool_handle, wallet_handle, submitter_did, request_json);
 
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_sign_and_submit_request", cb, command_handle);
+
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
+        .submit(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
             pool_handle,
             wallet_handle,
             submitter_did,
             request_json,
-            boxed_callback_string!("indy_sign_and_submit_request", cb, command_handle)
+            cb
         )));
 
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
     let res = prepare_result!(result);
 
     trace!("indy_sign_and_submit_request: <<< res: {:?}", res);
@@ -90,6 +101,8 @@ pub extern fn indy_sign_and_submit_request(command_handle: CommandHandle,
 /// #Errors
 /// Common*
 /// Ledger*
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
 #[no_mangle]
 pub extern fn indy_submit_request(command_handle: CommandHandle,
                                   pool_handle: PoolHandle,
@@ -104,13 +117,19 @@ pub extern fn indy_submit_request(command_handle: CommandHandle,
 
     trace!("indy_submit_request: entities >>> pool_handle: {:?}, request_json: {:?}", pool_handle, request_json);
 
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_submit_request", cb, command_handle);
+
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::SubmitRequest(
+        .submit(Command::Ledger(LedgerCommand::SubmitRequest(
             pool_handle,
             request_json,
-            boxed_callback_string!("indy_submit_request", cb, command_handle)
+            cb
         )));
 
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
     let res = prepare_result!(result);
 
     trace!("indy_submit_request: <<< res: {:?}", res);
@@ -142,6 +161,8 @@ pub extern fn indy_submit_request(command_handle: CommandHandle,
 /// #Errors
 /// Common*
 /// Ledger*
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
 #[no_mangle]
 pub extern fn indy_submit_action(command_handle: CommandHandle,
                                  pool_handle: PoolHandle,
@@ -161,16 +182,22 @@ pub extern fn indy_submit_action(command_handle: CommandHandle,
 
     trace!("indy_submit_action: entities >>> pool_handle: {:?}, request_json: {:?}, nodes: {:?}, timeout: {:?}", pool_handle, request_json, nodes, timeout);
 
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_submit_action", cb, command_handle);
+
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::SubmitAction(
                 pool_handle,
                 request_json,
                 nodes,
                 timeout,
-                boxed_callback_string!("indy_submit_action", cb, command_handle)
+                cb
             )));
 
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
     let res = prepare_result!(result);
 
     trace!("indy_submit_action: <<< res: {:?}", res);
@@ -178,6 +205,72 @@ pub extern fn indy_submit_action(command_handle: CommandHandle,
     res
 }
 
+/// Send read request to validator pool collecting matching replies of several nodes.
+///
+/// The request is sent to `fan_out` nodes at once and the result is returned as soon as `quorum` of them
+/// replied with the same data or one of them replied with valid state proof.
+/// If pending nodes can't make up the quorum anymore (a node failed or timed out) the request is sent
+/// to as many further nodes as needed.
+///
+/// Supported requests are those having state proofs:
+///     GET_NYM, GET_ATTR, GET_SCHEMA, GET_CRED_DEF, GET_REVOC_REG_DEF, GET_REVOC_REG, GET_REVOC_REG_DELTA,
+///     GET_AUTH_RULE, GET_TXN, GET_TXN_AUTHR_AGRMT, GET_TXN_AUTHR_AGRMT_AML
+///     and requests for which a state proof parser is registered (see indy_register_transaction_parser_for_sp)
+///
+/// The request is sent to the nodes as is. It's assumed that it's already prepared.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// request_json: Request data json.
+/// options_json: (Optional) Options of the read:
+///     {
+///         "quorum": Optional<int> - number of matching replies required to accept the result (f+1 by default),
+///                                       less than f+1 is accepted only with "require_state_proof",
+///         "fan_out": Optional<int> - number of nodes the request is sent to at once (`number_read_nodes` of the pool by default),
+///         "timeout": Optional<int> - time to wait for acknowledgement of a node in seconds (`timeout` of the pool by default),
+///         "require_state_proof": Optional<bool> - accept only a reply with valid state proof, matching replies without it are not enough (false by default)
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_submit_read_request(command_handle: CommandHandle,
+                                       pool_handle: PoolHandle,
+                                       request_json: *const c_char,
+                                       options_json: *const c_char,
+                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                            err: ErrorCode,
+                                                            request_result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_submit_read_request: >>> pool_handle: {:?}, request_json: {:?}, options_json: {:?}", pool_handle, request_json, options_json);
+
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_validatable_json!(options_json, ErrorCode::CommonInvalidParam4, ReadRequestOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_submit_read_request: entities >>> pool_handle: {:?}, request_json: {:?}, options_json: {:?}", pool_handle, request_json, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(
+            LedgerCommand::SubmitReadRequest(
+                pool_handle,
+                request_json,
+                options_json.unwrap_or_default(),
+                boxed_callback_string!("indy_submit_read_request", cb, command_handle)
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_submit_read_request: <<< res: {:?}", res);
+
+    res
+}
+
 /// Signs request message.
 ///
 /// Adds submitter information to passed request json, signs it with submitter
@@ -215,7 +308,7 @@ pub extern fn indy_sign_request(command_handle: CommandHandle,
     trace!("indy_sign_request: entities >>> wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}", wallet_handle, submitter_did, request_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::SignRequest(
+        .submit(Command::Ledger(LedgerCommand::SignRequest(
             wallet_handle,
             submitter_did,
             request_json,
@@ -265,7 +358,7 @@ pub extern fn indy_multi_sign_request(command_handle: CommandHandle,
     trace!("indy_multi_sign_request: entities >>> wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}", wallet_handle, submitter_did, request_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::MultiSignRequest(
+        .submit(Command::Ledger(LedgerCommand::MultiSignRequest(
             wallet_handle,
             submitter_did,
             request_json,
@@ -309,7 +402,7 @@ pub extern fn indy_build_get_ddo_request(command_handle: CommandHandle,
     trace!("indy_build_get_ddo_request: entities >>> submitter_did: {:?}, target_did: {:?}", submitter_did, target_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetDdoRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetDdoRequest(
             submitter_did,
             target_did,
             boxed_callback_string!("indy_build_get_ddo_request", cb, command_handle)
@@ -371,7 +464,7 @@ pub extern fn indy_build_nym_request(command_handle: CommandHandle,
            submitter_did, target_did, verkey, alias, role);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildNymRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildNymRequest(
             submitter_did,
             target_did,
             verkey,
@@ -416,7 +509,7 @@ pub extern fn indy_build_get_nym_request(command_handle: CommandHandle,
     trace!("indy_build_get_nym_request: entities >>> submitter_did: {:?}, target_did: {:?}", submitter_did, target_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetNymRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetNymRequest(
             submitter_did,
             target_did,
             boxed_callback_string!("indy_build_get_nym_request", cb, command_handle)
@@ -467,7 +560,7 @@ pub extern fn indy_parse_get_nym_response(command_handle: CommandHandle,
     trace!("indy_parse_get_nym_response: entities >>> get_nym_response: {:?}", get_nym_response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::ParseGetNymResponse(
+        .submit(Command::Ledger(LedgerCommand::ParseGetNymResponse(
             get_nym_response,
             boxed_callback_string!("indy_parse_get_nym_response", cb, command_handle)
         )));
@@ -526,7 +619,7 @@ pub extern fn indy_build_attrib_request(command_handle: CommandHandle,
     }
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildAttribRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildAttribRequest(
             submitter_did,
             target_did,
             hash,
@@ -588,7 +681,7 @@ pub extern fn indy_build_get_attrib_request(command_handle: CommandHandle,
     }
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetAttribRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetAttribRequest(
             submitter_did,
             target_did,
             raw,
@@ -641,7 +734,7 @@ pub extern fn indy_build_schema_request(command_handle: CommandHandle,
     trace!("indy_build_schema_request: entities >>> submitter_did: {:?}, data: {:?}", submitter_did, data);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildSchemaRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildSchemaRequest(
             submitter_did,
             data,
             boxed_callback_string!("indy_build_schema_request", cb, command_handle)
@@ -683,7 +776,7 @@ pub extern fn indy_build_get_schema_request(command_handle: CommandHandle,
     trace!("indy_build_get_schema_request: entities >>> submitter_did: {:?}, id: {:?}", submitter_did, id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetSchemaRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetSchemaRequest(
             submitter_did,
             id,
             boxed_callback_string!("indy_build_get_schema_request", cb, command_handle)
@@ -730,7 +823,7 @@ pub extern fn indy_parse_get_schema_response(command_handle: CommandHandle,
     trace!("indy_parse_get_schema_response: entities >>> get_schema_response: {:?}", get_schema_response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::ParseGetSchemaResponse(
+        .submit(Command::Ledger(LedgerCommand::ParseGetSchemaResponse(
             get_schema_response,
             Box::new(move |result| {
                 let (err, schema_id, schema_json) = prepare_result_2!(result, String::new(), String::new());
@@ -790,7 +883,7 @@ pub extern fn indy_build_cred_def_request(command_handle: CommandHandle,
     trace!("indy_build_cred_def_request: entities >>> submitter_did: {:?}, data: {:?}", submitter_did, data);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildCredDefRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildCredDefRequest(
             submitter_did,
             data,
             boxed_callback_string!("indy_build_cred_def_request", cb, command_handle)
@@ -833,7 +926,7 @@ pub extern fn indy_build_get_cred_def_request(command_handle: CommandHandle,
     trace!("indy_build_get_cred_def_request: entities >>> submitter_did: {:?}, id: {:?}", submitter_did, id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetCredDefRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetCredDefRequest(
             submitter_did,
             id,
             boxed_callback_string!("indy_build_get_cred_def_request", cb, command_handle)
@@ -884,7 +977,7 @@ pub extern fn indy_parse_get_cred_def_response(command_handle: CommandHandle,
     trace!("indy_parse_get_cred_def_response: entities >>> get_cred_def_response: {:?}", get_cred_def_response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::ParseGetCredDefResponse(
+        .submit(Command::Ledger(LedgerCommand::ParseGetCredDefResponse(
             get_cred_def_response,
             Box::new(move |result| {
                 let (err, cred_def_id, cred_def_json) = prepare_result_2!(result, String::new(), String::new());
@@ -944,7 +1037,7 @@ pub extern fn indy_build_node_request(command_handle: CommandHandle,
     trace!("indy_build_node_request: entities >>> submitter_did: {:?}, target_did: {:?}, data: {:?}", submitter_did, target_did, data);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildNodeRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildNodeRequest(
             submitter_did,
             target_did,
             data,
@@ -979,7 +1072,7 @@ pub extern fn indy_build_get_validator_info_request(command_handle: CommandHandl
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetValidatorInfoRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetValidatorInfoRequest(
             submitter_did,
             boxed_callback_string!("indy_build_get_validator_info_request", cb, command_handle)
         )));
@@ -987,6 +1080,53 @@ pub extern fn indy_build_get_validator_info_request(command_handle: CommandHandl
     prepare_result!(result)
 }
 
+/// Parse a GET_VALIDATOR_INFO action response to get validator info of every node of the pool.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_validator_info_response: response of GET_VALIDATOR_INFO action (result of `indy_submit_action`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Results of the action aggregated per node as json.
+/// {
+///     <node_alias>: {
+///         "status": String - "REPLY", "REQNACK", "REJECT" or "timeout",
+///         "data": Optional<object> - validator info reported by the node (present for "REPLY" status only),
+///         "reason": Optional<string> - reason of request rejection (present for "REQNACK" and "REJECT" statuses only),
+///     },
+///     ...
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_parse_get_validator_info_response(command_handle: CommandHandle,
+                                                     get_validator_info_response: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                          err: ErrorCode,
+                                                                          nodes_results_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_get_validator_info_response: >>> get_validator_info_response: {:?}", get_validator_info_response);
+
+    check_useful_c_str!(get_validator_info_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_parse_get_validator_info_response: entities >>> get_validator_info_response: {:?}", get_validator_info_response);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::ParseGetValidatorInfoResponse(
+            get_validator_info_response,
+            boxed_callback_string!("indy_parse_get_validator_info_response", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_parse_get_validator_info_response: <<< res: {:?}", res);
+
+    res
+}
+
 /// Builds a GET_TXN request. Request to get any transaction by its seq_no.
 ///
 /// #Params
@@ -1022,7 +1162,7 @@ pub extern fn indy_build_get_txn_request(command_handle: CommandHandle,
     trace!("indy_build_get_txn_request: entities >>> submitter_did: {:?}, ledger_type: {:?}, seq_no: {:?}", submitter_did, ledger_type, seq_no);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetTxnRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetTxnRequest(
             submitter_did,
             ledger_type,
             seq_no,
@@ -1069,7 +1209,7 @@ pub extern fn indy_build_pool_config_request(command_handle: CommandHandle,
     trace!("indy_build_pool_config_request: entities >>> submitter_did: {:?}, writes: {:?}, force: {:?}", submitter_did, writes, force);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildPoolConfigRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildPoolConfigRequest(
             submitter_did,
             writes,
             force,
@@ -1119,7 +1259,7 @@ pub extern fn indy_build_pool_restart_request(command_handle: CommandHandle,
     }
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildPoolRestartRequest(
                 submitter_did,
                 action,
@@ -1134,6 +1274,52 @@ pub extern fn indy_build_pool_restart_request(command_handle: CommandHandle,
     res
 }
 
+/// Parse a POOL_RESTART action response to get the result of the restart command for every node of the pool.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_restart_response: response of POOL_RESTART action (result of `indy_submit_action`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Results of the action aggregated per node as json.
+/// {
+///     <node_alias>: {
+///         "status": String - "REPLY", "REQNACK", "REJECT" or "timeout",
+///         "data": Optional<object> - restart command result reported by the node (present for "REPLY" status only),
+///         "reason": Optional<string> - reason of request rejection (present for "REQNACK" and "REJECT" statuses only),
+///     },
+///     ...
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_parse_pool_restart_response(command_handle: CommandHandle,
+                                               pool_restart_response: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    nodes_results_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_pool_restart_response: >>> pool_restart_response: {:?}", pool_restart_response);
+
+    check_useful_c_str!(pool_restart_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_parse_pool_restart_response: entities >>> pool_restart_response: {:?}", pool_restart_response);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::ParsePoolRestartResponse(
+            pool_restart_response,
+            boxed_callback_string!("indy_parse_pool_restart_response", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_parse_pool_restart_response: <<< res: {:?}", res);
+
+    res
+}
 
 /// Builds a POOL_UPGRADE request. Request to upgrade the Pool (sent by Trustee).
 /// It upgrades the specified Nodes (either all nodes in the Pool, or some specific ones).
@@ -1206,7 +1392,7 @@ pub extern fn indy_build_pool_upgrade_request(command_handle: CommandHandle,
     }
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildPoolUpgradeRequest(
                 submitter_did,
                 name,
@@ -1274,7 +1460,7 @@ pub extern fn indy_build_revoc_reg_def_request(command_handle: CommandHandle,
     trace!("indy_build_revoc_reg_def_request: entities >>> submitter_did: {:?}, data: {:?}", submitter_did, data);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildRevocRegDefRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildRevocRegDefRequest(
             submitter_did,
             data,
             boxed_callback_string!("indy_build_revoc_reg_def_request", cb, command_handle)
@@ -1317,7 +1503,7 @@ pub extern fn indy_build_get_revoc_reg_def_request(command_handle: CommandHandle
     trace!("indy_build_get_revoc_reg_def_request: entities>>> submitter_did: {:?}, id: {:?}", submitter_did, id);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetRevocRegDefRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetRevocRegDefRequest(
             submitter_did,
             id,
             boxed_callback_string!("indy_build_get_revoc_reg_def_request", cb, command_handle)
@@ -1372,7 +1558,7 @@ pub extern fn indy_parse_get_revoc_reg_def_response(command_handle: CommandHandl
     trace!("indy_parse_get_revoc_reg_def_response: entities >>> get_revoc_reg_def_response: {:?}", get_revoc_reg_def_response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::ParseGetRevocRegDefResponse(
+        .submit(Command::Ledger(LedgerCommand::ParseGetRevocRegDefResponse(
             get_revoc_reg_def_response,
             Box::new(move |result| {
                 let (err, revoc_reg_def_id, revoc_reg_def_json) = prepare_result_2!(result, String::new(), String::new());
@@ -1440,7 +1626,7 @@ pub extern fn indy_build_revoc_reg_entry_request(command_handle: CommandHandle,
            submitter_did, revoc_reg_def_id, rev_def_type, value);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildRevocRegEntryRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildRevocRegEntryRequest(
             submitter_did,
             revoc_reg_def_id,
             rev_def_type,
@@ -1487,7 +1673,7 @@ pub extern fn indy_build_get_revoc_reg_request(command_handle: CommandHandle,
     trace!("indy_build_get_revoc_reg_request: entities >>> submitter_did: {:?}, revoc_reg_def_id: {:?}, timestamp: {:?}", submitter_did, revoc_reg_def_id, timestamp);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetRevocRegRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetRevocRegRequest(
             submitter_did,
             revoc_reg_def_id,
             timestamp,
@@ -1535,7 +1721,7 @@ pub extern fn indy_parse_get_revoc_reg_response(command_handle: CommandHandle,
     trace!("indy_parse_get_revoc_reg_response: entities >>> get_revoc_reg_response: {:?}", get_revoc_reg_response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::ParseGetRevocRegResponse(
+        .submit(Command::Ledger(LedgerCommand::ParseGetRevocRegResponse(
             get_revoc_reg_response,
             Box::new(move |result| {
                 let (err, revoc_reg_def_id, revoc_reg_json, timestamp) = prepare_result_3!(result, String::new(), String::new(), 0);
@@ -1594,7 +1780,7 @@ pub extern fn indy_build_get_revoc_reg_delta_request(command_handle: CommandHand
            submitter_did, revoc_reg_def_id, from, to);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetRevocRegDeltaRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetRevocRegDeltaRequest(
             submitter_did,
             revoc_reg_def_id,
             from,
@@ -1646,7 +1832,7 @@ pub extern fn indy_parse_get_revoc_reg_delta_response(command_handle: CommandHan
     trace!("indy_parse_get_revoc_reg_delta_response: entities >>> get_revoc_reg_delta_response: {:?}", get_revoc_reg_delta_response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::ParseGetRevocRegDeltaResponse(
+        .submit(Command::Ledger(LedgerCommand::ParseGetRevocRegDeltaResponse(
             get_revoc_reg_delta_response,
             Box::new(move |result| {
                 let (err, revoc_reg_def_id, revoc_reg_delta_json, timestamp) = prepare_result_3!(result, String::new(), String::new(), 0);
@@ -1714,7 +1900,7 @@ pub extern fn indy_register_transaction_parser_for_sp(command_handle: CommandHan
            txn_type, parser, free);
 
     let res = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::RegisterSPParser(
+        .submit(Command::Ledger(LedgerCommand::RegisterSPParser(
             txn_type,
             parser,
             free,
@@ -1780,7 +1966,7 @@ pub extern fn indy_get_response_metadata(command_handle: CommandHandle,
     trace!("indy_get_response_metadata: entities >>> response: {:?}", response);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::GetResponseMetadata(
+        .submit(Command::Ledger(LedgerCommand::GetResponseMetadata(
             response,
             boxed_callback_string!("indy_get_response_metadata", cb, command_handle)
         )));
@@ -1819,7 +2005,7 @@ pub extern fn indy_build_ledgers_freeze_request(command_handle: CommandHandle,
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildLedgersFreezeRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildLedgersFreezeRequest(
             submitter_did,
             ledgers_ids,
             boxed_callback_string!("indy_build_ledgers_freeze_request", cb, command_handle)
@@ -1865,7 +2051,7 @@ pub extern fn indy_build_get_frozen_ledgers_request(command_handle: CommandHandl
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetFrozenLedgersRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetFrozenLedgersRequest(
             submitter_did,
             boxed_callback_string!("indy_build_get_frozen_ledgers_request", cb, command_handle)
         )));
@@ -1877,6 +2063,260 @@ pub extern fn indy_build_get_frozen_ledgers_request(command_handle: CommandHandl
     res
 }
 
+/// Parse a GET_FROZEN_LEDGERS response to get the list of frozen ledgers.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_frozen_ledgers_response: response of GET_FROZEN_LEDGERS request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Frozen ledgers as json (empty object if there are no frozen ledgers).
+/// {
+///     <ledger_id>: {
+///         "ledger": String - Ledger root hash,
+///         "state": String - State root hash,
+///         "seq_no": u64 - the latest transaction seqNo for particular Node,
+///     },
+///     ...
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_parse_get_frozen_ledgers_response(command_handle: CommandHandle,
+                                                     get_frozen_ledgers_response: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                          err: ErrorCode,
+                                                                          frozen_ledgers_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_get_frozen_ledgers_response: >>> get_frozen_ledgers_response: {:?}", get_frozen_ledgers_response);
+
+    check_useful_c_str!(get_frozen_ledgers_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_parse_get_frozen_ledgers_response: entities >>> get_frozen_ledgers_response: {:?}", get_frozen_ledgers_response);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::ParseGetFrozenLedgersResponse(
+            get_frozen_ledgers_response,
+            boxed_callback_string!("indy_parse_get_frozen_ledgers_response", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_parse_get_frozen_ledgers_response: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds a request to add an object of the rich schema family:
+/// JSON-LD context (SET_CONTEXT), rich schema (RICH_SCHEMA), mapping (MAPPING) or encoding (ENCODING).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: Identifier (DID) of the transaction author as base58-encoded string.
+///                Actual request sender may differ if Endorser is used (look at `indy_append_request_endorser`)
+/// data: rich schema object json
+/// {
+///     id: string - DID URL identifying the object
+///     content: string - JSON-LD content of the object serialized as string.
+///         JSON-LD context must contain `@context`, rich schema `@id` must be equal to `id`
+///     rsName: string - name of the object
+///     rsVersion: string - version of the object
+///     rsType: string - type of the object: "ctx" (context), "sch" (schema), "map" (mapping) or "enc" (encoding)
+///     ver: string - version of the object json
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_rich_schema_request(command_handle: CommandHandle,
+                                             submitter_did: *const c_char,
+                                             data: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode,
+                                                                  request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_rich_schema_request: >>> submitter_did: {:?}, data: {:?}", submitter_did, data);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam2, DidValue);
+    check_useful_validatable_json!(data, ErrorCode::CommonInvalidParam3, RichSchema);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_build_rich_schema_request: entities >>> submitter_did: {:?}, data: {:?}", submitter_did, data);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::BuildRichSchemaRequest(
+            submitter_did,
+            data,
+            boxed_callback_string!("indy_build_rich_schema_request", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_rich_schema_request: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds a GET_RICH_SCHEMA_OBJECT_BY_ID request. Request to get an object of the rich schema family by its id.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// id: DID URL identifying the object
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_get_rich_schema_object_by_id_request(command_handle: CommandHandle,
+                                                              submitter_did: *const c_char,
+                                                              id: *const c_char,
+                                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                                   err: ErrorCode,
+                                                                                   request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_get_rich_schema_object_by_id_request: >>> submitter_did: {:?}, id: {:?}", submitter_did, id);
+
+    check_useful_validatable_opt_string!(submitter_did, ErrorCode::CommonInvalidParam2, DidValue);
+    check_useful_c_str!(id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_build_get_rich_schema_object_by_id_request: entities >>> submitter_did: {:?}, id: {:?}", submitter_did, id);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::BuildGetRichSchemaObjectByIdRequest(
+            submitter_did,
+            id,
+            boxed_callback_string!("indy_build_get_rich_schema_object_by_id_request", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_get_rich_schema_object_by_id_request: <<< res: {:?}", res);
+
+    res
+}
+
+/// Builds a GET_RICH_SCHEMA_OBJECT_BY_METADATA request. Request to get an object of the rich schema family
+/// by its type, name and version.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// rs_type: type of the object: "ctx", "sch", "map" or "enc"
+/// rs_name: name of the object
+/// rs_version: version of the object
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_get_rich_schema_object_by_metadata_request(command_handle: CommandHandle,
+                                                                    submitter_did: *const c_char,
+                                                                    rs_type: *const c_char,
+                                                                    rs_name: *const c_char,
+                                                                    rs_version: *const c_char,
+                                                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                                         err: ErrorCode,
+                                                                                         request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_build_get_rich_schema_object_by_metadata_request: >>> submitter_did: {:?}, rs_type: {:?}, rs_name: {:?}, rs_version: {:?}",
+           submitter_did, rs_type, rs_name, rs_version);
+
+    check_useful_validatable_opt_string!(submitter_did, ErrorCode::CommonInvalidParam2, DidValue);
+    check_useful_c_str!(rs_type, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(rs_name, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(rs_version, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_build_get_rich_schema_object_by_metadata_request: entities >>> submitter_did: {:?}, rs_type: {:?}, rs_name: {:?}, rs_version: {:?}",
+           submitter_did, rs_type, rs_name, rs_version);
+
+    let rs_type: RichSchemaType = match serde_json::from_value(json!(rs_type)) {
+        Ok(rs_type) => rs_type,
+        Err(err) => return IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Unsupported rich schema type: {}", err)).into()
+    };
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::BuildGetRichSchemaObjectByMetadataRequest(
+            submitter_did,
+            rs_type,
+            rs_name,
+            rs_version,
+            boxed_callback_string!("indy_build_get_rich_schema_object_by_metadata_request", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_build_get_rich_schema_object_by_metadata_request: <<< res: {:?}", res);
+
+    res
+}
+
+/// Parse a GET_RICH_SCHEMA_OBJECT_BY_ID or GET_RICH_SCHEMA_OBJECT_BY_METADATA response.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_rich_schema_object_response: response of GET_RICH_SCHEMA_OBJECT_BY_ID or GET_RICH_SCHEMA_OBJECT_BY_METADATA request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Object id and object json.
+/// {
+///     id: string - DID URL identifying the object
+///     content: string - JSON-LD content of the object
+///     rsName: string - name of the object
+///     rsVersion: string - version of the object
+///     rsType: string - type of the object: "ctx", "sch", "map" or "enc"
+///     ver: string - version of the object json
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_parse_get_rich_schema_object_response(command_handle: CommandHandle,
+                                                         get_rich_schema_object_response: *const c_char,
+                                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                              err: ErrorCode,
+                                                                              id: *const c_char,
+                                                                              rich_schema_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_get_rich_schema_object_response: >>> get_rich_schema_object_response: {:?}", get_rich_schema_object_response);
+
+    check_useful_c_str!(get_rich_schema_object_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_parse_get_rich_schema_object_response: entities >>> get_rich_schema_object_response: {:?}", get_rich_schema_object_response);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(LedgerCommand::ParseGetRichSchemaObjectResponse(
+            get_rich_schema_object_response,
+            Box::new(move |result| {
+                let (err, id, rich_schema_json) = prepare_result_2!(result, String::new(), String::new());
+                trace!("indy_parse_get_rich_schema_object_response: id: {:?}, rich_schema_json: {:?}", id, rich_schema_json);
+                let id = ctypes::string_to_cstring(id);
+                let rich_schema_json = ctypes::string_to_cstring(rich_schema_json);
+                cb(command_handle, err, id.as_ptr(), rich_schema_json.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_parse_get_rich_schema_object_response: <<< res: {:?}", res);
+
+    res
+}
+
 /// Builds a AUTH_RULE request. Request to change authentication rules for a ledger transaction.
 ///
 /// #Params
@@ -1946,7 +2386,7 @@ pub extern fn indy_build_auth_rule_request(command_handle: CommandHandle,
            submitter_did, txn_type, action, field, old_value, new_value, constraint);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildAuthRuleRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildAuthRuleRequest(
             submitter_did,
             txn_type,
             action,
@@ -2013,7 +2453,7 @@ pub extern fn indy_build_auth_rules_request(command_handle: CommandHandle,
     trace!("indy_build_auth_rules_request: entities >>> submitter_did: {:?}, rules: {:?}", submitter_did, rules);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildAuthRulesRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildAuthRulesRequest(
             submitter_did,
             rules,
             boxed_callback_string!("indy_build_auth_rules_request", cb, command_handle)
@@ -2076,7 +2516,7 @@ pub extern fn indy_build_get_auth_rule_request(command_handle: CommandHandle,
            submitter_did, txn_type, action, field, old_value, new_value);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(LedgerCommand::BuildGetAuthRuleRequest(
+        .submit(Command::Ledger(LedgerCommand::BuildGetAuthRuleRequest(
             submitter_did,
             txn_type,
             action,
@@ -2154,7 +2594,7 @@ pub extern fn indy_build_txn_author_agreement_request(command_handle: CommandHan
            submitter_did, text, version, ratification_ts, retirement_ts);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildTxnAuthorAgreementRequest(
                 submitter_did,
                 text,
@@ -2201,7 +2641,7 @@ pub extern fn indy_build_disable_all_txn_author_agreements_request(command_handl
     trace!("indy_build_disable_all_txn_author_agreements_request: entities >>> submitter_did: {:?}", submitter_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildDisableAllTxnAuthorAgreementsRequest(
                 submitter_did,
                 boxed_callback_string!("indy_build_disable_all_txn_author_agreements_request", cb, command_handle)
@@ -2253,7 +2693,7 @@ pub extern fn indy_build_get_txn_author_agreement_request(command_handle: Comman
     trace!("indy_build_get_txn_author_agreement_request: entities >>> submitter_did: {:?}, data: {:?}", submitter_did, data);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildGetTxnAuthorAgreementRequest(
                 submitter_did,
                 data,
@@ -2313,7 +2753,7 @@ pub extern fn indy_build_acceptance_mechanisms_request(command_handle: CommandHa
            submitter_did, aml, version, aml_context);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildAcceptanceMechanismRequests(
                 submitter_did,
                 aml,
@@ -2367,7 +2807,7 @@ pub extern fn indy_build_get_acceptance_mechanisms_request(command_handle: Comma
     trace!("indy_build_get_acceptance_mechanisms_request: entities >>> submitter_did: {:?}, timestamp: {:?}, version: {:?}", submitter_did, timestamp, version);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::BuildGetAcceptanceMechanismsRequest(
                 submitter_did,
                 timestamp,
@@ -2436,7 +2876,7 @@ pub extern fn indy_append_txn_author_agreement_acceptance_to_request(command_han
            request_json, text, version, taa_digest, mechanism, time);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::AppendTxnAuthorAgreementAcceptanceToRequest(
                 request_json,
                 text,
@@ -2454,6 +2894,135 @@ pub extern fn indy_append_txn_author_agreement_acceptance_to_request(command_han
     res
 }
 
+/// Record transaction author agreement acceptance for the requests built by libindy.
+/// Once recorded, acceptance data is appended to every domain ledger write request
+/// (NYM, ATTRIB, SCHEMA, CRED_DEF, REVOC_REG_DEF, REVOC_REG_ENTRY) built by `indy_build_*_request` functions,
+/// so `indy_append_txn_author_agreement_acceptance_to_request` doesn't have to be called for them.
+///
+/// EXPERIMENTAL
+///
+/// Digest and time are prepared the same way as in `indy_append_txn_author_agreement_acceptance_to_request`.
+/// Use `indy_get_active_txn_author_agreement` to fetch text, version and acceptance mechanisms set on the Ledger.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// text and version - (optional) raw data about TAA from ledger.
+///     These parameters should be passed together.
+///     These parameters are required if taa_digest parameter is omitted.
+/// taa_digest - (optional) digest on text and version.
+///     Digest is sha256 hash calculated on concatenated strings: version || text.
+///     This parameter is required if text and version parameters are omitted.
+/// mechanism - (optional) mechanism how user has accepted the TAA.
+///     Pass null to remove recorded acceptance, other parameters are ignored in this case.
+/// time - UTC timestamp when user has accepted the TAA. Note that the time portion will be discarded to avoid a privacy risk.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_set_txn_author_agreement_acceptance(command_handle: CommandHandle,
+                                                       text: *const c_char,
+                                                       version: *const c_char,
+                                                       taa_digest: *const c_char,
+                                                       mechanism: *const c_char,
+                                                       time: u64,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                            err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_txn_author_agreement_acceptance: >>> text: {:?}, version: {:?}, taa_digest: {:?}, mechanism: {:?}, time: {:?}",
+           text, version, taa_digest, mechanism, time);
+
+    check_useful_opt_c_str!(text, ErrorCode::CommonInvalidParam2);
+    check_useful_opt_c_str!(version, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_c_str!(taa_digest, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(mechanism, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_set_txn_author_agreement_acceptance: entities >>> text: {:?}, version: {:?}, taa_digest: {:?}, mechanism: {:?}, time: {:?}",
+           text, version, taa_digest, mechanism, time);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(
+            LedgerCommand::SetTxnAuthorAgreementAcceptance(
+                text,
+                version,
+                taa_digest,
+                mechanism,
+                time,
+                Box::new(move |res| {
+                    let res = prepare_result!(res);
+                    trace!("indy_set_txn_author_agreement_acceptance: res: {:?}", res);
+                    cb(command_handle, res)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_txn_author_agreement_acceptance: <<< res: {:?}", res);
+
+    res
+}
+
+/// Get transaction author agreement and acceptance mechanisms which are active on the Ledger.
+///
+/// EXPERIMENTAL
+///
+/// Sends GET_TXN_AUTHR_AGRMT and GET_TXN_AUTHR_AGRMT_AML requests to the pool and combines their replies.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// {
+///     "taa": Optional<object> - active transaction author agreement, null if it isn't set on the Ledger
+///     {
+///         "text": string - text of TAA,
+///         "version": string - version of TAA,
+///         "digest": string - digest to use for acceptance,
+///         "ratification_ts": Optional<int> - time of TAA ratification
+///     },
+///     "aml": Optional<object> - latest acceptance mechanisms, null if they aren't set on the Ledger
+///     {
+///         "aml": {<label>: <description>} - acceptance mechanisms allowed for TAA acceptance,
+///         "version": string - version of acceptance mechanisms,
+///         "amlContext": Optional<string> - context information about acceptance mechanisms
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_active_txn_author_agreement(command_handle: CommandHandle,
+                                                   pool_handle: PoolHandle,
+                                                   submitter_did: *const c_char,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                        err: ErrorCode,
+                                                                        active_taa_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_active_txn_author_agreement: >>> pool_handle: {:?}, submitter_did: {:?}", pool_handle, submitter_did);
+
+    check_useful_validatable_opt_string!(submitter_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_active_txn_author_agreement: entities >>> pool_handle: {:?}, submitter_did: {:?}", pool_handle, submitter_did);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(
+            LedgerCommand::GetActiveTxnAuthorAgreement(
+                pool_handle,
+                submitter_did,
+                boxed_callback_string!("indy_get_active_txn_author_agreement", cb, command_handle)
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_active_txn_author_agreement: <<< res: {:?}", res);
+
+    res
+}
+
 /// Append Endorser to an existing request.
 ///
 /// An author of request still is a `DID` used as a `submitter_did` parameter for the building of the request.
@@ -2490,7 +3059,7 @@ pub extern fn indy_append_request_endorser(command_handle: CommandHandle,
     trace!("indy_append_request_endorser: entities >>> request_json: {:?},endorser_did: {:?}", request_json, endorser_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Ledger(
+        .submit(Command::Ledger(
             LedgerCommand::AppendRequestEndorser(
                 request_json,
                 endorser_did,
@@ -2502,4 +3071,123 @@ pub extern fn indy_append_request_endorser(command_handle: CommandHandle,
     trace!("indy_append_request_endorser: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+
+/// Sign request by its author for the Endorser.
+///
+/// Appends the Endorser to the request and multi signs it with the author key (see `indy_multi_sign_request`),
+/// so the output can be transported to the Endorser who adds their signature and submits it
+/// (see `indy_endorse_and_submit_request`).
+///
+/// More about Transaction Endorser: https://github.com/hyperledger/indy-node/blob/master/design/transaction_endorser.md
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// author_did: DID of the transaction author used to build the request. Must be stored in the wallet.
+/// request_json: original request data json.
+/// endorser_did: DID of the Endorser that will submit the transaction.
+///               The Endorser's DID must be present on the ledger.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request json signed by the author and ready to be passed to the Endorser.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_sign_request_for_endorser(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             author_did: *const c_char,
+                                             request_json: *const c_char,
+                                             endorser_did: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode,
+                                                                  signed_request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_sign_request_for_endorser: >>> wallet_handle: {:?}, author_did: {:?}, request_json: {:?}, endorser_did: {:?}",
+           wallet_handle, author_did, request_json, endorser_did);
+
+    check_useful_validatable_string!(author_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam4);
+    check_useful_validatable_string!(endorser_did, ErrorCode::CommonInvalidParam5, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_sign_request_for_endorser: entities >>> wallet_handle: {:?}, author_did: {:?}, request_json: {:?}, endorser_did: {:?}",
+           wallet_handle, author_did, request_json, endorser_did);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(
+            LedgerCommand::SignRequestForEndorser(
+                wallet_handle,
+                author_did,
+                request_json,
+                endorser_did,
+                boxed_callback_string!("indy_sign_request_for_endorser", cb, command_handle)
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_sign_request_for_endorser: <<< res: {:?}", res);
+
+    res
+}
+
+/// Endorse request signed by its author and send it to validator pool.
+///
+/// Checks that the request names the Endorser and is multi signed by its author (see `indy_sign_request_for_endorser`),
+/// adds the Endorser signature and sends the request to validator pool.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+///             Pass 0 to route the request to the pool opened for the network namespace of `did:indy:<namespace>:<id>` endorser DID.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// endorser_did: DID of the Endorser named in the request. Must be stored in the wallet.
+/// request_json: request json signed by the author.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_endorse_and_submit_request(command_handle: CommandHandle,
+                                              pool_handle: PoolHandle,
+                                              wallet_handle: WalletHandle,
+                                              endorser_did: *const c_char,
+                                              request_json: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   request_result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_endorse_and_submit_request: >>> pool_handle: {:?}, wallet_handle: {:?}, endorser_did: {:?}, request_json: {:?}",
+           pool_handle, wallet_handle, endorser_did, request_json);
+
+    check_useful_validatable_string!(endorser_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_str!(request_json, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_endorse_and_submit_request: entities >>> pool_handle: {:?}, wallet_handle: {:?}, endorser_did: {:?}, request_json: {:?}",
+           pool_handle, wallet_handle, endorser_did, request_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Ledger(
+            LedgerCommand::EndorseAndSubmitRequest(
+                pool_handle,
+                wallet_handle,
+                endorser_did,
+                request_json,
+                boxed_callback_string!("indy_endorse_and_submit_request", cb, command_handle)
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_endorse_and_submit_request: <<< res: {:?}", res);
+
+    res
+}
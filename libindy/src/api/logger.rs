@@ -3,7 +3,7 @@ use libc::{c_void, c_char};
 use indy_api_types::ErrorCode;
 use indy_api_types::errors::prelude::*;
 
-use crate::utils::logger::{EnabledCB, LogCB, FlushCB, LibindyLogger, LibindyDefaultLogger, LOGGER_STATE};
+use crate::utils::logger::{EnabledCB, LogCB, FlushCB, LibindyLogger, LibindyDefaultLogger, LOGGER_STATE, set_correlation_id};
 use indy_utils::ctypes;
 use log::LevelFilter;
 
@@ -162,4 +162,34 @@ pub extern fn indy_get_logger(context_p: *mut *const c_void,
     trace!("indy_get_logger: <<< res: {:?}", res);
 
     res
-}
\ No newline at end of file
+}
+
+/// Set correlation id for the subsequent calls from the current thread.
+///
+/// Every command started by libindy is tagged with correlation id that prefixes its log messages
+/// including messages of the service calls, pool requests and callbacks of the command.
+/// By default libindy generates unique id for every call, this function allows application
+/// to use its own ids (request id of the application for example) to trace the calls.
+///
+/// #Params
+/// correlation_id: (optional) correlation id to use for the calls from the current thread.
+///                 Pass null to restore generation of ids.
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_set_correlation_id(correlation_id: *const c_char) -> ErrorCode {
+    trace!("indy_set_correlation_id >>> correlation_id: {:?}", correlation_id);
+
+    check_useful_opt_c_str!(correlation_id, ErrorCode::CommonInvalidParam1);
+
+    trace!("indy_set_correlation_id: entities >>> correlation_id: {:?}", correlation_id);
+
+    set_correlation_id(correlation_id);
+
+    let res = ErrorCode::Success;
+
+    trace!("indy_set_correlation_id: <<< res: {:?}", res);
+
+    res
+}
@@ -22,7 +22,7 @@ pub extern fn indy_collect_metrics(command_handle: CommandHandle,
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
 
     let result = CommandExecutor::instance()
-        .send(Command::Metrics(MetricsCommand::CollectMetrics(
+        .submit(Command::Metrics(MetricsCommand::CollectMetrics(
             boxed_callback_string!("indy_collect_metrics", cb, command_handle)
         )));
     let res = prepare_result!(result);
@@ -15,6 +15,10 @@ pub mod metrics;
 
 use libc::c_char;
 
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::time::Duration;
+
 use crate::domain::IndyConfig;
 use indy_api_types::validation::Validatable;
 
@@ -27,9 +31,22 @@ use indy_utils::ctypes;
 /// #Params
 /// config: {
 ///     "crypto_thread_pool_size": Optional<int> - size of thread pool for the most expensive crypto operations. (4 by default)
+///     "cred_def_thread_pool_size": Optional<int> - size of dedicated thread pool for credential definition keys generation. (2 by default)
+///     "command_queue_size": Optional<int> - max number of commands waiting for execution. (0 by default - the queue is unbounded)
+///         Calls made when the queue is full are rejected with CommonBusy error and can be retried later.
+///     "command_workers": Optional<int> - number of threads executing commands. (1 by default, at most 3 are used)
+///         Wallet dependent commands always run on the same thread, pool and blob storage commands get own threads if available.
+///         NOTE: must be set before invocation of any other API functions.
 ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
 ///         Capturing of backtrace can affect library performance.
 ///         NOTE: must be set before invocation of any other API functions.
+///     "anoncreds_telemetry": Optional<bool> - whether proof size and timings should be collected (false by default).
+///         If enabled telemetry of `indy_prover_create_proof` and `indy_prover_create_proofs` is logged with "anoncreds_telemetry" target,
+///         it's never added to the proof json. `indy_prover_create_proof_with_details` and `indy_verifier_verify_proof_with_details`
+///         return it in "telemetry" field of the result.
+///     "ledger_cache_ttl": Optional<int> - seconds cached ledger data is returned by cache functions (`indy_get_schema`,
+///         `indy_get_cred_def`, `indy_get_rev_reg_def`, `indy_prefetch_proof_artifacts`) if `minFresh` option isn't set.
+///         -1 by default, which means cached data never expires.
 /// }
 ///
 /// #Errors
@@ -49,6 +66,61 @@ pub extern fn indy_set_runtime_config(config: *const c_char) -> ErrorCode {
     res
 }
 
+/// Cancel long-running command.
+///
+/// Callback of the command is called with CommonCancelled error and the result of the command is dropped when it's ready.
+/// Supported commands: `indy_submit_request`, `indy_sign_and_submit_request`, `indy_submit_action`,
+/// `indy_prover_create_proof`, `indy_prover_create_proof_with_details`, `indy_prover_fetch_credentials_for_proof_req`,
+/// `indy_fetch_wallet_search_next_records`.
+///
+/// Cancellation doesn't stop the work of the command: proof generation keeps running on the thread pool
+/// and a pool request is still sent to the ledger, only the result isn't returned.
+/// 
+/// NOTE: commands are identified by command handle passed to the function call,
+/// so the call of a cancellable command fails with CommonInvalidParam1 while another one with the same handle is pending.
+///
+/// #Params
+/// command_handle: command handle passed to the call of the command to cancel.
+///
+/// #Errors
+/// CommonInvalidParam1 - there is no pending cancellable command with the handle
+#[no_mangle]
+pub extern fn indy_cancel_command(command_handle: CommandHandle) -> ErrorCode {
+    trace!("indy_cancel_command >>> command_handle: {:?}", command_handle);
+
+    let result = crate::commands::cancellation::cancel(command_handle);
+
+    let res = prepare_result!(result);
+
+    trace!("indy_cancel_command: <<< res: {:?}", res);
+
+    res
+}
+
+/// Set timeout of long-running command.
+///
+/// The command is cancelled (see `indy_cancel_command`) if it isn't completed in `timeout_ms` milliseconds.
+/// Should be called right after the call of the command.
+///
+/// #Params
+/// command_handle: command handle passed to the call of the command.
+/// timeout_ms: timeout in milliseconds.
+///
+/// #Errors
+/// CommonInvalidParam1 - there is no pending cancellable command with the handle
+#[no_mangle]
+pub extern fn indy_set_command_timeout(command_handle: CommandHandle, timeout_ms: u32) -> ErrorCode {
+    trace!("indy_set_command_timeout >>> command_handle: {:?}, timeout_ms: {:?}", command_handle, timeout_ms);
+
+    let result = crate::commands::cancellation::cancel_after(command_handle, Duration::from_millis(u64::from(timeout_ms)));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_set_command_timeout: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get details for last occurred error.
 ///
 /// This function should be called in two places to handle both cases of error occurrence:
@@ -62,11 +134,17 @@ pub extern fn indy_set_runtime_config(config: *const c_char) -> ErrorCode {
 /// * `error_json_p` - Reference that will contain error details (if any error has occurred before)
 ///  in the format:
 /// {
+///     "code": int - error code returned by the function
 ///     "backtrace": Optional<str> - error backtrace.
 ///         Collecting of backtrace can be enabled by:
 ///             1) setting environment variable `RUST_BACKTRACE=1`
 ///             2) calling `indy_set_runtime_config` API function with `collect_backtrace: true`
 ///     "message": str - human-readable error description
+///     "extra": {
+///         "context": [str] - messages of the error context chain from the outermost one to the root cause
+///                            (names the invalid parameter for CommonInvalidParam errors for example)
+///         "vendor_code": Optional<int> - original error code returned by plugin (payment method, wallet storage, key management)
+///     }
 /// }
 ///
 #[no_mangle]
@@ -78,3 +156,42 @@ pub extern fn indy_get_current_error(error_json_p: *mut *const c_char) {
 
     trace!("indy_get_current_error: <<<");
 }
+
+thread_local! {
+    static FEATURES_C_JSON: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Get optional subsystems of libindy: whether they are compiled in and enabled at the moment of the call.
+///
+/// NOTE: Returning pointer is valid until the next call of this function in the same execution thread.
+///
+/// #Returns
+/// Map in the JSON format. Where keys are names of subsystems:
+/// {
+///     "revocation": {"compiled": bool, "enabled": bool},
+///     "payments": {"compiled": bool, "enabled": bool},
+///     "postgres_storage": {"compiled": bool, "enabled": bool} - always false as postgres storage is a separate plugin
+///         that has to be registered with `indy_register_wallet_storage`,
+///     "didcomm_v2": {"compiled": bool, "enabled": bool},
+///     "experimental_protocols": {"compiled": bool, "enabled": bool},
+///     "anoncreds_telemetry": {"compiled": bool, "enabled": bool} - enabled by `indy_set_runtime_config`
+/// }
+#[no_mangle]
+pub extern fn indy_get_features() -> *const c_char {
+    trace!("indy_get_features >>>");
+
+    let features = json!(crate::commands::indy_get_features()).to_string();
+
+    let mut value = std::ptr::null();
+
+    FEATURES_C_JSON.try_with(|features_c_json| {
+        let features = ctypes::string_to_cstring(features);
+        value = features.as_ptr();
+        features_c_json.replace(Some(features));
+    })
+        .map_err(|err| error!("Thread local variable access failed with: {:?}", err)).ok();
+
+    trace!("indy_get_features: <<< res: {:?}", value);
+
+    value
+}
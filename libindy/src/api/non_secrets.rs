@@ -2,7 +2,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, SearchHandle, INVALID_SEARCH_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::non_secrets::NonSecretsCommand;
-use indy_api_types::domain::wallet::Tags;
+use indy_api_types::domain::wallet::{Record, RecordId, RecordOperation, Tags};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 
@@ -47,7 +47,7 @@ pub extern fn indy_add_wallet_record(command_handle: CommandHandle,
     trace!("indy_add_wallet_record: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, value: {:?}, tags_json: {:?}", wallet_handle, type_, id, value, tags_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::AddRecord(
                 wallet_handle,
                 type_,
@@ -93,7 +93,7 @@ pub extern fn indy_update_wallet_record_value(command_handle: CommandHandle,
     trace!("indy_update_wallet_record_value: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, value: {:?}", wallet_handle, type_, id, value);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::UpdateRecordValue(
                 wallet_handle,
                 type_,
@@ -147,7 +147,7 @@ pub extern fn indy_update_wallet_record_tags(command_handle: CommandHandle,
     trace!("indy_update_wallet_record_tags: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tags_json: {:?}", wallet_handle, type_, id, tags_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::UpdateRecordTags(
                 wallet_handle,
                 type_,
@@ -203,7 +203,7 @@ pub extern fn indy_add_wallet_record_tags(command_handle: CommandHandle,
     trace!("indy_add_wallet_record_tags: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tags_json: {:?}", wallet_handle, type_, id, tags_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::AddRecordTags(
                 wallet_handle,
                 type_,
@@ -249,7 +249,7 @@ pub extern fn indy_delete_wallet_record_tags(command_handle: CommandHandle,
     trace!("indy_delete_wallet_record_tags: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, tag_names_json: {:?}", wallet_handle, type_, id, tag_names_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::DeleteRecordTags(
                 wallet_handle,
                 type_,
@@ -291,7 +291,7 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
     trace!("indy_delete_wallet_record: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}", wallet_handle, type_, id);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::DeleteRecord(
                 wallet_handle,
                 type_,
@@ -310,6 +310,143 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
     res
 }
 
+/// Create a list of new non-secret records in the wallet atomically.
+/// Either all records are added or none of them (if any record already exists for example).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// records_json: json array of records to add:
+///   [
+///     {
+///       "type": <str>, // allows to separate different record types collections
+///       "id": <str>, // the id of record
+///       "value": <str>, // the value of record
+///       "tags": (optional) <object>, // the record tags in the same format as for indy_add_wallet_record
+///     }
+///   ]
+#[no_mangle]
+pub extern fn indy_add_wallet_records(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      records_json: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_add_wallet_records: >>> wallet_handle: {:?}, records_json: {:?}", wallet_handle, records_json);
+
+    check_useful_json!(records_json, ErrorCode::CommonInvalidParam3, Vec<Record>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_add_wallet_records: entities >>> wallet_handle: {:?}, records_json: {:?}", wallet_handle, records_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::NonSecrets(
+            NonSecretsCommand::AddRecords(
+                wallet_handle,
+                records_json,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_add_wallet_records:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_add_wallet_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Delete a list of existing wallet records atomically.
+/// Either all records are deleted or none of them (if any record doesn't exist for example).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// record_ids_json: json array of ids of records to delete:
+///   [
+///     {
+///       "type": <str>, // record type
+///       "id": <str>, // the id of record
+///     }
+///   ]
+#[no_mangle]
+pub extern fn indy_delete_wallet_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         record_ids_json: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_delete_wallet_records: >>> wallet_handle: {:?}, record_ids_json: {:?}", wallet_handle, record_ids_json);
+
+    check_useful_json!(record_ids_json, ErrorCode::CommonInvalidParam3, Vec<RecordId>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_delete_wallet_records: entities >>> wallet_handle: {:?}, record_ids_json: {:?}", wallet_handle, record_ids_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::NonSecrets(
+            NonSecretsCommand::DeleteRecords(
+                wallet_handle,
+                record_ids_json,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_delete_wallet_records:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_delete_wallet_records: <<< res: {:?}", res);
+
+    res
+}
+
+/// Apply a list of add/update/delete operations to wallet records atomically (in a single storage transaction).
+/// Either all operations are applied or wallet is left unchanged.
+///
+/// Note: custom wallet storage must register batch handler (see indy_register_wallet_storage_batch).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// operations_json: json array of operations applied in the given order:
+///   [
+///     {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": (optional) <object>},
+///     {"op": "update", "type": <str>, "id": <str>, "value": (optional) <str>, "tags": (optional) <object>},
+///     {"op": "delete", "type": <str>, "id": <str>},
+///   ]
+///   "update" operation replaces record value and/or all record tags, at least one of them must be set.
+///   Tags have the same format as for indy_add_wallet_record.
+#[no_mangle]
+pub extern fn indy_with_wallet_records(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       operations_json: *const c_char,
+                                       cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_with_wallet_records: >>> wallet_handle: {:?}, operations_json: {:?}", wallet_handle, operations_json);
+
+    check_useful_json!(operations_json, ErrorCode::CommonInvalidParam3, Vec<RecordOperation>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_with_wallet_records: entities >>> wallet_handle: {:?}, operations_json: {:?}", wallet_handle, operations_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::NonSecrets(
+            NonSecretsCommand::WithRecords(
+                wallet_handle,
+                operations_json,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_with_wallet_records:");
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_with_wallet_records: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get an wallet record by id
 ///
 /// #Params
@@ -349,7 +486,7 @@ pub  extern fn indy_get_wallet_record(command_handle: CommandHandle,
     trace!("indy_get_wallet_record: entities >>> wallet_handle: {:?}, type_: {:?}, id: {:?}, options_json: {:?}", wallet_handle, type_, id, options_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::GetRecord(
                 wallet_handle,
                 type_,
@@ -411,7 +548,7 @@ pub  extern fn indy_open_wallet_search(command_handle: CommandHandle,
     trace!("indy_open_wallet_search: entities >>> wallet_handle: {:?}, type_: {:?}, query_json: {:?}, options_json: {:?}", wallet_handle, type_, query_json, options_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::OpenSearch(
                 wallet_handle,
                 type_,
@@ -451,6 +588,8 @@ pub  extern fn indy_open_wallet_search(command_handle: CommandHandle,
 ///       tags: <tags json>, // present only if retrieveTags set to true
 ///   }],
 /// }
+///
+/// The command can be cancelled with `indy_cancel_command` or `indy_set_command_timeout`.
 #[no_mangle]
 pub  extern fn indy_fetch_wallet_search_next_records(command_handle: CommandHandle,
                                                      wallet_handle: WalletHandle,
@@ -464,15 +603,21 @@ pub  extern fn indy_fetch_wallet_search_next_records(command_handle: CommandHand
 
     trace!("indy_fetch_wallet_search_next_records: entities >>> wallet_handle: {:?}, wallet_search_handle: {:?}, count: {:?}", wallet_handle, wallet_search_handle, count);
 
+    let (cancellation_id, cb) = cancellable_callback_string!("indy_fetch_wallet_search_next_records", cb, command_handle);
+
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::FetchSearchNextRecords(
                 wallet_handle,
                 wallet_search_handle,
                 count,
-                boxed_callback_string!("indy_fetch_wallet_search_next_records", cb, command_handle)
+                cb
             )));
 
+    if result.is_err() {
+        crate::commands::cancellation::complete(command_handle, cancellation_id);
+    }
+
     let res = prepare_result!(result);
 
     trace!("indy_fetch_wallet_search_next_records: <<< res: {:?}", res);
@@ -495,7 +640,7 @@ pub  extern fn indy_close_wallet_search(command_handle: CommandHandle,
     trace!("indy_close_wallet_search: entities >>> wallet_search_handle: {:?}", wallet_search_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::NonSecrets(
+        .submit(Command::NonSecrets(
             NonSecretsCommand::CloseSearch(
                 wallet_search_handle,
                 Box::new(move |result| {
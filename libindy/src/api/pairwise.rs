@@ -37,7 +37,7 @@ pub  extern fn indy_is_pairwise_exists(command_handle: CommandHandle,
     trace!("indy_is_pairwise_exists: entities >>> wallet_handle: {:?}, their_did: {:?}", wallet_handle, their_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pairwise(PairwiseCommand::PairwiseExists(
+        .submit(Command::Pairwise(PairwiseCommand::PairwiseExists(
             wallet_handle,
             their_did,
             Box::new(move |result| {
@@ -89,7 +89,7 @@ pub  extern fn indy_create_pairwise(command_handle: CommandHandle,
     trace!("indy_create_pairwise: entities >>> wallet_handle: {:?}, their_did: {:?}, my_did: {:?}, metadata: {:?}", wallet_handle, their_did, my_did, metadata);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pairwise(PairwiseCommand::CreatePairwise(
+        .submit(Command::Pairwise(PairwiseCommand::CreatePairwise(
             wallet_handle,
             their_did,
             my_did,
@@ -134,7 +134,7 @@ pub  extern fn indy_list_pairwise(command_handle: CommandHandle,
     trace!("indy_list_pairwise: entities >>> wallet_handle: {:?}", wallet_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pairwise(PairwiseCommand::ListPairwise(
+        .submit(Command::Pairwise(PairwiseCommand::ListPairwise(
             wallet_handle,
             boxed_callback_string!("indy_list_pairwise", cb, command_handle)
         )));
@@ -175,7 +175,7 @@ pub  extern fn indy_get_pairwise(command_handle: CommandHandle,
     trace!("indy_get_pairwise: entities >>> wallet_handle: {:?}, their_did: {:?}", wallet_handle, their_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pairwise(PairwiseCommand::GetPairwise(
+        .submit(Command::Pairwise(PairwiseCommand::GetPairwise(
             wallet_handle,
             their_did,
             boxed_callback_string!("indy_get_pairwise", cb, command_handle)
@@ -219,7 +219,7 @@ pub  extern fn indy_set_pairwise_metadata(command_handle: CommandHandle,
     trace!("indy_set_pairwise_metadata: entities >>> wallet_handle: {:?}, their_did: {:?}, metadata: {:?}", wallet_handle, their_did, metadata);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pairwise(PairwiseCommand::SetPairwiseMetadata(
+        .submit(Command::Pairwise(PairwiseCommand::SetPairwiseMetadata(
             wallet_handle,
             their_did,
             metadata,
@@ -432,7 +432,7 @@ pub extern fn indy_register_payment_method(command_handle: CommandHandle,
         verify_with_address
     );
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::RegisterMethod(
                     payment_method,
@@ -487,7 +487,7 @@ pub extern fn indy_create_payment_address(command_handle: CommandHandle,
     trace!("indy_create_payment_address: entities >>> wallet_handle: {:?}, payment_method: {:?}, config: {:?}", wallet_handle, payment_method, config);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::CreateAddress(
                     wallet_handle,
@@ -525,7 +525,7 @@ pub extern fn indy_list_payment_addresses(command_handle: CommandHandle,
     trace!("indy_list_payment_address: entities >>> wallet_handle: {:?}", wallet_handle);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::ListAddresses(
                     wallet_handle,
@@ -596,7 +596,7 @@ pub extern fn indy_add_request_fees(command_handle: CommandHandle,
            wallet_handle, submitter_did, req_json, inputs_json, outputs_json, extra);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::AddRequestFees(
                     wallet_handle,
@@ -651,7 +651,7 @@ pub extern fn indy_parse_response_with_fees(command_handle: CommandHandle,
     trace!("indy_parse_response_with_fees: entities >>> payment_method: {:?}, resp_json: {:?}", payment_method, resp_json);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::ParseResponseWithFees(
                     payment_method,
@@ -694,7 +694,7 @@ pub extern fn indy_build_get_payment_sources_request(command_handle: CommandHand
     trace!("indy_build_get_payment_sources_request: entities >>> wallet_handle: {:?}, submitter_did: {:?}, payment_address: {:?}", wallet_handle, submitter_did, payment_address);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::BuildGetPaymentSourcesRequest(
                     wallet_handle,
@@ -748,7 +748,7 @@ pub extern fn indy_parse_get_payment_sources_response(command_handle: CommandHan
     trace!("indy_parse_get_payment_sources_response: entities >>> payment_method: {:?}, resp_json: {:?}", payment_method, resp_json);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::ParseGetPaymentSourcesResponse(
                     payment_method,
@@ -816,7 +816,7 @@ pub extern fn indy_build_payment_req(command_handle: CommandHandle,
            wallet_handle, submitter_did, inputs_json, outputs_json, extra);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::BuildPaymentReq(
                     wallet_handle,
@@ -870,7 +870,7 @@ pub extern fn indy_parse_payment_response(command_handle: CommandHandle,
     trace!("indy_parse_payment_response: entities >>> payment_method: {:?}, resp_json: {:?}", payment_method, resp_json);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::ParsePaymentResponse(
                     payment_method,
@@ -936,7 +936,7 @@ pub extern fn indy_prepare_payment_extra_with_acceptance_data(command_handle: Co
            extra_json, text, version, taa_digest, mechanism, time);
 
     let result = CommandExecutor::instance()
-        .send(Command::Payments(
+        .submit(Command::Payments(
             PaymentsCommand::AppendTxnAuthorAgreementAcceptanceToExtra(
                 extra_json,
                 text,
@@ -990,7 +990,7 @@ pub extern fn indy_build_mint_req(command_handle: CommandHandle,
     trace!("indy_build_mint_req: entities >>> wallet_handle: {:?}, submitter_did: {:?}, outputs_json: {:?}, extra: {:?}", wallet_handle, submitter_did, outputs_json, extra);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::BuildMintReq(
                     wallet_handle,
@@ -1046,7 +1046,7 @@ pub extern fn indy_build_set_txn_fees_req(command_handle: CommandHandle,
     trace!("indy_build_set_txn_fees_req: entitites >>> wallet_handle: {:?}, submitter_did: {:?}, payment_method: {:?}, fees_json: {:?}", wallet_handle, submitter_did, payment_method, fees_json);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::BuildSetTxnFeesReq(
                     wallet_handle,
@@ -1088,7 +1088,7 @@ pub extern fn indy_build_get_txn_fees_req(command_handle: CommandHandle,
     trace!("indy_build_get_txn_fees_req: entities >>> wallet_handle: {:?}, submitter_did: {:?}, payment_method: {:?}", wallet_handle, submitter_did, payment_method);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::BuildGetTxnFeesReq(
                     wallet_handle,
@@ -1133,7 +1133,7 @@ pub extern fn indy_parse_get_txn_fees_response(command_handle: CommandHandle,
 
     let result =
         CommandExecutor::instance()
-            .send(Command::Payments(
+            .submit(Command::Payments(
                 PaymentsCommand::ParseGetTxnFeesResponse(
                     payment_method,
                     resp_json,
@@ -1174,7 +1174,7 @@ pub extern fn indy_build_verify_payment_req(command_handle: CommandHandle,
     trace!("indy_build_verify_payment_req: entities >>> wallet_handle {:?}, submitter_did: {:?}, receipt: {:?}", wallet_handle, submitter_did, receipt);
 
     let result = CommandExecutor::instance()
-        .send(Command::Payments(
+        .submit(Command::Payments(
             PaymentsCommand::BuildVerifyPaymentReq(
                 wallet_handle,
                 submitter_did,
@@ -1227,7 +1227,7 @@ pub extern fn indy_parse_verify_payment_response(command_handle: CommandHandle,
     trace!("indy_parse_verify_payment_response: entities >>> resp_json: {:?}", resp_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Payments(
+        .submit(Command::Payments(
             PaymentsCommand::ParseVerifyPaymentResponse(
                 payment_method,
                 resp_json,
@@ -1291,7 +1291,7 @@ pub extern fn indy_get_request_info(command_handle: CommandHandle,
            get_auth_rule_response_json, requester_info_json, fees_json);
 
     let result = CommandExecutor::instance()
-        .send(Command::Payments(
+        .submit(Command::Payments(
             PaymentsCommand::GetRequestInfo(
                 get_auth_rule_response_json,
                 requester_info_json,
@@ -1343,7 +1343,7 @@ pub extern fn indy_sign_with_address(command_handle: CommandHandle,
            wallet_handle, address, message_raw, message_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Payments(
+        .submit(Command::Payments(
             PaymentsCommand::SignWithAddressReq(wallet_handle,
                                                 address,
                                                 message_raw,
@@ -1404,7 +1404,7 @@ pub extern fn indy_verify_with_address(command_handle: CommandHandle,
            address, message_raw, message_len, signature_raw, signature_len);
 
     let result = CommandExecutor::instance()
-        .send(Command::Payments(PaymentsCommand::VerifyWithAddressReq(
+        .submit(Command::Payments(PaymentsCommand::VerifyWithAddressReq(
             address,
             message_raw,
             signature_raw,
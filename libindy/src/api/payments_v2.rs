@@ -43,7 +43,7 @@ pub extern fn indy_build_get_payment_sources_with_from_request(command_handle: C
     trace!("indy_build_get_payment_sources_with_from_request: entities >>> wallet_handle: {:?}, submitter_did: {:?}, payment_address: {:?}, from: {:?}", wallet_handle, submitter_did, payment_address, from);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::BuildGetPaymentSourcesRequest(
                     wallet_handle,
@@ -98,7 +98,7 @@ pub extern fn indy_parse_get_payment_sources_with_from_response(command_handle:
     trace!("indy_parse_get_payment_sources_with_from_response: entities >>> payment_method: {:?}, resp_json: {:?}", payment_method, resp_json);
 
     let result =
-        CommandExecutor::instance().send(
+        CommandExecutor::instance().submit(
             Command::Payments(
                 PaymentsCommand::ParseGetPaymentSourcesResponse(
                     payment_method,
@@ -2,7 +2,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, PoolHandle, INVALID_POOL_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::pool::PoolCommand;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{GenesisValidationOptions, PoolConfig, PoolOpenConfig};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 use indy_api_types::validation::Validatable;
@@ -41,7 +41,7 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
     trace!("indy_create_pool_ledger_config: entities >>> config_name: {:?}, config: {:?}", config_name, config);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::Create(
+        .submit(Command::Pool(PoolCommand::Create(
             config_name,
             config,
             Box::new(move |result| {
@@ -80,6 +80,13 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
 ///         By default Libindy sends a read requests to 2 nodes in the pool.
 ///         If response isn't received or `state proof` is invalid Libindy sends the request again but to 2 (`number_read_nodes`) * 2 = 4 nodes and so far until completion.
 ///     "socks_proxy": string (optional) - ZMQ socks proxy host name and port (example: proxy1.intranet.company.com:1080)
+///     "namespace": string (optional) - namespace of the network (example: sovrin:staging).
+///         Several pools can be opened at once but only one of them can use a namespace.
+///         Requests signed by `did:indy:<namespace>:<id>` DIDs are routed to this pool automatically (look at `indy_sign_and_submit_request`).
+///     "mock": bool (optional) - use in-process mock ledger instead of connecting to pool nodes (false by default).
+///         NYM, ATTRIB, SCHEMA and CRED_DEF transactions are kept in memory until the pool is closed
+///         and can be read back with the corresponding GET requests. Other requests are rejected.
+///         Pool ledger configuration isn't required to be created for the mock ledger.
 /// }
 ///
 /// #Returns
@@ -104,7 +111,7 @@ pub extern fn indy_open_pool_ledger(command_handle: CommandHandle,
     trace!("indy_open_pool_ledger: entities >>> config_name: {:?}, config: {:?}", config_name, config);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::Open(
+        .submit(Command::Pool(PoolCommand::Open(
             config_name,
             config,
             Box::new(move |result| {
@@ -144,7 +151,7 @@ pub extern fn indy_refresh_pool_ledger(command_handle: CommandHandle,
     trace!("indy_refresh_pool_ledger: entities >>> handle: {:?}", handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::Refresh(
+        .submit(Command::Pool(PoolCommand::Refresh(
             handle,
             Box::new(move |result| {
                 let err = prepare_result!(result);
@@ -180,7 +187,7 @@ pub extern fn indy_list_pools(command_handle: CommandHandle,
     trace!("indy_list_pools: entities >>>");
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::List(boxed_callback_string!("indy_list_pools", cb, command_handle))));
+        .submit(Command::Pool(PoolCommand::List(boxed_callback_string!("indy_list_pools", cb, command_handle))));
 
     let res = prepare_result!(result);
 
@@ -212,7 +219,7 @@ pub extern fn indy_close_pool_ledger(command_handle: CommandHandle,
     trace!("indy_close_pool_ledger: entities >>> handle: {:?}", handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::Close(
+        .submit(Command::Pool(PoolCommand::Close(
             handle,
             Box::new(move |result| {
                 let err = prepare_result!(result);
@@ -252,7 +259,7 @@ pub extern fn indy_delete_pool_ledger_config(command_handle: CommandHandle,
     trace!("indy_delete_pool_ledger_config: entities >>> config_name: {:?}", config_name);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(PoolCommand::Delete(
+        .submit(Command::Pool(PoolCommand::Delete(
             config_name,
             Box::new(move |result| {
                 let err = prepare_result!(result);
@@ -297,7 +304,7 @@ pub extern fn indy_set_protocol_version(command_handle: CommandHandle,
     trace!("indy_set_protocol_version: entities >>> protocol_version: {:?}", protocol_version);
 
     let result = CommandExecutor::instance()
-        .send(Command::Pool(
+        .submit(Command::Pool(
             PoolCommand::SetProtocolVersion(
             protocol_version,
             Box::new(move |result| {
@@ -313,3 +320,154 @@ pub extern fn indy_set_protocol_version(command_handle: CommandHandle,
 
     res
 }
+
+/// Returns the handle of the opened pool bound to the network namespace.
+///
+/// #Params
+/// network: namespace of the network (example: sovrin:staging)
+///     or DID qualified with the network namespace (example: did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e).
+///
+/// #Returns
+/// Handle to opened pool to use in methods that require pool connection.
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_pool_handle_for_network(command_handle: CommandHandle,
+                                               network: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    pool_handle: PoolHandle)>) -> ErrorCode {
+    trace!("indy_get_pool_handle_for_network: >>> network: {:?}", network);
+
+    check_useful_c_str!(network, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_get_pool_handle_for_network: entities >>> network: {:?}", network);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Pool(PoolCommand::GetPoolHandleForNetwork(
+            network,
+            Box::new(move |result| {
+                let (err, pool_handle) = prepare_result_1!(result, INVALID_POOL_HANDLE);
+                trace!("indy_get_pool_handle_for_network: pool_handle: {:?}", pool_handle);
+                cb(command_handle, err, pool_handle)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_pool_handle_for_network: <<< res: {:?}", res);
+
+    res
+}
+
+/// Validates a genesis transactions file before it is used to create or open a pool ledger.
+///
+/// Every transaction is checked to be a well-formed NODE transaction of the current protocol version,
+/// node verkeys and BLS keys with their proofs of possession are verified,
+/// nodes with the same alias or address and nodes without client address are detected.
+///
+/// #Params
+/// genesis_txn: path to genesis transactions file.
+/// options_json (optional): validation options json. if NULL, then default options will be used.
+/// {
+///     "check_endpoints": bool (optional) - try to connect to client endpoint of every validator node (false by default).
+///     "endpoint_timeout": int (optional) - time to wait for connection with a node (in sec).
+/// }
+///
+/// #Returns
+/// Validation report json.
+/// {
+///     "valid": bool - whether the pool can be opened with these genesis transactions,
+///     "txns_count": int - number of transactions in the file,
+///     "nodes_count": int - number of nodes defined by transactions,
+///     "issues": [{
+///         "kind": string - one of "invalid_json", "invalid_transaction", "incompatible_protocol_version", "invalid_verkey",
+///                 "invalid_bls_key", "invalid_bls_key_pop", "duplicate_node", "not_validator", "missing_client_address", "unreachable_endpoint",
+///                 "not_validator" issues don't make genesis transactions invalid,
+///         "line": int (optional) - line of the genesis transactions file,
+///         "node": string (optional) - alias of the node,
+///         "message": string - description of the issue,
+///     }]
+/// }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_validate_pool_genesis_txns(command_handle: CommandHandle,
+                                              genesis_txn: *const c_char,
+                                              options_json: *const c_char,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_validate_pool_genesis_txns: >>> genesis_txn: {:?}, options_json: {:?}", genesis_txn, options_json);
+
+    check_useful_c_str!(genesis_txn, ErrorCode::CommonInvalidParam2);
+    check_useful_opt_validatable_json!(options_json, ErrorCode::CommonInvalidParam3, GenesisValidationOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_validate_pool_genesis_txns: entities >>> genesis_txn: {:?}, options_json: {:?}", genesis_txn, options_json);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Pool(PoolCommand::ValidateGenesisTxns(
+            genesis_txn,
+            options_json,
+            boxed_callback_string!("indy_validate_pool_genesis_txns", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_validate_pool_genesis_txns: <<< res: {:?}", res);
+
+    res
+}
+
+/// Compares nodes defined by a genesis transactions file with the current nodes of created pool ledger configuration
+/// (cached pool ledger or its genesis transactions if pool was never opened).
+///
+/// #Params
+/// config_name: Name of the pool ledger configuration.
+/// genesis_txn: path to genesis transactions file.
+///
+/// #Returns
+/// Difference json.
+/// {
+///     "added": [{"alias": string, "dest": string}] - nodes defined only by the genesis transactions file,
+///     "removed": [{"alias": string, "dest": string}] - nodes defined only by the pool ledger configuration,
+///     "changed": [{"alias": string, "dest": string, "fields": [string]}] - nodes with different data,
+///     "unchanged": int - number of nodes with the same data,
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_diff_pool_genesis_txns(command_handle: CommandHandle,
+                                          config_name: *const c_char,
+                                          genesis_txn: *const c_char,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               diff_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_diff_pool_genesis_txns: >>> config_name: {:?}, genesis_txn: {:?}", config_name, genesis_txn);
+
+    check_useful_c_str!(config_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(genesis_txn, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_diff_pool_genesis_txns: entities >>> config_name: {:?}, genesis_txn: {:?}", config_name, genesis_txn);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Pool(PoolCommand::DiffGenesisTxns(
+            config_name,
+            genesis_txn,
+            boxed_callback_string!("indy_diff_pool_genesis_txns", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_diff_pool_genesis_txns: <<< res: {:?}", res);
+
+    res
+}
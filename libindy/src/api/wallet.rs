@@ -2,7 +2,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, INVALID_WALLET_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::wallet::WalletCommand;
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig, KeySharesConfig, MnemonicConfig, ReencryptionConfig};
 use indy_api_types::wallet::*;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -104,7 +104,7 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
     trace!("indy_register_wallet_type: params type_: {:?}", type_);
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(
+        .submit(Command::Wallet(
             WalletCommand::RegisterWalletType(
                 type_,
                 create,
@@ -143,6 +143,151 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
     res
 }
 
+/// Register wallet key wrapper implementation.
+///
+/// Key wrapper allows to seal wallet export with the transit key of external KMS (HashiCorp Vault transit engine for example)
+/// instead of passphrase. See `sealed` param of indy_export_wallet call.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Key wrapper name.
+/// wrap_key: KeyWrapper wrap key operation handler
+/// unwrap_key: KeyWrapper unwrap key operation handler
+/// free_key: Handler that allows to de-allocate keys allocated in caller code
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_key_wrapper(command_handle: CommandHandle,
+                                               type_: *const c_char,
+                                               wrap_key: Option<WalletKeyWrap>,
+                                               unwrap_key: Option<WalletKeyUnwrap>,
+                                               free_key: Option<WalletKeyFree>,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_key_wrapper: >>> command_handle: {:?}, type_: {:?}, cb: {:?}",
+           command_handle, type_, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(wrap_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(unwrap_key, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(free_key, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_register_wallet_key_wrapper: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(
+            WalletCommand::RegisterKeyWrapper(
+                type_,
+                wrap_key,
+                unwrap_key,
+                free_key,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_key_wrapper: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_key_wrapper: <<< res: {:?}", res);
+    res
+}
+
+/// Register atomic batch handler for the custom wallet storage registered with indy_register_wallet_storage.
+///
+/// Batch handler allows to apply atomic record operations (see indy_with_wallet_records)
+/// in a single storage transaction. Without it such operations fail for the custom storage.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Storage type name (must be registered already).
+/// batch_records: WalletType batch records operation handler
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_batch(command_handle: CommandHandle,
+                                                 type_: *const c_char,
+                                                 batch_records: Option<WalletBatchRecords>,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                      err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_batch: >>> command_handle: {:?}, type_: {:?}, cb: {:?}",
+           command_handle, type_, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(batch_records, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_register_wallet_storage_batch: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(
+            WalletCommand::RegisterStorageBatch(
+                type_,
+                batch_records,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_storage_batch: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_storage_batch: <<< res: {:?}", res);
+    res
+}
+
+/// Register chunked search handlers for the custom wallet storage registered with indy_register_wallet_storage.
+///
+/// Chunked search handlers allow libindy to fetch search results by chunks of records
+/// with a single handler call instead of fetch_search_next_record and get_record_* calls for every record.
+/// Storages without these handlers are searched record by record.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Storage type name (must be registered already).
+/// fetch_search_next_records: WalletType fetch search next records operation handler
+/// free_search_records: WalletType free search records operation handler
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_search_chunk(command_handle: CommandHandle,
+                                                        type_: *const c_char,
+                                                        fetch_search_next_records: Option<WalletFetchSearchNextRecords>,
+                                                        free_search_records: Option<WalletFreeSearchRecords>,
+                                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_search_chunk: >>> command_handle: {:?}, type_: {:?}, cb: {:?}",
+           command_handle, type_, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(fetch_search_next_records, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(free_search_records, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_register_wallet_storage_search_chunk: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(
+            WalletCommand::RegisterStorageSearchChunk(
+                type_,
+                fetch_search_next_records,
+                free_search_records,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_storage_search_chunk: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_storage_search_chunk: <<< res: {:?}", res);
+    res
+}
+
 /// Create a new secure wallet.
 ///
 /// #Params
@@ -199,7 +344,7 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
            config, secret!(&credentials));
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::Create(
+        .submit(Command::Wallet(WalletCommand::Create(
             config,
             credentials,
             Box::new(move |result| {
@@ -234,6 +379,16 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///                      Defaults to $HOME/.indy_client/wallet.
 ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
 ///           }
+///       "range_tags": optional<object>, Encrypted tags that support $gt, $gte, $lt and $lte queries:
+///           {
+///              "<tag name>": {
+///                  "bucket_size": int, Width of a bucket. Only the bucket index (value / bucket_size) of a
+///                                      non negative integer tag value is revealed to the storage.
+///              }
+///           }
+///           Configuration is stored with the wallet and used if it is omitted on opening.
+///           Records are re-indexed on opening with changed configuration.
+///           Total count of such searches is an upper bound.
 ///
 ///   }
 /// credentials: Wallet credentials json
@@ -241,6 +396,8 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///       "key": string, Key or passphrase used for wallet key derivation.
 ///                      Look to key_derivation_method param for information about supported key derivation methods.
 ///       "rekey": optional<string>, If present than wallet master key will be rotated to a new one.
+///       "key_shares": optional<array<string>>, Shares of the key returned by indy_split_wallet_key and decrypted by their holders.
+///                     If present than key is reconstructed from them and "key" must be omitted.
 ///       "storage_credentials": optional<object> Credentials for wallet storage. Storage type defines set of supported keys.
 ///                              Can be optional if storage supports default configuration.
 ///                              For 'default' storage type should be empty.
@@ -281,7 +438,7 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
            config, secret!(&credentials));
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::Open(
+        .submit(Command::Wallet(WalletCommand::Open(
             config,
             credentials,
             Box::new(move |result| {
@@ -299,6 +456,9 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 
 /// Exports opened wallet
 ///
+/// With "chunked" option records are exported in chunks sealed separately, so the export file is verified
+/// and imported chunk by chunk without loading the whole file into memory.
+///
 /// #Params:
 /// wallet_handle: wallet handle returned by indy_open_wallet
 /// export_config: JSON containing settings for input operation.
@@ -306,11 +466,26 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///     "path": <string>, Path of the file that contains exported wallet content
 ///     "key": <string>, Key or passphrase used for wallet export key derivation.
 ///                     Look to key_derivation_method param for information about supported key derivation methods.
+///                     Can be omitted for sealed export.
 ///     "key_derivation_method": optional<string> Algorithm to use for wallet export key derivation:
 ///                              ARGON2I_MOD - derive secured export key (used by default)
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "sealed": optional<object> Seal export with the transit key of external KMS instead of key.
+///               Random export key is generated and stored in the export file wrapped with the transit key.
+///         {
+///             "key_wrapper": <string>, Name of key wrapper registered with indy_register_wallet_key_wrapper call
+///             "key_id": <string>, Identifier of the transit key in KMS
+///         }
+///     "filter": optional<object> Export only matching records instead of the whole wallet.
+///         {
+///             "types": <array<string>>, Types of exported records. Records created by libindy have "Indy::" prefix
+///                                       (for example "Indy::Credential").
+///             "query": optional<object>, WQL query records of the listed types must match (see indy_open_wallet_search)
+///         }
+///     "chunked": optional<bool> Export in separately sealed chunks (false by default).
+///                Chunked export files can't be imported by libindy versions before chunked export.
 ///   }
 ///
 /// #Returns
@@ -333,7 +508,7 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
     trace!("indy_export_wallet: params wallet_handle: {:?}, export_config: {:?}", wallet_handle, secret!(&export_config));
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::Export(
+        .submit(Command::Wallet(WalletCommand::Export(
             wallet_handle,
             export_config,
             Box::new(move |result| {
@@ -348,6 +523,156 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Starts re-encryption of wallet records under a new master key.
+///
+/// Unlike rekey on wallet opening, that re-encrypts only the wallet keys, all records are re-encrypted
+/// with newly generated keys. Records are re-encrypted in batches by indy_reencrypt_wallet_records call,
+/// the wallet remains available for other operations between batches.
+/// Re-encryption is continued after reopening of the wallet with the current key.
+/// The new key has to be used for opening of the wallet after re-encryption is finished.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// reencryption_config: Re-encryption configuration json.
+///   {
+///       "key": string, Current key or passphrase of the wallet.
+///       "new_key": string, Key or passphrase used for derivation of the new wallet master key.
+///       "key_derivation_method": optional<string> Algorithm used for current key derivation:
+///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+///                          RAW - raw wallet key master provided (skip derivation).
+///       "new_key_derivation_method": optional<string> Algorithm to use for new key derivation:
+///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+///                          RAW - raw wallet key master provided (skip derivation).
+///                                RAW keys can be generated with indy_generate_wallet_key call
+///   }
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_start_wallet_reencryption(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             reencryption_config: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_start_wallet_reencryption: >>> wallet_handle: {:?}, reencryption_config: {:?}", wallet_handle, reencryption_config);
+
+    check_useful_json!(reencryption_config, ErrorCode::CommonInvalidParam3, ReencryptionConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_start_wallet_reencryption: params wallet_handle: {:?}, reencryption_config: {:?}", wallet_handle, secret!(&reencryption_config));
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(WalletCommand::StartReencryption(
+            wallet_handle,
+            reencryption_config,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_start_wallet_reencryption: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_start_wallet_reencryption: <<< res: {:?}", res);
+    res
+}
+
+/// Re-encrypts the next batch of wallet records under the new master key.
+/// Re-encryption has to be started with indy_start_wallet_reencryption call.
+///
+/// Call it repeatedly (with pauses to limit the load of the storage) until `finished` is true.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// batch_size: max number of records to re-encrypt
+///
+/// #Returns
+/// Error code
+/// progress_json: {
+///     "reencrypted": int, Number of records re-encrypted by the batch
+///     "finished": bool, All records are re-encrypted and the wallet uses the new master key since now
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_reencrypt_wallet_records(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            batch_size: usize,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 progress_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_reencrypt_wallet_records: >>> wallet_handle: {:?}, batch_size: {:?}", wallet_handle, batch_size);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(WalletCommand::ReencryptRecords(
+            wallet_handle,
+            batch_size,
+            Box::new(move |result| {
+                let (err, progress_json) = prepare_result_1!(result, String::new());
+                trace!("indy_reencrypt_wallet_records: cb command_handle: {:?} err: {:?}, progress_json: {:?}", command_handle, err, progress_json);
+                let progress_json = ctypes::string_to_cstring(progress_json);
+                cb(command_handle, err, progress_json.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_reencrypt_wallet_records: <<< res: {:?}", res);
+    res
+}
+
+/// Verifies wallet records can be decrypted and reports progress of re-encryption.
+/// Each record of the wallet is read, so it can take a while for large wallets.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+///
+/// #Returns
+/// Error code
+/// status_json: {
+///     "inProgress": bool, Re-encryption is started and not finished yet
+///     "total": int, Number of records in the wallet
+///     "remaining": int, Number of records still encrypted with the current master key
+///     "undecryptable": int, Number of records that can't be decrypted
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_get_wallet_reencryption_status(command_handle: CommandHandle,
+                                                  wallet_handle: WalletHandle,
+                                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                       err: ErrorCode,
+                                                                       status_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_wallet_reencryption_status: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(WalletCommand::GetReencryptionStatus(
+            wallet_handle,
+            Box::new(move |result| {
+                let (err, status_json) = prepare_result_1!(result, String::new());
+                trace!("indy_get_wallet_reencryption_status: cb command_handle: {:?} err: {:?}, status_json: {:?}", command_handle, err, status_json);
+                let status_json = ctypes::string_to_cstring(status_json);
+                cb(command_handle, err, status_json.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_get_wallet_reencryption_status: <<< res: {:?}", res);
+    res
+}
 
 /// Creates a new secure wallet and then imports its content
 /// according to fields provided in import_config
@@ -387,6 +712,14 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 /// {
 ///   "path": <string>, path of the file that contains exported wallet content
 ///   "key": <string>, key used for export of the wallet
+///                    Can be omitted for sealed export. Its key is unwrapped with the key wrapper used for export,
+///                    so the same key wrapper must be registered with indy_register_wallet_key_wrapper call.
+///   "key_shares": optional<array<string>>, Shares of the export key returned by indy_split_wallet_key and decrypted by their holders.
+///                 If present than key is reconstructed from them and "key" must be omitted.
+///   "resumable": optional<bool>, Keep partially imported wallet if import fails (false by default).
+///                Calling import with the same config, credentials and import_config again
+///                continues import from the first not imported chunk.
+///                Supported only for export files created with "chunked" option.
 /// }
 ///
 /// #Returns
@@ -414,7 +747,7 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
            config, secret!(&credentials), secret!(&import_config));
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::Import(
+        .submit(Command::Wallet(WalletCommand::Import(
             config,
             credentials,
             import_config,
@@ -430,6 +763,69 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Imports records of exported wallet into opened wallet
+///
+/// Whole export file is verified before the wallet is changed.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// import_config: Import settings json.
+/// {
+///   "path": <string>, path of the file that contains exported wallet content
+///   "key": <string>, key used for export of the wallet
+///                    Can be omitted for sealed export. Its key is unwrapped with the key wrapper used for export,
+///                    so the same key wrapper must be registered with indy_register_wallet_key_wrapper call.
+///   "key_shares": optional<array<string>>, Shares of the export key returned by indy_split_wallet_key and decrypted by their holders.
+///                 If present than key is reconstructed from them and "key" must be omitted.
+///   "conflict_policy": optional<string>, What to do with records already present in the wallet:
+///                      skip - keep existing record
+///                      overwrite - replace value and tags of existing record
+///                      fail - fail without changes in the wallet (used by default)
+/// }
+///
+/// #Returns
+/// Error code
+/// import_result_json: {
+///     "added": int, Number of added records
+///     "skipped": int, Number of records skipped as already present
+///     "overwritten": int, Number of overwritten records
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         import_config: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              import_result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_import_wallet_records: >>> command_handle: {:?}, wallet_handle: {:?}, import_config: {:?}, cb: {:?}",
+           command_handle, wallet_handle, import_config, cb);
+
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam3, ExportConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_import_wallet_records: params wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(&import_config));
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(WalletCommand::ImportRecords(
+            wallet_handle,
+            import_config,
+            Box::new(move |result| {
+                let (err, import_result_json) = prepare_result_1!(result, String::new());
+                trace!("indy_import_wallet_records: cb command_handle: {:?} err: {:?}, import_result_json: {:?}", command_handle, err, import_result_json);
+                let import_result_json = ctypes::string_to_cstring(import_result_json);
+                cb(command_handle, err, import_result_json.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_records: <<< res: {:?}", res);
+    res
+}
+
 
 /// Closes opened wallet and frees allocated resources.
 ///
@@ -455,7 +851,7 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
     trace!("indy_close_wallet: params wallet_handle: {:?}", wallet_handle);
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::Close(
+        .submit(Command::Wallet(WalletCommand::Close(
             wallet_handle,
             Box::new(move |result| {
                 let err = prepare_result!(result);
@@ -524,7 +920,7 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
     trace!("indy_delete_wallet: params config: {:?}, credentials: {:?}", config, secret!(&credentials));
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::Delete(
+        .submit(Command::Wallet(WalletCommand::Delete(
             config,
             credentials,
             Box::new(move |result| {
@@ -548,6 +944,13 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
 /// {
 ///   "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                              Can be UTF-8, base64 or hex string.
+///   "mnemonic": object, (optional) BIP39 mnemonic for deterministic key creation instead of seed.
+///               Key is derived with SLIP-0010 Ed25519 path m/1093'/0'/index'.
+///       {
+///           "phrase": string, mnemonic generated by indy_generate_mnemonic
+///           "passphrase": string, (optional) BIP39 passphrase, empty by default
+///           "index": int, (optional) index of the key, 0 by default
+///       }
 /// }
 ///
 /// #Returns
@@ -571,7 +974,7 @@ pub extern fn indy_generate_wallet_key(command_handle: CommandHandle,
     trace!("indy_generate_wallet_key: params config: {:?}", secret!(config.as_ref()));
 
     let result = CommandExecutor::instance()
-        .send(Command::Wallet(WalletCommand::GenerateKey(
+        .submit(Command::Wallet(WalletCommand::GenerateKey(
             config,
             boxed_callback_string!("indy_generate_wallet_key", cb, command_handle)
         )));
@@ -580,3 +983,98 @@ pub extern fn indy_generate_wallet_key(command_handle: CommandHandle,
     trace!("indy_generate_wallet_key: <<< res: {:?}", res);
     res
 }
+
+/// Generate BIP39 mnemonic.
+/// Mnemonic is a human readable backup of keys derived from it: it can be passed to indy_generate_wallet_key
+/// for derivation of the wallet key and to indy_create_and_store_my_did for derivation of DIDs,
+/// so the same wallet key and DIDs can be re-derived on another device.
+///
+/// #Params
+/// config: (optional) mnemonic configuration json.
+/// {
+///   "word_count": int, (optional) Number of words: 12, 15, 18, 21 or 24 (default).
+/// }
+///
+/// #Returns
+/// err: Error code
+/// mnemonic: mnemonic phrase of English words separated by spaces
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_generate_mnemonic(command_handle: CommandHandle,
+                                     config: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          mnemonic: *const c_char)>) -> ErrorCode {
+    trace!("indy_generate_mnemonic: >>> command_handle: {:?}, config: {:?}, cb: {:?}",
+           command_handle, config, cb);
+
+    check_useful_opt_json!(config, ErrorCode::CommonInvalidParam2, MnemonicConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_generate_mnemonic: params config: {:?}", config);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(WalletCommand::GenerateMnemonic(
+            config,
+            boxed_callback_string!("indy_generate_mnemonic", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_generate_mnemonic: <<< res: {:?}", res);
+    res
+}
+
+/// Split wallet key into shares for social recovery of the wallet.
+///
+/// Key is split with Shamir's secret sharing scheme into a share for each recipient, any `threshold`
+/// of shares are enough to reconstruct the key. Each share is encrypted for its recipient as in indy_crypto_anon_crypt.
+/// Recipient decrypts the share with indy_crypto_anon_decrypt and the decrypted shares can be passed
+/// as "key_shares" of credentials to indy_open_wallet and indy_import_wallet or of import config to indy_import_wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// key: wallet key, passphrase or export key to split.
+/// config: key shares configuration json.
+/// {
+///   "threshold": int, Number of shares required to reconstruct the key. Must be at least 2.
+///   "recipients": array<string>, Verkeys of share holders. Number of shares is equal to the number of recipients.
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// err: Error code
+/// shares: json array of base58 encrypted shares in the order of recipients.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_split_wallet_key(command_handle: CommandHandle,
+                                    key: *const c_char,
+                                    config: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         shares: *const c_char)>) -> ErrorCode {
+    trace!("indy_split_wallet_key: >>> command_handle: {:?}, key: {:?}, config: {:?}, cb: {:?}",
+           command_handle, key, config, cb);
+
+    check_useful_c_str!(key, ErrorCode::CommonInvalidParam2);
+    check_useful_json!(config, ErrorCode::CommonInvalidParam3, KeySharesConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_split_wallet_key: params key: {:?}, config: {:?}", secret!(&key), config);
+
+    let result = CommandExecutor::instance()
+        .submit(Command::Wallet(WalletCommand::SplitKey(
+            key,
+            config,
+            boxed_callback_string!("indy_split_wallet_key", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_split_wallet_key: <<< res: {:?}", res);
+    res
+}
@@ -0,0 +1,35 @@
+// Usage: indy-verifier-daemon <config.json>
+// See `indy::verifier_daemon::VerifierDaemonConfig` for the format of the config.
+use std::env;
+use std::fs;
+use std::process;
+
+fn main() {
+    env_logger::init();
+
+    let config_path = match env::args().nth(1) {
+        Some(config_path) => config_path,
+        None => {
+            eprintln!("Usage: indy-verifier-daemon <config.json>");
+            process::exit(2);
+        }
+    };
+
+    let config = fs::read_to_string(&config_path)
+        .map_err(|err| format!("Cannot read config {}: {}", config_path, err))
+        .and_then(|config| serde_json::from_str(&config)
+            .map_err(|err| format!("Cannot parse config {}: {}", config_path, err)));
+
+    let config = match config {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = indy::verifier_daemon::run(config) {
+        eprintln!("Verifier daemon failed: {}", err);
+        process::exit(1);
+    }
+}
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use ursa::cl::{
     new_nonce,
@@ -18,13 +19,14 @@ use crate::domain::anoncreds::credential_definition::{
     CredentialDefinitionCorrectnessProof,
     CredentialDefinitionData,
     CredentialDefinitionPrivateKey,
+    CredentialDefinitionStage,
     CredentialDefinitionV1,
     SignatureType,
     TemporaryCredentialDefinition,
     CredentialDefinitionId
 };
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
-use crate::domain::anoncreds::credential_request::CredentialRequest;
+use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialIssuanceRequests};
 use crate::domain::anoncreds::revocation_registry::{
     RevocationRegistry,
     RevocationRegistryV1,
@@ -49,7 +51,7 @@ use crate::domain::crypto::did::DidValue;
 use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
-use crate::services::anoncreds::helpers::parse_cred_rev_id;
+use crate::services::anoncreds::helpers::{parse_cred_rev_id, non_canonical_attributes, check_same_cred_def};
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::pool::PoolService;
@@ -59,6 +61,8 @@ use super::tails::{SDKTailsAccessor, store_tails_from_generator};
 use indy_api_types::{WalletHandle, CommandHandle};
 use indy_utils::next_command_handle;
 
+pub type CredentialDefinitionProgressCallback = Arc<dyn Fn(CredentialDefinitionStage) + Send + Sync>;
+
 pub enum IssuerCommand {
     CreateSchema(
         DidValue, // issuer did
@@ -73,6 +77,7 @@ pub enum IssuerCommand {
         String, // tag
         Option<String>, // type
         Option<CredentialDefinitionConfig>, // config
+        Option<CredentialDefinitionProgressCallback>, // progress
         BoxedCallbackStringStringSend),
     CreateAndStoreCredentialDefinitionContinue(
         WalletHandle,
@@ -84,6 +89,7 @@ pub enum IssuerCommand {
         IndyResult<(CredentialDefinitionData,
                     CredentialPrivateKey,
                     CredentialKeyCorrectnessProof)>,
+        Option<CredentialDefinitionProgressCallback>, // progress
         CommandHandle),
     RotateCredentialDefinitionStart(
         WalletHandle,
@@ -125,6 +131,13 @@ pub enum IssuerCommand {
         Option<RevocationRegistryId>, // revocation registry id
         Option<i32>, // blob storage reader config handle
         Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    CreateCredentials(
+        WalletHandle,
+        CredentialOffer, // credential offer
+        CredentialIssuanceRequests, // credential requests with values
+        Option<RevocationRegistryId>, // revocation registry id
+        Option<i32>, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>),
     RevokeCredential(
         WalletHandle,
         i32, // blob storage reader config handle
@@ -143,10 +156,32 @@ pub enum IssuerCommand {
         Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
+// Issuer secrets and revocation registry state loaded once for issuance of one or several credentials.
+struct IssuanceContext {
+    cred_def: CredentialDefinitionV1,
+    cred_def_priv_key: CredentialDefinitionPrivateKey,
+    revocation: Option<RevocationContext>,
+}
+
+struct RevocationContext {
+    rev_reg_id: RevocationRegistryId,
+    rev_reg_def: RevocationRegistryDefinitionV1,
+    rev_reg: RevocationRegistryV1,
+    rev_key_priv: RevocationRegistryDefinitionPrivate,
+    tails_accessor: SDKTailsAccessor,
+    rev_reg_info: RevocationRegistryInfo,
+}
+
+#[derive(Serialize)]
+struct IssuedCredential {
+    cred: Credential,
+    cred_revoc_id: Option<String>,
+}
+
 pub struct IssuerCommandExecutor {
     pub anoncreds_service: Rc<AnoncredsService>,
-    pub blob_storage_service: Rc<BlobStorageService>,
-    pub pool_service: Rc<PoolService>,
+    pub blob_storage_service: Arc<BlobStorageService>,
+    pub pool_service: Arc<PoolService>,
     pub wallet_service: Rc<WalletService>,
     pub crypto_service: Rc<CryptoService>,
     pending_str_str_callbacks: RefCell<HashMap<CommandHandle, BoxedCallbackStringStringSend>>,
@@ -155,8 +190,8 @@ pub struct IssuerCommandExecutor {
 
 impl IssuerCommandExecutor {
     pub fn new(anoncreds_service: Rc<AnoncredsService>,
-               pool_service: Rc<PoolService>,
-               blob_storage_service: Rc<BlobStorageService>,
+               pool_service: Arc<PoolService>,
+               blob_storage_service: Arc<BlobStorageService>,
                wallet_service: Rc<WalletService>,
                crypto_service: Rc<CryptoService>) -> IssuerCommandExecutor {
         IssuerCommandExecutor {
@@ -176,14 +211,14 @@ impl IssuerCommandExecutor {
                 debug!(target: "issuer_command_executor", "CreateSchema command received");
                 cb(self.create_schema(&issuer_did, &name, &version, attrs));
             }
-            IssuerCommand::CreateAndStoreCredentialDefinition(wallet_handle, issuer_did, schema, tag, type_, config, cb) => {
+            IssuerCommand::CreateAndStoreCredentialDefinition(wallet_handle, issuer_did, schema, tag, type_, config, progress, cb) => {
                 debug!(target: "issuer_command_executor", "CreateAndStoreCredentialDefinition command received");
                 self.create_and_store_credential_definition(wallet_handle, &issuer_did, &SchemaV1::from(schema), &tag,
-                                                            type_.as_ref().map(String::as_str), config.as_ref(), cb);
+                                                            type_.as_ref().map(String::as_str), config.as_ref(), progress, cb);
             }
-            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type, result, cb_id) => {
+            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type, result, progress, cb_id) => {
                 debug!(target: "wallet_command_executor", "CreateAndStoreCredentialDefinitionContinue command received");
-                self._create_and_store_credential_definition_continue(cb_id, wallet_handle, &schema, &schema_id, &cred_def_id, &tag, &signature_type, result)
+                self._create_and_store_credential_definition_continue(cb_id, wallet_handle, &schema, &schema_id, &cred_def_id, &tag, &signature_type, result, progress)
             }
             IssuerCommand::RotateCredentialDefinitionStart(wallet_handle, cred_def_id, cred_def_config, cb) => {
                 debug!(target: "wallet_command_executor", "RotateCredentialDefinitionStart command received");
@@ -216,6 +251,10 @@ impl IssuerCommandExecutor {
                 debug!(target: "issuer_command_executor", "CreateCredential command received");
                 cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle));
             }
+            IssuerCommand::CreateCredentials(wallet_handle, cred_offer, requests, rev_reg_id, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentials command received");
+                cb(self.new_credentials(wallet_handle, &cred_offer, &requests, rev_reg_id.as_ref(), blob_storage_reader_handle));
+            }
             IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
@@ -266,6 +305,7 @@ impl IssuerCommandExecutor {
                                               tag: &str,
                                               type_: Option<&str>,
                                               config: Option<&CredentialDefinitionConfig>,
+                                              progress: Option<CredentialDefinitionProgressCallback>,
                                               cb: BoxedCallbackStringStringSend) {
         debug!("create_and_store_credential_definition >>> wallet_handle: {:?}, issuer_did: {:?}, schema: {:?}, tag: {:?}, \
               type_: {:?}, config: {:?}", wallet_handle, issuer_did, schema, tag, type_, config);
@@ -285,7 +325,7 @@ impl IssuerCommandExecutor {
         let tag = tag.to_string();
         let attr_names = schema.attr_names.clone();
 
-        self._create_credential_definition(&attr_names, cred_def_config.support_revocation, Box::new(move |res| {
+        self._create_credential_definition(&attr_names, cred_def_config.support_revocation, progress.clone(), Box::new(move |res| {
             CommandExecutor::instance().send(
                 Command::Anoncreds(
                     AnoncredsCommand::Issuer(
@@ -297,6 +337,7 @@ impl IssuerCommandExecutor {
                             tag.clone(),
                             signature_type.clone(),
                             res,
+                            progress.clone(),
                             cb_id,
                         ))
                 )).unwrap();
@@ -306,11 +347,23 @@ impl IssuerCommandExecutor {
     fn _create_credential_definition(&self,
                                      attr_names: &AttributeNames,
                                      support_revocation: bool,
+                                     progress: Option<CredentialDefinitionProgressCallback>,
                                      cb: Box<dyn Fn(IndyResult<(CredentialDefinitionData,
                                                                 CredentialPrivateKey,
                                                                 CredentialKeyCorrectnessProof)>) + Send>) {
         let attr_names = attr_names.clone();
-        crate::commands::THREADPOOL.lock().unwrap().execute(move || cb(crate::services::anoncreds::issuer::Issuer::new_credential_definition(&attr_names, support_revocation)));
+
+        if let Some(ref progress) = progress {
+            progress(CredentialDefinitionStage::Queued);
+        }
+
+        crate::commands::execute_on(&crate::commands::CRED_DEF_THREADPOOL, move || {
+            if let Some(progress) = progress {
+                progress(CredentialDefinitionStage::Generating);
+            }
+
+            cb(crate::services::anoncreds::issuer::Issuer::new_credential_definition(&attr_names, support_revocation))
+        });
     }
 
     fn _create_and_store_credential_definition_continue(&self,
@@ -323,8 +376,14 @@ impl IssuerCommandExecutor {
                                                         signature_type: &SignatureType,
                                                         result: IndyResult<(CredentialDefinitionData,
                                                                             CredentialPrivateKey,
-                                                                            CredentialKeyCorrectnessProof)>) {
+                                                                            CredentialKeyCorrectnessProof)>,
+                                                        progress: Option<CredentialDefinitionProgressCallback>) {
         let cb = self.pending_str_str_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        if let (Ok(_), Some(progress)) = (&result, progress) {
+            progress(CredentialDefinitionStage::Storing);
+        }
+
         cb(result
             .and_then(|result| {
                 self._complete_create_and_store_credential_definition(wallet_handle, schema, schema_id, cred_def_id, tag, signature_type.clone(), result)
@@ -441,7 +500,7 @@ impl IssuerCommandExecutor {
 
         let support_revocation = cred_def_config.map(|config| config.support_revocation).unwrap_or_default();
 
-        self._create_credential_definition(&schema.attr_names, support_revocation, Box::new(move |res| {
+        self._create_credential_definition(&schema.attr_names, support_revocation, None, Box::new(move |res| {
             CommandExecutor::instance().send(
                 Command::Anoncreds(
                     AnoncredsCommand::Issuer(
@@ -677,6 +736,71 @@ impl IssuerCommandExecutor {
         debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
                wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
 
+        let mut context = self._load_issuance_context(wallet_handle, cred_offer, rev_reg_id, blob_storage_reader_handle)?;
+
+        let (credential, cred_rev_id, rev_reg_delta) = self._issue_credential(&mut context, cred_offer, cred_request, cred_values)?;
+
+        let cred_json = serde_json::to_string(&credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        let rev_reg_delta_json = IssuerCommandExecutor::_rev_reg_delta_to_json(rev_reg_delta)?;
+
+        self._store_revocation_context(wallet_handle, &context)?;
+
+        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+
+        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+    }
+
+    // Issues credentials for all requests against one credential offer.
+    // Revocation registry is updated in the wallet once, deltas of all credentials are merged into one,
+    // so the issuer publishes a single REVOC_REG_ENTRY for the batch.
+    // Nothing is stored if issuance of any credential fails.
+    fn new_credentials(&self,
+                       wallet_handle: WalletHandle,
+                       cred_offer: &CredentialOffer,
+                       requests: &CredentialIssuanceRequests,
+                       rev_reg_id: Option<&RevocationRegistryId>,
+                       blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>)> {
+        debug!("new_credentials >>> wallet_handle: {:?}, cred_offer: {:?}, requests: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&cred_offer), secret!(&requests), rev_reg_id, blob_storage_reader_handle);
+
+        let mut context = self._load_issuance_context(wallet_handle, cred_offer, rev_reg_id, blob_storage_reader_handle)?;
+
+        let mut credentials: Vec<IssuedCredential> = Vec::with_capacity(requests.0.len());
+        let mut merged_rev_reg_delta: Option<CryptoRevocationRegistryDelta> = None;
+
+        for request in requests.0.iter() {
+            let (credential, cred_rev_id, rev_reg_delta) = self._issue_credential(&mut context, cred_offer, &request.cred_req, &request.cred_values)?;
+
+            merged_rev_reg_delta = match (merged_rev_reg_delta, rev_reg_delta) {
+                (Some(mut merged), Some(rev_reg_delta)) => {
+                    merged.merge(&rev_reg_delta)?;
+                    Some(merged)
+                }
+                (merged, rev_reg_delta) => merged.or(rev_reg_delta)
+            };
+
+            credentials.push(IssuedCredential { cred: credential, cred_revoc_id: cred_rev_id });
+        }
+
+        let creds_json = serde_json::to_string(&credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credentials")?;
+
+        let rev_reg_delta_json = IssuerCommandExecutor::_rev_reg_delta_to_json(merged_rev_reg_delta)?;
+
+        self._store_revocation_context(wallet_handle, &context)?;
+
+        debug!("new_credentials <<< creds_json: {:?}, rev_reg_delta_json: {:?}", secret!(&creds_json), rev_reg_delta_json);
+
+        Ok((creds_json, rev_reg_delta_json))
+    }
+
+    fn _load_issuance_context(&self,
+                              wallet_handle: WalletHandle,
+                              cred_offer: &CredentialOffer,
+                              rev_reg_id: Option<&RevocationRegistryId>,
+                              blob_storage_reader_handle: Option<i32>) -> IndyResult<IssuanceContext> {
         let cred_def_id = match cred_offer.method_name {
             Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
             None => cred_offer.cred_def_id.clone()
@@ -689,13 +813,14 @@ impl IssuerCommandExecutor {
         let cred_def_priv_key: CredentialDefinitionPrivateKey =
             self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
 
-        let (rev_reg_def, mut rev_reg,
-            rev_reg_def_priv, sdk_tails_accessor, rev_reg_info) = match rev_reg_id {
+        let revocation = match rev_reg_id {
             Some(ref r_reg_id) => {
                 let rev_reg_def: RevocationRegistryDefinitionV1 =
                     RevocationRegistryDefinitionV1::from(
                         self._wallet_get_rev_reg_def(wallet_handle, &r_reg_id)?);
 
+                check_same_cred_def("Credential Offer and Revocation Registry", &cred_def_id, &rev_reg_def.cred_def_id)?;
+
                 let rev_reg: RevocationRegistryV1 =
                     RevocationRegistryV1::from(
                         self._wallet_get_rev_reg(wallet_handle, &r_reg_id)?);
@@ -703,62 +828,96 @@ impl IssuerCommandExecutor {
                 let rev_key_priv: RevocationRegistryDefinitionPrivate =
                     self.wallet_service.get_indy_object(wallet_handle, &r_reg_id.0, &RecordOptions::id_value())?;
 
-                let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
-
-                rev_reg_info.curr_id += 1;
-
-                if rev_reg_info.curr_id > rev_reg_def.value.max_cred_num {
-                    return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
-                }
-
-                if rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
-                    rev_reg_info.used_ids.insert(rev_reg_info.curr_id);
-                }
+                let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
 
                 // TODO: FIXME: Review error kind!
                 let blob_storage_reader_handle = blob_storage_reader_handle
                     .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
 
-                let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
-                                                               blob_storage_reader_handle,
-                                                               &rev_reg_def)?;
+                let tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                           blob_storage_reader_handle,
+                                                           &rev_reg_def)?;
+
+                Some(RevocationContext {
+                    rev_reg_id: (*r_reg_id).clone(),
+                    rev_reg_def,
+                    rev_reg,
+                    rev_key_priv,
+                    tails_accessor,
+                    rev_reg_info,
+                })
+            }
+            None => None
+        };
 
-                (Some(rev_reg_def), Some(rev_reg), Some(rev_key_priv), Some(sdk_tails_accessor), Some(rev_reg_info))
+        Ok(IssuanceContext { cred_def, cred_def_priv_key, revocation })
+    }
+
+    // Issues the credential updating revocation state of the context in memory only.
+    fn _issue_credential(&self,
+                         context: &mut IssuanceContext,
+                         cred_offer: &CredentialOffer,
+                         cred_request: &CredentialRequest,
+                         cred_values: &CredentialValues) -> IndyResult<(Credential, Option<String>, Option<CryptoRevocationRegistryDelta>)> {
+        check_same_cred_def("Credential Offer and Credential Request", &cred_offer.cred_def_id, &cred_request.cred_def_id)?;
+
+        let non_canonical_attrs = non_canonical_attributes(&cred_values.0)?;
+        if !non_canonical_attrs.is_empty() {
+            warn!("new_credential: values of attributes {:?} are not encoded canonically", non_canonical_attrs);
+        }
+
+        if let Some(ref mut revocation) = context.revocation {
+            revocation.rev_reg_info.curr_id += 1;
+
+            if revocation.rev_reg_info.curr_id > revocation.rev_reg_def.value.max_cred_num {
+                return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
+            }
+
+            if revocation.rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
+                let curr_id = revocation.rev_reg_info.curr_id;
+                revocation.rev_reg_info.used_ids.insert(curr_id);
             }
+        }
+
+        let (rev_idx, rev_reg_def, rev_reg, rev_key_priv, tails_accessor) = match context.revocation {
+            Some(ref mut revocation) => (Some(revocation.rev_reg_info.curr_id),
+                                         Some(&revocation.rev_reg_def),
+                                         Some(&mut revocation.rev_reg.value),
+                                         Some(&revocation.rev_key_priv.value),
+                                         Some(&revocation.tails_accessor)),
             None => (None, None, None, None, None)
         };
 
         let (credential_signature, signature_correctness_proof, rev_reg_delta) =
-            self.anoncreds_service.issuer.new_credential(&cred_def,
-                                                         &cred_def_priv_key.value,
+            self.anoncreds_service.issuer.new_credential(&context.cred_def,
+                                                         &context.cred_def_priv_key.value,
                                                          &cred_offer.nonce,
                                                          &cred_request,
                                                          &cred_values,
-                                                         rev_reg_info.as_ref().map(|r_reg_info| r_reg_info.curr_id),
-                                                         rev_reg_def.as_ref(),
-                                                         rev_reg.as_mut().map(|r_reg| &mut r_reg.value),
-                                                         rev_reg_def_priv.as_ref().map(|r_reg_def_priv| &r_reg_def_priv.value),
-                                                         sdk_tails_accessor.as_ref())?;
-
-        let witness =
-            if let (&Some(ref r_reg_def), &Some(ref r_reg), &Some(ref rev_tails_accessor), &Some(ref rev_reg_info)) =
-            (&rev_reg_def, &rev_reg, &sdk_tails_accessor, &rev_reg_info) {
-                let (issued, revoked) = match r_reg_def.value.issuance_type {
-                    IssuanceType::ISSUANCE_ON_DEMAND => (rev_reg_info.used_ids.clone(), HashSet::new()),
-                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), rev_reg_info.used_ids.clone())
+                                                         rev_idx,
+                                                         rev_reg_def,
+                                                         rev_reg,
+                                                         rev_key_priv,
+                                                         tails_accessor)?;
+
+        let witness = match context.revocation {
+            Some(ref revocation) => {
+                let (issued, revoked) = match revocation.rev_reg_def.value.issuance_type {
+                    IssuanceType::ISSUANCE_ON_DEMAND => (revocation.rev_reg_info.used_ids.clone(), HashSet::new()),
+                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), revocation.rev_reg_info.used_ids.clone())
                 };
 
-                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &r_reg.value, &issued, &revoked);
+                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &revocation.rev_reg.value, &issued, &revoked);
 
-                Some(Witness::new(rev_reg_info.curr_id, r_reg_def.value.max_cred_num,
-                                  r_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, rev_tails_accessor)?)
-            } else {
-                None
-            };
+                Some(Witness::new(revocation.rev_reg_info.curr_id, revocation.rev_reg_def.value.max_cred_num,
+                                  revocation.rev_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, &revocation.tails_accessor)?)
+            }
+            None => None
+        };
 
-        let cred_rev_reg_id = match (rev_reg_id, cred_offer.method_name.as_ref()) {
-            (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
-            (rev_reg_id, _) => rev_reg_id.cloned()
+        let cred_rev_reg_id = match (context.revocation.as_ref(), cred_offer.method_name.as_ref()) {
+            (Some(revocation), Some(ref _method_name)) => Some(revocation.rev_reg_id.to_unqualified()),
+            (revocation, _) => revocation.map(|revocation| revocation.rev_reg_id.clone())
         };
 
         let credential = Credential {
@@ -768,32 +927,33 @@ impl IssuerCommandExecutor {
             values: cred_values.clone(),
             signature: credential_signature,
             signature_correctness_proof,
-            rev_reg: rev_reg.map(|r_reg| r_reg.value),
+            rev_reg: context.revocation.as_ref().map(|revocation| revocation.rev_reg.value.clone()),
             witness,
         };
 
-        let cred_json = serde_json::to_string(&credential)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
-
-        let rev_reg_delta_json = rev_reg_delta
-            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
-            .as_ref()
-            .map(serde_json::to_string)
-            .map_or(Ok(None), |v| v.map(Some))
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+        let cred_rev_id = context.revocation.as_ref().map(|revocation| revocation.rev_reg_info.curr_id.to_string());
 
-        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (credential.rev_reg, rev_reg_id, rev_reg_info.clone()) {
-            let revoc_reg = RevocationRegistry::RevocationRegistryV1(RevocationRegistryV1 { value: r_reg });
+        Ok((credential, cred_rev_id, rev_reg_delta))
+    }
 
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
-        };
+    fn _store_revocation_context(&self, wallet_handle: WalletHandle, context: &IssuanceContext) -> IndyResult<()> {
+        if let Some(ref revocation) = context.revocation {
+            let revoc_reg = RevocationRegistry::RevocationRegistryV1(RevocationRegistryV1 { value: revocation.rev_reg.value.clone() });
 
-        let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
+            self.wallet_service.update_indy_object(wallet_handle, &revocation.rev_reg_id.0, &revoc_reg)?;
+            self.wallet_service.update_indy_object(wallet_handle, &revocation.rev_reg_id.0, &revocation.rev_reg_info)?;
+        }
 
-        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+        Ok(())
+    }
 
-        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+    fn _rev_reg_delta_to_json(rev_reg_delta: Option<CryptoRevocationRegistryDelta>) -> IndyResult<Option<String>> {
+        rev_reg_delta
+            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
+            .as_ref()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")
     }
 
     fn revoke_credential(&self,
@@ -12,11 +12,13 @@ use crate::services::blob_storage::BlobStorageService;
 use crate::services::pool::PoolService;
 use indy_wallet::WalletService;
 use crate::services::crypto::CryptoService;
-use crate::services::anoncreds::helpers::to_unqualified;
+use crate::services::anoncreds::helpers::{to_unqualified, encode_attribute_value, non_canonical_attributes};
+use crate::domain::anoncreds::credential::CredentialValues;
 
 use indy_api_types::errors::prelude::*;
 
 use std::rc::Rc;
+use std::sync::Arc;
 
 pub enum AnoncredsCommand {
     Issuer(IssuerCommand),
@@ -24,7 +26,13 @@ pub enum AnoncredsCommand {
     Verifier(VerifierCommand),
     ToUnqualified(
         String, // entity
-        Box<dyn Fn(IndyResult<String>) + Send>)
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeAttributeValue(
+        String, // raw value
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ValidateCredentialValues(
+        CredentialValues, // credential values
+        Box<dyn Fn(IndyResult<()>) + Send>)
 }
 
 pub struct AnoncredsCommandExecutor {
@@ -35,8 +43,8 @@ pub struct AnoncredsCommandExecutor {
 
 impl AnoncredsCommandExecutor {
     pub fn new(anoncreds_service: Rc<AnoncredsService>,
-               blob_storage_service: Rc<BlobStorageService>,
-               pool_service: Rc<PoolService>,
+               blob_storage_service: Arc<BlobStorageService>,
+               pool_service: Arc<PoolService>,
                wallet_service: Rc<WalletService>,
                crypto_service: Rc<CryptoService>) -> AnoncredsCommandExecutor {
         AnoncredsCommandExecutor {
@@ -68,6 +76,29 @@ impl AnoncredsCommandExecutor {
                 debug!("ToUnqualified command received");
                 cb(to_unqualified(&entity));
             }
+            AnoncredsCommand::EncodeAttributeValue(raw, cb) => {
+                debug!("EncodeAttributeValue command received");
+                cb(encode_attribute_value(&raw));
+            }
+            AnoncredsCommand::ValidateCredentialValues(values, cb) => {
+                debug!("ValidateCredentialValues command received");
+                cb(self.validate_credential_values(&values));
+            }
         };
     }
+
+    fn validate_credential_values(&self, values: &CredentialValues) -> IndyResult<()> {
+        debug!("validate_credential_values >>> values: {:?}", values);
+
+        let attrs = non_canonical_attributes(&values.0)?;
+
+        if !attrs.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Credential values are not encoded canonically: {:?}", attrs)));
+        }
+
+        debug!("validate_credential_values <<<");
+
+        Ok(())
+    }
 }
@@ -1,37 +1,45 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use ursa::cl::{new_nonce, RevocationRegistry, Witness};
 
 use serde_json::Value;
 
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialInfo};
+use crate::domain::anoncreds::credential::{AttributeValues, Credential, CredentialInfo, CredentialValues};
 use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinition, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
 use crate::domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest, RequestedCredential};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::master_secret::MasterSecret;
 use crate::domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery};
-use crate::domain::anoncreds::requested_credential::RequestedCredentials;
+use crate::domain::anoncreds::proof::{Proof, ProofCreationResult, ProofTelemetry};
+use crate::domain::anoncreds::requested_credential::{RequestedCredentials, ProofRequestsWithCredentials};
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1};
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use crate::domain::anoncreds::revocation_state::{RevocationState, RevocationStates};
 use crate::domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, SchemaV1, SchemaId, Schemas};
+use crate::domain::anoncreds::w3c_credential::{W3CCredential, W3CCredentialProof, W3CCredentialSchema, W3C_CREDENTIALS_CONTEXT,
+                                               W3C_VERIFIABLE_CREDENTIAL_TYPE, INDY_CREDENTIAL_TYPE, INDY_CREDENTIAL_SCHEMA_TYPE, CL_SIGNATURE_TYPE};
 use crate::domain::crypto::did::DidValue;
+use indy_api_types::domain::wallet::{RecordId, RecordOperation};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
+use crate::services::anoncreds::cache::AnoncredsCache;
 use crate::services::anoncreds::helpers::{parse_cred_rev_id, get_non_revoc_interval};
+use crate::services::anoncreds::prover::Prover;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
-use indy_utils::{next_search_handle};
+use indy_utils::{next_search_handle, next_command_handle};
 use crate::utils::wql::Query;
 
 use super::tails::SDKTailsAccessor;
-use indy_api_types::{WalletHandle, SearchHandle};
-use crate::commands::BoxedCallbackStringStringSend;
+use indy_api_types::{WalletHandle, SearchHandle, CommandHandle};
+use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
+use crate::commands::anoncreds::AnoncredsCommand;
 
 pub enum ProverCommand {
     CreateMasterSecret(
@@ -75,6 +83,11 @@ pub enum ProverCommand {
         WalletHandle,
         String, // credential id
         Box<dyn Fn(IndyResult<()>) + Send>),
+    DeleteCredentialWithRelated(
+        WalletHandle,
+        String, // credential id
+        Vec<RecordId>, // related non-secret records
+        Box<dyn Fn(IndyResult<String>) + Send>),
     SearchCredentials(
         WalletHandle,
         Option<String>, // query json
@@ -112,6 +125,27 @@ pub enum ProverCommand {
         CredentialDefinitions, // credential defs
         RevocationStates, // revocation states
         Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateProofWithDetails(
+        WalletHandle,
+        ProofRequest, // proof request
+        RequestedCredentials, // requested credentials
+        String, // master secret name
+        Schemas, // schemas
+        CredentialDefinitions, // credential defs
+        RevocationStates, // revocation states
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateProofs(
+        WalletHandle,
+        ProofRequestsWithCredentials, // proof requests with requested credentials
+        String, // master secret name
+        Schemas, // schemas
+        CredentialDefinitions, // credential defs
+        RevocationStates, // revocation states
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateProofsContinue(
+        CommandHandle, // batch id
+        usize, // index of proof request in batch
+        IndyResult<Proof>),
     CreateRevocationState(
         i32, // blob storage reader handle
         RevocationRegistryDefinition, // revocation registry definition
@@ -126,6 +160,12 @@ pub enum ProverCommand {
         RevocationRegistryDelta, // revocation registry delta
         u64, //timestamp
         String, //credential revocation id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CredentialToW3C(
+        Credential, // credential
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CredentialFromW3C(
+        W3CCredential, // w3c credential
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
@@ -151,16 +191,22 @@ pub struct ProverCommandExecutor {
     anoncreds_service: Rc<AnoncredsService>,
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
-    blob_storage_service: Rc<BlobStorageService>,
+    blob_storage_service: Arc<BlobStorageService>,
     searches: RefCell<HashMap<SearchHandle, Box<WalletSearch>>>,
     searches_for_proof_requests: RefCell<HashMap<SearchHandle, Box<HashMap<String, SearchForProofRequest>>>>,
+    pending_proofs: RefCell<HashMap<CommandHandle, PendingProofs>>,
+}
+
+struct PendingProofs {
+    proofs: Vec<Option<IndyResult<Proof>>>,
+    cb: Box<dyn Fn(IndyResult<String>) + Send>,
 }
 
 impl ProverCommandExecutor {
     pub fn new(anoncreds_service: Rc<AnoncredsService>,
                wallet_service: Rc<WalletService>,
                crypto_service: Rc<CryptoService>,
-               blob_storage_service: Rc<BlobStorageService>) -> ProverCommandExecutor {
+               blob_storage_service: Arc<BlobStorageService>) -> ProverCommandExecutor {
         ProverCommandExecutor {
             anoncreds_service,
             wallet_service,
@@ -168,6 +214,7 @@ impl ProverCommandExecutor {
             blob_storage_service,
             searches: RefCell::new(HashMap::new()),
             searches_for_proof_requests: RefCell::new(HashMap::new()),
+            pending_proofs: RefCell::new(HashMap::new()),
         }
     }
 
@@ -210,6 +257,10 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "DeleteCredential command received");
                 cb(self.delete_credential(wallet_handle, &cred_id));
             }
+            ProverCommand::DeleteCredentialWithRelated(wallet_handle, cred_id, related_records, cb) => {
+                debug!(target: "prover_command_executor", "DeleteCredentialWithRelated command received");
+                cb(self.delete_credential_with_related(wallet_handle, &cred_id, &related_records));
+            }
             ProverCommand::SearchCredentials(wallet_handle, query_json, cb) => {
                 debug!(target: "prover_command_executor", "SearchCredentials command received");
                 cb(self.search_credentials(wallet_handle, query_json.as_ref().map(String::as_str)));
@@ -246,6 +297,25 @@ impl ProverCommandExecutor {
                                      &cred_defs_map_to_cred_defs_v1_map(cred_defs),
                                      &rev_states));
             }
+            ProverCommand::CreateProofWithDetails(wallet_handle, proof_req, requested_credentials, master_secret_name,
+                                                  schemas, cred_defs, rev_states, cb) => {
+                debug!(target: "prover_command_executor", "CreateProofWithDetails command received");
+                cb(self.create_proof_with_details(wallet_handle, &proof_req, &requested_credentials, &master_secret_name,
+                                                  &schemas_map_to_schemas_v1_map(schemas),
+                                                  &cred_defs_map_to_cred_defs_v1_map(cred_defs),
+                                                  &rev_states));
+            }
+            ProverCommand::CreateProofs(wallet_handle, proof_requests, master_secret_name, schemas, cred_defs, rev_states, cb) => {
+                debug!(target: "prover_command_executor", "CreateProofs command received");
+                self.create_proofs(wallet_handle, proof_requests, &master_secret_name,
+                                   schemas_map_to_schemas_v1_map(schemas),
+                                   cred_defs_map_to_cred_defs_v1_map(cred_defs),
+                                   rev_states, cb);
+            }
+            ProverCommand::CreateProofsContinue(batch_id, index, result) => {
+                debug!(target: "prover_command_executor", "CreateProofsContinue command received");
+                self._create_proofs_continue(batch_id, index, result);
+            }
             ProverCommand::CreateRevocationState(blob_storage_reader_handle, rev_reg_def, rev_reg_delta, timestamp, cred_rev_id, cb) => {
                 debug!(target: "prover_command_executor", "CreateRevocationState command received");
                 cb(self.create_revocation_state(blob_storage_reader_handle, rev_reg_def, rev_reg_delta, timestamp, &cred_rev_id));
@@ -254,6 +324,14 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "UpdateRevocationState command received");
                 cb(self.update_revocation_state(blob_storage_reader_handle, rev_state, rev_reg_def, rev_reg_delta, timestamp, &cred_rev_id));
             }
+            ProverCommand::CredentialToW3C(credential, cb) => {
+                debug!(target: "prover_command_executor", "CredentialToW3C command received");
+                cb(self.credential_to_w3c(credential));
+            }
+            ProverCommand::CredentialFromW3C(w3c_credential, cb) => {
+                debug!(target: "prover_command_executor", "CredentialFromW3C command received");
+                cb(self.credential_from_w3c(w3c_credential));
+            }
         };
     }
 
@@ -458,6 +536,82 @@ impl ProverCommandExecutor {
         Ok(credential_info_json)
     }
 
+    fn credential_to_w3c(&self, credential: Credential) -> IndyResult<String> {
+        debug!("credential_to_w3c >>> credential: {:?}", secret!(&credential));
+
+        let issuer = credential.cred_def_id.issuer_did()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Cannot get issuer DID from credential definition id: {:?}", credential.cred_def_id)))?;
+
+        // Issuance time isn't kept in the credential, so the time of conversion is used
+        let issuance_date = time::now_utc().rfc3339().to_string();
+
+        let (credential_subject, encoding) = credential.values.0.into_iter()
+            .map(|(attr, values)| ((attr.clone(), values.raw), (attr, values.encoded)))
+            .unzip();
+
+        let w3c_credential = W3CCredential {
+            context: vec![W3C_CREDENTIALS_CONTEXT.to_string()],
+            type_: vec![W3C_VERIFIABLE_CREDENTIAL_TYPE.to_string(), INDY_CREDENTIAL_TYPE.to_string()],
+            issuer: if issuer.is_fully_qualified() { issuer } else { issuer.qualify("sov") },
+            issuance_date,
+            credential_schema: W3CCredentialSchema {
+                id: credential.schema_id,
+                type_: INDY_CREDENTIAL_SCHEMA_TYPE.to_string(),
+            },
+            credential_subject,
+            proof: W3CCredentialProof {
+                type_: CL_SIGNATURE_TYPE.to_string(),
+                cred_def_id: credential.cred_def_id,
+                rev_reg_id: credential.rev_reg_id,
+                encoding,
+                signature: credential.signature,
+                signature_correctness_proof: credential.signature_correctness_proof,
+                rev_reg: credential.rev_reg,
+                witness: credential.witness,
+            },
+        };
+
+        let w3c_credential_json = serde_json::to_string(&w3c_credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize W3CCredential")?;
+
+        debug!("credential_to_w3c <<< w3c_credential_json: {:?}", secret!(&w3c_credential_json));
+
+        Ok(w3c_credential_json)
+    }
+
+    fn credential_from_w3c(&self, w3c_credential: W3CCredential) -> IndyResult<String> {
+        debug!("credential_from_w3c >>> w3c_credential: {:?}", secret!(&w3c_credential));
+
+        let W3CCredential { credential_subject, credential_schema, mut proof, .. } = w3c_credential;
+
+        let mut values = HashMap::new();
+
+        for (attr, raw) in credential_subject {
+            let encoded = proof.encoding.remove(&attr)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Encoded value of attribute {} not found", attr)))?;
+
+            values.insert(attr, AttributeValues { raw, encoded });
+        }
+
+        let credential = Credential {
+            schema_id: credential_schema.id,
+            cred_def_id: proof.cred_def_id,
+            rev_reg_id: proof.rev_reg_id,
+            values: CredentialValues(values),
+            signature: proof.signature,
+            signature_correctness_proof: proof.signature_correctness_proof,
+            rev_reg: proof.rev_reg,
+            witness: proof.witness,
+        };
+
+        let credential_json = serde_json::to_string(&credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        debug!("credential_from_w3c <<< credential_json: {:?}", secret!(&credential_json));
+
+        Ok(credential_json)
+    }
+
     fn search_credentials(&self,
                           wallet_handle: WalletHandle,
                           query_json: Option<&str>) -> IndyResult<(SearchHandle, usize)> {
@@ -490,14 +644,9 @@ impl ProverCommandExecutor {
 
         let mut credentials_info: Vec<CredentialInfo> = Vec::new();
 
-        for _ in 0..count {
-            match search.fetch_next_record()? {
-                Some(credential_record) => {
-                    let (referent, credential) = self._get_credential(&credential_record)?;
-                    credentials_info.push(self._get_credential_info(&referent, credential))
-                }
-                None => break
-            }
+        for credential_record in search.fetch_next_records(count)? {
+            let (referent, credential) = self._get_credential(&credential_record)?;
+            credentials_info.push(self._get_credential_info(&referent, credential))
         }
 
         let credentials_info_json = serde_json::to_string(&credentials_info)
@@ -552,6 +701,7 @@ impl ProverCommandExecutor {
                                                                                          &predicate_id,
                                                                                          &requested_predicate.restrictions,
                                                                                          &None)?;
+            let query = self._add_predicate_query(wallet_handle, query, requested_predicate)?;
 
             let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_predicate.non_revoked);
 
@@ -605,6 +755,7 @@ impl ProverCommandExecutor {
                                                                                          &predicate_id,
                                                                                          &requested_predicate.restrictions,
                                                                                          &extra_query)?;
+            let query = self._add_predicate_query(wallet_handle, query, requested_predicate)?;
 
             let credentials_search =
                 self.wallet_service.search_indy_records::<Credential>(wallet_handle, &query.to_string(), &SearchOptions::id_value())?;
@@ -669,6 +820,48 @@ impl ProverCommandExecutor {
         self.wallet_service.delete_indy_record::<Credential>(wallet_handle, cred_id)
     }
 
+    // Deletes credential (with its tags) and related records of the application
+    // (revocation states or tails references kept for the credential for example) in a single wallet transaction.
+    // Related records that don't exist are skipped.
+    fn delete_credential_with_related(&self,
+                                      wallet_handle: WalletHandle,
+                                      cred_id: &str,
+                                      related_records: &[RecordId]) -> IndyResult<String> {
+        trace!("delete_credential_with_related >>> wallet_handle: {:?}, cred_id: {:?}, related_records: {:?}", wallet_handle, cred_id, related_records);
+
+        let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, cred_id, &RecordOptions::id_value())
+            .map_err(|err| match err.kind() {
+                IndyErrorKind::WalletItemNotFound => err_msg(IndyErrorKind::WalletItemNotFound, format!("Credential {} not found", cred_id)),
+                _ => err
+            })?;
+
+        let mut operations = vec![RecordOperation::Delete {
+            type_: self.wallet_service.add_prefix("Credential"),
+            id: cred_id.to_string(),
+        }];
+
+        for record in related_records {
+            if record.type_.starts_with(WalletService::PREFIX) {
+                return Err(err_msg(IndyErrorKind::WalletAccessFailed, format!("Record of type \"{}\" can't be deleted as related", record.type_)));
+            }
+
+            match self.wallet_service.get_record(wallet_handle, &record.type_, &record.id, &RecordOptions::id()) {
+                Ok(_) => operations.push(RecordOperation::Delete { type_: record.type_.clone(), id: record.id.clone() }),
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => continue,
+                Err(err) => return Err(err)
+            }
+        }
+
+        self.wallet_service.batch_records(wallet_handle, &operations)?;
+
+        let credential_info_json = serde_json::to_string(&self._get_credential_info(cred_id, credential))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialInfo")?;
+
+        trace!("delete_credential_with_related <<< credential_info_json: {:?}", credential_info_json);
+
+        Ok(credential_info_json)
+    }
+
     fn create_proof(&self,
                     wallet_handle: WalletHandle,
                     proof_req: &ProofRequest,
@@ -681,43 +874,168 @@ impl ProverCommandExecutor {
         cred_defs: {:?}, rev_states: {:?}",
                wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, cred_defs, rev_states);
 
-        let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
+        let (proof, telemetry) = self._create_proof(wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, cred_defs, rev_states)?;
 
-        let cred_refs_for_attrs =
-            requested_credentials.requested_attributes
-                .values()
-                .map(|requested_attr| requested_attr.cred_id.clone())
-                .collect::<HashSet<String>>();
+        ProverCommandExecutor::_log_telemetry(telemetry);
 
-        let cred_refs_for_predicates =
-            requested_credentials.requested_predicates
-                .values()
-                .map(|requested_predicate| requested_predicate.cred_id.clone())
-                .collect::<HashSet<String>>();
+        let proof_json = serde_json::to_string(&proof)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")?;
 
-        let cred_referents = cred_refs_for_attrs.union(&cred_refs_for_predicates).cloned().collect::<Vec<String>>();
+        debug!("create_proof <<< proof_json: {:?}", proof_json);
 
-        let mut credentials: HashMap<String, Credential> = HashMap::with_capacity(cred_referents.len());
+        Ok(proof_json)
+    }
 
-        for cred_referent in cred_referents.into_iter() {
-            let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, &cred_referent, &RecordOptions::id_value())?;
-            credentials.insert(cred_referent, credential);
+    fn create_proof_with_details(&self,
+                                 wallet_handle: WalletHandle,
+                                 proof_req: &ProofRequest,
+                                 requested_credentials: &RequestedCredentials,
+                                 master_secret_id: &str,
+                                 schemas: &HashMap<SchemaId, SchemaV1>,
+                                 cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                                 rev_states: &RevocationStates) -> IndyResult<String> {
+        debug!("create_proof_with_details >>> wallet_handle: {:?}, proof_req: {:?}, requested_credentials: {:?}, master_secret_id: {:?}, schemas: {:?}, \
+        cred_defs: {:?}, rev_states: {:?}",
+               wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, cred_defs, rev_states);
+
+        let (proof, telemetry) = self._create_proof(wallet_handle, proof_req, requested_credentials, master_secret_id, schemas, cred_defs, rev_states)?;
+
+        let result_json = serde_json::to_string(&ProofCreationResult { proof, telemetry })
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize ProofCreationResult")?;
+
+        debug!("create_proof_with_details <<< result_json: {:?}", result_json);
+
+        Ok(result_json)
+    }
+
+    fn _create_proof(&self,
+                     wallet_handle: WalletHandle,
+                     proof_req: &ProofRequest,
+                     requested_credentials: &RequestedCredentials,
+                     master_secret_id: &str,
+                     schemas: &HashMap<SchemaId, SchemaV1>,
+                     cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                     rev_states: &RevocationStates) -> IndyResult<(Proof, Option<ProofTelemetry>)> {
+        let master_secret: MasterSecret = self._wallet_get_master_secret(wallet_handle, &master_secret_id)?;
+
+        let credentials = self._wallet_get_credentials(wallet_handle, ProverCommandExecutor::_cred_referents(requested_credentials))?;
+
+        self.anoncreds_service.prover.create_proof(&credentials,
+                                                   &proof_req,
+                                                   &requested_credentials,
+                                                   &master_secret.value,
+                                                   schemas,
+                                                   cred_defs,
+                                                   rev_states)
+    }
+
+    // Telemetry is never added to the proof sent to the verifier
+    fn _log_telemetry(telemetry: Option<ProofTelemetry>) {
+        if let Some(telemetry) = telemetry {
+            info!(target: "anoncreds_telemetry", "create_proof telemetry: {}", json!(telemetry));
         }
+    }
 
-        let proof = self.anoncreds_service.prover.create_proof(&credentials,
-                                                               &proof_req,
-                                                               &requested_credentials,
-                                                               &master_secret.value,
-                                                               schemas,
-                                                               cred_defs,
-                                                               rev_states)?;
+    fn create_proofs(&self,
+                     wallet_handle: WalletHandle,
+                     proof_requests: ProofRequestsWithCredentials,
+                     master_secret_id: &str,
+                     schemas: HashMap<SchemaId, SchemaV1>,
+                     cred_defs: HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                     rev_states: RevocationStates,
+                     cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("create_proofs >>> wallet_handle: {:?}, proof_requests: {:?}, master_secret_id: {:?}, schemas: {:?}, \
+        cred_defs: {:?}, rev_states: {:?}",
+               wallet_handle, proof_requests, master_secret_id, schemas, cred_defs, rev_states);
+
+        // Master secret and credentials are read from the wallet once for all proof requests
+        let master_secret: MasterSecret = try_cb!(self._wallet_get_master_secret(wallet_handle, &master_secret_id), cb);
+
+        let cred_referents = proof_requests.0.iter()
+            .flat_map(|request| ProverCommandExecutor::_cred_referents(&request.requested_credentials))
+            .collect::<HashSet<String>>();
+
+        let credentials = try_cb!(self._wallet_get_credentials(wallet_handle, cred_referents), cb);
+
+        let batch_id = next_command_handle();
+        self.pending_proofs.borrow_mut().insert(batch_id, PendingProofs {
+            proofs: proof_requests.0.iter().map(|_| None).collect(),
+            cb,
+        });
+
+        let master_secret = Arc::new(master_secret);
+        let credentials = Arc::new(credentials);
+        let schemas = Arc::new(schemas);
+        let cred_defs = Arc::new(cred_defs);
+        let rev_states = Arc::new(rev_states);
+
+        for (index, request) in proof_requests.0.into_iter().enumerate() {
+            let master_secret = master_secret.clone();
+            let credentials = credentials.clone();
+            let schemas = schemas.clone();
+            let cred_defs = cred_defs.clone();
+            let rev_states = rev_states.clone();
+
+            crate::commands::execute_on(&crate::commands::THREADPOOL, move || {
+                // Cache of AnoncredsService belongs to the command thread so every proof is built with its own one
+                let prover = Prover::new(Rc::new(AnoncredsCache::default()));
+
+                let result = prover.create_proof(&credentials,
+                                                 &request.proof_request,
+                                                 &request.requested_credentials,
+                                                 &master_secret.value,
+                                                 &schemas,
+                                                 &cred_defs,
+                                                 &rev_states)
+                    .map(|(proof, telemetry)| {
+                        ProverCommandExecutor::_log_telemetry(telemetry);
+                        proof
+                    });
+
+                CommandExecutor::instance().send(
+                    Command::Anoncreds(
+                        AnoncredsCommand::Prover(
+                            ProverCommand::CreateProofsContinue(batch_id, index, result)
+                        )
+                    )).unwrap();
+            });
+        }
+    }
 
-        let proof_json = serde_json::to_string(&proof)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")?;
+    fn _create_proofs_continue(&self, batch_id: CommandHandle, index: usize, result: IndyResult<Proof>) {
+        let pending = {
+            let mut pending_proofs = self.pending_proofs.borrow_mut();
 
-        debug!("create_proof <<< proof_json: {:?}", proof_json);
+            let completed = match pending_proofs.get_mut(&batch_id) {
+                Some(pending) => {
+                    pending.proofs[index] = Some(result);
+                    pending.proofs.iter().all(Option::is_some)
+                }
+                None => {
+                    error!("create_proofs: unknown batch {:?}", batch_id);
+                    return;
+                }
+            };
 
-        Ok(proof_json)
+            if !completed {
+                return;
+            }
+
+            pending_proofs.remove(&batch_id).expect("FIXME INVALID STATE")
+        };
+
+        // The first failed proof request in order of the batch fails the whole batch
+        let res = pending.proofs.into_iter()
+            .flatten()
+            .collect::<IndyResult<Vec<Proof>>>()
+            .and_then(|proofs|
+                serde_json::to_string(&proofs)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot serialize list of FullProof")
+            );
+
+        debug!("create_proofs <<< res: {:?}", res);
+
+        (pending.cb)(res)
     }
 
     fn create_revocation_state(&self,
@@ -820,6 +1138,24 @@ impl ProverCommandExecutor {
         Ok((referent.to_string(), credential))
     }
 
+    // Predicates on range indexed attribute values are evaluated by the wallet search,
+    // for other attributes all credentials are fetched and checked one by one.
+    fn _add_predicate_query(&self,
+                            wallet_handle: WalletHandle,
+                            query: Query,
+                            predicate_info: &PredicateInfo) -> IndyResult<Query> {
+        let tag_name = self.anoncreds_service.prover.predicate_value_tag(predicate_info);
+
+        if !self.wallet_service.is_range_indexed_tag(wallet_handle, &tag_name)? {
+            return Ok(query);
+        }
+
+        match self.anoncreds_service.prover.build_predicate_query(predicate_info)? {
+            Some(predicate_query) => Ok(Query::And(vec![query, predicate_query])),
+            None => Ok(query)
+        }
+    }
+
     fn _query_requested_credentials(&self,
                                     wallet_handle: WalletHandle,
                                     query_json: &Query,
@@ -881,5 +1217,30 @@ impl ProverCommandExecutor {
     fn _wallet_get_master_secret(&self, wallet_handle: WalletHandle, key: &str) -> IndyResult<MasterSecret> {
         self.wallet_service.get_indy_object(wallet_handle, &key, &RecordOptions::id_value())
     }
+
+    fn _wallet_get_credentials(&self, wallet_handle: WalletHandle, cred_referents: HashSet<String>) -> IndyResult<HashMap<String, Credential>> {
+        let mut credentials: HashMap<String, Credential> = HashMap::with_capacity(cred_referents.len());
+
+        for cred_referent in cred_referents.into_iter() {
+            let credential: Credential = self.wallet_service.get_indy_object(wallet_handle, &cred_referent, &RecordOptions::id_value())?;
+            credentials.insert(cred_referent, credential);
+        }
+
+        Ok(credentials)
+    }
+
+    fn _cred_referents(requested_credentials: &RequestedCredentials) -> HashSet<String> {
+        let cred_refs_for_attrs =
+            requested_credentials.requested_attributes
+                .values()
+                .map(|requested_attr| requested_attr.cred_id.clone());
+
+        let cred_refs_for_predicates =
+            requested_credentials.requested_predicates
+                .values()
+                .map(|requested_predicate| requested_predicate.cred_id.clone());
+
+        cred_refs_for_attrs.chain(cred_refs_for_predicates).collect()
+    }
 }
 
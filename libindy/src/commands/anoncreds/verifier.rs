@@ -2,13 +2,16 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
+use crate::domain::anoncreds::presentation_exchange::PresentationDefinition;
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestPayload};
 use crate::domain::anoncreds::revocation_registry::{rev_regs_map_to_rev_regs_local_map, RevocationRegistryV1, RevocationRegistries};
 use crate::domain::anoncreds::revocation_registry_definition::{rev_reg_defs_map_to_rev_reg_defs_v1_map, RevocationRegistryDefinitionV1, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, SchemaV1, SchemaId, Schemas};
+use crate::domain::anoncreds::verification_result::{VerificationResult, VerificationOptions};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
+use crate::services::anoncreds::presentation_exchange;
 
 pub enum VerifierCommand {
     VerifyProof(
@@ -19,7 +22,23 @@ pub enum VerifierCommand {
         RevocationRegistryDefinitions, // rev reg defs
         RevocationRegistries, // rev reg entries
         Box<dyn Fn(IndyResult<bool>) + Send>),
+    VerifyProofWithDetails(
+        ProofRequest, // proof request
+        Proof, // proof
+        Schemas, // credential schemas
+        CredentialDefinitions, // credential defs
+        RevocationRegistryDefinitions, // rev reg defs
+        RevocationRegistries, // rev reg entries
+        Option<VerificationOptions>, // verification options
+        Box<dyn Fn(IndyResult<String>) + Send>),
     GenerateNonce(
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateProofRequestFromPresentationDefinition(
+        PresentationDefinition, // presentation definition
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreatePresentationSubmission(
+        PresentationDefinition, // presentation definition
+        Proof, // proof
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
@@ -42,12 +61,31 @@ impl VerifierCommandExecutor {
                                      &schemas_map_to_schemas_v1_map(schemas),
                                      &cred_defs_map_to_cred_defs_v1_map(credential_defs),
                                      &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
-                                     &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+                                     &rev_regs_map_to_rev_regs_local_map(rev_regs),
+                                     &VerificationOptions::default())
+                    .and_then(VerificationResult::into_legacy));
+            }
+            VerifierCommand::VerifyProofWithDetails(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, options, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofWithDetails command received");
+                cb(self.verify_proof_with_details(&proof_request.value(), proof,
+                                                  &schemas_map_to_schemas_v1_map(schemas),
+                                                  &cred_defs_map_to_cred_defs_v1_map(credential_defs),
+                                                  &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                                  &rev_regs_map_to_rev_regs_local_map(rev_regs),
+                                                  &options.unwrap_or_default()));
             }
             VerifierCommand::GenerateNonce(cb) => {
                 debug!(target: "verifier_command_executor", "GenerateNonce command received");
                 cb(self.generate_nonce());
             }
+            VerifierCommand::CreateProofRequestFromPresentationDefinition(presentation_definition, cb) => {
+                debug!(target: "verifier_command_executor", "CreateProofRequestFromPresentationDefinition command received");
+                cb(self.create_proof_request_from_presentation_definition(&presentation_definition));
+            }
+            VerifierCommand::CreatePresentationSubmission(presentation_definition, proof, cb) => {
+                debug!(target: "verifier_command_executor", "CreatePresentationSubmission command received");
+                cb(self.create_presentation_submission(&presentation_definition, &proof));
+            }
         };
     }
 
@@ -57,23 +95,51 @@ impl VerifierCommandExecutor {
                     schemas: &HashMap<SchemaId, SchemaV1>,
                     cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
                     rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
-                    rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<bool> {
+                    rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                    options: &VerificationOptions) -> IndyResult<VerificationResult> {
         debug!("verify_proof >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
-               rev_reg_defs: {:?}, rev_regs: {:?}",
-               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs);
+               rev_reg_defs: {:?}, rev_regs: {:?}, options: {:?}",
+               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs, options);
 
         let result = self.anoncreds_service.verifier.verify(&proof,
                                                             &proof_req,
                                                             schemas,
                                                             cred_defs,
                                                             rev_reg_defs,
-                                                            rev_regs)?;
+                                                            rev_regs,
+                                                            options)?;
+
+        if let Some(ref telemetry) = result.telemetry {
+            info!(target: "anoncreds_telemetry", "verify_proof telemetry: {}", json!(telemetry));
+        }
 
         debug!("verify_proof <<< result: {:?}", result);
 
         Ok(result)
     }
 
+    fn verify_proof_with_details(&self,
+                                 proof_req: &ProofRequestPayload,
+                                 proof: Proof,
+                                 schemas: &HashMap<SchemaId, SchemaV1>,
+                                 cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                                 rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                                 rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                                 options: &VerificationOptions) -> IndyResult<String> {
+        debug!("verify_proof_with_details >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
+               rev_reg_defs: {:?}, rev_regs: {:?}, options: {:?}",
+               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs, options);
+
+        let result = self.verify_proof(proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs, options)?;
+
+        let result = serde_json::to_string(&result)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize VerificationResult")?;
+
+        debug!("verify_proof_with_details <<< result: {:?}", result);
+
+        Ok(result)
+    }
+
     fn generate_nonce(&self) -> IndyResult<String> {
         debug!("generate_nonce >>> ");
 
@@ -86,4 +152,32 @@ impl VerifierCommandExecutor {
 
         Ok(result)
     }
-}
+
+    fn create_proof_request_from_presentation_definition(&self, presentation_definition: &PresentationDefinition) -> IndyResult<String> {
+        debug!("create_proof_request_from_presentation_definition >>> presentation_definition: {:?}", presentation_definition);
+
+        let nonce = self.anoncreds_service.verifier.generate_nonce()?;
+
+        let proof_request = presentation_exchange::build_proof_request(presentation_definition, nonce)?;
+
+        let res = serde_json::to_string(&proof_request)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize ProofRequest")?;
+
+        debug!("create_proof_request_from_presentation_definition <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn create_presentation_submission(&self, presentation_definition: &PresentationDefinition, proof: &Proof) -> IndyResult<String> {
+        debug!("create_presentation_submission >>> presentation_definition: {:?}", presentation_definition);
+
+        let presentation_submission = presentation_exchange::build_presentation_submission(presentation_definition, proof)?;
+
+        let res = serde_json::to_string(&presentation_submission)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize PresentationSubmission")?;
+
+        debug!("create_presentation_submission <<< res: {:?}", res);
+
+        Ok(res)
+    }
+}
\ No newline at end of file
@@ -1,11 +1,16 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use indy_api_types::domain::wallet::Tags;
 use crate::domain::anoncreds::schema::SchemaId;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::proof::Identifier;
+use crate::domain::anoncreds::proof_artifacts::{ProofArtifacts, ProofArtifactsPlan, RevocationRegistryAt, RevocationRegistryDeltaInterval, FetchedRevocationRegistryDelta};
+use crate::domain::anoncreds::proof_request::ProofRequest;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
 use indy_api_types::errors::prelude::*;
 use indy_wallet::{WalletService, WalletRecord};
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
@@ -18,6 +23,19 @@ use indy_utils::next_command_handle;
 
 const CRED_DEF_CACHE: &str = "cred_def_cache";
 const SCHEMA_CACHE: &str = "schema_cache";
+const REV_REG_DEF_CACHE: &str = "rev_reg_def_cache";
+const RICH_SCHEMA_CACHE: &str = "rich_schema_cache";
+
+// minFresh applied when it isn't set in options. -1 means do not check age.
+static LEDGER_CACHE_TTL: AtomicI32 = AtomicI32::new(-1);
+
+pub fn set_ledger_cache_ttl(ttl: i32) {
+    LEDGER_CACHE_TTL.store(ttl, Ordering::Relaxed);
+}
+
+pub fn get_ledger_cache_ttl() -> i32 {
+    LEDGER_CACHE_TTL.load(Ordering::Relaxed)
+}
 
 pub enum CacheCommand {
     GetSchema(PoolHandle,
@@ -50,18 +68,77 @@ pub enum CacheCommand {
     PurgeCredDefCache(WalletHandle,
                       PurgeOptions, // options
                       Box<dyn Fn(IndyResult<()>) + Send>),
+    GetRevRegDef(PoolHandle,
+                 WalletHandle,
+                 DidValue, // submitter_did
+                 RevocationRegistryId, // id
+                 GetCacheOptions, // options
+                 Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevRegDefContinue(
+        WalletHandle,
+        IndyResult<(String, String)>, // ledger_response
+        GetCacheOptions,              // options
+        CommandHandle,                          // cb_id
+    ),
+    PurgeRevRegDefCache(WalletHandle,
+                        PurgeOptions, // options
+                        Box<dyn Fn(IndyResult<()>) + Send>),
+    GetRichSchemaObject(PoolHandle,
+                        WalletHandle,
+                        DidValue, // submitter_did
+                        String, // id
+                        GetCacheOptions, // options
+                        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRichSchemaObjectContinue(
+        WalletHandle,
+        IndyResult<(String, String)>, // ledger_response
+        GetCacheOptions,              // options
+        CommandHandle,                          // cb_id
+    ),
+    PurgeRichSchemaCache(WalletHandle,
+                         PurgeOptions, // options
+                         Box<dyn Fn(IndyResult<()>) + Send>),
+    PrefetchProofArtifacts(PoolHandle,
+                           WalletHandle,
+                           DidValue, // submitter_did
+                           ProofRequest, // proof request
+                           Vec<Identifier>, // identifiers of proof or credentials
+                           GetCacheOptions, // options
+                           Box<dyn Fn(IndyResult<String>) + Send>),
+    PrefetchProofArtifactsContinue(
+        CommandHandle, // batch_id
+        ProofArtifact, // fetched artifact
+        IndyResult<(String, Option<u64>)>, // artifact json and ledger timestamp
+    ),
+}
+
+#[derive(Debug, Clone)]
+pub enum ProofArtifact {
+    Schema(SchemaId),
+    CredDef(CredentialDefinitionId),
+    RevRegDef(RevocationRegistryId),
+    RevReg(RevocationRegistryAt),
+    RevRegDelta(RevocationRegistryDeltaInterval),
+}
+
+struct PendingPrefetch {
+    remaining: usize,
+    artifacts: ProofArtifacts,
+    error: Option<IndyError>,
+    cb: Box<dyn Fn(IndyResult<String>) + Send>,
 }
 
 pub struct CacheCommandExecutor {
     wallet_service: Rc<WalletService>,
 
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    pending_prefetches: RefCell<HashMap<CommandHandle, PendingPrefetch>>,
 }
 
 macro_rules! check_cache {
     ($cache: ident, $options: ident, $cb: ident) => {
     if let Some(cache) = $cache {
-            let min_fresh = $options.min_fresh.unwrap_or(-1);
+            let min_fresh = $options.min_fresh.unwrap_or_else(get_ledger_cache_ttl);
             if min_fresh >= 0 {
                 let ts = match CacheCommandExecutor::get_seconds_since_epoch() {
                     Ok(ts) => ts,
@@ -84,6 +161,7 @@ impl CacheCommandExecutor {
         CacheCommandExecutor {
             wallet_service,
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_prefetches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -113,6 +191,38 @@ impl CacheCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "PurgeCredDefCache command received");
                 cb(self.purge_cred_def_cache(wallet_handle, options));
             }
+            CacheCommand::GetRevRegDef(pool_handle, wallet_handle, submitter_did, id, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetRevRegDef command received");
+                self.get_rev_reg_def(pool_handle, wallet_handle, &submitter_did, &id, options, cb);
+            }
+            CacheCommand::GetRevRegDefContinue(wallet_handle, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetRevRegDefContinue command received");
+                self._get_rev_reg_def_continue(wallet_handle, ledger_response, options, cb_id);
+            }
+            CacheCommand::PurgeRevRegDefCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeRevRegDefCache command received");
+                cb(self.purge_rev_reg_def_cache(wallet_handle, options));
+            }
+            CacheCommand::GetRichSchemaObject(pool_handle, wallet_handle, submitter_did, id, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetRichSchemaObject command received");
+                self.get_rich_schema_object(pool_handle, wallet_handle, &submitter_did, &id, options, cb);
+            }
+            CacheCommand::GetRichSchemaObjectContinue(wallet_handle, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetRichSchemaObjectContinue command received");
+                self._get_rich_schema_object_continue(wallet_handle, ledger_response, options, cb_id);
+            }
+            CacheCommand::PurgeRichSchemaCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeRichSchemaCache command received");
+                cb(self.purge_rich_schema_cache(wallet_handle, options));
+            }
+            CacheCommand::PrefetchProofArtifacts(pool_handle, wallet_handle, submitter_did, proof_req, identifiers, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PrefetchProofArtifacts command received");
+                self.prefetch_proof_artifacts(pool_handle, wallet_handle, &submitter_did, &proof_req, &identifiers, options, cb);
+            }
+            CacheCommand::PrefetchProofArtifactsContinue(batch_id, artifact, result) => {
+                debug!(target: "non_secrets_command_executor", "PrefetchProofArtifactsContinue command received");
+                self._prefetch_proof_artifacts_continue(batch_id, artifact, result);
+            }
         }
     }
 
@@ -268,6 +378,297 @@ impl CacheCommandExecutor {
         }
     }
 
+    fn get_rev_reg_def(&self,
+                       pool_handle: PoolHandle,
+                       wallet_handle: WalletHandle,
+                       submitter_did: &DidValue,
+                       id: &RevocationRegistryId,
+                       options: GetCacheOptions,
+                       cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("get_rev_reg_def >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, id, options);
+
+        let cache = self.get_record_from_cache(wallet_handle, &id.0, &options, REV_REG_DEF_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        check_cache!(cache, options, cb);
+
+        if options.no_update.unwrap_or(false) {
+            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
+        }
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetRevocRegDef(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    id.clone(),
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetRevRegDefContinue(
+                                    wallet_handle,
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_rev_reg_def_continue(&self, wallet_handle: WalletHandle, ledger_response: IndyResult<(String, String)>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let (rev_reg_def_id, rev_reg_def_json) = try_cb!(ledger_response, cb);
+
+        match self._delete_and_add_record(wallet_handle, options, &rev_reg_def_id, &rev_reg_def_json, REV_REG_DEF_CACHE) {
+            Ok(_) => cb(Ok(rev_reg_def_json)),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_rev_reg_def_continue failed: {:?}", err))))
+        }
+    }
+
+    // Rich schema objects (JSON-LD contexts among them) are immutable on the ledger
+    fn get_rich_schema_object(&self,
+                              pool_handle: PoolHandle,
+                              wallet_handle: WalletHandle,
+                              submitter_did: &DidValue,
+                              id: &str,
+                              options: GetCacheOptions,
+                              cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("get_rich_schema_object >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, id, options);
+
+        let cache = self.get_record_from_cache(wallet_handle, id, &options, RICH_SCHEMA_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        check_cache!(cache, options, cb);
+
+        if options.no_update.unwrap_or(false) {
+            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
+        }
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetRichSchemaObject(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    id.to_string(),
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetRichSchemaObjectContinue(
+                                    wallet_handle,
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_rich_schema_object_continue(&self, wallet_handle: WalletHandle, ledger_response: IndyResult<(String, String)>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let (id, rich_schema_json) = try_cb!(ledger_response, cb);
+
+        match self._delete_and_add_record(wallet_handle, options, &id, &rich_schema_json, RICH_SCHEMA_CACHE) {
+            Ok(_) => cb(Ok(rich_schema_json)),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_rich_schema_object_continue failed: {:?}", err))))
+        }
+    }
+
+    fn prefetch_proof_artifacts(&self,
+                                pool_handle: PoolHandle,
+                                wallet_handle: WalletHandle,
+                                submitter_did: &DidValue,
+                                proof_req: &ProofRequest,
+                                identifiers: &[Identifier],
+                                options: GetCacheOptions,
+                                cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("prefetch_proof_artifacts >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, proof_req: {:?}, identifiers: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, proof_req, identifiers, options);
+
+        let now = try_cb!(CacheCommandExecutor::get_seconds_since_epoch(), cb);
+
+        let plan = ProofArtifactsPlan::build(proof_req, identifiers, now as u64);
+
+        debug!("prefetch_proof_artifacts: plan: {:?}", plan);
+
+        if plan.is_empty() {
+            return cb(CacheCommandExecutor::_serialize_artifacts(&ProofArtifacts::default()));
+        }
+
+        let batch_id = next_command_handle();
+        self.pending_prefetches.borrow_mut().insert(batch_id, PendingPrefetch {
+            remaining: plan.len(),
+            artifacts: ProofArtifacts::default(),
+            error: None,
+            cb,
+        });
+
+        // All requests are sent at once. Schemas, credential definitions and revocation registry definitions
+        // go through the cache, states of revocation registries are always fetched from the ledger.
+        for id in plan.schema_ids {
+            self.get_schema(pool_handle, wallet_handle, submitter_did, &id, options.clone(),
+                            CacheCommandExecutor::_prefetch_cb(batch_id, ProofArtifact::Schema(id.clone())));
+        }
+
+        for id in plan.cred_def_ids {
+            self.get_cred_def(pool_handle, wallet_handle, submitter_did, &id, options.clone(),
+                              CacheCommandExecutor::_prefetch_cb(batch_id, ProofArtifact::CredDef(id.clone())));
+        }
+
+        for id in plan.rev_reg_def_ids {
+            self.get_rev_reg_def(pool_handle, wallet_handle, submitter_did, &id, options.clone(),
+                                 CacheCommandExecutor::_prefetch_cb(batch_id, ProofArtifact::RevRegDef(id.clone())));
+        }
+
+        for rev_reg in plan.rev_regs {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocReg(
+                        pool_handle,
+                        Some(submitter_did.clone()),
+                        rev_reg.rev_reg_id.clone(),
+                        rev_reg.timestamp as i64,
+                        CacheCommandExecutor::_prefetch_rev_reg_cb(batch_id, ProofArtifact::RevReg(rev_reg.clone())),
+                    )
+                )
+            ).unwrap();
+        }
+
+        for delta in plan.rev_reg_deltas {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegDelta(
+                        pool_handle,
+                        Some(submitter_did.clone()),
+                        delta.rev_reg_id.clone(),
+                        delta.from.map(|from| from as i64),
+                        delta.to as i64,
+                        CacheCommandExecutor::_prefetch_rev_reg_cb(batch_id, ProofArtifact::RevRegDelta(delta.clone())),
+                    )
+                )
+            ).unwrap();
+        }
+    }
+
+    fn _prefetch_cb(batch_id: CommandHandle, artifact: ProofArtifact) -> Box<dyn Fn(IndyResult<String>) + Send> {
+        Box::new(move |result| {
+            CommandExecutor::instance().send(
+                Command::Cache(
+                    CacheCommand::PrefetchProofArtifactsContinue(
+                        batch_id,
+                        artifact.clone(),
+                        result.map(|json| (json, None)),
+                    )
+                )
+            ).unwrap();
+        })
+    }
+
+    fn _prefetch_rev_reg_cb(batch_id: CommandHandle, artifact: ProofArtifact) -> Box<dyn Fn(IndyResult<(String, String, u64)>) + Send> {
+        Box::new(move |result| {
+            CommandExecutor::instance().send(
+                Command::Cache(
+                    CacheCommand::PrefetchProofArtifactsContinue(
+                        batch_id,
+                        artifact.clone(),
+                        result.map(|(_, json, timestamp)| (json, Some(timestamp))),
+                    )
+                )
+            ).unwrap();
+        })
+    }
+
+    fn _prefetch_proof_artifacts_continue(&self, batch_id: CommandHandle, artifact: ProofArtifact, result: IndyResult<(String, Option<u64>)>) {
+        let pending = {
+            let mut pending_prefetches = self.pending_prefetches.borrow_mut();
+
+            let completed = match pending_prefetches.get_mut(&batch_id) {
+                Some(pending) => {
+                    pending.remaining -= 1;
+
+                    let res = result.and_then(|(json, timestamp)|
+                        CacheCommandExecutor::_add_artifact(&mut pending.artifacts, artifact, &json, timestamp));
+
+                    if let Err(err) = res {
+                        // Keep the first error and wait for the rest of requests to complete
+                        pending.error.get_or_insert(err);
+                    }
+
+                    pending.remaining == 0
+                }
+                None => {
+                    error!("prefetch_proof_artifacts: unknown batch {:?}", batch_id);
+                    return;
+                }
+            };
+
+            if !completed {
+                return;
+            }
+
+            pending_prefetches.remove(&batch_id).expect("FIXME INVALID STATE")
+        };
+
+        let res = match pending.error {
+            Some(err) => Err(err),
+            None => CacheCommandExecutor::_serialize_artifacts(&pending.artifacts)
+        };
+
+        trace!("prefetch_proof_artifacts <<< res: {:?}", res);
+
+        (pending.cb)(res)
+    }
+
+    fn _add_artifact(artifacts: &mut ProofArtifacts, artifact: ProofArtifact, json: &str, timestamp: Option<u64>) -> IndyResult<()> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize ledger artifact")?;
+
+        match artifact {
+            ProofArtifact::Schema(id) => { artifacts.schemas.insert(id.0, value); }
+            ProofArtifact::CredDef(id) => { artifacts.cred_defs.insert(id.0, value); }
+            ProofArtifact::RevRegDef(id) => { artifacts.rev_reg_defs.insert(id.0, value); }
+            ProofArtifact::RevReg(rev_reg) => {
+                artifacts.rev_regs
+                    .entry(rev_reg.rev_reg_id.0)
+                    .or_insert_with(HashMap::new)
+                    .insert(rev_reg.timestamp, value);
+            }
+            ProofArtifact::RevRegDelta(delta) => {
+                artifacts.rev_reg_deltas.push(FetchedRevocationRegistryDelta {
+                    rev_reg_id: delta.rev_reg_id.0,
+                    from: delta.from,
+                    to: delta.to,
+                    timestamp: timestamp.unwrap_or(delta.to),
+                    delta: value,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _serialize_artifacts(artifacts: &ProofArtifacts) -> IndyResult<String> {
+        serde_json::to_string(artifacts)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize ledger artifacts")
+    }
+
     fn get_seconds_since_epoch() -> Result<i32, IndyError> {
         match SystemTime::now().duration_since(UNIX_EPOCH) {
             Ok(ts) => Ok(ts.as_secs() as i32),
@@ -292,25 +693,7 @@ impl CacheCommandExecutor {
                           options: PurgeOptions) -> IndyResult<()> {
         trace!("purge_schema_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
 
-        let max_age = options.max_age.unwrap_or(-1);
-        let query_json = CacheCommandExecutor::build_query_json(max_age)?;
-
-        let options_json = json!({
-            "retrieveType": false,
-            "retrieveValue": false,
-            "retrieveTags": false,
-        }).to_string();
-
-        let mut search = self.wallet_service.search_records(
-            wallet_handle,
-            SCHEMA_CACHE,
-            &query_json,
-            &options_json,
-        )?;
-
-        while let Some(record) = search.fetch_next_record()? {
-            self.wallet_service.delete_record(wallet_handle, SCHEMA_CACHE, record.get_id())?;
-        }
+        self._purge_cache(wallet_handle, options, SCHEMA_CACHE)?;
 
         trace!("purge_schema_cache <<< res: ()");
 
@@ -322,6 +705,41 @@ impl CacheCommandExecutor {
                             options: PurgeOptions) -> IndyResult<()> {
         trace!("purge_cred_def_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
 
+        self._purge_cache(wallet_handle, options, CRED_DEF_CACHE)?;
+
+        trace!("purge_cred_def_cache <<< res: ()");
+
+        Ok(())
+    }
+
+    fn purge_rev_reg_def_cache(&self,
+                               wallet_handle: WalletHandle,
+                               options: PurgeOptions) -> IndyResult<()> {
+        trace!("purge_rev_reg_def_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
+
+        self._purge_cache(wallet_handle, options, REV_REG_DEF_CACHE)?;
+
+        trace!("purge_rev_reg_def_cache <<< res: ()");
+
+        Ok(())
+    }
+
+    fn purge_rich_schema_cache(&self,
+                               wallet_handle: WalletHandle,
+                               options: PurgeOptions) -> IndyResult<()> {
+        trace!("purge_rich_schema_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
+
+        self._purge_cache(wallet_handle, options, RICH_SCHEMA_CACHE)?;
+
+        trace!("purge_rich_schema_cache <<< res: ()");
+
+        Ok(())
+    }
+
+    fn _purge_cache(&self,
+                    wallet_handle: WalletHandle,
+                    options: PurgeOptions,
+                    which_cache: &str) -> IndyResult<()> {
         let max_age = options.max_age.unwrap_or(-1);
         let query_json = CacheCommandExecutor::build_query_json(max_age)?;
 
@@ -333,17 +751,15 @@ impl CacheCommandExecutor {
 
         let mut search = self.wallet_service.search_records(
             wallet_handle,
-            CRED_DEF_CACHE,
+            which_cache,
             &query_json,
             &options_json,
         )?;
 
         while let Some(record) = search.fetch_next_record()? {
-            self.wallet_service.delete_record(wallet_handle, CRED_DEF_CACHE, record.get_id())?;
+            self.wallet_service.delete_record(wallet_handle, which_cache, record.get_id())?;
         }
 
-        trace!("purge_cred_def_cache <<< res: ()");
-
         Ok(())
     }
 }
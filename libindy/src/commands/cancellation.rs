@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use indy_api_types::CommandHandle;
+use indy_api_types::errors::prelude::*;
+
+// Long-running commands (pool requests, proof generation, wallet search) can be abandoned by the caller.
+// Such command registers a handler that calls its callback with Cancelled error,
+// the result of cancelled command is dropped when it's ready.
+// Cancellation doesn't stop the work of the command: proof generation keeps running on the thread pool
+// and a pool request is still sent to the ledger, only the result isn't returned.
+//
+// Commands are cancelled by their command handles, so a handle can be used by one pending cancellable command at a time.
+// Every registration gets an internal id, so completion and timeout of a command never affect
+// a later command reusing its handle.
+lazy_static! {
+    static ref PENDING_COMMANDS: Mutex<HashMap<CommandHandle, PendingCommand>> = Default::default();
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub type CancellationId = usize;
+
+struct PendingCommand {
+    id: CancellationId,
+    cancel_handler: Box<dyn FnOnce() + Send>,
+}
+
+// Fails if another cancellable command with the same handle is pending
+pub fn register(command_handle: CommandHandle, cancel_handler: Box<dyn FnOnce() + Send>) -> IndyResult<CancellationId> {
+    let mut pending_commands = PENDING_COMMANDS.lock().unwrap();
+
+    if pending_commands.contains_key(&command_handle) {
+        return Err(err_msg(IndyErrorKind::InvalidParam(1), format!("Cancellable command with handle {} is already pending", command_handle)));
+    }
+
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    pending_commands.insert(command_handle, PendingCommand { id, cancel_handler });
+
+    Ok(id)
+}
+
+// Returns false if the command was cancelled and its callback was already called
+pub fn complete(command_handle: CommandHandle, id: CancellationId) -> bool {
+    _remove(command_handle, Some(id)).is_some()
+}
+
+pub fn cancel(command_handle: CommandHandle) -> IndyResult<()> {
+    _cancel(command_handle, None)
+}
+
+pub fn cancel_after(command_handle: CommandHandle, timeout: Duration) -> IndyResult<()> {
+    let id = PENDING_COMMANDS.lock().unwrap()
+        .get(&command_handle)
+        .map(|pending_command| pending_command.id)
+        .ok_or_else(|| _not_pending_error(command_handle))?;
+
+    thread::spawn(move || {
+        thread::sleep(timeout);
+        // The command may be completed already
+        _cancel(command_handle, Some(id)).ok();
+    });
+
+    Ok(())
+}
+
+pub fn cancelled_error(command_handle: CommandHandle) -> IndyError {
+    err_msg(IndyErrorKind::Cancelled, format!("Command {} was cancelled", command_handle))
+}
+
+fn _cancel(command_handle: CommandHandle, id: Option<CancellationId>) -> IndyResult<()> {
+    let pending_command = _remove(command_handle, id)
+        .ok_or_else(|| _not_pending_error(command_handle))?;
+
+    // Callback is called on a separate thread to not block the caller and the command executor
+    thread::spawn(move || (pending_command.cancel_handler)());
+
+    Ok(())
+}
+
+// Removes the pending command with the handle if it is the registration with the id (any if id isn't set)
+fn _remove(command_handle: CommandHandle, id: Option<CancellationId>) -> Option<PendingCommand> {
+    let mut pending_commands = PENDING_COMMANDS.lock().unwrap();
+
+    match pending_commands.get(&command_handle) {
+        Some(pending_command) if id.map(|id| id == pending_command.id).unwrap_or(true) => pending_commands.remove(&command_handle),
+        _ => None
+    }
+}
+
+fn _not_pending_error(command_handle: CommandHandle) -> IndyError {
+    err_msg(IndyErrorKind::InvalidParam(1), format!("There is no pending cancellable command with handle {}", command_handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn cancel_works() {
+        let (sender, receiver) = channel();
+
+        let id = register(-1001, Box::new(move || sender.send(()).unwrap())).unwrap();
+        cancel(-1001).unwrap();
+
+        receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(!complete(-1001, id));
+    }
+
+    #[test]
+    fn cancel_works_for_completed_command() {
+        let id = register(-1002, Box::new(|| panic!("Cancel handler of completed command was called"))).unwrap();
+
+        assert!(complete(-1002, id));
+        assert_kind!(IndyErrorKind::InvalidParam(1), cancel(-1002));
+    }
+
+    #[test]
+    fn cancel_after_works() {
+        let (sender, receiver) = channel();
+
+        let id = register(-1003, Box::new(move || sender.send(()).unwrap())).unwrap();
+        cancel_after(-1003, Duration::from_millis(10)).unwrap();
+
+        receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(!complete(-1003, id));
+    }
+
+    #[test]
+    fn cancel_after_works_for_unknown_command() {
+        assert_kind!(IndyErrorKind::InvalidParam(1), cancel_after(-1004, Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn register_fails_for_pending_command_handle() {
+        let id = register(-1005, Box::new(|| panic!("Cancel handler of completed command was called"))).unwrap();
+
+        assert_kind!(IndyErrorKind::InvalidParam(1), register(-1005, Box::new(|| ())));
+
+        assert!(complete(-1005, id));
+    }
+
+    #[test]
+    fn cancel_after_does_not_cancel_command_reusing_handle() {
+        let id = register(-1006, Box::new(|| panic!("Cancel handler of completed command was called"))).unwrap();
+        cancel_after(-1006, Duration::from_millis(10)).unwrap();
+        assert!(complete(-1006, id));
+
+        let (sender, receiver) = channel();
+        let id = register(-1006, Box::new(move || sender.send(()).unwrap())).unwrap();
+
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        assert!(complete(-1006, id));
+    }
+}
@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use crate::domain::crypto::key::{Key, KeyInfo, KeyMetadata};
 use crate::domain::crypto::pack::*;
+use crate::domain::crypto::jws::*;
 use indy_api_types::errors::prelude::*;
-use crate::services::crypto::CryptoService;
+use crate::services::crypto::{CryptoService, DEFAULT_CRYPTO_TYPE, SECP256K1_CRYPTO_TYPE};
+use crate::utils::crypto::verkey_builder::split_verkey;
+use rust_base58::{FromBase58, ToBase58};
 use indy_wallet::{RecordOptions, WalletService};
 
 use std::rc::Rc;
@@ -12,12 +15,16 @@ use indy_utils::crypto::base64;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use crate::domain::crypto::combo_box::ComboBox;
 use indy_api_types::WalletHandle;
+use indy_api_types::crypto::{KeyManagementCreateKey, KeyManagementFree, KeyManagementSign};
 
 pub const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
 pub const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
 pub const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
 pub const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
 
+const DID_KEY_PREFIX: &str = "did:key:";
+const ED25519_MULTICODEC_PREFIX: [u8; 2] = [0xed, 0x01];
+
 pub enum CryptoCommand {
     CreateKey(
         WalletHandle,
@@ -83,6 +90,53 @@ pub enum CryptoCommand {
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    Ed25519ToX25519(
+        String, // verkey
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    X25519DiffieHellman(
+        WalletHandle,
+        String, // my vk
+        String, // their X25519 key
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    SignJws(
+        WalletHandle,
+        Vec<String>, // signer verkeys
+        Vec<u8>,     // payload
+        SignJwsOptions,
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    VerifyJws(
+        String, // jws
+        Box<dyn Fn(IndyResult<(String, Vec<u8>)>) + Send>,
+    ),
+    BlsCreateKeys(
+        Option<String>, // seed
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    BlsSign(
+        String,  // sign key
+        Vec<u8>, // msg
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    BlsAggregateSignatures(
+        Vec<String>, // signatures
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    BlsVerifyMultiSig(
+        String,      // multi-signature
+        Vec<u8>,     // msg
+        Vec<String>, // verkeys of signers
+        Box<dyn Fn(IndyResult<bool>) + Send>,
+    ),
+    RegisterKeyManagement(
+        String, // name
+        KeyManagementCreateKey, // create key
+        KeyManagementSign, // sign
+        KeyManagementFree, // free
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
 }
 
 pub struct CryptoCommandExecutor {
@@ -147,6 +201,42 @@ impl CryptoCommandExecutor {
                 debug!("UnpackMessage command received");
                 cb(self.unpack_msg(jwe_json, wallet_handle));
             }
+            CryptoCommand::Ed25519ToX25519(verkey, cb) => {
+                debug!("Ed25519ToX25519 command received");
+                cb(self.crypto_service.convert_ed25519_to_x25519(&verkey));
+            }
+            CryptoCommand::X25519DiffieHellman(wallet_handle, my_vk, their_x25519_key, cb) => {
+                debug!("X25519DiffieHellman command received");
+                cb(self.x25519_diffie_hellman(wallet_handle, &my_vk, &their_x25519_key));
+            }
+            CryptoCommand::SignJws(wallet_handle, signer_vks, payload, options, cb) => {
+                debug!("SignJws command received");
+                cb(self.sign_jws(wallet_handle, &signer_vks, &payload, &options));
+            }
+            CryptoCommand::VerifyJws(jws, cb) => {
+                debug!("VerifyJws command received");
+                cb(self.verify_jws(&jws));
+            }
+            CryptoCommand::BlsCreateKeys(seed, cb) => {
+                debug!("BlsCreateKeys command received");
+                cb(self.bls_create_keys(seed.as_ref().map(String::as_str)));
+            }
+            CryptoCommand::BlsSign(sign_key, msg, cb) => {
+                debug!("BlsSign command received");
+                cb(self.crypto_service.bls_sign(&sign_key, &msg));
+            }
+            CryptoCommand::BlsAggregateSignatures(signatures, cb) => {
+                debug!("BlsAggregateSignatures command received");
+                cb(self.crypto_service.bls_aggregate_signatures(&signatures));
+            }
+            CryptoCommand::BlsVerifyMultiSig(multi_sig, msg, ver_keys, cb) => {
+                debug!("BlsVerifyMultiSig command received");
+                cb(self.crypto_service.bls_verify_multi_sig(&multi_sig, &msg, &ver_keys));
+            }
+            CryptoCommand::RegisterKeyManagement(name, create_key, sign, free, cb) => {
+                debug!("RegisterKeyManagement command received");
+                cb(self.crypto_service.register_key_management(&name, create_key, sign, free));
+            }
         };
     }
 
@@ -166,6 +256,22 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn bls_create_keys(&self, seed: Option<&str>) -> IndyResult<String> {
+        debug!("bls_create_keys >>> seed: {:?}", secret!(seed));
+
+        let (sign_key, ver_key, pop) = self.crypto_service.bls_create_keys(seed)?;
+
+        let res = json!({
+            "sign_key": sign_key,
+            "ver_key": ver_key,
+            "pop": pop,
+        }).to_string();
+
+        debug!("bls_create_keys <<< ver_key: {:?}", ver_key);
+
+        Ok(res)
+    }
+
     fn crypto_sign(&self, wallet_handle: WalletHandle, my_vk: &str, msg: &[u8]) -> IndyResult<Vec<u8>> {
         trace!(
             "crypto_sign >>> wallet_handle: {:?}, sender_vk: {:?}, msg: {:?}",
@@ -187,6 +293,27 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn x25519_diffie_hellman(&self, wallet_handle: WalletHandle, my_vk: &str, their_x25519_key: &str) -> IndyResult<Vec<u8>> {
+        trace!(
+            "x25519_diffie_hellman >>> wallet_handle: {:?}, my_vk: {:?}, their_x25519_key: {:?}",
+            wallet_handle, my_vk, their_x25519_key
+        );
+
+        self.crypto_service.validate_key(my_vk)?;
+
+        let key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            &my_vk,
+            &RecordOptions::id_value(),
+        )?;
+
+        let res = self.crypto_service.x25519_diffie_hellman(&key, their_x25519_key)?;
+
+        trace!("x25519_diffie_hellman <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
     fn crypto_verify(&self,
                      their_vk: &str,
                      msg: &[u8],
@@ -357,6 +484,182 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    // RFC 7515 JWS. Protected header contains `alg` and `kid` (base58 verkey), Ed25519 keys are also set as `jwk`.
+    fn sign_jws(&self, wallet_handle: WalletHandle, signer_vks: &[String], payload: &[u8], options: &SignJwsOptions) -> IndyResult<String> {
+        trace!("sign_jws >>> wallet_handle: {:?}, signer_vks: {:?}, payload: {:?}, options: {:?}", wallet_handle, signer_vks, payload, options);
+
+        if signer_vks.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "No signer keys found"));
+        }
+
+        if options.format == JwsFormat::Compact && signer_vks.len() > 1 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS compact serialization supports a single signer only"));
+        }
+
+        let payload = CryptoCommandExecutor::_jws_encode(payload);
+
+        let signatures = signer_vks.iter()
+            .map(|signer_vk| self._jws_signature(wallet_handle, signer_vk, &payload))
+            .collect::<IndyResult<Vec<JwsSignature>>>()?;
+
+        let res = match options.format {
+            JwsFormat::Compact => format!("{}.{}.{}", signatures[0].protected, payload, signatures[0].signature),
+            JwsFormat::General => json!(GeneralJws { payload, signatures }).to_string(),
+        };
+
+        trace!("sign_jws <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // All the signatures must be valid. Returns verkeys of the signers and decoded payload.
+    fn verify_jws(&self, jws: &str) -> IndyResult<(String, Vec<u8>)> {
+        trace!("verify_jws >>> jws: {:?}", jws);
+
+        let jws = CryptoCommandExecutor::_parse_jws(jws)?;
+
+        if jws.signatures.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS doesn't contain signatures"));
+        }
+
+        let mut signers: Vec<String> = Vec::with_capacity(jws.signatures.len());
+
+        for signature in jws.signatures.iter() {
+            let protected: JwsHeader = serde_json::from_slice(&CryptoCommandExecutor::_jws_decode(&signature.protected)?)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS protected header")?;
+
+            let signer_vk = CryptoCommandExecutor::_jws_signer_verkey(&protected, signature.header.as_ref())?;
+            let signing_input = format!("{}.{}", signature.protected, jws.payload);
+
+            if !self.crypto_service.verify(&signer_vk, signing_input.as_bytes(), &CryptoCommandExecutor::_jws_decode(&signature.signature)?)? {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid JWS signature of key: {}", signer_vk)));
+            }
+
+            signers.push(signer_vk);
+        }
+
+        let res = (json!(signers).to_string(), CryptoCommandExecutor::_jws_decode(&jws.payload)?);
+
+        trace!("verify_jws <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _jws_signature(&self, wallet_handle: WalletHandle, signer_vk: &str, payload: &str) -> IndyResult<JwsSignature> {
+        self.crypto_service.validate_key(signer_vk)?;
+
+        let key: Key = self.wallet_service.get_indy_object(wallet_handle, signer_vk, &RecordOptions::id_value())?;
+
+        let (verkey, crypto_type_name) = split_verkey(signer_vk);
+
+        let protected = match crypto_type_name {
+            DEFAULT_CRYPTO_TYPE => JwsHeader {
+                alg: Some(ALG_EDDSA.to_string()),
+                kid: Some(verkey.to_string()),
+                jwk: Some(Jwk {
+                    kty: JWK_KTY_OKP.to_string(),
+                    crv: JWK_CRV_ED25519.to_string(),
+                    x: CryptoCommandExecutor::_jws_encode(&verkey.from_base58()?),
+                }),
+            },
+            SECP256K1_CRYPTO_TYPE => JwsHeader {
+                alg: Some(ALG_ES256K.to_string()),
+                kid: Some(verkey.to_string()),
+                jwk: None,
+            },
+            _ => return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("JWS signing is not supported for crypto: {}", crypto_type_name)))
+        };
+
+        let protected = CryptoCommandExecutor::_jws_encode(json!(protected).to_string().as_bytes());
+        let signature = self.crypto_service.sign(&key, format!("{}.{}", protected, payload).as_bytes())?;
+
+        Ok(JwsSignature {
+            protected,
+            header: None,
+            signature: CryptoCommandExecutor::_jws_encode(&signature),
+        })
+    }
+
+    fn _parse_jws(jws: &str) -> IndyResult<GeneralJws> {
+        let jws = jws.trim();
+
+        if !jws.starts_with('{') {
+            let parts: Vec<&str> = jws.split('.').collect();
+
+            if parts.len() != 3 {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "JWS compact serialization must consist of 3 parts"));
+            }
+
+            return Ok(GeneralJws {
+                payload: parts[1].to_string(),
+                signatures: vec![JwsSignature { protected: parts[0].to_string(), header: None, signature: parts[2].to_string() }],
+            });
+        }
+
+        let json: serde_json::Value = serde_json::from_str(jws)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS JSON serialization")?;
+
+        if json["signatures"].is_array() {
+            serde_json::from_value(json)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS general JSON serialization")
+        } else {
+            let flattened: FlattenedJws = serde_json::from_value(json)
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid JWS flattened JSON serialization")?;
+            Ok(GeneralJws { payload: flattened.payload, signatures: vec![flattened.signature] })
+        }
+    }
+
+    // Ed25519 key is taken from `jwk` if set, `kid` may be either base58 verkey or `did:key` DID
+    fn _jws_signer_verkey(protected: &JwsHeader, header: Option<&JwsHeader>) -> IndyResult<String> {
+        let alg = protected.alg.as_ref().map(String::as_str)
+            .ok_or(err_msg(IndyErrorKind::InvalidStructure, "JWS protected header doesn't contain alg"))?;
+
+        let kid = protected.kid.as_ref().or(header.and_then(|header| header.kid.as_ref()));
+
+        match alg {
+            ALG_EDDSA => {
+                if let Some(ref jwk) = protected.jwk {
+                    if jwk.kty != JWK_KTY_OKP || jwk.crv != JWK_CRV_ED25519 {
+                        return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported JWK: {} {}", jwk.kty, jwk.crv)));
+                    }
+                    return Ok(CryptoCommandExecutor::_jws_decode(&jwk.x)?.to_base58());
+                }
+
+                match kid {
+                    Some(kid) if kid.starts_with(DID_KEY_PREFIX) => CryptoCommandExecutor::_did_key_to_verkey(kid),
+                    Some(kid) => Ok(kid.to_string()),
+                    None => Err(err_msg(IndyErrorKind::InvalidStructure, "JWS header contains neither kid nor jwk")),
+                }
+            }
+            ALG_ES256K => {
+                let kid = kid.ok_or(err_msg(IndyErrorKind::InvalidStructure, "JWS header doesn't contain kid"))?;
+                Ok(format!("{}:{}", kid, SECP256K1_CRYPTO_TYPE))
+            }
+            _ => Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported JWS alg: {}", alg)))
+        }
+    }
+
+    fn _did_key_to_verkey(did: &str) -> IndyResult<String> {
+        let key = did[DID_KEY_PREFIX.len()..].split('#').next().unwrap_or_default();
+
+        let bytes = if key.starts_with('z') { key[1..].from_base58()? } else { vec![] };
+
+        if bytes.len() != ED25519_MULTICODEC_PREFIX.len() + 32 || !bytes.starts_with(&ED25519_MULTICODEC_PREFIX) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported did:key: {}", did)));
+        }
+
+        Ok(bytes[ED25519_MULTICODEC_PREFIX.len()..].to_base58())
+    }
+
+    fn _jws_encode(bytes: &[u8]) -> String {
+        base64::encode_urlsafe(bytes).trim_end_matches('=').to_string()
+    }
+
+    // Padding is not used in JWS, but some implementations add it
+    fn _jws_decode(value: &str) -> IndyResult<Vec<u8>> {
+        base64::decode_urlsafe(value.trim_end_matches('='))
+    }
+
     //TODO: Refactor pack to be more modular to version changes or crypto_scheme changes
     //this match statement is super messy, but the easiest way to comply with current architecture
     pub fn pack_msg(
@@ -7,7 +7,7 @@ use serde_json;
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::ledger::LedgerCommand;
 use crate::domain::crypto::did::{Did, DidValue, DidMetadata, DidWithMeta, MyDidInfo, TemporaryDid, TheirDid, TheirDidInfo, DidMethod};
-use crate::domain::crypto::key::KeyInfo;
+use crate::domain::crypto::key::{Key, KeyInfo};
 use crate::domain::ledger::attrib::{AttribData, Endpoint, GetAttrReplyResult};
 use crate::domain::ledger::nym::{GetNymReplyResult, GetNymResultDataV0};
 use crate::domain::ledger::response::Reply;
@@ -96,6 +96,21 @@ pub enum DidCommand {
         DidMethod, // method
         Box<dyn Fn(IndyResult<String /*full qualified did*/>) + Send>,
     ),
+    RotateKey(
+        PoolHandle, // pool handle
+        WalletHandle,
+        KeyInfo, // key info
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String /*new verkey*/>) + Send>),
+    // Internal commands
+    RotateKeyAck(
+        WalletHandle,
+        DidValue, // my did
+        String, // old verkey
+        String, // new verkey
+        IndyResult<String>, // Nym Result
+        CommandHandle, // cb id
+    ),
 }
 
 macro_rules! ensure_their_did {
@@ -114,6 +129,7 @@ pub struct DidCommandExecutor {
     crypto_service: Rc<CryptoService>,
     ledger_service: Rc<LedgerService>,
     deferred_commands: RefCell<HashMap<CommandHandle, DidCommand>>,
+    rotate_key_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
 }
 
 impl DidCommandExecutor {
@@ -125,6 +141,7 @@ impl DidCommandExecutor {
             crypto_service,
             ledger_service,
             deferred_commands: RefCell::new(HashMap::new()),
+            rotate_key_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -194,6 +211,14 @@ impl DidCommandExecutor {
                 debug!("QualifyDid command received");
                 cb(self.qualify_did(wallet_handle, &did, &method));
             }
+            DidCommand::RotateKey(pool_handle, wallet_handle, key_info, did, cb) => {
+                debug!("RotateKey command received");
+                self.rotate_key(pool_handle, wallet_handle, &key_info, &did, cb);
+            }
+            DidCommand::RotateKeyAck(wallet_handle, did, old_verkey, new_verkey, result, cb_id) => {
+                debug!("RotateKeyAck command received");
+                self.rotate_key_ack(wallet_handle, &did, &old_verkey, &new_verkey, result, cb_id);
+            }
         };
     }
 
@@ -583,6 +608,154 @@ impl DidCommandExecutor {
         Ok(curr_did.did.0)
     }
 
+    fn rotate_key(&self,
+                  pool_handle: PoolHandle,
+                  wallet_handle: WalletHandle,
+                  key_info: &KeyInfo,
+                  my_did: &DidValue,
+                  cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("rotate_key >>> pool_handle: {:?}, wallet_handle: {:?}, key_info: {:?}, my_did: {:?}", pool_handle, wallet_handle, secret!(key_info), my_did);
+
+        try_cb!(self.crypto_service.validate_did(my_did), cb);
+
+        let my_did = try_cb!(self._wallet_get_my_did(wallet_handle, my_did), cb);
+
+        let new_verkey = try_cb!(self.replace_keys_start(wallet_handle, key_info, &my_did.did), cb);
+
+        let nym_request = match self.ledger_service.build_nym_request(&my_did.did, &my_did.did, Some(&new_verkey), None, None) {
+            Ok(nym_request) => nym_request,
+            Err(err) => {
+                self._rollback_rotate_key(wallet_handle, &my_did.did, &new_verkey);
+                return cb(Err(err));
+            }
+        };
+
+        let cb_id = next_command_handle();
+        self.rotate_key_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let did = my_did.did.clone();
+        let old_verkey = my_did.verkey;
+
+        // NYM is signed by the current key of the DID as the temporary key isn't applied yet
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
+                pool_handle,
+                wallet_handle,
+                my_did.did,
+                nym_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::RotateKeyAck(
+                            wallet_handle,
+                            did.clone(),
+                            old_verkey.clone(),
+                            new_verkey.clone(),
+                            result,
+                            cb_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+
+        debug!("rotate_key <<<");
+    }
+
+    fn rotate_key_ack(&self,
+                      wallet_handle: WalletHandle,
+                      my_did: &DidValue,
+                      old_verkey: &str,
+                      new_verkey: &str,
+                      nym_result: IndyResult<String>,
+                      cb_id: CommandHandle) {
+        let res = self._rotate_key_ack(wallet_handle, my_did, old_verkey, new_verkey, nym_result);
+
+        match self.rotate_key_callbacks.borrow_mut().remove(&cb_id) {
+            Some(cb) => cb(res),
+            None => error!("Can't process DidCommand::RotateKeyAck for handle {:?} with result {:?} - appropriate callback not found!", cb_id, res)
+        }
+    }
+
+    fn _rotate_key_ack(&self,
+                       wallet_handle: WalletHandle,
+                       my_did: &DidValue,
+                       old_verkey: &str,
+                       new_verkey: &str,
+                       nym_result: IndyResult<String>) -> IndyResult<String> {
+        trace!("_rotate_key_ack >>> wallet_handle: {:?}, my_did: {:?}, old_verkey: {:?}, new_verkey: {:?}, nym_result: {:?}",
+               wallet_handle, my_did, old_verkey, new_verkey, nym_result);
+
+        match nym_result.and_then(|response| DidCommandExecutor::_check_nym_response(&response)) {
+            Ok(()) => {}
+            // The NYM may be written even though the reply wasn't received, so the temporary key is kept
+            // to be applied with `replace_keys_apply` once the ledger state is known
+            Err(err) if err.kind() == IndyErrorKind::PoolTimeout => return Err(err),
+            Err(err) => {
+                self._rollback_rotate_key(wallet_handle, my_did, new_verkey);
+                return Err(err);
+            }
+        }
+
+        self.replace_keys_apply(wallet_handle, my_did)?;
+
+        self._update_verkey_references(wallet_handle, old_verkey, new_verkey)?;
+
+        let res = new_verkey.to_string();
+
+        trace!("_rotate_key_ack <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _check_nym_response(response: &str) -> IndyResult<()> {
+        let response: serde_json::Value = serde_json::from_str(response)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Response is invalid json")?;
+
+        match response["op"].as_str() {
+            Some("REPLY") => Ok(()),
+            _ => Err(err_msg(IndyErrorKind::InvalidTransaction, format!("Transaction has been failed: {:?}", response["reason"])))
+        }
+    }
+
+    fn _rollback_rotate_key(&self, wallet_handle: WalletHandle, my_did: &DidValue, new_verkey: &str) {
+        if let Err(err) = self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, &my_did.0)
+            .and_then(|_| self.wallet_service.delete_indy_record::<Key>(wallet_handle, new_verkey)) {
+            warn!("Can't rollback keys rotation for DID {:?}: {:?}", my_did, err);
+        }
+    }
+
+    fn _update_verkey_references(&self, wallet_handle: WalletHandle, old_verkey: &str, new_verkey: &str) -> IndyResult<()> {
+        let mut their_did_search =
+            self.wallet_service.search_indy_records::<TheirDid>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        while let Some(their_did_record) = their_did_search.fetch_next_record()? {
+            let mut their_did: TheirDid = their_did_record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value for Their DID record"))
+                .and_then(|their_did_json| serde_json::from_str(&their_did_json)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize Their DID"))?;
+
+            if their_did.verkey == old_verkey {
+                their_did.verkey = new_verkey.to_string();
+                self.wallet_service.update_indy_object(wallet_handle, their_did_record.get_id(), &their_did)?;
+            }
+        }
+
+        let mut endpoint_search =
+            self.wallet_service.search_indy_records::<Endpoint>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        while let Some(endpoint_record) = endpoint_search.fetch_next_record()? {
+            let mut endpoint: Endpoint = endpoint_record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value for Endpoint record"))
+                .and_then(|endpoint_json| serde_json::from_str(&endpoint_json)
+                    .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize Endpoint"))?;
+
+            if endpoint.verkey.as_ref().map(String::as_str) == Some(old_verkey) {
+                endpoint.verkey = Some(new_verkey.to_string());
+                self.wallet_service.update_indy_object(wallet_handle, endpoint_record.get_id(), &endpoint)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn update_dependent_entity_reference<T>(&self, wallet_handle: WalletHandle, id: &str, new_id: &str) -> IndyResult<()>
         where T: ::serde::Serialize + ::serde::de::DeserializeOwned + Sized {
         if let Ok(record) = self.wallet_service.get_indy_record_value::<T>(wallet_handle, id, "{}") {
@@ -703,6 +876,9 @@ impl DidCommandExecutor {
             DidCommand::GetEndpointForDid(_, _, _, cb) => {
                 cb(Err(err));
             }
+            DidCommand::RotateKey(_, _, _, _, cb) => {
+                cb(Err(err));
+            }
             _ => {}
         }
     }
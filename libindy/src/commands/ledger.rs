@@ -1,9 +1,10 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::string::ToString;
 
-use indy_api_types::{CommandHandle, PoolHandle, WalletHandle};
+use indy_api_types::{CommandHandle, PoolHandle, WalletHandle, INVALID_POOL_HANDLE};
 use indy_api_types::errors::prelude::*;
 use indy_utils::next_command_handle;
 use rust_base58::ToBase58;
@@ -21,10 +22,13 @@ use crate::domain::anoncreds::schema::{Schema, SchemaId, SchemaV1};
 use crate::domain::crypto::did::{Did, DidValue};
 use crate::domain::crypto::key::Key;
 use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
-use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
+use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, POOL_RESTART};
+use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, ActiveTxnAuthorAgreement, GetTxnAuthorAgreementData, TxnAuthorAgreement};
 use crate::domain::ledger::node::NodeOperationData;
 use crate::domain::ledger::pool::Schedule;
-use crate::domain::ledger::request::Request;
+use crate::domain::ledger::request::{Request, TxnAuthrAgrmtAcceptanceData};
+use crate::domain::ledger::rich_schema::{RichSchema, RichSchemaType};
+use crate::domain::pool::ReadRequestOptions;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
 use crate::services::pool::{
@@ -32,6 +36,7 @@ use crate::services::pool::{
     PoolService
 };
 use crate::utils::crypto::signature_serializer::serialize_signature;
+use crate::utils::logger;
 
 pub enum LedgerCommand {
     SignAndSubmitRequest(
@@ -54,6 +59,11 @@ pub enum LedgerCommand {
         Option<String>, // nodes
         Option<i32>, // timeout
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SubmitReadRequest(
+        PoolHandle, // pool handle
+        String, // request json
+        ReadRequestOptions,
+        Box<dyn Fn(IndyResult<String>) + Send>),
     SignRequest(
         WalletHandle,
         DidValue, // submitter did
@@ -239,6 +249,39 @@ pub enum LedgerCommand {
         IndyResult<String>,
         CommandHandle,
     ),
+    GetRevocRegDef(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        BoxedCallbackStringStringSend,
+    ),
+    GetRevocRegDefContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    GetRevocReg(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        i64, // timestamp
+        Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>,
+    ),
+    GetRevocRegContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    GetRevocRegDelta(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        Option<i64>, // from
+        i64, // to
+        Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>,
+    ),
+    GetRevocRegDeltaContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
     BuildTxnAuthorAgreementRequest(
         DidValue, // submitter did
         Option<String>, // text
@@ -272,10 +315,44 @@ pub enum LedgerCommand {
         String, // acceptance mechanism type
         u64, // time of acceptance
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SetTxnAuthorAgreementAcceptance(
+        Option<String>, // text
+        Option<String>, // version
+        Option<String>, // hash
+        Option<String>, // acceptance mechanism type
+        u64, // time of acceptance
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    GetActiveTxnAuthorAgreement(
+        PoolHandle,
+        Option<DidValue>, // submitter did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetActiveTxnAuthorAgreementContinue(
+        PoolHandle,
+        Option<DidValue>, // submitter did
+        IndyResult<String>, // GET_TXN_AUTHR_AGRMT response
+        CommandHandle,
+    ),
+    GetActiveAcceptanceMechanismsContinue(
+        Option<TxnAuthorAgreement>,
+        IndyResult<String>, // GET_TXN_AUTHR_AGRMT_AML response
+        CommandHandle,
+    ),
     AppendRequestEndorser(
         String, // request json
         DidValue, // endorser did
         Box<dyn Fn(IndyResult<String>) + Send>),
+    SignRequestForEndorser(
+        WalletHandle,
+        DidValue, // author did
+        String, // request json
+        DidValue, // endorser did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EndorseAndSubmitRequest(
+        PoolHandle,
+        WalletHandle,
+        DidValue, // endorser did
+        String, // request json
+        Box<dyn Fn(IndyResult<String>) + Send>),
     BuildGetFrozenLedgersRequest(
         DidValue, // submitter did
         Box<dyn Fn(IndyResult<String>) + Send>),
@@ -283,20 +360,62 @@ pub enum LedgerCommand {
         DidValue, // submitter did
         Vec<u64>, // ledgers ids
         Box<dyn Fn(IndyResult<String>) + Send>),
+    ParseGetFrozenLedgersResponse(
+        String, // response json
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    BuildRichSchemaRequest(
+        DidValue, // submitter did
+        RichSchema, // data
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    BuildGetRichSchemaObjectByIdRequest(
+        Option<DidValue>, // submitter did
+        String, // id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    BuildGetRichSchemaObjectByMetadataRequest(
+        Option<DidValue>, // submitter did
+        RichSchemaType, // rs type
+        String, // rs name
+        String, // rs version
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ParseGetRichSchemaObjectResponse(
+        String, // response json
+        BoxedCallbackStringStringSend),
+    GetRichSchemaObject(
+        PoolHandle,
+        Option<DidValue>,
+        String, // id
+        BoxedCallbackStringStringSend,
+    ),
+    GetRichSchemaObjectContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    ParsePoolRestartResponse(
+        String, // response json
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ParseGetValidatorInfoResponse(
+        String, // response json
+        Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 pub struct LedgerCommandExecutor {
-    pool_service: Rc<PoolService>,
+    pool_service: Arc<PoolService>,
     crypto_service: Rc<CryptoService>,
     wallet_service: Rc<WalletService>,
     ledger_service: Rc<LedgerService>,
 
-    send_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    // Callbacks of pool requests are kept with correlation id of the command that sent the request
+    send_callbacks: RefCell<HashMap<CommandHandle, (Option<String>, Box<dyn Fn(IndyResult<String>)>)>>,
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String)>)>>>,
+    pending_rev_reg_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String, u64)>)>>>,
+    pending_taa_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+
+    // acceptance appended to domain ledger write requests built by libindy
+    taa_acceptance: RefCell<Option<TxnAuthrAgrmtAcceptanceData>>,
 }
 
 impl LedgerCommandExecutor {
-    pub fn new(pool_service: Rc<PoolService>,
+    pub fn new(pool_service: Arc<PoolService>,
                crypto_service: Rc<CryptoService>,
                wallet_service: Rc<WalletService>,
                ledger_service: Rc<LedgerService>) -> LedgerCommandExecutor {
@@ -307,6 +426,9 @@ impl LedgerCommandExecutor {
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_rev_reg_callbacks: RefCell::new(HashMap::new()),
+            pending_taa_callbacks: RefCell::new(HashMap::new()),
+            taa_acceptance: RefCell::new(None),
         }
     }
 
@@ -323,7 +445,10 @@ impl LedgerCommandExecutor {
             LedgerCommand::SubmitAck(handle, result) => {
                 debug!(target: "ledger_command_executor", "SubmitAck command received");
                 match self.send_callbacks.borrow_mut().remove(&handle) {
-                    Some(cb) => cb(result.map_err(IndyError::from)),
+                    Some((correlation_id, cb)) => {
+                        logger::set_correlation_id(correlation_id);
+                        cb(result.map_err(IndyError::from))
+                    }
                     None => {
                         error!("Can't process LedgerCommand::SubmitAck for handle {:?} with result {:?} - appropriate callback not found!",
                                handle, result);
@@ -334,6 +459,10 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "SubmitRequest command received");
                 self.submit_action(handle, &request_json, nodes.as_ref().map(String::as_str), timeout, cb);
             }
+            LedgerCommand::SubmitReadRequest(handle, request_json, options, cb) => {
+                debug!(target: "ledger_command_executor", "SubmitReadRequest command received");
+                self.submit_read_request(handle, &request_json, &options, cb);
+            }
             LedgerCommand::RegisterSPParser(txn_type, parser, free, cb) => {
                 debug!(target: "ledger_command_executor", "RegisterSPParser command received");
                 cb(self.register_sp_parser(&txn_type, parser, free));
@@ -499,6 +628,30 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "GetCredDefContinue command received");
                 self._get_cred_def_continue(id, pool_response, cb_id);
             }
+            LedgerCommand::GetRevocRegDef(pool_handle, submitter_did, id, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDef command received");
+                self.get_revoc_reg_def(pool_handle, submitter_did.as_ref(), &id, cb);
+            }
+            LedgerCommand::GetRevocRegDefContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDefContinue command received");
+                self._get_revoc_reg_def_continue(pool_response, cb_id);
+            }
+            LedgerCommand::GetRevocReg(pool_handle, submitter_did, revoc_reg_def_id, timestamp, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocReg command received");
+                self.get_revoc_reg(pool_handle, submitter_did.as_ref(), &revoc_reg_def_id, timestamp, cb);
+            }
+            LedgerCommand::GetRevocRegContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegContinue command received");
+                self._get_revoc_reg_continue(pool_response, cb_id);
+            }
+            LedgerCommand::GetRevocRegDelta(pool_handle, submitter_did, revoc_reg_def_id, from, to, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDelta command received");
+                self.get_revoc_reg_delta(pool_handle, submitter_did.as_ref(), &revoc_reg_def_id, from, to, cb);
+            }
+            LedgerCommand::GetRevocRegDeltaContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDeltaContinue command received");
+                self._get_revoc_reg_delta_continue(pool_response, cb_id);
+            }
             LedgerCommand::BuildTxnAuthorAgreementRequest(submitter_did, text, version, ratification_ts, retirement_ts, cb) => {
                 debug!(target: "ledger_command_executor", "BuildTxnAuthorAgreementRequest command received");
                 cb(self.build_txn_author_agreement_request(&submitter_did, text.as_ref().map(String::as_str), &version, ratification_ts, retirement_ts));
@@ -530,11 +683,39 @@ impl LedgerCommandExecutor {
                                                                           &acc_mech_type,
                                                                           time_of_acceptance));
             }
+            LedgerCommand::SetTxnAuthorAgreementAcceptance(text, version, hash, acc_mech_type, time_of_acceptance, cb) => {
+                debug!(target: "ledger_command_executor", "SetTxnAuthorAgreementAcceptance command received");
+                cb(self.set_txn_author_agreement_acceptance(text.as_ref().map(String::as_str),
+                                                            version.as_ref().map(String::as_str),
+                                                            hash.as_ref().map(String::as_str),
+                                                            acc_mech_type.as_ref().map(String::as_str),
+                                                            time_of_acceptance));
+            }
+            LedgerCommand::GetActiveTxnAuthorAgreement(pool_handle, submitter_did, cb) => {
+                debug!(target: "ledger_command_executor", "GetActiveTxnAuthorAgreement command received");
+                self.get_active_txn_author_agreement(pool_handle, submitter_did.as_ref(), cb);
+            }
+            LedgerCommand::GetActiveTxnAuthorAgreementContinue(pool_handle, submitter_did, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetActiveTxnAuthorAgreementContinue command received");
+                self._get_active_txn_author_agreement_continue(pool_handle, submitter_did.as_ref(), pool_response, cb_id);
+            }
+            LedgerCommand::GetActiveAcceptanceMechanismsContinue(taa, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetActiveAcceptanceMechanismsContinue command received");
+                self._get_active_acceptance_mechanisms_continue(taa, pool_response, cb_id);
+            }
             LedgerCommand::AppendRequestEndorser(request_json, endorser_did, cb) => {
                 debug!(target: "ledger_command_executor", "AppendRequestEndorser command received");
                 cb(self.append_request_endorser(&request_json,
                                                 &endorser_did));
             }
+            LedgerCommand::SignRequestForEndorser(wallet_handle, author_did, request_json, endorser_did, cb) => {
+                debug!(target: "ledger_command_executor", "SignRequestForEndorser command received");
+                cb(self.sign_request_for_endorser(wallet_handle, &author_did, &request_json, &endorser_did));
+            }
+            LedgerCommand::EndorseAndSubmitRequest(pool_handle, wallet_handle, endorser_did, request_json, cb) => {
+                debug!(target: "ledger_command_executor", "EndorseAndSubmitRequest command received");
+                self.endorse_and_submit_request(pool_handle, wallet_handle, &endorser_did, &request_json, cb);
+            }
             LedgerCommand::BuildLedgersFreezeRequest(submitter_did, ledgers_ids, cb) => {
                 debug!(target: "ledger_command_executor", "BuildLedgersFreezeRequest command received");
                 cb(self.build_ledgers_freeze_request(&submitter_did, ledgers_ids));
@@ -543,6 +724,42 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "BuildGetFrozenLedgersRequest command received");
                 cb(self.build_get_frozen_ledgers_request(&submitter_did));
             }
+            LedgerCommand::ParseGetFrozenLedgersResponse(response, cb) => {
+                debug!(target: "ledger_command_executor", "ParseGetFrozenLedgersResponse command received");
+                cb(self.parse_get_frozen_ledgers_response(&response));
+            }
+            LedgerCommand::BuildRichSchemaRequest(submitter_did, data, cb) => {
+                debug!(target: "ledger_command_executor", "BuildRichSchemaRequest command received");
+                cb(self.build_rich_schema_request(&submitter_did, data));
+            }
+            LedgerCommand::BuildGetRichSchemaObjectByIdRequest(submitter_did, id, cb) => {
+                debug!(target: "ledger_command_executor", "BuildGetRichSchemaObjectByIdRequest command received");
+                cb(self.build_get_rich_schema_object_by_id_request(submitter_did.as_ref(), &id));
+            }
+            LedgerCommand::BuildGetRichSchemaObjectByMetadataRequest(submitter_did, rs_type, rs_name, rs_version, cb) => {
+                debug!(target: "ledger_command_executor", "BuildGetRichSchemaObjectByMetadataRequest command received");
+                cb(self.build_get_rich_schema_object_by_metadata_request(submitter_did.as_ref(), rs_type, &rs_name, &rs_version));
+            }
+            LedgerCommand::ParseGetRichSchemaObjectResponse(response, cb) => {
+                debug!(target: "ledger_command_executor", "ParseGetRichSchemaObjectResponse command received");
+                cb(self.parse_get_rich_schema_object_response(&response));
+            }
+            LedgerCommand::GetRichSchemaObject(pool_handle, submitter_did, id, cb) => {
+                debug!(target: "ledger_command_executor", "GetRichSchemaObject command received");
+                self.get_rich_schema_object(pool_handle, submitter_did.as_ref(), &id, cb);
+            }
+            LedgerCommand::GetRichSchemaObjectContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRichSchemaObjectContinue command received");
+                self._get_rich_schema_object_continue(pool_response, cb_id);
+            }
+            LedgerCommand::ParsePoolRestartResponse(response, cb) => {
+                debug!(target: "ledger_command_executor", "ParsePoolRestartResponse command received");
+                cb(self.parse_action_response(&response, POOL_RESTART));
+            }
+            LedgerCommand::ParseGetValidatorInfoResponse(response, cb) => {
+                debug!(target: "ledger_command_executor", "ParseGetValidatorInfoResponse command received");
+                cb(self.parse_action_response(&response, GET_VALIDATOR_INFO));
+            }
         };
     }
 
@@ -564,12 +781,22 @@ impl LedgerCommandExecutor {
         debug!("sign_and_submit_request >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, request_json: {:?}",
                pool_handle, wallet_handle, submitter_did, request_json);
 
+        let pool_handle = try_cb!(self._route_request(pool_handle, submitter_did), cb);
+
         match self._sign_request(wallet_handle, submitter_did, request_json, SignatureType::Single) {
             Ok(signed_request) => self.submit_request(pool_handle, signed_request.as_str(), cb),
             Err(err) => cb(Err(err))
         }
     }
 
+    // requests sent to the invalid pool handle are routed to the pool opened for the network of did:indy DID
+    fn _route_request(&self, pool_handle: PoolHandle, did: &DidValue) -> IndyResult<PoolHandle> {
+        match (pool_handle, did.get_namespace()) {
+            (INVALID_POOL_HANDLE, Some(namespace)) => self.pool_service.get_pool_handle_for_network(&namespace),
+            _ => Ok(pool_handle)
+        }
+    }
+
     fn _sign_request(&self,
                      wallet_handle: WalletHandle,
                      submitter_did: &DidValue,
@@ -630,7 +857,7 @@ impl LedgerCommandExecutor {
 
         let x: IndyResult<CommandHandle> = self.pool_service.send_tx(handle, request_json);
         match x {
-            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, cb); }
+            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, (logger::get_correlation_id(), cb)); }
             Err(err) => { cb(Err(err)); }
         };
     }
@@ -649,7 +876,32 @@ impl LedgerCommandExecutor {
 
         let x: IndyResult<CommandHandle> = self.pool_service.send_action(handle, request_json, nodes, timeout);
         match x {
-            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, cb); }
+            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, (logger::get_correlation_id(), cb)); }
+            Err(err) => { cb(Err(err)); }
+        };
+    }
+
+    fn submit_read_request(&self,
+                           handle: PoolHandle,
+                           request_json: &str,
+                           options: &ReadRequestOptions,
+                           cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("submit_read_request >>> handle: {:?}, request_json: {:?}, options: {:?}", handle, request_json, options);
+
+        let request = match serde_json::from_str::<Request<serde_json::Value>>(&request_json) {
+            Ok(request) => request,
+            Err(err) => return cb(Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Request is invalid json: {:?}", err))))
+        };
+
+        match request.operation["type"].as_str() {
+            Some(txn_type) if PoolService::is_read_request(txn_type) => {}
+            Some(txn_type) => return cb(Err(err_msg(IndyErrorKind::InvalidStructure, format!("Request of type {} can't be sent as read request", txn_type)))),
+            None => return cb(Err(err_msg(IndyErrorKind::InvalidStructure, "No valid type field in request")))
+        }
+
+        let x: IndyResult<CommandHandle> = self.pool_service.send_read_request(handle, request_json, options);
+        match x {
+            Ok(cmd_id) => { self.send_callbacks.borrow_mut().insert(cmd_id, (logger::get_correlation_id(), cb)); }
             Err(err) => { cb(Err(err)); }
         };
     }
@@ -711,6 +963,7 @@ impl LedgerCommandExecutor {
                                                         verkey,
                                                         alias,
                                                         role)?;
+        let res = self._append_taa_acceptance(res)?;
 
         debug!("build_nym_request <<< res: {:?}", res);
 
@@ -734,6 +987,7 @@ impl LedgerCommandExecutor {
                                                            hash,
                                                            raw,
                                                            enc)?;
+        let res = self._append_taa_acceptance(res)?;
 
         debug!("build_attrib_request <<< res: {:?}", res);
 
@@ -798,6 +1052,7 @@ impl LedgerCommandExecutor {
         self.crypto_service.validate_did(submitter_did)?;
 
         let res = self.ledger_service.build_schema_request(submitter_did, schema)?;
+        let res = self._append_taa_acceptance(res)?;
 
         debug!("build_schema_request <<< res: {:?}", res);
 
@@ -838,6 +1093,7 @@ impl LedgerCommandExecutor {
         self.crypto_service.validate_did(submitter_did)?;
 
         let res = self.ledger_service.build_cred_def_request(submitter_did, cred_def)?;
+        let res = self._append_taa_acceptance(res)?;
 
         debug!("build_cred_def_request <<< res: {:?}", res);
 
@@ -977,6 +1233,7 @@ impl LedgerCommandExecutor {
         self.crypto_service.validate_did(&submitter_did)?;
 
         let res = self.ledger_service.build_revoc_reg_def_request(&submitter_did, data)?;
+        let res = self._append_taa_acceptance(res)?;
 
         debug!("build_revoc_reg_def_request  <<< res: {:?}", res);
 
@@ -1019,6 +1276,7 @@ impl LedgerCommandExecutor {
         self.crypto_service.validate_did(&submitter_did)?;
 
         let res = self.ledger_service.build_revoc_reg_entry_request(&submitter_did, revoc_reg_def_id, revoc_def_type, value)?;
+        let res = self._append_taa_acceptance(res)?;
 
         debug!("build_revoc_reg_request  <<< res: {:?}", res);
 
@@ -1243,6 +1501,44 @@ impl LedgerCommandExecutor {
         Ok(res)
     }
 
+    fn set_txn_author_agreement_acceptance(&self,
+                                           text: Option<&str>,
+                                           version: Option<&str>,
+                                           taa_digest: Option<&str>,
+                                           acc_mech_type: Option<&str>,
+                                           time: u64) -> IndyResult<()> {
+        debug!("set_txn_author_agreement_acceptance >>> text: {:?}, version: {:?}, taa_digest: {:?}, acc_mech_type: {:?}, time: {:?}",
+               text, version, taa_digest, acc_mech_type, time);
+
+        let taa_acceptance = match acc_mech_type {
+            Some(acc_mech_type) => Some(self.ledger_service.prepare_acceptance_data(text, version, taa_digest, acc_mech_type, time)?),
+            None => None
+        };
+
+        *self.taa_acceptance.borrow_mut() = taa_acceptance;
+
+        debug!("set_txn_author_agreement_acceptance <<<");
+
+        Ok(())
+    }
+
+    fn _append_taa_acceptance(&self, request_json: String) -> IndyResult<String> {
+        let taa_acceptance = self.taa_acceptance.borrow();
+
+        let taa_acceptance = match *taa_acceptance {
+            Some(ref taa_acceptance) => taa_acceptance,
+            None => return Ok(request_json)
+        };
+
+        let mut request: serde_json::Value = serde_json::from_str(&request_json)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize request")?;
+
+        request["taaAcceptance"] = json!(taa_acceptance);
+
+        serde_json::to_string(&request)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize request after adding author agreement acceptance data")
+    }
+
     fn append_request_endorser(&self,
                                request_json: &str,
                                endorser_did: &DidValue) -> IndyResult<String> {
@@ -1265,6 +1561,72 @@ impl LedgerCommandExecutor {
         Ok(res)
     }
 
+    fn sign_request_for_endorser(&self,
+                                 wallet_handle: WalletHandle,
+                                 author_did: &DidValue,
+                                 request_json: &str,
+                                 endorser_did: &DidValue) -> IndyResult<String> {
+        debug!("sign_request_for_endorser >>> wallet_handle: {:?}, author_did: {:?}, request_json: {:?}, endorser_did: {:?}",
+               wallet_handle, author_did, request_json, endorser_did);
+
+        self.crypto_service.validate_did(author_did)?;
+
+        let request: Request<serde_json::Value> = serde_json::from_str(request_json)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Request is invalid json: {:?}", err)))?;
+
+        if request.identifier != Some(author_did.to_short()) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Request can be signed for Endorser only by its author"));
+        }
+
+        let request_json = self.append_request_endorser(request_json, endorser_did)?;
+
+        let res = self._sign_request(wallet_handle, author_did, &request_json, SignatureType::Multi)?;
+
+        debug!("sign_request_for_endorser <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn endorse_and_submit_request(&self,
+                                  pool_handle: PoolHandle,
+                                  wallet_handle: WalletHandle,
+                                  endorser_did: &DidValue,
+                                  request_json: &str,
+                                  cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("endorse_and_submit_request >>> pool_handle: {:?}, wallet_handle: {:?}, endorser_did: {:?}, request_json: {:?}",
+               pool_handle, wallet_handle, endorser_did, request_json);
+
+        try_cb!(self._check_request_for_endorser(endorser_did, request_json), cb);
+        let pool_handle = try_cb!(self._route_request(pool_handle, endorser_did), cb);
+
+        match self._sign_request(wallet_handle, endorser_did, request_json, SignatureType::Multi) {
+            Ok(endorsed_request) => self.submit_request(pool_handle, endorsed_request.as_str(), cb),
+            Err(err) => cb(Err(err))
+        }
+    }
+
+    fn _check_request_for_endorser(&self, endorser_did: &DidValue, request_json: &str) -> IndyResult<()> {
+        self.crypto_service.validate_did(endorser_did)?;
+
+        let request: Request<serde_json::Value> = serde_json::from_str(request_json)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidStructure, format!("Request is invalid json: {:?}", err)))?;
+
+        if request.endorser.as_ref() != Some(&endorser_did.to_short()) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Request doesn't name given DID as its Endorser"));
+        }
+
+        let is_signed_by_author = match (request.identifier.as_ref(), request.signatures.as_ref()) {
+            (Some(identifier), Some(signatures)) => signatures.contains_key(&identifier.0),
+            _ => false
+        };
+
+        if !is_signed_by_author {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Request isn't multi signed by its author"));
+        }
+
+        Ok(())
+    }
+
     fn validate_opt_did(&self, did: Option<&DidValue>) -> IndyResult<()> {
         match did {
             Some(did) => Ok(self.crypto_service.validate_did(did)?),
@@ -1298,6 +1660,55 @@ impl LedgerCommandExecutor {
         cb(self.ledger_service.parse_get_schema_response(&pool_response, id.get_method().as_ref().map(String::as_str)))
     }
 
+    fn get_active_txn_author_agreement(&self, pool_handle: PoolHandle, submitter_did: Option<&DidValue>, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        let request_json = try_cb!(self.build_get_txn_author_agreement_request(submitter_did, None), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_taa_callbacks.borrow_mut().insert(cb_id, cb);
+        let submitter_did = submitter_did.cloned();
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetActiveTxnAuthorAgreementContinue(
+                        pool_handle,
+                        submitter_did.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_active_txn_author_agreement_continue(&self, pool_handle: PoolHandle, submitter_did: Option<&DidValue>, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_taa_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let taa = try_cb!(pool_response.and_then(|response| self.ledger_service.parse_get_txn_author_agreement_response(&response)), cb);
+        let request_json = try_cb!(self.build_get_acceptance_mechanisms_request(submitter_did, None, None), cb);
+
+        self.pending_taa_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetActiveAcceptanceMechanismsContinue(
+                        taa.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_active_acceptance_mechanisms_continue(&self, taa: Option<TxnAuthorAgreement>, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_taa_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let aml = try_cb!(pool_response.and_then(|response| self.ledger_service.parse_get_acceptance_mechanisms_response(&response)), cb);
+
+        cb(serde_json::to_string(&ActiveTxnAuthorAgreement { taa, aml })
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize active transaction author agreement"))
+    }
+
     fn get_cred_def(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &CredentialDefinitionId, cb: BoxedCallbackStringStringSend) {
         let request_json = try_cb!(self.build_get_cred_def_request(submitter_did, id), cb);
 
@@ -1324,6 +1735,80 @@ impl LedgerCommandExecutor {
         cb(self.ledger_service.parse_get_cred_def_response(&pool_response, id.get_method().as_ref().map(String::as_str)))
     }
 
+    fn get_revoc_reg_def(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &RevocationRegistryId, cb: BoxedCallbackStringStringSend) {
+        let request_json = try_cb!(self.build_get_revoc_reg_def_request(submitter_did, id), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegDefContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_def_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.parse_revoc_reg_def_response(&pool_response))
+    }
+
+    fn get_revoc_reg(&self, pool_handle: i32, submitter_did: Option<&DidValue>, revoc_reg_def_id: &RevocationRegistryId, timestamp: i64,
+                     cb: Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>) {
+        let request_json = try_cb!(self.build_get_revoc_reg_request(submitter_did, revoc_reg_def_id, timestamp), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_rev_reg_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_rev_reg_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.parse_revoc_reg_response(&pool_response))
+    }
+
+    fn get_revoc_reg_delta(&self, pool_handle: i32, submitter_did: Option<&DidValue>, revoc_reg_def_id: &RevocationRegistryId, from: Option<i64>, to: i64,
+                           cb: Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>) {
+        let request_json = try_cb!(self.build_get_revoc_reg_delta_request(submitter_did, revoc_reg_def_id, from, to), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_rev_reg_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegDeltaContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_delta_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_rev_reg_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.parse_revoc_reg_delta_response(&pool_response))
+    }
+
     fn build_ledgers_freeze_request(&self, submitter_did: &DidValue, ledgers_ids: Vec<u64>) -> IndyResult<String>{
         debug!("build_ledgers_freeze_request >>> submitter_did: {:?}, ledgers_ids: {:?}", submitter_did, ledgers_ids);
 
@@ -1347,6 +1832,103 @@ impl LedgerCommandExecutor {
 
         Ok(res)
     }
+
+    fn parse_get_frozen_ledgers_response(&self, response: &str) -> IndyResult<String> {
+        debug!("parse_get_frozen_ledgers_response >>> response: {:?}", response);
+
+        let frozen_ledgers = self.ledger_service.parse_get_frozen_ledgers_response(response)?;
+
+        let res = json!(frozen_ledgers).to_string();
+
+        debug!("parse_get_frozen_ledgers_response <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn build_rich_schema_request(&self, submitter_did: &DidValue, rich_schema: RichSchema) -> IndyResult<String> {
+        debug!("build_rich_schema_request >>> submitter_did: {:?}, rich_schema: {:?}", submitter_did, rich_schema);
+
+        self.crypto_service.validate_did(submitter_did)?;
+
+        let res = self.ledger_service.build_rich_schema_request(submitter_did, rich_schema)?;
+        let res = self._append_taa_acceptance(res)?;
+
+        debug!("build_rich_schema_request <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn build_get_rich_schema_object_by_id_request(&self, submitter_did: Option<&DidValue>, id: &str) -> IndyResult<String> {
+        debug!("build_get_rich_schema_object_by_id_request >>> submitter_did: {:?}, id: {:?}", submitter_did, id);
+
+        self.validate_opt_did(submitter_did)?;
+
+        let res = self.ledger_service.build_get_rich_schema_object_by_id_request(submitter_did, id)?;
+
+        debug!("build_get_rich_schema_object_by_id_request <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn build_get_rich_schema_object_by_metadata_request(&self, submitter_did: Option<&DidValue>, rs_type: RichSchemaType,
+                                                        rs_name: &str, rs_version: &str) -> IndyResult<String> {
+        debug!("build_get_rich_schema_object_by_metadata_request >>> submitter_did: {:?}, rs_type: {:?}, rs_name: {:?}, rs_version: {:?}",
+               submitter_did, rs_type, rs_name, rs_version);
+
+        self.validate_opt_did(submitter_did)?;
+
+        let res = self.ledger_service.build_get_rich_schema_object_by_metadata_request(submitter_did, rs_type, rs_name, rs_version)?;
+
+        debug!("build_get_rich_schema_object_by_metadata_request <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn parse_get_rich_schema_object_response(&self, response: &str) -> IndyResult<(String, String)> {
+        debug!("parse_get_rich_schema_object_response >>> response: {:?}", response);
+
+        let res = self.ledger_service.parse_get_rich_schema_object_response(response)?;
+
+        debug!("parse_get_rich_schema_object_response <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_rich_schema_object(&self, pool_handle: PoolHandle, submitter_did: Option<&DidValue>, id: &str, cb: BoxedCallbackStringStringSend) {
+        let request_json = try_cb!(self.build_get_rich_schema_object_by_id_request(submitter_did, id), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRichSchemaObjectContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_rich_schema_object_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.ledger_service.parse_get_rich_schema_object_response(&pool_response))
+    }
+
+    fn parse_action_response(&self, response: &str, txn_type: &str) -> IndyResult<String> {
+        debug!("parse_action_response >>> response: {:?}, txn_type: {:?}", response, txn_type);
+
+        let results = self.ledger_service.parse_action_response(response, txn_type)?;
+
+        let res = json!(results).to_string();
+
+        debug!("parse_action_response <<< res: {:?}", res);
+
+        Ok(res)
+    }
 }
 
 enum SignatureType {
@@ -5,11 +5,15 @@ use indy_wallet::WalletService;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use super::threadpool::ThreadPool;
 
 const THREADPOOL_ACTIVE_COUNT: &str = "active";
 const THREADPOOL_QUEUED_COUNT: &str = "queued";
 const THREADPOOL_MAX_COUNT: &str = "max";
 const THREADPOOL_PANIC_COUNT: &str = "panic";
+const COMMAND_QUEUE_QUEUED_COUNT: &str = "queued";
+const COMMAND_QUEUE_MAX_COUNT: &str = "max";
 const OPENED_WALLETS_COUNT: &str = "opened";
 const OPENED_WALLET_IDS_COUNT: &str = "opened_ids";
 const PENDING_FOR_IMPORT_WALLETS_COUNT: &str = "pending_for_import";
@@ -21,13 +25,13 @@ pub enum MetricsCommand {
 
 pub struct MetricsCommandExecutor {
     wallet_service: Rc<WalletService>,
-    metrics_service: Rc<MetricsService>,
+    metrics_service: Arc<MetricsService>,
 }
 
 impl MetricsCommandExecutor {
     pub fn new(
         wallet_service: Rc<WalletService>,
-        metrics_service: Rc<MetricsService>,
+        metrics_service: Arc<MetricsService>,
     ) -> MetricsCommandExecutor {
         MetricsCommandExecutor {
             wallet_service,
@@ -48,6 +52,7 @@ impl MetricsCommandExecutor {
         trace!("_collect >>>");
         let mut metrics_map = serde_json::Map::new();
         self.append_threapool_metrics(&mut metrics_map)?;
+        self.append_command_queue_metrics(&mut metrics_map)?;
         self.append_wallet_metrics(&mut metrics_map)?;
         self.metrics_service
             .append_command_metrics(&mut metrics_map)?;
@@ -60,12 +65,12 @@ impl MetricsCommandExecutor {
     }
 
     fn append_threapool_metrics(&self, metrics_map: &mut Map<String, Value>) -> IndyResult<()> {
-        #[derive(Serialize, Deserialize)]
-        struct MetricsTags {
-            label: String,
-        }
+        self.append_threapool_metrics_for(metrics_map, "threadpool_threads_count", &crate::commands::THREADPOOL)?;
+        self.append_threapool_metrics_for(metrics_map, "cred_def_threadpool_threads_count", &crate::commands::CRED_DEF_THREADPOOL)
+    }
 
-        let tp_instance = crate::commands::THREADPOOL.lock().unwrap();
+    fn append_threapool_metrics_for(&self, metrics_map: &mut Map<String, Value>, name: &str, threadpool: &Mutex<ThreadPool>) -> IndyResult<()> {
+        let tp_instance = threadpool.lock().unwrap();
         let mut threadpool_threads_count: Vec<Value> = Vec::new();
 
         threadpool_threads_count.push( self.get_metric_json(
@@ -89,7 +94,7 @@ impl MetricsCommandExecutor {
         )?);
 
         metrics_map.insert(
-            String::from("threadpool_threads_count"),
+            String::from(name),
             serde_json::to_value(threadpool_threads_count)
                 .to_indy(IndyErrorKind::IOError, "Unable to convert json")?,
         );
@@ -97,6 +102,21 @@ impl MetricsCommandExecutor {
         Ok(())
     }
 
+    fn append_command_queue_metrics(&self, metrics_map: &mut Map<String, Value>) -> IndyResult<()> {
+        let command_queue_count = vec![
+            self.get_metric_json(COMMAND_QUEUE_QUEUED_COUNT, crate::commands::get_queued_commands_count())?,
+            self.get_metric_json(COMMAND_QUEUE_MAX_COUNT, crate::commands::get_command_queue_size())?,
+        ];
+
+        metrics_map.insert(
+            String::from("command_queue_count"),
+            serde_json::to_value(command_queue_count)
+                .to_indy(IndyErrorKind::IOError, "Unable to convert json")?,
+        );
+
+        Ok(())
+    }
+
     fn append_wallet_metrics(&self, metrics_map: &mut Map<String, Value>) -> IndyResult<()> {
         #[derive(Serialize, Deserialize)]
         struct MetricsTags {
@@ -1,10 +1,11 @@
 extern crate ursa;
 extern crate threadpool;
 
-use std::env;
+use std::{cmp, env};
 use std::rc::Rc;
-use std::sync::{Mutex, MutexGuard};
-use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
 
 use crate::commands::anoncreds::{AnoncredsCommand, AnoncredsCommandExecutor};
@@ -17,11 +18,11 @@ use crate::commands::pairwise::{PairwiseCommand, PairwiseCommandExecutor};
 use crate::commands::payments::{PaymentsCommand, PaymentsCommandExecutor};
 use crate::commands::pool::{PoolCommand, PoolCommandExecutor};
 use crate::commands::wallet::{WalletCommand, WalletCommandExecutor};
-use crate::commands::cache::{CacheCommand, CacheCommandExecutor};
+use crate::commands::cache::{CacheCommand, CacheCommandExecutor, set_ledger_cache_ttl};
 use crate::commands::metrics::{MetricsCommand, MetricsCommandExecutor};
-use crate::domain::IndyConfig;
+use crate::domain::{IndyConfig, IndyFeatures, Feature};
 use indy_api_types::errors::prelude::*;
-use crate::services::anoncreds::AnoncredsService;
+use crate::services::anoncreds::{AnoncredsService, set_telemetry_enabled, is_telemetry_enabled};
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
@@ -30,12 +31,14 @@ use crate::services::pool::{PoolService, set_freshness_threshold};
 use crate::services::metrics::MetricsService;
 use crate::services::metrics::command_metrics::CommandMetric;
 use indy_wallet::WalletService;
+use crate::utils::logger;
 
 use self::threadpool::ThreadPool;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub mod anoncreds;
 pub mod blob_storage;
+pub mod cancellation;
 pub mod crypto;
 pub mod ledger;
 pub mod pool;
@@ -67,27 +70,65 @@ pub enum Command {
 
 pub struct InstrumentedCommand {
     pub enqueue_ts: u128,
+    pub correlation_id: String,
     pub command: Command
 }
 
 impl InstrumentedCommand {
+    // Command inherits correlation id of the sender thread (set by application or by parent command)
+    // so continuations are logged with the same id as the original call.
     pub fn new(command: Command) -> InstrumentedCommand {
         InstrumentedCommand {
             enqueue_ts: get_cur_time(),
+            correlation_id: logger::get_correlation_id().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
             command
         }
     }
 }
 
 
+// Max number of commands waiting for the executor thread. 0 means the queue is unbounded.
+static COMMAND_QUEUE_SIZE: AtomicUsize = AtomicUsize::new(0);
+static QUEUED_COMMANDS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_command_queue_size(size: usize) {
+    COMMAND_QUEUE_SIZE.store(size, Ordering::Relaxed);
+}
+
+pub fn get_command_queue_size() -> usize {
+    COMMAND_QUEUE_SIZE.load(Ordering::Relaxed)
+}
+
+pub fn get_queued_commands_count() -> usize {
+    QUEUED_COMMANDS.load(Ordering::SeqCst)
+}
+
 lazy_static! {
     static ref THREADPOOL: Mutex<ThreadPool> = Mutex::new(ThreadPool::new(4));
+    // Credential definition keys generation takes tens of seconds,
+    // so it has a dedicated pool to not hold up the other crypto operations
+    static ref CRED_DEF_THREADPOOL: Mutex<ThreadPool> = Mutex::new(ThreadPool::new(2));
+}
+
+// Executes job on the thread pool with correlation id of the current command.
+pub fn execute_on(pool: &Mutex<ThreadPool>, job: impl FnOnce() + Send + 'static) {
+    let correlation_id = logger::get_correlation_id();
+    pool.lock().unwrap().execute(move || logger::with_correlation_id(correlation_id, job));
 }
 
 pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(crypto_thread_pool_size) = config.crypto_thread_pool_size {
         THREADPOOL.lock().unwrap().set_num_threads(crypto_thread_pool_size);
     }
+    if let Some(cred_def_thread_pool_size) = config.cred_def_thread_pool_size {
+        CRED_DEF_THREADPOOL.lock().unwrap().set_num_threads(cred_def_thread_pool_size);
+    }
+    if let Some(command_queue_size) = config.command_queue_size {
+        set_command_queue_size(command_queue_size);
+    }
+    if let Some(command_workers) = config.command_workers {
+        set_command_workers(command_workers);
+    }
     match config.collect_backtrace {
         Some(true) => env::set_var("RUST_BACKTRACE", "1"),
         Some(false) => env::set_var("RUST_BACKTRACE", "0"),
@@ -96,6 +137,24 @@ pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(threshold) = config.freshness_threshold {
         set_freshness_threshold(threshold);
     }
+    if let Some(anoncreds_telemetry) = config.anoncreds_telemetry {
+        set_telemetry_enabled(anoncreds_telemetry);
+    }
+    if let Some(ledger_cache_ttl) = config.ledger_cache_ttl {
+        set_ledger_cache_ttl(ledger_cache_ttl);
+    }
+}
+
+pub fn indy_get_features() -> IndyFeatures {
+    IndyFeatures {
+        revocation: Feature::new(true, true),
+        payments: Feature::new(true, true),
+        // Postgres storage is a separate plugin registered through `indy_register_wallet_storage`
+        postgres_storage: Feature::new(false, false),
+        didcomm_v2: Feature::new(false, false),
+        experimental_protocols: Feature::new(false, false),
+        anoncreds_telemetry: Feature::new(true, is_telemetry_enabled()),
+    }
 }
 
 fn get_cur_time() -> u128 {
@@ -103,9 +162,48 @@ fn get_cur_time() -> u128 {
     since_epoch.as_millis()
 }
 
+// Command families are pinned to worker threads. Families sharing services with non-thread-safe state
+// (everything built around the wallet) always run on the same worker. Pool and blob storage services are
+// thread safe, so their families can get own workers and don't wait behind long wallet and anoncreds commands.
+const WALLET_AFFINITY: usize = 0;
+const POOL_AFFINITY: usize = 1;
+const BLOB_STORAGE_AFFINITY: usize = 2;
+const AFFINITY_GROUPS: usize = 3;
+
+impl Command {
+    fn affinity(&self) -> usize {
+        match self {
+            Command::Pool(_) => POOL_AFFINITY,
+            Command::BlobStorage(_) => BLOB_STORAGE_AFFINITY,
+            _ => WALLET_AFFINITY,
+        }
+    }
+}
+
+// Number of command worker threads, applied when the executor is created on the first command.
+// Groups of command families are distributed between workers, so extra workers above AFFINITY_GROUPS stay unused.
+static COMMAND_WORKERS: AtomicUsize = AtomicUsize::new(1);
+static EXECUTOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_command_workers(count: usize) {
+    if EXECUTOR_STARTED.load(Ordering::SeqCst) {
+        warn!("Number of command workers is changed after the first command, it takes effect after restart of the application");
+    }
+    COMMAND_WORKERS.store(cmp::max(count, 1), Ordering::SeqCst);
+}
+
+pub fn get_command_workers() -> usize {
+    COMMAND_WORKERS.load(Ordering::SeqCst)
+}
+
+// Worker of command family group
+fn _affinity_worker(group: usize, workers_count: usize) -> usize {
+    group % workers_count
+}
+
 pub struct CommandExecutor {
-    worker: Option<thread::JoinHandle<()>>,
-    sender: Sender<InstrumentedCommand>
+    workers: Vec<thread::JoinHandle<()>>,
+    senders: Vec<Sender<InstrumentedCommand>>,
 }
 
 // Global (lazy inited) instance of CommandExecutor
@@ -119,124 +217,261 @@ impl CommandExecutor {
     }
 
     fn new() -> CommandExecutor {
-        let (sender, receiver) = channel();
+        EXECUTOR_STARTED.store(true, Ordering::SeqCst);
+        CommandExecutor::with_workers(get_command_workers())
+    }
+
+    fn with_workers(workers_count: usize) -> CommandExecutor {
+        let workers_count = cmp::min(cmp::max(workers_count, 1), AFFINITY_GROUPS);
+
+        let pool_service = Arc::new(PoolService::new());
+        let blob_storage_service = Arc::new(BlobStorageService::new());
+        let metrics_service = Arc::new(MetricsService::new());
+
+        let mut workers = Vec::with_capacity(workers_count);
+        let mut senders = Vec::with_capacity(workers_count);
+
+        for worker in 0..workers_count {
+            let (sender, receiver) = channel();
+
+            let groups: Vec<usize> = (0..AFFINITY_GROUPS)
+                .filter(|group| _affinity_worker(*group, workers_count) == worker)
+                .collect();
+            let pool_service = pool_service.clone();
+            let blob_storage_service = blob_storage_service.clone();
+            let metrics_service = metrics_service.clone();
+
+            workers.push(thread::spawn(move || {
+                info!(target: "command_executor", "Worker thread {} started for command family groups {:?}", worker, groups);
+                _run_worker(receiver, &groups, pool_service, blob_storage_service, metrics_service);
+            }));
+            senders.push(sender);
+        }
 
         CommandExecutor {
-            sender,
-            worker: Some(thread::spawn(move || {
-                info!(target: "command_executor", "Worker thread started");
-
-                let anoncreds_service = Rc::new(AnoncredsService::new());
-                let blob_storage_service = Rc::new(BlobStorageService::new());
-                let crypto_service = Rc::new(CryptoService::new());
-                let ledger_service = Rc::new(LedgerService::new());
-                let payments_service = Rc::new(PaymentsService::new());
-                let pool_service = Rc::new(PoolService::new());
-                let wallet_service = Rc::new(WalletService::new());
-                let metrics_service = Rc::new(MetricsService::new());
-
-                let anoncreds_command_executor = AnoncredsCommandExecutor::new(anoncreds_service.clone(), blob_storage_service.clone(), pool_service.clone(), wallet_service.clone(), crypto_service.clone());
-                let crypto_command_executor = CryptoCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
-                let ledger_command_executor = LedgerCommandExecutor::new(pool_service.clone(), crypto_service.clone(), wallet_service.clone(), ledger_service.clone());
-                let pool_command_executor = PoolCommandExecutor::new(pool_service.clone());
-                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
-                let wallet_command_executor = WalletCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
-                let pairwise_command_executor = PairwiseCommandExecutor::new(wallet_service.clone());
-                let blob_storage_command_executor = BlobStorageCommandExecutor::new(blob_storage_service.clone());
-                let non_secret_command_executor = NonSecretsCommandExecutor::new(wallet_service.clone());
-                let payments_command_executor = PaymentsCommandExecutor::new(payments_service.clone(), wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
-                let cache_command_executor = CacheCommandExecutor::new(wallet_service.clone());
-                let metrics_command_executor = MetricsCommandExecutor::new(wallet_service.clone(), metrics_service.clone());
-
-                loop {
-                    let instrumented_cmd = match receiver.recv() {
-                        Ok(cmd) => {
-                            cmd
-                        }
-                        Err(err) => {
-                            error!("Failed to get command!");
-                            panic!("Failed to get command! {:?}", err)
-                        }
-                    };
-                    let cmd_index: CommandMetric = (&instrumented_cmd.command).into();
-                    let start_execution_ts = get_cur_time();
-                    metrics_service.cmd_left_queue(cmd_index,
-                                                   start_execution_ts - instrumented_cmd.enqueue_ts);
-
-                    match instrumented_cmd.command {
-                        Command::Anoncreds(cmd) => {
-                            debug!("AnoncredsCommand command received");
-                            anoncreds_command_executor.execute(cmd);
-                        }
-                        Command::BlobStorage(cmd) => {
-                            debug!("BlobStorageCommand command received");
-                            blob_storage_command_executor.execute(cmd);
-                        }
-                        Command::Crypto(cmd) => {
-                            debug!("CryptoCommand command received");
-                            crypto_command_executor.execute(cmd);
-                        }
-                        Command::Ledger(cmd) => {
-                            debug!("LedgerCommand command received");
-                            ledger_command_executor.execute(cmd);
-                        }
-                        Command::Pool(cmd) => {
-                            debug!("PoolCommand command received");
-                            pool_command_executor.execute(cmd);
-                        }
-                        Command::Did(cmd) => {
-                            debug!("DidCommand command received");
-                            did_command_executor.execute(cmd);
-                        }
-                        Command::Wallet(cmd) => {
-                            debug!("WalletCommand command received");
-                            wallet_command_executor.execute(cmd);
-                        }
-                        Command::Pairwise(cmd) => {
-                            debug!("PairwiseCommand command received");
-                            pairwise_command_executor.execute(cmd);
-                        }
-                        Command::NonSecrets(cmd) => {
-                            debug!("NonSecretCommand command received");
-                            non_secret_command_executor.execute(cmd);
-                        }
-                        Command::Payments(cmd) => {
-                            debug!("PaymentsCommand command received");
-                            payments_command_executor.execute(cmd);
-                        }
-                        Command::Cache(cmd) => {
-                            debug!("CacheCommand command received");
-                            cache_command_executor.execute(cmd);
-                        }
-                        Command::Metrics(cmd) => {
-                            debug!("MetricsCommand command received");
-                            metrics_command_executor.execute(cmd);
-                        }
-                        Command::Exit => {
-                            debug!("Exit command received");
-                            break
-                        }
-                    }
-                    metrics_service.cmd_executed(cmd_index,
-                                                 get_cur_time() - start_execution_ts);
-                }
-            }))
+            workers,
+            senders,
         }
     }
 
+    // Sends command received from the library caller.
+    // It's rejected with Busy error if the queue is full, so callers get backpressure instead of unbounded memory growth.
+    pub fn submit(&self, cmd: Command) -> IndyResult<()> {
+        _reserve_command_slot(get_command_queue_size())?;
+        self._send(cmd)
+    }
+
+    // Sends command without queue size check.
+    // Used for continuations of commands that are already accepted, their callbacks must be called anyway.
     pub fn send(&self, cmd: Command) -> IndyResult<()> {
-        self.sender
+        QUEUED_COMMANDS.fetch_add(1, Ordering::SeqCst);
+        self._send(cmd)
+    }
+
+    // The slot of the command in the queue is already taken
+    fn _send(&self, cmd: Command) -> IndyResult<()> {
+        let worker = _affinity_worker(cmd.affinity(), self.senders.len());
+        self._send_to(worker, cmd)
+    }
+
+    fn _send_to(&self, worker: usize, cmd: Command) -> IndyResult<()> {
+        self.senders[worker]
             .send(InstrumentedCommand::new(cmd))
-            .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't send msg to CommandExecutor: {}", err)))
+            .map_err(|err| {
+                QUEUED_COMMANDS.fetch_sub(1, Ordering::SeqCst);
+                err_msg(IndyErrorKind::InvalidState, format!("Can't send msg to CommandExecutor: {}", err))
+            })
     }
 }
 
+fn _run_worker(receiver: Receiver<InstrumentedCommand>,
+               groups: &[usize],
+               pool_service: Arc<PoolService>,
+               blob_storage_service: Arc<BlobStorageService>,
+               metrics_service: Arc<MetricsService>) {
+    let executors = WorkerExecutors::new(groups, pool_service, blob_storage_service, metrics_service.clone());
+
+    loop {
+        let instrumented_cmd = match receiver.recv() {
+            Ok(cmd) => {
+                cmd
+            }
+            Err(err) => {
+                error!("Failed to get command!");
+                panic!("Failed to get command! {:?}", err)
+            }
+        };
+        QUEUED_COMMANDS.fetch_sub(1, Ordering::SeqCst);
+        let cmd_index: CommandMetric = (&instrumented_cmd.command).into();
+        let start_execution_ts = get_cur_time();
+        metrics_service.cmd_left_queue(cmd_index,
+                                       start_execution_ts - instrumented_cmd.enqueue_ts);
+
+        logger::set_correlation_id(Some(instrumented_cmd.correlation_id));
+
+        if let Command::Exit = instrumented_cmd.command {
+            debug!("Exit command received");
+            break;
+        }
+
+        executors.execute(instrumented_cmd.command);
+
+        logger::set_correlation_id(None);
+
+        metrics_service.cmd_executed(cmd_index,
+                                     get_cur_time() - start_execution_ts);
+    }
+}
+
+// Executors of the command families pinned to the worker.
+// Services of the wallet group aren't thread safe and are created on the worker thread.
+struct WorkerExecutors {
+    wallet: Option<WalletGroupExecutors>,
+    pool: Option<PoolCommandExecutor>,
+    blob_storage: Option<BlobStorageCommandExecutor>,
+}
+
+struct WalletGroupExecutors {
+    anoncreds: AnoncredsCommandExecutor,
+    crypto: CryptoCommandExecutor,
+    ledger: LedgerCommandExecutor,
+    did: DidCommandExecutor,
+    wallet: WalletCommandExecutor,
+    pairwise: PairwiseCommandExecutor,
+    non_secrets: NonSecretsCommandExecutor,
+    payments: PaymentsCommandExecutor,
+    cache: CacheCommandExecutor,
+    metrics: MetricsCommandExecutor,
+}
+
+impl WorkerExecutors {
+    fn new(groups: &[usize],
+           pool_service: Arc<PoolService>,
+           blob_storage_service: Arc<BlobStorageService>,
+           metrics_service: Arc<MetricsService>) -> WorkerExecutors {
+        let wallet = if groups.contains(&WALLET_AFFINITY) {
+            Some(WalletGroupExecutors::new(pool_service.clone(), blob_storage_service.clone(), metrics_service))
+        } else {
+            None
+        };
+
+        WorkerExecutors {
+            wallet,
+            pool: if groups.contains(&POOL_AFFINITY) { Some(PoolCommandExecutor::new(pool_service)) } else { None },
+            blob_storage: if groups.contains(&BLOB_STORAGE_AFFINITY) { Some(BlobStorageCommandExecutor::new(blob_storage_service)) } else { None },
+        }
+    }
+
+    fn execute(&self, command: Command) {
+        match command {
+            Command::Anoncreds(cmd) => {
+                debug!("AnoncredsCommand command received");
+                self.wallet().anoncreds.execute(cmd);
+            }
+            Command::BlobStorage(cmd) => {
+                debug!("BlobStorageCommand command received");
+                self.blob_storage.as_ref().expect("BlobStorageCommand is sent to the worker without blob storage affinity").execute(cmd);
+            }
+            Command::Crypto(cmd) => {
+                debug!("CryptoCommand command received");
+                self.wallet().crypto.execute(cmd);
+            }
+            Command::Ledger(cmd) => {
+                debug!("LedgerCommand command received");
+                self.wallet().ledger.execute(cmd);
+            }
+            Command::Pool(cmd) => {
+                debug!("PoolCommand command received");
+                self.pool.as_ref().expect("PoolCommand is sent to the worker without pool affinity").execute(cmd);
+            }
+            Command::Did(cmd) => {
+                debug!("DidCommand command received");
+                self.wallet().did.execute(cmd);
+            }
+            Command::Wallet(cmd) => {
+                debug!("WalletCommand command received");
+                self.wallet().wallet.execute(cmd);
+            }
+            Command::Pairwise(cmd) => {
+                debug!("PairwiseCommand command received");
+                self.wallet().pairwise.execute(cmd);
+            }
+            Command::NonSecrets(cmd) => {
+                debug!("NonSecretCommand command received");
+                self.wallet().non_secrets.execute(cmd);
+            }
+            Command::Payments(cmd) => {
+                debug!("PaymentsCommand command received");
+                self.wallet().payments.execute(cmd);
+            }
+            Command::Cache(cmd) => {
+                debug!("CacheCommand command received");
+                self.wallet().cache.execute(cmd);
+            }
+            Command::Metrics(cmd) => {
+                debug!("MetricsCommand command received");
+                self.wallet().metrics.execute(cmd);
+            }
+            Command::Exit => {}
+        }
+    }
+
+    fn wallet(&self) -> &WalletGroupExecutors {
+        self.wallet.as_ref().expect("Command is sent to the worker without wallet affinity")
+    }
+}
+
+impl WalletGroupExecutors {
+    fn new(pool_service: Arc<PoolService>,
+           blob_storage_service: Arc<BlobStorageService>,
+           metrics_service: Arc<MetricsService>) -> WalletGroupExecutors {
+        let anoncreds_service = Rc::new(AnoncredsService::new());
+        let crypto_service = Rc::new(CryptoService::new());
+        let ledger_service = Rc::new(LedgerService::new());
+        let payments_service = Rc::new(PaymentsService::new());
+        let wallet_service = Rc::new(WalletService::new());
+
+        WalletGroupExecutors {
+            anoncreds: AnoncredsCommandExecutor::new(anoncreds_service.clone(), blob_storage_service, pool_service.clone(), wallet_service.clone(), crypto_service.clone()),
+            crypto: CryptoCommandExecutor::new(wallet_service.clone(), crypto_service.clone()),
+            ledger: LedgerCommandExecutor::new(pool_service, crypto_service.clone(), wallet_service.clone(), ledger_service.clone()),
+            did: DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone()),
+            wallet: WalletCommandExecutor::new(wallet_service.clone(), crypto_service.clone()),
+            pairwise: PairwiseCommandExecutor::new(wallet_service.clone()),
+            non_secrets: NonSecretsCommandExecutor::new(wallet_service.clone()),
+            payments: PaymentsCommandExecutor::new(payments_service.clone(), wallet_service.clone(), crypto_service.clone(), ledger_service.clone()),
+            cache: CacheCommandExecutor::new(wallet_service.clone()),
+            metrics: MetricsCommandExecutor::new(wallet_service.clone(), metrics_service),
+        }
+    }
+}
+
+// Check and increment are done atomically, so concurrent callers can't overfill the queue
+fn _reserve_command_slot(size: usize) -> IndyResult<()> {
+    QUEUED_COMMANDS
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |queued| _check_command_queue(queued, size).ok().map(|_| queued + 1))
+        .map(|_| ())
+        .map_err(|queued| _check_command_queue(queued, size).unwrap_err())
+}
+
+fn _check_command_queue(queued: usize, size: usize) -> IndyResult<()> {
+    if size != 0 && queued >= size {
+        return Err(err_msg(IndyErrorKind::Busy, format!("Command queue is full: {} commands are waiting", queued)));
+    }
+
+    Ok(())
+}
+
 impl Drop for CommandExecutor {
     fn drop(&mut self) {
         info!(target: "command_executor", "Drop started");
-        self.send(Command::Exit).unwrap();
-        // Option worker type and this kludge is workaround for rust
-        self.worker.take().unwrap().join().unwrap();
+        for worker in 0..self.senders.len() {
+            QUEUED_COMMANDS.fetch_add(1, Ordering::SeqCst);
+            self._send_to(worker, Command::Exit).unwrap();
+        }
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
         info!(target: "command_executor", "Drop finished");
     }
 }
@@ -261,6 +496,48 @@ mod tests {
         assert!(true, "No crashes on CommandExecutor::drop");
     }
 
+    #[test]
+    fn command_executor_can_be_created_with_several_workers() {
+        for workers_count in 1..=AFFINITY_GROUPS + 1 {
+            let command_executor = CommandExecutor::with_workers(workers_count);
+            assert_eq!(cmp::min(workers_count, AFFINITY_GROUPS), command_executor.workers.len());
+        }
+    }
+
+    #[test]
+    fn affinity_worker_works() {
+        assert_eq!(0, _affinity_worker(WALLET_AFFINITY, 1));
+        assert_eq!(0, _affinity_worker(POOL_AFFINITY, 1));
+        assert_eq!(0, _affinity_worker(BLOB_STORAGE_AFFINITY, 1));
+
+        assert_eq!(0, _affinity_worker(WALLET_AFFINITY, 2));
+        assert_eq!(1, _affinity_worker(POOL_AFFINITY, 2));
+        assert_eq!(0, _affinity_worker(BLOB_STORAGE_AFFINITY, 2));
+
+        assert_eq!(0, _affinity_worker(WALLET_AFFINITY, 3));
+        assert_eq!(1, _affinity_worker(POOL_AFFINITY, 3));
+        assert_eq!(2, _affinity_worker(BLOB_STORAGE_AFFINITY, 3));
+    }
+
+    #[test]
+    fn command_affinity_works() {
+        assert_eq!(POOL_AFFINITY, Command::Pool(pool::PoolCommand::List(Box::new(|_| ()))).affinity());
+        assert_eq!(WALLET_AFFINITY, Command::Metrics(metrics::MetricsCommand::CollectMetrics(Box::new(|_| ()))).affinity());
+    }
+
+    #[test]
+    fn check_command_queue_works() {
+        _check_command_queue(0, 0).unwrap();
+        _check_command_queue(1000, 0).unwrap();
+        _check_command_queue(9, 10).unwrap();
+    }
+
+    #[test]
+    fn check_command_queue_works_for_full_queue() {
+        assert_kind!(IndyErrorKind::Busy, _check_command_queue(10, 10));
+        assert_kind!(IndyErrorKind::Busy, _check_command_queue(11, 10));
+    }
+
     #[test]
     fn command_executor_can_get_instance() {
         let ref _command_executor: CommandExecutor = *CommandExecutor::instance();
@@ -2,7 +2,8 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use indy_api_types::domain::wallet::Tags;
+use indy_api_types::domain::wallet::{Record, RecordId, RecordOperation, Tags};
+use indy_api_types::validation::Validatable;
 use indy_api_types::errors::prelude::*;
 use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
 use indy_utils::next_search_handle;
@@ -56,6 +57,15 @@ pub enum NonSecretsCommand {
                            Box<dyn Fn(IndyResult<String>) + Send>),
     CloseSearch(SearchHandle, // wallet search handle
                 Box<dyn Fn(IndyResult<()>) + Send>),
+    AddRecords(WalletHandle,
+               Vec<Record>, // records
+               Box<dyn Fn(IndyResult<()>) + Send>),
+    DeleteRecords(WalletHandle,
+                  Vec<RecordId>, // record ids
+                  Box<dyn Fn(IndyResult<()>) + Send>),
+    WithRecords(WalletHandle,
+                Vec<RecordOperation>, // operations
+                Box<dyn Fn(IndyResult<()>) + Send>),
 }
 
 pub struct NonSecretsCommandExecutor {
@@ -113,6 +123,18 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "CloseSearch command received");
                 cb(self.close_search(wallet_search_handle));
             }
+            NonSecretsCommand::AddRecords(handle, records, cb) => {
+                debug!(target: "non_secrets_command_executor", "AddRecords command received");
+                cb(self.add_records(handle, records));
+            }
+            NonSecretsCommand::DeleteRecords(handle, record_ids, cb) => {
+                debug!(target: "non_secrets_command_executor", "DeleteRecords command received");
+                cb(self.delete_records(handle, record_ids));
+            }
+            NonSecretsCommand::WithRecords(handle, operations, cb) => {
+                debug!(target: "non_secrets_command_executor", "WithRecords command received");
+                cb(self.with_records(handle, &operations));
+            }
         };
     }
 
@@ -270,13 +292,7 @@ impl NonSecretsCommandExecutor {
         let search = searches.get_mut(&wallet_search_handle)
             .ok_or_else(||err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown WalletSearch handle: {:?}", wallet_search_handle)))?;
 
-        let mut records: Vec<WalletRecord> = Vec::new();
-        for _ in 0..count {
-            match search.fetch_next_record()? {
-                Some(record) => records.push(record),
-                None => break
-            }
-        }
+        let records: Vec<WalletRecord> = search.fetch_next_records(count)?;
 
         let search_result = SearchRecords {
             total_count: search.get_total_count()?,
@@ -304,6 +320,61 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    fn add_records(&self,
+                   wallet_handle: WalletHandle,
+                   records: Vec<Record>) -> IndyResult<()> {
+        trace!("add_records >>> wallet_handle: {:?}, records: {:?}", wallet_handle, records);
+
+        let operations = records
+            .into_iter()
+            .map(|record| RecordOperation::Add { type_: record.type_, id: record.id, value: record.value, tags: record.tags })
+            .collect::<Vec<RecordOperation>>();
+
+        self.with_records(wallet_handle, &operations)?;
+
+        trace!("add_records <<< res: ()");
+
+        Ok(())
+    }
+
+    fn delete_records(&self,
+                      wallet_handle: WalletHandle,
+                      record_ids: Vec<RecordId>) -> IndyResult<()> {
+        trace!("delete_records >>> wallet_handle: {:?}, record_ids: {:?}", wallet_handle, record_ids);
+
+        let operations = record_ids
+            .into_iter()
+            .map(|record_id| RecordOperation::Delete { type_: record_id.type_, id: record_id.id })
+            .collect::<Vec<RecordOperation>>();
+
+        self.with_records(wallet_handle, &operations)?;
+
+        trace!("delete_records <<< res: ()");
+
+        Ok(())
+    }
+
+    fn with_records(&self,
+                    wallet_handle: WalletHandle,
+                    operations: &[RecordOperation]) -> IndyResult<()> {
+        trace!("with_records >>> wallet_handle: {:?}, operations: {:?}", wallet_handle, operations);
+
+        if operations.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "List of record operations is empty"));
+        }
+
+        for operation in operations {
+            self._check_type(operation.type_())?;
+            operation.validate().map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+        }
+
+        self.wallet_service.batch_records(wallet_handle, operations)?;
+
+        trace!("with_records <<< res: ()");
+
+        Ok(())
+    }
+
     fn _check_type(&self, type_: &str) -> IndyResult<()> {
         if type_.starts_with(WalletService::PREFIX) {
             return Err(err_msg(IndyErrorKind::WalletAccessFailed, format!("Record of type \"{}\" is not available for fetching", type_)));
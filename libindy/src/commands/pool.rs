@@ -1,9 +1,9 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::domain::ledger::request::ProtocolVersion;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{GenesisValidationOptions, PoolConfig, PoolOpenConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::pool::PoolService;
 use indy_api_types::{PoolHandle, CommandHandle};
@@ -38,17 +38,28 @@ pub enum PoolCommand {
     SetProtocolVersion(
         usize, // protocol version
         Box<dyn Fn(IndyResult<()>) + Send>),
+    GetPoolHandleForNetwork(
+        String, // network namespace or qualified did
+        Box<dyn Fn(IndyResult<PoolHandle>) + Send>),
+    ValidateGenesisTxns(
+        String, // genesis txn file path
+        Option<GenesisValidationOptions>, // options
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    DiffGenesisTxns(
+        String, // name
+        String, // genesis txn file path
+        Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 pub struct PoolCommandExecutor {
-    pool_service: Rc<PoolService>,
+    pool_service: Arc<PoolService>,
     close_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<()>)>>>,
     refresh_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<()>)>>>,
     open_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<PoolHandle>)>>>,
 }
 
 impl PoolCommandExecutor {
-    pub fn new(pool_service: Rc<PoolService>) -> PoolCommandExecutor {
+    pub fn new(pool_service: Arc<PoolService>) -> PoolCommandExecutor {
         PoolCommandExecutor {
             pool_service,
             close_callbacks: RefCell::new(HashMap::new()),
@@ -132,6 +143,18 @@ impl PoolCommandExecutor {
                 debug!(target: "pool_command_executor", "SetProtocolVersion command received");
                 cb(self.set_protocol_version(protocol_version));
             }
+            PoolCommand::GetPoolHandleForNetwork(network, cb) => {
+                debug!(target: "pool_command_executor", "GetPoolHandleForNetwork command received");
+                cb(self.get_pool_handle_for_network(&network));
+            }
+            PoolCommand::ValidateGenesisTxns(genesis_txn, options, cb) => {
+                debug!(target: "pool_command_executor", "ValidateGenesisTxns command received");
+                cb(self.validate_genesis_txns(&genesis_txn, options));
+            }
+            PoolCommand::DiffGenesisTxns(name, genesis_txn, cb) => {
+                debug!(target: "pool_command_executor", "DiffGenesisTxns command received");
+                cb(self.diff_genesis_txns(&name, &genesis_txn));
+            }
         };
     }
 
@@ -234,4 +257,40 @@ impl PoolCommandExecutor {
 
         Ok(())
     }
+
+    fn get_pool_handle_for_network(&self, network: &str) -> IndyResult<PoolHandle> {
+        debug!("get_pool_handle_for_network >>> network: {:?}", network);
+
+        let res = self.pool_service.get_pool_handle_for_network(network)?;
+
+        debug!("get_pool_handle_for_network <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn validate_genesis_txns(&self, genesis_txn: &str, options: Option<GenesisValidationOptions>) -> IndyResult<String> {
+        debug!("validate_genesis_txns >>> genesis_txn: {:?}, options: {:?}", genesis_txn, options);
+
+        let report = self.pool_service.validate_genesis_txns(genesis_txn, &options.unwrap_or_default())?;
+
+        let res = ::serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize genesis validation report")?;
+
+        debug!("validate_genesis_txns <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn diff_genesis_txns(&self, name: &str, genesis_txn: &str) -> IndyResult<String> {
+        debug!("diff_genesis_txns >>> name: {:?}, genesis_txn: {:?}", name, genesis_txn);
+
+        let diff = self.pool_service.diff_genesis_txns(name, genesis_txn)?;
+
+        let res = ::serde_json::to_string(&diff)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize genesis diff")?;
+
+        debug!("diff_genesis_txns <<< res: {:?}", res);
+
+        Ok(res)
+    }
 }
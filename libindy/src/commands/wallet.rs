@@ -4,14 +4,15 @@ use std::rc::Rc;
 
 use indy_api_types::wallet::*;
 use crate::commands::{Command, CommandExecutor};
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig, KeySharesConfig, MnemonicConfig, ReencryptionConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
+use crate::services::crypto::{mnemonic, shamir};
 use indy_wallet::{KeyDerivationData, WalletService, Metadata};
 use indy_utils::crypto::{chacha20poly1305_ietf, randombytes};
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 use indy_api_types::{WalletHandle, CallbackHandle};
-use rust_base58::ToBase58;
+use rust_base58::{FromBase58, ToBase58};
 
 type DeriveKeyResult<T> = IndyResult<T>;
 
@@ -42,6 +43,18 @@ pub enum WalletCommand {
                        WalletFetchSearchNextRecord, // fetch search next record
                        WalletFreeSearch, // free search
                        Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterKeyWrapper(String, // type_
+                       WalletKeyWrap, // wrap key
+                       WalletKeyUnwrap, // unwrap key
+                       WalletKeyFree, // free key
+                       Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterStorageBatch(String, // type_
+                         WalletBatchRecords, // batch records
+                         Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterStorageSearchChunk(String, // type_
+                               WalletFetchSearchNextRecords, // fetch search next records
+                               WalletFreeSearchRecords, // free search records
+                               Box<dyn Fn(IndyResult<()>) + Send>),
     Create(Config, // config
            Credentials, // credentials
            Box<dyn Fn(IndyResult<()>) + Send>),
@@ -84,10 +97,33 @@ pub enum WalletCommand {
                    WalletHandle,
                    CallbackHandle
     ),
+    ImportRecords(WalletHandle,
+                  ExportConfig, // import config
+                  Box<dyn Fn(IndyResult<String>) + Send>),
+    ImportRecordsContinue(i32, // merge id
+                          DeriveKeyResult<MasterKey>,
+                          CallbackHandle),
+    StartReencryption(WalletHandle,
+                      ReencryptionConfig, // reencryption config
+                      Box<dyn Fn(IndyResult<()>) + Send>),
+    StartReencryptionContinue(WalletHandle,
+                              KeyDerivationData, // new key data
+                              DeriveKeyResult<(MasterKey, MasterKey)>, // derive_key_result
+                              CallbackHandle),
+    ReencryptRecords(WalletHandle,
+                     usize, // batch size
+                     Box<dyn Fn(IndyResult<String>) + Send>),
+    GetReencryptionStatus(WalletHandle,
+                          Box<dyn Fn(IndyResult<String>) + Send>),
     GenerateKey(Option<KeyConfig>, // config
                 Box<dyn Fn(IndyResult<String>) + Send>),
     DeriveKey(KeyDerivationData,
               Box<dyn Fn(DeriveKeyResult<MasterKey>) + Send>),
+    SplitKey(String, // key
+             KeySharesConfig, // config
+             Box<dyn Fn(IndyResult<String>) + Send>),
+    GenerateMnemonic(Option<MnemonicConfig>, // config
+                     Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 macro_rules! get_cb {
@@ -101,7 +137,8 @@ pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
-    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
+    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>,
+    pending_import_records_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<String>) + Send>>>
 }
 
 impl WalletCommandExecutor {
@@ -110,7 +147,8 @@ impl WalletCommandExecutor {
             wallet_service,
             crypto_service,
             open_callbacks: RefCell::new(HashMap::new()),
-            pending_callbacks: RefCell::new(HashMap::new())
+            pending_callbacks: RefCell::new(HashMap::new()),
+            pending_import_records_callbacks: RefCell::new(HashMap::new())
         }
     }
 
@@ -130,6 +168,18 @@ impl WalletCommandExecutor {
                                        free_storage_metadata, search_records, search_all_records, get_search_total_count,
                                        fetch_search_next_record, free_search));
             }
+            WalletCommand::RegisterKeyWrapper(type_, wrap_key, unwrap_key, free_key, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterKeyWrapper command received");
+                cb(self._register_key_wrapper(&type_, wrap_key, unwrap_key, free_key));
+            }
+            WalletCommand::RegisterStorageBatch(type_, batch_records, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterStorageBatch command received");
+                cb(self._register_storage_batch(&type_, batch_records));
+            }
+            WalletCommand::RegisterStorageSearchChunk(type_, fetch_search_next_records, free_search_records, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterStorageSearchChunk command received");
+                cb(self._register_storage_search_chunk(&type_, fetch_search_next_records, free_search_records));
+            }
             WalletCommand::Create(config, credentials, cb) => {
                 debug!(target: "wallet_command_executor", "Create command received");
                 self._create(&config, &credentials, cb)
@@ -174,6 +224,30 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "ImportContinue command received");
                 self._import_continue(cb_id, wallet_handle, &config, &credential, key_result);
             }
+            WalletCommand::ImportRecords(wallet_handle, import_config, cb) => {
+                debug!(target: "wallet_command_executor", "ImportRecords command received");
+                self._import_records(wallet_handle, &import_config, cb);
+            }
+            WalletCommand::ImportRecordsContinue(merge_id, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "ImportRecordsContinue command received");
+                self._import_records_continue(cb_id, merge_id, key_result);
+            }
+            WalletCommand::StartReencryption(wallet_handle, config, cb) => {
+                debug!(target: "wallet_command_executor", "StartReencryption command received");
+                self._start_reencryption(wallet_handle, &config, cb);
+            }
+            WalletCommand::StartReencryptionContinue(wallet_handle, new_key_data, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "StartReencryptionContinue command received");
+                self._start_reencryption_continue(cb_id, wallet_handle, new_key_data, key_result);
+            }
+            WalletCommand::ReencryptRecords(wallet_handle, batch_size, cb) => {
+                debug!(target: "wallet_command_executor", "ReencryptRecords command received");
+                cb(self._reencrypt_records(wallet_handle, batch_size));
+            }
+            WalletCommand::GetReencryptionStatus(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "GetReencryptionStatus command received");
+                cb(self._get_reencryption_status(wallet_handle));
+            }
             WalletCommand::GenerateKey(config, cb) => {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 cb(self._generate_key(config.as_ref()));
@@ -182,6 +256,14 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 self._derive_key(key_data, cb);
             }
+            WalletCommand::SplitKey(key, config, cb) => {
+                debug!(target: "wallet_command_executor", "SplitKey command received");
+                cb(self._split_key(&key, &config));
+            }
+            WalletCommand::GenerateMnemonic(config, cb) => {
+                debug!(target: "wallet_command_executor", "GenerateMnemonic command received");
+                cb(self._generate_mnemonic(config.as_ref()));
+            }
         };
     }
 
@@ -226,6 +308,42 @@ impl WalletCommandExecutor {
         Ok(())
     }
 
+    fn _register_key_wrapper(&self,
+                             type_: &str,
+                             wrap_key: WalletKeyWrap,
+                             unwrap_key: WalletKeyUnwrap,
+                             free_key: WalletKeyFree) -> IndyResult<()> {
+        trace!("_register_key_wrapper >>> type_: {:?}", type_);
+
+        self.wallet_service.register_key_wrapper(type_, wrap_key, unwrap_key, free_key)?;
+
+        trace!("_register_key_wrapper <<< res: ()");
+        Ok(())
+    }
+
+    fn _register_storage_batch(&self,
+                               type_: &str,
+                               batch_records: WalletBatchRecords) -> IndyResult<()> {
+        trace!("_register_storage_batch >>> type_: {:?}", type_);
+
+        self.wallet_service.register_wallet_storage_batch(type_, batch_records)?;
+
+        trace!("_register_storage_batch <<< res: ()");
+        Ok(())
+    }
+
+    fn _register_storage_search_chunk(&self,
+                                      type_: &str,
+                                      fetch_search_next_records: WalletFetchSearchNextRecords,
+                                      free_search_records: WalletFreeSearchRecords) -> IndyResult<()> {
+        trace!("_register_storage_search_chunk >>> type_: {:?}", type_);
+
+        self.wallet_service.register_wallet_storage_search_chunk(type_, fetch_search_next_records, free_search_records)?;
+
+        trace!("_register_storage_search_chunk <<< res: ()");
+        Ok(())
+    }
+
     fn _create(&self,
                config: &Config,
                credentials: &Credentials,
@@ -276,6 +394,8 @@ impl WalletCommandExecutor {
              cb: Box<dyn Fn(IndyResult<WalletHandle>) + Send>) {
         trace!("_open >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
 
+        let credentials = &try_cb!(WalletCommandExecutor::_resolve_credentials_key(credentials), cb);
+
         let (wallet_handle, key_derivation_data, rekey_data) = try_cb!(self.wallet_service.open_wallet_prepare(config, credentials), cb);
 
         self.open_callbacks.borrow_mut().insert(wallet_handle, cb);
@@ -393,7 +513,11 @@ impl WalletCommandExecutor {
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_export >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
 
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method);
+        // Sealed export key is random and is wrapped with the transit key of KMS, so there is nothing to derive
+        let key_data = match export_config.sealed {
+            Some(_) => KeyDerivationData::Raw(try_cb!(self._generate_key(None), cb)),
+            None => KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method)
+        };
 
         let cb_id = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
@@ -425,8 +549,11 @@ impl WalletCommandExecutor {
                         key_data: KeyDerivationData,
                         key_result: DeriveKeyResult<MasterKey>) {
         let cb = get_cb!(self, cb_id);
+        // version 1 is the chunked export format
+        let version = if export_config.chunked { 1 } else { 0 };
+
         cb(key_result
-            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key)))) // TODO - later add proper versioning
+            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, version, (&key_data,& key))))
     }
 
     fn _import(&self,
@@ -437,6 +564,12 @@ impl WalletCommandExecutor {
         trace!("_import >>> config: {:?}, credentials: {:?}, import_config: {:?}",
                config, secret!(credentials), secret!(import_config));
 
+        let credentials = &try_cb!(WalletCommandExecutor::_resolve_credentials_key(credentials), cb);
+
+        let mut import_config = import_config.clone();
+        import_config.key = try_cb!(WalletCommandExecutor::_combine_key_shares(&import_config.key, import_config.key_shares.as_ref()), cb);
+        let import_config = &import_config;
+
         let (wallet_handle, key_data, import_key_data) = try_cb!(self.wallet_service.import_wallet_prepare(&config, &credentials, &import_config), cb);
 
         let cb_id : CallbackHandle = indy_utils::sequence::get_next_id();
@@ -485,15 +618,137 @@ impl WalletCommandExecutor {
             .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key)))
     }
 
+    fn _import_records(&self,
+                       wallet_handle: WalletHandle,
+                       import_config: &ExportConfig,
+                       cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("_import_records >>> wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(import_config));
+
+        let mut import_config = import_config.clone();
+        import_config.key = try_cb!(WalletCommandExecutor::_combine_key_shares(&import_config.key, import_config.key_shares.as_ref()), cb);
+
+        let (merge_id, import_key_data) = try_cb!(self.wallet_service.import_records_prepare(wallet_handle, &import_config), cb);
+
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.pending_import_records_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                import_key_data,
+                Box::new(move |import_key_result| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::ImportRecordsContinue(
+                        merge_id,
+                        import_key_result,
+                        cb_id
+                    ))).unwrap();
+                }),
+            ))
+        ).unwrap();
+
+        trace!("_import_records <<<");
+    }
+
+    fn _import_records_continue(&self,
+                                cb_id: CallbackHandle,
+                                merge_id: i32,
+                                key_result: DeriveKeyResult<MasterKey>) {
+        let cb = match self.pending_import_records_callbacks.borrow_mut().remove(&cb_id) {
+            Some(cb) => cb,
+            None => return error!("No pending command for id: {}", cb_id)
+        };
+
+        cb(key_result
+            .and_then(|key| self.wallet_service.import_records_continue(merge_id, &key))
+            .and_then(|result| serde_json::to_string(&result)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize import result")))
+    }
+
+    fn _start_reencryption(&self,
+                           wallet_handle: WalletHandle,
+                           config: &ReencryptionConfig,
+                           cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_start_reencryption >>> wallet_handle: {:?}, config: {:?}", wallet_handle, secret!(config));
+
+        let (key_data, new_key_data) = try_cb!(self.wallet_service.start_reencryption_prepare(wallet_handle, config), cb);
+
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_data,
+                Box::new(move |key_result| {
+                    let new_key_data = new_key_data.clone();
+                    CommandExecutor::instance().send(
+                        Command::Wallet(WalletCommand::DeriveKey(
+                            new_key_data.clone(),
+                            Box::new(move |new_key_result| {
+                                let key_result = key_result.clone();
+                                CommandExecutor::instance().send(Command::Wallet(WalletCommand::StartReencryptionContinue(
+                                    wallet_handle,
+                                    new_key_data.clone(),
+                                    key_result.and_then(|key| new_key_result.map(|new_key| (key, new_key))),
+                                    cb_id
+                                ))).unwrap();
+                            }),
+                        ))
+                    ).unwrap();
+                }),
+            ))
+        ).unwrap();
+
+        trace!("_start_reencryption <<<");
+    }
+
+    fn _start_reencryption_continue(&self,
+                                    cb_id: CallbackHandle,
+                                    wallet_handle: WalletHandle,
+                                    new_key_data: KeyDerivationData,
+                                    key_result: DeriveKeyResult<(MasterKey, MasterKey)>) {
+        let cb = get_cb!(self, cb_id);
+        cb(key_result
+            .and_then(|(key, new_key)| self.wallet_service.start_reencryption_continue(wallet_handle, (&key, &new_key_data, &new_key))))
+    }
+
+    fn _reencrypt_records(&self,
+                          wallet_handle: WalletHandle,
+                          batch_size: usize) -> IndyResult<String> {
+        trace!("_reencrypt_records >>> wallet_handle: {:?}, batch_size: {:?}", wallet_handle, batch_size);
+
+        let progress = self.wallet_service.reencrypt_records(wallet_handle, batch_size)?;
+
+        let res = serde_json::to_string(&progress)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize re-encryption progress")?;
+
+        trace!("_reencrypt_records <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _get_reencryption_status(&self,
+                                wallet_handle: WalletHandle) -> IndyResult<String> {
+        trace!("_get_reencryption_status >>> wallet_handle: {:?}", wallet_handle);
+
+        let status = self.wallet_service.get_reencryption_status(wallet_handle)?;
+
+        let res = serde_json::to_string(&status)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize re-encryption status")?;
+
+        trace!("_get_reencryption_status <<< res: {:?}", res);
+        Ok(res)
+    }
+
     fn _generate_key(&self,
                      config: Option<&KeyConfig>) -> IndyResult<String> {
         trace!("_generate_key >>>config: {:?}", secret!(config));
 
         let seed = config.and_then(|config| config.seed.as_ref().map(String::as_str));
+        let mnemonic_seed = config.and_then(|config| config.mnemonic.as_ref());
 
-        let key = match self.crypto_service.convert_seed(seed)? {
-            Some(seed) => randombytes::randombytes_deterministic(chacha20poly1305_ietf::KEYBYTES, &randombytes::Seed::from_slice(&seed[..])?),
-            None => randombytes::randombytes(chacha20poly1305_ietf::KEYBYTES)
+        let key = match (self.crypto_service.convert_seed(seed)?, mnemonic_seed) {
+            (Some(_), Some(_)) => return Err(err_msg(IndyErrorKind::InvalidStructure, "Only one of seed and mnemonic can be provided")),
+            (Some(seed), None) => randombytes::randombytes_deterministic(chacha20poly1305_ietf::KEYBYTES, &randombytes::Seed::from_slice(&seed[..])?),
+            (None, Some(mnemonic_seed)) => mnemonic::derive_key(&mnemonic_seed.phrase, &mnemonic_seed.passphrase, mnemonic::WALLET_KEY_BRANCH, mnemonic_seed.index)?,
+            (None, None) => randombytes::randombytes(chacha20poly1305_ietf::KEYBYTES)
         };
 
         let res = key[..].to_base58();
@@ -502,7 +757,68 @@ impl WalletCommandExecutor {
         Ok(res)
     }
 
+    fn _generate_mnemonic(&self,
+                          config: Option<&MnemonicConfig>) -> IndyResult<String> {
+        trace!("_generate_mnemonic >>> config: {:?}", config);
+
+        let word_count = config.and_then(|config| config.word_count).unwrap_or(mnemonic::DEFAULT_WORD_COUNT);
+
+        let res = mnemonic::generate(word_count)?;
+
+        trace!("_generate_mnemonic <<< res: {:?}", secret!(&res));
+        Ok(res)
+    }
+
     fn _derive_key(&self, key_data: KeyDerivationData, cb: Box<dyn Fn(DeriveKeyResult<MasterKey>) + Send>){
-        crate::commands::THREADPOOL.lock().unwrap().execute(move || cb(key_data.calc_master_key()));
+        crate::commands::execute_on(&crate::commands::THREADPOOL, move || cb(key_data.calc_master_key()));
+    }
+
+    fn _split_key(&self,
+                  key: &str,
+                  config: &KeySharesConfig) -> IndyResult<String> {
+        trace!("_split_key >>> key: {:?}, config: {:?}", secret!(key), config);
+
+        for recipient in config.recipients.iter() {
+            self.crypto_service.validate_key(recipient)?;
+        }
+
+        let shares = shamir::split_secret(key.as_bytes(), config.threshold, config.recipients.len())?;
+
+        let artifacts = config.recipients.iter()
+            .zip(shares.iter())
+            .map(|(recipient, share)|
+                self.crypto_service.crypto_box_seal(recipient, share.to_base58().as_bytes())
+                    .map(|artifact| artifact.to_base58()))
+            .collect::<IndyResult<Vec<String>>>()?;
+
+        let res = json!(artifacts).to_string();
+
+        trace!("_split_key <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _resolve_credentials_key(credentials: &Credentials) -> IndyResult<Credentials> {
+        let mut credentials = credentials.clone();
+        credentials.key = WalletCommandExecutor::_combine_key_shares(&credentials.key, credentials.key_shares.as_ref())?;
+        Ok(credentials)
+    }
+
+    fn _combine_key_shares(key: &str, key_shares: Option<&Vec<String>>) -> IndyResult<String> {
+        let key_shares = match key_shares {
+            Some(key_shares) if !key.is_empty() =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Both key and {} key shares are provided", key_shares.len()))),
+            Some(key_shares) => key_shares,
+            None => return Ok(key.to_string())
+        };
+
+        let shares = key_shares.iter()
+            .map(|share| share.from_base58()
+                .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Invalid base58 key share")))
+            .collect::<IndyResult<Vec<Vec<u8>>>>()?;
+
+        let key = shamir::combine_shares(&shares)?;
+
+        String::from_utf8(key)
+            .to_indy(IndyErrorKind::InvalidStructure, "Key reconstructed from shares isn't valid UTF-8")
     }
 }
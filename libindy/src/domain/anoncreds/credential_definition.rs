@@ -30,6 +30,27 @@ impl SignatureType {
     }
 }
 
+// Stages of credential definition creation reported to the progress callback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CredentialDefinitionStage {
+    // Waiting for a free worker of the credential definition thread pool
+    Queued,
+    // Keys are being generated on the worker thread
+    Generating,
+    // Generated keys are being stored in the wallet
+    Storing,
+}
+
+impl CredentialDefinitionStage {
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            CredentialDefinitionStage::Queued => "queued",
+            CredentialDefinitionStage::Generating => "generating",
+            CredentialDefinitionStage::Storing => "storing",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CredentialDefinitionConfig {
     #[serde(default)]
@@ -6,6 +6,7 @@ use ursa::cl::{
 };
 use super::super::crypto::did::DidValue;
 
+use super::credential::CredentialValues;
 use super::credential_definition::CredentialDefinitionId;
 
 use indy_api_types::validation::Validatable;
@@ -46,4 +47,35 @@ impl Validatable for CredentialRequest {
     }
 }
 
-impl Validatable for CredentialRequestMetadata {}
\ No newline at end of file
+impl Validatable for CredentialRequestMetadata {}
+
+// Credential request with values of the credential for the holder in a batch issuance.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialIssuanceRequest {
+    pub cred_req: CredentialRequest,
+    pub cred_values: CredentialValues,
+}
+
+impl Validatable for CredentialIssuanceRequest {
+    fn validate(&self) -> Result<(), String> {
+        self.cred_req.validate()?;
+        self.cred_values.validate()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CredentialIssuanceRequests(pub Vec<CredentialIssuanceRequest>);
+
+impl Validatable for CredentialIssuanceRequests {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("Credential Issuance Requests validation failed: list is empty"));
+        }
+
+        for request in self.0.iter() {
+            request.validate()?;
+        }
+
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ pub mod credential_for_proof_request;
 pub mod credential_offer;
 pub mod credential_request;
 pub mod proof;
+pub mod proof_artifacts;
 pub mod proof_request;
 pub mod requested_credential;
 pub mod revocation_registry_definition;
@@ -12,6 +13,9 @@ pub mod revocation_registry_delta;
 pub mod revocation_registry;
 pub mod revocation_state;
 pub mod schema;
+pub mod verification_result;
+pub mod w3c_credential;
 pub mod master_secret;
+pub mod presentation_exchange;
 
 pub const DELIMITER: &str = ":";
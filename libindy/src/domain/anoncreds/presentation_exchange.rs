@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use indy_api_types::validation::Validatable;
+
+// DIF Presentation Exchange objects (Aries RFC 0510). Only the subset which can be expressed by Indy proof request is supported.
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PresentationDefinition {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    pub input_descriptors: Vec<InputDescriptor>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct InputDescriptor {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(default)]
+    pub schema: Vec<InputDescriptorSchema>,
+    #[serde(default)]
+    pub constraints: Constraints,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct InputDescriptorSchema {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct Constraints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_disclosure: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Field {
+    pub path: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predicate: Option<String>,
+}
+
+// JSON Schema filter of the field value.
+// Bounds are proved as Indy predicates, `const` is checked by restriction of the attribute value.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(rename = "const")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub const_: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<i32>,
+    #[serde(flatten)]
+    pub unsupported: serde_json::Map<String, Value>,
+}
+
+impl Filter {
+    pub fn has_bounds(&self) -> bool {
+        self.minimum.is_some() || self.exclusive_minimum.is_some() || self.maximum.is_some() || self.exclusive_maximum.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PresentationSubmission {
+    pub id: String,
+    pub definition_id: String,
+    pub descriptor_map: Vec<SubmissionDescriptor>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct SubmissionDescriptor {
+    pub id: String,
+    pub format: String,
+    pub path: String,
+}
+
+impl Validatable for PresentationDefinition {
+    fn validate(&self) -> Result<(), String> {
+        if self.input_descriptors.is_empty() {
+            return Err(String::from("Presentation Definition validation failed: `input_descriptors` is empty"));
+        }
+
+        let mut ids = HashSet::new();
+
+        for descriptor in self.input_descriptors.iter() {
+            if !ids.insert(descriptor.id.as_str()) {
+                return Err(format!("Presentation Definition validation failed: duplicated input descriptor id {}", descriptor.id));
+            }
+
+            if descriptor.constraints.fields.is_empty() {
+                return Err(format!("Presentation Definition validation failed: input descriptor {} doesn't constrain any field", descriptor.id));
+            }
+
+            for field in descriptor.constraints.fields.iter() {
+                if field.path.is_empty() {
+                    return Err(format!("Presentation Definition validation failed: field of input descriptor {} has empty `path`", descriptor.id));
+                }
+
+                if let Some(key) = field.filter.as_ref().and_then(|filter| filter.unsupported.keys().next()) {
+                    return Err(format!("Presentation Definition validation failed: unsupported filter `{}` in input descriptor {}", key, descriptor.id));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@ use indy_api_types::validation::Validatable;
 pub struct Proof {
     pub proof: CryptoProof,
     pub requested_proof: RequestedProof,
-    pub identifiers: Vec<Identifier>
+    pub identifiers: Vec<Identifier>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -72,7 +72,65 @@ pub struct Identifier {
     pub timestamp: Option<u64>
 }
 
-impl Validatable for Proof {}
+impl Validatable for Proof {
+    fn validate(&self) -> Result<(), String> {
+        for identifier in self.identifiers.iter() {
+            identifier.schema_id.validate()?;
+            identifier.cred_def_id.validate()?;
+            if let Some(ref rev_reg_id) = identifier.rev_reg_id {
+                rev_reg_id.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Collected only if `anoncreds_telemetry` runtime config option is enabled. Durations are in microseconds.
+// It's never a part of the proof, so the verifier doesn't learn anything about the prover environment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProofTelemetry {
+    pub proof_size: usize,
+    pub sub_proofs: Vec<SubProofTelemetry>,
+    // Revocation check durations by referents of requested attributes and predicates proven with non-revocation
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub revocation_durations: HashMap<String, u64>,
+    pub crypto_duration: u64,
+    pub total_duration: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubProofTelemetry {
+    pub cred_def_id: CredentialDefinitionId,
+    pub duration: u64,
+    // Time of resolving and checking revocation state or registry of the sub proof. Non-revocation cryptography is done
+    // by Ursa together with the primary proof, so it's a part of `duration` and `crypto_duration`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revocation_duration: Option<u64>,
+}
+
+impl ProofTelemetry {
+    pub fn add_sub_proof(&mut self, cred_def_id: &CredentialDefinitionId, duration: u64, revocation_duration: Option<u64>, referents: &[&str]) {
+        if let Some(revocation_duration) = revocation_duration {
+            for referent in referents {
+                self.revocation_durations.insert(referent.to_string(), revocation_duration);
+            }
+        }
+
+        self.sub_proofs.push(SubProofTelemetry {
+            cred_def_id: cred_def_id.clone(),
+            duration,
+            revocation_duration,
+        });
+    }
+}
+
+// Result of `indy_prover_create_proof_with_details`. Only the proof is sent to the verifier.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofCreationResult {
+    pub proof: Proof,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<ProofTelemetry>,
+}
 
 #[cfg(test)]
 mod tests {
@@ -92,4 +150,50 @@ mod tests {
         let req_proof: RequestedProof = serde_json::from_str(&json).unwrap();
         assert!(req_proof.revealed_attr_groups.is_empty())
     }
+
+    #[test]
+    fn serialize_proof_telemetry() {
+        let telemetry = ProofTelemetry {
+            proof_size: 1024,
+            sub_proofs: vec![
+                SubProofTelemetry { cred_def_id: CredentialDefinitionId("cred_def_1".to_string()), duration: 10, revocation_duration: Some(5) },
+                SubProofTelemetry { cred_def_id: CredentialDefinitionId("cred_def_2".to_string()), duration: 20, revocation_duration: None },
+            ],
+            revocation_durations: vec![("attr1_referent".to_string(), 5)].into_iter().collect(),
+            crypto_duration: 100,
+            total_duration: 150,
+        };
+
+        let expected = json!({
+            "proof_size": 1024,
+            "sub_proofs": [
+                {"cred_def_id": "cred_def_1", "duration": 10, "revocation_duration": 5},
+                {"cred_def_id": "cred_def_2", "duration": 20}
+            ],
+            "revocation_durations": {"attr1_referent": 5},
+            "crypto_duration": 100,
+            "total_duration": 150
+        });
+
+        assert_eq!(expected, json!(telemetry));
+        assert_eq!(telemetry, serde_json::from_value(expected).unwrap());
+    }
+
+    #[test]
+    fn proof_telemetry_add_sub_proof_works() {
+        let mut telemetry = ProofTelemetry::default();
+
+        telemetry.add_sub_proof(&CredentialDefinitionId("cred_def_1".to_string()), 10, Some(5), &["attr1_referent", "predicate1_referent"]);
+        telemetry.add_sub_proof(&CredentialDefinitionId("cred_def_2".to_string()), 20, None, &["attr2_referent"]);
+
+        assert_eq!(2, telemetry.sub_proofs.len());
+        assert_eq!(Some(5), telemetry.sub_proofs[0].revocation_duration);
+        assert_eq!(None, telemetry.sub_proofs[1].revocation_duration);
+
+        let expected: HashMap<String, u64> = vec![
+            ("attr1_referent".to_string(), 5),
+            ("predicate1_referent".to_string(), 5),
+        ].into_iter().collect();
+        assert_eq!(expected, telemetry.revocation_durations);
+    }
 }
@@ -0,0 +1,251 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::proof::Identifier;
+use super::proof_request::{NonRevocedInterval, ProofRequest};
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::schema::SchemaId;
+use crate::utils::wql::Query;
+
+// Ledger artifacts required to create or verify a proof for the proof request.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProofArtifactsPlan {
+    pub schema_ids: Vec<SchemaId>,
+    pub cred_def_ids: Vec<CredentialDefinitionId>,
+    pub rev_reg_def_ids: Vec<RevocationRegistryId>,
+    // States of revocation registries at timestamps already bound to the proof (verifier side)
+    pub rev_regs: Vec<RevocationRegistryAt>,
+    // Deltas of revocation registries for non-revocation intervals of the proof request (prover side)
+    pub rev_reg_deltas: Vec<RevocationRegistryDeltaInterval>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RevocationRegistryAt {
+    pub rev_reg_id: RevocationRegistryId,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RevocationRegistryDeltaInterval {
+    pub rev_reg_id: RevocationRegistryId,
+    pub from: Option<u64>,
+    pub to: u64,
+}
+
+// Fetched artifacts in the format expected by `indy_prover_create_proof` and `indy_verifier_verify_proof`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProofArtifacts {
+    pub schemas: HashMap<String, Value>,
+    pub cred_defs: HashMap<String, Value>,
+    pub rev_reg_defs: HashMap<String, Value>,
+    pub rev_regs: HashMap<String, HashMap<u64, Value>>,
+    pub rev_reg_deltas: Vec<FetchedRevocationRegistryDelta>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FetchedRevocationRegistryDelta {
+    pub rev_reg_id: String,
+    pub from: Option<u64>,
+    pub to: u64,
+    pub timestamp: u64,
+    pub delta: Value,
+}
+
+impl ProofArtifactsPlan {
+    // Identifiers come from the proof (verifier) or from credentials selected for the proof (prover).
+    // Restrictions of the proof request contribute ids listed explicitly by `$eq` or `$in` operators.
+    // `now` is used as the end of non-revocation intervals without `to`.
+    pub fn build(proof_req: &ProofRequest, identifiers: &[Identifier], now: u64) -> ProofArtifactsPlan {
+        let proof_req = proof_req.value();
+
+        let mut schema_ids: HashSet<SchemaId> = HashSet::new();
+        let mut cred_def_ids: HashSet<CredentialDefinitionId> = HashSet::new();
+        let mut rev_reg_def_ids: HashSet<RevocationRegistryId> = HashSet::new();
+        let mut rev_regs: HashSet<RevocationRegistryAt> = HashSet::new();
+
+        for identifier in identifiers {
+            schema_ids.insert(identifier.schema_id.clone());
+            cred_def_ids.insert(identifier.cred_def_id.clone());
+
+            if let Some(ref rev_reg_id) = identifier.rev_reg_id {
+                rev_reg_def_ids.insert(rev_reg_id.clone());
+
+                if let Some(timestamp) = identifier.timestamp {
+                    rev_regs.insert(RevocationRegistryAt { rev_reg_id: rev_reg_id.clone(), timestamp });
+                }
+            }
+        }
+
+        let restrictions = proof_req.requested_attributes.values().filter_map(|info| info.restrictions.as_ref())
+            .chain(proof_req.requested_predicates.values().filter_map(|info| info.restrictions.as_ref()));
+
+        for restriction in restrictions {
+            let mut values = HashMap::new();
+            _collect_restriction_values(restriction, &mut values);
+
+            schema_ids.extend(values.remove("schema_id").unwrap_or_default().into_iter().map(SchemaId));
+            cred_def_ids.extend(values.remove("cred_def_id").unwrap_or_default().into_iter().map(CredentialDefinitionId));
+            rev_reg_def_ids.extend(values.remove("rev_reg_id").unwrap_or_default().into_iter()
+                .filter(|id| id != "None")
+                .map(RevocationRegistryId));
+        }
+
+        // Registries already bound to timestamps do not need deltas
+        let bound_rev_reg_ids: HashSet<&RevocationRegistryId> = rev_regs.iter().map(|rev_reg| &rev_reg.rev_reg_id).collect();
+
+        let intervals = _non_revoc_intervals(proof_req.non_revoked.as_ref(),
+                                             proof_req.requested_attributes.values().map(|info| info.non_revoked.as_ref())
+                                                 .chain(proof_req.requested_predicates.values().map(|info| info.non_revoked.as_ref())));
+
+        let mut rev_reg_deltas: Vec<RevocationRegistryDeltaInterval> = Vec::new();
+        for rev_reg_id in rev_reg_def_ids.iter().filter(|id| !bound_rev_reg_ids.contains(id)) {
+            for interval in intervals.iter() {
+                rev_reg_deltas.push(RevocationRegistryDeltaInterval {
+                    rev_reg_id: rev_reg_id.clone(),
+                    from: interval.from,
+                    to: interval.to.unwrap_or(now),
+                });
+            }
+        }
+
+        let mut schema_ids: Vec<SchemaId> = schema_ids.into_iter().collect();
+        schema_ids.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut cred_def_ids: Vec<CredentialDefinitionId> = cred_def_ids.into_iter().collect();
+        cred_def_ids.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut rev_reg_def_ids: Vec<RevocationRegistryId> = rev_reg_def_ids.into_iter().collect();
+        rev_reg_def_ids.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut rev_regs: Vec<RevocationRegistryAt> = rev_regs.into_iter().collect();
+        rev_regs.sort_by(|a, b| (&a.rev_reg_id.0, a.timestamp).cmp(&(&b.rev_reg_id.0, b.timestamp)));
+
+        rev_reg_deltas.sort_by(|a, b| (&a.rev_reg_id.0, a.from, a.to).cmp(&(&b.rev_reg_id.0, b.from, b.to)));
+        rev_reg_deltas.dedup();
+
+        ProofArtifactsPlan { schema_ids, cred_def_ids, rev_reg_def_ids, rev_regs, rev_reg_deltas }
+    }
+
+    pub fn len(&self) -> usize {
+        self.schema_ids.len() + self.cred_def_ids.len() + self.rev_reg_def_ids.len() + self.rev_regs.len() + self.rev_reg_deltas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Values of `$eq` and `$in` operators. Negated operators do not name artifacts that are needed.
+fn _collect_restriction_values(query: &Query, values: &mut HashMap<String, Vec<String>>) {
+    match query {
+        Query::Eq(ref tag_name, ref tag_value) => {
+            values.entry(tag_name.clone()).or_insert_with(Vec::new).push(tag_value.clone());
+        }
+        Query::In(ref tag_name, ref tag_values) => {
+            values.entry(tag_name.clone()).or_insert_with(Vec::new).extend(tag_values.iter().cloned());
+        }
+        Query::And(ref operators) | Query::Or(ref operators) => {
+            for operator in operators {
+                _collect_restriction_values(operator, values);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Interval of a referent overrides the interval of the proof request.
+fn _non_revoc_intervals<'a, I>(global: Option<&'a NonRevocedInterval>, referents: I) -> Vec<&'a NonRevocedInterval>
+    where I: Iterator<Item=Option<&'a NonRevocedInterval>> {
+    let mut intervals: Vec<&NonRevocedInterval> = Vec::new();
+
+    for interval in referents.filter_map(|interval| interval.or(global)) {
+        if !intervals.contains(&interval) {
+            intervals.push(interval);
+        }
+    }
+
+    intervals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0";
+    const CRED_DEF_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag";
+    const REV_REG_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag:CL_ACCUM:TAG_1";
+    const OTHER_CRED_DEF_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:3:CL:2:tag";
+
+    fn _proof_request() -> ProofRequest {
+        serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "0.1",
+            "requested_attributes": {
+                "attr1_referent": {
+                    "name": "name",
+                    "restrictions": {"$or": [{"cred_def_id": CRED_DEF_ID}, {"cred_def_id": OTHER_CRED_DEF_ID}]}
+                },
+                "attr2_referent": {
+                    "name": "sex",
+                    "non_revoked": {"from": 10, "to": 20}
+                }
+            },
+            "requested_predicates": {},
+            "non_revoked": {"to": 30}
+        })).unwrap()
+    }
+
+    fn _identifier(timestamp: Option<u64>) -> Identifier {
+        Identifier {
+            schema_id: SchemaId(SCHEMA_ID.to_string()),
+            cred_def_id: CredentialDefinitionId(CRED_DEF_ID.to_string()),
+            rev_reg_id: Some(RevocationRegistryId(REV_REG_ID.to_string())),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn build_plan_works_for_prover() {
+        let plan = ProofArtifactsPlan::build(&_proof_request(), &[_identifier(None)], 100);
+
+        assert_eq!(vec![SchemaId(SCHEMA_ID.to_string())], plan.schema_ids);
+        assert_eq!(vec![CredentialDefinitionId(CRED_DEF_ID.to_string()), CredentialDefinitionId(OTHER_CRED_DEF_ID.to_string())], plan.cred_def_ids);
+        assert_eq!(vec![RevocationRegistryId(REV_REG_ID.to_string())], plan.rev_reg_def_ids);
+        assert!(plan.rev_regs.is_empty());
+        assert_eq!(vec![
+            RevocationRegistryDeltaInterval { rev_reg_id: RevocationRegistryId(REV_REG_ID.to_string()), from: None, to: 30 },
+            RevocationRegistryDeltaInterval { rev_reg_id: RevocationRegistryId(REV_REG_ID.to_string()), from: Some(10), to: 20 },
+        ], plan.rev_reg_deltas);
+    }
+
+    #[test]
+    fn build_plan_works_for_verifier() {
+        let plan = ProofArtifactsPlan::build(&_proof_request(), &[_identifier(Some(25))], 100);
+
+        assert_eq!(vec![RevocationRegistryAt { rev_reg_id: RevocationRegistryId(REV_REG_ID.to_string()), timestamp: 25 }], plan.rev_regs);
+        assert!(plan.rev_reg_deltas.is_empty());
+    }
+
+    #[test]
+    fn build_plan_ignores_negated_restrictions() {
+        let proof_req: ProofRequest = serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "0.1",
+            "requested_attributes": {
+                "attr1_referent": {
+                    "name": "name",
+                    "restrictions": {"$not": {"cred_def_id": CRED_DEF_ID}}
+                }
+            },
+            "requested_predicates": {}
+        })).unwrap();
+
+        let plan = ProofArtifactsPlan::build(&proof_req, &[], 100);
+
+        assert!(plan.is_empty());
+    }
+}
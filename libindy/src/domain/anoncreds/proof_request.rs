@@ -3,6 +3,7 @@ use std::fmt;
 use ursa::cl::Nonce;
 
 use indy_api_types::validation::Validatable;
+use indy_api_types::errors::prelude::*;
 
 use serde::{de, Deserialize, Deserializer, ser, Serialize, Serializer};
 use serde_json::Value;
@@ -143,11 +144,66 @@ pub struct AttributeInfo {
 pub struct PredicateInfo {
     pub name: String,
     pub p_type: PredicateTypes,
-    pub p_value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p_value: Option<i32>,
+    // bounds of RANGE predicate, both inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<i32>,
     pub restrictions: Option<Query>,
     pub non_revoked: Option<NonRevocedInterval>
 }
 
+impl PredicateInfo {
+    // Elementary predicates to prove. RANGE predicate is proved as a pair of `>=` and `<=` predicates
+    // over the same attribute of the same credential.
+    pub fn bounds(&self) -> IndyResult<Vec<(PredicateTypes, i32)>> {
+        self._bounds()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))
+    }
+
+    pub fn is_satisfied_by(&self, value: i32) -> IndyResult<bool> {
+        let res = self.bounds()?
+            .iter()
+            .all(|(p_type, p_value)| match p_type {
+                PredicateTypes::GE => value >= *p_value,
+                PredicateTypes::GT => value > *p_value,
+                PredicateTypes::LE => value <= *p_value,
+                PredicateTypes::LT => value < *p_value,
+                PredicateTypes::RANGE => false,
+            });
+
+        Ok(res)
+    }
+
+    fn _bounds(&self) -> Result<Vec<(PredicateTypes, i32)>, String> {
+        match self.p_type {
+            PredicateTypes::RANGE => {
+                if self.p_value.is_some() {
+                    return Err(format!("RANGE predicate must not contain \"p_value\": {:?}", self));
+                }
+
+                match (self.from, self.to) {
+                    (Some(from), Some(to)) if from <= to => Ok(vec![(PredicateTypes::GE, from), (PredicateTypes::LE, to)]),
+                    (Some(_), Some(_)) => Err(format!("RANGE predicate \"from\" must not be greater than \"to\": {:?}", self)),
+                    _ => Err(format!("RANGE predicate must contain both \"from\" and \"to\": {:?}", self)),
+                }
+            }
+            ref p_type => {
+                if self.from.is_some() || self.to.is_some() {
+                    return Err(format!("Only RANGE predicate can contain \"from\" and \"to\": {:?}", self));
+                }
+
+                match self.p_value {
+                    Some(p_value) => Ok(vec![(p_type.clone(), p_value)]),
+                    None => Err(format!("Predicate must contain \"p_value\": {:?}", self)),
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 pub enum PredicateTypes {
     #[serde(rename = ">=")]
@@ -157,7 +213,8 @@ pub enum PredicateTypes {
     #[serde(rename = ">")]
     GT,
     #[serde(rename = "<")]
-    LT
+    LT,
+    RANGE
 }
 
 impl fmt::Display for PredicateTypes {
@@ -166,7 +223,8 @@ impl fmt::Display for PredicateTypes {
             PredicateTypes::GE => write!(f, "GE"),
             PredicateTypes::GT => write!(f, "GT"),
             PredicateTypes::LE => write!(f, "LE"),
-            PredicateTypes::LT => write!(f, "LT")
+            PredicateTypes::LT => write!(f, "LT"),
+            PredicateTypes::RANGE => write!(f, "RANGE")
         }
     }
 }
@@ -213,6 +271,8 @@ impl Validatable for ProofRequest {
             if requested_predicate.name.is_empty() {
                 return Err(format!("Proof Request validation failed: there is empty requested attribute: {:?}", requested_predicate));
             }
+            requested_predicate._bounds()
+                .map_err(|err| format!("Proof Request validation failed: {}", err))?;
             if let Some(ref restrictions) = requested_predicate.restrictions {
                 _process_operator(&restrictions, &version)?;
             }
@@ -370,6 +430,54 @@ mod tests {
         }
     }
 
+    mod predicate_bounds {
+        use super::*;
+
+        fn _proof_request(predicate: serde_json::Value) -> ProofRequest {
+            serde_json::from_value(json!({
+                "nonce": "123456",
+                "name": "name",
+                "version": "0.1",
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": predicate
+                },
+            })).unwrap()
+        }
+
+        #[test]
+        fn predicate_bounds_works_for_range() {
+            let proof_req = _proof_request(json!({"name": "age", "p_type": "RANGE", "from": 18, "to": 65}));
+            proof_req.validate().unwrap();
+
+            let predicate = &proof_req.value().requested_predicates["predicate1_referent"];
+            assert_eq!(vec![(PredicateTypes::GE, 18), (PredicateTypes::LE, 65)], predicate.bounds().unwrap());
+        }
+
+        #[test]
+        fn predicate_bounds_works_for_comparison() {
+            for (p_type, expected) in vec![(">=", PredicateTypes::GE), (">", PredicateTypes::GT), ("<=", PredicateTypes::LE), ("<", PredicateTypes::LT)] {
+                let proof_req = _proof_request(json!({"name": "age", "p_type": p_type, "p_value": 18}));
+                proof_req.validate().unwrap();
+
+                let predicate = &proof_req.value().requested_predicates["predicate1_referent"];
+                assert_eq!(vec![(expected, 18)], predicate.bounds().unwrap());
+            }
+        }
+
+        #[test]
+        fn proof_request_validation_fails_for_invalid_range() {
+            _proof_request(json!({"name": "age", "p_type": "RANGE", "from": 65, "to": 18})).validate().unwrap_err();
+            _proof_request(json!({"name": "age", "p_type": "RANGE", "from": 18})).validate().unwrap_err();
+            _proof_request(json!({"name": "age", "p_type": "RANGE", "from": 18, "to": 65, "p_value": 18})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validation_fails_for_comparison_without_value() {
+            _proof_request(json!({"name": "age", "p_type": ">=", "from": 18})).validate().unwrap_err();
+        }
+    }
+
     mod to_unqualified {
         use super::*;
 
@@ -400,7 +508,9 @@ mod tests {
             requested_predicates.insert("predicate1_referent".to_string(), PredicateInfo {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
-                p_value: 0,
+                p_value: Some(0),
+                from: None,
+                to: None,
                 restrictions: Some(Query::And(vec![
                     Query::Eq("schema_issuer_did".to_string(), DID_QUALIFIED.to_string()),
                     Query::Eq("rev_reg_id".to_string(), REV_REG_ID_QUALIFIED.to_string()),
@@ -434,7 +544,9 @@ mod tests {
             expected_requested_predicates.insert("predicate1_referent".to_string(), PredicateInfo {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
-                p_value: 0,
+                p_value: Some(0),
+                from: None,
+                to: None,
                 restrictions: Some(Query::And(vec![
                     Query::Eq("schema_issuer_did".to_string(), DID_UNQUALIFIED.to_string()),
                     Query::Eq("rev_reg_id".to_string(), REV_REG_ID_UNQUALIFIED.to_string()),
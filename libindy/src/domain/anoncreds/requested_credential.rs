@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use indy_api_types::validation::Validatable;
 
+use super::proof_request::ProofRequest;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct RequestedCredentials {
     pub self_attested_attributes: HashMap<String, String>,
@@ -29,4 +31,34 @@ impl Validatable for RequestedCredentials {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProofRequestWithCredentials {
+    pub proof_request: ProofRequest,
+    pub requested_credentials: RequestedCredentials
+}
+
+impl Validatable for ProofRequestWithCredentials {
+    fn validate(&self) -> Result<(), String> {
+        self.proof_request.validate()?;
+        self.requested_credentials.validate()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProofRequestsWithCredentials(pub Vec<ProofRequestWithCredentials>);
+
+impl Validatable for ProofRequestsWithCredentials {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("Proof Requests validation failed: list is empty"));
+        }
+
+        for request in self.0.iter() {
+            request.validate()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use indy_api_types::errors::prelude::*;
+
+use super::proof::ProofTelemetry;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerificationResult {
+    pub valid: bool,
+    // None if cryptographic verification was skipped because of failed checks
+    pub crypto_valid: Option<bool>,
+    pub referents: HashMap<String, ReferentVerification>,
+    pub revealed_attrs: HashMap<String, String>,
+    pub revealed_attr_groups: HashMap<String, HashMap<String, String>>,
+    // true if revocation artifacts were unavailable and cryptographic verification was skipped by policy
+    #[serde(default)]
+    pub revocation_unchecked: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unavailable_rev_reg_ids: Vec<String>,
+    // referents proven by sub-proofs whose revocation registries were unavailable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revocation_unchecked_referents: Vec<String>,
+    // referents of revealed attributes whose encoded values don't match canonical encoding of raw values
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub non_canonical_attrs: Vec<String>,
+    // referents proven by credentials without revocation support despite requested non-revocation interval
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub non_revocable_accepted: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<ProofTelemetry>,
+    // Failures in order of checking. Used to build the error of legacy boolean verification.
+    #[serde(skip)]
+    pub failures: Vec<(VerificationCheck, IndyError)>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReferentVerification {
+    pub valid: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<VerificationFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerificationFailure {
+    pub check: VerificationCheck,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationCheck {
+    AttributeMismatch,
+    PredicateMismatch,
+    RevocationInterval,
+    Restriction,
+    AttributeEncoding,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VerificationOptions {
+    #[serde(default)]
+    pub revocation_unavailable: RevocationUnavailablePolicy,
+    #[serde(default)]
+    pub attribute_encoding: AttributeEncodingPolicy,
+    // referents for which credentials without revocation support are accepted
+    // even if the proof request demands a non-revocation interval
+    #[serde(default)]
+    pub non_revocable_referents: Vec<String>,
+}
+
+// What to do if encoded value of a revealed attribute doesn't match canonical encoding of its raw value.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeEncodingPolicy {
+    Warn,
+    Fail,
+}
+
+impl Default for AttributeEncodingPolicy {
+    fn default() -> Self {
+        AttributeEncodingPolicy::Warn
+    }
+}
+
+// What to do if a revocation registry definition or entry used by the proof is not provided.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RevocationUnavailablePolicy {
+    Fail,
+    Skip,
+}
+
+impl Default for RevocationUnavailablePolicy {
+    fn default() -> Self {
+        RevocationUnavailablePolicy::Fail
+    }
+}
+
+impl VerificationResult {
+    // Boolean result of `indy_verifier_verify_proof`: the first failed check is returned as error.
+    pub fn into_legacy(self) -> IndyResult<bool> {
+        match self.failures.into_iter().next() {
+            Some((_, err)) => Err(err),
+            None => Ok(self.valid)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_verification_options() {
+        let options: VerificationOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(RevocationUnavailablePolicy::Fail, options.revocation_unavailable);
+        assert_eq!(AttributeEncodingPolicy::Warn, options.attribute_encoding);
+        assert!(options.non_revocable_referents.is_empty());
+
+        let options: VerificationOptions = serde_json::from_str(r#"{"revocation_unavailable":"skip","attribute_encoding":"fail"}"#).unwrap();
+        assert_eq!(RevocationUnavailablePolicy::Skip, options.revocation_unavailable);
+        assert_eq!(AttributeEncodingPolicy::Fail, options.attribute_encoding);
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use ursa::cl::{
+    CredentialSignature,
+    RevocationRegistry,
+    SignatureCorrectnessProof,
+    Witness
+};
+
+use indy_api_types::validation::Validatable;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::schema::SchemaId;
+use crate::domain::crypto::did::DidValue;
+
+pub const W3C_CREDENTIALS_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+pub const W3C_VERIFIABLE_CREDENTIAL_TYPE: &str = "VerifiableCredential";
+pub const INDY_CREDENTIAL_TYPE: &str = "IndyCredential";
+pub const INDY_CREDENTIAL_SCHEMA_TYPE: &str = "IndyCredentialSchema";
+pub const CL_SIGNATURE_TYPE: &str = "CLSignature2019";
+
+/// Issued anoncreds credential represented in W3C Verifiable Credentials data model.
+/// Raw attribute values are exposed as `credentialSubject`,
+/// CL signature and encoded attribute values are kept in `proof` to restore the credential.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredential {
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    #[serde(rename = "type")]
+    pub type_: Vec<String>,
+    pub issuer: DidValue,
+    pub issuance_date: String,
+    pub credential_schema: W3CCredentialSchema,
+    pub credential_subject: HashMap<String, String>,
+    pub proof: W3CCredentialProof,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct W3CCredentialSchema {
+    pub id: SchemaId,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct W3CCredentialProof {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub cred_def_id: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    pub encoding: HashMap<String, String>,
+    pub signature: CredentialSignature,
+    pub signature_correctness_proof: SignatureCorrectnessProof,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg: Option<RevocationRegistry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness: Option<Witness>,
+}
+
+impl Validatable for W3CCredential {
+    fn validate(&self) -> Result<(), String> {
+        if self.context.first().map(String::as_str) != Some(W3C_CREDENTIALS_CONTEXT) {
+            return Err(format!("W3CCredential validation failed: the first `@context` must be {}", W3C_CREDENTIALS_CONTEXT));
+        }
+
+        if !self.type_.iter().any(|type_| type_ == W3C_VERIFIABLE_CREDENTIAL_TYPE) {
+            return Err(format!("W3CCredential validation failed: `type` must contain {}", W3C_VERIFIABLE_CREDENTIAL_TYPE));
+        }
+
+        if self.proof.type_ != CL_SIGNATURE_TYPE {
+            return Err(format!("W3CCredential validation failed: unsupported proof type {}", self.proof.type_));
+        }
+
+        self.credential_schema.id.validate()?;
+        self.proof.cred_def_id.validate()?;
+
+        if self.credential_subject.is_empty() {
+            return Err(String::from("W3CCredential validation failed: `credentialSubject` is empty"));
+        }
+
+        if let Some(attr) = self.credential_subject.keys().find(|attr| !self.proof.encoding.contains_key(*attr)) {
+            return Err(format!("W3CCredential validation failed: encoded value of `{}` isn't found in `proof`", attr));
+        }
+
+        if self.proof.encoding.len() != self.credential_subject.len() {
+            return Err(String::from("W3CCredential validation failed: `proof` contains encoded values of unknown attributes"));
+        }
+
+        if self.proof.rev_reg_id.is_some() && (self.proof.witness.is_none() || self.proof.rev_reg.is_none()) {
+            return Err(String::from("W3CCredential validation failed: `witness` and `revReg` must be passed for revocable Credential"));
+        }
+
+        Ok(())
+    }
+}
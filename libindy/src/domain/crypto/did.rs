@@ -1,6 +1,7 @@
 use regex::Regex;
 use rust_base58::FromBase58;
 
+use indy_api_types::domain::wallet::MnemonicSeed;
 use indy_api_types::validation::Validatable;
 use crate::utils::qualifier;
 
@@ -11,8 +12,9 @@ impl Validatable for DidMethod {
     fn validate(&self) -> Result<(), String> {
         lazy_static! {
                 static ref REGEX_METHOD_NAME: Regex = Regex::new("^[a-z0-9]+$").unwrap();
+                static ref REGEX_INDY_METHOD_NAME: Regex = Regex::new("^indy:[a-z0-9]+(:[a-z0-9]+)*$").unwrap();
             }
-        if !REGEX_METHOD_NAME.is_match(&self.0) {
+        if !REGEX_METHOD_NAME.is_match(&self.0) && !REGEX_INDY_METHOD_NAME.is_match(&self.0) {
             return Err(format!("Invalid default name: {}. It does not match the DID method name format.", self.0));
         }
         Ok(())
@@ -26,6 +28,9 @@ pub struct MyDidInfo {
     pub crypto_type: Option<String>,
     pub cid: Option<bool>,
     pub method_name: Option<DidMethod>,
+    pub mnemonic: Option<MnemonicSeed>,
+    // Name of key management registered with indy_register_key_management that creates and keeps the private key
+    pub kms: Option<String>,
 }
 
 impl Validatable for MyDidInfo {
@@ -33,6 +38,12 @@ impl Validatable for MyDidInfo {
         if let Some(ref did) = self.did {
             did.validate()?;
         }
+        if self.seed.is_some() && self.mnemonic.is_some() {
+            return Err("Only one of seed and mnemonic can be provided".to_string());
+        }
+        if self.mnemonic.is_some() && self.kms.is_some() {
+            return Err("Mnemonic can't be used for a key kept in external KMS".to_string());
+        }
         if let Some(ref name) = self.method_name {
             name.validate()?
         }
@@ -99,6 +110,10 @@ impl DidValue {
         DidValue(qualifier::to_unqualified(&self.0))
     }
 
+    pub fn get_namespace(&self) -> Option<String> {
+        qualifier::namespace(&self.0)
+    }
+
     pub fn is_abbreviatable(&self) -> bool {
         match self.get_method() {
             Some(ref method) if method.starts_with("sov") => true,
@@ -0,0 +1,68 @@
+pub const ALG_EDDSA: &str = "EdDSA";
+pub const ALG_ES256K: &str = "ES256K";
+
+pub const JWK_KTY_OKP: &str = "OKP";
+pub const JWK_CRV_ED25519: &str = "Ed25519";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum JwsFormat {
+    #[serde(rename = "compact")]
+    Compact,
+    #[serde(rename = "general")]
+    General,
+}
+
+impl Default for JwsFormat {
+    fn default() -> Self {
+        JwsFormat::Compact
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct SignJwsOptions {
+    #[serde(default)]
+    pub format: JwsFormat,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct JwsHeader {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwk: Option<Jwk>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct JwsSignature {
+    pub protected: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<JwsHeader>,
+    pub signature: String,
+}
+
+// General JSON serialization. Flattened one is parsed as general with a single signature.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct GeneralJws {
+    pub payload: String,
+    pub signatures: Vec<JwsSignature>,
+}
+
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct FlattenedJws {
+    pub payload: String,
+    #[serde(flatten)]
+    pub signature: JwsSignature,
+}
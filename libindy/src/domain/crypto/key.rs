@@ -12,6 +12,10 @@ pub struct Key {
     pub signkey: String,
     #[cfg(test)]
     pub signkey: String,
+    // Name of key management registered with indy_register_key_management that keeps the private key.
+    // signkey is the identifier of the private key in KMS then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kms: Option<String>,
 }
 
 impl Key {
@@ -19,6 +23,7 @@ impl Key {
         Key {
             verkey,
             signkey,
+            kms: None,
         }
     }
 }
@@ -39,6 +44,7 @@ impl Drop for Key {
 pub struct KeyInfo {
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
+    pub kms: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use indy_api_types::validation::Validatable;
 
 use super::constants::{GET_TXN_AUTHR_AGRMT, GET_TXN_AUTHR_AGRMT_AML, TXN_AUTHR_AGRMT, TXN_AUTHR_AGRMT_AML, DISABLE_ALL_TXN_AUTHR_AGRMTS};
+use super::response::{GetReplyResultV0, ReplyType};
 
 #[derive(Serialize, PartialEq, Debug)]
 pub struct TxnAuthorAgreementOperation {
@@ -144,4 +145,44 @@ impl GetAcceptanceMechanismOperation {
             version,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct TxnAuthorAgreement {
+    pub text: String,
+    pub version: String,
+    pub digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratification_ts: Option<u64>,
+}
+
+pub type GetTxnAuthorAgreementReplyResult = GetReplyResultV0<TxnAuthorAgreement>;
+
+impl ReplyType for GetTxnAuthorAgreementReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_TXN_AUTHR_AGRMT
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptanceMechanismsData {
+    pub aml: AcceptanceMechanisms,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aml_context: Option<String>,
+}
+
+pub type GetAcceptanceMechanismsReplyResult = GetReplyResultV0<AcceptanceMechanismsData>;
+
+impl ReplyType for GetAcceptanceMechanismsReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_TXN_AUTHR_AGRMT_AML
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct ActiveTxnAuthorAgreement {
+    pub taa: Option<TxnAuthorAgreement>,
+    pub aml: Option<AcceptanceMechanismsData>,
+}
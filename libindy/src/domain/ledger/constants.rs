@@ -28,11 +28,18 @@ pub const AUTH_RULE: &str = "120";
 pub const GET_AUTH_RULE: &str = "121";
 pub const AUTH_RULES: &str = "122";
 pub const GET_DDO: &str = "120";//TODO change number
+pub const RICH_SCHEMA_CONTEXT: &str = "200";
+pub const RICH_SCHEMA: &str = "201";
+pub const RICH_SCHEMA_ENCODING: &str = "202";
+pub const RICH_SCHEMA_MAPPING: &str = "203";
+pub const GET_RICH_SCHEMA_OBJECT_BY_ID: &str = "300";
+pub const GET_RICH_SCHEMA_OBJECT_BY_METADATA: &str = "301";
 
-pub const REQUESTS: [&str; 27] = [NODE, NYM, GET_TXN, ATTRIB, SCHEMA, CRED_DEF, GET_ATTR, GET_NYM, GET_SCHEMA,
+pub const REQUESTS: [&str; 33] = [NODE, NYM, GET_TXN, ATTRIB, SCHEMA, CRED_DEF, GET_ATTR, GET_NYM, GET_SCHEMA,
     GET_CRED_DEF, POOL_UPGRADE, POOL_RESTART, POOL_CONFIG, REVOC_REG_DEF, REVOC_REG_ENTRY, GET_REVOC_REG_DEF,
     GET_REVOC_REG, GET_REVOC_REG_DELTA, GET_VALIDATOR_INFO, AUTH_RULE, GET_DDO, TXN_AUTHR_AGRMT, TXN_AUTHR_AGRMT_AML,
-    GET_TXN_AUTHR_AGRMT, GET_TXN_AUTHR_AGRMT_AML, LEDGERS_FREEZE, GET_FROZEN_LEDGERS];
+    GET_TXN_AUTHR_AGRMT, GET_TXN_AUTHR_AGRMT_AML, LEDGERS_FREEZE, GET_FROZEN_LEDGERS, RICH_SCHEMA_CONTEXT, RICH_SCHEMA,
+    RICH_SCHEMA_ENCODING, RICH_SCHEMA_MAPPING, GET_RICH_SCHEMA_OBJECT_BY_ID, GET_RICH_SCHEMA_OBJECT_BY_METADATA];
 
 pub const TRUSTEE: &str = "0";
 pub const STEWARD: &str = "2";
@@ -75,6 +82,12 @@ pub fn txn_name_to_code(txn: &str) -> Option<&str> {
         "GET_TXN_AUTHR_AGRMT_AML" => Some(GET_TXN_AUTHR_AGRMT_AML),
         "LEDGERS_FREEZE" => Some(LEDGERS_FREEZE),
         "GET_FROZEN_LEDGERS" => Some(GET_FROZEN_LEDGERS),
+        "SET_CONTEXT" | "RICH_SCHEMA_CONTEXT" => Some(RICH_SCHEMA_CONTEXT),
+        "RICH_SCHEMA" => Some(RICH_SCHEMA),
+        "RICH_SCHEMA_ENCODING" | "ENCODING" => Some(RICH_SCHEMA_ENCODING),
+        "RICH_SCHEMA_MAPPING" | "MAPPING" => Some(RICH_SCHEMA_MAPPING),
+        "GET_RICH_SCHEMA_OBJECT_BY_ID" => Some(GET_RICH_SCHEMA_OBJECT_BY_ID),
+        "GET_RICH_SCHEMA_OBJECT_BY_METADATA" => Some(GET_RICH_SCHEMA_OBJECT_BY_METADATA),
         val => Some(val)
     }
 }
\ No newline at end of file
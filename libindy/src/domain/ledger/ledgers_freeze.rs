@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use super::constants::{LEDGERS_FREEZE, GET_FROZEN_LEDGERS};
+use super::response::{GetReplyResultV0, ReplyType};
 
 #[derive(Serialize, PartialEq, Debug)]
 pub struct LedgersFreezeOperation {
@@ -29,3 +32,18 @@ impl GetFrozenLedgersOperation {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct FrozenLedger {
+    pub ledger: String,
+    pub state: String,
+    pub seq_no: u64,
+}
+
+pub type GetFrozenLedgersReplyResult = GetReplyResultV0<HashMap<String, FrozenLedger>>;
+
+impl ReplyType for GetFrozenLedgersReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_FROZEN_LEDGERS
+    }
+}
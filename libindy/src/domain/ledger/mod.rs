@@ -15,3 +15,4 @@ pub mod constants;
 pub mod auth_rule;
 pub mod author_agreement;
 pub mod ledgers_freeze;
+pub mod rich_schema;
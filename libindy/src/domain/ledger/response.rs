@@ -86,4 +86,24 @@ pub struct ResponseMetadata {
     pub last_txn_time: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_seq_no: Option<u64>,
-}
\ No newline at end of file
+}
+#[derive(Serialize, Debug, PartialEq)]
+pub enum NodeActionStatus {
+    #[serde(rename = "REPLY")]
+    Reply,
+    #[serde(rename = "REQNACK")]
+    ReqNACK,
+    #[serde(rename = "REJECT")]
+    Reject,
+    #[serde(rename = "timeout")]
+    Timeout,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct NodeActionResult {
+    pub status: NodeActionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
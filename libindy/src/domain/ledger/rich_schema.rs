@@ -0,0 +1,157 @@
+use indy_api_types::validation::Validatable;
+use serde_json::Value;
+
+use super::constants::{RICH_SCHEMA, RICH_SCHEMA_CONTEXT, RICH_SCHEMA_ENCODING, RICH_SCHEMA_MAPPING,
+                       GET_RICH_SCHEMA_OBJECT_BY_ID, GET_RICH_SCHEMA_OBJECT_BY_METADATA};
+use super::response::{GetReplyResultV0, ReplyType};
+
+// Objects of the rich schema family. Every type is written by its own transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RichSchemaType {
+    Ctx,
+    Sch,
+    Map,
+    Enc,
+}
+
+impl RichSchemaType {
+    pub fn txn_type(&self) -> &'static str {
+        match self {
+            RichSchemaType::Ctx => RICH_SCHEMA_CONTEXT,
+            RichSchemaType::Sch => RICH_SCHEMA,
+            RichSchemaType::Map => RICH_SCHEMA_MAPPING,
+            RichSchemaType::Enc => RICH_SCHEMA_ENCODING,
+        }
+    }
+}
+
+// JSON-LD content is kept as string as it is written to the ledger as is
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RichSchema {
+    pub id: String,
+    pub content: String,
+    pub rs_name: String,
+    pub rs_version: String,
+    pub rs_type: RichSchemaType,
+    pub ver: String,
+}
+
+impl Validatable for RichSchema {
+    fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() || self.rs_name.is_empty() || self.rs_version.is_empty() {
+            return Err(String::from("Rich schema `id`, `rsName` and `rsVersion` must not be empty"));
+        }
+
+        let content: Value = serde_json::from_str(&self.content)
+            .map_err(|err| format!("Rich schema content is not a valid JSON: {}", err))?;
+
+        if !content.is_object() {
+            return Err(String::from("Rich schema content must be a JSON object"));
+        }
+
+        match self.rs_type {
+            RichSchemaType::Ctx if content["@context"].is_null() =>
+                Err(String::from("JSON-LD context must contain `@context`")),
+            RichSchemaType::Sch if content["@id"].as_str() != Some(self.id.as_str()) =>
+                Err(format!("Rich schema `@id` must be equal to its `id`: {}", self.id)),
+            _ => Ok(())
+        }
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RichSchemaOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: String,
+    pub content: String,
+    pub rs_name: String,
+    pub rs_version: String,
+    pub rs_type: RichSchemaType,
+    pub ver: String,
+}
+
+impl RichSchemaOperation {
+    pub fn new(rich_schema: RichSchema) -> RichSchemaOperation {
+        RichSchemaOperation {
+            _type: rich_schema.rs_type.txn_type().to_string(),
+            id: rich_schema.id,
+            content: rich_schema.content,
+            rs_name: rich_schema.rs_name,
+            rs_version: rich_schema.rs_version,
+            rs_type: rich_schema.rs_type,
+            ver: rich_schema.ver,
+        }
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct GetRichSchemaObjectByIdOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: String,
+}
+
+impl GetRichSchemaObjectByIdOperation {
+    pub fn new(id: String) -> GetRichSchemaObjectByIdOperation {
+        GetRichSchemaObjectByIdOperation {
+            _type: GET_RICH_SCHEMA_OBJECT_BY_ID.to_string(),
+            id,
+        }
+    }
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRichSchemaObjectByMetadataOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub rs_type: RichSchemaType,
+    pub rs_name: String,
+    pub rs_version: String,
+}
+
+impl GetRichSchemaObjectByMetadataOperation {
+    pub fn new(rs_type: RichSchemaType, rs_name: String, rs_version: String) -> GetRichSchemaObjectByMetadataOperation {
+        GetRichSchemaObjectByMetadataOperation {
+            _type: GET_RICH_SCHEMA_OBJECT_BY_METADATA.to_string(),
+            rs_type,
+            rs_name,
+            rs_version,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetRichSchemaObjectByIdReplyResult(GetReplyResultV0<RichSchema>);
+
+impl GetRichSchemaObjectByIdReplyResult {
+    pub fn data(self) -> Option<RichSchema> {
+        self.0.data
+    }
+}
+
+impl ReplyType for GetRichSchemaObjectByIdReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_RICH_SCHEMA_OBJECT_BY_ID
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetRichSchemaObjectByMetadataReplyResult(GetReplyResultV0<RichSchema>);
+
+impl GetRichSchemaObjectByMetadataReplyResult {
+    pub fn data(self) -> Option<RichSchema> {
+        self.0.data
+    }
+}
+
+impl ReplyType for GetRichSchemaObjectByMetadataReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_RICH_SCHEMA_OBJECT_BY_METADATA
+    }
+}
+
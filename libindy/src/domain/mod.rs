@@ -10,8 +10,35 @@ use indy_api_types::validation::Validatable;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IndyConfig {
     pub crypto_thread_pool_size: Option<usize>,
+    pub cred_def_thread_pool_size: Option<usize>,
+    pub command_queue_size: Option<usize>,
+    pub command_workers: Option<usize>,
     pub collect_backtrace: Option<bool>,
-    pub freshness_threshold: Option<u64>
+    pub freshness_threshold: Option<u64>,
+    pub anoncreds_telemetry: Option<bool>,
+    pub ledger_cache_ttl: Option<i32>,
 }
 
-impl Validatable for IndyConfig {}
\ No newline at end of file
+impl Validatable for IndyConfig {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndyFeatures {
+    pub revocation: Feature,
+    pub payments: Feature,
+    pub postgres_storage: Feature,
+    pub didcomm_v2: Feature,
+    pub experimental_protocols: Feature,
+    pub anoncreds_telemetry: Feature,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Feature {
+    pub compiled: bool,
+    pub enabled: bool,
+}
+
+impl Feature {
+    pub fn new(compiled: bool, enabled: bool) -> Feature {
+        Feature { compiled, enabled: compiled && enabled }
+    }
+}
@@ -1,5 +1,7 @@
 use indy_api_types::validation::Validatable;
 
+use crate::utils::qualifier;
+
 pub const POOL_CON_ACTIVE_TO: i64 = 5;
 pub const POOL_ACK_TIMEOUT: i64 = 20;
 pub const POOL_REPLY_TIMEOUT: i64 = 60;
@@ -35,6 +37,10 @@ pub struct PoolOpenConfig {
     pub number_read_nodes: u8,
     #[serde(default = "PoolOpenConfig::default_socks_proxy")]
     pub socks_proxy: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub mock: bool,
 }
 
 impl Validatable for PoolOpenConfig {
@@ -54,6 +60,11 @@ impl Validatable for PoolOpenConfig {
         if self.number_read_nodes == 0 {
             return Err(String::from("`number_read_nodes` must be greater than 0"));
         }
+        if let Some(ref namespace) = self.namespace {
+            if !qualifier::is_valid_namespace(namespace) {
+                return Err(format!("Invalid namespace: {}. It does not match the network namespace format.", namespace));
+            }
+        }
         Ok(())
     }
 }
@@ -68,6 +79,8 @@ impl Default for PoolOpenConfig {
             preordered_nodes: PoolOpenConfig::default_preordered_nodes(),
             number_read_nodes: PoolOpenConfig::default_number_read_nodes(),
             socks_proxy: PoolOpenConfig::default_socks_proxy(),
+            namespace: None,
+            mock: false,
         }
     }
 }
@@ -97,3 +110,103 @@ impl PoolOpenConfig {
 
     fn default_socks_proxy() -> String { String::new() }
 }
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReadRequestOptions {
+    // number of matching replies required to accept the result, f+1 if not set
+    pub quorum: Option<usize>,
+    // number of nodes the request is sent to at once, `number_read_nodes` of the pool if not set
+    pub fan_out: Option<usize>,
+    // time to wait for an acknowledgement of a node in seconds, `timeout` of the pool if not set
+    pub timeout: Option<i64>,
+    // only a reply with valid state proof completes the request, matching replies without it are not enough
+    #[serde(default)]
+    pub require_state_proof: bool,
+}
+
+impl Validatable for ReadRequestOptions {
+    fn validate(&self) -> Result<(), String> {
+        if self.quorum == Some(0) {
+            return Err(String::from("`quorum` must be greater than 0"));
+        }
+        if self.fan_out == Some(0) {
+            return Err(String::from("`fan_out` must be greater than 0"));
+        }
+        if self.timeout.map(|timeout| timeout <= 0).unwrap_or(false) {
+            return Err(String::from("`timeout` must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenesisValidationOptions {
+    // try to establish tcp connection with client endpoint of every node
+    #[serde(default)]
+    pub check_endpoints: bool,
+    // time to wait for connection with a node in seconds, POOL_CON_ACTIVE_TO if not set
+    pub endpoint_timeout: Option<u64>,
+}
+
+impl Validatable for GenesisValidationOptions {
+    fn validate(&self) -> Result<(), String> {
+        if self.endpoint_timeout == Some(0) {
+            return Err(String::from("`endpoint_timeout` must be greater than 0"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenesisIssueKind {
+    InvalidJson,
+    InvalidTransaction,
+    IncompatibleProtocolVersion,
+    InvalidVerkey,
+    InvalidBlsKey,
+    InvalidBlsKeyPop,
+    DuplicateNode,
+    NotValidator,
+    MissingClientAddress,
+    UnreachableEndpoint,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenesisIssue {
+    pub kind: GenesisIssueKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenesisValidationReport {
+    pub valid: bool,
+    pub txns_count: usize,
+    pub nodes_count: usize,
+    pub issues: Vec<GenesisIssue>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenesisNode {
+    pub alias: String,
+    pub dest: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GenesisNodeChange {
+    pub alias: String,
+    pub dest: String,
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct GenesisDiff {
+    pub added: Vec<GenesisNode>,
+    pub removed: Vec<GenesisNode>,
+    pub changed: Vec<GenesisNodeChange>,
+    pub unchanged: usize,
+}
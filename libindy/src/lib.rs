@@ -42,6 +42,8 @@ extern crate hex;
 extern crate log_derive;
 extern crate rust_base58;
 
+extern crate bip39;
+extern crate hmac;
 extern crate sha2;
 extern crate sha3;
 
@@ -65,6 +67,9 @@ mod commands;
 mod services;
 mod domain;
 
+#[cfg(feature = "verifier_daemon")]
+pub mod verifier_daemon;
+
 #[cfg(test)]
 mod tests {
     //use super::*;
@@ -0,0 +1,206 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::rc::Rc;
+
+use sha2::{Digest, Sha256};
+use ursa::cl::{CredentialPublicKey, CredentialSchema, NonCredentialSchema};
+
+use crate::domain::anoncreds::credential_definition::CredentialDefinitionV1;
+use crate::domain::anoncreds::schema::AttributeNames;
+use indy_api_types::errors::prelude::*;
+use crate::services::anoncreds::helpers::*;
+
+pub const DEFAULT_CACHE_SIZE: usize = 128;
+
+// Credential definitions are keyed by id and version of their format, as the content
+// of published credential definition never changes.
+type CacheKey = (String, &'static str);
+
+const CRED_DEF_V1_VERSION: &str = "1.0";
+
+// Keeps results of cryptographic pre-processing of schemas and credential definitions
+// that are used again and again for issuance, proving and verification.
+pub struct AnoncredsCache {
+    credential_pub_keys: RefCell<LruCache<CacheKey, Rc<CredentialPublicKey>>>,
+    credential_schemas: RefCell<LruCache<Vec<u8>, Rc<CredentialSchema>>>,
+    non_credential_schema: RefCell<Option<Rc<NonCredentialSchema>>>,
+}
+
+impl AnoncredsCache {
+    pub fn new(size: usize) -> AnoncredsCache {
+        AnoncredsCache {
+            credential_pub_keys: RefCell::new(LruCache::new(size)),
+            credential_schemas: RefCell::new(LruCache::new(size)),
+            non_credential_schema: RefCell::new(None),
+        }
+    }
+
+    pub fn credential_pub_key(&self, cred_def: &CredentialDefinitionV1) -> IndyResult<Rc<CredentialPublicKey>> {
+        let key = (cred_def.id.0.clone(), CRED_DEF_V1_VERSION);
+
+        if let Some(pub_key) = self.credential_pub_keys.borrow_mut().get(&key) {
+            return Ok(pub_key);
+        }
+
+        let pub_key = Rc::new(CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?);
+        self.credential_pub_keys.borrow_mut().put(key, pub_key.clone());
+
+        Ok(pub_key)
+    }
+
+    pub fn credential_schema(&self, attr_names: &AttributeNames) -> IndyResult<Rc<CredentialSchema>> {
+        let mut attrs: Vec<String> = attr_names.0.iter().map(|attr| attr_common_view(attr)).collect();
+        attrs.sort();
+        let key = AnoncredsCache::_hash(&attrs)?;
+
+        if let Some(schema) = self.credential_schemas.borrow_mut().get(&key) {
+            return Ok(schema);
+        }
+
+        let schema = Rc::new(build_credential_schema(&attr_names.0)?);
+        self.credential_schemas.borrow_mut().put(key, schema.clone());
+
+        Ok(schema)
+    }
+
+    pub fn non_credential_schema(&self) -> IndyResult<Rc<NonCredentialSchema>> {
+        if let Some(ref schema) = *self.non_credential_schema.borrow() {
+            return Ok(schema.clone());
+        }
+
+        let schema = Rc::new(build_non_credential_schema()?);
+        *self.non_credential_schema.borrow_mut() = Some(schema.clone());
+
+        Ok(schema)
+    }
+
+    fn _hash<T: serde::Serialize>(value: &T) -> IndyResult<Vec<u8>> {
+        let bytes = serde_json::to_vec(value)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize entity for caching")?;
+
+        Ok(Sha256::digest(&bytes).to_vec())
+    }
+}
+
+impl Default for AnoncredsCache {
+    fn default() -> Self {
+        AnoncredsCache::new(DEFAULT_CACHE_SIZE)
+    }
+}
+
+struct LruCache<K, V> {
+    size: usize,
+    entries: HashMap<K, V>,
+    // least recently used key first
+    order: VecDeque<K>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    fn new(size: usize) -> LruCache<K, V> {
+        LruCache {
+            size,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get(key).cloned()?;
+        self._touch(key);
+        Some(value)
+    }
+
+    fn put(&mut self, key: K, value: V) {
+        if self.size == 0 {
+            return;
+        }
+
+        if self.entries.insert(key.clone(), value).is_some() {
+            self._touch(&key);
+            return;
+        }
+
+        self.order.push_back(key);
+
+        while self.order.len() > self.size {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn _touch(&mut self, key: &K) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            if let Some(k) = self.order.remove(pos) {
+                self.order.push_back(k);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::anoncreds::credential_definition::{CredentialDefinitionId, SignatureType};
+    use crate::domain::anoncreds::schema::SchemaId;
+    use crate::services::anoncreds::issuer::Issuer;
+
+    use super::*;
+
+    #[test]
+    fn lru_cache_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "a");
+        cache.put(2, "b");
+
+        assert_eq!(Some("a"), cache.get(&1));
+
+        cache.put(3, "c");
+
+        assert_eq!(Some("a"), cache.get(&1));
+        assert_eq!(None, cache.get(&2));
+        assert_eq!(Some("c"), cache.get(&3));
+    }
+
+    #[test]
+    fn lru_cache_with_zero_size_keeps_nothing() {
+        let mut cache = LruCache::new(0);
+        cache.put(1, "a");
+
+        assert_eq!(None, cache.get(&1));
+    }
+
+    #[test]
+    fn credential_schema_is_reused_for_same_attributes() {
+        let cache = AnoncredsCache::default();
+
+        let attrs: AttributeNames = serde_json::from_str(r#"["name", "age"]"#).unwrap();
+        let same_attrs: AttributeNames = serde_json::from_str(r#"["Age", "name"]"#).unwrap();
+
+        let schema = cache.credential_schema(&attrs).unwrap();
+        let same_schema = cache.credential_schema(&same_attrs).unwrap();
+
+        assert!(Rc::ptr_eq(&schema, &same_schema));
+    }
+
+    #[test]
+    fn credential_pub_key_is_reused_for_same_id() {
+        let cache = AnoncredsCache::default();
+
+        let attrs: AttributeNames = serde_json::from_str(r#"["name", "age"]"#).unwrap();
+        let (value, _, _) = Issuer::new_credential_definition(&attrs, false).unwrap();
+
+        let cred_def = CredentialDefinitionV1 {
+            id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:1:tag".to_string()),
+            schema_id: SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+            signature_type: SignatureType::CL,
+            tag: "tag".to_string(),
+            value,
+        };
+
+        let pub_key = cache.credential_pub_key(&cred_def).unwrap();
+        let same_pub_key = cache.credential_pub_key(&cred_def).unwrap();
+
+        assert!(Rc::ptr_eq(&pub_key, &same_pub_key));
+    }
+}
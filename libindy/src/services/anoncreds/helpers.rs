@@ -1,4 +1,5 @@
 use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
 
 use crate::domain::anoncreds::credential::AttributeValues;
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, NonRevocedInterval};
@@ -16,6 +17,10 @@ use crate::domain::anoncreds::credential_request::CredentialRequest;
 use crate::domain::anoncreds::proof_request::ProofRequest;
 
 use std::collections::{HashSet, HashMap};
+use std::time::Instant;
+
+use sha2::{Digest, Sha256};
+use ursa::bn::BigNumber;
 
 pub fn attr_common_view(attr: &str) -> String {
     attr.replace(" ", "").to_lowercase()
@@ -47,6 +52,39 @@ pub fn build_non_credential_schema() -> IndyResult<NonCredentialSchema> {
     Ok(res)
 }
 
+// Canonical encoding of credential attribute values: a raw value which is a 32-bit signed integer
+// is encoded as this integer, any other raw value as SHA-256 hash of its UTF-8 bytes read as big-endian integer.
+pub fn encode_attribute_value(raw: &str) -> IndyResult<String> {
+    if let Ok(value) = raw.parse::<i32>() {
+        return Ok(value.to_string());
+    }
+
+    let hash = Sha256::digest(raw.as_bytes());
+
+    Ok(BigNumber::from_bytes(&hash)?.to_dec()?)
+}
+
+pub fn is_canonical_encoding(values: &AttributeValues) -> IndyResult<bool> {
+    let canonical = encode_attribute_value(&values.raw)?;
+
+    Ok(BigNumber::from_dec(&canonical)? == BigNumber::from_dec(&values.encoded)?)
+}
+
+// Names of attributes whose encoded values don't match canonical encoding of raw values.
+pub fn non_canonical_attributes(credential_values: &HashMap<String, AttributeValues>) -> IndyResult<Vec<String>> {
+    let mut attrs = Vec::new();
+
+    for (attr, values) in credential_values {
+        if !is_canonical_encoding(values)? {
+            attrs.push(attr.to_string());
+        }
+    }
+
+    attrs.sort();
+
+    Ok(attrs)
+}
+
 pub fn build_credential_values(credential_values: &HashMap<String, AttributeValues>, master_secret: Option<&MasterSecret>) -> IndyResult<CredentialValues> {
     trace!("build_credential_values >>> credential_values: {:?}", credential_values);
 
@@ -87,9 +125,9 @@ pub fn build_sub_proof_request(attrs_for_credential: &[AttributeInfo],
     }
 
     for predicate in predicates_for_credential {
-        let p_type = format!("{}", predicate.p_type);
-
-        sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.name), &p_type, predicate.p_value)?;
+        for (p_type, p_value) in predicate.bounds()? {
+            sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.name), &p_type.to_string(), p_value)?;
+        }
     }
 
     let res = sub_proof_request_builder.finalize()?;
@@ -110,6 +148,11 @@ pub fn parse_cred_rev_id(cred_rev_id: &str) -> IndyResult<u32> {
     Ok(res)
 }
 
+pub fn elapsed_micros(start: Instant) -> u64 {
+    let elapsed = start.elapsed();
+    elapsed.as_secs() * 1_000_000 + u64::from(elapsed.subsec_micros())
+}
+
 pub fn get_non_revoc_interval(global_interval: &Option<NonRevocedInterval>, local_interval: &Option<NonRevocedInterval>) -> Option<NonRevocedInterval> {
     trace!("get_non_revoc_interval >>> global_interval: {:?}, local_interval: {:?}", global_interval, local_interval);
 
@@ -157,10 +200,100 @@ pub fn to_unqualified(entity: &str) -> IndyResult<String> {
     Ok(entity.to_string())
 }
 
+// Artifacts are passed as maps keyed by ids. Malformed keys are rejected upfront
+// rather than surface later as artifacts not found for a credential.
+pub fn validate_artifact_ids<'a, K, I>(name: &str, ids: I) -> IndyResult<()>
+    where K: Validatable + 'a, I: IntoIterator<Item=&'a K> {
+    for id in ids {
+        id.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid {} Id: {}", name, err)))?;
+    }
+    Ok(())
+}
+
+// Ids are compared regardless of qualification as artifacts may come from different sources.
+pub fn check_same_cred_def(what: &str, id: &CredentialDefinitionId, other_id: &CredentialDefinitionId) -> IndyResult<()> {
+    if id.to_unqualified() != other_id.to_unqualified() {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("{} refer to different Credential Definitions: {:?} and {:?}", what, id, other_id)));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod validate_artifact_ids {
+        use super::*;
+
+        #[test]
+        fn validate_artifact_ids_works() {
+            let ids = vec![SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()), SchemaId("1".to_string())];
+            validate_artifact_ids("Schema", ids.iter()).unwrap();
+        }
+
+        #[test]
+        fn validate_artifact_ids_works_for_malformed_id() {
+            let ids = vec![SchemaId("NcYxiDXkpYi6ov5FcYDi1e:2".to_string())];
+            assert_kind!(IndyErrorKind::InvalidStructure, validate_artifact_ids("Schema", ids.iter()));
+        }
+    }
+
+    mod check_same_cred_def {
+        use super::*;
+
+        #[test]
+        fn check_same_cred_def_works_for_differently_qualified_ids() {
+            let id = CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag".to_string());
+            let qualified_id = CredentialDefinitionId("creddef:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:3:CL:schema:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag".to_string());
+            check_same_cred_def("Ids", &id, &qualified_id).unwrap();
+        }
+
+        #[test]
+        fn check_same_cred_def_works_for_different_ids() {
+            let id = CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag".to_string());
+            let other_id = CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:xyz:1.0:tag".to_string());
+            assert_kind!(IndyErrorKind::InvalidStructure, check_same_cred_def("Ids", &id, &other_id));
+        }
+    }
+
+    mod encode_attribute_value {
+        use super::*;
+
+        #[test]
+        fn encode_attribute_value_works_for_integer() {
+            assert_eq!("1234", encode_attribute_value("1234").unwrap());
+            assert_eq!("1234", encode_attribute_value("01234").unwrap());
+            assert_eq!("-5", encode_attribute_value("-5").unwrap());
+            assert_eq!("2147483647", encode_attribute_value("2147483647").unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_string() {
+            assert_eq!("99262857098057710338306967609588410025648622308394250666849665532448612202874",
+                       encode_attribute_value("Alex").unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_integer_out_of_range() {
+            let encoded = encode_attribute_value("2147483648").unwrap();
+            assert_ne!("2147483648", encoded);
+            assert_eq!(encode_attribute_value("2147483648").unwrap(), encoded);
+        }
+
+        #[test]
+        fn non_canonical_attributes_works() {
+            let values = hashmap!(
+                "name".to_string() => AttributeValues { raw: "Alex".to_string(), encoded: "1139481716457488690172217916278103335".to_string() },
+                "age".to_string() => AttributeValues { raw: "28".to_string(), encoded: "28".to_string() },
+                "sex".to_string() => AttributeValues { raw: "male".to_string(), encoded: encode_attribute_value("male").unwrap() }
+            );
+
+            assert_eq!(vec!["name".to_string()], non_canonical_attributes(&values).unwrap());
+        }
+    }
+
     fn _interval() -> NonRevocedInterval { NonRevocedInterval { from: None, to: Some(123) } }
 
     #[test]
@@ -1,7 +1,6 @@
 use ursa::cl::{
     CredentialKeyCorrectnessProof,
     CredentialPrivateKey,
-    CredentialPublicKey,
     CredentialSignature,
     Nonce,
     RevocationKeyPrivate,
@@ -21,12 +20,17 @@ use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistr
 use crate::domain::crypto::did::DidValue;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
+use crate::services::anoncreds::cache::AnoncredsCache;
 
-pub struct Issuer {}
+use std::rc::Rc;
+
+pub struct Issuer {
+    cache: Rc<AnoncredsCache>
+}
 
 impl Issuer {
-    pub fn new() -> Issuer {
-        Issuer {}
+    pub fn new(cache: Rc<AnoncredsCache>) -> Issuer {
+        Issuer { cache }
     }
 
     pub fn new_credential_definition(attr_names: &AttributeNames,
@@ -63,8 +67,7 @@ impl Issuer {
         trace!("new_revocation_registry >>> pub_key: {:?}, max_cred_num: {:?}, issuance_by_default: {:?}, issuer_did: {:?}",
                cred_def, max_cred_num, issuance_by_default, issuer_did);
 
-        let credential_pub_key =
-            CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+        let credential_pub_key = self.cache.credential_pub_key(cred_def)?;
 
         let (rev_key_pub, rev_key_priv, rev_reg_entry, rev_tails_generator) =
             CryptoIssuer::new_revocation_registry_def(&credential_pub_key, max_cred_num, issuance_by_default)?;
@@ -98,7 +101,7 @@ impl Issuer {
                rev_reg_def, rev_reg, secret!(&rev_key_priv));
 
         let credential_values = build_credential_values(&cred_values.0, None)?;
-        let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+        let credential_pub_key = self.cache.credential_pub_key(cred_def)?;
 
         let (credential_signature, signature_correctness_proof, rev_reg_delta) =
             match rev_idx {
@@ -1,11 +1,28 @@
+pub mod cache;
 pub mod helpers;
 pub mod issuer;
+pub mod presentation_exchange;
 pub mod prover;
 pub mod verifier;
 
 use crate::services::anoncreds::issuer::Issuer;
 use crate::services::anoncreds::prover::Prover;
 use crate::services::anoncreds::verifier::Verifier;
+use crate::services::anoncreds::cache::AnoncredsCache;
+
+use std::rc::Rc;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static TELEMETRY_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_telemetry_enabled(enabled: bool) {
+    TELEMETRY_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_telemetry_enabled() -> bool {
+    TELEMETRY_ENABLED.load(Ordering::Relaxed)
+}
 
 pub struct AnoncredsService {
     pub issuer: Issuer,
@@ -15,10 +32,12 @@ pub struct AnoncredsService {
 
 impl AnoncredsService {
     pub fn new() -> AnoncredsService {
+        let cache = Rc::new(AnoncredsCache::default());
+
         AnoncredsService {
-            issuer: Issuer::new(),
-            prover: Prover::new(),
-            verifier: Verifier::new()
+            issuer: Issuer::new(cache.clone()),
+            prover: Prover::new(cache.clone()),
+            verifier: Verifier::new(cache)
         }
     }
 }
\ No newline at end of file
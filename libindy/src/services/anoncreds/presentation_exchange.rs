@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use ursa::cl::Nonce;
+
+use indy_api_types::errors::prelude::*;
+
+use crate::domain::anoncreds::presentation_exchange::{Field, InputDescriptor, PresentationDefinition, PresentationSubmission, SubmissionDescriptor};
+use crate::domain::anoncreds::proof::{Proof, RequestedProof};
+use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload};
+use crate::utils::wql::Query;
+
+pub const INDY_PROOF_FORMAT: &str = "hlindy/proof@v2.0";
+
+/*
+    Translation of DIF Presentation Definition into Indy proof request:
+     * every input descriptor is requested as a group of attributes referenced by the descriptor id,
+       so all revealed fields of the descriptor come from the same credential
+     * numeric bounds of the field filter (`minimum`, `exclusiveMinimum`, `maximum`, `exclusiveMaximum`) are proved as predicates
+       referenced by `<descriptor id>::<index>`
+     * `const` filter restricts the value of the revealed attribute
+     * `schema` uris restrict schema id of the credential
+    Attribute name is the last segment of the first field path: `$.credentialSubject.age` -> `age`.
+*/
+pub fn build_proof_request(definition: &PresentationDefinition, nonce: Nonce) -> IndyResult<ProofRequest> {
+    trace!("build_proof_request >>> definition: {:?}", definition);
+
+    let mut requested_attributes = HashMap::new();
+    let mut requested_predicates = HashMap::new();
+
+    for descriptor in definition.input_descriptors.iter() {
+        let schema_restriction = _schema_restriction(descriptor);
+
+        let mut names = Vec::new();
+        let mut value_restrictions = Vec::new();
+
+        for field in descriptor.constraints.fields.iter() {
+            let name = _attribute_name(field)?;
+
+            let filter = match field.filter {
+                Some(ref filter) => filter,
+                None => {
+                    names.push(name);
+                    continue;
+                }
+            };
+
+            if let Some(ref value) = filter.const_ {
+                value_restrictions.push(Query::Eq(format!("attr::{}::value", name), _raw_value(value)));
+            }
+
+            if !filter.has_bounds() {
+                names.push(name);
+                continue;
+            }
+
+            for (p_type, p_value, from, to) in _bounds(field)? {
+                let referent = format!("{}::{}", descriptor.id, requested_predicates.len());
+
+                requested_predicates.insert(referent, PredicateInfo {
+                    name: name.clone(),
+                    p_type,
+                    p_value,
+                    from,
+                    to,
+                    restrictions: schema_restriction.clone(),
+                    non_revoked: None,
+                });
+            }
+        }
+
+        if !names.is_empty() {
+            let restrictions = match schema_restriction {
+                Some(restriction) => { value_restrictions.insert(0, restriction); value_restrictions }
+                None => value_restrictions
+            };
+
+            requested_attributes.insert(descriptor.id.clone(), AttributeInfo {
+                name: None,
+                names: Some(names),
+                restrictions: _and(restrictions),
+                non_revoked: None,
+            });
+        }
+    }
+
+    let res = ProofRequest::ProofRequestV2(ProofRequestPayload {
+        nonce,
+        name: definition.name.clone().unwrap_or_else(|| definition.id.clone()),
+        version: String::from("1.0"),
+        requested_attributes,
+        requested_predicates,
+        non_revoked: None,
+    });
+
+    trace!("build_proof_request <<< res: {:?}", res);
+
+    Ok(res)
+}
+
+// Maps every input descriptor to the identifier of the credential which satisfied it
+pub fn build_presentation_submission(definition: &PresentationDefinition, proof: &Proof) -> IndyResult<PresentationSubmission> {
+    trace!("build_presentation_submission >>> definition: {:?}", definition);
+
+    let res = PresentationSubmission {
+        id: uuid::Uuid::new_v4().to_string(),
+        definition_id: definition.id.clone(),
+        descriptor_map: _descriptor_map(definition, &proof.requested_proof, proof.identifiers.len())?,
+    };
+
+    trace!("build_presentation_submission <<< res: {:?}", res);
+
+    Ok(res)
+}
+
+fn _descriptor_map(definition: &PresentationDefinition, requested_proof: &RequestedProof, identifiers_count: usize) -> IndyResult<Vec<SubmissionDescriptor>> {
+    definition.input_descriptors
+        .iter()
+        .map(|descriptor| {
+            let sub_proof_index = _sub_proof_index(descriptor, requested_proof)?;
+
+            if sub_proof_index as usize >= identifiers_count {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Proof doesn't contain identifiers of the credential for input descriptor {}", descriptor.id)));
+            }
+
+            Ok(SubmissionDescriptor {
+                id: descriptor.id.clone(),
+                format: INDY_PROOF_FORMAT.to_string(),
+                path: format!("$.identifiers[{}]", sub_proof_index),
+            })
+        })
+        .collect()
+}
+
+fn _sub_proof_index(descriptor: &InputDescriptor, requested_proof: &RequestedProof) -> IndyResult<u32> {
+    if let Some(group) = requested_proof.revealed_attr_groups.get(&descriptor.id) {
+        return Ok(group.sub_proof_index);
+    }
+
+    let prefix = format!("{}::", descriptor.id);
+
+    requested_proof.predicates
+        .iter()
+        .filter(|(referent, _)| referent.starts_with(&prefix))
+        .map(|(_, predicate)| predicate.sub_proof_index)
+        .min()
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Proof doesn't satisfy input descriptor {}", descriptor.id)))
+}
+
+fn _attribute_name(field: &Field) -> IndyResult<String> {
+    let path = field.path.first().map(String::as_str).unwrap_or_default();
+
+    if !path.starts_with('$') {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported field path: {}", path)));
+    }
+
+    let name = path
+        .trim_end_matches(|c| c == ']' || c == '\'' || c == '"')
+        .rsplit(|c| c == '.' || c == '[' || c == '\'' || c == '"')
+        .next()
+        .unwrap_or_default();
+
+    if name.is_empty() || name == "$" {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Field path doesn't point to an attribute: {}", path)));
+    }
+
+    Ok(name.to_string())
+}
+
+// Inclusive bounds are requested as a single RANGE predicate if both are present
+fn _bounds(field: &Field) -> IndyResult<Vec<(PredicateTypes, Option<i32>, Option<i32>, Option<i32>)>> {
+    let filter = match field.filter {
+        Some(ref filter) => filter,
+        None => return Ok(Vec::new())
+    };
+
+    if filter.minimum.is_some() && filter.exclusive_minimum.is_some() ||
+        filter.maximum.is_some() && filter.exclusive_maximum.is_some() {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Field filter must not contain both inclusive and exclusive bounds of the same side: {:?}", field.path)));
+    }
+
+    if let (Some(from), Some(to)) = (filter.minimum, filter.maximum) {
+        return Ok(vec![(PredicateTypes::RANGE, None, Some(from), Some(to))]);
+    }
+
+    let bounds = vec![
+        (PredicateTypes::GE, filter.minimum),
+        (PredicateTypes::GT, filter.exclusive_minimum),
+        (PredicateTypes::LE, filter.maximum),
+        (PredicateTypes::LT, filter.exclusive_maximum),
+    ];
+
+    Ok(bounds
+        .into_iter()
+        .filter_map(|(p_type, p_value)| p_value.map(|p_value| (p_type, Some(p_value), None, None)))
+        .collect())
+}
+
+fn _schema_restriction(descriptor: &InputDescriptor) -> Option<Query> {
+    let restrictions = descriptor.schema
+        .iter()
+        .map(|schema| Query::Eq(String::from("schema_id"), schema.uri.clone()))
+        .collect::<Vec<Query>>();
+
+    match restrictions.len() {
+        0 => None,
+        1 => restrictions.into_iter().next(),
+        _ => Some(Query::Or(restrictions))
+    }
+}
+
+fn _and(mut restrictions: Vec<Query>) -> Option<Query> {
+    match restrictions.len() {
+        0 => None,
+        1 => restrictions.pop(),
+        _ => Some(Query::And(restrictions))
+    }
+}
+
+fn _raw_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::anoncreds::proof::{RevealedAttributeGroupInfo, SubProofReferent};
+
+    fn _nonce() -> Nonce {
+        ursa::cl::new_nonce().unwrap()
+    }
+
+    fn _definition() -> PresentationDefinition {
+        serde_json::from_value(json!({
+            "id": "definition",
+            "input_descriptors": [
+                {
+                    "id": "citizenship",
+                    "schema": [{"uri": "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0"}],
+                    "constraints": {
+                        "fields": [
+                            {"path": ["$.credentialSubject.name"]},
+                            {"path": ["$.credentialSubject['sex']"], "filter": {"type": "string", "const": "male"}},
+                            {"path": ["$.credentialSubject.age"], "filter": {"type": "number", "minimum": 18}}
+                        ]
+                    }
+                },
+                {
+                    "id": "height",
+                    "constraints": {
+                        "fields": [
+                            {"path": ["$.credentialSubject.height"], "filter": {"type": "number", "minimum": 150, "maximum": 200}}
+                        ]
+                    }
+                }
+            ]
+        })).unwrap()
+    }
+
+    fn _requested_proof() -> RequestedProof {
+        let mut requested_proof = RequestedProof::default();
+
+        requested_proof.revealed_attr_groups.insert("citizenship".to_string(), RevealedAttributeGroupInfo { sub_proof_index: 1, values: HashMap::new() });
+        requested_proof.predicates.insert("citizenship::0".to_string(), SubProofReferent { sub_proof_index: 1 });
+        requested_proof.predicates.insert("height::1".to_string(), SubProofReferent { sub_proof_index: 0 });
+
+        requested_proof
+    }
+
+    #[test]
+    fn build_proof_request_works() {
+        let proof_request = build_proof_request(&_definition(), _nonce()).unwrap();
+        let proof_request = proof_request.value();
+
+        assert_eq!("definition", proof_request.name);
+        assert_eq!(1, proof_request.requested_attributes.len());
+        assert_eq!(2, proof_request.requested_predicates.len());
+
+        let expected_attribute = AttributeInfo {
+            name: None,
+            names: Some(vec!["name".to_string(), "sex".to_string()]),
+            restrictions: Some(Query::And(vec![
+                Query::Eq("schema_id".to_string(), "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string()),
+                Query::Eq("attr::sex::value".to_string(), "male".to_string()),
+            ])),
+            non_revoked: None,
+        };
+        assert_eq!(expected_attribute, proof_request.requested_attributes["citizenship"]);
+
+        let age = &proof_request.requested_predicates["citizenship::0"];
+        assert_eq!("age", age.name);
+        assert_eq!(PredicateTypes::GE, age.p_type);
+        assert_eq!(Some(18), age.p_value);
+        assert_eq!(Some(Query::Eq("schema_id".to_string(), "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0".to_string())), age.restrictions);
+
+        let height = &proof_request.requested_predicates["height::1"];
+        assert_eq!("height", height.name);
+        assert_eq!(PredicateTypes::RANGE, height.p_type);
+        assert_eq!((Some(150), Some(200)), (height.from, height.to));
+        assert_eq!(None, height.restrictions);
+    }
+
+    #[test]
+    fn build_proof_request_works_for_exclusive_bounds() {
+        let mut definition = _definition();
+        definition.input_descriptors[1].constraints.fields[0].filter = serde_json::from_value(json!({"exclusiveMinimum": 150, "exclusiveMaximum": 200})).unwrap();
+
+        let proof_request = build_proof_request(&definition, _nonce()).unwrap();
+
+        let mut bounds = proof_request.value().requested_predicates
+            .values()
+            .filter(|predicate| predicate.name == "height")
+            .map(|predicate| (predicate.p_type.clone(), predicate.p_value))
+            .collect::<Vec<(PredicateTypes, Option<i32>)>>();
+        bounds.sort_by_key(|(_, p_value)| *p_value);
+
+        assert_eq!(vec![(PredicateTypes::GT, Some(150)), (PredicateTypes::LT, Some(200))], bounds);
+    }
+
+    #[test]
+    fn build_proof_request_fails_for_invalid_path() {
+        let mut definition = _definition();
+        definition.input_descriptors[0].constraints.fields[0].path = vec!["credentialSubject.name".to_string()];
+
+        let res = build_proof_request(&definition, _nonce());
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn build_descriptor_map_works() {
+        let descriptor_map = _descriptor_map(&_definition(), &_requested_proof(), 2).unwrap();
+
+        assert_eq!(vec![
+            SubmissionDescriptor { id: "citizenship".to_string(), format: INDY_PROOF_FORMAT.to_string(), path: "$.identifiers[1]".to_string() },
+            SubmissionDescriptor { id: "height".to_string(), format: INDY_PROOF_FORMAT.to_string(), path: "$.identifiers[0]".to_string() },
+        ], descriptor_map);
+    }
+
+    #[test]
+    fn build_descriptor_map_fails_for_unsatisfied_descriptor() {
+        let mut requested_proof = _requested_proof();
+        requested_proof.predicates.remove("height::1");
+
+        let res = _descriptor_map(&_definition(), &requested_proof, 2);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn build_descriptor_map_fails_for_missed_identifier() {
+        let res = _descriptor_map(&_definition(), &_requested_proof(), 1);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+}
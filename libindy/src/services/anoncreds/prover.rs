@@ -1,10 +1,11 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Instant;
 
 use ursa::cl::{
     BlindedCredentialSecrets,
     BlindedCredentialSecretsCorrectnessProof,
-    CredentialPublicKey,
     CredentialSecretsBlindingFactors,
     MasterSecret,
     SubProofRequest,
@@ -18,7 +19,7 @@ use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolic
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1 as CredentialDefinition, CredentialDefinitionId};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequestMetadata;
-use crate::domain::anoncreds::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo, SubProofReferent, RevealedAttributeGroupInfo, AttributeValue};
+use crate::domain::anoncreds::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo, SubProofReferent, RevealedAttributeGroupInfo, AttributeValue, ProofTelemetry};
 use crate::domain::anoncreds::proof_request::{PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload, ProofRequestsVersion, RequestedAttributeInfo, RequestedPredicateInfo, ProofRequestExtraQuery};
 use crate::domain::anoncreds::requested_credential::ProvingCredentialKey;
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
@@ -27,16 +28,20 @@ use crate::domain::anoncreds::revocation_state::RevocationState;
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
+use crate::services::anoncreds::is_telemetry_enabled;
 use crate::utils::wql::Query;
 use crate::services::anoncreds::verifier::Verifier;
+use crate::services::anoncreds::cache::AnoncredsCache;
 
 const ATTRIBUTE_EXISTENCE_MARKER: &str = "1";
 
-pub struct Prover {}
+pub struct Prover {
+    cache: Rc<AnoncredsCache>
+}
 
 impl Prover {
-    pub fn new() -> Prover {
-        Prover {}
+    pub fn new(cache: Rc<AnoncredsCache>) -> Prover {
+        Prover { cache }
     }
 
     pub fn new_master_secret(&self) -> IndyResult<MasterSecret> {
@@ -58,7 +63,9 @@ impl Prover {
         trace!("new_credential_request >>> cred_def: {:?}, master_secret: {:?}, credential_offer: {:?}",
                cred_def, secret!(&master_secret), credential_offer);
 
-        let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+        check_same_cred_def("Credential Offer and Credential Definition", &credential_offer.cred_def_id, &cred_def.id)?;
+
+        let credential_pub_key = self.cache.credential_pub_key(cred_def)?;
         let mut credential_values_builder = CryptoIssuer::new_credential_values_builder()?;
         credential_values_builder.add_value_hidden("master_secret", &master_secret.value()?)?;
         let cred_values = credential_values_builder.finalize()?;
@@ -84,7 +91,7 @@ impl Prover {
         trace!("process_credential >>> credential: {:?}, cred_request_metadata: {:?}, master_secret: {:?}, cred_def: {:?}, rev_reg_def: {:?}",
                credential, cred_request_metadata, secret!(&master_secret), cred_def, rev_reg_def);
 
-        let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+        let credential_pub_key = self.cache.credential_pub_key(cred_def)?;
         let credential_values = build_credential_values(&credential.values.0, Some(master_secret))?;
 
         CryptoProver::process_credential_signature(&mut credential.signature,
@@ -109,10 +116,16 @@ impl Prover {
                         master_secret: &MasterSecret,
                         schemas: &HashMap<SchemaId, SchemaV1>,
                         cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinition>,
-                        rev_states: &HashMap<String, HashMap<u64, RevocationState>>) -> IndyResult<Proof> {
+                        rev_states: &HashMap<String, HashMap<u64, RevocationState>>) -> IndyResult<(Proof, Option<ProofTelemetry>)> {
         trace!("create_proof >>> credentials: {:?}, proof_req: {:?}, requested_credentials: {:?}, master_secret: {:?}, schemas: {:?}, cred_defs: {:?}, rev_states: {:?}",
                credentials, proof_req, requested_credentials, secret!(&master_secret), schemas, cred_defs, rev_states);
 
+        validate_artifact_ids("Schema", schemas.keys())?;
+        validate_artifact_ids("Credential Definition", cred_defs.keys())?;
+
+        let start = Instant::now();
+        let mut telemetry = if is_telemetry_enabled() { Some(ProofTelemetry::default()) } else { None };
+
         let proof_req_val = proof_req.value();
         let mut proof_builder = CryptoProver::new_proof_builder()?;
         proof_builder.add_common_attribute("master_secret")?;
@@ -123,10 +136,12 @@ impl Prover {
 
         let credentials_for_proving = Prover::_prepare_credentials_for_proving(requested_credentials, proof_req_val)?;
         let mut sub_proof_index = 0;
-        let non_credential_schema = build_non_credential_schema()?;
+        let non_credential_schema = self.cache.non_credential_schema()?;
 
         let mut identifiers: Vec<Identifier> = Vec::with_capacity(credentials_for_proving.len());
         for (cred_key, (req_attrs_for_cred, req_predicates_for_cred)) in credentials_for_proving {
+            let sub_proof_start = Instant::now();
+
             let credential: &Credential = credentials.get(cred_key.cred_id.as_str())
                 .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id: {:?}", cred_key.cred_id)))?;
 
@@ -136,6 +151,7 @@ impl Prover {
             let cred_def: &CredentialDefinition = cred_defs.get(&credential.cred_def_id)
                 .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found by id: {:?}", credential.cred_def_id)))?;
 
+            let revocation_start = Instant::now();
             let rev_state = if let Some(timestamp) = cred_key.timestamp {
                 let rev_reg_id = credential.rev_reg_id
                     .clone()
@@ -148,10 +164,11 @@ impl Prover {
                 Some(rev_states_for_timestamp.get(&timestamp)
                     .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationInfo not found by timestamp: {:?}", timestamp)))?)
             } else { None };
+            let revocation_duration = rev_state.map(|_| elapsed_micros(revocation_start));
 
-            let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+            let credential_pub_key = self.cache.credential_pub_key(cred_def)?;
 
-            let credential_schema = build_credential_schema(&schema.attr_names.0)?;
+            let credential_schema = self.cache.credential_schema(&schema.attr_names)?;
             let credential_values = build_credential_values(&credential.values.0, Some(master_secret))?;
             let sub_proof_request = Prover::_build_sub_proof_request(&req_attrs_for_cred, &req_predicates_for_cred)?;
 
@@ -164,6 +181,12 @@ impl Prover {
                                                 rev_state.as_ref().map(|r_info| &r_info.rev_reg),
                                                 rev_state.as_ref().map(|r_info| &r_info.witness))?;
 
+            if let Some(ref mut telemetry) = telemetry {
+                let referents: Vec<&str> = req_attrs_for_cred.iter().map(|attr| attr.attr_referent.as_str())
+                    .chain(req_predicates_for_cred.iter().map(|predicate| predicate.predicate_referent.as_str()))
+                    .collect();
+                telemetry.add_sub_proof(&credential.cred_def_id, elapsed_micros(sub_proof_start), revocation_duration, &referents);
+            }
 
             let identifier = match proof_req {
                 ProofRequest::ProofRequestV1(_) => {
@@ -195,7 +218,9 @@ impl Prover {
             sub_proof_index += 1;
         }
 
+        let crypto_start = Instant::now();
         let proof = proof_builder.finalize(&proof_req_val.nonce)?;
+        let crypto_duration = elapsed_micros(crypto_start);
 
         let full_proof = Proof {
             proof,
@@ -203,9 +228,17 @@ impl Prover {
             identifiers,
         };
 
-        trace!("create_proof <<< full_proof: {:?}", full_proof);
+        if let Some(ref mut telemetry) = telemetry {
+            telemetry.proof_size = serde_json::to_vec(&full_proof)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")?
+                .len();
+            telemetry.crypto_duration = crypto_duration;
+            telemetry.total_duration = elapsed_micros(start);
+        }
+
+        trace!("create_proof <<< full_proof: {:?}, telemetry: {:?}", full_proof, telemetry);
 
-        Ok(full_proof)
+        Ok((full_proof, telemetry))
     }
 
     pub fn _prepare_credentials_for_proving(requested_credentials: &RequestedCredentials,
@@ -330,28 +363,10 @@ impl Prover {
                                        attribute_value: &str) -> IndyResult<bool> {
         trace!("attribute_satisfy_predicate >>> predicate: {:?}, attribute_value: {:?}", predicate, attribute_value);
 
-        let res = match predicate.p_type {
-            PredicateTypes::GE => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value >= predicate.p_value)
-            }
-            PredicateTypes::GT => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value > predicate.p_value)
-            }
-            PredicateTypes::LE => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value <= predicate.p_value)
-            }
-            PredicateTypes::LT => {
-                let attribute_value = attribute_value.parse::<i32>()
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
-                Ok(attribute_value < predicate.p_value)
-            }
-        };
+        let attribute_value = attribute_value.parse::<i32>()
+            .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
+
+        let res = predicate.is_satisfied_by(attribute_value);
 
         trace!("attribute_satisfy_predicate <<< res: {:?}", res);
         res
@@ -431,9 +446,9 @@ impl Prover {
         }
 
         for predicate in req_predicates_for_credential {
-            let p_type = format!("{}", predicate.predicate_info.p_type);
-
-            sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.predicate_info.name), &p_type, predicate.predicate_info.p_value)?;
+            for (p_type, p_value) in predicate.predicate_info.bounds()? {
+                sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.predicate_info.name), &p_type.to_string(), p_value)?;
+            }
         }
 
         let sub_proof_request = sub_proof_request_builder.finalize()?;
@@ -487,6 +502,37 @@ impl Prover {
         Ok(Query::And(queries))
     }
 
+    pub fn predicate_value_tag(&self, predicate: &PredicateInfo) -> String {
+        Self::_build_attr_value_tag(&predicate.name)
+    }
+
+    // Builds range query on attribute value tag selecting credentials that satisfy predicate bounds.
+    // Wallet can evaluate it only if the value tag is range indexed (see `range_tags` of wallet config).
+    // Negative bounds are skipped as range indexed tags hold non negative values only.
+    pub fn build_predicate_query(&self, predicate: &PredicateInfo) -> IndyResult<Option<Query>> {
+        trace!("build_predicate_query >>> predicate: {:?}", predicate);
+
+        let tag_name = self.predicate_value_tag(predicate);
+
+        let queries: Vec<Query> = predicate.bounds()?
+            .into_iter()
+            .filter(|&(_, p_value)| p_value >= 0)
+            .filter_map(|(p_type, p_value)| match p_type {
+                PredicateTypes::GE => Some(Query::Gte(tag_name.clone(), p_value.to_string())),
+                PredicateTypes::GT => Some(Query::Gt(tag_name.clone(), p_value.to_string())),
+                PredicateTypes::LE => Some(Query::Lte(tag_name.clone(), p_value.to_string())),
+                PredicateTypes::LT => Some(Query::Lt(tag_name.clone(), p_value.to_string())),
+                PredicateTypes::RANGE => None
+            })
+            .collect();
+
+        let res = if queries.is_empty() { None } else { Some(Query::And(queries)) };
+
+        trace!("build_predicate_query <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn _make_restrictions_by_internal_tags_case_insensitive(operator: Query) -> IndyResult<Query> {
         Ok(match operator {
             Query::Eq(tag_name, tag_value) => {
@@ -593,6 +639,10 @@ impl Prover {
 mod tests {
     use super::*;
 
+    fn _prover() -> Prover {
+        Prover::new(Rc::new(AnoncredsCache::default()))
+    }
+
     const SCHEMA_ID: &str = "NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0";
     const SCHEMA_ISSUER_DID: &str = "NcYxiDXkpYi6ov5FcYDi1e";
     const SCHEMA_NAME: &str = "gvt";
@@ -640,7 +690,7 @@ mod tests {
 
         #[test]
         fn build_credential_tags_works() {
-            let ps = Prover::new();
+            let ps = _prover();
             let tags = ps.build_credential_tags(&_credential(), None).unwrap();
 
             let expected_tags: HashMap<String, String> = hashmap!(
@@ -662,7 +712,7 @@ mod tests {
 
         #[test]
         fn build_credential_tags_works_for_catpol() {
-            let ps = Prover::new();
+            let ps = _prover();
             let catpol = CredentialAttrTagPolicy::from(vec!(String::from("name")));
             let tags = ps.build_credential_tags(&_credential(), Some(catpol).as_ref()).unwrap();
 
@@ -683,7 +733,7 @@ mod tests {
 
         #[test]
         fn build_credential_tags_works_for_rev_reg_id() {
-            let ps = Prover::new();
+            let ps = _prover();
             let mut credential = _credential();
             credential.rev_reg_id = Some(RevocationRegistryId(REV_REG_ID.to_string()));
             let tags = ps.build_credential_tags(&credential, None).unwrap();
@@ -707,7 +757,7 @@ mod tests {
 
         #[test]
         fn build_credential_tags_works_for_fully_qualified_ids() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let schema_id = "schema:sov:did:sov:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0";
             let issuer_did = "did:sov:NcYxiDXkpYi6ov5FcYDi1e";
@@ -746,12 +796,15 @@ mod tests {
 
     mod attribute_satisfy_predicate {
         use super::*;
+        use crate::domain::anoncreds::proof_request::PredicateTypes;
 
         fn predicate_info() -> PredicateInfo {
             PredicateInfo {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
-                p_value: 8,
+                p_value: Some(8),
+                from: None,
+                to: None,
                 restrictions: None,
                 non_revoked: None,
             }
@@ -759,28 +812,108 @@ mod tests {
 
         #[test]
         fn attribute_satisfy_predicate_works() {
-            let ps = Prover::new();
+            let ps = _prover();
             let res = ps.attribute_satisfy_predicate(&predicate_info(), "10").unwrap();
             assert!(res);
         }
 
         #[test]
         fn attribute_satisfy_predicate_works_for_false() {
-            let ps = Prover::new();
+            let ps = _prover();
             let res = ps.attribute_satisfy_predicate(&predicate_info(), "5").unwrap();
             assert!(!res);
         }
 
         #[test]
         fn attribute_satisfy_predicate_works_for_invalid_attribute_value() {
-            let ps = Prover::new();
+            let ps = _prover();
             let res = ps.attribute_satisfy_predicate(&predicate_info(), "string");
             assert_kind!(IndyErrorKind::InvalidStructure, res);
         }
+
+        #[test]
+        fn attribute_satisfy_predicate_works_for_range() {
+            let ps = _prover();
+            let predicate = PredicateInfo {
+                p_type: PredicateTypes::RANGE,
+                p_value: None,
+                from: Some(18),
+                to: Some(65),
+                ..predicate_info()
+            };
+
+            assert!(ps.attribute_satisfy_predicate(&predicate, "18").unwrap());
+            assert!(ps.attribute_satisfy_predicate(&predicate, "65").unwrap());
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "17").unwrap());
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "66").unwrap());
+        }
+    }
+
+    mod build_predicate_query {
+        use super::*;
+        use crate::domain::anoncreds::proof_request::PredicateTypes;
+
+        fn predicate_info() -> PredicateInfo {
+            PredicateInfo {
+                name: "Age".to_string(),
+                p_type: PredicateTypes::GE,
+                p_value: Some(18),
+                from: None,
+                to: None,
+                restrictions: None,
+                non_revoked: None,
+            }
+        }
+
+        #[test]
+        fn build_predicate_query_works() {
+            let ps = _prover();
+
+            let query = ps.build_predicate_query(&predicate_info()).unwrap();
+
+            let expected_query = Query::And(vec![
+                Query::Gte("attr::age::value".to_string(), "18".to_string())
+            ]);
+
+            assert_eq!(Some(expected_query), query);
+        }
+
+        #[test]
+        fn build_predicate_query_works_for_range() {
+            let ps = _prover();
+            let predicate = PredicateInfo {
+                p_type: PredicateTypes::RANGE,
+                p_value: None,
+                from: Some(18),
+                to: Some(65),
+                ..predicate_info()
+            };
+
+            let query = ps.build_predicate_query(&predicate).unwrap();
+
+            let expected_query = Query::And(vec![
+                Query::Gte("attr::age::value".to_string(), "18".to_string()),
+                Query::Lte("attr::age::value".to_string(), "65".to_string())
+            ]);
+
+            assert_eq!(Some(expected_query), query);
+        }
+
+        #[test]
+        fn build_predicate_query_works_for_negative_value() {
+            let ps = _prover();
+            let predicate = PredicateInfo {
+                p_type: PredicateTypes::GT,
+                p_value: Some(-1),
+                ..predicate_info()
+            };
+
+            assert_eq!(None, ps.build_predicate_query(&predicate).unwrap());
+        }
     }
 
     mod prepare_credentials_for_proving {
-        use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo};
+        use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, PredicateTypes};
         use crate::domain::anoncreds::requested_credential::RequestedAttribute;
 
         use super::*;
@@ -802,7 +935,9 @@ mod tests {
             PredicateInfo {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
-                p_value: 8,
+                p_value: Some(8),
+                from: None,
+                to: None,
                 restrictions: None,
                 non_revoked: None,
             }
@@ -919,7 +1054,7 @@ mod tests {
 
         #[test]
         fn get_credential_values_for_attribute_works() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let res = ps.get_credential_values_for_attribute(&_cred_values(), "name").unwrap();
             assert_eq!(_attr_values(), res);
@@ -927,7 +1062,7 @@ mod tests {
 
         #[test]
         fn get_credential_values_for_attribute_works_for_requested_attr_different_case() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let res = ps.get_credential_values_for_attribute(&_cred_values(), "NAme").unwrap();
             assert_eq!(_attr_values(), res);
@@ -935,7 +1070,7 @@ mod tests {
 
         #[test]
         fn get_credential_values_for_attribute_works_for_requested_attr_contains_spaces() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let res = ps.get_credential_values_for_attribute(&_cred_values(), "   na me  ").unwrap();
             assert_eq!(_attr_values(), res);
@@ -943,7 +1078,7 @@ mod tests {
 
         #[test]
         fn get_credential_values_for_attribute_works_for_cred_values_different_case() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let cred_values = hashmap!("NAME".to_string() => _attr_values());
 
@@ -953,7 +1088,7 @@ mod tests {
 
         #[test]
         fn get_credential_values_for_attribute_works_for_cred_values_contains_spaces() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let cred_values = hashmap!("    name    ".to_string() => _attr_values());
 
@@ -963,7 +1098,7 @@ mod tests {
 
         #[test]
         fn get_credential_values_for_attribute_works_for_cred_values_and_requested_attr_contains_spaces() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let cred_values = hashmap!("    name    ".to_string() => _attr_values());
 
@@ -981,7 +1116,7 @@ mod tests {
 
         #[test]
         fn extend_operator_works_for_qualifiable_tag() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let query = Query::Eq(QUALIFIABLE_TAG.to_string(), VALUE.to_string());
             let query = ps.double_restrictions(query).unwrap();
@@ -996,7 +1131,7 @@ mod tests {
 
         #[test]
         fn extend_operator_works_for_not_qualifiable_tag() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let query = Query::Eq(NOT_QUALIFIABLE_TAG.to_string(), VALUE.to_string());
             let query = ps.double_restrictions(query).unwrap();
@@ -1008,7 +1143,7 @@ mod tests {
 
         #[test]
         fn extend_operator_works_for_qualifiable_tag_for_combination() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let query = Query::And(vec![
                 Query::Eq(QUALIFIABLE_TAG.to_string(), VALUE.to_string()),
@@ -1041,7 +1176,7 @@ mod tests {
 
         #[test]
         fn build_query_works() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let query = ps.process_proof_request_restrictions(&ProofRequestsVersion::V2,
                                                               &Some(ATTR_NAME.to_string()),
@@ -1059,7 +1194,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_name() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let query = ps.process_proof_request_restrictions(&ProofRequestsVersion::V2,
                                                               &None,
@@ -1078,7 +1213,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_restriction() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let restriction = Query::And(vec![
                 Query::Eq("schema_id".to_string(), SCHEMA_ID.to_string()),
@@ -1105,7 +1240,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_extra_query() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let extra_query: ProofRequestExtraQuery = hashmap!(
                 ATTR_REFERENT.to_string() => Query::Eq("name".to_string(), "Alex".to_string())
@@ -1128,7 +1263,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_mix_restriction_and_extra_query() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let restriction = Query::And(vec![
                 Query::Eq("schema_id".to_string(), SCHEMA_ID.to_string()),
@@ -1160,7 +1295,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_extra_query_with_other_referent() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let extra_query: ProofRequestExtraQuery = hashmap!(
                 "other_attr_referent".to_string() => Query::Eq("name".to_string(), "Alex".to_string())
@@ -1182,7 +1317,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_restriction_and_extra_query_contain_or_operator() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let restriction = Query::Or(vec![
                 Query::Eq("schema_id".to_string(), SCHEMA_ID.to_string()),
@@ -1221,7 +1356,7 @@ mod tests {
 
         #[test]
         fn build_query_works_for_restriction_by_internal_tags() {
-            let ps = Prover::new();
+            let ps = _prover();
 
             let restriction = Query::And(vec![
                 Query::Eq("schema_id".to_string(), SCHEMA_ID.to_string()),
@@ -1,19 +1,26 @@
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::time::Instant;
 
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1, CredentialDefinitionId};
-use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo};
+use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo, ProofTelemetry};
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistryV1;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
+use crate::domain::anoncreds::verification_result::{VerificationResult, ReferentVerification, VerificationFailure, VerificationCheck, VerificationOptions, RevocationUnavailablePolicy, AttributeEncodingPolicy};
+use crate::domain::anoncreds::credential::AttributeValues;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
+use crate::services::anoncreds::is_telemetry_enabled;
+use crate::services::anoncreds::cache::AnoncredsCache;
 
 use ursa::bn::BigNumber;
-use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
+use ursa::cl::{new_nonce, Nonce};
 use ursa::cl::verifier::Verifier as CryptoVerifier;
-use crate::utils::wql::Query;
+use crate::utils::wql::{self, Query};
 use regex::Regex;
+use failure::Fail;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Filter {
@@ -30,11 +37,131 @@ lazy_static! {
     pub static ref MARKER_TAG_MATCHER: Regex = Regex::new("^attr::([^:]+)::marker$").unwrap();
 }
 
-pub struct Verifier {}
+// Reason of rejecting a proof that does not match non-revocation intervals of the proof request.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum NonRevocationFailure {
+    TimestampNotProvided { referent: String },
+    TimestampAfterInterval { referent: String, timestamp: u64, to: u64 },
+}
+
+impl From<NonRevocationFailure> for IndyError {
+    fn from(failure: NonRevocationFailure) -> IndyError {
+        let kind = match failure {
+            NonRevocationFailure::TimestampNotProvided { .. } => IndyErrorKind::InvalidStructure,
+            _ => IndyErrorKind::ProofRejected,
+        };
+
+        let details = serde_json::to_string(&failure).unwrap_or_else(|_| format!("{:?}", failure));
+
+        IndyError::from_msg(kind, format!("Proof does not satisfy requested non-revocation interval: {}", details))
+    }
+}
+
+// Collects failed checks of proof referents instead of failing on the first one.
+struct Checks {
+    referents: HashMap<String, ReferentVerification>,
+    failures: Vec<(VerificationCheck, IndyError)>,
+}
+
+impl Checks {
+    fn new(proof_req: &ProofRequestPayload) -> Checks {
+        let referents = proof_req.requested_attributes.keys()
+            .chain(proof_req.requested_predicates.keys())
+            .map(|referent| (referent.to_string(), ReferentVerification::default()))
+            .collect();
+
+        Checks { referents, failures: Vec::new() }
+    }
+
+    fn add(&mut self, referent: &str, check: VerificationCheck, res: IndyResult<()>) {
+        if let Err(err) = res {
+            let message = Checks::_failure_message(&err);
+
+            self.referents
+                .entry(referent.to_string())
+                .or_insert_with(ReferentVerification::default)
+                .failures
+                .push(VerificationFailure { check, message });
+
+            self.failures.push((check, err));
+        }
+    }
+
+    fn has_failed(&self, referent: &str) -> bool {
+        self.referents.get(referent)
+            .map(|verification| !verification.failures.is_empty())
+            .unwrap_or(false)
+    }
+
+    fn into_result(self, proof: &Proof, crypto_valid: Option<bool>, telemetry: Option<ProofTelemetry>) -> VerificationResult {
+        let crypto_passed = crypto_valid == Some(true);
+
+        let referents = self.referents
+            .into_iter()
+            .map(|(referent, mut verification)| {
+                verification.valid = crypto_passed && verification.failures.is_empty();
+                (referent, verification)
+            })
+            .collect();
+
+        let revealed_attrs = proof.requested_proof.revealed_attrs
+            .iter()
+            .map(|(referent, info)| (referent.to_string(), info.raw.to_string()))
+            .collect();
+
+        let revealed_attr_groups = proof.requested_proof.revealed_attr_groups
+            .iter()
+            .map(|(referent, group)| {
+                let values = group.values
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.raw.to_string()))
+                    .collect();
+                (referent.to_string(), values)
+            })
+            .collect();
+
+        VerificationResult {
+            valid: crypto_passed && self.failures.is_empty(),
+            crypto_valid,
+            referents,
+            revealed_attrs,
+            revealed_attr_groups,
+            revocation_unchecked: false,
+            unavailable_rev_reg_ids: Vec::new(),
+            revocation_unchecked_referents: Vec::new(),
+            non_canonical_attrs: Vec::new(),
+            non_revocable_accepted: Vec::new(),
+            telemetry,
+            failures: self.failures,
+        }
+    }
+
+    fn _failure_message(err: &IndyError) -> String {
+        let causes: Vec<String> = <dyn Fail>::iter_causes(err).map(|cause| cause.to_string()).collect();
+
+        if causes.is_empty() {
+            err.kind().to_string()
+        } else {
+            causes.join(": ")
+        }
+    }
+}
+
+// Requested attributes and predicates proven by one sub-proof.
+#[derive(Debug, Default)]
+struct SubProofReferents {
+    attrs: Vec<AttributeInfo>,
+    predicates: Vec<PredicateInfo>,
+}
+
+pub struct Verifier {
+    cache: Rc<AnoncredsCache>
+}
 
 impl Verifier {
-    pub fn new() -> Verifier {
-        Verifier {}
+    pub fn new(cache: Rc<AnoncredsCache>) -> Verifier {
+        Verifier { cache }
     }
 
     pub fn verify(&self,
@@ -43,41 +170,158 @@ impl Verifier {
                   schemas: &HashMap<SchemaId, SchemaV1>,
                   cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
                   rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
-                  rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<bool> {
-        trace!("verify >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
-               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
+                  rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                  options: &VerificationOptions) -> IndyResult<VerificationResult> {
+        trace!("verify >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}, options: {:?}",
+               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs, options);
+
+        validate_artifact_ids("Schema", schemas.keys())?;
+        validate_artifact_ids("Credential Definition", cred_defs.keys())?;
+        validate_artifact_ids("Revocation Registry Definition", rev_reg_defs.keys())?;
+        validate_artifact_ids("Revocation Registry", rev_regs.keys())?;
+
+        let start = Instant::now();
+        let mut telemetry = if is_telemetry_enabled() { Some(ProofTelemetry::default()) } else { None };
 
         let received_revealed_attrs: HashMap<String, Identifier> = Verifier::_received_revealed_attrs(&full_proof)?;
         let received_unrevealed_attrs: HashMap<String, Identifier> = Verifier::_received_unrevealed_attrs(&full_proof)?;
         let received_predicates: HashMap<String, Identifier> = Verifier::_received_predicates(&full_proof)?;
         let received_self_attested_attrs: HashSet<String> = Verifier::_received_self_attested_attrs(&full_proof);
 
+        let mut checks = Checks::new(proof_req);
+
         Verifier::_compare_attr_from_proof_and_request(proof_req,
                                                        &received_revealed_attrs,
                                                        &received_unrevealed_attrs,
                                                        &received_self_attested_attrs,
-                                                       &received_predicates)?;
+                                                       &received_predicates,
+                                                       &mut checks);
 
-        Verifier::_verify_revealed_attribute_values(&proof_req, &full_proof)?;
+        Verifier::_verify_revealed_attribute_values(&proof_req, &full_proof, &mut checks);
+
+        let non_canonical_attrs = Verifier::_verify_revealed_attribute_encoding(&full_proof, options.attribute_encoding, &mut checks)?;
 
         Verifier::_verify_requested_restrictions(&proof_req,
                                                  &full_proof.requested_proof,
                                                  &received_revealed_attrs,
                                                  &received_unrevealed_attrs,
                                                  &received_predicates,
-                                                 &received_self_attested_attrs)?;
+                                                 &received_self_attested_attrs,
+                                                 &mut checks);
+
+        let non_revocable_accepted = Verifier::_compare_timestamps_from_proof_and_request(proof_req,
+                                                                                          &received_revealed_attrs,
+                                                                                          &received_unrevealed_attrs,
+                                                                                          &received_self_attested_attrs,
+                                                                                          &received_predicates,
+                                                                                          &options.non_revocable_referents,
+                                                                                          &mut checks);
+
+        // Non-revocation proofs are bound into the aggregated challenge of the proof: tau values of a non-revocation
+        // proof are computed from the accumulator of its registry, and primary proofs are checked only through
+        // the same challenge. Leaving out the non-revocation proof of one sub-proof changes the recomputed challenge,
+        // so CL verification would fail for any valid proof. Crypto is skipped only if a registry is really unavailable
+        // and `valid` is never true then; referents of the affected sub-proofs are reported separately.
+        let unavailable_rev_reg_ids = match options.revocation_unavailable {
+            RevocationUnavailablePolicy::Skip => Verifier::_unavailable_rev_reg_ids(full_proof, rev_reg_defs, rev_regs),
+            RevocationUnavailablePolicy::Fail => Vec::new(),
+        };
+
+        let crypto_valid = if checks.failures.is_empty() && unavailable_rev_reg_ids.is_empty() {
+            Some(self._verify_crypto_proof(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs, &mut telemetry)?)
+        } else {
+            None
+        };
+
+        if let Some(ref mut telemetry) = telemetry {
+            telemetry.proof_size = serde_json::to_vec(full_proof)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize FullProof")?
+                .len();
+            telemetry.total_duration = elapsed_micros(start);
+        }
+
+        let mut result = checks.into_result(full_proof, crypto_valid, telemetry);
+
+        if !unavailable_rev_reg_ids.is_empty() {
+            warn!("verify: revocation registries {:?} are unavailable, proof is not verified cryptographically", unavailable_rev_reg_ids);
+            result.revocation_unchecked = true;
+            result.revocation_unchecked_referents = Verifier::_revocation_unchecked_referents(&full_proof.identifiers,
+                                                                                                 &full_proof.requested_proof,
+                                                                                                 &unavailable_rev_reg_ids);
+            result.unavailable_rev_reg_ids = unavailable_rev_reg_ids;
+        }
+
+        if !non_revocable_accepted.is_empty() {
+            info!("verify: credentials without revocation support are accepted for referents {:?}", non_revocable_accepted);
+        }
+
+        result.non_canonical_attrs = non_canonical_attrs;
+        result.non_revocable_accepted = non_revocable_accepted;
 
-        Verifier::_compare_timestamps_from_proof_and_request(proof_req,
-                                                             &received_revealed_attrs,
-                                                             &received_unrevealed_attrs,
-                                                             &received_self_attested_attrs,
-                                                             &received_predicates)?;
+        trace!("verify <<< result: {:?}", result);
 
+        Ok(result)
+    }
+
+    fn _unavailable_rev_reg_ids(full_proof: &Proof,
+                                rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                                rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> Vec<String> {
+        let mut ids: Vec<String> = full_proof.identifiers
+            .iter()
+            .filter_map(|identifier| match (identifier.rev_reg_id.as_ref(), identifier.timestamp) {
+                (Some(rev_reg_id), Some(timestamp)) => {
+                    let available = rev_reg_defs.contains_key(rev_reg_id) &&
+                        rev_regs.get(rev_reg_id).map(|entries| entries.contains_key(&timestamp)).unwrap_or(false);
+                    if available { None } else { Some(rev_reg_id.0.clone()) }
+                }
+                _ => None
+            })
+            .collect();
+
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    // Referents proven by sub-proofs that refer to unavailable revocation registries
+    fn _revocation_unchecked_referents(identifiers: &[Identifier],
+                                       requested_proof: &RequestedProof,
+                                       unavailable_rev_reg_ids: &[String]) -> Vec<String> {
+        let referents_by_sub_proof = Verifier::_referent_names_by_sub_proof(requested_proof);
+
+        let mut referents: Vec<String> = identifiers
+            .iter()
+            .enumerate()
+            .filter(|(_, identifier)| identifier.timestamp.is_some() &&
+                identifier.rev_reg_id.as_ref().map(|id| unavailable_rev_reg_ids.contains(&id.0)).unwrap_or(false))
+            .filter_map(|(sub_proof_index, _)| referents_by_sub_proof.get(&(sub_proof_index as u32)))
+            .flat_map(|referents| referents.iter().map(|referent| referent.to_string()))
+            .collect();
+
+        referents.sort();
+        referents
+    }
+
+    fn _verify_crypto_proof(&self,
+                            full_proof: &Proof,
+                            proof_req: &ProofRequestPayload,
+                            schemas: &HashMap<SchemaId, SchemaV1>,
+                            cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                            rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                            rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>,
+                            telemetry: &mut Option<ProofTelemetry>) -> IndyResult<bool> {
         let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
-        let non_credential_schema = build_non_credential_schema()?;
+        let non_credential_schema = self.cache.non_credential_schema()?;
+
+        let referents_by_sub_proof = Verifier::_referents_by_sub_proof(full_proof.identifiers.len(), &full_proof.requested_proof, proof_req)?;
+        let referent_names_by_sub_proof = if telemetry.is_some() {
+            Verifier::_referent_names_by_sub_proof(&full_proof.requested_proof)
+        } else {
+            HashMap::new()
+        };
 
-        for sub_proof_index in 0..full_proof.identifiers.len() {
-            let identifier = full_proof.identifiers[sub_proof_index].clone();
+        for (sub_proof_index, (identifier, referents)) in full_proof.identifiers.iter().zip(referents_by_sub_proof.iter()).enumerate() {
+            let sub_proof_start = Instant::now();
 
             let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
                 .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
@@ -85,15 +329,20 @@ impl Verifier {
             let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
                 .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
 
+            let revocation_start = Instant::now();
             let (rev_reg_def, rev_reg) =
                 if let Some(timestamp) = identifier.timestamp {
                     let rev_reg_id = identifier.rev_reg_id
                         .clone()
                         .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
 
-                    let rev_reg_def = Some(rev_reg_defs
+                    let rev_reg_def = rev_reg_defs
                         .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?;
+
+                    check_same_cred_def("Proof identifier and RevocationRegistryDefinition", &identifier.cred_def_id, &rev_reg_def.cred_def_id)?;
+
+                    let rev_reg_def = Some(rev_reg_def);
 
                     let rev_regs_for_cred = rev_regs
                         .get(&rev_reg_id)
@@ -105,14 +354,12 @@ impl Verifier {
 
                     (rev_reg_def, rev_reg)
                 } else { (None, None) };
+            let revocation_duration = rev_reg.map(|_| elapsed_micros(revocation_start));
 
-            let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
-            let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
-
-            let credential_schema = build_credential_schema(&schema.attr_names.0)?;
-            let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
+            let credential_schema = self.cache.credential_schema(&schema.attr_names)?;
+            let sub_proof_request = build_sub_proof_request(&referents.attrs, &referents.predicates)?;
 
-            let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+            let credential_pub_key = self.cache.credential_pub_key(cred_def)?;
 
             proof_verifier.add_sub_proof_request(&sub_proof_request,
                                                  &credential_schema,
@@ -120,11 +367,19 @@ impl Verifier {
                                                  &credential_pub_key,
                                                  rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
                                                  rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
+
+            if let Some(ref mut telemetry) = telemetry {
+                let referents = referent_names_by_sub_proof.get(&(sub_proof_index as u32)).map(Vec::as_slice).unwrap_or(&[]);
+                telemetry.add_sub_proof(&identifier.cred_def_id, elapsed_micros(sub_proof_start), revocation_duration, referents);
+            }
         }
 
+        let crypto_start = Instant::now();
         let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
 
-        trace!("verify <<< valid: {:?}", valid);
+        if let Some(ref mut telemetry) = telemetry {
+            telemetry.crypto_duration = elapsed_micros(crypto_start);
+        }
 
         Ok(valid)
     }
@@ -139,128 +394,192 @@ impl Verifier {
         Ok(nonce)
     }
 
-    fn _get_revealed_attributes_for_credential(sub_proof_index: usize,
-                                               requested_proof: &RequestedProof,
-                                               proof_req: &ProofRequestPayload) -> IndyResult<Vec<AttributeInfo>> {
-        trace!("_get_revealed_attributes_for_credential >>> sub_proof_index: {:?}, requested_credentials: {:?}, proof_req: {:?}",
-               sub_proof_index, requested_proof, proof_req);
+    // Referents of requested attributes and predicates by index of sub-proof proving them
+    fn _referent_names_by_sub_proof(requested_proof: &RequestedProof) -> HashMap<u32, Vec<&str>> {
+        let mut referents: HashMap<u32, Vec<&str>> = HashMap::new();
 
-        let mut revealed_attrs_for_credential = requested_proof.revealed_attrs
-            .iter()
-            .filter(|&(attr_referent, ref revealed_attr_info)|
-                sub_proof_index == revealed_attr_info.sub_proof_index as usize && proof_req.requested_attributes.contains_key(attr_referent))
-            .map(|(attr_referent, _)|
-                proof_req.requested_attributes[attr_referent].clone())
-            .collect::<Vec<AttributeInfo>>();
-
-        revealed_attrs_for_credential.append(
-            &mut requested_proof.revealed_attr_groups
-                .iter()
-                .filter(|&(attr_referent, ref revealed_attr_info)|
-                    sub_proof_index == revealed_attr_info.sub_proof_index as usize && proof_req.requested_attributes.contains_key(attr_referent))
-                .map(|(attr_referent, _)|
-                    proof_req.requested_attributes[attr_referent].clone())
-                .collect::<Vec<AttributeInfo>>()
-        );
+        let sub_proof_referents = requested_proof.revealed_attrs.iter().map(|(referent, info)| (referent, info.sub_proof_index))
+            .chain(requested_proof.revealed_attr_groups.iter().map(|(referent, info)| (referent, info.sub_proof_index)))
+            .chain(requested_proof.unrevealed_attrs.iter().map(|(referent, info)| (referent, info.sub_proof_index)))
+            .chain(requested_proof.predicates.iter().map(|(referent, info)| (referent, info.sub_proof_index)));
 
-        trace!("_get_revealed_attributes_for_credential <<< revealed_attrs_for_credential: {:?}", revealed_attrs_for_credential);
+        for (referent, sub_proof_index) in sub_proof_referents {
+            referents.entry(sub_proof_index).or_insert_with(Vec::new).push(referent.as_str());
+        }
 
-        Ok(revealed_attrs_for_credential)
+        referents
     }
 
-    fn _get_predicates_for_credential(sub_proof_index: usize,
-                                      requested_proof: &RequestedProof,
-                                      proof_req: &ProofRequestPayload) -> IndyResult<Vec<PredicateInfo>> {
-        trace!("_get_predicates_for_credential >>> sub_proof_index: {:?}, requested_credentials: {:?}, proof_req: {:?}",
-               sub_proof_index, requested_proof, proof_req);
+    // Groups requested attributes and predicates by sub-proof in a single pass. The same credential may
+    // satisfy several referents, so one sub-proof can serve any number of them and the same attribute
+    // may come under different referents.
+    fn _referents_by_sub_proof(sub_proofs_count: usize,
+                               requested_proof: &RequestedProof,
+                               proof_req: &ProofRequestPayload) -> IndyResult<Vec<SubProofReferents>> {
+        trace!("_referents_by_sub_proof >>> sub_proofs_count: {:?}, requested_proof: {:?}, proof_req: {:?}",
+               sub_proofs_count, requested_proof, proof_req);
 
-        let predicates_for_credential = requested_proof.predicates
+        let mut referents_by_sub_proof: Vec<SubProofReferents> = (0..sub_proofs_count).map(|_| SubProofReferents::default()).collect();
+
+        let revealed_attrs = requested_proof.revealed_attrs
             .iter()
-            .filter(|&(predicate_referent, requested_referent)|
-                sub_proof_index == requested_referent.sub_proof_index as usize && proof_req.requested_predicates.contains_key(predicate_referent))
-            .map(|(predicate_referent, _)|
-                proof_req.requested_predicates[predicate_referent].clone())
-            .collect::<Vec<PredicateInfo>>();
+            .map(|(referent, info)| (referent, info.sub_proof_index))
+            .chain(requested_proof.revealed_attr_groups
+                .iter()
+                .map(|(referent, info)| (referent, info.sub_proof_index)));
 
-        trace!("_get_predicates_for_credential <<< predicates_for_credential: {:?}", predicates_for_credential);
+        for (referent, sub_proof_index) in revealed_attrs {
+            if let Some(attr_info) = proof_req.requested_attributes.get(referent) {
+                Verifier::_sub_proof_referents(&mut referents_by_sub_proof, sub_proof_index)?
+                    .attrs.push(attr_info.clone());
+            }
+        }
 
-        Ok(predicates_for_credential)
+        for (referent, info) in requested_proof.predicates.iter() {
+            if let Some(predicate_info) = proof_req.requested_predicates.get(referent) {
+                Verifier::_sub_proof_referents(&mut referents_by_sub_proof, info.sub_proof_index)?
+                    .predicates.push(predicate_info.clone());
+            }
+        }
+
+        trace!("_referents_by_sub_proof <<< referents_by_sub_proof: {:?}", referents_by_sub_proof);
+
+        Ok(referents_by_sub_proof)
+    }
+
+    fn _sub_proof_referents(referents_by_sub_proof: &mut [SubProofReferents], index: u32) -> IndyResult<&mut SubProofReferents> {
+        referents_by_sub_proof
+            .get_mut(index as usize)
+            .ok_or_else(|| err_msg(
+                IndyErrorKind::InvalidStructure,
+                format!("Identifier not found for index: {}", index)
+            ))
     }
 
     fn _compare_attr_from_proof_and_request(proof_req: &ProofRequestPayload,
                                             received_revealed_attrs: &HashMap<String, Identifier>,
                                             received_unrevealed_attrs: &HashMap<String, Identifier>,
                                             received_self_attested_attrs: &HashSet<String>,
-                                            received_predicates: &HashMap<String, Identifier>) -> IndyResult<()> {
-        let requested_attrs: HashSet<String> = proof_req.requested_attributes
+                                            received_predicates: &HashMap<String, Identifier>,
+                                            checks: &mut Checks) {
+        let received_attrs: HashSet<&String> = received_revealed_attrs
             .keys()
-            .cloned()
+            .chain(received_unrevealed_attrs.keys())
+            .chain(received_self_attested_attrs.iter())
             .collect();
 
-        let received_attrs: HashSet<String> = received_revealed_attrs
-            .iter()
-            .chain(received_unrevealed_attrs)
-            .map(|(r, _)| r.to_string())
-            .collect::<HashSet<String>>()
-            .union(&received_self_attested_attrs)
-            .cloned()
-            .collect();
-
-        if requested_attrs != received_attrs {
-            return Err(err_msg(IndyErrorKind::InvalidStructure,
-                               format!("Requested attributes {:?} do not correspond to received {:?}", requested_attrs, received_attrs)));
+        for referent in proof_req.requested_attributes.keys() {
+            if !received_attrs.contains(referent) {
+                checks.add(referent, VerificationCheck::AttributeMismatch,
+                           Err(err_msg(IndyErrorKind::InvalidStructure, format!("Requested attribute \"{}\" not found in proof", referent))));
+            }
         }
 
-        let requested_predicates: HashSet<&String> = proof_req.requested_predicates
-            .keys()
-            .collect();
-
-        let received_predicates_: HashSet<&String> = received_predicates
-            .keys()
-            .collect();
+        for referent in received_attrs {
+            if !proof_req.requested_attributes.contains_key(referent) {
+                checks.add(referent, VerificationCheck::AttributeMismatch,
+                           Err(err_msg(IndyErrorKind::InvalidStructure, format!("Received attribute \"{}\" was not requested", referent))));
+            }
+        }
 
-        if requested_predicates != received_predicates_ {
-            return Err(err_msg(IndyErrorKind::InvalidStructure,
-                               format!("Requested predicates {:?} do not correspond to received {:?}", requested_predicates, received_predicates)));
+        for referent in proof_req.requested_predicates.keys() {
+            if !received_predicates.contains_key(referent) {
+                checks.add(referent, VerificationCheck::PredicateMismatch,
+                           Err(err_msg(IndyErrorKind::InvalidStructure, format!("Requested predicate \"{}\" not found in proof", referent))));
+            }
         }
 
-        Ok(())
+        for referent in received_predicates.keys() {
+            if !proof_req.requested_predicates.contains_key(referent) {
+                checks.add(referent, VerificationCheck::PredicateMismatch,
+                           Err(err_msg(IndyErrorKind::InvalidStructure, format!("Received predicate \"{}\" was not requested", referent))));
+            }
+        }
     }
 
+    // Returns referents proven by credentials without revocation support that are accepted
+    // despite the requested non-revocation interval.
     fn _compare_timestamps_from_proof_and_request(proof_req: &ProofRequestPayload,
                                                   received_revealed_attrs: &HashMap<String, Identifier>,
                                                   received_unrevealed_attrs: &HashMap<String, Identifier>,
                                                   received_self_attested_attrs: &HashSet<String>,
-                                                  received_predicates: &HashMap<String, Identifier>) -> IndyResult<()> {
-        proof_req.requested_attributes
-            .iter()
-            .map(|(referent, info)|
-                Verifier::_validate_timestamp(&received_revealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked)
-                    .or_else(|_| Verifier::_validate_timestamp(&received_unrevealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked))
-                    .or_else(|_| received_self_attested_attrs.get(referent).map(|_| ()).ok_or_else(|| IndyError::from(IndyErrorKind::InvalidStructure)))
-            )
-            .collect::<IndyResult<Vec<()>>>()?;
-
-        proof_req.requested_predicates
-            .iter()
-            .map(|(referent, info)|
-                Verifier::_validate_timestamp(received_predicates, referent, &proof_req.non_revoked, &info.non_revoked))
-            .collect::<IndyResult<Vec<()>>>()?;
+                                                  received_predicates: &HashMap<String, Identifier>,
+                                                  non_revocable_referents: &[String],
+                                                  checks: &mut Checks) -> Vec<String> {
+        let mut non_revocable_accepted = Vec::new();
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            if checks.has_failed(referent) {
+                continue;
+            }
 
-        Ok(())
+            let received = if received_revealed_attrs.contains_key(referent) {
+                received_revealed_attrs
+            } else if received_unrevealed_attrs.contains_key(referent) {
+                received_unrevealed_attrs
+            } else if received_self_attested_attrs.contains(referent) {
+                continue;
+            } else {
+                received_revealed_attrs
+            };
+
+            let res = Verifier::_validate_timestamp(received, referent, &proof_req.non_revoked, &info.non_revoked);
+
+            if res.is_err() && Verifier::_is_non_revocable_accepted(received, referent, non_revocable_referents) {
+                non_revocable_accepted.push(referent.to_string());
+            } else {
+                checks.add(referent, VerificationCheck::RevocationInterval, res);
+            }
+        }
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            if checks.has_failed(referent) {
+                continue;
+            }
+
+            let res = Verifier::_validate_timestamp(received_predicates, referent, &proof_req.non_revoked, &info.non_revoked);
+
+            if res.is_err() && Verifier::_is_non_revocable_accepted(received_predicates, referent, non_revocable_referents) {
+                non_revocable_accepted.push(referent.to_string());
+            } else {
+                checks.add(referent, VerificationCheck::RevocationInterval, res);
+            }
+        }
+
+        non_revocable_accepted.sort();
+        non_revocable_accepted
     }
 
-    fn _validate_timestamp(received_: &HashMap<String, Identifier>, referent: &str,
-                           global_interval: &Option<NonRevocedInterval>, local_interval: &Option<NonRevocedInterval>) -> IndyResult<()> {
-        if get_non_revoc_interval(global_interval, local_interval).is_none() {
-            return Ok(());
+    // Credential without revocation registry can't provide non-revocation proof at all,
+    // while missing timestamp of a revocable one is always an error.
+    fn _is_non_revocable_accepted(received_: &HashMap<String, Identifier>, referent: &str, non_revocable_referents: &[String]) -> bool {
+        if !non_revocable_referents.iter().any(|accepted| accepted == referent) {
+            return false;
         }
 
-        if !received_
-            .get(referent)
-            .map(|attr| attr.timestamp.is_some())
-            .unwrap_or(false) {
-            return Err(IndyError::from(IndyErrorKind::InvalidStructure));
+        received_.get(referent)
+            .map(|identifier| identifier.rev_reg_id.is_none() && identifier.timestamp.is_none())
+            .unwrap_or(false)
+    }
+
+    fn _validate_timestamp(received_: &HashMap<String, Identifier>, referent: &str,
+                           global_interval: &Option<NonRevocedInterval>, local_interval: &Option<NonRevocedInterval>) -> IndyResult<()> {
+        let interval = match get_non_revoc_interval(global_interval, local_interval) {
+            Some(interval) => interval,
+            None => return Ok(())
+        };
+
+        let timestamp = match received_.get(referent).and_then(|attr| attr.timestamp) {
+            Some(timestamp) => timestamp,
+            None => return Err(NonRevocationFailure::TimestampNotProvided { referent: referent.to_string() }.into())
+        };
+
+        // `from` is ignored: the holder proves against the latest registry state at or before `to`,
+        // which can be older than `from` if the registry wasn't updated since
+        if let Some(to) = interval.to {
+            if timestamp > to {
+                return Err(NonRevocationFailure::TimestampAfterInterval { referent: referent.to_string(), timestamp, to }.into());
+            }
         }
 
         Ok(())
@@ -323,38 +642,104 @@ impl Verifier {
     }
 
     fn _verify_revealed_attribute_values(proof_req: &ProofRequestPayload,
-                                         proof: &Proof) -> IndyResult<()> {
+                                         proof: &Proof,
+                                         checks: &mut Checks) {
+        for (attr_referent, attr_info) in proof.requested_proof.revealed_attrs.iter() {
+            if checks.has_failed(attr_referent) {
+                continue;
+            }
+
+            let res = proof_req.requested_attributes.get(attr_referent)
+                .and_then(|info| info.name.as_ref())
+                .ok_or_else(|| IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))
+                .and_then(|attr_name| Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info));
+
+            checks.add(attr_referent, VerificationCheck::AttributeMismatch, res);
+        }
+
+        for (attr_referent, attr_infos) in proof.requested_proof.revealed_attr_groups.iter() {
+            if checks.has_failed(attr_referent) {
+                continue;
+            }
+
+            let res = proof_req.requested_attributes.get(attr_referent)
+                .and_then(|info| info.names.as_ref())
+                .ok_or_else(|| IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))
+                .and_then(|attr_names| {
+                    if attr_infos.values.len() != attr_names.len() {
+                        error!("Proof Revealed Attr Group does not match Proof Request Attribute Group, proof request attrs: {:?}, referent: {:?}, attr_infos: {:?}", proof_req.requested_attributes, attr_referent, attr_infos);
+                        return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"));
+                    }
+                    for attr_name in attr_names {
+                        let attr_info = &attr_infos.values.get(attr_name)
+                            .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))?;
+                        Verifier::_verify_revealed_attribute_value(attr_name, proof, &RevealedAttributeInfo {
+                            sub_proof_index: attr_infos.sub_proof_index,
+                            raw: attr_info.raw.clone(),
+                            encoded: attr_info.encoded.clone()
+                        })?;
+                    }
+                    Ok(())
+                });
+
+            checks.add(attr_referent, VerificationCheck::AttributeMismatch, res);
+        }
+    }
+
+    // Encoded values are checked against the cryptographic proof, raw values are only bound to them by encoding.
+    // Returns referents of revealed attributes with non-canonical encoding.
+    fn _verify_revealed_attribute_encoding(proof: &Proof,
+                                           policy: AttributeEncodingPolicy,
+                                           checks: &mut Checks) -> IndyResult<Vec<String>> {
+        let mut non_canonical_attrs: Vec<String> = Vec::new();
+
         for (attr_referent, attr_info) in proof.requested_proof.revealed_attrs.iter() {
+            if checks.has_failed(attr_referent) {
+                continue;
+            }
 
-            let attr_name = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
-                .name.as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info)?;
+            if !Verifier::_is_canonical_encoding(&attr_info.raw, &attr_info.encoded)? {
+                non_canonical_attrs.push(attr_referent.to_string());
+            }
         }
 
         for (attr_referent, attr_infos) in proof.requested_proof.revealed_attr_groups.iter() {
-            let attr_names = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
-                .names.as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            if attr_infos.values.len() != attr_names.len() {
-                error!("Proof Revealed Attr Group does not match Proof Request Attribute Group, proof request attrs: {:?}, referent: {:?}, attr_infos: {:?}", proof_req.requested_attributes, attr_referent, attr_infos);
-                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))
-            }
-            for attr_name in attr_names {
-                let attr_info = &attr_infos.values.get(attr_name)
-                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))?;
-                Verifier::_verify_revealed_attribute_value(attr_name, proof, &RevealedAttributeInfo {
-                    sub_proof_index: attr_infos.sub_proof_index,
-                    raw: attr_info.raw.clone(),
-                    encoded: attr_info.encoded.clone()
-                })?;
+            if checks.has_failed(attr_referent) {
+                continue;
+            }
+
+            for attr_info in attr_infos.values.values() {
+                if !Verifier::_is_canonical_encoding(&attr_info.raw, &attr_info.encoded)? {
+                    non_canonical_attrs.push(attr_referent.to_string());
+                    break;
+                }
             }
         }
-        Ok(())
+
+        non_canonical_attrs.sort();
+
+        if !non_canonical_attrs.is_empty() {
+            warn!("verify: encoded values of revealed attributes {:?} don't match canonical encoding of raw values", non_canonical_attrs);
+        }
+
+        if policy == AttributeEncodingPolicy::Fail {
+            for attr_referent in non_canonical_attrs.iter() {
+                checks.add(attr_referent, VerificationCheck::AttributeEncoding,
+                           Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                                   format!("Encoded value of attribute with referent \"{}\" doesn't match canonical encoding of raw value", attr_referent))));
+            }
+        }
+
+        Ok(non_canonical_attrs)
+    }
+
+    fn _is_canonical_encoding(raw: &str, encoded: &str) -> IndyResult<bool> {
+        // encoded value which is not a number is rejected as attribute mismatch by cryptographic proof check
+        if BigNumber::from_dec(encoded).is_err() {
+            return Ok(true);
+        }
+
+        is_canonical_encoding(&AttributeValues { raw: raw.to_string(), encoded: encoded.to_string() })
     }
 
     fn _verify_revealed_attribute_value(attr_name: &str,
@@ -385,7 +770,8 @@ impl Verifier {
                                       received_revealed_attrs: &HashMap<String, Identifier>,
                                       received_unrevealed_attrs: &HashMap<String, Identifier>,
                                       received_predicates: &HashMap<String, Identifier>,
-                                      self_attested_attrs: &HashSet<String>) -> IndyResult<()> {
+                                      self_attested_attrs: &HashSet<String>,
+                                      checks: &mut Checks) {
         let proof_attr_identifiers: HashMap<String, Identifier> = received_revealed_attrs
             .iter()
             .chain(received_unrevealed_attrs)
@@ -398,71 +784,101 @@ impl Verifier {
             .map(|(referent, info)| (referent.to_string(), info.clone()))
             .collect();
 
-        for (referent, info) in requested_attrs.clone() {
-            if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, &proof_attr_identifiers)?;
-
-                let name_value_map: HashMap<String, Option<&str>> = if let Some(name) = info.name {
-                    let mut map = HashMap::new();
-                    map.insert(name.clone(), requested_proof.revealed_attrs.get(&referent).map(|attr| attr.raw.as_str()));
-                    map
-                } else if let Some(names) = info.names {
-                    let mut map = HashMap::new();
-                    let attrs = requested_proof.revealed_attr_groups.get(&referent)
-                        .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
-                    for name in names {
-                        let val = attrs.values.get(&name).map(|attr| attr.raw.as_str());
-                        map.insert(name, val);
-                    }
-                    map
-                } else {
-                    error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
-                    return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
-                };
-
-                Verifier::_do_process_operator(&name_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
+        for (referent, info) in requested_attrs.iter() {
+            if checks.has_failed(referent) {
+                continue;
             }
+
+            checks.add(referent, VerificationCheck::Restriction,
+                       Verifier::_verify_attr_restrictions(proof_req, requested_proof, &proof_attr_identifiers, referent, info));
         }
 
         for (referent, info) in proof_req.requested_predicates.iter() {
-            if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
-
-                // start with the predicate requested attribute, which is un-revealed
-                let mut attr_value_map = HashMap::new();
-                attr_value_map.insert(info.name.to_string(), None);
-
-                // include any revealed attributes for the same credential (based on sub_proof_index)
-                let pred_sub_proof_index = requested_proof.predicates.get(referent).unwrap().sub_proof_index;
-                for attr_referent in requested_proof.revealed_attrs.keys() {
-                    let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        let attr_name = requested_attrs.get(attr_referent).unwrap().name.clone();
-                        if let Some(name) = attr_name {
-                            attr_value_map.insert(name, Some(attr_info.raw.as_str()));
-                        }
+            if checks.has_failed(referent) {
+                continue;
+            }
+
+            checks.add(referent, VerificationCheck::Restriction,
+                       Verifier::_verify_predicate_restrictions(requested_proof, received_predicates, &requested_attrs, referent, info));
+        }
+    }
+
+    fn _verify_attr_restrictions(proof_req: &ProofRequestPayload,
+                                 requested_proof: &RequestedProof,
+                                 proof_attr_identifiers: &HashMap<String, Identifier>,
+                                 referent: &str,
+                                 info: &AttributeInfo) -> IndyResult<()> {
+        if let Some(ref query) = info.restrictions {
+            let filter = Verifier::_gather_filter_info(&referent, &proof_attr_identifiers)?;
+
+            let name_value_map: HashMap<String, Option<&str>> = if let Some(ref name) = info.name {
+                let mut map = HashMap::new();
+                map.insert(name.clone(), requested_proof.revealed_attrs.get(referent).map(|attr| attr.raw.as_str()));
+                map
+            } else if let Some(ref names) = info.names {
+                let mut map = HashMap::new();
+                let attrs = requested_proof.revealed_attr_groups.get(referent)
+                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
+                for name in names {
+                    let val = attrs.values.get(name).map(|attr| attr.raw.as_str());
+                    map.insert(name.clone(), val);
+                }
+                map
+            } else {
+                error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
+                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
+            };
+
+            Verifier::_do_process_operator(&name_value_map, &query, &filter)
+                .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
+        }
+
+        Ok(())
+    }
+
+    fn _verify_predicate_restrictions(requested_proof: &RequestedProof,
+                                      received_predicates: &HashMap<String, Identifier>,
+                                      requested_attrs: &HashMap<String, AttributeInfo>,
+                                      referent: &str,
+                                      info: &PredicateInfo) -> IndyResult<()> {
+        if let Some(ref query) = info.restrictions {
+            let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
+
+            // start with the predicate requested attribute, which is un-revealed
+            let mut attr_value_map = HashMap::new();
+            attr_value_map.insert(info.name.to_string(), None);
+
+            // include any revealed attributes for the same credential (based on sub_proof_index)
+            let pred_sub_proof_index = requested_proof.predicates.get(referent)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Predicate \"{}\" not found in proof", referent)))?
+                .sub_proof_index;
+            for attr_referent in requested_proof.revealed_attrs.keys() {
+                let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
+                let attr_sub_proof_index = attr_info.sub_proof_index;
+                if pred_sub_proof_index == attr_sub_proof_index {
+                    let attr_name = requested_attrs.get(attr_referent).and_then(|info| info.name.clone());
+                    if let Some(name) = attr_name {
+                        attr_value_map.insert(name, Some(attr_info.raw.as_str()));
                     }
                 }
-                for attr_referent in requested_proof.revealed_attr_groups.keys() {
-                    let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        for name in attr_info.values.keys() {
-                            let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
-                            attr_value_map.insert(name.clone(), Some(raw_val.clone()));
-                        }
+            }
+            for attr_referent in requested_proof.revealed_attr_groups.keys() {
+                let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
+                let attr_sub_proof_index = attr_info.sub_proof_index;
+                if pred_sub_proof_index == attr_sub_proof_index {
+                    for name in attr_info.values.keys() {
+                        let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
+                        attr_value_map.insert(name.clone(), Some(raw_val.clone()));
                     }
                 }
+            }
 
-                Verifier::_do_process_operator(&attr_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
+            Verifier::_do_process_operator(&attr_value_map, &query, &filter)
+                .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
 
-                // old style :-/ which fails for attribute restrictions on predicates
-                //Verifier::_process_operator(&info.name, &query, &filter, None)
-                //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
-            }
+            // old style :-/ which fails for attribute restrictions on predicates
+            //Verifier::_process_operator(&info.name, &query, &filter, None)
+            //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
         }
 
         Ok(())
@@ -520,7 +936,7 @@ impl Verifier {
                     .map_err(|err| err.extend(format!("$eq operator validation failed for tag: \"{}\", value: \"{}\"", tag_name, tag_value)))
             }
             Query::Neq(ref tag_name, ref tag_value) => {
-                if Verifier::_process_filter(attr_value_map, &tag_name, &tag_value, filter).is_err() {
+                if Verifier::_is_rejected(Verifier::_process_filter(attr_value_map, &tag_name, &tag_value, filter))? {
                     Ok(())
                 } else {
                     Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
@@ -528,9 +944,13 @@ impl Verifier {
                 }
             }
             Query::In(ref tag_name, ref tag_values) => {
-                let res = tag_values
-                    .iter()
-                    .any(|val| Verifier::_process_filter(attr_value_map, &tag_name, &val, filter).is_ok());
+                // every value is checked, so malformed ones fail the restriction even if another one matches
+                let mut res = false;
+                for val in tag_values {
+                    if !Verifier::_is_rejected(Verifier::_process_filter(attr_value_map, &tag_name, &val, filter))? {
+                        res = true;
+                    }
+                }
                 if res {
                     Ok(())
                 } else {
@@ -538,6 +958,10 @@ impl Verifier {
                                             format!("$in operator validation failed for tag: \"{}\", values \"{:?}\".", tag_name, tag_values)))
                 }
             }
+            Query::Like(ref tag_name, ref pattern) => {
+                Verifier::_process_like_filter(attr_value_map, &tag_name, &pattern, filter)
+                    .map_err(|err| err.extend(format!("$like operator validation failed for tag: \"{}\", pattern: \"{}\"", tag_name, pattern)))
+            }
             Query::And(ref operators) => {
                 operators
                     .iter()
@@ -547,9 +971,13 @@ impl Verifier {
                     .map_err(|err| err.extend("$and operator validation failed."))
             }
             Query::Or(ref operators) => {
-                let res = operators
-                    .iter()
-                    .any(|op| Verifier::_do_process_operator(attr_value_map, op, filter).is_ok());
+                // every branch is evaluated, so errors of malformed ones surface even if another one matches
+                let mut res = false;
+                for op in operators {
+                    if !Verifier::_is_rejected(Verifier::_do_process_operator(attr_value_map, op, filter))? {
+                        res = true;
+                    }
+                }
                 if res {
                     Ok(())
                 } else {
@@ -557,13 +985,45 @@ impl Verifier {
                 }
             }
             Query::Not(ref operator) => {
-                if Verifier::_do_process_operator(attr_value_map, &*operator, filter).is_err() {
+                if Verifier::_is_rejected(Verifier::_do_process_operator(attr_value_map, &*operator, filter))? {
                     Ok(())
                 } else {
                     Err(IndyError::from_msg(IndyErrorKind::ProofRejected, "$not operator validation failed. All conditions were passed."))
                 }
             }
-            _ => Err(IndyError::from_msg(IndyErrorKind::ProofRejected, "unsupported operator"))
+            _ => Err(IndyError::from_msg(IndyErrorKind::ProofRejected, "unsupported operator: $gt, $gte, $lt and $lte can't be used in restrictions"))
+        }
+    }
+
+    // Negated conditions must not turn malformed restrictions (unknown tags) into a match,
+    // so only ProofRejected counts as a failed condition and other errors are propagated.
+    fn _is_rejected(res: IndyResult<()>) -> IndyResult<bool> {
+        match res {
+            Ok(()) => Ok(false),
+            Err(ref err) if err.kind() == IndyErrorKind::ProofRejected => Ok(true),
+            Err(err) => Err(err)
+        }
+    }
+
+    fn _process_like_filter(attr_value_map: &HashMap<String, Option<&str>>,
+                            tag: &str,
+                            pattern: &str,
+                            filter: &Filter) -> IndyResult<()> {
+        let value = match tag {
+            "schema_id" => filter.schema_id.as_str(),
+            "schema_issuer_did" => filter.schema_issuer_did.as_str(),
+            "schema_name" => filter.schema_name.as_str(),
+            "schema_version" => filter.schema_version.as_str(),
+            "cred_def_id" => filter.cred_def_id.as_str(),
+            "issuer_did" => filter.issuer_did.as_str(),
+            x if Verifier::_is_attr_with_revealed_value(x, attr_value_map) => Verifier::_get_revealed_value(x, attr_value_map)?,
+            _ => return Err(err_msg(IndyErrorKind::InvalidStructure, "Unknown Filter Type"))
+        };
+
+        if wql::like(pattern, value) {
+            Ok(())
+        } else {
+            Err(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("\"{}\" value doesn't match: pattern: \"{}\", actual: \"{}\"", tag, pattern, value)))
         }
     }
 
@@ -620,6 +1080,16 @@ impl Verifier {
     }
 
     fn _check_internal_tag_revealed_value(key: &str, tag_value: &str, attr_value_map: &HashMap<String, Option<&str>>) -> IndyResult<()> {
+        let revealed_value = Verifier::_get_revealed_value(key, attr_value_map)?;
+
+        if revealed_value != tag_value {
+            return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                           format!("\"{}\" values are different: expected: \"{}\", actual: \"{}\"", key, tag_value, revealed_value)));
+        }
+        Ok(())
+    }
+
+    fn _get_revealed_value<'a>(key: &str, attr_value_map: &HashMap<String, Option<&'a str>>) -> IndyResult<&'a str> {
         let captures = VALUE_TAG_MATCHER.captures(key)
             .ok_or(IndyError::from_msg(IndyErrorKind::InvalidState, format!("Attribute name became unparseable")))?;
 
@@ -633,16 +1103,11 @@ impl Verifier {
                 .iter()
                 .find(|(key, _)| attr_common_view(key)  == attr_common_view(attr_name));
 
-        if let Some((_key, Some(revealed_value))) = revealed_value {
-            if *revealed_value != tag_value {
-                return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
-                                               format!("\"{}\" values are different: expected: \"{}\", actual: \"{}\"", key, tag_value, revealed_value)));
-            }
-        } else {
-            return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
-                                           format!("Revealed value hasn't been find by key: expected key: \"{}\", attr_value_map: \"{:?}\"", key, attr_value_map)));
+        match revealed_value {
+            Some((_key, Some(revealed_value))) => Ok(*revealed_value),
+            _ => Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                         format!("Revealed value hasn't been find by key: expected key: \"{}\", attr_value_map: \"{:?}\"", key, attr_value_map)))
         }
-        Ok(())
     }
 
     fn _is_attr_marker_operator(key: &str) -> bool {
@@ -912,6 +1377,44 @@ mod tests {
         assert!(Verifier::_process_operator("zip", &op, &filter, None).is_err());
     }
 
+    #[test]
+    fn test_process_op_like() {
+        let filter = filter();
+
+        let mut op = Query::Like(schema_name_tag(), "Schema%".to_string());
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        op = Query::Like(schema_version_tag(), "1._.3".to_string());
+        Verifier::_process_operator("zip", &op, &filter, None).unwrap();
+
+        op = Query::Like(cred_def_id_tag(), "%6".to_string());
+        assert_kind!(IndyErrorKind::ProofRejected, Verifier::_process_operator("zip", &op, &filter, None));
+
+        op = Query::Like(attr_tag_value(), "12%".to_string());
+        Verifier::_process_operator("zip", &op, &filter, Some("12345")).unwrap();
+        assert_kind!(IndyErrorKind::ProofRejected, Verifier::_process_operator("zip", &op, &filter, Some("54321")));
+    }
+
+    #[test]
+    fn test_process_op_negation_fails_for_unknown_tag() {
+        let filter = filter();
+
+        let mut op = Query::Not(Box::new(Query::Eq(bad_attr_tag(), "1".to_string())));
+        assert_kind!(IndyErrorKind::InvalidStructure, Verifier::_process_operator("zip", &op, &filter, None));
+
+        op = Query::Neq(bad_attr_tag(), "1".to_string());
+        assert_kind!(IndyErrorKind::InvalidStructure, Verifier::_process_operator("zip", &op, &filter, None));
+
+        op = Query::Or(vec![
+            Query::Eq(schema_id_tag(), SCHEMA_ID.to_string()),
+            Query::Eq(bad_attr_tag(), "1".to_string())
+        ]);
+        assert_kind!(IndyErrorKind::InvalidStructure, Verifier::_process_operator("zip", &op, &filter, None));
+
+        op = Query::In(bad_attr_tag(), vec!["1".to_string()]);
+        assert_kind!(IndyErrorKind::InvalidStructure, Verifier::_process_operator("zip", &op, &filter, None));
+    }
+
     #[test]
     fn test_process_op_eq_revealed_value() {
         let filter = filter();
@@ -961,6 +1464,7 @@ mod tests {
         Verifier::_validate_timestamp(&_received(), "referent_1", &None, &None).unwrap();
         Verifier::_validate_timestamp(&_received(), "referent_1", &Some(_interval()), &None).unwrap();
         Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(_interval())).unwrap();
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(NonRevocedInterval { from: Some(1234), to: None })).unwrap();
     }
 
     #[test]
@@ -969,4 +1473,171 @@ mod tests {
         Verifier::_validate_timestamp(&_received(), "referent_2", &None, &Some(_interval())).unwrap_err();
         Verifier::_validate_timestamp(&_received(), "referent_3", &None, &Some(_interval())).unwrap_err();
     }
+
+    #[test]
+    fn validate_timestamp_not_work_for_timestamp_after_interval() {
+        let res = Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(NonRevocedInterval { from: None, to: Some(1000) }));
+        assert_kind!(IndyErrorKind::ProofRejected, res);
+    }
+
+    #[test]
+    fn validate_timestamp_works_for_timestamp_before_interval_from() {
+        Verifier::_validate_timestamp(&_received(), "referent_1", &None, &Some(NonRevocedInterval { from: Some(2000), to: Some(3000) })).unwrap();
+        Verifier::_validate_timestamp(&_received(), "referent_1", &Some(_interval()), &Some(NonRevocedInterval { from: Some(2000), to: None })).unwrap();
+    }
+
+    fn _proof_req_with_interval() -> ProofRequestPayload {
+        serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "0.1",
+            "requested_attributes": {
+                "attr1_referent": {"name": "name"},
+                "attr2_referent": {"name": "sex"}
+            },
+            "requested_predicates": {
+                "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18}
+            },
+            "non_revoked": {"to": 1234}
+        })).unwrap()
+    }
+
+    fn _non_revocable_identifier() -> Identifier {
+        Identifier { timestamp: None, schema_id: SchemaId(String::new()), cred_def_id: CredentialDefinitionId(String::new()), rev_reg_id: None }
+    }
+
+    #[test]
+    fn compare_timestamps_accepts_non_revocable_credentials_for_listed_referents() {
+        let proof_req = _proof_req_with_interval();
+
+        let mut received_attrs = HashMap::new();
+        received_attrs.insert("attr1_referent".to_string(), _non_revocable_identifier());
+        received_attrs.insert("attr2_referent".to_string(), _received().remove("referent_2").unwrap());
+
+        let mut received_predicates = HashMap::new();
+        received_predicates.insert("predicate1_referent".to_string(), _non_revocable_identifier());
+
+        let mut checks = Checks::new(&proof_req);
+        let accepted = Verifier::_compare_timestamps_from_proof_and_request(&proof_req,
+                                                                            &received_attrs,
+                                                                            &HashMap::new(),
+                                                                            &HashSet::new(),
+                                                                            &received_predicates,
+                                                                            &["attr1_referent".to_string(), "attr2_referent".to_string()],
+                                                                            &mut checks);
+
+        // revocable credential without timestamp and non-listed referent are still rejected
+        assert_eq!(vec!["attr1_referent".to_string()], accepted);
+        assert!(!checks.has_failed("attr1_referent"));
+        assert!(checks.has_failed("attr2_referent"));
+        assert!(checks.has_failed("predicate1_referent"));
+    }
+
+    #[test]
+    fn non_revocation_failure_describes_reason() {
+        let err: IndyError = NonRevocationFailure::TimestampAfterInterval { referent: "referent_1".to_string(), timestamp: 1234, to: 1000 }.into();
+        assert_eq!(IndyErrorKind::ProofRejected, err.kind());
+        assert!(err.to_string().contains(r#"{"reason":"timestamp_after_interval","referent":"referent_1","timestamp":1234,"to":1000}"#));
+
+        let err: IndyError = NonRevocationFailure::TimestampNotProvided { referent: "referent_2".to_string() }.into();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+    }
+
+    fn _proof_req_for_single_credential() -> ProofRequestPayload {
+        serde_json::from_value(json!({
+            "nonce": "123432421212",
+            "name": "proof_req_1",
+            "version": "0.1",
+            "requested_attributes": {
+                "attr1_referent": {"name": "name"},
+                "attr2_referent": {"name": "name", "restrictions": {"cred_def_id": CRED_DEF_ID}},
+                "attr3_referent": {"names": ["name", "sex"]},
+                "attr4_referent": {"name": "height"}
+            },
+            "requested_predicates": {
+                "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18},
+                "predicate2_referent": {"name": "age", "p_type": "<=", "p_value": 60}
+            }
+        })).unwrap()
+    }
+
+    fn _requested_proof(sub_proof_index: u32) -> RequestedProof {
+        serde_json::from_value(json!({
+            "revealed_attrs": {
+                "attr1_referent": {"sub_proof_index": sub_proof_index, "raw": "Alex", "encoded": "1139481716457488690172217916278103335"},
+                "attr2_referent": {"sub_proof_index": sub_proof_index, "raw": "Alex", "encoded": "1139481716457488690172217916278103335"}
+            },
+            "revealed_attr_groups": {
+                "attr3_referent": {"sub_proof_index": sub_proof_index, "values": {
+                    "name": {"raw": "Alex", "encoded": "1139481716457488690172217916278103335"},
+                    "sex": {"raw": "male", "encoded": "5944657099558967239210949258394887428692050081607692519917050011144233115103"}
+                }}
+            },
+            "unrevealed_attrs": {
+                "attr4_referent": {"sub_proof_index": sub_proof_index}
+            },
+            "predicates": {
+                "predicate1_referent": {"sub_proof_index": sub_proof_index},
+                "predicate2_referent": {"sub_proof_index": sub_proof_index}
+            }
+        })).unwrap()
+    }
+
+    #[test]
+    fn referents_by_sub_proof_works_for_multiple_referents_from_single_credential() {
+        let referents = Verifier::_referents_by_sub_proof(1, &_requested_proof(0), &_proof_req_for_single_credential()).unwrap();
+
+        assert_eq!(1, referents.len());
+        assert_eq!(3, referents[0].attrs.len());
+        assert_eq!(2, referents[0].predicates.len());
+
+        build_sub_proof_request(&referents[0].attrs, &referents[0].predicates).unwrap();
+    }
+
+    #[test]
+    fn referents_by_sub_proof_works_for_sub_proof_without_referents() {
+        let referents = Verifier::_referents_by_sub_proof(2, &_requested_proof(1), &_proof_req_for_single_credential()).unwrap();
+
+        assert_eq!(2, referents.len());
+        assert!(referents[0].attrs.is_empty());
+        assert!(referents[0].predicates.is_empty());
+        assert_eq!(3, referents[1].attrs.len());
+        assert_eq!(2, referents[1].predicates.len());
+    }
+
+    #[test]
+    fn revocation_unchecked_referents_works_for_one_unavailable_registry() {
+        let identifier = |rev_reg_id: &str| Identifier {
+            timestamp: Some(1234),
+            schema_id: SchemaId(String::new()),
+            cred_def_id: CredentialDefinitionId(String::new()),
+            rev_reg_id: Some(RevocationRegistryId(rev_reg_id.to_string())),
+        };
+        let identifiers = vec![identifier("rev_reg_1"), identifier("rev_reg_2")];
+
+        let requested_proof: RequestedProof = serde_json::from_value(json!({
+            "revealed_attrs": {
+                "attr1_referent": {"sub_proof_index": 0, "raw": "Alex", "encoded": "1139481716457488690172217916278103335"},
+                "attr2_referent": {"sub_proof_index": 1, "raw": "Alex", "encoded": "1139481716457488690172217916278103335"}
+            },
+            "unrevealed_attrs": {
+                "attr3_referent": {"sub_proof_index": 1}
+            },
+            "predicates": {
+                "predicate1_referent": {"sub_proof_index": 1}
+            }
+        })).unwrap();
+
+        let referents = Verifier::_revocation_unchecked_referents(&identifiers, &requested_proof, &["rev_reg_2".to_string()]);
+        assert_eq!(vec!["attr2_referent", "attr3_referent", "predicate1_referent"], referents);
+
+        let referents = Verifier::_revocation_unchecked_referents(&identifiers, &requested_proof, &[]);
+        assert!(referents.is_empty());
+    }
+
+    #[test]
+    fn referents_by_sub_proof_fails_for_unknown_sub_proof_index() {
+        let res = Verifier::_referents_by_sub_proof(1, &_requested_proof(1), &_proof_req_for_single_credential());
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
 }
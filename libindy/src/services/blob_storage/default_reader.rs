@@ -14,6 +14,15 @@ pub struct DefaultReader {
     hash: Vec<u8>,
 }
 
+impl DefaultReader {
+    pub(super) fn new(file: File, hash: &[u8]) -> DefaultReader {
+        DefaultReader {
+            file,
+            hash: hash.to_owned(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct DefaultReaderConfig {
     base_dir: String,
@@ -33,10 +42,7 @@ impl Reader for DefaultReaderConfig {
         let mut path = PathBuf::from(&self.base_dir);
         path.push(hash.to_base58());
         let file = File::open(path)?;
-        Ok(Box::new(DefaultReader {
-            file,
-            hash: hash.to_owned()
-        }))
+        Ok(Box::new(DefaultReader::new(file, hash)))
     }
 }
 
@@ -0,0 +1,300 @@
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use rust_base58::ToBase58;
+use serde_json;
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::hash::Hash;
+use indy_utils::sequence;
+use crate::utils::environment;
+
+use super::{ReadableBlob, Reader, ReaderType};
+use super::default_reader::DefaultReader;
+
+const DEFAULT_TIMEOUT: u64 = 60;
+const DEFAULT_MAX_TAILS_SIZE: u64 = 1024 * 1024 * 1024;
+
+// Fetches tails files by `tailsLocation` url and caches them locally.
+// Cached files are named by base58 of tails hash as the default reader expects, so cache dir
+// can be shared with the default reader.
+#[derive(Serialize, Deserialize)]
+struct HttpReaderConfig {
+    #[serde(default = "default_cache_dir")]
+    cache_dir: String,
+    // Max total size of cached tails files in bytes. The least recently downloaded files are evicted first.
+    max_cache_size: Option<u64>,
+    #[serde(default = "default_timeout")]
+    timeout: u64,
+    // Max size of single tails file in bytes. Download is aborted as soon as it is exceeded.
+    #[serde(default = "default_max_tails_size")]
+    max_tails_size: u64,
+}
+
+fn default_cache_dir() -> String {
+    environment::indy_home_path().join("tails_cache").to_string_lossy().into_owned()
+}
+
+fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT
+}
+
+fn default_max_tails_size() -> u64 {
+    DEFAULT_MAX_TAILS_SIZE
+}
+
+impl ReaderType for HttpReaderType {
+    fn open(&self, config: &str) -> IndyResult<Box<dyn Reader>> {
+        let config: HttpReaderConfig = serde_json::from_str(config)
+            .to_indy(IndyErrorKind::InvalidStructure, "Can't deserialize HttpReaderConfig")?;
+
+        Ok(Box::new(config))
+    }
+}
+
+impl Reader for HttpReaderConfig {
+    fn open(&self, hash: &[u8], location: &str) -> IndyResult<Box<dyn ReadableBlob>> {
+        let cache_dir = PathBuf::from(&self.cache_dir);
+
+        let mut path = cache_dir.clone();
+        path.push(hash.to_base58());
+
+        if !path.exists() {
+            fs::create_dir_all(&cache_dir)
+                .map_err(map_err_trace!(format!("path: {:?}", cache_dir)))?;
+
+            self._download(hash, location, &path)?;
+
+            if let Some(max_cache_size) = self.max_cache_size {
+                _evict(&cache_dir, &path, max_cache_size)?;
+            }
+        }
+
+        let file = File::open(path)?;
+        Ok(Box::new(DefaultReader::new(file, hash)))
+    }
+}
+
+impl HttpReaderConfig {
+    // Downloads to temporary file in the cache dir that is renamed only after the hash is verified,
+    // so concurrent readers never see partially downloaded or tampered tails.
+    fn _download(&self, hash: &[u8], location: &str, path: &Path) -> IndyResult<()> {
+        trace!("_download >>> location: {:?}, path: {:?}", location, path);
+
+        if !location.starts_with("http://") && !location.starts_with("https://") {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Tails location isn't http(s) url: {}", location)));
+        }
+
+        let response = ureq::get(location)
+            .timeout(Duration::from_secs(self.timeout))
+            .call();
+
+        if let Some(err) = response.synthetic_error() {
+            return Err(err_msg(IndyErrorKind::IOError, format!("Can't fetch tails file from {}: {}", location, err)));
+        }
+
+        if !response.ok() {
+            return Err(err_msg(IndyErrorKind::IOError, format!("Can't fetch tails file from {}: status {}", location, response.status())));
+        }
+
+        // Content-Length is only a hint of the server, so it can only lower the limit
+        let max_size = match response.header("Content-Length").and_then(|len| len.parse::<u64>().ok()) {
+            Some(len) if len > self.max_tails_size =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Tails file at {} exceeds max size: {} bytes", location, len))),
+            Some(len) => len,
+            None => self.max_tails_size,
+        };
+
+        let tmp_path = path.with_extension(format!("{}.tmp", sequence::get_next_id()));
+
+        let res = _write_verified(response.into_reader(), hash, max_size, &tmp_path)
+            .and_then(|_| fs::rename(&tmp_path, path).map_err(IndyError::from));
+
+        if res.is_err() {
+            fs::remove_file(&tmp_path).ok();
+        }
+
+        trace!("_download <<< res: {:?}", res);
+        res
+    }
+}
+
+// Reads at most `max_size` + 1 bytes, so oversized or endless responses are rejected without being stored.
+fn _write_verified<R: Read>(reader: R, hash: &[u8], max_size: u64, path: &Path) -> IndyResult<()> {
+    let mut reader = reader.take(max_size.saturating_add(1));
+    let mut file = File::create(path)?;
+    let mut hasher = Hash::new_context()?;
+    let mut buf = [0u8; 8192];
+    let mut size: u64 = 0;
+
+    loop {
+        let sz = reader.read(&mut buf)?;
+
+        if sz == 0 {
+            break;
+        }
+
+        size += sz as u64;
+
+        if size > max_size {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Fetched tails file exceeds max size: {} bytes", max_size)));
+        }
+
+        hasher.update(&buf[0..sz])?;
+        file.write_all(&buf[0..sz])?;
+    }
+
+    file.flush()?;
+
+    if hasher.finish()?.to_vec() != hash {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Fetched tails file doesn't match tails hash"));
+    }
+
+    Ok(())
+}
+
+fn _evict(cache_dir: &Path, keep: &Path, max_cache_size: u64) -> IndyResult<()> {
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+
+    for entry in fs::read_dir(cache_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+
+        if !metadata.is_file() || path.extension().is_some() {
+            continue;
+        }
+
+        files.push((metadata.modified()?, metadata.len(), path));
+    }
+
+    let mut total_size: u64 = files.iter().map(|&(_, len, _)| len).sum();
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (_, len, path) in files {
+        if total_size <= max_cache_size {
+            break;
+        }
+
+        if path == keep {
+            continue;
+        }
+
+        fs::remove_file(&path)
+            .map_err(map_err_trace!(format!("path: {:?}", path)))?;
+
+        total_size -= len;
+    }
+
+    Ok(())
+}
+
+pub struct HttpReaderType {}
+
+impl HttpReaderType {
+    pub fn new() -> Self {
+        HttpReaderType {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _config(cache_dir: &Path, max_cache_size: Option<u64>) -> HttpReaderConfig {
+        HttpReaderConfig {
+            cache_dir: cache_dir.to_string_lossy().into_owned(),
+            max_cache_size,
+            timeout: 1,
+            max_tails_size: DEFAULT_MAX_TAILS_SIZE,
+        }
+    }
+
+    fn _cache_dir(name: &str) -> PathBuf {
+        let path = environment::tmp_file_path(name);
+        fs::remove_dir_all(&path).ok();
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn http_reader_open_works_for_cached_tails() {
+        let cache_dir = _cache_dir("http_reader_open_works_for_cached_tails");
+
+        let content = b"tails";
+        let hash = indy_utils::crypto::hash::hash(content).unwrap();
+        fs::write(cache_dir.join(hash.to_base58()), content).unwrap();
+
+        let mut blob = _config(&cache_dir, None).open(&hash, "http://127.0.0.1:1/tails").unwrap();
+
+        assert_eq!(content.to_vec(), blob.read(5, 0).unwrap());
+        assert!(blob.verify().unwrap());
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn http_reader_open_fails_for_not_http_location() {
+        let cache_dir = _cache_dir("http_reader_open_fails_for_not_http_location");
+
+        let res = _config(&cache_dir, None).open(&[1, 2, 3], "/tmp/tails");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn write_verified_fails_for_hash_mismatch() {
+        let cache_dir = _cache_dir("write_verified_fails_for_hash_mismatch");
+
+        let res = _write_verified(&b"tails"[..], &[1, 2, 3], DEFAULT_MAX_TAILS_SIZE, &cache_dir.join("tails.tmp"));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn write_verified_works_for_max_size() {
+        let cache_dir = _cache_dir("write_verified_works_for_max_size");
+
+        let content = b"tails";
+        let hash = indy_utils::crypto::hash::hash(content).unwrap();
+
+        _write_verified(&content[..], &hash, 5, &cache_dir.join("tails.tmp")).unwrap();
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn write_verified_fails_for_exceeded_max_size() {
+        let cache_dir = _cache_dir("write_verified_fails_for_exceeded_max_size");
+
+        // Endless stream must be cut right after the limit
+        let res = _write_verified(std::io::repeat(0), &[1, 2, 3], 10, &cache_dir.join("tails.tmp"));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+        assert!(fs::metadata(cache_dir.join("tails.tmp")).unwrap().len() <= 10);
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn evict_works() {
+        let cache_dir = _cache_dir("http_reader_evict_works");
+
+        let old = cache_dir.join("old");
+        let kept = cache_dir.join("kept");
+        fs::write(&old, b"0123456789").unwrap();
+        fs::write(&kept, b"0123456789").unwrap();
+
+        _evict(&cache_dir, &kept, 15).unwrap();
+
+        assert!(!old.exists());
+        assert!(kept.exists());
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
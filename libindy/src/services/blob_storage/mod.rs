@@ -1,5 +1,5 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 use indy_api_types::errors::prelude::*;
 use indy_utils::sequence;
@@ -9,42 +9,44 @@ use sha2::digest::{FixedOutput, Update};
 
 mod default_writer;
 mod default_reader;
+#[cfg(feature = "http_tails")]
+mod http_reader;
 
-trait WriterType {
+trait WriterType: Send {
     fn open(&self, config: &str) -> IndyResult<Box<dyn Writer>>;
 }
 
-trait Writer {
+trait Writer: Send {
     fn create(&self, id: i32) -> IndyResult<Box<dyn WritableBlob>>;
 }
 
-trait WritableBlob {
+trait WritableBlob: Send {
     fn append(&mut self, bytes: &[u8]) -> IndyResult<usize>;
     fn finalize(&mut self, hash: &[u8]) -> IndyResult<String>;
 }
 
-trait ReaderType {
+trait ReaderType: Send {
     fn open(&self, config: &str) -> IndyResult<Box<dyn Reader>>;
 }
 
-trait Reader {
+trait Reader: Send {
     fn open(&self, hash: &[u8], location: &str) -> IndyResult<Box<dyn ReadableBlob>>;
 }
 
-trait ReadableBlob {
+trait ReadableBlob: Send {
     fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>>;
     fn verify(&mut self) -> IndyResult<bool>;
     fn close(&self) -> IndyResult<()>;
 }
 
 pub struct BlobStorageService {
-    writer_types: RefCell<HashMap<String, Box<dyn WriterType>>>,
-    writer_configs: RefCell<HashMap<i32, Box<dyn Writer>>>,
-    writer_blobs: RefCell<HashMap<i32, (Box<dyn WritableBlob>, Sha256)>>,
+    writer_types: Mutex<HashMap<String, Box<dyn WriterType>>>,
+    writer_configs: Mutex<HashMap<i32, Box<dyn Writer>>>,
+    writer_blobs: Mutex<HashMap<i32, (Box<dyn WritableBlob>, Sha256)>>,
 
-    reader_types: RefCell<HashMap<String, Box<dyn ReaderType>>>,
-    reader_configs: RefCell<HashMap<i32, Box<dyn Reader>>>,
-    reader_blobs: RefCell<HashMap<i32, Box<dyn ReadableBlob>>>,
+    reader_types: Mutex<HashMap<String, Box<dyn ReaderType>>>,
+    reader_configs: Mutex<HashMap<i32, Box<dyn Reader>>>,
+    reader_blobs: Mutex<HashMap<i32, Box<dyn ReadableBlob>>>,
 }
 
 impl BlobStorageService {
@@ -53,15 +55,17 @@ impl BlobStorageService {
         writer_types.insert("default".to_owned(), Box::new(default_writer::DefaultWriterType::new()));
         let mut reader_types: HashMap<String, Box<dyn ReaderType>> = HashMap::new();
         reader_types.insert("default".to_owned(), Box::new(default_reader::DefaultReaderType::new()));
+        #[cfg(feature = "http_tails")]
+        reader_types.insert("http".to_owned(), Box::new(http_reader::HttpReaderType::new()));
 
         BlobStorageService {
-            writer_types: RefCell::new(writer_types),
-            writer_configs: RefCell::new(HashMap::new()),
-            writer_blobs: RefCell::new(HashMap::new()),
+            writer_types: Mutex::new(writer_types),
+            writer_configs: Mutex::new(HashMap::new()),
+            writer_blobs: Mutex::new(HashMap::new()),
 
-            reader_types: RefCell::new(reader_types),
-            reader_configs: RefCell::new(HashMap::new()),
-            reader_blobs: RefCell::new(HashMap::new()),
+            reader_types: Mutex::new(reader_types),
+            reader_configs: Mutex::new(HashMap::new()),
+            reader_blobs: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -69,29 +73,29 @@ impl BlobStorageService {
 /* Writer */
 impl BlobStorageService {
     pub fn open_writer(&self, type_: &str, config: &str) -> IndyResult<i32> {
-        let writer_config = self.writer_types.try_borrow()?
+        let writer_config = self.writer_types.lock().unwrap()
             .get(type_).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Unknown BlobStorage Writer type"))?
             .open(config)?;
 
         let config_handle = sequence::get_next_id();
-        self.writer_configs.try_borrow_mut()?.insert(config_handle, writer_config);
+        self.writer_configs.lock().unwrap().insert(config_handle, writer_config);
 
         Ok(config_handle)
     }
 
     pub fn create_blob(&self, config_handle: i32) -> IndyResult<i32> {
         let blob_handle = sequence::get_next_id();
-        let writer = self.writer_configs.try_borrow()?
+        let writer = self.writer_configs.lock().unwrap()
             .get(&config_handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage config handle"))? // FIXME: Review error kind
             .create(blob_handle)?;
 
-        self.writer_blobs.try_borrow_mut()?.insert(blob_handle, (writer, Sha256::default()));
+        self.writer_blobs.lock().unwrap().insert(blob_handle, (writer, Sha256::default()));
 
         Ok(blob_handle)
     }
 
     pub fn append(&self, handle: i32, bytes: &[u8]) -> IndyResult<usize> {
-        let mut writers = self.writer_blobs.try_borrow_mut()?;
+        let mut writers = self.writer_blobs.lock().unwrap();
         let &mut (ref mut writer, ref mut hasher) = writers
             .get_mut(&handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage handle"))?; // FIXME: Review error kind
 
@@ -100,7 +104,7 @@ impl BlobStorageService {
     }
 
     pub fn finalize(&self, handle: i32) -> IndyResult<(String, Vec<u8>)> {
-        let mut writers = self.writer_blobs.try_borrow_mut()?;
+        let mut writers = self.writer_blobs.lock().unwrap();
         let (mut writer, hasher) = writers
             .remove(&handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage handle"))?; // FIXME: Review error kind
 
@@ -114,41 +118,41 @@ impl BlobStorageService {
 /* Reader */
 impl BlobStorageService {
     pub fn open_reader(&self, type_: &str, config: &str) -> IndyResult<i32> {
-        let reader_config = self.reader_types.try_borrow()?
+        let reader_config = self.reader_types.lock().unwrap()
             .get(type_).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage Reader type"))? // FIXME: Review error kind
             .open(config)?;
 
         let config_handle = sequence::get_next_id();
-        self.reader_configs.try_borrow_mut()?.insert(config_handle, reader_config);
+        self.reader_configs.lock().unwrap().insert(config_handle, reader_config);
 
         Ok(config_handle)
     }
 
     pub fn open_blob(&self, config_handle: i32, location: &str, hash: &[u8]) -> IndyResult<i32> {
-        let reader = self.reader_configs.try_borrow()?
+        let reader = self.reader_configs.lock().unwrap()
             .get(&config_handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage config handle"))? // FIXME: Review error kind
             .open(hash, location)?;
 
         let reader_handle = sequence::get_next_id();
-        self.reader_blobs.try_borrow_mut()?.insert(reader_handle, reader);
+        self.reader_blobs.lock().unwrap().insert(reader_handle, reader);
 
         Ok(reader_handle)
     }
 
     pub fn read(&self, handle: i32, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
-        self.reader_blobs.try_borrow_mut()?
+        self.reader_blobs.lock().unwrap()
             .get_mut(&handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage handle"))? // FIXME: Review error kind
             .read(size, offset)
     }
 
     pub fn _verify(&self, handle: i32) -> IndyResult<bool> {
-        self.reader_blobs.try_borrow_mut()?
+        self.reader_blobs.lock().unwrap()
             .get_mut(&handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage handle"))? // FIXME: Review error kind
             .verify()
     }
 
     pub fn close(&self, handle: i32) -> IndyResult<()> {
-        self.reader_blobs.try_borrow_mut()?
+        self.reader_blobs.lock().unwrap()
             .remove(&handle).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage handle"))? // FIXME: Review error kind
             .close()
     }
@@ -0,0 +1,122 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::slice;
+
+use libc::c_char;
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::crypto::*;
+use indy_api_types::errors::prelude::*;
+
+// Name of the key management that keeps private keys in the wallet.
+// Keys of other key managements are kept in external KMS, the wallet stores only KMS key identifiers.
+pub const WALLET_KEY_MANAGEMENT: &str = "wallet";
+
+// Delegates key generation and signing to external KMS (PKCS#11 HSM or mobile secure enclave for example).
+pub trait KeyManagement {
+    // Returns KMS identifier of the private key and base58 encoded public key
+    fn create_key(&self, crypto_type: &str) -> IndyResult<(String, String)>;
+    fn sign(&self, key_id: &str, crypto_type: &str, message: &[u8]) -> IndyResult<Vec<u8>>;
+}
+
+// This struct is used as a helper to free the returned data even in case of error.
+struct HandleGuard {
+    handle: IndyHandle,
+    free_handler: KeyManagementFree,
+}
+
+impl Drop for HandleGuard {
+    fn drop(&mut self) {
+        (self.free_handler)(self.handle);
+    }
+}
+
+pub(super) struct PluggedKeyManagement {
+    create_key_handler: KeyManagementCreateKey,
+    sign_handler: KeyManagementSign,
+    free_handler: KeyManagementFree,
+}
+
+impl PluggedKeyManagement {
+    pub(super) fn new(create_key_handler: KeyManagementCreateKey,
+                      sign_handler: KeyManagementSign,
+                      free_handler: KeyManagementFree) -> PluggedKeyManagement {
+        PluggedKeyManagement {
+            create_key_handler,
+            sign_handler,
+            free_handler,
+        }
+    }
+
+    fn _read_str(str_p: *const c_char, name: &str) -> IndyResult<String> {
+        if str_p.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("Key management returned null {}", name)));
+        }
+
+        let res = unsafe {
+            CStr::from_ptr(str_p)
+                .to_str()
+                .to_indy(IndyErrorKind::InvalidState, format!("Key management returned non-utf8 {}", name))?
+                .to_string()
+        };
+
+        Ok(res)
+    }
+}
+
+impl KeyManagement for PluggedKeyManagement {
+    fn create_key(&self, crypto_type: &str) -> IndyResult<(String, String)> {
+        let crypto_type = CString::new(crypto_type)?;
+
+        let mut key_id_p: *const c_char = ptr::null();
+        let mut verkey_p: *const c_char = ptr::null();
+        let mut key_handle: IndyHandle = -1;
+
+        let err = (self.create_key_handler)(crypto_type.as_ptr(),
+                                            &mut key_id_p,
+                                            &mut verkey_p,
+                                            &mut key_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _guard = HandleGuard { handle: key_handle, free_handler: self.free_handler };
+
+        let key_id = PluggedKeyManagement::_read_str(key_id_p, "key id")?;
+        let verkey = PluggedKeyManagement::_read_str(verkey_p, "verkey")?;
+
+        Ok((key_id, verkey))
+    }
+
+    fn sign(&self, key_id: &str, crypto_type: &str, message: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_id = CString::new(key_id)?;
+        let crypto_type = CString::new(crypto_type)?;
+
+        let mut signature_p: *const u8 = ptr::null();
+        let mut signature_len: u32 = 0;
+        let mut signature_handle: IndyHandle = -1;
+
+        let err = (self.sign_handler)(key_id.as_ptr(),
+                                      crypto_type.as_ptr(),
+                                      message.as_ptr(),
+                                      message.len() as u32,
+                                      &mut signature_p,
+                                      &mut signature_len,
+                                      &mut signature_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _guard = HandleGuard { handle: signature_handle, free_handler: self.free_handler };
+
+        if signature_p.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key management returned null signature"));
+        }
+
+        let signature = unsafe { slice::from_raw_parts(signature_p, signature_len as usize) }.to_vec();
+
+        Ok(signature)
+    }
+}
@@ -0,0 +1,114 @@
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac, NewMac};
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::randombytes;
+use sha2::Sha512;
+
+// Keys are derived from BIP39 seed of mnemonic with SLIP-0010 Ed25519 (all levels are hardened):
+//   wallet master key: m/1093'/0'/index'
+//   DID seed:          m/1093'/1'/index'
+pub const DERIVATION_PURPOSE: u32 = 1093;
+pub const WALLET_KEY_BRANCH: u32 = 0;
+pub const DID_SEED_BRANCH: u32 = 1;
+
+pub const DEFAULT_WORD_COUNT: usize = 24;
+
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+const SLIP10_ED25519_CURVE: &[u8] = b"ed25519 seed";
+
+pub fn generate(word_count: usize) -> IndyResult<String> {
+    if word_count < 12 || word_count > 24 || word_count % 3 != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Invalid mnemonic word count: {}. It can be 12, 15, 18, 21 or 24", word_count)));
+    }
+
+    // Each 3 words encode 32 bits of entropy
+    let entropy = randombytes::randombytes(word_count / 3 * 4);
+
+    let mnemonic = Mnemonic::from_entropy(&entropy)
+        .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't create mnemonic: {}", err)))?;
+
+    Ok(mnemonic.to_string())
+}
+
+pub fn derive_key(phrase: &str, passphrase: &str, branch: u32, index: u32) -> IndyResult<Vec<u8>> {
+    let mnemonic = Mnemonic::parse(phrase)
+        .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid mnemonic: {}", err)))?;
+
+    let seed = mnemonic.to_seed(passphrase);
+
+    _slip10_derive(&seed, &[DERIVATION_PURPOSE, branch, index])
+}
+
+fn _slip10_derive(seed: &[u8], path: &[u32]) -> IndyResult<Vec<u8>> {
+    let i = _hmac_sha512(SLIP10_ED25519_CURVE, seed)?;
+    let (mut key, mut chain_code) = (i[..32].to_vec(), i[32..].to_vec());
+
+    for index in path {
+        let mut data = vec![0u8];
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&(index | HARDENED_OFFSET).to_be_bytes());
+
+        let i = _hmac_sha512(&chain_code, &data)?;
+        key = i[..32].to_vec();
+        chain_code = i[32..].to_vec();
+    }
+
+    Ok(key)
+}
+
+fn _hmac_sha512(key: &[u8], data: &[u8]) -> IndyResult<Vec<u8>> {
+    let mut mac = Hmac::<Sha512>::new_varkey(key)
+        .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't create HMAC-SHA512: {}", err)))?;
+
+    mac.update(data);
+
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn generate_works() {
+        let phrase = generate(DEFAULT_WORD_COUNT).unwrap();
+        assert_eq!(DEFAULT_WORD_COUNT, phrase.split_whitespace().count());
+        derive_key(&phrase, "", WALLET_KEY_BRANCH, 0).unwrap();
+
+        assert_eq!(12, generate(12).unwrap().split_whitespace().count());
+    }
+
+    #[test]
+    fn generate_fails_for_invalid_word_count() {
+        assert_kind!(IndyErrorKind::InvalidStructure, generate(13));
+        assert_kind!(IndyErrorKind::InvalidStructure, generate(27));
+    }
+
+    #[test]
+    fn derive_key_works() {
+        let wallet_key = derive_key(PHRASE, "", WALLET_KEY_BRANCH, 0).unwrap();
+        assert_eq!(32, wallet_key.len());
+        assert_eq!(wallet_key, derive_key(PHRASE, "", WALLET_KEY_BRANCH, 0).unwrap());
+
+        assert_ne!(wallet_key, derive_key(PHRASE, "", WALLET_KEY_BRANCH, 1).unwrap());
+        assert_ne!(wallet_key, derive_key(PHRASE, "", DID_SEED_BRANCH, 0).unwrap());
+        assert_ne!(wallet_key, derive_key(PHRASE, "passphrase", WALLET_KEY_BRANCH, 0).unwrap());
+    }
+
+    #[test]
+    fn derive_key_fails_for_invalid_checksum() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert_kind!(IndyErrorKind::InvalidStructure, derive_key(phrase, "", WALLET_KEY_BRANCH, 0));
+    }
+
+    #[test]
+    fn slip10_derive_works_for_test_vector() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        assert_eq!("2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7", hex::encode(_slip10_derive(&seed, &[]).unwrap()));
+        assert_eq!("68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3", hex::encode(_slip10_derive(&seed, &[0]).unwrap()));
+    }
+}
@@ -1,5 +1,6 @@
 extern crate hex;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::str;
 
@@ -13,12 +14,23 @@ use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::gen_nonce_and_encrypt_detached;
 use indy_utils::crypto::ed25519_sign;
 use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_get_cryptoname};
+use crate::services::pool::DEFAULT_GENERATOR;
+use indy_api_types::crypto::{KeyManagementCreateKey, KeyManagementFree, KeyManagementSign};
+use ursa::bls::{Bls, Generator, MultiSignature, ProofOfPossession, Signature, SignKey as BlsSignKey, VerKey as BlsVerKey};
 
 use self::ed25519::ED25519CryptoType;
+use self::key_management::{KeyManagement, PluggedKeyManagement, WALLET_KEY_MANAGEMENT};
+use self::secp256k1::Secp256k1CryptoType;
 use self::hex::FromHex;
 use rust_base58::{FromBase58, ToBase58};
 
 mod ed25519;
+pub mod key_management;
+pub mod mnemonic;
+mod secp256k1;
+pub mod shamir;
+
+pub use self::secp256k1::SECP256K1_CRYPTO_TYPE;
 
 pub const DEFAULT_CRYPTO_TYPE: &str = "ed25519";
 
@@ -37,7 +49,10 @@ trait CryptoType {
 }
 
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>
+    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>,
+    // Doesn't fit CryptoType as supports signing only
+    secp256k1: Secp256k1CryptoType,
+    key_managements: RefCell<HashMap<String, Box<dyn KeyManagement>>>,
 }
 
 impl CryptoService {
@@ -46,10 +61,31 @@ impl CryptoService {
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
 
         CryptoService {
-            crypto_types
+            crypto_types,
+            secp256k1: Secp256k1CryptoType::new(),
+            key_managements: RefCell::new(HashMap::new()),
         }
     }
 
+    pub fn register_key_management(&self,
+                                   name: &str,
+                                   create_key: KeyManagementCreateKey,
+                                   sign: KeyManagementSign,
+                                   free: KeyManagementFree) -> IndyResult<()> {
+        trace!("register_key_management >>> name: {:?}", name);
+
+        let mut key_managements = self.key_managements.borrow_mut();
+
+        if name == WALLET_KEY_MANAGEMENT || key_managements.contains_key(name) {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("Key management is already registered for name: {}", name)));
+        }
+
+        key_managements.insert(name.to_string(), Box::new(PluggedKeyManagement::new(create_key, sign, free)));
+
+        trace!("register_key_management <<<");
+        Ok(())
+    }
+
     pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
         trace!("create_key >>> key_info: {:?}", secret!(key_info));
 
@@ -58,6 +94,21 @@ impl CryptoService {
             .map(String::as_str)
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
+        if let Some(kms) = key_info.kms.as_ref().filter(|kms| kms.as_str() != WALLET_KEY_MANAGEMENT) {
+            return self._create_kms_key(kms, crypto_type_name, key_info);
+        }
+
+        if crypto_type_name == SECP256K1_CRYPTO_TYPE {
+            let seed = self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?;
+            let (vk, sk) = self.secp256k1.create_key(seed.as_ref().map(|seed| &seed[..]))?;
+
+            let key = Key::new(format!("{}:{}", vk.to_base58(), SECP256K1_CRYPTO_TYPE), sk.to_base58());
+
+            trace!("create_key <<< key: {:?}", key);
+
+            return Ok(key);
+        }
+
         if !self.crypto_types.contains_key(crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("KeyInfo contains unknown crypto: {}", crypto_type_name)));
         }
@@ -80,6 +131,39 @@ impl CryptoService {
         Ok(key)
     }
 
+    fn _create_kms_key(&self, kms: &str, crypto_type_name: &str, key_info: &KeyInfo) -> IndyResult<Key> {
+        if key_info.seed.is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Seed can't be used for a key kept in external KMS"));
+        }
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE && crypto_type_name != SECP256K1_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("KeyInfo contains unknown crypto: {}", crypto_type_name)));
+        }
+
+        let (key_id, vk) = self.key_managements.borrow()
+            .get(kms)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Unknown key management: {}", kms)))?
+            .create_key(crypto_type_name)?;
+
+        let vk = if crypto_type_name == DEFAULT_CRYPTO_TYPE { vk } else { format!("{}:{}", vk, crypto_type_name) };
+        self.validate_key(&vk)?;
+
+        let key = Key { verkey: vk, signkey: key_id, kms: Some(kms.to_string()) };
+
+        trace!("create_key <<< key: {:?}", key);
+
+        Ok(key)
+    }
+
+    // Private keys kept in external KMS are used for signing only
+    fn _check_key_in_wallet(&self, key: &Key) -> IndyResult<()> {
+        match key.kms {
+            Some(ref kms) if kms != WALLET_KEY_MANAGEMENT =>
+                Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Key {} is kept in external KMS {} and can be used for signing only", key.verkey, kms))),
+            _ => Ok(())
+        }
+    }
+
     pub fn create_my_did(&self, my_did_info: &MyDidInfo) -> IndyResult<(Did, Key)> {
         trace!("create_my_did >>> my_did_info: {:?}", secret!(my_did_info));
 
@@ -92,17 +176,33 @@ impl CryptoService {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("MyDidInfo contains unknown crypto: {}", crypto_type_name)));
         }
 
+        if let Some(kms) = my_did_info.kms.as_ref().filter(|kms| kms.as_str() != WALLET_KEY_MANAGEMENT) {
+            let key_info = KeyInfo {
+                seed: my_did_info.seed.clone(),
+                crypto_type: Some(crypto_type_name.to_string()),
+                kms: Some(kms.to_string()),
+            };
+            let key = self._create_kms_key(kms, crypto_type_name, &key_info)?;
+
+            let vk = split_verkey(&key.verkey).0.from_base58()?;
+            let did = (Did::new(CryptoService::_build_did(my_did_info, &vk), key.verkey.clone()), key);
+
+            trace!("create_my_did <<< did: {:?}", did);
+
+            return Ok(did);
+        }
+
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
-        let did = match my_did_info.did {
-            Some(ref did) => did.clone(),
-            _ if my_did_info.cid == Some(true) =>
-                DidValue::new(&vk[..].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
-            _ =>
-                DidValue::new(&vk[0..16].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
+        let seed = match my_did_info.mnemonic {
+            Some(ref mnemonic) => {
+                let seed = mnemonic::derive_key(&mnemonic.phrase, &mnemonic.passphrase, mnemonic::DID_SEED_BRANCH, mnemonic.index)?;
+                Some(ed25519_sign::Seed::from_slice(&seed)?)
+            }
+            None => self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?
         };
+        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
+        let did = CryptoService::_build_did(my_did_info, &vk[..]);
 
         let mut vk = vk[..].to_base58();
         let sk = sk[..].to_base58();
@@ -119,6 +219,16 @@ impl CryptoService {
         Ok(did)
     }
 
+    fn _build_did(my_did_info: &MyDidInfo, vk: &[u8]) -> DidValue {
+        match my_did_info.did {
+            Some(ref did) => did.clone(),
+            _ if my_did_info.cid == Some(true) =>
+                DidValue::new(&vk.to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
+            _ =>
+                DidValue::new(&vk[0..16].to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
+        }
+    }
+
     pub fn create_their_did(&self, their_did_info: &TheirDidInfo) -> IndyResult<TheirDid> {
         trace!("create_their_did >>> their_did_info: {:?}", their_did_info);
 
@@ -142,6 +252,31 @@ impl CryptoService {
 
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
+        if let Some(kms) = my_key.kms.as_ref().filter(|kms| kms.as_str() != WALLET_KEY_MANAGEMENT) {
+            let signature = self.key_managements.borrow()
+                .get(kms)
+                .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Unknown key management: {}", kms)))?
+                .sign(&my_key.signkey, crypto_type_name, doc)?;
+
+            // Don't trust external KMS to sign with the key bound to the verkey
+            if !self.verify(&my_key.verkey, doc, &signature).unwrap_or(false) {
+                return Err(err_msg(IndyErrorKind::InvalidState,
+                                   format!("Key management {} returned signature that doesn't match verkey {}", kms, my_key.verkey)));
+            }
+
+            trace!("sign <<< signature: {:?}", signature);
+
+            return Ok(signature);
+        }
+
+        if crypto_type_name == SECP256K1_CRYPTO_TYPE {
+            let signature = self.secp256k1.sign(&my_key.signkey.as_str().from_base58()?, doc)?;
+
+            trace!("sign <<< signature: {:?}", signature);
+
+            return Ok(signature);
+        }
+
         if !self.crypto_types.contains_key(crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to sign message with unknown crypto: {}", crypto_type_name)));
         }
@@ -161,6 +296,14 @@ impl CryptoService {
 
         let (their_vk, crypto_type_name) = split_verkey(their_vk);
 
+        if crypto_type_name == SECP256K1_CRYPTO_TYPE {
+            let valid = self.secp256k1.verify(&their_vk.from_base58()?, msg, signature)?;
+
+            trace!("verify <<< valid: {:?}", valid);
+
+            return Ok(valid);
+        }
+
         if !self.crypto_types.contains_key(crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to verify message with unknown crypto: {}", crypto_type_name)));
         }
@@ -196,6 +339,8 @@ impl CryptoService {
     pub fn crypto_box(&self, my_key: &Key, their_vk: &str, doc: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
         trace!("crypto_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, doc);
 
+        self._check_key_in_wallet(my_key)?;
+
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
         let (their_vk, their_crypto_type_name) = split_verkey(their_vk);
@@ -229,6 +374,8 @@ impl CryptoService {
     pub fn crypto_box_open(&self, my_key: &Key, their_vk: &str, doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
         trace!("crypto_box_open >>> my_key: {:?}, their_vk: {:?}, doc: {:?}, nonce: {:?}", my_key, their_vk, doc, nonce);
 
+        self._check_key_in_wallet(my_key)?;
+
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
         let (their_vk, their_crypto_type_name) = split_verkey(their_vk);
@@ -282,6 +429,8 @@ impl CryptoService {
     pub fn crypto_box_seal_open(&self, my_key: &Key, doc: &[u8]) -> IndyResult<Vec<u8>> {
         trace!("crypto_box_seal_open >>> my_key: {:?}, doc: {:?}", my_key, doc);
 
+        self._check_key_in_wallet(my_key)?;
+
         let (my_vk, crypto_type_name) = split_verkey(&my_key.verkey);
 
         if !self.crypto_types.contains_key(&crypto_type_name) {
@@ -347,6 +496,12 @@ impl CryptoService {
 
         let (vk, crypto_type_name) = split_verkey(vk);
 
+        if crypto_type_name == SECP256K1_CRYPTO_TYPE {
+            self.secp256k1.validate_key(&vk.from_base58()?)?;
+            trace!("validate_key <<<");
+            return Ok(());
+        }
+
         if !self.crypto_types.contains_key(&crypto_type_name) {
             return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to use key with unknown crypto: {}", crypto_type_name)));
         }
@@ -365,6 +520,50 @@ impl CryptoService {
         Ok(())
     }
 
+    // X25519 key of the same key pair is used for Diffie-Hellman key agreement (e.g. in DIDComm)
+    pub fn convert_ed25519_to_x25519(&self, verkey: &str) -> IndyResult<String> {
+        trace!("convert_ed25519_to_x25519 >>> verkey: {:?}", verkey);
+
+        let (vk, crypto_type_name) = split_verkey(verkey);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Only {} keys can be converted to X25519: {}", DEFAULT_CRYPTO_TYPE, crypto_type_name)));
+        }
+
+        if vk.starts_with('~') {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Abbreviated verkey can't be converted to X25519, use full verkey"));
+        }
+
+        let vk = ed25519_sign::PublicKey::from_slice(vk.from_base58()?.as_slice())?;
+        let res = ed25519_sign::vk_to_curve25519(&vk)?[..].to_base58();
+
+        trace!("convert_ed25519_to_x25519 <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Key agreement with the X25519 form of the wallet key, the secret never leaves the wallet
+    pub fn x25519_diffie_hellman(&self, my_key: &Key, their_x25519_key: &str) -> IndyResult<Vec<u8>> {
+        trace!("x25519_diffie_hellman >>> my_key: {:?}, their_x25519_key: {:?}", my_key, their_x25519_key);
+
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE
+            || my_key.kms.as_ref().map(|kms| kms.as_str() != WALLET_KEY_MANAGEMENT).unwrap_or(false) {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Only {} wallet keys can be used for X25519 key agreement", DEFAULT_CRYPTO_TYPE)));
+        }
+
+        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
+        let my_sk = ed25519_sign::sk_to_curve25519(&my_sk)?;
+        let their_pk = ed25519_box::PublicKey::from_slice(their_x25519_key.from_base58()?.as_slice())?;
+
+        let res = ed25519_box::diffie_hellman(&my_sk, &their_pk)?;
+
+        trace!("x25519_diffie_hellman <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
     pub fn validate_did(&self, did: &DidValue) -> IndyResult<()> {
         trace!("validate_did >>> did: {:?}", did);
         // Useful method, huh?
@@ -374,6 +573,85 @@ impl CryptoService {
         Ok(())
     }
 
+    // BLS keys and signatures are base58 encoded as in ledger transactions and state proofs.
+    // Default generator of Indy pools is used, so node keys and multi-signatures can be checked.
+    pub fn bls_create_keys(&self, seed: Option<&str>) -> IndyResult<(String, String, String)> {
+        trace!("bls_create_keys >>> seed: {:?}", secret!(seed));
+
+        let seed = self.convert_seed(seed)?;
+
+        let sign_key = BlsSignKey::new(seed.as_ref().map(|seed| &seed[..]))?;
+        let ver_key = BlsVerKey::new(&CryptoService::_bls_generator()?, &sign_key)?;
+        let pop = ProofOfPossession::new(&ver_key, &sign_key)?;
+
+        let res = (sign_key.as_bytes().to_base58(), ver_key.as_bytes().to_base58(), pop.as_bytes().to_base58());
+
+        trace!("bls_create_keys <<< ver_key: {:?}, pop: {:?}", res.1, res.2);
+
+        Ok(res)
+    }
+
+    pub fn bls_sign(&self, sign_key: &str, msg: &[u8]) -> IndyResult<String> {
+        trace!("bls_sign >>> msg: {:?}", msg);
+
+        let sign_key = BlsSignKey::from_bytes(&CryptoService::_decode_bls_value(sign_key, "sign key")?)?;
+        let res = Bls::sign(msg, &sign_key)?.as_bytes().to_base58();
+
+        trace!("bls_sign <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    pub fn bls_aggregate_signatures(&self, signatures: &[String]) -> IndyResult<String> {
+        trace!("bls_aggregate_signatures >>> signatures: {:?}", signatures);
+
+        if signatures.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "No BLS signatures to aggregate"));
+        }
+
+        let signatures = signatures.iter()
+            .map(|signature| Ok(Signature::from_bytes(&CryptoService::_decode_bls_value(signature, "signature")?)?))
+            .collect::<IndyResult<Vec<Signature>>>()?;
+
+        let signatures: Vec<&Signature> = signatures.iter().collect();
+        let res = MultiSignature::new(signatures.as_slice())?.as_bytes().to_base58();
+
+        trace!("bls_aggregate_signatures <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Single signature can be verified as multi-signature of one signer
+    pub fn bls_verify_multi_sig(&self, multi_sig: &str, msg: &[u8], ver_keys: &[String]) -> IndyResult<bool> {
+        trace!("bls_verify_multi_sig >>> multi_sig: {:?}, msg: {:?}, ver_keys: {:?}", multi_sig, msg, ver_keys);
+
+        if ver_keys.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "No BLS keys to verify multi-signature with"));
+        }
+
+        let multi_sig = MultiSignature::from_bytes(&CryptoService::_decode_bls_value(multi_sig, "multi-signature")?)?;
+
+        let ver_keys = ver_keys.iter()
+            .map(|ver_key| Ok(BlsVerKey::from_bytes(&CryptoService::_decode_bls_value(ver_key, "verkey")?)?))
+            .collect::<IndyResult<Vec<BlsVerKey>>>()?;
+
+        let ver_keys: Vec<&BlsVerKey> = ver_keys.iter().collect();
+        let res = Bls::verify_multi_sig(&multi_sig, msg, ver_keys.as_slice(), &CryptoService::_bls_generator()?)?;
+
+        trace!("bls_verify_multi_sig <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _bls_generator() -> IndyResult<Generator> {
+        Ok(Generator::from_bytes(&DEFAULT_GENERATOR.from_base58()?)?)
+    }
+
+    fn _decode_bls_value(value: &str, name: &str) -> IndyResult<Vec<u8>> {
+        value.from_base58()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid BLS {}: {:?}", name, err)))
+    }
+
     pub fn encrypt_plaintext(&self,
                              plaintext: Vec<u8>,
                              aad: &str,
@@ -446,6 +724,7 @@ impl CryptoService {
 #[cfg(test)]
 mod tests {
     use crate::domain::crypto::did::MyDidInfo;
+    use indy_api_types::domain::wallet::MnemonicSeed;
     use indy_utils::crypto::chacha20poly1305_ietf::gen_key;
 
     use super::*;
@@ -453,17 +732,80 @@ mod tests {
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let my_did = service.create_my_did(&did_info);
         assert!(my_did.is_ok());
     }
 
+    // Keeps private keys in memory, KMS key identifier is the private key itself.
+    // Signs with another key if `forge_signatures` is set.
+    struct InMemoryKeyManagement {
+        forge_signatures: bool,
+    }
+
+    impl KeyManagement for InMemoryKeyManagement {
+        fn create_key(&self, _crypto_type: &str) -> IndyResult<(String, String)> {
+            let key = CryptoService::new().create_key(&KeyInfo { seed: None, crypto_type: None, kms: None })?;
+            Ok((key.signkey, key.verkey))
+        }
+
+        fn sign(&self, key_id: &str, _crypto_type: &str, message: &[u8]) -> IndyResult<Vec<u8>> {
+            let service = CryptoService::new();
+            let key = if self.forge_signatures {
+                service.create_key(&KeyInfo { seed: None, crypto_type: None, kms: None })?
+            } else {
+                Key::new(String::new(), key_id.to_string())
+            };
+            service.sign(&key, message)
+        }
+    }
+
+    fn _service_with_kms(forge_signatures: bool) -> CryptoService {
+        let service = CryptoService::new();
+        service.key_managements.borrow_mut().insert("memory".to_string(), Box::new(InMemoryKeyManagement { forge_signatures }));
+        service
+    }
+
+    #[test]
+    fn create_my_did_works_for_kms() {
+        let service = _service_with_kms(false);
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: Some("memory".to_string()) };
+        let (did, key) = service.create_my_did(&did_info).unwrap();
+
+        assert_eq!(Some("memory".to_string()), key.kms);
+        assert_eq!(did.verkey, key.verkey);
+        assert_eq!(did.did.0, key.verkey.from_base58().unwrap()[0..16].to_base58());
+
+        let message = r#"{"reqId":1496822211362017764}"#.as_bytes();
+        let signature = service.sign(&key, message).unwrap();
+        assert!(service.verify(&did.verkey, message, &signature).unwrap());
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_unknown_kms() {
+        let service = CryptoService::new();
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: Some("memory".to_string()) };
+        assert_kind!(IndyErrorKind::UnknownCrypto, service.create_my_did(&did_info));
+    }
+
+    #[test]
+    fn sign_not_works_for_kms_signature_not_matching_verkey() {
+        let service = _service_with_kms(false);
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None, kms: Some("memory".to_string()) }).unwrap();
+
+        let forging_service = _service_with_kms(true);
+        let res = forging_service.sign(&key, "message".as_bytes());
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
     #[test]
     fn create_my_did_works_for_passed_did() {
         let service = CryptoService::new();
 
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
-        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
 
         let (my_did, _) = service.create_my_did(&did_info).unwrap();
         assert_eq!(did, my_did.did);
@@ -476,7 +818,7 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None };
+        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None, mnemonic: None, kms: None };
 
         assert!(service.create_my_did(&did_info).is_err());
     }
@@ -488,8 +830,8 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let seed = Some("00000000000000000000000000000My1".to_string());
 
-        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None };
-        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None, mnemonic: None, kms: None };
+        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
 
         let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
         let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
@@ -497,6 +839,25 @@ mod tests {
         assert_ne!(did_with_seed.verkey, did_without_seed.verkey)
     }
 
+    #[test]
+    fn create_my_did_works_for_mnemonic() {
+        let service = CryptoService::new();
+
+        let phrase = mnemonic::generate(mnemonic::DEFAULT_WORD_COUNT).unwrap();
+        let mnemonic_seed = |index| Some(MnemonicSeed { phrase: phrase.clone(), passphrase: String::new(), index });
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: mnemonic_seed(0), kms: None };
+        let other_did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: mnemonic_seed(1), kms: None };
+
+        let (did, _) = service.create_my_did(&did_info).unwrap();
+        let (same_did, _) = service.create_my_did(&did_info).unwrap();
+        let (other_did, _) = service.create_my_did(&other_did_info).unwrap();
+
+        assert_eq!(did.did, same_did.did);
+        assert_eq!(did.verkey, same_did.verkey);
+        assert_ne!(did.did, other_did.did);
+    }
+
     #[test]
     fn create_their_did_works_without_verkey() {
         let service = CryptoService::new();
@@ -536,7 +897,7 @@ mod tests {
     #[test]
     fn sign_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
 
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
@@ -555,7 +916,7 @@ mod tests {
     #[test]
     fn sign_verify_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -566,7 +927,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -579,7 +940,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -590,7 +951,7 @@ mod tests {
     #[test]
     fn verify_not_works_for_invalid_verkey() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -603,7 +964,7 @@ mod tests {
     fn crypto_box_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let their_did = Did::new(their_did.did, their_did.verkey);
@@ -617,7 +978,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -645,7 +1006,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -671,7 +1032,7 @@ mod tests {
     fn crypto_box_seal_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let did = Did::new(did.did, did.verkey);
         let encrypted_message = service.crypto_box_seal(&did.verkey, msg.as_bytes());
@@ -682,7 +1043,7 @@ mod tests {
     fn crypto_box_seal_and_crypto_box_seal_open_works() {
         let service = CryptoService::new();
         let msg = "some message".as_bytes();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, mnemonic: None, kms: None };
         let (did, key) = service.create_my_did(&did_info.clone()).unwrap();
         let encrypt_did = Did::new(did.did.clone(), did.verkey.clone());
         let encrypted_message = service.crypto_box_seal(&encrypt_did.verkey, msg).unwrap();
@@ -824,4 +1185,94 @@ mod tests {
             .decrypt_ciphertext(&expected_ciphertext, &iv_encoded, &tag, bad_aad, &cek);
         assert!(expected_error.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn bls_sign_and_verify_multi_sig_works() {
+        let service = CryptoService::new();
+        let msg = "Hello World".as_bytes();
+
+        let (sign_key_1, ver_key_1, _) = service.bls_create_keys(None).unwrap();
+        let (sign_key_2, ver_key_2, _) = service.bls_create_keys(Some("00000000000000000000000000000My1")).unwrap();
+
+        let signatures = vec![service.bls_sign(&sign_key_1, msg).unwrap(), service.bls_sign(&sign_key_2, msg).unwrap()];
+        let multi_sig = service.bls_aggregate_signatures(&signatures).unwrap();
+
+        assert!(service.bls_verify_multi_sig(&multi_sig, msg, &[ver_key_1.clone(), ver_key_2.clone()]).unwrap());
+        assert!(service.bls_verify_multi_sig(&signatures[0], msg, &[ver_key_1.clone()]).unwrap());
+        assert!(!service.bls_verify_multi_sig(&multi_sig, "Other message".as_bytes(), &[ver_key_1.clone(), ver_key_2]).unwrap());
+        assert!(!service.bls_verify_multi_sig(&multi_sig, msg, &[ver_key_1]).unwrap());
+    }
+
+    #[test]
+    fn bls_create_keys_works_for_seed() {
+        let service = CryptoService::new();
+
+        let (_, ver_key_1, _) = service.bls_create_keys(Some("00000000000000000000000000000My1")).unwrap();
+        let (_, ver_key_2, _) = service.bls_create_keys(Some("00000000000000000000000000000My1")).unwrap();
+        assert_eq!(ver_key_1, ver_key_2);
+    }
+
+    #[test]
+    fn bls_aggregate_signatures_fails_for_invalid_signature() {
+        let service = CryptoService::new();
+
+        assert_kind!(IndyErrorKind::InvalidStructure, service.bls_aggregate_signatures(&[]));
+        assert_kind!(IndyErrorKind::InvalidStructure, service.bls_aggregate_signatures(&["0OIl".to_string()]));
+    }
+
+    #[test]
+    fn secp256k1_key_sign_verify_works() {
+        let service = CryptoService::new();
+        let key_info = KeyInfo { seed: None, crypto_type: Some(SECP256K1_CRYPTO_TYPE.to_string()), kms: None };
+
+        let key = service.create_key(&key_info).unwrap();
+        assert!(key.verkey.ends_with(":secp256k1"));
+        service.validate_key(&key.verkey).unwrap();
+
+        let message = "Hello World".as_bytes();
+        let signature = service.sign(&key, message).unwrap();
+
+        assert!(service.verify(&key.verkey, message, &signature).unwrap());
+        assert!(!service.verify(&key.verkey, "Other message".as_bytes(), &signature).unwrap());
+    }
+
+    #[test]
+    fn secp256k1_key_fails_for_encryption() {
+        let service = CryptoService::new();
+        let key_info = KeyInfo { seed: None, crypto_type: Some(SECP256K1_CRYPTO_TYPE.to_string()), kms: None };
+
+        let key = service.create_key(&key_info).unwrap();
+        assert_kind!(IndyErrorKind::UnknownCrypto, service.crypto_box_seal(&key.verkey, "Hello World".as_bytes()));
+    }
+
+    #[test]
+    fn convert_ed25519_to_x25519_works() {
+        let service = CryptoService::new();
+        let key_info = KeyInfo { seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: None, kms: None };
+        let key = service.create_key(&key_info).unwrap();
+
+        let x25519_key = service.convert_ed25519_to_x25519(&key.verkey).unwrap();
+
+        let vk = ed25519_sign::PublicKey::from_slice(key.verkey.from_base58().unwrap().as_slice()).unwrap();
+        assert_eq!(ed25519_sign::vk_to_curve25519(&vk).unwrap()[..].to_base58(), x25519_key);
+        assert_eq!(32, x25519_key.from_base58().unwrap().len());
+
+        assert_kind!(IndyErrorKind::InvalidStructure, service.convert_ed25519_to_x25519("~CnEDk9HrMnmiHXEV1WFgbV"));
+    }
+
+    #[test]
+    fn x25519_diffie_hellman_works() {
+        let service = CryptoService::new();
+        let alice = service.create_key(&KeyInfo { seed: None, crypto_type: None, kms: None }).unwrap();
+        let bob = service.create_key(&KeyInfo { seed: None, crypto_type: None, kms: None }).unwrap();
+
+        let alice_x25519 = service.convert_ed25519_to_x25519(&alice.verkey).unwrap();
+        let bob_x25519 = service.convert_ed25519_to_x25519(&bob.verkey).unwrap();
+
+        let alice_shared = service.x25519_diffie_hellman(&alice, &bob_x25519).unwrap();
+        let bob_shared = service.x25519_diffie_hellman(&bob, &alice_x25519).unwrap();
+
+        assert_eq!(alice_shared, bob_shared);
+        assert_eq!(32, alice_shared.len());
+    }
+}
@@ -0,0 +1,88 @@
+use indy_api_types::errors::prelude::*;
+use ursa::keys::{KeyGenOption, PrivateKey, PublicKey};
+use ursa::signatures::SignatureScheme;
+use ursa::signatures::secp256k1::EcdsaSecp256k1Sha256;
+
+pub const SECP256K1_CRYPTO_TYPE: &str = "secp256k1";
+
+const COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
+const UNCOMPRESSED_PUBLIC_KEY_LENGTH: usize = 65;
+
+// ECDSA secp256k1 keys are used for signing only, libindy encryption primitives require Ed25519 keys.
+// Signatures are 64 bytes compact `r || s` as in ES256K JWS.
+pub struct Secp256k1CryptoType {
+    scheme: EcdsaSecp256k1Sha256
+}
+
+impl Secp256k1CryptoType {
+    pub fn new() -> Secp256k1CryptoType {
+        Secp256k1CryptoType {
+            scheme: EcdsaSecp256k1Sha256::new()
+        }
+    }
+
+    pub fn create_key(&self, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+        let option = seed.map(|seed| KeyGenOption::UseSeed(seed.to_vec()));
+
+        let (pk, sk) = self.scheme.keypair(option)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't create secp256k1 key: {:?}", err)))?;
+
+        Ok((pk.0.clone(), sk.0.clone()))
+    }
+
+    pub fn validate_key(&self, vk: &[u8]) -> IndyResult<()> {
+        match (vk.len(), vk.first()) {
+            (COMPRESSED_PUBLIC_KEY_LENGTH, Some(0x02)) |
+            (COMPRESSED_PUBLIC_KEY_LENGTH, Some(0x03)) |
+            (UNCOMPRESSED_PUBLIC_KEY_LENGTH, Some(0x04)) => Ok(()),
+            _ => Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid secp256k1 public key"))
+        }
+    }
+
+    pub fn sign(&self, sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+        self.scheme.sign(doc, &PrivateKey(sk.to_vec()))
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Can't sign message with secp256k1 key: {:?}", err)))
+    }
+
+    pub fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+        self.validate_key(vk)?;
+
+        Ok(self.scheme.verify(doc, signature, &PublicKey(vk.to_vec())).unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_verify_works() {
+        let crypto_type = Secp256k1CryptoType::new();
+        let message = "Hello World".as_bytes();
+
+        let (vk, sk) = crypto_type.create_key(None).unwrap();
+        crypto_type.validate_key(&vk).unwrap();
+
+        let signature = crypto_type.sign(&sk, message).unwrap();
+        assert_eq!(64, signature.len());
+
+        assert!(crypto_type.verify(&vk, message, &signature).unwrap());
+        assert!(!crypto_type.verify(&vk, "Other message".as_bytes(), &signature).unwrap());
+    }
+
+    #[test]
+    fn create_key_works_for_seed() {
+        let crypto_type = Secp256k1CryptoType::new();
+        let seed = [1u8; 32];
+
+        let (vk_1, _) = crypto_type.create_key(Some(&seed)).unwrap();
+        let (vk_2, _) = crypto_type.create_key(Some(&seed)).unwrap();
+        assert_eq!(vk_1, vk_2);
+    }
+
+    #[test]
+    fn validate_key_fails_for_ed25519_key() {
+        let crypto_type = Secp256k1CryptoType::new();
+        assert_kind!(IndyErrorKind::InvalidStructure, crypto_type.validate_key(&[0u8; 32]));
+    }
+}
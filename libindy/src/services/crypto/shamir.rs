@@ -0,0 +1,191 @@
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::{hash, randombytes};
+
+// Shamir's secret sharing over GF(2^8) with the AES reduction polynomial.
+// Serialized share is `threshold || x || y_1 .. y_n` where y_i is the share of i-th byte of the secret.
+// Secret is extended with the first bytes of its SHA-256 hash to detect mismatched shares on combining.
+
+const CHECKSUM_LENGTH: usize = 4;
+const SHARE_HEADER_LENGTH: usize = 2;
+
+pub const MAX_SHARES: usize = 255;
+
+pub fn split_secret(secret: &[u8], threshold: usize, shares: usize) -> IndyResult<Vec<Vec<u8>>> {
+    if threshold < 2 || threshold > shares || shares > MAX_SHARES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Invalid Shamir parameters: threshold {} of {} shares", threshold, shares)));
+    }
+
+    if secret.is_empty() {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Secret to split is empty"));
+    }
+
+    let mut secret = secret.to_vec();
+    secret.extend_from_slice(&_checksum(&secret)?);
+
+    let mut res: Vec<Vec<u8>> = (1..=shares)
+        .map(|x| vec![threshold as u8, x as u8])
+        .collect();
+
+    for byte in secret.iter() {
+        let mut coefficients = vec![*byte];
+        coefficients.extend(randombytes::randombytes(threshold - 1));
+
+        for share in res.iter_mut() {
+            let x = share[1];
+            share.push(_evaluate(&coefficients, x));
+        }
+    }
+
+    Ok(res)
+}
+
+pub fn combine_shares(shares: &[Vec<u8>]) -> IndyResult<Vec<u8>> {
+    let first = shares.first()
+        .ok_or(err_msg(IndyErrorKind::InvalidStructure, "No shares to combine"))?;
+
+    if first.len() <= SHARE_HEADER_LENGTH + CHECKSUM_LENGTH {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid share length"));
+    }
+
+    let threshold = first[0] as usize;
+
+    if shares.iter().any(|share| share.len() != first.len() || share[0] as usize != threshold || share[1] == 0) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Shares are inconsistent"));
+    }
+
+    let mut xs: Vec<u8> = shares.iter().map(|share| share[1]).collect();
+    xs.sort();
+    xs.dedup();
+
+    if xs.len() < threshold {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Not enough distinct shares: {} provided, {} required", xs.len(), threshold)));
+    }
+
+    let shares: Vec<&Vec<u8>> = xs.iter().take(threshold)
+        .map(|x| shares.iter().find(|share| share[1] == *x).unwrap())
+        .collect();
+
+    let mut secret: Vec<u8> = (SHARE_HEADER_LENGTH..first.len())
+        .map(|i| _interpolate_at_zero(&shares, i))
+        .collect();
+
+    let checksum = secret.split_off(secret.len() - CHECKSUM_LENGTH);
+
+    if checksum != _checksum(&secret)? {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Shares don't belong to the same secret"));
+    }
+
+    Ok(secret)
+}
+
+fn _checksum(secret: &[u8]) -> IndyResult<Vec<u8>> {
+    Ok(hash::hash(secret)?[..CHECKSUM_LENGTH].to_vec())
+}
+
+fn _evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients.iter().rev()
+        .fold(0, |acc, coefficient| _gf_mul(acc, x) ^ coefficient)
+}
+
+// Lagrange interpolation of the polynomial value at x = 0
+fn _interpolate_at_zero(shares: &[&Vec<u8>], i: usize) -> u8 {
+    let mut res = 0;
+
+    for (j, share_j) in shares.iter().enumerate() {
+        let mut basis = 1;
+
+        for (m, share_m) in shares.iter().enumerate() {
+            if m != j {
+                basis = _gf_mul(basis, _gf_div(share_m[1], share_m[1] ^ share_j[1]));
+            }
+        }
+
+        res ^= _gf_mul(share_j[i], basis);
+    }
+
+    res
+}
+
+// Operands are secret bytes, so there are no branches or early exits depending on them:
+// all 8 bits are processed and conditional steps are applied with masks.
+fn _gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut res = 0;
+
+    for _ in 0..8 {
+        res ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+
+    res
+}
+
+fn _gf_inv(a: u8) -> u8 {
+    // a^254 = a^-1 in GF(2^8), fixed number of constant-time multiplications for any a
+    let mut res = 1;
+    for _ in 0..254 {
+        res = _gf_mul(res, a);
+    }
+    res
+}
+
+fn _gf_div(a: u8, b: u8) -> u8 {
+    _gf_mul(a, _gf_inv(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"6KBoeMpAeUfBxNZB9NLXzKQ1ZLbWBnfb8BnkE5SUXu9i";
+
+    #[test]
+    fn split_and_combine_works() {
+        let shares = split_secret(SECRET, 3, 5).unwrap();
+        assert_eq!(5, shares.len());
+
+        assert_eq!(SECRET.to_vec(), combine_shares(&shares[0..3]).unwrap());
+        assert_eq!(SECRET.to_vec(), combine_shares(&shares[2..5]).unwrap());
+        assert_eq!(SECRET.to_vec(), combine_shares(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]).unwrap());
+        assert_eq!(SECRET.to_vec(), combine_shares(&shares).unwrap());
+    }
+
+    #[test]
+    fn combine_fails_for_not_enough_shares() {
+        let shares = split_secret(SECRET, 3, 5).unwrap();
+        assert_kind!(IndyErrorKind::InvalidStructure, combine_shares(&shares[0..2]));
+        assert_kind!(IndyErrorKind::InvalidStructure, combine_shares(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]));
+    }
+
+    #[test]
+    fn combine_fails_for_shares_of_different_secrets() {
+        let shares_1 = split_secret(SECRET, 2, 3).unwrap();
+        let shares_2 = split_secret(SECRET, 2, 3).unwrap();
+        assert_kind!(IndyErrorKind::InvalidStructure, combine_shares(&[shares_1[0].clone(), shares_2[1].clone()]));
+    }
+
+    #[test]
+    fn split_fails_for_invalid_threshold() {
+        assert_kind!(IndyErrorKind::InvalidStructure, split_secret(SECRET, 1, 3));
+        assert_kind!(IndyErrorKind::InvalidStructure, split_secret(SECRET, 4, 3));
+        assert_kind!(IndyErrorKind::InvalidStructure, split_secret(SECRET, 2, 256));
+    }
+
+    #[test]
+    fn gf_mul_works() {
+        assert_eq!(0xc1, _gf_mul(0x57, 0x83));
+        assert_eq!(0xfe, _gf_mul(0x57, 0x13));
+        assert_eq!(0, _gf_mul(0x57, 0));
+        assert_eq!(0x57, _gf_mul(0x57, 1));
+    }
+
+    #[test]
+    fn gf_inv_works() {
+        for a in 1..=255u8 {
+            assert_eq!(1, _gf_mul(a, _gf_inv(a)));
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use hex::FromHex;
 use ursa::cl::RevocationRegistryDelta as CryproRevocationRegistryDelta;
 use serde::de::DeserializeOwned;
@@ -12,14 +14,15 @@ use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelt
 use crate::domain::anoncreds::schema::{Schema, SchemaV1, SchemaId};
 use crate::domain::crypto::did::DidValue;
 use crate::domain::ledger::attrib::{AttribOperation, GetAttribOperation};
-use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, POOL_RESTART, ROLE_REMOVE, STEWARD, ENDORSER, TRUSTEE, NETWORK_MONITOR, ROLES, txn_name_to_code};
+use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, POOL_RESTART, ROLE_REMOVE, STEWARD, ENDORSER, TRUSTEE, NETWORK_MONITOR, ROLES,
+                                       GET_RICH_SCHEMA_OBJECT_BY_METADATA, txn_name_to_code};
 use crate::domain::ledger::cred_def::{CredDefOperation, GetCredDefOperation, GetCredDefReplyResult};
 use crate::domain::ledger::ddo::GetDdoOperation;
 use crate::domain::ledger::node::{NodeOperation, NodeOperationData};
 use crate::domain::ledger::nym::{GetNymOperation, GetNymReplyResult, GetNymResultDataV0, NymData, NymOperation};
 use crate::domain::ledger::pool::{PoolConfigOperation, PoolRestartOperation, PoolUpgradeOperation, Schedule};
 use crate::domain::ledger::request::{TxnAuthrAgrmtAcceptanceData, Request};
-use crate::domain::ledger::response::{Message, Reply, ReplyType};
+use crate::domain::ledger::response::{Message, NodeActionResult, NodeActionStatus, Reply, ReplyType};
 use crate::domain::ledger::rev_reg::{GetRevocRegDeltaReplyResult, GetRevocRegReplyResult, GetRevRegDeltaOperation, GetRevRegOperation, RevRegEntryOperation};
 use crate::domain::ledger::rev_reg_def::{GetRevocRegDefReplyResult, GetRevRegDefOperation, RevRegDefOperation};
 use crate::domain::ledger::schema::{GetSchemaOperation, GetSchemaOperationData, GetSchemaReplyResult, SchemaOperation, SchemaOperationData};
@@ -27,7 +30,10 @@ use crate::domain::ledger::txn::{GetTxnOperation, LedgerType};
 use crate::domain::ledger::validator_info::GetValidatorInfoOperation;
 use crate::domain::ledger::auth_rule::*;
 use crate::domain::ledger::author_agreement::*;
-use crate::domain::ledger::ledgers_freeze::{LedgersFreezeOperation, GetFrozenLedgersOperation};
+use crate::domain::ledger::ledgers_freeze::{FrozenLedger, GetFrozenLedgersReplyResult, LedgersFreezeOperation, GetFrozenLedgersOperation};
+use crate::domain::ledger::rich_schema::{RichSchema, RichSchemaType, RichSchemaOperation, GetRichSchemaObjectByIdOperation,
+                                         GetRichSchemaObjectByMetadataOperation, GetRichSchemaObjectByIdReplyResult,
+                                         GetRichSchemaObjectByMetadataReplyResult};
 use indy_api_types::errors::prelude::*;
 use indy_utils::crypto::hash::hash as openssl_hash;
 
@@ -405,6 +411,22 @@ impl LedgerService {
         build_result!(GetFrozenLedgersOperation, Some(submitter_did))
     }
 
+    #[logfn(Info)]
+    pub fn build_rich_schema_request(&self, submitter_did: &DidValue, rich_schema: RichSchema) -> IndyResult<String> {
+        build_result!(RichSchemaOperation, Some(submitter_did), rich_schema)
+    }
+
+    #[logfn(Info)]
+    pub fn build_get_rich_schema_object_by_id_request(&self, submitter_did: Option<&DidValue>, id: &str) -> IndyResult<String> {
+        build_result!(GetRichSchemaObjectByIdOperation, submitter_did, id.to_string())
+    }
+
+    #[logfn(Info)]
+    pub fn build_get_rich_schema_object_by_metadata_request(&self, submitter_did: Option<&DidValue>, rs_type: RichSchemaType,
+                                                            rs_name: &str, rs_version: &str) -> IndyResult<String> {
+        build_result!(GetRichSchemaObjectByMetadataOperation, submitter_did, rs_type, rs_name.to_string(), rs_version.to_string())
+    }
+
     #[logfn(Info)]
     pub fn build_auth_rules_request(&self, submitter_did: &DidValue, rules: AuthRules) -> IndyResult<String> {
         build_result!(AuthRulesOperation, Some(submitter_did), rules)
@@ -498,6 +520,80 @@ impl LedgerService {
         }
     }
 
+    #[logfn(Info)]
+    pub fn parse_action_response(&self, response: &str, txn_type: &str) -> IndyResult<HashMap<String, NodeActionResult>> {
+        let replies: HashMap<String, String> = serde_json::from_str(response)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid action response: map of node replies expected")?;
+
+        replies
+            .into_iter()
+            .map(|(node, reply)| Ok((node, self._parse_node_action_reply(&reply, txn_type)?)))
+            .collect()
+    }
+
+    fn _parse_node_action_reply(&self, reply: &str, txn_type: &str) -> IndyResult<NodeActionResult> {
+        if reply == "timeout" {
+            return Ok(NodeActionResult { status: NodeActionStatus::Timeout, data: None, reason: None });
+        }
+
+        let mut reply: serde_json::Value = serde_json::from_str(reply)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Node reply is invalid json")?;
+
+        let op = reply["op"].as_str().map(String::from);
+
+        match op.as_ref().map(String::as_str) {
+            Some("REPLY") => {
+                if reply["result"]["type"] != json!(txn_type) {
+                    return Err(err_msg(IndyErrorKind::InvalidTransaction, "Invalid response type"));
+                }
+
+                let mut result = reply["result"].take();
+                let data = match result["data"].take() {
+                    Value::Null => result,
+                    data => data
+                };
+
+                Ok(NodeActionResult { status: NodeActionStatus::Reply, data: Some(data), reason: None })
+            }
+            Some(op @ "REQNACK") | Some(op @ "REJECT") => {
+                let status = if op == "REQNACK" { NodeActionStatus::ReqNACK } else { NodeActionStatus::Reject };
+                let reason = reply["reason"].as_str().map(String::from);
+
+                Ok(NodeActionResult { status, data: None, reason })
+            }
+            _ => Err(err_msg(IndyErrorKind::InvalidTransaction, "Node reply has unknown `op` value"))
+        }
+    }
+
+    #[logfn(Info)]
+    pub fn parse_get_frozen_ledgers_response(&self, response: &str) -> IndyResult<HashMap<String, FrozenLedger>> {
+        let reply: Reply<GetFrozenLedgersReplyResult> = LedgerService::parse_response(response)?;
+
+        Ok(reply.result().data.unwrap_or_default())
+    }
+
+    // Handles replies to both GET_RICH_SCHEMA_OBJECT_BY_ID and GET_RICH_SCHEMA_OBJECT_BY_METADATA requests
+    #[logfn(Info)]
+    pub fn parse_get_rich_schema_object_response(&self, response: &str) -> IndyResult<(String, String)> {
+        let message: serde_json::Value = serde_json::from_str(&response)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Response is invalid json")?;
+
+        let rich_schema = if message["result"]["type"] == json!(GET_RICH_SCHEMA_OBJECT_BY_METADATA) {
+            LedgerService::parse_response::<GetRichSchemaObjectByMetadataReplyResult>(response)?.result().data()
+        } else {
+            LedgerService::parse_response::<GetRichSchemaObjectByIdReplyResult>(response)?.result().data()
+        };
+
+        let rich_schema = rich_schema
+            .ok_or_else(|| err_msg(IndyErrorKind::LedgerItemNotFound, "Rich schema object not found"))?;
+
+        let res = (rich_schema.id.clone(),
+                   serde_json::to_string(&rich_schema)
+                       .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RichSchema")?);
+
+        Ok(res)
+    }
+
     #[logfn(Info)]
     pub fn prepare_acceptance_data(&self, text: Option<&str>, version: Option<&str>, hash: Option<&str>, mechanism: &str, time: u64) -> IndyResult<TxnAuthrAgrmtAcceptanceData> {
         let taa_digest = match (text, version, hash) {
@@ -528,6 +624,28 @@ impl LedgerService {
         Ok(acceptance_data)
     }
 
+    #[logfn(Info)]
+    pub fn parse_get_txn_author_agreement_response(&self, response: &str) -> IndyResult<Option<TxnAuthorAgreement>> {
+        let reply: Reply<GetTxnAuthorAgreementReplyResult> = LedgerService::parse_response(response)?;
+
+        match reply.result().data {
+            Some(mut taa) => {
+                if taa.digest.is_none() {
+                    taa.digest = Some(hex::encode(self._calculate_hash(&taa.text, &taa.version)?));
+                }
+                Ok(Some(taa))
+            }
+            None => Ok(None)
+        }
+    }
+
+    #[logfn(Info)]
+    pub fn parse_get_acceptance_mechanisms_response(&self, response: &str) -> IndyResult<Option<AcceptanceMechanismsData>> {
+        let reply: Reply<GetAcceptanceMechanismsReplyResult> = LedgerService::parse_response(response)?;
+
+        Ok(reply.result().data)
+    }
+
     fn datetime_to_date_timestamp(time: u64) -> u64 {
         const SEC_IN_DAY: u64 = 86400;
         time / SEC_IN_DAY * SEC_IN_DAY
@@ -1193,6 +1311,44 @@ mod tests {
             let request = ledger_service.build_get_txn_author_agreement_request(Some(&identifier()), Some(&data)).unwrap();
             check_request(&request, expected_result);
         }
+
+        #[test]
+        fn parse_get_txn_author_agreement_response_works() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_TXN_AUTHR_AGRMT,
+                    "data": {"text": TEXT, "version": VERSION, "ratification_ts": 12345}
+                }
+            }).to_string();
+
+            let taa = ledger_service.parse_get_txn_author_agreement_response(&response).unwrap().unwrap();
+
+            let expected_taa = TxnAuthorAgreement {
+                text: TEXT.to_string(),
+                version: VERSION.to_string(),
+                digest: Some(hex::encode(ledger_service._calculate_hash(TEXT, VERSION).unwrap())),
+                ratification_ts: Some(12345),
+            };
+            assert_eq!(expected_taa, taa);
+        }
+
+        #[test]
+        fn parse_get_txn_author_agreement_response_works_for_not_set() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_TXN_AUTHR_AGRMT,
+                    "data": null
+                }
+            }).to_string();
+
+            assert!(ledger_service.parse_get_txn_author_agreement_response(&response).unwrap().is_none());
+        }
     }
 
     mod acceptance_mechanism {
@@ -1283,6 +1439,196 @@ mod tests {
             let res = ledger_service.build_get_acceptance_mechanisms_request(None, Some(TIMESTAMP), Some(VERSION));
             assert_kind!(IndyErrorKind::InvalidStructure, res);
         }
+
+        #[test]
+        fn parse_get_acceptance_mechanisms_response_works() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_TXN_AUTHR_AGRMT_AML,
+                    "data": {"aml": _aml(), "version": VERSION, "amlContext": CONTEXT}
+                }
+            }).to_string();
+
+            let aml = ledger_service.parse_get_acceptance_mechanisms_response(&response).unwrap().unwrap();
+
+            let expected_aml = AcceptanceMechanismsData {
+                aml: _aml(),
+                version: VERSION.to_string(),
+                aml_context: Some(CONTEXT.to_string()),
+            };
+            assert_eq!(expected_aml, aml);
+        }
+    }
+
+    mod action_response {
+        use super::*;
+
+        #[test]
+        fn parse_action_response_works() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "Node1": json!({"op": "REPLY", "result": {"type": GET_VALIDATOR_INFO, "data": {"alias": "Node1"}}}).to_string(),
+                "Node2": json!({"op": "REQNACK", "reason": "client request invalid"}).to_string(),
+                "Node3": "timeout",
+            }).to_string();
+
+            let result = ledger_service.parse_action_response(&response, GET_VALIDATOR_INFO).unwrap();
+
+            assert_eq!(3, result.len());
+            assert_eq!(NodeActionResult { status: NodeActionStatus::Reply, data: Some(json!({"alias": "Node1"})), reason: None }, result["Node1"]);
+            assert_eq!(NodeActionResult { status: NodeActionStatus::ReqNACK, data: None, reason: Some("client request invalid".to_string()) }, result["Node2"]);
+            assert_eq!(NodeActionResult { status: NodeActionStatus::Timeout, data: None, reason: None }, result["Node3"]);
+        }
+
+        #[test]
+        fn parse_action_response_works_for_result_without_data() {
+            let ledger_service = LedgerService::new();
+
+            let node_reply = json!({"op": "REPLY", "result": {"type": POOL_RESTART, "action": "start"}});
+            let response = json!({"Node1": node_reply.to_string()}).to_string();
+
+            let result = ledger_service.parse_action_response(&response, POOL_RESTART).unwrap();
+
+            assert_eq!(Some(json!({"type": POOL_RESTART, "action": "start"})), result["Node1"].data);
+        }
+
+        #[test]
+        fn parse_action_response_works_for_other_type() {
+            let ledger_service = LedgerService::new();
+
+            let node_reply = json!({"op": "REPLY", "result": {"type": GET_VALIDATOR_INFO, "data": {}}});
+            let response = json!({"Node1": node_reply.to_string()}).to_string();
+
+            let res = ledger_service.parse_action_response(&response, POOL_RESTART);
+            assert_kind!(IndyErrorKind::InvalidTransaction, res);
+        }
+
+        #[test]
+        fn parse_get_frozen_ledgers_response_works() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": GET_FROZEN_LEDGERS,
+                    "data": {"909": {"ledger": "ledger_root_hash", "state": "state_root_hash", "seq_no": 10}}
+                }
+            }).to_string();
+
+            let frozen_ledgers = ledger_service.parse_get_frozen_ledgers_response(&response).unwrap();
+
+            let expected_ledger = FrozenLedger {
+                ledger: "ledger_root_hash".to_string(),
+                state: "state_root_hash".to_string(),
+                seq_no: 10,
+            };
+            assert_eq!(1, frozen_ledgers.len());
+            assert_eq!(expected_ledger, frozen_ledgers["909"]);
+        }
+
+        #[test]
+        fn parse_get_frozen_ledgers_response_works_for_no_frozen_ledgers() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({"op": "REPLY", "result": {"type": GET_FROZEN_LEDGERS, "data": null}}).to_string();
+
+            let frozen_ledgers = ledger_service.parse_get_frozen_ledgers_response(&response).unwrap();
+            assert!(frozen_ledgers.is_empty());
+        }
+    }
+
+    mod rich_schema {
+        use super::*;
+
+        const RICH_SCHEMA_ID: &str = "did:sov:2f9F8ZmxuvDqRiqqY29x6dx9oU4qwFTkPbDpWtwGbdUsrCD";
+
+        fn _rich_schema() -> RichSchema {
+            RichSchema {
+                id: RICH_SCHEMA_ID.to_string(),
+                content: json!({"@context": {"name": "http://schema.org/name"}}).to_string(),
+                rs_name: "SISDriversLicense".to_string(),
+                rs_version: "1.0".to_string(),
+                rs_type: RichSchemaType::Ctx,
+                ver: "1".to_string(),
+            }
+        }
+
+        #[test]
+        fn build_rich_schema_request_works() {
+            let ledger_service = LedgerService::new();
+
+            let expected_result = json!({
+                "type": RICH_SCHEMA_CONTEXT,
+                "id": RICH_SCHEMA_ID,
+                "content": _rich_schema().content,
+                "rsName": "SISDriversLicense",
+                "rsVersion": "1.0",
+                "rsType": "ctx",
+                "ver": "1"
+            });
+
+            let request = ledger_service.build_rich_schema_request(&identifier(), _rich_schema()).unwrap();
+            check_request(&request, expected_result);
+        }
+
+        #[test]
+        fn build_rich_schema_request_works_for_every_type() {
+            let ledger_service = LedgerService::new();
+
+            for (rs_type, txn_type) in vec![(RichSchemaType::Sch, RICH_SCHEMA), (RichSchemaType::Map, RICH_SCHEMA_MAPPING), (RichSchemaType::Enc, RICH_SCHEMA_ENCODING)] {
+                let rich_schema = RichSchema { rs_type, .._rich_schema() };
+
+                let request = ledger_service.build_rich_schema_request(&identifier(), rich_schema).unwrap();
+                let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+                assert_eq!(json!(txn_type), request["operation"]["type"]);
+            }
+        }
+
+        #[test]
+        fn build_get_rich_schema_object_requests_work() {
+            let ledger_service = LedgerService::new();
+
+            let request = ledger_service.build_get_rich_schema_object_by_id_request(Some(&identifier()), RICH_SCHEMA_ID).unwrap();
+            check_request(&request, json!({"type": GET_RICH_SCHEMA_OBJECT_BY_ID, "id": RICH_SCHEMA_ID}));
+
+            let request = ledger_service.build_get_rich_schema_object_by_metadata_request(None, RichSchemaType::Sch, "SISDriversLicense", "1.0").unwrap();
+            check_request(&request, json!({
+                "type": GET_RICH_SCHEMA_OBJECT_BY_METADATA,
+                "rsType": "sch",
+                "rsName": "SISDriversLicense",
+                "rsVersion": "1.0"
+            }));
+        }
+
+        #[test]
+        fn parse_get_rich_schema_object_response_works() {
+            let ledger_service = LedgerService::new();
+
+            for txn_type in vec![GET_RICH_SCHEMA_OBJECT_BY_ID, GET_RICH_SCHEMA_OBJECT_BY_METADATA] {
+                let mut data = serde_json::to_value(_rich_schema()).unwrap();
+                data["from"] = json!(IDENTIFIER);
+
+                let response = json!({"op": "REPLY", "result": {"type": txn_type, "data": data}}).to_string();
+
+                let (id, rich_schema) = ledger_service.parse_get_rich_schema_object_response(&response).unwrap();
+                assert_eq!(RICH_SCHEMA_ID, id);
+                assert_eq!(_rich_schema(), serde_json::from_str::<RichSchema>(&rich_schema).unwrap());
+            }
+        }
+
+        #[test]
+        fn parse_get_rich_schema_object_response_works_for_not_found() {
+            let ledger_service = LedgerService::new();
+
+            let response = json!({"op": "REPLY", "result": {"type": GET_RICH_SCHEMA_OBJECT_BY_ID, "data": null}}).to_string();
+
+            let res = ledger_service.parse_get_rich_schema_object_response(&response);
+            assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+        }
     }
 
     #[test]
@@ -40,10 +40,10 @@ impl From<&IssuerCommand> for CommandMetric {
             IssuerCommand::CreateSchema(_, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateSchema
             }
-            IssuerCommand::CreateAndStoreCredentialDefinition(_, _, _, _, _, _, _) => {
+            IssuerCommand::CreateAndStoreCredentialDefinition(_, _, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateAndStoreCredentialDefinition
             }
-            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(_, _, _, _, _, _, _, _) => {
+            IssuerCommand::CreateAndStoreCredentialDefinitionContinue(_, _, _, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateAndStoreCredentialDefinitionContinue
             }
             IssuerCommand::RotateCredentialDefinitionStart(_, _, _, _) => {
@@ -64,6 +64,9 @@ impl From<&IssuerCommand> for CommandMetric {
             IssuerCommand::CreateCredential(_, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateCredential
             }
+            IssuerCommand::CreateCredentials(_, _, _, _, _, _) => {
+                CommandMetric::IssuerCommandCreateCredentials
+            }
             IssuerCommand::RevokeCredential(_, _, _, _, _) => {
                 CommandMetric::IssuerCommandRevokeCredential
             }
@@ -85,6 +88,7 @@ impl From<&ProverCommand> for CommandMetric {
             ProverCommand::GetCredentials(_, _, _) => { CommandMetric::ProverCommandGetCredentials }
             ProverCommand::GetCredential(_, _, _) => { CommandMetric::ProverCommandGetCredential }
             ProverCommand::DeleteCredential(_, _, _) => { CommandMetric::ProverCommandDeleteCredential }
+            ProverCommand::DeleteCredentialWithRelated(_, _, _, _) => { CommandMetric::ProverCommandDeleteCredentialWithRelated }
             ProverCommand::SearchCredentials(_, _, _) => { CommandMetric::ProverCommandSearchCredentials }
             ProverCommand::FetchCredentials(_, _, _) => { CommandMetric::ProverCommandFetchCredentials }
             ProverCommand::CloseCredentialsSearch(_, _) => { CommandMetric::ProverCommandCloseCredentialsSearch }
@@ -93,8 +97,13 @@ impl From<&ProverCommand> for CommandMetric {
             ProverCommand::FetchCredentialForProofReq(_, _, _, _) => { CommandMetric::ProverCommandFetchCredentialForProofReq }
             ProverCommand::CloseCredentialsSearchForProofReq(_, _) => { CommandMetric::ProverCommandCloseCredentialsSearchForProofReq }
             ProverCommand::CreateProof(_, _, _, _, _, _, _, _) => { CommandMetric::ProverCommandCreateProof }
+            ProverCommand::CreateProofWithDetails(_, _, _, _, _, _, _, _) => { CommandMetric::ProverCommandCreateProofWithDetails }
+            ProverCommand::CreateProofs(_, _, _, _, _, _, _) => { CommandMetric::ProverCommandCreateProofs }
+            ProverCommand::CreateProofsContinue(_, _, _) => { CommandMetric::ProverCommandCreateProofsContinue }
             ProverCommand::CreateRevocationState(_, _, _, _, _, _) => { CommandMetric::ProverCommandCreateRevocationState }
             ProverCommand::UpdateRevocationState(_, _, _, _, _, _, _) => { CommandMetric::ProverCommandUpdateRevocationState }
+            ProverCommand::CredentialToW3C(_, _) => { CommandMetric::ProverCommandCredentialToW3C }
+            ProverCommand::CredentialFromW3C(_, _) => { CommandMetric::ProverCommandCredentialFromW3C }
         }
     }
 }
@@ -103,7 +112,10 @@ impl From<&VerifierCommand> for CommandMetric {
     fn from(cmd: &VerifierCommand) -> Self {
         match cmd {
             VerifierCommand::VerifyProof(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProof }
+            VerifierCommand::VerifyProofWithDetails(_, _, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProofWithDetails }
             VerifierCommand::GenerateNonce(_) => { CommandMetric::VerifierCommandGenerateNonce }
+            VerifierCommand::CreateProofRequestFromPresentationDefinition(_, _) => { CommandMetric::VerifierCommandCreateProofRequestFromPresentationDefinition }
+            VerifierCommand::CreatePresentationSubmission(_, _, _) => { CommandMetric::VerifierCommandCreatePresentationSubmission }
         }
     }
 }
@@ -118,6 +130,8 @@ impl From<&Command> for CommandMetric {
                     AnoncredsCommand::Prover(cmd) => { cmd.into() }
                     AnoncredsCommand::Verifier(cmd) => { cmd.into() }
                     AnoncredsCommand::ToUnqualified(_, _) => { CommandMetric::AnoncredsCommandToUnqualified }
+                    AnoncredsCommand::EncodeAttributeValue(_, _) => { CommandMetric::AnoncredsCommandEncodeAttributeValue }
+                    AnoncredsCommand::ValidateCredentialValues(_, _) => { CommandMetric::AnoncredsCommandValidateCredentialValues }
                 }
             }
             Command::BlobStorage(cmd) => {
@@ -139,6 +153,15 @@ impl From<&Command> for CommandMetric {
                     CryptoCommand::AnonymousDecrypt(_, _, _, _) => { CommandMetric::CryptoCommandAnonymousDecrypt }
                     CryptoCommand::PackMessage(_, _, _, _, _) => { CommandMetric::CryptoCommandPackMessage }
                     CryptoCommand::UnpackMessage(_, _, _) => { CommandMetric::CryptoCommandUnpackMessage }
+                    CryptoCommand::Ed25519ToX25519(_, _) => { CommandMetric::CryptoCommandEd25519ToX25519 }
+                    CryptoCommand::X25519DiffieHellman(_, _, _, _) => { CommandMetric::CryptoCommandX25519DiffieHellman }
+                    CryptoCommand::SignJws(_, _, _, _, _) => { CommandMetric::CryptoCommandSignJws }
+                    CryptoCommand::VerifyJws(_, _) => { CommandMetric::CryptoCommandVerifyJws }
+                    CryptoCommand::BlsCreateKeys(_, _) => { CommandMetric::CryptoCommandBlsCreateKeys }
+                    CryptoCommand::BlsSign(_, _, _) => { CommandMetric::CryptoCommandBlsSign }
+                    CryptoCommand::BlsAggregateSignatures(_, _) => { CommandMetric::CryptoCommandBlsAggregateSignatures }
+                    CryptoCommand::BlsVerifyMultiSig(_, _, _, _) => { CommandMetric::CryptoCommandBlsVerifyMultiSig }
+                    CryptoCommand::RegisterKeyManagement(_, _, _, _, _) => { CommandMetric::CryptoCommandRegisterKeyManagement }
                 }
             }
             Command::Ledger(cmd) => {
@@ -147,6 +170,7 @@ impl From<&Command> for CommandMetric {
                     LedgerCommand::SubmitRequest(_, _, _) => { CommandMetric::LedgerCommandSubmitRequest }
                     LedgerCommand::SubmitAck(_, _) => { CommandMetric::LedgerCommandSubmitAck }
                     LedgerCommand::SubmitAction(_, _, _, _, _) => { CommandMetric::LedgerCommandSubmitAction }
+                    LedgerCommand::SubmitReadRequest(_, _, _, _) => { CommandMetric::LedgerCommandSubmitReadRequest }
                     LedgerCommand::SignRequest(_, _, _, _) => { CommandMetric::LedgerCommandSignRequest }
                     LedgerCommand::MultiSignRequest(_, _, _, _) => { CommandMetric::LedgerCommandMultiSignRequest }
                     LedgerCommand::BuildGetDdoRequest(_, _, _) => { CommandMetric::LedgerCommandBuildGetDdoRequest }
@@ -184,15 +208,36 @@ impl From<&Command> for CommandMetric {
                     LedgerCommand::GetSchemaContinue(_, _, _) => { CommandMetric::LedgerCommandGetSchemaContinue }
                     LedgerCommand::GetCredDef(_, _, _, _) => { CommandMetric::LedgerCommandGetCredDef }
                     LedgerCommand::GetCredDefContinue(_, _, _) => { CommandMetric::LedgerCommandGetCredDefContinue }
+                    LedgerCommand::GetRevocRegDef(_, _, _, _) => { CommandMetric::LedgerCommandGetRevocRegDef }
+                    LedgerCommand::GetRevocRegDefContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegDefContinue }
+                    LedgerCommand::GetRevocReg(_, _, _, _, _) => { CommandMetric::LedgerCommandGetRevocReg }
+                    LedgerCommand::GetRevocRegContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegContinue }
+                    LedgerCommand::GetRevocRegDelta(_, _, _, _, _, _) => { CommandMetric::LedgerCommandGetRevocRegDelta }
+                    LedgerCommand::GetRevocRegDeltaContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegDeltaContinue }
                     LedgerCommand::BuildTxnAuthorAgreementRequest(_, _, _, _, _, _) => { CommandMetric::LedgerCommandBuildTxnAuthorAgreementRequest }
                     LedgerCommand::BuildDisableAllTxnAuthorAgreementsRequest(_, _) => { CommandMetric::LedgerCommandBuildDisableAllTxnAuthorAgreementsRequest }
                     LedgerCommand::BuildGetTxnAuthorAgreementRequest(_, _, _) => { CommandMetric::LedgerCommandBuildGetTxnAuthorAgreementRequest }
                     LedgerCommand::BuildAcceptanceMechanismRequests(_, _, _, _, _) => { CommandMetric::LedgerCommandBuildAcceptanceMechanismRequests }
                     LedgerCommand::BuildGetAcceptanceMechanismsRequest(_, _, _, _) => { CommandMetric::LedgerCommandBuildGetAcceptanceMechanismsRequest }
                     LedgerCommand::AppendTxnAuthorAgreementAcceptanceToRequest(_, _, _, _, _, _, _) => { CommandMetric::LedgerCommandAppendTxnAuthorAgreementAcceptanceToRequest }
+                    LedgerCommand::SetTxnAuthorAgreementAcceptance(_, _, _, _, _, _) => { CommandMetric::LedgerCommandSetTxnAuthorAgreementAcceptance }
+                    LedgerCommand::GetActiveTxnAuthorAgreement(_, _, _) => { CommandMetric::LedgerCommandGetActiveTxnAuthorAgreement }
+                    LedgerCommand::GetActiveTxnAuthorAgreementContinue(_, _, _, _) => { CommandMetric::LedgerCommandGetActiveTxnAuthorAgreementContinue }
+                    LedgerCommand::GetActiveAcceptanceMechanismsContinue(_, _, _) => { CommandMetric::LedgerCommandGetActiveAcceptanceMechanismsContinue }
                     LedgerCommand::AppendRequestEndorser(_, _, _) => { CommandMetric::LedgerCommandAppendRequestEndorser }
+                    LedgerCommand::SignRequestForEndorser(_, _, _, _, _) => { CommandMetric::LedgerCommandSignRequestForEndorser }
+                    LedgerCommand::EndorseAndSubmitRequest(_, _, _, _, _) => { CommandMetric::LedgerCommandEndorseAndSubmitRequest }
                     LedgerCommand::BuildGetFrozenLedgersRequest(_,_,) => { CommandMetric::LedgerCommandBuildGetFrozenLedgersRequest }
                     LedgerCommand::BuildLedgersFreezeRequest(_,_,_,) => { CommandMetric::LedgerCommandBuildLedgersFreezeRequest }
+                    LedgerCommand::ParseGetFrozenLedgersResponse(_, _) => { CommandMetric::LedgerCommandParseGetFrozenLedgersResponse }
+                    LedgerCommand::BuildRichSchemaRequest(_, _, _) => { CommandMetric::LedgerCommandBuildRichSchemaRequest }
+                    LedgerCommand::BuildGetRichSchemaObjectByIdRequest(_, _, _) => { CommandMetric::LedgerCommandBuildGetRichSchemaObjectByIdRequest }
+                    LedgerCommand::BuildGetRichSchemaObjectByMetadataRequest(_, _, _, _, _) => { CommandMetric::LedgerCommandBuildGetRichSchemaObjectByMetadataRequest }
+                    LedgerCommand::ParseGetRichSchemaObjectResponse(_, _) => { CommandMetric::LedgerCommandParseGetRichSchemaObjectResponse }
+                    LedgerCommand::GetRichSchemaObject(_, _, _, _) => { CommandMetric::LedgerCommandGetRichSchemaObject }
+                    LedgerCommand::GetRichSchemaObjectContinue(_, _) => { CommandMetric::LedgerCommandGetRichSchemaObjectContinue }
+                    LedgerCommand::ParsePoolRestartResponse(_, _) => { CommandMetric::LedgerCommandParsePoolRestartResponse }
+                    LedgerCommand::ParseGetValidatorInfoResponse(_, _) => { CommandMetric::LedgerCommandParseGetValidatorInfoResponse }
                 }
             }
             Command::Pool(cmd) => {
@@ -207,6 +252,9 @@ impl From<&Command> for CommandMetric {
                     PoolCommand::Refresh(_, _) => { CommandMetric::PoolCommandRefresh }
                     PoolCommand::RefreshAck(_, _) => { CommandMetric::PoolCommandRefreshAck }
                     PoolCommand::SetProtocolVersion(_, _) => { CommandMetric::PoolCommandSetProtocolVersion }
+                    PoolCommand::GetPoolHandleForNetwork(_, _) => { CommandMetric::PoolCommandGetPoolHandleForNetwork }
+                    PoolCommand::ValidateGenesisTxns(_, _, _) => { CommandMetric::PoolCommandValidateGenesisTxns }
+                    PoolCommand::DiffGenesisTxns(_, _, _) => { CommandMetric::PoolCommandDiffGenesisTxns }
                 }
             }
             Command::Did(cmd) => {
@@ -227,11 +275,16 @@ impl From<&Command> for CommandMetric {
                     DidCommand::GetNymAck(_, _, _, _) => { CommandMetric::DidCommandGetNymAck }
                     DidCommand::GetAttribAck(_, _, _) => { CommandMetric::DidCommandGetAttribAck }
                     DidCommand::QualifyDid(_, _, _, _) => { CommandMetric::DidCommandQualifyDid }
+                    DidCommand::RotateKey(_, _, _, _, _) => { CommandMetric::DidCommandRotateKey }
+                    DidCommand::RotateKeyAck(_, _, _, _, _, _) => { CommandMetric::DidCommandRotateKeyAck }
                 }
             }
             Command::Wallet(cmd) => {
                 match cmd {
                     WalletCommand::RegisterWalletType(_, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) => { CommandMetric::WalletCommandRegisterWalletType }
+                    WalletCommand::RegisterKeyWrapper(_, _, _, _, _) => { CommandMetric::WalletCommandRegisterKeyWrapper }
+                    WalletCommand::RegisterStorageBatch(_, _, _) => { CommandMetric::WalletCommandRegisterStorageBatch }
+                    WalletCommand::RegisterStorageSearchChunk(_, _, _, _) => { CommandMetric::WalletCommandRegisterStorageSearchChunk }
                     WalletCommand::Create(_, _, _) => { CommandMetric::WalletCommandCreate }
                     WalletCommand::CreateContinue(_, _, _, _, _) => { CommandMetric::WalletCommandCreateContinue }
                     WalletCommand::Open(_, _, _) => { CommandMetric::WalletCommandOpen }
@@ -243,8 +296,16 @@ impl From<&Command> for CommandMetric {
                     WalletCommand::ExportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandExportContinue }
                     WalletCommand::Import(_, _, _, _) => { CommandMetric::WalletCommandImport }
                     WalletCommand::ImportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandImportContinue }
+                    WalletCommand::ImportRecords(_, _, _) => { CommandMetric::WalletCommandImportRecords }
+                    WalletCommand::ImportRecordsContinue(_, _, _) => { CommandMetric::WalletCommandImportRecordsContinue }
+                    WalletCommand::StartReencryption(_, _, _) => { CommandMetric::WalletCommandStartReencryption }
+                    WalletCommand::StartReencryptionContinue(_, _, _, _) => { CommandMetric::WalletCommandStartReencryptionContinue }
+                    WalletCommand::ReencryptRecords(_, _, _) => { CommandMetric::WalletCommandReencryptRecords }
+                    WalletCommand::GetReencryptionStatus(_, _) => { CommandMetric::WalletCommandGetReencryptionStatus }
                     WalletCommand::GenerateKey(_, _) => { CommandMetric::WalletCommandGenerateKey }
                     WalletCommand::DeriveKey(_, _) => { CommandMetric::WalletCommandDeriveKey }
+                    WalletCommand::SplitKey(_, _, _) => { CommandMetric::WalletCommandSplitKey }
+                    WalletCommand::GenerateMnemonic(_, _) => { CommandMetric::WalletCommandGenerateMnemonic }
                 }
             }
             Command::Pairwise(cmd) => {
@@ -268,6 +329,9 @@ impl From<&Command> for CommandMetric {
                     NonSecretsCommand::OpenSearch(_, _, _, _, _) => { CommandMetric::NonSecretsCommandOpenSearch }
                     NonSecretsCommand::FetchSearchNextRecords(_, _, _, _) => { CommandMetric::NonSecretsCommandFetchSearchNextRecords }
                     NonSecretsCommand::CloseSearch(_, _) => { CommandMetric::NonSecretsCommandCloseSearch }
+                    NonSecretsCommand::AddRecords(_, _, _) => { CommandMetric::NonSecretsCommandAddRecords }
+                    NonSecretsCommand::DeleteRecords(_, _, _) => { CommandMetric::NonSecretsCommandDeleteRecords }
+                    NonSecretsCommand::WithRecords(_, _, _) => { CommandMetric::NonSecretsCommandWithRecords }
                 }
             }
             Command::Payments(cmd) => {
@@ -314,8 +378,16 @@ impl From<&Command> for CommandMetric {
                     CacheCommand::GetSchemaContinue(_, _, _, _) => { CommandMetric::CacheCommandGetSchemaContinue }
                     CacheCommand::GetCredDef(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetCredDef }
                     CacheCommand::GetCredDefContinue(_, _, _, _) => { CommandMetric::CacheCommandGetCredDefContinue }
+                    CacheCommand::GetRevRegDef(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetRevRegDef }
                     CacheCommand::PurgeSchemaCache(_, _, _) => { CommandMetric::CacheCommandPurgeSchemaCache }
                     CacheCommand::PurgeCredDefCache(_, _, _) => { CommandMetric::CacheCommandPurgeCredDefCache }
+                    CacheCommand::PurgeRevRegDefCache(_, _, _) => { CommandMetric::CacheCommandPurgeRevRegDefCache }
+                    CacheCommand::GetRevRegDefContinue(_, _, _, _) => { CommandMetric::CacheCommandGetRevRegDefContinue }
+                    CacheCommand::GetRichSchemaObject(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetRichSchemaObject }
+                    CacheCommand::GetRichSchemaObjectContinue(_, _, _, _) => { CommandMetric::CacheCommandGetRichSchemaObjectContinue }
+                    CacheCommand::PurgeRichSchemaCache(_, _, _) => { CommandMetric::CacheCommandPurgeRichSchemaCache }
+                    CacheCommand::PrefetchProofArtifacts(_, _, _, _, _, _, _) => { CommandMetric::CacheCommandPrefetchProofArtifacts }
+                    CacheCommand::PrefetchProofArtifactsContinue(_, _, _) => { CommandMetric::CacheCommandPrefetchProofArtifactsContinue }
                 }
             }
             Command::Metrics(cmd) => {
@@ -339,6 +411,7 @@ pub enum CommandMetric {
     IssuerCommandCreateAndStoreRevocationRegistry,
     IssuerCommandCreateCredentialOffer,
     IssuerCommandCreateCredential,
+    IssuerCommandCreateCredentials,
     IssuerCommandRevokeCredential,
     IssuerCommandMergeRevocationRegistryDeltas,
     // ProverCommand
@@ -350,6 +423,7 @@ pub enum CommandMetric {
     ProverCommandGetCredentials,
     ProverCommandGetCredential,
     ProverCommandDeleteCredential,
+    ProverCommandDeleteCredentialWithRelated,
     ProverCommandSearchCredentials,
     ProverCommandFetchCredentials,
     ProverCommandCloseCredentialsSearch,
@@ -358,13 +432,23 @@ pub enum CommandMetric {
     ProverCommandFetchCredentialForProofReq,
     ProverCommandCloseCredentialsSearchForProofReq,
     ProverCommandCreateProof,
+    ProverCommandCreateProofWithDetails,
+    ProverCommandCreateProofs,
+    ProverCommandCreateProofsContinue,
     ProverCommandCreateRevocationState,
     ProverCommandUpdateRevocationState,
+    ProverCommandCredentialToW3C,
+    ProverCommandCredentialFromW3C,
     // VerifierCommand
     VerifierCommandVerifyProof,
+    VerifierCommandVerifyProofWithDetails,
     VerifierCommandGenerateNonce,
+    VerifierCommandCreateProofRequestFromPresentationDefinition,
+    VerifierCommandCreatePresentationSubmission,
     // AnoncredsCommand
     AnoncredsCommandToUnqualified,
+    AnoncredsCommandEncodeAttributeValue,
+    AnoncredsCommandValidateCredentialValues,
     // BlobStorage
     BlobStorageCommandOpenReader,
     BlobStorageCommandOpenWriter,
@@ -380,11 +464,21 @@ pub enum CommandMetric {
     CryptoCommandAnonymousDecrypt,
     CryptoCommandPackMessage,
     CryptoCommandUnpackMessage,
+    CryptoCommandEd25519ToX25519,
+    CryptoCommandX25519DiffieHellman,
+    CryptoCommandSignJws,
+    CryptoCommandVerifyJws,
+    CryptoCommandBlsCreateKeys,
+    CryptoCommandBlsSign,
+    CryptoCommandBlsAggregateSignatures,
+    CryptoCommandBlsVerifyMultiSig,
+    CryptoCommandRegisterKeyManagement,
     LedgerCommandSignAndSubmitRequest,
     // LedgerCommand
     LedgerCommandSubmitRequest,
     LedgerCommandSubmitAck,
     LedgerCommandSubmitAction,
+    LedgerCommandSubmitReadRequest,
     LedgerCommandSignRequest,
     LedgerCommandMultiSignRequest,
     LedgerCommandBuildGetDdoRequest,
@@ -422,15 +516,36 @@ pub enum CommandMetric {
     LedgerCommandGetSchemaContinue,
     LedgerCommandGetCredDef,
     LedgerCommandGetCredDefContinue,
+    LedgerCommandGetRevocRegDef,
+    LedgerCommandGetRevocRegDefContinue,
+    LedgerCommandGetRevocReg,
+    LedgerCommandGetRevocRegContinue,
+    LedgerCommandGetRevocRegDelta,
+    LedgerCommandGetRevocRegDeltaContinue,
     LedgerCommandBuildTxnAuthorAgreementRequest,
     LedgerCommandBuildDisableAllTxnAuthorAgreementsRequest,
     LedgerCommandBuildGetTxnAuthorAgreementRequest,
     LedgerCommandBuildAcceptanceMechanismRequests,
     LedgerCommandBuildGetAcceptanceMechanismsRequest,
     LedgerCommandAppendTxnAuthorAgreementAcceptanceToRequest,
+    LedgerCommandSetTxnAuthorAgreementAcceptance,
+    LedgerCommandGetActiveTxnAuthorAgreement,
+    LedgerCommandGetActiveTxnAuthorAgreementContinue,
+    LedgerCommandGetActiveAcceptanceMechanismsContinue,
     LedgerCommandAppendRequestEndorser,
+    LedgerCommandSignRequestForEndorser,
+    LedgerCommandEndorseAndSubmitRequest,
     LedgerCommandBuildGetFrozenLedgersRequest,
     LedgerCommandBuildLedgersFreezeRequest,
+    LedgerCommandParseGetFrozenLedgersResponse,
+    LedgerCommandBuildRichSchemaRequest,
+    LedgerCommandBuildGetRichSchemaObjectByIdRequest,
+    LedgerCommandBuildGetRichSchemaObjectByMetadataRequest,
+    LedgerCommandParseGetRichSchemaObjectResponse,
+    LedgerCommandGetRichSchemaObject,
+    LedgerCommandGetRichSchemaObjectContinue,
+    LedgerCommandParsePoolRestartResponse,
+    LedgerCommandParseGetValidatorInfoResponse,
     // PoolCommand
     PoolCommandCreate,
     PoolCommandDelete,
@@ -442,6 +557,9 @@ pub enum CommandMetric {
     PoolCommandRefresh,
     PoolCommandRefreshAck,
     PoolCommandSetProtocolVersion,
+    PoolCommandGetPoolHandleForNetwork,
+    PoolCommandValidateGenesisTxns,
+    PoolCommandDiffGenesisTxns,
     // DidCommand
     DidCommandCreateAndStoreMyDid,
     DidCommandReplaceKeysStart,
@@ -459,8 +577,13 @@ pub enum CommandMetric {
     DidCommandGetNymAck,
     DidCommandGetAttribAck,
     DidCommandQualifyDid,
+    DidCommandRotateKey,
+    DidCommandRotateKeyAck,
     // WalletCommand
     WalletCommandRegisterWalletType,
+    WalletCommandRegisterKeyWrapper,
+    WalletCommandRegisterStorageBatch,
+    WalletCommandRegisterStorageSearchChunk,
     WalletCommandCreate,
     WalletCommandCreateContinue,
     WalletCommandOpen,
@@ -472,8 +595,16 @@ pub enum CommandMetric {
     WalletCommandExportContinue,
     WalletCommandImport,
     WalletCommandImportContinue,
+    WalletCommandImportRecords,
+    WalletCommandImportRecordsContinue,
+    WalletCommandStartReencryption,
+    WalletCommandStartReencryptionContinue,
+    WalletCommandReencryptRecords,
+    WalletCommandGetReencryptionStatus,
     WalletCommandGenerateKey,
     WalletCommandDeriveKey,
+    WalletCommandSplitKey,
+    WalletCommandGenerateMnemonic,
     // PairwiseCommand
     PairwiseCommandPairwiseExists,
     PairwiseCommandCreatePairwise,
@@ -491,6 +622,9 @@ pub enum CommandMetric {
     NonSecretsCommandOpenSearch,
     NonSecretsCommandFetchSearchNextRecords,
     NonSecretsCommandCloseSearch,
+    NonSecretsCommandAddRecords,
+    NonSecretsCommandDeleteRecords,
+    NonSecretsCommandWithRecords,
     // PaymentsCommand
     PaymentsCommandRegisterMethod,
     PaymentsCommandCreateAddress,
@@ -531,8 +665,16 @@ pub enum CommandMetric {
     CacheCommandGetSchemaContinue,
     CacheCommandGetCredDef,
     CacheCommandGetCredDefContinue,
+    CacheCommandGetRevRegDef,
     CacheCommandPurgeSchemaCache,
     CacheCommandPurgeCredDefCache,
+    CacheCommandPurgeRevRegDefCache,
+    CacheCommandGetRevRegDefContinue,
+    CacheCommandGetRichSchemaObject,
+    CacheCommandGetRichSchemaObjectContinue,
+    CacheCommandPurgeRichSchemaCache,
+    CacheCommandPrefetchProofArtifacts,
+    CacheCommandPrefetchProofArtifactsContinue,
     // MetricsCommand
     MetricsCommandCollectMetrics,
     // Exit
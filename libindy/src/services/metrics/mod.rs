@@ -3,8 +3,8 @@ use convert_case::{Case, Casing};
 use indy_api_types::errors::{IndyErrorKind, IndyResult, IndyResultExt};
 use models::{MetricsValue, CommandCounters};
 use serde_json::{Map, Value};
-use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 pub mod command_metrics;
 pub mod models;
@@ -12,24 +12,24 @@ pub mod models;
 const COMMANDS_COUNT: usize = MetricsService::commands_count();
 
 pub struct MetricsService {
-    queued_counters: RefCell<[CommandCounters; COMMANDS_COUNT]>,
-    executed_counters: RefCell<[CommandCounters; COMMANDS_COUNT]>,
+    queued_counters: Mutex<[CommandCounters; COMMANDS_COUNT]>,
+    executed_counters: Mutex<[CommandCounters; COMMANDS_COUNT]>,
 }
 
 impl MetricsService {
     pub fn new() -> Self {
         MetricsService {
-            queued_counters: RefCell::new([CommandCounters::new(); COMMANDS_COUNT]),
-            executed_counters: RefCell::new([CommandCounters::new(); COMMANDS_COUNT]),
+            queued_counters: Mutex::new([CommandCounters::new(); COMMANDS_COUNT]),
+            executed_counters: Mutex::new([CommandCounters::new(); COMMANDS_COUNT]),
         }
     }
 
     pub fn cmd_left_queue(&self, command_metric: CommandMetric, duration: u128) {
-        self.queued_counters.borrow_mut()[command_metric as usize].add(duration);
+        self.queued_counters.lock().unwrap()[command_metric as usize].add(duration);
     }
 
     pub fn cmd_executed(&self, command_metric: CommandMetric, duration: u128) {
-        self.executed_counters.borrow_mut()[command_metric as usize].add(duration);
+        self.executed_counters.lock().unwrap()[command_metric as usize].add(duration);
     }
 
     pub fn cmd_name(index: usize) -> String {
@@ -55,6 +55,10 @@ impl MetricsService {
         let mut commands_duration_ms = Vec::new();
         let mut commands_duration_ms_bucket = Vec::new();
 
+        // Counters are updated by all command workers, so consistent copies are taken
+        let executed_counters = *self.executed_counters.lock().unwrap();
+        let queued_counters = *self.queued_counters.lock().unwrap();
+
         for index in (0..MetricsService::commands_count()).rev() {
             let command_name = MetricsService::cmd_name(index);
             let tags_executed = MetricsService::get_command_tags(
@@ -66,15 +70,15 @@ impl MetricsService {
                 String::from("queued"),
             );
 
-            commands_count.push(self.get_metric_json(self.executed_counters.borrow()[index].count as usize, tags_executed.clone())?);
-            commands_count.push(self.get_metric_json(self.queued_counters.borrow()[index].count as usize, tags_queued.clone())?);
+            commands_count.push(self.get_metric_json(executed_counters[index].count as usize, tags_executed.clone())?);
+            commands_count.push(self.get_metric_json(queued_counters[index].count as usize, tags_queued.clone())?);
 
-            commands_duration_ms.push(self.get_metric_json(self.executed_counters.borrow()[index].duration_ms_sum as usize, tags_executed.clone())?);
-            commands_duration_ms.push(self.get_metric_json(self.queued_counters.borrow()[index].duration_ms_sum as usize,tags_queued.clone())?);
+            commands_duration_ms.push(self.get_metric_json(executed_counters[index].duration_ms_sum as usize, tags_executed.clone())?);
+            commands_duration_ms.push(self.get_metric_json(queued_counters[index].duration_ms_sum as usize,tags_queued.clone())?);
 
-            for index_bucket in (0..self.executed_counters.borrow()[index].duration_ms_bucket.len()).rev() {
-                let executed_bucket = self.executed_counters.borrow()[index].duration_ms_bucket[index_bucket];
-                let queued_bucket = self.queued_counters.borrow()[index].duration_ms_bucket[index_bucket];
+            for index_bucket in (0..executed_counters[index].duration_ms_bucket.len()).rev() {
+                let executed_bucket = executed_counters[index].duration_ms_bucket[index_bucket];
+                let queued_bucket = queued_counters[index].duration_ms_bucket[index_bucket];
 
                 commands_duration_ms_bucket.push(self.get_metric_json(executed_bucket as usize, tags_executed.clone())?);
                 commands_duration_ms_bucket.push(self.get_metric_json(queued_bucket as usize, tags_queued.clone())?);
@@ -117,8 +121,8 @@ mod test {
     #[test]
     fn test_counters_are_initialized() {
         let metrics_service = MetricsService::new();
-        assert_eq!(metrics_service.queued_counters.borrow().len(), COMMANDS_COUNT);
-        assert_eq!(metrics_service.executed_counters.borrow().len(), COMMANDS_COUNT);
+        assert_eq!(metrics_service.queued_counters.lock().unwrap().len(), COMMANDS_COUNT);
+        assert_eq!(metrics_service.executed_counters.lock().unwrap().len(), COMMANDS_COUNT);
     }
 
     #[test]
@@ -130,38 +134,22 @@ mod test {
 
         metrics_service.cmd_left_queue(index, duration1);
 
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize].count, 1);
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize].duration_ms_sum, duration1);
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize]
-                       .duration_ms_bucket[
-                            metrics_service.queued_counters.borrow()[index as usize]
-                            .duration_ms_bucket.len()-1
-                       ],
-                    1
-        );
+        let queued = metrics_service.queued_counters.lock().unwrap()[index as usize];
+        assert_eq!(queued.count, 1);
+        assert_eq!(queued.duration_ms_sum, duration1);
+        assert_eq!(queued.duration_ms_bucket[queued.duration_ms_bucket.len() - 1], 1);
 
         metrics_service.cmd_left_queue(index, duration2);
 
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize].count, 1 + 1);
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize].duration_ms_sum,
-                   duration1 + duration2);
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize]
-                        .duration_ms_bucket[
-                            metrics_service.queued_counters.borrow()[index as usize]
-                           .duration_ms_bucket.len()-1
-                       ],
-                    2
-        );
+        let queued = metrics_service.queued_counters.lock().unwrap()[index as usize];
+        assert_eq!(queued.count, 1 + 1);
+        assert_eq!(queued.duration_ms_sum, duration1 + duration2);
+        assert_eq!(queued.duration_ms_bucket[queued.duration_ms_bucket.len() - 1], 2);
 
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize].count, 0);
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize].duration_ms_sum, 0);
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize]
-                       .duration_ms_bucket[
-                            metrics_service.executed_counters.borrow()[index as usize]
-                            .duration_ms_bucket.len()-1
-                       ],
-                    0
-        );
+        let executed = metrics_service.executed_counters.lock().unwrap()[index as usize];
+        assert_eq!(executed.count, 0);
+        assert_eq!(executed.duration_ms_sum, 0);
+        assert_eq!(executed.duration_ms_bucket[executed.duration_ms_bucket.len() - 1], 0);
     }
 
     #[test]
@@ -173,15 +161,15 @@ mod test {
 
         metrics_service.cmd_executed(index, duration1);
 
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize].count, 1);
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize].duration_ms_sum, duration1);
+        assert_eq!(metrics_service.executed_counters.lock().unwrap()[index as usize].count, 1);
+        assert_eq!(metrics_service.executed_counters.lock().unwrap()[index as usize].duration_ms_sum, duration1);
 
         metrics_service.cmd_executed(index, duration2);
 
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize].count, 0);
-        assert_eq!(metrics_service.queued_counters.borrow()[index as usize].duration_ms_sum, 0);
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize].count, 1 + 1);
-        assert_eq!(metrics_service.executed_counters.borrow()[index as usize].duration_ms_sum, duration1 + duration2);
+        assert_eq!(metrics_service.queued_counters.lock().unwrap()[index as usize].count, 0);
+        assert_eq!(metrics_service.queued_counters.lock().unwrap()[index as usize].duration_ms_sum, 0);
+        assert_eq!(metrics_service.executed_counters.lock().unwrap()[index as usize].count, 1 + 1);
+        assert_eq!(metrics_service.executed_counters.lock().unwrap()[index as usize].duration_ms_sum, duration1 + duration2);
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use indy_api_types::errors::prelude::*;
+use crate::domain::pool::ReadRequestOptions;
 use crate::services::pool::events::PoolEvent;
 
 use super::zmq;
@@ -43,15 +44,24 @@ impl Commander {
             let timeout = LittleEndian::read_i32(cmd_parts[2].as_slice());
             let timeout = if timeout == -1 { None } else { Some(timeout) };
 
-            let nodes = if let Some(nodes) = cmd_parts.get(3) {
-                Some(String::from_utf8(nodes.clone())
-                    .to_indy(IndyErrorKind::InvalidState, "Invalid utf8 sequence in command") // FIXME: review kind
+            let nodes = match cmd_parts.get(3) {
+                Some(nodes) if !nodes.is_empty() => {
+                    Some(String::from_utf8(nodes.clone())
+                        .to_indy(IndyErrorKind::InvalidState, "Invalid utf8 sequence in command") // FIXME: review kind
+                        .map_err(map_err_trace!()).ok()?)
+                }
+                _ => None
+            };
+
+            let read_options = if let Some(read_options) = cmd_parts.get(4) {
+                Some(serde_json::from_slice::<ReadRequestOptions>(read_options)
+                    .to_indy(IndyErrorKind::InvalidState, "Invalid read request options in command")
                     .map_err(map_err_trace!()).ok()?)
             } else {
                 None
             };
 
-            Some(PoolEvent::SendRequest(id, cmd_s, timeout, nodes))
+            Some(PoolEvent::SendRequest(id, cmd_s, timeout, nodes, read_options))
         }
     }
 
@@ -151,9 +161,33 @@ mod commander_tests {
         LittleEndian::write_i32(&mut buf_to, -1);
         let msg = "test";
         send_cmd_sock.send_multipart(&[msg.as_bytes(), &buf, &buf_to], zmq::DONTWAIT).expect("FIXME");
-        assert_match!(Some(PoolEvent::SendRequest(cmd_id_, msg_, None, None)), cmd.fetch_events(),
+        assert_match!(Some(PoolEvent::SendRequest(cmd_id_, msg_, None, None, None)), cmd.fetch_events(),
                       cmd_id_, cmd_id,
                       msg_, msg);
     }
 
+    #[test]
+    pub fn commander_fetch_send_request_event_works_for_read_options() {
+        let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("send_read_request");
+
+        let cmd = Commander::new(recv_cmd_sock);
+
+        let cmd_id: CommandHandle = next_command_handle();
+        let mut buf = [0u8; 4];
+        LittleEndian::write_i32(&mut buf, cmd_id);
+        let mut buf_to = [0u8; 4];
+        LittleEndian::write_i32(&mut buf_to, -1);
+        let msg = "test";
+        let options = r#"{"quorum":3}"#;
+        send_cmd_sock.send_multipart(&[msg.as_bytes(), &buf, &buf_to, "".as_bytes(), options.as_bytes()], zmq::DONTWAIT).expect("FIXME");
+        match cmd.fetch_events() {
+            Some(PoolEvent::SendRequest(cmd_id_, msg_, None, None, Some(options_))) => {
+                assert_eq!(cmd_id, cmd_id_);
+                assert_eq!(msg, msg_);
+                assert_eq!(ReadRequestOptions { quorum: Some(3), fan_out: None, timeout: None, require_state_proof: false }, options_);
+            }
+            event => panic!("Unexpected event {:?}", event)
+        }
+    }
+
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ use serde_json;
 use serde_json::Value as SJsonValue;
 
 use crate::domain::ledger::constants;
+use crate::domain::pool::ReadRequestOptions;
 use indy_api_types::errors::prelude::*;
 use crate::services::ledger::merkletree::merkletree::MerkleTree;
 use crate::services::pool::{PoolService, types:: *};
@@ -107,6 +108,7 @@ pub enum PoolEvent {
         String, // request
         Option<i32>, // timeout
         Option<String>, // node list
+        Option<ReadRequestOptions>, // options of quorum read
     ),
     Timeout(
         String, //req_id
@@ -138,7 +140,8 @@ pub enum RequestEvent {
         String, // message
         String, // req_id
         Option<Vec<u8>>, // expected key for State Proof in Reply,
-        (Option<u64>, Option<u64>) // expected timestamps for freshness comparison
+        (Option<u64>, Option<u64>), // expected timestamps for freshness comparison
+        ReadRequestOptions,
     ),
     CustomConsensusRequest(
         String, // message
@@ -187,7 +190,7 @@ pub enum RequestEvent {
 impl RequestEvent {
     pub fn get_req_id(&self) -> String {
         match *self {
-            RequestEvent::CustomSingleRequest(_, ref id, _, _) => id.to_string(),
+            RequestEvent::CustomSingleRequest(_, ref id, _, _, _) => id.to_string(),
             RequestEvent::CustomConsensusRequest(_, ref id) => id.to_string(),
             RequestEvent::CustomFullRequest(_, ref id, _, _) => id.to_string(),
             RequestEvent::Reply(_, _, _, ref id) => id.to_string(),
@@ -232,10 +235,13 @@ impl Into<Option<RequestEvent>> for PoolEvent {
                         Message::Pong => RequestEvent::Pong,
                     })
             }
-            PoolEvent::SendRequest(_, msg, timeout, nodes) => {
+            PoolEvent::SendRequest(_, msg, timeout, nodes, read_options) => {
                 let parsed_req = _parse_req_id_and_op(&msg);
                 if let Ok((ref req, ref req_id, ref op)) = parsed_req {
-                    if REQUEST_FOR_FULL.contains(&op.as_str()) {
+                    if read_options.is_some() && !PoolService::is_read_request(&op.as_str()) {
+                        error!("Read options {:?} are specified for non-read request operation type {}", read_options, op);
+                        None
+                    } else if REQUEST_FOR_FULL.contains(&op.as_str()) {
                         Some(RequestEvent::CustomFullRequest(msg, req_id.clone(), timeout, nodes))
                     } else if timeout.is_some() || nodes.is_some() {
                         error!("Timeout {:?} or nodes {:?} is specified for non-supported request operation type {}",
@@ -244,9 +250,9 @@ impl Into<Option<RequestEvent>> for PoolEvent {
                     } else if REQUESTS_FOR_STATE_PROOFS.contains(&op.as_str()) {
                         let key = super::state_proof::parse_key_from_request_for_builtin_sp(&req);
                         let timestamps = _parse_timestamp_from_req_for_builtin_sp(req, &op);
-                        Some(RequestEvent::CustomSingleRequest(msg, req_id.clone(), key, timestamps))
+                        Some(RequestEvent::CustomSingleRequest(msg, req_id.clone(), key, timestamps, read_options.unwrap_or_default()))
                     } else if PoolService::get_sp_parser(&op.as_str()).is_some() {
-                        Some(RequestEvent::CustomSingleRequest(msg, req_id.clone(), None, (None, None)))
+                        Some(RequestEvent::CustomSingleRequest(msg, req_id.clone(), None, (None, None), read_options.unwrap_or_default()))
                     } else {
                         Some(RequestEvent::CustomConsensusRequest(msg, req_id.clone()))
                     }
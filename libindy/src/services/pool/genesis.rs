@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs;
+use std::io::{self, BufRead};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use serde_json;
+use serde_json::Value as SJsonValue;
+
+use crate::domain::ledger::request::ProtocolVersion;
+use crate::domain::pool::{GenesisDiff, GenesisIssue, GenesisIssueKind, GenesisNode, GenesisNodeChange,
+                          GenesisValidationOptions, GenesisValidationReport, POOL_CON_ACTIVE_TO};
+use indy_api_types::errors::prelude::*;
+use crate::services::pool::merkle_tree_factory;
+use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+use crate::services::pool::types::{NodeTransaction, NodeTransactionV0, NodeTransactionV1};
+use indy_utils::crypto::ed25519_sign;
+use rust_base58::FromBase58;
+use super::ursa::bls::{Bls, Generator, ProofOfPossession, VerKey};
+
+impl GenesisIssueKind {
+    // nodes without VALIDATOR service are skipped on pool opening, it isn't an error itself
+    fn is_error(self) -> bool {
+        self != GenesisIssueKind::NotValidator
+    }
+}
+
+pub fn validate(txn_file: &str, options: &GenesisValidationOptions) -> IndyResult<GenesisValidationReport> {
+    let file = fs::File::open(txn_file)
+        .to_indy(IndyErrorKind::IOError, "Can't open genesis txn file")?;
+
+    let mut issues: Vec<GenesisIssue> = Vec::new();
+    let mut txns_count = 0;
+    let mut nodes: HashMap<String, (usize, NodeTransactionV1)> = HashMap::new();
+
+    for (i, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line
+            .to_indy(IndyErrorKind::IOError, "Can't read from genesis txn file")?;
+        let line_no = i + 1;
+
+        if line.trim().is_empty() { continue; }
+        txns_count += 1;
+
+        let mut txn = match _parse_txn(&line) {
+            Ok(txn) => txn,
+            Err((kind, message)) => {
+                issues.push(GenesisIssue { kind, line: Some(line_no), node: None, message });
+                continue;
+            }
+        };
+
+        match nodes.entry(txn.txn.data.dest.clone()) {
+            Entry::Occupied(mut entry) => {
+                let (_, ref mut node) = *entry.get_mut();
+
+                if node.txn.data.data.alias != txn.txn.data.data.alias {
+                    issues.push(GenesisIssue {
+                        kind: GenesisIssueKind::DuplicateNode,
+                        line: Some(line_no),
+                        node: Some(txn.txn.data.data.alias.clone()),
+                        message: format!("Node {} is already defined with alias {}", txn.txn.data.dest, node.txn.data.data.alias),
+                    });
+                    continue;
+                }
+
+                if let Err(err) = node.update(&mut txn) {
+                    issues.push(GenesisIssue {
+                        kind: GenesisIssueKind::InvalidVerkey,
+                        line: Some(line_no),
+                        node: Some(txn.txn.data.data.alias.clone()),
+                        message: err.to_string(),
+                    });
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((line_no, txn));
+            }
+        }
+    }
+
+    let mut nodes: Vec<(usize, NodeTransactionV1)> = nodes.into_iter().map(|(_, node)| node).collect();
+    nodes.sort_by_key(|&(line_no, _)| line_no);
+
+    let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut addresses: HashMap<String, String> = HashMap::new();
+    let mut endpoints: Vec<(String, String)> = Vec::new();
+
+    for &(line_no, ref node) in nodes.iter() {
+        let alias = node.txn.data.data.alias.clone();
+        let mut issue = |kind: GenesisIssueKind, message: String| {
+            issues.push(GenesisIssue { kind, line: Some(line_no), node: Some(alias.clone()), message })
+        };
+
+        if let Err(message) = _check_verkey(&node.txn.data.dest) {
+            issue(GenesisIssueKind::InvalidVerkey, message);
+        }
+
+        if let Err((kind, message)) = _check_bls_key(&node.txn.data.data.blskey, &node.txn.data.data.blskey_pop, &gen) {
+            issue(kind, message);
+        }
+
+        if let Some(other) = aliases.insert(node.txn.data.data.alias.clone(), node.txn.data.dest.clone()) {
+            issue(GenesisIssueKind::DuplicateNode, format!("Alias is already used by node {}", other));
+        }
+
+        let node_address = match (&node.txn.data.data.node_ip, &node.txn.data.data.node_port) {
+            (&Some(ref node_ip), &Some(ref node_port)) => Some(format!("{}:{}", node_ip, node_port)),
+            _ => None
+        };
+
+        let client_address = match (&node.txn.data.data.client_ip, &node.txn.data.data.client_port) {
+            (&Some(ref client_ip), &Some(ref client_port)) => Some(format!("{}:{}", client_ip, client_port)),
+            _ => None
+        };
+
+        for address in node_address.iter().chain(client_address.iter()) {
+            if let Some(other) = addresses.insert(address.clone(), node.txn.data.data.alias.clone()) {
+                issue(GenesisIssueKind::DuplicateNode, format!("Address {} is already used by node {}", address, other));
+            }
+        }
+
+        if !node.txn.data.data.services.as_ref().map(|services| services.contains(&"VALIDATOR".to_string())).unwrap_or(false) {
+            issue(GenesisIssueKind::NotValidator, String::from("Node is not a validator and will be skipped"));
+            continue;
+        }
+
+        match client_address {
+            Some(client_address) => endpoints.push((node.txn.data.data.alias.clone(), client_address)),
+            None => issue(GenesisIssueKind::MissingClientAddress, String::from("Client address not found"))
+        }
+    }
+
+    if options.check_endpoints {
+        let timeout = Duration::from_secs(options.endpoint_timeout.unwrap_or(POOL_CON_ACTIVE_TO as u64));
+
+        for (alias, address, message) in _check_endpoints(endpoints, timeout) {
+            let line = nodes.iter().find(|&&(_, ref node)| node.txn.data.data.alias == alias).map(|&(line_no, _)| line_no);
+            issues.push(GenesisIssue {
+                kind: GenesisIssueKind::UnreachableEndpoint,
+                line,
+                node: Some(alias),
+                message: format!("Can't connect to {}: {}", address, message),
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+
+    let valid = txns_count > 0 && !issues.iter().any(|issue| issue.kind.is_error());
+
+    Ok(GenesisValidationReport {
+        valid,
+        txns_count,
+        nodes_count: nodes.len(),
+        issues,
+    })
+}
+
+pub fn diff(pool_name: &str, txn_file: &str) -> IndyResult<GenesisDiff> {
+    let current = merkle_tree_factory::build_node_state(&merkle_tree_factory::create(pool_name)?)?;
+    let mut new = merkle_tree_factory::build_node_state(&merkle_tree_factory::from_file(txn_file)?)?;
+
+    let mut diff = GenesisDiff::default();
+
+    for (dest, current_node) in current {
+        match new.remove(&dest) {
+            Some(new_node) => {
+                let fields = _changed_fields(&current_node, &new_node);
+
+                if fields.is_empty() {
+                    diff.unchanged += 1;
+                } else {
+                    diff.changed.push(GenesisNodeChange { alias: new_node.txn.data.data.alias.clone(), dest, fields });
+                }
+            }
+            None => diff.removed.push(GenesisNode { alias: current_node.txn.data.data.alias.clone(), dest })
+        }
+    }
+
+    diff.added = new.into_iter()
+        .map(|(dest, node)| GenesisNode { alias: node.txn.data.data.alias.clone(), dest })
+        .collect();
+
+    diff.added.sort_by(|a, b| a.alias.cmp(&b.alias));
+    diff.removed.sort_by(|a, b| a.alias.cmp(&b.alias));
+    diff.changed.sort_by(|a, b| a.alias.cmp(&b.alias));
+
+    Ok(diff)
+}
+
+fn _parse_txn(line: &str) -> Result<NodeTransactionV1, (GenesisIssueKind, String)> {
+    let txn: SJsonValue = serde_json::from_str(line.trim())
+        .map_err(|err| (GenesisIssueKind::InvalidJson, format!("Genesis txn is malformed json: {}", err)))?;
+
+    let txn: NodeTransaction = serde_json::from_value(txn)
+        .map_err(|err| (GenesisIssueKind::InvalidTransaction, format!("Genesis txn is not a NODE transaction: {}", err)))?;
+
+    let protocol_version = ProtocolVersion::get();
+
+    match txn {
+        NodeTransaction::NodeTransactionV0(_) if protocol_version != 1 =>
+            Err((GenesisIssueKind::IncompatibleProtocolVersion,
+                 format!("Libindy PROTOCOL_VERSION is {} but genesis txn is of version {}", protocol_version, NodeTransactionV0::VERSION))),
+        NodeTransaction::NodeTransactionV1(_) if protocol_version != 2 =>
+            Err((GenesisIssueKind::IncompatibleProtocolVersion,
+                 format!("Libindy PROTOCOL_VERSION is {} but genesis txn is of version {}", protocol_version, NodeTransactionV1::VERSION))),
+        NodeTransaction::NodeTransactionV0(txn) => Ok(NodeTransactionV1::from(txn)),
+        NodeTransaction::NodeTransactionV1(txn) => Ok(txn),
+    }
+}
+
+fn _check_verkey(dest: &str) -> Result<(), String> {
+    let verkey = dest.from_base58()
+        .map_err(|_| String::from("Field dest is not a base58 string"))?;
+
+    ed25519_sign::PublicKey::from_slice(&verkey)
+        .and_then(|vk| ed25519_sign::vk_to_curve25519(&vk))
+        .map(|_| ())
+        .map_err(|_| String::from("Field dest is not a valid node verkey"))
+}
+
+fn _check_bls_key(blskey: &Option<String>, blskey_pop: &Option<String>, gen: &Generator) -> Result<(), (GenesisIssueKind, String)> {
+    let blskey = match *blskey {
+        Some(ref blskey) => blskey,
+        None => return Ok(())
+    };
+
+    let blskey = blskey.from_base58().ok()
+        .and_then(|blskey| VerKey::from_bytes(&blskey).ok())
+        .ok_or_else(|| (GenesisIssueKind::InvalidBlsKey, String::from("Field blskey is not a valid BLS key")))?;
+
+    let blskey_pop = match *blskey_pop {
+        Some(ref blskey_pop) => blskey_pop,
+        None => return Ok(())
+    };
+
+    let blskey_pop = blskey_pop.from_base58().ok()
+        .and_then(|blskey_pop| ProofOfPossession::from_bytes(&blskey_pop).ok())
+        .ok_or_else(|| (GenesisIssueKind::InvalidBlsKeyPop, String::from("Field blskey_pop is not a valid BLS proof of possession")))?;
+
+    match Bls::verify_proof_of_posession(&blskey_pop, &blskey, gen) {
+        Ok(true) => Ok(()),
+        _ => Err((GenesisIssueKind::InvalidBlsKeyPop, String::from("Proof of possession doesn't match the BLS key")))
+    }
+}
+
+fn _check_endpoints(endpoints: Vec<(String, String)>, timeout: Duration) -> Vec<(String, String, String)> {
+    let checks: Vec<_> = endpoints.into_iter()
+        .map(|(alias, address)| {
+            thread::spawn(move || {
+                let res = address.to_socket_addrs()
+                    .map_err(|err| err.to_string())
+                    .and_then(|mut addrs| addrs.next().ok_or_else(|| String::from("address can't be resolved")))
+                    .and_then(|addr| TcpStream::connect_timeout(&addr, timeout).map_err(|err| err.to_string()));
+
+                match res {
+                    Ok(_) => None,
+                    Err(message) => Some((alias, address, message))
+                }
+            })
+        })
+        .collect();
+
+    checks.into_iter()
+        .filter_map(|check| check.join().ok().and_then(|res| res))
+        .collect()
+}
+
+fn _changed_fields(current: &NodeTransactionV1, new: &NodeTransactionV1) -> Vec<String> {
+    let (current_data, new_data) = (&current.txn.data.data, &new.txn.data.data);
+
+    let mut fields = Vec::new();
+
+    if current_data.alias != new_data.alias { fields.push("alias"); }
+    if current_data.client_ip != new_data.client_ip { fields.push("client_ip"); }
+    if current_data.client_port != new_data.client_port { fields.push("client_port"); }
+    if current_data.node_ip != new_data.node_ip { fields.push("node_ip"); }
+    if current_data.node_port != new_data.node_port { fields.push("node_port"); }
+    if current_data.services != new_data.services { fields.push("services"); }
+    if current_data.blskey != new_data.blskey { fields.push("blskey"); }
+    if current_data.blskey_pop != new_data.blskey_pop { fields.push("blskey_pop"); }
+    if current.txn.data.verkey != new.txn.data.verkey { fields.push("verkey"); }
+
+    fields.into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use crate::utils::environment;
+    use crate::utils::test;
+
+    use super::*;
+
+    fn _write_genesis_txns(name: &str, txns: &[String]) -> String {
+        let mut path = environment::tmp_path();
+        fs::create_dir_all(path.as_path()).unwrap();
+        path.push(name);
+        path.set_extension("txn");
+
+        let mut file = fs::File::create(path.as_path()).unwrap();
+        file.write_all(txns.join("\n").as_bytes()).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    fn _set_protocol_version(version: usize) {
+        ProtocolVersion::set(version);
+    }
+
+    #[test]
+    fn validate_works_for_test_genesis() {
+        test::cleanup_storage("validate_works_for_test_genesis");
+        _set_protocol_version(2);
+
+        let txn_file = _write_genesis_txns("validate_works_for_test_genesis", &test::gen_txns());
+
+        let report = validate(&txn_file, &GenesisValidationOptions::default()).unwrap();
+
+        assert!(report.valid);
+        assert_eq!(4, report.txns_count);
+        assert_eq!(4, report.nodes_count);
+        assert!(report.issues.is_empty());
+    }
+
+    #[test]
+    fn validate_works_for_malformed_and_duplicate_txns() {
+        test::cleanup_storage("validate_works_for_malformed_and_duplicate_txns");
+        _set_protocol_version(2);
+
+        let mut txns = test::gen_txns();
+        txns.truncate(2);
+        txns.push(String::from("{not json"));
+        txns.push(txns[0].replace("Node1", "Node5"));
+
+        let txn_file = _write_genesis_txns("validate_works_for_malformed_and_duplicate_txns", &txns);
+
+        let report = validate(&txn_file, &GenesisValidationOptions::default()).unwrap();
+
+        assert!(!report.valid);
+        assert_eq!(2, report.nodes_count);
+        assert_eq!(GenesisIssueKind::InvalidJson, report.issues[0].kind);
+        assert_eq!(Some(3), report.issues[0].line);
+        assert_eq!(GenesisIssueKind::DuplicateNode, report.issues[1].kind);
+        assert_eq!(Some(4), report.issues[1].line);
+    }
+
+    #[test]
+    fn validate_works_for_incompatible_protocol_version() {
+        test::cleanup_storage("validate_works_for_incompatible_protocol_version");
+        _set_protocol_version(1);
+
+        let txn_file = _write_genesis_txns("validate_works_for_incompatible_protocol_version", &test::gen_txns());
+
+        let report = validate(&txn_file, &GenesisValidationOptions::default()).unwrap();
+
+        _set_protocol_version(2);
+
+        assert!(!report.valid);
+        assert_eq!(0, report.nodes_count);
+        assert_eq!(4, report.issues.len());
+        assert!(report.issues.iter().all(|issue| issue.kind == GenesisIssueKind::IncompatibleProtocolVersion));
+    }
+
+    #[test]
+    fn diff_works() {
+        test::cleanup_storage("diff_works");
+        _set_protocol_version(2);
+
+        let pool_name = "diff_works";
+        let txns = test::gen_txns();
+
+        let mut pool_path = environment::pool_path(pool_name);
+        fs::create_dir_all(pool_path.as_path()).unwrap();
+        pool_path.push(pool_name);
+        pool_path.set_extension("txn");
+        fs::File::create(pool_path.as_path()).unwrap().write_all(txns[0..3].join("\n").as_bytes()).unwrap();
+
+        let new_txns = vec![txns[0].clone(), txns[1].replace(r#""client_port":9704"#, r#""client_port":9714"#), txns[3].clone()];
+        let txn_file = _write_genesis_txns("diff_works_new", &new_txns);
+
+        let diff = diff(pool_name, &txn_file).unwrap();
+
+        assert_eq!(vec!["Node4"], diff.added.iter().map(|node| node.alias.as_str()).collect::<Vec<_>>());
+        assert_eq!(vec!["Node3"], diff.removed.iter().map(|node| node.alias.as_str()).collect::<Vec<_>>());
+        assert_eq!(1, diff.changed.len());
+        assert_eq!("Node2", diff.changed[0].alias);
+        assert_eq!(vec!["client_port".to_string()], diff.changed[0].fields);
+        assert_eq!(1, diff.unchanged);
+    }
+}
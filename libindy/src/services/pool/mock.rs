@@ -0,0 +1,517 @@
+use std::sync::Mutex;
+use std::collections::HashMap;
+
+use serde_json::{self, Value};
+
+use crate::commands::{Command, CommandExecutor};
+use crate::commands::ledger::LedgerCommand;
+use crate::commands::pool::PoolCommand;
+use crate::domain::ledger::constants::{ATTRIB, CRED_DEF, GET_ATTR, GET_CRED_DEF, GET_NYM, GET_SCHEMA, NYM, SCHEMA};
+use indy_api_types::errors::prelude::*;
+use indy_api_types::{CommandHandle, PoolHandle};
+use indy_utils::next_command_handle;
+
+/// In-process ledger backend used instead of the network pool when pool is opened with `"mock": true`.
+///
+/// NYM, ATTRIB, SCHEMA and CRED_DEF writes are applied to in-memory state of the opened pool
+/// and can be read back by the corresponding GET requests. State is dropped when the pool is closed.
+pub struct MockPoolService {
+    pools: Mutex<HashMap<PoolHandle, MockPool>>,
+}
+
+struct MockPool {
+    name: String,
+    namespace: Option<String>,
+    ledger: MockLedger,
+}
+
+impl MockPoolService {
+    pub fn new() -> MockPoolService {
+        MockPoolService {
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn open(&self, name: &str, handle: PoolHandle, namespace: Option<String>) -> IndyResult<()> {
+        trace!("MockPoolService::open >>> name: {:?}, handle: {:?}, namespace: {:?}", name, handle, namespace);
+
+        self.pools.lock().unwrap()
+            .insert(handle, MockPool { name: name.to_string(), namespace, ledger: MockLedger::new() });
+
+        _send_pool_command(PoolCommand::OpenAck(handle, handle, Ok(())));
+        Ok(())
+    }
+
+    pub fn has_pool(&self, handle: PoolHandle) -> IndyResult<bool> {
+        Ok(self.pools.lock().unwrap().contains_key(&handle))
+    }
+
+    pub fn is_opened(&self, name: &str) -> IndyResult<bool> {
+        Ok(self.pools.lock().unwrap().values().any(|pool| pool.name == name))
+    }
+
+    pub fn get_pool_handle_for_namespace(&self, namespace: &str) -> IndyResult<Option<PoolHandle>> {
+        Ok(self.pools.lock().unwrap()
+            .iter()
+            .find(|&(_, pool)| pool.namespace.as_ref().map(String::as_str) == Some(namespace))
+            .map(|(handle, _)| *handle))
+    }
+
+    pub fn send_tx(&self, handle: PoolHandle, msg: &str) -> IndyResult<CommandHandle> {
+        let reply = self.pools.lock().unwrap()
+            .get_mut(&handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))?
+            .ledger
+            .process(msg)?;
+
+        let cmd_id: CommandHandle = next_command_handle();
+        _send_ledger_command(LedgerCommand::SubmitAck(cmd_id, Ok(reply)));
+        Ok(cmd_id)
+    }
+
+    pub fn refresh(&self, handle: PoolHandle) -> IndyResult<CommandHandle> {
+        if !self.has_pool(handle)? {
+            return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)));
+        }
+
+        let cmd_id: CommandHandle = next_command_handle();
+        _send_pool_command(PoolCommand::RefreshAck(cmd_id, Ok(())));
+        Ok(cmd_id)
+    }
+
+    pub fn close(&self, handle: PoolHandle) -> IndyResult<CommandHandle> {
+        self.pools.lock().unwrap()
+            .remove(&handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))?;
+
+        let cmd_id: CommandHandle = next_command_handle();
+        _send_pool_command(PoolCommand::CloseAck(cmd_id, Ok(())));
+        Ok(cmd_id)
+    }
+}
+
+fn _send_pool_command(pc: PoolCommand) {
+    CommandExecutor::instance().send(Command::Pool(pc)).unwrap();
+}
+
+fn _send_ledger_command(lc: LedgerCommand) {
+    CommandExecutor::instance().send(Command::Ledger(lc)).unwrap();
+}
+
+struct MockLedger {
+    seq_no: u64,
+    nyms: HashMap<String, Value>,
+    attribs: HashMap<(String, String), Value>,
+    schemas: HashMap<String, Value>,
+    cred_defs: HashMap<String, Value>,
+}
+
+impl MockLedger {
+    fn new() -> MockLedger {
+        MockLedger {
+            seq_no: 0,
+            nyms: HashMap::new(),
+            attribs: HashMap::new(),
+            schemas: HashMap::new(),
+            cred_defs: HashMap::new(),
+        }
+    }
+
+    fn process(&mut self, msg: &str) -> IndyResult<String> {
+        trace!("MockLedger::process >>> msg: {:?}", msg);
+
+        let request: Value = serde_json::from_str(msg)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is invalid json")?;
+
+        let req_id = request["reqId"].clone();
+        let operation = &request["operation"];
+
+        let txn_type = operation["type"].as_str()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "No valid type field in request"))?;
+
+        let reply = match txn_type {
+            NYM | ATTRIB | SCHEMA | CRED_DEF if request["signature"].is_null() && request["signatures"].is_null() =>
+                _reqnack(&req_id, "MissingSignature()"),
+            NYM => self.write_nym(&request),
+            ATTRIB => self.write_attrib(&request),
+            SCHEMA => self.write_schema(&request),
+            CRED_DEF => self.write_cred_def(&request),
+            GET_NYM => self.get_nym(&request),
+            GET_ATTR => self.get_attrib(&request),
+            GET_SCHEMA => self.get_schema(&request),
+            GET_CRED_DEF => self.get_cred_def(&request),
+            _ => _reqnack(&req_id, &format!("Transaction type {} is not supported by mock ledger", txn_type)),
+        };
+
+        let res = reply.to_string();
+
+        trace!("MockLedger::process <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn write_nym(&mut self, request: &Value) -> Value {
+        let operation = &request["operation"];
+        let dest = operation["dest"].as_str().unwrap_or_default().to_string();
+
+        let (verkey, role) = match self.nyms.get(&dest) {
+            Some(nym) => (nym["verkey"].clone(), nym["role"].clone()),
+            None => (Value::Null, Value::Null)
+        };
+
+        let seq_no = self._next_seq_no();
+
+        self.nyms.insert(dest.clone(), json!({
+            "dest": dest,
+            "identifier": request["identifier"],
+            "verkey": if operation["verkey"].is_null() { verkey } else { operation["verkey"].clone() },
+            "role": if operation.get("role").is_none() { role } else { operation["role"].clone() },
+            "seqNo": seq_no,
+            "txnTime": _now(),
+        }));
+
+        self._write_reply(request, seq_no)
+    }
+
+    fn write_attrib(&mut self, request: &Value) -> Value {
+        let operation = &request["operation"];
+        let dest = operation["dest"].as_str().unwrap_or_default().to_string();
+
+        let (key, value) = if let Some(raw) = operation["raw"].as_str() {
+            match serde_json::from_str::<Value>(raw).ok().as_ref().and_then(Value::as_object).and_then(|raw| raw.keys().next().cloned()) {
+                Some(name) => (name, raw.to_string()),
+                None => return _reject(request, "Attribute raw value must be a json object")
+            }
+        } else if let Some(hash) = operation["hash"].as_str() {
+            (hash.to_string(), hash.to_string())
+        } else if let Some(enc) = operation["enc"].as_str() {
+            (enc.to_string(), enc.to_string())
+        } else {
+            return _reject(request, "Attribute must contain one of raw, hash or enc");
+        };
+
+        let seq_no = self._next_seq_no();
+
+        self.attribs.insert((dest, key), json!({
+            "data": value,
+            "seqNo": seq_no,
+            "txnTime": _now(),
+        }));
+
+        self._write_reply(request, seq_no)
+    }
+
+    fn write_schema(&mut self, request: &Value) -> Value {
+        let data = &request["operation"]["data"];
+        let id = format!("{}:2:{}:{}",
+                         request["identifier"].as_str().unwrap_or_default(),
+                         data["name"].as_str().unwrap_or_default(),
+                         data["version"].as_str().unwrap_or_default());
+
+        if self.schemas.contains_key(&id) {
+            return _reject(request, &format!("Schema {} already exists", id));
+        }
+
+        let seq_no = self._next_seq_no();
+
+        self.schemas.insert(id, json!({
+            "data": data,
+            "seqNo": seq_no,
+            "txnTime": _now(),
+        }));
+
+        self._write_reply(request, seq_no)
+    }
+
+    fn write_cred_def(&mut self, request: &Value) -> Value {
+        let operation = &request["operation"];
+        let identifier = request["identifier"].as_str().unwrap_or_default();
+
+        if !self.schemas.values().any(|schema| schema["seqNo"] == operation["ref"]) {
+            return _reject(request, &format!("Schema with seqNo {} not found", operation["ref"]));
+        }
+
+        let id = _cred_def_key(identifier, &operation["ref"], &operation["signature_type"], &operation["tag"]);
+
+        let seq_no = self._next_seq_no();
+
+        self.cred_defs.insert(id, json!({
+            "data": operation["data"],
+            "seqNo": seq_no,
+            "txnTime": _now(),
+        }));
+
+        self._write_reply(request, seq_no)
+    }
+
+    fn get_nym(&self, request: &Value) -> Value {
+        let operation = &request["operation"];
+
+        let (data, seq_no, txn_time) = match operation["dest"].as_str().and_then(|dest| self.nyms.get(dest)) {
+            Some(nym) => (Value::String(nym.to_string()), nym["seqNo"].clone(), nym["txnTime"].clone()),
+            None => (Value::Null, Value::Null, Value::Null)
+        };
+
+        _read_reply(request, json!({
+            "dest": operation["dest"],
+            "data": data,
+            "seqNo": seq_no,
+            "txnTime": txn_time,
+        }))
+    }
+
+    fn get_attrib(&self, request: &Value) -> Value {
+        let operation = &request["operation"];
+
+        let key = ["raw", "hash", "enc"].iter()
+            .filter_map(|field| operation[*field].as_str())
+            .next()
+            .unwrap_or_default();
+
+        let attrib = operation["dest"].as_str()
+            .and_then(|dest| self.attribs.get(&(dest.to_string(), key.to_string())));
+
+        let mut result = json!({
+            "dest": operation["dest"],
+            "data": attrib.map(|attrib| attrib["data"].clone()),
+            "seqNo": attrib.map(|attrib| attrib["seqNo"].clone()),
+            "txnTime": attrib.map(|attrib| attrib["txnTime"].clone()),
+        });
+
+        for field in ["raw", "hash", "enc"].iter() {
+            if !operation[*field].is_null() {
+                result[*field] = operation[*field].clone();
+            }
+        }
+
+        _read_reply(request, result)
+    }
+
+    fn get_schema(&self, request: &Value) -> Value {
+        let operation = &request["operation"];
+        let id = format!("{}:2:{}:{}",
+                         operation["dest"].as_str().unwrap_or_default(),
+                         operation["data"]["name"].as_str().unwrap_or_default(),
+                         operation["data"]["version"].as_str().unwrap_or_default());
+
+        // like the ledger, returns name and version without attributes for unknown schema
+        let (data, seq_no, txn_time) = match self.schemas.get(&id) {
+            Some(schema) => (schema["data"].clone(), schema["seqNo"].clone(), schema["txnTime"].clone()),
+            None => (operation["data"].clone(), Value::Null, Value::Null)
+        };
+
+        _read_reply(request, json!({
+            "dest": operation["dest"],
+            "data": data,
+            "seqNo": seq_no,
+            "txnTime": txn_time,
+        }))
+    }
+
+    fn get_cred_def(&self, request: &Value) -> Value {
+        let operation = &request["operation"];
+        let id = _cred_def_key(operation["origin"].as_str().unwrap_or_default(), &operation["ref"], &operation["signature_type"], &operation["tag"]);
+
+        let (data, seq_no, txn_time) = match self.cred_defs.get(&id) {
+            Some(cred_def) => (cred_def["data"].clone(), cred_def["seqNo"].clone(), cred_def["txnTime"].clone()),
+            None => (Value::Null, Value::Null, Value::Null)
+        };
+
+        let mut result = json!({
+            "identifier": request["identifier"],
+            "origin": operation["origin"],
+            "ref": operation["ref"],
+            "signature_type": operation["signature_type"],
+            "data": data,
+            "seqNo": seq_no,
+            "txnTime": txn_time,
+        });
+
+        if !operation["tag"].is_null() {
+            result["tag"] = operation["tag"].clone();
+        }
+
+        _read_reply(request, result)
+    }
+
+    fn _next_seq_no(&mut self) -> u64 {
+        self.seq_no += 1;
+        self.seq_no
+    }
+
+    fn _write_reply(&self, request: &Value, seq_no: u64) -> Value {
+        let mut data = request["operation"].clone();
+        if let Some(data) = data.as_object_mut() {
+            data.remove("type");
+        }
+
+        json!({
+            "op": "REPLY",
+            "result": {
+                "ver": "1",
+                "txn": {
+                    "type": request["operation"]["type"],
+                    "protocolVersion": request["protocolVersion"],
+                    "data": data,
+                    "metadata": {
+                        "reqId": request["reqId"],
+                        "from": request["identifier"],
+                    },
+                },
+                "txnMetadata": {
+                    "seqNo": seq_no,
+                    "txnTime": _now(),
+                },
+                "reqSignature": {},
+            }
+        })
+    }
+}
+
+fn _read_reply(request: &Value, mut result: Value) -> Value {
+    result["type"] = request["operation"]["type"].clone();
+    result["identifier"] = request["identifier"].clone();
+    result["reqId"] = request["reqId"].clone();
+
+    json!({
+        "op": "REPLY",
+        "result": result,
+    })
+}
+
+fn _reqnack(req_id: &Value, reason: &str) -> Value {
+    json!({
+        "op": "REQNACK",
+        "reqId": req_id,
+        "reason": reason,
+    })
+}
+
+fn _reject(request: &Value, reason: &str) -> Value {
+    json!({
+        "op": "REJECT",
+        "identifier": request["identifier"],
+        "reqId": request["reqId"],
+        "reason": reason,
+    })
+}
+
+fn _cred_def_key(origin: &str, ref_: &Value, signature_type: &Value, tag: &Value) -> String {
+    format!("{}:3:{}:{}:{}", origin, signature_type.as_str().unwrap_or_default(), ref_, tag.as_str().unwrap_or_default())
+}
+
+fn _now() -> u64 {
+    time::get_time().sec as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTIFIER: &str = "V4SGRU86Z58d6TV7PBUe6f";
+    const DEST: &str = "VsKV7grR1BUE29mG2Fm2kX";
+
+    fn _request(operation: Value) -> String {
+        json!({
+            "reqId": 1,
+            "identifier": IDENTIFIER,
+            "operation": operation,
+            "protocolVersion": 2,
+            "signature": "signature",
+        }).to_string()
+    }
+
+    fn _process(ledger: &mut MockLedger, operation: Value) -> Value {
+        serde_json::from_str(&ledger.process(&_request(operation)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn mock_ledger_works_for_nym() {
+        let mut ledger = MockLedger::new();
+
+        let reply = _process(&mut ledger, json!({"type": NYM, "dest": DEST, "verkey": "verkey", "role": "101"}));
+        assert_eq!("REPLY", reply["op"]);
+        assert_eq!(1, reply["result"]["txnMetadata"]["seqNo"]);
+
+        let reply = _process(&mut ledger, json!({"type": GET_NYM, "dest": DEST}));
+        let data: Value = serde_json::from_str(reply["result"]["data"].as_str().unwrap()).unwrap();
+        assert_eq!("verkey", data["verkey"]);
+        assert_eq!("101", data["role"]);
+        assert_eq!(IDENTIFIER, data["identifier"]);
+    }
+
+    #[test]
+    fn mock_ledger_works_for_unknown_nym() {
+        let mut ledger = MockLedger::new();
+
+        let reply = _process(&mut ledger, json!({"type": GET_NYM, "dest": DEST}));
+        assert_eq!("REPLY", reply["op"]);
+        assert!(reply["result"]["data"].is_null());
+    }
+
+    #[test]
+    fn mock_ledger_works_for_attrib() {
+        let mut ledger = MockLedger::new();
+
+        let raw = r#"{"endpoint":{"ha":"127.0.0.1:5555"}}"#;
+        _process(&mut ledger, json!({"type": ATTRIB, "dest": DEST, "raw": raw}));
+
+        let reply = _process(&mut ledger, json!({"type": GET_ATTR, "dest": DEST, "raw": "endpoint"}));
+        assert_eq!(raw, reply["result"]["data"]);
+    }
+
+    #[test]
+    fn mock_ledger_works_for_schema_and_cred_def() {
+        let mut ledger = MockLedger::new();
+
+        let schema_data = json!({"name": "gvt", "version": "1.0", "attr_names": ["name"]});
+        _process(&mut ledger, json!({"type": SCHEMA, "data": schema_data}));
+
+        let reply = _process(&mut ledger, json!({"type": GET_SCHEMA, "dest": IDENTIFIER, "data": {"name": "gvt", "version": "1.0"}}));
+        assert_eq!(schema_data, reply["result"]["data"]);
+        assert_eq!(1, reply["result"]["seqNo"]);
+
+        let reply = _process(&mut ledger, json!({"type": CRED_DEF, "ref": 1, "signature_type": "CL", "tag": "tag", "data": {"primary": {}}}));
+        assert_eq!("REPLY", reply["op"]);
+
+        let reply = _process(&mut ledger, json!({"type": GET_CRED_DEF, "ref": 1, "signature_type": "CL", "origin": IDENTIFIER, "tag": "tag"}));
+        assert_eq!(json!({"primary": {}}), reply["result"]["data"]);
+        assert_eq!(2, reply["result"]["seqNo"]);
+    }
+
+    #[test]
+    fn mock_ledger_works_for_duplicate_schema() {
+        let mut ledger = MockLedger::new();
+
+        let operation = json!({"type": SCHEMA, "data": {"name": "gvt", "version": "1.0", "attr_names": ["name"]}});
+        _process(&mut ledger, operation.clone());
+
+        let reply = _process(&mut ledger, operation);
+        assert_eq!("REJECT", reply["op"]);
+    }
+
+    #[test]
+    fn mock_ledger_works_for_cred_def_for_unknown_schema() {
+        let mut ledger = MockLedger::new();
+
+        let reply = _process(&mut ledger, json!({"type": CRED_DEF, "ref": 1, "signature_type": "CL", "tag": "tag", "data": {}}));
+        assert_eq!("REJECT", reply["op"]);
+    }
+
+    #[test]
+    fn mock_ledger_works_for_unsigned_write() {
+        let mut ledger = MockLedger::new();
+
+        let request = json!({"reqId": 1, "identifier": IDENTIFIER, "operation": {"type": NYM, "dest": DEST}});
+        let reply: Value = serde_json::from_str(&ledger.process(&request.to_string()).unwrap()).unwrap();
+        assert_eq!("REQNACK", reply["op"]);
+    }
+
+    #[test]
+    fn mock_ledger_works_for_unsupported_type() {
+        let mut ledger = MockLedger::new();
+
+        let reply = _process(&mut ledger, json!({"type": "119"}));
+        assert_eq!("REQNACK", reply["op"]);
+    }
+}
@@ -9,7 +9,6 @@ use byteorder::{ByteOrder, LittleEndian};
 use self::zmq::Socket;
 
 use std::{fs, io};
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::Write;
 use std::sync::Mutex;
@@ -19,7 +18,7 @@ use serde::de::DeserializeOwned;
 
 use crate::api::ledger::{CustomFree, CustomTransactionParser};
 use crate::domain::{
-    pool::{PoolConfig, PoolOpenConfig},
+    pool::{GenesisDiff, GenesisValidationOptions, GenesisValidationReport, PoolConfig, PoolOpenConfig, ReadRequestOptions},
     ledger::response::{
         Message,
         Reply,
@@ -27,8 +26,10 @@ use crate::domain::{
     }
 };
 use indy_api_types::errors::*;
+use crate::services::pool::mock::MockPoolService;
 use crate::services::pool::pool::{Pool, ZMQPool};
 use crate::utils::environment;
+use crate::utils::qualifier;
 use crate::services::pool::events::{COMMAND_EXIT, COMMAND_CONNECT, COMMAND_REFRESH};
 use indy_api_types::{CommandHandle, PoolHandle};
 use indy_utils::{next_command_handle, next_pool_handle};
@@ -37,13 +38,17 @@ use ursa::bls::VerKey;
 mod catchup;
 mod commander;
 mod events;
+mod genesis;
 mod merkle_tree_factory;
+mod mock;
 mod networker;
 mod pool;
 mod request_handler;
 mod state_proof;
 mod types;
 
+pub use self::request_handler::DEFAULT_GENERATOR;
+
 lazy_static! {
     static ref REGISTERED_SP_PARSERS: Mutex<HashMap<String, (CustomTransactionParser, CustomFree)>> = Mutex::new(HashMap::new());
 }
@@ -51,15 +56,17 @@ lazy_static! {
 type Nodes = HashMap<String, Option<VerKey>>;
 
 pub struct PoolService {
-    open_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
-    pending_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
+    open_pools: Mutex<HashMap<PoolHandle, ZMQPool>>,
+    pending_pools: Mutex<HashMap<PoolHandle, ZMQPool>>,
+    mock_service: MockPoolService,
 }
 
 impl PoolService {
     pub fn new() -> PoolService {
         PoolService {
-            open_pools: RefCell::new(HashMap::new()),
-            pending_pools: RefCell::new(HashMap::new()),
+            open_pools: Mutex::new(HashMap::new()),
+            pending_pools: Mutex::new(HashMap::new()),
+            mock_service: MockPoolService::new(),
         }
     }
 
@@ -131,12 +138,16 @@ impl PoolService {
     }
 
     pub fn delete(&self, name: &str) -> IndyResult<()> {
-        for ref pool in self.open_pools.try_borrow()?.values() {
+        for ref pool in self.open_pools.lock().unwrap().values() {
             if pool.pool.get_name().eq(name) {
                 return Err(err_msg(IndyErrorKind::InvalidState, "Can't delete pool config - pool is open now"));
             }
         }
 
+        if self.mock_service.is_opened(name)? {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Can't delete pool config - pool is open now"));
+        }
+
         let path = environment::pool_path(name);
 
         fs::remove_dir_all(path)
@@ -144,55 +155,127 @@ impl PoolService {
     }
 
     pub fn open(&self, name: &str, config: Option<PoolOpenConfig>) -> IndyResult<PoolHandle> {
-        for ref pool in self.open_pools.try_borrow()?.values() {
+        for ref pool in self.open_pools.lock().unwrap().values() {
             if name.eq(pool.pool.get_name()) {
                 //TODO change error
                 return Err(err_msg(IndyErrorKind::InvalidPoolHandle, "Pool with the same name is already opened"));
             }
         }
 
+        if self.mock_service.is_opened(name)? {
+            return Err(err_msg(IndyErrorKind::InvalidPoolHandle, "Pool with the same name is already opened"));
+        }
+
         let config = config.unwrap_or_default();
 
+        if let Some(ref namespace) = config.namespace {
+            let open_pools = self.open_pools.lock().unwrap();
+            let pending_pools = self.pending_pools.lock().unwrap();
+
+            if open_pools.values().chain(pending_pools.values()).any(|pool| pool.pool.get_namespace() == Some(namespace.as_str()))
+                || self.mock_service.get_pool_handle_for_namespace(namespace)?.is_some() {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Pool with namespace \"{}\" is already opened", namespace)));
+            }
+        }
+
         let pool_handle: PoolHandle = next_pool_handle();
+
+        if config.mock {
+            self.mock_service.open(name, pool_handle, config.namespace)?;
+            return Ok(pool_handle);
+        }
         let mut new_pool = Pool::new(name, pool_handle, config);
 
         let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets(&format!("pool_{}", name));
 
         new_pool.work(recv_cmd_sock);
-        self._send_msg(pool_handle, COMMAND_CONNECT, &send_cmd_sock, None, None)?;
+        self._send_msg(pool_handle, COMMAND_CONNECT, &send_cmd_sock, None, None, None)?;
 
-        self.pending_pools.try_borrow_mut()?
+        self.pending_pools.lock().unwrap()
             .insert(new_pool.get_id(), ZMQPool::new(new_pool, send_cmd_sock));
         Ok(pool_handle)
     }
 
     pub fn add_open_pool(&self, pool_id: PoolHandle) -> IndyResult<PoolHandle> {
-        let pool = self.pending_pools.try_borrow_mut()?
+        if self.mock_service.has_pool(pool_id)? {
+            return Ok(pool_id);
+        }
+
+        let pool = self.pending_pools.lock().unwrap()
             .remove(&pool_id)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", pool_id)))?;
 
-        self.open_pools.try_borrow_mut()?.insert(pool_id, pool);
+        self.open_pools.lock().unwrap().insert(pool_id, pool);
 
         Ok(pool_id)
     }
 
 
+    pub fn validate_genesis_txns(&self, txn_file: &str, options: &GenesisValidationOptions) -> IndyResult<GenesisValidationReport> {
+        genesis::validate(txn_file, options)
+    }
+
+    pub fn diff_genesis_txns(&self, name: &str, txn_file: &str) -> IndyResult<GenesisDiff> {
+        genesis::diff(name, txn_file)
+    }
+
+    pub fn get_pool_handle_for_network(&self, network: &str) -> IndyResult<PoolHandle> {
+        let namespace = qualifier::namespace(network).unwrap_or_else(|| network.to_string());
+
+        if let Some(pool_handle) = self.mock_service.get_pool_handle_for_namespace(&namespace)? {
+            return Ok(pool_handle);
+        }
+
+        self.open_pools.lock().unwrap()
+            .values()
+            .find(|pool| pool.pool.get_namespace() == Some(namespace.as_str()))
+            .map(|pool| pool.pool.get_id())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No opened pool for network namespace \"{}\"", namespace)))
+    }
+
     pub fn send_tx(&self, handle: PoolHandle, msg: &str) -> IndyResult<CommandHandle> {
         self.send_action(handle, msg, None, None)
     }
 
     pub fn send_action(&self, handle: PoolHandle, msg: &str, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<CommandHandle> {
-        let pools = self.open_pools.try_borrow()?;
+        if self.mock_service.has_pool(handle)? {
+            return self.mock_service.send_tx(handle, msg);
+        }
+
+        let pools = self.open_pools.lock().unwrap();
 
         if let Some(ref pool) = pools.get(&handle) {
             let cmd_id: CommandHandle = next_command_handle();
-            self._send_msg(cmd_id, msg, &pool.cmd_socket, nodes, timeout)?;
+            self._send_msg(cmd_id, msg, &pool.cmd_socket, nodes, timeout, None)?;
             Ok(cmd_id)
         } else {
             Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
         }
     }
 
+    pub fn send_read_request(&self, handle: PoolHandle, msg: &str, options: &ReadRequestOptions) -> IndyResult<CommandHandle> {
+        if self.mock_service.has_pool(handle)? {
+            return self.mock_service.send_tx(handle, msg);
+        }
+
+        let pools = self.open_pools.lock().unwrap();
+
+        if let Some(ref pool) = pools.get(&handle) {
+            let cmd_id: CommandHandle = next_command_handle();
+            let options = serde_json::to_string(options)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize read request options")?;
+            self._send_msg(cmd_id, msg, &pool.cmd_socket, None, None, Some(&options))?;
+            Ok(cmd_id)
+        } else {
+            Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", handle)))
+        }
+    }
+
+    // Requests which are answered by a single node with a state proof and can be sent as quorum reads
+    pub fn is_read_request(txn_type: &str) -> bool {
+        events::REQUESTS_FOR_STATE_PROOFS.contains(&txn_type) || PoolService::get_sp_parser(txn_type).is_some()
+    }
+
     pub fn register_sp_parser(txn_type: &str,
                               parser: CustomTransactionParser, free: CustomFree) -> IndyResult<()> {
         if events::REQUESTS_FOR_STATE_PROOFS.contains(&txn_type) {
@@ -215,12 +298,16 @@ impl PoolService {
     }
 
     pub fn close(&self, handle: PoolHandle) -> IndyResult<CommandHandle> {
+        if self.mock_service.has_pool(handle)? {
+            return self.mock_service.close(handle);
+        }
+
         let cmd_id: CommandHandle = next_command_handle();
 
-        let mut pools = self.open_pools.try_borrow_mut()?;
+        let mut pools = self.open_pools.lock().unwrap();
 
         match pools.remove(&handle) {
-            Some(ref pool) => self._send_msg(cmd_id, COMMAND_EXIT, &pool.cmd_socket, None, None)?,
+            Some(ref pool) => self._send_msg(cmd_id, COMMAND_EXIT, &pool.cmd_socket, None, None, None)?,
             None => return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))
         }
 
@@ -228,16 +315,26 @@ impl PoolService {
     }
 
     pub fn refresh(&self, handle: PoolHandle) -> IndyResult<i32> {
+        if self.mock_service.has_pool(handle)? {
+            return self.mock_service.refresh(handle);
+        }
+
         self.send_action(handle, COMMAND_REFRESH, None, None)
     }
 
-    fn _send_msg(&self, cmd_id: CommandHandle, msg: &str, socket: &Socket, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<()> {
+    fn _send_msg(&self, cmd_id: CommandHandle, msg: &str, socket: &Socket, nodes: Option<&str>, timeout: Option<i32>, read_options: Option<&str>) -> IndyResult<()> {
+        // Pool thread logs requests by cmd_id, this record links it with the correlation id of the command
+        debug!("Sending pool request with cmd_id {}", cmd_id);
+
         let mut buf = [0u8; 4];
         let mut buf_to = [0u8; 4];
         LittleEndian::write_i32(&mut buf, cmd_id);
         let timeout = timeout.unwrap_or(-1);
         LittleEndian::write_i32(&mut buf_to, timeout);
-        if let Some(nodes) = nodes {
+        if let Some(read_options) = read_options {
+            // empty list of nodes keeps the position of read options in the message
+            Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to, nodes.unwrap_or("").as_bytes(), read_options.as_bytes()], zmq::DONTWAIT)?)
+        } else if let Some(nodes) = nodes {
             Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to, nodes.as_bytes()], zmq::DONTWAIT)?)
         } else {
             Ok(socket.send_multipart(&[msg.as_bytes(), &buf, &buf_to], zmq::DONTWAIT)?)
@@ -384,7 +481,7 @@ mod tests {
             let ps = PoolService::new();
             let pool_id = next_pool_handle();
             let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("pool_service_close_works");
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
             let cmd_id = ps.close(pool_id).unwrap();
             let recv = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
             assert_eq!(recv.len(), 3);
@@ -399,7 +496,7 @@ mod tests {
             let ps = PoolService::new();
             let pool_id = next_pool_handle();
             let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("pool_service_refresh_works");
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
             let cmd_id = ps.refresh(pool_id).unwrap();
             let recv = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
             assert_eq!(recv.len(), 3);
@@ -407,6 +504,24 @@ mod tests {
             assert_eq!(cmd_id, LittleEndian::read_i32(recv[1].as_slice()));
         }
 
+        #[test]
+        fn pool_service_send_read_request_works() {
+            test::cleanup_storage("pool_service_send_read_request_works");
+
+            let ps = PoolService::new();
+            let pool_id = next_pool_handle();
+            let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("pool_service_send_read_request_works");
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
+            let options = ReadRequestOptions { quorum: Some(3), fan_out: Some(4), timeout: None, require_state_proof: false };
+            let cmd_id = ps.send_read_request(pool_id, "test_read", &options).unwrap();
+            let recv = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
+            assert_eq!(recv.len(), 5);
+            assert_eq!("test_read", String::from_utf8(recv[0].clone()).unwrap());
+            assert_eq!(cmd_id, LittleEndian::read_i32(recv[1].as_slice()));
+            assert!(recv[3].is_empty());
+            assert_eq!(options, serde_json::from_slice::<ReadRequestOptions>(&recv[4]).unwrap());
+        }
+
         #[test]
         fn pool_service_delete_works() {
             test::cleanup_storage("pool_service_delete_works");
@@ -433,7 +548,7 @@ mod tests {
             let pool_id = next_pool_handle();
 
             let pool = Pool::new(pool_name, pool_id, PoolOpenConfig::default());
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
 
             fs::create_dir_all(path.as_path()).unwrap();
             assert!(path.exists());
@@ -453,7 +568,7 @@ mod tests {
             let pool_id = next_pool_handle();
             let pool = Pool::new(name, pool_id, PoolOpenConfig::default());
             let ps = PoolService::new();
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
             let test_data = "str_instead_of_tx_json";
             ps.send_tx(pool_id, test_data).unwrap();
             assert_eq!(recv_cmd_sock.recv_string(zmq::DONTWAIT).unwrap().unwrap(), test_data);
@@ -470,7 +585,7 @@ mod tests {
             let pool_id = next_pool_handle();
             let pool = Pool::new(name, pool_id, PoolOpenConfig::default());
             let ps = PoolService::new();
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
             let res = ps.send_tx(pool_id, "test_data");
             assert_eq!(IndyErrorKind::IOError, res.unwrap_err().kind());
         }
@@ -491,7 +606,7 @@ mod tests {
             let pool_id = next_pool_handle();
             let pool = Pool::new("pool_send_action_works", pool_id, PoolOpenConfig::default());
             let ps = PoolService::new();
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
             let test_data = "str_instead_of_tx_json";
             ps.send_action(pool_id, test_data, None, None).unwrap();
             assert_eq!(recv_cmd_sock.recv_string(zmq::DONTWAIT).unwrap().unwrap(), test_data);
@@ -554,7 +669,7 @@ mod tests {
             let (send_cmd_sock, _recv_cmd_sock) = pool_create_pair_of_sockets("pool_add_open_pool_works");
             let pool_id = next_pool_handle();
             let pool = Pool::new("pool_add_open_pool_works", pool_id, PoolOpenConfig::default());
-            ps.pending_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            ps.pending_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
             assert_match!(Ok(_pool_id), ps.add_open_pool(pool_id));
         }
 
@@ -565,6 +680,43 @@ mod tests {
             let res = ps.add_open_pool(INVALID_POOL_HANDLE);
             assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
         }
+
+        #[test]
+        pub fn pool_get_pool_handle_for_network_works() {
+            test::cleanup_storage("pool_get_pool_handle_for_network_works");
+            let ps = PoolService::new();
+            let (send_cmd_sock, _recv_cmd_sock) = pool_create_pair_of_sockets("pool_get_pool_handle_for_network_works");
+            let pool_id = next_pool_handle();
+            let config = PoolOpenConfig { namespace: Some("sovrin:staging".to_string()), ..PoolOpenConfig::default() };
+            let pool = Pool::new("pool_get_pool_handle_for_network_works", pool_id, config);
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+
+            assert_eq!(pool_id, ps.get_pool_handle_for_network("sovrin:staging").unwrap());
+            assert_eq!(pool_id, ps.get_pool_handle_for_network("did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e").unwrap());
+        }
+
+        #[test]
+        pub fn pool_get_pool_handle_for_network_works_for_unknown_namespace() {
+            test::cleanup_storage("pool_get_pool_handle_for_network_works_for_unknown_namespace");
+            let ps = PoolService::new();
+            let res = ps.get_pool_handle_for_network("did:indy:sovrin:NcYxiDXkpYi6ov5FcYDi1e");
+            assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
+        }
+
+        #[test]
+        pub fn pool_open_works_for_namespace_already_opened() {
+            test::cleanup_storage("pool_open_works_for_namespace_already_opened");
+            let ps = PoolService::new();
+            let (send_cmd_sock, _recv_cmd_sock) = pool_create_pair_of_sockets("pool_open_works_for_namespace_already_opened");
+            let pool_id = next_pool_handle();
+            let config = PoolOpenConfig { namespace: Some("sovrin".to_string()), ..PoolOpenConfig::default() };
+            let pool = Pool::new("pool_open_works_for_namespace_already_opened", pool_id, config);
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+
+            let config = PoolOpenConfig { namespace: Some("sovrin".to_string()), ..PoolOpenConfig::default() };
+            let res = ps.open("other_pool", Some(config));
+            assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+        }
     }
 
     #[test]
@@ -594,7 +746,7 @@ mod tests {
             let pool_id = next_pool_handle();
             let mut pool = Pool::new(pool_name, pool_id, PoolOpenConfig::default());
             pool.work(recv_cmd_sock);
-            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
+            ps.open_pools.lock().unwrap().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
             thread::sleep(time::Duration::from_secs(1));
             ps.close(pool_id).unwrap();
             thread::sleep(time::Duration::from_secs(1));
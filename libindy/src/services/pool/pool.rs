@@ -379,7 +379,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                             PoolState::Terminated(state.into())
                         }
                     }
-                    PoolEvent::SendRequest(cmd_id, _, _, _) => {
+                    PoolEvent::SendRequest(cmd_id, _, _, _, _) => {
                         trace!("received request to send");
                         let re: Option<RequestEvent> = pe.into();
                         match re.as_ref().map(|r| r.get_req_id()) {
@@ -465,7 +465,8 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
 }
 
 pub struct Pool<S: Networker, R: RequestHandler<S>> {
-    _pd: PhantomData<(S, R)>,
+    // Networker and request handler live on the pool thread, so they don't affect whether the pool can be sent between threads
+    _pd: PhantomData<fn() -> (S, R)>,
     worker: Option<JoinHandle<()>>,
     name: String,
     id: PoolHandle,
@@ -476,13 +477,14 @@ pub struct Pool<S: Networker, R: RequestHandler<S>> {
     preordered_nodes: Vec<String>,
     number_read_nodes: u8,
     socks_proxy: String,
+    namespace: Option<String>,
 }
 
 impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
     pub fn new(name: &str, id: PoolHandle, config: PoolOpenConfig) -> Self {
         trace!("Pool::new name {}, id {:?}, config {:?}", name, id, config);
         Pool {
-            _pd: PhantomData::<(S, R)>,
+            _pd: PhantomData,
             worker: None,
             name: name.to_string(),
             id,
@@ -493,6 +495,7 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
             preordered_nodes: config.preordered_nodes,
             number_read_nodes: config.number_read_nodes,
             socks_proxy: config.socks_proxy,
+            namespace: config.namespace,
         }
     }
 
@@ -524,6 +527,10 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
     pub fn get_id(&self) -> PoolHandle {
         self.id
     }
+
+    pub fn get_namespace(&self) -> Option<&str> {
+        self.namespace.as_ref().map(String::as_str)
+    }
 }
 
 struct PoolThread<S: Networker, R: RequestHandler<S>> {
@@ -1143,7 +1150,7 @@ mod tests {
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
             let cmd_id: CommandHandle = next_command_handle();
-            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None, None));
             assert_match!(PoolState::Active(_), p.state);
             match p.state {
                 PoolState::Active(state) => {
@@ -1184,7 +1191,7 @@ mod tests {
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
             let cmd_id: CommandHandle = next_command_handle();
-            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None, None));
             assert_match!(PoolState::Active(_), p.state);
             match p.state {
                 PoolState::Active(state) => {
@@ -1238,7 +1245,7 @@ mod tests {
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
             let cmd_id: CommandHandle = next_command_handle();
-            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None, None));
             let p = p.handle_event(PoolEvent::NodeReply(rep, "node".to_string()));
             assert_match!(PoolState::Active(_), p.state);
             match p.state {
@@ -1277,7 +1284,7 @@ mod tests {
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
             let cmd_id: CommandHandle = next_command_handle();
-            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None, None));
             assert_match!(PoolState::Active(_), p.state);
             match p.state {
                 PoolState::Active(state) => {
@@ -1330,7 +1337,7 @@ mod tests {
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
             let cmd_id: CommandHandle = next_command_handle();
-            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None, None));
             let p = p.handle_event(PoolEvent::NodeReply(rep, "node".to_string()));
             assert_match!(PoolState::Active(_), p.state);
             match p.state {
@@ -1374,7 +1381,7 @@ mod tests {
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
             let cmd_id: CommandHandle = next_command_handle();
-            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None));
+            let p = p.handle_event(PoolEvent::SendRequest(cmd_id, req, None, None, None));
             let p = p.handle_event(PoolEvent::NodeReply(rep.to_string(), "node".to_string()));
             assert_match!(PoolState::Active(_), p.state);
             match p.state {
@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::cmp;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
@@ -140,6 +141,11 @@ struct CatchupSingleState<T: Networker> {
     req_id: String,
 }
 
+/*
+ Read request is sent to `fan_out` nodes at once and is completed by `quorum` matching replies
+ or by a single reply with a valid state proof (the only way if `require_state_proof` is set).
+ Each time the pending requests can't make up the quorum anymore, the request is sent to as many further nodes as missing.
+*/
 struct SingleState<T: Networker> {
     denied_nodes: HashSet<String> /* FIXME should be map, may be merged with replies */,
     replies: HashMap<HashableValue, HashSet<NodeResponse>>,
@@ -147,6 +153,10 @@ struct SingleState<T: Networker> {
     networker: Rc<RefCell<T>>,
     sp_key: Option<Vec<u8>>,
     timestamps: (Option<u64>, Option<u64>),
+    quorum: usize,
+    sent_cnt: usize,
+    timeout: i64,
+    require_state_proof: bool,
 }
 
 struct FullState<T: Networker> {
@@ -157,8 +167,8 @@ struct FullState<T: Networker> {
 
 struct FinishState {}
 
-impl<T: Networker> From<(StartState<T>, Option<Vec<u8>>, (Option<u64>, Option<u64>))> for SingleState<T> {
-    fn from((state, sp_key, timestamps): (StartState<T>, Option<Vec<u8>>, (Option<u64>, Option<u64>))) -> Self {
+impl<T: Networker> From<(StartState<T>, Option<Vec<u8>>, (Option<u64>, Option<u64>), usize, usize, i64, bool)> for SingleState<T> {
+    fn from((state, sp_key, timestamps, quorum, sent_cnt, timeout, require_state_proof): (StartState<T>, Option<Vec<u8>>, (Option<u64>, Option<u64>), usize, usize, i64, bool)) -> Self {
         SingleState {
             denied_nodes: HashSet::new(),
             replies: HashMap::new(),
@@ -166,6 +176,10 @@ impl<T: Networker> From<(StartState<T>, Option<Vec<u8>>, (Option<u64>, Option<u6
             networker: state.networker.clone(),
             sp_key,
             timestamps,
+            quorum,
+            sent_cnt,
+            timeout,
+            require_state_proof,
         }
     }
 }
@@ -279,14 +293,33 @@ impl<T: Networker> RequestSM<T> {
                             }
                         }
                     }
-                    RequestEvent::CustomSingleRequest(msg, req_id, sp_key, timestamps) => {
-                        state.networker.borrow_mut().process_event(Some(NetworkerEvent::SendOneRequest(msg.clone(), req_id.clone(), timeout)));
+                    RequestEvent::CustomSingleRequest(msg, req_id, sp_key, timestamps, options) => {
+                        match options.quorum {
+                            Some(quorum) if quorum > nodes.len() => {
+                                _send_replies(&cmd_ids, Err(err_msg(IndyErrorKind::InvalidStructure,
+                                                                    format!("Quorum {} is greater than the number of nodes {}", quorum, nodes.len()))));
+                                (RequestState::finish(), None)
+                            }
+                            // Fewer than f+1 matching replies may all come from malicious nodes
+                            Some(quorum) if quorum < f + 1 && !options.require_state_proof => {
+                                _send_replies(&cmd_ids, Err(err_msg(IndyErrorKind::InvalidStructure,
+                                                                    format!("Quorum {} is less than f+1 = {}, it can be used only with `require_state_proof`", quorum, f + 1))));
+                                (RequestState::finish(), None)
+                            }
+                            quorum => {
+                                let quorum = quorum.unwrap_or(f + 1);
+                                let fan_out = cmp::max(cmp::min(options.fan_out.unwrap_or(number_read_nodes as usize), nodes.len()), 1);
+                                let timeout = options.timeout.unwrap_or(timeout);
 
-                        for _ in 0..number_read_nodes - 1 {
-                            state.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
-                        }
+                                state.networker.borrow_mut().process_event(Some(NetworkerEvent::SendOneRequest(msg.clone(), req_id.clone(), timeout)));
+
+                                for _ in 1..fan_out {
+                                    state.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
+                                }
 
-                        (RequestState::Single((state, sp_key, timestamps).into()), None)
+                                (RequestState::Single((state, sp_key, timestamps, quorum, fan_out, timeout, options.require_state_proof).into()), None)
+                            }
+                        }
                     }
                     RequestEvent::CustomFullRequest(msg, req_id, local_timeout, nodes_to_send) => {
                         let timeout = local_timeout.map(|to| to as i64).unwrap_or(extended_timeout);
@@ -406,17 +439,19 @@ impl<T: Networker> RequestSM<T> {
                                 )
                             };
 
-                            if cnt > f
+                            let is_quorum_reached = !state.require_state_proof && cnt >= state.quorum;
+
+                            if is_quorum_reached
                                 || _check_state_proof(&result, f, &generator, &nodes, &raw_msg, state.sp_key.as_ref().map(Vec::as_slice), state.timestamps, last_write_time) {
                                 state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
-                                _send_ok_replies(&cmd_ids, if cnt > f { &soonest } else { &raw_msg });
+                                _send_ok_replies(&cmd_ids, if is_quorum_reached { &soonest } else { &raw_msg });
                                 (RequestState::finish(), None)
                             } else {
-                                (state.try_to_continue(req_id, node_alias, &cmd_ids, nodes.len(), timeout), None)
+                                (state.try_to_continue(req_id, node_alias, &cmd_ids, nodes.len()), None)
                             }
                         } else {
                             state.denied_nodes.insert(node_alias.clone());
-                            (state.try_to_continue(req_id, node_alias, &cmd_ids, nodes.len(), timeout), None)
+                            (state.try_to_continue(req_id, node_alias, &cmd_ids, nodes.len()), None)
                         }
                     }
                     RequestEvent::ReqACK(_, _, node_alias, req_id) => {
@@ -425,7 +460,7 @@ impl<T: Networker> RequestSM<T> {
                     }
                     RequestEvent::Timeout(req_id, node_alias) => {
                         state.timeout_nodes.insert(node_alias.clone());
-                        (state.try_to_continue(req_id, node_alias, &cmd_ids, nodes.len(), timeout), None)
+                        (state.try_to_continue(req_id, node_alias, &cmd_ids, nodes.len()), None)
                     }
                     RequestEvent::Terminate => {
                         _finish_request(&cmd_ids);
@@ -649,15 +684,30 @@ impl<T: Networker> RequestHandler<T> for RequestHandlerImpl<T> {
 }
 
 impl<T: Networker> SingleState<T> {
-    fn is_consensus_reachable(&self, total_nodes_cnt: usize) -> bool {
-        (self.timeout_nodes.len() + self.denied_nodes.len() + self.replies.values().map(|set| set.len()).sum::<usize>())
-            < total_nodes_cnt
+    fn pending_cnt(&self) -> usize {
+        let answered_cnt = self.timeout_nodes.len() + self.denied_nodes.len() + self.replies.values().map(|set| set.len()).sum::<usize>();
+        self.sent_cnt.saturating_sub(answered_cnt)
     }
 
-    fn try_to_continue(self, req_id: String, node_alias: String, cmd_ids: &[CommandHandle], nodes_cnt: usize, timeout: i64) -> RequestState<T> {
-        if self.is_consensus_reachable(nodes_cnt) {
-            self.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
-            self.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
+    fn missing_cnt(&self) -> usize {
+        // any pending node may still reply with state proof
+        let required_cnt = if self.require_state_proof {
+            1
+        } else {
+            let max_replies_cnt = self.replies.values().map(|set| set.len()).max().unwrap_or(0);
+            self.quorum.saturating_sub(max_replies_cnt)
+        };
+        required_cnt.saturating_sub(self.pending_cnt())
+    }
+
+    fn try_to_continue(mut self, req_id: String, node_alias: String, cmd_ids: &[CommandHandle], nodes_cnt: usize) -> RequestState<T> {
+        let resend_cnt = cmp::min(self.missing_cnt(), nodes_cnt.saturating_sub(self.sent_cnt));
+
+        if resend_cnt > 0 || self.pending_cnt() > 0 {
+            for _ in 0..resend_cnt {
+                self.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), self.timeout)));
+            }
+            self.sent_cnt += resend_cnt;
             self.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias))));
             RequestState::Single(self)
         } else {
@@ -843,7 +893,7 @@ pub mod tests {
     use crate::services::pool::types::{ConsistencyProof, LedgerStatus, Reply, ReplyResultV1, ReplyTxnV1, ReplyV1, Response, ResponseMetadata, ResponseV1};
     use crate::utils::test;
     use crate::utils::test::test_pool_create_poolfile;
-    use crate::domain::pool::NUMBER_READ_NODES;
+    use crate::domain::pool::{NUMBER_READ_NODES, ReadRequestOptions};
 
     use super::*;
     use std::io::Write;
@@ -991,7 +1041,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_custom_single_req_event_from_start_works() {
             let mut request_handler = _request_handler("request_handler_process_custom_single_req_event_from_start_works", 0, 1);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
 
@@ -1223,7 +1273,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reply_event_from_single_state_works_for_consensus_reached() {
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_consensus_reached", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), "{}".to_string(), NODE_2.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
@@ -1251,7 +1301,7 @@ pub mod tests {
             set_freshness_threshold(600);
             add_state_proof_parser();
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_state_proof", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(
                 RequestEvent::Reply(Reply::default(), correct_state_proof_reply(_get_cur_time() - 300), NODE.to_string(), REQ_ID.to_string()))
             );
@@ -1263,7 +1313,7 @@ pub mod tests {
             set_freshness_threshold(600);
             add_state_proof_parser();
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_state_proof_from_future", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(
                 Some(RequestEvent::Reply(Reply::default(), correct_state_proof_reply(_get_cur_time() + 300), NODE.to_string(), REQ_ID.to_string()))
             );
@@ -1294,7 +1344,7 @@ pub mod tests {
             set_freshness_threshold(600);
             add_state_proof_parser();
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_freshness_filtering", 2, 4);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             //
             request_handler.process_event(Some(RequestEvent::Reply(
                 Reply::default(),
@@ -1321,7 +1371,7 @@ pub mod tests {
             add_state_proof_parser();
 
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_state_proof_from_past", 2, 4);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, Some(_get_cur_time() - 400)))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, Some(_get_cur_time() - 400)), ReadRequestOptions::default())));
 
             {
                 request_handler.process_event(
@@ -1348,7 +1398,7 @@ pub mod tests {
             add_state_proof_parser();
 
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_freshness_filtering_from_env_variable", 2, 4);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             //
             request_handler.process_event(Some(RequestEvent::Reply(
                 Reply::default(),
@@ -1373,7 +1423,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reply_event_from_single_state_works_for_not_completed() {
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_not_completed", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1381,7 +1431,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reply_event_from_single_state_works_for_cannot_be_completed() {
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_cannot_be_completed", 1, 1);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1389,7 +1439,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reply_event_from_single_state_works_for_invalid_message() {
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_invalid_message", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), "".to_string(), NODE.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1397,7 +1447,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reqack_event_from_single_state_works() {
             let mut request_handler = _request_handler("request_handler_process_reqack_event_from_single_state_works", 1, 1);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::ReqACK(Response::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1405,7 +1455,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reqnack_event_from_single_state_works_for_completed() {
             let mut request_handler = _request_handler("request_handler_process_reqnack_event_from_single_state_works_for_completed", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::ReqNACK(Response::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::ReqNACK(Response::default(), "{}".to_string(), NODE_2.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
@@ -1414,7 +1464,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reqnack_event_from_single_state_works_for_not_completed() {
             let mut request_handler = _request_handler("request_handler_process_reqnack_event_from_single_state_works_for_not_completed", 1, 3);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::ReqNACK(Response::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1422,7 +1472,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reject_event_from_single_state_works_for_completed() {
             let mut request_handler = _request_handler("request_handler_process_reject_event_from_single_state_works_for_completed", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reject(Response::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::Reject(Response::default(), "{}".to_string(), NODE_2.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
@@ -1431,7 +1481,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_reject_event_from_single_state_works_for_not_completed() {
             let mut request_handler = _request_handler("request_handler_process_reject_event_from_single_state_works_for_not_completed", 1, 3);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reject(Response::default(), "{}".to_string(), NODE.to_string(), REQ_ID.to_string())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1439,7 +1489,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_timeout_event_from_single_state_works() {
             let mut request_handler = _request_handler("request_handler_process_timeout_event_from_single_state_works", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Timeout(REQ_ID.to_string(), NODE.to_string())));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1447,7 +1497,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_timeout_event_from_single_state_works_for_cannot_be_completed() {
             let mut request_handler = _request_handler("request_handler_process_timeout_event_from_single_state_works_for_cannot_be_completed", 1, 1);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Timeout(REQ_ID.to_string(), NODE.to_string())));
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1455,7 +1505,7 @@ pub mod tests {
         #[test]
         fn request_handler_process_terminate_event_from_single_state_works() {
             let mut request_handler = _request_handler("request_handler_process_terminate_event_from_single_state_works", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Terminate));
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
         }
@@ -1463,18 +1513,100 @@ pub mod tests {
         #[test]
         fn request_handler_process_other_event_from_single_state_works() {
             let mut request_handler = _request_handler("request_handler_process_other_event_from_single_state_works", 1, 2);
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Pong));
             assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
         }
 
+        fn _sent_requests_cnt(request_handler: &RequestHandlerImpl<MockNetworker>) -> usize {
+            match request_handler.request_wrapper.as_ref().unwrap().state {
+                RequestState::Single(ref state) => state.networker.borrow().events.iter()
+                    .filter(|event| match event {
+                        Some(NetworkerEvent::SendOneRequest(..)) | Some(NetworkerEvent::Resend(..)) => true,
+                        _ => false
+                    })
+                    .count(),
+                _ => panic!("Request is expected to be in Single state")
+            }
+        }
+
+        #[test]
+        fn request_handler_process_custom_single_req_event_works_for_fan_out() {
+            let mut request_handler = _request_handler("request_handler_process_custom_single_req_event_works_for_fan_out", 1, 4);
+            let options = ReadRequestOptions { quorum: None, fan_out: Some(3), timeout: None, require_state_proof: false };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            assert_eq!(3, _sent_requests_cnt(&request_handler));
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_single_state_works_for_quorum() {
+            let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_quorum", 1, 4);
+            let options = ReadRequestOptions { quorum: Some(3), fan_out: Some(3), timeout: None, require_state_proof: false };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE_2.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Single(_), request_handler.request_wrapper.as_ref().unwrap().state);
+
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE_3.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_single_state_works_for_sending_to_missing_nodes() {
+            let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_sending_to_missing_nodes", 1, 4);
+            let options = ReadRequestOptions { quorum: Some(3), fan_out: Some(1), timeout: None, require_state_proof: false };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            assert_eq!(1, _sent_requests_cnt(&request_handler));
+
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            assert_eq!(3, _sent_requests_cnt(&request_handler));
+
+            request_handler.process_event(Some(RequestEvent::Timeout(REQ_ID.to_string(), NODE_2.to_string())));
+            assert_eq!(4, _sent_requests_cnt(&request_handler));
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_single_state_works_for_required_state_proof() {
+            let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_required_state_proof", 1, 3);
+            let options = ReadRequestOptions { quorum: None, fan_out: Some(2), timeout: None, require_state_proof: true };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE_2.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Single(_), request_handler.request_wrapper.as_ref().unwrap().state);
+            assert_eq!(3, _sent_requests_cnt(&request_handler));
+
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE_3.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_custom_single_req_event_works_for_quorum_greater_than_nodes() {
+            let mut request_handler = _request_handler("request_handler_process_custom_single_req_event_works_for_quorum_greater_than_nodes", 1, 4);
+            let options = ReadRequestOptions { quorum: Some(5), fan_out: None, timeout: None, require_state_proof: false };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_custom_single_req_event_works_for_quorum_less_than_f_plus_one() {
+            let mut request_handler = _request_handler("request_handler_process_custom_single_req_event_works_for_quorum_less_than_f_plus_one", 1, 4);
+            let options = ReadRequestOptions { quorum: Some(1), fan_out: None, timeout: None, require_state_proof: false };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+
+            let mut request_handler = _request_handler("request_handler_process_custom_single_req_event_works_for_quorum_less_than_f_plus_one", 1, 4);
+            let options = ReadRequestOptions { quorum: Some(1), fan_out: None, timeout: None, require_state_proof: true };
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), options)));
+            assert_match!(RequestState::Single(_), request_handler.request_wrapper.unwrap().state);
+        }
+
         #[test]
         fn request_handler_process_reply_event_from_single_state_works_for_consensus_reached_with_mixed_msgs() {
             // the test will use 4 nodes, each node replying with a response to the "custom consensus request" message
             // some nodes accept, some reject and some nack.  the end result is consensus should not be reached
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_consensus_reached_with_mixed_msgs", 1, 4);
 
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::Reject(Response::default(), REJECT_REPLY.to_string(), NODE_2.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::ReqNACK(Response::default(), NACK_REPLY.to_string(), NODE_3.to_string(), REQ_ID.to_string())));
@@ -1497,7 +1629,7 @@ pub mod tests {
             // some nodes accept, some reject and some nack.  the end result is consensus should not be reached
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_single_state_works_for_consensus_reached_with_0_concensus", 1, 4);
 
-            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None))));
+            request_handler.process_event(Some(RequestEvent::CustomSingleRequest(MESSAGE.to_string(), REQ_ID.to_string(), None, (None, None), ReadRequestOptions::default())));
             request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::Reject(Response::default(), "".to_string(), NODE_2.to_string(), REQ_ID.to_string())));
             request_handler.process_event(Some(RequestEvent::ReqNACK(Response::default(), "".to_string(), NODE_3.to_string(), REQ_ID.to_string())));
@@ -80,10 +80,14 @@ pub fn verify_parsed_sp(parsed_sps: Vec<ParsedSP>,
                         f: usize,
                         gen: &Generator) -> bool {
     for parsed_sp in parsed_sps {
-        if parsed_sp.multi_signature["value"]["state_root_hash"].as_str().ne(
-            &Some(&parsed_sp.root_hash)) && parsed_sp.multi_signature["value"]["txn_root_hash"].as_str().ne(
-            &Some(&parsed_sp.root_hash)) {
-            error!("Given signature is not for current root hash, aborting");
+        // audit proofs are checked against the root of the ledger, other proofs against the root of the state
+        let signed_root_hash = match parsed_sp.kvs_to_verify {
+            KeyValuesInSP::Simple(KeyValueSimpleData { verification_type: KeyValueSimpleDataVerificationType::MerkleTree(_), .. }) => "txn_root_hash",
+            _ => "state_root_hash"
+        };
+
+        if parsed_sp.multi_signature["value"][signed_root_hash].as_str().ne(&Some(&parsed_sp.root_hash)) {
+            error!("Given signature is not for current {}, aborting", signed_root_hash);
             return false;
         }
 
@@ -5,4 +5,31 @@ macro_rules! check_useful_c_callback {
             None => return $e
         };
     }
-}
\ No newline at end of file
+}
+// The same as boxed_callback_string! but the command can be cancelled with indy_cancel_command.
+// Evaluates to id of the cancellation registration and the callback.
+// Returns CommonInvalidParam1 from the calling function if a cancellable command with the same handle is pending.
+macro_rules! cancellable_callback_string {
+    ($method_name: expr, $cb: ident, $command_handle: ident) => {{
+        let cancellation_id = match crate::commands::cancellation::register($command_handle, Box::new(move || {
+            trace!("{}: cancelled", $method_name);
+            let err: ErrorCode = crate::commands::cancellation::cancelled_error($command_handle).into();
+            $cb($command_handle, err, ::std::ptr::null())
+        })) {
+            Ok(cancellation_id) => cancellation_id,
+            Err(err) => return err.into()
+        };
+
+        (cancellation_id, Box::new(move |result: ::indy_api_types::errors::IndyResult<String>| {
+            if !crate::commands::cancellation::complete($command_handle, cancellation_id) {
+                trace!("{}: result of cancelled command is dropped", $method_name);
+                return;
+            }
+
+            let (err, result_string) = prepare_result_1!(result, String::new());
+            trace!("{}: result: {:?}", $method_name, result_string);
+            let result_string = ctypes::string_to_cstring(result_string);
+            $cb($command_handle, err, result_string.as_ptr())
+        }))
+    }}
+}
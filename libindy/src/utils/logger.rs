@@ -13,6 +13,7 @@ use self::android_logger::Filter;
 use log::{Record, Metadata};
 
 use libc::{c_void, c_char};
+use std::cell::RefCell;
 use std::ffi::CString;
 use std::ptr;
 
@@ -51,6 +52,36 @@ pub type LogCB = extern fn(context: *const c_void,
 
 pub type FlushCB = extern fn(context: *const c_void);
 
+thread_local! {
+    // Correlation id of the command processed by the current thread.
+    // It's set by the application for its calls or generated by the command executor and prefixes every log message.
+    static CORRELATION_ID: RefCell<Option<String>> = RefCell::new(None);
+}
+
+pub fn set_correlation_id(correlation_id: Option<String>) {
+    CORRELATION_ID.with(|id| *id.borrow_mut() = correlation_id);
+}
+
+pub fn get_correlation_id() -> Option<String> {
+    CORRELATION_ID.with(|id| id.borrow().clone())
+}
+
+// Runs closure with the given correlation id and restores the previous one after.
+pub fn with_correlation_id<T, F: FnOnce() -> T>(correlation_id: Option<String>, f: F) -> T {
+    let prev = get_correlation_id();
+    set_correlation_id(correlation_id);
+    let res = f();
+    set_correlation_id(prev);
+    res
+}
+
+fn _format_message(record: &Record) -> String {
+    match get_correlation_id() {
+        Some(correlation_id) => format!("[{}] {}", correlation_id, record.args()),
+        None => record.args().to_string()
+    }
+}
+
 static mut CONTEXT: *const c_void = ptr::null();
 static mut ENABLED_CB: Option<EnabledCB> = None;
 static mut LOG_CB: Option<LogCB> = None;
@@ -92,7 +123,7 @@ impl log::Log for LibindyLogger {
 
         let level = record.level() as u32;
         let target = CString::new(record.target()).unwrap();
-        let message = CString::new(record.args().to_string()).unwrap();
+        let message = CString::new(_format_message(record)).unwrap();
 
         let module_path = record.module_path().map(|a| CString::new(a).unwrap());
         let file = record.file().map(|a| CString::new(a).unwrap());
@@ -191,7 +222,7 @@ impl LibindyDefaultLogger {
             info!("Logging for Android");
         } else {
             EnvLoggerBuilder::new()
-                .format(|buf, record| writeln!(buf, "{:>5}|{:<30}|{:>35}:{:<4}| {}", record.level(), record.target(), record.file().get_or_insert(""), record.line().get_or_insert(0), record.args()))
+                .format(|buf, record| writeln!(buf, "{:>5}|{:<30}|{:>35}:{:<4}| {}", record.level(), record.target(), record.file().get_or_insert(""), record.line().get_or_insert(0), _format_message(record)))
                 .filter(None, LevelFilter::Off)
                 .parse_filters(pattern.as_ref().map(String::as_str).unwrap_or(""))
                 .try_init()?;
@@ -310,4 +341,21 @@ macro_rules! secret {
 #[macro_export]
 macro_rules! secret {
     ($val:expr) => {{ "_" }};
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_correlation_id_works() {
+        set_correlation_id(Some("outer".to_string()));
+
+        let inner = with_correlation_id(Some("inner".to_string()), get_correlation_id);
+        assert_eq!(Some("inner".to_string()), inner);
+        assert_eq!(Some("outer".to_string()), get_correlation_id());
+
+        set_correlation_id(None);
+        assert_eq!(None, get_correlation_id());
+    }
+}
@@ -2,6 +2,8 @@ use regex::Regex;
 
 lazy_static! {
     pub static ref REGEX: Regex = Regex::new("^[a-z0-9]+:([a-z0-9]+):(.*)$").unwrap();
+    pub static ref INDY_REGEX: Regex = Regex::new("^did:indy:([a-z0-9]+(?::[a-z0-9]+)*):([^:]+)$").unwrap();
+    pub static ref NAMESPACE_REGEX: Regex = Regex::new("^[a-z0-9]+(?::[a-z0-9]+)*$").unwrap();
 }
 
 pub fn qualify(entity: &str, prefix: &str, method: &str) -> String {
@@ -9,6 +11,10 @@ pub fn qualify(entity: &str, prefix: &str, method: &str) -> String {
 }
 
 pub fn to_unqualified(entity: &str) -> String {
+    if let Some(caps) = INDY_REGEX.captures(entity) {
+        return caps.get(2).map(|m| m.as_str().to_string()).unwrap_or(entity.to_string());
+    }
+
     match REGEX.captures(entity) {
         None => entity.to_string(),
         Some(caps) => {
@@ -26,6 +32,19 @@ pub fn method(entity: &str) -> Option<String> {
     }
 }
 
+pub fn namespace(entity: &str) -> Option<String> {
+    match INDY_REGEX.captures(entity) {
+        None => None,
+        Some(caps) => {
+            caps.get(1).map(|m| m.as_str().to_string())
+        }
+    }
+}
+
+pub fn is_valid_namespace(namespace: &str) -> bool {
+    NAMESPACE_REGEX.is_match(namespace)
+}
+
 pub fn is_fully_qualified(entity: &str) -> bool {
     REGEX.is_match(&entity)
 }
@@ -51,4 +70,33 @@ macro_rules! qualifiable_type (($newtype:ident) => (
             self.0.starts_with($newtype::PREFIX) && qualifier::is_fully_qualified(&self.0)
         }
     }
-));
\ No newline at end of file
+));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_unqualified_works() {
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("did:indy:sovrin:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn namespace_works() {
+        assert_eq!(Some("sovrin".to_string()), namespace("did:indy:sovrin:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(Some("sovrin:staging".to_string()), namespace("did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, namespace("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, namespace("NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn is_valid_namespace_works() {
+        assert!(is_valid_namespace("sovrin"));
+        assert!(is_valid_namespace("sovrin:staging"));
+        assert!(!is_valid_namespace("Sovrin"));
+        assert!(!is_valid_namespace("sovrin:"));
+    }
+}
@@ -0,0 +1,337 @@
+// Small verification daemon built on top of the library internals.
+// Accepts (proof request, proof) pairs over local HTTP endpoint, fetches required ledger artifacts
+// through the cache and returns detailed verification result (see indy_verifier_verify_proof_with_details).
+//
+// POST /verify {"proof_request": {..}, "proof": {..}, "options": Optional<{..}>} -> VerificationResult json
+// GET /health -> {"status": "ok"}
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::channel;
+use std::thread;
+
+use serde_json::Value;
+
+use indy_api_types::{ErrorCode, PoolHandle, WalletHandle};
+use indy_api_types::domain::wallet::{Config, Credentials};
+use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
+
+use crate::commands::{Command, CommandExecutor};
+use crate::commands::anoncreds::AnoncredsCommand;
+use crate::commands::anoncreds::verifier::VerifierCommand;
+use crate::commands::cache::CacheCommand;
+use crate::commands::pool::PoolCommand;
+use crate::commands::wallet::WalletCommand;
+use crate::domain::anoncreds::credential_definition::CredentialDefinitions;
+use crate::domain::anoncreds::proof::Proof;
+use crate::domain::anoncreds::proof_request::ProofRequest;
+use crate::domain::anoncreds::revocation_registry::RevocationRegistries;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinitions;
+use crate::domain::anoncreds::schema::Schemas;
+use crate::domain::anoncreds::verification_result::VerificationOptions;
+use crate::domain::cache::GetCacheOptions;
+use crate::domain::crypto::did::DidValue;
+use crate::domain::ledger::request::DEFAULT_LIBIDY_DID;
+use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+
+const MAX_REQUEST_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct VerifierDaemonConfig {
+    // Address of HTTP endpoint
+    #[serde(default = "VerifierDaemonConfig::default_listen")]
+    pub listen: String,
+    pub pool_name: String,
+    // Path to genesis transactions. Pool ledger config is created if it doesn't exist yet.
+    pub genesis_txn: Option<String>,
+    pub pool_open_config: Option<PoolOpenConfig>,
+    #[serde(default = "VerifierDaemonConfig::default_protocol_version")]
+    pub protocol_version: usize,
+    // Wallet keeps cache of ledger artifacts. Wallet is created if it doesn't exist yet.
+    pub wallet_config: Config,
+    pub wallet_credentials: Credentials,
+    pub submitter_did: Option<DidValue>,
+    // Options of the cache for schemas, credential definitions and revocation registry definitions
+    pub cache_options: Option<GetCacheOptions>,
+}
+
+impl VerifierDaemonConfig {
+    fn default_listen() -> String {
+        "127.0.0.1:8090".to_string()
+    }
+
+    fn default_protocol_version() -> usize {
+        2
+    }
+}
+
+#[derive(Deserialize)]
+struct FetchedArtifacts {
+    schemas: Schemas,
+    cred_defs: CredentialDefinitions,
+    rev_reg_defs: RevocationRegistryDefinitions,
+    rev_regs: RevocationRegistries,
+}
+
+struct VerifierDaemon {
+    pool_handle: PoolHandle,
+    wallet_handle: WalletHandle,
+    submitter_did: DidValue,
+    cache_options: Option<GetCacheOptions>,
+}
+
+// Opens the pool and the wallet and serves requests until the listener fails.
+pub fn run(config: VerifierDaemonConfig) -> IndyResult<()> {
+    trace!("run >>> config: {:?}", config);
+
+    let listener = TcpListener::bind(&config.listen)
+        .to_indy(IndyErrorKind::IOError, format!("Cannot listen on {}", config.listen))?;
+
+    let daemon = VerifierDaemon::open(&config)?;
+
+    info!("Verifier daemon is listening on {}", config.listen);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let daemon = daemon.clone_handles();
+                thread::spawn(move || daemon.serve(stream));
+            }
+            Err(err) => warn!("Cannot accept connection: {}", err)
+        }
+    }
+
+    Ok(())
+}
+
+impl VerifierDaemon {
+    fn open(config: &VerifierDaemonConfig) -> IndyResult<VerifierDaemon> {
+        _send_command(|cb| Command::Pool(PoolCommand::SetProtocolVersion(config.protocol_version, cb)))?;
+
+        if let Some(ref genesis_txn) = config.genesis_txn {
+            let pool_config = PoolConfig { genesis_txn: genesis_txn.clone() };
+
+            match _send_command(|cb| Command::Pool(PoolCommand::Create(config.pool_name.clone(), Some(pool_config), cb))) {
+                Err(ref err) if err.kind() == IndyErrorKind::PoolConfigAlreadyExists => {}
+                res => res?
+            }
+        }
+
+        let pool_handle = _send_command(|cb| Command::Pool(PoolCommand::Open(config.pool_name.clone(), config.pool_open_config.clone(), cb)))?;
+
+        match _send_command(|cb| Command::Wallet(WalletCommand::Create(config.wallet_config.clone(), config.wallet_credentials.clone(), cb))) {
+            Err(ref err) if err.kind() == IndyErrorKind::WalletAlreadyExists => {}
+            res => res?
+        }
+
+        let wallet_handle = _send_command(|cb| Command::Wallet(WalletCommand::Open(config.wallet_config.clone(), config.wallet_credentials.clone(), cb)))?;
+
+        Ok(VerifierDaemon {
+            pool_handle,
+            wallet_handle,
+            submitter_did: config.submitter_did.clone().unwrap_or_else(|| DidValue(DEFAULT_LIBIDY_DID.to_string())),
+            cache_options: config.cache_options.clone(),
+        })
+    }
+
+    fn clone_handles(&self) -> VerifierDaemon {
+        VerifierDaemon {
+            pool_handle: self.pool_handle,
+            wallet_handle: self.wallet_handle,
+            submitter_did: self.submitter_did.clone(),
+            cache_options: self.cache_options.clone(),
+        }
+    }
+
+    fn serve(&self, mut stream: TcpStream) {
+        let (status, body) = match _read_request(&stream) {
+            Ok((ref method, ref path, _)) if method == "GET" && path == "/health" =>
+                (200, json!({"status": "ok"}).to_string()),
+            Ok((ref method, ref path, ref body)) if method == "POST" && path == "/verify" =>
+                match self.verify(body) {
+                    Ok(result) => (200, result),
+                    Err(err) => _error_response(err),
+                },
+            Ok((method, path, _)) =>
+                (404, json!({"message": format!("Unknown endpoint: {} {}", method, path)}).to_string()),
+            Err(err) => _error_response(err),
+        };
+
+        let response = format!("HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                               status, _reason_phrase(status), body.len(), body);
+
+        if let Err(err) = stream.write_all(response.as_bytes()) {
+            warn!("Cannot write response: {}", err);
+        }
+    }
+
+    fn verify(&self, body: &[u8]) -> IndyResult<String> {
+        trace!("verify >>> body: {:?}", String::from_utf8_lossy(body));
+
+        let request: Value = serde_json::from_slice(body)
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize verification request")?;
+
+        let proof_request = _parse_proof_request(&request)?;
+        let proof: Proof = serde_json::from_value(request["proof"].clone())
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize Proof")?;
+        proof.validate().map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        let options: Option<VerificationOptions> = serde_json::from_value(request["options"].clone())
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize VerificationOptions")?;
+
+        let cache_options = self.cache_options.clone().unwrap_or(GetCacheOptions {
+            no_cache: None,
+            no_update: None,
+            no_store: None,
+            min_fresh: None,
+        });
+
+        let artifacts = _send_command(|cb| Command::Cache(CacheCommand::PrefetchProofArtifacts(
+            self.pool_handle,
+            self.wallet_handle,
+            self.submitter_did.clone(),
+            proof_request,
+            proof.identifiers.clone(),
+            cache_options,
+            cb)))?;
+
+        let artifacts: FetchedArtifacts = serde_json::from_str(&artifacts)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize fetched proof artifacts")?;
+
+        let proof_request = _parse_proof_request(&request)?;
+
+        let result = _send_command(|cb| Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithDetails(
+            proof_request,
+            proof,
+            artifacts.schemas,
+            artifacts.cred_defs,
+            artifacts.rev_reg_defs,
+            artifacts.rev_regs,
+            options,
+            cb))))?;
+
+        trace!("verify <<< result: {:?}", result);
+
+        Ok(result)
+    }
+}
+
+// Sends the command to the executor and waits for the result.
+fn _send_command<T, F>(build: F) -> IndyResult<T>
+    where T: Send + 'static, F: FnOnce(Box<dyn Fn(IndyResult<T>) + Send>) -> Command {
+    let (sender, receiver) = channel();
+
+    let cb = Box::new(move |result: IndyResult<T>| {
+        sender.send(result).ok();
+    });
+
+    CommandExecutor::instance().submit(build(cb))?;
+
+    receiver.recv()
+        .to_indy(IndyErrorKind::InvalidState, "Command result was not received")?
+}
+
+fn _parse_proof_request(request: &Value) -> IndyResult<ProofRequest> {
+    let proof_request: ProofRequest = serde_json::from_value(request["proof_request"].clone())
+        .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize ProofRequest")?;
+
+    proof_request.validate().map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+    Ok(proof_request)
+}
+
+// Reads request line, headers and body of HTTP/1.1 request.
+fn _read_request(stream: &TcpStream) -> IndyResult<(String, String, Vec<u8>)> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)
+        .to_indy(IndyErrorKind::IOError, "Cannot read request line")?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length: usize = 0;
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)
+            .to_indy(IndyErrorKind::IOError, "Cannot read request header")?;
+
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+
+        let mut header = header.splitn(2, ':');
+        let name = header.next().unwrap_or_default().trim();
+        let value = header.next().unwrap_or_default().trim();
+
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse()
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid Content-Length header")?;
+        }
+    }
+
+    if content_length > MAX_REQUEST_BODY_SIZE {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Request body is larger than {} bytes", MAX_REQUEST_BODY_SIZE)));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)
+        .to_indy(IndyErrorKind::IOError, "Cannot read request body")?;
+
+    Ok((method, path, body))
+}
+
+fn _error_response(err: IndyError) -> (u16, String) {
+    let status = match err.kind() {
+        IndyErrorKind::InvalidStructure => 400,
+        IndyErrorKind::LedgerItemNotFound => 404,
+        _ => 500
+    };
+
+    let message = err.to_string();
+    let error_code: ErrorCode = err.into();
+
+    (status, json!({"error_code": error_code as i32, "message": message}).to_string())
+}
+
+fn _reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifier_daemon_config_deserialize_works_with_defaults() {
+        let config: VerifierDaemonConfig = serde_json::from_value(json!({
+            "pool_name": "sandbox",
+            "wallet_config": {"id": "verifier_daemon"},
+            "wallet_credentials": {"key": "8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY", "key_derivation_method": "RAW"}
+        })).unwrap();
+
+        assert_eq!("127.0.0.1:8090", config.listen);
+        assert_eq!(2, config.protocol_version);
+        assert!(config.genesis_txn.is_none());
+        assert!(config.submitter_did.is_none());
+    }
+
+    #[test]
+    fn error_response_works_for_invalid_structure() {
+        let (status, body) = _error_response(err_msg(IndyErrorKind::InvalidStructure, "Cannot deserialize Proof"));
+
+        assert_eq!(400, status);
+
+        let body: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json!(ErrorCode::CommonInvalidStructure as i32), body["error_code"]);
+    }
+}
@@ -54,6 +54,20 @@ mod high_cases {
         fn issuer_create_and_store_credential_def_works() {
             anoncreds::init_common_wallet();
         }
+
+        #[test]
+        fn issuer_create_and_store_credential_def_works_with_progress() {
+            let setup = Setup::wallet();
+
+            let (cred_def_id, _, stages) = anoncreds::issuer_create_credential_definition_with_progress(setup.wallet_handle,
+                                                                                                        ISSUER_DID,
+                                                                                                        &anoncreds::gvt_schema_json(),
+                                                                                                        TAG_1,
+                                                                                                        None,
+                                                                                                        None).unwrap();
+            assert_eq!(anoncreds::issuer_1_gvt_cred_def_id(), cred_def_id);
+            assert_eq!(vec!["queued", "generating", "storing"], stages);
+        }
     }
 
     mod issuer_create_credential_offer {
@@ -92,6 +106,55 @@ mod high_cases {
         }
     }
 
+    mod issuer_create_credentials {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credentials_works() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let credential_req: serde_json::Value = serde_json::from_str(&credential_req).unwrap();
+            let credential_values: serde_json::Value = serde_json::from_str(&anoncreds::gvt_credential_values_json()).unwrap();
+
+            let requests = json!([
+                {"cred_req": credential_req, "cred_values": credential_values},
+                {"cred_req": credential_req, "cred_values": credential_values}
+            ]).to_string();
+
+            let (credentials, rev_reg_delta) = anoncreds::issuer_create_credentials(wallet_handle,
+                                                                                    &credential_offer,
+                                                                                    &requests,
+                                                                                    None,
+                                                                                    None).unwrap();
+
+            let credentials: Vec<serde_json::Value> = serde_json::from_str(&credentials).unwrap();
+            assert_eq!(2, credentials.len());
+            assert_eq!(credential_values, credentials[0]["cred"]["values"]);
+            assert!(credentials[0]["cred_revoc_id"].is_null());
+            assert!(rev_reg_delta.is_none());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credentials_works_for_empty_requests() {
+            let (_, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credentials(wallet_handle,
+                                                           &credential_offer,
+                                                           "[]",
+                                                           None,
+                                                           None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -282,6 +345,97 @@ mod high_cases {
         }
     }
 
+    mod prover_credential_w3c {
+        use super::*;
+
+        fn _issue_credential(prover_wallet_handle: indy::WalletHandle) -> (String, String, &'static str) {
+            let (credential_def_json, credential_offer, _, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let (credential_req, credential_req_meta) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                                DID_MY1,
+                                                                                                &credential_offer,
+                                                                                                credential_def_json,
+                                                                                                COMMON_MASTER_SECRET).unwrap();
+
+            let (credential_json, _, _) = anoncreds::issuer_create_credential(wallet_handle,
+                                                                              &credential_offer,
+                                                                              &credential_req,
+                                                                              &anoncreds::gvt_credential_values_json(),
+                                                                              None,
+                                                                              None).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            (credential_json, credential_req_meta, credential_def_json)
+        }
+
+        #[test]
+        fn prover_credential_to_w3c_works() {
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("prover_credential_to_w3c_works").unwrap();
+
+            let (credential_json, _, _) = _issue_credential(prover_wallet_handle);
+
+            let w3c_credential = anoncreds::prover_credential_to_w3c(&credential_json).unwrap();
+            let w3c_credential: serde_json::Value = serde_json::from_str(&w3c_credential).unwrap();
+
+            assert_eq!(json!(["https://www.w3.org/2018/credentials/v1"]), w3c_credential["@context"]);
+            assert_eq!(json!(["VerifiableCredential", "IndyCredential"]), w3c_credential["type"]);
+            assert_eq!(format!("did:sov:{}", ISSUER_DID), w3c_credential["issuer"]);
+            assert_eq!(anoncreds::gvt_schema_id(), w3c_credential["credentialSchema"]["id"]);
+            assert_eq!("Alex", w3c_credential["credentialSubject"]["name"]);
+            assert_eq!(anoncreds::issuer_1_gvt_cred_def_id(), w3c_credential["proof"]["credDefId"]);
+            assert_eq!("CLSignature2019", w3c_credential["proof"]["type"]);
+
+            wallet::close_wallet(prover_wallet_handle).unwrap();
+            wallet::delete_wallet(&prover_wallet_config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn prover_credential_from_w3c_works() {
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("prover_credential_from_w3c_works").unwrap();
+
+            let (credential_json, credential_req_meta, credential_def_json) = _issue_credential(prover_wallet_handle);
+
+            let w3c_credential = anoncreds::prover_credential_to_w3c(&credential_json).unwrap();
+            let restored_credential_json = anoncreds::prover_credential_from_w3c(&w3c_credential).unwrap();
+
+            let credential: serde_json::Value = serde_json::from_str(&credential_json).unwrap();
+            let restored_credential: serde_json::Value = serde_json::from_str(&restored_credential_json).unwrap();
+            assert_eq!(credential, restored_credential);
+
+            anoncreds::prover_store_credential(prover_wallet_handle,
+                                               CREDENTIAL1_ID,
+                                               &credential_req_meta,
+                                               &restored_credential_json,
+                                               credential_def_json,
+                                               None).unwrap();
+
+            wallet::close_wallet(prover_wallet_handle).unwrap();
+            wallet::delete_wallet(&prover_wallet_config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn prover_credential_from_w3c_works_for_missed_encoded_value() {
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("prover_credential_from_w3c_works_for_missed_encoded_value").unwrap();
+
+            let (credential_json, _, _) = _issue_credential(prover_wallet_handle);
+
+            let w3c_credential = anoncreds::prover_credential_to_w3c(&credential_json).unwrap();
+            let mut w3c_credential: serde_json::Value = serde_json::from_str(&w3c_credential).unwrap();
+            w3c_credential["proof"]["encoding"].as_object_mut().unwrap().remove("name");
+
+            let res = anoncreds::prover_credential_from_w3c(&w3c_credential.to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(prover_wallet_handle).unwrap();
+            wallet::delete_wallet(&prover_wallet_config, WALLET_CREDENTIALS).unwrap();
+        }
+    }
+
     mod prover_credentials_search {
         use super::*;
 
@@ -3116,6 +3270,42 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn prover_create_proof_with_details_works() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": {},
+                 "requested_attributes": {
+                    "attr1_referent": { "cred_id": CREDENTIAL1_ID, "revealed":true }
+                 },
+                 "requested_predicates": {
+                    "predicate1_referent": { "cred_id": CREDENTIAL1_ID }
+                 }
+            }).to_string();
+
+            let result_json = anoncreds::prover_create_proof_with_details(wallet_handle,
+                                                                          &anoncreds::proof_request_attr_and_predicate(),
+                                                                          &requested_credentials_json,
+                                                                          COMMON_MASTER_SECRET,
+                                                                          &anoncreds::schemas_for_proof(),
+                                                                          &anoncreds::cred_defs_for_proof(),
+                                                                          "{}").unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result_json).unwrap();
+
+            let valid = anoncreds::verifier_verify_proof(&anoncreds::proof_request_attr_and_predicate(),
+                                                         &result["proof"].to_string(),
+                                                         &anoncreds::schemas_for_proof(),
+                                                         &anoncreds::cred_defs_for_proof(),
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(valid);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn prover_create_proof_works_for_names() {
             anoncreds::init_common_wallet();
@@ -3180,6 +3370,122 @@ mod high_cases {
         }
     }
 
+    mod prover_create_proofs {
+        use super::*;
+
+        #[test]
+        fn prover_create_proofs_works() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_requests_json = json!([
+                {
+                    "proof_request": serde_json::from_str::<serde_json::Value>(&anoncreds::proof_request_attr_and_predicate()).unwrap(),
+                    "requested_credentials": {
+                        "self_attested_attributes": {},
+                        "requested_attributes": {
+                            "attr1_referent": { "cred_id": CREDENTIAL1_ID, "revealed": true }
+                        },
+                        "requested_predicates": {
+                            "predicate1_referent": { "cred_id": CREDENTIAL1_ID }
+                        }
+                    }
+                },
+                {
+                    "proof_request": serde_json::from_str::<serde_json::Value>(&anoncreds::proof_request_attr()).unwrap(),
+                    "requested_credentials": {
+                        "self_attested_attributes": {},
+                        "requested_attributes": {
+                            "attr1_referent": { "cred_id": CREDENTIAL1_ID, "revealed": true }
+                        },
+                        "requested_predicates": {}
+                    }
+                }
+            ]).to_string();
+
+            let proofs_json = anoncreds::prover_create_proofs(wallet_handle,
+                                                              &proof_requests_json,
+                                                              COMMON_MASTER_SECRET,
+                                                              &anoncreds::schemas_for_proof(),
+                                                              &anoncreds::cred_defs_for_proof(),
+                                                              "{}").unwrap();
+            let proofs: Vec<serde_json::Value> = serde_json::from_str(&proofs_json).unwrap();
+            assert_eq!(2, proofs.len());
+
+            let proof_requests = vec![anoncreds::proof_request_attr_and_predicate(), anoncreds::proof_request_attr()];
+
+            for (proof_request, proof) in proof_requests.iter().zip(proofs) {
+                let valid = anoncreds::verifier_verify_proof(proof_request,
+                                                             &proof.to_string(),
+                                                             &anoncreds::schemas_for_proof(),
+                                                             &anoncreds::cred_defs_for_proof(),
+                                                             "{}",
+                                                             "{}").unwrap();
+                assert!(valid);
+            }
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_create_proofs_works_for_unknown_credential_in_one_request() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_requests_json = json!([
+                {
+                    "proof_request": serde_json::from_str::<serde_json::Value>(&anoncreds::proof_request_attr()).unwrap(),
+                    "requested_credentials": {
+                        "self_attested_attributes": {},
+                        "requested_attributes": {
+                            "attr1_referent": { "cred_id": CREDENTIAL1_ID, "revealed": true }
+                        },
+                        "requested_predicates": {}
+                    }
+                },
+                {
+                    "proof_request": serde_json::from_str::<serde_json::Value>(&anoncreds::proof_request_attr()).unwrap(),
+                    "requested_credentials": {
+                        "self_attested_attributes": {},
+                        "requested_attributes": {
+                            "attr1_referent": { "cred_id": "unknown_credential", "revealed": true }
+                        },
+                        "requested_predicates": {}
+                    }
+                }
+            ]).to_string();
+
+            let res = anoncreds::prover_create_proofs(wallet_handle,
+                                                      &proof_requests_json,
+                                                      COMMON_MASTER_SECRET,
+                                                      &anoncreds::schemas_for_proof(),
+                                                      &anoncreds::cred_defs_for_proof(),
+                                                      "{}");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_create_proofs_works_for_empty_list() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::prover_create_proofs(wallet_handle,
+                                                      "[]",
+                                                      COMMON_MASTER_SECRET,
+                                                      &anoncreds::schemas_for_proof(),
+                                                      &anoncreds::cred_defs_for_proof(),
+                                                      "{}");
+            assert_code!(ErrorCode::CommonInvalidParam3, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod verifier_verify_proof {
         use super::*;
 
@@ -3317,6 +3623,60 @@ mod high_cases {
                                                          "{}").unwrap();
             assert!(valid);
         }
+
+        #[test]
+        fn verifier_verify_proof_with_details_works_for_correct_proof() {
+            let result = anoncreds::verifier_verify_proof_with_details(&anoncreds::proof_request_attr(),
+                                                                       &anoncreds::proof_json(),
+                                                                       &anoncreds::schemas_for_proof(),
+                                                                       &anoncreds::cred_defs_for_proof(),
+                                                                       "{}",
+                                                                       "{}",
+                                                                       None).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+            assert_eq!(true, result["valid"]);
+            assert_eq!(true, result["crypto_valid"]);
+            assert_eq!(true, result["referents"]["attr1_referent"]["valid"]);
+            assert_eq!("Alex", result["revealed_attrs"]["attr1_referent"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_details_works_for_proof_does_not_correspond_to_request() {
+            let other_proof_req_json = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"sex"
+                   })
+               }),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({ "name":"age", "p_type":">=", "p_value":18 })
+               }),
+            }).to_string();
+
+            let result = anoncreds::verifier_verify_proof_with_details(&other_proof_req_json,
+                                                                       &anoncreds::proof_json(),
+                                                                       &anoncreds::schemas_for_proof(),
+                                                                       &anoncreds::cred_defs_for_proof(),
+                                                                       "{}",
+                                                                       "{}",
+                                                                       None).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+            assert_eq!(false, result["valid"]);
+            assert_eq!(serde_json::Value::Null, result["crypto_valid"]);
+
+            let attr = &result["referents"]["attr1_referent"];
+            assert_eq!(false, attr["valid"]);
+            assert_eq!("attribute_mismatch", attr["failures"][0]["check"]);
+
+            let predicate = &result["referents"]["predicate1_referent"];
+            assert_eq!(false, predicate["valid"]);
+            assert_eq!("predicate_mismatch", predicate["failures"][0]["check"]);
+        }
     }
 
     #[test]
@@ -3702,6 +4062,101 @@ mod high_cases {
         }
     }
 
+    mod verifier_presentation_exchange {
+        use super::*;
+
+        fn _presentation_definition() -> String {
+            json!({
+                "id": "presentation_definition",
+                "input_descriptors": [
+                    {
+                        "id": "gvt",
+                        "schema": [{"uri": anoncreds::gvt_schema_id()}],
+                        "constraints": {
+                            "fields": [
+                                {"path": ["$.credentialSubject.name"], "filter": {"type": "string", "const": "Alex"}},
+                                {"path": ["$.credentialSubject.age"], "filter": {"type": "number", "minimum": 18}}
+                            ]
+                        }
+                    }
+                ]
+            }).to_string()
+        }
+
+        #[test]
+        fn verifier_presentation_exchange_works() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req_json = anoncreds::verifier_create_proof_request_from_presentation_definition(&_presentation_definition()).unwrap();
+
+            let proof_req: serde_json::Value = serde_json::from_str(&proof_req_json).unwrap();
+            assert_eq!(json!(["name"]), proof_req["requested_attributes"]["gvt"]["names"]);
+            assert_eq!(json!("age"), proof_req["requested_predicates"]["gvt::0"]["name"]);
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": {},
+                 "requested_attributes": {
+                    "gvt": { "cred_id": CREDENTIAL1_ID, "revealed": true }
+                 },
+                 "requested_predicates": {
+                    "gvt::0": { "cred_id": CREDENTIAL1_ID }
+                 }
+            }).to_string();
+
+            let proof_json = anoncreds::prover_create_proof(wallet_handle,
+                                                            &proof_req_json,
+                                                            &requested_credentials_json,
+                                                            COMMON_MASTER_SECRET,
+                                                            &anoncreds::schemas_for_proof(),
+                                                            &anoncreds::cred_defs_for_proof(),
+                                                            "{}").unwrap();
+
+            let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                         &proof_json,
+                                                         &anoncreds::schemas_for_proof(),
+                                                         &anoncreds::cred_defs_for_proof(),
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(valid);
+
+            let submission_json = anoncreds::verifier_create_presentation_submission(&_presentation_definition(), &proof_json).unwrap();
+
+            let submission: serde_json::Value = serde_json::from_str(&submission_json).unwrap();
+            assert_eq!(json!("presentation_definition"), submission["definition_id"]);
+            assert_eq!(json!([{"id": "gvt", "format": "hlindy/proof@v2.0", "path": "$.identifiers[0]"}]), submission["descriptor_map"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn verifier_create_proof_request_from_presentation_definition_fails_for_unsupported_filter() {
+            let presentation_definition = json!({
+                "id": "presentation_definition",
+                "input_descriptors": [
+                    {
+                        "id": "gvt",
+                        "constraints": {
+                            "fields": [
+                                {"path": ["$.credentialSubject.name"], "filter": {"type": "string", "pattern": "^A"}}
+                            ]
+                        }
+                    }
+                ]
+            }).to_string();
+
+            let res = anoncreds::verifier_create_proof_request_from_presentation_definition(&presentation_definition);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn verifier_create_presentation_submission_fails_for_other_proof() {
+            let res = anoncreds::verifier_create_presentation_submission(&_presentation_definition(), &anoncreds::proof_json());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod issuer_rotate_credential_def {
         use super::*;
 
@@ -3797,6 +4252,34 @@ mod high_cases {
             assert_eq!(anoncreds::local_gvt_cred_def_id(), cred_req.cred_def_id.0);
         }
     }
+
+    mod encode_credential_attribute {
+        use super::*;
+
+        const ALEX_ENCODED: &str = "99262857098057710338306967609588410025648622308394250666849665532448612202874";
+
+        #[test]
+        fn encode_credential_attribute_works() {
+            assert_eq!(ALEX_ENCODED, anoncreds::encode_credential_attribute("Alex").unwrap());
+            assert_eq!("28", anoncreds::encode_credential_attribute("28").unwrap());
+        }
+
+        #[test]
+        fn validate_credential_values_works() {
+            let cred_values = json!({
+                "name": {"raw": "Alex", "encoded": ALEX_ENCODED},
+                "age": {"raw": "28", "encoded": "28"}
+            }).to_string();
+
+            anoncreds::validate_credential_values(&cred_values).unwrap();
+        }
+
+        #[test]
+        fn validate_credential_values_works_for_non_canonical_values() {
+            let res = anoncreds::validate_credential_values(&anoncreds::gvt_credential_values_json());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -3843,6 +4326,24 @@ mod medium_cases {
     mod issuer_create_and_store_credential_def {
         use super::*;
 
+        #[test]
+        fn issuer_create_and_store_credential_def_with_progress_works_for_existing_cred_def() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let (cred_def_id, _, stages) = anoncreds::issuer_create_credential_definition_with_progress(wallet_handle,
+                                                                                                        ISSUER_DID,
+                                                                                                        &anoncreds::gvt_schema_json(),
+                                                                                                        TAG_1,
+                                                                                                        None,
+                                                                                                        Some(&anoncreds::default_cred_def_config())).unwrap();
+            assert_eq!(anoncreds::issuer_1_gvt_cred_def_id(), cred_def_id);
+            assert!(stages.is_empty());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn issuer_create_and_store_credential_def_works_for_invalid_schema() {
             anoncreds::init_common_wallet();
@@ -4338,6 +4839,39 @@ mod medium_cases {
         }
     }
 
+    mod prover_delete_credential_with_related {
+        use super::*;
+
+        #[test]
+        fn prover_delete_credential_with_related_works_for_not_found() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::prover_delete_credential_with_related(wallet_handle, "other_cred_id", "[]");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_delete_credential_with_related_works_for_invalid_related_records() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::prover_delete_credential_with_related(wallet_handle, CREDENTIAL1_ID, r#"[{"type": "cache"}]"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = anoncreds::prover_delete_credential_with_related(wallet_handle, CREDENTIAL1_ID, &json!([{"type": "Indy::MasterSecret", "id": COMMON_MASTER_SECRET}]).to_string());
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            anoncreds::prover_get_credential(wallet_handle, CREDENTIAL1_ID).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_get_credentials_for_proof_req {
         use super::*;
 
@@ -4568,6 +5102,36 @@ mod medium_cases {
             wallet::close_wallet(wallet_handle).unwrap();
         }
 
+        #[test]
+        fn prover_create_proof_works_for_malformed_schema_id() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let requested_credentials_json = json!({
+                 "self_attested_attributes": json!({}),
+                 "requested_attributes": json!({
+                    "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":true })
+                 }),
+                 "requested_predicates": json!({})
+            }).to_string();
+
+            let schemas_json = json!({
+                "NcYxiDXkpYi6ov5FcYDi1e:2": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_schema_json()).unwrap(),
+            }).to_string();
+
+            let res = anoncreds::prover_create_proof(wallet_handle,
+                                                     &anoncreds::proof_request_attr(),
+                                                     &requested_credentials_json,
+                                                     COMMON_MASTER_SECRET,
+                                                     &schemas_json,
+                                                     &anoncreds::cred_defs_for_proof(),
+                                                     "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
         #[test]
         fn prover_create_proof_works_for_invalid_credential_defs_json() {
             anoncreds::init_common_wallet();
@@ -10,7 +10,7 @@ extern crate indyrs as api;
 mod demos {
     use super::*;
 
-    use crate::utils::{wallet, anoncreds};
+    use crate::utils::{wallet, anoncreds, non_secrets};
     use crate::utils::anoncreds::{COMMON_MASTER_SECRET, CREDENTIAL1_ID, CREDENTIAL2_ID, CREDENTIAL3_ID};
 
     use indy::ErrorCode;
@@ -860,6 +860,42 @@ mod demos {
                                                      &rev_regs_json).unwrap();
         assert!(valid);
 
+        let result = anoncreds::verifier_verify_proof_with_details(&proof_request,
+                                                                   &proof_json,
+                                                                   &schemas_json,
+                                                                   &credential_defs_json,
+                                                                   &rev_reg_defs_json,
+                                                                   &rev_regs_json,
+                                                                   Some(r#"{"revocation_unavailable":"skip"}"#)).unwrap();
+        let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(true, result["valid"]);
+        assert_eq!(true, result["crypto_valid"]);
+        assert_eq!(false, result["revocation_unchecked"]);
+
+        //11. Verifier verifies proof without revocation registries
+        let res = anoncreds::verifier_verify_proof_with_details(&proof_request,
+                                                                &proof_json,
+                                                                &schemas_json,
+                                                                &credential_defs_json,
+                                                                "{}",
+                                                                "{}",
+                                                                None);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        let result = anoncreds::verifier_verify_proof_with_details(&proof_request,
+                                                                   &proof_json,
+                                                                   &schemas_json,
+                                                                   &credential_defs_json,
+                                                                   "{}",
+                                                                   "{}",
+                                                                   Some(r#"{"revocation_unavailable":"skip"}"#)).unwrap();
+        let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(false, result["valid"]);
+        assert_eq!(true, result["revocation_unchecked"]);
+        assert_eq!(serde_json::Value::Null, result["crypto_valid"]);
+        assert_eq!(json!([rev_reg_id]), result["unavailable_rev_reg_ids"]);
+        assert_eq!(json!(["attr1_referent", "predicate1_referent"]), result["revocation_unchecked_referents"]);
+
         wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
@@ -2456,6 +2492,104 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_multiple_referents_from_one_credential() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_multiple_referents_from_one_credential").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_multiple_referents_from_one_credential").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) =
+            anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                      ISSUER_DID,
+                                                      GVT_SCHEMA_NAME,
+                                                      GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuance credential for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //6. Proof request: the same attribute is requested twice, partially overlapping group and two predicates over one attribute
+        let proof_req_json = json!({
+            "nonce":"123432421212",
+            "name":"proof_req_1",
+            "version":"0.1",
+            "requested_attributes":{
+                "attr1_referent":{"name":"name"},
+                "attr2_referent":{"name":"name", "restrictions":{"cred_def_id": cred_def_id}},
+                "attr3_referent":{"names":["name", "sex"]},
+                "attr4_referent":{"name":"height"}
+            },
+            "requested_predicates":{
+                "predicate1_referent":{"name":"age","p_type":">=","p_value":18},
+                "predicate2_referent":{"name":"age","p_type":"<=","p_value":60}
+            }
+        }).to_string();
+
+        //7. Prover gets Credentials for Proof Request
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credential = anoncreds::get_credential_for_attr_referent(&credentials_json, "attr1_referent");
+
+        //8. Prover creates Proof
+        let requested_credentials_json = json!({
+            "self_attested_attributes":{},
+            "requested_attributes":{
+                "attr1_referent":{"cred_id": credential.referent, "revealed":true},
+                "attr2_referent":{"cred_id": credential.referent, "revealed":true},
+                "attr3_referent":{"cred_id": credential.referent, "revealed":true},
+                "attr4_referent":{"cred_id": credential.referent, "revealed":false}
+            },
+            "requested_predicates":{
+                "predicate1_referent":{"cred_id": credential.referent},
+                "predicate2_referent":{"cred_id": credential.referent}
+            }
+        }).to_string();
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
+        let rev_states_json = json!({}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+        assert_eq!(1, proof.identifiers.len());
+        assert_eq!("Alex", proof.requested_proof.revealed_attrs.get("attr1_referent").unwrap().raw);
+        assert_eq!("Alex", proof.requested_proof.revealed_attrs.get("attr2_referent").unwrap().raw);
+
+        //9. Verifier verifies proof
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_credential_attr_tag_policy() {
         Setup::empty();
@@ -2575,6 +2709,60 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_credential_deletion_with_related_records() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_deletion_with_related_records").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_credential_deletion_with_related_records").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_schema_id, _schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                               ISSUER_DID,
+                                                                                                               GVT_SCHEMA_NAME,
+                                                                                                               GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuance credential for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //6. Prover keeps related record for the credential
+        non_secrets::add_wallet_record(prover_wallet_handle, "rev_state", CREDENTIAL1_ID, "{}", None).unwrap();
+
+        //7. Prover deletes credential with related records
+        let related_records = json!([
+            {"type": "rev_state", "id": CREDENTIAL1_ID},
+            {"type": "tails_reference", "id": CREDENTIAL1_ID},
+        ]).to_string();
+
+        let deleted_credential_json = anoncreds::prover_delete_credential_with_related(prover_wallet_handle, CREDENTIAL1_ID, &related_records).unwrap();
+        let deleted_credential: serde_json::Value = serde_json::from_str(&deleted_credential_json).unwrap();
+        assert_eq!(CREDENTIAL1_ID, deleted_credential["referent"].as_str().unwrap());
+        assert_eq!(cred_def_id, deleted_credential["cred_def_id"].as_str().unwrap());
+
+        //8. Neither credential nor related record can be found
+        assert!(anoncreds::prover_get_credential(prover_wallet_handle, CREDENTIAL1_ID).is_err());
+        assert!(non_secrets::get_wallet_record(prover_wallet_handle, "rev_state", CREDENTIAL1_ID, "{}").is_err());
+
+        let (search_handle, count) = anoncreds::prover_search_credentials(prover_wallet_handle, &json!({"cred_def_id": cred_def_id}).to_string()).unwrap();
+        assert_eq!(count, 0);
+        anoncreds::prover_close_credentials_search(search_handle).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[cfg(feature = "revocation_tests")]
     #[test]
     fn anoncreds_works_for_cred_def_with_revocation_but_primary_proof_only() {
@@ -2992,6 +3180,131 @@ mod demos {
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_range_predicate() {
+        Setup::empty();
+
+        //1. Create Issuer wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_range_predicate").unwrap();
+
+        //2. Create Prover wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_range_predicate").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             GVT_SCHEMA_ATTRIBUTES);
+
+        //4. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //5. Issuance credential for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //6. Proof request
+        let nonce = anoncreds::generate_nonce().unwrap();
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{},
+                                       "requested_predicates":{
+                                            "predicate1_referent":{
+                                                "name":"age","p_type":"RANGE","from":18,"to":65
+                                            },
+                                            "predicate2_referent":{
+                                                "name":"height","p_type":"RANGE","from":180,"to":200
+                                            }
+                                       }
+                                    }).to_string();
+
+        //7. Prover gets Credentials for Proof Request
+        let credentials_json = anoncreds::prover_get_credentials_for_proof_req(prover_wallet_handle, &proof_req_json).unwrap();
+        let credential = anoncreds::get_credential_for_predicate_referent(&credentials_json, "predicate1_referent");
+
+        let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+        assert!(credentials.predicates["predicate2_referent"].is_empty());
+
+        //8. Prover creates Proof for the satisfied range only
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{},
+                                       "requested_predicates":{
+                                            "predicate1_referent":{
+                                                "name":"age","p_type":"RANGE","from":18,"to":65
+                                            }
+                                       }
+                                    }).to_string();
+
+        let requested_credentials_json = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {},
+            "requested_predicates": {
+                "predicate1_referent": {"cred_id": credential.referent}
+            },
+        }).to_string();
+
+        let schemas_json = json!({schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap()}).to_string();
+        let cred_defs_json = json!({cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap()}).to_string();
+        let rev_states_json = json!({}).to_string();
+
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &cred_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+        assert_eq!(0, proof.requested_proof.predicates.get("predicate1_referent").unwrap().sub_proof_index);
+
+        //9. Verifier verifies proof
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //10. Verifier rejects proof for narrower range
+        let proof_req_json = json!({
+                                       "nonce": nonce,
+                                       "name":"proof_req_1",
+                                       "version":"0.1",
+                                       "requested_attributes":{},
+                                       "requested_predicates":{
+                                            "predicate1_referent":{
+                                                "name":"age","p_type":"RANGE","from":18,"to":21
+                                            }
+                                       }
+                                    }).to_string();
+
+        let res = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                   &proof_json,
+                                                   &schemas_json,
+                                                   &cred_defs_json,
+                                                   &rev_reg_defs_json,
+                                                   &rev_regs_json);
+        assert!(!res.unwrap_or(false));
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[test] // IS-1363 attr::<attribute_name>::value restriction
     fn anoncreds_works_for_attr_value_restriction() {
         Setup::empty();
@@ -426,4 +426,200 @@ mod high_cases {
             purge_cred_def_cache(setup.wallet_handle, &json!({"minFresh": 1000}).to_string()).unwrap();
         }
     }
+
+    mod rev_reg_def_cache {
+        use super::*;
+        use crate::utils::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinition;
+        use crate::utils::constants::*;
+
+        #[test]
+        fn indy_get_rev_reg_def_cache_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            let rev_reg_def_json1 = get_rev_reg_def_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                "{}"
+            ).unwrap();
+            let _rev_reg_def: RevocationRegistryDefinition = serde_json::from_str(&rev_reg_def_json1).unwrap();
+
+            // now retrieve it from cache
+            let options_json = json!({"noUpdate": true}).to_string();
+            let rev_reg_def_json2 = get_rev_reg_def_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                &options_json
+            ).unwrap();
+
+            assert_eq!(rev_reg_def_json1, rev_reg_def_json2);
+        }
+
+        #[test]
+        fn indy_get_rev_reg_def_only_cache_no_cached_data() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            let options_json = json!({"noUpdate": true}).to_string();
+
+            let res = get_rev_reg_def_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                &options_json);
+
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_rev_reg_def_cache_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            get_rev_reg_def_cache(setup.pool_handle, setup.wallet_handle, DID_MY1, rev_reg_id, "{}").unwrap();
+
+            purge_rev_reg_def_cache(setup.wallet_handle, "{}").unwrap();
+
+            let options_json = json!({"noUpdate": true}).to_string();
+            let res = get_rev_reg_def_cache(setup.pool_handle, setup.wallet_handle, DID_MY1, rev_reg_id, &options_json);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_rev_reg_def_cache_older_than_1000_seconds() {
+            let setup = Setup::wallet();
+            purge_rev_reg_def_cache(setup.wallet_handle, &json!({"maxAge": 1000}).to_string()).unwrap();
+        }
+    }
+
+    mod rich_schema_cache {
+        use super::*;
+        use crate::utils::constants::*;
+
+        const RICH_SCHEMA_ID: &str = "did:sov:2f9F8ZmxuvDqRiqqY29x6dx9oU4qwFTkPbDpWtwGbdUsrCD";
+
+        #[test]
+        fn indy_get_rich_schema_object_only_cache_no_cached_data() {
+            let setup = Setup::wallet_and_pool();
+
+            let options_json = json!({"noUpdate": true}).to_string();
+
+            let res = get_rich_schema_object_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                RICH_SCHEMA_ID,
+                &options_json);
+
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_rich_schema_cache_no_options() {
+            let setup = Setup::wallet();
+            purge_rich_schema_cache(setup.wallet_handle, "{}").unwrap();
+        }
+
+        #[test]
+        fn indy_purge_rich_schema_cache_older_than_1000_seconds() {
+            let setup = Setup::wallet();
+            purge_rich_schema_cache(setup.wallet_handle, &json!({"maxAge": 1000}).to_string()).unwrap();
+        }
+    }
+
+    mod prefetch_proof_artifacts {
+        use super::*;
+        use crate::utils::constants::*;
+
+        fn _proof_request(cred_def_id: &str) -> String {
+            json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {
+                        "name": "name",
+                        "restrictions": {"cred_def_id": cred_def_id}
+                    }
+                },
+                "requested_predicates": {}
+            }).to_string()
+        }
+
+        #[test]
+        fn indy_prefetch_proof_artifacts_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let (schema_id, cred_def_id, _) = utils::ledger::post_entities();
+
+            let identifiers_json = json!([{"schema_id": schema_id, "cred_def_id": cred_def_id}]).to_string();
+
+            let artifacts_json = prefetch_proof_artifacts(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                &_proof_request(cred_def_id),
+                &identifiers_json,
+                "{}").unwrap();
+
+            let artifacts: serde_json::Value = serde_json::from_str(&artifacts_json).unwrap();
+            assert!(artifacts["schemas"][schema_id].is_object());
+            assert!(artifacts["cred_defs"][cred_def_id].is_object());
+
+            // artifacts are stored in cache
+            let options_json = json!({"noUpdate": true}).to_string();
+            get_schema_cache(setup.pool_handle, setup.wallet_handle, DID_MY1, schema_id, &options_json).unwrap();
+            get_cred_def_cache(setup.pool_handle, setup.wallet_handle, DID_MY1, cred_def_id, &options_json).unwrap();
+        }
+
+        #[test]
+        fn indy_prefetch_proof_artifacts_works_for_nothing_to_fetch() {
+            let setup = Setup::wallet_and_pool();
+
+            let proof_request_json = json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name"}
+                },
+                "requested_predicates": {}
+            }).to_string();
+
+            let artifacts_json = prefetch_proof_artifacts(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                &proof_request_json,
+                "[]",
+                "{}").unwrap();
+
+            let artifacts: serde_json::Value = serde_json::from_str(&artifacts_json).unwrap();
+            assert_eq!(json!({}), artifacts["schemas"]);
+            assert_eq!(json!([]), artifacts["rev_reg_deltas"]);
+        }
+
+        #[test]
+        fn indy_prefetch_proof_artifacts_works_for_invalid_identifiers() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = prefetch_proof_artifacts(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                &_proof_request(&utils::anoncreds::issuer_1_gvt_cred_def_id()),
+                r#"[{"schema_id": 1}]"#,
+                "{}");
+
+            assert_code!(ErrorCode::CommonInvalidParam6, res);
+        }
+    }
 }
\ No newline at end of file
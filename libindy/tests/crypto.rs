@@ -35,6 +35,81 @@ mod high_cases {
             let verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
             assert_eq!(verkey.from_base58().unwrap().len(), 32);
         }
+
+        #[test]
+        fn indy_create_key_works_for_secp256k1() {
+            let setup = Setup::wallet();
+            let verkey = crypto::create_key_for_crypto_type(setup.wallet_handle, "secp256k1").unwrap();
+            assert!(verkey.ends_with(":secp256k1"));
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(64, signature.len());
+
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+            assert!(!crypto::verify(&verkey, "Other message".as_bytes(), &signature).unwrap());
+        }
+    }
+
+    mod jws {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_compact_serialization() {
+            let setup = Setup::key();
+
+            let jws = crypto::sign_jws(setup.wallet_handle, &json!([setup.verkey]).to_string(), MESSAGE.as_bytes(), None).unwrap();
+            assert_eq!(3, jws.split('.').count());
+
+            let (signers, payload) = crypto::verify_jws(&jws).unwrap();
+            assert_eq!(json!([setup.verkey]).to_string(), signers);
+            assert_eq!(MESSAGE.as_bytes().to_vec(), payload);
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_general_serialization() {
+            let setup = Setup::key();
+            let verkey_2 = crypto::create_key_for_crypto_type(setup.wallet_handle, "secp256k1").unwrap();
+
+            let signers = json!([setup.verkey, verkey_2]).to_string();
+            let jws = crypto::sign_jws(setup.wallet_handle, &signers, MESSAGE.as_bytes(), Some(r#"{"format":"general"}"#)).unwrap();
+
+            let jws_json: serde_json::Value = serde_json::from_str(&jws).unwrap();
+            assert_eq!(2, jws_json["signatures"].as_array().unwrap().len());
+
+            let (verified_signers, payload) = crypto::verify_jws(&jws).unwrap();
+            assert_eq!(signers, verified_signers);
+            assert_eq!(MESSAGE.as_bytes().to_vec(), payload);
+        }
+    }
+
+    mod ed25519_to_x25519 {
+        use super::*;
+        use rust_base58::FromBase58;
+
+        #[test]
+        fn indy_crypto_ed25519_to_x25519_works() {
+            Setup::empty();
+            let x25519_key = crypto::ed25519_to_x25519(VERKEY_MY1).unwrap();
+            assert_eq!(x25519_key.from_base58().unwrap().len(), 32);
+            assert_ne!(VERKEY_MY1, x25519_key);
+        }
+    }
+
+    mod x25519_diffie_hellman {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_x25519_diffie_hellman_works() {
+            let setup = Setup::wallet();
+            let verkey_1 = crypto::create_key(setup.wallet_handle, None).unwrap();
+            let verkey_2 = crypto::create_key(setup.wallet_handle, None).unwrap();
+
+            let shared_1 = crypto::x25519_diffie_hellman(setup.wallet_handle, &verkey_1, &crypto::ed25519_to_x25519(&verkey_2).unwrap()).unwrap();
+            let shared_2 = crypto::x25519_diffie_hellman(setup.wallet_handle, &verkey_2, &crypto::ed25519_to_x25519(&verkey_1).unwrap()).unwrap();
+
+            assert_eq!(32, shared_1.len());
+            assert_eq!(shared_1, shared_2);
+        }
     }
 
     mod set_key_metadata {
@@ -326,6 +401,55 @@ mod high_cases {
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
     }
+
+    mod bls {
+        use super::*;
+
+        #[test]
+        fn indy_bls_sign_and_verify_multi_sig_works() {
+            Setup::empty();
+
+            let message = "Hello World".as_bytes();
+
+            let keys_1: serde_json::Value = serde_json::from_str(&crypto::bls_create_keys(None).unwrap()).unwrap();
+            let keys_2: serde_json::Value = serde_json::from_str(&crypto::bls_create_keys(Some(MY1_SEED)).unwrap()).unwrap();
+
+            let signature_1 = crypto::bls_sign(keys_1["sign_key"].as_str().unwrap(), message).unwrap();
+            let signature_2 = crypto::bls_sign(keys_2["sign_key"].as_str().unwrap(), message).unwrap();
+
+            let multi_sig = crypto::bls_aggregate_signatures(&json!([signature_1, signature_2]).to_string()).unwrap();
+
+            let ver_keys = json!([keys_1["ver_key"], keys_2["ver_key"]]).to_string();
+            assert!(crypto::bls_verify_multi_sig(&multi_sig, message, &ver_keys).unwrap());
+            assert!(!crypto::bls_verify_multi_sig(&multi_sig, "Other message".as_bytes(), &ver_keys).unwrap());
+        }
+
+        #[test]
+        fn indy_bls_create_keys_works_for_seed() {
+            Setup::empty();
+
+            let keys_1: serde_json::Value = serde_json::from_str(&crypto::bls_create_keys(Some(MY1_SEED)).unwrap()).unwrap();
+            let keys_2: serde_json::Value = serde_json::from_str(&crypto::bls_create_keys(Some(MY1_SEED)).unwrap()).unwrap();
+
+            assert_eq!(keys_1, keys_2);
+            assert!(keys_1["pop"].is_string());
+        }
+    }
+
+    mod key_management {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_sign_works_for_kms_key() {
+            let setup = Setup::wallet();
+            crypto::register_test_key_management().unwrap();
+
+            let verkey = crypto::create_kms_key(setup.wallet_handle).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -588,6 +712,145 @@ mod medium_cases {
             assert!(res.is_ok());
         }
     }
+
+    mod jws {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_compact_serialization_and_several_signers() {
+            let setup = Setup::key();
+            let verkey_2 = crypto::create_key(setup.wallet_handle, None).unwrap();
+
+            let res = crypto::sign_jws(setup.wallet_handle, &json!([setup.verkey, verkey_2]).to_string(), MESSAGE.as_bytes(), None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_sign_jws_works_for_unknown_key() {
+            let setup = Setup::wallet();
+
+            let res = crypto::sign_jws(setup.wallet_handle, &json!([VERKEY_MY1]).to_string(), MESSAGE.as_bytes(), None);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_tampered_payload() {
+            let setup = Setup::key();
+
+            let jws = crypto::sign_jws(setup.wallet_handle, &json!([setup.verkey]).to_string(), MESSAGE.as_bytes(), None).unwrap();
+            let parts: Vec<&str> = jws.split('.').collect();
+            let tampered = format!("{}.{}.{}", parts[0], "b3RoZXI", parts[2]);
+
+            let res = crypto::verify_jws(&tampered);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_jws_works_for_invalid_jws() {
+            Setup::empty();
+
+            let res = crypto::verify_jws("invalid");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod ed25519_to_x25519 {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_ed25519_to_x25519_works_for_secp256k1_key() {
+            let setup = Setup::wallet();
+            let verkey = crypto::create_key_for_crypto_type(setup.wallet_handle, "secp256k1").unwrap();
+
+            let res = crypto::ed25519_to_x25519(&verkey);
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+    }
+
+    mod x25519_diffie_hellman {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_x25519_diffie_hellman_works_for_unknown_key() {
+            let setup = Setup::wallet();
+            let x25519_key = crypto::ed25519_to_x25519(VERKEY_MY1).unwrap();
+
+            let res = crypto::x25519_diffie_hellman(setup.wallet_handle, VERKEY_MY2, &x25519_key);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod bls {
+        use super::*;
+
+        #[test]
+        fn indy_bls_sign_works_for_invalid_sign_key() {
+            Setup::empty();
+
+            let res = crypto::bls_sign("invalid0OIl", "Hello World".as_bytes());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_bls_aggregate_signatures_works_for_empty_list() {
+            Setup::empty();
+
+            let res = crypto::bls_aggregate_signatures("[]");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_bls_verify_multi_sig_works_for_invalid_ver_keys_json() {
+            Setup::empty();
+
+            let res = crypto::bls_verify_multi_sig("signature", "Hello World".as_bytes(), "invalid");
+            assert_code!(ErrorCode::CommonInvalidParam5, res);
+        }
+    }
+
+    mod key_management {
+        use super::*;
+
+        #[test]
+        fn indy_create_key_works_for_unknown_kms() {
+            let setup = Setup::wallet();
+
+            let res = crypto::create_key_with_info(setup.wallet_handle, &json!({"kms": "unknown"}).to_string());
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+
+        #[test]
+        fn indy_create_key_works_for_kms_and_seed() {
+            let setup = Setup::wallet();
+            crypto::register_test_key_management().unwrap();
+
+            let res = crypto::create_key_with_info(setup.wallet_handle, &json!({"kms": crypto::TEST_KEY_MANAGEMENT, "seed": MY1_SEED}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_auth_crypt_works_for_kms_key() {
+            let setup = Setup::wallet();
+            crypto::register_test_key_management().unwrap();
+
+            let verkey = crypto::create_kms_key(setup.wallet_handle).unwrap();
+
+            let res = crypto::auth_crypt(setup.wallet_handle, &verkey, VERKEY_MY2, MESSAGE.as_bytes());
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+
+        #[test]
+        fn indy_crypto_anon_decrypt_works_for_kms_key() {
+            let setup = Setup::wallet();
+            crypto::register_test_key_management().unwrap();
+
+            let verkey = crypto::create_kms_key(setup.wallet_handle).unwrap();
+            let encrypted_msg = crypto::anon_crypt(&verkey, MESSAGE.as_bytes()).unwrap();
+
+            let res = crypto::anon_decrypt(setup.wallet_handle, &verkey, &encrypted_msg);
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -6,7 +6,7 @@ inject_indy_dependencies!();
 extern crate indyrs as indy;
 extern crate indyrs as api;
 
-use crate::utils::{did, pool, ledger};
+use crate::utils::{did, pool, ledger, wallet};
 use crate::utils::constants::*;
 use crate::utils::types::ResponseType;
 use crate::utils::Setup;
@@ -381,6 +381,29 @@ mod high_cases {
         use super::*;
         use rust_base58::FromBase58;
 
+        #[test]
+        fn indy_create_my_did_works_for_mnemonic() {
+            let setup = Setup::wallet();
+
+            let phrase = wallet::generate_mnemonic(None).unwrap();
+
+            let my_did_json = json!({"mnemonic": {"phrase": phrase}}).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            let other_did_json = json!({"mnemonic": {"phrase": phrase, "index": 1}}).to_string();
+            let (other_did, _) = did::create_my_did(setup.wallet_handle, &other_did_json).unwrap();
+            assert_ne!(my_did, other_did);
+
+            // the same DID is re-derived on another device
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_restored", setup.name)).unwrap();
+
+            let (restored_did, restored_verkey) = did::create_my_did(wallet_handle, &my_did_json).unwrap();
+            assert_eq!(my_did, restored_did);
+            assert_eq!(my_verkey, restored_verkey);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+
         #[test]
         fn indy_create_my_did_works_for_empty_json() {
             let setup = Setup::wallet();
@@ -619,6 +642,41 @@ mod high_cases {
         }
     }
 
+    mod rotate_key {
+        use super::*;
+
+        #[test]
+        fn rotate_key_works() {
+            let setup = Setup::new_identity();
+
+            let new_verkey = did::rotate_key(setup.wallet_handle, setup.pool_handle, &setup.did, "{}").unwrap();
+            assert_ne!(setup.verkey, new_verkey);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(new_verkey, verkey);
+
+            let schema_request = ledger::build_schema_request(&setup.did, SCHEMA_DATA).unwrap();
+            let response = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
+            pool::check_response_type(&response, ResponseType::REPLY);
+        }
+
+        #[test]
+        fn rotate_key_works_for_mock_ledger() {
+            let setup = Setup::did();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(r#"{"mock": true}"#)).unwrap();
+
+            let new_verkey = did::rotate_key(setup.wallet_handle, pool_handle, &setup.did, "{}").unwrap();
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert_eq!(new_verkey, did_with_meta["verkey"]);
+            assert!(did_with_meta["tempVerkey"].is_null());
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
     mod abbreviate_verkey {
         use super::*;
 
@@ -830,6 +888,26 @@ mod medium_cases {
     mod create_my_did {
         use super::*;
 
+        #[test]
+        fn indy_create_my_did_works_for_seed_and_mnemonic() {
+            let setup = Setup::wallet();
+
+            let phrase = wallet::generate_mnemonic(None).unwrap();
+
+            let my_did_json = json!({"seed": MY1_SEED, "mnemonic": {"phrase": phrase}}).to_string();
+            let res = did::create_my_did(setup.wallet_handle, &my_did_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_mnemonic() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"mnemonic": {"phrase": "invalid mnemonic phrase"}}).to_string();
+            let res = did::create_my_did(setup.wallet_handle, &my_did_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_create_my_did_works_as_cid() {
             let setup = Setup::wallet();
@@ -1018,6 +1096,47 @@ mod medium_cases {
     }
 
 
+    mod rotate_key {
+        use super::*;
+
+        #[test]
+        fn rotate_key_works_for_did_not_written_to_ledger() {
+            let setup = Setup::wallet_and_pool();
+
+            let (my_did, my_verkey) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let res = did::rotate_key(setup.wallet_handle, setup.pool_handle, &my_did, "{}");
+            assert_code!(ErrorCode::LedgerInvalidTransaction, res);
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &my_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert_eq!(my_verkey, did_with_meta["verkey"]);
+            assert!(did_with_meta["tempVerkey"].is_null());
+        }
+
+        #[test]
+        fn rotate_key_works_for_invalid_pool_handle() {
+            let setup = Setup::did();
+
+            let res = did::rotate_key(setup.wallet_handle, INVALID_POOL_HANDLE, &setup.did, "{}");
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            // temporary keys are removed so rotation can be started again
+            did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+        }
+
+        #[test]
+        fn rotate_key_works_for_unknown_did() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::rotate_key(setup.wallet_handle, setup.pool_handle, DID, "{}");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod abbreviate_verkey {
         use super::*;
 
@@ -26,6 +26,21 @@ fn get_current_error_works_for_sync_error_occurred() {
     assert!(c_str_to_string(error_json_p).unwrap().is_some());
 }
 
+#[test]
+fn get_current_error_works_for_invalid_param() {
+    let mut error_json_p: *const c_char = ptr::null();
+
+    unsafe { indy_set_runtime_config(ptr::null()) };
+
+    unsafe { indy_get_current_error(&mut error_json_p); }
+    let error: serde_json::Value = serde_json::from_str(c_str_to_string(error_json_p).unwrap().unwrap()).unwrap();
+
+    assert_eq!(100, error["code"].as_i64().unwrap());
+    assert!(error["message"].as_str().unwrap().contains("`config`"));
+    assert!(error["extra"]["context"].as_array().unwrap().len() > 1);
+    assert!(error["extra"]["vendor_code"].is_null());
+}
+
 #[test]
 fn get_current_error_works_for_async_error_occurred() {
     extern fn cb(_command_handle_: CommandHandle,
@@ -1,6 +1,26 @@
 extern crate indyrs as indy;
+#[macro_use]
+extern crate serde_json;
 
 #[test]
 fn set_runtime_config_works() {
     indy::set_runtime_config(r#"{"crypto_thread_pool_size": 2}"#);
-}
\ No newline at end of file
+}
+
+#[test]
+fn get_features_works() {
+    let features: serde_json::Value = serde_json::from_str(&indy::get_features()).unwrap();
+
+    assert_eq!(json!({"compiled": true, "enabled": true}), features["revocation"]);
+    assert_eq!(json!({"compiled": false, "enabled": false}), features["didcomm_v2"]);
+}
+
+#[test]
+fn cancel_command_works_for_unknown_command() {
+    assert_eq!(indy::ErrorCode::CommonInvalidParam1, indy::cancel_command(-1));
+}
+
+#[test]
+fn set_command_timeout_works_for_unknown_command() {
+    assert_eq!(indy::ErrorCode::CommonInvalidParam1, indy::set_command_timeout(-1, 1000));
+}
@@ -169,6 +169,44 @@ mod high_cases {
         }
     }
 
+    mod submit_read_request {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_read_request_works() {
+            let setup = Setup::trustee();
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&setup.did), &setup.did).unwrap();
+            let get_nym_response = ledger::submit_read_request(setup.pool_handle, &get_nym_request, None).unwrap();
+            pool::check_response_type(&get_nym_response, ResponseType::REPLY);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_read_request_works_for_quorum() {
+            let setup = Setup::trustee();
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&setup.did), &setup.did).unwrap();
+            let options = json!({"quorum": 4, "fan_out": 4, "timeout": 10}).to_string();
+            let get_nym_response = ledger::submit_read_request(setup.pool_handle, &get_nym_request, Some(&options)).unwrap();
+            pool::check_response_type(&get_nym_response, ResponseType::REPLY);
+        }
+
+        #[test]
+        fn indy_submit_read_request_works_for_required_state_proof() {
+            let setup = Setup::trustee();
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&setup.did), &setup.did).unwrap();
+            let options = json!({"require_state_proof": true}).to_string();
+            let get_nym_response = ledger::submit_read_request(setup.pool_handle, &get_nym_request, Some(&options)).unwrap();
+            pool::check_response_type(&get_nym_response, ResponseType::REPLY);
+
+            let get_nym_response: serde_json::Value = serde_json::from_str(&get_nym_response).unwrap();
+            assert!(get_nym_response["result"]["state_proof"].is_object());
+        }
+    }
+
     mod sign_request {
         use super::*;
 
@@ -228,6 +266,145 @@ mod high_cases {
                 }
             }
         }
+
+        #[test]
+        fn indy_parse_get_frozen_ledgers_response_works() {
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": constants::GET_FROZEN_LEDGERS,
+                    "data": {"909": {"ledger": "ledger_root_hash", "state": "state_root_hash", "seq_no": 10}}
+                }
+            }).to_string();
+
+            let frozen_ledgers = ledger::parse_get_frozen_ledgers_response(&response).unwrap();
+            let frozen_ledgers: serde_json::Value = serde_json::from_str(&frozen_ledgers).unwrap();
+
+            assert_eq!(json!({"909": {"ledger": "ledger_root_hash", "state": "state_root_hash", "seq_no": 10}}), frozen_ledgers);
+        }
+
+        #[test]
+        fn indy_parse_get_frozen_ledgers_response_works_for_reject() {
+            let response = json!({"op": "REJECT", "reqId": 1, "reason": "Not allowed"}).to_string();
+
+            let res = ledger::parse_get_frozen_ledgers_response(&response);
+            assert_code!(ErrorCode::LedgerInvalidTransaction, res);
+        }
+    }
+
+    mod rich_schema {
+        use super::*;
+
+        const RICH_SCHEMA_ID: &str = "did:sov:2f9F8ZmxuvDqRiqqY29x6dx9oU4qwFTkPbDpWtwGbdUsrCD";
+
+        fn _rich_schema() -> serde_json::Value {
+            json!({
+                "id": RICH_SCHEMA_ID,
+                "content": json!({"@id": RICH_SCHEMA_ID, "@type": "rdfs:Class", "name": "SchemaName"}).to_string(),
+                "rsName": "SchemaName",
+                "rsVersion": "1.0",
+                "rsType": "sch",
+                "ver": "1.0"
+            })
+        }
+
+        #[test]
+        fn indy_build_rich_schema_request_works() {
+            let request = ledger::build_rich_schema_request(DID_TRUSTEE, &_rich_schema().to_string()).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+            assert_eq!(constants::RICH_SCHEMA, request["operation"]["type"]);
+            assert_eq!(RICH_SCHEMA_ID, request["operation"]["id"]);
+            assert_eq!("sch", request["operation"]["rsType"]);
+        }
+
+        #[test]
+        fn indy_build_rich_schema_request_works_for_context() {
+            let context = json!({
+                "id": RICH_SCHEMA_ID,
+                "content": json!({"@context": {"name": "http://schema.org/name"}}).to_string(),
+                "rsName": "ContextName",
+                "rsVersion": "1.0",
+                "rsType": "ctx",
+                "ver": "1.0"
+            }).to_string();
+
+            let request = ledger::build_rich_schema_request(DID_TRUSTEE, &context).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+            assert_eq!(constants::RICH_SCHEMA_CONTEXT, request["operation"]["type"]);
+        }
+
+        #[test]
+        fn indy_build_rich_schema_request_works_for_id_mismatch() {
+            let mut rich_schema = _rich_schema();
+            rich_schema["content"] = json!(json!({"@id": "did:sov:other", "@type": "rdfs:Class"}).to_string());
+
+            let res = ledger::build_rich_schema_request(DID_TRUSTEE, &rich_schema.to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_build_get_rich_schema_object_by_id_request_works() {
+            let request = ledger::build_get_rich_schema_object_by_id_request(None, RICH_SCHEMA_ID).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+            assert_eq!(json!({"type": constants::GET_RICH_SCHEMA_OBJECT_BY_ID, "id": RICH_SCHEMA_ID}), request["operation"]);
+        }
+
+        #[test]
+        fn indy_build_get_rich_schema_object_by_metadata_request_works() {
+            let request = ledger::build_get_rich_schema_object_by_metadata_request(Some(DID_TRUSTEE), "sch", "SchemaName", "1.0").unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+            assert_eq!(json!({
+                "type": constants::GET_RICH_SCHEMA_OBJECT_BY_METADATA,
+                "rsType": "sch",
+                "rsName": "SchemaName",
+                "rsVersion": "1.0"
+            }), request["operation"]);
+        }
+
+        #[test]
+        fn indy_build_get_rich_schema_object_by_metadata_request_works_for_unknown_type() {
+            let res = ledger::build_get_rich_schema_object_by_metadata_request(None, "unknown", "SchemaName", "1.0");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_parse_get_rich_schema_object_response_works() {
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": constants::GET_RICH_SCHEMA_OBJECT_BY_ID,
+                    "seqNo": 10,
+                    "txnTime": 1_600_000_000,
+                    "data": _rich_schema()
+                }
+            }).to_string();
+
+            let (id, rich_schema) = ledger::parse_get_rich_schema_object_response(&response).unwrap();
+            let rich_schema: serde_json::Value = serde_json::from_str(&rich_schema).unwrap();
+
+            assert_eq!(RICH_SCHEMA_ID, id);
+            assert_eq!(_rich_schema(), rich_schema);
+        }
+
+        #[test]
+        fn indy_parse_get_rich_schema_object_response_works_for_not_found() {
+            let response = json!({
+                "op": "REPLY",
+                "result": {
+                    "type": constants::GET_RICH_SCHEMA_OBJECT_BY_METADATA,
+                    "seqNo": null,
+                    "txnTime": null,
+                    "data": null
+                }
+            }).to_string();
+
+            let res = ledger::parse_get_rich_schema_object_response(&response);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
     }
 
     mod multi_sign_request {
@@ -831,6 +1008,49 @@ mod high_cases {
                 serde_json::from_str::<Reply<GetValidatorInfoResult>>(value).unwrap();
             }
         }
+
+        #[test]
+        fn indy_parse_get_validator_info_response_works() {
+            let response = json!({
+                "Node1": json!({"op": "REPLY", "result": {"type": constants::GET_VALIDATOR_INFO, "data": {"Node_info": {"Name": "Node1"}}}}).to_string(),
+                "Node2": json!({"op": "REJECT", "reqId": 1, "reason": "Not allowed"}).to_string(),
+                "Node3": "timeout",
+            }).to_string();
+
+            let results = ledger::parse_get_validator_info_response(&response).unwrap();
+            let results: serde_json::Value = serde_json::from_str(&results).unwrap();
+
+            let expected_results = json!({
+                "Node1": {"status": "REPLY", "data": {"Node_info": {"Name": "Node1"}}},
+                "Node2": {"status": "REJECT", "reason": "Not allowed"},
+                "Node3": {"status": "timeout"},
+            });
+            assert_eq!(expected_results, results);
+        }
+
+        #[test]
+        fn indy_parse_get_validator_info_response_works_for_invalid_response() {
+            let res = ledger::parse_get_validator_info_response(r#"["Node1"]"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_parse_get_validator_info_response_works_for_submitted_action() {
+            let setup = Setup::trustee();
+
+            let get_validator_info_request = ledger::build_get_validator_info_request(&setup.did).unwrap();
+            let get_validator_info_request = ledger::sign_request(setup.wallet_handle, &setup.did, &get_validator_info_request).unwrap();
+            let response = ledger::submit_action(setup.pool_handle, &get_validator_info_request, None, None).unwrap();
+
+            let results = ledger::parse_get_validator_info_response(&response).unwrap();
+            let results: HashMap<String, serde_json::Value> = serde_json::from_str(&results).unwrap();
+
+            assert_eq!(4, results.len());
+            for result in results.values() {
+                assert_eq!("REPLY", result["status"].as_str().unwrap());
+            }
+        }
     }
 
     mod get_txn_requests {
@@ -1009,6 +1229,33 @@ mod high_cases {
             let request = ledger::build_pool_restart_request(&setup.did, "cancel", None).unwrap();
             ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &request).unwrap();
         }
+
+        #[test]
+        fn indy_parse_pool_restart_response_works() {
+            let response = json!({
+                "Node1": json!({"op": "REPLY", "result": {"type": constants::POOL_RESTART, "action": "start"}}).to_string(),
+                "Node2": json!({"op": "REQNACK", "reqId": 1, "reason": "client request invalid"}).to_string(),
+            }).to_string();
+
+            let results = ledger::parse_pool_restart_response(&response).unwrap();
+            let results: serde_json::Value = serde_json::from_str(&results).unwrap();
+
+            let expected_results = json!({
+                "Node1": {"status": "REPLY", "data": {"type": constants::POOL_RESTART, "action": "start"}},
+                "Node2": {"status": "REQNACK", "reason": "client request invalid"},
+            });
+            assert_eq!(expected_results, results);
+        }
+
+        #[test]
+        fn indy_parse_pool_restart_response_works_for_other_type() {
+            let response = json!({
+                "Node1": json!({"op": "REPLY", "result": {"type": constants::GET_VALIDATOR_INFO, "data": {}}}).to_string(),
+            }).to_string();
+
+            let res = ledger::parse_pool_restart_response(&response);
+            assert_code!(ErrorCode::LedgerInvalidTransaction, res);
+        }
     }
 
     mod pool_upgrade {
@@ -2567,6 +2814,56 @@ mod high_cases {
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
 
+        #[test]
+        fn indy_set_txn_author_agreement_acceptance_works() {
+            Setup::empty();
+
+            ledger::set_txn_author_agreement_acceptance(Some(TEXT),
+                                                        Some(VERSION),
+                                                        None,
+                                                        Some(ACCEPTANCE_MECH_TYPE),
+                                                        TIME_OF_ACCEPTANCE).unwrap();
+
+            let request = ledger::build_nym_request(&IDENTIFIER, &DEST, None, None, None).unwrap();
+            _check_request_meta(&request);
+
+            ledger::set_txn_author_agreement_acceptance(None, None, None, None, 0).unwrap();
+
+            let request = ledger::build_nym_request(&IDENTIFIER, &DEST, None, None, None).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            assert!(request.get("taaAcceptance").is_none());
+        }
+
+        #[test]
+        fn indy_set_txn_author_agreement_acceptance_works_for_read_request() {
+            Setup::empty();
+
+            ledger::set_txn_author_agreement_acceptance(None,
+                                                        None,
+                                                        Some(HASH),
+                                                        Some(ACCEPTANCE_MECH_TYPE),
+                                                        TIME_OF_ACCEPTANCE).unwrap();
+
+            let request = ledger::build_get_nym_request(Some(IDENTIFIER), &DEST).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            assert!(request.get("taaAcceptance").is_none());
+
+            ledger::set_txn_author_agreement_acceptance(None, None, None, None, 0).unwrap();
+        }
+
+        #[test]
+        #[cfg(not(feature = "only_high_cases"))]
+        fn indy_set_txn_author_agreement_acceptance_works_for_missed_text_version_hash() {
+            Setup::empty();
+
+            let res = ledger::set_txn_author_agreement_acceptance(None,
+                                                                  None,
+                                                                  None,
+                                                                  Some(ACCEPTANCE_MECH_TYPE),
+                                                                  TIME_OF_ACCEPTANCE);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         fn _rand_string() -> String {
             rand::thread_rng()
                 .sample_iter(&Alphanumeric)
@@ -2702,6 +2999,38 @@ mod high_cases {
             _disable_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
         }
 
+        #[test]
+        fn indy_author_agreement_works_for_recorded_acceptance() {
+            let setup = Setup::trustee();
+
+            let (_, aml_label, aml_version, _) = _set_aml(setup.pool_handle, setup.wallet_handle, &setup.did);
+            let (taa_text, taa_version, taa_digest, _) = _set_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
+
+            let active_taa = ledger::get_active_txn_author_agreement(setup.pool_handle, None).unwrap();
+            let active_taa: serde_json::Value = serde_json::from_str(&active_taa).unwrap();
+            assert_eq!(active_taa["taa"]["text"], json!(taa_text));
+            assert_eq!(active_taa["taa"]["version"], json!(taa_version));
+            assert_eq!(active_taa["taa"]["digest"], json!(taa_digest));
+            assert_eq!(active_taa["aml"]["version"], json!(aml_version));
+            assert!(active_taa["aml"]["aml"].get(&aml_label).is_some());
+
+            ledger::set_txn_author_agreement_acceptance(None,
+                                                        None,
+                                                        active_taa["taa"]["digest"].as_str(),
+                                                        Some(&aml_label),
+                                                        time::get_time().sec as u64).unwrap();
+
+            let (did_, verkey_) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let nym_req = ledger::build_nym_request(&setup.did, &did_, Some(&verkey_), None, None).unwrap();
+            let nym_resp = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym_req).unwrap();
+            pool::check_response_type(&nym_resp, ResponseType::REPLY);
+
+            ledger::set_txn_author_agreement_acceptance(None, None, None, None, 0).unwrap();
+
+            _disable_taa(setup.pool_handle, setup.wallet_handle, &setup.did);
+        }
+
         #[test]
         #[cfg(not(feature = "only_high_cases"))]
         fn indy_reset_author_agreement_works() {
@@ -3014,6 +3343,72 @@ mod high_cases {
             let response = ledger::submit_request(setup.pool_handle, &request_2).unwrap();
             pool::check_response_type(&response, ResponseType::REQNACK);
         }
+
+        #[test]
+        fn indy_sign_request_for_endorser_works() {
+            let setup = Setup::wallet();
+
+            let (author_did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let request = ledger::build_schema_request(&author_did, SCHEMA_DATA).unwrap();
+            let request = ledger::sign_request_for_endorser(setup.wallet_handle, &author_did, &request, DID_TRUSTEE).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+            assert_eq!(DID_TRUSTEE, request["endorser"].as_str().unwrap());
+            assert!(request["signatures"][&author_did].is_string());
+            assert!(request.get("signature").is_none());
+        }
+
+        #[test]
+        #[cfg(not(feature = "only_high_cases"))]
+        fn indy_sign_request_for_endorser_works_for_not_author() {
+            let setup = Setup::wallet();
+
+            let (author_did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+            let (other_did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let request = ledger::build_schema_request(&author_did, SCHEMA_DATA).unwrap();
+            let res = ledger::sign_request_for_endorser(setup.wallet_handle, &other_did, &request, DID_TRUSTEE);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_endorse_and_submit_request_works() {
+            let setup = Setup::endorser();
+
+            let author_did = _setup_new_identity(setup.wallet_handle, setup.pool_handle);
+
+            let request = ledger::build_schema_request(&author_did, SCHEMA_DATA).unwrap();
+            let request = ledger::sign_request_for_endorser(setup.wallet_handle, &author_did, &request, &setup.did).unwrap();
+            let response = ledger::endorse_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &request).unwrap();
+            pool::check_response_type(&response, ResponseType::REPLY);
+        }
+
+        #[test]
+        #[cfg(not(feature = "only_high_cases"))]
+        fn indy_endorse_and_submit_request_works_for_other_endorser() {
+            let setup = Setup::endorser();
+
+            let author_did = _setup_new_identity(setup.wallet_handle, setup.pool_handle);
+
+            let request = ledger::build_schema_request(&author_did, SCHEMA_DATA).unwrap();
+            let request = ledger::sign_request_for_endorser(setup.wallet_handle, &author_did, &request, DID_TRUSTEE).unwrap();
+            let res = ledger::endorse_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &request);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        #[cfg(not(feature = "only_high_cases"))]
+        fn indy_endorse_and_submit_request_works_for_not_signed_by_author() {
+            let setup = Setup::endorser();
+
+            let author_did = _setup_new_identity(setup.wallet_handle, setup.pool_handle);
+
+            let request = ledger::build_schema_request(&author_did, SCHEMA_DATA).unwrap();
+            let request = ledger::append_request_endorser(&request, &setup.did).unwrap();
+            let res = ledger::endorse_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &request);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
     }
 }
 
@@ -3057,6 +3452,40 @@ mod medium_cases {
         }
     }
 
+    mod submit_read_request {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_read_request_works_for_not_supported_request_type() {
+            let setup = Setup::trustee();
+
+            let get_validator_info_request = ledger::build_get_validator_info_request(&setup.did).unwrap();
+            let res = ledger::submit_read_request(setup.pool_handle, &get_validator_info_request, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_read_request_works_for_invalid_options() {
+            let setup = Setup::trustee();
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&setup.did), &setup.did).unwrap();
+            let res = ledger::submit_read_request(setup.pool_handle, &get_nym_request, Some(r#"{"quorum": 0}"#));
+            assert_code!(ErrorCode::CommonInvalidParam4, res);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_submit_read_request_works_for_quorum_greater_than_nodes() {
+            let setup = Setup::trustee();
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&setup.did), &setup.did).unwrap();
+            let res = ledger::submit_read_request(setup.pool_handle, &get_nym_request, Some(r#"{"quorum": 5}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod submit_action {
         use super::*;
 
@@ -41,3 +41,21 @@ fn indy_set_default_logger_works() {
     wallet::close_wallet(wallet_handle).unwrap();
     test::cleanup_storage("indy_set_default_logger_works");
 }
+
+#[test]
+fn indy_set_correlation_id_works() {
+    const DEFAULT_WALLET_CONFIG: &str = r#"{"id":"indy_set_correlation_id_works","storage_type":"default"}"#;
+    test::cleanup_storage("indy_set_correlation_id_works");
+
+    wallet::create_wallet(DEFAULT_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+    logger::set_default_logger();
+    logger::set_correlation_id(Some("indy_set_correlation_id_works"));
+
+    let wallet_handle = wallet::open_wallet(DEFAULT_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+    logger::set_correlation_id(None);
+
+    wallet::close_wallet(wallet_handle).unwrap();
+    test::cleanup_storage("indy_set_correlation_id_works");
+}
@@ -73,6 +73,34 @@ mod collect {
         assert!(threadpool_threads_count.contains(&json!({"tags":{"label":"panic"},"value":0})));
     }
 
+    #[test]
+    fn collect_metrics_contains_command_queue_statistics() {
+        let result_metrics = metrics::collect_metrics().unwrap();
+        let metrics_map = serde_json::from_str::<HashMap<String, Value>>(&result_metrics).unwrap();
+
+        let command_queue_count = metrics_map
+            .get("command_queue_count")
+            .unwrap()
+            .as_array()
+            .unwrap();
+
+        assert!(command_queue_count.contains(&json!({"tags":{"label":"max"},"value":0})));
+    }
+
+    #[test]
+    fn collect_metrics_contains_cred_def_thread_pool_statistics() {
+        let result_metrics = metrics::collect_metrics().unwrap();
+        let metrics_map = serde_json::from_str::<HashMap<String, Value>>(&result_metrics).unwrap();
+
+        let threadpool_threads_count = metrics_map
+            .get("cred_def_threadpool_threads_count")
+            .unwrap()
+            .as_array()
+            .unwrap();
+
+        assert!(threadpool_threads_count.contains(&json!({"tags":{"label":"panic"},"value":0})));
+    }
+
     #[test]
     fn collect_metrics_includes_commands_count() {
         let setup = Setup::empty();
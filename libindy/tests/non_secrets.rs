@@ -232,6 +232,130 @@ mod high_cases {
         }
     }
 
+    mod add_records {
+        use super::*;
+
+        #[test]
+        fn indy_add_wallet_records_works() {
+            let setup = Setup::wallet();
+
+            let records = json!([
+                {"type": TYPE, "id": ID, "value": VALUE, "tags": tags_1()},
+                {"type": TYPE, "id": ID_2, "value": VALUE_2},
+            ]).to_string();
+
+            add_wallet_records(setup.wallet_handle, &records).unwrap();
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "value", VALUE);
+            check_record_field(setup.wallet_handle, TYPE, ID, "tags", TAGS);
+            check_record_field(setup.wallet_handle, TYPE, ID_2, "value", VALUE_2);
+        }
+
+        #[test]
+        fn indy_add_wallet_records_works_for_duplicate() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let records = json!([
+                {"type": TYPE, "id": ID, "value": VALUE},
+                {"type": TYPE, "id": ID_2, "value": VALUE_2},
+            ]).to_string();
+
+            let res = add_wallet_records(setup.wallet_handle, &records);
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod delete_records {
+        use super::*;
+
+        #[test]
+        fn indy_delete_wallet_records_works() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let record_ids = json!([{"type": TYPE, "id": ID}, {"type": TYPE, "id": ID_2}]).to_string();
+
+            delete_wallet_records(setup.wallet_handle, &record_ids).unwrap();
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID_2, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_delete_wallet_records_works_for_not_found_record() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let record_ids = json!([{"type": TYPE, "id": ID}, {"type": TYPE, "id": ID_2}]).to_string();
+
+            let res = delete_wallet_records(setup.wallet_handle, &record_ids);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "value", VALUE);
+        }
+    }
+
+    mod with_records {
+        use super::*;
+
+        #[test]
+        fn indy_with_wallet_records_works() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, Some(TAGS_2)).unwrap();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID_3, "value": VALUE_3},
+                {"op": "update", "type": TYPE, "id": ID, "value": VALUE_4, "tags": tags_1()},
+                {"op": "update", "type": TYPE, "id": ID_2, "tags": {}},
+                {"op": "delete", "type": TYPE, "id": ID_3},
+            ]).to_string();
+
+            with_wallet_records(setup.wallet_handle, &operations).unwrap();
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "value", VALUE_4);
+            check_record_field(setup.wallet_handle, TYPE, ID, "tags", TAGS);
+            check_record_field(setup.wallet_handle, TYPE, ID_2, "value", VALUE_2);
+            check_record_field(setup.wallet_handle, TYPE, ID_2, "tags", TAGS_EMPTY);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID_3, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_with_wallet_records_works_for_rollback() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let operations = json!([
+                {"op": "update", "type": TYPE, "id": ID, "value": VALUE_2},
+                {"op": "add", "type": TYPE, "id": ID_2, "value": VALUE_2},
+                {"op": "update", "type": TYPE, "id": ID_3, "value": VALUE_3},
+            ]).to_string();
+
+            let res = with_wallet_records(setup.wallet_handle, &operations);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            check_record_field(setup.wallet_handle, TYPE, ID, "value", VALUE);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID_2, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod get_record {
         use super::*;
 
@@ -1121,6 +1245,55 @@ mod medium_cases {
         }
     }
 
+    mod with_records {
+        use super::*;
+
+        #[test]
+        fn indy_with_wallet_records_works_for_invalid_handle() {
+            Setup::empty();
+
+            let operations = json!([{"op": "delete", "type": TYPE, "id": ID}]).to_string();
+
+            let res = with_wallet_records(INVALID_WALLET_HANDLE, &operations);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_with_wallet_records_works_for_empty_operations() {
+            let setup = Setup::wallet();
+
+            let res = with_wallet_records(setup.wallet_handle, "[]");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_with_wallet_records_works_for_invalid_operation() {
+            let setup = Setup::wallet();
+
+            let res = with_wallet_records(setup.wallet_handle, &json!([{"op": "rename", "type": TYPE, "id": ID}]).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = with_wallet_records(setup.wallet_handle, &json!([{"op": "update", "type": TYPE, "id": ID}]).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_with_wallet_records_works_for_invalid_type() {
+            let setup = Setup::wallet();
+
+            let operations = json!([
+                {"op": "add", "type": TYPE, "id": ID, "value": VALUE},
+                {"op": "add", "type": FORBIDDEN_TYPE, "id": ID, "value": VALUE},
+            ]).to_string();
+
+            let res = with_wallet_records(setup.wallet_handle, &operations);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
     mod get_record {
         use super::*;
 
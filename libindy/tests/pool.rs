@@ -110,6 +110,179 @@ mod high_cases {
         }
     }
 
+    mod get_pool_handle_for_network {
+        use super::*;
+        use crate::utils::{did, ledger};
+        use crate::utils::types::ResponseType;
+        use crate::api::INVALID_POOL_HANDLE;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn get_pool_handle_for_network_works() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(r#"{"namespace": "test:local"}"#)).unwrap();
+
+            assert_eq!(pool_handle, pool::get_pool_handle_for_network("test:local").unwrap());
+            assert_eq!(pool_handle, pool::get_pool_handle_for_network("did:indy:test:local:NcYxiDXkpYi6ov5FcYDi1e").unwrap());
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn sign_and_submit_request_works_for_routing_by_did() {
+            let setup = Setup::wallet();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(r#"{"namespace": "test"}"#)).unwrap();
+
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, &json!({"seed": TRUSTEE_SEED, "method_name": "indy:test"}).to_string()).unwrap();
+            assert!(my_did.starts_with("did:indy:test:"));
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&my_did), &my_did).unwrap();
+            let get_nym_response = ledger::sign_and_submit_request(INVALID_POOL_HANDLE, setup.wallet_handle, &my_did, &get_nym_request).unwrap();
+            pool::check_response_type(&get_nym_response, ResponseType::REPLY);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
+    mod mock_ledger {
+        use super::*;
+        use crate::utils::{anoncreds, did, ledger};
+        use crate::utils::types::ResponseType;
+        use serde_json::Value;
+
+        const MOCK_CONFIG: &str = r#"{"mock": true}"#;
+
+        #[test]
+        fn mock_ledger_works_for_nym() {
+            let setup = Setup::wallet();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(MOCK_CONFIG)).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_verkey) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let nym_request = ledger::build_nym_request(&trustee_did, &my_did, Some(&my_verkey), None, None).unwrap();
+            let nym_response = ledger::sign_and_submit_request(pool_handle, setup.wallet_handle, &trustee_did, &nym_request).unwrap();
+            pool::check_response_type(&nym_response, ResponseType::REPLY);
+
+            let get_nym_request = ledger::build_get_nym_request(Some(&my_did), &my_did).unwrap();
+            let get_nym_response = ledger::submit_request(pool_handle, &get_nym_request).unwrap();
+
+            let nym_data = ledger::parse_get_nym_response(&get_nym_response).unwrap();
+            let nym_data: Value = serde_json::from_str(&nym_data).unwrap();
+            assert_eq!(my_verkey, nym_data["verkey"]);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn mock_ledger_works_for_schema() {
+            let setup = Setup::wallet();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(MOCK_CONFIG)).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let (schema_id, schema_json) = anoncreds::issuer_create_schema(&trustee_did, GVT_SCHEMA_NAME, SCHEMA_VERSION, GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+            let schema_request = ledger::build_schema_request(&trustee_did, &schema_json).unwrap();
+            let schema_response = ledger::sign_and_submit_request(pool_handle, setup.wallet_handle, &trustee_did, &schema_request).unwrap();
+            pool::check_response_type(&schema_response, ResponseType::REPLY);
+
+            let get_schema_request = ledger::build_get_schema_request(Some(&trustee_did), &schema_id).unwrap();
+            let get_schema_response = ledger::submit_request(pool_handle, &get_schema_request).unwrap();
+
+            let (received_schema_id, received_schema_json) = ledger::parse_get_schema_response(&get_schema_response).unwrap();
+            let received_schema: Value = serde_json::from_str(&received_schema_json).unwrap();
+            assert_eq!(schema_id, received_schema_id);
+            assert_eq!(1, received_schema["seqNo"]);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn mock_ledger_works_for_not_signed_write_request() {
+            let setup = Setup::wallet();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(MOCK_CONFIG)).unwrap();
+
+            let (trustee_did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let nym_request = ledger::build_nym_request(&trustee_did, &trustee_did, None, None, None).unwrap();
+            let nym_response = ledger::submit_request(pool_handle, &nym_request).unwrap();
+            pool::check_response_type(&nym_response, ResponseType::REQNACK);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
+    mod validate_genesis_txns {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn validate_genesis_txns_works() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+
+            let report = pool::validate_genesis_txns(txn_file_path.to_str().unwrap(), None).unwrap();
+            let report: Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(true, report["valid"]);
+            assert_eq!(4, report["nodes_count"]);
+            assert!(report["issues"].as_array().unwrap().is_empty());
+        }
+
+        #[test]
+        fn validate_genesis_txns_works_for_nodes_without_client_address() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool_with_invalid_nodes(&setup.name, None);
+
+            let report = pool::validate_genesis_txns(txn_file_path.to_str().unwrap(), None).unwrap();
+            let report: Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(false, report["valid"]);
+            assert!(report["issues"].as_array().unwrap().iter().all(|issue| issue["kind"] == "missing_client_address"));
+        }
+    }
+
+    mod diff_genesis_txns {
+        use super::*;
+        use serde_json::Value;
+
+        #[test]
+        fn diff_genesis_txns_works() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let new_txn_file_path = environment::tmp_file_path("diff_genesis.txn");
+            let new_txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, Some(3), Some(new_txn_file_path.as_path()));
+
+            let diff = pool::diff_genesis_txns(&setup.name, new_txn_file_path.to_str().unwrap()).unwrap();
+            let diff: Value = serde_json::from_str(&diff).unwrap();
+
+            assert!(diff["added"].as_array().unwrap().is_empty());
+            assert_eq!("Node4", diff["removed"][0]["alias"]);
+            assert_eq!(3, diff["unchanged"]);
+        }
+    }
+
     mod refresh {
         use super::*;
 
@@ -331,6 +504,21 @@ mod medium_cases {
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
 
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn open_pool_ledger_works_for_invalid_namespace() {
+            let setup = Setup::empty();
+
+            let config = r#"{"namespace": "Test:"}"#;
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let res = pool::open_pool_ledger(&setup.name, Some(config));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         #[cfg(feature = "local_nodes_pool")]
         fn open_pool_ledger_works_for_incompatible_protocol_version() {
@@ -382,6 +570,66 @@ mod medium_cases {
         }
     }
 
+    mod get_pool_handle_for_network {
+        use super::*;
+
+        #[test]
+        fn get_pool_handle_for_network_works_for_unknown_namespace() {
+            Setup::empty();
+
+            let res = pool::get_pool_handle_for_network("unknown");
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
+    mod validate_genesis_txns {
+        use super::*;
+
+        #[test]
+        fn validate_genesis_txns_works_for_invalid_options() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+
+            let res = pool::validate_genesis_txns(txn_file_path.to_str().unwrap(), Some(r#"{"endpoint_timeout": 0}"#));
+            assert_code!(ErrorCode::CommonInvalidParam3, res);
+        }
+    }
+
+    mod diff_genesis_txns {
+        use super::*;
+
+        #[test]
+        fn diff_genesis_txns_works_for_unknown_config() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+
+            let res = pool::diff_genesis_txns("unknown_pool", txn_file_path.to_str().unwrap());
+            assert_code!(ErrorCode::PoolLedgerNotCreatedError, res);
+        }
+    }
+
+    mod mock_ledger {
+        use super::*;
+        use crate::utils::{anoncreds, ledger};
+
+        #[test]
+        fn mock_ledger_works_for_unknown_schema() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(r#"{"mock": true}"#)).unwrap();
+
+            let get_schema_request = ledger::build_get_schema_request(None, &anoncreds::gvt_schema_id()).unwrap();
+            let get_schema_response = ledger::submit_request(pool_handle, &get_schema_request).unwrap();
+
+            let res = ledger::parse_get_schema_response(&get_schema_response);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
     mod close {
         use super::*;
 
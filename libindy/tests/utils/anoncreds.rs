@@ -8,7 +8,7 @@ use serde_json;
 use crate::utils::{environment, wallet, blob_storage, test};
 use crate::utils::types::CredentialOfferInfo;
 
-use std::sync::Once;
+use std::sync::{Arc, Mutex, Once};
 use std::mem;
 use crate::utils::constants::*;
 
@@ -57,6 +57,20 @@ pub fn issuer_create_credential_definition(wallet_handle: WalletHandle, issuer_d
     anoncreds::issuer_create_and_store_credential_def(wallet_handle, issuer_did, schema, tag, signature_type, config.unwrap_or("{}")).wait() // TODO: FIXME OPTIONAL CONFIG
 }
 
+pub fn issuer_create_credential_definition_with_progress(wallet_handle: WalletHandle, issuer_did: &str, schema: &str, tag: &str,
+                                                         signature_type: Option<&str>, config: Option<&str>) -> Result<(String, String, Vec<String>), IndyError> {
+    let stages: Arc<Mutex<Vec<String>>> = Default::default();
+    let stages_ = stages.clone();
+
+    let (cred_def_id, cred_def_json) =
+        anoncreds::issuer_create_and_store_credential_def_with_progress(wallet_handle, issuer_did, schema, tag, signature_type, config.unwrap_or("{}"),
+                                                                        Box::new(move |stage| stages_.lock().unwrap().push(stage.to_string())))
+            .wait()?;
+
+    let stages = stages.lock().unwrap().clone();
+    Ok((cred_def_id, cred_def_json, stages))
+}
+
 pub fn issuer_rotate_credential_def_start(wallet_handle: WalletHandle, cred_def_id: &str, config_json: Option<&str>) -> Result<String, IndyError> {
     anoncreds::issuer_rotate_credential_def_start(wallet_handle, cred_def_id, config_json).wait()
 }
@@ -80,6 +94,11 @@ pub fn issuer_create_credential(wallet_handle: WalletHandle, cred_offer_json: &s
     anoncreds::issuer_create_credential(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait() // TODO OPTIONAL blob_storage_reader_handle
 }
 
+pub fn issuer_create_credentials(wallet_handle: WalletHandle, cred_offer_json: &str, cred_reqs_json: &str,
+                                 rev_reg_id: Option<&str>, blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>), IndyError> {
+    anoncreds::issuer_create_credentials(wallet_handle, cred_offer_json, cred_reqs_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
 pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
@@ -115,6 +134,10 @@ pub fn prover_delete_credential(wallet_handle: WalletHandle, cred_id: &str) -> R
     anoncreds::prover_delete_credential(wallet_handle, cred_id).wait()
 }
 
+pub fn prover_delete_credential_with_related(wallet_handle: WalletHandle, cred_id: &str, related_records_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_delete_credential_with_related(wallet_handle, cred_id, related_records_json).wait()
+}
+
 //TODO mark as deprecated and use only in target tests
 pub fn prover_get_credentials(wallet_handle: WalletHandle, filter_json: &str) -> Result<String, IndyError> {
     anoncreds::prover_get_credentials(wallet_handle, Some(filter_json)).wait()
@@ -124,6 +147,14 @@ pub fn prover_get_credential(wallet_handle: WalletHandle, cred_id: &str) -> Resu
     anoncreds::prover_get_credential(wallet_handle, cred_id).wait()
 }
 
+pub fn prover_credential_to_w3c(cred_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_credential_to_w3c(cred_json).wait()
+}
+
+pub fn prover_credential_from_w3c(w3c_cred_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_credential_from_w3c(w3c_cred_json).wait()
+}
+
 pub fn prover_search_credentials(wallet_handle: WalletHandle, filter_json: &str) -> Result<(i32, usize), IndyError> {
     anoncreds::prover_search_credentials(wallet_handle, Some(filter_json)).wait()
 }
@@ -160,11 +191,30 @@ pub fn prover_create_proof(wallet_handle: WalletHandle, proof_req_json: &str, re
                                    master_secret_name, schemas_json, cred_defs_json, rev_states_json).wait()
 }
 
+pub fn prover_create_proof_with_details(wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str,
+                                        master_secret_name: &str, schemas_json: &str, cred_defs_json: &str,
+                                        rev_states_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_create_proof_with_details(wallet_handle, proof_req_json, requested_credentials_json,
+                                                master_secret_name, schemas_json, cred_defs_json, rev_states_json).wait()
+}
+
+pub fn prover_create_proofs(wallet_handle: WalletHandle, proof_requests_json: &str, master_secret_name: &str,
+                            schemas_json: &str, cred_defs_json: &str, rev_states_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_create_proofs(wallet_handle, proof_requests_json, master_secret_name,
+                                    schemas_json, cred_defs_json, rev_states_json).wait()
+}
+
 pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas_json: &str,
                              cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<bool, IndyError> {
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_verify_proof_with_details(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                          cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str,
+                                          options_json: Option<&str>) -> Result<String, IndyError> {
+    anoncreds::verifier_verify_proof_with_details(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json, options_json).wait()
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -179,10 +229,26 @@ pub fn generate_nonce() -> Result<String, IndyError> {
     anoncreds::generate_nonce().wait()
 }
 
+pub fn verifier_create_proof_request_from_presentation_definition(presentation_definition_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_create_proof_request_from_presentation_definition(presentation_definition_json).wait()
+}
+
+pub fn verifier_create_presentation_submission(presentation_definition_json: &str, proof_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_create_presentation_submission(presentation_definition_json, proof_json).wait()
+}
+
 pub fn to_unqualified(entity: &str) -> Result<String, IndyError> {
     anoncreds::to_unqualified(entity).wait()
 }
 
+pub fn encode_credential_attribute(raw_value: &str) -> Result<String, IndyError> {
+    anoncreds::encode_credential_attribute(raw_value).wait()
+}
+
+pub fn validate_credential_values(cred_values_json: &str) -> Result<(), IndyError> {
+    anoncreds::validate_credential_values(cred_values_json).wait()
+}
+
 pub fn default_cred_def_config() -> String {
     serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false }).unwrap()
 }
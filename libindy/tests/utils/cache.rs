@@ -15,10 +15,30 @@ pub fn get_cred_def_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle,
     cache::get_cred_def(pool_handle, wallet_handle, submitter_did, id, options_json).wait()
 }
 
+pub fn get_rev_reg_def_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
+    cache::get_rev_reg_def(pool_handle, wallet_handle, submitter_did, id, options_json).wait()
+}
+
 pub fn purge_schema_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
     cache::purge_schema_cache(wallet_handle, options_json).wait()
 }
 
 pub fn purge_cred_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
     cache::purge_cred_def_cache(wallet_handle, options_json).wait()
-}
\ No newline at end of file
+}
+
+pub fn purge_rev_reg_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_rev_reg_def_cache(wallet_handle, options_json).wait()
+}
+
+pub fn get_rich_schema_object_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
+    cache::get_rich_schema_object(pool_handle, wallet_handle, submitter_did, id, options_json).wait()
+}
+
+pub fn purge_rich_schema_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_rich_schema_cache(wallet_handle, options_json).wait()
+}
+
+pub fn prefetch_proof_artifacts(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, proof_request_json: &str, identifiers_json: &str, options_json: &str) -> Result<String, IndyError> {
+    cache::prefetch_proof_artifacts(pool_handle, wallet_handle, submitter_did, proof_request_json, identifiers_json, options_json).wait()
+}
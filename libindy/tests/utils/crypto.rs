@@ -1,16 +1,41 @@
 extern crate futures;
 
-use indy::IndyError;
+use indy::{ErrorCode, IndyError};
 use indy::crypto;
 use self::futures::Future;
 
 use indy::WalletHandle;
 
+use crate::utils::sequence;
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::slice;
+use std::sync::Mutex;
+use super::libc::c_char;
+
+use rust_base58::ToBase58;
+use sodiumoxide::crypto::sign as ed25519;
+
 pub fn create_key(wallet_handle: WalletHandle, seed: Option<&str>) -> Result<String, IndyError> {
     let key_json = json!({"seed": seed}).to_string();
     crypto::create_key(wallet_handle, Some(&key_json)).wait()
 }
 
+pub fn create_key_for_crypto_type(wallet_handle: WalletHandle, crypto_type: &str) -> Result<String, IndyError> {
+    let key_json = json!({"crypto_type": crypto_type}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
+pub fn create_key_with_info(wallet_handle: WalletHandle, key_json: &str) -> Result<String, IndyError> {
+    crypto::create_key(wallet_handle, Some(key_json)).wait()
+}
+
+pub fn create_kms_key(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    let key_json = json!({"kms": TEST_KEY_MANAGEMENT}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
 pub fn set_key_metadata(wallet_handle: WalletHandle, verkey: &str, metadata: &str) -> Result<(), IndyError> {
     crypto::set_key_metadata(wallet_handle, verkey, metadata).wait()
 }
@@ -49,4 +74,112 @@ pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys:
 
 pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::unpack_message(wallet_handle, jwe).wait()
-}
\ No newline at end of file
+}
+pub fn sign_jws(wallet_handle: WalletHandle, signer_vks_json: &str, payload: &[u8], options_json: Option<&str>) -> Result<String, IndyError> {
+    crypto::sign_jws(wallet_handle, signer_vks_json, payload, options_json).wait()
+}
+
+pub fn verify_jws(jws: &str) -> Result<(String, Vec<u8>), IndyError> {
+    crypto::verify_jws(jws).wait()
+}
+
+pub fn ed25519_to_x25519(verkey: &str) -> Result<String, IndyError> {
+    crypto::ed25519_to_x25519(verkey).wait()
+}
+
+pub fn x25519_diffie_hellman(wallet_handle: WalletHandle, my_vk: &str, their_x25519_key: &str) -> Result<Vec<u8>, IndyError> {
+    crypto::x25519_diffie_hellman(wallet_handle, my_vk, their_x25519_key).wait()
+}
+
+pub fn bls_create_keys(seed: Option<&str>) -> Result<String, IndyError> {
+    crypto::bls_create_keys(seed).wait()
+}
+
+pub fn bls_sign(sign_key: &str, msg: &[u8]) -> Result<String, IndyError> {
+    crypto::bls_sign(sign_key, msg).wait()
+}
+
+pub fn bls_aggregate_signatures(signatures_json: &str) -> Result<String, IndyError> {
+    crypto::bls_aggregate_signatures(signatures_json).wait()
+}
+
+pub fn bls_verify_multi_sig(multi_sig: &str, msg: &[u8], ver_keys_json: &str) -> Result<bool, IndyError> {
+    crypto::bls_verify_multi_sig(multi_sig, msg, ver_keys_json).wait()
+}
+
+pub const TEST_KEY_MANAGEMENT: &str = "test";
+
+lazy_static! {
+    static ref KMS_KEYS: Mutex<HashMap<String, ed25519::SecretKey>> = Default::default();
+    static ref KMS_BUFFERS: Mutex<HashMap<i32, (Option<CString>, Option<CString>, Option<Vec<u8>>)>> = Default::default();
+}
+
+// Emulates HSM: private keys never leave KMS_KEYS, libindy gets only the key id
+pub fn register_test_key_management() -> Result<(), IndyError> {
+    lazy_static! {
+        static ref REGISTERED: Mutex<bool> = Default::default();
+    }
+
+    let mut registered = REGISTERED.lock().unwrap();
+
+    if *registered {
+        return Ok(());
+    }
+
+    crypto::register_key_management(TEST_KEY_MANAGEMENT, Some(_kms_create_key), Some(_kms_sign), Some(_kms_free)).wait()?;
+
+    *registered = true;
+    Ok(())
+}
+
+extern fn _kms_create_key(crypto_type: *const c_char, key_id_p: *mut *const c_char, verkey_p: *mut *const c_char, key_handle_p: *mut i32) -> i32 {
+    let crypto_type = unsafe { CStr::from_ptr(crypto_type).to_str().unwrap() };
+
+    if crypto_type != "ed25519" {
+        return ErrorCode::UnknownCryptoTypeError as i32;
+    }
+
+    let (pk, sk) = ed25519::gen_keypair();
+    let key_id = format!("hsm-key-{}", sequence::get_next_id());
+    KMS_KEYS.lock().unwrap().insert(key_id.clone(), sk);
+
+    let key_id = CString::new(key_id).unwrap();
+    let verkey = CString::new(pk[..].to_base58()).unwrap();
+    let handle = sequence::get_next_id();
+
+    unsafe {
+        *key_id_p = key_id.as_ptr();
+        *verkey_p = verkey.as_ptr();
+        *key_handle_p = handle;
+    }
+
+    KMS_BUFFERS.lock().unwrap().insert(handle, (Some(key_id), Some(verkey), None));
+    ErrorCode::Success as i32
+}
+
+extern fn _kms_sign(key_id: *const c_char, _crypto_type: *const c_char, message_raw: *const u8, message_len: u32,
+                    signature_raw_p: *mut *const u8, signature_len_p: *mut u32, signature_handle_p: *mut i32) -> i32 {
+    let key_id = unsafe { CStr::from_ptr(key_id).to_str().unwrap() };
+    let message = unsafe { slice::from_raw_parts(message_raw, message_len as usize) };
+
+    let signature = match KMS_KEYS.lock().unwrap().get(key_id) {
+        Some(sk) => ed25519::sign_detached(message, sk)[..].to_vec(),
+        None => return ErrorCode::WalletItemNotFound as i32
+    };
+
+    let handle = sequence::get_next_id();
+
+    unsafe {
+        *signature_raw_p = signature.as_ptr();
+        *signature_len_p = signature.len() as u32;
+        *signature_handle_p = handle;
+    }
+
+    KMS_BUFFERS.lock().unwrap().insert(handle, (None, None, Some(signature)));
+    ErrorCode::Success as i32
+}
+
+extern fn _kms_free(handle: i32) -> i32 {
+    KMS_BUFFERS.lock().unwrap().remove(&handle);
+    ErrorCode::Success as i32
+}
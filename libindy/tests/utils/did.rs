@@ -63,6 +63,10 @@ pub fn replace_keys_apply(wallet_handle: WalletHandle, did: &str) -> Result<(),
     did::replace_keys_apply(wallet_handle, did).wait()
 }
 
+pub fn rotate_key(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, identity_json: &str) -> Result<String, IndyError> {
+    did::rotate_key(wallet_handle, pool_handle, did, identity_json).wait()
+}
+
 pub fn replace_keys(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
     let verkey = did::replace_keys_start(wallet_handle, did, "{}").wait().unwrap();
 
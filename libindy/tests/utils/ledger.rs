@@ -43,6 +43,10 @@ pub fn submit_action(pool_handle: PoolHandle, request_json: &str, nodes: Option<
     ledger::submit_action(pool_handle, request_json, nodes, timeout).wait()
 }
 
+pub fn submit_read_request(pool_handle: PoolHandle, request_json: &str, options_json: Option<&str>) -> Result<String, IndyError> {
+    ledger::submit_read_request(pool_handle, request_json, options_json).wait()
+}
+
 pub fn sign_request(wallet_handle: WalletHandle, submitter_did: &str, request_json: &str) -> Result<String, IndyError> {
     ledger::sign_request(wallet_handle, submitter_did, request_json).wait()
 }
@@ -134,6 +138,10 @@ pub fn build_get_validator_info_request(submitter_did: &str) -> Result<String, I
     ledger::build_get_validator_info_request(submitter_did).wait()
 }
 
+pub fn parse_get_validator_info_response(get_validator_info_response: &str) -> Result<String, IndyError> {
+    ledger::parse_get_validator_info_response(get_validator_info_response).wait()
+}
+
 pub fn build_get_txn_request(submitter_did: Option<&str>, data: i32, ledger_type: Option<&str>) -> Result<String, IndyError> {
     ledger::build_get_txn_request(submitter_did, ledger_type, data).wait()
 }
@@ -148,6 +156,10 @@ pub fn build_pool_restart_request(submitter_did: &str,
     ledger::build_pool_restart_request(submitter_did, action, datetime).wait()
 }
 
+pub fn parse_pool_restart_response(pool_restart_response: &str) -> Result<String, IndyError> {
+    ledger::parse_pool_restart_response(pool_restart_response).wait()
+}
+
 pub fn build_pool_upgrade_request(submitter_did: &str, name: &str, version: &str, action: &str, sha256: &str, timeout: Option<u32>, schedule: Option<&str>,
                                   justification: Option<&str>, reinstall: bool, force: bool, package: Option<&str>) -> Result<String, IndyError> {
     ledger::build_pool_upgrade_request(submitter_did, name, version, action, sha256,
@@ -278,11 +290,37 @@ pub fn append_txn_author_agreement_acceptance_to_request(request_json: &str,
     ledger::append_txn_author_agreement_acceptance_to_request(request_json, text, version, taa_digest, acc_mech_type, time_of_acceptance).wait()
 }
 
+pub fn set_txn_author_agreement_acceptance(text: Option<&str>,
+                                           version: Option<&str>,
+                                           taa_digest: Option<&str>,
+                                           acc_mech_type: Option<&str>,
+                                           time_of_acceptance: u64) -> Result<(), IndyError> {
+    ledger::set_txn_author_agreement_acceptance(text, version, taa_digest, acc_mech_type, time_of_acceptance).wait()
+}
+
+pub fn get_active_txn_author_agreement(pool_handle: PoolHandle, submitter_did: Option<&str>) -> Result<String, IndyError> {
+    ledger::get_active_txn_author_agreement(pool_handle, submitter_did).wait()
+}
+
 pub fn append_request_endorser(request_json: &str,
                                endorser_did: &str) -> Result<String, IndyError> {
     ledger::append_request_endorser(request_json, endorser_did).wait()
 }
 
+pub fn sign_request_for_endorser(wallet_handle: WalletHandle,
+                                 author_did: &str,
+                                 request_json: &str,
+                                 endorser_did: &str) -> Result<String, IndyError> {
+    ledger::sign_request_for_endorser(wallet_handle, author_did, request_json, endorser_did).wait()
+}
+
+pub fn endorse_and_submit_request(pool_handle: PoolHandle,
+                                  wallet_handle: WalletHandle,
+                                  endorser_did: &str,
+                                  request_json: &str) -> Result<String, IndyError> {
+    ledger::endorse_and_submit_request(pool_handle, wallet_handle, endorser_did, request_json).wait()
+}
+
 pub fn post_entities() -> (&'static str, &'static str, &'static str) {
     lazy_static! {
                     static ref COMMON_ENTITIES_INIT: Once = Once::new();
@@ -427,4 +465,23 @@ pub fn build_ledgers_freeze_request(submitter_did: &str, ledgers_ids: Vec<u64>)
 
 pub fn get_frozen_ledgers_request(submitter_did: &str) -> Result<String, IndyError> {
     ledger::build_get_frozen_ledgers_request(submitter_did).wait()
-}
\ No newline at end of file
+}
+
+pub fn parse_get_frozen_ledgers_response(get_frozen_ledgers_response: &str) -> Result<String, IndyError> {
+    ledger::parse_get_frozen_ledgers_response(get_frozen_ledgers_response).wait()
+}
+pub fn build_rich_schema_request(submitter_did: &str, data: &str) -> Result<String, IndyError> {
+    ledger::build_rich_schema_request(submitter_did, data).wait()
+}
+
+pub fn build_get_rich_schema_object_by_id_request(submitter_did: Option<&str>, id: &str) -> Result<String, IndyError> {
+    ledger::build_get_rich_schema_object_by_id_request(submitter_did, id).wait()
+}
+
+pub fn build_get_rich_schema_object_by_metadata_request(submitter_did: Option<&str>, rs_type: &str, rs_name: &str, rs_version: &str) -> Result<String, IndyError> {
+    ledger::build_get_rich_schema_object_by_metadata_request(submitter_did, rs_type, rs_name, rs_version).wait()
+}
+
+pub fn parse_get_rich_schema_object_response(get_rich_schema_object_response: &str) -> Result<(String, String), IndyError> {
+    ledger::parse_get_rich_schema_object_response(get_rich_schema_object_response).wait()
+}
@@ -31,4 +31,7 @@ pub fn set_logger(logger: &'static dyn log::Log) {
 
 pub fn set_default_logger() {
     logger::set_default_logger(None).ok();
-}
\ No newline at end of file
+}
+pub fn set_correlation_id(correlation_id: Option<&str>) {
+    logger::set_correlation_id(correlation_id).unwrap();
+}
@@ -62,6 +62,18 @@ pub fn delete_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str)
     wallet::delete_wallet_record(wallet_handle, type_, id).wait()
 }
 
+pub fn add_wallet_records(wallet_handle: WalletHandle, records_json: &str) -> Result<(), IndyError> {
+    wallet::add_wallet_records(wallet_handle, records_json).wait()
+}
+
+pub fn delete_wallet_records(wallet_handle: WalletHandle, record_ids_json: &str) -> Result<(), IndyError> {
+    wallet::delete_wallet_records(wallet_handle, record_ids_json).wait()
+}
+
+pub fn with_wallet_records(wallet_handle: WalletHandle, operations_json: &str) -> Result<(), IndyError> {
+    wallet::with_wallet_records(wallet_handle, operations_json).wait()
+}
+
 pub fn get_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
     wallet::get_wallet_record(wallet_handle, type_, id, options_json).wait()
 }
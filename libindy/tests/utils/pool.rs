@@ -186,6 +186,18 @@ pub fn set_protocol_version(protocol_version: usize) -> Result<(), IndyError> {
     pool::set_protocol_version(protocol_version).wait()
 }
 
+pub fn get_pool_handle_for_network(network: &str) -> Result<PoolHandle, IndyError> {
+    pool::get_pool_handle_for_network(network).wait()
+}
+
+pub fn validate_genesis_txns(genesis_txn: &str, options: Option<&str>) -> Result<String, IndyError> {
+    pool::validate_pool_genesis_txns(genesis_txn, options).wait()
+}
+
+pub fn diff_genesis_txns(config_name: &str, genesis_txn: &str) -> Result<String, IndyError> {
+    pool::diff_pool_genesis_txns(config_name, genesis_txn).wait()
+}
+
 pub fn check_response_type(response: &str, _type: ResponseType) {
     let response: Response = serde_json::from_str(&response).unwrap();
     assert_eq!(response.op, _type);
@@ -10,9 +10,9 @@ use self::futures::Future;
 use crate::utils::{callback, sequence, environment};
 use crate::utils::inmem_wallet::InmemWallet;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use super::libc::c_char;
 
 use crate::utils::constants::{TYPE, INMEM_TYPE, WALLET_CREDENTIALS};
@@ -121,10 +121,26 @@ pub fn export_wallet(wallet_handle: WalletHandle, export_config_json: &str) -> R
     wallet::export_wallet(wallet_handle, export_config_json).wait()
 }
 
+pub fn start_wallet_reencryption(wallet_handle: WalletHandle, reencryption_config: &str) -> Result<(), IndyError> {
+    wallet::start_wallet_reencryption(wallet_handle, reencryption_config).wait()
+}
+
+pub fn reencrypt_wallet_records(wallet_handle: WalletHandle, batch_size: usize) -> Result<String, IndyError> {
+    wallet::reencrypt_wallet_records(wallet_handle, batch_size).wait()
+}
+
+pub fn get_wallet_reencryption_status(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    wallet::get_wallet_reencryption_status(wallet_handle).wait()
+}
+
 pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Result<(), IndyError> {
     wallet::import_wallet(config, credentials, import_config).wait()
 }
 
+pub fn import_wallet_records(wallet_handle: WalletHandle, import_config: &str) -> Result<String, IndyError> {
+    wallet::import_wallet_records(wallet_handle, import_config).wait()
+}
+
 pub fn export_wallet_path(name: &str) -> PathBuf {
     environment::tmp_file_path(name)
 }
@@ -141,6 +157,86 @@ pub fn generate_wallet_key(config: Option<&str>) -> Result<String, IndyError> {
     wallet::generate_wallet_key(config).wait()
 }
 
+pub fn generate_mnemonic(config: Option<&str>) -> Result<String, IndyError> {
+    wallet::generate_mnemonic(config).wait()
+}
+
+pub fn split_wallet_key(key: &str, config: &str) -> Result<String, IndyError> {
+    wallet::split_wallet_key(key, config).wait()
+}
+
+pub fn prepare_sealed_export_wallet_config(path: &Path) -> String {
+    json!({
+        "path": path.to_str().unwrap(),
+        "sealed": {
+            "key_wrapper": TEST_KEY_WRAPPER,
+            "key_id": "wallet-backup",
+        }
+    }).to_string()
+}
+
+pub const TEST_KEY_WRAPPER: &str = "test";
+
+lazy_static! {
+    static ref WRAPPED_KEYS: Mutex<HashMap<i32, CString>> = Default::default();
+}
+
+// Emulates KMS transit engine: the key is "wrapped" by prefixing it with the transit key id
+pub fn register_test_key_wrapper() -> Result<(), IndyError> {
+    lazy_static! {
+        static ref REGISTERED: Mutex<bool> = Default::default();
+    }
+
+    let mut registered = REGISTERED.lock().unwrap();
+
+    if *registered {
+        return Ok(());
+    }
+
+    wallet::register_wallet_key_wrapper(TEST_KEY_WRAPPER, Some(_wrap_key), Some(_unwrap_key), Some(_free_key)).wait()?;
+
+    *registered = true;
+    Ok(())
+}
+
+fn _store_key(key: String, key_p: *mut *const c_char, key_handle_p: *mut i32) -> i32 {
+    let key = CString::new(key).unwrap();
+    let handle = sequence::get_next_id();
+
+    unsafe {
+        *key_p = key.as_ptr();
+        *key_handle_p = handle;
+    }
+
+    WRAPPED_KEYS.lock().unwrap().insert(handle, key);
+    ErrorCode::Success as i32
+}
+
+extern fn _wrap_key(key_id: *const c_char, key: *const c_char, wrapped_key_p: *mut *const c_char, wrapped_key_handle_p: *mut i32) -> i32 {
+    let key_id = unsafe { CStr::from_ptr(key_id).to_str().unwrap() };
+    let key = unsafe { CStr::from_ptr(key).to_str().unwrap() };
+
+    _store_key(format!("{}:{}", key_id, key), wrapped_key_p, wrapped_key_handle_p)
+}
+
+extern fn _unwrap_key(key_id: *const c_char, wrapped_key: *const c_char, key_p: *mut *const c_char, key_handle_p: *mut i32) -> i32 {
+    let key_id = unsafe { CStr::from_ptr(key_id).to_str().unwrap() };
+    let wrapped_key = unsafe { CStr::from_ptr(wrapped_key).to_str().unwrap() };
+
+    let prefix = format!("{}:", key_id);
+
+    if !wrapped_key.starts_with(&prefix) {
+        return ErrorCode::CommonInvalidStructure as i32;
+    }
+
+    _store_key(wrapped_key[prefix.len()..].to_string(), key_p, key_handle_p)
+}
+
+extern fn _free_key(key_handle: i32) -> i32 {
+    WRAPPED_KEYS.lock().unwrap().remove(&key_handle);
+    ErrorCode::Success as i32
+}
+
 extern {
     #[no_mangle]
     pub fn indy_register_wallet_storage(command_handle: CommandHandle,
@@ -7,11 +7,11 @@ extern crate indyrs as indy;
 extern crate indyrs as api;
 
 use crate::utils::inmem_wallet::InmemWallet;
-use crate::utils::{environment, wallet, test, did};
+use crate::utils::{environment, wallet, test, did, crypto};
 use crate::utils::constants::*;
 use crate::utils::Setup;
 
-use self::indy::ErrorCode;
+use self::indy::{ErrorCode, WalletHandle};
 use std::path::PathBuf;
 use std::fs;
 
@@ -246,6 +246,66 @@ mod high_cases {
         }
     }
 
+    mod wallet_reencryption {
+        use super::*;
+
+        #[test]
+        fn indy_wallet_reencryption_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+            did::create_my_did(wallet_handle, "{}").unwrap();
+
+            let new_key = wallet::generate_wallet_key(None).unwrap();
+            let reencryption_config = json!({
+                "key": "8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY",
+                "key_derivation_method": "RAW",
+                "new_key": new_key,
+                "new_key_derivation_method": "RAW",
+            }).to_string();
+
+            wallet::start_wallet_reencryption(wallet_handle, &reencryption_config).unwrap();
+
+            let progress = wallet::reencrypt_wallet_records(wallet_handle, 1).unwrap();
+            let progress: serde_json::Value = serde_json::from_str(&progress).unwrap();
+            assert_eq!(json!({"reencrypted": 1, "finished": false}), progress);
+
+            // The wallet remains available between batches
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            loop {
+                let progress = wallet::reencrypt_wallet_records(wallet_handle, 10).unwrap();
+                let progress: serde_json::Value = serde_json::from_str(&progress).unwrap();
+                if progress["finished"].as_bool().unwrap() {
+                    break;
+                }
+            }
+
+            let status = wallet::get_wallet_reencryption_status(wallet_handle).unwrap();
+            let status: serde_json::Value = serde_json::from_str(&status).unwrap();
+            assert_eq!(false, status["inProgress"].as_bool().unwrap());
+            assert_eq!(0, status["remaining"].as_u64().unwrap());
+            assert_eq!(0, status["undecryptable"].as_u64().unwrap());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let new_credentials = json!({"key": new_key, "key_derivation_method": "RAW"}).to_string();
+
+            let res = wallet::open_wallet(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            let wallet_handle = wallet::open_wallet(&config, &new_credentials).unwrap();
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            wallet::delete_wallet(&config, &new_credentials).unwrap();
+        }
+    }
+
     mod import_wallet {
         use super::*;
 
@@ -281,6 +341,83 @@ mod high_cases {
             wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
             cleanup_file(&path);
         }
+
+        #[test]
+        fn indy_import_wallet_works_for_resumable_import() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "chunked": true,
+            }).to_string();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            let import_config = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "resumable": true,
+            }).to_string();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &import_config).unwrap();
+
+            // Completely imported wallet can't be imported again
+            let res = wallet::import_wallet(&config, WALLET_CREDENTIALS, &import_config);
+            assert_code!(ErrorCode::WalletAlreadyExistsError, res);
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
+
+        #[test]
+        fn indy_import_wallet_works_for_sealed_export() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::register_test_key_wrapper().unwrap();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = wallet::prepare_sealed_export_wallet_config(&path);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+            did::set_did_metadata(wallet_handle, &did, METADATA).unwrap();
+
+            let did_with_meta = did::get_my_did_with_metadata(wallet_handle, &did).unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            let import_config = json!({"path": path.to_str().unwrap()}).to_string();
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &import_config).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let did_with_meta_after_import = did::get_my_did_with_metadata(wallet_handle, &did).unwrap();
+
+            assert_eq!(did_with_meta, did_with_meta_after_import);
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
     }
 
     mod generate_wallet_key {
@@ -318,6 +455,139 @@ mod high_cases {
             wallet::close_wallet(wallet_handle).unwrap();
             wallet::delete_wallet(&wallet_config, &credentials).unwrap();
         }
+
+        #[test]
+        fn indy_generate_wallet_key_works_for_mnemonic() {
+            let setup = Setup::empty();
+            let wallet_config = config(&setup.name);
+
+            let phrase = wallet::generate_mnemonic(None).unwrap();
+            assert_eq!(24, phrase.split_whitespace().count());
+
+            let config = json!({"mnemonic": {"phrase": phrase}}).to_string();
+            let key = wallet::generate_wallet_key(Some(config.as_str())).unwrap();
+
+            let credentials = json!({"key": key, "key_derivation_method": "RAW"}).to_string();
+            wallet::create_wallet(&wallet_config, &credentials).unwrap();
+
+            // the same key is re-derived from the mnemonic
+            let restored_key = wallet::generate_wallet_key(Some(config.as_str())).unwrap();
+            assert_eq!(key, restored_key);
+
+            let wallet_handle = wallet::open_wallet(&wallet_config, &credentials).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&wallet_config, &credentials).unwrap();
+        }
+    }
+
+    mod generate_mnemonic {
+        use super::*;
+
+        #[test]
+        fn indy_generate_mnemonic_works_for_word_count() {
+            Setup::empty();
+
+            let phrase = wallet::generate_mnemonic(Some(r#"{"word_count": 12}"#)).unwrap();
+            assert_eq!(12, phrase.split_whitespace().count());
+        }
+    }
+
+    mod import_wallet_records {
+        use super::*;
+
+        #[test]
+        fn indy_import_wallet_records_works_for_filtered_export() {
+            let setup = Setup::empty();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let export_config = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "filter": {"types": ["Indy::Did"]},
+            }).to_string();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+            did::create_my_did(wallet_handle, "{}").unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &export_config).unwrap();
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_target", setup.name)).unwrap();
+
+            let import_config = |conflict_policy: &str| json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "conflict_policy": conflict_policy,
+            }).to_string();
+
+            let result = wallet::import_wallet_records(wallet_handle, &import_config("fail")).unwrap();
+            assert_eq!(json!({"added": 2, "skipped": 0, "overwritten": 0}), serde_json::from_str::<serde_json::Value>(&result).unwrap());
+
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            let res = wallet::import_wallet_records(wallet_handle, &import_config("fail"));
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            let result = wallet::import_wallet_records(wallet_handle, &import_config("skip")).unwrap();
+            assert_eq!(json!({"added": 0, "skipped": 2, "overwritten": 0}), serde_json::from_str::<serde_json::Value>(&result).unwrap());
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+            cleanup_file(&path);
+        }
+    }
+
+    mod split_wallet_key {
+        use super::*;
+
+        #[test]
+        fn indy_split_wallet_key_works_for_open_wallet() {
+            let setup = Setup::wallet();
+            let config = config(&format!("{}_owner", setup.name));
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let recipients = _create_recipients(setup.wallet_handle, 3);
+            let shares = wallet::split_wallet_key(WALLET_KEY, &json!({"threshold": 2, "recipients": recipients}).to_string()).unwrap();
+
+            let key_shares = _decrypt_key_shares(setup.wallet_handle, &recipients[1..], &shares, 1);
+            assert_eq!(2, key_shares.len());
+
+            let credentials = json!({"key_shares": key_shares, "key_derivation_method": "RAW"}).to_string();
+            let wallet_handle = wallet::open_wallet(&config, &credentials).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_split_wallet_key_works_for_import_wallet() {
+            let setup = Setup::wallet();
+            let config = config(&format!("{}_imported", setup.name));
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let export_config = wallet::prepare_export_wallet_config(&path);
+
+            let (did, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(setup.wallet_handle, &export_config).unwrap();
+
+            let recipients = _create_recipients(setup.wallet_handle, 3);
+            let shares = wallet::split_wallet_key("export_key", &json!({"threshold": 3, "recipients": recipients}).to_string()).unwrap();
+
+            let key_shares = _decrypt_key_shares(setup.wallet_handle, &recipients, &shares, 0);
+            let import_config = json!({"path": path.to_str().unwrap(), "key_shares": key_shares}).to_string();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &import_config).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::key_for_local_did(wallet_handle, &did).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
     }
 }
 
@@ -633,6 +903,22 @@ mod medium_cases {
             let res = wallet::export_wallet(INVALID_WALLET_HANDLE, &config_json);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_export_wallet_returns_error_for_unknown_key_wrapper() {
+            let setup = Setup::wallet();
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = json!({
+                "path": path.to_str().unwrap(),
+                "sealed": {"key_wrapper": "unknown", "key_id": "wallet-backup"}
+            }).to_string();
+
+            let res = wallet::export_wallet(setup.wallet_handle, &config_json);
+            assert_code!(ErrorCode::WalletUnknownTypeError, res);
+
+            assert!(!path.exists());
+        }
     }
 
     mod import_wallet {
@@ -694,6 +980,15 @@ mod medium_cases {
             cleanup_file(&path);
         }
 
+        #[test]
+        fn indy_import_wallet_records_works_for_invalid_handle() {
+            let path = wallet::export_wallet_path("indy_import_wallet_records_works_for_invalid_handle");
+            let import_config = wallet::prepare_export_wallet_config(&path);
+
+            let res = wallet::import_wallet_records(INVALID_WALLET_HANDLE, &import_config);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
         #[test]
         fn indy_import_wallet_works_for_duplicate_name() {
             let setup = Setup::empty();
@@ -718,6 +1013,81 @@ mod medium_cases {
             cleanup_file(&path);
         }
     }
+
+    mod split_wallet_key {
+        use super::*;
+
+        #[test]
+        fn indy_split_wallet_key_works_for_not_enough_shares() {
+            let setup = Setup::wallet();
+            let config = config(&format!("{}_owner", setup.name));
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let recipients = _create_recipients(setup.wallet_handle, 3);
+            let shares = wallet::split_wallet_key(WALLET_KEY, &json!({"threshold": 3, "recipients": recipients}).to_string()).unwrap();
+
+            let key_shares = _decrypt_key_shares(setup.wallet_handle, &recipients[1..], &shares, 1);
+
+            let credentials = json!({"key_shares": key_shares, "key_derivation_method": "RAW"}).to_string();
+            let res = wallet::open_wallet(&config, &credentials);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_split_wallet_key_works_for_invalid_threshold() {
+            let setup = Setup::wallet();
+
+            let recipients = _create_recipients(setup.wallet_handle, 2);
+
+            let res = wallet::split_wallet_key(WALLET_KEY, &json!({"threshold": 1, "recipients": recipients}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = wallet::split_wallet_key(WALLET_KEY, &json!({"threshold": 3, "recipients": recipients}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_split_wallet_key_works_for_invalid_recipient() {
+            Setup::empty();
+
+            let res = wallet::split_wallet_key(WALLET_KEY, &json!({"threshold": 2, "recipients": [INVALID_BASE58_VERKEY, VERKEY]}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod generate_mnemonic {
+        use super::*;
+
+        #[test]
+        fn indy_generate_mnemonic_works_for_invalid_word_count() {
+            Setup::empty();
+
+            let res = wallet::generate_mnemonic(Some(r#"{"word_count": 13}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+}
+
+const WALLET_KEY: &str = "8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY";
+
+fn _create_recipients(wallet_handle: WalletHandle, count: usize) -> Vec<String> {
+    (0..count).map(|_| crypto::create_key(wallet_handle, None).unwrap()).collect()
+}
+
+fn _decrypt_key_shares(wallet_handle: WalletHandle, recipients: &[String], shares: &str, skip: usize) -> Vec<String> {
+    use rust_base58::FromBase58;
+
+    let shares: Vec<String> = serde_json::from_str(shares).unwrap();
+
+    recipients.iter()
+        .zip(shares.iter().skip(skip))
+        .map(|(recipient, share)| {
+            let share = crypto::anon_decrypt(wallet_handle, recipient, &share.from_base58().unwrap()).unwrap();
+            String::from_utf8(share).unwrap()
+        })
+        .collect()
 }
 
 fn _custom_path(name: &str) -> String {
@@ -128,6 +128,22 @@ vcx_error_t vcx_connection_create_with_invite(vcx_command_handle_t command_handl
                                            const char *invite_details,
                                            void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_connection_handle_t));
 
+// Find completed Connection with the sender of the given invite_details by the public DID stated in the invitation.
+// The existing connection can be reused instead of creating a new one with "vcx_connection_create_with_invite".
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// invite_details: invitation in any format accepted by "vcx_connection_create_with_invite"
+//
+// cb: Callback that provides handle of the existing connection (0 if there is no such connection) and error status of request
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_connection_find_existing(vcx_command_handle_t command_handle,
+                                         const char *invite_details,
+                                         void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_connection_handle_t));
+
 // Delete a Connection object and release its handle
 //
 // #Params
@@ -284,6 +300,25 @@ vcx_error_t vcx_connection_update_state_with_message(vcx_command_handle_t comman
                                                      const char *message,
                                                      void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
 
+/// Update the state of the connection based on an encrypted message downloaded from the agency
+/// (for example delivered by push notification or background fetch) without polling the agency.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: Connection handle that was provided during creation. Used to identify connection object
+///
+/// message: message as returned by vcx_messages_download (single element of "msgs" array)
+///
+/// cb: Callback that provides most current state of the connection and error status of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_connection_update_state_with_agency_message(vcx_command_handle_t command_handle,
+                                                            vcx_connection_handle_t connection_handle,
+                                                            const char *message,
+                                                            void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
+
 /// Send trust ping message to the specified connection to prove that two agents have a functional pairwise channel.
 ///
 /// Note that this function is useful in case `aries` communication method is used.
@@ -306,6 +341,129 @@ vcx_error_t vcx_connection_send_ping(vcx_u32_t command_handle,
                                      const char* comment,
                                      void (*cb)(vcx_command_handle_t, vcx_error_t));
 
+/// Set TLS constraints the remote endpoint of the connection must satisfy when messages are delivered to it.
+/// Take precedence over TLS constraints from the service of remote DIDDoc.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set TLS constraints for.
+///
+/// tls_constraints: (Optional) TLS constraints json. Pass null to remove previously set constraints.
+///     {
+///         "spkiSha256Pins": Optional<[string]> - base64 encoded SHA-256 digests of pinned certificate public keys (SubjectPublicKeyInfo),
+///         "caCertificate": Optional<string> - PEM encoded CA certificate endpoint certificate must be issued by
+///     }
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_connection_set_tls_constraints(vcx_command_handle_t command_handle,
+                                               vcx_connection_handle_t connection_handle,
+                                               const char* tls_constraints,
+                                               void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Select format of encrypted envelopes used for messages sent over the connection.
+/// Indy pack format is used by default. DIDComm v2 (JWE) format lets to communicate with agents supporting DIDComm v2 messaging.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set envelope format for.
+///
+/// format_json: envelope format json.
+///     {
+///         "format": string - one of `indy_pack`, `didcomm_v2`
+///         "enc": Optional<string> - content encryption: `A256CBC-HS512` (default) or `A256GCM`
+///         "authcrypt": Optional<bool> - use authenticated encryption (ECDH-1PU) instead of anonymous (ECDH-ES). Default is true.
+///         "recipientKeys": Optional<[string]> - base58 encoded X25519 key agreement keys of the remote side.
+///     }
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_connection_set_envelope_format(vcx_command_handle_t command_handle,
+                                               vcx_connection_handle_t connection_handle,
+                                               const char* format_json,
+                                               void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Ask the remote side to send responses back over the same transport connection (`~transport.return_route` decorator).
+/// Return route is always requested for WebSocket (`ws://`, `wss://`) endpoints.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set return route for.
+///
+/// return_route: whether responses should be sent back over the same connection.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_connection_set_return_route(vcx_command_handle_t command_handle,
+                                            vcx_connection_handle_t connection_handle,
+                                            vcx_bool_t return_route,
+                                            void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Set method of the DID sent to the remote side in Connection Request or Response.
+/// Must be set before the connection is established.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set DID method for.
+///
+/// method: one of `unqualified` (default), `did:key`, `did:peer:0`, `did:peer:2`.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_connection_set_did_method(vcx_command_handle_t command_handle,
+                                          vcx_connection_handle_t connection_handle,
+                                          const char* method,
+                                          void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Set policy of answering Credential Offers and Presentation Requests received over the connection without user involvement.
+/// Overrides `auto_accept_credential_offers` and `auto_present_proofs` library settings for the connection.
+/// Policy is applied when Credential or Disclosed Proof object is created with `*_create_with_msgid`.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set policy for.
+///
+/// policy_json: auto-accept policy json. Unset fields are taken from the library settings.
+///     {
+///         "credential_offers": Optional<string> - `never` or `always` send Credential Request for the received Credential Offer
+///         "presentation_requests": Optional<string> - `never`, `single_match` - send Presentation
+///                                  if every requested attribute and predicate is matched by exactly one credential,
+///                                  or `always` - choose credentials with `credential_selection_strategy` library setting
+///     }
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_connection_set_auto_accept(vcx_command_handle_t command_handle,
+                                           vcx_connection_handle_t connection_handle,
+                                           const char* policy_json,
+                                           void (*cb)(vcx_command_handle_t, vcx_error_t));
+
 /// Send discovery features message to the specified connection to discover which features it supports, and to what extent.
 ///
 /// Note that this function is useful in case `aries` communication method is used.
@@ -526,6 +684,21 @@ vcx_error_t vcx_credential_get_request_msg(vcx_command_handle_t command_handle,
                                            vcx_payment_handle_t payment_handle,
                                            void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
 
+// Gets the Credential Preview of the received offer, including display hints provided by the issuer (Aries protocol only)
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// handle: Credential handle that was provided during creation. Used to identify credential object
+//
+// cb: Callback that provides Credential Preview json and provides error status
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_credential_get_preview(vcx_command_handle_t command_handle,
+                                    vcx_credential_handle_t handle,
+                                    void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
+
 // Takes the credential object and returns a json string of all its attributes
 //
 // #Params
@@ -575,6 +748,28 @@ vcx_error_t vcx_credential_update_state_with_message(vcx_command_handle_t comman
                                                      const char *message,
                                                      void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
 
+/// Update the state of the credential based on an encrypted message downloaded from the agency
+/// (for example delivered by push notification or background fetch) without polling the agency.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// connection_handle: Connection handle the message was received over. Used to decrypt the message
+///
+/// message: message as returned by vcx_messages_download (single element of "msgs" array)
+///
+/// cb: Callback that provides most current state of the credential and error status of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_credential_update_state_with_agency_message(vcx_command_handle_t command_handle,
+                                                            vcx_credential_handle_t credential_handle,
+                                                            vcx_connection_handle_t connection_handle,
+                                                            const char *message,
+                                                            void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
+
 // Create a new CredentialDef object that can create credential definitions on the ledger
 //
 // #Params
@@ -1099,6 +1294,34 @@ vcx_error_t vcx_disclosed_proof_update_state_with_message(vcx_command_handle_t c
                                                           const char *message,
                                                           void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
 
+/// Update the state of the proof based on an encrypted message downloaded from the agency
+/// (for example delivered by push notification or background fetch) without polling the agency.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// proof_handle: Proof handle that was provided during creation. Used to identify the disclosed proof object
+///
+/// connection_handle: Connection handle the message was received over. Used to decrypt the message
+///
+/// message: message as returned by vcx_messages_download (single element of "msgs" array)
+///
+/// cb: Callback that provides most current state of the proof and error status of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_disclosed_proof_update_state_with_agency_message(vcx_command_handle_t command_handle,
+                                                                 vcx_disclosed_proof_handle_t proof_handle,
+                                                                 vcx_connection_handle_t connection_handle,
+                                                                 const char *message,
+                                                                 void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
+
+/// Set handler choosing a credential when several credentials match a requested attribute or predicate
+/// on automatic presentation of proofs (`auto_present_proofs` is `always`, `credential_selection_strategy` is `{"type": "callback"}`).
+/// Handler receives referent and JSON array of matching credentials and returns index of the chosen one or -1.
+/// Pass NULL to remove the handler.
+vcx_error_t vcx_set_credential_selection_handler(int (*handler)(const char *referent, const char *credentials));
+
 const char *vcx_error_c_message(vcx_error_t error_code);
 
 // Retrieve information about a stored credential in user's wallet, including credential id and the credential itself.
@@ -1199,6 +1422,49 @@ vcx_error_t vcx_issuer_create_credential(vcx_command_handle_t command_handle,
                                       vcx_payment_handle_t price,
                                       void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_issuer_credential_handle_t));
 
+// Create a Issuer Credential object whose attributes are derived from an existing Issuer Credential object (master record)
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+// source_id: Enterprise's personal identification for the user.
+// master_handle: handle of Issuer Credential object holding master values (not available in the object restored after issuance)
+// cred_def_handle: handle of credential definition of derived credential
+// issuer_did: did corresponding to entity issuing a credential
+// derivation: json mapping attributes of derived credential to master attributes
+//     {"<attr_name>": "<master_attr_name>", "<attr_name>": {"from": "<master_attr_name>", "transform": {"type": "uppercase"}}, "<attr_name>": {"value": "<value>"}}
+// credential_name: Name of the credential - ex. Drivers Licence
+// price: price of credential
+// cb: Callback that provides credential handle and error status of request
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_issuer_create_derived_credential(vcx_command_handle_t command_handle,
+                                                 const char *source_id,
+                                                 vcx_issuer_credential_handle_t master_handle,
+                                                 vcx_credentialdef_handle_t cred_def_handle,
+                                                 const char *issuer_did,
+                                                 const char *derivation,
+                                                 const char *credential_name,
+                                                 const char *price,
+                                                 void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_issuer_credential_handle_t));
+
+// Sets display hints (label, order, group, locale) for the attributes of credential preview.
+// Hints are sent to the holder within Credential Offer (Aries protocol only).
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+// credential_handle: Credential handle that was provided during creation.
+// display_hints: json map of attribute names to display hints
+//     {"attr_name": {"label": Optional<string>, "order": Optional<int>, "group": Optional<string>, "locale": Optional<string>}}
+// cb: Callback that provides error status of setting display hints
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_issuer_credential_set_display_hints(vcx_command_handle_t command_handle,
+                                                 vcx_issuer_credential_handle_t credential_handle,
+                                                 const char *display_hints,
+                                                 void (*cb)(vcx_command_handle_t, vcx_error_t));
+
 // Takes a json string representing an issuer credential object and recreates an object matching the json
 //
 // #Params
@@ -1313,6 +1579,28 @@ vcx_error_t vcx_issuer_credential_update_state_with_message(vcx_command_handle_t
                                                             const char *message,
                                                             void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
 
+/// Update the state of the credential based on an encrypted message downloaded from the agency
+/// (for example delivered by push notification or background fetch) without polling the agency.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// connection_handle: Connection handle the message was received over. Used to decrypt the message
+///
+/// message: message as returned by vcx_messages_download (single element of "msgs" array)
+///
+/// cb: Callback that provides most current state of the credential and error status of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_issuer_credential_update_state_with_agency_message(vcx_command_handle_t command_handle,
+                                                                   vcx_issuer_credential_handle_t credential_handle,
+                                                                   vcx_connection_handle_t connection_handle,
+                                                                   const char *message,
+                                                                   void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
+
 // Send Credential that was requested by user
 //
 // #Params
@@ -1331,6 +1619,31 @@ vcx_error_t vcx_issuer_send_credential(vcx_command_handle_t command_handle,
                                     vcx_connection_handle_t connection_handle,
                                     void (*cb)(vcx_command_handle_t, vcx_error_t));
 
+/// Send Revocation Notification to the holder of the issued credential.
+/// Holder marks the stored credential as revoked and moves it into VcxStateRevoked state without checking the ledger.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// connection_handle: Connection handle that identifies pairwise connection the credential was issued over
+///
+/// comment: (Optional) human-readable reason of revocation
+///
+/// cb: Callback that provides error status of sending the notification
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_issuer_send_revocation_notification(vcx_command_handle_t command_handle,
+                                                    vcx_issuer_credential_handle_t credential_handle,
+                                                    vcx_connection_handle_t connection_handle,
+                                                    const char *comment,
+                                                    void (*cb)(vcx_command_handle_t, vcx_error_t));
+
 // Send a credential offer to user showing what will be included in the actual credential
 //
 // #Params
@@ -1413,6 +1726,62 @@ vcx_error_t vcx_messages_download(vcx_command_handle_t command_handle,
                                const char *pw_dids,
                                void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
 
+// Retrieve messages from the specified connection matching the filter
+//
+// #params
+//
+// command_handle: command handle to map callback to user context.
+//
+// message_status: optional - query for messages with the specified status
+//
+// uids: optional, comma separated - query for messages with the specified uids
+//
+// pw_dids: optional, comma separated - DID's pointing to specific connection
+//
+// filter_json: optional - {"protocolFamilies": [string], "threadId": string, "since": int, "until": int, "msgTypes": [string]}
+//
+// cb: Callback that provides array of matching messages retrieved
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_messages_download_with_filter(vcx_command_handle_t command_handle,
+                                           const char *message_status,
+                                           const char *uids,
+                                           const char *pw_dids,
+                                           const char *filter_json,
+                                           void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
+
+// Retrieve one page of messages from the specified connection matching the filter
+//
+// #params
+//
+// command_handle: command handle to map callback to user context.
+//
+// message_status: optional - query for messages with the specified status
+//
+// uids: optional, comma separated - query for messages with the specified uids
+//
+// pw_dids: optional, comma separated - DID's pointing to specific connection
+//
+// filter_json: optional - {"protocolFamilies": [string], "threadId": string, "since": int, "until": int, "msgTypes": [string]}
+//
+// page_size: maximal number of messages in the page, 0 to use the agency default
+//
+// page_token: optional - token of the page returned by the previous call
+//
+// cb: Callback that provides array of matching messages retrieved and the token of the next page (null for the last page)
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_messages_download_page(vcx_command_handle_t command_handle,
+                                    const char *message_status,
+                                    const char *uids,
+                                    const char *pw_dids,
+                                    const char *filter_json,
+                                    vcx_u32_t page_size,
+                                    const char *page_token,
+                                    void (*cb)(vcx_command_handle_t, vcx_error_t, const char*, const char*));
+
 // Retrieve messages from the cloud agent
 //
 // #params
@@ -1479,6 +1848,84 @@ vcx_error_t vcx_proof_create(vcx_command_handle_t command_handle,
                           const char *name,
                           void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_proof_handle_t));
 
+// Store a proof request template in the wallet (replaces the template stored with the same id).
+// The template is instantiated by vcx_proof_create_from_template with a fresh nonce and relative times resolved to timestamps.
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// template_id: identifier of the template
+//
+// template: proof request template
+//     {
+//         "name": string,
+//         "requested_attributes": [<requested_attr>],
+//         "requested_predicates": [<requested_predicate>],
+//         "non_revoked": Optional<{"from": Optional<time>, "to": Optional<time>}>,
+//         "accept_non_revocable": Optional<[string]>,
+//     }
+//     time: either timestamp or string relative to the moment of instantiation: "now", "now-<seconds>", "now+<seconds>"
+//
+// # Example template -> "{"name":"Employment","requested_attributes":[{"name":"employer"}],"non_revoked":{"from":"now-86400","to":"now"}}"
+//
+// cb: Callback that provides error status of request.
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_proof_template_store(vcx_command_handle_t command_handle,
+                                     const char *template_id,
+                                     const char *template_json,
+                                     void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+// Get a proof request template stored in the wallet
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// template_id: identifier of the template
+//
+// cb: Callback that provides the template and error status of request.
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_proof_template_get(vcx_command_handle_t command_handle,
+                                   const char *template_id,
+                                   void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
+
+// Delete a proof request template from the wallet
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// template_id: identifier of the template
+//
+// cb: Callback that provides error status of request.
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_proof_template_delete(vcx_command_handle_t command_handle,
+                                      const char *template_id,
+                                      void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+// Create a new Proof object from a proof request template stored in the wallet.
+// The proof gets a fresh nonce and relative times of the template resolved to timestamps of the moment.
+//
+// #Params
+// command_handle: command handle to map callback to user context.
+//
+// source_id: Enterprise's personal identification for the user.
+//
+// template_id: identifier of the template (see vcx_proof_template_store)
+//
+// cb: Callback that provides proof handle and error status of request.
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_proof_create_from_template(vcx_command_handle_t command_handle,
+                                           const char *source_id,
+                                           const char *template_id,
+                                           void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_proof_handle_t));
+
 // Takes a json string representing a proof object and recreates an object matching the json
 //
 // #Params
@@ -1606,6 +2053,28 @@ vcx_error_t vcx_proof_update_state_with_message(vcx_command_handle_t command_han
                                                 const char *message,
                                                 void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
 
+/// Update the state of the proof based on an encrypted message downloaded from the agency
+/// (for example delivered by push notification or background fetch) without polling the agency.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// proof_handle: Proof handle that was provided during creation. Used to identify proof object
+///
+/// connection_handle: Connection handle the message was received over. Used to decrypt the message
+///
+/// message: message as returned by vcx_messages_download (single element of "msgs" array)
+///
+/// cb: Callback that provides most current state of the proof and error status of request
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_proof_update_state_with_agency_message(vcx_command_handle_t command_handle,
+                                                       vcx_proof_handle_t proof_handle,
+                                                       vcx_connection_handle_t connection_handle,
+                                                       const char *message,
+                                                       void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t));
+
 // Provision an agent in the agency, populate configuration and wallet for this agent.
 // NOTE: for asynchronous call use vcx_agent_provision_async
 //
@@ -1616,6 +2085,18 @@ vcx_error_t vcx_proof_update_state_with_message(vcx_command_handle_t command_han
 // Configuration (wallet also populated), on error returns NULL
 char *vcx_provision_agent(const char *json);
 
+// Provision an agent in the agency admitting only agents sponsored by the issuer of the token,
+// populate configuration and wallet for this agent.
+//
+// #Params
+// json: configuration (see vcx_provision_agent)
+// token: provisioning token signed by the sponsor (valid for 15 minutes since it is issued)
+//     {"sponseeId": string, "sponsorId": string, "nonce": string, "timestamp": string, "sig": string, "sponsorVk": string}
+//
+// #Returns
+// Configuration (wallet also populated), on error returns NULL
+char *vcx_provision_agent_with_token(const char *json, const char *token);
+
 // Create a new Schema object that can create or look up schemas on the ledger
 //
 // #Params
@@ -1799,6 +2280,8 @@ vcx_error_t vcx_schema_get_state(vcx_command_handle_t command_handle,
 // Reset libvcx to a pre-configured state, releasing/deleting any handles and freeing memory
 //
 // libvcx will be inoperable and must be initialized again with vcx_init_with_config
+// Only the context the calling thread is bound to is reset: other contexts keep their objects,
+// event subscriptions and the pool they share with it.
 //
 // #Params
 // delete: specify whether wallet/pool should be deleted
@@ -1807,8 +2290,62 @@ vcx_error_t vcx_schema_get_state(vcx_command_handle_t command_handle,
 // Success
 vcx_error_t vcx_shutdown(vcx_bool_t delete);
 
+// Create a new context isolating settings, wallet and pool of a tenant.
+// Bind the context with vcx_context_bind and initialize it with vcx_init_with_config (or vcx_init_minimal)
+// as if it was a separate library instance.
+// API functions don't take a context handle: every call is executed in the context bound to the calling thread,
+// so existing function signatures stay unchanged. Applications serving many tenants on a thread pool
+// must bind the context of the tenant before each call.
+//
+// #Params
+// context_handle_p: reference that will contain the handle of created context
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_context_create(vcx_u32_t *context_handle_p);
+
+// Bind the calling thread to the context. Subsequent API calls made by the thread
+// (including async work and callbacks they start) are executed in this context.
+// Object handles (connections, credentials, proofs, ...) must be used in the context they were created in.
+// The default context (0) is used by threads which have never been bound.
+//
+// #Params
+// context_handle: handle of the context or 0 for the default context
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_context_bind(vcx_u32_t context_handle);
+
+// Get the context the calling thread is bound to
+//
+// #Returns
+// Handle of the context
+vcx_u32_t vcx_context_current();
+
+// Stop services started in the context, unload its objects, close the wallet and the pool opened in the context
+// and release the context. Persisted objects are kept in the wallet.
+// The pool is kept open while other contexts use it (see vcx_pool_set_handle).
+// Threads bound to the released context must be bound to another one.
+//
+// #Params
+// context_handle: handle of the context
+//
+// #Returns
+// Error code as a u32
+vcx_error_t vcx_context_release(vcx_u32_t context_handle);
+
 const char *vcx_version();
 
+/// Get optional subsystems of libvcx and underlying libindy: whether they are compiled in and enabled by current configuration.
+///
+/// NOTE: Returning pointer is valid until the next call of this function in the same execution thread.
+///
+/// #Returns
+/// Map in the JSON format. Where keys are names of subsystems (revocation, payments, postgres_storage, didcomm_v2,
+/// aries, experimental_protocols) and values are {"compiled": bool, "enabled": bool}.
+/// "libindy" key contains features reported by `indy_get_features`.
+const char *vcx_get_features();
+
 // Adds a record to the wallet
 // Assumes there is an open wallet.
 // #Params
@@ -2178,6 +2715,26 @@ vcx_error_t vcx_wallet_update_record_value(vcx_command_handle_t command_handle,
                                         const char *value,
                                         void (*cb)(vcx_command_handle_t, vcx_error_t));
 
+// Upgrades VCX objects kept serialized in the values of wallet records to the current serialization format.
+// Records which values cannot be upgraded are left unchanged.
+// #Params
+//
+// command_handle: command handle to map callback to user context.
+//
+// type_: type of records containing serialized objects.
+//
+// object_type: type of serialized objects: `connection`, `credential`, `issuer_credential`, `proof` or `disclosed_proof`.
+//
+// cb: Callback that provides the number of rewritten records
+//
+// #Returns
+// Error code as a u32
+//
+vcx_error_t vcx_wallet_migrate_serialized_objects(vcx_command_handle_t command_handle,
+                                                  const char *type_,
+                                                  const char *object_type,
+                                                  void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_u32_t));
+
 // Validates a Payment address
 //
 // #Params
@@ -2252,9 +2809,16 @@ vcx_error_t vcx_get_logger(const void* vcx_get_logger,
 /// * `error_json_p` - Reference that will contain error details (if any error has occurred before)
 ///  in the format:
 /// {
+///     "code": int - error code returned by the function
+///     "error": str - error kind description
 ///     "backtrace": Optional<str> - error backtrace.
 ///         Collecting of backtrace can be enabled by setting environment variable `RUST_BACKTRACE=1`
 ///     "message": str - human-readable error description
+///     "cause": str - root cause of the error
+///     "extra": {
+///         "context": [str] - messages of the error context chain from the outermost one to the root cause
+///         "vendor_code": Optional<int> - original error code of libindy if the error came from it
+///     }
 /// }
 ///
 vcx_error_t vcx_get_current_error(const char ** error_json_p);
@@ -2272,6 +2836,11 @@ vcx_error_t vcx_get_current_error(const char ** error_json_p);
 vcx_error_t vcx_get_ledger_author_agreement(vcx_u32_t command_handle,
                                             void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
 
+/// Update states of all connections, credentials and proofs downloading pending agency messages once.
+/// Returns JSON array of objects which changed state.
+vcx_error_t vcx_update_all_states(vcx_command_handle_t command_handle,
+                                  void (*cb)(vcx_command_handle_t, vcx_error_t, const char *changes));
+
 /// Set some accepted agreement as active.
 ///
 /// As result of succesfull call of this funciton appropriate metadata will be appended to each write request by `indy_append_txn_author_agreement_meta_to_request` libindy call.
@@ -2303,6 +2872,143 @@ vcx_error_t vcx_endorse_transaction(vcx_u32_t command_handle,
                                     const char* transaction
                                     void (*cb)(vcx_command_handle_t, vcx_error_t));
 
+/// Get information about an object (connection, credential, proof, ...) by its handle
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+/// handle: handle of the object
+///
+/// cb: Callback that provides the information about the object
+///     {"handle":1,"type":"proof","source_id":"1","state":1,"thread_id":"5a0a3c3b-0d30-4b1f-a3b6-3bd1c6b5e5f2"}
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_object_info(vcx_command_handle_t command_handle,
+                            vcx_u32_t handle,
+                            void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
+
+/// List information about live objects of the current context including the ones persisted in the wallet but not loaded into memory
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+/// object_type: (Optional) type of objects to list, one of:
+///     "connection", "credential", "issuer_credential", "proof", "disclosed_proof"
+///     objects of all types are listed if not set
+///
+/// cb: Callback that provides the list of object information (see vcx_object_info)
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_object_list(vcx_command_handle_t command_handle,
+                            const char *object_type,
+                            void (*cb)(vcx_command_handle_t, vcx_error_t, const char*));
+
+/// Start revocation publisher service which publishes revocation registry deltas in batches
+/// and uploads tails files of newly created revocation registries.
+/// Pending deltas are kept in the wallet and published after restart of the service if their publication failed.
+///
+/// #Params
+/// config: publisher configuration
+///     {
+///         "publish_interval": Optional<int>, // seconds between publications of pending deltas (60 by default)
+///         "max_batch_size": Optional<int>, // publish as soon as this number of revocations is pending for a registry
+///         "tails_upload_url": Optional<string>, // tails files are uploaded with PUT to <tails_upload_url>/<rev_reg_id>
+///     }
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_revocation_publisher_start(const char *config);
+
+/// Publish all pending deltas and stop revocation publisher service.
+/// Deltas which couldn't be published stay in the wallet until the service is started again.
+vcx_error_t vcx_revocation_publisher_stop(vcx_command_handle_t command_handle,
+                                          void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Publish pending deltas of all revocation registries right now.
+vcx_error_t vcx_revocation_publisher_publish(vcx_command_handle_t command_handle,
+                                             void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Get status of revocation registries handled by revocation publisher service.
+vcx_error_t vcx_revocation_publisher_get_status(vcx_command_handle_t command_handle,
+                                                void (*cb)(vcx_command_handle_t, vcx_error_t, const char *status));
+
+/// Start outbound message queue which stores messages in the wallet before the delivery,
+/// retries failed deliveries with exponential backoff and moves undeliverable messages to dead-letter state.
+///
+/// #Params
+/// config: queue configuration
+///     {
+///         "retry_interval": Optional<int>, // seconds between checks for messages due for retry (10 by default)
+///         "initial_delay": Optional<int>, // seconds before the first retry, doubled with every failed attempt (5 by default)
+///         "max_delay": Optional<int>, // the longest delay between retries in seconds (3600 by default)
+///         "max_attempts": Optional<int>, // failed deliveries after which the message is moved to dead-letter state (10 by default)
+///     }
+///
+/// #Returns
+/// Error code as a u32
+vcx_error_t vcx_outbound_queue_start(const char *config);
+
+/// Stop outbound message queue. Stored messages are kept and retried once the queue is started again.
+vcx_error_t vcx_outbound_queue_stop();
+
+/// Get messages stored in outbound message queue, optionally filtered by state ("pending" or "dead_letter").
+vcx_error_t vcx_outbound_queue_get_messages(vcx_command_handle_t command_handle,
+                                            const char *state,
+                                            void (*cb)(vcx_command_handle_t, vcx_error_t, const char *messages));
+
+/// Move the message back to pending state and try to deliver it right away.
+/// Fails if the message is being delivered right now.
+vcx_error_t vcx_outbound_queue_retry(vcx_command_handle_t command_handle,
+                                     const char *message_id,
+                                     void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Delete the message from outbound message queue.
+vcx_error_t vcx_outbound_queue_delete(vcx_command_handle_t command_handle,
+                                      const char *message_id,
+                                      void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Request mediation from the mediator over established connection (Mediator Coordination protocol).
+/// Once mediation is granted, recipient keys of new connections are registered with the mediator.
+vcx_error_t vcx_mediation_request(vcx_command_handle_t command_handle,
+                                  vcx_connection_handle_t connection_handle,
+                                  void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Query the mediator connection for the answer to mediation request or keylist updates.
+vcx_error_t vcx_mediation_update_state(vcx_command_handle_t command_handle,
+                                       void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t state));
+
+/// Returns the current state of mediation (0 if mediation is not requested).
+vcx_error_t vcx_mediation_get_state(vcx_command_handle_t command_handle,
+                                    void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_state_t state));
+
+/// Query the mediator for the number of messages waiting to be picked up.
+vcx_error_t vcx_mediation_get_message_count(vcx_command_handle_t command_handle,
+                                            void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_u32_t count));
+
+/// Serialize mediation to be restored with `vcx_mediation_deserialize` after restart.
+vcx_error_t vcx_mediation_serialize(vcx_command_handle_t command_handle,
+                                    void (*cb)(vcx_command_handle_t, vcx_error_t, const char *data));
+
+/// Restore mediation serialized with `vcx_mediation_serialize`.
+vcx_error_t vcx_mediation_deserialize(vcx_command_handle_t command_handle,
+                                      const char *data,
+                                      void (*cb)(vcx_command_handle_t, vcx_error_t));
+
+/// Forget the mediator. New connections are routed through the agency again.
+vcx_error_t vcx_mediation_reset();
+
+/// Register handler of protocol events (state changes of connections, credentials and proofs).
+/// Handler receives handle of the registered handler and event JSON.
+vcx_error_t vcx_register_event_handler(vcx_command_handle_t command_handle,
+                                       const char *event_types,
+                                       void (*handler)(vcx_u32_t handler_handle, const char *event),
+                                       void (*cb)(vcx_command_handle_t, vcx_error_t, vcx_u32_t handler_handle));
+
+/// Unregister handler of protocol events.
+vcx_error_t vcx_unregister_event_handler(vcx_u32_t handler_handle);
+
 #ifdef __cplusplus
 } // extern "C"
 #endif
@@ -240,6 +240,21 @@ pub extern fn vcx_connection_create(command_handle: CommandHandle,
 ///         "serviceEndpoint": "https://example.com/endpoint",
 ///         "routingKeys": ["8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"]
 ///      }
+///     aries out-of-band: https://github.com/hyperledger/aries-rfcs/tree/master/features/0434-outofband#messages
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/out-of-band/1.0/invitation",
+///         "@id": "69212a3a-d068-4f9d-a2dd-4741bca89af3",
+///         "label": "Alice",
+///         "handshake_protocols": ["did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/connections/1.0"],
+///         "services": [{
+///             "id": "#inline",
+///             "type": "did-communication",
+///             "recipientKeys": ["8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"],
+///             "routingKeys": [],
+///             "serviceEndpoint": "https://example.com/endpoint"
+///         }]
+///      }
+///     Note that only inline services are supported for out-of-band invitations.
 ///
 /// # Returns
 /// Error code as a u32
@@ -274,6 +289,52 @@ pub extern fn vcx_connection_create_with_invite(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Find completed Connection with the sender of the given invite_details.
+/// Invitation must contain the public DID of the sender, so it's recognized as the one we already have a connection with.
+/// The existing connection can be reused instead of creating a new one with `vcx_connection_create_with_invite`.
+/// For Out-of-Band invitation the reuse should be reported to the sender with `vcx_connection_send_handshake_reuse`.
+///
+/// # Params
+/// command_handle: command handle to map callback to user context.
+///
+/// invite_details: A string representing a json object which is provided by an entity that wishes to make a connection.
+///     Accepts the same formats as `vcx_connection_create_with_invite`.
+///
+/// cb: Callback that provides handle of the existing connection (0 if there is no such connection) and error status of request
+///
+/// # Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_find_existing(command_handle: CommandHandle,
+                                           invite_details: *const c_char,
+                                           cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, connection_handle: u32)>) -> u32 {
+    info!("vcx_connection_find_existing >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(invite_details, VcxErrorKind::InvalidOption);
+    trace!("vcx_connection_find_existing(command_handle: {}, invite_details: {})", command_handle, invite_details);
+
+    spawn(move || {
+        match find_existing_connection(&invite_details) {
+            Ok(handle) => {
+                let handle = handle.unwrap_or(0);
+                trace!("vcx_connection_find_existing_cb(command_handle: {}, rc: {}, handle: {})",
+                       command_handle, error::SUCCESS.message, handle);
+                cb(command_handle, error::SUCCESS.code_num, handle);
+            }
+            Err(x) => {
+                warn!("vcx_connection_find_existing_cb(command_handle: {}, rc: {}, handle: {})",
+                      command_handle, x, 0);
+                cb(command_handle, x.into(), 0);
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Establishes connection between institution and its user
 ///
 /// # Params
@@ -638,6 +699,61 @@ pub extern fn vcx_connection_update_state_with_message(command_handle: CommandHa
     error::SUCCESS.code_num
 }
 
+/// Update the state of the connection based on the message already downloaded from the agency
+/// (for example, delivered within push notification payload).
+/// Agency is not queried for new messages, so state can be advanced within background time budget of mobile platforms.
+/// Note that replies required by the protocol are still sent.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: was provided during creation. Used to identify connection object
+///
+/// message: agency message json as returned by `vcx_messages_download`
+///
+/// cb: Callback that provides most current state of the connection and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_update_state_with_agency_message(command_handle: CommandHandle,
+                                                              connection_handle: u32,
+                                                              message: *const c_char,
+                                                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_connection_update_state_with_agency_message >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message, VcxErrorKind::InvalidOption);
+
+    if !is_valid_handle(connection_handle) {
+        error!("vcx_connection_update_state_with_agency_message - invalid handle");
+        return VcxError::from(VcxErrorKind::InvalidConnectionHandle).into();
+    }
+
+    let source_id = get_source_id(connection_handle).unwrap_or_default();
+    trace!("vcx_connection_update_state_with_agency_message(command_handle: {}, connection_handle: {}), source_id: {:?}",
+           command_handle, connection_handle, source_id);
+
+    spawn(move || {
+        match update_state_with_agency_message(connection_handle, &message) {
+            Ok(state) => {
+                trace!("vcx_connection_update_state_with_agency_message_cb(command_handle: {}, rc: {}, connection_handle: {}, state: {}), source_id: {:?}",
+                       command_handle, error::SUCCESS.message, connection_handle, state, source_id);
+                cb(command_handle, error::SUCCESS.code_num, state)
+            }
+            Err(e) => {
+                warn!("vcx_connection_update_state_with_agency_message_cb(command_handle: {}, rc: {}, connection_handle: {}, state: {}), source_id: {:?}",
+                      command_handle, e, connection_handle, get_state(connection_handle), source_id);
+                cb(command_handle, e.into(), get_state(connection_handle))
+            }
+        }
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Returns the current internal state of the connection. Does NOT query agency for state updates.
 ///     Possible states:
 ///         1 - Initialized
@@ -761,11 +877,13 @@ pub extern fn vcx_connection_invite_details(command_handle: CommandHandle,
 ///
 /// msg: actual message to send
 ///
-/// send_msg_options: (applicable for `proprietary` communication method only)
+/// send_msg_options:
 ///     {
-///         msg_type: String, // type of message to send. can be any string.
-///         msg_title: String, // message title (user notification)
-///         ref_msg_id: Option<String>, // If responding to a message, id of the message
+///         msg_type: String, // (`proprietary` only) type of message to send. can be any string.
+///         msg_title: String, // (`proprietary` only) message title (user notification)
+///         ref_msg_id: Option<String>, // (`proprietary` only) If responding to a message, id of the message
+///         please_ack: Option<bool>, // (`aries` only) request delivery receipt for plain text message.
+///                                   // Receipt is Ack message with `~thread.thid` equal to the id returned in callback.
 ///     }
 ///
 /// # Example:
@@ -793,7 +911,7 @@ pub extern fn vcx_connection_invite_details(command_handle: CommandHandle,
 ///         "ref_msg_id" "as2d343sag"
 ///     }
 ///
-/// cb: Callback that provides id of retrieved response message
+/// cb: Callback that provides id of retrieved response message (for `aries` - id of the sent Basic Message)
 ///
 /// #Returns
 /// Error code as a u32
@@ -836,6 +954,7 @@ pub extern fn vcx_connection_send_message(command_handle: CommandHandle,
 }
 
 /// Send trust ping message to the specified connection to prove that two agents have a functional pairwise channel.
+/// Receiving of the response is tracked on connection state update and can be checked with `vcx_connection_info` (`last_ping` field).
 ///
 /// Note that this function is useful in case `aries` communication method is used.
 /// In other cases it returns ActionNotSupported error.
@@ -888,6 +1007,351 @@ pub extern fn vcx_connection_send_ping(command_handle: u32,
     error::SUCCESS.code_num
 }
 
+/// Send delivery receipt (Ack) for the Basic Message received over the specified connection.
+/// The remote side must request the receipt by sending the message with `~please_ack` decorator.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to use to send ack message.
+///                    Was provided during creation. Used to identify connection object.
+///                    Note that connection must be in Accepted state.
+///
+/// message_id: id of the received message (as returned by `vcx_messages_download`).
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_send_basic_message_ack(command_handle: u32,
+                                                    connection_handle: u32,
+                                                    message_id: *const c_char,
+                                                    cb: Option<extern fn(xcommand_handle: u32, err: u32)>) -> u32 {
+    info!("vcx_connection_send_basic_message_ack >>>");
+
+    check_useful_c_str!(message_id, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_send_basic_message_ack(command_handle: {}, connection_handle: {}, message_id: {:?})",
+           command_handle, connection_handle, message_id);
+
+    spawn(move || {
+        match send_basic_message_ack(connection_handle, &message_id) {
+            Ok(()) => {
+                trace!("vcx_connection_send_basic_message_ack(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_send_basic_message_ack(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Set TLS constraints the remote endpoint of the connection must satisfy when messages are delivered to it.
+/// Take precedence over TLS constraints from the service of remote DIDDoc.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set TLS constraints for.
+///
+/// tls_constraints: (Optional) TLS constraints json. Pass null to remove previously set constraints.
+///     {
+///         "spkiSha256Pins": Optional<[string]> - base64 encoded SHA-256 digests of pinned certificate public keys (SubjectPublicKeyInfo),
+///         "caCertificate": Optional<string> - PEM encoded CA certificate endpoint certificate must be issued by
+///     }
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_set_tls_constraints(command_handle: CommandHandle,
+                                                 connection_handle: u32,
+                                                 tls_constraints: *const c_char,
+                                                 cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_connection_set_tls_constraints >>>");
+
+    check_useful_opt_c_str!(tls_constraints, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_set_tls_constraints(command_handle: {}, connection_handle: {}, tls_constraints: {:?})",
+           command_handle, connection_handle, tls_constraints);
+
+    spawn(move || {
+        match set_tls_constraints(connection_handle, tls_constraints) {
+            Ok(()) => {
+                trace!("vcx_connection_set_tls_constraints(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_set_tls_constraints(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Set policy of answering Credential Offers and Presentation Requests received over the connection without user involvement.
+/// Overrides `auto_accept_credential_offers` and `auto_present_proofs` library settings for the connection.
+/// Policy is applied when Credential or Disclosed Proof object is created with `*_create_with_msgid`.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set policy for.
+///
+/// policy_json: auto-accept policy json. Unset fields are taken from the library settings.
+///     {
+///         "credential_offers": Optional<string> - `never` or `always` send Credential Request for the received Credential Offer
+///         "presentation_requests": Optional<string> - `never`, `single_match` - send Presentation
+///                                  if every requested attribute and predicate is matched by exactly one credential,
+///                                  or `always` - choose credentials with `credential_selection_strategy` library setting
+///     }
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_set_auto_accept(command_handle: CommandHandle,
+                                             connection_handle: u32,
+                                             policy_json: *const c_char,
+                                             cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_connection_set_auto_accept >>>");
+
+    check_useful_c_str!(policy_json, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_set_auto_accept(command_handle: {}, connection_handle: {}, policy_json: {})",
+           command_handle, connection_handle, policy_json);
+
+    spawn(move || {
+        match set_auto_accept(connection_handle, &policy_json) {
+            Ok(()) => {
+                trace!("vcx_connection_set_auto_accept(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_set_auto_accept(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Select format of encrypted envelopes used for messages sent over the connection.
+/// Indy pack format is used by default. DIDComm v2 (JWE) format lets to communicate with agents supporting DIDComm v2 messaging.
+/// Received messages are accepted in both formats regardless of the selected one.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set envelope format for.
+///
+/// format_json: envelope format json.
+///     {
+///         "format": string - one of `indy_pack`, `didcomm_v2`
+///         // following fields are used for `didcomm_v2` only
+///         "enc": Optional<string> - content encryption: `A256CBC-HS512` (default) or `A256GCM`
+///         "authcrypt": Optional<bool> - use authenticated encryption (ECDH-1PU) instead of anonymous (ECDH-ES). Default is true.
+///                                       ECDH-1PU requires `A256CBC-HS512` content encryption.
+///         "recipientKeys": Optional<[string]> - base58 encoded X25519 key agreement keys of the remote side.
+///                                               Derived from recipient keys of remote DIDDoc if not set.
+///     }
+///     DIDComm v2 key agreement key is X25519 form of the connection verkey, the remote side derives it from our DIDDoc.
+///     It is returned as `keyAgreementKey` by `vcx_connection_info`.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_set_envelope_format(command_handle: CommandHandle,
+                                                 connection_handle: u32,
+                                                 format_json: *const c_char,
+                                                 cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_connection_set_envelope_format >>>");
+
+    check_useful_c_str!(format_json, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_set_envelope_format(command_handle: {}, connection_handle: {}, format_json: {})",
+           command_handle, connection_handle, format_json);
+
+    spawn(move || {
+        match set_envelope_format(connection_handle, &format_json) {
+            Ok(()) => {
+                trace!("vcx_connection_set_envelope_format(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_set_envelope_format(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Ask the remote side to send responses back over the same transport connection (`~transport.return_route` decorator).
+/// Received responses are returned along with messages downloaded from the agency, so the state of protocols
+/// over the connection is updated without the remote side pushing messages to the agency.
+/// Return route is always requested for WebSocket (`ws://`, `wss://`) endpoints which keep the socket open
+/// for messages pushed by the remote side.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set return route for.
+///
+/// return_route: whether responses should be sent back over the same connection.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_set_return_route(command_handle: CommandHandle,
+                                              connection_handle: u32,
+                                              return_route: bool,
+                                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_connection_set_return_route >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_set_return_route(command_handle: {}, connection_handle: {}, return_route: {})",
+           command_handle, connection_handle, return_route);
+
+    spawn(move || {
+        match set_return_route(connection_handle, return_route) {
+            Ok(()) => {
+                trace!("vcx_connection_set_return_route(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_set_return_route(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Set method of the DID sent to the remote side in Connection Request or Response.
+/// Some agents refuse unqualified Indy DIDs, use `did:peer` or `did:key` DIDs to connect them.
+/// The method must be set before `vcx_connection_connect` (inviter side: before the request is received).
+/// Pairwise DID registered with the agency stays unqualified.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to set DID method for.
+///
+/// method: one of
+///     `unqualified` - Indy DID (default)
+///     `did:key` - `did:key` DID of the pairwise key
+///     `did:peer:0` - `did:peer` DID of the pairwise key (numalgo 0)
+///     `did:peer:2` - `did:peer` DID encoding the pairwise key and service endpoint (numalgo 2)
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_set_did_method(command_handle: CommandHandle,
+                                            connection_handle: u32,
+                                            method: *const c_char,
+                                            cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_connection_set_did_method >>>");
+
+    check_useful_c_str!(method, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_set_did_method(command_handle: {}, connection_handle: {}, method: {})",
+           command_handle, connection_handle, method);
+
+    spawn(move || {
+        match set_did_method(connection_handle, &method) {
+            Ok(()) => {
+                trace!("vcx_connection_set_did_method(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_set_did_method(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Generate a signature for the specified data using connection pairwise keys
 ///
 /// #params
@@ -1078,7 +1542,7 @@ pub extern fn vcx_connection_release(connection_handle: u32) -> u32 {
 ///                    Was provided during creation. Used to identify connection object.
 ///                    Note that connection must be in Accepted state.
 ///
-/// query: (Optional) query string to match against supported message types.
+/// query: (Optional) query string to match against supported message types. `*` matches any sequence of characters (e.g. `did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/issue-credential/*`).
 ///
 /// comment: (Optional) human-friendly description of the query.
 ///
@@ -1127,6 +1591,197 @@ pub extern fn vcx_connection_send_discovery_features(command_handle: u32,
     error::SUCCESS.code_num
 }
 
+/// Get the invitation details of the connection wrapped into Out-of-Band invitation message (Aries RFC 0434).
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: was provided during creation. Used to identify connection object.
+///                    Note that connection must be created by inviter and be in OfferSent state.
+///
+/// goal_code: (Optional) self-attested code the receiver may want to display to the user or use in automatically deciding what to do with the invitation.
+///
+/// goal: (Optional) self-attested string that the receiver may want to display to the user about the context-specific goal of the invitation.
+///
+/// cb: Callback that provides the json string of Out-of-Band invitation
+///
+/// # Example
+/// goal_code -> `issue-vc`
+///
+/// goal -> `To issue a Faber College Graduate credential`
+///
+/// details ->
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/out-of-band/1.0/invitation",
+///         "@id": "69212a3a-d068-4f9d-a2dd-4741bca89af3",
+///         "label": "Faber College",
+///         "goal_code": "issue-vc",
+///         "goal": "To issue a Faber College Graduate credential",
+///         "handshake_protocols": ["did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/connections/1.0"],
+///         "services": [{
+///             "id": "#inline",
+///             "type": "did-communication",
+///             "recipientKeys": ["8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"],
+///             "routingKeys": [],
+///             "serviceEndpoint": "https://example.com/endpoint"
+///         }]
+///      }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_get_outofband_invite_details(command_handle: CommandHandle,
+                                                          connection_handle: u32,
+                                                          goal_code: *const c_char,
+                                                          goal: *const c_char,
+                                                          cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, details: *const c_char)>) -> u32 {
+    info!("vcx_connection_get_outofband_invite_details >>>");
+
+    check_useful_opt_c_str!(goal_code, VcxErrorKind::InvalidOption);
+    check_useful_opt_c_str!(goal, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_get_outofband_invite_details(command_handle: {}, connection_handle: {}, goal_code: {:?}, goal: {:?})",
+           command_handle, connection_handle, goal_code, goal);
+
+    spawn(move || {
+        match get_outofband_invite_details(connection_handle, goal_code, goal) {
+            Ok(details) => {
+                trace!("vcx_connection_get_outofband_invite_details(command_handle: {}, rc: {}, details: {})",
+                       command_handle, error::SUCCESS.message, details);
+                let details = CStringUtils::string_to_cstring(details);
+                cb(command_handle, error::SUCCESS.code_num, details.as_ptr());
+            }
+            Err(e) => {
+                warn!("vcx_connection_get_outofband_invite_details(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Answer Out-of-Band invitation received from the remote side with the existing connection instead of creating a new one.
+/// Handshake Reuse message will be sent over the specified connection.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns ActionNotSupported error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: connection to use to send message.
+///                    Was provided during creation. Used to identify connection object.
+///                    Note that connection must be in Accepted state.
+///
+/// oob_invitation: Out-of-Band invitation json received from the remote side.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_send_handshake_reuse(command_handle: CommandHandle,
+                                                  connection_handle: u32,
+                                                  oob_invitation: *const c_char,
+                                                  cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_connection_send_handshake_reuse >>>");
+
+    check_useful_c_str!(oob_invitation, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_send_handshake_reuse(command_handle: {}, connection_handle: {}, oob_invitation: {})",
+           command_handle, connection_handle, oob_invitation);
+
+    spawn(move || {
+        match send_handshake_reuse(connection_handle, &oob_invitation) {
+            Ok(()) => {
+                trace!("vcx_connection_send_handshake_reuse(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_connection_send_handshake_reuse(command_handle: {}, rc: {})",
+                      command_handle, e);
+
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Get Problem Report received from the remote side or sent on failure of the connection establishment.
+/// Problem Report describes the reason the connection was abandoned: `problem-code` contains the error code,
+/// `explain` contains a human-readable explanation.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: was provided during creation. Used to identify connection object
+///
+/// cb: Callback that provides Problem Report json or NotReady error if there is no Problem Report
+///
+/// # Example
+/// problem_report ->
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/connections/1.0/problem_report",
+///         "@id": "7c4c4eb4-1f5e-4a1e-a7b3-3b8c9f4a2d11",
+///         "problem-code": "request_not_accepted",
+///         "explain": "Connection Request is rejected",
+///         "~thread": {"thid": "a1f3e5a9-ff8e-4d25-9d5b-7a8e9f8b3c0d"}
+///      }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_connection_get_problem_report(command_handle: CommandHandle,
+                                                connection_handle: u32,
+                                                cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, problem_report: *const c_char)>) -> u32 {
+    info!("vcx_connection_get_problem_report >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_connection_get_problem_report(command_handle: {}, connection_handle: {})", command_handle, connection_handle);
+
+    spawn(move || {
+        match get_problem_report(connection_handle) {
+            Ok(problem_report) => {
+                trace!("vcx_connection_get_problem_report_cb(command_handle: {}, rc: {}, problem_report: {})",
+                       command_handle, error::SUCCESS.message, problem_report);
+                let problem_report = CStringUtils::string_to_cstring(problem_report);
+                cb(command_handle, error::SUCCESS.code_num, problem_report.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_connection_get_problem_report_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Get the information about the connection state.
 ///
 /// Note: This method can be used for `aries` communication method only.
@@ -1148,6 +1803,7 @@ pub extern fn vcx_connection_send_discovery_features(command_handle: u32,
 ///             "routingKeys": array<str>
 ///             "serviceEndpoint": <str>,
 ///             "protocols": array<str> -  The set of protocol supported by current side.
+///             "keyAgreementKey": <str> - X25519 key used for DIDComm v2 envelopes, derived from the recipient key.
 ///         },
 ///         "remote: { <Option> - details about remote connection side
 ///             "did": <str> - DID of remote side
@@ -1155,6 +1811,12 @@ pub extern fn vcx_connection_send_discovery_features(command_handle: u32,
 ///             "routingKeys": array<str> - Routing keys
 ///             "serviceEndpoint": <str> - Endpoint
 ///             "protocols": array<str> - The set of protocol supported by side. Is filled after DiscoveryFeatures process was completed.
+///             "keyAgreementKey": <Option<str>> - X25519 key agreement key derived from the first recipient key
+///          },
+///         "last_ping": { <Option> - the last trust ping sent over the connection
+///             "ping_id": <str> - id of the ping message
+///             "sent_at": <int> - unix timestamp of sending
+///             "response_received_at": <Option<int>> - unix timestamp of receiving the response. Is filled after connection state update.
 ///          }
 ///    }
 ///
@@ -1337,6 +1999,27 @@ mod tests {
         assert_eq!(rc, error::INVALID_OPTION.code_num);
     }
 
+    #[test]
+    fn test_vcx_connection_find_existing() {
+        let _setup = SetupMocks::init();
+
+        let invitation = json!(::v3::messages::connection::invite::tests::_invitation()).to_string();
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_connection_find_existing(cb.command_handle,
+                                                CString::new(invitation).unwrap().into_raw(),
+                                                Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        assert_eq!(0, cb.receive(TimeoutUtils::some_medium()).unwrap());
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_connection_find_existing(cb.command_handle,
+                                                CString::new("{}").unwrap().into_raw(),
+                                                Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        assert_eq!(cb.receive(TimeoutUtils::some_medium()).unwrap_err(), error::INVALID_JSON.code_num);
+    }
+
     #[test]
     fn test_vcx_connection_connect() {
         let _setup = SetupMocks::init();
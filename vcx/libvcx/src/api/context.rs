@@ -0,0 +1,171 @@
+use context;
+use utils::error;
+use utils::libindy::{wallet, pool};
+use error::prelude::*;
+
+/// Create a new context isolating settings, wallet and pool of a tenant.
+/// Bind the context with `vcx_context_bind` and initialize it with `vcx_init_with_config` (or `vcx_init_minimal`)
+/// as if it was a separate library instance.
+/// API functions don't take a context handle: every call is executed in the context bound to the calling thread,
+/// so existing function signatures stay unchanged. Applications serving many tenants on a thread pool
+/// must bind the context of the tenant before each call.
+///
+/// #Params
+/// context_handle_p: reference that will contain the handle of created context
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_context_create(context_handle_p: *mut u32) -> u32 {
+    info!("vcx_context_create >>>");
+
+    if context_handle_p.is_null() {
+        return VcxError::from_msg(VcxErrorKind::InvalidOption, "Invalid pointer has been passed").into();
+    }
+
+    match context::create() {
+        Ok(handle) => {
+            trace!("vcx_context_create(context_handle: {})", handle);
+            unsafe { *context_handle_p = handle; }
+            error::SUCCESS.code_num
+        }
+        Err(err) => {
+            error!("vcx_context_create: {}", err);
+            err.into()
+        }
+    }
+}
+
+/// Bind the calling thread to the context. Subsequent API calls made by the thread
+/// (including async work and callbacks they start) are executed in this context.
+/// Object handles (connections, credentials, proofs, ...) must be used in the context they were created in.
+/// The default context (0) is used by threads which have never been bound.
+///
+/// #Params
+/// context_handle: handle of the context or 0 for the default context
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_context_bind(context_handle: u32) -> u32 {
+    info!("vcx_context_bind >>>");
+
+    trace!("vcx_context_bind(context_handle: {})", context_handle);
+
+    match context::bind(context_handle) {
+        Ok(()) => error::SUCCESS.code_num,
+        Err(err) => {
+            error!("vcx_context_bind: {}", err);
+            err.into()
+        }
+    }
+}
+
+/// Get the context the calling thread is bound to
+///
+/// #Returns
+/// Handle of the context
+#[no_mangle]
+pub extern fn vcx_context_current() -> u32 {
+    info!("vcx_context_current >>>");
+
+    context::current()
+}
+
+/// Stop services started in the context, unload its objects, close the wallet and the pool opened in the context
+/// and release the context. Persisted objects are kept in the wallet.
+/// The pool is kept open while other contexts use it (see `vcx_pool_set_handle`).
+/// Threads bound to the released context must be bound to another one.
+///
+/// #Params
+/// context_handle: handle of the context
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_context_release(context_handle: u32) -> u32 {
+    info!("vcx_context_release >>>");
+
+    trace!("vcx_context_release(context_handle: {})", context_handle);
+
+    if context_handle == context::DEFAULT_CONTEXT || !context::exists(context_handle) {
+        return VcxError::from_msg(VcxErrorKind::InvalidContextHandle, format!("Cannot release context: {}", context_handle)).into();
+    }
+
+    context::run_in(context_handle, || {
+        ::revocation_publisher::stop().ok();
+        ::outbound_queue::stop().ok();
+        ::mediation::reset();
+        ::events::unsubscribe_all();
+
+        ::schema::release_all();
+        ::connection::release_all();
+        ::issuer_credential::release_all();
+        ::credential_def::release_all();
+        ::proof::release_all();
+        ::disclosed_proof::release_all();
+        ::credential::release_all();
+
+        wallet::close_wallet().ok();
+
+        if !context::is_pool_shared() {
+            pool::close().ok();
+        }
+    });
+
+    match context::release(context_handle) {
+        Ok(()) => error::SUCCESS.code_num,
+        Err(err) => {
+            error!("vcx_context_release: {}", err);
+            err.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+    use utils::devsetup::*;
+
+    #[test]
+    fn test_vcx_context_create_bind_release() {
+        let _setup = SetupDefaults::init();
+
+        let mut context_handle = 0;
+        assert_eq!(error::SUCCESS.code_num, vcx_context_create(&mut context_handle));
+        assert_ne!(context::DEFAULT_CONTEXT, context_handle);
+
+        assert_eq!(error::SUCCESS.code_num, vcx_context_bind(context_handle));
+        assert_eq!(context_handle, vcx_context_current());
+
+        assert_eq!(error::SUCCESS.code_num, vcx_context_bind(context::DEFAULT_CONTEXT));
+        assert_eq!(context::DEFAULT_CONTEXT, vcx_context_current());
+
+        assert_eq!(error::SUCCESS.code_num, vcx_context_release(context_handle));
+        assert_eq!(error::INVALID_CONTEXT_HANDLE.code_num, vcx_context_bind(context_handle));
+        assert_eq!(error::INVALID_CONTEXT_HANDLE.code_num, vcx_context_release(context_handle));
+        assert_eq!(error::INVALID_CONTEXT_HANDLE.code_num, vcx_context_release(context::DEFAULT_CONTEXT));
+
+        assert_eq!(error::INVALID_OPTION.code_num, vcx_context_create(ptr::null_mut()));
+    }
+
+    #[test]
+    fn test_vcx_context_isolates_settings() {
+        let _setup = SetupDefaults::init();
+
+        let mut context_handle = 0;
+        vcx_context_create(&mut context_handle);
+
+        ::settings::set_config_value(::settings::CONFIG_INSTITUTION_NAME, "default");
+
+        context::run_in(context_handle, || {
+            assert!(::settings::get_config_value(::settings::CONFIG_INSTITUTION_NAME).is_err());
+            ::settings::set_config_value(::settings::CONFIG_INSTITUTION_NAME, "tenant");
+        });
+
+        assert_eq!("default", ::settings::get_config_value(::settings::CONFIG_INSTITUTION_NAME).unwrap());
+
+        vcx_context_release(context_handle);
+    }
+}
@@ -34,6 +34,7 @@ use indy_sys::CommandHandle;
 
         VcxStateType::VcxStateAccepted - once `Credential` messages is received.
         VcxStateType::None - once `ProblemReport` messages is received.
+        VcxStateType::VcxStateRevoked - once `Revoke` message is received for the accepted credential.
                                                 use `vcx_credential_update_state` or `vcx_credential_update_state_with_message` functions for state updates.
 
     # Transitions
@@ -53,6 +54,8 @@ use indy_sys::CommandHandle;
         VcxStateType::VcxStateOfferSent - received `Credential` - VcxStateType::VcxStateAccepted
         VcxStateType::VcxStateOfferSent - received `ProblemReport` - VcxStateType::None
 
+        VcxStateType::VcxStateAccepted - received `Revoke` - VcxStateType::VcxStateRevoked
+
     # Messages
 
     proprietary:
@@ -67,6 +70,7 @@ use indy_sys::CommandHandle;
         Credential - https://github.com/hyperledger/aries-rfcs/tree/7b6b93acbaf9611d3c892c4bada142fe2613de6e/features/0036-issue-credential#issue-credential
         ProblemReport - https://github.com/hyperledger/aries-rfcs/tree/7b6b93acbaf9611d3c892c4bada142fe2613de6e/features/0035-report-problem#the-problem-report-message-type
         Ack - https://github.com/hyperledger/aries-rfcs/tree/master/features/0015-acks#explicit-acks
+        Revoke - https://github.com/hyperledger/aries-rfcs/tree/master/features/0183-revocation-notification
 */
 
 /// Retrieve Payment Transaction Information for this Credential. Typically this will include
@@ -241,6 +245,8 @@ pub extern fn vcx_get_credential(command_handle: CommandHandle,
 }
 
 /// Delete a Credential from the wallet and release its handle.
+/// Cached revocation state of the credential's revocation registry is deleted in the same wallet transaction
+/// unless other credentials of the wallet belong to the registry. `credential_deleted` event is emitted on success.
 ///
 /// # Params
 /// command_handle: command handle to map callback to user context.
@@ -617,6 +623,68 @@ pub extern fn vcx_credential_update_state_with_message(command_handle: CommandHa
     error::SUCCESS.code_num
 }
 
+/// Update the state of the credential based on the message already downloaded from the agency
+/// (for example, delivered within push notification payload).
+/// Agency is not queried for new messages, so state can be advanced within background time budget of mobile platforms.
+/// Note that replies required by the protocol are still sent.
+/// Only Aries credentials are supported.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// connection_handle: handle of the connection the message was received on
+///
+/// message: agency message json as returned by `vcx_messages_download`
+///
+/// cb: Callback that provides most current state of the credential and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_credential_update_state_with_agency_message(command_handle: CommandHandle,
+                                                              credential_handle: u32,
+                                                              connection_handle: u32,
+                                                              message: *const c_char,
+                                                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_credential_update_state_with_agency_message >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message, VcxErrorKind::InvalidOption);
+
+    if !credential::is_valid_handle(credential_handle) {
+        return VcxError::from(VcxErrorKind::InvalidCredentialHandle).into();
+    }
+
+    if !connection::is_valid_handle(connection_handle) {
+        return VcxError::from(VcxErrorKind::InvalidConnectionHandle).into();
+    }
+
+    let source_id = credential::get_source_id(credential_handle).unwrap_or_default();
+    trace!("vcx_credential_update_state_with_agency_message(command_handle: {}, credential_handle: {}, connection_handle: {}), source_id: {:?}",
+           command_handle, credential_handle, connection_handle, source_id);
+
+    spawn(move || {
+        match credential::update_state_with_agency_message(credential_handle, connection_handle, &message) {
+            Ok(state) => {
+                trace!("vcx_credential_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, error::SUCCESS.message, state, source_id);
+                cb(command_handle, error::SUCCESS.code_num, state)
+            }
+            Err(e) => {
+                error!("vcx_credential_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, e, 0, source_id);
+                cb(command_handle, e.into(), 0)
+            }
+        }
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Get the current state of the credential object
 ///
 /// #Params
@@ -716,6 +784,54 @@ pub extern fn vcx_credential_serialize(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Gets the Credential Preview of the received offer, including display hints provided by the issuer (Aries protocol only)
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// cb: Callback that provides Credential Preview json and provides error status
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_credential_get_preview(command_handle: CommandHandle,
+                                         handle: u32,
+                                         cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, preview: *const c_char)>) -> u32 {
+    info!("vcx_credential_get_preview >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    if !credential::is_valid_handle(handle) {
+        return VcxError::from(VcxErrorKind::InvalidCredentialHandle).into()
+    }
+
+    let source_id = credential::get_source_id(handle).unwrap_or_default();
+    trace!("vcx_credential_get_preview(command_handle: {}, credential_handle: {}), source_id: {:?}",
+           command_handle, handle, source_id);
+
+    spawn(move || {
+        match credential::get_credential_preview(handle) {
+            Ok(x) => {
+                trace!("vcx_credential_get_preview_cb(command_handle: {}, rc: {}, preview: {}), source_id: {:?}",
+                       command_handle, error::SUCCESS.message, x, source_id);
+                let msg = CStringUtils::string_to_cstring(x);
+                cb(command_handle, error::SUCCESS.code_num, msg.as_ptr());
+            }
+            Err(x) => {
+                error!("vcx_credential_get_preview_cb(command_handle: {}, rc: {}, preview: {}), source_id: {:?}",
+                       command_handle, x, "null", source_id);
+                cb(command_handle, x.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Takes a json string representing an credential object and recreates an object matching the json
 ///
 /// #Params
@@ -849,6 +965,65 @@ pub extern fn vcx_credential_get_payment_txn(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Get Problem Report received from the Issuer or sent on failure of the credential issuance.
+/// Problem Report describes the reason the protocol was abandoned: `description.code` contains the error code,
+/// `explain` (or `comment`) contains a human-readable explanation.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// cb: Callback that provides Problem Report json or NotReady error if there is no Problem Report
+///
+/// # Example
+/// problem_report ->
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/notification/1.0/problem-report",
+///         "@id": "7c4c4eb4-1f5e-4a1e-a7b3-3b8c9f4a2d11",
+///         "description": {"code": "request-not-accepted"},
+///         "impact": "thread",
+///         "explain": "Credential Request is rejected",
+///         "~thread": {"thid": "a1f3e5a9-ff8e-4d25-9d5b-7a8e9f8b3c0d"}
+///      }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_credential_get_problem_report(command_handle: CommandHandle,
+                                                credential_handle: u32,
+                                                cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, problem_report: *const c_char)>) -> u32 {
+    info!("vcx_credential_get_problem_report >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_credential_get_problem_report(command_handle: {}, credential_handle: {})", command_handle, credential_handle);
+
+    spawn(move || {
+        match credential::get_problem_report(credential_handle) {
+            Ok(problem_report) => {
+                trace!("vcx_credential_get_problem_report_cb(command_handle: {}, rc: {}, problem_report: {})",
+                       command_handle, error::SUCCESS.message, problem_report);
+                let problem_report = CStringUtils::string_to_cstring(problem_report);
+                cb(command_handle, error::SUCCESS.code_num, problem_report.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_credential_get_problem_report_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 #[cfg(test)]
 mod tests {
     extern crate serde_json;
@@ -7,6 +7,7 @@ use std::ptr;
 use utils::threadpool::spawn;
 use error::prelude::*;
 use indy_sys::CommandHandle;
+use v3::handlers::proof_presentation::prover::credential_selection;
 
 /*
     APIs in this module are called by a prover throughout the request-proof-and-verify process.
@@ -566,6 +567,68 @@ pub extern fn vcx_disclosed_proof_update_state_with_message(command_handle: Comm
     error::SUCCESS.code_num
 }
 
+/// Update the state of the proof based on the message already downloaded from the agency
+/// (for example, delivered within push notification payload).
+/// Agency is not queried for new messages, so state can be advanced within background time budget of mobile platforms.
+/// Note that replies required by the protocol are still sent.
+/// Only Aries proofs are supported.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// proof_handle: Proof handle that was provided during creation. Used to identify disclosed proof object
+///
+/// connection_handle: handle of the connection the message was received on
+///
+/// message: agency message json as returned by `vcx_messages_download`
+///
+/// cb: Callback that provides most current state of the proof and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_disclosed_proof_update_state_with_agency_message(command_handle: CommandHandle,
+                                                                   proof_handle: u32,
+                                                                   connection_handle: u32,
+                                                                   message: *const c_char,
+                                                                   cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_disclosed_proof_update_state_with_agency_message >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message, VcxErrorKind::InvalidOption);
+
+    if !disclosed_proof::is_valid_handle(proof_handle) {
+        return VcxError::from(VcxErrorKind::InvalidDisclosedProofHandle).into();
+    }
+
+    if !connection::is_valid_handle(connection_handle) {
+        return VcxError::from(VcxErrorKind::InvalidConnectionHandle).into();
+    }
+
+    let source_id = disclosed_proof::get_source_id(proof_handle).unwrap_or_default();
+    trace!("vcx_disclosed_proof_update_state_with_agency_message(command_handle: {}, proof_handle: {}, connection_handle: {}), source_id: {:?}",
+           command_handle, proof_handle, connection_handle, source_id);
+
+    spawn(move || {
+        match disclosed_proof::update_state_with_agency_message(proof_handle, connection_handle, &message) {
+            Ok(state) => {
+                trace!("vcx_disclosed_proof_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, error::SUCCESS.message, state, source_id);
+                cb(command_handle, error::SUCCESS.code_num, state)
+            }
+            Err(e) => {
+                error!("vcx_disclosed_proof_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, e, 0, source_id);
+                cb(command_handle, e.into(), 0)
+            }
+        }
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Takes the disclosed proof object and returns a json string of all its attributes
 ///
 /// #Params
@@ -924,6 +987,95 @@ pub extern fn vcx_disclosed_proof_release(handle: u32) -> u32 {
     }
 }
 
+/// Get Problem Report received from the Verifier or sent on failure of the proof presentation.
+/// Problem Report describes the reason the protocol was abandoned: `description.code` contains the error code,
+/// `explain` (or `comment`) contains a human-readable explanation.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// proof_handle: Proof handle that was provided during creation. Used to identify disclosed proof object
+///
+/// cb: Callback that provides Problem Report json or NotReady error if there is no Problem Report
+///
+/// # Example
+/// problem_report ->
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/notification/1.0/problem-report",
+///         "@id": "7c4c4eb4-1f5e-4a1e-a7b3-3b8c9f4a2d11",
+///         "description": {"code": "request-not-accepted"},
+///         "impact": "thread",
+///         "explain": "Credential Request is rejected",
+///         "~thread": {"thid": "a1f3e5a9-ff8e-4d25-9d5b-7a8e9f8b3c0d"}
+///      }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_disclosed_proof_get_problem_report(command_handle: CommandHandle,
+                                                     proof_handle: u32,
+                                                     cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, problem_report: *const c_char)>) -> u32 {
+    info!("vcx_disclosed_proof_get_problem_report >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_disclosed_proof_get_problem_report(command_handle: {}, proof_handle: {})", command_handle, proof_handle);
+
+    spawn(move || {
+        match disclosed_proof::get_problem_report(proof_handle) {
+            Ok(problem_report) => {
+                trace!("vcx_disclosed_proof_get_problem_report_cb(command_handle: {}, rc: {}, problem_report: {})",
+                       command_handle, error::SUCCESS.message, problem_report);
+                let problem_report = CStringUtils::string_to_cstring(problem_report);
+                cb(command_handle, error::SUCCESS.code_num, problem_report.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_disclosed_proof_get_problem_report_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Set handler choosing a credential when several credentials match a requested attribute or predicate
+/// on automatic presentation of proofs (`auto_present_proofs` is `always`)
+/// and `credential_selection_strategy` library setting is `{"type": "callback"}`.
+///
+/// Handler is called synchronously on the thread which processed the presentation request.
+///
+/// #Params
+/// handler: (optional) Handler of the selection, the previous handler is removed if not specified.
+///     Receives requested attribute or predicate referent and JSON array of matching credentials
+///     (as the element of `vcx_disclosed_proof_retrieve_credentials` result).
+///     Returns index of the chosen credential or -1 to leave the presentation request to the application.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_set_credential_selection_handler(handler: Option<extern fn(referent: *const c_char, credentials: *const c_char) -> i32>) -> u32 {
+    info!("vcx_set_credential_selection_handler >>>");
+
+    match credential_selection::set_selection_handler(handler) {
+        Ok(()) => {
+            trace!("vcx_set_credential_selection_handler(rc: {})", error::SUCCESS.message);
+            error::SUCCESS.code_num
+        }
+        Err(e) => {
+            warn!("vcx_set_credential_selection_handler(rc: {})", e);
+            e.into()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate serde_json;
@@ -1130,4 +1282,14 @@ mod tests {
                                                       Some(cb.get_callback())), error::SUCCESS.code_num);
         cb.receive(TimeoutUtils::some_medium()).unwrap();
     }
+
+    extern fn _selection_handler(_referent: *const c_char, _credentials: *const c_char) -> i32 { 0 }
+
+    #[test]
+    fn test_vcx_set_credential_selection_handler() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(vcx_set_credential_selection_handler(Some(_selection_handler)), error::SUCCESS.code_num);
+        assert_eq!(vcx_set_credential_selection_handler(None), error::SUCCESS.code_num);
+    }
 }
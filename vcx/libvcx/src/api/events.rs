@@ -0,0 +1,146 @@
+use libc::c_char;
+use utils::cstring::CStringUtils;
+use utils::error;
+use std::sync::Arc;
+use events::{self, Event};
+use utils::threadpool::spawn;
+use error::prelude::*;
+use indy_sys::CommandHandle;
+
+/// Register handler of protocol events, so application doesn't need to poll states of objects.
+///
+/// Events are emitted when a state of Connection, Credential, IssuerCredential, Proof or DisclosedProof object
+/// changes while processing messages (`vcx_*_update_state*` functions).
+/// Every event is passed to the handler as JSON:
+///     {
+///         "event_type": string, - one of event types listed below
+///         "object_type": string, - "connection", "credential", "issuer_credential", "proof" or "disclosed_proof"
+///         "handle": u32, - handle of the object
+///         "source_id": string, - source id of the object
+///         "prev_state": u32, - state of the object before the change
+///         "state": u32 - current state of the object
+///     }
+///
+/// Event types:
+///     "state_changed" - emitted on every state change
+///     "connection_established" - connection became Accepted
+///     "credential_offer_received" - credential became RequestReceived
+///     "credential_received" - credential became Accepted
+///     "credential_issued" - issuer credential became Accepted
+///     "proof_request_received" - disclosed proof became RequestReceived
+///     "proof_verified" - proof became Accepted
+///     "credential_deleted" - credential was deleted from the wallet with `vcx_delete_credential`
+///                            (handle is already released, state is the last state of the credential)
+///
+/// Handler is called synchronously on the thread which processed the message and should return fast.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// event_types: (optional) JSON array of event types to receive. All events are received if not specified.
+///     Example: ["connection_established", "credential_received"]
+///
+/// handler: Handler of events. Receives handle of the registered handler and event JSON.
+///
+/// cb: Callback that provides handle of the registered handler (to unregister it) and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_register_event_handler(command_handle: CommandHandle,
+                                         event_types: *const c_char,
+                                         handler: Option<extern fn(handler_handle: u32, event: *const c_char)>,
+                                         cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, handler_handle: u32)>) -> u32 {
+    info!("vcx_register_event_handler >>>");
+
+    check_useful_c_callback!(handler, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_opt_c_str!(event_types, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_register_event_handler(command_handle: {}, event_types: {:?})", command_handle, event_types);
+
+    let event_types = match event_types.as_ref().map(|event_types| events::parse_event_types(event_types)) {
+        Some(Ok(event_types)) => Some(event_types),
+        Some(Err(err)) => return err.into(),
+        None => None
+    };
+
+    spawn(move || {
+        let handler_handle = events::subscribe(event_types, Arc::new(move |handler_handle, event: &Event| {
+            let event = CStringUtils::string_to_cstring(event.to_json());
+            handler(handler_handle, event.as_ptr());
+        }));
+
+        trace!("vcx_register_event_handler_cb(command_handle: {}, rc: {}, handler_handle: {})",
+               command_handle, error::SUCCESS.message, handler_handle);
+        cb(command_handle, error::SUCCESS.code_num, handler_handle);
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Unregister handler of protocol events.
+///
+/// #Params
+/// handler_handle: handle of the handler returned by `vcx_register_event_handler`
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_unregister_event_handler(handler_handle: u32) -> u32 {
+    info!("vcx_unregister_event_handler >>>");
+
+    match events::unsubscribe(handler_handle) {
+        Ok(()) => {
+            trace!("vcx_unregister_event_handler(handler_handle: {}, rc: {})",
+                   handler_handle, error::SUCCESS.message);
+            error::SUCCESS.code_num
+        }
+        Err(e) => {
+            warn!("vcx_unregister_event_handler(handler_handle: {}, rc: {})",
+                  handler_handle, e);
+            e.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use utils::devsetup::*;
+    use utils::timeout::TimeoutUtils;
+    use api::return_types_u32;
+
+    extern fn _handler(_handler_handle: u32, _event: *const c_char) {}
+
+    #[test]
+    fn test_vcx_register_event_handler() {
+        let _setup = SetupDefaults::init();
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_register_event_handler(cb.command_handle,
+                                              CString::new(r#"["proof_verified"]"#).unwrap().into_raw(),
+                                              Some(_handler),
+                                              Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        let handler_handle = cb.receive(TimeoutUtils::some_medium()).unwrap();
+
+        assert_eq!(vcx_unregister_event_handler(handler_handle), error::SUCCESS.code_num);
+        assert_eq!(vcx_unregister_event_handler(handler_handle), error::INVALID_OBJ_HANDLE.code_num);
+    }
+
+    #[test]
+    fn test_vcx_register_event_handler_fails_for_invalid_event_types() {
+        let _setup = SetupDefaults::init();
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_register_event_handler(cb.command_handle,
+                                              CString::new(r#"["unknown"]"#).unwrap().into_raw(),
+                                              Some(_handler),
+                                              Some(cb.get_callback())),
+                   error::INVALID_OPTION.code_num);
+    }
+}
@@ -176,6 +176,114 @@ pub extern fn vcx_issuer_create_credential(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Create a Issuer Credential object whose attributes are derived from an existing Issuer Credential object (master record).
+/// Allows to issue a narrower credential (e.g. summary credential) under a different credential definition
+/// without re-entering data. The link to the master record is stored in the created object.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// source_id: Enterprise's personal identification for the user.
+///
+/// master_handle: handle of Issuer Credential object holding master values.
+///     Values of issued credential aren't kept in its serialized state, so the object restored after issuance can't be used.
+///
+/// cred_def_handle: handle of credential definition of derived credential
+///
+/// issuer_did: did corresponding to entity issuing a credential. Needs to have Trust Anchor permissions on ledger
+///
+/// derivation: json mapping attributes of derived credential to master attributes
+///     {
+///         "<attr_name>": "<master_attr_name>", // copy value
+///         "<attr_name>": {"from": "<master_attr_name>", "transform": {"type": "uppercase"}}, // transformed value: uppercase, lowercase, substring
+///         "<attr_name>": {"from": "<master_attr_name>", "transform": {"type": "substring", "start": int, "length": Optional<int>}},
+///         "<attr_name>": {"value": "<value>"}, // constant value
+///     }
+///
+/// credential_name: Name of the credential - ex. Drivers Licence
+///
+/// price: price of credential
+///
+/// cb: Callback that provides credential handle and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_create_derived_credential(command_handle: CommandHandle,
+                                                   source_id: *const c_char,
+                                                   master_handle: u32,
+                                                   cred_def_handle: u32,
+                                                   issuer_did: *const c_char,
+                                                   derivation: *const c_char,
+                                                   credential_name: *const c_char,
+                                                   price: *const c_char,
+                                                   cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, credential_handle: u32)>) -> u32 {
+    info!("vcx_issuer_create_derived_credential >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(derivation, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(credential_name, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(source_id, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(price, VcxErrorKind::InvalidOption);
+
+    let issuer_did: String = if !issuer_did.is_null() {
+        check_useful_c_str!(issuer_did, VcxErrorKind::InvalidOption);
+        issuer_did.to_owned()
+    } else {
+        match settings::get_config_value(settings::CONFIG_INSTITUTION_DID) {
+            Ok(x) => x,
+            Err(x) => return x.into()
+        }
+    };
+
+    let price: u64 = match price.parse::<u64>() {
+        Ok(x) => x,
+        Err(err) => return VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Cannot parse price: {}", err)).into(),
+    };
+
+    if !issuer_credential::is_valid_handle(master_handle) {
+        return VcxError::from(VcxErrorKind::InvalidIssuerCredentialHandle).into();
+    }
+
+    if !::credential_def::is_valid_handle(cred_def_handle) {
+        return VcxError::from(VcxErrorKind::InvalidCredDefHandle).into()
+    }
+
+    if !::credential_def::check_is_published(cred_def_handle).unwrap_or(false) {
+        return VcxError::from_msg(VcxErrorKind::InvalidCredDefHandle, "Credential Definition is not in the Published State yet").into()
+    }
+
+    trace!("vcx_issuer_create_derived_credential(command_handle: {}, source_id: {}, master_handle: {}, cred_def_handle: {}, issuer_did: {}, derivation: {}, credential_name: {})",
+           command_handle,
+           source_id,
+           master_handle,
+           cred_def_handle,
+           issuer_did,
+           derivation,
+           credential_name);
+
+    spawn(move || {
+        let (rc, handle) = match issuer_credential::issuer_credential_create_derived(master_handle, cred_def_handle, source_id, issuer_did, credential_name, derivation, price) {
+            Ok(x) => {
+                trace!("vcx_issuer_create_derived_credential_cb(command_handle: {}, rc: {}, handle: {}) source_id: {}",
+                       command_handle, error::SUCCESS.message, x, issuer_credential::get_source_id(x).unwrap_or_default());
+                (error::SUCCESS.code_num, x)
+            }
+            Err(x) => {
+                warn!("vcx_issuer_create_derived_credential_cb(command_handle: {}, rc: {}, handle: {}) source_id: {}",
+                      command_handle, x, 0, "");
+                (x.into(), 0)
+            }
+        };
+
+        cb(command_handle, rc, handle);
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Send a credential offer to user showing what will be included in the actual credential
 ///
 /// #Params
@@ -389,6 +497,68 @@ pub extern fn vcx_issuer_credential_update_state_with_message(command_handle: Co
     error::SUCCESS.code_num
 }
 
+/// Update the state of the credential based on the message already downloaded from the agency
+/// (for example, delivered within push notification payload).
+/// Agency is not queried for new messages, so state can be advanced within background time budget of mobile platforms.
+/// Note that replies required by the protocol are still sent.
+/// Only Aries credentials are supported.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// connection_handle: handle of the connection the message was received on
+///
+/// message: agency message json as returned by `vcx_messages_download`
+///
+/// cb: Callback that provides most current state of the credential and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_credential_update_state_with_agency_message(command_handle: CommandHandle,
+                                                                     credential_handle: u32,
+                                                                     connection_handle: u32,
+                                                                     message: *const c_char,
+                                                                     cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_issuer_credential_update_state_with_agency_message >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message, VcxErrorKind::InvalidOption);
+
+    if !issuer_credential::is_valid_handle(credential_handle) {
+        return VcxError::from(VcxErrorKind::InvalidIssuerCredentialHandle).into();
+    }
+
+    if !connection::is_valid_handle(connection_handle) {
+        return VcxError::from(VcxErrorKind::InvalidConnectionHandle).into();
+    }
+
+    let source_id = issuer_credential::get_source_id(credential_handle).unwrap_or_default();
+    trace!("vcx_issuer_credential_update_state_with_agency_message(command_handle: {}, credential_handle: {}, connection_handle: {}), source_id: {:?}",
+           command_handle, credential_handle, connection_handle, source_id);
+
+    spawn(move || {
+        match issuer_credential::update_state_with_agency_message(credential_handle, connection_handle, &message) {
+            Ok(state) => {
+                trace!("vcx_issuer_credential_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, error::SUCCESS.message, state, source_id);
+                cb(command_handle, error::SUCCESS.code_num, state)
+            }
+            Err(e) => {
+                error!("vcx_issuer_credential_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, e, 0, source_id);
+                cb(command_handle, e.into(), 0)
+            }
+        }
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Get the current state of the issuer credential object
 ///
 /// #Params
@@ -791,6 +961,304 @@ pub extern fn vcx_issuer_revoke_credential(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Send Revocation Notification to the holder of the issued credential.
+/// Holder marks the stored credential as revoked and moves it into VcxStateRevoked state without checking the ledger.
+/// The credential itself is revoked on the ledger by `vcx_issuer_revoke_credential`.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// connection_handle: Connection handle that identifies pairwise connection the credential was issued over
+///
+/// comment: (Optional) human-readable reason of revocation
+///
+/// cb: Callback that provides error status of sending the notification
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_send_revocation_notification(command_handle: CommandHandle,
+                                                      credential_handle: u32,
+                                                      connection_handle: u32,
+                                                      comment: *const c_char,
+                                                      cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_issuer_send_revocation_notification >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_opt_c_str!(comment, VcxErrorKind::InvalidOption);
+
+    if !issuer_credential::is_valid_handle(credential_handle) {
+        return VcxError::from(VcxErrorKind::InvalidIssuerCredentialHandle).into()
+    }
+
+    if !connection::is_valid_handle(connection_handle) {
+        return VcxError::from(VcxErrorKind::InvalidConnectionHandle).into()
+    }
+
+    let source_id = issuer_credential::get_source_id(credential_handle).unwrap_or_default();
+    trace!("vcx_issuer_send_revocation_notification(command_handle: {}, credential_handle: {}, connection_handle: {}, comment: {:?}) source_id: {}",
+           command_handle, credential_handle, connection_handle, comment, source_id);
+
+    spawn(move || {
+        let err = match issuer_credential::send_revocation_notification(credential_handle, connection_handle, comment) {
+            Ok(()) => {
+                trace!("vcx_issuer_send_revocation_notification_cb(command_handle: {}, credential_handle: {}, rc: {}) source_id: {}",
+                       command_handle, credential_handle, error::SUCCESS.message, source_id);
+                error::SUCCESS.code_num
+            }
+            Err(x) => {
+                warn!("vcx_issuer_send_revocation_notification_cb(command_handle: {}, credential_handle: {}, rc: {}) source_id: {}",
+                      command_handle, credential_handle, x, source_id);
+                x.into()
+            }
+        };
+
+        cb(command_handle, err);
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Sets display hints (label, order, group, locale) for the attributes of credential preview.
+/// Hints are sent to the holder within Credential Offer (Aries protocol only).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// display_hints: json map of attribute names to display hints
+///     {
+///         "attr_name": {"label": Optional<string>, "order": Optional<int>, "group": Optional<string>, "locale": Optional<string>},
+///         ...
+///     }
+///
+/// cb: Callback that provides error status of setting display hints
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_credential_set_display_hints(command_handle: CommandHandle,
+                                                      credential_handle: u32,
+                                                      display_hints: *const c_char,
+                                                      cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_issuer_credential_set_display_hints >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(display_hints, VcxErrorKind::InvalidOption);
+
+    if !issuer_credential::is_valid_handle(credential_handle) {
+        return VcxError::from(VcxErrorKind::InvalidIssuerCredentialHandle).into()
+    }
+
+    let source_id = issuer_credential::get_source_id(credential_handle).unwrap_or_default();
+    trace!("vcx_issuer_credential_set_display_hints(command_handle: {}, credential_handle: {}, display_hints: {}) source_id: {}",
+           command_handle, credential_handle, display_hints, source_id);
+
+    spawn(move || {
+        let err = match issuer_credential::set_display_hints(credential_handle, &display_hints) {
+            Ok(()) => {
+                trace!("vcx_issuer_credential_set_display_hints_cb(command_handle: {}, credential_handle: {}, rc: {}) source_id: {}",
+                       command_handle, credential_handle, error::SUCCESS.message, source_id);
+                error::SUCCESS.code_num
+            }
+            Err(x) => {
+                warn!("vcx_issuer_credential_set_display_hints_cb(command_handle: {}, credential_handle: {}, rc: {}) source_id: {}",
+                      command_handle, credential_handle, x, source_id);
+                x.into()
+            }
+        };
+
+        cb(command_handle, err);
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Sets the terms of data processing the holder is asked to consent to (Aries protocol only).
+/// Template is sent to the holder within Credential Offer and the holder signs the consent receipt within Credential Request.
+/// Credential Request without valid consent receipt is rejected with Problem Report.
+/// Valid receipts are stored in the wallet and can be queried with `vcx_issuer_get_consent_receipts`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// consent_template: json describing the terms
+///     {
+///         "purpose": string - purpose of data processing,
+///         "data_categories": [string] - categories of personal data being processed,
+///         "retention": string - ISO 8601 duration the data will be kept for (e.g. "P1Y")
+///     }
+///
+/// cb: Callback that provides error status of setting consent template
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_credential_set_consent_template(command_handle: CommandHandle,
+                                                         credential_handle: u32,
+                                                         consent_template: *const c_char,
+                                                         cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_issuer_credential_set_consent_template >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(consent_template, VcxErrorKind::InvalidOption);
+
+    if !issuer_credential::is_valid_handle(credential_handle) {
+        return VcxError::from(VcxErrorKind::InvalidIssuerCredentialHandle).into()
+    }
+
+    let source_id = issuer_credential::get_source_id(credential_handle).unwrap_or_default();
+    trace!("vcx_issuer_credential_set_consent_template(command_handle: {}, credential_handle: {}, consent_template: {}) source_id: {}",
+           command_handle, credential_handle, consent_template, source_id);
+
+    spawn(move || {
+        let err = match issuer_credential::set_consent_template(credential_handle, &consent_template) {
+            Ok(()) => {
+                trace!("vcx_issuer_credential_set_consent_template_cb(command_handle: {}, credential_handle: {}, rc: {}) source_id: {}",
+                       command_handle, credential_handle, error::SUCCESS.message, source_id);
+                error::SUCCESS.code_num
+            }
+            Err(x) => {
+                warn!("vcx_issuer_credential_set_consent_template_cb(command_handle: {}, credential_handle: {}, rc: {}) source_id: {}",
+                      command_handle, credential_handle, x, source_id);
+                x.into()
+            }
+        };
+
+        cb(command_handle, err);
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Queries consent receipts signed by holders and stored in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// query: (Optional) WQL query over receipt tags: `cred_def_id`, `thread_id`, `holder_verkey`, `purpose`.
+///     All receipts are returned if query is not specified.
+///     Example: {"cred_def_id": "V4SGRU86Z58d6TV7PBUe6f:3:CL:123:tag"}
+///
+/// cb: Callback that provides json list of consent receipts
+///     [{
+///         "receipt_id": string,
+///         "purpose": string,
+///         "data_categories": [string],
+///         "retention": string,
+///         "thread_id": string - thread of credential issuance,
+///         "holder_verkey": string - key of the holder pairwise connection,
+///         "consent_timestamp": int - unix time of consent,
+///         "signature": string - base64 encoded signature of the holder over the other fields
+///     }]
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_get_consent_receipts(command_handle: CommandHandle,
+                                              query: *const c_char,
+                                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, receipts: *const c_char)>) -> u32 {
+    info!("vcx_issuer_get_consent_receipts >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_opt_c_str!(query, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_issuer_get_consent_receipts(command_handle: {}, query: {:?})", command_handle, query);
+
+    spawn(move || {
+        match issuer_credential::get_consent_receipts(query.as_ref().map(String::as_str)) {
+            Ok(receipts) => {
+                trace!("vcx_issuer_get_consent_receipts_cb(command_handle: {}, rc: {}, receipts: {})",
+                       command_handle, error::SUCCESS.message, receipts);
+                let receipts = CStringUtils::string_to_cstring(receipts);
+                cb(command_handle, error::SUCCESS.code_num, receipts.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_issuer_get_consent_receipts_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Get Problem Report received from the Holder or sent on failure of the credential issuance.
+/// Problem Report describes the reason the protocol was abandoned: `description.code` contains the error code,
+/// `explain` (or `comment`) contains a human-readable explanation.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// credential_handle: Credential handle that was provided during creation. Used to identify credential object
+///
+/// cb: Callback that provides Problem Report json or NotReady error if there is no Problem Report
+///
+/// # Example
+/// problem_report ->
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/notification/1.0/problem-report",
+///         "@id": "7c4c4eb4-1f5e-4a1e-a7b3-3b8c9f4a2d11",
+///         "description": {"code": "request-not-accepted"},
+///         "impact": "thread",
+///         "explain": "Credential Request is rejected",
+///         "~thread": {"thid": "a1f3e5a9-ff8e-4d25-9d5b-7a8e9f8b3c0d"}
+///      }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_issuer_credential_get_problem_report(command_handle: CommandHandle,
+                                                       credential_handle: u32,
+                                                       cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, problem_report: *const c_char)>) -> u32 {
+    info!("vcx_issuer_credential_get_problem_report >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_issuer_credential_get_problem_report(command_handle: {}, credential_handle: {})", command_handle, credential_handle);
+
+    spawn(move || {
+        match issuer_credential::get_problem_report(credential_handle) {
+            Ok(problem_report) => {
+                trace!("vcx_issuer_credential_get_problem_report_cb(command_handle: {}, rc: {}, problem_report: {})",
+                       command_handle, error::SUCCESS.message, problem_report);
+                let problem_report = CStringUtils::string_to_cstring(problem_report);
+                cb(command_handle, error::SUCCESS.code_num, problem_report.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_issuer_credential_get_problem_report_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 #[cfg(test)]
 pub mod tests {
     extern crate serde_json;
@@ -899,6 +1367,28 @@ pub mod tests {
         assert!(handle > 0);
     }
 
+    #[test]
+    fn test_vcx_issuer_create_derived_credential_success() {
+        let _setup = SetupMocks::init();
+
+        let master_handle = _vcx_issuer_create_credential_c_closure().unwrap();
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_issuer_create_derived_credential(cb.command_handle,
+                                                        CString::new(DEFAULT_CREDENTIAL_NAME).unwrap().into_raw(),
+                                                        master_handle,
+                                                        ::credential_def::tests::create_cred_def_fake(),
+                                                        CString::new(DEFAULT_DID).unwrap().into_raw(),
+                                                        CString::new(r#"{"derived_attr": {"from": "attr", "transform": {"type": "uppercase"}}}"#).unwrap().into_raw(),
+                                                        CString::new(DEFAULT_CREDENTIAL_NAME).unwrap().into_raw(),
+                                                        CString::new("1").unwrap().into_raw(),
+                                                        Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        let handle = cb.receive(TimeoutUtils::some_short()).unwrap();
+        assert!(handle > 0);
+        assert_eq!(r#"{"derived_attr":"VALUE"}"#, issuer_credential::get_credential_attributes(handle).unwrap());
+    }
+
     #[test]
     fn test_vcx_issuer_create_credential_fails() {
         let _setup = SetupMocks::init();
@@ -0,0 +1,300 @@
+use libc::c_char;
+use utils::cstring::CStringUtils;
+use utils::error;
+use std::ptr;
+use mediation;
+use utils::threadpool::spawn;
+use error::prelude::*;
+use indy_sys::CommandHandle;
+
+/// Request mediation from the mediator over established connection (Mediator Coordination protocol).
+/// Once mediation is granted, recipient keys of new connections are registered with the mediator and its
+/// endpoint and routing keys are used in invitations and DIDDocs instead of ones of the agency.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// connection_handle: handle of established connection with the mediator
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_request(command_handle: CommandHandle,
+                                    connection_handle: u32,
+                                    cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_mediation_request >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_mediation_request(command_handle: {}, connection_handle: {})", command_handle, connection_handle);
+
+    spawn(move || {
+        match mediation::request(connection_handle) {
+            Ok(()) => {
+                trace!("vcx_mediation_request_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_mediation_request_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Query the mediator connection for the answer to mediation request or keylist updates.
+///     Possible states:
+///         2 - Requested
+///         4 - Granted
+///         9 - Denied
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides most current state of mediation and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_update_state(command_handle: CommandHandle,
+                                         cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_mediation_update_state >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_mediation_update_state(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match mediation::update_state() {
+            Ok(state) => {
+                trace!("vcx_mediation_update_state_cb(command_handle: {}, rc: {}, state: {})",
+                       command_handle, error::SUCCESS.message, state);
+                cb(command_handle, error::SUCCESS.code_num, state);
+            }
+            Err(e) => {
+                warn!("vcx_mediation_update_state_cb(command_handle: {}, rc: {}, state: {})",
+                      command_handle, e, 0);
+                cb(command_handle, e.into(), 0);
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Returns the current state of mediation (0 if mediation is not requested). Does NOT query the mediator.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides most current state of mediation
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_get_state(command_handle: CommandHandle,
+                                      cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_mediation_get_state >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_mediation_get_state(command_handle: {})", command_handle);
+
+    spawn(move || {
+        let state = mediation::get_state();
+        trace!("vcx_mediation_get_state_cb(command_handle: {}, rc: {}, state: {})",
+               command_handle, error::SUCCESS.message, state);
+        cb(command_handle, error::SUCCESS.code_num, state);
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Query the mediator for the number of messages waiting to be picked up (Pickup protocol).
+/// Messages of connections created while mediation is granted are picked up by `vcx_connection_update_state`
+/// and other functions checking for new messages.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides number of queued messages and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_get_message_count(command_handle: CommandHandle,
+                                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, count: u32)>) -> u32 {
+    info!("vcx_mediation_get_message_count >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_mediation_get_message_count(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match mediation::get_message_count(None) {
+            Ok(count) => {
+                trace!("vcx_mediation_get_message_count_cb(command_handle: {}, rc: {}, count: {})",
+                       command_handle, error::SUCCESS.message, count);
+                cb(command_handle, error::SUCCESS.code_num, count);
+            }
+            Err(e) => {
+                warn!("vcx_mediation_get_message_count_cb(command_handle: {}, rc: {}, count: {})",
+                      command_handle, e, 0);
+                cb(command_handle, e.into(), 0);
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Serialize mediation to be restored with `vcx_mediation_deserialize` after restart.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides json string of mediation state
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_serialize(command_handle: CommandHandle,
+                                      cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, data: *const c_char)>) -> u32 {
+    info!("vcx_mediation_serialize >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_mediation_serialize(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match mediation::to_string() {
+            Ok(data) => {
+                trace!("vcx_mediation_serialize_cb(command_handle: {}, rc: {}, data: {})",
+                       command_handle, error::SUCCESS.message, data);
+                let data = CStringUtils::string_to_cstring(data);
+                cb(command_handle, error::SUCCESS.code_num, data.as_ptr());
+            }
+            Err(e) => {
+                warn!("vcx_mediation_serialize_cb(command_handle: {}, rc: {}, data: {})",
+                      command_handle, e, "null");
+                cb(command_handle, e.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Restore mediation serialized with `vcx_mediation_serialize`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// data: json string of mediation state
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_deserialize(command_handle: CommandHandle,
+                                        data: *const c_char,
+                                        cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_mediation_deserialize >>>");
+
+    check_useful_c_str!(data, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_mediation_deserialize(command_handle: {}, data: {})", command_handle, data);
+
+    spawn(move || {
+        match mediation::from_string(&data) {
+            Ok(()) => {
+                trace!("vcx_mediation_deserialize_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_mediation_deserialize_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Forget the mediator. New connections are routed through the agency again,
+/// connections created while mediation was granted keep using the mediator.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_mediation_reset() -> u32 {
+    info!("vcx_mediation_reset >>>");
+
+    mediation::reset();
+
+    error::SUCCESS.code_num
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+    use utils::devsetup::*;
+    use utils::timeout::TimeoutUtils;
+    use api::return_types_u32;
+    use v3::handlers::mediation::states::tests::_mediation_sm;
+
+    #[test]
+    fn test_vcx_mediation_serialize_deserialize() {
+        let _setup = SetupAriesMocks::init();
+
+        let data = ::serde_json::to_string(&_mediation_sm()).unwrap();
+
+        let cb = return_types_u32::Return_U32::new().unwrap();
+        assert_eq!(vcx_mediation_deserialize(cb.command_handle,
+                                             CString::new(data).unwrap().into_raw(),
+                                             Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        cb.receive(TimeoutUtils::some_medium()).unwrap();
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_mediation_get_state(cb.command_handle, Some(cb.get_callback())), error::SUCCESS.code_num);
+        assert_eq!(::api::VcxStateType::VcxStateOfferSent as u32, cb.receive(TimeoutUtils::some_medium()).unwrap());
+
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_mediation_serialize(cb.command_handle, Some(cb.get_callback())), error::SUCCESS.code_num);
+        assert!(cb.receive(TimeoutUtils::some_medium()).unwrap().is_some());
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_mediation_get_message_count(cb.command_handle, Some(cb.get_callback())), error::SUCCESS.code_num);
+        assert!(cb.receive(TimeoutUtils::some_medium()).is_err());
+
+        assert_eq!(vcx_mediation_reset(), error::SUCCESS.code_num);
+
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_mediation_serialize(cb.command_handle, Some(cb.get_callback())), error::SUCCESS.code_num);
+        assert_eq!(cb.receive(TimeoutUtils::some_medium()).unwrap_err(), error::NOT_READY.code_num);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod vcx;
+pub mod context;
 pub mod connection;
 pub mod issuer_credential;
 pub mod utils;
@@ -9,6 +10,10 @@ pub mod credential;
 pub mod disclosed_proof;
 pub mod wallet;
 pub mod logger;
+pub mod revocation_publisher;
+pub mod outbound_queue;
+pub mod mediation;
+pub mod events;
 pub mod return_types_u32;
 
 use std::fmt;
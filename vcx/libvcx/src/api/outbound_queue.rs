@@ -0,0 +1,206 @@
+use libc::c_char;
+use utils::cstring::CStringUtils;
+use utils::error;
+use std::ptr;
+use outbound_queue;
+use outbound_queue::MessageState;
+use utils::threadpool::spawn;
+use error::prelude::*;
+use indy_sys::CommandHandle;
+
+/// Start outbound message queue.
+/// While it is started messages sent to HTTP endpoints are stored in the wallet before the delivery.
+/// Failed deliveries are retried with exponential backoff, messages which couldn't be delivered
+/// within `max_attempts` are moved to dead-letter state.
+///
+/// #Params
+/// config: queue configuration
+///     {
+///         "retry_interval": Optional<int>, // seconds between checks for messages due for retry (10 by default)
+///         "initial_delay": Optional<int>, // seconds before the first retry, doubled with every failed attempt (5 by default)
+///         "max_delay": Optional<int>, // the longest delay between retries in seconds (3600 by default)
+///         "max_attempts": Optional<int>, // failed deliveries after which the message is moved to dead-letter state (10 by default)
+///     }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_outbound_queue_start(config: *const c_char) -> u32 {
+    info!("vcx_outbound_queue_start >>>");
+
+    check_useful_c_str!(config, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_outbound_queue_start(config: {})", config);
+
+    match outbound_queue::start(&config) {
+        Ok(()) => error::SUCCESS.code_num,
+        Err(e) => {
+            warn!("vcx_outbound_queue_start(rc: {})", e);
+            e.into()
+        }
+    }
+}
+
+/// Stop outbound message queue. Messages are sent directly again.
+/// Stored messages are kept and retried once the queue is started again.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_outbound_queue_stop() -> u32 {
+    info!("vcx_outbound_queue_stop >>>");
+
+    match outbound_queue::stop() {
+        Ok(()) => error::SUCCESS.code_num,
+        Err(e) => {
+            warn!("vcx_outbound_queue_stop(rc: {})", e);
+            e.into()
+        }
+    }
+}
+
+/// Get messages stored in outbound message queue.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// state: (optional) state of messages to return: "pending" or "dead_letter". All messages are returned if not set.
+///
+/// cb: Callback that provides JSON array of messages
+///     [{
+///         "id": string,
+///         "endpoint": string,
+///         "message": string, // packed message, base64 encoded
+///         "state": "pending" | "dead_letter",
+///         "attempts": int, // failed deliveries
+///         "created": int, // timestamp
+///         "next_attempt": int, // timestamp of the next retry of pending message
+///         "last_error": Optional<string>,
+///     }]
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_outbound_queue_get_messages(command_handle: CommandHandle,
+                                              state: *const c_char,
+                                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, messages: *const c_char)>) -> u32 {
+    info!("vcx_outbound_queue_get_messages >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_opt_c_str!(state, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_outbound_queue_get_messages(command_handle: {}, state: {:?})", command_handle, state);
+
+    let state: Option<MessageState> = match state {
+        Some(state) => match ::serde_json::from_value(json!(state)) {
+            Ok(state) => Some(state),
+            Err(err) => return VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Invalid outbound message state: {:?}", err)).into()
+        },
+        None => None
+    };
+
+    spawn(move || {
+        match outbound_queue::get_messages(state) {
+            Ok(messages) => {
+                trace!("vcx_outbound_queue_get_messages_cb(command_handle: {}, rc: {}, messages: {})",
+                       command_handle, error::SUCCESS.message, messages);
+                let messages = CStringUtils::string_to_cstring(messages);
+                cb(command_handle, error::SUCCESS.code_num, messages.as_ptr());
+            }
+            Err(e) => {
+                warn!("vcx_outbound_queue_get_messages_cb(command_handle: {}, rc: {}, messages: {})",
+                      command_handle, e, "null");
+                cb(command_handle, e.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Move the message back to pending state and try to deliver it right away.
+/// Fails if the message is being delivered right now.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// message_id: id of the message in outbound message queue
+///
+/// cb: Callback that provides success or failure of the delivery
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_outbound_queue_retry(command_handle: CommandHandle,
+                                       message_id: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_outbound_queue_retry >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message_id, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_outbound_queue_retry(command_handle: {}, message_id: {})", command_handle, message_id);
+
+    spawn(move || {
+        match outbound_queue::retry(&message_id) {
+            Ok(()) => {
+                trace!("vcx_outbound_queue_retry_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_outbound_queue_retry_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Delete the message from outbound message queue.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// message_id: id of the message in outbound message queue
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_outbound_queue_delete(command_handle: CommandHandle,
+                                        message_id: *const c_char,
+                                        cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_outbound_queue_delete >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message_id, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_outbound_queue_delete(command_handle: {}, message_id: {})", command_handle, message_id);
+
+    spawn(move || {
+        match outbound_queue::delete(&message_id) {
+            Ok(()) => {
+                trace!("vcx_outbound_queue_delete_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_outbound_queue_delete_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
@@ -2,6 +2,7 @@ use libc::c_char;
 use utils::cstring::CStringUtils;
 use utils::error;
 use proof;
+use proof_template;
 use connection;
 use std::ptr;
 use utils::threadpool::spawn;
@@ -94,8 +95,10 @@ use indy_sys::CommandHandle;
 /// requested_predicates: predicate specifications prover must provide claim for
 ///          { // set of requested predicates
 ///             "name": attribute name, (case insensitive and ignore spaces)
-///             "p_type": predicate type (Currently ">=" only)
-///             "p_value": int predicate value
+///             "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///             "p_value": int predicate value (for all predicate types except "RANGE")
+///             "from": int inclusive lower bound (for "RANGE" only)
+///             "to": int inclusive upper bound (for "RANGE" only)
 ///             "restrictions":  Optional<wql query> -  set of restrictions applying to requested credentials. (see below)
 ///             "non_revoked": Optional<{
 ///                 "from": Optional<(u64)> Requested time represented as a total number of seconds from Unix Epoch, Optional
@@ -112,7 +115,10 @@ use indy_sys::CommandHandle;
 ///     from: Optional<u64> // timestamp of interval beginning
 ///     to: Optional<u64> // timestamp of interval beginning
 ///         // Requested time represented as a total number of seconds from Unix Epoch, Optional
-/// # Examples config ->  "{}" | "{"to": 123} | "{"from": 100, "to": 123}"
+///     accept_non_revocable: Optional<[string]> // referents for which credentials without revocation support
+///         // are accepted despite requested interval (Aries protocol only).
+///         // Prover signals such credentials in Presentation Proposal, the request is re-sent then.
+/// # Examples config ->  "{}" | "{"to": 123} | "{"from": 100, "to": 123}" | "{"to": 123, "accept_non_revocable": ["attribute_0"]}"
 ///
 /// wql query: indy-sdk/docs/design/011-wallet-query-language/README.md
 ///     The list of allowed keys that can be combine into complex queries.
@@ -172,6 +178,205 @@ pub extern fn vcx_proof_create(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Store a proof request template in the wallet (replaces the template stored with the same id).
+/// The template is a skeleton of proof request which is instantiated by `vcx_proof_create_from_template`
+/// with a fresh nonce and relative times resolved to timestamps.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// template_id: identifier of the template
+///
+/// template: proof request template
+///     {
+///         "name": string, // name of proof request
+///         "requested_attributes": [<requested_attr>], // see `requested_attrs` of `vcx_proof_create`
+///         "requested_predicates": [<requested_predicate>], // see `requested_predicates` of `vcx_proof_create`
+///         "non_revoked": Optional<{
+///             "from": Optional<time>,
+///             "to": Optional<time>,
+///         }>,
+///         "accept_non_revocable": Optional<[string]>, // see `revocation_interval` of `vcx_proof_create`
+///     }
+///     "non_revoked" intervals of requested attributes and predicates accept the same times.
+///     time: either timestamp or string relative to the moment the template is instantiated:
+///         "now", "now-<seconds>", "now+<seconds>"
+///
+/// # Example template -> "{"name":"Employment","requested_attributes":[{"name":"employer"}],"non_revoked":{"from":"now-86400","to":"now"}}"
+///
+/// cb: Callback that provides error status of request.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_proof_template_store(command_handle: CommandHandle,
+                                       template_id: *const c_char,
+                                       template: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_proof_template_store >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(template_id, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(template, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_proof_template_store(command_handle: {}, template_id: {}, template: {})",
+           command_handle, template_id, template);
+
+    spawn(move || {
+        match proof_template::store(&template_id, &template) {
+            Ok(()) => {
+                trace!("vcx_proof_template_store_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(x) => {
+                warn!("vcx_proof_template_store_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Get a proof request template stored in the wallet
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// template_id: identifier of the template
+///
+/// cb: Callback that provides the template (see `vcx_proof_template_store`) and error status of request.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_proof_template_get(command_handle: CommandHandle,
+                                     template_id: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, template: *const c_char)>) -> u32 {
+    info!("vcx_proof_template_get >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(template_id, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_proof_template_get(command_handle: {}, template_id: {})",
+           command_handle, template_id);
+
+    spawn(move || {
+        match proof_template::get(&template_id) {
+            Ok(template) => {
+                trace!("vcx_proof_template_get_cb(command_handle: {}, rc: {}, template: {})",
+                       command_handle, error::SUCCESS.message, template);
+                let template = CStringUtils::string_to_cstring(template);
+                cb(command_handle, error::SUCCESS.code_num, template.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_proof_template_get_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Delete a proof request template from the wallet
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// template_id: identifier of the template
+///
+/// cb: Callback that provides error status of request.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_proof_template_delete(command_handle: CommandHandle,
+                                        template_id: *const c_char,
+                                        cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_proof_template_delete >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(template_id, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_proof_template_delete(command_handle: {}, template_id: {})",
+           command_handle, template_id);
+
+    spawn(move || {
+        match proof_template::delete(&template_id) {
+            Ok(()) => {
+                trace!("vcx_proof_template_delete_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(x) => {
+                warn!("vcx_proof_template_delete_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Create a new Proof object from a proof request template stored in the wallet.
+/// The proof gets a fresh nonce and relative times of the template resolved to timestamps of the moment.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// source_id: Enterprise's personal identification for the user.
+///
+/// template_id: identifier of the template (see `vcx_proof_template_store`)
+///
+/// cb: Callback that provides proof handle and error status of request.
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_proof_create_from_template(command_handle: CommandHandle,
+                                             source_id: *const c_char,
+                                             template_id: *const c_char,
+                                             cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, proof_handle: u32)>) -> u32 {
+    info!("vcx_proof_create_from_template >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(source_id, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(template_id, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_proof_create_from_template(command_handle: {}, source_id: {}, template_id: {})",
+           command_handle, source_id, template_id);
+
+    spawn(move || {
+        match proof_template::create_proof(source_id, &template_id) {
+            Ok(handle) => {
+                trace!("vcx_proof_create_from_template_cb(command_handle: {}, rc: {}, handle: {})",
+                       command_handle, error::SUCCESS.message, handle);
+                cb(command_handle, error::SUCCESS.code_num, handle);
+            }
+            Err(x) => {
+                warn!("vcx_proof_create_from_template_cb(command_handle: {}, rc: {}, handle: {})",
+                      command_handle, x, 0);
+                cb(command_handle, x.into(), 0);
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Query the agency for the received messages.
 /// Checks for any messages changing state in the object and updates the state attribute.
 ///
@@ -281,6 +486,68 @@ pub extern fn vcx_proof_update_state_with_message(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Update the state of the proof based on the message already downloaded from the agency
+/// (for example, delivered within push notification payload).
+/// Agency is not queried for new messages, so state can be advanced within background time budget of mobile platforms.
+/// Note that replies required by the protocol are still sent.
+/// Only Aries proofs are supported.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// proof_handle: Proof handle that was provided during creation. Used to identify proof object
+///
+/// connection_handle: handle of the connection the message was received on
+///
+/// message: agency message json as returned by `vcx_messages_download`
+///
+/// cb: Callback that provides most current state of the proof and error status of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_proof_update_state_with_agency_message(command_handle: CommandHandle,
+                                                         proof_handle: u32,
+                                                         connection_handle: u32,
+                                                         message: *const c_char,
+                                                         cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, state: u32)>) -> u32 {
+    info!("vcx_proof_update_state_with_agency_message >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(message, VcxErrorKind::InvalidOption);
+
+    if !proof::is_valid_handle(proof_handle) {
+        return VcxError::from(VcxErrorKind::InvalidProofHandle).into();
+    }
+
+    if !connection::is_valid_handle(connection_handle) {
+        return VcxError::from(VcxErrorKind::InvalidConnectionHandle).into();
+    }
+
+    let source_id = proof::get_source_id(proof_handle).unwrap_or_default();
+    trace!("vcx_proof_update_state_with_agency_message(command_handle: {}, proof_handle: {}, connection_handle: {}), source_id: {:?}",
+           command_handle, proof_handle, connection_handle, source_id);
+
+    spawn(move || {
+        match proof::update_state_with_agency_message(proof_handle, connection_handle, &message) {
+            Ok(state) => {
+                trace!("vcx_proof_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, error::SUCCESS.message, state, source_id);
+                cb(command_handle, error::SUCCESS.code_num, state)
+            }
+            Err(e) => {
+                error!("vcx_proof_update_state_with_agency_message_cb(command_handle: {}, rc: {}, state: {}), source_id: {:?}",
+                       command_handle, e, 0, source_id);
+                cb(command_handle, e.into(), 0)
+            }
+        }
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Get the current state of the proof object
 ///
 /// #Params
@@ -658,6 +925,65 @@ pub extern fn vcx_proof_accepted(proof_handle: u32, response_data: *const c_char
 }
 
 
+/// Get Problem Report received from the Prover or sent on failure of the proof verification.
+/// Problem Report describes the reason the protocol was abandoned: `description.code` contains the error code,
+/// `explain` (or `comment`) contains a human-readable explanation.
+///
+/// Note that this function is useful in case `aries` communication method is used.
+/// In other cases it returns error.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// proof_handle: Proof handle that was provided during creation. Used to identify proof object
+///
+/// cb: Callback that provides Problem Report json or NotReady error if there is no Problem Report
+///
+/// # Example
+/// problem_report ->
+///      {
+///         "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/notification/1.0/problem-report",
+///         "@id": "7c4c4eb4-1f5e-4a1e-a7b3-3b8c9f4a2d11",
+///         "description": {"code": "request-not-accepted"},
+///         "impact": "thread",
+///         "explain": "Credential Request is rejected",
+///         "~thread": {"thid": "a1f3e5a9-ff8e-4d25-9d5b-7a8e9f8b3c0d"}
+///      }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_proof_get_problem_report(command_handle: CommandHandle,
+                                           proof_handle: u32,
+                                           cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, problem_report: *const c_char)>) -> u32 {
+    info!("vcx_proof_get_problem_report >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_proof_get_problem_report(command_handle: {}, proof_handle: {})", command_handle, proof_handle);
+
+    spawn(move || {
+        match proof::get_problem_report(proof_handle) {
+            Ok(problem_report) => {
+                trace!("vcx_proof_get_problem_report_cb(command_handle: {}, rc: {}, problem_report: {})",
+                       command_handle, error::SUCCESS.message, problem_report);
+                let problem_report = CStringUtils::string_to_cstring(problem_report);
+                cb(command_handle, error::SUCCESS.code_num, problem_report.as_ptr());
+            }
+            Err(x) => {
+                warn!("vcx_proof_get_problem_report_cb(command_handle: {}, rc: {})",
+                      command_handle, x);
+                cb(command_handle, x.into(), ptr::null());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -854,4 +1180,33 @@ mod tests {
         let state = cb.receive(TimeoutUtils::some_short()).unwrap();
         assert_eq!(state, VcxStateType::VcxStateOfferSent as u32);
     }
+
+    #[test]
+    fn test_vcx_proof_create_from_template() {
+        let _setup = SetupLibraryWallet::init();
+
+        let template_id = CString::new("employment").unwrap().into_raw();
+        let template = CString::new(r#"{"name":"Employment","requested_attributes":[{"name":"employer"}],"non_revoked":{"from":"now-86400","to":"now"}}"#).unwrap().into_raw();
+
+        let cb = return_types_u32::Return_U32::new().unwrap();
+        assert_eq!(vcx_proof_template_store(cb.command_handle, template_id, template, Some(cb.get_callback())), error::SUCCESS.code_num);
+        cb.receive(TimeoutUtils::some_medium()).unwrap();
+
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_proof_create_from_template(cb.command_handle,
+                                                  CString::new("test_create_from_template").unwrap().into_raw(),
+                                                  template_id,
+                                                  Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        let handle = cb.receive(TimeoutUtils::some_medium()).unwrap();
+        assert_eq!(proof::get_source_id(handle).unwrap(), "test_create_from_template");
+
+        let cb = return_types_u32::Return_U32::new().unwrap();
+        assert_eq!(vcx_proof_template_delete(cb.command_handle, template_id, Some(cb.get_callback())), error::SUCCESS.code_num);
+        cb.receive(TimeoutUtils::some_medium()).unwrap();
+
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_proof_template_get(cb.command_handle, template_id, Some(cb.get_callback())), error::SUCCESS.code_num);
+        assert_eq!(cb.receive(TimeoutUtils::some_medium()).err(), Some(error::WALLET_RECORD_NOT_FOUND.code_num));
+    }
 }
@@ -0,0 +1,166 @@
+use libc::c_char;
+use utils::cstring::CStringUtils;
+use utils::error;
+use std::ptr;
+use revocation_publisher;
+use utils::threadpool::spawn;
+use error::prelude::*;
+use indy_sys::CommandHandle;
+
+/// Start revocation publisher service.
+/// While it is started revoked credentials are updated only in the wallet and corresponding deltas are merged
+/// and published on the ledger in batches. Deltas are kept in the wallet until they are published,
+/// deltas left after a failed publication or stop are published once the service is started again. Tails files of newly created revocation registries are uploaded
+/// to the tails server if it is configured.
+///
+/// #Params
+/// config: publisher configuration
+///     {
+///         "publish_interval": Optional<int>, // seconds between publications of pending deltas (60 by default)
+///         "max_batch_size": Optional<int>, // publish as soon as this number of revocations is pending for a registry
+///         "tails_upload_url": Optional<string>, // tails files are uploaded with PUT to <tails_upload_url>/<rev_reg_id>
+///     }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_revocation_publisher_start(config: *const c_char) -> u32 {
+    info!("vcx_revocation_publisher_start >>>");
+
+    check_useful_c_str!(config, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_revocation_publisher_start(config: {})", config);
+
+    match revocation_publisher::start(&config) {
+        Ok(()) => error::SUCCESS.code_num,
+        Err(e) => {
+            warn!("vcx_revocation_publisher_start(rc: {})", e);
+            e.into()
+        }
+    }
+}
+
+/// Publish all pending deltas and stop revocation publisher service.
+/// Deltas which couldn't be published stay in the wallet until the service is started again.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_revocation_publisher_stop(command_handle: CommandHandle,
+                                            cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_revocation_publisher_stop >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_revocation_publisher_stop(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match revocation_publisher::stop() {
+            Ok(()) => {
+                trace!("vcx_revocation_publisher_stop_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_revocation_publisher_stop_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Publish pending deltas of all revocation registries right now.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides success or failure of request
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_revocation_publisher_publish(command_handle: CommandHandle,
+                                               cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32)>) -> u32 {
+    info!("vcx_revocation_publisher_publish >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_revocation_publisher_publish(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match revocation_publisher::publish() {
+            Ok(()) => {
+                trace!("vcx_revocation_publisher_publish_cb(command_handle: {}, rc: {})",
+                       command_handle, error::SUCCESS.message);
+                cb(command_handle, error::SUCCESS.code_num);
+            }
+            Err(e) => {
+                warn!("vcx_revocation_publisher_publish_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Get status of revocation registries handled by revocation publisher service.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides status json
+///     {
+///         "<rev_reg_id>": {
+///             "pending_revocations": int, // revocations waiting for publication
+///             "published_revocations": int, // revocations published since start
+///             "last_published": Optional<int>, // timestamp of last publication
+///             "tails_uploaded": bool,
+///             "last_error": Optional<string>, // last publication or tails upload error
+///         }
+///     }
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_revocation_publisher_get_status(command_handle: CommandHandle,
+                                                  cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, status: *const c_char)>) -> u32 {
+    info!("vcx_revocation_publisher_get_status >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_revocation_publisher_get_status(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match revocation_publisher::get_status() {
+            Ok(status) => {
+                trace!("vcx_revocation_publisher_get_status_cb(command_handle: {}, rc: {}, status: {})",
+                       command_handle, error::SUCCESS.message, status);
+                let status = CStringUtils::string_to_cstring(status);
+                cb(command_handle, error::SUCCESS.code_num, status.as_ptr());
+            }
+            Err(e) => {
+                warn!("vcx_revocation_publisher_get_status_cb(command_handle: {}, rc: {}, status: {})",
+                      command_handle, e, "null");
+                cb(command_handle, e.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
@@ -6,11 +6,11 @@ use utils::cstring::CStringUtils;
 use utils::error;
 use utils::threadpool::spawn;
 use utils::libindy::payments;
-use std::thread;
 use error::prelude::*;
 use indy_sys::CommandHandle;
 use utils::httpclient::AgencyMock;
 use utils::constants::*;
+use utils::serialization::ObjectType;
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct UpdateAgentInfo {
@@ -23,6 +23,10 @@ pub struct UpdateAgentInfo {
 ///
 /// #Params
 /// config: configuration
+///     "cloud_agent": Optional<string> - cloud agent messages are received through:
+///         "evernym" (default) - Evernym agency at "agency_url",
+///         "direct" - no agency, "agency_url" is the endpoint hosted by the application,
+///         or the name of the implementation registered at runtime
 ///
 /// #Returns
 /// Configuration (wallet also populated), on error returns NULL
@@ -55,13 +59,68 @@ pub extern fn vcx_provision_agent(config: *const c_char) -> *mut c_char {
     }
 }
 
+/// Provision an agent in the agency admitting only agents sponsored by the issuer of the token,
+/// populate configuration and wallet for this agent.
+///
+/// #Params
+/// config: configuration (see `vcx_provision_agent`)
+///
+/// token: provisioning token signed by the sponsor (valid for 15 minutes since it is issued)
+///     {
+///         "sponseeId": string - id of the sponsee assigned by the sponsor,
+///         "sponsorId": string - id of the sponsor registered in the agency,
+///         "nonce": string,
+///         "timestamp": string - RFC 3339 time the token is issued at,
+///         "sig": string - base64 encoded sponsor signature of nonce + timestamp + sponseeId + sponsorId,
+///         "sponsorVk": string - verkey of the sponsor
+///     }
+///
+/// #Returns
+/// Configuration (wallet also populated), on error returns NULL
+#[no_mangle]
+pub extern fn vcx_provision_agent_with_token(config: *const c_char, token: *const c_char) -> *mut c_char {
+    info!("vcx_provision_agent_with_token >>>");
+
+    let config = match CStringUtils::c_str_to_string(config) {
+        Ok(Some(val)) => val,
+        _ => {
+            let _res: u32 = VcxError::from_msg(VcxErrorKind::InvalidOption, "Invalid pointer has been passed").into();
+            return ptr::null_mut();
+        }
+    };
+
+    let token = match CStringUtils::c_str_to_string(token) {
+        Ok(Some(val)) => val,
+        _ => {
+            let _res: u32 = VcxError::from_msg(VcxErrorKind::InvalidOption, "Invalid pointer has been passed").into();
+            return ptr::null_mut();
+        }
+    };
+
+    trace!("vcx_provision_agent_with_token(config: {}, token: {})", config, token);
+
+    match messages::agent_utils::connect_register_provision_with_token(&config, &token) {
+        Err(e) => {
+            error!("Provision Agent With Token Error {}.", e);
+            let _res: u32 = e.into();
+            ptr::null_mut()
+        }
+        Ok(s) => {
+            debug!("Provision Agent With Token Successful");
+            let msg = CStringUtils::string_to_cstring(s);
+
+            msg.into_raw()
+        }
+    }
+}
+
 /// Provision an agent in the agency, populate configuration and wallet for this agent.
 /// NOTE: for synchronous call use vcx_provision_agent
 ///
 /// #Params
 /// command_handle: command handle to map callback to user context.
 ///
-/// config: configuration
+/// config: configuration (see `vcx_provision_agent`)
 ///
 /// cb: Callback that provides configuration or error status
 ///
@@ -79,7 +138,7 @@ pub extern fn vcx_agent_provision_async(command_handle: CommandHandle,
     trace!("vcx_agent_provision_async(command_handle: {}, json: {})",
            command_handle, config);
 
-    thread::spawn(move || {
+    ::context::spawn_thread(move || {
         match messages::agent_utils::connect_register_provision(&config) {
             Err(e) => {
                 error!("vcx_agent_provision_async_cb(command_handle: {}, rc: {}, config: NULL", command_handle, e);
@@ -395,6 +454,247 @@ pub extern fn vcx_messages_download(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Retrieve messages from the agent matching the filter
+///
+/// Works as `vcx_messages_download` but returns only messages matching the filter.
+/// Filter is sent to the agency and is also applied right after the message is decrypted,
+/// so not matching messages are discarded before parsing.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// message_status: optional, comma separated - query for messages with the specified status (see `vcx_messages_download`).
+///
+/// uids: optional, comma separated - query for messages with the specified uids
+///
+/// pw_dids: optional, comma separated - DID's pointing to specific connection
+///
+/// filter_json: optional, filter of messages
+///     {
+///         "protocolFamilies": Optional<[string]> - Aries protocol families, like "present-proof" or "issue-credential",
+///         "threadId": Optional<string> - thread the message belongs to,
+///         "since": Optional<int> - unix timestamp of the oldest message (applied by the agency only),
+///         "until": Optional<int> - unix timestamp of the newest message (applied by the agency only),
+///         "msgTypes": Optional<[string]> - types of messages as stored by the agency, like "credOffer" or "aries"
+///     }
+///     Legacy (non Aries) messages never match `protocolFamilies` and `threadId` filters.
+///
+/// cb: Callback that provides array of matching messages retrieved
+///
+/// # Example filter_json -> {"protocolFamilies": ["present-proof"], "threadId": "b5fd6b0a-d8b1-4d50-8b27-49c0a5c1f0a9"}
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_messages_download_with_filter(command_handle: CommandHandle,
+                                                message_status: *const c_char,
+                                                uids: *const c_char,
+                                                pw_dids: *const c_char,
+                                                filter_json: *const c_char,
+                                                cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, messages: *const c_char)>) -> u32 {
+    info!("vcx_messages_download_with_filter >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    let message_status = if !message_status.is_null() {
+        check_useful_c_str!(message_status, VcxErrorKind::InvalidOption);
+        let v: Vec<&str> = message_status.split(',').collect();
+        let v = v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        Some(v.to_owned())
+    } else {
+        None
+    };
+
+    let uids = if !uids.is_null() {
+        check_useful_c_str!(uids, VcxErrorKind::InvalidOption);
+        let v: Vec<&str> = uids.split(',').collect();
+        let v = v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        Some(v.to_owned())
+    } else {
+        None
+    };
+
+    let pw_dids = if !pw_dids.is_null() {
+        check_useful_c_str!(pw_dids, VcxErrorKind::InvalidOption);
+        let v: Vec<&str> = pw_dids.split(',').collect();
+        let v = v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        Some(v.to_owned())
+    } else {
+        None
+    };
+
+    let filter = if !filter_json.is_null() {
+        check_useful_c_str!(filter_json, VcxErrorKind::InvalidOption);
+        match serde_json::from_str::<::messages::get_message::MessageFilter>(&filter_json) {
+            Ok(filter) => filter,
+            Err(err) => return VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot parse message filter: {}", err)).into()
+        }
+    } else {
+        ::messages::get_message::MessageFilter::default()
+    };
+
+    trace!("vcx_messages_download_with_filter(command_handle: {}, message_status: {:?}, uids: {:?}, filter: {:?})",
+           command_handle, message_status, uids, filter);
+
+    spawn(move || {
+        match ::messages::get_message::download_messages_filtered(pw_dids, message_status, uids, filter) {
+            Ok(x) => {
+                match serde_json::to_string(&x) {
+                    Ok(x) => {
+                        trace!("vcx_messages_download_with_filter_cb(command_handle: {}, rc: {}, messages: {})",
+                               command_handle, error::SUCCESS.message, x);
+
+                        let msg = CStringUtils::string_to_cstring(x);
+                        cb(command_handle, error::SUCCESS.code_num, msg.as_ptr());
+                    }
+                    Err(e) => {
+                        let err = VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize messages: {}", e));
+                        warn!("vcx_messages_download_with_filter_cb(command_handle: {}, rc: {}, messages: {})",
+                              command_handle, err, "null");
+
+                        cb(command_handle, err.into(), ptr::null_mut());
+                    }
+                };
+            }
+            Err(e) => {
+                warn!("vcx_messages_download_with_filter_cb(command_handle: {}, rc: {}, messages: {})",
+                      command_handle, e, "null");
+
+                cb(command_handle, e.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// Retrieve one page of messages from the agent matching the filter
+///
+/// Works as `vcx_messages_download_with_filter` but lets the agency split matching messages into pages,
+/// so agents with large backlogs do not need to download all messages at once.
+///
+/// #params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// message_status: optional, comma separated - query for messages with the specified status (see `vcx_messages_download`).
+///
+/// uids: optional, comma separated - query for messages with the specified uids
+///
+/// pw_dids: optional, comma separated - DID's pointing to specific connection
+///
+/// filter_json: optional, filter of messages (see `vcx_messages_download_with_filter`)
+///
+/// page_size: maximal number of messages in the page, 0 to use the agency default
+///
+/// page_token: optional - token of the page returned by the previous call, null to get the first page
+///
+/// cb: Callback that provides array of matching messages retrieved and the token of the next page
+///     (null if there are no more messages)
+///
+/// # Example filter_json -> {"msgTypes": ["credOffer"], "since": 1579000000, "until": 1579086400}
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_messages_download_page(command_handle: CommandHandle,
+                                         message_status: *const c_char,
+                                         uids: *const c_char,
+                                         pw_dids: *const c_char,
+                                         filter_json: *const c_char,
+                                         page_size: u32,
+                                         page_token: *const c_char,
+                                         cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, messages: *const c_char, next_page_token: *const c_char)>) -> u32 {
+    info!("vcx_messages_download_page >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    let message_status = if !message_status.is_null() {
+        check_useful_c_str!(message_status, VcxErrorKind::InvalidOption);
+        let v: Vec<&str> = message_status.split(',').collect();
+        let v = v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        Some(v.to_owned())
+    } else {
+        None
+    };
+
+    let uids = if !uids.is_null() {
+        check_useful_c_str!(uids, VcxErrorKind::InvalidOption);
+        let v: Vec<&str> = uids.split(',').collect();
+        let v = v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        Some(v.to_owned())
+    } else {
+        None
+    };
+
+    let pw_dids = if !pw_dids.is_null() {
+        check_useful_c_str!(pw_dids, VcxErrorKind::InvalidOption);
+        let v: Vec<&str> = pw_dids.split(',').collect();
+        let v = v.iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        Some(v.to_owned())
+    } else {
+        None
+    };
+
+    let filter = if !filter_json.is_null() {
+        check_useful_c_str!(filter_json, VcxErrorKind::InvalidOption);
+        match serde_json::from_str::<::messages::get_message::MessageFilter>(&filter_json) {
+            Ok(filter) => filter,
+            Err(err) => return VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot parse message filter: {}", err)).into()
+        }
+    } else {
+        ::messages::get_message::MessageFilter::default()
+    };
+
+    check_useful_opt_c_str!(page_token, VcxErrorKind::InvalidOption);
+
+    let page = ::messages::get_message::MessagePage {
+        page_size: if page_size == 0 { None } else { Some(page_size) },
+        page_token,
+    };
+
+    trace!("vcx_messages_download_page(command_handle: {}, message_status: {:?}, uids: {:?}, filter: {:?}, page: {:?})",
+           command_handle, message_status, uids, filter, page);
+
+    spawn(move || {
+        match ::messages::get_message::download_messages_page(pw_dids, message_status, uids, filter, page) {
+            Ok((x, next_page_token)) => {
+                match serde_json::to_string(&x) {
+                    Ok(x) => {
+                        trace!("vcx_messages_download_page_cb(command_handle: {}, rc: {}, messages: {}, next_page_token: {:?})",
+                               command_handle, error::SUCCESS.message, x, next_page_token);
+
+                        let msg = CStringUtils::string_to_cstring(x);
+                        let next_page_token = next_page_token.map(CStringUtils::string_to_cstring);
+                        cb(command_handle, error::SUCCESS.code_num, msg.as_ptr(),
+                           next_page_token.as_ref().map(|token| token.as_ptr()).unwrap_or(ptr::null()));
+                    }
+                    Err(e) => {
+                        let err = VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize messages: {}", e));
+                        warn!("vcx_messages_download_page_cb(command_handle: {}, rc: {}, messages: {})",
+                              command_handle, err, "null");
+
+                        cb(command_handle, err.into(), ptr::null_mut(), ptr::null_mut());
+                    }
+                };
+            }
+            Err(e) => {
+                warn!("vcx_messages_download_page_cb(command_handle: {}, rc: {}, messages: {})",
+                      command_handle, e, "null");
+
+                cb(command_handle, e.into(), ptr::null_mut(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Update the status of messages from the specified connection
 ///
 /// #params
@@ -459,13 +759,16 @@ pub extern fn vcx_messages_update_status(command_handle: CommandHandle,
 /// handle: pool handle that libvcx should use
 ///
 /// #Returns
-/// Error code as u32
+/// The handle, or 0 if the context bound to the calling thread has been released
 #[no_mangle]
 pub extern fn vcx_pool_set_handle(handle: i32) -> i32 {
-    if handle <= 0 { ::utils::libindy::pool::set_pool_handle(None); }
-    else { ::utils::libindy::pool::set_pool_handle(Some(handle)); }
+    let res = if handle <= 0 { ::utils::libindy::pool::set_pool_handle(None) }
+    else { ::utils::libindy::pool::set_pool_handle(Some(handle)) };
 
-    handle
+    match res {
+        Ok(()) => handle,
+        Err(_) => 0
+    }
 }
 
 /// Gets minimal request price for performing an action in case the requester can perform this action.
@@ -563,6 +866,97 @@ pub extern fn vcx_endorse_transaction(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Get information about an object (connection, credential, proof, ...) by its handle
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// handle: handle of the object
+///
+/// cb: Callback that provides the information about the object
+///
+/// # Example info -> "{"handle":1,"type":"proof","source_id":"1","state":1,"thread_id":"5a0a3c3b-0d30-4b1f-a3b6-3bd1c6b5e5f2"}"
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_object_info(command_handle: CommandHandle,
+                              handle: u32,
+                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, info: *const c_char)>) -> u32 {
+    info!("vcx_object_info >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    trace!("vcx_object_info(command_handle: {}, handle: {})",
+           command_handle, handle);
+
+    spawn(move || {
+        match ::object_cache::info::get(handle) {
+            Ok(info) => {
+                let info = json!(info).to_string();
+                trace!("vcx_object_info_cb(command_handle: {}, rc: {}, info: {})",
+                       command_handle, error::SUCCESS.message, info);
+
+                let msg = CStringUtils::string_to_cstring(info);
+                cb(command_handle, error::SUCCESS.code_num, msg.as_ptr());
+            }
+            Err(e) => {
+                warn!("vcx_object_info_cb(command_handle: {}, rc: {}, info: {})",
+                      command_handle, e, "null");
+
+                cb(command_handle, e.into(), ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
+/// List information about live objects of the current context including the ones persisted in the wallet but not loaded into memory
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// object_type: (Optional) type of objects to list, one of:
+///     "connection", "credential", "issuer_credential", "proof", "disclosed_proof"
+///     objects of all types are listed if not set
+///
+/// cb: Callback that provides the list of object information (see `vcx_object_info`)
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_object_list(command_handle: CommandHandle,
+                              object_type: *const c_char,
+                              cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, objects: *const c_char)>) -> u32 {
+    info!("vcx_object_list >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+    check_useful_opt_c_str!(object_type, VcxErrorKind::InvalidOption);
+    trace!("vcx_object_list(command_handle: {}, object_type: {:?})",
+           command_handle, object_type);
+
+    let object_type = match object_type.as_ref().map(|object_type| ObjectType::from_str(object_type)) {
+        Some(Ok(object_type)) => Some(object_type),
+        Some(Err(err)) => return err.into(),
+        None => None,
+    };
+
+    spawn(move || {
+        let objects = json!(::object_cache::info::list(object_type)).to_string();
+        trace!("vcx_object_list_cb(command_handle: {}, rc: {}, objects: {})",
+               command_handle, error::SUCCESS.message, objects);
+
+        let msg = CStringUtils::string_to_cstring(objects);
+        cb(command_handle, error::SUCCESS.code_num, msg.as_ptr());
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -680,6 +1074,40 @@ mod tests {
         cb.receive(TimeoutUtils::some_medium()).unwrap();
     }
 
+    #[test]
+    fn test_messages_download_with_filter() {
+        let _setup = SetupMocks::init();
+
+        let filter = CString::new(r#"{"protocolFamilies":["present-proof"]}"#).unwrap().into_raw();
+
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_messages_download_with_filter(cb.command_handle, ptr::null_mut(), ptr::null_mut(), ptr::null_mut(), filter, Some(cb.get_callback())), error::SUCCESS.code_num);
+        cb.receive(TimeoutUtils::some_medium()).unwrap();
+    }
+
+    #[test]
+    fn test_messages_download_with_filter_fails_for_invalid_filter() {
+        let _setup = SetupMocks::init();
+
+        let filter = CString::new(r#"{"protocolFamilies":"present-proof"}"#).unwrap().into_raw();
+
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_messages_download_with_filter(cb.command_handle, ptr::null_mut(), ptr::null_mut(), ptr::null_mut(), filter, Some(cb.get_callback())), error::INVALID_JSON.code_num);
+    }
+
+    #[test]
+    fn test_messages_download_page() {
+        let _setup = SetupMocks::init();
+
+        let filter = CString::new(r#"{"msgTypes":["credOffer"],"since":1579000000,"until":1579086400}"#).unwrap().into_raw();
+        let page_token = CString::new("token").unwrap().into_raw();
+
+        let cb = return_types_u32::Return_U32_STR_STR::new().unwrap();
+        assert_eq!(vcx_messages_download_page(cb.command_handle, ptr::null_mut(), ptr::null_mut(), ptr::null_mut(), filter, 10, page_token, Some(cb.get_callback())), error::SUCCESS.code_num);
+        let (_messages, next_page_token) = cb.receive(TimeoutUtils::some_medium()).unwrap();
+        assert_eq!(next_page_token, None);
+    }
+
     #[test]
     fn test_messages_update_status() {
         let _setup = SetupMocks::init();
@@ -695,5 +1123,27 @@ mod tests {
                    error::SUCCESS.code_num);
         cb.receive(TimeoutUtils::some_medium()).unwrap();
     }
-}
 
+    #[test]
+    fn test_vcx_object_info_and_list() {
+        let _setup = SetupAriesMocks::init();
+
+        let handle = ::connection::create_connection("test_vcx_object_info").unwrap();
+
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_object_info(cb.command_handle, handle, Some(cb.get_callback())), error::SUCCESS.code_num);
+        let info: serde_json::Value = serde_json::from_str(&cb.receive(TimeoutUtils::some_medium()).unwrap().unwrap()).unwrap();
+        assert_eq!(info["type"], json!("connection"));
+        assert_eq!(info["source_id"], json!("test_vcx_object_info"));
+
+        let object_type = CString::new("connection").unwrap().into_raw();
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_object_list(cb.command_handle, object_type, Some(cb.get_callback())), error::SUCCESS.code_num);
+        let objects: Vec<serde_json::Value> = serde_json::from_str(&cb.receive(TimeoutUtils::some_medium()).unwrap().unwrap()).unwrap();
+        assert!(objects.iter().any(|info| info["handle"] == json!(handle)));
+
+        let object_type = CString::new("unknown").unwrap().into_raw();
+        let cb = return_types_u32::Return_U32_STR::new().unwrap();
+        assert_eq!(vcx_object_list(cb.command_handle, object_type, Some(cb.get_callback())), error::INVALID_OPTION.code_num);
+    }
+}
@@ -5,6 +5,7 @@ use utils::libindy::{wallet, pool, ledger};
 use utils::error;
 use settings;
 use std::ffi::CString;
+use std::cell::RefCell;
 use utils::threadpool::spawn;
 use error::prelude::*;
 use indy::{INVALID_WALLET_HANDLE, CommandHandle};
@@ -228,6 +229,8 @@ pub extern fn vcx_version() -> *const c_char {
 /// Reset libvcx to a pre-configured state, releasing/deleting any handles and freeing memory
 ///
 /// libvcx will be inoperable and must be initialized again with vcx_init_with_config
+/// Only the context the calling thread is bound to is reset: other contexts keep their objects,
+/// event subscriptions and the pool they share with it.
 ///
 /// #Params
 /// delete: specify whether wallet/pool should be deleted
@@ -244,10 +247,13 @@ pub extern fn vcx_shutdown(delete: bool) -> u32 {
         Err(_) => {}
     };
 
-    match pool::close() {
-        Ok(()) => {}
-        Err(_) => {}
-    };
+    // the pool can be shared with other contexts
+    if !::context::is_pool_shared() {
+        match pool::close() {
+            Ok(()) => {}
+            Err(_) => {}
+        };
+    }
 
     ::schema::release_all();
     ::connection::release_all();
@@ -256,6 +262,8 @@ pub extern fn vcx_shutdown(delete: bool) -> u32 {
     ::proof::release_all();
     ::disclosed_proof::release_all();
     ::credential::release_all();
+    ::mediation::reset();
+    ::events::unsubscribe_all();
 
     if delete {
         let pool_name = settings::get_config_value(settings::CONFIG_POOL_NAME)
@@ -409,6 +417,58 @@ pub extern fn vcx_get_ledger_author_agreement(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Update states of all Connection, Credential, IssuerCredential, Proof and DisclosedProof objects in one pass.
+///
+/// Pending messages of all Aries connections are downloaded from the agency with a single request and dispatched
+/// to the objects they belong to. Objects of the legacy protocol query the agency for their messages themselves.
+/// Failure to update an object doesn't stop updating the others.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+///
+/// cb: Callback that provides JSON array of objects which changed state:
+///     [{
+///         "object_type": string, - "connection", "credential", "issuer_credential", "proof" or "disclosed_proof"
+///         "handle": u32,
+///         "source_id": string,
+///         "prev_state": u32,
+///         "state": u32
+///     }]
+///
+/// #Returns
+/// Error code as a u32
+#[no_mangle]
+pub extern fn vcx_update_all_states(command_handle: CommandHandle,
+                                    cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, changes: *const c_char)>) -> u32 {
+    info!("vcx_update_all_states >>>");
+
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_update_all_states(command_handle: {})", command_handle);
+
+    spawn(move || {
+        match ::state_updater::update_all_states() {
+            Ok(changes) => {
+                let changes = json!(changes).to_string();
+                trace!("vcx_update_all_states_cb(command_handle: {}, rc: {}, changes: {})",
+                       command_handle, error::SUCCESS.message, changes);
+
+                let msg = CStringUtils::string_to_cstring(changes);
+                cb(command_handle, error::SUCCESS.code_num, msg.as_ptr());
+            }
+            Err(e) => {
+                warn!("vcx_update_all_states_cb(command_handle: {}, rc: {})",
+                      command_handle, e);
+                cb(command_handle, e.into(), ::std::ptr::null_mut());
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Set some accepted agreement as active.
 ///
 /// As result of successful call of this function appropriate metadata will be appended to each write request.
@@ -485,9 +545,16 @@ pub extern fn vcx_mint_tokens(seed: *const c_char, fees: *const c_char) {
 /// * `error_json_p` - Reference that will contain error details (if any error has occurred before)
 ///  in the format:
 /// {
+///     "code": int - error code returned by the function
+///     "error": str - error kind description
 ///     "backtrace": Optional<str> - error backtrace.
 ///         Collecting of backtrace can be enabled by setting environment variable `RUST_BACKTRACE=1`
 ///     "message": str - human-readable error description
+///     "cause": str - root cause of the error
+///     "extra": {
+///         "context": [str] - messages of the error context chain from the outermost one to the root cause
+///         "vendor_code": Optional<int> - original error code of libindy if the error came from it
+///     }
 /// }
 ///
 #[no_mangle]
@@ -500,6 +567,61 @@ pub extern fn vcx_get_current_error(error_json_p: *mut *const c_char) {
     trace!("vcx_get_current_error: <<<");
 }
 
+thread_local! {
+    static FEATURES_C_JSON: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// Get optional subsystems of libvcx and underlying libindy: whether they are compiled in and enabled by current configuration.
+///
+/// NOTE: Returning pointer is valid until the next call of this function in the same execution thread.
+///
+/// #Returns
+/// Map in the JSON format. Where keys are names of subsystems:
+/// {
+///     "revocation": {"compiled": bool, "enabled": bool},
+///     "payments": {"compiled": bool, "enabled": bool} - enabled if `payment_method` is configured,
+///     "postgres_storage": {"compiled": bool, "enabled": bool},
+///     "didcomm_v2": {"compiled": bool, "enabled": bool},
+///     "aries": {"compiled": bool, "enabled": bool} - enabled if Aries protocols are selected by `protocol_type`,
+///     "experimental_protocols": {"compiled": bool, "enabled": bool},
+///     "libindy": {...} - features reported by `indy_get_features`
+/// }
+#[no_mangle]
+pub extern fn vcx_get_features() -> *const c_char {
+    trace!("vcx_get_features >>>");
+
+    let features = _get_features().to_string();
+
+    let mut value = ::std::ptr::null();
+
+    FEATURES_C_JSON.try_with(|features_c_json| {
+        if let Ok(features) = CString::new(features) {
+            value = features.as_ptr();
+            features_c_json.replace(Some(features));
+        }
+    })
+        .map_err(|err| error!("Thread local variable access failed with: {:?}", err)).ok();
+
+    trace!("vcx_get_features: <<< res: {:?}", value);
+
+    value
+}
+
+fn _get_features() -> ::serde_json::Value {
+    let libindy_features: ::serde_json::Value = ::serde_json::from_str(&::indy::get_features())
+        .unwrap_or_else(|_| json!({}));
+
+    json!({
+        "revocation": {"compiled": true, "enabled": true},
+        "payments": {"compiled": true, "enabled": settings::get_payment_method().is_ok()},
+        "postgres_storage": {"compiled": false, "enabled": false},
+        "didcomm_v2": {"compiled": false, "enabled": false},
+        "aries": {"compiled": true, "enabled": settings::is_aries_protocol_set()},
+        "experimental_protocols": {"compiled": false, "enabled": false},
+        "libindy": libindy_features,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -875,6 +997,18 @@ mod tests {
         assert!(return_version.len() > 5);
     }
 
+    #[test]
+    fn test_vcx_get_features() {
+        let _setup = SetupDefaults::init();
+
+        let features = CStringUtils::c_str_to_string(vcx_get_features()).unwrap().unwrap();
+        let features: ::serde_json::Value = ::serde_json::from_str(&features).unwrap();
+
+        assert_eq!(json!({"compiled": true, "enabled": true}), features["payments"]);
+        assert_eq!(json!({"compiled": true, "enabled": false}), features["aries"]);
+        assert!(features["libindy"]["revocation"]["compiled"].as_bool().unwrap());
+    }
+
     #[test]
     fn test_vcx_update_institution_info() {
         let _setup = SetupDefaults::init();
@@ -926,7 +1060,9 @@ mod tests {
 
         let mut error_json_p: *const c_char = ptr::null();
         vcx_get_current_error(&mut error_json_p);
-        assert!(CStringUtils::c_str_to_string(error_json_p).unwrap().is_some());
+        let error: ::serde_json::Value = ::serde_json::from_str(&CStringUtils::c_str_to_string(error_json_p).unwrap().unwrap()).unwrap();
+        assert!(error["code"].as_u64().is_some());
+        assert!(!error["extra"]["context"].as_array().unwrap().is_empty());
     }
 
     #[test]
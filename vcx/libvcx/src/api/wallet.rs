@@ -4,11 +4,11 @@ use utils::error;
 use utils::libindy::payments::{pay_a_payee, get_wallet_token_info, create_address, sign_with_address, verify_with_address};
 use utils::libindy::wallet::{export, import, get_wallet_handle};
 use utils::libindy::wallet;
+use utils::serialization::{self, ObjectType};
 use utils::threadpool::spawn;
-use std::thread;
 use std::ptr::null;
 use error::prelude::*;
-use indy::{CommandHandle, SearchHandle, WalletHandle};
+use indy::{CommandHandle, SearchHandle, WalletHandle, INVALID_WALLET_HANDLE};
 
 /// Get the total balance from all addresses contained in the configured wallet
 ///
@@ -336,6 +336,63 @@ pub extern fn vcx_wallet_update_record_value(command_handle: CommandHandle,
     error::SUCCESS.code_num
 }
 
+/// Upgrades VCX objects kept serialized in the values of wallet records to the current serialization format.
+/// Objects are upgraded on deserialization anyway, the migration rewrites all of them at once after VCX upgrade.
+/// Records which values cannot be upgraded are left unchanged.
+/// #Params
+///
+/// command_handle: command handle to map callback to user context.
+///
+/// type_: type of records containing serialized objects.
+///
+/// object_type: type of serialized objects: `connection`, `credential`, `issuer_credential`, `proof` or `disclosed_proof`.
+///
+/// cb: Callback that provides the number of rewritten records
+///
+/// #Returns
+/// Error code as a u32
+///
+#[no_mangle]
+pub extern fn vcx_wallet_migrate_serialized_objects(command_handle: CommandHandle,
+                                                    type_: *const c_char,
+                                                    object_type: *const c_char,
+                                                    cb: Option<extern fn(xcommand_handle: CommandHandle, err: u32, migrated: u32)>) -> u32 {
+    info!("vcx_wallet_migrate_serialized_objects >>>");
+
+    check_useful_c_str!(type_, VcxErrorKind::InvalidOption);
+    check_useful_c_str!(object_type, VcxErrorKind::InvalidOption);
+    check_useful_c_callback!(cb, VcxErrorKind::InvalidOption);
+
+    trace!("vcx_wallet_migrate_serialized_objects(command_handle: {}, type_: {}, object_type: {})",
+           command_handle, secret!(&type_), object_type);
+
+    let object_type = match ObjectType::from_str(&object_type) {
+        Ok(object_type) => object_type,
+        Err(err) => return err.into()
+    };
+
+    spawn(move || {
+        match serialization::migrate_wallet_records(&type_, object_type) {
+            Ok(migrated) => {
+                trace!("vcx_wallet_migrate_serialized_objects(command_handle: {}, rc: {}, migrated: {})",
+                       command_handle, error::SUCCESS.message, migrated);
+
+                cb(command_handle, error::SUCCESS.code_num, migrated);
+            }
+            Err(x) => {
+                warn!("vcx_wallet_migrate_serialized_objects(command_handle: {}, rc: {})",
+                      command_handle, x);
+
+                cb(command_handle, x.into(), 0);
+            }
+        };
+
+        Ok(())
+    });
+
+    error::SUCCESS.code_num
+}
+
 /// Updates the value of a record already in the wallet.
 /// Assumes there is an open wallet and that a type and id pair already exists.
 /// #Params
@@ -827,7 +884,7 @@ pub extern fn vcx_wallet_import(command_handle: CommandHandle,
     trace!("vcx_wallet_import(command_handle: {}, config: ****)",
            command_handle);
 
-    thread::spawn(move || {
+    ::context::spawn_thread(move || {
         trace!("vcx_wallet_import(command_handle: {}, config: ****)", command_handle);
         match import(&config) {
             Ok(()) => {
@@ -884,10 +941,11 @@ pub  extern fn vcx_wallet_validate_payment_address(command_handle: i32,
 /// handle: wallet handle that libvcx should use
 ///
 /// #Returns
-/// Error code as u32
+/// The handle, or 0 if the context bound to the calling thread has been released
 #[no_mangle]
 pub extern fn vcx_wallet_set_handle(handle: WalletHandle) -> WalletHandle {
     wallet::set_wallet_handle(handle)
+        .unwrap_or(INVALID_WALLET_HANDLE)
 }
 
 #[cfg(test)]
@@ -1243,6 +1301,42 @@ pub mod tests {
         cb.receive(TimeoutUtils::some_medium()).unwrap();
     }
 
+    #[test]
+    fn test_migrate_serialized_objects() {
+        let _setup = SetupLibraryWallet::init();
+
+        let xtype = CStringUtils::string_to_cstring("record_type".to_string());
+        let id = CStringUtils::string_to_cstring("123".to_string());
+        let value = CStringUtils::string_to_cstring(json!({"version": "1.0", "data": {}}).to_string());
+        let tags = CStringUtils::string_to_cstring("{}".to_string());
+
+        let cb = return_types_u32::Return_U32::new().unwrap();
+        assert_eq!(vcx_wallet_add_record(cb.command_handle, xtype.as_ptr(),
+                                         id.as_ptr(),
+                                         value.as_ptr(),
+                                         tags.as_ptr(),
+                                         Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        cb.receive(TimeoutUtils::some_medium()).unwrap();
+
+        let object_type = CStringUtils::string_to_cstring("unknown".to_string());
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_wallet_migrate_serialized_objects(cb.command_handle,
+                                                         xtype.as_ptr(),
+                                                         object_type.as_ptr(),
+                                                         Some(cb.get_callback())),
+                   error::INVALID_OPTION.code_num);
+
+        let object_type = CStringUtils::string_to_cstring("connection".to_string());
+        let cb = return_types_u32::Return_U32_U32::new().unwrap();
+        assert_eq!(vcx_wallet_migrate_serialized_objects(cb.command_handle,
+                                                         xtype.as_ptr(),
+                                                         object_type.as_ptr(),
+                                                         Some(cb.get_callback())),
+                   error::SUCCESS.code_num);
+        assert_eq!(1, cb.receive(TimeoutUtils::some_medium()).unwrap());
+    }
+
     #[test]
     fn test_wallet_import_export() {
         let _setup = SetupDefaults::init();
@@ -0,0 +1,39 @@
+// Usage: vcx-issuer-daemon <config.json>
+// See `vcx::issuer_daemon::IssuerDaemonConfig` for the format of the config.
+extern crate env_logger;
+extern crate serde_json;
+extern crate vcx;
+
+use std::env;
+use std::fs;
+use std::process;
+
+fn main() {
+    env_logger::init();
+
+    let config_path = match env::args().nth(1) {
+        Some(config_path) => config_path,
+        None => {
+            eprintln!("Usage: vcx-issuer-daemon <config.json>");
+            process::exit(2);
+        }
+    };
+
+    let config = fs::read_to_string(&config_path)
+        .map_err(|err| format!("Cannot read config {}: {}", config_path, err))
+        .and_then(|config| serde_json::from_str(&config)
+            .map_err(|err| format!("Cannot parse config {}: {}", config_path, err)));
+
+    let config = match config {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+
+    if let Err(err) = vcx::issuer_daemon::run(config) {
+        eprintln!("Issuer daemon failed: {}", err);
+        process::exit(1);
+    }
+}
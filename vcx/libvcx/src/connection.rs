@@ -20,15 +20,18 @@ use utils::libindy::signus::create_and_store_my_did;
 use utils::libindy::crypto;
 use utils::json::mapped_key_rewrite;
 use utils::json::KeyMatch;
+use utils::serialization::{self, ObjectType};
+use events;
 
 use v3::handlers::connection::connection::Connection as ConnectionV3;
 use v3::handlers::connection::states::ActorDidExchangeState;
-use v3::handlers::connection::agent::AgentInfo;
+use v3::handlers::connection::agent::{AgentInfo, AutoAcceptPolicy};
 use v3::messages::connection::invite::Invitation as InvitationV3;
+use v3::messages::out_of_band::invitation::Invitation as OutOfBandInvitation;
 use settings::ProtocolTypes;
 
 lazy_static! {
-    static ref CONNECTION_MAP: ObjectCache<Connections> = Default::default();
+    static ref CONNECTION_MAP: ObjectCache<Connections> = ObjectCache::persisted("VcxConnection", _serialize, _deserialize);
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -521,7 +524,7 @@ pub fn get_their_public_did(handle: u32) -> VcxResult<Option<String>> {
     CONNECTION_MAP.get(handle, |connection| {
         match connection {
             Connections::V1(ref connection) => Ok(connection.get_their_public_did()),
-            Connections::V3(_) => Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle))
+            Connections::V3(ref connection) => Ok(connection.their_public_did())
         }
     }).or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
 }
@@ -702,38 +705,95 @@ pub fn create_connection(source_id: &str) -> VcxResult<u32> {
 pub fn create_connection_with_invite(source_id: &str, details: &str) -> VcxResult<u32> {
     debug!("create connection {} with invite {}", source_id, details);
 
+    if let Ok(invitation) = _parse_outofband_invitation(details) {
+        let connection = Connections::V3(ConnectionV3::create_with_outofband_invite(source_id, invitation)?);
+        return store_connection(connection);
+    }
+
     // Invitation of new format -- redirect to v3 folder
     if let Ok(invitation) = serde_json::from_str::<InvitationV3>(details) {
         let connection = Connections::V3(ConnectionV3::create_with_invite(source_id, invitation)?);
         return store_connection(connection);
     }
 
+    let invite_details = _parse_invite_detail(details)?;
+
+    let mut connection = create_connection_v1(source_id)?;
+
+    connection.set_their_pw_did(invite_details.sender_detail.did.as_str());
+    connection.set_their_pw_verkey(invite_details.sender_detail.verkey.as_str());
+
+    if let Some(did) = invite_details.sender_detail.public_did.as_ref() {
+        connection.set_their_public_did(did);
+    }
+
+    connection.set_invite_detail(invite_details);
+    connection.set_state(VcxStateType::VcxStateRequestReceived);
+
+    store_connection(Connections::V1(connection))
+}
+
+fn _parse_invite_detail(details: &str) -> VcxResult<InviteDetail> {
     let details: Value = serde_json::from_str(&details)
         .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize invite details: {}", err)))?;
 
-    let invite_details: InviteDetail = match serde_json::from_value(details.clone()) {
-        Ok(x) => x,
+    match serde_json::from_value(details.clone()) {
+        Ok(x) => Ok(x),
         Err(_) => {
             // Try converting to abbreviated
             let details = unabbrv_event_detail(details)?;
             serde_json::from_value(details)
-                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize invite details: {}", err)))?
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize invite details: {}", err)))
         }
+    }
+}
+
+/*
+    Completed connection with the sender of the invitation if the invitation states the public DID
+    of the sender and we already have a connection with it.
+    Can be reused instead of creating a new pairwise connection with `create_connection_with_invite`.
+*/
+pub fn find_existing_connection(details: &str) -> VcxResult<Option<u32>> {
+    debug!("find existing connection for invite {}", details);
+
+    let public_did = match _invitation_public_did(details)? {
+        Some(did) => did,
+        None => return Ok(None)
     };
 
-    let mut connection = create_connection_v1(source_id)?;
+    let public_did = _unqualified_did(&public_did);
 
-    connection.set_their_pw_did(invite_details.sender_detail.did.as_str());
-    connection.set_their_pw_verkey(invite_details.sender_detail.verkey.as_str());
+    let handle = CONNECTION_MAP.find(|connection| {
+        match connection {
+            Connections::V1(ref connection) => {
+                connection.get_state() == VcxStateType::VcxStateAccepted as u32 &&
+                    connection.get_their_public_did().as_ref().map(|did| _unqualified_did(did) == public_did).unwrap_or(false)
+            }
+            Connections::V3(ref connection) => {
+                connection.state() == VcxStateType::VcxStateAccepted as u32 &&
+                    connection.their_public_did().as_ref().map(|did| _unqualified_did(did) == public_did).unwrap_or(false)
+            }
+        }
+    });
 
-    if let Some(did) = invite_details.sender_detail.public_did.as_ref() {
-        connection.set_their_public_did(did);
+    Ok(handle)
+}
+
+fn _invitation_public_did(details: &str) -> VcxResult<Option<String>> {
+    if let Ok(invitation) = _parse_outofband_invitation(details) {
+        return Ok(invitation.public_did().map(String::from));
     }
 
-    connection.set_invite_detail(invite_details);
-    connection.set_state(VcxStateType::VcxStateRequestReceived);
+    if let Ok(invitation) = serde_json::from_str::<InvitationV3>(details) {
+        return Ok(invitation.did);
+    }
 
-    store_connection(Connections::V1(connection))
+    Ok(_parse_invite_detail(details)?.sender_detail.public_did)
+}
+
+// Legacy connections keep unqualified DIDs, while Aries invitations may carry `did:sov:` ones
+fn _unqualified_did(did: &str) -> &str {
+    did.rsplit(':').next().unwrap_or(did)
 }
 
 pub fn parse_acceptance_details(message: &Message) -> VcxResult<SenderDetail> {
@@ -849,7 +909,9 @@ pub fn send_generic_message(connection_handle: u32, msg: &str, msg_options: &str
 }
 
 pub fn update_state_with_message(handle: u32, message: Message) -> VcxResult<u32> {
-    CONNECTION_MAP.get_mut(handle, |connection| {
+    let prev_state = get_state(handle);
+
+    let res = CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
                 if message.status_code == MessageStatusCode::Redirected && message.msg_type == RemoteMessageType::ConnReqRedirect {
@@ -864,7 +926,10 @@ pub fn update_state_with_message(handle: u32, message: Message) -> VcxResult<u32
             }
         }
     })
-        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
+        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))?;
+
+    _publish_state_change(handle, prev_state);
+    Ok(res)
 }
 
 impl Connection {
@@ -903,7 +968,9 @@ impl Connection {
 }
 
 pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
-    CONNECTION_MAP.get_mut(handle, |connection| {
+    let prev_state = get_state(handle);
+
+    let res = CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
             Connections::V1(ref mut connection) => {
                 connection.update_state(message.clone())
@@ -914,7 +981,31 @@ pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
             }
         }
     })
-        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
+        .or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))?;
+
+    _publish_state_change(handle, prev_state);
+    Ok(res)
+}
+
+fn _publish_state_change(handle: u32, prev_state: u32) {
+    if events::has_subscribers() {
+        events::state_changed(ObjectType::Connection, handle, get_source_id(handle).unwrap_or_default(), prev_state, get_state(handle));
+    }
+}
+
+// Advances state with a message already downloaded from the agency (e.g. delivered in push notification)
+// without querying the agency for new messages.
+pub fn update_state_with_agency_message(handle: u32, message: &str) -> VcxResult<u32> {
+    if is_v3_connection(handle)? {
+        let message = decode_agency_message(handle, message)?;
+        update_state(handle, Some(message))?;
+    } else {
+        let message: Message = serde_json::from_str(message)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize agency message: {}", err)))?;
+        update_state_with_message(handle, message)?;
+    }
+
+    Ok(get_state(handle))
 }
 
 impl Connection {
@@ -1008,40 +1099,38 @@ pub fn redirect(handle: u32, redirect_handle: u32) -> VcxResult<u32> {
 }
 
 
-pub fn to_string(handle: u32) -> VcxResult<String> {
-    CONNECTION_MAP.get(handle, |connection| {
-        match connection {
-            Connections::V1(ref connection) => {
-                let object: SerializableObjectWithState<Connection, ConnectionV3> = SerializableObjectWithState::V1 { data: connection.to_owned() };
+fn _serialize(connection: &Connections) -> VcxResult<String> {
+    match connection {
+        Connections::V1(ref connection) => {
+            let object: SerializableObjectWithState<Connection, ConnectionV3> = SerializableObjectWithState::V1 { data: connection.to_owned() };
 
-                ::serde_json::to_string(&object)
-                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot serialize Connection: {:?}", err)))
-            }
-            Connections::V3(ref connection) => {
-                let (data, state) = connection.to_owned().into();
-                let object = SerializableObjectWithState::V2 { data, state };
+            serialization::serialize(ObjectType::Connection, &object)
+        }
+        Connections::V3(ref connection) => {
+            let (data, state) = connection.to_owned().into();
+            let object = SerializableObjectWithState::V2 { data, state };
 
-                ::serde_json::to_string(&object)
-                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot serialize Connection: {:?}", err)))
-            }
+            serialization::serialize(ObjectType::Connection, &object)
         }
-    })
+    }
 }
 
-pub fn from_string(connection_data: &str) -> VcxResult<u32> {
-    let object: SerializableObjectWithState<Connection, ::v3::handlers::connection::states::ActorDidExchangeState> = ::serde_json::from_str(connection_data)
-        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Connection: {:?}", err)))?;
+fn _deserialize(connection_data: &str) -> VcxResult<Connections> {
+    let object: SerializableObjectWithState<Connection, ::v3::handlers::connection::states::ActorDidExchangeState> =
+        serialization::deserialize(ObjectType::Connection, connection_data)?;
 
-    let handle = match object {
-        SerializableObjectWithState::V1 { data, .. } => {
-            CONNECTION_MAP.add(Connections::V1(data))?
-        }
-        SerializableObjectWithState::V2 { data, state } => {
-            CONNECTION_MAP.add(Connections::V3((data, state).into()))?
-        }
-    };
+    match object {
+        SerializableObjectWithState::V1 { data, .. } => Ok(Connections::V1(data)),
+        SerializableObjectWithState::V2 { data, state } => Ok(Connections::V3((data, state).into())),
+    }
+}
 
-    Ok(handle)
+pub fn to_string(handle: u32) -> VcxResult<String> {
+    CONNECTION_MAP.get(handle, _serialize)
+}
+
+pub fn from_string(connection_data: &str) -> VcxResult<u32> {
+    CONNECTION_MAP.add(_deserialize(connection_data)?)
 }
 
 pub fn release(handle: u32) -> VcxResult<()> {
@@ -1053,6 +1142,10 @@ pub fn release_all() {
     CONNECTION_MAP.drain().ok();
 }
 
+pub fn get_handles() -> Vec<u32> {
+    CONNECTION_MAP.handles()
+}
+
 pub fn get_invite_details(handle: u32, abbreviated: bool) -> VcxResult<String> {
     CONNECTION_MAP.get(handle, |connection| {
         match connection {
@@ -1221,7 +1314,7 @@ impl Into<(Connection, ActorDidExchangeState)> for ConnectionV3 {
             their_pw_did: self.remote_did().unwrap_or_default(),
             their_pw_verkey: self.remote_vk().unwrap_or_default(),
             public_did: None,
-            their_public_did: None,
+            their_public_did: self.their_public_did(),
             version: Some(ProtocolTypes::V2), // TODO check correctness
         };
 
@@ -1236,14 +1329,23 @@ impl From<(Connection, ActorDidExchangeState)> for ConnectionV3 {
             pw_vk: connection.get_pw_verkey().to_string(),
             agent_did: connection.get_agent_did().to_string(),
             agent_vk: connection.get_agent_verkey().to_string(),
+            tls: None,
+            envelope: EnvelopeFormat::default(),
+            mediator: None,
+            return_route: false,
+            auto_accept: AutoAcceptPolicy::default(),
+            did_method: DidMethod::default(),
         };
 
-        ConnectionV3::from_parts(connection.get_source_id().to_string(), agent_info, state)
+        ConnectionV3::from_parts(connection.get_source_id().to_string(), agent_info, state, connection.get_their_public_did())
     }
 }
 
 use v3::messages::a2a::A2AMessage;
-use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::connection::did_doc::{DidDoc, TlsConstraints};
+use v3::utils::encryption_envelope::EnvelopeFormat;
+use v3::utils::did_method::DidMethod;
+use v3::messages::discovery::disclose::ProtocolDescriptor;
 
 pub fn get_messages(handle: u32) -> VcxResult<HashMap<String, A2AMessage>> {
     CONNECTION_MAP.get_mut(handle, |connection| {
@@ -1254,6 +1356,16 @@ pub fn get_messages(handle: u32) -> VcxResult<HashMap<String, A2AMessage>> {
     })
 }
 
+// Protocols the remote side disclosed via Discover Features, if any
+pub fn get_remote_protocols(handle: u32) -> VcxResult<Option<Vec<ProtocolDescriptor>>> {
+    CONNECTION_MAP.get(handle, |connection| {
+        match connection {
+            Connections::V3(ref connection) => Ok(connection.remote_protocols()),
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle))
+        }
+    })
+}
+
 pub fn update_message_status(handle: u32, uid: String) -> VcxResult<()> {
     CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
@@ -1281,6 +1393,16 @@ pub fn decode_message(handle: u32, message: Message) -> VcxResult<A2AMessage> {
     })
 }
 
+// Decodes a message downloaded from the agency into Aries message using keys of the connection.
+pub fn decode_agency_message(handle: u32, message: &str) -> VcxResult<String> {
+    let message: Message = serde_json::from_str(message)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize agency message: {}", err)))?;
+
+    let message = decode_message(handle, message)?;
+
+    Ok(json!(message).to_string())
+}
+
 pub fn send_message(handle: u32, message: A2AMessage) -> VcxResult<()> {
     CONNECTION_MAP.get_mut(handle, |connection| {
         match connection {
@@ -1312,6 +1434,15 @@ pub fn send_ping(connection_handle: u32, comment: Option<String>) -> VcxResult<(
     })
 }
 
+pub fn send_basic_message_ack(connection_handle: u32, message_id: &str) -> VcxResult<()> {
+    CONNECTION_MAP.get(connection_handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref connection) => connection.send_basic_message_ack(message_id)
+        }
+    })
+}
+
 pub fn send_discovery_features(connection_handle: u32, query: Option<String>, comment: Option<String>) -> VcxResult<()> {
     CONNECTION_MAP.get_mut(connection_handle, |connection| {
         match connection {
@@ -1321,6 +1452,119 @@ pub fn send_discovery_features(connection_handle: u32, query: Option<String>, co
     })
 }
 
+fn _parse_outofband_invitation(invitation: &str) -> VcxResult<OutOfBandInvitation> {
+    match serde_json::from_str::<A2AMessage>(invitation) {
+        Ok(A2AMessage::OutOfBandInvitation(invitation)) => Ok(invitation),
+        Ok(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Message is not an Out-of-Band invitation")),
+        Err(err) => Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Out-of-Band invitation: {}", err)))
+    }
+}
+
+pub fn get_outofband_invite_details(handle: u32, goal_code: Option<String>, goal: Option<String>) -> VcxResult<String> {
+    CONNECTION_MAP.get(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref connection) => connection.get_outofband_invite_details(goal_code.clone(), goal.clone())
+        }
+    })
+}
+
+pub fn get_problem_report(handle: u32) -> VcxResult<String> {
+    CONNECTION_MAP.get(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref connection) => connection.get_problem_report()
+        }
+    })
+}
+
+pub fn send_handshake_reuse(handle: u32, invitation: &str) -> VcxResult<()> {
+    let invitation = _parse_outofband_invitation(invitation)?;
+
+    CONNECTION_MAP.get_mut(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref mut connection) => connection.send_handshake_reuse(invitation.clone())
+        }
+    })
+}
+
+pub fn set_tls_constraints(handle: u32, tls_json: Option<String>) -> VcxResult<()> {
+    let tls: Option<TlsConstraints> = match tls_json {
+        Some(tls_json) => Some(::serde_json::from_str(&tls_json)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize TLS constraints: {:?}", err)))?),
+        None => None
+    };
+
+    CONNECTION_MAP.get_mut(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref mut connection) => connection.set_tls_constraints(tls.clone())
+        }
+    })
+}
+
+pub fn set_envelope_format(handle: u32, format_json: &str) -> VcxResult<()> {
+    let envelope: EnvelopeFormat = ::serde_json::from_str(format_json)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize envelope format: {:?}", err)))?;
+
+    CONNECTION_MAP.get_mut(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref mut connection) => connection.set_envelope_format(envelope.clone())
+        }
+    })
+}
+
+pub fn set_return_route(handle: u32, return_route: bool) -> VcxResult<()> {
+    CONNECTION_MAP.get_mut(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref mut connection) => connection.set_return_route(return_route)
+        }
+    })
+}
+
+pub fn set_auto_accept(handle: u32, policy_json: &str) -> VcxResult<()> {
+    let policy: AutoAcceptPolicy = ::serde_json::from_str(policy_json)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize auto-accept policy: {:?}", err)))?;
+
+    CONNECTION_MAP.get_mut(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref mut connection) => connection.set_auto_accept(policy.clone())
+        }
+    })
+}
+
+// `method` is the serialized DidMethod name
+pub fn set_did_method(handle: u32, method: &str) -> VcxResult<()> {
+    let did_method: DidMethod = ::serde_json::from_value(json!(method))
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unsupported DID method: {:?}, err: {:?}", method, err)))?;
+
+    CONNECTION_MAP.get_mut(handle, |connection| {
+        match connection {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref mut connection) => connection.set_did_method(did_method)
+        }
+    })
+}
+
+// Policy of the connection with the library settings applied for the unset values
+pub fn get_auto_accept(handle: u32) -> VcxResult<AutoAcceptPolicy> {
+    CONNECTION_MAP.get(handle, |connection| {
+        let policy = match connection {
+            Connections::V1(_) => AutoAcceptPolicy::default(),
+            Connections::V3(ref connection) => connection.agent_info().auto_accept.clone()
+        };
+
+        Ok(AutoAcceptPolicy {
+            credential_offers: Some(policy.credential_offers()),
+            presentation_requests: Some(policy.presentation_requests()),
+        })
+    }).or(Err(VcxError::from(VcxErrorKind::InvalidConnectionHandle)))
+}
+
 pub fn get_connection_info(handle: u32) -> VcxResult<String> {
     CONNECTION_MAP.get(handle, |cxn| {
         match cxn {
@@ -1330,6 +1574,16 @@ pub fn get_connection_info(handle: u32) -> VcxResult<String> {
     })
 }
 
+// Pairwise agent and remote DIDDoc of established connection for services talking over it on their own
+pub fn get_completed_connection(handle: u32) -> VcxResult<(AgentInfo, DidDoc)> {
+    CONNECTION_MAP.get(handle, |cxn| {
+        match cxn {
+            Connections::V1(_) => Err(VcxError::from(VcxErrorKind::ActionNotSupported)),
+            Connections::V3(ref connection) => connection.get_completed_connection()
+        }
+    })
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::thread;
@@ -1812,6 +2066,104 @@ pub mod tests {
         let _serialized = to_string(handle).unwrap();
     }
 
+    #[test]
+    fn test_create_with_outofband_invite_details() {
+        let _setup = SetupMocks::init();
+
+        let invitation = json!(::v3::messages::out_of_band::invitation::tests::_oob_invitation().to_a2a_message()).to_string();
+        let handle = create_connection_with_invite("alice", &invitation).unwrap();
+
+        CONNECTION_MAP.get_mut(handle, |connection| {
+            match connection {
+                Connections::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidState, "It is suppose to be V3")),
+                Connections::V3(_) => Ok(()),
+            }
+        }).unwrap();
+
+        assert_eq!(get_outofband_invite_details(handle, None, None).unwrap_err().kind(), VcxErrorKind::NotReady);
+        assert_eq!(send_handshake_reuse(handle, &invitation).unwrap_err().kind(), VcxErrorKind::NotReady);
+        assert_eq!(send_handshake_reuse(handle, "{}").unwrap_err().kind(), VcxErrorKind::InvalidJson);
+    }
+
+    #[test]
+    fn test_auto_accept_policy_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let handle = create_connection("alice").unwrap();
+
+        let policy = get_auto_accept(handle).unwrap();
+        assert_eq!(Some(settings::AutoAcceptOffers::Never), policy.credential_offers);
+        assert_eq!(Some(settings::AutoPresentProofs::Never), policy.presentation_requests);
+
+        settings::set_config_value(settings::CONFIG_AUTO_PRESENT_PROOFS, "single_match");
+        set_auto_accept(handle, r#"{"credential_offers":"always"}"#).unwrap();
+
+        let policy = get_auto_accept(handle).unwrap();
+        assert_eq!(Some(settings::AutoAcceptOffers::Always), policy.credential_offers);
+        assert_eq!(Some(settings::AutoPresentProofs::SingleMatch), policy.presentation_requests);
+
+        assert_eq!(set_auto_accept(handle, r#"{"credential_offers":"sometimes"}"#).unwrap_err().kind(), VcxErrorKind::InvalidJson);
+        assert_eq!(get_auto_accept(0).unwrap_err().kind(), VcxErrorKind::InvalidConnectionHandle);
+    }
+
+    #[test]
+    fn test_set_did_method_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let handle = create_connection("alice").unwrap();
+
+        set_did_method(handle, "did:peer:2").unwrap();
+        assert!(to_string(handle).unwrap().contains("did:peer:2"));
+
+        assert_eq!(set_did_method(handle, "did:web").unwrap_err().kind(), VcxErrorKind::InvalidOption);
+        assert_eq!(set_did_method(0, "did:key").unwrap_err().kind(), VcxErrorKind::InvalidConnectionHandle);
+    }
+
+    #[test]
+    fn test_find_existing_connection_works() {
+        let _setup = SetupMocks::init();
+
+        use v3::messages::connection::invite::tests::{_invitation, _public_did};
+
+        let invitation = _invitation().set_public_did(Some(_public_did()));
+        let invitation_json = json!(invitation).to_string();
+
+        // not completed connection is not offered for reuse
+        let handle = create_connection_with_invite("alice", &invitation_json).unwrap();
+        assert_eq!(Some(_public_did()), get_their_public_did(handle).unwrap());
+        assert_eq!(None, find_existing_connection(&invitation_json).unwrap());
+
+        let connection = Connections::V1(Connection {
+            source_id: "faber".to_string(),
+            pw_did: "8XFh8yBzrpJQmNyZzgoTqB".to_string(),
+            pw_verkey: "EkVTa7SCJ5SntpYyX7CSb2pcBhiVGT9kWSagA8a9T69A".to_string(),
+            state: VcxStateType::VcxStateAccepted,
+            uuid: String::new(),
+            endpoint: String::new(),
+            invite_detail: None,
+            redirect_detail: None,
+            invite_url: None,
+            agent_did: "8XFh8yBzrpJQmNyZzgoTqB".to_string(),
+            agent_vk: "EkVTa7SCJ5SntpYyX7CSb2pcBhiVGT9kWSagA8a9T69A".to_string(),
+            their_pw_did: String::new(),
+            their_pw_verkey: String::new(),
+            public_did: None,
+            their_public_did: Some("LjgpST2rjsoxYegQDRm7EL".to_string()),
+            version: None,
+        });
+        let existing = CONNECTION_MAP.add(connection).unwrap();
+
+        assert_eq!(Some(existing), find_existing_connection(&invitation_json).unwrap());
+
+        let oob_invitation = json!(OutOfBandInvitation::from(invitation).to_a2a_message()).to_string();
+        assert_eq!(Some(existing), find_existing_connection(&oob_invitation).unwrap());
+
+        assert_eq!(None, find_existing_connection(&json!(_invitation()).to_string()).unwrap());
+        assert_eq!(find_existing_connection("{}").unwrap_err().kind(), VcxErrorKind::InvalidJson);
+
+        release(existing).unwrap();
+    }
+
     #[cfg(feature = "agency")]
     #[cfg(feature = "pool_tests")]
     #[test]
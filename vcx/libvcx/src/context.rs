@@ -0,0 +1,286 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::thread;
+
+use rand::Rng;
+
+use indy::{WalletHandle, INVALID_WALLET_HANDLE};
+
+use error::prelude::*;
+
+/*
+    Context isolates the state of a tenant: library settings, opened wallet and pool.
+    A single process can serve many tenants concurrently through separate contexts.
+
+    Every API call is executed in the context bound to the calling thread (`vcx_context_bind`).
+    API functions don't take a context handle, so their signatures stay the same for single tenant applications.
+    Async work started by the call continues in the same context.
+    Threads which don't bind any context use the default one, so applications serving a single tenant
+    don't need to care about contexts at all.
+*/
+
+pub type ContextHandle = u32;
+
+pub const DEFAULT_CONTEXT: ContextHandle = 0;
+
+struct Context {
+    settings: HashMap<String, String>,
+    wallet_handle: WalletHandle,
+    pool_handle: Option<i32>,
+}
+
+impl Context {
+    fn new() -> Context {
+        Context {
+            settings: HashMap::new(),
+            wallet_handle: INVALID_WALLET_HANDLE,
+            pool_handle: None,
+        }
+    }
+}
+
+lazy_static! {
+    static ref CONTEXTS: RwLock<HashMap<ContextHandle, Context>> = {
+        let mut contexts = HashMap::new();
+        contexts.insert(DEFAULT_CONTEXT, Context::new());
+        RwLock::new(contexts)
+    };
+}
+
+thread_local! {
+    static CURRENT_CONTEXT: Cell<ContextHandle> = Cell::new(DEFAULT_CONTEXT);
+}
+
+pub fn create() -> VcxResult<ContextHandle> {
+    trace!("context::create >>>");
+
+    let mut contexts = CONTEXTS.write()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock contexts: {:?}", err)))?;
+
+    let mut handle = rand::thread_rng().gen::<ContextHandle>();
+    while handle == DEFAULT_CONTEXT || contexts.contains_key(&handle) {
+        handle = rand::thread_rng().gen::<ContextHandle>();
+    }
+
+    contexts.insert(handle, Context::new());
+
+    Ok(handle)
+}
+
+// Wallet and pool of the context must be closed by the caller
+pub fn release(handle: ContextHandle) -> VcxResult<()> {
+    trace!("context::release >>> handle: {}", handle);
+
+    if handle == DEFAULT_CONTEXT {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidContextHandle, "Default context can't be released"));
+    }
+
+    CONTEXTS.write()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock contexts: {:?}", err)))?
+        .remove(&handle)
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidContextHandle, format!("Unknown context: {}", handle)))?;
+
+    if current() == handle {
+        CURRENT_CONTEXT.with(|current| current.set(DEFAULT_CONTEXT));
+    }
+
+    Ok(())
+}
+
+pub fn exists(handle: ContextHandle) -> bool {
+    CONTEXTS.read()
+        .map(|contexts| contexts.contains_key(&handle))
+        .unwrap_or(false)
+}
+
+// Binds the calling thread to the context
+pub fn bind(handle: ContextHandle) -> VcxResult<()> {
+    trace!("context::bind >>> handle: {}", handle);
+
+    if !exists(handle) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidContextHandle, format!("Unknown context: {}", handle)));
+    }
+
+    CURRENT_CONTEXT.with(|current| current.set(handle));
+
+    Ok(())
+}
+
+pub fn current() -> ContextHandle {
+    CURRENT_CONTEXT.with(|current| current.get())
+}
+
+// Restores the context the thread was bound to when dropped, even if the closure run in another context panics
+struct Rebind(ContextHandle);
+
+impl Drop for Rebind {
+    fn drop(&mut self) {
+        CURRENT_CONTEXT.with(|current| current.set(self.0));
+    }
+}
+
+// Runs the closure in the context and restores the context the thread was bound to
+pub fn run_in<F, T>(handle: ContextHandle, f: F) -> T where F: FnOnce() -> T {
+    let _rebind = Rebind(current());
+    CURRENT_CONTEXT.with(|current| current.set(handle));
+    f()
+}
+
+// Spawns the thread executing the closure in the context of the caller
+pub fn spawn_thread<F, T>(f: F) -> thread::JoinHandle<T> where F: FnOnce() -> T + Send + 'static, T: Send + 'static {
+    let context = current();
+    thread::spawn(move || run_in(context, f))
+}
+
+// Settings of released context are empty
+pub fn with_settings<F, T>(f: F) -> T where F: FnOnce(&HashMap<String, String>) -> T {
+    let contexts = CONTEXTS.read().unwrap();
+
+    match contexts.get(&current()) {
+        Some(context) => f(&context.settings),
+        None => f(&HashMap::new())
+    }
+}
+
+// Released context is never recreated: writes to it fail with `InvalidContextHandle`
+pub fn with_settings_mut<F, T>(f: F) -> VcxResult<T> where F: FnOnce(&mut HashMap<String, String>) -> T {
+    _with_context_mut(|context| f(&mut context.settings))
+}
+
+pub fn get_wallet_handle() -> WalletHandle {
+    CONTEXTS.read().unwrap()
+        .get(&current())
+        .map(|context| context.wallet_handle)
+        .unwrap_or(INVALID_WALLET_HANDLE)
+}
+
+pub fn set_wallet_handle(handle: WalletHandle) -> VcxResult<()> {
+    _with_context_mut(|context| context.wallet_handle = handle)
+}
+
+pub fn get_pool_handle() -> Option<i32> {
+    CONTEXTS.read().unwrap()
+        .get(&current())
+        .and_then(|context| context.pool_handle)
+}
+
+pub fn set_pool_handle(handle: Option<i32>) -> VcxResult<()> {
+    _with_context_mut(|context| context.pool_handle = handle)
+}
+
+fn _with_context_mut<F, T>(f: F) -> VcxResult<T> where F: FnOnce(&mut Context) -> T {
+    let handle = current();
+
+    let mut contexts = CONTEXTS.write()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock contexts: {:?}", err)))?;
+
+    let context = contexts.get_mut(&handle)
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidContextHandle, format!("Unknown context: {}", handle)))?;
+
+    Ok(f(context))
+}
+
+// Whether the pool of the current context is used by other contexts as well
+pub fn is_pool_shared() -> bool {
+    let current = current();
+    let contexts = CONTEXTS.read().unwrap();
+
+    match contexts.get(&current).and_then(|context| context.pool_handle) {
+        Some(pool_handle) => contexts.iter()
+            .any(|(handle, context)| *handle != current && context.pool_handle == Some(pool_handle)),
+        None => false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contexts_are_isolated() {
+        let context = create().unwrap();
+
+        run_in(context, || {
+            with_settings_mut(|settings| settings.insert("key".to_string(), "value".to_string())).unwrap();
+            set_wallet_handle(WalletHandle(10)).unwrap();
+            set_pool_handle(Some(20)).unwrap();
+        });
+
+        run_in(context, || {
+            assert_eq!(Some("value".to_string()), with_settings(|settings| settings.get("key").cloned()));
+            assert_eq!(WalletHandle(10), get_wallet_handle());
+            assert_eq!(Some(20), get_pool_handle());
+        });
+
+        let other = create().unwrap();
+
+        run_in(other, || {
+            assert_eq!(None, with_settings(|settings| settings.get("key").cloned()));
+            assert_eq!(INVALID_WALLET_HANDLE, get_wallet_handle());
+            assert_eq!(None, get_pool_handle());
+        });
+
+        release(context).unwrap();
+        release(other).unwrap();
+    }
+
+    #[test]
+    fn test_is_pool_shared() {
+        let context = create().unwrap();
+        let other = create().unwrap();
+
+        run_in(context, || set_pool_handle(Some(30))).unwrap();
+        assert!(!run_in(context, is_pool_shared));
+
+        run_in(other, || set_pool_handle(Some(30))).unwrap();
+        assert!(run_in(context, is_pool_shared));
+
+        release(other).unwrap();
+        assert!(!run_in(context, is_pool_shared));
+
+        release(context).unwrap();
+    }
+
+    #[test]
+    fn test_bind_and_release() {
+        let context = create().unwrap();
+
+        bind(context).unwrap();
+        assert_eq!(context, current());
+
+        release(context).unwrap();
+        assert_eq!(DEFAULT_CONTEXT, current());
+        assert!(!exists(context));
+
+        assert_eq!(VcxErrorKind::InvalidContextHandle, bind(context).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidContextHandle, release(context).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidContextHandle, release(DEFAULT_CONTEXT).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_released_context_is_not_recreated() {
+        let context = create().unwrap();
+        release(context).unwrap();
+
+        run_in(context, || {
+            assert_eq!(VcxErrorKind::InvalidContextHandle, with_settings_mut(|settings| settings.clear()).unwrap_err().kind());
+            assert_eq!(VcxErrorKind::InvalidContextHandle, set_wallet_handle(WalletHandle(10)).unwrap_err().kind());
+            assert_eq!(VcxErrorKind::InvalidContextHandle, set_pool_handle(Some(20)).unwrap_err().kind());
+        });
+
+        assert!(!exists(context));
+    }
+
+    #[test]
+    fn test_run_in_restores_context_after_panic() {
+        let context = create().unwrap();
+
+        let res = ::std::panic::catch_unwind(|| run_in(context, || panic!("failed in context")));
+
+        assert!(res.is_err());
+        assert_eq!(DEFAULT_CONTEXT, current());
+
+        release(context).unwrap();
+    }
+}
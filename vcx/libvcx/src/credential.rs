@@ -33,6 +33,8 @@ use utils::{error, constants};
 
 use utils::agent_info::{get_agent_info, MyAgentInfo, get_agent_attr};
 use utils::httpclient::AgencyMock;
+use utils::serialization::{self, ObjectType};
+use events;
 
 use v3::{
     messages::issuance::credential_offer::CredentialOffer as CredentialOfferV3,
@@ -40,7 +42,7 @@ use v3::{
 };
 
 lazy_static! {
-    static ref HANDLE_MAP: ObjectCache<Credentials>  = Default::default();
+    static ref HANDLE_MAP: ObjectCache<Credentials> = ObjectCache::persisted("VcxCredential", _serialize, _deserialize);
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -485,8 +487,17 @@ pub fn credential_create_with_msgid(source_id: &str, connection_handle: u32, msg
     let offer = get_credential_offer_msg(connection_handle, &msg_id)?;
 
     let credential = if connection::is_v3_connection(connection_handle)? {
-        create_credential_v3(source_id, &offer)?
-            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidConnectionHandle, format!("Connection can not be used for Proprietary Issuance protocol")))?
+        let mut credential = create_credential_v3(source_id, &offer)?
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidConnectionHandle, format!("Connection can not be used for Proprietary Issuance protocol")))?;
+
+        // Failed auto-acceptance leaves the offer to the user
+        if let Credentials::V3(ref mut holder) = credential {
+            if let Err(err) = holder.auto_accept_offer(connection_handle) {
+                warn!("Credential Offer {} was not accepted automatically: {}", secret!(&msg_id), err);
+            }
+        }
+
+        credential
     } else {
         create_credential_v1(source_id, &offer)?
     };
@@ -498,7 +509,9 @@ pub fn credential_create_with_msgid(source_id: &str, connection_handle: u32, msg
 }
 
 pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
-    HANDLE_MAP.get_mut(handle, |obj| {
+    let prev_state = get_state(handle).unwrap_or_default();
+
+    let res = HANDLE_MAP.get_mut(handle, |obj| {
         match obj {
             Credentials::Pending(ref mut obj) => {
                 debug!("Credentials::Pending: updating state >>> state: {:?}", obj.state);
@@ -515,7 +528,35 @@ pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
                 Ok(error::SUCCESS.code_num)
             }
         }
-    })
+    })?;
+
+    _publish_state_change(handle, prev_state);
+    Ok(res)
+}
+
+fn _publish_state_change(handle: u32, prev_state: u32) {
+    if events::has_subscribers() {
+        if let (Ok(source_id), Ok(state)) = (get_source_id(handle), get_state(handle)) {
+            events::state_changed(ObjectType::Credential, handle, source_id, prev_state, state);
+        }
+    }
+}
+
+// Advances state with a message already downloaded from the agency (e.g. delivered in push notification)
+// without querying the agency for new messages.
+pub fn update_state_with_agency_message(handle: u32, connection_handle: u32, message: &str) -> VcxResult<u32> {
+    HANDLE_MAP.get(handle, |obj| {
+        match obj {
+            Credentials::V3(_) => Ok(()),
+            _ => Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Agency messages can be processed only for Aries credentials"))
+        }
+    })?;
+
+    let message = connection::decode_agency_message(connection_handle, message)?;
+
+    update_state(handle, Some(message))?;
+
+    get_state(handle)
 }
 
 pub fn get_credential(handle: u32) -> VcxResult<String> {
@@ -550,6 +591,8 @@ pub fn delete_credential(handle: u32) -> VcxResult<u32> {
     let source_id = get_source_id(handle).unwrap_or_default();
     trace!("Credential::delete_credential >>> credential_handle: {}, source_id: {}", handle, source_id);
 
+    let state = get_state(handle).unwrap_or_default();
+
     HANDLE_MAP.get(handle, |credential| {
         match credential {
             Credentials::Pending(_) => {
@@ -572,7 +615,10 @@ pub fn delete_credential(handle: u32) -> VcxResult<u32> {
         .map(|_| error::SUCCESS.code_num)
         .or(Err(VcxError::from(VcxErrorKind::InvalidCredentialHandle)))
         .and(release(handle))
-        .and_then(|_| Ok(error::SUCCESS.code_num))
+        .map(|_| {
+            events::credential_deleted(handle, source_id, state);
+            error::SUCCESS.code_num
+        })
 }
 
 pub fn get_payment_txn(handle: u32) -> VcxResult<PaymentTxn> {
@@ -601,6 +647,22 @@ pub fn get_credential_offer(handle: u32) -> VcxResult<String> {
     })
 }
 
+// Returns the Credential Preview of the offer together with issuer provided display hints
+pub fn get_credential_preview(handle: u32) -> VcxResult<String> {
+    HANDLE_MAP.get(handle, |obj| {
+        match obj {
+            Credentials::Pending(_) | Credentials::V1(_) => {
+                Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialHandle, "Cannot get credential preview for V1 object"))
+            }
+            Credentials::V3(ref obj) => {
+                let preview = obj.get_credential_preview()?;
+                ::serde_json::to_string(&preview)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize Credential Preview: {:?}", err)))
+            }
+        }
+    })
+}
+
 pub fn get_credential_id(handle: u32) -> VcxResult<String> {
     HANDLE_MAP.get(handle, |obj| {
         match obj {
@@ -821,15 +883,24 @@ pub fn release_all() {
     HANDLE_MAP.drain().ok();
 }
 
+pub fn get_handles() -> Vec<u32> {
+    HANDLE_MAP.handles()
+}
+
 pub fn is_valid_handle(handle: u32) -> bool {
     HANDLE_MAP.has_handle(handle)
 }
 
+fn _serialize(obj: &Credentials) -> VcxResult<String> {
+    serialization::serialize(ObjectType::Credential, obj)
+}
+
+fn _deserialize(data: &str) -> VcxResult<Credentials> {
+    serialization::deserialize(ObjectType::Credential, data)
+}
+
 pub fn to_string(handle: u32) -> VcxResult<String> {
-    HANDLE_MAP.get(handle, |obj| {
-        serde_json::to_string(obj)
-            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("cannot serialize Credential object: {:?}", err)))
-    })
+    HANDLE_MAP.get(handle, _serialize)
 }
 
 pub fn get_source_id(handle: u32) -> VcxResult<String> {
@@ -842,11 +913,18 @@ pub fn get_source_id(handle: u32) -> VcxResult<String> {
     }).map_err(handle_err)
 }
 
-pub fn from_string(credential_data: &str) -> VcxResult<u32> {
-    let credential: Credentials = serde_json::from_str(credential_data)
-        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Credential: {:?}", err)))?;
+// Thread of Aries credential exchange
+pub fn get_thread_id(handle: u32) -> VcxResult<Option<String>> {
+    HANDLE_MAP.get(handle, |obj| {
+        match obj {
+            Credentials::Pending(_) | Credentials::V1(_) => Ok(None),
+            Credentials::V3(ref obj) => Ok(Some(obj.get_thread_id()).filter(|thread_id| !thread_id.is_empty())),
+        }
+    }).map_err(handle_err)
+}
 
-    HANDLE_MAP.add(credential)
+pub fn from_string(credential_data: &str) -> VcxResult<u32> {
+    HANDLE_MAP.add(_deserialize(credential_data)?)
 }
 
 pub fn is_payment_required(handle: u32) -> VcxResult<bool> {
@@ -893,6 +971,20 @@ pub fn get_credential_status(handle: u32) -> VcxResult<u32> {
     })
 }
 
+pub fn get_problem_report(handle: u32) -> VcxResult<String> {
+    HANDLE_MAP.get(handle, |obj| {
+        match obj {
+            Credentials::Pending(_) => {
+                Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialHandle, "Cannot get problem report for Pending object"))
+            }
+            Credentials::V1(_) => {
+                Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialHandle, "Cannot get problem report for V1 object"))
+            }
+            Credentials::V3(ref obj) => obj.get_problem_report(),
+        }
+    })
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -948,6 +1040,16 @@ pub mod tests {
         assert!(handle > 0);
     }
 
+    #[test]
+    fn test_update_state_with_agency_message_is_not_supported_for_proprietary_credential() {
+        let _setup = SetupDefaults::init();
+
+        let handle = credential_create_with_offer("test_update_state_with_agency_message", constants::CREDENTIAL_OFFER_JSON).unwrap();
+
+        let err = update_state_with_agency_message(handle, 0, "{}").unwrap_err();
+        assert_eq!(err.kind(), VcxErrorKind::ActionNotSupported);
+    }
+
     #[test]
     fn test_credential_create_with_bad_offer() {
         let _setup = SetupDefaults::init();
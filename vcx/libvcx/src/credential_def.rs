@@ -122,7 +122,7 @@ fn _create_credentialdef(issuer_did: &str,
                 .ok_or(VcxError::from_msg(VcxErrorKind::InvalidRevocationDetails, "Invalid RevocationDetails: `max_creds` field not found"))?;
 
             let (rev_reg_id, rev_reg_def, rev_reg_entry) =
-                anoncreds::generate_rev_reg(&issuer_did, &cred_def_id, &tails_file, max_creds)
+                anoncreds::generate_rev_reg(&issuer_did, &cred_def_id, &tails_file, max_creds, anoncreds::DEFAULT_REV_REG_TAG)
                     .map_err(|err| err.map(VcxErrorKind::CreateCredDef, "Cannot create CredentialDefinition"))?;
 
             (Some(rev_reg_id), Some(rev_reg_def), Some(rev_reg_entry))
@@ -217,6 +217,9 @@ pub fn create_and_publish_credentialdef(source_id: String,
             let (rev_delta_payment, _) = anoncreds::publish_rev_reg_delta(&issuer_did, &rev_reg_id, &rev_reg_entry)
                 .map_err(|err| err.map(VcxErrorKind::InvalidRevocationEntry, "Cannot post RevocationEntry"))?;
 
+            // tails upload failure is reported in the revocation publisher status
+            ::revocation_publisher::add_rev_reg(&rev_reg_id, &rev_reg_def).ok();
+
             (rev_def_payment, rev_delta_payment)
         }
         _ => (None, None)
@@ -29,6 +29,7 @@ use messages::proofs::{
     },
 };
 use settings;
+use settings::AutoPresentProofs;
 use utils::error;
 use utils::constants::{CREDS_FROM_PROOF_REQ, DEFAULT_GENERATED_PROOF, DEFAULT_REJECTED_PROOF, NEW_PROOF_REQUEST_RESPONSE};
 use utils::libindy::cache::{get_rev_reg_cache, set_rev_reg_cache, RevRegCache, RevState};
@@ -42,9 +43,11 @@ use v3::{
 
 use utils::agent_info::{get_agent_info, MyAgentInfo, get_agent_attr};
 use utils::httpclient::AgencyMock;
+use utils::serialization::{self, ObjectType};
+use events;
 
 lazy_static! {
-    static ref HANDLE_MAP: ObjectCache<DisclosedProofs>  = Default::default();
+    static ref HANDLE_MAP: ObjectCache<DisclosedProofs> = ObjectCache::persisted("VcxDisclosedProof", _serialize, _deserialize);
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -591,6 +594,14 @@ fn create_proof_v3(source_id: &str, proof_req: &str) -> VcxResult<Option<Disclos
     Ok(None)
 }
 
+fn _auto_present(prover: &mut Prover, connection_handle: u32) -> VcxResult<bool> {
+    match connection::get_auto_accept(connection_handle)?.presentation_requests() {
+        AutoPresentProofs::Never => Ok(false),
+        AutoPresentProofs::SingleMatch => prover.present_single_match(connection_handle),
+        AutoPresentProofs::Always => prover.present_selected(connection_handle)
+    }
+}
+
 fn create_pending_proof(source_id: &str, proof_req: &str) -> VcxResult<DisclosedProofs> {
     trace!("create_pending_proof >>> source_id: {}, proof_req: {}", source_id, proof_req);
 
@@ -641,8 +652,17 @@ pub fn create_proof_with_msgid(source_id: &str, connection_handle: u32, msg_id:
     let proof_request = get_proof_request(connection_handle, &msg_id)?;
 
     let proof = if connection::is_v3_connection(connection_handle)? {
-        create_proof_v3(source_id, &proof_request)?
-            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidConnectionHandle, format!("Connection can not be used for Proprietary Issuance protocol")))?
+        let mut proof = create_proof_v3(source_id, &proof_request)?
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidConnectionHandle, format!("Connection can not be used for Proprietary Issuance protocol")))?;
+
+        // Failed auto-presentation leaves the request to the user
+        if let DisclosedProofs::V3(ref mut prover) = proof {
+            if let Err(err) = _auto_present(prover, connection_handle) {
+                warn!("Presentation Request {} was not answered automatically: {}", msg_id, err);
+            }
+        }
+
+        proof
     } else {
         create_proof_v1(source_id, &proof_request)?
     };
@@ -664,7 +684,9 @@ pub fn get_state(handle: u32) -> VcxResult<u32> {
 }
 
 pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
-    HANDLE_MAP.get_mut(handle, |obj| {
+    let prev_state = get_state(handle).unwrap_or_default();
+
+    let res = HANDLE_MAP.get_mut(handle, |obj| {
         match obj {
             DisclosedProofs::Pending(obj) => {
                 // update_state is just the same as get_state for disclosed_proof
@@ -679,21 +701,51 @@ pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
                 Ok(obj.state())
             }
         }
-    })
+    })?;
+
+    _publish_state_change(handle, prev_state);
+    Ok(res)
 }
 
-pub fn to_string(handle: u32) -> VcxResult<String> {
+fn _publish_state_change(handle: u32, prev_state: u32) {
+    if events::has_subscribers() {
+        if let (Ok(source_id), Ok(state)) = (get_source_id(handle), get_state(handle)) {
+            events::state_changed(ObjectType::DisclosedProof, handle, source_id, prev_state, state);
+        }
+    }
+}
+
+// Advances state with a message already downloaded from the agency (e.g. delivered in push notification)
+// without querying the agency for new messages.
+pub fn update_state_with_agency_message(handle: u32, connection_handle: u32, message: &str) -> VcxResult<u32> {
     HANDLE_MAP.get(handle, |obj| {
-        serde_json::to_string(obj)
-            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("cannot serialize DisclosedProof object: {:?}", err)))
-    })
+        match obj {
+            DisclosedProofs::V3(_) => Ok(()),
+            _ => Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Agency messages can be processed only for Aries proofs"))
+        }
+    })?;
+
+    let message = connection::decode_agency_message(connection_handle, message)?;
+
+    update_state(handle, Some(message))?;
+
+    get_state(handle)
 }
 
-pub fn from_string(proof_data: &str) -> VcxResult<u32> {
-    let proof: DisclosedProofs = serde_json::from_str(proof_data)
-        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("cannot deserialize DisclosedProofs object: {:?}", err)))?;
+fn _serialize(obj: &DisclosedProofs) -> VcxResult<String> {
+    serialization::serialize(ObjectType::DisclosedProof, obj)
+}
+
+fn _deserialize(data: &str) -> VcxResult<DisclosedProofs> {
+    serialization::deserialize(ObjectType::DisclosedProof, data)
+}
 
-    HANDLE_MAP.add(proof)
+pub fn to_string(handle: u32) -> VcxResult<String> {
+    HANDLE_MAP.get(handle, _serialize)
+}
+
+pub fn from_string(proof_data: &str) -> VcxResult<u32> {
+    HANDLE_MAP.add(_deserialize(proof_data)?)
 }
 
 pub fn release(handle: u32) -> VcxResult<()> {
@@ -704,6 +756,10 @@ pub fn release_all() {
     HANDLE_MAP.drain().ok();
 }
 
+pub fn get_handles() -> Vec<u32> {
+    HANDLE_MAP.handles()
+}
+
 pub fn generate_proof_msg(handle: u32) -> VcxResult<String> {
     HANDLE_MAP.get(handle, |obj| {
         match obj {
@@ -968,6 +1024,16 @@ pub fn get_source_id(handle: u32) -> VcxResult<String> {
     }).map_err(handle_err)
 }
 
+// Thread of Aries presentation exchange
+pub fn get_thread_id(handle: u32) -> VcxResult<Option<String>> {
+    HANDLE_MAP.get(handle, |obj| {
+        match obj {
+            DisclosedProofs::Pending(_) | DisclosedProofs::V1(_) => Ok(None),
+            DisclosedProofs::V3(ref obj) => Ok(Some(obj.get_thread_id()).filter(|thread_id| !thread_id.is_empty()))
+        }
+    }).map_err(handle_err)
+}
+
 pub fn get_presentation_status(handle: u32) -> VcxResult<u32> {
     HANDLE_MAP.get(handle, |obj| {
         match obj {
@@ -978,6 +1044,16 @@ pub fn get_presentation_status(handle: u32) -> VcxResult<u32> {
     })
 }
 
+pub fn get_problem_report(handle: u32) -> VcxResult<String> {
+    HANDLE_MAP.get(handle, |obj| {
+        match obj {
+            DisclosedProofs::Pending(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidDisclosedProofHandle, "Cannot get problem report for V1 DisclosedProof object")),
+            DisclosedProofs::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidDisclosedProofHandle, "Cannot get problem report for V1 DisclosedProof object")),
+            DisclosedProofs::V3(ref obj) => obj.get_problem_report()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     extern crate serde_json;
@@ -213,11 +213,17 @@ pub enum VcxErrorKind {
     UnknownLibndyError,
     #[fail(display = "No Agent pairwise information")]
     NoAgentInformation,
+    #[fail(display = "Provisioning token is invalid or expired")]
+    InvalidProvisioningToken,
+    #[fail(display = "Invalid Context Handle")]
+    InvalidContextHandle,
 }
 
 #[derive(Debug)]
 pub struct VcxError {
-    inner: Context<VcxErrorKind>
+    inner: Context<VcxErrorKind>,
+    // Original error code of the underlying library (libindy) or plugin
+    vendor_code: Option<i32>,
 }
 
 impl Fail for VcxError {
@@ -250,7 +256,7 @@ impl fmt::Display for VcxError {
 impl VcxError {
     pub fn from_msg<D>(kind: VcxErrorKind, msg: D) -> VcxError
         where D: fmt::Display + fmt::Debug + Send + Sync + 'static {
-        VcxError { inner: Context::new(msg).context(kind) }
+        VcxError { inner: Context::new(msg).context(kind), vendor_code: None }
     }
 
     pub fn kind(&self) -> VcxErrorKind {
@@ -260,12 +266,27 @@ impl VcxError {
     pub fn extend<D>(self, msg: D) -> VcxError
         where D: fmt::Display + fmt::Debug + Send + Sync + 'static {
         let kind = self.kind();
-        VcxError { inner: self.inner.map(|_| msg).context(kind) }
+        VcxError { inner: self.inner.map(|_| msg).context(kind), vendor_code: self.vendor_code }
     }
 
     pub fn map<D>(self, kind: VcxErrorKind, msg: D) -> VcxError
         where D: fmt::Display + fmt::Debug + Send + Sync + 'static {
-        VcxError { inner: self.inner.map(|_| msg).context(kind) }
+        VcxError { inner: self.inner.map(|_| msg).context(kind), vendor_code: self.vendor_code }
+    }
+
+    pub fn with_vendor_code(self, vendor_code: i32) -> VcxError {
+        VcxError { vendor_code: Some(vendor_code), ..self }
+    }
+
+    pub fn vendor_code(&self) -> Option<i32> {
+        self.vendor_code
+    }
+
+    // Messages of the error chain from the outermost context to the root cause
+    pub fn context_chain(&self) -> Vec<String> {
+        dyn Fail::iter_chain(&self.inner)
+            .map(|cause| cause.to_string())
+            .collect()
     }
 }
 
@@ -282,7 +303,7 @@ impl From<VcxErrorKind> for VcxError {
 
 impl From<Context<VcxErrorKind>> for VcxError {
     fn from(inner: Context<VcxErrorKind>) -> VcxError {
-        VcxError { inner }
+        VcxError { inner, vendor_code: None }
     }
 }
 
@@ -381,6 +402,8 @@ impl From<VcxErrorKind> for u32 {
             VcxErrorKind::Common(num) => num,
             VcxErrorKind::LibndyError(num) => num,
             VcxErrorKind::NoAgentInformation => error::NO_AGENT_INFO.code_num,
+            VcxErrorKind::InvalidProvisioningToken => error::INVALID_PROVISIONING_TOKEN.code_num,
+            VcxErrorKind::InvalidContextHandle => error::INVALID_CONTEXT_HANDLE.code_num,
         }
     }
 }
@@ -423,11 +446,17 @@ pub fn reset_current_error() {
 
 pub fn set_current_error(err: &VcxError) {
     CURRENT_ERROR_C_JSON.try_with(|error| {
+        let code: u32 = err.kind().into();
         let error_json = json!({
+            "code": code,
             "error": err.kind().to_string(),
             "message": err.to_string(),
             "cause": dyn Fail::find_root_cause(err).to_string(),
-            "backtrace": err.backtrace().map(|bt| bt.to_string())
+            "backtrace": err.backtrace().map(|bt| bt.to_string()),
+            "extra": {
+                "context": err.context_chain(),
+                "vendor_code": err.vendor_code(),
+            }
         }).to_string();
         error.replace(Some(CStringUtils::string_to_cstring(error_json)));
     })
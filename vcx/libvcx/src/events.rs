@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use api::VcxStateType;
+use context::{self, ContextHandle};
+use error::prelude::*;
+use utils::serialization::ObjectType;
+
+/*
+    In-process bus of protocol events.
+    Every state change of a Connection, Credential, IssuerCredential, Proof or DisclosedProof object done while
+    processing messages (`update_state` functions) is published as `state_changed` event. Milestones of protocols
+    additionally emit dedicated events (e.g. `credential_received`), so applications can react on them without
+    tracking states of objects themselves.
+
+    Handlers are called synchronously on the thread which processed the message, after the object is released,
+    so they are allowed to call VCX functions for the object but should return fast.
+    Subscriptions belong to the context they are made in and receive events of objects of that context only.
+*/
+
+lazy_static! {
+    static ref HANDLERS: Mutex<HashMap<ContextHandle, HashMap<u32, Subscription>>> = Default::default();
+}
+
+static NEXT_HANDLER_HANDLE: AtomicUsize = AtomicUsize::new(1);
+
+// Receives handle of the subscription and the event
+pub type EventHandler = Arc<dyn Fn(u32, &Event) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    StateChanged,
+    ConnectionEstablished,
+    CredentialOfferReceived,
+    CredentialReceived,
+    CredentialIssued,
+    ProofRequestReceived,
+    ProofVerified,
+    CredentialDeleted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Event {
+    pub event_type: EventType,
+    pub object_type: ObjectType,
+    pub handle: u32,
+    pub source_id: String,
+    pub prev_state: u32,
+    pub state: u32,
+}
+
+impl Event {
+    pub fn to_json(&self) -> String {
+        json!(self).to_string()
+    }
+}
+
+struct Subscription {
+    // None means all event types
+    event_types: Option<Vec<EventType>>,
+    handler: EventHandler,
+}
+
+impl Subscription {
+    fn accepts(&self, event_type: EventType) -> bool {
+        self.event_types.as_ref()
+            .map(|event_types| event_types.contains(&event_type))
+            .unwrap_or(true)
+    }
+}
+
+pub fn parse_event_types(event_types: &str) -> VcxResult<Vec<EventType>> {
+    ::serde_json::from_str(event_types)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Invalid event types: {}", err)))
+}
+
+pub fn subscribe(event_types: Option<Vec<EventType>>, handler: EventHandler) -> u32 {
+    let handle = NEXT_HANDLER_HANDLE.fetch_add(1, Ordering::SeqCst) as u32;
+
+    HANDLERS.lock().unwrap()
+        .entry(context::current())
+        .or_insert_with(HashMap::new)
+        .insert(handle, Subscription { event_types, handler });
+
+    trace!("events::subscribe <<< handle: {}", handle);
+    handle
+}
+
+pub fn unsubscribe(handle: u32) -> VcxResult<()> {
+    trace!("events::unsubscribe >>> handle: {}", handle);
+
+    HANDLERS.lock().unwrap()
+        .get_mut(&context::current())
+        .and_then(|subscriptions| subscriptions.remove(&handle))
+        .map(|_| ())
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Event handler {} is not registered", handle)))
+}
+
+// Removes subscriptions of the current context
+pub fn unsubscribe_all() {
+    HANDLERS.lock().unwrap().remove(&context::current());
+}
+
+pub fn has_subscribers() -> bool {
+    HANDLERS.lock().unwrap()
+        .get(&context::current())
+        .map(|subscriptions| !subscriptions.is_empty())
+        .unwrap_or(false)
+}
+
+// Publishes events for the object if its state was changed
+pub fn state_changed(object_type: ObjectType, handle: u32, source_id: String, prev_state: u32, state: u32) {
+    if prev_state == state {
+        return;
+    }
+
+    let mut event = Event { event_type: EventType::StateChanged, object_type, handle, source_id, prev_state, state };
+    publish(&event);
+
+    if let Some(event_type) = _milestone(object_type, state) {
+        event.event_type = event_type;
+        publish(&event);
+    }
+}
+
+// Publishes event about deletion of the credential (and related records) from the wallet.
+// The object is already released, so state of the event is the last state of the object.
+pub fn credential_deleted(handle: u32, source_id: String, state: u32) {
+    publish(&Event { event_type: EventType::CredentialDeleted, object_type: ObjectType::Credential, handle, source_id, prev_state: state, state });
+}
+
+pub fn publish(event: &Event) {
+    trace!("events::publish >>> event: {:?}", event);
+
+    // handlers are collected first so they can subscribe or unsubscribe while being called
+    let handlers: Vec<(u32, EventHandler)> = match HANDLERS.lock().unwrap().get(&context::current()) {
+        Some(subscriptions) => subscriptions
+            .iter()
+            .filter(|(_, subscription)| subscription.accepts(event.event_type))
+            .map(|(handle, subscription)| (*handle, subscription.handler.clone()))
+            .collect(),
+        None => return
+    };
+
+    for (handle, handler) in handlers {
+        handler(handle, event);
+    }
+}
+
+fn _milestone(object_type: ObjectType, state: u32) -> Option<EventType> {
+    let state = VcxStateType::from_u32(state);
+
+    match (object_type, state) {
+        (ObjectType::Connection, VcxStateType::VcxStateAccepted) => Some(EventType::ConnectionEstablished),
+        (ObjectType::Credential, VcxStateType::VcxStateRequestReceived) => Some(EventType::CredentialOfferReceived),
+        (ObjectType::Credential, VcxStateType::VcxStateAccepted) => Some(EventType::CredentialReceived),
+        (ObjectType::IssuerCredential, VcxStateType::VcxStateAccepted) => Some(EventType::CredentialIssued),
+        (ObjectType::DisclosedProof, VcxStateType::VcxStateRequestReceived) => Some(EventType::ProofRequestReceived),
+        (ObjectType::Proof, VcxStateType::VcxStateAccepted) => Some(EventType::ProofVerified),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use utils::devsetup::SetupDefaults;
+
+    // events of objects of other tests running in parallel are ignored
+    fn _collecting_handler(source_id: &'static str, events: Arc<Mutex<Vec<Event>>>) -> EventHandler {
+        Arc::new(move |_, event: &Event| if event.source_id == source_id { events.lock().unwrap().push(event.clone()) })
+    }
+
+    #[test]
+    fn test_state_changed_publishes_events() {
+        let _setup = SetupDefaults::init();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let handle = subscribe(None, _collecting_handler("events_test_all", events.clone()));
+
+        state_changed(ObjectType::Credential, 101, String::from("events_test_all"), 3, 3);
+        assert_eq!(0, events.lock().unwrap().len());
+
+        state_changed(ObjectType::Credential, 101, String::from("events_test_all"), 3, 4);
+        {
+            let events = events.lock().unwrap();
+            assert_eq!(2, events.len());
+            assert_eq!(EventType::StateChanged, events[0].event_type);
+            assert_eq!(EventType::CredentialReceived, events[1].event_type);
+            assert_eq!(101, events[1].handle);
+            assert_eq!(4, events[1].state);
+        }
+
+        unsubscribe(handle).unwrap();
+        state_changed(ObjectType::Credential, 101, String::from("events_test_all"), 4, 7);
+        assert_eq!(2, events.lock().unwrap().len());
+    }
+
+    #[test]
+    fn test_credential_deleted_publishes_event() {
+        let _setup = SetupDefaults::init();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let event_types = parse_event_types(r#"["credential_deleted"]"#).unwrap();
+        let handle = subscribe(Some(event_types), _collecting_handler("events_test_deleted", events.clone()));
+
+        credential_deleted(7, String::from("events_test_deleted"), 4);
+
+        {
+            let events = events.lock().unwrap();
+            assert_eq!(1, events.len());
+            assert_eq!(EventType::CredentialDeleted, events[0].event_type);
+            assert_eq!(ObjectType::Credential, events[0].object_type);
+            assert_eq!(7, events[0].handle);
+            assert_eq!(4, events[0].state);
+        }
+
+        unsubscribe(handle).unwrap();
+    }
+
+    #[test]
+    fn test_subscription_filters_event_types() {
+        let _setup = SetupDefaults::init();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let event_types = parse_event_types(r#"["proof_verified"]"#).unwrap();
+        let handle = subscribe(Some(event_types), _collecting_handler("events_test_filtered", events.clone()));
+
+        state_changed(ObjectType::Connection, 1, String::from("events_test_filtered"), 2, 4);
+        state_changed(ObjectType::Proof, 2, String::from("events_test_filtered"), 2, 4);
+
+        {
+            let events = events.lock().unwrap();
+            assert_eq!(1, events.len());
+            assert_eq!(EventType::ProofVerified, events[0].event_type);
+            assert_eq!(ObjectType::Proof, events[0].object_type);
+        }
+
+        unsubscribe(handle).unwrap();
+    }
+
+    #[test]
+    fn test_subscriptions_are_isolated_per_context() {
+        let _setup = SetupDefaults::init();
+
+        let other = context::create().unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let handle = subscribe(None, _collecting_handler("events_test_context", events.clone()));
+        let other_events = Arc::new(Mutex::new(Vec::new()));
+        let other_handle = context::run_in(other, || subscribe(None, _collecting_handler("events_test_context", other_events.clone())));
+
+        context::run_in(other, || state_changed(ObjectType::Connection, 1, String::from("events_test_context"), 2, 3));
+        assert_eq!(0, events.lock().unwrap().len());
+        assert_eq!(1, other_events.lock().unwrap().len());
+
+        assert_eq!(VcxErrorKind::InvalidHandle, unsubscribe(other_handle).unwrap_err().kind());
+
+        context::run_in(other, unsubscribe_all);
+        assert!(!context::run_in(other, has_subscribers));
+        assert!(has_subscribers());
+
+        unsubscribe(handle).unwrap();
+        context::release(other).unwrap();
+    }
+
+    #[test]
+    fn test_unsubscribe_fails_for_unknown_handle() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(VcxErrorKind::InvalidHandle, unsubscribe(0).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidOption, parse_event_types(r#"["unknown"]"#).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_event_to_json() {
+        let event = Event { event_type: EventType::ConnectionEstablished, object_type: ObjectType::Connection, handle: 1, source_id: String::from("alice"), prev_state: 3, state: 4 };
+        let json: ::serde_json::Value = ::serde_json::from_str(&event.to_json()).unwrap();
+
+        assert_eq!(json!("connection_established"), json["event_type"]);
+        assert_eq!(json!("connection"), json["object_type"]);
+    }
+}
@@ -16,14 +16,18 @@ use utils::constants::CRED_MSG;
 use utils::openssl::encode;
 use utils::libindy::payments::PaymentTxn;
 use utils::qualifier;
+use utils::serialization::{self, ObjectType};
+use events;
 use object_cache::ObjectCache;
 use error::prelude::*;
 
 use v3::handlers::issuance::Issuer;
+use v3::messages::issuance::DisplayHints;
+use v3::messages::issuance::consent::ConsentTemplate;
 use utils::agent_info::{get_agent_info, MyAgentInfo, get_agent_attr};
 
 lazy_static! {
-    static ref ISSUER_CREDENTIAL_MAP: ObjectCache < IssuerCredentials > = Default::default();
+    static ref ISSUER_CREDENTIAL_MAP: ObjectCache<IssuerCredentials> = ObjectCache::persisted("VcxIssuerCredential", _serialize, _deserialize);
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -75,6 +79,14 @@ pub struct IssuerCredential {
     agent_did: Option<String>,
     agent_vk: Option<String>,
     thread: Option<Thread>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    derived_from: Option<DerivationLink>,
+    // Display hints to apply to the credential preview once the object is converted to Aries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_hints: Option<String>,
+    // Consent template to ask the Holder for once the object is converted to Aries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    consent_template: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -134,6 +146,58 @@ impl PaymentInfo {
     }
 }
 
+// Links a derived credential to the master IssuerCredential its values were taken from.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DerivationLink {
+    pub source_id: String,
+    // derived attribute name -> master attribute name (None for constant values)
+    pub attributes: HashMap<String, Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DerivedAttribute {
+    Source(String),
+    Constant { value: String },
+    Transformed {
+        from: String,
+        #[serde(default)]
+        transform: AttributeTransform,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AttributeTransform {
+    Copy,
+    Uppercase,
+    Lowercase,
+    Substring { start: usize, length: Option<usize> },
+}
+
+impl Default for AttributeTransform {
+    fn default() -> Self {
+        AttributeTransform::Copy
+    }
+}
+
+impl AttributeTransform {
+    fn apply(&self, value: &str) -> String {
+        match self {
+            AttributeTransform::Copy => value.to_string(),
+            AttributeTransform::Uppercase => value.to_uppercase(),
+            AttributeTransform::Lowercase => value.to_lowercase(),
+            AttributeTransform::Substring { start, length } => {
+                let chars = value.chars().skip(*start);
+                match length {
+                    Some(length) => chars.take(*length).collect(),
+                    None => chars.collect(),
+                }
+            }
+        }
+    }
+}
+
 impl IssuerCredential {
     pub fn create(cred_def_handle: u32,
                   source_id: String,
@@ -178,6 +242,9 @@ impl IssuerCredential {
             their_vk: None,
             agent_did: None,
             agent_vk: None,
+            derived_from: None,
+            display_hints: None,
+            consent_template: None,
         };
         apply_agent_info(&mut issuer_credential, &get_agent_info()?);
 
@@ -417,12 +484,20 @@ impl IssuerCredential {
             .as_ref()
             .ok_or(VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, "Invalid Credential: `credential_request` field not found"))?;
 
-        let (cred, cred_revoc_id, revoc_reg_delta_json) =
-            anoncreds::libindy_issuer_create_credential(&indy_cred_offer.libindy_offer,
-                                                        &indy_cred_req.libindy_cred_req,
-                                                        &credential_data,
-                                                        self.rev_reg_id.clone(),
-                                                        self.tails_file.clone())?;
+        let (cred, cred_revoc_id, revoc_reg_delta_json, rev_reg_id) =
+            anoncreds::issuer_create_credential_rotating(&indy_cred_offer.libindy_offer,
+                                                         &indy_cred_req.libindy_cred_req,
+                                                         &credential_data,
+                                                         self.rev_reg_id.clone(),
+                                                         self.tails_file.clone())?;
+
+        if rev_reg_id != self.rev_reg_id {
+            if let Some(ref rev_reg_id) = rev_reg_id {
+                let (_, rev_reg_def_json) = anoncreds::get_rev_reg_def_json(rev_reg_id)?;
+                self.rev_reg_def_json = Some(rev_reg_def_json);
+            }
+            self.rev_reg_id = rev_reg_id;
+        }
 
         self.cred_rev_id = cred_revoc_id.clone();
 
@@ -675,8 +750,161 @@ pub fn issuer_credential_create(cred_def_handle: u32,
     Ok(handle)
 }
 
-pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
+/*
+    Derivation: json, derived attribute name -> rule
+    {
+        "name": "full_name",                                                          // copy value of master attribute
+        "birth_year": {"from": "dob", "transform": {"type": "substring", "start": 0, "length": 4}},
+        "country": {"from": "country", "transform": {"type": "uppercase"}},           // uppercase, lowercase, substring
+        "level": {"value": "summary"}                                                 // constant value
+    }
+
+    Output: credential data with derived attributes and link to master attributes
+*/
+pub fn derive_credential_data(master_data: &str, derivation: &str) -> VcxResult<(String, HashMap<String, Option<String>>)> {
+    let master_values = get_raw_attributes(master_data)?;
+
+    let derivation: HashMap<String, DerivedAttribute> = serde_json::from_str(derivation)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize credential derivation: {}", err)))?;
+
+    if derivation.is_empty() {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, "Credential derivation must contain at least one attribute"));
+    }
+
+    let mut credential_data = serde_json::Map::new();
+    let mut attributes = HashMap::new();
+
+    for (name, rule) in derivation {
+        let (value, source) = match rule {
+            DerivedAttribute::Constant { value } => (value, None),
+            DerivedAttribute::Source(from) => (_get_master_value(&master_values, &from)?.to_string(), Some(from)),
+            DerivedAttribute::Transformed { from, transform } => (transform.apply(_get_master_value(&master_values, &from)?), Some(from)),
+        };
+
+        credential_data.insert(name.clone(), serde_json::Value::String(value));
+        attributes.insert(name, source);
+    }
+
+    let credential_data = serde_json::to_string(&credential_data)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize derived credential data: {}", err)))?;
+
+    Ok((credential_data, attributes))
+}
+
+fn get_raw_attributes(credential_data: &str) -> VcxResult<HashMap<String, String>> {
+    let attributes: HashMap<String, serde_json::Value> = serde_json::from_str(credential_data)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize credential attributes: {}", err)))?;
+
+    attributes
+        .into_iter()
+        .map(|(name, value)| {
+            let raw = match value {
+                serde_json::Value::String(value) => Some(value),
+                serde_json::Value::Array(values) => values.get(0).and_then(serde_json::Value::as_str).map(String::from),
+                _ => None
+            };
+
+            raw.map(|raw| (name.clone(), raw))
+                .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Attribute value not found: {}", name)))
+        })
+        .collect()
+}
+
+fn _get_master_value<'a>(master_values: &'a HashMap<String, String>, name: &str) -> VcxResult<&'a str> {
+    master_values.get(name)
+        .map(String::as_str)
+        .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Master credential does not contain attribute: {}", name)))
+}
+
+pub fn issuer_credential_create_derived(master_handle: u32,
+                                        cred_def_handle: u32,
+                                        source_id: String,
+                                        issuer_did: String,
+                                        credential_name: String,
+                                        derivation: String,
+                                        price: u64) -> VcxResult<u32> {
+    trace!("issuer_credential_create_derived >>> master_handle: {}, cred_def_handle: {}, source_id: {}, issuer_did: {}, credential_name: {}, derivation: {}, price: {}",
+           master_handle, cred_def_handle, source_id, issuer_did, credential_name, derivation, price);
+
+    if !is_valid_handle(master_handle) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, format!("Invalid master credential handle: {}", master_handle)));
+    }
+
+    let (master_source_id, master_data) = ISSUER_CREDENTIAL_MAP.get(master_handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(ref obj) => Ok((obj.get_source_id().to_string(), obj.get_credential_attributes().to_string())),
+            IssuerCredentials::V1(ref obj) => Ok((obj.get_source_id().to_string(), obj.get_credential_attributes().to_string())),
+            IssuerCredentials::V3(ref obj) => Ok((obj.get_source_id()?, obj.get_credential_data()?)),
+        }
+    })?;
+
+    let (credential_data, attributes) = derive_credential_data(&master_data, &derivation)?;
+
+    let handle = issuer_credential_create(cred_def_handle, source_id, issuer_did, credential_name, credential_data, price)?;
+
+    let derived_from = DerivationLink { source_id: master_source_id, attributes };
+
+    ISSUER_CREDENTIAL_MAP.get_mut(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(ref mut obj) => obj.derived_from = Some(derived_from.clone()),
+            IssuerCredentials::V1(ref mut obj) => obj.derived_from = Some(derived_from.clone()),
+            IssuerCredentials::V3(ref mut obj) => obj.set_derived_from(Some(derived_from.clone())),
+        };
+        Ok(())
+    })?;
+
+    Ok(handle)
+}
+
+pub fn get_derived_from(handle: u32) -> VcxResult<Option<DerivationLink>> {
+    ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(ref obj) => Ok(obj.derived_from.clone()),
+            IssuerCredentials::V1(ref obj) => Ok(obj.derived_from.clone()),
+            IssuerCredentials::V3(ref obj) => Ok(obj.get_derived_from().cloned()),
+        }
+    })
+}
+
+pub fn set_display_hints(handle: u32, display_hints: &str) -> VcxResult<()> {
     ISSUER_CREDENTIAL_MAP.get_mut(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(ref mut obj) => {
+                let _: HashMap<String, DisplayHints> = serde_json::from_str(display_hints)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Display Hints: {:?}", err)))?;
+                obj.display_hints = Some(display_hints.to_string());
+                Ok(())
+            }
+            IssuerCredentials::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, "Display hints are not supported for V1 Credential object")),
+            IssuerCredentials::V3(ref mut obj) => obj.set_display_hints(display_hints),
+        }
+    })
+}
+
+pub fn set_consent_template(handle: u32, consent_template: &str) -> VcxResult<()> {
+    ISSUER_CREDENTIAL_MAP.get_mut(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(ref mut obj) => {
+                ConsentTemplate::from_json(consent_template)?;
+                obj.consent_template = Some(consent_template.to_string());
+                Ok(())
+            }
+            IssuerCredentials::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, "Consent receipts are not supported for V1 Credential object")),
+            IssuerCredentials::V3(ref mut obj) => obj.set_consent_template(consent_template),
+        }
+    })
+}
+
+// Consent receipts signed by Holders are kept in the wallet and are not bound to the Credential object lifetime
+pub fn get_consent_receipts(query: Option<&str>) -> VcxResult<String> {
+    let receipts = ::v3::handlers::issuance::consent::get_receipts(query)?;
+    Ok(json!(receipts).to_string())
+}
+
+pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
+    let prev_state = get_state(handle).unwrap_or_default();
+
+    let res = ISSUER_CREDENTIAL_MAP.get_mut(handle, |obj| {
         match obj {
             IssuerCredentials::Pending(ref mut obj) => {
                 obj.update_state(message.clone())
@@ -691,7 +919,35 @@ pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
                 obj.get_state()
             }
         }
-    })
+    })?;
+
+    _publish_state_change(handle, prev_state);
+    Ok(res)
+}
+
+fn _publish_state_change(handle: u32, prev_state: u32) {
+    if events::has_subscribers() {
+        if let (Ok(source_id), Ok(state)) = (get_source_id(handle), get_state(handle)) {
+            events::state_changed(ObjectType::IssuerCredential, handle, source_id, prev_state, state);
+        }
+    }
+}
+
+// Advances state with a message already downloaded from the agency (e.g. delivered in push notification)
+// without querying the agency for new messages.
+pub fn update_state_with_agency_message(handle: u32, connection_handle: u32, message: &str) -> VcxResult<u32> {
+    ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
+        match obj {
+            IssuerCredentials::V3(_) => Ok(()),
+            _ => Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Agency messages can be processed only for Aries credentials"))
+        }
+    })?;
+
+    let message = connection::decode_agency_message(connection_handle, message)?;
+
+    update_state(handle, Some(message))?;
+
+    get_state(handle)
 }
 
 pub fn get_state(handle: u32) -> VcxResult<u32> {
@@ -714,6 +970,16 @@ pub fn get_credential_status(handle: u32) -> VcxResult<u32> {
     })
 }
 
+pub fn get_problem_report(handle: u32) -> VcxResult<String> {
+    ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, "Problem reports are not supported for V1 Credential object")),
+            IssuerCredentials::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, "Problem reports are not supported for V1 Credential object")),
+            IssuerCredentials::V3(ref obj) => obj.get_problem_report(),
+        }
+    })
+}
+
 pub fn release(handle: u32) -> VcxResult<()> {
     ISSUER_CREDENTIAL_MAP.release(handle)
         .or(Err(VcxError::from(VcxErrorKind::InvalidIssuerCredentialHandle)))
@@ -723,22 +989,28 @@ pub fn release_all() {
     ISSUER_CREDENTIAL_MAP.drain().ok();
 }
 
+pub fn get_handles() -> Vec<u32> {
+    ISSUER_CREDENTIAL_MAP.handles()
+}
+
 pub fn is_valid_handle(handle: u32) -> bool {
     ISSUER_CREDENTIAL_MAP.has_handle(handle)
 }
 
+fn _serialize(obj: &IssuerCredentials) -> VcxResult<String> {
+    serialization::serialize(ObjectType::IssuerCredential, obj)
+}
+
+fn _deserialize(data: &str) -> VcxResult<IssuerCredentials> {
+    serialization::deserialize(ObjectType::IssuerCredential, data)
+}
+
 pub fn to_string(handle: u32) -> VcxResult<String> {
-    ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
-        serde_json::to_string(obj)
-            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("cannot serialize IssuerCredential object: {:?}", err)))
-    })
+    ISSUER_CREDENTIAL_MAP.get(handle, _serialize)
 }
 
 pub fn from_string(credential_data: &str) -> VcxResult<u32> {
-    let issuer_credential: IssuerCredentials = serde_json::from_str(credential_data)
-        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize IssuerCredential: {:?}", err)))?;
-
-    ISSUER_CREDENTIAL_MAP.add(issuer_credential)
+    ISSUER_CREDENTIAL_MAP.add(_deserialize(credential_data)?)
 }
 
 pub fn generate_credential_offer_msg(handle: u32) -> VcxResult<(String, String)> {
@@ -758,6 +1030,13 @@ pub fn send_credential_offer(handle: u32, connection_handle: u32) -> VcxResult<u
                 // if Aries connection is established --> Convert Pending object to Aries credential
                 if ::connection::is_v3_connection(connection_handle)? {
                     let mut issuer = Issuer::create(obj.cred_def_handle, &obj.credential_attributes, &obj.source_id)?;
+                    issuer.set_derived_from(obj.derived_from.clone());
+                    if let Some(ref display_hints) = obj.display_hints {
+                        issuer.set_display_hints(display_hints)?;
+                    }
+                    if let Some(ref consent_template) = obj.consent_template {
+                        issuer.set_consent_template(consent_template)?;
+                    }
                     issuer.send_credential_offer(connection_handle)?;
 
                     IssuerCredentials::V3(issuer)
@@ -817,6 +1096,16 @@ pub fn revoke_credential(handle: u32) -> VcxResult<()> {
     })
 }
 
+pub fn send_revocation_notification(handle: u32, connection_handle: u32, comment: Option<String>) -> VcxResult<()> {
+    ISSUER_CREDENTIAL_MAP.get_mut(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, "Revocation notifications are not supported for V1 Credential object")),
+            IssuerCredentials::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, "Revocation notifications are not supported for V1 Credential object")),
+            IssuerCredentials::V3(ref mut obj) => obj.send_revocation_notification(connection_handle, comment.clone()),
+        }
+    })
+}
+
 pub fn convert_to_map(s: &str) -> VcxResult<serde_json::Map<String, serde_json::Value>> {
     serde_json::from_str(s)
         .map_err(|_| {
@@ -845,6 +1134,16 @@ pub fn get_source_id(handle: u32) -> VcxResult<String> {
     })
 }
 
+// Thread of Aries credential exchange
+pub fn get_thread_id(handle: u32) -> VcxResult<Option<String>> {
+    ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
+        match obj {
+            IssuerCredentials::Pending(_) | IssuerCredentials::V1(_) => Ok(None),
+            IssuerCredentials::V3(ref obj) => Ok(Some(obj.get_thread_id()).filter(|thread_id| !thread_id.is_empty()))
+        }
+    })
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -932,6 +1231,9 @@ pub mod tests {
             their_vk: None,
             agent_did: None,
             agent_vk: None,
+            derived_from: None,
+            display_hints: None,
+            consent_template: None,
         };
         apply_agent_info(&mut issuer_credential, &default_agent_info(connection_handle));
         issuer_credential
@@ -974,6 +1276,9 @@ pub mod tests {
             their_vk: None,
             agent_did: None,
             agent_vk: None,
+            derived_from: None,
+            display_hints: None,
+            consent_template: None,
         };
 
         apply_agent_info(&mut credential, &default_agent_info(connection_handle));
@@ -1018,6 +1323,9 @@ pub mod tests {
             their_vk: None,
             agent_did: None,
             agent_vk: None,
+            derived_from: None,
+            display_hints: None,
+            consent_template: None,
         };
 
         apply_agent_info(&mut issuer_credential, &get_agent_info().unwrap());
@@ -1055,6 +1363,114 @@ pub mod tests {
         assert!(handle > 0);
     }
 
+    #[test]
+    fn test_issuer_credential_create_derived_succeeds() {
+        let _setup = SetupMocks::init();
+
+        let master_handle = issuer_credential_create(create_cred_def_fake(),
+                                                     "master".to_string(),
+                                                     "8XFh8yBzrpJQmNyZzgoTqB".to_owned(),
+                                                     "credential_name".to_string(),
+                                                     json!({"name": "Alice Smith", "dob": "1990-05-01", "country": ["us"]}).to_string(),
+                                                     0).unwrap();
+
+        let derivation = json!({
+            "name": "name",
+            "birth_year": {"from": "dob", "transform": {"type": "substring", "start": 0, "length": 4}},
+            "country": {"from": "country", "transform": {"type": "uppercase"}},
+            "level": {"value": "summary"}
+        }).to_string();
+
+        let handle = issuer_credential_create_derived(master_handle,
+                                                      create_cred_def_fake(),
+                                                      "derived".to_string(),
+                                                      "8XFh8yBzrpJQmNyZzgoTqB".to_owned(),
+                                                      "summary".to_string(),
+                                                      derivation,
+                                                      0).unwrap();
+
+        let attributes: Value = serde_json::from_str(&get_credential_attributes(handle).unwrap()).unwrap();
+        assert_eq!(json!({"name": "Alice Smith", "birth_year": "1990", "country": "US", "level": "summary"}), attributes);
+
+        let derived_from = get_derived_from(handle).unwrap().unwrap();
+        assert_eq!("master", derived_from.source_id);
+        assert_eq!(Some("dob".to_string()), derived_from.attributes["birth_year"]);
+        assert_eq!(None, derived_from.attributes["level"]);
+
+        let handle = from_string(&to_string(handle).unwrap()).unwrap();
+        assert_eq!(Some(derived_from), get_derived_from(handle).unwrap());
+
+        assert_eq!(None, get_derived_from(master_handle).unwrap());
+    }
+
+    #[test]
+    fn test_issuer_credential_create_derived_fails_for_unknown_master_attribute() {
+        let _setup = SetupMocks::init();
+
+        let master_handle = _issuer_credential_create();
+
+        let res = issuer_credential_create_derived(master_handle,
+                                                   create_cred_def_fake(),
+                                                   "derived".to_string(),
+                                                   "8XFh8yBzrpJQmNyZzgoTqB".to_owned(),
+                                                   "summary".to_string(),
+                                                   json!({"name": "name"}).to_string(),
+                                                   0);
+        assert_eq!(VcxErrorKind::InvalidAttributesStructure, res.unwrap_err().kind());
+
+        let res = issuer_credential_create_derived(0,
+                                                   create_cred_def_fake(),
+                                                   "derived".to_string(),
+                                                   "8XFh8yBzrpJQmNyZzgoTqB".to_owned(),
+                                                   "summary".to_string(),
+                                                   json!({"attr": "attr"}).to_string(),
+                                                   0);
+        assert_eq!(VcxErrorKind::InvalidIssuerCredentialHandle, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_set_display_hints_works_for_pending_credential() {
+        let _setup = SetupMocks::init();
+
+        let handle = _issuer_credential_create();
+
+        let res = set_display_hints(handle, "invalid");
+        assert_eq!(VcxErrorKind::InvalidJson, res.unwrap_err().kind());
+
+        set_display_hints(handle, &json!({"attr": {"label": "Attribute", "order": 1}}).to_string()).unwrap();
+
+        ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
+            match obj {
+                IssuerCredentials::Pending(ref obj) => assert!(obj.display_hints.is_some()),
+                _ => panic!("Unexpected IssuerCredential object"),
+            }
+            Ok(())
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_set_consent_template_works_for_pending_credential() {
+        let _setup = SetupMocks::init();
+
+        let handle = _issuer_credential_create();
+
+        let res = set_consent_template(handle, "invalid");
+        assert_eq!(VcxErrorKind::InvalidJson, res.unwrap_err().kind());
+
+        let res = set_consent_template(handle, &json!({"purpose": "Issuance", "data_categories": [], "retention": "P1Y"}).to_string());
+        assert_eq!(VcxErrorKind::InvalidOption, res.unwrap_err().kind());
+
+        set_consent_template(handle, &json!({"purpose": "Issuance", "data_categories": ["name"], "retention": "P1Y"}).to_string()).unwrap();
+
+        ISSUER_CREDENTIAL_MAP.get(handle, |obj| {
+            match obj {
+                IssuerCredentials::Pending(ref obj) => assert!(obj.consent_template.is_some()),
+                _ => panic!("Unexpected IssuerCredential object"),
+            }
+            Ok(())
+        }).unwrap();
+    }
+
     #[test]
     fn test_to_string_succeeds() {
         let _setup = SetupMocks::init();
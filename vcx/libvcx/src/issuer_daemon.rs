@@ -0,0 +1,381 @@
+// Small issuer daemon driving VCX issuance state machines behind a local HTTP control API.
+//
+// Connections are read from `<state_dir>/connections/<connection_id>.json` (as serialized by `vcx_connection_serialize`).
+// Issuer credentials are stored into `<state_dir>/credentials/<credential_id>.json` after every state change,
+// so the daemon can be restarted without losing issuance in progress.
+//
+// POST /connections/<connection_id>/offers {"values": {..}, "name": Optional<string>, "price": Optional<int>}
+//      -> {"credential_id": string, "state": int}
+// GET /requests -> [{"credential_id": string, "connection_id": string, "state": int}] - credentials waiting for issuance
+// POST /credentials/<credential_id>/issue -> {"credential_id": string, "state": int}
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use api::VcxStateType;
+use connection;
+use credential_def;
+use error::prelude::*;
+use issuer_credential;
+use settings;
+use utils::libindy::pool::init_pool;
+use utils::libindy::wallet;
+
+const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct IssuerDaemonConfig {
+    // Address of HTTP endpoint
+    #[serde(default = "IssuerDaemonConfig::default_listen")]
+    pub listen: String,
+    // VCX configuration of provisioned agent (see `vcx_init_with_config`)
+    pub vcx_config: Value,
+    // Credential definition as serialized by `vcx_credentialdef_serialize`
+    pub cred_def: Value,
+    pub state_dir: String,
+}
+
+impl IssuerDaemonConfig {
+    fn default_listen() -> String {
+        "127.0.0.1:8091".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredCredential {
+    connection_id: String,
+    issuer_credential: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct OfferRequest {
+    values: HashMap<String, String>,
+    name: Option<String>,
+    price: Option<u64>,
+}
+
+struct IssuerDaemon {
+    cred_def_handle: u32,
+    state_dir: PathBuf,
+    connections: HashMap<String, u32>,
+}
+
+// Initializes VCX and serves requests one by one until the listener fails.
+pub fn run(config: IssuerDaemonConfig) -> VcxResult<()> {
+    trace!("run >>> listen: {}, state_dir: {}", config.listen, config.state_dir);
+
+    _init_vcx(&config.vcx_config)?;
+
+    let listener = TcpListener::bind(&config.listen)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot listen on {}: {}", config.listen, err)))?;
+
+    let mut daemon = IssuerDaemon::new(&config)?;
+
+    info!("Issuer daemon is listening on {}", config.listen);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => daemon.serve(stream),
+            Err(err) => warn!("Cannot accept connection: {}", err)
+        }
+    }
+
+    Ok(())
+}
+
+fn _init_vcx(vcx_config: &Value) -> VcxResult<()> {
+    settings::process_config_string(&vcx_config.to_string(), true)?;
+
+    ::utils::threadpool::init();
+
+    if settings::get_config_value(settings::CONFIG_GENESIS_PATH).is_ok() {
+        init_pool()?;
+    }
+
+    let wallet_name = settings::get_config_value(settings::CONFIG_WALLET_NAME)
+        .unwrap_or(settings::DEFAULT_WALLET_NAME.to_string());
+    let wallet_type = settings::get_config_value(settings::CONFIG_WALLET_TYPE).ok();
+    let storage_config = settings::get_config_value(settings::CONFIG_WALLET_STORAGE_CONFIG).ok();
+    let storage_creds = settings::get_config_value(settings::CONFIG_WALLET_STORAGE_CREDS).ok();
+
+    wallet::open_wallet(&wallet_name, wallet_type.as_ref().map(String::as_str),
+                        storage_config.as_ref().map(String::as_str), storage_creds.as_ref().map(String::as_str))?;
+
+    Ok(())
+}
+
+impl IssuerDaemon {
+    fn new(config: &IssuerDaemonConfig) -> VcxResult<IssuerDaemon> {
+        let state_dir = PathBuf::from(&config.state_dir);
+
+        fs::create_dir_all(state_dir.join("credentials"))
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot create state directory: {}", err)))?;
+
+        let cred_def_handle = credential_def::from_string(&config.cred_def.to_string())?;
+
+        Ok(IssuerDaemon {
+            cred_def_handle,
+            state_dir,
+            connections: HashMap::new(),
+        })
+    }
+
+    fn serve(&mut self, mut stream: TcpStream) {
+        let result = _read_request(&stream)
+            .and_then(|(method, path, body)| self.route(&method, &path, &body));
+
+        let (status, body) = match result {
+            Ok(Some(body)) => (200, body.to_string()),
+            Ok(None) => (404, json!({"message": "Unknown endpoint"}).to_string()),
+            Err(err) => _error_response(err),
+        };
+
+        let response = format!("HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                               status, _reason_phrase(status), body.len(), body);
+
+        if let Err(err) = stream.write_all(response.as_bytes()) {
+            warn!("Cannot write response: {}", err);
+        }
+    }
+
+    fn route(&mut self, method: &str, path: &str, body: &[u8]) -> VcxResult<Option<Value>> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            ("POST", ["connections", connection_id, "offers"]) => {
+                let request: OfferRequest = ::serde_json::from_slice(body)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize offer request: {}", err)))?;
+                self.create_offer(connection_id, request).map(Some)
+            }
+            ("GET", ["requests"]) => self.list_pending_requests().map(Some),
+            ("POST", ["credentials", credential_id, "issue"]) => self.issue(credential_id).map(Some),
+            _ => Ok(None)
+        }
+    }
+
+    fn create_offer(&mut self, connection_id: &str, request: OfferRequest) -> VcxResult<Value> {
+        trace!("create_offer >>> connection_id: {}, values: {:?}", connection_id, secret!(&request.values));
+
+        let connection_handle = self._connection(connection_id)?;
+
+        let credential_id = ::utils::uuid::uuid();
+        let issuer_did = settings::get_config_value(settings::CONFIG_INSTITUTION_DID)?;
+        let credential_data = json!(request.values).to_string();
+
+        let handle = issuer_credential::issuer_credential_create(self.cred_def_handle,
+                                                                 credential_id.clone(),
+                                                                 issuer_did,
+                                                                 request.name.unwrap_or_else(|| credential_id.clone()),
+                                                                 credential_data,
+                                                                 request.price.unwrap_or(0))?;
+
+        let result = issuer_credential::send_credential_offer(handle, connection_handle)
+            .and_then(|_| self._store_credential(&credential_id, connection_id, handle))
+            .and_then(|_| issuer_credential::get_state(handle));
+
+        issuer_credential::release(handle).ok();
+
+        Ok(json!({"credential_id": credential_id, "state": result?}))
+    }
+
+    fn list_pending_requests(&self) -> VcxResult<Value> {
+        let mut pending = Vec::new();
+
+        for credential_id in self._stored_credential_ids()? {
+            let (connection_id, handle) = self._load_credential(&credential_id)?;
+
+            let result = issuer_credential::get_state(handle)
+                .and_then(|state| {
+                    if state != VcxStateType::VcxStateOfferSent as u32 {
+                        return Ok(state);
+                    }
+
+                    issuer_credential::update_state(handle, None)?;
+                    self._store_credential(&credential_id, &connection_id, handle)?;
+                    issuer_credential::get_state(handle)
+                });
+
+            issuer_credential::release(handle).ok();
+
+            match result {
+                Ok(state) if state == VcxStateType::VcxStateRequestReceived as u32 =>
+                    pending.push(json!({"credential_id": credential_id, "connection_id": connection_id, "state": state})),
+                Ok(_) => {}
+                Err(err) => warn!("Cannot update state of credential {}: {}", credential_id, err)
+            }
+        }
+
+        Ok(json!(pending))
+    }
+
+    fn issue(&mut self, credential_id: &str) -> VcxResult<Value> {
+        trace!("issue >>> credential_id: {}", credential_id);
+
+        let (connection_id, handle) = self._load_credential(credential_id)?;
+
+        let result = self._connection(&connection_id)
+            .and_then(|connection_handle| issuer_credential::send_credential(handle, connection_handle))
+            .and_then(|_| self._store_credential(credential_id, &connection_id, handle))
+            .and_then(|_| issuer_credential::get_state(handle));
+
+        issuer_credential::release(handle).ok();
+
+        Ok(json!({"credential_id": credential_id, "state": result?}))
+    }
+
+    fn _connection(&mut self, connection_id: &str) -> VcxResult<u32> {
+        if let Some(handle) = self.connections.get(connection_id) {
+            return Ok(*handle);
+        }
+
+        let path = self.state_dir.join("connections").join(format!("{}.json", _validate_id(connection_id)?));
+        let data = fs::read_to_string(&path)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidConnectionHandle, format!("Cannot read connection {}: {}", connection_id, err)))?;
+
+        let handle = connection::from_string(&data)?;
+        self.connections.insert(connection_id.to_string(), handle);
+
+        Ok(handle)
+    }
+
+    fn _store_credential(&self, credential_id: &str, connection_id: &str, handle: u32) -> VcxResult<()> {
+        let issuer_credential: Value = ::serde_json::from_str(&issuer_credential::to_string(handle)?)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize IssuerCredential: {}", err)))?;
+
+        let stored = StoredCredential { connection_id: connection_id.to_string(), issuer_credential };
+
+        fs::write(self._credential_path(credential_id)?, json!(stored).to_string())
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot store credential {}: {}", credential_id, err)))
+    }
+
+    fn _load_credential(&self, credential_id: &str) -> VcxResult<(String, u32)> {
+        let data = fs::read_to_string(self._credential_path(credential_id)?)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidIssuerCredentialHandle, format!("Cannot read credential {}: {}", credential_id, err)))?;
+
+        let stored: StoredCredential = ::serde_json::from_str(&data)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize stored credential: {}", err)))?;
+
+        let handle = issuer_credential::from_string(&stored.issuer_credential.to_string())?;
+
+        Ok((stored.connection_id, handle))
+    }
+
+    fn _stored_credential_ids(&self) -> VcxResult<Vec<String>> {
+        let entries = fs::read_dir(self.state_dir.join("credentials"))
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot read credentials directory: {}", err)))?;
+
+        let mut ids: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.path().file_stem().and_then(|stem| stem.to_str()).map(String::from))
+            .collect();
+        ids.sort();
+
+        Ok(ids)
+    }
+
+    fn _credential_path(&self, credential_id: &str) -> VcxResult<PathBuf> {
+        Ok(self.state_dir.join("credentials").join(format!("{}.json", _validate_id(credential_id)?)))
+    }
+}
+
+// Ids are used as file names, so they must not point outside of the state directory.
+fn _validate_id(id: &str) -> VcxResult<&str> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Invalid id: {}", id)));
+    }
+    Ok(id)
+}
+
+// Reads request line, headers and body of HTTP/1.1 request.
+fn _read_request(stream: &TcpStream) -> VcxResult<(String, String, Vec<u8>)> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot read request line: {}", err)))?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length: usize = 0;
+
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot read request header: {}", err)))?;
+
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+
+        let mut header = header.splitn(2, ':');
+        let name = header.next().unwrap_or_default().trim();
+        let value = header.next().unwrap_or_default().trim();
+
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse()
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Invalid Content-Length header: {}", err)))?;
+        }
+    }
+
+    if content_length > MAX_REQUEST_BODY_SIZE {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Request body is larger than {} bytes", MAX_REQUEST_BODY_SIZE)));
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot read request body: {}", err)))?;
+
+    Ok((method, path, body))
+}
+
+fn _error_response(err: VcxError) -> (u16, String) {
+    let status = match err.kind() {
+        VcxErrorKind::InvalidJson | VcxErrorKind::InvalidOption | VcxErrorKind::InvalidAttributesStructure => 400,
+        VcxErrorKind::InvalidConnectionHandle | VcxErrorKind::InvalidIssuerCredentialHandle => 404,
+        _ => 500
+    };
+
+    let message = err.to_string();
+    let error_code: u32 = err.into();
+
+    (status, json!({"error_code": error_code, "message": message}).to_string())
+}
+
+fn _reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_id() {
+        assert!(_validate_id("6a1b2c3d-e4f5").is_ok());
+        assert!(_validate_id("").is_err());
+        assert!(_validate_id("../wallet").is_err());
+        assert!(_validate_id("a/b").is_err());
+    }
+
+    #[test]
+    fn test_error_response_for_invalid_json() {
+        let (status, body) = _error_response(VcxError::from_msg(VcxErrorKind::InvalidJson, "Cannot deserialize offer request"));
+
+        assert_eq!(400, status);
+
+        let body: Value = ::serde_json::from_str(&body).unwrap();
+        assert_eq!(json!(::utils::error::INVALID_JSON.code_num), body["error_code"]);
+    }
+}
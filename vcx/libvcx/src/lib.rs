@@ -43,8 +43,15 @@ extern crate strum_macros;
 
 extern crate chrono;
 
+extern crate tungstenite;
+extern crate ureq;
+extern crate rustls;
+extern crate webpki;
+extern crate webpki_roots;
+
 #[macro_use]
 pub mod utils;
+pub mod context;
 pub mod settings;
 #[macro_use]
 pub mod messages;
@@ -54,15 +61,25 @@ pub mod connection;
 pub mod issuer_credential;
 pub mod credential_request;
 pub mod proof;
+pub mod proof_template;
 pub mod schema;
 pub mod credential_def;
 pub mod error;
 pub mod credential;
 pub mod object_cache;
 pub mod disclosed_proof;
+pub mod revocation_publisher;
+pub mod mediation;
+pub mod scheduler;
+pub mod outbound_queue;
+pub mod events;
+pub mod state_updater;
 
 pub mod v3;
 
+#[cfg(feature = "issuer_daemon")]
+pub mod issuer_daemon;
+
 #[allow(unused_imports)]
 #[allow(dead_code)]
 #[cfg(test)]
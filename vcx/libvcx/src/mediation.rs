@@ -0,0 +1,248 @@
+use std::sync::Mutex;
+use std::collections::HashMap;
+
+use connection;
+use context::{self, ContextHandle};
+use error::prelude::*;
+use v3::handlers::connection::agent::AgentInfo;
+use v3::handlers::mediation::states::{MediationSM, MediatorRoute};
+use v3::handlers::mediation::messages::MediationMessages;
+use v3::handlers::mediation::pickup;
+use v3::messages::a2a::A2AMessage;
+use v3::messages::connection::did_doc::DidDoc;
+
+/*
+    Mediation client of the agent.
+    Once the mediator grants mediation, keys of new pairwise connections are registered with the mediator
+    and its endpoint and routing keys are used in DIDDocs instead of ones of the agency.
+    Messages with the mediator are exchanged over established connection, so it must be kept alive.
+    Messages of mediated connections are picked up from the mediator queue (Pickup protocol).
+    Every context has its own mediator.
+*/
+
+lazy_static! {
+    static ref MEDIATION: Mutex<HashMap<ContextHandle, MediationSM>> = Default::default();
+}
+
+pub fn request(connection_handle: u32) -> VcxResult<()> {
+    trace!("mediation::request >>> connection_handle: {}", connection_handle);
+
+    let (agent_info, did_doc) = connection::get_completed_connection(connection_handle)?;
+
+    let mut mediation = MEDIATION.lock().unwrap();
+
+    if mediation.contains_key(&context::current()) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidState, "Mediation is already requested. Reset it to use another mediator"));
+    }
+
+    mediation.insert(context::current(), MediationSM::request(agent_info, did_doc)?);
+
+    Ok(())
+}
+
+pub fn update_state() -> VcxResult<u32> {
+    trace!("mediation::update_state >>>");
+
+    _step(|mediation_sm| {
+        let messages = mediation_sm.agent_info().get_messages(Some(mediation_sm.did_doc()))?;
+
+        match mediation_sm.find_message_to_handle(messages) {
+            Some((uid, message)) => {
+                let mediation_sm = mediation_sm.step(message.into())?;
+                mediation_sm.agent_info().update_message_status(uid)?;
+                Ok(mediation_sm)
+            }
+            None => Ok(mediation_sm)
+        }
+    })?;
+
+    Ok(get_state())
+}
+
+pub fn get_state() -> u32 {
+    MEDIATION.lock().unwrap().get(&context::current())
+        .map(MediationSM::state)
+        .unwrap_or(0)
+}
+
+// Registers recipient key of a new connection with the mediator. Returns `None` if mediation isn't granted.
+pub fn add_key(key: &str) -> VcxResult<Option<MediatorRoute>> {
+    trace!("mediation::add_key >>> key: {}", key);
+
+    if get_route().is_none() {
+        return Ok(None);
+    }
+
+    _step(|mediation_sm| mediation_sm.step(MediationMessages::AddKey(key.to_string())))?;
+
+    Ok(get_route())
+}
+
+pub fn remove_key(key: &str) -> VcxResult<()> {
+    trace!("mediation::remove_key >>> key: {}", key);
+
+    if get_route().is_none() {
+        return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Mediation is not granted"));
+    }
+
+    _step(|mediation_sm| mediation_sm.step(MediationMessages::RemoveKey(key.to_string())))
+}
+
+pub fn get_route() -> Option<MediatorRoute> {
+    MEDIATION.lock().unwrap().get(&context::current())
+        .and_then(|mediation_sm| mediation_sm.route().cloned())
+}
+
+// Number of messages queued by the mediator, for all connections if recipient key is not set
+pub fn get_message_count(recipient_key: Option<&str>) -> VcxResult<u32> {
+    trace!("mediation::get_message_count >>> recipient_key: {:?}", recipient_key);
+
+    let (agent_info, did_doc) = _mediator_connection()?;
+    pickup::status(&agent_info, &did_doc, recipient_key)
+}
+
+// Messages queued by the mediator for the recipient key of a mediated connection
+// Authenticated messages must be sent from a key of `sender_did_doc` if it is set
+pub fn get_messages(recipient_key: &str, sender_did_doc: Option<&DidDoc>) -> VcxResult<HashMap<String, A2AMessage>> {
+    trace!("mediation::get_messages >>> recipient_key: {:?}, sender_did_doc: {:?}", recipient_key, sender_did_doc);
+
+    let (agent_info, did_doc) = _mediator_connection()?;
+    pickup::deliver(&agent_info, &did_doc, recipient_key, sender_did_doc)
+}
+
+// Removes handled messages from the mediator queue
+pub fn messages_received(uids: Vec<String>) -> VcxResult<()> {
+    trace!("mediation::messages_received >>> uids: {:?}", uids);
+
+    let (agent_info, did_doc) = _mediator_connection()?;
+    pickup::messages_received(&agent_info, &did_doc, uids)
+}
+
+pub fn to_string() -> VcxResult<String> {
+    let mediation = MEDIATION.lock().unwrap();
+
+    let mediation_sm = mediation.get(&context::current())
+        .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Mediation is not requested"))?;
+
+    ::serde_json::to_string(mediation_sm)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize Mediation: {:?}", err)))
+}
+
+// Restores mediation persisted by `to_string`
+pub fn from_string(data: &str) -> VcxResult<()> {
+    let mediation_sm: MediationSM = ::serde_json::from_str(data)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Mediation: {:?}", err)))?;
+
+    MEDIATION.lock().unwrap().insert(context::current(), mediation_sm);
+
+    Ok(())
+}
+
+// Connections created before keep using the mediator
+pub fn reset() {
+    MEDIATION.lock().unwrap().remove(&context::current());
+}
+
+// Lock isn't held while waiting for the mediator
+fn _mediator_connection() -> VcxResult<(AgentInfo, DidDoc)> {
+    MEDIATION.lock().unwrap().get(&context::current())
+        .map(|mediation_sm| (mediation_sm.agent_info().clone(), mediation_sm.did_doc().clone()))
+        .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Mediation is not requested"))
+}
+
+fn _step<F>(step: F) -> VcxResult<()> where F: FnOnce(MediationSM) -> VcxResult<MediationSM> {
+    let mut mediation = MEDIATION.lock().unwrap();
+
+    let mediation_sm = mediation.get(&context::current()).cloned()
+        .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Mediation is not requested"))?;
+
+    mediation.insert(context::current(), step(mediation_sm)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::VcxStateType;
+    use utils::devsetup::SetupAriesMocks;
+    use v3::handlers::mediation::states::tests::_mediation_sm;
+    use v3::messages::mediation::keylist_update::tests::_key;
+    use v3::handlers::mediation::pickup::tests::_envelope;
+    use v3::messages::pickup::delivery::{Delivery, DeliveredMessage};
+    use v3::messages::ack::tests::_ack;
+    use utils::httpclient::AgencyMock;
+
+    #[test]
+    fn test_mediation_service_works() {
+        let _setup = SetupAriesMocks::init();
+        reset();
+
+        assert_eq!(0, get_state());
+        assert_eq!(None, add_key(&_key()).unwrap());
+        assert_eq!(remove_key(&_key()).unwrap_err().kind(), VcxErrorKind::NotReady);
+        assert_eq!(to_string().unwrap_err().kind(), VcxErrorKind::NotReady);
+
+        let mediation_sm = _mediation_sm().to_granted_state();
+        from_string(&::serde_json::to_string(&mediation_sm).unwrap()).unwrap();
+        assert_eq!(VcxStateType::VcxStateAccepted as u32, get_state());
+
+        let route = add_key(&_key()).unwrap();
+        assert_eq!(mediation_sm.route().cloned(), route);
+
+        let mediation_sm: MediationSM = ::serde_json::from_str(&to_string().unwrap()).unwrap();
+        assert_eq!(vec![_key()], mediation_sm.keys());
+
+        reset();
+        assert!(get_route().is_none());
+    }
+
+    #[test]
+    fn test_mediation_pickup_works() {
+        let _setup = SetupAriesMocks::init();
+        reset();
+
+        assert_eq!(get_messages(&_key(), None).unwrap_err().kind(), VcxErrorKind::NotReady);
+        assert_eq!(get_message_count(None).unwrap_err().kind(), VcxErrorKind::NotReady);
+
+        from_string(&::serde_json::to_string(&_mediation_sm().to_granted_state()).unwrap()).unwrap();
+
+        let delivery = Delivery::create()
+            .add_message(DeliveredMessage::new("message_1", &_envelope(&_ack().to_a2a_message())));
+        AgencyMock::set_next_response(_envelope(&delivery.to_a2a_message()));
+
+        let messages = get_messages(&_key(), None).unwrap();
+        assert_eq!(_ack().to_a2a_message(), messages["message_1"]);
+
+        messages_received(vec![String::from("message_1")]).unwrap();
+
+        reset();
+    }
+
+    #[test]
+    fn test_mediation_is_isolated_per_context() {
+        let _setup = SetupAriesMocks::init();
+        reset();
+
+        let other = context::create().unwrap();
+
+        from_string(&::serde_json::to_string(&_mediation_sm().to_granted_state()).unwrap()).unwrap();
+        assert!(get_route().is_some());
+
+        assert!(context::run_in(other, get_route).is_none());
+        assert_eq!(context::run_in(other, to_string).unwrap_err().kind(), VcxErrorKind::NotReady);
+
+        context::run_in(other, reset);
+        assert!(get_route().is_some());
+
+        reset();
+        context::release(other).unwrap();
+    }
+
+    #[test]
+    fn test_mediation_request_fails_for_invalid_connection() {
+        let _setup = SetupAriesMocks::init();
+
+        assert_eq!(request(0).unwrap_err().kind(), VcxErrorKind::InvalidHandle);
+    }
+}
@@ -1,9 +1,11 @@
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
+use chrono::{DateTime, Duration, Utc};
 
 use settings;
 use messages::{A2AMessage, A2AMessageV1, A2AMessageV2, A2AMessageKinds, prepare_message_for_agency, parse_response_from_agency};
 use messages::message_type::MessageTypes;
+use messages::cloud_agent;
 use utils::{error, httpclient, constants};
 use utils::libindy::{wallet, anoncreds};
 use utils::libindy::signus::create_and_store_my_did;
@@ -85,6 +87,86 @@ pub struct CreateAgentResponse {
     from_vk: String,
 }
 
+// Token issued by a sponsor to its sponsee. The agency provisions the agent only if the sponsor signature is valid.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionToken {
+    pub sponsee_id: String,
+    pub sponsor_id: String,
+    pub nonce: String,
+    pub timestamp: String,
+    pub sig: String,
+    pub sponsor_vk: String,
+}
+
+// Agency rejects tokens which were issued earlier
+const PROVISION_TOKEN_LIFETIME_MINUTES: i64 = 15;
+// Tolerated difference between clocks of the sponsor and the library
+const PROVISION_TOKEN_CLOCK_SKEW_MINUTES: i64 = 1;
+
+impl ProvisionToken {
+    pub fn parse(token: &str) -> VcxResult<ProvisionToken> {
+        let token: ProvisionToken = ::serde_json::from_str(token)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidProvisioningToken, format!("Cannot parse provisioning token: {}", err)))?;
+
+        token.validate()?;
+
+        Ok(token)
+    }
+
+    pub fn validate(&self) -> VcxResult<()> {
+        if self.sponsee_id.is_empty() || self.sponsor_id.is_empty() || self.nonce.is_empty() || self.sig.is_empty() || self.sponsor_vk.is_empty() {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidProvisioningToken, "Provisioning token has empty fields"));
+        }
+
+        let timestamp = DateTime::parse_from_rfc3339(&self.timestamp)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidProvisioningToken, format!("Invalid timestamp of provisioning token: {}", err)))?
+            .with_timezone(&Utc);
+
+        let now = Utc::now();
+
+        if timestamp > now + Duration::minutes(PROVISION_TOKEN_CLOCK_SKEW_MINUTES) {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidProvisioningToken, format!("Provisioning token is issued in the future: {}", self.timestamp)));
+        }
+
+        if timestamp + Duration::minutes(PROVISION_TOKEN_LIFETIME_MINUTES) < now {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidProvisioningToken, format!("Provisioning token is expired: {}", self.timestamp)));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProvisionAgent {
+    #[serde(rename = "@type")]
+    msg_type: MessageTypes,
+    #[serde(rename = "requesterVk")]
+    requester_vk: String,
+    #[serde(rename = "provisionToken")]
+    provision_token: ProvisionToken,
+}
+
+impl ProvisionAgent {
+    fn build(requester_vk: &str, provision_token: ProvisionToken) -> ProvisionAgent {
+        ProvisionAgent {
+            msg_type: MessageTypes::build(A2AMessageKinds::ProvisionAgent),
+            requester_vk: requester_vk.to_string(),
+            provision_token,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProvisionAgentResponse {
+    #[serde(rename = "@type")]
+    msg_type: MessageTypes,
+    #[serde(rename = "selfDID")]
+    self_did: String,
+    #[serde(rename = "agentVerKey")]
+    agent_vk: String,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ComMethodUpdated {
     #[serde(rename = "@type")]
@@ -167,6 +249,7 @@ pub struct Config {
     communication_method: Option<String>,
     webhook_url: Option<String>,
     use_latest_protocols: Option<String>,
+    cloud_agent: Option<String>,
 }
 
 pub fn set_config_values(my_config: &Config) {
@@ -188,6 +271,7 @@ pub fn set_config_values(my_config: &Config) {
     settings::set_opt_config_value(settings::CONFIG_DID_METHOD, &my_config.did_method);
     settings::set_opt_config_value(settings::COMMUNICATION_METHOD, &my_config.communication_method);
     settings::set_opt_config_value(settings::CONFIG_WEBHOOK_URL, &my_config.webhook_url);
+    settings::set_opt_config_value(settings::CONFIG_CLOUD_AGENT, &my_config.cloud_agent);
 }
 
 fn _create_issuer_keys(my_did: &str, my_vk: &str, my_config: &Config) -> VcxResult<(String, String)> {
@@ -276,6 +360,9 @@ pub fn get_final_config(my_did: &str,
     if let Some(_use_latest_protocols) = &my_config.use_latest_protocols {
         final_config["use_latest_protocols"] = json!(_use_latest_protocols);
     }
+    if let Some(_cloud_agent) = &my_config.cloud_agent {
+        final_config["cloud_agent"] = json!(_cloud_agent);
+    }
 
     Ok(final_config.to_string())
 }
@@ -302,12 +389,28 @@ pub fn connect_register_provision(config: &str) -> VcxResult<String> {
     let (my_did, my_vk, wallet_name) = configure_wallet(&my_config)?;
 
     trace!("Connecting to Agency");
-    let (agent_did, agent_vk) = match my_config.protocol_type {
-        settings::ProtocolTypes::V1 => onboarding_v1(&my_did, &my_vk, &my_config.agency_did)?,
-        settings::ProtocolTypes::V2 |
-        settings::ProtocolTypes::V3 |
-        settings::ProtocolTypes::V4 => onboarding_v2(&my_did, &my_vk, &my_config.agency_did)?,
-    };
+    let (agent_did, agent_vk) = cloud_agent::get()?.provision(&my_did, &my_vk)?;
+
+    let config = get_final_config(&my_did, &my_vk, &agent_did, &agent_vk, &wallet_name, &my_config)?;
+
+    wallet::close_wallet()?;
+
+    Ok(config)
+}
+
+pub fn connect_register_provision_with_token(config: &str, token: &str) -> VcxResult<String> {
+    trace!("connect_register_provision_with_token >>> config: {:?}, token: {:?}", config, token);
+    let my_config = parse_config(config)?;
+    let token = ProvisionToken::parse(token)?;
+
+    trace!("***Configuring Library");
+    set_config_values(&my_config);
+
+    trace!("***Configuring Wallet");
+    let (my_did, my_vk, wallet_name) = configure_wallet(&my_config)?;
+
+    trace!("Connecting to Agency");
+    let (agent_did, agent_vk) = cloud_agent::get()?.provision_with_token(&my_did, &my_vk, token)?;
 
     let config = get_final_config(&my_did, &my_vk, &agent_did, &agent_vk, &wallet_name, &my_config)?;
 
@@ -316,6 +419,16 @@ pub fn connect_register_provision(config: &str) -> VcxResult<String> {
     Ok(config)
 }
 
+// Onboarding with Evernym agency
+pub fn onboarding(my_did: &str, my_vk: &str, agency_did: &str) -> VcxResult<(String, String)> {
+    match settings::get_protocol_type() {
+        settings::ProtocolTypes::V1 => onboarding_v1(my_did, my_vk, agency_did),
+        settings::ProtocolTypes::V2 |
+        settings::ProtocolTypes::V3 |
+        settings::ProtocolTypes::V4 => onboarding_v2(my_did, my_vk, agency_did),
+    }
+}
+
 fn onboarding_v1(my_did: &str, my_vk: &str, agency_did: &str) -> VcxResult<(String, String)> {
     /* STEP 1 - CONNECT */
     AgencyMock::set_next_response(constants::CONNECTED_RESPONSE.to_vec());
@@ -423,45 +536,39 @@ fn onboarding_v2(my_did: &str, my_vk: &str, agency_did: &str) -> VcxResult<(Stri
     Ok((response.from_did, response.from_vk))
 }
 
+// Onboarding with Evernym agency gated by the token of a sponsor. Replaces SIGNUP and CREATE_AGENT steps.
+pub fn onboarding_with_token(my_did: &str, my_vk: &str, agency_did: &str, token: ProvisionToken) -> VcxResult<(String, String)> {
+    if settings::get_protocol_type() == settings::ProtocolTypes::V1 {
+        return Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Provisioning with token is not supported by protocol V1"));
+    }
+
+    let (agency_pw_did, _) = connect_v2(my_did, my_vk, agency_did)?;
+
+    let message = A2AMessage::Version2(
+        A2AMessageV2::ProvisionAgent(ProvisionAgent::build(my_vk, token))
+    );
+
+    let mut response = send_message_to_agency(&message, &agency_pw_did)?;
+
+    let response: ProvisionAgentResponse =
+        match response.remove(0) {
+            A2AMessage::Version2(A2AMessageV2::ProvisionAgentResponse(resp)) => resp,
+            _ => return Err(VcxError::from_msg(VcxErrorKind::InvalidHttpResponse, "Message does not match any variant of ProvisionAgentResponse"))
+        };
+
+    Ok((response.self_did, response.agent_vk))
+}
+
 pub fn update_agent_info(id: &str, value: &str) -> VcxResult<()> {
     trace!("update_agent_info >>> id: {}, value: {}", id, value);
 
-    let to_did = settings::get_config_value(settings::CONFIG_REMOTE_TO_SDK_DID)?;
-
     let com_method = ComMethod {
         id: id.to_string(),
         e_type: ComMethodType::A2A,
         value: value.to_string(),
     };
 
-    match settings::get_protocol_type() {
-        settings::ProtocolTypes::V1 => {
-            update_agent_info_v1(&to_did, com_method)
-        }
-        settings::ProtocolTypes::V2 |
-        settings::ProtocolTypes::V3 |
-        settings::ProtocolTypes::V4 => {
-            update_agent_info_v2(&to_did, com_method)
-        }
-    }
-}
-
-fn update_agent_info_v1(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
-    AgencyMock::set_next_response(constants::REGISTER_RESPONSE.to_vec());
-
-    let message = A2AMessage::Version1(
-        A2AMessageV1::UpdateComMethod(UpdateComMethod::build(com_method))
-    );
-    send_message_to_agency(&message, to_did)?;
-    Ok(())
-}
-
-fn update_agent_info_v2(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
-    let message = A2AMessage::Version2(
-        A2AMessageV2::UpdateComMethod(UpdateComMethod::build(com_method))
-    );
-    send_message_to_agency(&message, to_did)?;
-    Ok(())
+    cloud_agent::get()?.update_com_method(com_method)
 }
 
 pub fn update_agent_webhook(webhook_url: &str) -> VcxResult<()> {
@@ -474,34 +581,37 @@ pub fn update_agent_webhook(webhook_url: &str) -> VcxResult<()> {
     };
 
     match settings::get_config_value(settings::CONFIG_REMOTE_TO_SDK_DID) {
-        Ok(to_did) => {
-            match settings::get_protocol_type() {
-                settings::ProtocolTypes::V1 => update_agent_webhook_v1(&to_did, com_method)?,
-                settings::ProtocolTypes::V2 |
-                settings::ProtocolTypes::V3 |
-                settings::ProtocolTypes::V4 => update_agent_webhook_v2(&to_did, com_method)?,
-            }
-        },
+        Ok(_) => cloud_agent::get()?.update_com_method(com_method)?,
         Err(e) => warn!("Unable to update webhook (did you provide remote did in the config?): {}", e)
     }
     Ok(())
 }
 
-fn update_agent_webhook_v1(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
-    if settings::agency_mocks_enabled() { return Ok(()) }
+// Updates communication method of Evernym agency agent
+pub fn update_com_method(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
+    match settings::get_protocol_type() {
+        settings::ProtocolTypes::V1 => update_com_method_v1(to_did, com_method),
+        settings::ProtocolTypes::V2 |
+        settings::ProtocolTypes::V3 |
+        settings::ProtocolTypes::V4 => update_com_method_v2(to_did, com_method),
+    }
+}
+
+fn update_com_method_v1(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
+    AgencyMock::set_next_response(constants::REGISTER_RESPONSE.to_vec());
 
     let message = A2AMessage::Version1(
         A2AMessageV1::UpdateComMethod(UpdateComMethod::build(com_method))
     );
-    send_message_to_agency(&message, &to_did)?;
+    send_message_to_agency(&message, to_did)?;
     Ok(())
 }
 
-fn update_agent_webhook_v2(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
+fn update_com_method_v2(to_did: &str, com_method: ComMethod) -> VcxResult<()> {
     let message = A2AMessage::Version2(
         A2AMessageV2::UpdateComMethod(UpdateComMethod::build(com_method))
     );
-    send_message_to_agency(&message, &to_did)?;
+    send_message_to_agency(&message, to_did)?;
     Ok(())
 }
 
@@ -632,6 +742,99 @@ mod tests {
         update_agent_info("123", "value").unwrap();
     }
 
+    #[test]
+    fn test_connect_register_provision_works_for_direct_endpoint() {
+        let _setup = SetupMocks::init();
+
+        let config = json!({
+            "agency_url": "https://agent.example.com/didcomm",
+            "agency_did": "Ab8TvZa3Q19VNkQVzAWVL7",
+            "agency_verkey": "5LXaR43B1aQyeh94VBP8LG1Sgvjk7aNfqiksBCSjwqbf",
+            "wallet_key": "test_key",
+            "cloud_agent": "direct",
+        });
+
+        let result = connect_register_provision(&config.to_string()).unwrap();
+        let result: Value = ::serde_json::from_str(&result).unwrap();
+
+        assert_eq!(result["sdk_to_remote_did"], result["remote_to_sdk_did"]);
+        assert_eq!(result["sdk_to_remote_verkey"], result["remote_to_sdk_verkey"]);
+        assert_eq!(json!("direct"), result["cloud_agent"]);
+    }
+
+    fn _provision_token(timestamp: DateTime<Utc>) -> ProvisionToken {
+        ProvisionToken {
+            sponsee_id: "sponsee".to_string(),
+            sponsor_id: "sponsor".to_string(),
+            nonce: "1234567890".to_string(),
+            timestamp: timestamp.to_rfc3339(),
+            sig: "ZThlZGI2NzE0ZmQ1ZTE2ZGM2NmYzN2U5YzNmODYxNjFjMjgxNDJjZjRiMTY4MDhhNzFiYTRmMTU0MzNiYTcwZQ==".to_string(),
+            sponsor_vk: "5LXaR43B1aQyeh94VBP8LG1Sgvjk7aNfqiksBCSjwqbf".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_provision_token_validate() {
+        _provision_token(Utc::now()).validate().unwrap();
+        _provision_token(Utc::now() - Duration::minutes(PROVISION_TOKEN_LIFETIME_MINUTES - 1)).validate().unwrap();
+
+        let expired = _provision_token(Utc::now() - Duration::minutes(PROVISION_TOKEN_LIFETIME_MINUTES + 1));
+        assert_eq!(VcxErrorKind::InvalidProvisioningToken, expired.validate().unwrap_err().kind());
+
+        let issued_in_future = _provision_token(Utc::now() + Duration::minutes(PROVISION_TOKEN_CLOCK_SKEW_MINUTES + 1));
+        assert_eq!(VcxErrorKind::InvalidProvisioningToken, issued_in_future.validate().unwrap_err().kind());
+
+        let mut invalid_timestamp = _provision_token(Utc::now());
+        invalid_timestamp.timestamp = "yesterday".to_string();
+        assert_eq!(VcxErrorKind::InvalidProvisioningToken, invalid_timestamp.validate().unwrap_err().kind());
+
+        let mut unsigned = _provision_token(Utc::now());
+        unsigned.sig = String::new();
+        assert_eq!(VcxErrorKind::InvalidProvisioningToken, unsigned.validate().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_provision_token_parse() {
+        let token = _provision_token(Utc::now());
+        assert_eq!(token, ProvisionToken::parse(&json!(token).to_string()).unwrap());
+
+        assert_eq!(VcxErrorKind::InvalidProvisioningToken, ProvisionToken::parse(r#"{"sponseeId": "sponsee"}"#).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_provision_agent_response_deserialization() {
+        let response = json!({
+            "@type": "did:sov:123456789abcdefghi1234;spec/agent-provisioning/0.7/AGENT_CREATED",
+            "selfDID": "Ab8TvZa3Q19VNkQVzAWVL7",
+            "agentVerKey": "5LXaR43B1aQyeh94VBP8LG1Sgvjk7aNfqiksBCSjwqbf"
+        });
+
+        match ::serde_json::from_value::<A2AMessage>(response).unwrap() {
+            A2AMessage::Version2(A2AMessageV2::ProvisionAgentResponse(response)) => {
+                assert_eq!("Ab8TvZa3Q19VNkQVzAWVL7", response.self_did);
+                assert_eq!("5LXaR43B1aQyeh94VBP8LG1Sgvjk7aNfqiksBCSjwqbf", response.agent_vk);
+            }
+            message => panic!("Unexpected message: {:?}", message)
+        }
+    }
+
+    #[test]
+    fn test_connect_register_provision_with_token_fails_for_direct_endpoint() {
+        let _setup = SetupMocks::init();
+
+        let config = json!({
+            "agency_url": "https://agent.example.com/didcomm",
+            "agency_did": "Ab8TvZa3Q19VNkQVzAWVL7",
+            "agency_verkey": "5LXaR43B1aQyeh94VBP8LG1Sgvjk7aNfqiksBCSjwqbf",
+            "wallet_key": "test_key",
+            "cloud_agent": "direct",
+        });
+        let token = json!(_provision_token(Utc::now())).to_string();
+
+        let err = connect_register_provision_with_token(&config.to_string(), &token).unwrap_err();
+        assert_eq!(VcxErrorKind::ActionNotSupported, err.kind());
+    }
+
     #[cfg(feature = "agency")]
     #[cfg(feature = "pool_tests")]
     #[test]
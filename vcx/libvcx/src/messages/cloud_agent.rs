@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use settings;
+use messages::agent_utils::{self, ComMethod, ProvisionToken};
+use utils::httpclient;
+use connection::create_agent_keys;
+use error::prelude::*;
+
+/*
+    Cloud agent receives messages for the library while it is offline.
+    The client of Evernym agency protocol is used by default. Other implementations (alternative mediators for example)
+    can be registered at runtime and selected by `cloud_agent` config option.
+*/
+pub trait CloudAgent: Send + Sync {
+    // Onboards SDK DID with the agency. Returns DID and verkey of the cloud agent created for the SDK.
+    fn provision(&self, my_did: &str, my_vk: &str) -> VcxResult<(String, String)>;
+
+    // Onboards SDK DID with the agency admitting only agents sponsored by the owner of the token
+    fn provision_with_token(&self, _my_did: &str, _my_vk: &str, _token: ProvisionToken) -> VcxResult<(String, String)> {
+        Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Cloud agent doesn't support provisioning with token"))
+    }
+
+    // Registers the way the cloud agent notifies the SDK about new messages (webhook or push notifications)
+    fn update_com_method(&self, com_method: ComMethod) -> VcxResult<()>;
+
+    // Creates agent receiving messages of the pairwise relationship.
+    // Returns empty DID and verkey if messages are not received through the pairwise agent.
+    fn create_pairwise_agent(&self, pw_did: &str, pw_vk: &str) -> VcxResult<(String, String)>;
+
+    // Endpoint remote sides send messages to
+    fn endpoint(&self) -> VcxResult<String>;
+
+    // Keys messages for the pairwise agent are forwarded through, the last one is the key of the endpoint
+    fn routing_keys(&self, pairwise_agent_vk: &str) -> VcxResult<Vec<String>>;
+
+    // Delivers the message packed for the agency and returns the response of the agency
+    fn post(&self, message: &[u8]) -> VcxResult<Vec<u8>>;
+
+    // Whether received messages are kept by the agency until they are downloaded.
+    // Otherwise they are passed to the library by the application.
+    fn keeps_messages(&self) -> bool {
+        true
+    }
+}
+
+pub static EVERNYM_AGENCY: &str = "evernym";
+pub static DIRECT_ENDPOINT: &str = "direct";
+
+lazy_static! {
+    static ref CLOUD_AGENTS: RwLock<HashMap<String, Arc<dyn CloudAgent>>> = {
+        let mut cloud_agents: HashMap<String, Arc<dyn CloudAgent>> = HashMap::new();
+        cloud_agents.insert(EVERNYM_AGENCY.to_string(), Arc::new(EvernymAgency));
+        cloud_agents.insert(DIRECT_ENDPOINT.to_string(), Arc::new(DirectEndpoint));
+        RwLock::new(cloud_agents)
+    };
+}
+
+// Built-in implementations can't be replaced
+pub fn register(name: &str, cloud_agent: Box<dyn CloudAgent>) -> VcxResult<()> {
+    trace!("cloud_agent::register >>> name: {:?}", name);
+
+    if name == EVERNYM_AGENCY || name == DIRECT_ENDPOINT {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Built-in cloud agent can't be replaced: {:?}", name)));
+    }
+
+    CLOUD_AGENTS.write()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock cloud agents: {:?}", err)))?
+        .insert(name.to_string(), Arc::from(cloud_agent));
+
+    Ok(())
+}
+
+pub fn by_name(name: &str) -> VcxResult<Arc<dyn CloudAgent>> {
+    CLOUD_AGENTS.read()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock cloud agents: {:?}", err)))?
+        .get(name)
+        .cloned()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unknown cloud agent: {:?}", name)))
+}
+
+// Cloud agent selected by the library config
+pub fn get() -> VcxResult<Arc<dyn CloudAgent>> {
+    by_name(&settings::get_cloud_agent())
+}
+
+// Client of Evernym agency protocol
+pub struct EvernymAgency;
+
+impl CloudAgent for EvernymAgency {
+    fn provision(&self, my_did: &str, my_vk: &str) -> VcxResult<(String, String)> {
+        let agency_did = settings::get_config_value(settings::CONFIG_AGENCY_DID)?;
+        agent_utils::onboarding(my_did, my_vk, &agency_did)
+    }
+
+    fn provision_with_token(&self, my_did: &str, my_vk: &str, token: ProvisionToken) -> VcxResult<(String, String)> {
+        let agency_did = settings::get_config_value(settings::CONFIG_AGENCY_DID)?;
+        agent_utils::onboarding_with_token(my_did, my_vk, &agency_did, token)
+    }
+
+    fn update_com_method(&self, com_method: ComMethod) -> VcxResult<()> {
+        let to_did = settings::get_config_value(settings::CONFIG_REMOTE_TO_SDK_DID)?;
+        agent_utils::update_com_method(&to_did, com_method)
+    }
+
+    fn create_pairwise_agent(&self, pw_did: &str, pw_vk: &str) -> VcxResult<(String, String)> {
+        create_agent_keys("", pw_did, pw_vk)
+    }
+
+    fn endpoint(&self) -> VcxResult<String> {
+        settings::get_config_value(settings::CONFIG_AGENCY_ENDPOINT)
+            .map(|endpoint| format!("{}/agency/msg", endpoint))
+    }
+
+    fn routing_keys(&self, pairwise_agent_vk: &str) -> VcxResult<Vec<String>> {
+        let agency_vk = settings::get_config_value(settings::CONFIG_AGENCY_VERKEY)?;
+        Ok(vec![pairwise_agent_vk.to_string(), agency_vk])
+    }
+
+    fn post(&self, message: &[u8]) -> VcxResult<Vec<u8>> {
+        httpclient::post_message(&message.to_vec(), &self.endpoint()?)
+    }
+}
+
+// No agency: `agency_endpoint` is the endpoint hosted by the application itself.
+// Messages the application receives are passed to the library with `vcx_connection_update_state_with_message`.
+// Only Aries connections can be used as proprietary protocol messages are exchanged through the agency.
+pub struct DirectEndpoint;
+
+impl CloudAgent for DirectEndpoint {
+    fn provision(&self, my_did: &str, my_vk: &str) -> VcxResult<(String, String)> {
+        Ok((my_did.to_string(), my_vk.to_string()))
+    }
+
+    fn update_com_method(&self, _com_method: ComMethod) -> VcxResult<()> {
+        Ok(())
+    }
+
+    fn create_pairwise_agent(&self, _pw_did: &str, _pw_vk: &str) -> VcxResult<(String, String)> {
+        Ok((String::new(), String::new()))
+    }
+
+    fn endpoint(&self) -> VcxResult<String> {
+        settings::get_config_value(settings::CONFIG_AGENCY_ENDPOINT)
+    }
+
+    fn routing_keys(&self, _pairwise_agent_vk: &str) -> VcxResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn post(&self, _message: &[u8]) -> VcxResult<Vec<u8>> {
+        Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "There is no agency to send messages to for direct endpoint"))
+    }
+
+    fn keeps_messages(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::devsetup::*;
+
+    struct TestCloudAgent;
+
+    impl CloudAgent for TestCloudAgent {
+        fn provision(&self, _my_did: &str, _my_vk: &str) -> VcxResult<(String, String)> { Ok(("agent_did".to_string(), "agent_vk".to_string())) }
+        fn update_com_method(&self, _com_method: ComMethod) -> VcxResult<()> { Ok(()) }
+        fn create_pairwise_agent(&self, _pw_did: &str, _pw_vk: &str) -> VcxResult<(String, String)> { Ok((String::new(), String::new())) }
+        fn endpoint(&self) -> VcxResult<String> { Ok("https://mediator.example.com".to_string()) }
+        fn routing_keys(&self, _pairwise_agent_vk: &str) -> VcxResult<Vec<String>> { Ok(vec!["mediator_vk".to_string()]) }
+        fn post(&self, _message: &[u8]) -> VcxResult<Vec<u8>> { Ok(Vec::new()) }
+    }
+
+    #[test]
+    fn test_get_works_for_default_cloud_agent() {
+        let _setup = SetupDefaults::init();
+
+        let cloud_agent = get().unwrap();
+        assert_eq!(format!("{}/agency/msg", settings::DEFAULT_URL), cloud_agent.endpoint().unwrap());
+        assert_eq!(vec!["agent_vk".to_string(), settings::DEFAULT_VERKEY.to_string()], cloud_agent.routing_keys("agent_vk").unwrap());
+    }
+
+    #[test]
+    fn test_get_works_for_direct_endpoint() {
+        let _setup = SetupDefaults::init();
+
+        settings::set_config_value(settings::CONFIG_CLOUD_AGENT, DIRECT_ENDPOINT);
+
+        let cloud_agent = get().unwrap();
+        assert_eq!(settings::DEFAULT_URL, cloud_agent.endpoint().unwrap());
+        assert!(cloud_agent.routing_keys("agent_vk").unwrap().is_empty());
+        assert_eq!(("did".to_string(), "vk".to_string()), cloud_agent.provision("did", "vk").unwrap());
+        assert_eq!(cloud_agent.post(&[1, 2, 3]).unwrap_err().kind(), VcxErrorKind::ActionNotSupported);
+        assert!(!cloud_agent.keeps_messages());
+    }
+
+    #[test]
+    fn test_register_works() {
+        let _setup = SetupDefaults::init();
+
+        register("test_mediator", Box::new(TestCloudAgent)).unwrap();
+        settings::set_config_value(settings::CONFIG_CLOUD_AGENT, "test_mediator");
+
+        let cloud_agent = get().unwrap();
+        assert_eq!("https://mediator.example.com", cloud_agent.endpoint().unwrap());
+        assert_eq!(("agent_did".to_string(), "agent_vk".to_string()), cloud_agent.provision("did", "vk").unwrap());
+    }
+
+    #[test]
+    fn test_register_fails_for_built_in_cloud_agent() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(register(EVERNYM_AGENCY, Box::new(TestCloudAgent)).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+        assert_eq!(register(DIRECT_ENDPOINT, Box::new(TestCloudAgent)).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+    }
+
+    #[test]
+    fn test_get_fails_for_unknown_cloud_agent() {
+        let _setup = SetupDefaults::init();
+
+        settings::set_config_value(settings::CONFIG_CLOUD_AGENT, "unknown");
+        assert_eq!(get().err().unwrap().kind(), VcxErrorKind::InvalidOption);
+    }
+}
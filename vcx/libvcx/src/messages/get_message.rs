@@ -7,6 +7,7 @@ use utils::{httpclient, constants};
 use error::prelude::*;
 use settings::ProtocolTypes;
 use utils::httpclient::AgencyMock;
+use v3::utils::encryption_envelope::EncryptionEnvelope;
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -24,21 +25,104 @@ pub struct GetMessages {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "pairwiseDIDs")]
     pairwise_dids: Option<Vec<String>>,
+    #[serde(flatten)]
+    filter: MessageFilter,
+    #[serde(flatten)]
+    page: MessagePage,
 }
 
 impl GetMessages {
     fn build(kind: A2AMessageKinds, exclude_payload: Option<String>, uids: Option<Vec<String>>,
-             status_codes: Option<Vec<MessageStatusCode>>, pairwise_dids: Option<Vec<String>>) -> GetMessages {
+             status_codes: Option<Vec<MessageStatusCode>>, pairwise_dids: Option<Vec<String>>, filter: MessageFilter, page: MessagePage) -> GetMessages {
         GetMessages {
             msg_type: MessageTypes::build(kind),
             exclude_payload,
             uids,
             status_codes,
             pairwise_dids,
+            filter,
+            page,
         }
     }
 }
 
+// Filter of downloaded messages.
+// It is sent to the agency with the query, and is applied again right after the envelope of a message is opened,
+// so messages that do not match are discarded before their parsing for agencies not supporting filtering.
+// Only Aries messages can match `protocolFamilies` and `threadId`. `since` and `until` are applied by the agency only.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageFilter {
+    // Aries protocol families, like `present-proof` or `issue-credential`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_families: Option<Vec<String>>,
+    // Thread of the message: `~thread.thid` or `@id` of the message starting the thread
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    // Unix timestamp (seconds) of the oldest message to download
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<u64>,
+    // Unix timestamp (seconds) of the newest message to download
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<u64>,
+    // Types of messages as stored by the agency, like `credOffer` or `aries`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg_types: Option<Vec<String>>,
+}
+
+impl MessageFilter {
+    fn is_applied_locally(&self) -> bool {
+        self.protocol_families.is_some() || self.thread_id.is_some()
+    }
+
+    fn matches_type(&self, msg_type: &RemoteMessageType) -> bool {
+        match self.msg_types {
+            Some(ref msg_types) => {
+                let msg_type = json!(msg_type);
+                msg_types.iter().any(|type_| msg_type.as_str() == Some(type_.as_str()))
+            }
+            None => true
+        }
+    }
+
+    fn matches(&self, message: &::serde_json::Value) -> bool {
+        use v3::messages::a2a::message_type::MessageType;
+
+        if let Some(ref protocol_families) = self.protocol_families {
+            let family = ::serde_json::from_value::<MessageType>(message["@type"].clone())
+                .map(|message_type| message_type.family.to_string());
+
+            match family {
+                Ok(ref family) if protocol_families.contains(family) => {}
+                _ => return false
+            }
+        }
+
+        if let Some(ref thread_id) = self.thread_id {
+            let thid = message["~thread"]["thid"].as_str().or(message["@id"].as_str());
+
+            if thid != Some(thread_id.as_str()) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+// Page of downloaded messages negotiated with the agency.
+// The agency returns `nextPageToken` along with the messages while more messages match the query,
+// the token is passed back as `pageToken` to get the next page.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessagePage {
+    // Maximal number of messages in the page, the agency default is used if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GetMessagesResponse {
@@ -54,6 +138,10 @@ pub struct MessagesByConnections {
     #[serde(rename = "msgsByConns")]
     #[serde(default)]
     msgs: Vec<MessageByConnection>,
+    #[serde(rename = "nextPageToken")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_page_token: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -73,6 +161,8 @@ pub struct GetMessagesBuilder {
     uids: Option<Vec<String>>,
     status_codes: Option<Vec<MessageStatusCode>>,
     pairwise_dids: Option<Vec<String>>,
+    filter: MessageFilter,
+    page: MessagePage,
     version: ProtocolTypes,
 }
 
@@ -89,6 +179,8 @@ impl GetMessagesBuilder {
             exclude_payload: None,
             status_codes: None,
             pairwise_dids: None,
+            filter: MessageFilter::default(),
+            page: MessagePage::default(),
             version: settings::get_protocol_type(),
         }
     }
@@ -117,6 +209,19 @@ impl GetMessagesBuilder {
         Ok(self)
     }
 
+    pub fn filter(&mut self, filter: MessageFilter) -> VcxResult<&mut Self> {
+        self.filter = filter;
+        Ok(self)
+    }
+
+    pub fn page(&mut self, page: MessagePage) -> VcxResult<&mut Self> {
+        if page.page_size == Some(0) {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidOption, "Page size must be positive"));
+        }
+        self.page = page;
+        Ok(self)
+    }
+
     pub fn include_edge_payload(&mut self, payload: &str) -> VcxResult<&mut Self> {
         //todo: is this a json value, String??
         self.exclude_payload = Some(payload.to_string());
@@ -158,6 +263,12 @@ impl GetMessagesBuilder {
     }
 
     pub fn download_messages(&mut self) -> VcxResult<Vec<MessageByConnection>> {
+        self.download_messages_page()
+            .map(|(messages, _)| messages)
+    }
+
+    // Returns downloaded messages together with the token of the next page, if the agency has more messages.
+    pub fn download_messages_page(&mut self) -> VcxResult<(Vec<MessageByConnection>, Option<String>)> {
         trace!("GetMessages::download >>>");
 
         let data = self.prepare_download_request()?;
@@ -165,7 +276,7 @@ impl GetMessagesBuilder {
         let response = httpclient::post_u8(&data)?;
 
         if settings::agency_mocks_enabled() && response.len() == 0 {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), None));
         }
 
         let response = self.parse_download_messages_response(response)?;
@@ -182,7 +293,9 @@ impl GetMessagesBuilder {
                                            self.exclude_payload.clone(),
                                            self.uids.clone(),
                                            self.status_codes.clone(),
-                                           self.pairwise_dids.clone()))
+                                           self.pairwise_dids.clone(),
+                                           self.filter.clone(),
+                                           self.page.clone()))
                 ),
             settings::ProtocolTypes::V2 |
             settings::ProtocolTypes::V3 |
@@ -193,7 +306,9 @@ impl GetMessagesBuilder {
                                            self.exclude_payload.clone(),
                                            self.uids.clone(),
                                            self.status_codes.clone(),
-                                           self.pairwise_dids.clone()))
+                                           self.pairwise_dids.clone(),
+                                           self.filter.clone(),
+                                           self.page.clone()))
                 ),
         };
 
@@ -202,27 +317,29 @@ impl GetMessagesBuilder {
         prepare_message_for_agency(&message, &agency_did, &self.version)
     }
 
-    fn parse_download_messages_response(&self, response: Vec<u8>) -> VcxResult<Vec<MessageByConnection>> {
+    fn parse_download_messages_response(&self, response: Vec<u8>) -> VcxResult<(Vec<MessageByConnection>, Option<String>)> {
         trace!("parse_download_messages_response >>>");
         let mut response = parse_response_from_agency(&response, &self.version)?;
 
         trace!("parse_download_messages_response: parsed response {:?}", response);
-        let msgs = match response.remove(0) {
-            A2AMessage::Version1(A2AMessageV1::GetMessagesByConnectionsResponse(res)) => res.msgs,
-            A2AMessage::Version2(A2AMessageV2::GetMessagesByConnectionsResponse(res)) => res.msgs,
+        let response = match response.remove(0) {
+            A2AMessage::Version1(A2AMessageV1::GetMessagesByConnectionsResponse(res)) => res,
+            A2AMessage::Version2(A2AMessageV2::GetMessagesByConnectionsResponse(res)) => res,
             _ => return Err(VcxError::from_msg(VcxErrorKind::InvalidHttpResponse, "Message does not match any variant of GetMessagesByConnectionsResponse"))
         };
 
-        msgs
+        let msgs = response.msgs
             .iter()
             .map(|connection| {
                 ::utils::libindy::signus::get_local_verkey(&connection.pairwise_did)
                     .map(|vk| MessageByConnection {
                         pairwise_did: connection.pairwise_did.clone(),
-                        msgs: connection.msgs.iter().map(|message| message.decrypt(&vk)).collect(),
+                        msgs: connection.msgs.iter().filter_map(|message| message.decrypt_filtered(&vk, &self.filter)).collect(),
                     })
             })
-            .collect()
+            .collect::<VcxResult<Vec<MessageByConnection>>>()?;
+
+        Ok((msgs, response.next_page_token))
     }
 }
 
@@ -244,7 +361,9 @@ impl GeneralMessage for GetMessagesBuilder {
                                            self.exclude_payload.clone(),
                                            self.uids.clone(),
                                            self.status_codes.clone(),
-                                           self.pairwise_dids.clone()))
+                                           self.pairwise_dids.clone(),
+                                           self.filter.clone(),
+                                           self.page.clone()))
                 ),
             settings::ProtocolTypes::V2 |
             settings::ProtocolTypes::V3 |
@@ -255,7 +374,9 @@ impl GeneralMessage for GetMessagesBuilder {
                                            self.exclude_payload.clone(),
                                            self.uids.clone(),
                                            self.status_codes.clone(),
-                                           self.pairwise_dids.clone()))
+                                           self.pairwise_dids.clone(),
+                                           self.filter.clone(),
+                                           self.page.clone()))
                 ),
         };
 
@@ -340,16 +461,57 @@ impl Message {
         new_message
     }
 
+    // Returns None for messages not matching the filter.
+    // Legacy messages do not match filters by protocol family or thread, so they are discarded without decryption.
+    pub fn decrypt_filtered(&self, vk: &str, filter: &MessageFilter) -> Option<Message> {
+        if !filter.matches_type(&self.msg_type) {
+            return None;
+        }
+
+        if !filter.is_applied_locally() {
+            return Some(self.decrypt(vk));
+        }
+
+        let payload = match self.payload() {
+            Ok(payload) => payload,
+            Err(_) => return None
+        };
+
+        // Sender is checked against the remote DIDDoc once the message is handled by the connection
+        let message = match EncryptionEnvelope::open_raw(payload) {
+            Ok((message, _)) => message,
+            Err(_) => return None
+        };
+
+        if !filter.matches(&message) {
+            return None;
+        }
+
+        let decrypted_payload = ::serde_json::from_value::<::v3::messages::a2a::A2AMessage>(message)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize A2A message: {}", err)))
+            .and_then(|a2a_message| Message::_convert_v3_message(a2a_message));
+
+        let mut new_message = self.clone();
+        new_message.decrypted_payload = match decrypted_payload {
+            Ok(decrypted_payload) => ::serde_json::to_string(&json!(decrypted_payload)).ok(),
+            Err(_) => ::serde_json::to_string(&json!(null)).ok()
+        };
+        new_message.payload = None;
+        Some(new_message)
+    }
+
     fn _decrypt_v3_message(&self) -> VcxResult<::messages::payload::PayloadV1> {
+        let a2a_message = EncryptionEnvelope::open(self.payload()?)?;
+        Message::_convert_v3_message(a2a_message)
+    }
+
+    fn _convert_v3_message(a2a_message: ::v3::messages::a2a::A2AMessage) -> VcxResult<::messages::payload::PayloadV1> {
         use v3::messages::a2a::A2AMessage;
-        use v3::utils::encryption_envelope::EncryptionEnvelope;
         use ::issuer_credential::{CredentialOffer, CredentialMessage};
         use ::messages::proofs::proof_message::ProofMessage;
         use ::messages::payload::{PayloadTypes, PayloadV1, PayloadKinds};
         use std::convert::TryInto;
 
-        let a2a_message = EncryptionEnvelope::open(self.payload()?)?;
-
         let (kind, msg) = match a2a_message.clone() {
             A2AMessage::PresentationRequest(presentation_request) => {
                 convert_aries_message!(presentation_request, a2a_message, ProofRequestMessage, ProofRequest)
@@ -442,8 +604,18 @@ fn _parse_status_code(status_codes: Option<Vec<String>>) -> VcxResult<Option<Vec
 }
 
 pub fn download_messages(pairwise_dids: Option<Vec<String>>, status_codes: Option<Vec<String>>, uids: Option<Vec<String>>) -> VcxResult<Vec<MessageByConnection>> {
-    trace!("download_messages >>> pairwise_dids: {:?}, status_codes: {:?}, uids: {:?}",
-           pairwise_dids, status_codes, uids);
+    download_messages_filtered(pairwise_dids, status_codes, uids, MessageFilter::default())
+}
+
+pub fn download_messages_filtered(pairwise_dids: Option<Vec<String>>, status_codes: Option<Vec<String>>, uids: Option<Vec<String>>, filter: MessageFilter) -> VcxResult<Vec<MessageByConnection>> {
+    download_messages_page(pairwise_dids, status_codes, uids, filter, MessagePage::default())
+        .map(|(messages, _)| messages)
+}
+
+pub fn download_messages_page(pairwise_dids: Option<Vec<String>>, status_codes: Option<Vec<String>>, uids: Option<Vec<String>>,
+                              filter: MessageFilter, page: MessagePage) -> VcxResult<(Vec<MessageByConnection>, Option<String>)> {
+    trace!("download_messages_page >>> pairwise_dids: {:?}, status_codes: {:?}, uids: {:?}, filter: {:?}, page: {:?}",
+           pairwise_dids, status_codes, uids, filter, page);
 
     AgencyMock::set_next_response(constants::GET_ALL_MESSAGES_RESPONSE.to_vec());
 
@@ -454,8 +626,10 @@ pub fn download_messages(pairwise_dids: Option<Vec<String>>, status_codes: Optio
             .uid(uids)?
             .status_codes(status_codes)?
             .pairwise_dids(pairwise_dids)?
+            .filter(filter)?
+            .page(page)?
             .version(&Some(::settings::get_protocol_type()))?
-            .download_messages()?;
+            .download_messages_page()?;
 
     trace!("message returned: {:?}", response);
     Ok(response)
@@ -505,7 +679,98 @@ mod tests {
         let _setup = SetupMocks::init();
 
         let result = GetMessagesBuilder::create().version(&Some(ProtocolTypes::V1)).unwrap().parse_download_messages_response(GET_ALL_MESSAGES_RESPONSE.to_vec()).unwrap();
-        assert_eq!(result.len(), 1)
+        assert_eq!(result.0.len(), 1);
+        assert_eq!(result.1, None);
+    }
+
+    #[test]
+    fn test_message_filter_matches() {
+        let _setup = SetupDefaults::init();
+
+        let message = json!({
+            "@id": "testid",
+            "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/present-proof/1.0/presentation",
+            "~thread": {"thid": "thread-1"}
+        });
+
+        assert!(MessageFilter::default().matches(&message));
+
+        let filter = MessageFilter { protocol_families: Some(vec!["present-proof".to_string()]), thread_id: Some("thread-1".to_string()), ..MessageFilter::default() };
+        assert!(filter.matches(&message));
+
+        let filter = MessageFilter { protocol_families: Some(vec!["issue-credential".to_string()]), ..MessageFilter::default() };
+        assert!(!filter.matches(&message));
+
+        let filter = MessageFilter { thread_id: Some("thread-2".to_string()), ..MessageFilter::default() };
+        assert!(!filter.matches(&message));
+
+        let filter = MessageFilter { thread_id: Some("testid".to_string()), ..MessageFilter::default() };
+        assert!(filter.matches(&json!({"@id": "testid", "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/present-proof/1.0/request-presentation"})));
+    }
+
+    #[test]
+    fn test_get_messages_serializes_filter() {
+        let _setup = SetupDefaults::init();
+
+        let filter = MessageFilter { protocol_families: Some(vec!["present-proof".to_string()]), since: Some(1579000000), ..MessageFilter::default() };
+        let message = GetMessages::build(A2AMessageKinds::GetMessagesByConnections, None, None, None, None, filter, MessagePage::default());
+        let message = ::serde_json::to_value(&message).unwrap();
+
+        assert_eq!(json!(["present-proof"]), message["protocolFamilies"]);
+        assert_eq!(json!(1579000000), message["since"]);
+        assert!(message.get("threadId").is_none());
+        assert!(message.get("pageToken").is_none());
+    }
+
+    #[test]
+    fn test_get_messages_serializes_range_types_and_page() {
+        let _setup = SetupDefaults::init();
+
+        let filter = MessageFilter { since: Some(1579000000), until: Some(1579086400), msg_types: Some(vec!["credOffer".to_string()]), ..MessageFilter::default() };
+        let page = MessagePage { page_size: Some(50), page_token: Some("token".to_string()) };
+        let message = GetMessages::build(A2AMessageKinds::GetMessagesByConnections, None, None, None, None, filter, page);
+        let message = ::serde_json::to_value(&message).unwrap();
+
+        assert_eq!(json!(1579086400), message["until"]);
+        assert_eq!(json!(["credOffer"]), message["msgTypes"]);
+        assert_eq!(json!(50), message["pageSize"]);
+        assert_eq!(json!("token"), message["pageToken"]);
+    }
+
+    #[test]
+    fn test_message_filter_matches_type() {
+        let _setup = SetupDefaults::init();
+
+        assert!(MessageFilter::default().matches_type(&RemoteMessageType::CredOffer));
+
+        let filter = MessageFilter { msg_types: Some(vec!["credOffer".to_string(), "aries".to_string()]), ..MessageFilter::default() };
+        assert!(filter.matches_type(&RemoteMessageType::CredOffer));
+        assert!(filter.matches_type(&RemoteMessageType::Other("aries".to_string())));
+        assert!(!filter.matches_type(&RemoteMessageType::ProofReq));
+    }
+
+    #[test]
+    fn test_messages_by_connections_deserializes_next_page_token() {
+        let _setup = SetupDefaults::init();
+
+        let response = json!({
+            "@type": {"name": "MSGS_BY_CONNS", "ver": "1.0"},
+            "msgsByConns": [],
+            "nextPageToken": "token"
+        });
+        let response: MessagesByConnections = ::serde_json::from_value(response).unwrap();
+        assert_eq!(Some("token".to_string()), response.next_page_token);
+
+        let response: MessagesByConnections = ::serde_json::from_value(json!({"@type": {"name": "MSGS_BY_CONNS", "ver": "1.0"}})).unwrap();
+        assert_eq!(None, response.next_page_token);
+    }
+
+    #[test]
+    fn test_get_messages_builder_fails_for_zero_page_size() {
+        let _setup = SetupDefaults::init();
+
+        let page = MessagePage { page_size: Some(0), page_token: None };
+        assert_eq!(GetMessagesBuilder::create().page(page).unwrap_err().kind(), VcxErrorKind::InvalidOption);
     }
 
     #[cfg(feature = "agency")]
@@ -71,6 +71,7 @@ pub enum MessageFamilies {
     Pairwise,
     Configs,
     CredentialExchange,
+    AgentProvisioning,
     Unknown(String),
 }
 
@@ -82,6 +83,7 @@ impl MessageFamilies {
             MessageFamilies::Pairwise => "1.0",
             MessageFamilies::Configs => "1.0",
             MessageFamilies::CredentialExchange => "1.0",
+            MessageFamilies::AgentProvisioning => "0.7",
             _ => "1.0"
         }
     }
@@ -95,6 +97,7 @@ impl From<String> for MessageFamilies {
             "pairwise" => MessageFamilies::Pairwise,
             "configs" => MessageFamilies::Configs,
             "credential-exchange" => MessageFamilies::CredentialExchange,
+            "agent-provisioning" => MessageFamilies::AgentProvisioning,
             family @ _ => MessageFamilies::Unknown(family.to_string())
         }
     }
@@ -108,6 +111,7 @@ impl ::std::string::ToString for MessageFamilies {
             MessageFamilies::Pairwise => "pairwise".to_string(),
             MessageFamilies::CredentialExchange => "credential_exchange".to_string(),
             MessageFamilies::Configs => "configs".to_string(),
+            MessageFamilies::AgentProvisioning => "agent-provisioning".to_string(),
             MessageFamilies::Unknown(family) => family.to_string()
         }
     }
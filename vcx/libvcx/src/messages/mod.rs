@@ -6,6 +6,7 @@ pub mod send_message;
 pub mod update_profile;
 pub mod proofs;
 pub mod agent_utils;
+pub mod cloud_agent;
 pub mod update_connection;
 pub mod update_message;
 pub mod message_type;
@@ -28,7 +29,7 @@ use self::get_message::{GetMessagesBuilder, GetMessages, GetMessagesResponse, Me
 use self::send_message::SendMessageBuilder;
 use self::update_message::{UpdateMessageStatusByConnections, UpdateMessageStatusByConnectionsResponse};
 use self::proofs::proof_request::ProofRequestMessage;
-use self::agent_utils::{Connect, ConnectResponse, SignUp, SignUpResponse, CreateAgent, CreateAgentResponse, UpdateComMethod, ComMethodUpdated};
+use self::agent_utils::{Connect, ConnectResponse, SignUp, SignUpResponse, CreateAgent, CreateAgentResponse, ProvisionAgent, ProvisionAgentResponse, UpdateComMethod, ComMethodUpdated};
 use self::message_type::*;
 use error::prelude::*;
 
@@ -225,6 +226,8 @@ pub enum A2AMessageV2 {
     SignUpResponse(SignUpResponse),
     CreateAgent(CreateAgent),
     CreateAgentResponse(CreateAgentResponse),
+    ProvisionAgent(ProvisionAgent),
+    ProvisionAgentResponse(ProvisionAgentResponse),
 
     /// PW Connection
     CreateKey(CreateKey),
@@ -263,6 +266,23 @@ impl<'de> Deserialize<'de> for A2AMessageV2 {
         let value = Value::deserialize(deserializer).map_err(de::Error::custom)?;
         let message_type: MessageTypeV2 = serde_json::from_value(value["@type"].clone()).map_err(de::Error::custom)?;
 
+        // Token based provisioning messages share names with onboarding ones
+        if message_type.family == MessageFamilies::AgentProvisioning {
+            return match message_type.type_.as_str() {
+                "CREATE_AGENT" => {
+                    ProvisionAgent::deserialize(value)
+                        .map(A2AMessageV2::ProvisionAgent)
+                        .map_err(de::Error::custom)
+                }
+                "AGENT_CREATED" => {
+                    ProvisionAgentResponse::deserialize(value)
+                        .map(A2AMessageV2::ProvisionAgentResponse)
+                        .map_err(de::Error::custom)
+                }
+                _ => Err(de::Error::custom("Unexpected @type field structure."))
+            };
+        }
+
         match message_type.type_.as_str() {
             "FWD" => {
                 ForwardV2::deserialize(value)
@@ -711,6 +731,8 @@ pub enum A2AMessageKinds {
     SignedUp,
     CreateAgent,
     AgentCreated,
+    ProvisionAgent,
+    AgentProvisioned,
     CreateKey,
     KeyCreated,
     CreateMessage,
@@ -742,6 +764,8 @@ impl A2AMessageKinds {
             A2AMessageKinds::Connected => MessageFamilies::Onboarding,
             A2AMessageKinds::CreateAgent => MessageFamilies::Onboarding,
             A2AMessageKinds::AgentCreated => MessageFamilies::Onboarding,
+            A2AMessageKinds::ProvisionAgent => MessageFamilies::AgentProvisioning,
+            A2AMessageKinds::AgentProvisioned => MessageFamilies::AgentProvisioning,
             A2AMessageKinds::SignUp => MessageFamilies::Onboarding,
             A2AMessageKinds::SignedUp => MessageFamilies::Onboarding,
             A2AMessageKinds::CreateKey => MessageFamilies::Pairwise,
@@ -775,6 +799,8 @@ impl A2AMessageKinds {
             A2AMessageKinds::Connected => "CONNECTED".to_string(),
             A2AMessageKinds::CreateAgent => "CREATE_AGENT".to_string(),
             A2AMessageKinds::AgentCreated => "AGENT_CREATED".to_string(),
+            A2AMessageKinds::ProvisionAgent => "CREATE_AGENT".to_string(),
+            A2AMessageKinds::AgentProvisioned => "AGENT_CREATED".to_string(),
             A2AMessageKinds::SignUp => "SIGNUP".to_string(),
             A2AMessageKinds::SignedUp => "SIGNED_UP".to_string(),
             A2AMessageKinds::CreateKey => "CREATE_KEY".to_string(),
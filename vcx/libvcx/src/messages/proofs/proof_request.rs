@@ -62,7 +62,13 @@ pub struct PredicateInfo {
     pub name: String,
     //Todo: Update p_type to use Enum
     pub p_type: String,
-    pub p_value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p_value: Option<i32>,
+    // bounds of RANGE predicate, both inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub restrictions: Option<Restrictions>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -510,6 +516,22 @@ mod tests {
         assert_eq!(request.proof_request_data.requested_predicates, check_predicates);
     }
 
+    #[test]
+    fn test_requested_predicates_constructed_correctly_for_range() {
+        let _setup = SetupDefaults::init();
+
+        let predicates = json!([{"name": "age", "p_type": "RANGE", "from": 18, "to": 65}]).to_string();
+
+        let request = proof_request().requested_predicates(&predicates).unwrap().clone();
+        let predicate = &request.proof_request_data.requested_predicates["age"];
+        assert_eq!(None, predicate.p_value);
+        assert_eq!(Some(18), predicate.from);
+        assert_eq!(Some(65), predicate.to);
+
+        let expected = json!({"name": "age", "p_type": "RANGE", "from": 18, "to": 65});
+        assert_eq!(expected, serde_json::to_value(predicate).unwrap());
+    }
+
     #[test]
     fn test_requested_attrs_constructed_correctly_for_names() {
         let _setup = SetupDefaults::init();
@@ -22,6 +22,11 @@ impl Thread {
         self
     }
 
+    pub fn set_pthid(mut self, pthid: String) -> Thread {
+        self.pthid = Some(pthid);
+        self
+    }
+
     pub fn increment_receiver(&mut self, did: &str) {
         self.received_orders.entry(did.to_string())
             .and_modify(|e| *e += 1)
@@ -52,8 +57,15 @@ macro_rules! threadlike (($type:ident) => (
             self
         }
 
+        pub fn set_parent_thread_id(mut self, id: &str) -> $type {
+            self.thread.pthid = Some(id.to_string());
+            self
+        }
+
         pub fn from_thread(&self, id: &str) -> bool {
             self.thread.is_reply(id)
         }
     }
+
+    threaded!($type);
 ));
\ No newline at end of file
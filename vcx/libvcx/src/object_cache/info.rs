@@ -0,0 +1,126 @@
+use connection;
+use credential;
+use disclosed_proof;
+use issuer_credential;
+use proof;
+use error::prelude::*;
+use utils::serialization::ObjectType;
+
+// Introspection of protocol objects by handle regardless of their type
+
+const OBJECT_TYPES: [ObjectType; 5] = [
+    ObjectType::Connection,
+    ObjectType::Credential,
+    ObjectType::IssuerCredential,
+    ObjectType::Proof,
+    ObjectType::DisclosedProof,
+];
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ObjectInfo {
+    pub handle: u32,
+    #[serde(rename = "type")]
+    pub object_type: ObjectType,
+    pub source_id: String,
+    pub state: u32,
+    // Thread of Aries credential and presentation exchanges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+}
+
+pub fn get(handle: u32) -> VcxResult<ObjectInfo> {
+    trace!("object_info::get >>> handle: {}", handle);
+
+    let object_type = OBJECT_TYPES.iter()
+        .find(|object_type| _is_valid_handle(**object_type, handle))
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Object not found for handle: {}", handle)))?;
+
+    _info(*object_type, handle)
+}
+
+// Live objects of the current context of the type (or of all types), including persisted ones which aren't loaded into memory
+pub fn list(object_type: Option<ObjectType>) -> Vec<ObjectInfo> {
+    trace!("object_info::list >>> object_type: {:?}", object_type);
+
+    OBJECT_TYPES.iter()
+        .filter(|type_| object_type.map(|object_type| object_type == **type_).unwrap_or(true))
+        .flat_map(|object_type| _handles(*object_type).into_iter()
+            // skips objects released meanwhile
+            .filter_map(move |handle| _info(*object_type, handle).ok()))
+        .collect()
+}
+
+fn _is_valid_handle(object_type: ObjectType, handle: u32) -> bool {
+    match object_type {
+        ObjectType::Connection => connection::is_valid_handle(handle),
+        ObjectType::Credential => credential::is_valid_handle(handle),
+        ObjectType::IssuerCredential => issuer_credential::is_valid_handle(handle),
+        ObjectType::Proof => proof::is_valid_handle(handle),
+        ObjectType::DisclosedProof => disclosed_proof::is_valid_handle(handle),
+    }
+}
+
+fn _handles(object_type: ObjectType) -> Vec<u32> {
+    match object_type {
+        ObjectType::Connection => connection::get_handles(),
+        ObjectType::Credential => credential::get_handles(),
+        ObjectType::IssuerCredential => issuer_credential::get_handles(),
+        ObjectType::Proof => proof::get_handles(),
+        ObjectType::DisclosedProof => disclosed_proof::get_handles(),
+    }
+}
+
+fn _info(object_type: ObjectType, handle: u32) -> VcxResult<ObjectInfo> {
+    let (source_id, state, thread_id) = match object_type {
+        ObjectType::Connection =>
+            (connection::get_source_id(handle)?, connection::get_state(handle), None),
+        ObjectType::Credential =>
+            (credential::get_source_id(handle)?, credential::get_state(handle)?, credential::get_thread_id(handle)?),
+        ObjectType::IssuerCredential =>
+            (issuer_credential::get_source_id(handle)?, issuer_credential::get_state(handle)?, issuer_credential::get_thread_id(handle)?),
+        ObjectType::Proof =>
+            (proof::get_source_id(handle)?, proof::get_state(handle)?, proof::get_thread_id(handle)?),
+        ObjectType::DisclosedProof =>
+            (disclosed_proof::get_source_id(handle)?, disclosed_proof::get_state(handle)?, disclosed_proof::get_thread_id(handle)?),
+    };
+
+    Ok(ObjectInfo { handle, object_type, source_id, state, thread_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use api::VcxStateType;
+    use utils::devsetup::*;
+
+    #[test]
+    fn test_object_info_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let connection_handle = connection::create_connection("test_object_info").unwrap();
+        let proof_handle = proof::create_proof("test_object_info_proof".to_string(),
+                                               json!([{"name": "name"}]).to_string(),
+                                               json!([]).to_string(),
+                                               r#"{"support_revocation":false}"#.to_string(),
+                                               "Optional".to_owned()).unwrap();
+
+        let info = get(connection_handle).unwrap();
+        assert_eq!(ObjectInfo {
+            handle: connection_handle,
+            object_type: ObjectType::Connection,
+            source_id: "test_object_info".to_string(),
+            state: VcxStateType::VcxStateInitialized as u32,
+            thread_id: None,
+        }, info);
+
+        let info = get(proof_handle).unwrap();
+        assert_eq!(ObjectType::Proof, info.object_type);
+        assert_eq!("test_object_info_proof", info.source_id);
+
+        let proofs = list(Some(ObjectType::Proof));
+        assert!(proofs.iter().any(|info| info.handle == proof_handle));
+        assert!(!proofs.iter().any(|info| info.handle == connection_handle));
+
+        assert_eq!(VcxErrorKind::InvalidHandle, get(0).unwrap_err().kind());
+    }
+}
@@ -1,111 +1,363 @@
 use rand::Rng;
-use std::sync::Mutex;
-use std::sync::MutexGuard;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
+use context::{self, ContextHandle};
 use error::prelude::*;
+use settings;
+use utils::libindy::wallet;
 
+pub mod info;
+
+const RECORD_OPTIONS: &str = r#"{"retrieveType":false,"retrieveValue":true,"retrieveTags":false}"#;
+const SEARCH_OPTIONS: &str = r#"{"retrieveRecords":true,"retrieveTotalCount":false,"retrieveType":false,"retrieveValue":false,"retrieveTags":false}"#;
+const SEARCH_BATCH_SIZE: usize = 100;
+
+/*
+    Objects are locked one by one: operations on different objects run concurrently,
+    the store itself is locked only to look up, add or remove objects.
+    Every object is reference counted, so an object being used isn't dropped if it is released or evicted meanwhile.
+
+    Persisted objects over `object_cache_size` of their context are evicted from memory, least recently used first.
+    They are kept in the wallet and restored on access by handle. Objects whose last change couldn't be persisted
+    are kept in memory until it's persisted.
+
+    Every object belongs to the context it was created or restored in and can't be accessed from other contexts.
+*/
 pub struct ObjectCache<T> {
-    pub store: Mutex<HashMap<u32, Mutex<T>>>,
+    store: RwLock<HashMap<u32, Entry<T>>>,
+    persistence: Option<Persistence<T>>,
+    clock: AtomicUsize,
+}
+
+struct Entry<T> {
+    obj: Arc<Mutex<T>>,
+    context: ContextHandle,
+    last_used: AtomicUsize,
+    // the wallet holds the current state of the object
+    persisted: AtomicBool,
+}
+
+impl<T> Entry<T> {
+    fn is_owned(&self) -> bool {
+        self.context == context::current()
+    }
+}
+
+// Objects are kept in wallet records of the type under their handles if `persist_objects` setting is enabled
+struct Persistence<T> {
+    record_type: &'static str,
+    serialize: fn(&T) -> VcxResult<String>,
+    deserialize: fn(&str) -> VcxResult<T>,
 }
 
 impl<T> Default for ObjectCache<T> {
     fn default() -> ObjectCache<T>
     {
         ObjectCache {
-            store: Default::default()
+            store: Default::default(),
+            persistence: None,
+            clock: AtomicUsize::new(0),
         }
     }
 }
 
 impl<T> ObjectCache<T> {
-    fn _lock_store(&self) -> VcxResult<MutexGuard<HashMap<u32, Mutex<T>>>> {
-        match self.store.lock() {
-            Ok(g) => Ok(g),
-            Err(e) => {
-                error!("Unable to lock Object Store: {:?}", e);
-                Err(VcxError::from_msg(VcxErrorKind::Common(10), format!("Unable to lock Object Store: {:?}", e)))
+    pub fn persisted(record_type: &'static str,
+                     serialize: fn(&T) -> VcxResult<String>,
+                     deserialize: fn(&str) -> VcxResult<T>) -> ObjectCache<T> {
+        ObjectCache {
+            store: Default::default(),
+            persistence: Some(Persistence { record_type, serialize, deserialize }),
+            clock: AtomicUsize::new(0),
+        }
+    }
+
+    fn _persistence(&self) -> Option<&Persistence<T>> {
+        self.persistence.as_ref().filter(|_| settings::persist_objects_enabled())
+    }
+
+    fn _entry(&self, obj: T, persisted: bool) -> Entry<T> {
+        Entry {
+            obj: Arc::new(Mutex::new(obj)),
+            context: context::current(),
+            last_used: AtomicUsize::new(self._tick()),
+            persisted: AtomicBool::new(persisted),
+        }
+    }
+
+    fn _tick(&self) -> usize {
+        self.clock.fetch_add(1, Ordering::SeqCst)
+    }
+
+    // Returns whether the object is persisted. Failure to persist does not fail the operation already applied
+    // to the object, it is kept in memory and persisted with the next change.
+    fn _persist(&self, handle: u32, obj: &T) -> bool {
+        let persistence = match self._persistence() {
+            Some(persistence) => persistence,
+            None => return false
+        };
+
+        let result = (persistence.serialize)(obj)
+            .and_then(|value| {
+                wallet::update_record_value(persistence.record_type, &handle.to_string(), &value)
+                    .or_else(|err| match err.kind() {
+                        VcxErrorKind::WalletRecordNotFound => wallet::add_record(persistence.record_type, &handle.to_string(), &value, None),
+                        _ => Err(err)
+                    })
+            });
+
+        match result {
+            Ok(()) => true,
+            Err(err) => {
+                error!("ObjectCache: cannot persist {} object {}: {}", persistence.record_type, handle, err);
+                false
             }
         }
     }
 
-    pub fn has_handle(&self, handle: u32) -> bool {
-        let store = match self._lock_store() {
-            Ok(g) => g,
-            Err(_) => return false
+    fn _unpersist(&self, handle: u32) {
+        if let Some(persistence) = self._persistence() {
+            wallet::delete_record(persistence.record_type, &handle.to_string()).ok();
+        }
+    }
+
+    // Loads the object persisted before restart (or evicted) into the store
+    fn _restore(&self, store: &mut HashMap<u32, Entry<T>>, handle: u32) {
+        if store.contains_key(&handle) { return; }
+
+        let persistence = match self._persistence() {
+            Some(persistence) => persistence,
+            None => return
+        };
+
+        let obj = wallet::get_record(persistence.record_type, &handle.to_string(), RECORD_OPTIONS)
+            .and_then(|record| ::serde_json::from_str::<::serde_json::Value>(&record)
+                .to_vcx(VcxErrorKind::InvalidJson, "Cannot deserialize wallet record"))
+            .and_then(|record| record["value"].as_str().map(String::from)
+                .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Wallet record has no value")))
+            .and_then(|value| (persistence.deserialize)(&value));
+
+        match obj {
+            Ok(obj) => {
+                debug!("ObjectCache: restored {} object {}", persistence.record_type, handle);
+                store.insert(handle, self._entry(obj, true));
+            }
+            Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => {}
+            Err(err) => warn!("ObjectCache: cannot restore {} object {}: {}", persistence.record_type, handle, err)
+        }
+    }
+
+    // Unloads least recently used objects of the current context which aren't being used and are persisted.
+    // Limit and persistence are settings of the current context, so objects of other contexts are left intact.
+    fn _evict(&self, store: &mut HashMap<u32, Entry<T>>) {
+        let loaded = store.values().filter(|entry| entry.is_owned()).count();
+
+        let (persistence, limit) = match (self._persistence(), settings::get_object_cache_size()) {
+            (Some(persistence), Some(limit)) if loaded > limit => (persistence, limit),
+            _ => return
+        };
+
+        let mut unused: Vec<(usize, u32)> = store.iter()
+            .filter(|(_, entry)| entry.is_owned() && entry.persisted.load(Ordering::SeqCst) && Arc::strong_count(&entry.obj) == 1)
+            .map(|(handle, entry)| (entry.last_used.load(Ordering::SeqCst), *handle))
+            .collect();
+        unused.sort();
+
+        let count = loaded - limit;
+        for (_, handle) in unused.into_iter().take(count) {
+            debug!("ObjectCache: evicted {} object {}", persistence.record_type, handle);
+            store.remove(&handle);
+        }
+    }
+
+    fn _read_store(&self) -> VcxResult<RwLockReadGuard<HashMap<u32, Entry<T>>>> {
+        self.store.read()
+            .map_err(|e| {
+                error!("Unable to lock Object Store: {:?}", e);
+                VcxError::from_msg(VcxErrorKind::Common(10), format!("Unable to lock Object Store: {:?}", e))
+            })
+    }
+
+    fn _write_store(&self) -> VcxResult<RwLockWriteGuard<HashMap<u32, Entry<T>>>> {
+        self.store.write()
+            .map_err(|e| {
+                error!("Unable to lock Object Store: {:?}", e);
+                VcxError::from_msg(VcxErrorKind::Common(10), format!("Unable to lock Object Store: {:?}", e))
+            })
+    }
+
+    // Takes a reference to the object, restoring it if it isn't loaded
+    fn _acquire(&self, handle: u32) -> VcxResult<Arc<Mutex<T>>> {
+        if let Some(entry) = self._read_store()?.get(&handle) {
+            return self._use(handle, entry);
+        }
+
+        let mut store = self._write_store()?;
+        self._restore(&mut store, handle);
+
+        let obj = match store.get(&handle) {
+            Some(entry) => self._use(handle, entry)?,
+            None => return Err(_not_found(handle))
         };
-        store.contains_key(&handle)
+
+        self._evict(&mut store);
+
+        Ok(obj)
+    }
+
+    // Object of another context is reported as not found
+    fn _use(&self, handle: u32, entry: &Entry<T>) -> VcxResult<Arc<Mutex<T>>> {
+        if !entry.is_owned() {
+            return Err(_not_found(handle));
+        }
+
+        entry.last_used.store(self._tick(), Ordering::SeqCst);
+        Ok(entry.obj.clone())
+    }
+
+    pub fn has_handle(&self, handle: u32) -> bool {
+        self._acquire(handle).is_ok()
     }
 
     pub fn get<F, R>(&self, handle: u32, closure: F) -> VcxResult<R>
         where F: Fn(&T) -> VcxResult<R> {
-        let store = self._lock_store()?;
-        match store.get(&handle) {
-            Some(m) => match m.lock() {
-                Ok(obj) => closure(obj.deref()),
-                Err(_) => Err(VcxError::from_msg(VcxErrorKind::Common(10), "Unable to lock Object Store")) //TODO better error
-            },
-            None => Err(VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Object not found for handle: {}", handle)))
-        }
+        let obj = self._acquire(handle)?;
+        let obj = obj.lock()
+            .map_err(|_| VcxError::from_msg(VcxErrorKind::Common(10), "Unable to lock Object Store"))?; //TODO better error
+        closure(obj.deref())
     }
 
     pub fn get_mut<F, R>(&self, handle: u32, closure: F) -> VcxResult<R>
         where F: Fn(&mut T) -> VcxResult<R> {
-        let mut store = self._lock_store()?;
-        match store.get_mut(&handle) {
-            Some(m) => match m.lock() {
-                Ok(mut obj) => closure(obj.deref_mut()),
-                Err(_) => Err(VcxError::from_msg(VcxErrorKind::Common(10), "Unable to lock Object Store")) //TODO better error
-            },
-            None => Err(VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Object not found for handle: {}", handle)))
+        let entry = self._acquire(handle)?;
+        let mut obj = entry.lock()
+            .map_err(|_| VcxError::from_msg(VcxErrorKind::Common(10), "Unable to lock Object Store"))?; //TODO better error
+
+        let result = closure(obj.deref_mut());
+
+        // Object released meanwhile must not be persisted again
+        let store = self._read_store()?;
+        if let Some(stored) = store.get(&handle).filter(|stored| Arc::ptr_eq(&stored.obj, &entry)) {
+            stored.persisted.store(self._persist(handle, obj.deref()), Ordering::SeqCst);
         }
+
+        result
     }
 
     pub fn add(&self, obj: T) -> VcxResult<u32> {
-        let mut store = self._lock_store()?;
+        let mut store = self._write_store()?;
 
         let mut new_handle = rand::thread_rng().gen::<u32>();
         loop {
+            self._restore(&mut store, new_handle);
             if !store.contains_key(&new_handle) {
                 break;
             }
             new_handle = rand::thread_rng().gen::<u32>();
         }
 
-        match store.insert(new_handle, Mutex::new(obj)) {
-            Some(_) => Ok(new_handle),
-            None => Ok(new_handle)
-        }
+        let persisted = self._persist(new_handle, &obj);
+
+        store.insert(new_handle, self._entry(obj, persisted));
+        self._evict(&mut store);
+
+        Ok(new_handle)
     }
 
     pub fn insert(&self, handle: u32, obj: T) -> VcxResult<()> {
-        let mut store = self._lock_store()?;
+        let mut store = self._write_store()?;
 
-        match store.insert(handle, Mutex::new(obj)) {
-            _ => Ok(()),
+        if store.get(&handle).map(|entry| !entry.is_owned()).unwrap_or(false) {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Handle is used by another context: {}", handle)));
         }
+
+        let persisted = self._persist(handle, &obj);
+
+        store.insert(handle, self._entry(obj, persisted));
+        self._evict(&mut store);
+
+        Ok(())
     }
 
     pub fn release(&self, handle: u32) -> VcxResult<()> {
-        let mut store = self._lock_store()?;
-        match store.remove(&handle) {
-            Some(_) => Ok(()),
-            None => Err(VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Object not found for handle: {}", handle)))
+        let mut store = self._write_store()?;
+        self._restore(&mut store, handle);
+
+        if !store.get(&handle).map(Entry::is_owned).unwrap_or(false) {
+            return Err(_not_found(handle));
+        }
+
+        store.remove(&handle);
+        self._unpersist(handle);
+        Ok(())
+    }
+
+    // Handle of the first object of the current context loaded into memory matching the predicate
+    pub fn find<F>(&self, predicate: F) -> Option<u32>
+        where F: Fn(&T) -> bool {
+        let objects: Vec<(u32, Arc<Mutex<T>>)> = self._read_store().ok()?.iter()
+            .filter(|(_, entry)| entry.is_owned())
+            .map(|(handle, entry)| (*handle, entry.obj.clone()))
+            .collect();
+
+        objects.into_iter()
+            .find(|(_, obj)| obj.lock().map(|obj| predicate(obj.deref())).unwrap_or(false))
+            .map(|(handle, _)| handle)
+    }
+
+    // Handles of objects of the current context loaded into memory and persisted in its wallet
+    pub fn handles(&self) -> Vec<u32> {
+        let mut handles: Vec<u32> = match self._read_store() {
+            Ok(store) => store.iter()
+                .filter(|(_, entry)| entry.is_owned())
+                .map(|(handle, _)| *handle)
+                .collect(),
+            Err(_) => Vec::new()
+        };
+
+        if let Some(persistence) = self._persistence() {
+            match wallet::search_all_records(persistence.record_type, "{}", SEARCH_OPTIONS, SEARCH_BATCH_SIZE) {
+                Ok(records) => handles.extend(records.into_iter()
+                    .filter_map(|record| record.id.and_then(|id| id.parse::<u32>().ok()))),
+                Err(err) => warn!("ObjectCache: cannot search {} objects: {}", persistence.record_type, err)
+            }
+            handles.sort();
+            handles.dedup();
         }
+
+        handles
     }
 
+    // Number of objects of the current context loaded into memory
+    pub fn loaded_count(&self) -> usize {
+        self._read_store()
+            .map(|store| store.values().filter(|entry| entry.is_owned()).count())
+            .unwrap_or(0)
+    }
+
+    // Unloads objects of the current context. Persisted objects are kept in the wallet to be restored after restart
     pub fn drain(&self) -> VcxResult<()> {
-        let mut store = self._lock_store()?;
-        Ok(store.clear())
+        let mut store = self._write_store()?;
+        store.retain(|_, entry| !entry.is_owned());
+        Ok(())
     }
 }
 
+fn _not_found(handle: u32) -> VcxError {
+    VcxError::from_msg(VcxErrorKind::InvalidHandle, format!("Object not found for handle: {}", handle))
+}
+
 #[cfg(test)]
 mod tests {
     use object_cache::ObjectCache;
-    use utils::devsetup::SetupDefaults;
+    use context;
+    use error::prelude::*;
+    use settings;
+    use utils::devsetup::{SetupDefaults, SetupLibraryWallet};
 
     #[test]
     fn create_test() {
@@ -155,4 +407,202 @@ mod tests {
 
         assert_eq!("TEST", string);
     }
+
+    #[test]
+    fn find_test() {
+        let _setup = SetupDefaults::init();
+
+        let test: ObjectCache<u32> = Default::default();
+        test.add(1111).unwrap();
+        let handle = test.add(2222).unwrap();
+
+        assert_eq!(Some(handle), test.find(|obj| *obj == 2222));
+        assert_eq!(None, test.find(|obj| *obj == 3333));
+    }
+
+    #[test]
+    fn handles_test() {
+        let _setup = SetupDefaults::init();
+
+        let test: ObjectCache<u32> = Default::default();
+        assert!(test.handles().is_empty());
+
+        let handle = test.add(1111).unwrap();
+        assert_eq!(vec![handle], test.handles());
+    }
+
+    #[test]
+    fn objects_are_isolated_per_context_test() {
+        let _setup = SetupDefaults::init();
+
+        let test: ObjectCache<u32> = Default::default();
+        let handle = test.add(1111).unwrap();
+
+        let other = context::create().unwrap();
+
+        context::run_in(other, || {
+            assert!(!test.has_handle(handle));
+            assert_eq!(VcxErrorKind::InvalidHandle, test.get(handle, |obj| Ok(obj.clone())).unwrap_err().kind());
+            assert_eq!(VcxErrorKind::InvalidHandle, test.get_mut(handle, |_| Ok(())).unwrap_err().kind());
+            assert_eq!(VcxErrorKind::InvalidHandle, test.insert(handle, 2222).unwrap_err().kind());
+            assert_eq!(VcxErrorKind::InvalidHandle, test.release(handle).unwrap_err().kind());
+            assert_eq!(None, test.find(|obj| *obj == 1111));
+            assert!(test.handles().is_empty());
+            assert_eq!(0, test.loaded_count());
+            test.drain().unwrap();
+        });
+
+        assert_eq!(1111, test.get(handle, |obj| Ok(obj.clone())).unwrap());
+        assert_eq!(Some(handle), test.find(|obj| *obj == 1111));
+
+        test.release(handle).unwrap();
+        context::release(other).unwrap();
+    }
+
+    fn _serialize(obj: &String) -> VcxResult<String> {
+        Ok(obj.clone())
+    }
+
+    fn _deserialize(data: &str) -> VcxResult<String> {
+        Ok(data.to_string())
+    }
+
+    #[test]
+    fn persisted_object_test() {
+        let _setup = SetupLibraryWallet::init();
+        settings::set_config_value(settings::CONFIG_PERSIST_OBJECTS, "true");
+
+        let test: ObjectCache<String> = ObjectCache::persisted("TestObject", _serialize, _deserialize);
+        let handle = test.add(String::from("TEST")).unwrap();
+        test.get_mut(handle, |obj| {
+            obj.push_str("_UPDATED");
+            Ok(())
+        }).unwrap();
+
+        // restart
+        test.drain().unwrap();
+
+        assert!(test.has_handle(handle));
+        assert_eq!("TEST_UPDATED", test.get(handle, |obj| Ok(obj.clone())).unwrap());
+
+        test.release(handle).unwrap();
+        test.drain().unwrap();
+        assert!(!test.has_handle(handle));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_persisted_objects_test() {
+        let _setup = SetupLibraryWallet::init();
+        settings::set_config_value(settings::CONFIG_PERSIST_OBJECTS, "true");
+        settings::set_config_value(settings::CONFIG_OBJECT_CACHE_SIZE, "2");
+
+        let test: ObjectCache<String> = ObjectCache::persisted("TestObject", _serialize, _deserialize);
+        let handle_1 = test.add(String::from("TEST_1")).unwrap();
+        let handle_2 = test.add(String::from("TEST_2")).unwrap();
+
+        test.get(handle_1, |_| Ok(())).unwrap();
+
+        let handle_3 = test.add(String::from("TEST_3")).unwrap();
+        assert_eq!(2, test.loaded_count());
+
+        let mut handles = vec![handle_1, handle_2, handle_3];
+        handles.sort();
+        assert_eq!(handles, test.handles());
+
+        // evicted object is restored on access
+        assert_eq!("TEST_2", test.get(handle_2, |obj| Ok(obj.clone())).unwrap());
+        assert_eq!(2, test.loaded_count());
+
+        for handle in handles {
+            test.release(handle).unwrap();
+        }
+    }
+
+    #[test]
+    fn keeps_objects_in_use_test() {
+        let _setup = SetupLibraryWallet::init();
+        settings::set_config_value(settings::CONFIG_PERSIST_OBJECTS, "true");
+        settings::set_config_value(settings::CONFIG_OBJECT_CACHE_SIZE, "1");
+
+        let test: ObjectCache<String> = ObjectCache::persisted("TestObject", _serialize, _deserialize);
+        let handle = test.add(String::from("TEST")).unwrap();
+
+        test.get(handle, |_| {
+            let other = test.add(String::from("OTHER")).unwrap();
+            assert!(test.store.read().unwrap().contains_key(&handle));
+            assert_eq!("OTHER", test.get(other, |obj| Ok(obj.clone())).unwrap());
+            test.release(other)
+        }).unwrap();
+
+        assert_eq!("TEST", test.get(handle, |obj| Ok(obj.clone())).unwrap());
+        test.release(handle).unwrap();
+    }
+
+    #[test]
+    fn evicts_objects_of_current_context_only_test() {
+        let _setup = SetupLibraryWallet::init();
+        settings::set_config_value(settings::CONFIG_PERSIST_OBJECTS, "true");
+        settings::set_config_value(settings::CONFIG_OBJECT_CACHE_SIZE, "1");
+
+        let test: ObjectCache<String> = ObjectCache::persisted("TestObject", _serialize, _deserialize);
+
+        let other = context::create().unwrap();
+        let other_handle = context::run_in(other, || test.add(String::from("OTHER"))).unwrap();
+
+        let handle_1 = test.add(String::from("TEST_1")).unwrap();
+        let handle_2 = test.add(String::from("TEST_2")).unwrap();
+
+        assert_eq!(1, test.loaded_count());
+        assert_eq!(1, context::run_in(other, || test.loaded_count()));
+        assert!(context::run_in(other, || test.has_handle(other_handle)));
+
+        test.release(handle_1).unwrap();
+        test.release(handle_2).unwrap();
+        context::run_in(other, || test.release(other_handle)).unwrap();
+        context::release(other).unwrap();
+    }
+
+    fn _serialize_fails(_obj: &String) -> VcxResult<String> {
+        Err(VcxError::from(VcxErrorKind::SerializationError))
+    }
+
+    #[test]
+    fn does_not_evict_objects_not_persisted_test() {
+        let _setup = SetupLibraryWallet::init();
+        settings::set_config_value(settings::CONFIG_PERSIST_OBJECTS, "true");
+        settings::set_config_value(settings::CONFIG_OBJECT_CACHE_SIZE, "1");
+
+        let test: ObjectCache<String> = ObjectCache::persisted("TestObject", _serialize_fails, _deserialize);
+        let handle_1 = test.add(String::from("TEST_1")).unwrap();
+        let handle_2 = test.add(String::from("TEST_2")).unwrap();
+
+        assert_eq!(2, test.loaded_count());
+        assert_eq!("TEST_1", test.get(handle_1, |obj| Ok(obj.clone())).unwrap());
+
+        test.release(handle_1).unwrap();
+        test.release(handle_2).unwrap();
+    }
+
+    #[test]
+    fn does_not_evict_objects_without_persistence_test() {
+        let _setup = SetupDefaults::init();
+        settings::set_config_value(settings::CONFIG_OBJECT_CACHE_SIZE, "1");
+
+        let test: ObjectCache<u32> = Default::default();
+        test.add(1111).unwrap();
+        test.add(2222).unwrap();
+
+        assert_eq!(2, test.loaded_count());
+    }
+
+    #[test]
+    fn persistence_is_opt_in_test() {
+        let _setup = SetupLibraryWallet::init();
+
+        let test: ObjectCache<String> = ObjectCache::persisted("TestObject", _serialize, _deserialize);
+        let handle = test.add(String::from("TEST")).unwrap();
+
+        test.drain().unwrap();
+        assert!(!test.has_handle(handle));
+    }
 }
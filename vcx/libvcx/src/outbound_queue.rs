@@ -0,0 +1,535 @@
+use serde_json;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use context::{self, ContextHandle};
+use error::prelude::*;
+use scheduler;
+use scheduler::Schedule;
+use utils::libindy::wallet;
+use utils::{transport, uuid};
+use utils::timeout::TimeoutUtils;
+use v3::messages::connection::did_doc::TlsConstraints;
+
+/*
+    Durable queue of outbound DIDComm messages.
+    While it is started messages sent to HTTP endpoints are stored in the wallet before the delivery and removed
+    once the endpoint accepted them. Failed deliveries are retried by a scheduled task with exponential backoff.
+    Messages which couldn't be delivered within `max_attempts` are moved to dead-letter state and kept there
+    until the application retries or deletes them.
+    The queue is started per context: messages are kept in the wallet of the context.
+    A message is delivered by one thread at a time: the delivering thread claims it as in flight, concurrent
+    deliveries (scheduled retries, `retry` called by the application) skip it until the delivery is finished.
+*/
+
+lazy_static! {
+    static ref QUEUES: Mutex<HashMap<ContextHandle, QueueConfig>> = Default::default();
+    static ref IN_FLIGHT: Mutex<HashSet<(ContextHandle, String)>> = Default::default();
+}
+
+const DELIVERY_TASK: &str = "outbound_queue.deliver";
+const MESSAGE_RECORD_TYPE: &str = "VcxOutboundMessage";
+const SEARCH_BATCH_SIZE: usize = 100;
+
+fn default_retry_interval() -> u64 { 10 }
+
+fn default_initial_delay() -> u64 { 5 }
+
+fn default_max_delay() -> u64 { 3600 }
+
+fn default_max_attempts() -> u32 { 10 }
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct QueueConfig {
+    // seconds between checks for messages due for retry
+    #[serde(default = "default_retry_interval")]
+    pub retry_interval: u64,
+    // seconds before the first retry, doubled with every failed attempt
+    #[serde(default = "default_initial_delay")]
+    pub initial_delay: u64,
+    // the longest delay between retries in seconds
+    #[serde(default = "default_max_delay")]
+    pub max_delay: u64,
+    // failed deliveries after which the message is moved to dead-letter state
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+impl QueueConfig {
+    fn retry_delay(&self, attempts: u32) -> i64 {
+        let delay = self.initial_delay << attempts.saturating_sub(1).min(16);
+        delay.min(self.max_delay) as i64
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageState {
+    Pending,
+    DeadLetter,
+}
+
+impl MessageState {
+    fn tag(&self) -> &'static str {
+        match self {
+            MessageState::Pending => "pending",
+            MessageState::DeadLetter => "dead_letter",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutboundMessage {
+    pub id: String,
+    pub endpoint: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConstraints>,
+    // packed message, base64 encoded
+    pub message: String,
+    pub state: MessageState,
+    pub attempts: u32,
+    pub created: i64,
+    pub next_attempt: i64,
+    pub last_error: Option<String>,
+}
+
+impl OutboundMessage {
+    fn failed(mut self, config: &QueueConfig, err: &VcxError, now: i64) -> OutboundMessage {
+        self.attempts += 1;
+        self.last_error = Some(err.to_string());
+        self.next_attempt = now + config.retry_delay(self.attempts);
+
+        if self.attempts >= config.max_attempts {
+            self.state = MessageState::DeadLetter;
+        }
+
+        self
+    }
+}
+
+// Claim of a message being delivered, released when the delivery is finished
+struct InFlight((ContextHandle, String));
+
+impl InFlight {
+    fn claim(id: &str) -> Option<InFlight> {
+        let key = (context::current(), id.to_string());
+
+        if IN_FLIGHT.lock().unwrap().insert(key.clone()) {
+            Some(InFlight(key))
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT.lock().unwrap().remove(&self.0);
+    }
+}
+
+pub fn start(config: &str) -> VcxResult<()> {
+    trace!("outbound_queue::start >>> config: {}", config);
+
+    let config: QueueConfig = serde_json::from_str(config)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidConfiguration, format!("Cannot deserialize outbound queue config: {:?}", err)))?;
+
+    if config.retry_interval == 0 || config.max_attempts == 0 {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidConfiguration, "Outbound queue `retry_interval` and `max_attempts` must be greater than 0"));
+    }
+
+    let mut queues = QUEUES.lock().unwrap();
+
+    if queues.contains_key(&context::current()) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidState, "Outbound queue is already started"));
+    }
+
+    scheduler::register(DELIVERY_TASK, Schedule::every(config.retry_interval), deliver_pending)?;
+
+    queues.insert(context::current(), config);
+
+    Ok(())
+}
+
+// Stops retrying. Stored messages are kept and delivered once the queue is started again.
+pub fn stop() -> VcxResult<()> {
+    trace!("outbound_queue::stop >>>");
+
+    if QUEUES.lock().unwrap().remove(&context::current()).is_none() {
+        return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Outbound queue is not started"));
+    }
+
+    scheduler::unregister(DELIVERY_TASK);
+
+    Ok(())
+}
+
+pub fn is_running() -> bool {
+    QUEUES.lock().unwrap().contains_key(&context::current())
+}
+
+fn _config() -> VcxResult<QueueConfig> {
+    QUEUES.lock().unwrap().get(&context::current()).cloned()
+        .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Outbound queue is not started"))
+}
+
+// Stores the message and tries to deliver it right away.
+// Succeeds once the message is stored, failed delivery is retried later.
+pub fn send(endpoint: &str, tls: Option<TlsConstraints>, message: &[u8]) -> VcxResult<()> {
+    trace!("outbound_queue::send >>> endpoint: {}", endpoint);
+
+    let config = _config()?;
+    let now = _now();
+    let id = uuid::uuid();
+
+    let _in_flight = InFlight::claim(&id);
+
+    // The first retry isn't due before the delivery below timed out, so even a queue without
+    // the in flight claim (e.g. started again by another process) doesn't send the message twice
+    let message = OutboundMessage {
+        id,
+        endpoint: endpoint.to_string(),
+        tls,
+        message: ::base64::encode(message),
+        state: MessageState::Pending,
+        attempts: 0,
+        created: now,
+        next_attempt: now + TimeoutUtils::long_timeout().as_secs() as i64 + config.retry_delay(1),
+        last_error: None,
+    };
+
+    wallet::add_record(MESSAGE_RECORD_TYPE, &message.id, &_serialize(&message)?, Some(&_tags(message.state)))?;
+
+    if let Err(err) = _deliver(&config, message) {
+        warn!("outbound_queue: delivery failed, will be retried: {}", err);
+    }
+
+    Ok(())
+}
+
+// Delivers pending messages which are due. All messages are tried, the first error is returned.
+// Messages being delivered by another thread are skipped.
+pub fn deliver_pending() -> VcxResult<()> {
+    let config = _config()?;
+    let now = _now();
+
+    let mut res = Ok(());
+    for message in _search(Some(MessageState::Pending))? {
+        if message.next_attempt > now {
+            continue;
+        }
+
+        let _in_flight = match InFlight::claim(&message.id) {
+            Some(in_flight) => in_flight,
+            None => continue
+        };
+
+        // the message could be delivered or rescheduled since the search
+        let message = match _get(&message.id) {
+            Ok(ref message) if message.state != MessageState::Pending || message.next_attempt > now => continue,
+            Ok(message) => Ok(message),
+            Err(ref err) if err.kind() == VcxErrorKind::InvalidOption => continue,
+            Err(err) => Err(err)
+        };
+
+        if let Err(err) = message.and_then(|message| _deliver(&config, message)) {
+            if res.is_ok() {
+                res = Err(err);
+            }
+        }
+    }
+    res
+}
+
+// Returns stored messages (all if state isn't set) as JSON array
+pub fn get_messages(state: Option<MessageState>) -> VcxResult<String> {
+    trace!("outbound_queue::get_messages >>> state: {:?}", state);
+
+    let messages = _search(state)?;
+
+    serde_json::to_string(&messages)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize outbound messages: {:?}", err)))
+}
+
+// Moves the message back to pending state with a fresh count of attempts and tries to deliver it right away.
+// Fails if the message is being delivered right now.
+pub fn retry(id: &str) -> VcxResult<()> {
+    trace!("outbound_queue::retry >>> id: {}", id);
+
+    let config = _config()?;
+
+    let _in_flight = InFlight::claim(id)
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, format!("Outbound message is being delivered: {}", id)))?;
+
+    let mut message = _get(id)?;
+    message.state = MessageState::Pending;
+    message.attempts = 0;
+    message.next_attempt = _now() + TimeoutUtils::long_timeout().as_secs() as i64 + config.retry_delay(1);
+    _store(&message)?;
+
+    _deliver(&config, message)
+}
+
+pub fn delete(id: &str) -> VcxResult<()> {
+    trace!("outbound_queue::delete >>> id: {}", id);
+
+    wallet::delete_record(MESSAGE_RECORD_TYPE, id)
+        .map_err(|err| match err.kind() {
+            VcxErrorKind::WalletRecordNotFound => VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Outbound message not found: {}", id)),
+            _ => err
+        })
+}
+
+fn _deliver(config: &QueueConfig, message: OutboundMessage) -> VcxResult<()> {
+    let res = ::base64::decode(&message.message)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidMessagePack, format!("Cannot decode outbound message: {:?}", err)))
+        .and_then(|payload| transport::for_endpoint(&message.endpoint, message.tls.clone(), None).send(&payload));
+
+    match res {
+        Ok(_) => wallet::delete_record(MESSAGE_RECORD_TYPE, &message.id),
+        Err(err) => {
+            let message = message.failed(config, &err, _now());
+
+            if message.state == MessageState::DeadLetter {
+                warn!("outbound_queue: message {} to {} is moved to dead letters after {} attempts", message.id, message.endpoint, message.attempts);
+            }
+
+            _store(&message)?;
+            Err(err)
+        }
+    }
+}
+
+fn _get(id: &str) -> VcxResult<OutboundMessage> {
+    let options = json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+
+    let record = wallet::get_record(MESSAGE_RECORD_TYPE, id, &options)
+        .map_err(|err| match err.kind() {
+            VcxErrorKind::WalletRecordNotFound => VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Outbound message not found: {}", id)),
+            _ => err
+        })?;
+
+    let record: serde_json::Value = serde_json::from_str(&record)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize wallet record: {:?}", err)))?;
+
+    _deserialize(record["value"].as_str().unwrap_or_default())
+}
+
+fn _search(state: Option<MessageState>) -> VcxResult<Vec<OutboundMessage>> {
+    let query = match state {
+        Some(state) => json!({"state": state.tag()}),
+        None => json!({})
+    };
+    let options = json!({"retrieveRecords": true, "retrieveTotalCount": false, "retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+
+    wallet::search_all_records(MESSAGE_RECORD_TYPE, &query.to_string(), &options, SEARCH_BATCH_SIZE)?
+        .into_iter()
+        .map(|record| _deserialize(record.value.as_ref().map(String::as_str).unwrap_or_default()))
+        .collect()
+}
+
+fn _store(message: &OutboundMessage) -> VcxResult<()> {
+    wallet::update_record_value(MESSAGE_RECORD_TYPE, &message.id, &_serialize(message)?)?;
+    wallet::update_record_tags(MESSAGE_RECORD_TYPE, &message.id, &_tags(message.state))
+}
+
+fn _tags(state: MessageState) -> String {
+    json!({"state": state.tag()}).to_string()
+}
+
+fn _serialize(message: &OutboundMessage) -> VcxResult<String> {
+    serde_json::to_string(message)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize outbound message: {:?}", err)))
+}
+
+fn _deserialize(message: &str) -> VcxResult<OutboundMessage> {
+    serde_json::from_str(message)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize outbound message: {:?}", err)))
+}
+
+fn _now() -> i64 {
+    ::time::get_time().sec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings;
+    use utils::devsetup::*;
+
+    // Nothing listens on the port, so delivery fails
+    const UNREACHABLE_ENDPOINT: &str = "http://127.0.0.1:9/agent";
+
+    fn _queue_config(max_attempts: u32) -> QueueConfig {
+        QueueConfig { retry_interval: 3600, initial_delay: 5, max_delay: 60, max_attempts }
+    }
+
+    fn _start(max_attempts: u32) {
+        start(&json!({"retry_interval": 3600, "max_attempts": max_attempts}).to_string()).unwrap();
+    }
+
+    fn _messages(state: Option<MessageState>) -> Vec<OutboundMessage> {
+        serde_json::from_str(&get_messages(state).unwrap()).unwrap()
+    }
+
+    fn _set_reachable(reachable: bool) {
+        settings::set_config_value(settings::CONFIG_ENABLE_TEST_MODE, if reachable { "agency" } else { "false" });
+    }
+
+    #[test]
+    fn test_retry_delay_works() {
+        let config = _queue_config(10);
+
+        assert_eq!(5, config.retry_delay(1));
+        assert_eq!(10, config.retry_delay(2));
+        assert_eq!(40, config.retry_delay(4));
+        assert_eq!(60, config.retry_delay(5));
+        assert_eq!(60, config.retry_delay(100));
+    }
+
+    #[test]
+    fn test_outbound_message_failed_works() {
+        let config = _queue_config(2);
+        let message = OutboundMessage {
+            id: "1".to_string(),
+            endpoint: UNREACHABLE_ENDPOINT.to_string(),
+            tls: None,
+            message: String::new(),
+            state: MessageState::Pending,
+            attempts: 0,
+            created: 0,
+            next_attempt: 0,
+            last_error: None,
+        };
+
+        let message = message.failed(&config, &VcxError::from(VcxErrorKind::PostMessageFailed), 100);
+        assert_eq!(MessageState::Pending, message.state);
+        assert_eq!(105, message.next_attempt);
+        assert!(message.last_error.is_some());
+
+        let message = message.failed(&config, &VcxError::from(VcxErrorKind::PostMessageFailed), 105);
+        assert_eq!(MessageState::DeadLetter, message.state);
+        assert_eq!(2, message.attempts);
+    }
+
+    #[test]
+    fn test_start_fails_for_invalid_config() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(start(r#"{"retry_interval": 0}"#).unwrap_err().kind(), VcxErrorKind::InvalidConfiguration);
+        assert_eq!(start(r#"{"max_attempts": 0}"#).unwrap_err().kind(), VcxErrorKind::InvalidConfiguration);
+        assert_eq!(start("invalid").unwrap_err().kind(), VcxErrorKind::InvalidConfiguration);
+        assert!(!is_running());
+    }
+
+    #[test]
+    fn test_send_works_for_reachable_endpoint() {
+        let _setup = SetupAgencyMock::init();
+
+        _start(3);
+        send(UNREACHABLE_ENDPOINT, None, &[1, 2, 3]).unwrap();
+        assert!(_messages(None).is_empty());
+
+        stop().unwrap();
+    }
+
+    #[test]
+    fn test_send_keeps_message_until_delivered() {
+        let _setup = SetupAgencyMock::init();
+
+        _start(3);
+
+        _set_reachable(false);
+        send(UNREACHABLE_ENDPOINT, None, &[1, 2, 3]).unwrap();
+
+        let messages = _messages(Some(MessageState::Pending));
+        assert_eq!(1, messages.len());
+        assert_eq!(1, messages[0].attempts);
+        assert_eq!(vec![1, 2, 3], ::base64::decode(&messages[0].message).unwrap());
+
+        // not due yet
+        deliver_pending().unwrap();
+        assert_eq!(1, _messages(None).len());
+
+        _set_reachable(true);
+        retry(&messages[0].id).unwrap();
+        assert!(_messages(None).is_empty());
+
+        stop().unwrap();
+    }
+
+    #[test]
+    fn test_send_moves_message_to_dead_letters() {
+        let _setup = SetupAgencyMock::init();
+
+        _start(1);
+
+        _set_reachable(false);
+        send(UNREACHABLE_ENDPOINT, None, &[1, 2, 3]).unwrap();
+
+        assert!(_messages(Some(MessageState::Pending)).is_empty());
+        let dead_letters = _messages(Some(MessageState::DeadLetter));
+        assert_eq!(1, dead_letters.len());
+        assert!(dead_letters[0].last_error.is_some());
+
+        assert!(retry(&dead_letters[0].id).is_err());
+        assert_eq!(1, _messages(Some(MessageState::DeadLetter)).len());
+
+        delete(&dead_letters[0].id).unwrap();
+        assert!(_messages(None).is_empty());
+        assert_eq!(delete(&dead_letters[0].id).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+
+        _set_reachable(true);
+        stop().unwrap();
+    }
+
+    #[test]
+    fn test_message_in_flight_is_not_delivered_again() {
+        let _setup = SetupAgencyMock::init();
+
+        _start(3);
+
+        _set_reachable(false);
+        send(UNREACHABLE_ENDPOINT, None, &[1, 2, 3]).unwrap();
+        let id = _messages(Some(MessageState::Pending))[0].id.clone();
+
+        _set_reachable(true);
+        {
+            let _in_flight = InFlight::claim(&id).unwrap();
+            assert!(InFlight::claim(&id).is_none());
+
+            assert_eq!(retry(&id).unwrap_err().kind(), VcxErrorKind::InvalidState);
+            assert_eq!(1, _messages(None).len());
+        }
+
+        retry(&id).unwrap();
+        assert!(_messages(None).is_empty());
+
+        stop().unwrap();
+    }
+
+    #[test]
+    fn test_send_fails_for_not_started_queue() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(send(UNREACHABLE_ENDPOINT, None, &[1, 2, 3]).unwrap_err().kind(), VcxErrorKind::NotReady);
+        assert_eq!(stop().unwrap_err().kind(), VcxErrorKind::NotReady);
+    }
+
+    #[test]
+    fn test_queue_is_started_per_context() {
+        let _setup = SetupAgencyMock::init();
+
+        let other = context::create().unwrap();
+
+        _start(3);
+        assert!(is_running());
+        assert!(!context::run_in(other, is_running));
+        assert_eq!(context::run_in(other, stop).unwrap_err().kind(), VcxErrorKind::NotReady);
+
+        stop().unwrap();
+        context::release(other).unwrap();
+    }
+}
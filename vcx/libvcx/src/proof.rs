@@ -19,6 +19,8 @@ use object_cache::ObjectCache;
 use error::prelude::*;
 use utils::openssl::encode;
 use utils::qualifier;
+use utils::serialization::{self, ObjectType};
+use events;
 use messages::proofs::proof_message::get_credential_info;
 
 use v3::handlers::proof_presentation::verifier::verifier::Verifier;
@@ -26,7 +28,7 @@ use utils::agent_info::{get_agent_info, MyAgentInfo, get_agent_attr};
 use settings::get_config_value;
 
 lazy_static! {
-    static ref PROOF_MAP: ObjectCache<Proofs> = Default::default();
+    static ref PROOF_MAP: ObjectCache<Proofs> = ObjectCache::persisted("VcxProof", _serialize, _deserialize);
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -44,6 +46,9 @@ enum Proofs {
 struct RevocationInterval {
     from: Option<u64>,
     to: Option<u64>,
+    // referents for which credentials without revocation support are accepted (Aries protocol only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    accept_non_revocable: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -286,6 +291,12 @@ impl Proof {
     }
 
     pub fn validate_indy_proof(proof_json: &str, proof_req_json: &str) -> VcxResult<bool> {
+        Proof::validate_indy_proof_accepting_non_revocable(proof_json, proof_req_json, &[])
+    }
+
+    // Credentials without revocation support are accepted for the given referents even if
+    // the proof request demands non-revocation interval.
+    pub fn validate_indy_proof_accepting_non_revocable(proof_json: &str, proof_req_json: &str, non_revocable_referents: &[String]) -> VcxResult<bool> {
         if settings::indy_mocks_enabled() {
             let mock_result: bool = get_config_value(settings::MOCK_INDY_PROOF_VALIDATION).unwrap_or("true".into()).parse().unwrap();
             return Ok(mock_result);
@@ -310,12 +321,34 @@ impl Proof {
         debug!("*******\n{}\n********", proof_req_json);
         debug!("*******\n{}\n********", rev_reg_defs_json);
         debug!("*******\n{}\n********", rev_regs_json);
-        anoncreds::libindy_verifier_verify_proof(proof_req_json,
-                                                 proof_json,
-                                                 &schemas_json,
-                                                 &credential_defs_json,
-                                                 &rev_reg_defs_json,
-                                                 &rev_regs_json)
+
+        if non_revocable_referents.is_empty() {
+            return anoncreds::libindy_verifier_verify_proof(proof_req_json,
+                                                            proof_json,
+                                                            &schemas_json,
+                                                            &credential_defs_json,
+                                                            &rev_reg_defs_json,
+                                                            &rev_regs_json);
+        }
+
+        let options = json!({"non_revocable_referents": non_revocable_referents}).to_string();
+
+        let result = anoncreds::libindy_verifier_verify_proof_with_details(proof_req_json,
+                                                                           proof_json,
+                                                                           &schemas_json,
+                                                                           &credential_defs_json,
+                                                                           &rev_reg_defs_json,
+                                                                           &rev_regs_json,
+                                                                           Some(&options))?;
+
+        let result: serde_json::Value = serde_json::from_str(&result)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize verification result: {:?}", err)))?;
+
+        if let Some(accepted) = result["non_revocable_accepted"].as_array() {
+            info!("credentials without revocation support were accepted for referents: {:?}", accepted);
+        }
+
+        Ok(result["valid"].as_bool().unwrap_or(false))
     }
 
     fn generate_proof_request_msg(&mut self) -> VcxResult<String> {
@@ -506,7 +539,9 @@ pub fn is_valid_handle(handle: u32) -> bool {
 }
 
 pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
-    PROOF_MAP.get_mut(handle, |obj| {
+    let prev_state = get_state(handle).unwrap_or_default();
+
+    let res = PROOF_MAP.get_mut(handle, |obj| {
         match obj {
             Proofs::Pending(ref mut obj) => {
                 obj.update_state(message.clone())
@@ -521,7 +556,35 @@ pub fn update_state(handle: u32, message: Option<String>) -> VcxResult<u32> {
                 Ok(obj.state())
             }
         }
-    })
+    })?;
+
+    _publish_state_change(handle, prev_state);
+    Ok(res)
+}
+
+fn _publish_state_change(handle: u32, prev_state: u32) {
+    if events::has_subscribers() {
+        if let (Ok(source_id), Ok(state)) = (get_source_id(handle), get_state(handle)) {
+            events::state_changed(ObjectType::Proof, handle, source_id, prev_state, state);
+        }
+    }
+}
+
+// Advances state with a message already downloaded from the agency (e.g. delivered in push notification)
+// without querying the agency for new messages.
+pub fn update_state_with_agency_message(handle: u32, connection_handle: u32, message: &str) -> VcxResult<u32> {
+    PROOF_MAP.get(handle, |obj| {
+        match obj {
+            Proofs::V3(_) => Ok(()),
+            _ => Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Agency messages can be processed only for Aries proofs"))
+        }
+    })?;
+
+    let message = ::connection::decode_agency_message(connection_handle, message)?;
+
+    update_state(handle, Some(message))?;
+
+    get_state(handle)
 }
 
 pub fn get_state(handle: u32) -> VcxResult<u32> {
@@ -544,6 +607,16 @@ pub fn get_proof_state(handle: u32) -> VcxResult<u32> {
     })
 }
 
+pub fn get_problem_report(handle: u32) -> VcxResult<String> {
+    PROOF_MAP.get(handle, |obj| {
+        match obj {
+            Proofs::Pending(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidProofHandle, "Cannot get problem report for V1 Proof object")),
+            Proofs::V1(_) => Err(VcxError::from_msg(VcxErrorKind::InvalidProofHandle, "Cannot get problem report for V1 Proof object")),
+            Proofs::V3(ref obj) => obj.get_problem_report()
+        }
+    })
+}
+
 pub fn release(handle: u32) -> VcxResult<()> {
     PROOF_MAP.release(handle).or(Err(VcxError::from(VcxErrorKind::InvalidProofHandle)))
 }
@@ -552,11 +625,20 @@ pub fn release_all() {
     PROOF_MAP.drain().ok();
 }
 
+pub fn get_handles() -> Vec<u32> {
+    PROOF_MAP.handles()
+}
+
+fn _serialize(obj: &Proofs) -> VcxResult<String> {
+    serialization::serialize(ObjectType::Proof, obj)
+}
+
+fn _deserialize(data: &str) -> VcxResult<Proofs> {
+    serialization::deserialize(ObjectType::Proof, data)
+}
+
 pub fn to_string(handle: u32) -> VcxResult<String> {
-    PROOF_MAP.get(handle, |obj| {
-        serde_json::to_string(obj)
-            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("cannot serialize Proof object: {:?}", err)))
-    })
+    PROOF_MAP.get(handle, _serialize)
 }
 
 pub fn get_source_id(handle: u32) -> VcxResult<String> {
@@ -569,11 +651,18 @@ pub fn get_source_id(handle: u32) -> VcxResult<String> {
     })
 }
 
-pub fn from_string(proof_data: &str) -> VcxResult<u32> {
-    let proof: Proofs = serde_json::from_str(proof_data)
-        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("cannot deserialize Proofs object: {:?}", err)))?;
+// Thread of Aries presentation exchange
+pub fn get_thread_id(handle: u32) -> VcxResult<Option<String>> {
+    PROOF_MAP.get(handle, |obj| {
+        match obj {
+            Proofs::Pending(_) | Proofs::V1(_) => Ok(None),
+            Proofs::V3(ref obj) => Ok(Some(obj.get_thread_id()).filter(|thread_id| !thread_id.is_empty()))
+        }
+    })
+}
 
-    PROOF_MAP.add(proof)
+pub fn from_string(proof_data: &str) -> VcxResult<u32> {
+    PROOF_MAP.add(_deserialize(proof_data)?)
 }
 
 pub fn generate_proof_request_msg(handle: u32) -> VcxResult<String> {
@@ -709,7 +798,7 @@ pub mod tests {
             agent_vk: None,
             proof: None,
             proof_request: None,
-            revocation_interval: RevocationInterval { from: None, to: None },
+            revocation_interval: RevocationInterval { from: None, to: None, accept_non_revocable: Vec::new() },
             thread: Some(Thread::new()),
         };
         apply_agent_info(&mut proof, &agent_info);
@@ -883,7 +972,7 @@ pub mod tests {
             their_vk: None,
             agent_did: None,
             agent_vk: None,
-            revocation_interval: RevocationInterval { from: None, to: None },
+            revocation_interval: RevocationInterval { from: None, to: None, accept_non_revocable: Vec::new() },
             thread: Some(Thread::new()),
         };
 
@@ -1094,7 +1183,7 @@ pub mod tests {
             agent_vk: None,
             proof: Some(proof_msg),
             proof_request: Some(proof_req_msg),
-            revocation_interval: RevocationInterval { from: None, to: None },
+            revocation_interval: RevocationInterval { from: None, to: None, accept_non_revocable: Vec::new() },
             thread: Some(Thread::new()),
         };
         apply_agent_info(&mut proof, &default_agent_info(None));
@@ -0,0 +1,314 @@
+use serde_json;
+use serde_json::Value;
+
+use error::prelude::*;
+use messages::proofs::proof_request::{AttrInfo, PredicateInfo};
+use proof;
+use utils::libindy::wallet;
+
+/*
+    Proof request templates of a verifier.
+    A template is a skeleton of proof request stored in the wallet:
+        {
+            "name": string,
+            "requested_attributes": [AttrInfo],
+            "requested_predicates": [PredicateInfo],
+            "non_revoked": Optional<{"from": Optional<time>, "to": Optional<time>}>,
+            "accept_non_revocable": Optional<[string]>
+        }
+    `non_revoked` intervals of attributes and predicates accept the same times.
+    A time is either an absolute timestamp or relative to the moment the template is instantiated:
+    "now", "now-86400" (a day ago) or "now+3600" (in an hour).
+
+    Every proof created from the template gets a fresh nonce and the relative times resolved to timestamps.
+*/
+
+const RECORD_TYPE: &str = "VcxProofTemplate";
+const RECORD_OPTIONS: &str = r#"{"retrieveType":false,"retrieveValue":true,"retrieveTags":false}"#;
+
+const NOW: &str = "now";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct ProofTemplate {
+    name: String,
+    #[serde(default)]
+    requested_attributes: Vec<Value>,
+    #[serde(default)]
+    requested_predicates: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    non_revoked: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    accept_non_revocable: Vec<String>,
+}
+
+// Arguments of `proof::create_proof`
+#[derive(Debug, PartialEq)]
+struct ProofRequestInstance {
+    name: String,
+    requested_attrs: String,
+    requested_predicates: String,
+    revocation_details: String,
+}
+
+impl ProofTemplate {
+    fn from_str(template: &str) -> VcxResult<ProofTemplate> {
+        serde_json::from_str(template)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize proof template: {}", err)))
+    }
+
+    fn to_string(&self) -> VcxResult<String> {
+        serde_json::to_string(self)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize proof template: {}", err)))
+    }
+
+    fn instantiate(&self, now: u64) -> VcxResult<ProofRequestInstance> {
+        let requested_attributes = self.requested_attributes.iter()
+            .map(|attribute| _resolve_item::<AttrInfo>(attribute, now))
+            .collect::<VcxResult<Vec<AttrInfo>>>()?;
+
+        if let Some(attribute) = requested_attributes.iter().find(|attribute| attribute.name.is_some() == attribute.names.is_some()) {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidProofRequest,
+                                          format!("Requested attribute of proof template must have either name or names: {:?}", attribute)));
+        }
+
+        let requested_predicates = self.requested_predicates.iter()
+            .map(|predicate| _resolve_item::<PredicateInfo>(predicate, now))
+            .collect::<VcxResult<Vec<PredicateInfo>>>()?;
+
+        let mut revocation_details = match self.non_revoked {
+            Some(ref interval) => _resolve_interval(interval, now)?,
+            None => json!({})
+        };
+        revocation_details["accept_non_revocable"] = json!(self.accept_non_revocable);
+
+        Ok(ProofRequestInstance {
+            name: self.name.clone(),
+            requested_attrs: json!(requested_attributes).to_string(),
+            requested_predicates: json!(requested_predicates).to_string(),
+            revocation_details: revocation_details.to_string(),
+        })
+    }
+}
+
+// Stores the template replacing the one stored with the same id
+pub fn store(template_id: &str, template: &str) -> VcxResult<()> {
+    trace!("proof_template::store >>> template_id: {}, template: {}", template_id, template);
+
+    let template = ProofTemplate::from_str(template)?;
+
+    // reports malformed templates when they are stored rather than on every instantiation
+    template.instantiate(_now())?;
+
+    let template = template.to_string()?;
+
+    wallet::update_record_value(RECORD_TYPE, template_id, &template)
+        .or_else(|_| wallet::add_record(RECORD_TYPE, template_id, &template, None))
+}
+
+pub fn get(template_id: &str) -> VcxResult<String> {
+    trace!("proof_template::get >>> template_id: {}", template_id);
+
+    _get(template_id)?.to_string()
+}
+
+pub fn delete(template_id: &str) -> VcxResult<()> {
+    trace!("proof_template::delete >>> template_id: {}", template_id);
+
+    wallet::delete_record(RECORD_TYPE, template_id)
+}
+
+// Creates a proof requesting the template with the nonce and times of the moment
+pub fn create_proof(source_id: String, template_id: &str) -> VcxResult<u32> {
+    trace!("proof_template::create_proof >>> source_id: {}, template_id: {}", source_id, template_id);
+
+    let instance = _get(template_id)?.instantiate(_now())?;
+
+    debug!("creating proof {} from template {}: {:?}", source_id, template_id, instance);
+
+    proof::create_proof(source_id,
+                        instance.requested_attrs,
+                        instance.requested_predicates,
+                        instance.revocation_details,
+                        instance.name)
+}
+
+fn _get(template_id: &str) -> VcxResult<ProofTemplate> {
+    let record = wallet::get_record(RECORD_TYPE, template_id, RECORD_OPTIONS)?;
+
+    let record = wallet::WalletRecord::from_str(&record)?;
+
+    let template = record.value
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Proof template has no value: {}", template_id)))?;
+
+    ProofTemplate::from_str(&template)
+}
+
+fn _now() -> u64 {
+    ::time::get_time().sec as u64
+}
+
+fn _resolve_item<T>(item: &Value, now: u64) -> VcxResult<T> where T: ::serde::de::DeserializeOwned {
+    let mut item = item.clone();
+
+    if let Some(interval) = item.get("non_revoked").cloned() {
+        item["non_revoked"] = _resolve_interval(&interval, now)?;
+    }
+
+    serde_json::from_value(item.clone())
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidProofRequest, format!("Invalid item of proof template: {}, err: {}", item, err)))
+}
+
+fn _resolve_interval(interval: &Value, now: u64) -> VcxResult<Value> {
+    let mut interval = interval.clone();
+
+    if !interval.is_object() {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidProofRequest, format!("Invalid interval of proof template: {}", interval)));
+    }
+
+    for bound in ["from", "to"].iter() {
+        let time = match interval.get(*bound) {
+            Some(Value::String(time)) => _resolve_time(time, now)?,
+            Some(Value::Number(_)) | Some(Value::Null) | None => continue,
+            Some(time) => return Err(VcxError::from_msg(VcxErrorKind::InvalidProofRequest, format!("Invalid time of proof template: {}", time)))
+        };
+        interval[*bound] = json!(time);
+    }
+
+    Ok(interval)
+}
+
+fn _resolve_time(time: &str, now: u64) -> VcxResult<u64> {
+    let invalid_time = || VcxError::from_msg(VcxErrorKind::InvalidProofRequest, format!("Invalid time of proof template: {}", time));
+
+    let time = time.trim();
+
+    if !time.starts_with(NOW) {
+        return Err(invalid_time());
+    }
+
+    let offset = time[NOW.len()..].trim();
+
+    if offset.is_empty() {
+        return Ok(now);
+    }
+
+    let mut chars = offset.chars();
+    let sign = chars.next();
+    let seconds = chars.as_str().trim().parse::<u64>().map_err(|_| invalid_time())?;
+
+    let time = match sign {
+        Some('-') => now.checked_sub(seconds),
+        Some('+') => now.checked_add(seconds),
+        _ => None
+    };
+
+    time.ok_or_else(invalid_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::devsetup::*;
+
+    const NOW_TIME: u64 = 1_600_000_000;
+
+    fn _template() -> String {
+        json!({
+            "name": "Employment check",
+            "requested_attributes": [
+                {"name": "name", "restrictions": [{"issuer_did": "V4SGRU86Z58d6TV7PBUe6f"}]},
+                {"names": ["employer", "position"], "non_revoked": {"to": "now"}}
+            ],
+            "requested_predicates": [
+                {"name": "age", "p_type": ">=", "p_value": 18}
+            ],
+            "non_revoked": {"from": "now-86400", "to": "now"},
+            "accept_non_revocable": ["attribute_0"]
+        }).to_string()
+    }
+
+    #[test]
+    fn test_resolve_time() {
+        assert_eq!(NOW_TIME, _resolve_time("now", NOW_TIME).unwrap());
+        assert_eq!(NOW_TIME - 86400, _resolve_time("now-86400", NOW_TIME).unwrap());
+        assert_eq!(NOW_TIME + 3600, _resolve_time("now + 3600", NOW_TIME).unwrap());
+
+        assert_eq!(VcxErrorKind::InvalidProofRequest, _resolve_time("yesterday", NOW_TIME).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidProofRequest, _resolve_time("now*2", NOW_TIME).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidProofRequest, _resolve_time("now-abc", NOW_TIME).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidProofRequest, _resolve_time("now-1600000001", NOW_TIME).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_instantiate_template() {
+        let instance = ProofTemplate::from_str(&_template()).unwrap().instantiate(NOW_TIME).unwrap();
+
+        assert_eq!("Employment check", instance.name);
+
+        let requested_attrs: Vec<AttrInfo> = serde_json::from_str(&instance.requested_attrs).unwrap();
+        assert_eq!(2, requested_attrs.len());
+        assert_eq!(None, requested_attrs[0].non_revoked);
+        assert_eq!(Some(NOW_TIME), requested_attrs[1].non_revoked.as_ref().unwrap().to);
+
+        let requested_predicates: Vec<PredicateInfo> = serde_json::from_str(&instance.requested_predicates).unwrap();
+        assert_eq!("age", requested_predicates[0].name);
+
+        assert_eq!(json!({"from": NOW_TIME - 86400, "to": NOW_TIME, "accept_non_revocable": ["attribute_0"]}),
+                   serde_json::from_str::<Value>(&instance.revocation_details).unwrap());
+    }
+
+    #[test]
+    fn test_instantiate_template_fails_for_invalid_template() {
+        let template = json!({
+            "name": "Invalid",
+            "requested_attributes": [{"name": "name", "non_revoked": {"from": "last week"}}]
+        });
+        let template: ProofTemplate = serde_json::from_value(template).unwrap();
+        assert_eq!(VcxErrorKind::InvalidProofRequest, template.instantiate(NOW_TIME).unwrap_err().kind());
+
+        let template = json!({
+            "name": "Invalid",
+            "requested_attributes": [{"name": "name", "names": ["name", "age"]}]
+        });
+        let template: ProofTemplate = serde_json::from_value(template).unwrap();
+        assert_eq!(VcxErrorKind::InvalidProofRequest, template.instantiate(NOW_TIME).unwrap_err().kind());
+
+        let template = json!({
+            "name": "Invalid",
+            "requested_predicates": [{"name": "age"}]
+        });
+        let template: ProofTemplate = serde_json::from_value(template).unwrap();
+        assert_eq!(VcxErrorKind::InvalidProofRequest, template.instantiate(NOW_TIME).unwrap_err().kind());
+
+        assert_eq!(VcxErrorKind::InvalidJson, ProofTemplate::from_str(r#"{"requested_attributes":[]}"#).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_store_template_and_create_proof() {
+        let _setup = SetupLibraryWallet::init();
+
+        store("employment", &_template()).unwrap();
+        assert_eq!(ProofTemplate::from_str(&_template()).unwrap(), ProofTemplate::from_str(&get("employment").unwrap()).unwrap());
+
+        let handle_1 = create_proof("proof_1".to_string(), "employment").unwrap();
+        let handle_2 = create_proof("proof_2".to_string(), "employment").unwrap();
+
+        let request_1: Value = serde_json::from_str(&proof::generate_proof_request_msg(handle_1).unwrap()).unwrap();
+        let request_2: Value = serde_json::from_str(&proof::generate_proof_request_msg(handle_2).unwrap()).unwrap();
+
+        assert_eq!(json!("Employment check"), request_1["proof_request_data"]["name"]);
+        assert!(request_1["proof_request_data"]["requested_attributes"]["name"].is_object());
+        assert!(request_1["from_timestamp"].is_number());
+        assert_ne!(request_1["proof_request_data"]["nonce"], request_2["proof_request_data"]["nonce"]);
+
+        let template = json!({"name": "Updated", "requested_attributes": [{"name": "name"}]}).to_string();
+        store("employment", &template).unwrap();
+        assert_eq!("Updated", ProofTemplate::from_str(&get("employment").unwrap()).unwrap().name);
+
+        delete("employment").unwrap();
+        assert_eq!(VcxErrorKind::WalletRecordNotFound, get("employment").unwrap_err().kind());
+        assert_eq!(VcxErrorKind::WalletRecordNotFound, create_proof("proof_3".to_string(), "employment").unwrap_err().kind());
+
+        assert_eq!(VcxErrorKind::InvalidProofRequest, store("invalid", r#"{"name":"Invalid","non_revoked":{"to":"tomorrow"}}"#).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,455 @@
+use serde_json;
+use serde_json::Value;
+use reqwest;
+use reqwest::header::CONTENT_TYPE;
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::sync::Mutex;
+
+use context::{self, ContextHandle};
+use settings;
+use error::prelude::*;
+use scheduler;
+use scheduler::Schedule;
+use utils::libindy::{anoncreds, wallet};
+
+/*
+    Embeddable service batching revocations of the issuer.
+    While it is started revoked credentials are only updated in the wallet, deltas are queued per
+    revocation registry and published on the ledger merged by a scheduled task.
+    Pending deltas are kept in the wallet until they are published, so they survive failed publications,
+    stop and restart of the publisher.
+    Tails files of newly created revocation registries are uploaded to the configured tails server.
+    The publisher is started per context and publishes with the issuer DID of the context.
+*/
+
+lazy_static! {
+    static ref PUBLISHERS: Mutex<HashMap<ContextHandle, Publisher>> = Default::default();
+}
+
+const PUBLISH_TASK: &str = "revocation_publisher.publish";
+const PENDING_RECORD_TYPE: &str = "VcxRevocationPendingDeltas";
+const SEARCH_BATCH_SIZE: usize = 100;
+
+fn default_publish_interval() -> u64 { 60 }
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct PublisherConfig {
+    // seconds between publications of pending deltas
+    #[serde(default = "default_publish_interval")]
+    pub publish_interval: u64,
+    // publish as soon as this number of revocations is pending for a registry
+    pub max_batch_size: Option<u32>,
+    // tails files are uploaded with PUT to <tails_upload_url>/<rev_reg_id>
+    pub tails_upload_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RevRegStatus {
+    pub pending_revocations: u32,
+    pub published_revocations: u64,
+    pub last_published: Option<i64>,
+    pub tails_uploaded: bool,
+    pub last_error: Option<String>,
+}
+
+struct Publisher {
+    config: PublisherConfig,
+    // deltas of revocations not published yet in the order of revocation
+    pending: HashMap<String, Vec<String>>,
+    // registries which deltas are being published
+    publishing: HashSet<String>,
+    status: HashMap<String, RevRegStatus>,
+}
+
+pub fn start(config: &str) -> VcxResult<()> {
+    trace!("revocation_publisher::start >>> config: {}", config);
+
+    let config: PublisherConfig = serde_json::from_str(config)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidConfiguration, format!("Cannot deserialize revocation publisher config: {:?}", err)))?;
+
+    if config.publish_interval == 0 {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidConfiguration, "Revocation publisher `publish_interval` must be greater than 0"));
+    }
+
+    let mut publishers = PUBLISHERS.lock().unwrap();
+
+    if publishers.contains_key(&context::current()) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidState, "Revocation publisher is already started"));
+    }
+
+    let pending = _load_pending()?;
+
+    let status = pending.iter()
+        .map(|(rev_reg_id, deltas)| (rev_reg_id.clone(), RevRegStatus { pending_revocations: deltas.len() as u32, ..RevRegStatus::default() }))
+        .collect();
+
+    scheduler::register(PUBLISH_TASK, Schedule::every(config.publish_interval), publish)?;
+
+    publishers.insert(context::current(), Publisher {
+        config,
+        pending,
+        publishing: HashSet::new(),
+        status,
+    });
+
+    Ok(())
+}
+
+// Publishes pending deltas and stops scheduled publishing. Deltas which couldn't be published are kept in the wallet
+// and published once the publisher is started again.
+pub fn stop() -> VcxResult<()> {
+    trace!("revocation_publisher::stop >>>");
+
+    let res = publish();
+
+    if PUBLISHERS.lock().unwrap().remove(&context::current()).is_some() {
+        scheduler::unregister(PUBLISH_TASK);
+    }
+
+    res
+}
+
+pub fn is_running() -> bool {
+    PUBLISHERS.lock().unwrap().contains_key(&context::current())
+}
+
+// Queues delta of credential revoked in the wallet. Returns false if publisher is not started.
+pub fn add_revocation(rev_reg_id: &str, delta: &str) -> VcxResult<bool> {
+    trace!("revocation_publisher::add_revocation >>> rev_reg_id: {}, delta: {}", rev_reg_id, delta);
+
+    let batch_is_full = {
+        let mut publishers = PUBLISHERS.lock().unwrap();
+
+        let publisher = match publishers.get_mut(&context::current()) {
+            Some(publisher) => publisher,
+            None => return Ok(false)
+        };
+
+        let mut deltas = publisher.pending.get(rev_reg_id).cloned().unwrap_or_default();
+        deltas.push(delta.to_string());
+
+        // the caller publishes the delta itself if it can't be queued durably
+        if let Err(err) = _store_pending(rev_reg_id, &deltas) {
+            warn!("revocation_publisher: cannot queue delta of {}, it is published right away: {}", rev_reg_id, err);
+            return Ok(false);
+        }
+
+        let revocations = deltas.len() as u32;
+        publisher.pending.insert(rev_reg_id.to_string(), deltas);
+
+        publisher.status.entry(rev_reg_id.to_string()).or_insert_with(RevRegStatus::default).pending_revocations = revocations;
+
+        publisher.config.max_batch_size.map(|max| revocations >= max).unwrap_or(false)
+    };
+
+    // the delta is queued already, failed publication is retried by the scheduled task
+    if batch_is_full {
+        if let Err(err) = publish_rev_reg(rev_reg_id) {
+            warn!("revocation_publisher: cannot publish full batch of {}: {}", rev_reg_id, err);
+        }
+    }
+
+    Ok(true)
+}
+
+// Uploads tails file of the revocation registry if tails server is configured.
+pub fn add_rev_reg(rev_reg_id: &str, rev_reg_def: &str) -> VcxResult<()> {
+    trace!("revocation_publisher::add_rev_reg >>> rev_reg_id: {}", rev_reg_id);
+
+    let tails_upload_url = match PUBLISHERS.lock().unwrap().get_mut(&context::current()) {
+        Some(publisher) => {
+            publisher.status.entry(rev_reg_id.to_string()).or_insert_with(RevRegStatus::default);
+            publisher.config.tails_upload_url.clone()
+        }
+        None => return Ok(())
+    };
+
+    let tails_upload_url = match tails_upload_url {
+        Some(tails_upload_url) => tails_upload_url,
+        None => return Ok(())
+    };
+
+    let res = _upload_tails(&tails_upload_url, rev_reg_id, rev_reg_def);
+    _update_status(rev_reg_id, |status| {
+        match res {
+            Ok(()) => status.tails_uploaded = true,
+            Err(ref err) => status.last_error = Some(err.to_string())
+        }
+    });
+    res
+}
+
+// Publishes pending deltas of all registries. All registries are tried, the first error is returned.
+pub fn publish() -> VcxResult<()> {
+    let rev_reg_ids: Vec<String> = match PUBLISHERS.lock().unwrap().get(&context::current()) {
+        Some(publisher) => publisher.pending.keys().cloned().collect(),
+        None => return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Revocation publisher is not started"))
+    };
+
+    let mut res = Ok(());
+    for rev_reg_id in rev_reg_ids {
+        if let Err(err) = publish_rev_reg(&rev_reg_id) {
+            if res.is_ok() {
+                res = Err(err);
+            }
+        }
+    }
+    res
+}
+
+// Deltas stay pending until they are published, revocations queued meanwhile are published with the next batch.
+pub fn publish_rev_reg(rev_reg_id: &str) -> VcxResult<()> {
+    trace!("revocation_publisher::publish_rev_reg >>> rev_reg_id: {}", rev_reg_id);
+
+    let deltas = match PUBLISHERS.lock().unwrap().get_mut(&context::current()) {
+        Some(ref mut publisher) if !publisher.publishing.contains(rev_reg_id) => {
+            let deltas = publisher.pending.get(rev_reg_id).cloned().unwrap_or_default();
+            if !deltas.is_empty() {
+                publisher.publishing.insert(rev_reg_id.to_string());
+            }
+            deltas
+        }
+        // published by another thread right now
+        Some(_) => return Ok(()),
+        None => return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Revocation publisher is not started"))
+    };
+
+    if deltas.is_empty() {
+        return Ok(());
+    }
+
+    let res = _merge(&deltas)
+        .and_then(|delta| settings::get_config_value(settings::CONFIG_INSTITUTION_DID)
+            .and_then(|issuer_did| anoncreds::publish_rev_reg_delta(&issuer_did, rev_reg_id, &delta)))
+        .map_err(|err| err.map(VcxErrorKind::InvalidRevocationEntry, "Cannot publish RevocationEntry"));
+
+    let mut publishers = PUBLISHERS.lock().unwrap();
+    let publisher = match publishers.get_mut(&context::current()) {
+        Some(publisher) => publisher,
+        None => return res.map(|_| ())
+    };
+
+    publisher.publishing.remove(rev_reg_id);
+
+    match res {
+        Ok(_) => {
+            let remaining: Vec<String> = publisher.pending.remove(rev_reg_id).unwrap_or_default()
+                .into_iter()
+                .skip(deltas.len())
+                .collect();
+
+            if let Err(err) = _store_pending(rev_reg_id, &remaining) {
+                error!("revocation_publisher: cannot remove published deltas of {} from the wallet: {}", rev_reg_id, err);
+            }
+
+            let status = publisher.status.entry(rev_reg_id.to_string()).or_insert_with(RevRegStatus::default);
+            status.pending_revocations = remaining.len() as u32;
+            status.published_revocations += deltas.len() as u64;
+            status.last_published = Some(::time::get_time().sec);
+            status.last_error = None;
+
+            if !remaining.is_empty() {
+                publisher.pending.insert(rev_reg_id.to_string(), remaining);
+            }
+
+            Ok(())
+        }
+        Err(err) => {
+            publisher.status.entry(rev_reg_id.to_string()).or_insert_with(RevRegStatus::default).last_error = Some(err.to_string());
+            Err(err)
+        }
+    }
+}
+
+pub fn get_status() -> VcxResult<String> {
+    let publishers = PUBLISHERS.lock().unwrap();
+
+    let publisher = publishers.get(&context::current())
+        .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Revocation publisher is not started"))?;
+
+    serde_json::to_string(&publisher.status)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize revocation publisher status: {:?}", err)))
+}
+
+fn _update_status<F>(rev_reg_id: &str, update: F) where F: FnOnce(&mut RevRegStatus) {
+    if let Some(publisher) = PUBLISHERS.lock().unwrap().get_mut(&context::current()) {
+        update(publisher.status.entry(rev_reg_id.to_string()).or_insert_with(RevRegStatus::default));
+    }
+}
+
+fn _merge(deltas: &[String]) -> VcxResult<String> {
+    let (first, rest) = deltas.split_first()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, "There are no deltas to merge"))?;
+
+    rest.iter()
+        .try_fold(first.clone(), |merged, delta| anoncreds::libindy_issuer_merge_revocation_registry_deltas(&merged, delta))
+}
+
+// Pending deltas of a registry are kept in a wallet record under its id, the record is removed once all are published
+fn _store_pending(rev_reg_id: &str, deltas: &[String]) -> VcxResult<()> {
+    if deltas.is_empty() {
+        return match wallet::delete_record(PENDING_RECORD_TYPE, rev_reg_id) {
+            Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => Ok(()),
+            res => res
+        };
+    }
+
+    let value = serde_json::to_string(deltas)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize revocation registry deltas: {:?}", err)))?;
+
+    match wallet::update_record_value(PENDING_RECORD_TYPE, rev_reg_id, &value) {
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => wallet::add_record(PENDING_RECORD_TYPE, rev_reg_id, &value, None),
+        res => res
+    }
+}
+
+fn _load_pending() -> VcxResult<HashMap<String, Vec<String>>> {
+    let options = json!({"retrieveRecords": true, "retrieveTotalCount": false, "retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+
+    wallet::search_all_records(PENDING_RECORD_TYPE, "{}", &options, SEARCH_BATCH_SIZE)?
+        .into_iter()
+        .map(|record| {
+            let rev_reg_id = record.id.unwrap_or_default();
+            let deltas: Vec<String> = serde_json::from_str(record.value.as_ref().map(String::as_str).unwrap_or_default())
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize pending deltas of {}: {:?}", rev_reg_id, err)))?;
+            Ok((rev_reg_id, deltas))
+        })
+        .collect()
+}
+
+fn _upload_tails(tails_upload_url: &str, rev_reg_id: &str, rev_reg_def: &str) -> VcxResult<()> {
+    if settings::indy_mocks_enabled() { return Ok(()); }
+
+    let rev_reg_def: Value = serde_json::from_str(rev_reg_def)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidRevocationDetails, format!("Cannot deserialize RevocationRegistryDefinition: {:?}", err)))?;
+
+    let tails_location = rev_reg_def["value"]["tailsLocation"].as_str()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidRevocationDetails, "RevocationRegistryDefinition doesn't contain `tailsLocation`"))?;
+
+    let tails_file = File::open(tails_location)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot open tails file {:?}: {:?}", tails_location, err)))?;
+
+    let url = format!("{}/{}", tails_upload_url.trim_end_matches('/'), rev_reg_id);
+
+    let client = reqwest::ClientBuilder::new().timeout(::utils::timeout::TimeoutUtils::long_timeout()).build()
+        .or(Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, "Preparing tails upload failed")))?;
+
+    let response = client.put(&url)
+        .body(tails_file)
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .send()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Could not upload tails file to {:?}: {:?}", url, err)))?;
+
+    if !response.status().is_success() {
+        return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Tails upload to {:?} failed with: {}", url, response.status())));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::constants::{REV_REG_ID, REV_REG_DELTA_JSON, rev_def_json};
+    use utils::devsetup::*;
+
+    fn _status(rev_reg_id: &str) -> RevRegStatus {
+        let status: HashMap<String, RevRegStatus> = serde_json::from_str(&get_status().unwrap()).unwrap();
+        status.get(rev_reg_id).cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_publisher_config_works() {
+        let config: PublisherConfig = serde_json::from_str(r#"{"max_batch_size": 10}"#).unwrap();
+        assert_eq!(PublisherConfig { publish_interval: 60, max_batch_size: Some(10), tails_upload_url: None }, config);
+
+        assert_eq!(start(r#"{"publish_interval": 0}"#).unwrap_err().kind(), VcxErrorKind::InvalidConfiguration);
+        assert_eq!(start(r#"{"publish_interval": "often"}"#).unwrap_err().kind(), VcxErrorKind::InvalidConfiguration);
+    }
+
+    #[test]
+    fn test_publisher_batches_revocations() {
+        let _setup = SetupMocks::init();
+
+        assert!(!add_revocation(REV_REG_ID, REV_REG_DELTA_JSON).unwrap());
+
+        start(r#"{"publish_interval": 3600}"#).unwrap();
+        add_rev_reg(REV_REG_ID, &rev_def_json()).unwrap();
+
+        assert!(add_revocation(REV_REG_ID, REV_REG_DELTA_JSON).unwrap());
+        assert!(add_revocation(REV_REG_ID, REV_REG_DELTA_JSON).unwrap());
+        assert_eq!(2, _status(REV_REG_ID).pending_revocations);
+
+        publish().unwrap();
+        let status = _status(REV_REG_ID);
+        assert_eq!(0, status.pending_revocations);
+        assert_eq!(2, status.published_revocations);
+        assert!(status.last_published.is_some());
+
+        stop().unwrap();
+        assert!(!is_running());
+        assert_eq!(get_status().unwrap_err().kind(), VcxErrorKind::NotReady);
+    }
+
+    #[test]
+    fn test_publisher_publishes_full_batch() {
+        let _setup = SetupMocks::init();
+
+        start(r#"{"publish_interval": 3600, "max_batch_size": 1}"#).unwrap();
+
+        add_revocation(REV_REG_ID, REV_REG_DELTA_JSON).unwrap();
+        let status = _status(REV_REG_ID);
+        assert_eq!(0, status.pending_revocations);
+        assert_eq!(1, status.published_revocations);
+
+        stop().unwrap();
+    }
+
+    #[test]
+    fn test_publisher_keeps_deltas_on_failed_publication() {
+        let _setup = SetupMocks::init();
+
+        start(r#"{"publish_interval": 3600, "max_batch_size": 2}"#).unwrap();
+
+        let institution_did = settings::get_config_value(settings::CONFIG_INSTITUTION_DID).unwrap();
+        context::with_settings_mut(|settings| settings.remove(settings::CONFIG_INSTITUTION_DID)).unwrap();
+
+        assert!(add_revocation(REV_REG_ID, REV_REG_DELTA_JSON).unwrap());
+        assert!(add_revocation(REV_REG_ID, REV_REG_DELTA_JSON).unwrap());
+        assert!(publish().is_err());
+
+        let status = _status(REV_REG_ID);
+        assert_eq!(2, status.pending_revocations);
+        assert_eq!(0, status.published_revocations);
+        assert!(status.last_error.is_some());
+
+        settings::set_config_value(settings::CONFIG_INSTITUTION_DID, &institution_did);
+
+        publish().unwrap();
+        let status = _status(REV_REG_ID);
+        assert_eq!(0, status.pending_revocations);
+        assert_eq!(2, status.published_revocations);
+        assert!(status.last_error.is_none());
+
+        stop().unwrap();
+    }
+
+    #[test]
+    fn test_publisher_is_started_per_context() {
+        let _setup = SetupMocks::init();
+
+        let other = context::create().unwrap();
+
+        start(r#"{"publish_interval": 3600}"#).unwrap();
+        assert!(is_running());
+
+        assert!(!context::run_in(other, is_running));
+        assert!(!context::run_in(other, || add_revocation(REV_REG_ID, REV_REG_DELTA_JSON)).unwrap());
+        assert_eq!(context::run_in(other, get_status).unwrap_err().kind(), VcxErrorKind::NotReady);
+
+        stop().unwrap();
+        context::release(other).unwrap();
+    }
+}
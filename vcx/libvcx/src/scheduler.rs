@@ -0,0 +1,492 @@
+use serde_json;
+use rand;
+use rand::Rng;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use context::{self, ContextHandle};
+use error::prelude::*;
+use utils::libindy::wallet;
+
+/*
+    Background task scheduler shared by VCX subsystems.
+    Subsystems register named jobs with a schedule, a single worker thread runs them when they are due.
+    The worker thread is started with the first registered job and exits when the last one is unregistered.
+
+    Tasks are registered per context: names are unique within a context and jobs are run in the context
+    they were registered in.
+    State of every task (next run, failed attempts, lock) is persisted in the wallet, so schedules survive restarts
+    and several processes sharing the wallet don't run the same task concurrently.
+    Execution is at-least-once: next run is scheduled only after the job succeeded, failed jobs are retried with
+    exponential backoff and a task locked by a crashed process is picked up again once its lock expires.
+*/
+
+lazy_static! {
+    static ref SCHEDULER: Mutex<Scheduler> = Mutex::new(Scheduler::default());
+    static ref INSTANCE_ID: String = ::utils::uuid::uuid();
+}
+
+const TASK_RECORD_TYPE: &str = "VcxScheduledTask";
+const TICK: Duration = Duration::from_millis(200);
+// seconds a task is locked for while it's running
+const LOCK_TIMEOUT: i64 = 600;
+// seconds before the first retry of a failed task, doubled with every failed attempt
+const RETRY_DELAY: i64 = 5;
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Schedule {
+    // seconds between runs
+    pub interval: u64,
+    // up to this number of seconds is randomly added to every run time
+    #[serde(default)]
+    pub jitter: u64,
+    // run at multiples of the interval since Unix Epoch (like cron does) instead of counting from the last run
+    #[serde(default)]
+    pub aligned: bool,
+}
+
+impl Schedule {
+    pub fn every(interval: u64) -> Schedule {
+        Schedule { interval, jitter: 0, aligned: false }
+    }
+
+    pub fn set_jitter(mut self, jitter: u64) -> Schedule {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn set_aligned(mut self, aligned: bool) -> Schedule {
+        self.aligned = aligned;
+        self
+    }
+
+    fn next_run(&self, now: i64) -> i64 {
+        let interval = self.interval as i64;
+
+        let next_run = if self.aligned { (now / interval + 1) * interval } else { now + interval };
+
+        if self.jitter == 0 { next_run } else { next_run + rand::thread_rng().gen_range(0, self.jitter as i64 + 1) }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TaskStatus {
+    pub next_run: i64,
+    pub last_run: Option<i64>,
+    pub last_success: Option<i64>,
+    pub failed_attempts: u32,
+    pub last_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock: Option<TaskLock>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct TaskLock {
+    owner: String,
+    until: i64,
+}
+
+impl TaskStatus {
+    fn new(schedule: &Schedule, now: i64) -> TaskStatus {
+        TaskStatus {
+            next_run: schedule.next_run(now),
+            last_run: None,
+            last_success: None,
+            failed_attempts: 0,
+            last_error: None,
+            lock: None,
+        }
+    }
+
+    fn is_locked_by_other(&self, now: i64) -> bool {
+        match self.lock {
+            Some(ref lock) => lock.owner != *INSTANCE_ID && lock.until > now,
+            None => false
+        }
+    }
+
+    fn completed(mut self, schedule: &Schedule, res: &VcxResult<()>, now: i64) -> TaskStatus {
+        self.last_run = Some(now);
+        self.lock = None;
+
+        match *res {
+            Ok(()) => {
+                self.next_run = schedule.next_run(now);
+                self.last_success = Some(now);
+                self.failed_attempts = 0;
+                self.last_error = None;
+            }
+            Err(ref err) => {
+                let retry_delay = RETRY_DELAY << self.failed_attempts.min(16);
+                self.next_run = now + retry_delay.min(schedule.interval as i64);
+                self.failed_attempts += 1;
+                self.last_error = Some(err.to_string());
+            }
+        }
+
+        self
+    }
+}
+
+type Job = Arc<dyn Fn() -> VcxResult<()> + Send + Sync>;
+
+// Tasks of different contexts may have the same name
+type TaskKey = (ContextHandle, String);
+
+struct Task {
+    schedule: Schedule,
+    job: Job,
+    status: TaskStatus,
+    running: bool,
+}
+
+#[derive(Default)]
+struct Scheduler {
+    tasks: HashMap<TaskKey, Task>,
+    worker_running: bool,
+}
+
+// Registers job to be run on the schedule. Persisted state of the task with the same name is restored.
+pub fn register<F>(name: &str, schedule: Schedule, job: F) -> VcxResult<()> where F: Fn() -> VcxResult<()> + Send + Sync + 'static {
+    trace!("scheduler::register >>> name: {}, schedule: {:?}", name, schedule);
+
+    if schedule.interval == 0 {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidConfiguration, "Scheduled task `interval` must be greater than 0"));
+    }
+
+    if is_registered(name) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidState, format!("Task {} is already registered", name)));
+    }
+
+    let status = match _load_status(name) {
+        Some(status) => status,
+        None => {
+            let status = TaskStatus::new(&schedule, _now());
+            _add_status(name, &status);
+            status
+        }
+    };
+
+    let mut scheduler = SCHEDULER.lock().unwrap();
+
+    scheduler.tasks.insert(_key(name), Task { schedule, job: Arc::new(job), status, running: false });
+
+    if !scheduler.worker_running {
+        scheduler.worker_running = true;
+        thread::spawn(_worker);
+    }
+
+    Ok(())
+}
+
+// Removes the task. Persisted state is kept, so the task continues on its schedule when it is registered again.
+pub fn unregister(name: &str) -> bool {
+    trace!("scheduler::unregister >>> name: {}", name);
+
+    SCHEDULER.lock().unwrap().tasks.remove(&_key(name)).is_some()
+}
+
+pub fn is_registered(name: &str) -> bool {
+    SCHEDULER.lock().unwrap().tasks.contains_key(&_key(name))
+}
+
+// Runs all registered tasks of all contexts which are due. All tasks are tried, the first error is returned.
+pub fn run_pending() -> VcxResult<()> {
+    let now = _now();
+
+    let keys: Vec<TaskKey> = SCHEDULER.lock().unwrap().tasks.iter()
+        .filter(|&(_, task)| !task.running && task.status.next_run <= now)
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    let mut res = Ok(());
+    for (task_context, name) in keys {
+        if let Err(err) = context::run_in(task_context, || run_task(&name)) {
+            if res.is_ok() {
+                res = Err(err);
+            }
+        }
+    }
+    res
+}
+
+// Runs the task of the current context right now regardless of its schedule unless it's already running in this or another process.
+pub fn run_task(name: &str) -> VcxResult<()> {
+    trace!("scheduler::run_task >>> name: {}", name);
+
+    let (schedule, job) = {
+        let mut scheduler = SCHEDULER.lock().unwrap();
+
+        let task = scheduler.tasks.get_mut(&_key(name))
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Task {} is not registered", name)))?;
+
+        if task.running { return Ok(()); }
+
+        task.running = true;
+        (task.schedule.clone(), task.job.clone())
+    };
+
+    _run_task(name, &schedule, &job)
+}
+
+fn _run_task(name: &str, schedule: &Schedule, job: &Job) -> VcxResult<()> {
+    let status = match _lock(name) {
+        Some(status) => status,
+        None => {
+            _update_task(name, |task| task.running = false);
+            return Ok(());
+        }
+    };
+
+    let res = job();
+
+    if let Err(ref err) = res {
+        warn!("scheduler: task {} failed: {}", name, err);
+    }
+
+    let status = status.completed(schedule, &res, _now());
+    _store_status(name, &status);
+
+    _update_task(name, |task| {
+        task.status = status;
+        task.running = false;
+    });
+
+    res
+}
+
+// Status of the tasks of the current context
+pub fn get_status() -> VcxResult<String> {
+    let current = context::current();
+    let scheduler = SCHEDULER.lock().unwrap();
+
+    let status: HashMap<&String, &TaskStatus> = scheduler.tasks.iter()
+        .filter(|&(key, _)| key.0 == current)
+        .map(|(key, task)| (&key.1, &task.status))
+        .collect();
+
+    serde_json::to_string(&status)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize scheduler status: {:?}", err)))
+}
+
+fn _worker() {
+    loop {
+        thread::sleep(TICK);
+
+        {
+            let mut scheduler = SCHEDULER.lock().unwrap();
+            if scheduler.tasks.is_empty() {
+                scheduler.worker_running = false;
+                return;
+            }
+        }
+
+        run_pending().ok();
+    }
+}
+
+// Takes the lock of the task in the wallet. Returns None if another process holds it.
+fn _lock(name: &str) -> Option<TaskStatus> {
+    let now = _now();
+
+    let status = match _load_status(name) {
+        Some(status) => status,
+        None => match SCHEDULER.lock().unwrap().tasks.get(&_key(name)) {
+            Some(task) => task.status.clone(),
+            None => return None
+        }
+    };
+
+    if status.is_locked_by_other(now) {
+        debug!("scheduler: task {} is locked by another process", name);
+        _update_task(name, |task| task.status = status.clone());
+        return None;
+    }
+
+    let mut status = status;
+    status.lock = Some(TaskLock { owner: INSTANCE_ID.clone(), until: now + LOCK_TIMEOUT });
+    _store_status(name, &status);
+
+    Some(status)
+}
+
+fn _update_task<F>(name: &str, update: F) where F: FnOnce(&mut Task) {
+    if let Some(task) = SCHEDULER.lock().unwrap().tasks.get_mut(&_key(name)) {
+        update(task);
+    }
+}
+
+fn _key(name: &str) -> TaskKey {
+    (context::current(), name.to_string())
+}
+
+// Wallet is optional for the scheduler: tasks keep running on in-memory state if it can't be used.
+fn _load_status(name: &str) -> Option<TaskStatus> {
+    let options = json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+
+    let record = match wallet::get_record(TASK_RECORD_TYPE, name, &options) {
+        Ok(record) => record,
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => return None,
+        Err(err) => {
+            warn!("scheduler: cannot get state of task {}: {}", name, err);
+            return None;
+        }
+    };
+
+    let record: serde_json::Value = serde_json::from_str(&record).ok()?;
+
+    match record["value"].as_str().map(|value| serde_json::from_str(value)) {
+        Some(Ok(status)) => Some(status),
+        _ => {
+            warn!("scheduler: cannot deserialize state of task {}", name);
+            None
+        }
+    }
+}
+
+fn _add_status(name: &str, status: &TaskStatus) {
+    if let Err(err) = wallet::add_record(TASK_RECORD_TYPE, name, &json!(status).to_string(), None) {
+        warn!("scheduler: cannot store state of task {}: {}", name, err);
+    }
+}
+
+fn _store_status(name: &str, status: &TaskStatus) {
+    if let Err(err) = wallet::update_record_value(TASK_RECORD_TYPE, name, &json!(status).to_string()) {
+        warn!("scheduler: cannot store state of task {}: {}", name, err);
+    }
+}
+
+fn _now() -> i64 {
+    ::time::get_time().sec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use utils::devsetup::*;
+
+    fn _status(name: &str) -> TaskStatus {
+        let status: HashMap<String, TaskStatus> = serde_json::from_str(&get_status().unwrap()).unwrap();
+        status.get(name).cloned().unwrap()
+    }
+
+    #[test]
+    fn test_schedule_next_run_works() {
+        assert_eq!(160, Schedule::every(60).next_run(100));
+        assert_eq!(120, Schedule::every(60).set_aligned(true).next_run(100));
+        assert_eq!(180, Schedule::every(60).set_aligned(true).next_run(120));
+
+        let next_run = Schedule::every(60).set_jitter(10).next_run(100);
+        assert!(next_run >= 160 && next_run <= 170);
+
+        let schedule: Schedule = serde_json::from_str(r#"{"interval": 60}"#).unwrap();
+        assert_eq!(Schedule::every(60), schedule);
+    }
+
+    #[test]
+    fn test_task_status_completed_works() {
+        let schedule = Schedule::every(60);
+        let status = TaskStatus::new(&schedule, 0);
+
+        let status = status.completed(&schedule, &Err(VcxError::from(VcxErrorKind::PostMessageFailed)), 100);
+        assert_eq!(105, status.next_run);
+        assert_eq!(1, status.failed_attempts);
+        assert!(status.last_error.is_some());
+
+        let status = status.completed(&schedule, &Err(VcxError::from(VcxErrorKind::PostMessageFailed)), 105);
+        assert_eq!(115, status.next_run);
+        assert_eq!(2, status.failed_attempts);
+
+        let status = status.completed(&schedule, &Ok(()), 115);
+        assert_eq!(175, status.next_run);
+        assert_eq!(0, status.failed_attempts);
+        assert_eq!(Some(115), status.last_success);
+        assert_eq!(None, status.last_error);
+    }
+
+    #[test]
+    fn test_task_status_lock_works() {
+        let mut status = TaskStatus::new(&Schedule::every(60), 0);
+        assert!(!status.is_locked_by_other(100));
+
+        status.lock = Some(TaskLock { owner: String::from("other"), until: 200 });
+        assert!(status.is_locked_by_other(100));
+        assert!(!status.is_locked_by_other(200));
+
+        status.lock = Some(TaskLock { owner: INSTANCE_ID.clone(), until: 200 });
+        assert!(!status.is_locked_by_other(100));
+    }
+
+    #[test]
+    fn test_scheduler_runs_registered_task() {
+        let _setup = SetupMocks::init();
+
+        let name = "test_scheduler_runs_registered_task";
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+
+        register(name, Schedule::every(3600), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }).unwrap();
+        assert_eq!(VcxErrorKind::InvalidState, register(name, Schedule::every(3600), || Ok(())).unwrap_err().kind());
+
+        run_pending().unwrap();
+        assert_eq!(0, runs.load(Ordering::SeqCst));
+
+        run_task(name).unwrap();
+        assert_eq!(1, runs.load(Ordering::SeqCst));
+
+        let status = _status(name);
+        assert!(status.last_success.is_some());
+        assert!(status.next_run > _now());
+
+        assert!(unregister(name));
+        assert!(!is_registered(name));
+        assert_eq!(VcxErrorKind::InvalidOption, run_task(name).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_scheduler_retries_failed_task() {
+        let _setup = SetupMocks::init();
+
+        let name = "test_scheduler_retries_failed_task";
+
+        register(name, Schedule::every(3600), || Err(VcxError::from(VcxErrorKind::PostMessageFailed))).unwrap();
+
+        assert_eq!(VcxErrorKind::PostMessageFailed, run_task(name).unwrap_err().kind());
+
+        let status = _status(name);
+        assert_eq!(1, status.failed_attempts);
+        assert!(status.next_run <= _now() + RETRY_DELAY);
+
+        unregister(name);
+    }
+
+    #[test]
+    fn test_scheduler_tasks_are_registered_per_context() {
+        let _setup = SetupMocks::init();
+
+        let name = "test_scheduler_tasks_are_registered_per_context";
+        let other = context::create().unwrap();
+
+        register(name, Schedule::every(3600), || Ok(())).unwrap();
+        context::run_in(other, || register(name, Schedule::every(3600), || Ok(()))).unwrap();
+
+        assert!(context::run_in(other, || unregister(name)));
+        assert!(!context::run_in(other, || is_registered(name)));
+        assert!(is_registered(name));
+
+        unregister(name);
+        context::release(other).unwrap();
+    }
+
+    #[test]
+    fn test_scheduler_rejects_zero_interval() {
+        assert_eq!(VcxErrorKind::InvalidConfiguration, register("zero", Schedule::every(0), || Ok(())).unwrap_err().kind());
+    }
+}
@@ -2,15 +2,15 @@ extern crate url;
 extern crate serde_json;
 
 use std::collections::HashMap;
-use std::sync::RwLock;
 use utils::{get_temp_dir_path, error};
 use std::path::Path;
 use url::Url;
 use messages::validation;
 use serde_json::Value;
 use strum::IntoEnumIterator;
-use std::borrow::Borrow;
+use std::str::FromStr;
 
+use context;
 use error::prelude::*;
 use utils::file::read_file;
 use indy_sys::INVALID_WALLET_HANDLE;
@@ -51,8 +51,16 @@ pub static CONFIG_USE_LATEST_PROTOCOLS: &'static str = "use_latest_protocols";
 pub static CONFIG_POOL_CONFIG: &'static str = "pool_config";
 pub static CONFIG_DID_METHOD: &str = "did_method";
 pub static COMMUNICATION_METHOD: &str = "communication_method";// proprietary or aries
-pub static CONFIG_ACTORS: &str = "actors"; // inviter, invitee, issuer, holder, prover, verifier, sender, receiver
+pub static CONFIG_ACTORS: &str = "actors"; // inviter, invitee, issuer, holder, prover, verifier, sender, receiver, mediator, recipient
 pub static MOCK_INDY_PROOF_VALIDATION: &str = "mock_indy_proof_validation";
+pub static CONFIG_SIGNED_JSON_FORMAT: &str = "signed_json_format"; // legacy, canonical or canonical_strict
+pub static CONFIG_AUTO_ACCEPT_CREDENTIAL_OFFERS: &str = "auto_accept_credential_offers"; // never or always
+pub static CONFIG_AUTO_PRESENT_PROOFS: &str = "auto_present_proofs"; // never, single_match or always
+pub static CONFIG_CREDENTIAL_SELECTION_STRATEGY: &str = "credential_selection_strategy"; // JSON, see CredentialSelectionStrategy
+pub static CONFIG_PERSIST_OBJECTS: &str = "persist_objects"; // true or false
+pub static CONFIG_OBJECT_CACHE_SIZE: &str = "object_cache_size"; // max number of persisted objects of a type kept in memory
+pub static CONFIG_CLOUD_AGENT: &str = "cloud_agent"; // evernym, direct or name of registered implementation
+pub static CONFIG_ORDER_RECEIVED_MESSAGES: &str = "order_received_messages"; // true or false
 
 pub static DEFAULT_PROTOCOL_VERSION: usize = 2;
 pub static MAX_SUPPORTED_PROTOCOL_VERSION: usize = 2;
@@ -78,13 +86,10 @@ pub static DEFAULT_PAYMENT_INIT_FUNCTION: &str = "nullpay_init";
 pub static DEFAULT_USE_LATEST_PROTOCOLS: &str = "false";
 pub static DEFAULT_PAYMENT_METHOD: &str = "null";
 pub static DEFAULT_PROTOCOL_TYPE: &str = "1.0";
+pub static DEFAULT_CLOUD_AGENT: &str = "evernym";
 pub static MAX_THREADPOOL_SIZE: usize = 128;
 pub static MOCK_DEFAULT_INDY_PROOF_VALIDATION: &str = "true";
 
-lazy_static! {
-    static ref SETTINGS: RwLock<HashMap<String, String>> = RwLock::new(HashMap::new());
-}
-
 trait ToString {
     fn to_string(&self) -> Self;
 }
@@ -100,36 +105,38 @@ impl ToString for HashMap<String, String> {
 pub fn set_defaults() -> u32 {
     trace!("set_defaults >>>");
 
-    // if this fails the program should exit
-    let mut settings = SETTINGS.write().unwrap();
-
-    settings.insert(CONFIG_POOL_NAME.to_string(), DEFAULT_POOL_NAME.to_string());
-    settings.insert(CONFIG_WALLET_NAME.to_string(), DEFAULT_WALLET_NAME.to_string());
-    settings.insert(CONFIG_WALLET_TYPE.to_string(), DEFAULT_DEFAULT.to_string());
-    settings.insert(CONFIG_AGENCY_ENDPOINT.to_string(), DEFAULT_URL.to_string());
-    settings.insert(CONFIG_AGENCY_DID.to_string(), DEFAULT_DID.to_string());
-    settings.insert(CONFIG_AGENCY_VERKEY.to_string(), DEFAULT_VERKEY.to_string());
-    settings.insert(CONFIG_REMOTE_TO_SDK_DID.to_string(), DEFAULT_DID.to_string());
-    settings.insert(CONFIG_REMOTE_TO_SDK_VERKEY.to_string(), DEFAULT_VERKEY.to_string());
-    settings.insert(CONFIG_INSTITUTION_DID.to_string(), DEFAULT_DID.to_string());
-    settings.insert(CONFIG_INSTITUTION_NAME.to_string(), DEFAULT_DEFAULT.to_string());
-    settings.insert(CONFIG_INSTITUTION_LOGO_URL.to_string(), DEFAULT_URL.to_string());
-    settings.insert(CONFIG_WEBHOOK_URL.to_string(), DEFAULT_URL.to_string());
-    settings.insert(CONFIG_SDK_TO_REMOTE_DID.to_string(), DEFAULT_DID.to_string());
-    settings.insert(CONFIG_SDK_TO_REMOTE_VERKEY.to_string(), DEFAULT_VERKEY.to_string());
-    settings.insert(CONFIG_SDK_TO_REMOTE_ROLE.to_string(), DEFAULT_ROLE.to_string());
-    settings.insert(CONFIG_WALLET_KEY.to_string(), DEFAULT_WALLET_KEY.to_string());
-    settings.insert(CONFIG_WALLET_KEY_DERIVATION.to_string(), DEFAULT_WALLET_KEY_DERIVATION.to_string());
-    settings.insert(CONFIG_LINK_SECRET_ALIAS.to_string(), DEFAULT_LINK_SECRET_ALIAS.to_string());
-    settings.insert(CONFIG_PROTOCOL_VERSION.to_string(), DEFAULT_PROTOCOL_VERSION.to_string());
-    settings.insert(CONFIG_EXPORTED_WALLET_PATH.to_string(),
-                    get_temp_dir_path(DEFAULT_EXPORTED_WALLET_PATH).to_str().unwrap_or("").to_string());
-    settings.insert(CONFIG_WALLET_BACKUP_KEY.to_string(), DEFAULT_WALLET_BACKUP_KEY.to_string());
-    settings.insert(CONFIG_THREADPOOL_SIZE.to_string(), DEFAULT_THREADPOOL_SIZE.to_string());
-    settings.insert(CONFIG_PAYMENT_METHOD.to_string(), DEFAULT_PAYMENT_METHOD.to_string());
-    settings.insert(CONFIG_USE_LATEST_PROTOCOLS.to_string(), DEFAULT_USE_LATEST_PROTOCOLS.to_string());
-
-    error::SUCCESS.code_num
+    let res = context::with_settings_mut(|settings| {
+        settings.insert(CONFIG_POOL_NAME.to_string(), DEFAULT_POOL_NAME.to_string());
+        settings.insert(CONFIG_WALLET_NAME.to_string(), DEFAULT_WALLET_NAME.to_string());
+        settings.insert(CONFIG_WALLET_TYPE.to_string(), DEFAULT_DEFAULT.to_string());
+        settings.insert(CONFIG_AGENCY_ENDPOINT.to_string(), DEFAULT_URL.to_string());
+        settings.insert(CONFIG_AGENCY_DID.to_string(), DEFAULT_DID.to_string());
+        settings.insert(CONFIG_AGENCY_VERKEY.to_string(), DEFAULT_VERKEY.to_string());
+        settings.insert(CONFIG_REMOTE_TO_SDK_DID.to_string(), DEFAULT_DID.to_string());
+        settings.insert(CONFIG_REMOTE_TO_SDK_VERKEY.to_string(), DEFAULT_VERKEY.to_string());
+        settings.insert(CONFIG_INSTITUTION_DID.to_string(), DEFAULT_DID.to_string());
+        settings.insert(CONFIG_INSTITUTION_NAME.to_string(), DEFAULT_DEFAULT.to_string());
+        settings.insert(CONFIG_INSTITUTION_LOGO_URL.to_string(), DEFAULT_URL.to_string());
+        settings.insert(CONFIG_WEBHOOK_URL.to_string(), DEFAULT_URL.to_string());
+        settings.insert(CONFIG_SDK_TO_REMOTE_DID.to_string(), DEFAULT_DID.to_string());
+        settings.insert(CONFIG_SDK_TO_REMOTE_VERKEY.to_string(), DEFAULT_VERKEY.to_string());
+        settings.insert(CONFIG_SDK_TO_REMOTE_ROLE.to_string(), DEFAULT_ROLE.to_string());
+        settings.insert(CONFIG_WALLET_KEY.to_string(), DEFAULT_WALLET_KEY.to_string());
+        settings.insert(CONFIG_WALLET_KEY_DERIVATION.to_string(), DEFAULT_WALLET_KEY_DERIVATION.to_string());
+        settings.insert(CONFIG_LINK_SECRET_ALIAS.to_string(), DEFAULT_LINK_SECRET_ALIAS.to_string());
+        settings.insert(CONFIG_PROTOCOL_VERSION.to_string(), DEFAULT_PROTOCOL_VERSION.to_string());
+        settings.insert(CONFIG_EXPORTED_WALLET_PATH.to_string(),
+                        get_temp_dir_path(DEFAULT_EXPORTED_WALLET_PATH).to_str().unwrap_or("").to_string());
+        settings.insert(CONFIG_WALLET_BACKUP_KEY.to_string(), DEFAULT_WALLET_BACKUP_KEY.to_string());
+        settings.insert(CONFIG_THREADPOOL_SIZE.to_string(), DEFAULT_THREADPOOL_SIZE.to_string());
+        settings.insert(CONFIG_PAYMENT_METHOD.to_string(), DEFAULT_PAYMENT_METHOD.to_string());
+        settings.insert(CONFIG_USE_LATEST_PROTOCOLS.to_string(), DEFAULT_USE_LATEST_PROTOCOLS.to_string());
+    });
+
+    match res {
+        Ok(()) => error::SUCCESS.code_num,
+        Err(err) => err.into()
+    }
 }
 
 pub fn validate_config(config: &HashMap<String, String>) -> VcxResult<u32> {
@@ -160,6 +167,16 @@ pub fn validate_config(config: &HashMap<String, String>) -> VcxResult<u32> {
 
     validate_optional_config_val(config.get(CONFIG_ACTORS), VcxErrorKind::InvalidOption, validation::validate_actors)?;
 
+    validate_optional_config_val(config.get(CONFIG_SIGNED_JSON_FORMAT), VcxErrorKind::InvalidOption, SignedJsonFormat::from_str)?;
+
+    validate_optional_config_val(config.get(CONFIG_AUTO_ACCEPT_CREDENTIAL_OFFERS), VcxErrorKind::InvalidOption, AutoAcceptOffers::from_str)?;
+    validate_optional_config_val(config.get(CONFIG_AUTO_PRESENT_PROOFS), VcxErrorKind::InvalidOption, AutoPresentProofs::from_str)?;
+    validate_optional_config_val(config.get(CONFIG_CREDENTIAL_SELECTION_STRATEGY), VcxErrorKind::InvalidOption, CredentialSelectionStrategy::from_str)?;
+    validate_optional_config_val(config.get(CONFIG_PERSIST_OBJECTS), VcxErrorKind::InvalidOption, bool::from_str)?;
+    validate_optional_config_val(config.get(CONFIG_OBJECT_CACHE_SIZE), VcxErrorKind::InvalidOption, usize::from_str)?;
+    validate_optional_config_val(config.get(CONFIG_CLOUD_AGENT), VcxErrorKind::InvalidOption, ::messages::cloud_agent::by_name)?;
+    validate_optional_config_val(config.get(CONFIG_ORDER_RECEIVED_MESSAGES), VcxErrorKind::InvalidOption, bool::from_str)?;
+
     Ok(error::SUCCESS.code_num)
 }
 
@@ -187,26 +204,25 @@ pub fn validate_payment_method() -> VcxResult<u32> {
 }
 
 pub fn log_settings() {
-    let settings = SETTINGS.read().unwrap();
-    trace!("loaded settings: {:?}", settings.to_string());
+    context::with_settings(|settings| trace!("loaded settings: {:?}", settings.to_string()));
 }
 
 pub fn indy_mocks_enabled() -> bool {
-    let config = SETTINGS.read().unwrap();
-
-    match config.get(CONFIG_ENABLE_TEST_MODE) {
-        None => false,
-        Some(value) => value == "true" || value == "indy"
-    }
+    context::with_settings(|config|
+        match config.get(CONFIG_ENABLE_TEST_MODE) {
+            None => false,
+            Some(value) => value == "true" || value == "indy"
+        }
+    )
 }
 
 pub fn agency_mocks_enabled() -> bool {
-    let config = SETTINGS.read().unwrap();
-
-    match config.get(CONFIG_ENABLE_TEST_MODE) {
-        None => false,
-        Some(value) => value == "true" || value == "agency"
-    }
+    context::with_settings(|config|
+        match config.get(CONFIG_ENABLE_TEST_MODE) {
+            None => false,
+            Some(value) => value == "true" || value == "agency"
+        }
+    )
 }
 
 pub fn process_config_string(config: &str, do_validation: bool) -> VcxResult<u32> {
@@ -216,21 +232,24 @@ pub fn process_config_string(config: &str, do_validation: bool) -> VcxResult<u32
         .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot parse config: {}", err)))?;
 
     if let Value::Object(ref map) = configuration {
+        let mut values = HashMap::new();
+
         for (key, value) in map {
             match value {
-                Value::String(value_) => set_config_value(key, &value_),
-                Value::Array(value_) => set_config_value(key, &json!(value_).to_string()),
-                Value::Object(value_) => set_config_value(key, &json!(value_).to_string()),
-                Value::Bool(value_) => set_config_value(key, &json!(value_).to_string()),
+                Value::String(value_) => values.insert(key.to_string(), value_.to_string()),
+                Value::Array(value_) => values.insert(key.to_string(), json!(value_).to_string()),
+                Value::Object(value_) => values.insert(key.to_string(), json!(value_).to_string()),
+                Value::Bool(value_) => values.insert(key.to_string(), json!(value_).to_string()),
                 _ => return Err(VcxError::from(VcxErrorKind::InvalidJson)),
-            }
+            };
         }
+
+        context::with_settings_mut(|settings| settings.extend(values))?;
     }
 
     if do_validation {
-        let setting = SETTINGS.read()
-            .or(Err(VcxError::from(VcxErrorKind::InvalidConfiguration)))?;
-        validate_config(&setting.borrow())
+        let setting = context::with_settings(|settings| settings.clone());
+        validate_config(&setting)
     } else {
         Ok(error::SUCCESS.code_num)
     }
@@ -251,19 +270,16 @@ pub fn process_config_file(path: &str) -> VcxResult<u32> {
 pub fn get_config_value(key: &str) -> VcxResult<String> {
     trace!("get_config_value >>> key: {}", key);
 
-    SETTINGS
-        .read()
-        .or(Err(VcxError::from_msg(VcxErrorKind::InvalidConfiguration, "Cannot read settings")))?
-        .get(key)
-        .map(|v| v.to_string())
+    context::with_settings(|settings| settings.get(key).map(|v| v.to_string()))
         .ok_or(VcxError::from_msg(VcxErrorKind::InvalidConfiguration, format!("Cannot read \"{}\" from settings", key)))
 }
 
 pub fn set_config_value(key: &str, value: &str) {
     trace!("set_config_value >>> key: {}, value: {}", key, value);
-    SETTINGS
-        .write().unwrap()
-        .insert(key.to_string(), value.to_string());
+    // value can't be stored when the context has been released in the meantime
+    if let Err(err) = context::with_settings_mut(|settings| settings.insert(key.to_string(), value.to_string())) {
+        warn!("Cannot set config value {}: {}", key, err);
+    }
 }
 
 pub fn get_wallet_name() -> VcxResult<String> {
@@ -306,12 +322,7 @@ pub fn get_protocol_version() -> usize {
 
 pub fn get_opt_config_value(key: &str) -> Option<String> {
     trace!("get_opt_config_value >>> key: {}", key);
-    match SETTINGS.read() {
-        Ok(x) => x,
-        Err(_) => return None
-    }
-        .get(key)
-        .map(|v| v.to_string())
+    context::with_settings(|settings| settings.get(key).map(|v| v.to_string()))
 }
 
 pub fn set_opt_config_value(key: &str, value: &Option<String>) {
@@ -394,6 +405,8 @@ pub enum Actors {
     Verifier,
     Sender,
     Receiver,
+    Mediator,
+    Recipient,
 }
 
 pub const ARIES_COMMUNICATION_METHOD: &str = "aries";
@@ -443,6 +456,179 @@ impl ::std::string::ToString for ProtocolTypes {
     }
 }
 
+// Form of JSON payloads which are signed or hashed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignedJsonFormat {
+    // Serialization used before canonical form was introduced. Signatures in any form are accepted.
+    Legacy,
+    // Payloads are signed in canonical form. Signatures in legacy form are still accepted.
+    Canonical,
+    // Payloads are signed in canonical form. Signatures in legacy form are rejected.
+    CanonicalStrict,
+}
+
+impl Default for SignedJsonFormat {
+    fn default() -> Self {
+        SignedJsonFormat::Canonical
+    }
+}
+
+impl FromStr for SignedJsonFormat {
+    type Err = VcxError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "legacy" => Ok(SignedJsonFormat::Legacy),
+            "canonical" => Ok(SignedJsonFormat::Canonical),
+            "canonical_strict" => Ok(SignedJsonFormat::CanonicalStrict),
+            format => Err(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unknown signed json format: {:?}", format)))
+        }
+    }
+}
+
+pub fn get_signed_json_format() -> SignedJsonFormat {
+    get_config_value(CONFIG_SIGNED_JSON_FORMAT)
+        .ok()
+        .and_then(|format| format.parse().ok())
+        .unwrap_or_default()
+}
+
+// Whether Aries Credential Offers are answered with Credential Request without user involvement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoAcceptOffers {
+    Never,
+    Always,
+}
+
+impl Default for AutoAcceptOffers {
+    fn default() -> Self {
+        AutoAcceptOffers::Never
+    }
+}
+
+impl FromStr for AutoAcceptOffers {
+    type Err = VcxError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "never" => Ok(AutoAcceptOffers::Never),
+            "always" => Ok(AutoAcceptOffers::Always),
+            policy => Err(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unknown credential offers auto-accept policy: {:?}", policy)))
+        }
+    }
+}
+
+// Whether Aries Presentation Requests are answered with Presentation without user involvement.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoPresentProofs {
+    Never,
+    // Only if every requested attribute and predicate is matched by exactly one credential
+    SingleMatch,
+    // If every requested attribute and predicate is matched by some credential.
+    // Credential is chosen with `credential_selection_strategy` when there are several of them.
+    Always,
+}
+
+impl Default for AutoPresentProofs {
+    fn default() -> Self {
+        AutoPresentProofs::Never
+    }
+}
+
+impl FromStr for AutoPresentProofs {
+    type Err = VcxError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "never" => Ok(AutoPresentProofs::Never),
+            "single_match" => Ok(AutoPresentProofs::SingleMatch),
+            "always" => Ok(AutoPresentProofs::Always),
+            policy => Err(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unknown proofs auto-present policy: {:?}", policy)))
+        }
+    }
+}
+
+pub fn get_auto_accept_credential_offers() -> AutoAcceptOffers {
+    get_config_value(CONFIG_AUTO_ACCEPT_CREDENTIAL_OFFERS)
+        .ok()
+        .and_then(|policy| policy.parse().ok())
+        .unwrap_or_default()
+}
+
+pub fn get_auto_present_proofs() -> AutoPresentProofs {
+    get_config_value(CONFIG_AUTO_PRESENT_PROOFS)
+        .ok()
+        .and_then(|policy| policy.parse().ok())
+        .unwrap_or_default()
+}
+
+// How a credential is chosen when several credentials match a requested attribute or predicate on auto-presenting proofs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CredentialSelectionStrategy {
+    // The first credential returned by the wallet
+    FirstMatch,
+    // The credential with the greatest integer value of the attribute (issuance timestamp for example)
+    Newest { attribute: String },
+    // The credential with the least integer value of the attribute (expiration timestamp for example)
+    EarliestExpiry { attribute: String },
+    // The credential of the first credential definition from the list, the first match if there is none
+    CredDefPreference { cred_def_ids: Vec<String> },
+    // The credential chosen by the application handler set with `vcx_set_credential_selection_handler`
+    Callback,
+}
+
+impl Default for CredentialSelectionStrategy {
+    fn default() -> Self {
+        CredentialSelectionStrategy::FirstMatch
+    }
+}
+
+impl FromStr for CredentialSelectionStrategy {
+    type Err = VcxError;
+
+    fn from_str(strategy: &str) -> Result<Self, Self::Err> {
+        ::serde_json::from_str(strategy)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unknown credential selection strategy: {:?}, err: {:?}", strategy, err)))
+    }
+}
+
+pub fn get_credential_selection_strategy() -> CredentialSelectionStrategy {
+    get_config_value(CONFIG_CREDENTIAL_SELECTION_STRATEGY)
+        .ok()
+        .and_then(|strategy| strategy.parse().ok())
+        .unwrap_or_default()
+}
+
+// Whether connections, credentials and proofs are stored in the wallet on every change and restored by handle after restart
+pub fn persist_objects_enabled() -> bool {
+    get_config_value(CONFIG_PERSIST_OBJECTS)
+        .map(|value| value == "true")
+        .unwrap_or(false)
+}
+
+// Least recently used persisted objects over the limit are unloaded from memory and restored on access
+pub fn get_object_cache_size() -> Option<usize> {
+    get_config_value(CONFIG_OBJECT_CACHE_SIZE)
+        .ok()
+        .and_then(|size| size.parse().ok())
+}
+
+// Name of the cloud agent implementation messages are received through
+pub fn get_cloud_agent() -> String {
+    get_config_value(CONFIG_CLOUD_AGENT)
+        .unwrap_or(DEFAULT_CLOUD_AGENT.to_string())
+}
+
+// Whether messages of a thread received over a connection are handled in the order they were sent (by `~timing.out_time`)
+pub fn order_received_messages_enabled() -> bool {
+    get_config_value(CONFIG_ORDER_RECEIVED_MESSAGES)
+        .map(|value| value == "true")
+        .unwrap_or(false)
+}
+
 pub fn get_protocol_type() -> ProtocolTypes {
     ProtocolTypes::from(get_config_value(CONFIG_PROTOCOL_TYPE)
         .unwrap_or(DEFAULT_PROTOCOL_TYPE.to_string()))
@@ -450,8 +636,8 @@ pub fn get_protocol_type() -> ProtocolTypes {
 
 pub fn clear_config() {
     trace!("clear_config >>>");
-    let mut config = SETTINGS.write().unwrap();
-    config.clear();
+    // settings of released context are already gone
+    context::with_settings_mut(|config| config.clear()).ok();
 }
 
 #[cfg(test)]
@@ -592,6 +778,26 @@ pub mod tests {
         let mut config = _mandatory_config();
         config.insert(CONFIG_WEBHOOK_URL.to_string(), invalid.to_string());
         assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidUrl);
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_SIGNED_JSON_FORMAT.to_string(), invalid.to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_AUTO_ACCEPT_CREDENTIAL_OFFERS.to_string(), invalid.to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_AUTO_PRESENT_PROOFS.to_string(), invalid.to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_PERSIST_OBJECTS.to_string(), invalid.to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_OBJECT_CACHE_SIZE.to_string(), invalid.to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
     }
 
     #[test]
@@ -679,4 +885,87 @@ pub mod tests {
         config["actors"] = json!(["wrong"]);
         assert_eq!(process_config_string(&config.to_string(), true).unwrap_err().kind(), VcxErrorKind::InvalidOption);
     }
+
+    #[test]
+    fn test_get_signed_json_format() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(SignedJsonFormat::Canonical, get_signed_json_format());
+
+        set_config_value(CONFIG_SIGNED_JSON_FORMAT, "legacy");
+        assert_eq!(SignedJsonFormat::Legacy, get_signed_json_format());
+
+        set_config_value(CONFIG_SIGNED_JSON_FORMAT, "canonical_strict");
+        assert_eq!(SignedJsonFormat::CanonicalStrict, get_signed_json_format());
+    }
+
+    #[test]
+    fn test_get_auto_accept_policies() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(AutoAcceptOffers::Never, get_auto_accept_credential_offers());
+        assert_eq!(AutoPresentProofs::Never, get_auto_present_proofs());
+
+        set_config_value(CONFIG_AUTO_ACCEPT_CREDENTIAL_OFFERS, "always");
+        set_config_value(CONFIG_AUTO_PRESENT_PROOFS, "single_match");
+        assert_eq!(AutoAcceptOffers::Always, get_auto_accept_credential_offers());
+        assert_eq!(AutoPresentProofs::SingleMatch, get_auto_present_proofs());
+
+        set_config_value(CONFIG_AUTO_PRESENT_PROOFS, "always");
+        assert_eq!(AutoPresentProofs::Always, get_auto_present_proofs());
+    }
+
+    #[test]
+    fn test_get_credential_selection_strategy() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(CredentialSelectionStrategy::FirstMatch, get_credential_selection_strategy());
+
+        set_config_value(CONFIG_CREDENTIAL_SELECTION_STRATEGY, r#"{"type":"newest","attribute":"issued_at"}"#);
+        assert_eq!(CredentialSelectionStrategy::Newest { attribute: "issued_at".to_string() }, get_credential_selection_strategy());
+
+        set_config_value(CONFIG_CREDENTIAL_SELECTION_STRATEGY, r#"{"type":"cred_def_preference","cred_def_ids":["cred_def_1"]}"#);
+        assert_eq!(CredentialSelectionStrategy::CredDefPreference { cred_def_ids: vec!["cred_def_1".to_string()] }, get_credential_selection_strategy());
+
+        assert!(CredentialSelectionStrategy::from_str(r#"{"type":"random"}"#).is_err());
+        assert!(CredentialSelectionStrategy::from_str(r#"{"type":"newest"}"#).is_err());
+    }
+
+    #[test]
+    fn test_persist_objects_enabled() {
+        let _setup = SetupDefaults::init();
+
+        assert!(!persist_objects_enabled());
+
+        set_config_value(CONFIG_PERSIST_OBJECTS, "true");
+        assert!(persist_objects_enabled());
+    }
+
+    #[test]
+    fn test_get_cloud_agent() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(DEFAULT_CLOUD_AGENT, get_cloud_agent());
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_CLOUD_AGENT.to_string(), "direct".to_string());
+        assert_eq!(validate_config(&config).unwrap(), error::SUCCESS.code_num);
+
+        config.insert(CONFIG_CLOUD_AGENT.to_string(), "unknown".to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+    }
+
+    #[test]
+    fn test_order_received_messages_enabled() {
+        let _setup = SetupDefaults::init();
+
+        assert!(!order_received_messages_enabled());
+
+        set_config_value(CONFIG_ORDER_RECEIVED_MESSAGES, "true");
+        assert!(order_received_messages_enabled());
+
+        let mut config = _mandatory_config();
+        config.insert(CONFIG_ORDER_RECEIVED_MESSAGES.to_string(), "yes".to_string());
+        assert_eq!(validate_config(&config).unwrap_err().kind(), VcxErrorKind::InvalidOption);
+    }
 }
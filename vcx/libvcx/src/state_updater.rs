@@ -0,0 +1,126 @@
+use connection;
+use credential;
+use disclosed_proof;
+use issuer_credential;
+use proof;
+use error::prelude::*;
+use messages::MessageStatusCode;
+use messages::get_message::download_messages;
+use utils::serialization::ObjectType;
+use v3::handlers::connection::agent;
+
+/*
+    Updates states of all in-flight Connection, Credential, IssuerCredential, Proof and DisclosedProof objects in one pass.
+
+    Pending agency messages of all Aries connections are downloaded with a single request and objects take their
+    messages from the downloaded set instead of querying the agency one by one.
+    Connections are updated first, so protocols of just established connections can proceed in the same pass.
+    Objects of the legacy protocol and mediated connections still query for their messages themselves.
+*/
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateChange {
+    pub object_type: ObjectType,
+    pub handle: u32,
+    pub source_id: String,
+    pub prev_state: u32,
+    pub state: u32,
+}
+
+struct Objects {
+    object_type: ObjectType,
+    get_handles: fn() -> Vec<u32>,
+    get_state: fn(u32) -> VcxResult<u32>,
+    update_state: fn(u32, Option<String>) -> VcxResult<u32>,
+    get_source_id: fn(u32) -> VcxResult<String>,
+}
+
+pub fn update_all_states() -> VcxResult<Vec<StateChange>> {
+    trace!("state_updater::update_all_states >>>");
+
+    _prefetch_messages()?;
+
+    let objects = [
+        Objects { object_type: ObjectType::Connection, get_handles: connection::get_handles, get_state: _connection_state, update_state: connection::update_state, get_source_id: connection::get_source_id },
+        Objects { object_type: ObjectType::Credential, get_handles: credential::get_handles, get_state: credential::get_state, update_state: credential::update_state, get_source_id: credential::get_source_id },
+        Objects { object_type: ObjectType::IssuerCredential, get_handles: issuer_credential::get_handles, get_state: issuer_credential::get_state, update_state: issuer_credential::update_state, get_source_id: issuer_credential::get_source_id },
+        Objects { object_type: ObjectType::Proof, get_handles: proof::get_handles, get_state: proof::get_state, update_state: proof::update_state, get_source_id: proof::get_source_id },
+        Objects { object_type: ObjectType::DisclosedProof, get_handles: disclosed_proof::get_handles, get_state: disclosed_proof::get_state, update_state: disclosed_proof::update_state, get_source_id: disclosed_proof::get_source_id },
+    ];
+
+    let mut changes = Vec::new();
+    for objects in objects.iter() {
+        changes.extend(_update_objects(objects));
+    }
+
+    agent::clear_prefetched_messages();
+
+    trace!("state_updater::update_all_states <<< changes: {:?}", changes);
+    Ok(changes)
+}
+
+fn _prefetch_messages() -> VcxResult<()> {
+    let pairwise_dids: Vec<String> = connection::get_handles()
+        .into_iter()
+        .filter(|handle| connection::is_v3_connection(*handle).unwrap_or(false))
+        .filter_map(|handle| connection::get_pw_did(handle).ok())
+        .collect();
+
+    if pairwise_dids.is_empty() {
+        return Ok(());
+    }
+
+    let messages = download_messages(Some(pairwise_dids.clone()), Some(vec![MessageStatusCode::Received.to_string()]), None)
+        .map_err(|err| err.extend("Cannot download messages of connections"))?;
+
+    agent::set_prefetched_messages(&pairwise_dids, messages);
+    Ok(())
+}
+
+// Failure of one object doesn't prevent updating the others
+fn _update_objects(objects: &Objects) -> Vec<StateChange> {
+    let mut changes = Vec::new();
+
+    for handle in (objects.get_handles)() {
+        let prev_state = match (objects.get_state)(handle) {
+            Ok(state) => state,
+            Err(_) => continue
+        };
+
+        if let Err(err) = (objects.update_state)(handle, None) {
+            warn!("state_updater::update_all_states: cannot update state of {:?} {}: {}", objects.object_type, handle, err);
+            continue;
+        }
+
+        let state = (objects.get_state)(handle).unwrap_or(prev_state);
+        if state != prev_state {
+            changes.push(StateChange {
+                object_type: objects.object_type,
+                handle,
+                source_id: (objects.get_source_id)(handle).unwrap_or_default(),
+                prev_state,
+                state,
+            });
+        }
+    }
+
+    changes
+}
+
+fn _connection_state(handle: u32) -> VcxResult<u32> {
+    Ok(connection::get_state(handle))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_change_to_json() {
+        let change = StateChange { object_type: ObjectType::IssuerCredential, handle: 1, source_id: String::from("test"), prev_state: 2, state: 4 };
+        let json: ::serde_json::Value = ::serde_json::from_str(&json!(change).to_string()).unwrap();
+
+        assert_eq!(json!("issuer_credential"), json["object_type"]);
+        assert_eq!(json!(4), json["state"]);
+    }
+}
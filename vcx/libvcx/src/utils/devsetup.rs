@@ -48,6 +48,7 @@ fn tear_down() {
     settings::clear_config();
     reset_wallet_handle();
     reset_pool_handle();
+    ::mediation::reset();
 }
 
 impl SetupEmpty {
@@ -408,7 +409,7 @@ pub fn set_consumer() {
 
 fn change_wallet_handle() {
     let wallet_handle = settings::get_config_value(settings::CONFIG_WALLET_HANDLE).unwrap();
-    unsafe { wallet::WALLET_HANDLE = WalletHandle(wallet_handle.parse::<i32>().unwrap()) }
+    wallet::set_wallet_handle(WalletHandle(wallet_handle.parse::<i32>().unwrap())).unwrap();
 }
 
 pub fn setup_agency_env(protocol_type: &str, use_zero_fees: bool) {
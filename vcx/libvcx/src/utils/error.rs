@@ -115,6 +115,8 @@ pub static ACTION_NOT_SUPPORTED: Error = Error { code_num: 1103, message: "Actio
 pub static INVALID_REDIRECT_DETAILS: Error = Error{code_num: 1104, message: "Invalid redirect details structure"};
 /* EC 1105 is reserved for proprietary forks of libVCX */
 pub static NO_AGENT_INFO: Error = Error{code_num: 1106, message: "Agent pairwise information not found"};
+pub static INVALID_PROVISIONING_TOKEN: Error = Error{code_num: 1107, message: "Provisioning token is invalid or expired"};
+pub static INVALID_CONTEXT_HANDLE: Error = Error{code_num: 1108, message: "Invalid Context Handle"};
 
 lazy_static! {
     static ref ERROR_C_MESSAGES: HashMap<u32, CString> = {
@@ -221,6 +223,8 @@ lazy_static! {
         insert_c_message(&mut m, &ACTION_NOT_SUPPORTED);
         insert_c_message(&mut m, &INVALID_REDIRECT_DETAILS);
         insert_c_message(&mut m, &NO_AGENT_INFO);
+        insert_c_message(&mut m, &INVALID_PROVISIONING_TOKEN);
+        insert_c_message(&mut m, &INVALID_CONTEXT_HANDLE);
 
         m
     };
@@ -1,10 +1,21 @@
 use settings;
 use std::io::Read;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use reqwest;
 use reqwest::header::CONTENT_TYPE;
 use std::env;
+use std::net::TcpStream;
 use error::prelude::*;
+use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode, SslStream};
+use openssl::x509::{X509, X509Ref};
+use openssl::x509::store::X509StoreBuilder;
+use openssl::sha::sha256;
+use rustls::{Certificate, ClientConfig, RootCertStore, ServerCertVerified, ServerCertVerifier, TLSError, WebPKIVerifier};
+use ureq;
+use url::Url;
+use webpki::DNSNameRef;
+use webpki_roots;
+use v3::messages::connection::did_doc::TlsConstraints;
 
 lazy_static! {
     static ref AGENCY_MOCK: Mutex<AgencyMock> = Mutex::new(AgencyMock::default());
@@ -27,13 +38,16 @@ impl AgencyMock {
     }
 }
 
-//Todo: change this RC to a u32
+// Message packed for the agency is delivered by the cloud agent selected by the library config
 pub fn post_u8(body_content: &Vec<u8>) -> VcxResult<Vec<u8>> {
-    let endpoint = format!("{}/agency/msg", settings::get_config_value(settings::CONFIG_AGENCY_ENDPOINT)?);
-    post_message(body_content, &endpoint)
+    ::messages::cloud_agent::get()?.post(body_content)
 }
 
 pub fn post_message(body_content: &Vec<u8>, url: &str) -> VcxResult<Vec<u8>> {
+    post_message_with_tls(body_content, url, None)
+}
+
+pub fn post_message_with_tls(body_content: &Vec<u8>, url: &str, tls: Option<&TlsConstraints>) -> VcxResult<Vec<u8>> {
     if settings::agency_mocks_enabled() {
         return AgencyMock::get_response();
     }
@@ -43,6 +57,11 @@ pub fn post_message(body_content: &Vec<u8>, url: &str) -> VcxResult<Vec<u8>> {
         info!("::Android code");
         set_ssl_cert_location();
     }
+
+    if let Some(tls) = tls {
+        return post_message_over_tls(body_content, url, tls);
+    }
+
     let client = reqwest::ClientBuilder::new().timeout(::utils::timeout::TimeoutUtils::long_timeout()).build()
         .or(Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, "Preparing Post failed")))?;
     debug!("Posting encrypted bundle to: \"{}\"", url);
@@ -74,6 +93,157 @@ pub fn post_message(body_content: &Vec<u8>, url: &str) -> VcxResult<Vec<u8>> {
     Ok(content)
 }
 
+// reqwest doesn't expose the peer certificate and trusts the system roots, so messages for endpoints with
+// TLS constraints are posted by ureq with the certificate verifier checking the constraints.
+fn post_message_over_tls(body_content: &Vec<u8>, url: &str, tls: &TlsConstraints) -> VcxResult<Vec<u8>> {
+    let url = Url::parse(url)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Invalid endpoint {:?}: {:?}", url, err)))?;
+
+    if url.scheme() != "https" {
+        return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("TLS constraints are set for not https endpoint {:?}", url.as_str())));
+    }
+
+    let tls_config = tls_client_config(tls)?;
+
+    debug!("Posting encrypted bundle to: \"{}\" over pinned connection", url);
+
+    let response = ureq::post(url.as_str())
+        .timeout(::utils::timeout::TimeoutUtils::long_timeout())
+        .set("Content-Type", "application/ssi-agent-wire")
+        .set_tls_config(Arc::new(tls_config))
+        .send_bytes(body_content);
+
+    if let Some(err) = response.synthetic_error() {
+        error!("error: {}", err);
+        return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Could not connect {:?}", err)));
+    }
+
+    let success = response.ok();
+
+    let mut content = Vec::new();
+    response.into_reader().read_to_end(&mut content)
+        .or(Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, "could not read response")))?;
+
+    if !success {
+        let content = String::from_utf8_lossy(&content);
+        info!("Request failed: {}", content);
+        return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("POST failed with: {}", content)));
+    }
+
+    Ok(content)
+}
+
+// Trusts only the CA certificate of the constraints if it is set, and the default roots otherwise
+fn tls_client_config(tls: &TlsConstraints) -> VcxResult<ClientConfig> {
+    let mut config = ClientConfig::new();
+
+    match tls.ca_certificate {
+        Some(ref ca_certificate) => {
+            let ca_certificate = X509::from_pem(ca_certificate.as_bytes())
+                .and_then(|ca_certificate| ca_certificate.to_der())
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidConfiguration, format!("Invalid CA certificate: {:?}", err)))?;
+
+            config.root_store.add(&Certificate(ca_certificate))
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidConfiguration, format!("Invalid CA certificate: {:?}", err)))?;
+        }
+        None => config.root_store.add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS)
+    }
+
+    config.dangerous().set_certificate_verifier(Arc::new(PinnedCertVerifier { pins: tls.spki_sha256_pins.clone() }));
+
+    Ok(config)
+}
+
+// Verifies the certificate chain as usual and then checks that one of its certificates matches the pins
+struct PinnedCertVerifier {
+    pins: Vec<String>,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(&self,
+                          roots: &RootCertStore,
+                          presented_certs: &[Certificate],
+                          dns_name: DNSNameRef,
+                          ocsp_response: &[u8]) -> Result<ServerCertVerified, TLSError> {
+        let verified = WebPKIVerifier::new().verify_server_cert(roots, presented_certs, dns_name, ocsp_response)?;
+
+        if self.pins.is_empty() {
+            return Ok(verified);
+        }
+
+        let pinned = presented_certs.iter().any(|certificate| {
+            X509::from_der(&certificate.0)
+                .map(|certificate| matches_pins(&certificate, &self.pins))
+                .unwrap_or(false)
+        });
+
+        if !pinned {
+            return Err(TLSError::General("Certificate presented by endpoint doesn't match any pin".to_string()));
+        }
+
+        Ok(verified)
+    }
+}
+
+fn matches_pins(certificate: &X509Ref, pins: &[String]) -> bool {
+    certificate.public_key()
+        .and_then(|public_key| public_key.public_key_to_der())
+        .map(|spki| pins.contains(&::base64::encode(&sha256(&spki))))
+        .unwrap_or(false)
+}
+
+// Opens TLS connection with the endpoint host checking the constraints if they are set
+pub fn tls_connect(url: &Url, tls: Option<&TlsConstraints>) -> VcxResult<SslStream<TcpStream>> {
+    let host = url.host_str()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Endpoint without host: {:?}", url.as_str())))?;
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let mut connector = SslConnector::builder(SslMethod::tls())
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Cannot create TLS connector: {:?}", err)))?;
+    connector.set_verify(SslVerifyMode::PEER);
+
+    if let Some(ca_certificate) = tls.and_then(|tls| tls.ca_certificate.as_ref()) {
+        let ca_certificate = X509::from_pem(ca_certificate.as_bytes())
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidConfiguration, format!("Invalid CA certificate: {:?}", err)))?;
+
+        let mut store = X509StoreBuilder::new()
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Cannot create certificate store: {:?}", err)))?;
+        store.add_cert(ca_certificate)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Cannot add CA certificate: {:?}", err)))?;
+        connector.set_cert_store(store.build());
+    }
+
+    let stream = tcp_connect(host, port)?;
+
+    let stream = connector.build().connect(host, stream)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("TLS handshake with {:?} failed: {:?}", host, err)))?;
+
+    let pins = tls.map(|tls| &tls.spki_sha256_pins[..]).unwrap_or(&[]);
+
+    if pins.is_empty() {
+        return Ok(stream);
+    }
+
+    let chain = stream.ssl().peer_cert_chain()
+        .ok_or(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Endpoint {:?} didn't present certificate", host)))?;
+
+    let pinned = chain.iter().any(|certificate| matches_pins(certificate, pins));
+
+    if !pinned {
+        return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Certificate presented by {:?} doesn't match any pin", host)));
+    }
+
+    Ok(stream)
+}
+
+pub fn tcp_connect(host: &str, port: u16) -> VcxResult<TcpStream> {
+    let stream = TcpStream::connect((host, port))
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Could not connect {:?}", err)))?;
+    stream.set_read_timeout(Some(::utils::timeout::TimeoutUtils::long_timeout())).ok();
+    stream.set_write_timeout(Some(::utils::timeout::TimeoutUtils::long_timeout())).ok();
+    Ok(stream)
+}
+
 fn set_ssl_cert_location() {
     let ssl_cert_file = "SSL_CERT_FILE";
     env::set_var(ssl_cert_file, env::var("EXTERNAL_STORAGE").unwrap() + "/cacert.pem"); //TODO: CHANGE ME, HARDCODING FOR TESTING ONLY
@@ -84,3 +254,23 @@ fn set_ssl_cert_location() {
     }
     info!("::SSL_CERT_FILE has been set");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_message_over_tls_fails_for_not_https_endpoint() {
+        let tls = TlsConstraints { spki_sha256_pins: vec!["pin".to_string()], ca_certificate: None };
+
+        let res = post_message_over_tls(&b"message".to_vec(), "http://localhost:8080/agency/msg", &tls);
+        assert_eq!(VcxErrorKind::PostMessageFailed, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_tls_client_config_fails_for_invalid_ca_certificate() {
+        let tls = TlsConstraints { spki_sha256_pins: vec![], ca_certificate: Some("certificate".to_string()) };
+
+        assert_eq!(VcxErrorKind::InvalidConfiguration, tls_client_config(&tls).err().unwrap().kind());
+    }
+}
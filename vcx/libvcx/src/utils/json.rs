@@ -5,6 +5,7 @@ use serde_json::Value;
 use serde_json::Map;
 use std::string::String;
 use error::prelude::*;
+use settings;
 
 pub trait KeyMatch {
     fn matches(&self, key: &String, context: &Vec<String>) -> bool;
@@ -67,9 +68,87 @@ fn _collect_keys(map:&Map<String, Value>) -> Vec<String>{
 }
 
 
+/*
+Canonical form of JSON used for signed and hashed payloads: keys of objects are sorted at every level
+and there is no insignificant whitespace. Unlike plain serialization it doesn't depend on
+declaration order of struct fields or on `preserve_order` feature of serde_json.
+*/
+pub fn to_canonical_string<T: ::serde::Serialize>(value: &T) -> VcxResult<String> {
+    let value = serde_json::to_value(value)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize JSON: {:?}", err)))?;
+
+    let mut res = String::new();
+    _write_canonical(&value, &mut res)?;
+    Ok(res)
+}
+
+fn _write_canonical(value: &Value, res: &mut String) -> VcxResult<()> {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+
+            res.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 { res.push(','); }
+                res.push_str(&_to_string(&key)?);
+                res.push(':');
+                _write_canonical(&map[key], res)?;
+            }
+            res.push('}');
+        }
+        Value::Array(values) => {
+            res.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 { res.push(','); }
+                _write_canonical(value, res)?;
+            }
+            res.push(']');
+        }
+        value => res.push_str(&_to_string(value)?)
+    }
+    Ok(())
+}
+
+fn _to_string<T: ::serde::Serialize>(value: &T) -> VcxResult<String> {
+    serde_json::to_string(value)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize JSON: {:?}", err)))
+}
+
+/*
+Serializes payload which is going to be signed or hashed in the form set by `signed_json_format` option.
+*/
+pub fn to_signed_string<T: ::serde::Serialize>(value: &T) -> VcxResult<String> {
+    match settings::get_signed_json_format() {
+        settings::SignedJsonFormat::Legacy => _to_string(&json!(value)),
+        settings::SignedJsonFormat::Canonical | settings::SignedJsonFormat::CanonicalStrict => to_canonical_string(value),
+    }
+}
+
+/*
+Checks that signed payload is acceptable with the current `signed_json_format` option.
+Signatures are always checked over received bytes, so during migration both canonical and legacy forms are accepted,
+`canonical_strict` rejects payloads which are not in canonical form.
+*/
+pub fn check_signed_json(json: &str) -> VcxResult<()> {
+    if settings::get_signed_json_format() != settings::SignedJsonFormat::CanonicalStrict {
+        return Ok(());
+    }
+
+    let value: Value = serde_json::from_str(json)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize signed JSON: {:?}", err)))?;
+
+    if to_canonical_string(&value)? != json {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Signed JSON is not in canonical form"));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use utils::devsetup::SetupDefaults;
 
 
     #[test]
@@ -157,4 +236,36 @@ mod tests {
         let transformed = mapped_key_rewrite(un_abbr, &map).unwrap();
         assert_eq!(abbr, transformed);
     }
-}
\ No newline at end of file
+
+    #[derive(Serialize)]
+    struct Payload {
+        did: String,
+        did_doc: Value,
+    }
+
+    #[test]
+    fn to_canonical_string_sorts_keys() {
+        let payload = Payload {
+            did: "did".to_string(),
+            did_doc: json!({"service": [{"type": "did-communication", "id": "1"}], "@context": "https://w3id.org/did/v1"}),
+        };
+
+        assert_eq!(r#"{"did":"did","did_doc":{"@context":"https://w3id.org/did/v1","service":[{"id":"1","type":"did-communication"}]}}"#,
+                   to_canonical_string(&payload).unwrap());
+    }
+
+    #[test]
+    fn check_signed_json_works() {
+        let _setup = SetupDefaults::init();
+
+        let canonical = r#"{"a":1,"b":{"c":"d"}}"#;
+        let legacy = r#"{"b":{"c":"d"},"a":1}"#;
+
+        check_signed_json(canonical).unwrap();
+        check_signed_json(legacy).unwrap();
+
+        settings::set_config_value(settings::CONFIG_SIGNED_JSON_FORMAT, "canonical_strict");
+        check_signed_json(canonical).unwrap();
+        assert_eq!(VcxErrorKind::InvalidJson, check_signed_json(legacy).unwrap_err().kind());
+    }
+}
@@ -14,6 +14,10 @@ use error::prelude::*;
 
 const BLOB_STORAGE_TYPE: &str = "default";
 const REVOCATION_REGISTRY_TYPE: &str = "ISSUANCE_BY_DEFAULT";
+const REVOCATION_REGISTRY_FULL: u32 = 400;
+// wallet records keeping the registry new credentials of a credential definition are issued from
+const CURRENT_REV_REG_TYPE: &str = "current_rev_reg";
+pub const DEFAULT_REV_REG_TAG: &str = "tag1";
 
 pub fn libindy_verifier_verify_proof(proof_req_json: &str,
                                      proof_json: &str,
@@ -31,8 +35,26 @@ pub fn libindy_verifier_verify_proof(proof_req_json: &str,
         .map_err(VcxError::from)
 }
 
-pub fn libindy_create_and_store_revoc_reg(issuer_did: &str, cred_def_id: &str, tails_path: &str, max_creds: u32) -> VcxResult<(String, String, String)> {
-    trace!("creating revocation: {}, {}, {}", cred_def_id, tails_path, max_creds);
+pub fn libindy_verifier_verify_proof_with_details(proof_req_json: &str,
+                                                  proof_json: &str,
+                                                  schemas_json: &str,
+                                                  credential_defs_json: &str,
+                                                  rev_reg_defs_json: &str,
+                                                  rev_regs_json: &str,
+                                                  options_json: Option<&str>) -> VcxResult<String> {
+    anoncreds::verifier_verify_proof_with_details(proof_req_json,
+                                                  proof_json,
+                                                  schemas_json,
+                                                  credential_defs_json,
+                                                  rev_reg_defs_json,
+                                                  rev_regs_json,
+                                                  options_json)
+        .wait()
+        .map_err(VcxError::from)
+}
+
+pub fn libindy_create_and_store_revoc_reg(issuer_did: &str, cred_def_id: &str, tails_path: &str, max_creds: u32, tag: &str) -> VcxResult<(String, String, String)> {
+    trace!("creating revocation: {}, {}, {}, {}", cred_def_id, tails_path, max_creds, tag);
 
     let tails_config = json!({"base_dir": tails_path,"uri_pattern": ""}).to_string();
 
@@ -41,7 +63,7 @@ pub fn libindy_create_and_store_revoc_reg(issuer_did: &str, cred_def_id: &str, t
 
     let revoc_config = json!({"max_cred_num": max_creds, "issuance_type": REVOCATION_REGISTRY_TYPE}).to_string();
 
-    anoncreds::issuer_create_and_store_revoc_reg(get_wallet_handle(), issuer_did, None, "tag1", cred_def_id, &revoc_config, writer)
+    anoncreds::issuer_create_and_store_revoc_reg(get_wallet_handle(), issuer_did, None, tag, cred_def_id, &revoc_config, writer)
         .wait()
         .map_err(VcxError::from)
 }
@@ -103,6 +125,112 @@ pub fn libindy_issuer_create_credential(cred_offer_json: &str,
         .map_err(VcxError::from)
 }
 
+/// Issues the credential and rotates the revocation registry when it is full.
+/// The new registry is created from the same credential definition with the same capacity,
+/// published on the ledger and used for all next credentials of the credential definition.
+///
+/// Returns the id of the revocation registry the credential was issued from in addition to
+/// the results of `libindy_issuer_create_credential`.
+pub fn issuer_create_credential_rotating(cred_offer_json: &str,
+                                         cred_req_json: &str,
+                                         cred_values_json: &str,
+                                         rev_reg_id: Option<String>,
+                                         tails_file: Option<String>) -> VcxResult<(String, Option<String>, Option<String>, Option<String>)> {
+    let mut rev_reg_id = rev_reg_id;
+
+    loop {
+        match libindy_issuer_create_credential(cred_offer_json, cred_req_json, cred_values_json, rev_reg_id.clone(), tails_file.clone()) {
+            Err(ref err) if err.kind() == VcxErrorKind::LibndyError(REVOCATION_REGISTRY_FULL) => {
+                let full_rev_reg_id = rev_reg_id.clone()
+                    .ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, "Revocation registry is full but `rev_reg_id` is not set"))?;
+
+                let tails_file = tails_file.as_ref()
+                    .ok_or(VcxError::from_msg(VcxErrorKind::InvalidRevocationDetails, "Invalid RevocationInfo: `tails_file` field not found"))?;
+
+                let cred_def_id = _get_cred_def_id_from_offer(cred_offer_json)?;
+
+                // another credential may have already rotated the registry
+                let next_rev_reg_id = match _get_current_rev_reg(&cred_def_id) {
+                    Some(ref current_rev_reg_id) if current_rev_reg_id != &full_rev_reg_id => current_rev_reg_id.clone(),
+                    _ => {
+                        let (next_rev_reg_id, _, _) = rotate_rev_reg(&full_rev_reg_id, tails_file)?;
+                        _set_current_rev_reg(&cred_def_id, &next_rev_reg_id)?;
+                        next_rev_reg_id
+                    }
+                };
+
+                warn!("Revocation registry {} is full, issuing credential from {}", full_rev_reg_id, next_rev_reg_id);
+
+                rev_reg_id = Some(next_rev_reg_id);
+            }
+            result => {
+                return result.map(|(cred, cred_rev_id, rev_reg_delta)| (cred, cred_rev_id, rev_reg_delta, rev_reg_id));
+            }
+        }
+    }
+}
+
+/// Creates the next revocation registry for the credential definition of the full one
+/// and publishes its definition and initial entry on the ledger.
+pub fn rotate_rev_reg(full_rev_reg_id: &str, tails_file: &str) -> VcxResult<(String, String, String)> {
+    trace!("rotate_rev_reg >>> full_rev_reg_id: {}, tails_file: {}", full_rev_reg_id, tails_file);
+
+    let (_, full_rev_reg_def) = get_rev_reg_def_json(full_rev_reg_id)?;
+
+    let full_rev_reg_def: Value = serde_json::from_str(&full_rev_reg_def)
+        .to_vcx(VcxErrorKind::InvalidJson, "Cannot deserialize RevocationRegistryDefinition")?;
+
+    let cred_def_id = full_rev_reg_def["credDefId"].as_str()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid RevocationRegistryDefinition: `credDefId` field not found"))?;
+
+    let max_creds = full_rev_reg_def["value"]["maxCredNum"].as_u64()
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid RevocationRegistryDefinition: `maxCredNum` field not found"))?;
+
+    let issuer_did = settings::get_config_value(settings::CONFIG_INSTITUTION_DID)?;
+
+    let (rev_reg_id, rev_reg_def, rev_reg_entry) =
+        generate_rev_reg(&issuer_did, cred_def_id, tails_file, max_creds as u32, &_next_rev_reg_tag(full_rev_reg_id))?;
+
+    publish_rev_reg_def(&issuer_did, &rev_reg_def)?;
+    publish_rev_reg_delta(&issuer_did, &rev_reg_id, &rev_reg_entry)?;
+
+    // tails upload failure is reported in the revocation publisher status
+    ::revocation_publisher::add_rev_reg(&rev_reg_id, &rev_reg_def).ok();
+
+    Ok((rev_reg_id, rev_reg_def, rev_reg_entry))
+}
+
+fn _get_cred_def_id_from_offer(cred_offer_json: &str) -> VcxResult<String> {
+    let cred_offer: Value = serde_json::from_str(cred_offer_json)
+        .to_vcx(VcxErrorKind::InvalidJson, "Cannot deserialize CredentialOffer")?;
+
+    cred_offer["cred_def_id"].as_str()
+        .map(String::from)
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid CredentialOffer: `cred_def_id` field not found"))
+}
+
+fn _get_current_rev_reg(cred_def_id: &str) -> Option<String> {
+    ::utils::libindy::wallet::get_record(CURRENT_REV_REG_TYPE, cred_def_id, &json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string())
+        .ok()
+        .and_then(|record| serde_json::from_str::<Value>(&record).ok())
+        .and_then(|record| record["value"].as_str().map(String::from))
+}
+
+fn _set_current_rev_reg(cred_def_id: &str, rev_reg_id: &str) -> VcxResult<()> {
+    ::utils::libindy::wallet::update_record_value(CURRENT_REV_REG_TYPE, cred_def_id, rev_reg_id)
+        .or(::utils::libindy::wallet::add_record(CURRENT_REV_REG_TYPE, cred_def_id, rev_reg_id, None))
+}
+
+// Registries created by VCX are tagged `tag1`, `tag2` and so on
+fn _next_rev_reg_tag(rev_reg_id: &str) -> String {
+    let tag = rev_reg_id.rsplit(':').next().unwrap_or(DEFAULT_REV_REG_TAG);
+
+    match tag.trim_start_matches("tag").parse::<u32>() {
+        Ok(num) if tag.starts_with("tag") => format!("tag{}", num + 1),
+        _ => format!("{}_2", tag),
+    }
+}
+
 pub fn libindy_prover_create_proof(proof_req_json: &str,
                                    requested_credentials_json: &str,
                                    master_secret_id: &str,
@@ -256,6 +384,27 @@ pub fn libindy_prover_delete_credential(cred_id: &str) -> VcxResult<()>{
         .map_err(VcxError::from)
 }
 
+// Deletes credential together with related records in a single wallet transaction, returns info of the deleted credential
+pub fn libindy_prover_delete_credential_with_related(cred_id: &str, related_records_json: &str) -> VcxResult<String> {
+    anoncreds::prover_delete_credential_with_related(get_wallet_handle(),
+                                                     cred_id,
+                                                     related_records_json)
+        .wait()
+        .map_err(VcxError::from)
+}
+
+pub fn libindy_prover_count_credentials(query_json: &str) -> VcxResult<usize> {
+    let (search_handle, count) = anoncreds::prover_search_credentials(get_wallet_handle(), Some(query_json))
+        .wait()
+        .map_err(VcxError::from)?;
+
+    anoncreds::prover_close_credentials_search(search_handle)
+        .wait()
+        .map_err(VcxError::from)?;
+
+    Ok(count)
+}
+
 pub fn libindy_prover_create_master_secret(master_secret_id: &str) -> VcxResult<String> {
     if settings::indy_mocks_enabled() { return Ok(settings::DEFAULT_LINK_SECRET_ALIAS.to_string()); }
 
@@ -285,6 +434,14 @@ pub fn libindy_issuer_revoke_credential(tails_file: &str, rev_reg_id: &str, cred
         .map_err(VcxError::from)
 }
 
+pub fn libindy_issuer_merge_revocation_registry_deltas(rev_reg_delta_json: &str, other_rev_reg_delta_json: &str) -> VcxResult<String> {
+    if settings::indy_mocks_enabled() { return Ok(other_rev_reg_delta_json.to_string()); }
+
+    anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta_json, other_rev_reg_delta_json)
+        .wait()
+        .map_err(VcxError::from)
+}
+
 pub fn libindy_build_revoc_reg_def_request(submitter_did: &str,
                                            rev_reg_def_json: &str) -> VcxResult<String> {
     if settings::indy_mocks_enabled() { return Ok("".to_string()); }
@@ -454,7 +611,7 @@ pub fn get_cred_def_json(cred_def_id: &str) -> VcxResult<(String, String)> {
     Ok((cred_def_id.to_string(), cred_def_json))
 }
 
-pub fn generate_rev_reg(issuer_did: &str, cred_def_id: &str, tails_file: &str, max_creds: u32)
+pub fn generate_rev_reg(issuer_did: &str, cred_def_id: &str, tails_file: &str, max_creds: u32, tag: &str)
                         -> VcxResult<(String, String, String)> {
     if settings::indy_mocks_enabled() { return Ok((REV_REG_ID.to_string(), rev_def_json(), "".to_string())); }
 
@@ -462,7 +619,8 @@ pub fn generate_rev_reg(issuer_did: &str, cred_def_id: &str, tails_file: &str, m
         libindy_create_and_store_revoc_reg(issuer_did,
                                            cred_def_id,
                                            tails_file,
-                                           max_creds)?;
+                                           max_creds,
+                                           tag)?;
 
     Ok((rev_reg_id, rev_reg_def_json, rev_reg_entry_json))
 }
@@ -539,6 +697,12 @@ pub fn revoke_credential(tails_file: &str, rev_reg_id: &str, cred_rev_id: &str)
     let submitter_did = settings::get_config_value(settings::CONFIG_INSTITUTION_DID)?;
 
     let delta = libindy_issuer_revoke_credential(tails_file, rev_reg_id, cred_rev_id)?;
+
+    // delta is published later by the revocation publisher if it is started
+    if ::revocation_publisher::add_revocation(rev_reg_id, &delta)? {
+        return Ok((None, delta));
+    }
+
     let (payment, _) = publish_rev_reg_delta(&submitter_did, rev_reg_id, &delta)?;
 
     Ok((payment, delta))
@@ -565,6 +729,18 @@ pub fn generate_nonce() -> VcxResult<String> {
         .map_err(VcxError::from)
 }
 
+pub fn libindy_verifier_create_proof_request_from_presentation_definition(presentation_definition_json: &str) -> VcxResult<String> {
+    anoncreds::verifier_create_proof_request_from_presentation_definition(presentation_definition_json)
+        .wait()
+        .map_err(VcxError::from)
+}
+
+pub fn libindy_verifier_create_presentation_submission(presentation_definition_json: &str, proof_json: &str) -> VcxResult<String> {
+    anoncreds::verifier_create_presentation_submission(presentation_definition_json, proof_json)
+        .wait()
+        .map_err(VcxError::from)
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -964,7 +1140,7 @@ pub mod tests {
         // revoc_reg_def will fail in libindy because cred_Def doesn't have revocation keys
         let (_, _, cred_def_id, _, _, _) = ::utils::libindy::anoncreds::tests::create_and_store_credential_def(::utils::constants::DEFAULT_SCHEMA_ATTRS, false);
         let did = settings::get_config_value(settings::CONFIG_INSTITUTION_DID).unwrap();
-        let rc = generate_rev_reg(&did, &cred_def_id, get_temp_dir_path("path.txt").to_str().unwrap(), 2);
+        let rc = generate_rev_reg(&did, &cred_def_id, get_temp_dir_path("path.txt").to_str().unwrap(), 2, DEFAULT_REV_REG_TAG);
 
         assert_eq!(rc.unwrap_err().kind(), VcxErrorKind::LibindyInvalidStructure);
     }
@@ -980,7 +1156,7 @@ pub mod tests {
 
         let (cred_def_id, cred_def_json) = generate_cred_def(&did, &schema_json, "tag_1", None, Some(true)).unwrap();
         publish_cred_def(&did, &cred_def_json).unwrap();
-        let (rev_reg_def_id, rev_reg_def_json, rev_reg_entry_json) = generate_rev_reg(&did, &cred_def_id, "tails.txt", 2).unwrap();
+        let (rev_reg_def_id, rev_reg_def_json, rev_reg_entry_json) = generate_rev_reg(&did, &cred_def_id, "tails.txt", 2, DEFAULT_REV_REG_TAG).unwrap();
         publish_rev_reg_def(&did, &rev_reg_def_json).unwrap();
         publish_rev_reg_delta(&did, &rev_reg_def_id, &rev_reg_entry_json).unwrap();
     }
@@ -1072,4 +1248,21 @@ pub mod tests {
         assert!(payment.is_some());
         assert_ne!(first_rev_reg_delta, second_rev_reg_delta);
     }
+
+    #[test]
+    fn test_next_rev_reg_tag() {
+        let _setup = SetupDefaults::init();
+
+        assert_eq!(_next_rev_reg_tag("2hoqvcwupRTUNkXn6ArYzs:4:2hoqvcwupRTUNkXn6ArYzs:3:CL:1766:tag1:CL_ACCUM:tag1"), "tag2");
+        assert_eq!(_next_rev_reg_tag("2hoqvcwupRTUNkXn6ArYzs:4:2hoqvcwupRTUNkXn6ArYzs:3:CL:1766:tag1:CL_ACCUM:tag9"), "tag10");
+        assert_eq!(_next_rev_reg_tag("2hoqvcwupRTUNkXn6ArYzs:4:2hoqvcwupRTUNkXn6ArYzs:3:CL:1766:tag1:CL_ACCUM:custom"), "custom_2");
+    }
+
+    #[test]
+    fn test_issuer_create_credential_rotating_keeps_rev_reg_id() {
+        let _setup = SetupMocks::init();
+
+        let (_, _, _, rev_reg_id) = issuer_create_credential_rotating(LIBINDY_CRED_OFFER, "{}", "{}", Some(REV_REG_ID.to_string()), None).unwrap();
+        assert_eq!(Some(REV_REG_ID.to_string()), rev_reg_id);
+    }
 }
@@ -52,6 +52,16 @@ pub fn get_rev_reg_cache(rev_reg_id: &str) -> RevRegCache {
     }
 }
 
+///
+/// Returns wallet record id (`{"type": string, "id": string}`) of the rev reg cache.
+///
+/// # Arguments
+/// `rev_reg_id`: revocation registry id
+///
+pub fn rev_reg_cache_record_id(rev_reg_id: &str) -> serde_json::Value {
+    json!({"type": CACHE_TYPE, "id": format!("{}{}", REV_REG_CACHE_PREFIX, rev_reg_id)})
+}
+
 ///
 /// Saves rev reg cache.
 /// Errors are silently ignored.
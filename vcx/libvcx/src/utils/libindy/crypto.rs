@@ -80,4 +80,15 @@ pub fn create_key(seed: Option<&str>) -> VcxResult<String> {
     crypto::create_key(::utils::libindy::wallet::get_wallet_handle(), Some(&key_json))
         .wait()
         .map_err(VcxError::from)
-}
\ No newline at end of file
+}
+pub fn ed25519_to_x25519(verkey: &str) -> VcxResult<String> {
+    crypto::ed25519_to_x25519(verkey)
+        .wait()
+        .map_err(VcxError::from)
+}
+
+pub fn x25519_diffie_hellman(my_vk: &str, their_x25519_key: &str) -> VcxResult<Vec<u8>> {
+    crypto::x25519_diffie_hellman(::utils::libindy::wallet::get_wallet_handle(), my_vk, their_x25519_key)
+        .wait()
+        .map_err(VcxError::from)
+}
@@ -8,23 +8,26 @@ use error::prelude::{VcxError, VcxErrorKind};
 
 impl From<IndyError> for VcxError {
     fn from(error: IndyError) -> Self {
-        match error.error_code as u32 {
-            100..=111 => VcxError::from_msg(VcxErrorKind::InvalidLibindyParam, error.message),
-            113 => VcxError::from_msg(VcxErrorKind::LibindyInvalidStructure, error.message),
-            114 => VcxError::from_msg(VcxErrorKind::IOError, error.message),
-            200 => VcxError::from_msg(VcxErrorKind::InvalidWalletHandle, error.message),
-            203 => VcxError::from_msg(VcxErrorKind::DuplicationWallet, error.message),
-            204 => VcxError::from_msg(VcxErrorKind::WalletNotFound, error.message),
-            206 => VcxError::from_msg(VcxErrorKind::WalletAlreadyOpen, error.message),
-            212 => VcxError::from_msg(VcxErrorKind::WalletRecordNotFound, error.message),
-            213 => VcxError::from_msg(VcxErrorKind::DuplicationWalletRecord, error.message),
-            306 => VcxError::from_msg(VcxErrorKind::CreatePoolConfig, error.message),
-            404 => VcxError::from_msg(VcxErrorKind::DuplicationMasterSecret, error.message),
-            407 => VcxError::from_msg(VcxErrorKind::CredDefAlreadyCreated, error.message),
-            600 => VcxError::from_msg(VcxErrorKind::DuplicationDid, error.message),
-            702 => VcxError::from_msg(VcxErrorKind::InsufficientTokenAmount, error.message),
-            error_code => VcxError::from_msg(VcxErrorKind::LibndyError(error_code), error.message)
-        }
+        let kind = match error.error_code as u32 {
+            100..=111 => VcxErrorKind::InvalidLibindyParam,
+            113 => VcxErrorKind::LibindyInvalidStructure,
+            114 => VcxErrorKind::IOError,
+            200 => VcxErrorKind::InvalidWalletHandle,
+            203 => VcxErrorKind::DuplicationWallet,
+            204 => VcxErrorKind::WalletNotFound,
+            206 => VcxErrorKind::WalletAlreadyOpen,
+            212 => VcxErrorKind::WalletRecordNotFound,
+            213 => VcxErrorKind::DuplicationWalletRecord,
+            306 => VcxErrorKind::CreatePoolConfig,
+            404 => VcxErrorKind::DuplicationMasterSecret,
+            407 => VcxErrorKind::CredDefAlreadyCreated,
+            600 => VcxErrorKind::DuplicationDid,
+            702 => VcxErrorKind::InsufficientTokenAmount,
+            error_code => VcxErrorKind::LibndyError(error_code)
+        };
+
+        VcxError::from_msg(kind, error.message)
+            .with_vendor_code(error.error_code as i32)
     }
 }
 
@@ -80,24 +83,34 @@ pub mod tests {
             error_code: ErrorCode::CommonInvalidParam1,
             message: String::new(),
             indy_backtrace: None,
+            indy_context: Vec::new(),
+            vendor_code: None,
         };
         let err107: IndyError = IndyError {
             error_code: ErrorCode::CommonInvalidParam8,
             message: String::new(),
             indy_backtrace: None,
+            indy_context: Vec::new(),
+            vendor_code: None,
         };
         let err111: IndyError = IndyError {
             error_code: ErrorCode::CommonInvalidParam12,
             message: String::new(),
             indy_backtrace: None,
+            indy_context: Vec::new(),
+            vendor_code: None,
         };
         let err112: IndyError = IndyError {
             error_code: ErrorCode::CommonInvalidState,
             message: String::new(),
             indy_backtrace: None,
+            indy_context: Vec::new(),
+            vendor_code: None,
         };
 
-        assert_eq!(VcxError::from(err100).kind(), VcxErrorKind::InvalidLibindyParam);
+        let err100 = VcxError::from(err100);
+        assert_eq!(err100.kind(), VcxErrorKind::InvalidLibindyParam);
+        assert_eq!(Some(100), err100.vendor_code());
         assert_eq!(VcxError::from(err107).kind(), VcxErrorKind::InvalidLibindyParam);
         assert_eq!(VcxError::from(err111).kind(), VcxErrorKind::InvalidLibindyParam);
         // Test that RC 112 falls out of the range 100...112
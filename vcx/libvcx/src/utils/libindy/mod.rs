@@ -79,7 +79,7 @@ pub mod tests {
             let wallet_handle = indy::wallet::open_wallet(&wallet_config, WALLET_CREDENTIALS).wait().unwrap();
             let key = indy::crypto::create_key(wallet_handle, Some(&key_config)).wait().unwrap();
 
-            wallet::set_wallet_handle(wallet_handle);
+            wallet::set_wallet_handle(wallet_handle).unwrap();
 
             Setup { name, wallet_config, wallet_handle, key }
         }
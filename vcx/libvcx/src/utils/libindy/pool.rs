@@ -1,27 +1,22 @@
 use futures::Future;
 use indy::{pool, ErrorCode};
 
-use std::sync::RwLock;
-
+use context;
 use settings;
 use error::prelude::*;
 
-lazy_static! {
-    static ref POOL_HANDLE: RwLock<Option<i32>> = RwLock::new(None);
-}
-
-pub fn set_pool_handle(handle: Option<i32>) {
-    let mut h = POOL_HANDLE.write().unwrap();
-    *h = handle;
+// Pool handle of the current context
+pub fn set_pool_handle(handle: Option<i32>) -> VcxResult<()> {
+    context::set_pool_handle(handle)
 }
 
 pub fn get_pool_handle() -> VcxResult<i32> {
-    POOL_HANDLE.read()
-        .or(Err(VcxError::from_msg(VcxErrorKind::NoPoolOpen, "There is no pool opened")))?
+    context::get_pool_handle()
         .ok_or(VcxError::from_msg(VcxErrorKind::NoPoolOpen, "There is no pool opened"))
 }
 
-pub fn reset_pool_handle() { set_pool_handle(None); }
+// released context has no pool to reset
+pub fn reset_pool_handle() { set_pool_handle(None).ok(); }
 
 pub fn set_protocol_version() -> VcxResult<()> {
     pool::set_protocol_version(settings::get_protocol_version())
@@ -79,7 +74,13 @@ pub fn open_pool_ledger(pool_name: &str, config: Option<&str>) -> VcxResult<u32>
                 }
             })?;
 
-    set_pool_handle(Some(handle));
+    // the context may be released while the pool was opening
+    set_pool_handle(Some(handle))
+        .map_err(|err| {
+            pool::close_pool_ledger(handle).wait().ok();
+            err
+        })?;
+
     Ok(handle as u32)
 }
 
@@ -122,7 +123,7 @@ pub fn delete(pool_name: &str) -> VcxResult<()> {
     trace!("delete >>> pool_name: {}", pool_name);
 
     if settings::indy_mocks_enabled() {
-        set_pool_handle(None);
+        reset_pool_handle();
         return Ok(());
     }
 
@@ -1,6 +1,7 @@
 use futures::Future;
 use indy::{wallet, ErrorCode};
 
+use context;
 use settings;
 
 use error::prelude::*;
@@ -8,7 +9,7 @@ use indy::{WalletHandle, INVALID_WALLET_HANDLE};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WalletRecord {
-    id: Option<String>,
+    pub id: Option<String>,
     #[serde(rename = "type")]
     record_type: Option<String>,
     pub value: Option<String>,
@@ -49,16 +50,16 @@ impl RestoreWalletConfigs {
     }
 }
 
-pub static mut WALLET_HANDLE: WalletHandle = INVALID_WALLET_HANDLE;
-
-pub fn set_wallet_handle(handle: WalletHandle) -> WalletHandle {
-    unsafe { WALLET_HANDLE = handle; }
-    unsafe { WALLET_HANDLE }
+// Wallet handle of the current context
+pub fn set_wallet_handle(handle: WalletHandle) -> VcxResult<WalletHandle> {
+    context::set_wallet_handle(handle)?;
+    Ok(handle)
 }
 
-pub fn get_wallet_handle() -> WalletHandle { unsafe { WALLET_HANDLE } }
+pub fn get_wallet_handle() -> WalletHandle { context::get_wallet_handle() }
 
-pub fn reset_wallet_handle() { set_wallet_handle(INVALID_WALLET_HANDLE); }
+// released context has no wallet to reset
+pub fn reset_wallet_handle() { set_wallet_handle(INVALID_WALLET_HANDLE).ok(); }
 
 pub fn create_wallet(wallet_name: &str, wallet_type: Option<&str>, storage_config: Option<&str>, storage_creds: Option<&str>) -> VcxResult<()> {
     trace!("creating wallet: {}", wallet_name);
@@ -87,7 +88,7 @@ pub fn create_wallet(wallet_name: &str, wallet_type: Option<&str>, storage_confi
 pub fn open_wallet(wallet_name: &str, wallet_type: Option<&str>, storage_config: Option<&str>, storage_creds: Option<&str>) -> VcxResult<WalletHandle> {
     trace!("open_wallet >>> wallet_name: {}", wallet_name);
     if settings::indy_mocks_enabled() {
-        return Ok(set_wallet_handle(WalletHandle(1)));
+        return set_wallet_handle(WalletHandle(1));
     }
 
     let config = settings::get_wallet_config(wallet_name, wallet_type, storage_config);
@@ -114,14 +115,17 @@ pub fn open_wallet(wallet_name: &str, wallet_type: Option<&str>, storage_config:
                 }
             })?;
 
-    set_wallet_handle(handle);
-
-    Ok(handle)
+    // the context may be released while the wallet was opening
+    set_wallet_handle(handle)
+        .map_err(|err| {
+            wallet::close_wallet(handle).wait().ok();
+            err
+        })
 }
 
 pub fn init_wallet(wallet_name: &str, wallet_type: Option<&str>, storage_config: Option<&str>, storage_creds: Option<&str>) -> VcxResult<WalletHandle> {
     if settings::indy_mocks_enabled() {
-        return Ok(set_wallet_handle(WalletHandle(1)));
+        return set_wallet_handle(WalletHandle(1));
     }
 
     create_wallet(wallet_name, wallet_type, storage_config, storage_creds)?;
@@ -132,7 +136,7 @@ pub fn close_wallet() -> VcxResult<()> {
     trace!("close_wallet >>>");
 
     if settings::indy_mocks_enabled() {
-        set_wallet_handle(INVALID_WALLET_HANDLE);
+        reset_wallet_handle();
         return Ok(());
     }
 
@@ -214,6 +218,76 @@ pub fn update_record_value(xtype: &str, id: &str, value: &str) -> VcxResult<()>
         .map_err(VcxError::from)
 }
 
+pub fn update_record_tags(xtype: &str, id: &str, tags: &str) -> VcxResult<()> {
+    trace!("update_record_tags >>> xtype: {}, id: {}, tags: {}", secret!(&xtype), secret!(&id), secret!(&tags));
+
+    if settings::indy_mocks_enabled() { return Ok(()); }
+
+    wallet::update_wallet_record_tags(get_wallet_handle(), xtype, id, tags)
+        .wait()
+        .map_err(VcxError::from)
+}
+
+// Fetches up to `count` records matching the WQL query in a single batch
+pub fn search_records(xtype: &str, query: &str, options: &str, count: usize) -> VcxResult<String> {
+    trace!("search_records >>> xtype: {}, query: {}, options: {}, count: {}", secret!(&xtype), secret!(&query), options, count);
+
+    if settings::indy_mocks_enabled() {
+        return Ok(r#"{"totalCount":0,"records":null}"#.to_string());
+    }
+
+    let search_handle = wallet::open_wallet_search(get_wallet_handle(), xtype, query, options)
+        .wait()
+        .map_err(VcxError::from)?;
+
+    let records = wallet::fetch_wallet_search_next_records(get_wallet_handle(), search_handle, count)
+        .wait()
+        .map_err(VcxError::from);
+
+    wallet::close_wallet_search(search_handle)
+        .wait()
+        .map_err(VcxError::from)?;
+
+    records
+}
+
+#[derive(Deserialize)]
+struct SearchBatch {
+    records: Option<Vec<WalletRecord>>
+}
+
+// Fetches all records matching the WQL query in batches of `batch_size`
+pub fn search_all_records(xtype: &str, query: &str, options: &str, batch_size: usize) -> VcxResult<Vec<WalletRecord>> {
+    trace!("search_all_records >>> xtype: {}, query: {}, options: {}, batch_size: {}", secret!(&xtype), secret!(&query), options, batch_size);
+
+    if settings::indy_mocks_enabled() { return Ok(Vec::new()); }
+
+    let search_handle = wallet::open_wallet_search(get_wallet_handle(), xtype, query, options)
+        .wait()
+        .map_err(VcxError::from)?;
+
+    let mut result = Vec::new();
+    let records = loop {
+        let batch = wallet::fetch_wallet_search_next_records(get_wallet_handle(), search_handle, batch_size)
+            .wait()
+            .map_err(VcxError::from)
+            .and_then(|batch| ::serde_json::from_str::<SearchBatch>(&batch)
+                .to_vcx(VcxErrorKind::InvalidJson, "Cannot deserialize wallet search records"));
+
+        match batch {
+            Ok(SearchBatch { records: Some(records) }) if !records.is_empty() => result.extend(records),
+            Ok(_) => break Ok(result),
+            Err(err) => break Err(err)
+        }
+    };
+
+    wallet::close_wallet_search(search_handle)
+        .wait()
+        .map_err(VcxError::from)?;
+
+    records
+}
+
 pub fn export(wallet_handle: WalletHandle, path: &str, backup_key: &str) -> VcxResult<()> {
     trace!("export >>> wallet_handle: {:?}, path: {:?}, backup_key: ****", wallet_handle, path);
 
@@ -589,4 +663,21 @@ pub mod tests {
         assert_eq!(initial_record, expected_initial_record);
         assert_eq!(changed_record, expected_updated_record);
     }
+
+    #[test]
+    fn test_search_all_records_fetches_every_batch() {
+        let _setup = SetupLibraryWallet::init();
+
+        let record_type = "Type";
+        for id in 0..5 {
+            add_record(record_type, &id.to_string(), "value", None).unwrap();
+        }
+        add_record("OtherType", "other", "value", None).unwrap();
+
+        let options = json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+        let records = search_all_records(record_type, "{}", &options, 2).unwrap();
+
+        assert_eq!(5, records.len());
+        assert!(records.iter().all(|record| record.value == Some(String::from("value"))));
+    }
 }
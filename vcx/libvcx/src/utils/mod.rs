@@ -38,6 +38,7 @@ macro_rules! map (
 
 pub mod error;
 pub mod httpclient;
+pub mod transport;
 pub mod constants;
 pub mod timeout;
 pub mod openssl;
@@ -50,6 +51,7 @@ pub mod qualifier;
 pub mod file;
 pub mod option_util;
 pub mod agent_info;
+pub mod serialization;
 
 #[cfg(test)]
 pub mod plugins;
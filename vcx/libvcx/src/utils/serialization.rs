@@ -0,0 +1,226 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use error::prelude::*;
+use utils::libindy::wallet;
+
+// Revision of the layout of serialized objects, kept in the `format` field next to `version` and `data`.
+// Bump it together with adding the upgrade from the previous revision into `_upgrade_step`.
+pub const SERIALIZATION_FORMAT: u64 = 1;
+
+// Objects serialized before the `format` field was introduced
+const LEGACY_FORMAT: u64 = 0;
+const FORMAT_FIELD: &str = "format";
+
+const MIGRATION_BATCH_SIZE: usize = 100;
+const SEARCH_OPTIONS: &str = r#"{"retrieveRecords":true,"retrieveTotalCount":false,"retrieveType":false,"retrieveValue":true,"retrieveTags":false}"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectType {
+    Connection,
+    Credential,
+    IssuerCredential,
+    Proof,
+    DisclosedProof,
+}
+
+impl ObjectType {
+    pub fn from_str(object_type: &str) -> VcxResult<ObjectType> {
+        ::serde_json::from_value(json!(object_type))
+            .map_err(|_| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Unknown object type: {}", object_type)))
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ObjectType::Connection => "Connection",
+            ObjectType::Credential => "Credential",
+            ObjectType::IssuerCredential => "IssuerCredential",
+            ObjectType::Proof => "Proof",
+            ObjectType::DisclosedProof => "DisclosedProof",
+        }
+    }
+}
+
+pub fn serialize<T: Serialize>(object_type: ObjectType, object: &T) -> VcxResult<String> {
+    let mut value = ::serde_json::to_value(object)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot serialize {}: {:?}", object_type.name(), err)))?;
+
+    _set_format(object_type, &mut value, SERIALIZATION_FORMAT)?;
+
+    Ok(value.to_string())
+}
+
+// Objects serialized by previous VCX versions are upgraded to the current format before deserialization
+pub fn deserialize<T: DeserializeOwned>(object_type: ObjectType, data: &str) -> VcxResult<T> {
+    let value = _upgrade(object_type, _parse(object_type, data)?)?;
+
+    ::serde_json::from_value(value)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize {}: {:?}", object_type.name(), err)))
+}
+
+// Returns the object rewritten in the current format or None if it is up to date
+pub fn upgrade(object_type: ObjectType, data: &str) -> VcxResult<Option<String>> {
+    let value = _parse(object_type, data)?;
+
+    if _format(object_type, &value)? == SERIALIZATION_FORMAT {
+        return Ok(None);
+    }
+
+    Ok(Some(_upgrade(object_type, value)?.to_string()))
+}
+
+// Rewrites objects the application keeps serialized in values of wallet records of the type.
+// Records which cannot be upgraded are left as is. Returns the number of rewritten records.
+pub fn migrate_wallet_records(xtype: &str, object_type: ObjectType) -> VcxResult<u32> {
+    trace!("serialization::migrate_wallet_records >>> xtype: {}, object_type: {:?}", secret!(xtype), object_type);
+
+    let records = wallet::search_all_records(xtype, "{}", SEARCH_OPTIONS, MIGRATION_BATCH_SIZE)?;
+
+    let mut migrated = 0;
+    for record in records {
+        let (id, value) = match (record.id, record.value) {
+            (Some(id), Some(value)) => (id, value),
+            _ => continue
+        };
+
+        match upgrade(object_type, &value) {
+            Ok(Some(value)) => {
+                wallet::update_record_value(xtype, &id, &value)?;
+                migrated += 1;
+            }
+            Ok(None) => {}
+            Err(err) => warn!("serialization::migrate_wallet_records: skipping record {}: {}", secret!(&id), err)
+        }
+    }
+
+    Ok(migrated)
+}
+
+fn _parse(object_type: ObjectType, data: &str) -> VcxResult<Value> {
+    ::serde_json::from_str(data)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize {}: {:?}", object_type.name(), err)))
+}
+
+fn _format(object_type: ObjectType, value: &Value) -> VcxResult<u64> {
+    match value.get(FORMAT_FIELD) {
+        None => Ok(LEGACY_FORMAT),
+        Some(format) => format.as_u64()
+            .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Invalid serialization format of {}: {}", object_type.name(), format)))
+    }
+}
+
+fn _set_format(object_type: ObjectType, value: &mut Value, format: u64) -> VcxResult<()> {
+    match value.as_object_mut() {
+        Some(object) => {
+            object.insert(FORMAT_FIELD.to_string(), json!(format));
+            Ok(())
+        }
+        None => Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Serialized {} must be a JSON object", object_type.name())))
+    }
+}
+
+fn _upgrade(object_type: ObjectType, mut value: Value) -> VcxResult<Value> {
+    let format = _format(object_type, &value)?;
+
+    if format > SERIALIZATION_FORMAT {
+        return Err(VcxError::from_msg(VcxErrorKind::SerializationError,
+                                      format!("{} is serialized in format {} which is newer than supported {}", object_type.name(), format, SERIALIZATION_FORMAT)));
+    }
+
+    for from in format..SERIALIZATION_FORMAT {
+        _upgrade_step(object_type, from, &mut value)?;
+    }
+
+    _set_format(object_type, &mut value, SERIALIZATION_FORMAT)?;
+    Ok(value)
+}
+
+fn _upgrade_step(object_type: ObjectType, from: u64, _value: &mut Value) -> VcxResult<()> {
+    trace!("serialization::_upgrade_step >>> object_type: {:?}, from: {}", object_type, from);
+
+    match from {
+        // Layout is the same, only `format` field is missing
+        LEGACY_FORMAT => Ok(()),
+        _ => Err(VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot upgrade {} from format {}", object_type.name(), from)))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use utils::devsetup::{SetupDefaults, SetupLibraryWallet};
+    use utils::libindy::wallet::{add_record, get_record};
+
+    fn _legacy_object() -> String {
+        json!({"version": "1.0", "data": {"source_id": "test"}}).to_string()
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "version", content = "data")]
+    enum TestObject {
+        #[serde(rename = "1.0")]
+        V1 { source_id: String },
+    }
+
+    #[test]
+    fn test_serialize_sets_format() {
+        let _setup = SetupDefaults::init();
+
+        let data = serialize(ObjectType::Connection, &TestObject::V1 { source_id: String::from("test") }).unwrap();
+        let value: Value = ::serde_json::from_str(&data).unwrap();
+
+        assert_eq!(json!(SERIALIZATION_FORMAT), value[FORMAT_FIELD]);
+        assert_eq!(json!("1.0"), value["version"]);
+    }
+
+    #[test]
+    fn test_deserialize_upgrades_legacy_object() {
+        let _setup = SetupDefaults::init();
+
+        let object: TestObject = deserialize(ObjectType::Connection, &_legacy_object()).unwrap();
+        assert_eq!(TestObject::V1 { source_id: String::from("test") }, object);
+    }
+
+    #[test]
+    fn test_deserialize_fails_for_newer_format() {
+        let _setup = SetupDefaults::init();
+
+        let data = json!({"version": "1.0", "format": SERIALIZATION_FORMAT + 1, "data": {"source_id": "test"}}).to_string();
+        let err = deserialize::<TestObject>(ObjectType::Connection, &data).unwrap_err();
+        assert_eq!(VcxErrorKind::SerializationError, err.kind());
+    }
+
+    #[test]
+    fn test_upgrade_works() {
+        let _setup = SetupDefaults::init();
+
+        let upgraded = upgrade(ObjectType::Credential, &_legacy_object()).unwrap().unwrap();
+        assert_eq!(None, upgrade(ObjectType::Credential, &upgraded).unwrap());
+    }
+
+    #[test]
+    fn test_object_type_from_str_works() {
+        assert_eq!(ObjectType::IssuerCredential, ObjectType::from_str("issuer_credential").unwrap());
+        assert_eq!(VcxErrorKind::InvalidOption, ObjectType::from_str("schema").unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_migrate_wallet_records_works() {
+        let _setup = SetupLibraryWallet::init();
+
+        let options = json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+        let upgraded = serialize(ObjectType::Proof, &TestObject::V1 { source_id: String::from("test") }).unwrap();
+
+        add_record("VcxProof", "legacy", &_legacy_object(), None).unwrap();
+        add_record("VcxProof", "current", &upgraded, None).unwrap();
+        add_record("VcxProof", "invalid", "not a json", None).unwrap();
+
+        assert_eq!(1, migrate_wallet_records("VcxProof", ObjectType::Proof).unwrap());
+        assert_eq!(0, migrate_wallet_records("VcxProof", ObjectType::Proof).unwrap());
+
+        let record: Value = ::serde_json::from_str(&get_record("VcxProof", "legacy", &options).unwrap()).unwrap();
+        assert_eq!(None, upgrade(ObjectType::Proof, record["value"].as_str().unwrap()).unwrap());
+    }
+}
@@ -34,9 +34,13 @@ pub fn init() {
     }
 }
 
+// The task is executed in the context of the caller
 pub fn spawn<F>(future: F)
 where
     F: FnOnce() -> Result<(), ()> + Send + 'static {
+        let context = ::context::current();
+        let future = move || ::context::run_in(context, future);
+
         let handle;
         unsafe { handle = TP_HANDLE; }
         if ::settings::get_threadpool_size() == 0 || handle == 0{
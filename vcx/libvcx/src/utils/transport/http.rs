@@ -0,0 +1,28 @@
+use error::prelude::*;
+use utils::httpclient;
+use utils::transport::Transport;
+use v3::messages::connection::did_doc::TlsConstraints;
+
+pub struct HttpTransport {
+    endpoint: String,
+    tls: Option<TlsConstraints>,
+}
+
+impl HttpTransport {
+    pub fn new(endpoint: &str, tls: Option<TlsConstraints>) -> HttpTransport {
+        HttpTransport { endpoint: endpoint.to_string(), tls }
+    }
+}
+
+impl Transport for HttpTransport {
+    // Response comes back in the body of the POST request
+    fn send(&self, message: &[u8]) -> VcxResult<Option<Vec<u8>>> {
+        let response = httpclient::post_message_with_tls(&message.to_vec(), &self.endpoint, self.tls.as_ref())?;
+
+        if response.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(response))
+    }
+}
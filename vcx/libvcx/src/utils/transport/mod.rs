@@ -0,0 +1,110 @@
+pub mod http;
+pub mod ws;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use error::prelude::*;
+use utils::uuid;
+use v3::messages::connection::did_doc::TlsConstraints;
+
+use self::http::HttpTransport;
+use self::ws::WsTransport;
+
+/*
+    Delivery of packed messages to the remote endpoint. Transport is selected by the endpoint scheme.
+    Messages the remote side sends back over the same connection (`~transport.return_route`)
+    are kept by session (our pairwise verkey) until they are handled.
+*/
+
+pub trait Transport {
+    // Returns the message sent back within the same exchange if there is one
+    fn send(&self, message: &[u8]) -> VcxResult<Option<Vec<u8>>>;
+
+    // Same as `send`, but waits for the response if the transport delivers it asynchronously
+    fn send_and_wait(&self, message: &[u8]) -> VcxResult<Option<Vec<u8>>> {
+        self.send(message)
+    }
+}
+
+lazy_static! {
+    static ref RECEIVED: Mutex<HashMap<String, HashMap<String, Vec<u8>>>> = Mutex::new(HashMap::new());
+}
+
+// Messages are sent without session if no response is expected, so a persistent connection isn't kept for them
+pub fn for_endpoint(endpoint: &str, tls: Option<TlsConstraints>, session: Option<&str>) -> Box<dyn Transport> {
+    if is_websocket(endpoint) {
+        Box::new(WsTransport::new(endpoint, tls, session))
+    } else {
+        Box::new(HttpTransport::new(endpoint, tls))
+    }
+}
+
+// Remote side can reach us over WebSocket only through the connection we opened
+pub fn is_websocket(endpoint: &str) -> bool {
+    endpoint.starts_with("ws://") || endpoint.starts_with("wss://")
+}
+
+pub fn store_received(session: &str, message: Vec<u8>) -> String {
+    let uid = uuid::uuid();
+
+    RECEIVED.lock().unwrap()
+        .entry(session.to_string())
+        .or_insert_with(HashMap::new)
+        .insert(uid.clone(), message);
+
+    uid
+}
+
+// Includes messages pushed over open WebSocket since the last call
+pub fn get_received(session: &str) -> HashMap<String, Vec<u8>> {
+    for message in ws::receive(session) {
+        store_received(session, message);
+    }
+
+    RECEIVED.lock().unwrap()
+        .get(session)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn remove_received(session: &str, uid: &str) -> bool {
+    RECEIVED.lock().unwrap()
+        .get_mut(session)
+        .and_then(|messages| messages.remove(uid))
+        .is_some()
+}
+
+pub fn close(session: &str) {
+    ws::close(session);
+    RECEIVED.lock().unwrap().remove(session);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_websocket_works() {
+        assert!(is_websocket("ws://localhost:8080"));
+        assert!(is_websocket("wss://agent.example.com/ws"));
+        assert!(!is_websocket("https://agent.example.com"));
+        assert!(!is_websocket("http://localhost:8080/ws"));
+    }
+
+    #[test]
+    fn test_received_messages_works() {
+        let session = uuid::uuid();
+
+        let uid = store_received(&session, vec![1, 2, 3]);
+        assert_eq!(map!(uid.clone() => vec![1, 2, 3]), get_received(&session));
+
+        assert!(remove_received(&session, &uid));
+        assert!(!remove_received(&session, &uid));
+        assert!(get_received(&session).is_empty());
+
+        store_received(&session, vec![1, 2, 3]);
+        close(&session);
+        assert!(get_received(&session).is_empty());
+    }
+}
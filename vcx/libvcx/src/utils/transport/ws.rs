@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use openssl::ssl::SslStream;
+use tungstenite::{self, Message, WebSocket};
+use url::Url;
+
+use error::prelude::*;
+use settings;
+use utils::httpclient::{self, AgencyMock};
+use utils::timeout::TimeoutUtils;
+use utils::transport::Transport;
+use v3::messages::connection::did_doc::TlsConstraints;
+
+lazy_static! {
+    // Sockets stay open so the remote side can push messages over them
+    static ref SOCKETS: Mutex<HashMap<String, Arc<Mutex<Socket>>>> = Mutex::new(HashMap::new());
+}
+
+pub struct WsTransport {
+    endpoint: String,
+    tls: Option<TlsConstraints>,
+    session: Option<String>,
+}
+
+struct Socket {
+    endpoint: String,
+    websocket: WebSocket<WsStream>,
+}
+
+pub enum WsStream {
+    Plain(TcpStream),
+    Tls(SslStream<TcpStream>),
+}
+
+impl WsStream {
+    fn tcp(&self) -> &TcpStream {
+        match self {
+            WsStream::Plain(stream) => stream,
+            WsStream::Tls(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl Read for WsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            WsStream::Plain(stream) => stream.read(buf),
+            WsStream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for WsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            WsStream::Plain(stream) => stream.write(buf),
+            WsStream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            WsStream::Plain(stream) => stream.flush(),
+            WsStream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+impl WsTransport {
+    pub fn new(endpoint: &str, tls: Option<TlsConstraints>, session: Option<&str>) -> WsTransport {
+        WsTransport { endpoint: endpoint.to_string(), tls, session: session.map(String::from) }
+    }
+
+    fn exchange(&self, message: &[u8], timeout: Option<Duration>) -> VcxResult<Option<Vec<u8>>> {
+        if settings::agency_mocks_enabled() {
+            return AgencyMock::get_response()
+                .map(|response| if response.is_empty() { None } else { Some(response) });
+        }
+
+        let session = match self.session {
+            Some(ref session) => session,
+            None => {
+                let mut socket = Socket::connect(&self.endpoint, self.tls.as_ref())?;
+                let response = socket.exchange(message, timeout);
+                socket.close();
+                return response;
+            }
+        };
+
+        let socket = self.session_socket(session)?;
+        let mut socket = socket.lock().unwrap();
+
+        match socket.exchange(message, timeout) {
+            Ok(response) => Ok(response),
+            // The remote side may have closed the socket while it was idle
+            Err(err) => {
+                warn!("WebSocket exchange with {:?} failed: {}. Reconnecting", self.endpoint, err);
+                *socket = Socket::connect(&self.endpoint, self.tls.as_ref())?;
+                socket.exchange(message, timeout)
+            }
+        }
+    }
+
+    fn session_socket(&self, session: &str) -> VcxResult<Arc<Mutex<Socket>>> {
+        let mut sockets = SOCKETS.lock().unwrap();
+
+        if let Some(socket) = sockets.get(session) {
+            if socket.lock().unwrap().endpoint == self.endpoint {
+                return Ok(socket.clone());
+            }
+        }
+
+        let socket = Arc::new(Mutex::new(Socket::connect(&self.endpoint, self.tls.as_ref())?));
+
+        if let Some(previous) = sockets.insert(session.to_string(), socket.clone()) {
+            previous.lock().unwrap().close();
+        }
+
+        Ok(socket)
+    }
+}
+
+impl Transport for WsTransport {
+    fn send(&self, message: &[u8]) -> VcxResult<Option<Vec<u8>>> {
+        self.exchange(message, None)
+    }
+
+    fn send_and_wait(&self, message: &[u8]) -> VcxResult<Option<Vec<u8>>> {
+        self.exchange(message, Some(TimeoutUtils::medium_timeout()))
+    }
+}
+
+impl Socket {
+    fn connect(endpoint: &str, tls: Option<&TlsConstraints>) -> VcxResult<Socket> {
+        let url = Url::parse(endpoint)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Invalid endpoint {:?}: {:?}", endpoint, err)))?;
+
+        let stream = match url.scheme() {
+            "ws" if tls.is_some() => {
+                return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("TLS constraints are set for not wss endpoint {:?}", endpoint)));
+            }
+            "ws" => {
+                let host = url.host_str()
+                    .ok_or(VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Endpoint without host: {:?}", endpoint)))?;
+                WsStream::Plain(httpclient::tcp_connect(host, url.port_or_known_default().unwrap_or(80))?)
+            }
+            "wss" => WsStream::Tls(httpclient::tls_connect(&url, tls)?),
+            scheme => {
+                return Err(VcxError::from_msg(VcxErrorKind::InvalidUrl, format!("Unsupported WebSocket scheme: {:?}", scheme)));
+            }
+        };
+
+        let (websocket, _) = tungstenite::client(endpoint, stream)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("WebSocket handshake with {:?} failed: {}", endpoint, err)))?;
+
+        debug!("WebSocket connection with {:?} is opened", endpoint);
+
+        Ok(Socket { endpoint: endpoint.to_string(), websocket })
+    }
+
+    fn exchange(&mut self, message: &[u8], timeout: Option<Duration>) -> VcxResult<Option<Vec<u8>>> {
+        self.websocket.write_message(Message::Binary(message.to_vec()))
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Cannot send message over WebSocket: {}", err)))?;
+
+        match timeout {
+            Some(timeout) => self.read(timeout),
+            None => Ok(None)
+        }
+    }
+
+    // Returns `None` if nothing arrives within the timeout
+    fn read(&mut self, timeout: Duration) -> VcxResult<Option<Vec<u8>>> {
+        self.websocket.get_ref().tcp().set_read_timeout(Some(timeout))
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::IOError, format!("Cannot set WebSocket read timeout: {:?}", err)))?;
+
+        loop {
+            match self.websocket.read_message() {
+                Ok(Message::Binary(message)) => return Ok(Some(message)),
+                Ok(Message::Text(message)) => return Ok(Some(message.into_bytes())),
+                // Control frames are answered by the socket itself
+                Ok(_) => continue,
+                Err(tungstenite::Error::Io(ref err)) if err.kind() == io::ErrorKind::WouldBlock || err.kind() == io::ErrorKind::TimedOut => {
+                    return Ok(None);
+                }
+                Err(err) => {
+                    return Err(VcxError::from_msg(VcxErrorKind::PostMessageFailed, format!("Cannot read message from WebSocket: {}", err)));
+                }
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        self.websocket.close(None).ok();
+    }
+}
+
+// Messages pushed over the socket of the session. Closed socket is dropped, so the next message reopens it.
+pub fn receive(session: &str) -> Vec<Vec<u8>> {
+    let socket = match SOCKETS.lock().unwrap().get(session) {
+        Some(socket) => socket.clone(),
+        None => return Vec::new()
+    };
+
+    let mut messages = Vec::new();
+
+    let lost = {
+        let mut socket = socket.lock().unwrap();
+
+        loop {
+            match socket.read(Duration::from_millis(1)) {
+                Ok(Some(message)) => messages.push(message),
+                Ok(None) => break false,
+                Err(err) => {
+                    warn!("WebSocket connection with {:?} is lost: {}", socket.endpoint, err);
+                    break true;
+                }
+            }
+        }
+    };
+
+    if lost {
+        SOCKETS.lock().unwrap().remove(session);
+    }
+
+    messages
+}
+
+pub fn close(session: &str) {
+    if let Some(socket) = SOCKETS.lock().unwrap().remove(session) {
+        socket.lock().unwrap().close();
+    }
+}
@@ -1,21 +1,61 @@
 use messages::update_message::{UIDsByConn, update_messages as update_messages_status};
 use messages::MessageStatusCode;
-use messages::get_message::{Message, get_connection_messages};
+use messages::get_message::{Message, MessageByConnection, get_connection_messages};
 use messages::update_connection::send_delete_connection_message;
 
-use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::connection::did_doc::{DidDoc, TlsConstraints};
 use v3::messages::a2a::A2AMessage;
+use v3::messages::decorators::{Decorators, Transport, ReturnRoute};
 
-use v3::utils::encryption_envelope::EncryptionEnvelope;
+use v3::utils::encryption_envelope::{EncryptionEnvelope, EnvelopeFormat};
+use v3::utils::didcomm_v2;
+use v3::utils::did_method::DidMethod;
+use v3::handlers::mediation::states::MediatorRoute;
+use v3::handlers::connection::received_messages;
 
 use std::collections::HashMap;
+use std::sync::Mutex;
 
-use connection::create_agent_keys;
-use utils::httpclient;
+use messages::cloud_agent;
+use outbound_queue;
+use utils::transport;
 use utils::libindy::signus::create_and_store_my_did;
 use settings;
 use error::prelude::*;
-use settings::ProtocolTypes;
+use settings::{ProtocolTypes, AutoAcceptOffers, AutoPresentProofs};
+
+lazy_static! {
+    // Agency messages downloaded for several connections at once, keyed by pairwise DID.
+    // Used instead of querying the agency for every connection separately.
+    static ref PREFETCHED_MESSAGES: Mutex<HashMap<String, Vec<Message>>> = Default::default();
+}
+
+// Connections of all the pairwise DIDs are considered to have no other messages than downloaded ones
+pub fn set_prefetched_messages(pairwise_dids: &[String], messages: Vec<MessageByConnection>) {
+    let mut prefetched = PREFETCHED_MESSAGES.lock().unwrap();
+
+    for pairwise_did in pairwise_dids {
+        prefetched.insert(pairwise_did.to_string(), Vec::new());
+    }
+
+    for messages in messages {
+        prefetched.insert(messages.pairwise_did, messages.msgs);
+    }
+}
+
+pub fn clear_prefetched_messages() {
+    PREFETCHED_MESSAGES.lock().unwrap().clear();
+}
+
+fn _prefetched_messages(pairwise_did: &str) -> Option<Vec<Message>> {
+    PREFETCHED_MESSAGES.lock().unwrap().get(pairwise_did).cloned()
+}
+
+fn _remove_prefetched_message(pairwise_did: &str, uid: &str) {
+    if let Some(messages) = PREFETCHED_MESSAGES.lock().unwrap().get_mut(pairwise_did) {
+        messages.retain(|message| message.uid != uid);
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentInfo {
@@ -23,6 +63,46 @@ pub struct AgentInfo {
     pub pw_vk: String,
     pub agent_did: String,
     pub agent_vk: String,
+    // TLS constraints for the remote endpoint set locally. Take precedence over ones from remote DIDDoc.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConstraints>,
+    #[serde(default)]
+    pub envelope: EnvelopeFormat,
+    // Set if the recipient key is registered with the mediator instead of the agency
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mediator: Option<MediatorRoute>,
+    // Ask the remote side to reply over the connection messages are sent with. Always used for WebSocket endpoints.
+    #[serde(default)]
+    pub return_route: bool,
+    #[serde(default)]
+    pub auto_accept: AutoAcceptPolicy,
+    // Method of the DID sent to the remote side. Pairwise DID registered with the agency stays unqualified.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "DidMethod::is_unqualified")]
+    pub did_method: DidMethod,
+}
+
+// Auto-accept policies set for the connection. Unset ones are taken from the library settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AutoAcceptPolicy {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_offers: Option<AutoAcceptOffers>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_requests: Option<AutoPresentProofs>,
+}
+
+impl AutoAcceptPolicy {
+    pub fn credential_offers(&self) -> AutoAcceptOffers {
+        self.credential_offers.unwrap_or_else(settings::get_auto_accept_credential_offers)
+    }
+
+    pub fn presentation_requests(&self) -> AutoPresentProofs {
+        self.presentation_requests.unwrap_or_else(settings::get_auto_present_proofs)
+    }
 }
 
 impl Default for AgentInfo {
@@ -32,6 +112,12 @@ impl Default for AgentInfo {
             pw_vk: String::new(),
             agent_did: String::new(),
             agent_vk: String::new(),
+            tls: None,
+            envelope: EnvelopeFormat::default(),
+            mediator: None,
+            return_route: false,
+            auto_accept: AutoAcceptPolicy::default(),
+            did_method: DidMethod::default(),
         }
     }
 }
@@ -43,32 +129,74 @@ impl AgentInfo {
         let method_name = settings::get_config_value(settings::CONFIG_DID_METHOD).ok();
         let (pw_did, pw_vk) = create_and_store_my_did(None, method_name.as_ref().map(String::as_str))?;
 
-        /*
-            Create User Pairwise Agent in old way.
-            Send Messages corresponding to V2 Protocol to avoid code changes on Agency side.
-        */
-        let (agent_did, agent_vk) = create_agent_keys("", &pw_did, &pw_vk)?;
-
-        Ok(AgentInfo { pw_did, pw_vk, agent_did, agent_vk })
+        // DIDComm v2 messages are encrypted to X25519 form of the verkey whatever envelope format we use
+        didcomm_v2::add_key(&pw_vk)?;
+
+        let mediator = ::mediation::add_key(&pw_vk)?;
+
+        let (agent_did, agent_vk) = match mediator {
+            Some(_) => (String::new(), String::new()),
+            None => cloud_agent::get()?.create_pairwise_agent(&pw_did, &pw_vk)?
+        };
+
+        Ok(AgentInfo {
+            pw_did,
+            pw_vk,
+            agent_did,
+            agent_vk,
+            tls: self.tls.clone(),
+            envelope: self.envelope.clone(),
+            mediator,
+            return_route: self.return_route,
+            auto_accept: self.auto_accept.clone(),
+            did_method: self.did_method,
+        })
     }
 
     pub fn agency_endpoint(&self) -> VcxResult<String> {
-        settings::get_config_value(settings::CONFIG_AGENCY_ENDPOINT)
-            .map(|str| format!("{}/agency/msg", str))
+        if let Some(ref mediator) = self.mediator {
+            return Ok(mediator.endpoint.clone());
+        }
+
+        cloud_agent::get()?.endpoint()
     }
 
     pub fn routing_keys(&self) -> VcxResult<Vec<String>> {
-        let agency_vk = settings::get_config_value(settings::CONFIG_AGENCY_VERKEY)?;
-        Ok(vec![self.agent_vk.to_string(), agency_vk])
+        if let Some(ref mediator) = self.mediator {
+            return Ok(mediator.routing_keys.clone());
+        }
+
+        cloud_agent::get()?.routing_keys(&self.agent_vk)
     }
 
     pub fn recipient_keys(&self) -> Vec<String> {
         vec![self.pw_vk.to_string()]
     }
 
+    // DID used as `did` and DIDDoc id of connection request and response
+    pub fn connection_did(&self) -> VcxResult<String> {
+        self.did_method.build_did(&self.pw_did, &self.pw_vk, &self.agency_endpoint()?, &self.routing_keys()?)
+    }
+
     pub fn update_message_status(&self, uid: String) -> VcxResult<()> {
         trace!("Agent::update_message_status >>> uid: {:?}", uid);
 
+        received_messages::mark_processed(&self.pw_vk, &uid)?;
+
+        if transport::remove_received(&self.pw_vk, &uid) {
+            return Ok(());
+        }
+
+        if self.mediator.is_some() {
+            return ::mediation::messages_received(vec![uid]);
+        }
+
+        if !cloud_agent::get()?.keeps_messages() {
+            return Ok(());
+        }
+
+        _remove_prefetched_message(&self.pw_did, &uid);
+
         let messages_to_update = vec![UIDsByConn {
             pairwise_did: self.pw_did.clone(),
             uids: vec![uid],
@@ -77,29 +205,84 @@ impl AgentInfo {
         update_messages_status(MessageStatusCode::Reviewed, messages_to_update)
     }
 
-    pub fn get_messages(&self) -> VcxResult<HashMap<String, A2AMessage>> {
-        trace!("Agent::get_messages >>>");
+    // Received messages which weren't processed yet. Redelivered messages are marked reviewed and dropped.
+    // Authenticated messages must be sent from a key of `did_doc` if the remote DIDDoc is known.
+    pub fn get_messages(&self, did_doc: Option<&DidDoc>) -> VcxResult<HashMap<String, A2AMessage>> {
+        trace!("Agent::get_messages >>> did_doc: {:?}", did_doc);
 
-        let messages = get_connection_messages(&self.pw_did,
-                                               &self.pw_vk,
-                                               &self.agent_did,
-                                               &self.agent_vk,
-                                               None,
-                                               Some(vec![MessageStatusCode::Received]),
-                                               &Some(ProtocolTypes::V2))?;
+        let (messages, redelivered) = received_messages::deduplicate(&self.pw_vk, self.download_messages(did_doc)?)?;
 
+        for uid in redelivered {
+            if let Err(err) = self.update_message_status(uid.clone()) {
+                warn!("Agent::get_messages: cannot update status of redelivered message {}: {}", uid, err);
+            }
+        }
+
+        if settings::order_received_messages_enabled() {
+            return received_messages::order(messages);
+        }
+
+        Ok(messages)
+    }
+
+    fn download_messages(&self, did_doc: Option<&DidDoc>) -> VcxResult<HashMap<String, A2AMessage>> {
+        let mut a2a_messages = self.get_received_messages(did_doc)?;
+
+        if self.mediator.is_some() {
+            a2a_messages.extend(::mediation::get_messages(&self.pw_vk, did_doc)?);
+            return Ok(a2a_messages);
+        }
+
+        if !cloud_agent::get()?.keeps_messages() {
+            return Ok(a2a_messages);
+        }
+
+        let messages = match _prefetched_messages(&self.pw_did) {
+            Some(messages) => messages,
+            None => get_connection_messages(&self.pw_did,
+                                            &self.pw_vk,
+                                            &self.agent_did,
+                                            &self.agent_vk,
+                                            None,
+                                            Some(vec![MessageStatusCode::Received]),
+                                            &Some(ProtocolTypes::V2))?
+        };
 
-        let mut a2a_messages: HashMap<String, A2AMessage> = HashMap::new();
 
         for message in messages {
-            a2a_messages.insert(message.uid.clone(), self.decode_message(&message)?);
+            a2a_messages.insert(message.uid.clone(), self.decode_message(&message, did_doc)?);
+        }
+
+        Ok(a2a_messages)
+    }
+
+    // Messages the remote side sent back over the transport
+    fn get_received_messages(&self, did_doc: Option<&DidDoc>) -> VcxResult<HashMap<String, A2AMessage>> {
+        let mut a2a_messages: HashMap<String, A2AMessage> = HashMap::new();
+
+        for (uid, payload) in transport::get_received(&self.pw_vk) {
+            a2a_messages.insert(uid, EncryptionEnvelope::open_from(payload, did_doc)?);
         }
 
         Ok(a2a_messages)
     }
 
-    pub fn get_message_by_id(&self, msg_id: &str) -> VcxResult<A2AMessage> {
-        trace!("Agent::get_message_by_id >>> msg_id: {:?}", msg_id);
+    pub fn get_message_by_id(&self, msg_id: &str, did_doc: Option<&DidDoc>) -> VcxResult<A2AMessage> {
+        trace!("Agent::get_message_by_id >>> msg_id: {:?}, did_doc: {:?}", msg_id, did_doc);
+
+        if let Some(payload) = transport::get_received(&self.pw_vk).remove(msg_id) {
+            return EncryptionEnvelope::open_from(payload, did_doc);
+        }
+
+        if self.mediator.is_some() {
+            return ::mediation::get_messages(&self.pw_vk, did_doc)?
+                .remove(msg_id)
+                .ok_or(VcxError::from_msg(VcxErrorKind::InvalidMessages, format!("Message not found for id: {:?}", msg_id)));
+        }
+
+        if !cloud_agent::get()?.keeps_messages() {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidMessages, format!("Message not found for id: {:?}", msg_id)));
+        }
 
         let mut messages = get_connection_messages(&self.pw_did,
                                                    &self.pw_vk,
@@ -114,33 +297,167 @@ impl AgentInfo {
                 .pop()
                 .ok_or(VcxError::from_msg(VcxErrorKind::InvalidMessages, format!("Message not found for id: {:?}", msg_id)))?;
 
-        let message = self.decode_message(&message)?;
+        let message = self.decode_message(&message, did_doc)?;
 
         Ok(message)
     }
 
-    pub fn decode_message(&self, message: &Message) -> VcxResult<A2AMessage> {
-        trace!("Agent::decode_message >>>");
+    pub fn decode_message(&self, message: &Message, did_doc: Option<&DidDoc>) -> VcxResult<A2AMessage> {
+        trace!("Agent::decode_message >>> did_doc: {:?}", did_doc);
 
-        EncryptionEnvelope::open(message.payload()?)
+        EncryptionEnvelope::open_from(message.payload()?, did_doc)
     }
 
     pub fn send_message(&self, message: &A2AMessage, did_dod: &DidDoc) -> VcxResult<()> {
         trace!("Agent::send_message >>> message: {:?}, did_doc: {:?}", message, did_dod);
-        let envelope = EncryptionEnvelope::create(&message, Some(&self.pw_vk), &did_dod)?;
-        httpclient::post_message(&envelope.0, &did_dod.get_endpoint())?;
+
+        let endpoint = did_dod.get_endpoint();
+
+        if !self.return_route && !transport::is_websocket(&endpoint) {
+            let message = self.pack_message(message, did_dod)?;
+
+            if outbound_queue::is_running() {
+                return outbound_queue::send(&endpoint, self.tls.clone().or(did_dod.get_tls_constraints()), &message);
+            }
+
+            self.transport(&endpoint, did_dod, None).send(&message)?;
+            return Ok(());
+        }
+
+        let message = Decorators::default()
+            .set_transport(Transport::return_route(ReturnRoute::All))
+            .attach(message)?;
+
+        let response = self.transport(&endpoint, did_dod, Some(&self.pw_vk))
+            .send(&self.pack_message(&message, did_dod)?)?;
+
+        if let Some(response) = response {
+            transport::store_received(&self.pw_vk, response);
+        }
+
         Ok(())
     }
 
+    // Waits for the reply sent back over the same connection. Returns `None` if there is no reply.
+    pub fn send_message_with_return_route(&self, message: &A2AMessage, did_dod: &DidDoc) -> VcxResult<Option<A2AMessage>> {
+        trace!("Agent::send_message_with_return_route >>> message: {:?}, did_doc: {:?}", message, did_dod);
+
+        let message = Decorators::default()
+            .set_transport(Transport::return_route(ReturnRoute::All))
+            .attach(message)?;
+
+        let response = self.transport(&did_dod.get_endpoint(), did_dod, Some(&self.pw_vk))
+            .send_and_wait(&self.pack_message(&message, did_dod)?)?;
+
+        match response {
+            Some(response) => EncryptionEnvelope::open_from(response, Some(did_dod)).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    fn pack_message(&self, message: &A2AMessage, did_dod: &DidDoc) -> VcxResult<Vec<u8>> {
+        let envelope = match self.envelope {
+            EnvelopeFormat::IndyPack =>
+                EncryptionEnvelope::create(&message, Some(&self.pw_vk), &did_dod)?,
+            EnvelopeFormat::DidcommV2(ref options) =>
+                EncryptionEnvelope::create_didcomm_v2(&message, Some(&self.pw_vk), &did_dod, options)?,
+        };
+        Ok(envelope.0)
+    }
+
+    fn transport(&self, endpoint: &str, did_dod: &DidDoc, session: Option<&str>) -> Box<dyn transport::Transport> {
+        let tls = self.tls.clone().or(did_dod.get_tls_constraints());
+        transport::for_endpoint(endpoint, tls, session)
+    }
+
     pub fn send_message_anonymously(message: &A2AMessage, did_dod: &DidDoc) -> VcxResult<()> {
         trace!("Agent::send_message_anonymously >>> message: {:?}, did_doc: {:?}", message, did_dod);
         let envelope = EncryptionEnvelope::create(&message, None, &did_dod)?;
-        httpclient::post_message(&envelope.0, &did_dod.get_endpoint())?;
+        let endpoint = did_dod.get_endpoint();
+
+        if outbound_queue::is_running() && !transport::is_websocket(&endpoint) {
+            return outbound_queue::send(&endpoint, did_dod.get_tls_constraints(), &envelope.0);
+        }
+
+        transport::for_endpoint(&endpoint, did_dod.get_tls_constraints(), None).send(&envelope.0)?;
+        Ok(())
+    }
+
+    pub fn set_tls_constraints(&mut self, tls: Option<TlsConstraints>) {
+        self.tls = tls;
+    }
+
+    pub fn set_return_route(&mut self, return_route: bool) {
+        self.return_route = return_route;
+    }
+
+    pub fn set_auto_accept(&mut self, auto_accept: AutoAcceptPolicy) {
+        self.auto_accept = auto_accept;
+    }
+
+    pub fn set_did_method(&mut self, did_method: DidMethod) {
+        self.did_method = did_method;
+    }
+
+    pub fn set_envelope_format(&mut self, envelope: EnvelopeFormat) -> VcxResult<()> {
+        // Connections created before DIDComm v2 support may have no key agreement key registered
+        if let EnvelopeFormat::DidcommV2(_) = envelope {
+            didcomm_v2::add_key(&self.pw_vk)?;
+        }
+
+        self.envelope = envelope;
         Ok(())
     }
 
     pub fn delete(&self) -> VcxResult<()> {
         trace!("Agent::delete >>>");
+
+        transport::close(&self.pw_vk);
+        received_messages::clear(&self.pw_vk)?;
+
+        if self.mediator.is_some() {
+            return ::mediation::remove_key(&self.pw_vk);
+        }
+
         send_delete_connection_message(&self.pw_did, &self.pw_vk, &self.agent_did, &self.agent_vk)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::devsetup::SetupAriesMocks;
+    use utils::httpclient::AgencyMock;
+    use utils::uuid;
+    use v3::handlers::mediation::pickup::tests::_envelope;
+    use v3::messages::ack::tests::_ack;
+    use v3::messages::connection::did_doc::tests::_did_doc;
+
+    #[test]
+    fn test_send_message_keeps_returned_message() {
+        let _setup = SetupAriesMocks::init();
+
+        let mut agent_info = AgentInfo { pw_vk: uuid::uuid(), ..AgentInfo::default() };
+        agent_info.set_return_route(true);
+
+        AgencyMock::set_next_response(_envelope(&_ack().to_a2a_message()));
+        agent_info.send_message(&_ack().to_a2a_message(), &_did_doc()).unwrap();
+
+        let uid = transport::get_received(&agent_info.pw_vk).keys().next().cloned().unwrap();
+        assert_eq!(_ack().to_a2a_message(), agent_info.get_message_by_id(&uid, None).unwrap());
+
+        agent_info.update_message_status(uid).unwrap();
+        assert!(transport::get_received(&agent_info.pw_vk).is_empty());
+    }
+
+    #[test]
+    fn test_send_message_ignores_response_without_return_route() {
+        let _setup = SetupAriesMocks::init();
+
+        let agent_info = AgentInfo { pw_vk: uuid::uuid(), ..AgentInfo::default() };
+
+        AgencyMock::set_next_response(_envelope(&_ack().to_a2a_message()));
+        agent_info.send_message(&_ack().to_a2a_message(), &_did_doc()).unwrap();
+
+        assert!(transport::get_received(&agent_info.pw_vk).is_empty());
+    }
+}
@@ -1,14 +1,19 @@
 use messages::get_message::Message;
 use error::prelude::*;
 
-use v3::handlers::connection::states::{DidExchangeSM, Actor, ActorDidExchangeState};
+use v3::handlers::connection::states::{DidExchangeSM, Actor, ActorDidExchangeState, PingStatus};
 use v3::handlers::connection::messages::DidExchangeMessages;
-use v3::handlers::connection::agent::AgentInfo;
+use v3::handlers::connection::agent::{AgentInfo, AutoAcceptPolicy};
 use v3::messages::a2a::A2AMessage;
 use v3::messages::connection::invite::Invitation;
+use v3::messages::out_of_band::invitation::Invitation as OutOfBandInvitation;
+use api::VcxStateType;
 
 use std::collections::HashMap;
-use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::connection::did_doc::{DidDoc, TlsConstraints};
+use v3::utils::encryption_envelope::EnvelopeFormat;
+use v3::utils::didcomm_v2;
+use v3::utils::did_method::DidMethod;
 use v3::messages::basic_message::message::BasicMessage;
 use v3::messages::discovery::disclose::ProtocolDescriptor;
 
@@ -27,8 +32,8 @@ impl Connection {
         }
     }
 
-    pub fn from_parts(source_id: String, agent_info: AgentInfo, state: ActorDidExchangeState) -> Connection {
-        Connection { connection_sm: DidExchangeSM::from(source_id, agent_info, state) }
+    pub fn from_parts(source_id: String, agent_info: AgentInfo, state: ActorDidExchangeState, their_public_did: Option<String>) -> Connection {
+        Connection { connection_sm: DidExchangeSM::from(source_id, agent_info, state, their_public_did) }
     }
 
     pub fn create_with_invite(source_id: &str, invitation: Invitation) -> VcxResult<Connection> {
@@ -43,6 +48,19 @@ impl Connection {
         Ok(connection)
     }
 
+    // Handshake is performed with the first protocol offered by Out-of-Band invitation which is supported
+    pub fn create_with_outofband_invite(source_id: &str, invitation: OutOfBandInvitation) -> VcxResult<Connection> {
+        trace!("Connection::create_with_outofband_invite >>> source_id: {}", source_id);
+
+        let mut connection = Connection {
+            connection_sm: DidExchangeSM::new(Actor::Invitee, source_id),
+        };
+
+        connection.step(DidExchangeMessages::OutOfBandInvitationReceived(invitation))?;
+
+        Ok(connection)
+    }
+
     pub fn source_id(&self) -> String { self.connection_sm.source_id().to_string() }
 
     pub fn state(&self) -> u32 { self.connection_sm.state() }
@@ -53,10 +71,18 @@ impl Connection {
         self.connection_sm.remote_did()
     }
 
+    pub fn remote_protocols(&self) -> Option<Vec<ProtocolDescriptor>> {
+        self.connection_sm.get_remote_protocols()
+    }
+
     pub fn remote_vk(&self) -> VcxResult<String> {
         self.connection_sm.remote_vk()
     }
 
+    pub fn their_public_did(&self) -> Option<String> {
+        self.connection_sm.their_public_did().map(String::from)
+    }
+
     pub fn state_object<'a>(&'a self) -> &'a ActorDidExchangeState {
         &self.connection_sm.state_object()
     }
@@ -82,6 +108,50 @@ impl Connection {
         }
     }
 
+    pub fn get_problem_report(&self) -> VcxResult<String> {
+        trace!("Connection::get_problem_report >>>");
+        self.connection_sm.get_problem_report()
+            .map(|problem_report| json!(problem_report.to_a2a_message()).to_string())
+            .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "No Problem Report was sent or received"))
+    }
+
+    pub fn get_outofband_invite_details(&self, goal_code: Option<String>, goal: Option<String>) -> VcxResult<String> {
+        trace!("Connection::get_outofband_invite_details >>> goal_code: {:?}, goal: {:?}", goal_code, goal);
+
+        let invitation = match (self.actor(), self.connection_sm.get_invitation()) {
+            (Actor::Inviter, Some(invitation)) => invitation.clone(),
+            _ => return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Out-of-Band invitation is available only for Inviter which hasn't received Connection Request yet"))
+        };
+
+        let invitation = OutOfBandInvitation::from(invitation)
+            .set_goal_code(goal_code)
+            .set_goal(goal);
+
+        Ok(json!(invitation.to_a2a_message()).to_string())
+    }
+
+    // Answers Out-of-Band invitation of the remote side with the existing connection instead of a new handshake
+    pub fn send_handshake_reuse(&mut self, invitation: OutOfBandInvitation) -> VcxResult<()> {
+        trace!("Connection::send_handshake_reuse >>> invitation: {:?}", invitation);
+
+        if self.state() != VcxStateType::VcxStateAccepted as u32 {
+            return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Connection is not established yet"));
+        }
+
+        self.handle_message(DidExchangeMessages::SendHandshakeReuse(invitation))
+    }
+
+    pub fn get_completed_connection(&self) -> VcxResult<(AgentInfo, DidDoc)> {
+        if self.state() != VcxStateType::VcxStateAccepted as u32 {
+            return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Connection is not established yet"));
+        }
+
+        let did_doc = self.connection_sm.did_doc()
+            .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Remote Connection information is not set"))?;
+
+        Ok((self.agent_info().clone(), did_doc))
+    }
+
     pub fn actor(&self) -> Actor {
         self.connection_sm.actor()
     }
@@ -107,7 +177,7 @@ impl Connection {
         };
 
         if let Some(prev_agent_info) = self.connection_sm.prev_agent_info().cloned() {
-            let messages = prev_agent_info.get_messages()?;
+            let messages = prev_agent_info.get_messages(self.connection_sm.remote_did_doc().as_ref())?;
 
             if let Some((uid, message)) = self.connection_sm.find_message_to_handle(messages) {
                 self.handle_message(message.into())?;
@@ -137,12 +207,12 @@ impl Connection {
 
     pub fn get_messages(&self) -> VcxResult<HashMap<String, A2AMessage>> {
         trace!("Connection: get_messages >>>");
-        self.agent_info().get_messages()
+        self.agent_info().get_messages(self.connection_sm.remote_did_doc().as_ref())
     }
 
     pub fn get_message_by_id(&self, msg_id: &str) -> VcxResult<A2AMessage> {
         trace!("Connection: get_message_by_id >>>");
-        self.agent_info().get_message_by_id(msg_id)
+        self.agent_info().get_message_by_id(msg_id, self.connection_sm.remote_did_doc().as_ref())
     }
 
     pub fn handle_message(&mut self, message: DidExchangeMessages) -> VcxResult<()> {
@@ -159,7 +229,7 @@ impl Connection {
                 ::serde_json::from_str::<A2AMessage>(&message.msg)
                     .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize A2A message: {}", err)))
             }
-            None => self.agent_info().decode_message(message)
+            None => self.agent_info().decode_message(message, self.connection_sm.remote_did_doc().as_ref())
         }
     }
 
@@ -178,23 +248,56 @@ impl Connection {
         AgentInfo::send_message_anonymously(message, did_doc)
     }
 
-    fn parse_generic_message(message: &str, _message_options: &str) -> A2AMessage {
+    fn parse_generic_message(message: &str, message_options: &str) -> VcxResult<A2AMessage> {
+        let message_options: BasicMessageOptions = match message_options {
+            "" => BasicMessageOptions::default(),
+            options => ::serde_json::from_str(options)
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Cannot deserialize message options: {:?}", err)))?
+        };
+
         match ::serde_json::from_str::<A2AMessage>(message) {
-            Ok(a2a_message) => a2a_message,
+            Ok(a2a_message) => Ok(a2a_message),
             Err(_) => {
-                BasicMessage::create()
+                let mut basic_message = BasicMessage::create()
                     .set_content(message.to_string())
-                    .set_time()
-                    .to_a2a_message()
+                    .set_time();
+
+                if message_options.please_ack {
+                    basic_message = basic_message.ask_for_ack();
+                }
+
+                Ok(basic_message.to_a2a_message())
             }
         }
     }
 
-    pub fn send_generic_message(&self, message: &str, _message_options: &str) -> VcxResult<String> {
+    // Returns id of the sent Basic Message to match it with the delivery receipt (Ack)
+    pub fn send_generic_message(&self, message: &str, message_options: &str) -> VcxResult<String> {
         trace!("Connection::send_generic_message >>> message: {:?}", message);
 
-        let message = Connection::parse_generic_message(message, _message_options);
-        self.send_message(&message).map(|_| String::new())
+        let message = Connection::parse_generic_message(message, message_options)?;
+        self.send_message(&message)?;
+
+        match message {
+            A2AMessage::BasicMessage(basic_message) => Ok(basic_message.id.0),
+            _ => Ok(String::new())
+        }
+    }
+
+    // Sends delivery receipt (Ack) for the Basic Message received from the remote side
+    pub fn send_basic_message_ack(&self, message_id: &str) -> VcxResult<()> {
+        trace!("Connection::send_basic_message_ack >>> message_id: {:?}", message_id);
+
+        let basic_message = match self.get_message_by_id(message_id)? {
+            A2AMessage::BasicMessage(basic_message) => basic_message,
+            message => return Err(VcxError::from_msg(VcxErrorKind::InvalidMessages, format!("Message {} is not Basic Message: {:?}", message_id, message)))
+        };
+
+        if basic_message.please_ack.is_none() {
+            return Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Delivery receipt was not requested for Basic Message"));
+        }
+
+        self.send_message(&basic_message.create_ack().to_a2a_message())
     }
 
     pub fn send_ping(&mut self, comment: Option<String>) -> VcxResult<()> {
@@ -202,6 +305,40 @@ impl Connection {
         self.handle_message(DidExchangeMessages::SendPing(comment))
     }
 
+    pub fn set_tls_constraints(&mut self, tls: Option<TlsConstraints>) -> VcxResult<()> {
+        trace!("Connection::set_tls_constraints >>> tls: {:?}", tls);
+
+        if let Some(ref tls) = tls {
+            tls.validate()?;
+        }
+
+        self.connection_sm.set_tls_constraints(tls);
+        Ok(())
+    }
+
+    pub fn set_envelope_format(&mut self, envelope: EnvelopeFormat) -> VcxResult<()> {
+        trace!("Connection::set_envelope_format >>> envelope: {:?}", envelope);
+        self.connection_sm.set_envelope_format(envelope)
+    }
+
+    pub fn set_return_route(&mut self, return_route: bool) -> VcxResult<()> {
+        trace!("Connection::set_return_route >>> return_route: {:?}", return_route);
+        self.connection_sm.set_return_route(return_route);
+        Ok(())
+    }
+
+    pub fn set_auto_accept(&mut self, auto_accept: AutoAcceptPolicy) -> VcxResult<()> {
+        trace!("Connection::set_auto_accept >>> auto_accept: {:?}", auto_accept);
+        self.connection_sm.set_auto_accept(auto_accept);
+        Ok(())
+    }
+
+    pub fn set_did_method(&mut self, did_method: DidMethod) -> VcxResult<()> {
+        trace!("Connection::set_did_method >>> did_method: {:?}", did_method);
+        self.connection_sm.set_did_method(did_method);
+        Ok(())
+    }
+
     pub fn delete(&self) -> VcxResult<()> {
         trace!("Connection: delete >>> {:?}", self.connection_sm.source_id());
         self.agent_info().delete()
@@ -228,6 +365,7 @@ impl Connection {
             routing_keys: agent_info.routing_keys()?,
             service_endpoint: agent_info.agency_endpoint()?,
             protocols: Some(self.connection_sm.get_protocols()),
+            key_agreement_key: Some(didcomm_v2::key_agreement_key_from_verkey(&agent_info.pw_vk)?),
         };
 
         let remote = match self.connection_sm.did_doc() {
@@ -238,11 +376,15 @@ impl Connection {
                     routing_keys: did_doc.routing_keys(),
                     service_endpoint: did_doc.get_endpoint(),
                     protocols: self.connection_sm.get_remote_protocols(),
+                    key_agreement_key: match did_doc.recipient_keys().get(0) {
+                        Some(verkey) => Some(didcomm_v2::key_agreement_key_from_verkey(verkey)?),
+                        None => None
+                    },
                 }),
             None => None
         };
 
-        let connection_info = ConnectionInfo { my: current, their: remote };
+        let connection_info = ConnectionInfo { my: current, their: remote, last_ping: self.connection_sm.get_last_ping().cloned() };
 
         let connection_info_json = serde_json::to_string(&connection_info)
             .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot serialize ConnectionInfo: {:?}", err)))?;
@@ -251,10 +393,18 @@ impl Connection {
     }
 }
 
+#[derive(Debug, Deserialize, Default)]
+struct BasicMessageOptions {
+    #[serde(default)]
+    please_ack: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct ConnectionInfo {
     my: SideConnectionInfo,
     their: Option<SideConnectionInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_ping: Option<PingStatus>,
 }
 
 #[derive(Debug, Serialize)]
@@ -266,6 +416,8 @@ struct SideConnectionInfo {
     service_endpoint: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     protocols: Option<Vec<ProtocolDescriptor>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_agreement_key: Option<String>,
 }
 
 #[cfg(test)]
@@ -276,7 +428,7 @@ mod tests {
     #[test]
     fn test_parse_generic_message_plain_string_should_be_parsed_as_basic_msg() -> Result<(), String> {
         let message = "Some plain text message";
-        let result = Connection::parse_generic_message(message, "");
+        let result = Connection::parse_generic_message(message, "").unwrap();
         match result {
             A2AMessage::BasicMessage(basic_msg) => {
                 assert_eq!(basic_msg.content, message);
@@ -293,7 +445,7 @@ mod tests {
             "@type": "some type",
             "content": "some content"
         }).to_string();
-        let result = Connection::parse_generic_message(&message, "");
+        let result = Connection::parse_generic_message(&message, "").unwrap();
         match result {
             A2AMessage::Generic(value) => {
                 assert_eq!(value.to_string(), message);
@@ -302,4 +454,24 @@ mod tests {
             other => Err(format!("Result is not Generic, but: {:?}", other))
         }
     }
+
+    #[test]
+    fn test_parse_generic_message_with_please_ack_option() -> Result<(), String> {
+        let result = Connection::parse_generic_message("Some plain text message", r#"{"please_ack":true}"#).unwrap();
+        match result {
+            A2AMessage::BasicMessage(basic_msg) => {
+                assert!(basic_msg.please_ack.is_some());
+                Ok(())
+            }
+            other => Err(format!("Result is not BasicMessage, but: {:?}", other))
+        }
+    }
+
+    #[test]
+    fn test_parse_generic_message_with_proprietary_options() {
+        let result = Connection::parse_generic_message("Some plain text message", r#"{"msg_type":"Greeting","msg_title":"Hi There"}"#).unwrap();
+        assert_match!(A2AMessage::BasicMessage(_), result);
+
+        assert!(Connection::parse_generic_message("Some plain text message", "invalid").is_err());
+    }
 }
\ No newline at end of file
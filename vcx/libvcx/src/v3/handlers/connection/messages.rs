@@ -7,6 +7,12 @@ use v3::messages::trust_ping::ping_response::PingResponse;
 use v3::messages::ack::Ack;
 use v3::messages::discovery::query::Query;
 use v3::messages::discovery::disclose::Disclose;
+use v3::messages::out_of_band::invitation::Invitation as OutOfBandInvitation;
+use v3::messages::out_of_band::handshake_reuse::HandshakeReuse;
+use v3::messages::out_of_band::handshake_reuse_accepted::HandshakeReuseAccepted;
+use v3::messages::didexchange::request::Request as DidExchangeRequest;
+use v3::messages::didexchange::response::Response as DidExchangeResponse;
+use v3::messages::didexchange::complete::Complete;
 use v3::messages::a2a::A2AMessage;
 
 
@@ -14,8 +20,12 @@ use v3::messages::a2a::A2AMessage;
 pub enum DidExchangeMessages {
     Connect(),
     InvitationReceived(Invitation),
+    OutOfBandInvitationReceived(OutOfBandInvitation),
     ExchangeRequestReceived(Request),
     ExchangeResponseReceived(SignedResponse),
+    DidExchangeRequestReceived(DidExchangeRequest),
+    DidExchangeResponseReceived(DidExchangeResponse),
+    DidExchangeCompleteReceived(Complete),
     AckReceived(Ack),
     ProblemReportReceived(ProblemReport),
     SendPing(Option<String>),
@@ -24,6 +34,9 @@ pub enum DidExchangeMessages {
     DiscoverFeatures((Option<String>, Option<String>)),
     QueryReceived(Query),
     DiscloseReceived(Disclose),
+    SendHandshakeReuse(OutOfBandInvitation),
+    HandshakeReuseReceived(HandshakeReuse),
+    HandshakeReuseAcceptedReceived(HandshakeReuseAccepted),
     Unknown
 }
 
@@ -39,6 +52,18 @@ impl From<A2AMessage> for DidExchangeMessages {
             A2AMessage::ConnectionResponse(request) => {
                 DidExchangeMessages::ExchangeResponseReceived(request)
             }
+            A2AMessage::OutOfBandInvitation(invitation) => {
+                DidExchangeMessages::OutOfBandInvitationReceived(invitation)
+            }
+            A2AMessage::DidExchangeRequest(request) => {
+                DidExchangeMessages::DidExchangeRequestReceived(request)
+            }
+            A2AMessage::DidExchangeResponse(response) => {
+                DidExchangeMessages::DidExchangeResponseReceived(response)
+            }
+            A2AMessage::DidExchangeComplete(complete) => {
+                DidExchangeMessages::DidExchangeCompleteReceived(complete)
+            }
             A2AMessage::Ping(ping) => {
                 DidExchangeMessages::PingReceived(ping)
             }
@@ -57,6 +82,12 @@ impl From<A2AMessage> for DidExchangeMessages {
             A2AMessage::ConnectionProblemReport(report) => {
                 DidExchangeMessages::ProblemReportReceived(report)
             }
+            A2AMessage::HandshakeReuse(reuse) => {
+                DidExchangeMessages::HandshakeReuseReceived(reuse)
+            }
+            A2AMessage::HandshakeReuseAccepted(accepted) => {
+                DidExchangeMessages::HandshakeReuseAcceptedReceived(accepted)
+            }
             _ => {
                 DidExchangeMessages::Unknown
             }
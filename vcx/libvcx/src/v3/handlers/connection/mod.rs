@@ -2,6 +2,7 @@ pub mod agent;
 pub mod states;
 pub mod connection;
 pub mod messages;
+pub mod received_messages;
 
 #[cfg(test)]
 pub mod tests {
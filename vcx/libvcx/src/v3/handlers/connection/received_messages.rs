@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+
+use error::prelude::*;
+use utils::libindy::wallet;
+use v3::messages::a2a::A2AMessage;
+use v3::messages::decorators::Decorators;
+
+/*
+    Log of messages received over a connection.
+    Agency can deliver the same message (the same `@id` in the same thread) several times under different uids.
+    The first delivery is recorded in the wallet and the message is marked processed once the protocol handler
+    updates its status. Later deliveries are dropped instead of being processed again.
+*/
+
+const RECEIVED_MESSAGE_RECORD_TYPE: &str = "VcxReceivedMessage";
+const SEARCH_BATCH_SIZE: usize = 100;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct ReceivedMessage {
+    uid: String,
+    processed: bool,
+}
+
+// Returns messages which weren't processed yet and uids of redelivered messages which were
+pub fn deduplicate(pw_vk: &str, messages: HashMap<String, A2AMessage>) -> VcxResult<(HashMap<String, A2AMessage>, Vec<String>)> {
+    trace!("received_messages::deduplicate >>> pw_vk: {}, messages: {:?}", pw_vk, messages.keys());
+
+    let mut uids: Vec<String> = messages.keys().cloned().collect();
+    uids.sort();
+
+    let mut unique = HashMap::new();
+    let mut redelivered = Vec::new();
+
+    for uid in uids {
+        let message = &messages[&uid];
+
+        let id = match _record_id(pw_vk, message)? {
+            Some(id) => id,
+            None => {
+                unique.insert(uid, message.clone());
+                continue;
+            }
+        };
+
+        match _get(&id)? {
+            None => {
+                _add(&id, pw_vk, &uid)?;
+                unique.insert(uid, message.clone());
+            }
+            Some(ref record) if record.processed => redelivered.push(uid),
+            Some(ref record) if record.uid == uid => { unique.insert(uid, message.clone()); }
+            // the first delivery is pending in the same batch
+            Some(ref record) if messages.contains_key(&record.uid) => {}
+            // the first delivery is gone without being processed
+            Some(_) => {
+                _update(&id, pw_vk, &ReceivedMessage { uid: uid.clone(), processed: false })?;
+                unique.insert(uid, message.clone());
+            }
+        }
+    }
+
+    Ok((unique, redelivered))
+}
+
+// Keeps only the earliest sent message of every thread (by `~timing.out_time`).
+// Later messages of the thread are returned once the earlier ones are processed.
+pub fn order(messages: HashMap<String, A2AMessage>) -> VcxResult<HashMap<String, A2AMessage>> {
+    let mut earliest: HashMap<String, (DateTime<FixedOffset>, String)> = HashMap::new();
+    let mut ordered = HashMap::new();
+
+    for (uid, message) in messages.iter() {
+        let decorators = Decorators::from_message(message)?;
+
+        let thread_id = decorators.thread.and_then(|thread| thread.thid);
+        let out_time = decorators.timing
+            .and_then(|timing| timing.out_time)
+            .and_then(|out_time| DateTime::parse_from_rfc3339(&out_time).ok());
+
+        match (thread_id, out_time) {
+            (Some(thread_id), Some(out_time)) => {
+                let replace = match earliest.get(&thread_id) {
+                    Some((time, earliest_uid)) => (out_time, uid) < (*time, earliest_uid),
+                    None => true
+                };
+
+                if replace {
+                    earliest.insert(thread_id, (out_time, uid.clone()));
+                }
+            }
+            _ => { ordered.insert(uid.clone(), message.clone()); }
+        }
+    }
+
+    for (_, (_, uid)) in earliest {
+        let message = messages[&uid].clone();
+        ordered.insert(uid, message);
+    }
+
+    Ok(ordered)
+}
+
+pub fn mark_processed(pw_vk: &str, uid: &str) -> VcxResult<()> {
+    trace!("received_messages::mark_processed >>> pw_vk: {}, uid: {}", pw_vk, uid);
+
+    let query = json!({"connection": pw_vk, "uid": uid}).to_string();
+
+    for record in _search(&query)? {
+        if let Some(id) = record.id {
+            _update(&id, pw_vk, &ReceivedMessage { uid: uid.to_string(), processed: true })?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn clear(pw_vk: &str) -> VcxResult<()> {
+    trace!("received_messages::clear >>> pw_vk: {}", pw_vk);
+
+    let query = json!({"connection": pw_vk}).to_string();
+
+    for record in _search(&query)? {
+        if let Some(id) = record.id {
+            wallet::delete_record(RECEIVED_MESSAGE_RECORD_TYPE, &id)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Messages without `@id` aren't tracked
+fn _record_id(pw_vk: &str, message: &A2AMessage) -> VcxResult<Option<String>> {
+    let message_json = ::serde_json::to_value(message)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize message: {:?}", err)))?;
+
+    let message_id = match message_json["@id"].as_str() {
+        Some(message_id) => message_id.to_string(),
+        None => return Ok(None)
+    };
+
+    let thread_id = Decorators::from_message(message)?.thread
+        .and_then(|thread| thread.thid)
+        .unwrap_or_default();
+
+    Ok(Some(format!("{}:{}:{}", pw_vk, thread_id, message_id)))
+}
+
+fn _get(id: &str) -> VcxResult<Option<ReceivedMessage>> {
+    let options = json!({"retrieveType": false, "retrieveValue": true, "retrieveTags": false}).to_string();
+
+    let record = match wallet::get_record(RECEIVED_MESSAGE_RECORD_TYPE, id, &options) {
+        Ok(record) => record,
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => return Ok(None),
+        Err(err) => return Err(err)
+    };
+
+    let record: ::serde_json::Value = ::serde_json::from_str(&record)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize wallet record: {:?}", err)))?;
+
+    Ok(record["value"].as_str().and_then(|value| ::serde_json::from_str(value).ok()))
+}
+
+fn _add(id: &str, pw_vk: &str, uid: &str) -> VcxResult<()> {
+    let record = ReceivedMessage { uid: uid.to_string(), processed: false };
+    wallet::add_record(RECEIVED_MESSAGE_RECORD_TYPE, id, &json!(record).to_string(), Some(&_tags(pw_vk, uid)))
+}
+
+fn _update(id: &str, pw_vk: &str, record: &ReceivedMessage) -> VcxResult<()> {
+    wallet::update_record_value(RECEIVED_MESSAGE_RECORD_TYPE, id, &json!(record).to_string())?;
+    wallet::update_record_tags(RECEIVED_MESSAGE_RECORD_TYPE, id, &_tags(pw_vk, &record.uid))
+}
+
+fn _search(query: &str) -> VcxResult<Vec<wallet::WalletRecord>> {
+    let options = json!({"retrieveRecords": true, "retrieveTotalCount": false, "retrieveType": false, "retrieveValue": false, "retrieveTags": false}).to_string();
+    wallet::search_all_records(RECEIVED_MESSAGE_RECORD_TYPE, query, &options, SEARCH_BATCH_SIZE)
+}
+
+fn _tags(pw_vk: &str, uid: &str) -> String {
+    json!({"connection": pw_vk, "uid": uid}).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::devsetup::*;
+
+    const PW_VK: &str = "pw_vk";
+
+    fn _message(id: &str, thread_id: &str, out_time: &str) -> A2AMessage {
+        A2AMessage::Generic(json!({
+            "@id": id,
+            "@type": "https://didcomm.org/issue-credential/1.0/offer-credential",
+            "~thread": {"thid": thread_id},
+            "~timing": {"out_time": out_time}
+        }))
+    }
+
+    fn _messages(messages: Vec<(&str, A2AMessage)>) -> HashMap<String, A2AMessage> {
+        messages.into_iter().map(|(uid, message)| (uid.to_string(), message)).collect()
+    }
+
+    fn _uids(messages: &HashMap<String, A2AMessage>) -> Vec<String> {
+        let mut uids: Vec<String> = messages.keys().cloned().collect();
+        uids.sort();
+        uids
+    }
+
+    #[test]
+    fn test_deduplicate_drops_redelivered_messages() {
+        let _setup = SetupLibraryWallet::init();
+
+        let offer = _message("offer", "thread", "2020-01-01T00:00:00Z");
+
+        let (messages, redelivered) = deduplicate(PW_VK, _messages(vec![("uid_1", offer.clone()), ("uid_2", offer.clone())])).unwrap();
+        assert_eq!(vec!["uid_1"], _uids(&messages));
+        assert!(redelivered.is_empty());
+
+        // not processed yet
+        let (messages, _) = deduplicate(PW_VK, _messages(vec![("uid_1", offer.clone())])).unwrap();
+        assert_eq!(vec!["uid_1"], _uids(&messages));
+
+        mark_processed(PW_VK, "uid_1").unwrap();
+
+        let (messages, redelivered) = deduplicate(PW_VK, _messages(vec![("uid_2", offer.clone())])).unwrap();
+        assert!(messages.is_empty());
+        assert_eq!(vec!["uid_2"], redelivered);
+
+        // other connection
+        let (messages, _) = deduplicate("other_vk", _messages(vec![("uid_3", offer)])).unwrap();
+        assert_eq!(vec!["uid_3"], _uids(&messages));
+    }
+
+    #[test]
+    fn test_deduplicate_keeps_redelivered_message_which_was_not_processed() {
+        let _setup = SetupLibraryWallet::init();
+
+        let offer = _message("offer", "thread", "2020-01-01T00:00:00Z");
+
+        deduplicate(PW_VK, _messages(vec![("uid_1", offer.clone())])).unwrap();
+
+        let (messages, _) = deduplicate(PW_VK, _messages(vec![("uid_2", offer)])).unwrap();
+        assert_eq!(vec!["uid_2"], _uids(&messages));
+
+        mark_processed(PW_VK, "uid_2").unwrap();
+        clear(PW_VK).unwrap();
+
+        let (messages, _) = deduplicate(PW_VK, _messages(vec![("uid_3", _message("offer", "thread", "2020-01-01T00:00:00Z"))])).unwrap();
+        assert_eq!(vec!["uid_3"], _uids(&messages));
+    }
+
+    #[test]
+    fn test_deduplicate_keeps_messages_without_id() {
+        let _setup = SetupLibraryWallet::init();
+
+        let message = A2AMessage::Generic(json!({"@type": "https://didcomm.org/basicmessage/1.0/message"}));
+
+        mark_processed(PW_VK, "uid_1").unwrap();
+        let (messages, redelivered) = deduplicate(PW_VK, _messages(vec![("uid_1", message.clone()), ("uid_2", message)])).unwrap();
+        assert_eq!(vec!["uid_1", "uid_2"], _uids(&messages));
+        assert!(redelivered.is_empty());
+    }
+
+    #[test]
+    fn test_order_keeps_earliest_message_of_thread() {
+        let messages = _messages(vec![
+            ("uid_1", _message("request", "thread_1", "2020-01-01T00:00:02Z")),
+            ("uid_2", _message("offer", "thread_1", "2020-01-01T00:00:01+00:00")),
+            ("uid_3", _message("ack", "thread_2", "2020-01-01T00:00:03Z")),
+            ("uid_4", A2AMessage::Generic(json!({"@id": "untimed", "~thread": {"thid": "thread_1"}}))),
+        ]);
+
+        assert_eq!(vec!["uid_2", "uid_3", "uid_4"], _uids(&order(messages).unwrap()));
+    }
+}
@@ -2,18 +2,28 @@ use api::VcxStateType;
 
 use v3::handlers::connection::messages::DidExchangeMessages;
 use v3::messages::a2a::A2AMessage;
-use v3::handlers::connection::agent::AgentInfo;
+use v3::handlers::connection::agent::{AgentInfo, AutoAcceptPolicy};
 use v3::messages::connection::invite::Invitation;
 use v3::messages::connection::request::Request;
 use v3::messages::connection::response::{Response, SignedResponse};
 use v3::messages::connection::problem_report::{ProblemReport, ProblemCode};
 use v3::messages::trust_ping::ping::Ping;
+use v3::messages::decorators::Threaded;
 use v3::messages::trust_ping::ping_response::PingResponse;
 use v3::messages::ack::Ack;
-use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::connection::did_doc::{DidDoc, TlsConstraints};
+use v3::utils::encryption_envelope::EnvelopeFormat;
+use v3::utils::did_method::DidMethod;
 use v3::messages::discovery::query::Query;
 use v3::messages::discovery::disclose::{Disclose, ProtocolDescriptor};
 use v3::messages::a2a::protocol_registry::ProtocolRegistry;
+use v3::messages::out_of_band::invitation::Invitation as OutOfBandInvitation;
+use v3::messages::out_of_band::handshake_reuse::HandshakeReuse;
+use v3::messages::out_of_band::handshake_reuse_accepted::HandshakeReuseAccepted;
+use v3::messages::didexchange::request::Request as DidExchangeRequest;
+use v3::messages::didexchange::response::Response as DidExchangeResponse;
+use v3::messages::didexchange::complete::Complete;
+use v3::messages::a2a::message_family::MessageFamilies;
 
 use std::collections::HashMap;
 
@@ -24,6 +34,9 @@ pub struct DidExchangeSM {
     source_id: String,
     agent_info: AgentInfo,
     state: ActorDidExchangeState,
+    // Public DID the Inviter stated in the invitation. Used to find the connection for reuse.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    their_public_did: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +68,7 @@ pub enum DidExchangeState {
 impl DidExchangeState {
     pub fn code(&self) -> u32 {
         match self {
+            DidExchangeState::Null(ref state) if state.problem_report.is_some() => VcxStateType::VcxStateNone as u32,
             DidExchangeState::Null(_) => VcxStateType::VcxStateInitialized as u32,
             DidExchangeState::Invited(_) => VcxStateType::VcxStateOfferSent as u32,
             DidExchangeState::Requested(_) => VcxStateType::VcxStateRequestReceived as u32,
@@ -64,18 +78,40 @@ impl DidExchangeState {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NullState {}
+// Connection gets back to Null state on failure of exchange. Problem Report is kept to be available for the user.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NullState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    problem_report: Option<ProblemReport>,
+}
+
+// Protocol the Invitee performs handshake with. It is chosen from the protocols offered by Out-of-Band invitation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum HandshakeProtocol {
+    Connections,
+    DidExchange,
+}
+
+impl Default for HandshakeProtocol {
+    fn default() -> HandshakeProtocol {
+        HandshakeProtocol::Connections
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InvitedState {
-    invitation: Invitation
+    invitation: Invitation,
+    #[serde(default)]
+    protocol: HandshakeProtocol,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestedState {
     request: Request,
     did_doc: DidDoc,
+    // Set for DID Exchange to thread Complete message to the invitation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    invitation_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,26 +125,48 @@ pub struct RespondedState {
 pub struct CompleteState {
     did_doc: DidDoc,
     protocols: Option<Vec<ProtocolDescriptor>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_ping: Option<PingStatus>,
+}
+
+// The last Trust Ping sent over established connection. Response confirms that remote side is still reachable.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PingStatus {
+    pub ping_id: String,
+    pub sent_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_received_at: Option<i64>,
 }
 
 impl From<(NullState, Invitation)> for InvitedState {
     fn from((_state, invitation): (NullState, Invitation)) -> InvitedState {
         trace!("DidExchangeStateSM: transit state from NullState to InvitedState");
-        InvitedState { invitation }
+        InvitedState { invitation, protocol: HandshakeProtocol::Connections }
+    }
+}
+
+impl From<(NullState, Invitation, HandshakeProtocol)> for InvitedState {
+    fn from((_state, invitation, protocol): (NullState, Invitation, HandshakeProtocol)) -> InvitedState {
+        trace!("DidExchangeStateSM: transit state from NullState to InvitedState");
+        InvitedState { invitation, protocol }
     }
 }
 
 impl From<(InvitedState, ProblemReport)> for NullState {
-    fn from((_state, _error): (InvitedState, ProblemReport)) -> NullState {
+    fn from((_state, problem_report): (InvitedState, ProblemReport)) -> NullState {
         trace!("DidExchangeStateSM: transit state from InvitedState to NullState");
-        NullState {}
+        NullState { problem_report: Some(problem_report) }
     }
 }
 
 impl From<(InvitedState, Request)> for RequestedState {
     fn from((state, request): (InvitedState, Request)) -> RequestedState {
         trace!("DidExchangeStateSM: transit state from InvitedState to RequestedState");
-        RequestedState { request, did_doc: DidDoc::from(state.invitation) }
+        let invitation_id = match state.protocol {
+            HandshakeProtocol::Connections => None,
+            HandshakeProtocol::DidExchange => Some(state.invitation.id.0.clone()),
+        };
+        RequestedState { request, did_doc: DidDoc::from(state.invitation), invitation_id }
     }
 }
 
@@ -120,58 +178,99 @@ impl From<(InvitedState, Request, SignedResponse, AgentInfo)> for RespondedState
 }
 
 impl From<(RequestedState, ProblemReport)> for NullState {
-    fn from((_state, _error): (RequestedState, ProblemReport)) -> NullState {
+    fn from((_state, problem_report): (RequestedState, ProblemReport)) -> NullState {
         trace!("DidExchangeStateSM: transit state from RequestedState to NullState");
-        NullState {}
+        NullState { problem_report: Some(problem_report) }
     }
 }
 
 impl From<(RequestedState, Response)> for CompleteState {
     fn from((_state, response): (RequestedState, Response)) -> CompleteState {
         trace!("DidExchangeStateSM: transit state from RequestedState to RespondedState");
-        CompleteState { did_doc: response.connection.did_doc, protocols: None }
+        CompleteState { did_doc: response.connection.did_doc, protocols: None, last_ping: None }
     }
 }
 
 impl From<(RespondedState, ProblemReport)> for NullState {
-    fn from((_state, _error): (RespondedState, ProblemReport)) -> NullState {
+    fn from((_state, problem_report): (RespondedState, ProblemReport)) -> NullState {
         trace!("DidExchangeStateSM: transit state from RespondedState to NullState");
-        NullState {}
+        NullState { problem_report: Some(problem_report) }
     }
 }
 
 impl From<(RespondedState, Ack)> for CompleteState {
     fn from((state, _ack): (RespondedState, Ack)) -> CompleteState {
         trace!("DidExchangeStateSM: transit state from RespondedState to CompleteState");
-        CompleteState { did_doc: state.did_doc, protocols: None }
+        CompleteState { did_doc: state.did_doc, protocols: None, last_ping: None }
+    }
+}
+
+impl From<(RespondedState, Complete)> for CompleteState {
+    fn from((state, _complete): (RespondedState, Complete)) -> CompleteState {
+        trace!("DidExchangeStateSM: transit state from RespondedState to CompleteState");
+        CompleteState { did_doc: state.did_doc, protocols: None, last_ping: None }
     }
 }
 
 impl From<(RespondedState, Ping)> for CompleteState {
     fn from((state, _ping): (RespondedState, Ping)) -> CompleteState {
         trace!("DidExchangeStateSM: transit state from RespondedState to CompleteState");
-        CompleteState { did_doc: state.did_doc, protocols: None }
+        CompleteState { did_doc: state.did_doc, protocols: None, last_ping: None }
     }
 }
 
 impl From<(RespondedState, PingResponse)> for CompleteState {
     fn from((state, _ping_response): (RespondedState, PingResponse)) -> CompleteState {
         trace!("DidExchangeStateSM: transit state from RespondedState to CompleteState");
-        CompleteState { did_doc: state.did_doc, protocols: None }
+        CompleteState { did_doc: state.did_doc, protocols: None, last_ping: None }
     }
 }
 
 impl From<(CompleteState, Vec<ProtocolDescriptor>)> for CompleteState {
     fn from((state, protocols): (CompleteState, Vec<ProtocolDescriptor>)) -> CompleteState {
         trace!("DidExchangeStateSM: transit state from CompleteState to CompleteState");
-        CompleteState { did_doc: state.did_doc, protocols: Some(protocols) }
+        CompleteState { did_doc: state.did_doc, protocols: Some(protocols), last_ping: state.last_ping }
     }
 }
 
 impl InvitedState {
-    fn handle_connection_request(&self, request: &Request,
+    fn handle_request(self, request: Request, protocol: HandshakeProtocol, agent_info: &mut AgentInfo) -> DidExchangeState {
+        match self.handle_connection_request(&request, protocol, agent_info) {
+            Ok((response, new_agent_info)) => {
+                let prev_agent_info = agent_info.clone();
+                *agent_info = new_agent_info;
+                DidExchangeState::Responded((self, request, response, prev_agent_info).into())
+            }
+            Err(err) => {
+                let problem_report = ProblemReport::create()
+                    .set_problem_code(ProblemCode::RequestProcessingError)
+                    .set_explain(err.to_string())
+                    .set_thread_id(&request.id.0);
+
+                agent_info.send_message(&problem_report.to_a2a_message(), &request.connection.did_doc).ok(); // IS is possible?
+                DidExchangeState::Null((self, problem_report).into())
+            }
+        }
+    }
+
+    // DIDDoc of DID Exchange request is extracted first, then the request is handled as Connection Request
+    fn handle_didexchange_request(self, request: DidExchangeRequest, agent_info: &mut AgentInfo) -> DidExchangeState {
+        match request.to_connection_request() {
+            Ok(request) => self.handle_request(request, HandshakeProtocol::DidExchange, agent_info),
+            Err(err) => {
+                let problem_report = ProblemReport::create()
+                    .set_problem_code(ProblemCode::RequestNotAccepted)
+                    .set_explain(err.to_string())
+                    .set_thread_id(request.thread.thid.as_ref().unwrap_or(&request.id.0));
+
+                DidExchangeState::Null((self, problem_report).into())
+            }
+        }
+    }
+
+    fn handle_connection_request(&self, request: &Request, protocol: HandshakeProtocol,
                                  agent_info: &AgentInfo) -> VcxResult<(SignedResponse, AgentInfo)> {
-        trace!("InvitedState:handle_connection_request >>> request: {:?}, agent_info: {:?}", request, agent_info);
+        trace!("InvitedState:handle_connection_request >>> request: {:?}, protocol: {:?}, agent_info: {:?}", request, protocol, agent_info);
 
         request.connection.did_doc.validate()?;
 
@@ -181,33 +280,49 @@ impl InvitedState {
         let new_agent_info: AgentInfo = agent_info.create_agent()?;
 
         let response = Response::create()
-            .set_did(new_agent_info.pw_did.to_string())
+            .set_did(new_agent_info.connection_did()?)
             .set_service_endpoint(new_agent_info.agency_endpoint()?)
             .set_keys(new_agent_info.recipient_keys(), new_agent_info.routing_keys()?)
             .ask_for_ack();
 
-        let signed_response = response.clone()
-            .set_thread_id(&request.id.0)
-            .encode(&prev_agent_info.pw_vk)?;
+        let response = response.set_thread_id(&request.id.0);
+
+        let signed_response = response.encode(&prev_agent_info.pw_vk)?;
+
+        let message = match protocol {
+            HandshakeProtocol::Connections => signed_response.to_a2a_message(),
+            HandshakeProtocol::DidExchange => DidExchangeResponse::from_connection_response(&response, &prev_agent_info.pw_vk)?.to_a2a_message(),
+        };
 
-        new_agent_info.send_message(&signed_response.to_a2a_message(), &request.connection.did_doc)?;
+        new_agent_info.send_message(&message, &request.connection.did_doc)?;
 
         Ok((signed_response, new_agent_info))
     }
 }
 
 impl RequestedState {
+    fn complete(self, result: VcxResult<Response>, agent_info: &AgentInfo) -> DidExchangeState {
+        match result {
+            Ok(response) => {
+                DidExchangeState::Completed((self, response).into())
+            }
+            Err(err) => {
+                let problem_report = ProblemReport::create()
+                    .set_problem_code(ProblemCode::ResponseProcessingError)
+                    .set_explain(err.to_string())
+                    .set_thread_id(&self.request.id.0);
+                agent_info.send_message(&problem_report.to_a2a_message(), &self.did_doc).ok();
+                DidExchangeState::Null((self, problem_report).into())
+            }
+        }
+    }
+
     fn handle_connection_response(&self, response: SignedResponse, agent_info: &AgentInfo) -> VcxResult<Response> {
         trace!("RequestedState:handle_connection_response >>> response: {:?}, agent_info: {:?}", response, agent_info);
 
-        let remote_vk: String = self.did_doc.recipient_keys().get(0).cloned()
-            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, "Cannot handle Response: Remote Verkey not found"))?;
-
-        let response: Response = response.decode(&remote_vk)?;
+        let response: Response = response.decode(&self.remote_vk()?)?;
 
-        if !response.from_thread(&self.request.id.0) {
-            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot handle Response: thread id does not match: {:?}", response.thread)));
-        }
+        self.check_thread(&response)?;
 
         let message = if response.please_ack.is_some() {
             Ack::create()
@@ -223,6 +338,38 @@ impl RequestedState {
 
         Ok(response)
     }
+
+    // DID Exchange Response is confirmed by Complete message instead of Ack
+    fn handle_didexchange_response(&self, response: DidExchangeResponse, agent_info: &AgentInfo) -> VcxResult<Response> {
+        trace!("RequestedState:handle_didexchange_response >>> response: {:?}, agent_info: {:?}", response, agent_info);
+
+        let response: Response = response.to_connection_response(&self.remote_vk()?)?;
+
+        self.check_thread(&response)?;
+
+        let mut complete = Complete::create()
+            .set_thread_id(&self.request.id.0);
+
+        if let Some(ref invitation_id) = self.invitation_id {
+            complete = complete.set_parent_thread_id(invitation_id);
+        }
+
+        agent_info.send_message(&complete.to_a2a_message(), &response.connection.did_doc)?;
+
+        Ok(response)
+    }
+
+    fn remote_vk(&self) -> VcxResult<String> {
+        self.did_doc.recipient_keys().get(0).cloned()
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, "Cannot handle Response: Remote Verkey not found"))
+    }
+
+    fn check_thread(&self, response: &Response) -> VcxResult<()> {
+        if !response.from_thread(&self.request.id.0) {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot handle Response: thread id does not match: {:?}", response.thread)));
+        }
+        Ok(())
+    }
 }
 
 impl RespondedState {
@@ -235,15 +382,14 @@ impl CompleteState {
     fn handle_message(self, message: DidExchangeMessages, agent_info: &AgentInfo) -> VcxResult<DidExchangeState> {
         Ok(match message {
             DidExchangeMessages::SendPing(comment) => {
-                self.handle_send_ping(comment, agent_info)?;
-                DidExchangeState::Completed(self)
+                DidExchangeState::Completed(self.handle_send_ping(comment, agent_info)?)
             }
             DidExchangeMessages::PingReceived(ping) => {
                 self.handle_ping(&ping, agent_info)?;
                 DidExchangeState::Completed(self)
             }
-            DidExchangeMessages::PingResponseReceived(_) => {
-                DidExchangeState::Completed(self)
+            DidExchangeMessages::PingResponseReceived(ping_response) => {
+                DidExchangeState::Completed(self.handle_ping_response(&ping_response))
             }
             DidExchangeMessages::DiscoverFeatures((query_, comment)) => {
                 self.handle_discover_features(query_, comment, agent_info)?;
@@ -256,20 +402,48 @@ impl CompleteState {
             DidExchangeMessages::DiscloseReceived(disclose) => {
                 DidExchangeState::Completed((self, disclose.protocols).into())
             }
+            DidExchangeMessages::SendHandshakeReuse(invitation) => {
+                self.handle_send_handshake_reuse(&invitation, agent_info)?;
+                DidExchangeState::Completed(self)
+            }
+            DidExchangeMessages::HandshakeReuseReceived(reuse) => {
+                self.handle_handshake_reuse(&reuse, agent_info)?;
+                DidExchangeState::Completed(self)
+            }
+            DidExchangeMessages::HandshakeReuseAcceptedReceived(accepted) => {
+                debug!("Out-of-Band invitation {:?} was answered by existing connection", accepted.thread.pthid);
+                DidExchangeState::Completed(self)
+            }
             _ => {
                 DidExchangeState::Completed(self)
             }
         })
     }
 
-    fn handle_send_ping(&self, comment: Option<String>, agent_info: &AgentInfo) -> VcxResult<()> {
+    fn handle_send_ping(mut self, comment: Option<String>, agent_info: &AgentInfo) -> VcxResult<CompleteState> {
         let ping =
             Ping::create()
                 .request_response()
                 .set_comment(comment);
 
-        agent_info.send_message(&ping.to_a2a_message(), &self.did_doc).ok();
-        Ok(())
+        if agent_info.send_message(&ping.to_a2a_message(), &self.did_doc).is_ok() {
+            self.last_ping = Some(PingStatus {
+                ping_id: ping.id.0.clone(),
+                sent_at: ::time::get_time().sec,
+                response_received_at: None,
+            });
+        }
+
+        Ok(self)
+    }
+
+    fn handle_ping_response(mut self, ping_response: &PingResponse) -> CompleteState {
+        if let Some(ref mut last_ping) = self.last_ping {
+            if ping_response.from_thread(&last_ping.ping_id) {
+                last_ping.response_received_at = Some(::time::get_time().sec);
+            }
+        }
+        self
     }
 
     fn handle_ping(&self, ping: &Ping, agent_info: &AgentInfo) -> VcxResult<()> {
@@ -294,12 +468,24 @@ impl CompleteState {
 
         agent_info.send_message(&disclose.to_a2a_message(), &self.did_doc)
     }
+
+    fn handle_send_handshake_reuse(&self, invitation: &OutOfBandInvitation, agent_info: &AgentInfo) -> VcxResult<()> {
+        let reuse = HandshakeReuse::create()
+            .set_invitation_id(&invitation.id.0);
+
+        agent_info.send_message(&reuse.to_a2a_message(), &self.did_doc)
+    }
+
+    fn handle_handshake_reuse(&self, reuse: &HandshakeReuse, agent_info: &AgentInfo) -> VcxResult<()> {
+        let accepted = HandshakeReuseAccepted::for_reuse(reuse);
+
+        agent_info.send_message(&accepted.to_a2a_message(), &self.did_doc)
+    }
 }
 
 fn _handle_ping(ping: &Ping, agent_info: &AgentInfo, did_doc: &DidDoc) -> VcxResult<()> {
     if ping.response_requested {
-        let ping_response = PingResponse::create().set_thread_id(
-            &ping.thread.as_ref().and_then(|thread| thread.thid.clone()).unwrap_or(ping.id.0.clone()));
+        let ping_response = PingResponse::create().set_thread_id(ping.thread_id().unwrap_or(&ping.id.0));
         agent_info.send_message(&ping_response.to_a2a_message(), did_doc)?;
     }
     Ok(())
@@ -311,25 +497,28 @@ impl DidExchangeSM {
             Actor::Inviter => {
                 DidExchangeSM {
                     source_id: source_id.to_string(),
-                    state: ActorDidExchangeState::Inviter(DidExchangeState::Null(NullState {})),
+                    state: ActorDidExchangeState::Inviter(DidExchangeState::Null(NullState::default())),
                     agent_info: AgentInfo::default(),
+                    their_public_did: None,
                 }
             }
             Actor::Invitee => {
                 DidExchangeSM {
                     source_id: source_id.to_string(),
-                    state: ActorDidExchangeState::Invitee(DidExchangeState::Null(NullState {})),
+                    state: ActorDidExchangeState::Invitee(DidExchangeState::Null(NullState::default())),
                     agent_info: AgentInfo::default(),
+                    their_public_did: None,
                 }
             }
         }
     }
 
-    pub fn from(source_id: String, agent_info: AgentInfo, state: ActorDidExchangeState) -> Self {
+    pub fn from(source_id: String, agent_info: AgentInfo, state: ActorDidExchangeState, their_public_did: Option<String>) -> Self {
         DidExchangeSM {
             source_id,
             agent_info,
             state,
+            their_public_did,
         }
     }
 
@@ -337,10 +526,34 @@ impl DidExchangeSM {
         &self.agent_info
     }
 
+    pub fn set_tls_constraints(&mut self, tls: Option<TlsConstraints>) {
+        self.agent_info.set_tls_constraints(tls)
+    }
+
+    pub fn set_envelope_format(&mut self, envelope: EnvelopeFormat) -> VcxResult<()> {
+        self.agent_info.set_envelope_format(envelope)
+    }
+
+    pub fn set_return_route(&mut self, return_route: bool) {
+        self.agent_info.set_return_route(return_route)
+    }
+
+    pub fn set_auto_accept(&mut self, auto_accept: AutoAcceptPolicy) {
+        self.agent_info.set_auto_accept(auto_accept)
+    }
+
+    pub fn set_did_method(&mut self, did_method: DidMethod) {
+        self.agent_info.set_did_method(did_method)
+    }
+
     pub fn source_id(&self) -> &str {
         &self.source_id
     }
 
+    pub fn their_public_did(&self) -> Option<&str> {
+        self.their_public_did.as_ref().map(String::as_str)
+    }
+
     pub fn state(&self) -> u32 {
         match self.state {
             ActorDidExchangeState::Inviter(ref state) | ActorDidExchangeState::Invitee(ref state) => state.code(),
@@ -362,6 +575,10 @@ impl DidExchangeSM {
                             debug!("Inviter received ConnectionRequest message");
                             return Some((uid, request));
                         }
+                        request @ A2AMessage::DidExchangeRequest(_) => {
+                            debug!("Inviter received DidExchangeRequest message");
+                            return Some((uid, request));
+                        }
                         problem_report @ A2AMessage::ConnectionProblemReport(_) => {
                             debug!("Inviter received ProblemReport message");
                             return Some((uid, problem_report));
@@ -377,6 +594,10 @@ impl DidExchangeSM {
                             debug!("Invitee received ConnectionResponse message");
                             return Some((uid, response));
                         }
+                        response @ A2AMessage::DidExchangeResponse(_) => {
+                            debug!("Invitee received DidExchangeResponse message");
+                            return Some((uid, response));
+                        }
                         problem_report @ A2AMessage::ConnectionProblemReport(_) => {
                             debug!("Invitee received ProblemReport message");
                             return Some((uid, problem_report));
@@ -392,6 +613,10 @@ impl DidExchangeSM {
                             debug!("Ack message received");
                             return Some((uid, ack));
                         }
+                        complete @ A2AMessage::DidExchangeComplete(_) => {
+                            debug!("DidExchangeComplete message received");
+                            return Some((uid, complete));
+                        }
                         ping @ A2AMessage::Ping(_) => {
                             debug!("Ping message received");
                             return Some((uid, ping));
@@ -428,6 +653,14 @@ impl DidExchangeSM {
                             debug!("Disclose message received");
                             return Some((uid, disclose));
                         }
+                        reuse @ A2AMessage::HandshakeReuse(_) => {
+                            debug!("HandshakeReuse message received");
+                            return Some((uid, reuse));
+                        }
+                        accepted @ A2AMessage::HandshakeReuseAccepted(_) => {
+                            debug!("HandshakeReuseAccepted message received");
+                            return Some((uid, accepted));
+                        }
                         message @ _ => {
                             debug!("Unexpected message received in Completed state: {:?}", message);
                         }
@@ -445,7 +678,7 @@ impl DidExchangeSM {
     pub fn step(self, message: DidExchangeMessages) -> VcxResult<DidExchangeSM> {
         trace!("DidExchangeStateSM::step >>> message: {:?}", message);
 
-        let DidExchangeSM { source_id, mut agent_info, state } = self;
+        let DidExchangeSM { source_id, mut agent_info, state, mut their_public_did } = self;
 
         let state = match state {
             ActorDidExchangeState::Inviter(state) => {
@@ -455,10 +688,7 @@ impl DidExchangeSM {
                             DidExchangeMessages::Connect() => {
                                 agent_info = agent_info.create_agent()?;
 
-                                let invite: Invitation = Invitation::create()
-                                    .set_label(source_id.to_string())
-                                    .set_service_endpoint(agent_info.agency_endpoint()?)
-                                    .set_recipient_keys(agent_info.recipient_keys())
+                                let invite: Invitation = Invitation::build(source_id.to_string(), agent_info.recipient_keys(), agent_info.agency_endpoint()?)
                                     .set_routing_keys(agent_info.routing_keys()?);
 
                                 ActorDidExchangeState::Inviter(DidExchangeState::Invited((state, invite).into()))
@@ -471,22 +701,10 @@ impl DidExchangeSM {
                     DidExchangeState::Invited(state) => {
                         match message {
                             DidExchangeMessages::ExchangeRequestReceived(request) => {
-                                match state.handle_connection_request(&request, &agent_info) {
-                                    Ok((response, new_agent_info)) => {
-                                        let prev_agent_info = agent_info.clone();
-                                        agent_info = new_agent_info;
-                                        ActorDidExchangeState::Inviter(DidExchangeState::Responded((state, request, response, prev_agent_info).into()))
-                                    }
-                                    Err(err) => {
-                                        let problem_report = ProblemReport::create()
-                                            .set_problem_code(ProblemCode::RequestProcessingError)
-                                            .set_explain(err.to_string())
-                                            .set_thread_id(&request.id.0);
-
-                                        agent_info.send_message(&problem_report.to_a2a_message(), &request.connection.did_doc).ok(); // IS is possible?
-                                        ActorDidExchangeState::Inviter(DidExchangeState::Null((state, problem_report).into()))
-                                    }
-                                }
+                                ActorDidExchangeState::Inviter(state.handle_request(request, HandshakeProtocol::Connections, &mut agent_info))
+                            }
+                            DidExchangeMessages::DidExchangeRequestReceived(request) => {
+                                ActorDidExchangeState::Inviter(state.handle_didexchange_request(request, &mut agent_info))
                             }
                             DidExchangeMessages::ProblemReportReceived(problem_report) => {
                                 ActorDidExchangeState::Inviter(DidExchangeState::Null((state, problem_report).into()))
@@ -504,6 +722,9 @@ impl DidExchangeSM {
                             DidExchangeMessages::AckReceived(ack) => {
                                 ActorDidExchangeState::Inviter(DidExchangeState::Completed((state, ack).into()))
                             }
+                            DidExchangeMessages::DidExchangeCompleteReceived(complete) => {
+                                ActorDidExchangeState::Inviter(DidExchangeState::Completed((state, complete).into()))
+                            }
                             DidExchangeMessages::PingReceived(ping) => {
                                 state.handle_ping(&ping, &agent_info)?;
                                 ActorDidExchangeState::Inviter(DidExchangeState::Completed((state, ping).into()))
@@ -538,8 +759,18 @@ impl DidExchangeSM {
                     DidExchangeState::Null(state) => {
                         match message {
                             DidExchangeMessages::InvitationReceived(invitation) => {
+                                their_public_did = invitation.did.clone();
                                 ActorDidExchangeState::Invitee(DidExchangeState::Invited((state, invitation).into()))
                             }
+                            DidExchangeMessages::OutOfBandInvitationReceived(invitation) => {
+                                let protocol = match invitation.handshake_protocol() {
+                                    Some(MessageFamilies::DidExchange) => HandshakeProtocol::DidExchange,
+                                    _ => HandshakeProtocol::Connections,
+                                };
+                                let invitation = invitation.to_connection_invitation()?;
+                                their_public_did = invitation.did.clone();
+                                ActorDidExchangeState::Invitee(DidExchangeState::Invited((state, invitation, protocol).into()))
+                            }
                             _ => {
                                 ActorDidExchangeState::Invitee(DidExchangeState::Null(state))
                             }
@@ -552,11 +783,23 @@ impl DidExchangeSM {
 
                                 let request = Request::create()
                                     .set_label(source_id.to_string())
-                                    .set_did(agent_info.pw_did.to_string())
+                                    .set_did(agent_info.connection_did()?)
                                     .set_service_endpoint(agent_info.agency_endpoint()?)
                                     .set_keys(agent_info.recipient_keys(), agent_info.routing_keys()?);
 
-                                agent_info.send_message(&request.to_a2a_message(), &DidDoc::from(state.invitation.clone()))?;
+                                let (message, request) = match state.protocol {
+                                    HandshakeProtocol::Connections => (request.to_a2a_message(), request),
+                                    HandshakeProtocol::DidExchange => {
+                                        let request = DidExchangeRequest::create()
+                                            .set_label(request.label)
+                                            .set_did(request.connection.did)
+                                            .set_did_doc(&request.connection.did_doc)?
+                                            .set_invitation_id(&state.invitation.id.0);
+                                        (request.to_a2a_message(), request.to_connection_request()?)
+                                    }
+                                };
+
+                                agent_info.send_message(&message, &DidDoc::from(state.invitation.clone()))?;
                                 ActorDidExchangeState::Invitee(DidExchangeState::Requested((state, request).into()))
                             }
                             DidExchangeMessages::ProblemReportReceived(problem_report) => {
@@ -570,19 +813,12 @@ impl DidExchangeSM {
                     DidExchangeState::Requested(state) => {
                         match message {
                             DidExchangeMessages::ExchangeResponseReceived(response) => {
-                                match state.handle_connection_response(response, &agent_info) {
-                                    Ok(response) => {
-                                        ActorDidExchangeState::Invitee(DidExchangeState::Completed((state, response).into()))
-                                    }
-                                    Err(err) => {
-                                        let problem_report = ProblemReport::create()
-                                            .set_problem_code(ProblemCode::ResponseProcessingError)
-                                            .set_explain(err.to_string())
-                                            .set_thread_id(&state.request.id.0);
-                                        agent_info.send_message(&problem_report.to_a2a_message(), &state.did_doc).ok();
-                                        ActorDidExchangeState::Invitee(DidExchangeState::Null((state, problem_report).into()))
-                                    }
-                                }
+                                let result = state.handle_connection_response(response, &agent_info);
+                                ActorDidExchangeState::Invitee(state.complete(result, &agent_info))
+                            }
+                            DidExchangeMessages::DidExchangeResponseReceived(response) => {
+                                let result = state.handle_didexchange_response(response, &agent_info);
+                                ActorDidExchangeState::Invitee(state.complete(result, &agent_info))
                             }
                             DidExchangeMessages::ProblemReportReceived(problem_report) => {
                                 ActorDidExchangeState::Invitee(DidExchangeState::Null((state, problem_report).into()))
@@ -601,7 +837,7 @@ impl DidExchangeSM {
                 }
             }
         };
-        Ok(DidExchangeSM { source_id, agent_info, state })
+        Ok(DidExchangeSM { source_id, agent_info, state, their_public_did })
     }
 
     pub fn did_doc(&self) -> Option<DidDoc> {
@@ -625,6 +861,18 @@ impl DidExchangeSM {
         }
     }
 
+    // DIDDoc of the keys the remote side sends messages from. Unknown to the invitee until the response is accepted,
+    // as DIDDoc of the invitation contains the invitation key only.
+    pub fn remote_did_doc(&self) -> Option<DidDoc> {
+        match self.state {
+            ActorDidExchangeState::Inviter(DidExchangeState::Requested(ref state)) => Some(state.did_doc.clone()),
+            ActorDidExchangeState::Inviter(DidExchangeState::Responded(ref state)) => Some(state.did_doc.clone()),
+            ActorDidExchangeState::Inviter(DidExchangeState::Completed(ref state)) |
+            ActorDidExchangeState::Invitee(DidExchangeState::Completed(ref state)) => Some(state.did_doc.clone()),
+            _ => None
+        }
+    }
+
     pub fn get_invitation(&self) -> Option<&Invitation> {
         match self.state {
             ActorDidExchangeState::Inviter(DidExchangeState::Invited(ref state)) |
@@ -637,6 +885,22 @@ impl DidExchangeSM {
         ProtocolRegistry::init().protocols()
     }
 
+    pub fn get_last_ping(&self) -> Option<&PingStatus> {
+        match self.state {
+            ActorDidExchangeState::Inviter(DidExchangeState::Completed(ref state)) |
+            ActorDidExchangeState::Invitee(DidExchangeState::Completed(ref state)) => state.last_ping.as_ref(),
+            _ => None
+        }
+    }
+
+    pub fn get_problem_report(&self) -> Option<&ProblemReport> {
+        match self.state {
+            ActorDidExchangeState::Inviter(DidExchangeState::Null(ref state)) |
+            ActorDidExchangeState::Invitee(DidExchangeState::Null(ref state)) => state.problem_report.as_ref(),
+            _ => None
+        }
+    }
+
     pub fn get_remote_protocols(&self) -> Option<Vec<ProtocolDescriptor>> {
         match self.state {
             ActorDidExchangeState::Inviter(DidExchangeState::Completed(ref state)) |
@@ -685,7 +949,7 @@ pub mod test {
     use utils::devsetup::SetupAriesMocks;
     use v3::test::source_id;
     use v3::test::setup::AgencyModeSetup;
-    use v3::messages::connection::invite::tests::_invitation;
+    use v3::messages::connection::invite::tests::{_invitation, _public_did};
     use v3::messages::connection::request::tests::_request;
     use v3::messages::connection::response::tests::_signed_response;
     use v3::messages::connection::problem_report::tests::_problem_report;
@@ -694,6 +958,11 @@ pub mod test {
     use v3::messages::ack::tests::_ack;
     use v3::messages::discovery::query::tests::_query;
     use v3::messages::discovery::disclose::tests::_disclose;
+    use v3::messages::out_of_band::invitation::tests::_oob_invitation;
+    use v3::messages::out_of_band::handshake_reuse::tests::_handshake_reuse;
+    use v3::messages::out_of_band::handshake_reuse_accepted::tests::_handshake_reuse_accepted;
+    use v3::messages::didexchange::request::tests::_didexchange_request;
+    use v3::messages::didexchange::complete::tests::_complete;
 
     pub mod inviter {
         use super::*;
@@ -795,6 +1064,31 @@ pub mod test {
                 assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Null(_)), did_exchange_sm.state);
             }
 
+            #[test]
+            fn test_did_exchange_handle_didexchange_request_message_from_invited_state() {
+                let _setup = AgencyModeSetup::init();
+
+                let mut did_exchange_sm = inviter_sm().to_inviter_invited_state();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::DidExchangeRequestReceived(_didexchange_request())).unwrap();
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Responded(_)), did_exchange_sm.state);
+            }
+
+            #[test]
+            fn test_did_exchange_handle_didexchange_request_message_without_did_doc_from_invited_state() {
+                let _setup = AgencyModeSetup::init();
+
+                let mut did_exchange_sm = inviter_sm().to_inviter_invited_state();
+
+                let mut request = _didexchange_request();
+                request.did_doc_attach = Default::default();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::DidExchangeRequestReceived(request)).unwrap();
+
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Null(_)), did_exchange_sm.state);
+                assert!(did_exchange_sm.get_problem_report().is_some());
+            }
+
             #[test]
             fn test_did_exchange_handle_problem_report_message_from_invited_state() {
                 let _setup = AgencyModeSetup::init();
@@ -804,6 +1098,8 @@ pub mod test {
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::ProblemReportReceived(_problem_report())).unwrap();
 
                 assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Null(_)), did_exchange_sm.state);
+                assert_eq!(VcxStateType::VcxStateNone as u32, did_exchange_sm.state());
+                assert_eq!(Some(&_problem_report()), did_exchange_sm.get_problem_report());
             }
 
             #[test]
@@ -831,6 +1127,17 @@ pub mod test {
                 assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
             }
 
+            #[test]
+            fn test_did_exchange_handle_complete_message_from_responded_state() {
+                let _setup = AgencyModeSetup::init();
+
+                let mut did_exchange_sm = inviter_sm().to_inviter_responded_state();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::DidExchangeCompleteReceived(_complete())).unwrap();
+
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+            }
+
             #[test]
             fn test_did_exchange_handle_ping_message_from_responded_state() {
                 let _setup = AgencyModeSetup::init();
@@ -864,6 +1171,22 @@ pub mod test {
                 assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Responded(_)), did_exchange_sm.state);
             }
 
+            #[test]
+            fn test_did_exchange_tracks_ping_response_from_completed_state() {
+                let _setup = AgencyModeSetup::init();
+
+                let mut did_exchange_sm = inviter_sm().to_inviter_completed_state();
+                assert!(did_exchange_sm.get_last_ping().is_none());
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::SendPing(None)).unwrap();
+                let ping_id = did_exchange_sm.get_last_ping().unwrap().ping_id.clone();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::PingResponseReceived(PingResponse::create().set_thread_id(&ping_id))).unwrap();
+
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+                assert!(did_exchange_sm.get_last_ping().unwrap().response_received_at.is_some());
+            }
+
             #[test]
             fn test_did_exchange_handle_messages_from_completed_state() {
                 let _setup = AgencyModeSetup::init();
@@ -873,6 +1196,7 @@ pub mod test {
                 // Send Ping
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::SendPing(None)).unwrap();
                 assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+                assert!(did_exchange_sm.get_last_ping().is_some());
 
                 // Ping
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::PingReceived(_ping())).unwrap();
@@ -881,6 +1205,7 @@ pub mod test {
                 // Ping Response
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::PingResponseReceived(_ping_response())).unwrap();
                 assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+                assert!(did_exchange_sm.get_last_ping().unwrap().response_received_at.is_none());
 
                 // Discovery Features
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::DiscoverFeatures((None, None))).unwrap();
@@ -898,6 +1223,16 @@ pub mod test {
 
                 assert!(did_exchange_sm.get_remote_protocols().is_some());
 
+                // Handshake Reuse
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::SendHandshakeReuse(_oob_invitation())).unwrap();
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::HandshakeReuseReceived(_handshake_reuse())).unwrap();
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::HandshakeReuseAcceptedReceived(_handshake_reuse_accepted())).unwrap();
+                assert_match!(ActorDidExchangeState::Inviter(DidExchangeState::Completed(_)), did_exchange_sm.state);
+
                 // ignore
                 // Ack
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::AckReceived(_ack())).unwrap();
@@ -1091,6 +1426,19 @@ pub mod test {
                     assert_eq!("key_3", uid);
                     assert_match!(A2AMessage::Disclose(_), message);
                 }
+
+                // Handshake Reuse
+                {
+                    let messages = map!(
+                        "key_1".to_string() => A2AMessage::ConnectionRequest(_request()),
+                        "key_2".to_string() => A2AMessage::OutOfBandInvitation(_oob_invitation()),
+                        "key_3".to_string() => A2AMessage::HandshakeReuse(_handshake_reuse())
+                    );
+
+                    let (uid, message) = connection.find_message_to_handle(messages).unwrap();
+                    assert_eq!("key_3", uid);
+                    assert_match!(A2AMessage::HandshakeReuse(_), message);
+                }
             }
         }
 
@@ -1132,7 +1480,7 @@ pub mod test {
 
             pub fn to_invitee_completed_state(mut self) -> DidExchangeSM {
                 let key = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL".to_string();
-                let invitation = Invitation::default().set_recipient_keys(vec![key.clone()]);
+                let invitation = Invitation::build(String::new(), vec![key.clone()], String::new());
 
                 self = self.step(DidExchangeMessages::InvitationReceived(invitation)).unwrap();
                 self = self.step(DidExchangeMessages::Connect()).unwrap();
@@ -1150,6 +1498,12 @@ pub mod test {
                 .encode(&key).unwrap()
         }
 
+        fn _didexchange_invitation(key: &str) -> OutOfBandInvitation {
+            let mut invitation = OutOfBandInvitation::from(Invitation::build(String::new(), vec![key.to_string()], String::new()));
+            invitation.handshake_protocols = vec![MessageFamilies::DidExchange.id()];
+            invitation
+        }
+
         mod new {
             use super::*;
 
@@ -1187,6 +1541,58 @@ pub mod test {
                 assert_match!(ActorDidExchangeState::Invitee(DidExchangeState::Invited(_)), did_exchange_sm.state);
             }
 
+            #[test]
+            fn test_did_exchange_handle_invite_message_keeps_public_did() {
+                let _setup = AgencyModeSetup::init();
+
+                let did_exchange_sm = invitee_sm().step(DidExchangeMessages::InvitationReceived(_invitation())).unwrap();
+                assert_eq!(None, did_exchange_sm.their_public_did());
+
+                let invitation = _invitation().set_public_did(Some(_public_did()));
+                let did_exchange_sm = invitee_sm().step(DidExchangeMessages::InvitationReceived(invitation.clone())).unwrap();
+                assert_eq!(Some(_public_did().as_str()), did_exchange_sm.their_public_did());
+
+                let did_exchange_sm = invitee_sm().step(DidExchangeMessages::OutOfBandInvitationReceived(OutOfBandInvitation::from(invitation))).unwrap();
+                assert_eq!(Some(_public_did().as_str()), did_exchange_sm.their_public_did());
+            }
+
+            #[test]
+            fn test_did_exchange_handle_outofband_invite_message_from_null_state() {
+                let _setup = AgencyModeSetup::init();
+
+                let mut did_exchange_sm = invitee_sm();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::OutOfBandInvitationReceived(_oob_invitation())).unwrap();
+                assert_match!(ActorDidExchangeState::Invitee(DidExchangeState::Invited(InvitedState { protocol: HandshakeProtocol::Connections, .. })), did_exchange_sm.state);
+
+                let mut did_exchange_sm = invitee_sm();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::OutOfBandInvitationReceived(_didexchange_invitation("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL"))).unwrap();
+                assert_match!(ActorDidExchangeState::Invitee(DidExchangeState::Invited(InvitedState { protocol: HandshakeProtocol::DidExchange, .. })), did_exchange_sm.state);
+            }
+
+            #[test]
+            fn test_did_exchange_handle_didexchange_flow() {
+                let _setup = AgencyModeSetup::init();
+
+                let key = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+
+                let mut did_exchange_sm = invitee_sm();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::OutOfBandInvitationReceived(_didexchange_invitation(key))).unwrap();
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::Connect()).unwrap();
+                assert_match!(ActorDidExchangeState::Invitee(DidExchangeState::Requested(RequestedState { invitation_id: Some(_), .. })), did_exchange_sm.state);
+
+                let response = Response::default()
+                    .set_service_endpoint(_service_endpoint())
+                    .set_keys(vec![key.to_string()], vec![])
+                    .set_thread_id(&_request().id.0);
+                let response = DidExchangeResponse::from_connection_response(&response, key).unwrap();
+
+                did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::DidExchangeResponseReceived(response)).unwrap();
+                assert_match!(ActorDidExchangeState::Invitee(DidExchangeState::Completed(_)), did_exchange_sm.state);
+            }
+
             #[test]
             fn test_did_exchange_handle_other_message_from_null_state() {
                 let _setup = AgencyModeSetup::init();
@@ -1220,6 +1626,7 @@ pub mod test {
                 did_exchange_sm = did_exchange_sm.step(DidExchangeMessages::ProblemReportReceived(_problem_report())).unwrap();
 
                 assert_match!(ActorDidExchangeState::Invitee(DidExchangeState::Null(_)), did_exchange_sm.state);
+                assert_eq!(Some(&_problem_report()), did_exchange_sm.get_problem_report());
             }
 
             #[test]
@@ -0,0 +1,74 @@
+use error::prelude::*;
+use utils::libindy::wallet::{add_record, search_records};
+use v3::messages::issuance::consent::ConsentReceipt;
+
+const CONSENT_RECEIPT_RECORD_TYPE: &str = "VcxConsentReceipt";
+const SEARCH_OPTIONS: &str = r#"{"retrieveRecords":true,"retrieveTotalCount":false,"retrieveType":false,"retrieveValue":true,"retrieveTags":false}"#;
+const MAX_RECEIPTS: usize = 1000;
+
+#[derive(Deserialize)]
+struct SearchResult {
+    records: Option<Vec<SearchRecord>>
+}
+
+#[derive(Deserialize)]
+struct SearchRecord {
+    value: String
+}
+
+// Receipts are tagged to be found by the credential definition, the thread of issuance and the Holder key
+pub fn store_receipt(receipt: &ConsentReceipt, cred_def_id: &str) -> VcxResult<()> {
+    trace!("consent::store_receipt >>> receipt_id: {}, cred_def_id: {}", receipt.data.receipt_id, cred_def_id);
+
+    let tags = json!({
+        "cred_def_id": cred_def_id,
+        "thread_id": receipt.data.thread_id,
+        "holder_verkey": receipt.data.holder_verkey,
+        "purpose": receipt.data.template.purpose,
+    });
+
+    add_record(CONSENT_RECEIPT_RECORD_TYPE, &receipt.data.receipt_id, &json!(receipt).to_string(), Some(&tags.to_string()))
+}
+
+// Query is WQL over tags `cred_def_id`, `thread_id`, `holder_verkey` and `purpose`
+pub fn get_receipts(query: Option<&str>) -> VcxResult<Vec<ConsentReceipt>> {
+    trace!("consent::get_receipts >>> query: {:?}", query);
+
+    let records = search_records(CONSENT_RECEIPT_RECORD_TYPE, query.unwrap_or("{}"), SEARCH_OPTIONS, MAX_RECEIPTS)?;
+
+    let result: SearchResult = ::serde_json::from_str(&records)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Consent Receipt records: {:?}", err)))?;
+
+    result.records.unwrap_or_default()
+        .iter()
+        .map(|record| ::serde_json::from_str::<ConsentReceipt>(&record.value)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Consent Receipt: {:?}", err))))
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use utils::devsetup::{SetupAriesMocks, SetupLibraryWallet};
+    use v3::messages::issuance::consent::tests::{_consent_receipt, _consent_receipt_data};
+
+    #[test]
+    fn test_get_receipts_works_for_mocks() {
+        let _setup = SetupAriesMocks::init();
+
+        store_receipt(&_consent_receipt(), "cred_def_id").unwrap();
+        assert!(get_receipts(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_store_and_get_receipts_works() {
+        let _setup = SetupLibraryWallet::init();
+
+        let receipt = ConsentReceipt { data: _consent_receipt_data(), signature: String::from("signature") };
+        store_receipt(&receipt, "cred_def_id").unwrap();
+
+        assert_eq!(vec![receipt.clone()], get_receipts(None).unwrap());
+        assert_eq!(vec![receipt], get_receipts(Some(r#"{"cred_def_id":"cred_def_id"}"#)).unwrap());
+        assert!(get_receipts(Some(r#"{"cred_def_id":"other"}"#)).unwrap().is_empty());
+    }
+}
@@ -2,16 +2,22 @@ use api::VcxStateType;
 
 use v3::handlers::issuance::states::{HolderState, OfferReceivedState};
 use v3::handlers::issuance::messages::CredentialIssuanceMessage;
+use v3::handlers::issuance::protocol::IssuanceProtocol;
+use v3::handlers::issuance::revocation;
 use v3::messages::issuance::credential::Credential;
+use v3::messages::issuance::CredentialPreviewData;
 use v3::messages::issuance::credential_offer::CredentialOffer;
 use v3::messages::issuance::credential_request::CredentialRequest;
 use v3::messages::issuance::credential_ack::CredentialAck;
-use v3::messages::error::ProblemReport;
+use v3::messages::issuance::consent::ConsentReceipt;
+use v3::messages::error::{ProblemReport, Impact, codes};
+use v3::messages::ack::Ack;
 use v3::messages::a2a::A2AMessage;
 use v3::messages::status::Status;
 use connection;
 
-use utils::libindy::anoncreds::{self, libindy_prover_store_credential, libindy_prover_delete_credential};
+use utils::libindy::anoncreds::{self, libindy_prover_store_credential, libindy_prover_delete_credential_with_related};
+use utils::libindy::cache;
 use error::prelude::*;
 use std::collections::HashMap;
 
@@ -21,7 +27,9 @@ use credential;
 pub struct HolderSM {
     state: HolderState,
     source_id: String,
-    thread_id: String
+    thread_id: String,
+    #[serde(default)]
+    protocol: IssuanceProtocol,
 }
 
 impl HolderSM {
@@ -30,6 +38,7 @@ impl HolderSM {
             thread_id: offer.id.0.clone(),
             state: HolderState::OfferReceived(OfferReceivedState::new(offer)),
             source_id,
+            protocol: IssuanceProtocol::default(),
         }
     }
 
@@ -37,12 +46,18 @@ impl HolderSM {
         self.source_id.clone()
     }
 
+    pub fn thread_id(&self) -> String {
+        self.thread_id.clone()
+    }
+
     pub fn state(&self) -> u32 {
         match self.state {
             HolderState::OfferReceived(_) => VcxStateType::VcxStateRequestReceived as u32,
+            HolderState::ProposalSent(_) => VcxStateType::VcxStateOfferSent as u32,
             HolderState::RequestSent(_) => VcxStateType::VcxStateOfferSent as u32,
             HolderState::Finished(ref status) => {
                 match status.status {
+                    Status::Success if status.revoked => VcxStateType::VcxStateRevoked as u32,
                     Status::Success => VcxStateType::VcxStateAccepted as u32,
                     _ => VcxStateType::VcxStateNone as u32,
                 }
@@ -53,7 +68,7 @@ impl HolderSM {
     pub fn update_state(self) -> VcxResult<Self> {
         trace!("Holder::update_state >>> ");
 
-        if self.is_terminal_state() { return Ok(self); }
+        if self.is_terminal_state() && !self.awaits_revocation_notification() { return Ok(self); }
 
         let conn_handle = self.state.get_connection_handle();
         let messages = connection::get_messages(conn_handle)?;
@@ -73,10 +88,33 @@ impl HolderSM {
         trace!("Holder::find_message_to_handle >>> messages: {:?}", messages);
 
         for (uid, message) in messages {
+            let message = match IssuanceProtocol::decode(message) {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!("Holder::find_message_to_handle: skipping message {}: {}", uid, err);
+                    continue;
+                }
+            };
+
             match self.state {
                 HolderState::OfferReceived(_) => {
                     // do not process messages
                 }
+                HolderState::ProposalSent(_) => {
+                    match message {
+                        A2AMessage::CredentialOffer(offer) => {
+                            if offer.from_thread(&self.thread_id) {
+                                return Some((uid, A2AMessage::CredentialOffer(offer)));
+                            }
+                        }
+                        A2AMessage::CommonProblemReport(problem_report) => {
+                            if problem_report.from_thread(&self.thread_id) {
+                                return Some((uid, A2AMessage::CommonProblemReport(problem_report)));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
                 HolderState::RequestSent(_) => {
                     match message {
                         A2AMessage::Credential(credential) => {
@@ -93,7 +131,14 @@ impl HolderSM {
                     }
                 }
                 HolderState::Finished(_) => {
-                    // do not process messages
+                    match message {
+                        A2AMessage::RevocationNotification(revoke) => {
+                            if revoke.thread_id == self.thread_id {
+                                return Some((uid, A2AMessage::RevocationNotification(revoke)));
+                            }
+                        }
+                        _ => {}
+                    }
                 }
             };
         }
@@ -105,39 +150,58 @@ impl HolderSM {
         self.state.get_connection_handle()
     }
 
-    pub fn step(state: HolderState, source_id: String, thread_id: String) -> Self {
-        HolderSM { state, source_id, thread_id }
+    pub fn step(state: HolderState, source_id: String, thread_id: String, protocol: IssuanceProtocol) -> Self {
+        HolderSM { state, source_id, thread_id, protocol }
     }
 
     pub fn handle_message(self, cim: CredentialIssuanceMessage) -> VcxResult<HolderSM> {
         trace!("Holder::handle_message >>> cim: {:?}", cim);
 
-        let HolderSM { state, source_id, thread_id } = self;
+        let HolderSM { state, source_id, thread_id, mut protocol } = self;
         let state = match state {
             HolderState::OfferReceived(state_data) => match cim {
                 CredentialIssuanceMessage::CredentialRequestSend(connection_handle) => {
-                    let request = _make_credential_request(connection_handle, &state_data.offer);
+                    protocol = IssuanceProtocol::for_connection(connection_handle);
+                    let request = _make_credential_request(connection_handle, &state_data.offer, &thread_id);
                     match request {
                         Ok((cred_request, req_meta, cred_def_json)) => {
-                            let cred_request = cred_request
-                                .set_thread_id(&thread_id);
-                            connection::send_message(connection_handle, cred_request.to_a2a_message())?;
+                            protocol.send_message(connection_handle, cred_request.to_a2a_message())?;
                             HolderState::RequestSent((state_data, req_meta, cred_def_json, connection_handle).into())
                         }
                         Err(err) => {
                             let problem_report = ProblemReport::create()
+                                .set_description(codes::MESSAGE_PROCESSING_ERROR)
+                                .set_impact(Impact::Thread)
                                 .set_comment(err.to_string())
                                 .set_thread_id(&thread_id);
-                            connection::send_message(connection_handle, problem_report.to_a2a_message())?;
+                            protocol.send_message(connection_handle, problem_report.to_a2a_message())?;
                             HolderState::Finished((state_data, problem_report).into())
                         }
                     }
                 }
+                CredentialIssuanceMessage::CredentialProposalSend((connection_handle, proposal)) => {
+                    protocol = IssuanceProtocol::for_connection(connection_handle);
+                    let proposal = proposal.set_thread_id(&thread_id);
+                    protocol.send_message(connection_handle, proposal.to_a2a_message())?;
+                    HolderState::ProposalSent((state_data, proposal, connection_handle).into())
+                }
                 _ => {
                     warn!("Credential Issuance can only start on holder side with Credential Offer");
                     HolderState::OfferReceived(state_data)
                 }
             },
+            HolderState::ProposalSent(state_data) => match cim {
+                CredentialIssuanceMessage::CredentialOffer(offer) => {
+                    HolderState::OfferReceived((state_data, offer).into())
+                }
+                CredentialIssuanceMessage::ProblemReport(problem_report) => {
+                    HolderState::Finished((state_data, problem_report).into())
+                }
+                _ => {
+                    warn!("In this state Credential Issuance can accept only Credential Offer and Problem Report");
+                    HolderState::ProposalSent(state_data)
+                }
+            },
             HolderState::RequestSent(state_data) => match cim {
                 CredentialIssuanceMessage::Credential(credential) => {
                     let result = _store_credential(&credential, &state_data.req_meta, &state_data.cred_def_json);
@@ -145,17 +209,19 @@ impl HolderSM {
                         Ok((cred_id, rev_reg_def_json)) => {
                             if credential.please_ack.is_some() {
                                 let ack = CredentialAck::create().set_thread_id(&thread_id);
-                                connection::send_message(state_data.connection_handle, A2AMessage::CredentialAck(ack))?;
+                                protocol.send_message(state_data.connection_handle, A2AMessage::CredentialAck(ack))?;
                             }
 
                             HolderState::Finished((state_data, cred_id, credential, rev_reg_def_json).into())
                         }
                         Err(err) => {
                             let problem_report = ProblemReport::create()
+                                .set_description(codes::MESSAGE_PROCESSING_ERROR)
+                                .set_impact(Impact::Thread)
                                 .set_comment(err.to_string())
                                 .set_thread_id(&thread_id);
 
-                            connection::send_message(state_data.connection_handle, problem_report.to_a2a_message())?;
+                            protocol.send_message(state_data.connection_handle, problem_report.to_a2a_message())?;
                             HolderState::Finished((state_data, problem_report).into())
                         }
                     }
@@ -168,12 +234,44 @@ impl HolderSM {
                     HolderState::RequestSent(state_data)
                 }
             },
-            HolderState::Finished(state_data) => {
-                warn!("Exchange is finished, no messages can be sent or received");
-                HolderState::Finished(state_data)
+            HolderState::Finished(mut state_data) => match cim {
+                CredentialIssuanceMessage::RevocationNotification(ref revoke) if state_data.status == Status::Success => {
+                    if let Some(ref cred_id) = state_data.cred_id {
+                        revocation::store_revocation(cred_id, revoke)?;
+                    }
+                    if let (Some(_), Some(connection_handle)) = (&revoke.please_ack, state_data.connection_handle) {
+                        let ack = Ack::create().set_thread_id(&revoke.id.0);
+                        protocol.send_message(connection_handle, A2AMessage::Ack(ack))?;
+                    }
+                    trace!("Credential is revoked");
+                    state_data.revoked = true;
+                    HolderState::Finished(state_data)
+                }
+                _ => {
+                    warn!("Exchange is finished, only Revocation Notification can be received");
+                    HolderState::Finished(state_data)
+                }
             }
         };
-        Ok(HolderSM::step(state, source_id, thread_id))
+        Ok(HolderSM::step(state, source_id, thread_id, protocol))
+    }
+
+    pub fn get_credential_preview(&self) -> VcxResult<CredentialPreviewData> {
+        match self.state {
+            HolderState::OfferReceived(ref state) => Ok(state.offer.credential_preview.clone()),
+            HolderState::ProposalSent(ref state) => Ok(state.offer.credential_preview.clone()),
+            HolderState::RequestSent(ref state) => state.credential_preview.clone()
+                .ok_or_else(|| VcxError::from_msg(VcxErrorKind::NotReady, "Credential Preview is not available")),
+            HolderState::Finished(ref state) => state.credential_preview.clone()
+                .ok_or_else(|| VcxError::from_msg(VcxErrorKind::NotReady, "Credential Preview is not available")),
+        }
+    }
+
+    pub fn get_credential_offer(&self) -> VcxResult<CredentialOffer> {
+        match self.state {
+            HolderState::OfferReceived(ref state) => Ok(state.offer.clone()),
+            _ => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Credential Offer is not received"))
+        }
     }
 
     pub fn credential_status(&self) -> u32 {
@@ -183,6 +281,14 @@ impl HolderSM {
         }
     }
 
+    // Problem Report either received from Issuer or sent on failure
+    pub fn get_problem_report(&self) -> Option<ProblemReport> {
+        match self.state {
+            HolderState::Finished(ref state) => state.status.problem_report().cloned(),
+            _ => None
+        }
+    }
+
     pub fn is_terminal_state(&self) -> bool {
         match self.state {
             HolderState::Finished(_) => true,
@@ -190,6 +296,14 @@ impl HolderSM {
         }
     }
 
+    // Issued credential stays bound to the connection until Issuer notifies about its revocation
+    fn awaits_revocation_notification(&self) -> bool {
+        match self.state {
+            HolderState::Finished(ref state) => state.status == Status::Success && !state.revoked && state.connection_handle.is_some(),
+            _ => false
+        }
+    }
+
     pub fn get_credential(&self) -> VcxResult<(String, Credential)> {
         match self.state {
             HolderState::Finished(ref state) => {
@@ -207,7 +321,11 @@ impl HolderSM {
         match self.state {
             HolderState::Finished(ref state) => {
                 let cred_id = state.cred_id.clone().ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, "Cannot get credential: credential id not found"))?;
-                _delete_credential(&cred_id)
+                let rev_reg_id = match state.credential {
+                    Some(ref credential) => _parse_rev_reg_id_from_credential(&credential.credentials_attach.content()?)?,
+                    None => None
+                };
+                _delete_credential(&cred_id, rev_reg_id.as_ref().map(String::as_str))
             }
             _ => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Cannot delete credential: credential issuance is not finished yet"))
         }
@@ -258,21 +376,40 @@ fn _store_credential(credential: &Credential,
     Ok((cred_id, rev_reg_def_json))
 }
 
-fn _delete_credential(cred_id: &str) -> VcxResult<()> {
-    trace!("Holder::_delete_credential >>> cred_id: {}", cred_id);
+// Deletes the credential together with the cached revocation state of its registry
+// unless the registry is still used by other credentials of the wallet.
+fn _delete_credential(cred_id: &str, rev_reg_id: Option<&str>) -> VcxResult<()> {
+    trace!("Holder::_delete_credential >>> cred_id: {}, rev_reg_id: {:?}", cred_id, rev_reg_id);
+
+    let mut related_records = Vec::new();
+
+    if let Some(rev_reg_id) = rev_reg_id {
+        if anoncreds::libindy_prover_count_credentials(&json!({"rev_reg_id": rev_reg_id}).to_string())? <= 1 {
+            related_records.push(cache::rev_reg_cache_record_id(rev_reg_id));
+        }
+    }
 
-    libindy_prover_delete_credential(cred_id)
+    libindy_prover_delete_credential_with_related(cred_id, &json!(related_records).to_string())
+        .map(|_| ())
 }
 
-fn _make_credential_request(conn_handle: u32, offer: &CredentialOffer) -> VcxResult<(CredentialRequest, String, String)> {
-    trace!("Holder::_make_credential_request >>> conn_handle: {:?}, offer: {:?}", conn_handle, offer);
+fn _make_credential_request(conn_handle: u32, offer: &CredentialOffer, thread_id: &str) -> VcxResult<(CredentialRequest, String, String)> {
+    trace!("Holder::_make_credential_request >>> conn_handle: {:?}, offer: {:?}, thread_id: {:?}", conn_handle, offer, thread_id);
 
     let my_did = connection::get_pw_did(conn_handle)?;
     let cred_offer = offer.offers_attach.content()?;
     let cred_def_id = _parse_cred_def_from_cred_offer(&cred_offer)?;
     let (req, req_meta, _cred_def_id, cred_def_json) =
         credential::Credential::create_credential_request(&cred_def_id, &my_did, &cred_offer)?;
-    Ok((CredentialRequest::create().set_requests_attach(req)?, req_meta, cred_def_json))
+    let mut request = CredentialRequest::build(req, thread_id)?;
+
+    // Sending of request to the offer asking for consent means Holder agrees with its terms
+    if let Some(ref consent) = offer.consent {
+        let my_vk = connection::get_pw_verkey(conn_handle)?;
+        request = request.set_consent_receipt(ConsentReceipt::sign(consent, thread_id, &my_vk)?);
+    }
+
+    Ok((request, req_meta, cred_def_json))
 }
 
 #[cfg(test)]
@@ -287,6 +424,8 @@ mod test {
     use v3::messages::issuance::credential_request::tests::_credential_request;
     use v3::messages::issuance::credential_proposal::tests::_credential_proposal;
     use v3::messages::issuance::test::{_ack, _problem_report};
+    use v3::messages::issuance::consent::tests::_consent_template;
+    use v3::messages::revocation_notification::revoke::tests::_revoke;
 
     fn _holder_sm() -> HolderSM {
         HolderSM::new(_credential_offer(), source_id())
@@ -298,6 +437,11 @@ mod test {
             self
         }
 
+        fn to_proposal_sent_state(mut self) -> HolderSM {
+            self = self.handle_message(CredentialIssuanceMessage::CredentialProposalSend((mock_connection(), _credential_proposal()))).unwrap();
+            self
+        }
+
         fn to_finished_state(mut self) -> HolderSM {
             self = self.handle_message(CredentialIssuanceMessage::CredentialRequestSend(mock_connection())).unwrap();
             self = self.handle_message(CredentialIssuanceMessage::Credential(_credential())).unwrap();
@@ -340,17 +484,35 @@ mod test {
             assert_match!(HolderState::RequestSent(_), holder_sm.state);
         }
 
+        #[test]
+        fn test_holder_make_credential_request_signs_consent_receipt() {
+            let _setup = SetupAriesMocks::init();
+
+            let connection_handle = mock_connection();
+            let thread_id = _credential_offer().id.0;
+
+            let (request, _, _) = _make_credential_request(connection_handle, &_credential_offer(), &thread_id).unwrap();
+            assert!(request.consent_receipt.is_none());
+
+            let offer = _credential_offer().set_consent(Some(_consent_template()));
+            let (request, _, _) = _make_credential_request(connection_handle, &offer, &thread_id).unwrap();
+
+            let holder_verkey = connection::get_pw_verkey(connection_handle).unwrap();
+            request.consent_receipt.unwrap().verify(&_consent_template(), &thread_id, &holder_verkey).unwrap();
+        }
+
         #[test]
         fn test_issuer_handle_credential_request_sent_message_from_offer_received_state_for_invalid_offer() {
             let _setup = SetupAriesMocks::init();
 
-            let credential_offer = CredentialOffer::create().set_offers_attach(r#"{"credential offer": {}}"#).unwrap();
+            let credential_offer = CredentialOffer::build(r#"{"credential offer": {}}"#, CredentialPreviewData::new(), None).unwrap();
 
             let mut holder_sm = HolderSM::new(credential_offer, "test source".to_string());
             holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::CredentialRequestSend(mock_connection())).unwrap();
 
             assert_match!(HolderState::Finished(_), holder_sm.state);
             assert_eq!(Status::Failed(ProblemReport::default()).code(), holder_sm.credential_status());
+            assert_eq!(Some(_problem_report()), holder_sm.get_problem_report());
         }
 
         #[test]
@@ -366,6 +528,56 @@ mod test {
             assert_match!(HolderState::OfferReceived(_), holder_sm.state);
         }
 
+        #[test]
+        fn test_holder_handle_credential_proposal_send_message_from_offer_received_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut holder_sm = _holder_sm();
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::CredentialProposalSend((mock_connection(), _credential_proposal()))).unwrap();
+
+            assert_match!(HolderState::ProposalSent(_), holder_sm.state);
+        }
+
+        #[test]
+        fn test_holder_handle_credential_offer_message_from_proposal_sent_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let counter_offer = _credential_offer().set_comment(String::from("counter offer"));
+
+            let mut holder_sm = _holder_sm().to_proposal_sent_state();
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::CredentialOffer(counter_offer.clone())).unwrap();
+
+            assert_match!(HolderState::OfferReceived(_), holder_sm.state);
+            assert_eq!(counter_offer, holder_sm.get_credential_offer().unwrap());
+
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::CredentialRequestSend(mock_connection())).unwrap();
+            assert_match!(HolderState::RequestSent(_), holder_sm.state);
+        }
+
+        #[test]
+        fn test_holder_handle_problem_report_from_proposal_sent_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut holder_sm = _holder_sm().to_proposal_sent_state();
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::ProblemReport(_problem_report())).unwrap();
+
+            assert_match!(HolderState::Finished(_), holder_sm.state);
+            assert_eq!(Status::Failed(ProblemReport::default()).code(), holder_sm.credential_status());
+        }
+
+        #[test]
+        fn test_holder_handle_other_messages_from_proposal_sent_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut holder_sm = _holder_sm().to_proposal_sent_state();
+
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::CredentialRequestSend(mock_connection())).unwrap();
+            assert_match!(HolderState::ProposalSent(_), holder_sm.state);
+
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::Credential(_credential())).unwrap();
+            assert_match!(HolderState::ProposalSent(_), holder_sm.state);
+        }
+
         #[test]
         fn test_issuer_handle_credential_message_from_request_sent_state() {
             let _setup = SetupAriesMocks::init();
@@ -433,6 +645,33 @@ mod test {
             holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::CredentialAck(_ack())).unwrap();
             assert_match!(HolderState::Finished(_), holder_sm.state);
         }
+
+        #[test]
+        fn test_holder_handle_revocation_notification_from_finished_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut holder_sm = _holder_sm().to_finished_state();
+            assert_eq!(VcxStateType::VcxStateAccepted as u32, holder_sm.state());
+            assert!(holder_sm.awaits_revocation_notification());
+
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::RevocationNotification(_revoke().ask_for_ack())).unwrap();
+            assert_match!(HolderState::Finished(_), holder_sm.state);
+            assert_eq!(VcxStateType::VcxStateRevoked as u32, holder_sm.state());
+            assert_eq!(Status::Success.code(), holder_sm.credential_status());
+            assert!(!holder_sm.awaits_revocation_notification());
+        }
+
+        #[test]
+        fn test_holder_handle_revocation_notification_for_failed_issuance() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut holder_sm = _holder_sm().to_request_sent_state();
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::ProblemReport(_problem_report())).unwrap();
+            assert!(!holder_sm.awaits_revocation_notification());
+
+            holder_sm = holder_sm.handle_message(CredentialIssuanceMessage::RevocationNotification(_revoke())).unwrap();
+            assert_eq!(VcxStateType::VcxStateNone as u32, holder_sm.state());
+        }
     }
 
     mod find_message_to_handle {
@@ -521,6 +760,48 @@ mod test {
             }
         }
 
+        #[test]
+        fn test_holder_find_message_to_handle_from_proposal_sent_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let holder = _holder_sm().to_proposal_sent_state();
+
+            // CredentialOffer
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::CredentialRequest(_credential_request()),
+                    "key_2".to_string() => A2AMessage::Credential(_credential()),
+                    "key_3".to_string() => A2AMessage::CredentialOffer(_credential_offer())
+                );
+
+                let (uid, message) = holder.find_message_to_handle(messages).unwrap();
+                assert_eq!("key_3", uid);
+                assert_match!(A2AMessage::CredentialOffer(_), message);
+            }
+
+            // Problem Report
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::CredentialRequest(_credential_request()),
+                    "key_2".to_string() => A2AMessage::CommonProblemReport(_problem_report())
+                );
+
+                let (uid, message) = holder.find_message_to_handle(messages).unwrap();
+                assert_eq!("key_2", uid);
+                assert_match!(A2AMessage::CommonProblemReport(_), message);
+            }
+
+            // No messages for different Thread ID
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::CredentialOffer(_credential_offer().set_thread_id("")),
+                    "key_2".to_string() => A2AMessage::CommonProblemReport(_problem_report().set_thread_id(""))
+                );
+
+                assert!(holder.find_message_to_handle(messages).is_none());
+            }
+        }
+
         #[test]
         fn test_holder_find_message_to_handle_from_finished_state() {
             let _setup = SetupAriesMocks::init();
@@ -540,6 +821,40 @@ mod test {
 
                 assert!(holder.find_message_to_handle(messages).is_none());
             }
+
+            // Revocation Notification
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::Credential(_credential()),
+                    "key_2".to_string() => A2AMessage::RevocationNotification(_revoke())
+                );
+
+                let (uid, message) = holder.find_message_to_handle(messages).unwrap();
+                assert_eq!("key_2", uid);
+                assert_match!(A2AMessage::RevocationNotification(_), message);
+            }
+
+            // No messages for different Thread ID
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::RevocationNotification(_revoke().set_credential_thread_id(""))
+                );
+
+                assert!(holder.find_message_to_handle(messages).is_none());
+            }
+        }
+    }
+
+    mod get_credential_preview {
+        use super::*;
+
+        #[test]
+        fn test_holder_credential_preview_is_kept_with_credential() {
+            let _setup = SetupAriesMocks::init();
+
+            assert_eq!(_credential_offer().credential_preview, _holder_sm().get_credential_preview().unwrap());
+            assert_eq!(_credential_offer().credential_preview, _holder_sm().to_request_sent_state().get_credential_preview().unwrap());
+            assert_eq!(_credential_offer().credential_preview, _holder_sm().to_finished_state().get_credential_preview().unwrap());
         }
     }
 
@@ -551,6 +866,7 @@ mod test {
             let _setup = SetupAriesMocks::init();
 
             assert_eq!(VcxStateType::VcxStateRequestReceived as u32, _holder_sm().state());
+            assert_eq!(VcxStateType::VcxStateOfferSent as u32, _holder_sm().to_proposal_sent_state().state());
             assert_eq!(VcxStateType::VcxStateOfferSent as u32, _holder_sm().to_request_sent_state().state());
             assert_eq!(VcxStateType::VcxStateAccepted as u32, _holder_sm().to_finished_state().state());
         }
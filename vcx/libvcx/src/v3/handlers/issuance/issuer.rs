@@ -1,31 +1,46 @@
 use api::VcxStateType;
 use v3::handlers::issuance::messages::CredentialIssuanceMessage;
-use v3::handlers::issuance::states::{IssuerState, InitialState };
+use v3::handlers::issuance::states::{IssuerState, InitialState, OfferSentState};
+use v3::handlers::issuance::consent;
+use v3::handlers::issuance::protocol::IssuanceProtocol;
 use v3::messages::a2a::A2AMessage;
+use v3::messages::issuance::{CredentialPreviewData, DisplayHints};
 use v3::messages::issuance::credential_offer::CredentialOffer;
+use v3::messages::issuance::credential_proposal::CredentialProposal;
 use v3::messages::issuance::credential_request::CredentialRequest;
 use v3::messages::issuance::credential::Credential;
-use v3::messages::error::ProblemReport;
+use v3::messages::issuance::consent::ConsentTemplate;
+use v3::messages::revocation_notification::revoke::Revoke;
+use v3::messages::error::{ProblemReport, Impact, codes};
 use v3::messages::mime_type::MimeType;
 use error::{VcxResult, VcxError, VcxErrorKind};
 use utils::libindy::anoncreds::{self, libindy_issuer_create_credential_offer, revoke_credential};
 use issuer_credential::encode_attributes;
 use v3::messages::status::Status;
 use std::collections::HashMap;
-use connection::{send_message, get_messages};
+use connection::get_messages;
 use connection;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct IssuerSM {
     state: IssuerState,
-    source_id: String
+    source_id: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    display_hints: HashMap<String, DisplayHints>,
+    #[serde(default)]
+    protocol: IssuanceProtocol,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    consent_template: Option<ConsentTemplate>,
 }
 
 impl IssuerSM {
     pub fn new(cred_def_id: &str, credential_data: &str, rev_reg_id: Option<String>, tails_file: Option<String>, source_id: &str) -> Self {
         IssuerSM {
             state: IssuerState::Initial(InitialState::new(cred_def_id, credential_data, rev_reg_id, tails_file)),
-            source_id: source_id.to_string()
+            source_id: source_id.to_string(),
+            display_hints: HashMap::new(),
+            protocol: IssuanceProtocol::default(),
+            consent_template: None,
         }
     }
 
@@ -33,10 +48,54 @@ impl IssuerSM {
         self.source_id.clone()
     }
 
-    pub fn step(state: IssuerState, source_id: String) -> Self {
+    pub fn thread_id(&self) -> String {
+        self.state.thread_id()
+    }
+
+    pub fn get_credential_data(&self) -> VcxResult<String> {
+        let cred_data = match self.state {
+            IssuerState::Initial(ref state) => Some(&state.credential_json),
+            IssuerState::OfferSent(ref state) => Some(&state.cred_data),
+            IssuerState::ProposalReceived(ref state) => Some(&state.cred_data),
+            IssuerState::RequestReceived(ref state) => Some(&state.cred_data),
+            IssuerState::CredentialSent(ref state) => state.cred_data.as_ref(),
+            IssuerState::Finished(ref state) => state.cred_data.as_ref(),
+        };
+
+        cred_data
+            .cloned()
+            .ok_or_else(|| VcxError::from_msg(VcxErrorKind::NotReady, "Credential data is not available for this Issuer object"))
+    }
+
+    pub fn step(state: IssuerState, source_id: String, display_hints: HashMap<String, DisplayHints>, protocol: IssuanceProtocol, consent_template: Option<ConsentTemplate>) -> Self {
         IssuerSM {
             state,
-            source_id
+            source_id,
+            display_hints,
+            protocol,
+            consent_template,
+        }
+    }
+
+    // Display hints are attached to the credential preview of offers sent since now.
+    pub fn set_display_hints(&mut self, display_hints: HashMap<String, DisplayHints>) -> VcxResult<()> {
+        match self.state {
+            IssuerState::Initial(_) | IssuerState::ProposalReceived(_) => {
+                self.display_hints = display_hints;
+                Ok(())
+            }
+            _ => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Display hints can be set only before sending of Credential Offer"))
+        }
+    }
+
+    // Holder is asked to sign the consent receipt for these terms within the Credential Request.
+    pub fn set_consent_template(&mut self, consent_template: ConsentTemplate) -> VcxResult<()> {
+        match self.state {
+            IssuerState::Initial(_) | IssuerState::ProposalReceived(_) => {
+                self.consent_template = Some(consent_template);
+                Ok(())
+            }
+            _ => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Consent template can be set only before sending of Credential Offer"))
         }
     }
 
@@ -86,6 +145,14 @@ impl IssuerSM {
         trace!("Issuer::find_message_to_handle >>> messages: {:?}", messages);
 
         for (uid, message) in messages {
+            let message = match IssuanceProtocol::decode(message) {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!("Issuer::find_message_to_handle: skipping message {}: {}", uid, err);
+                    continue;
+                }
+            };
+
             match self.state {
                 IssuerState::Initial(_) => {
                     // do not process messages
@@ -98,10 +165,8 @@ impl IssuerSM {
                             }
                         }
                         A2AMessage::CredentialProposal(credential_proposal) => {
-                            if let Some(ref thread) = credential_proposal.thread {
-                                if thread.is_reply(&self.state.thread_id()) {
-                                    return Some((uid, A2AMessage::CredentialProposal(credential_proposal)));
-                                }
+                            if credential_proposal.from_thread(&self.state.thread_id()) {
+                                return Some((uid, A2AMessage::CredentialProposal(credential_proposal)));
                             }
                         }
                         A2AMessage::CommonProblemReport(problem_report) => {
@@ -112,6 +177,9 @@ impl IssuerSM {
                         _ => {}
                     }
                 }
+                IssuerState::ProposalReceived(_) => {
+                    // waits for counter-offer
+                }
                 IssuerState::RequestReceived(_) => {
                     // do not process messages
                 }
@@ -143,6 +211,7 @@ impl IssuerSM {
         match self.state {
             IssuerState::Initial(_) => VcxStateType::VcxStateInitialized as u32,
             IssuerState::OfferSent(_) => VcxStateType::VcxStateOfferSent as u32,
+            IssuerState::ProposalReceived(_) => VcxStateType::VcxStateRequestReceived as u32,
             IssuerState::RequestReceived(_) => VcxStateType::VcxStateRequestReceived as u32,
             IssuerState::CredentialSent(_) => VcxStateType::VcxStateAccepted as u32,
             IssuerState::Finished(ref status) => {
@@ -157,15 +226,16 @@ impl IssuerSM {
     pub fn handle_message(self, cim: CredentialIssuanceMessage) -> VcxResult<IssuerSM> {
         trace!("IssuerSM::handle_message >>> cim: {:?}", cim);
 
-        let IssuerSM { state, source_id } = self;
+        let IssuerSM { state, source_id, display_hints, mut protocol, consent_template } = self;
         let state = match state {
             IssuerState::Initial(state_data) => match cim {
                 CredentialIssuanceMessage::CredentialInit(connection_handle) => {
                     let cred_offer = libindy_issuer_create_credential_offer(&state_data.cred_def_id)?;
-                    let cred_offer_msg = CredentialOffer::create()
-                        .set_offers_attach(&cred_offer)?;
-                    let cred_offer_msg = _append_credential_preview(cred_offer_msg, &state_data.credential_json)?;
-                    send_message(connection_handle, cred_offer_msg.to_a2a_message())?;
+                    let preview = _credential_preview(&state_data.credential_json)?.set_display_hints(&display_hints);
+                    let cred_offer_msg = CredentialOffer::build(&cred_offer, preview, None)?
+                        .set_consent(consent_template.clone());
+                    protocol = IssuanceProtocol::for_connection(connection_handle);
+                    protocol.send_message(connection_handle, cred_offer_msg.to_a2a_message())?;
                     IssuerState::OfferSent((state_data, cred_offer, connection_handle, cred_offer_msg.id).into())
                 }
                 _ => {
@@ -175,15 +245,22 @@ impl IssuerSM {
             }
             IssuerState::OfferSent(state_data) => match cim {
                 CredentialIssuanceMessage::CredentialRequest(request) => {
-                    IssuerState::RequestReceived((state_data, request).into())
-                }
-                CredentialIssuanceMessage::CredentialProposal(_) => {
-                    let problem_report = ProblemReport::create()
-                        .set_comment(String::from("CredentialProposal is not supported"))
-                        .set_thread_id(&state_data.thread_id);
+                    match _check_consent_receipt(&consent_template, &request, &state_data) {
+                        Ok(()) => IssuerState::RequestReceived((state_data, request).into()),
+                        Err(err) => {
+                            let problem_report = ProblemReport::create()
+                                .set_description(codes::REQUEST_NOT_ACCEPTED)
+                                .set_impact(Impact::Thread)
+                                .set_comment(err.to_string())
+                                .set_thread_id(&state_data.thread_id);
 
-                    send_message(state_data.connection_handle, problem_report.to_a2a_message())?;
-                    IssuerState::Finished((state_data, problem_report).into())
+                            protocol.send_message(state_data.connection_handle, problem_report.to_a2a_message())?;
+                            IssuerState::Finished((state_data, problem_report).into())
+                        }
+                    }
+                }
+                CredentialIssuanceMessage::CredentialProposal(proposal) => {
+                    IssuerState::ProposalReceived((state_data, proposal).into())
                 }
                 CredentialIssuanceMessage::ProblemReport(problem_report) => {
                     IssuerState::Finished((state_data, problem_report).into())
@@ -193,21 +270,41 @@ impl IssuerSM {
                     IssuerState::OfferSent(state_data)
                 }
             },
-            IssuerState::RequestReceived(state_data) => match cim {
+            IssuerState::ProposalReceived(state_data) => match cim {
+                CredentialIssuanceMessage::CredentialCounterOfferSend(cred_data) => {
+                    let preview = _credential_preview(&cred_data)?.set_display_hints(&display_hints);
+                    let cred_offer_msg = CredentialOffer::build(&state_data.offer, preview, Some(&state_data.thread_id))?
+                        .set_consent(consent_template.clone());
+                    protocol.send_message(state_data.connection_handle, cred_offer_msg.to_a2a_message())?;
+                    IssuerState::OfferSent((state_data, cred_data).into())
+                }
+                CredentialIssuanceMessage::ProblemReport(problem_report) => {
+                    IssuerState::Finished((state_data, problem_report).into())
+                }
+                _ => {
+                    warn!("In this state Credential Issuance can accept only CounterOfferSend and Problem Report");
+                    IssuerState::ProposalReceived(state_data)
+                }
+            },
+            IssuerState::RequestReceived(mut state_data) => match cim {
                 CredentialIssuanceMessage::CredentialSend() => {
                     let credential_msg = _create_credential(&state_data.request, &state_data.rev_reg_id, &state_data.tails_file, &state_data.offer, &state_data.cred_data);
                     match credential_msg {
-                        Ok((credential_msg, cred_rev_id)) => {
+                        Ok((credential_msg, cred_rev_id, rev_reg_id)) => {
+                            // the registry is rotated when the one of the credential definition is full
+                            state_data.rev_reg_id = rev_reg_id;
                             let credential_msg = credential_msg.set_thread_id(&state_data.thread_id);
-                            send_message(state_data.connection_handle, credential_msg.to_a2a_message())?;
+                            protocol.send_message(state_data.connection_handle, credential_msg.to_a2a_message())?;
                             IssuerState::Finished((state_data, cred_rev_id).into())
                         }
                         Err(err) => {
                             let problem_report = ProblemReport::create()
+                                .set_description(codes::REQUEST_PROCESSING_ERROR)
+                                .set_impact(Impact::Thread)
                                 .set_comment(err.to_string())
                                 .set_thread_id(&state_data.thread_id);
 
-                            send_message(state_data.connection_handle, problem_report.to_a2a_message())?;
+                            protocol.send_message(state_data.connection_handle, problem_report.to_a2a_message())?;
                             IssuerState::Finished((state_data, problem_report).into())
                         }
                     }
@@ -231,13 +328,32 @@ impl IssuerSM {
                     IssuerState::CredentialSent(state_data)
                 }
             }
-            IssuerState::Finished(state_data) => {
-                warn!("Exchange is finished, no messages can be sent or received");
-                IssuerState::Finished(state_data)
+            IssuerState::Finished(state_data) => match cim {
+                CredentialIssuanceMessage::RevocationNotificationSend((connection_handle, comment)) => {
+                    if state_data.status != Status::Success {
+                        return Err(VcxError::from_msg(VcxErrorKind::NotReady, "Revocation Notification can be sent only for issued Credential"));
+                    }
+                    let revoke = Revoke::create()
+                        .set_credential_thread_id(&state_data.thread_id)
+                        .set_comment(comment);
+                    protocol.send_message(connection_handle, revoke.to_a2a_message())?;
+                    IssuerState::Finished(state_data)
+                }
+                _ => {
+                    warn!("Exchange is finished, only Revocation Notification can be sent");
+                    IssuerState::Finished(state_data)
+                }
             }
         };
 
-        Ok(IssuerSM::step(state, source_id))
+        Ok(IssuerSM::step(state, source_id, display_hints, protocol, consent_template))
+    }
+
+    pub fn get_credential_proposal(&self) -> VcxResult<CredentialProposal> {
+        match self.state {
+            IssuerState::ProposalReceived(ref state) => Ok(state.proposal.clone()),
+            _ => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Credential Proposal is not received"))
+        }
     }
 
     pub fn credential_status(&self) -> u32 {
@@ -249,6 +365,14 @@ impl IssuerSM {
         }
     }
 
+    // Problem Report either received from Holder or sent on failure
+    pub fn get_problem_report(&self) -> Option<ProblemReport> {
+        match self.state {
+            IssuerState::Finished(ref state) => state.status.problem_report().cloned(),
+            _ => None
+        }
+    }
+
     pub fn is_terminal_state(&self) -> bool {
         match self.state {
             IssuerState::Finished(_) => true,
@@ -258,8 +382,8 @@ impl IssuerSM {
 }
 
 
-fn _append_credential_preview(cred_offer_msg: CredentialOffer, credential_json: &str) -> VcxResult<CredentialOffer> {
-    trace!("Issuer::_append_credential_preview >>> cred_offer_msg: {:?}, credential_json: {:?}", cred_offer_msg, credential_json);
+fn _credential_preview(credential_json: &str) -> VcxResult<CredentialPreviewData> {
+    trace!("Issuer::_credential_preview >>> credential_json: {:?}", credential_json);
 
     let cred_values: serde_json::Value = serde_json::from_str(credential_json)
         .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Invalid Credential Preview Json: {:?}", err)))?;
@@ -267,34 +391,56 @@ fn _append_credential_preview(cred_offer_msg: CredentialOffer, credential_json:
     let values_map = cred_values.as_object()
         .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid Credential Preview Json".to_string()))?;
 
-    let mut new_offer = cred_offer_msg;
+    let mut preview = CredentialPreviewData::new();
     for item in values_map.iter() {
         let (key, value) = item;
-        new_offer = new_offer.add_credential_preview_data(
+        preview = preview.add_value(
             key,
             value.as_str()
                 .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid Credential Preview Json".to_string()))?,
             MimeType::Plain,
         )?;
     }
-    Ok(new_offer)
+    Ok(preview)
 }
 
-fn _create_credential(request: &CredentialRequest, rev_reg_id: &Option<String>, tails_file: &Option<String>, offer: &str, cred_data: &str) -> VcxResult<(Credential, Option<String>)> {
+// Receipt is required only if consent was asked in the offer. Valid receipt is stored in the wallet.
+fn _check_consent_receipt(consent_template: &Option<ConsentTemplate>, request: &CredentialRequest, state: &OfferSentState) -> VcxResult<()> {
+    trace!("Issuer::_check_consent_receipt >>> consent_template: {:?}", consent_template);
+
+    let consent_template = match consent_template {
+        Some(consent_template) => consent_template,
+        None => return Ok(())
+    };
+
+    let receipt = request.consent_receipt.as_ref()
+        .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, "Credential Request doesn't contain Consent Receipt"))?;
+
+    let holder_verkey = connection::get_their_pw_verkey(state.connection_handle)?;
+    receipt.verify(consent_template, &state.thread_id, &holder_verkey)?;
+
+    let offer: serde_json::Value = serde_json::from_str(&state.offer)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Invalid Credential Offer Json: {:?}", err)))?;
+    let cred_def_id = offer["cred_def_id"].as_str().unwrap_or_default();
+
+    consent::store_receipt(receipt, cred_def_id)
+}
+
+fn _create_credential(request: &CredentialRequest, rev_reg_id: &Option<String>, tails_file: &Option<String>, offer: &str, cred_data: &str) -> VcxResult<(Credential, Option<String>, Option<String>)> {
     trace!("Issuer::_create_credential >>> request: {:?}, rev_reg_id: {:?}, tails_file: {:?}, offer: {:?}, cred_data: {:?}", request, rev_reg_id, tails_file, offer, cred_data);
 
     let request = &request.requests_attach.content()?;
 
     let cred_data = encode_attributes(cred_data)?;
 
-    let (ser_credential, cred_rev_id, _) = anoncreds::libindy_issuer_create_credential(offer,
-                                                                         &request,
-                                                                         &cred_data,
-                                                                         rev_reg_id.clone(),
-                                                                         tails_file.clone())?;
+    let (ser_credential, cred_rev_id, _, rev_reg_id) = anoncreds::issuer_create_credential_rotating(offer,
+                                                                                                 &request,
+                                                                                                 &cred_data,
+                                                                                                 rev_reg_id.clone(),
+                                                                                                 tails_file.clone())?;
     let credential = Credential::create().set_credential(ser_credential)?;
 
-    Ok((credential, cred_rev_id))
+    Ok((credential, cred_rev_id, rev_reg_id))
 }
 
 #[cfg(test)]
@@ -309,6 +455,8 @@ pub mod test {
     use v3::messages::issuance::credential_request::tests::_credential_request;
     use v3::messages::issuance::credential_proposal::tests::_credential_proposal;
     use v3::messages::issuance::credential_offer::tests::_credential_offer;
+    use v3::messages::issuance::consent::ConsentReceipt;
+    use v3::messages::issuance::consent::tests::_consent_template;
 
     fn _issuer_sm() -> IssuerSM {
         IssuerSM::new("test", &json!({"name": "alice"}).to_string(), None, None, &source_id())
@@ -346,6 +494,29 @@ pub mod test {
             assert_match!(IssuerState::Initial(_), issuer_sm.state);
             assert_eq!(source_id(), issuer_sm.get_source_id());
         }
+
+        #[test]
+        fn test_issuer_get_credential_data_is_kept_in_finished_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let issuer_sm = _issuer_sm().to_finished_state();
+
+            assert_match!(IssuerState::Finished(_), issuer_sm.state);
+            assert_eq!(json!({"name": "alice"}).to_string(), issuer_sm.get_credential_data().unwrap());
+        }
+
+        #[test]
+        fn test_issuer_serialization_drops_credential_data_in_finished_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let issuer_sm = _issuer_sm().to_finished_state();
+
+            let serialized = serde_json::to_string(&issuer_sm).unwrap();
+            assert!(!serialized.contains("alice"));
+
+            let issuer_sm: IssuerSM = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(VcxErrorKind::NotReady, issuer_sm.get_credential_data().unwrap_err().kind());
+        }
     }
 
     mod handle_message {
@@ -394,6 +565,45 @@ pub mod test {
             assert_match!(IssuerState::RequestReceived(_), issuer_sm.state);
         }
 
+        #[test]
+        fn test_issuer_handle_credential_request_message_from_offer_sent_state_for_consent() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+            issuer_sm.set_consent_template(_consent_template()).unwrap();
+            issuer_sm = issuer_sm.to_offer_sent_state();
+
+            let holder_verkey = connection::get_their_pw_verkey(issuer_sm.get_connection_handle()).unwrap();
+            let receipt = ConsentReceipt::sign(&_consent_template(), &issuer_sm.state.thread_id(), &holder_verkey).unwrap();
+
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialRequest(_credential_request().set_consent_receipt(receipt))).unwrap();
+
+            assert_match!(IssuerState::RequestReceived(_), issuer_sm.state);
+        }
+
+        #[test]
+        fn test_issuer_handle_credential_request_message_from_offer_sent_state_for_missed_consent_receipt() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+            issuer_sm.set_consent_template(_consent_template()).unwrap();
+            issuer_sm = issuer_sm.to_offer_sent_state();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialRequest(_credential_request())).unwrap();
+
+            assert_match!(IssuerState::Finished(_), issuer_sm.state);
+            assert_eq!(Status::Failed(ProblemReport::default()).code(), issuer_sm.credential_status());
+            assert_eq!(Some(_problem_report()), issuer_sm.get_problem_report());
+        }
+
+        #[test]
+        fn test_issuer_set_consent_template_fails_after_offer_sent() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm().to_offer_sent_state();
+
+            assert_eq!(VcxErrorKind::NotReady, issuer_sm.set_consent_template(_consent_template()).unwrap_err().kind());
+        }
+
         #[test]
         fn test_issuer_handle_credential_proposal_message_from_offer_sent_state() {
             let _setup = SetupAriesMocks::init();
@@ -402,10 +612,56 @@ pub mod test {
             issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialInit(mock_connection())).unwrap();
             issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialProposal(_credential_proposal())).unwrap();
 
+            assert_match!(IssuerState::ProposalReceived(_), issuer_sm.state);
+            assert_eq!(_credential_proposal(), issuer_sm.get_credential_proposal().unwrap());
+        }
+
+        #[test]
+        fn test_issuer_handle_counter_offer_send_message_from_proposal_received_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialInit(mock_connection())).unwrap();
+            let thread_id = issuer_sm.state.thread_id();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialProposal(_credential_proposal())).unwrap();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialCounterOfferSend(json!({"name": "bob"}).to_string())).unwrap();
+
+            assert_match!(IssuerState::OfferSent(_), issuer_sm.state);
+            assert_eq!(thread_id, issuer_sm.state.thread_id());
+            assert_eq!(json!({"name": "bob"}).to_string(), issuer_sm.get_credential_data().unwrap());
+
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialRequest(_credential_request())).unwrap();
+            assert_match!(IssuerState::RequestReceived(_), issuer_sm.state);
+        }
+
+        #[test]
+        fn test_issuer_handle_problem_report_message_from_proposal_received_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialInit(mock_connection())).unwrap();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialProposal(_credential_proposal())).unwrap();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::ProblemReport(_problem_report())).unwrap();
+
             assert_match!(IssuerState::Finished(_), issuer_sm.state);
             assert_eq!(Status::Failed(ProblemReport::default()).code(), issuer_sm.credential_status());
         }
 
+        #[test]
+        fn test_issuer_handle_other_messages_from_proposal_received_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialInit(mock_connection())).unwrap();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialProposal(_credential_proposal())).unwrap();
+
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialSend()).unwrap();
+            assert_match!(IssuerState::ProposalReceived(_), issuer_sm.state);
+
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialRequest(_credential_request())).unwrap();
+            assert_match!(IssuerState::ProposalReceived(_), issuer_sm.state);
+        }
+
         #[test]
         fn test_issuer_handle_problem_report_message_from_offer_sent_state() {
             let _setup = SetupAriesMocks::init();
@@ -448,7 +704,7 @@ pub mod test {
 
             let mut issuer_sm = _issuer_sm();
             issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialInit(mock_connection())).unwrap();
-            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialRequest(CredentialRequest::create())).unwrap();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialRequest(CredentialRequest::default())).unwrap();
             issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialSend()).unwrap();
 
             assert_match!(IssuerState::Finished(_), issuer_sm.state);
@@ -491,6 +747,28 @@ pub mod test {
             issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::Credential(_credential())).unwrap();
             assert_match!(IssuerState::Finished(_), issuer_sm.state);
         }
+
+        #[test]
+        fn test_issuer_handle_revocation_notification_send_from_finished_state() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm().to_finished_state();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::RevocationNotificationSend((mock_connection(), None))).unwrap();
+            assert_match!(IssuerState::Finished(_), issuer_sm.state);
+            assert_eq!(VcxStateType::VcxStateAccepted as u32, issuer_sm.state());
+        }
+
+        #[test]
+        fn test_issuer_handle_revocation_notification_send_for_failed_issuance() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::CredentialInit(mock_connection())).unwrap();
+            issuer_sm = issuer_sm.handle_message(CredentialIssuanceMessage::ProblemReport(_problem_report())).unwrap();
+
+            let err = issuer_sm.handle_message(CredentialIssuanceMessage::RevocationNotificationSend((mock_connection(), None))).unwrap_err();
+            assert_eq!(VcxErrorKind::NotReady, err.kind());
+        }
     }
 
     mod find_message_to_handle {
@@ -536,6 +814,18 @@ pub mod test {
                 assert_match!(A2AMessage::CredentialRequest(_), message);
             }
 
+            // CredentialRequest of issue-credential 2.0
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::CredentialOffer(_credential_offer()),
+                    "key_2".to_string() => A2AMessage::CredentialRequestV2(_credential_request().into())
+                );
+
+                let (uid, message) = issuer.find_message_to_handle(messages).unwrap();
+                assert_eq!("key_2", uid);
+                assert_eq!(A2AMessage::CredentialRequest(_credential_request()), message);
+            }
+
             // CredentialProposal
             {
                 let messages = map!(
@@ -631,6 +921,28 @@ pub mod test {
         }
     }
 
+    mod display_hints {
+        use super::*;
+
+        #[test]
+        fn test_issuer_set_display_hints_works() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut issuer_sm = _issuer_sm();
+
+            let display = DisplayHints { label: Some(String::from("Name")), order: Some(1), ..DisplayHints::default() };
+            issuer_sm.set_display_hints(map!("name".to_string() => display.clone())).unwrap();
+            assert_eq!(Some(&display), issuer_sm.display_hints.get("name"));
+
+            issuer_sm = issuer_sm.to_offer_sent_state();
+            assert!(issuer_sm.set_display_hints(HashMap::new()).is_err());
+
+            let serialized = ::serde_json::to_string(&issuer_sm).unwrap();
+            let deserialized: IssuerSM = ::serde_json::from_str(&serialized).unwrap();
+            assert_eq!(Some(&display), deserialized.display_hints.get("name"));
+        }
+    }
+
     mod get_state {
         use super::*;
 
@@ -4,25 +4,36 @@ use v3::messages::issuance::credential_offer::CredentialOffer;
 use v3::messages::issuance::credential_request::CredentialRequest;
 use v3::messages::issuance::credential::Credential;
 use v3::messages::issuance::credential_ack::CredentialAck;
+use v3::messages::revocation_notification::revoke::Revoke;
 use v3::messages::a2a::A2AMessage;
+use v3::handlers::issuance::protocol::IssuanceProtocol;
 
 
 #[derive(Debug, Clone)]
 pub enum CredentialIssuanceMessage {
     CredentialInit(u32),
     CredentialSend(),
+    CredentialProposalSend((u32, CredentialProposal)),
     CredentialProposal(CredentialProposal),
+    CredentialCounterOfferSend(String),
     CredentialOffer(CredentialOffer),
     CredentialRequestSend(u32),
     CredentialRequest(CredentialRequest),
     Credential(Credential),
     CredentialAck(CredentialAck),
     ProblemReport(ProblemReport),
+    RevocationNotificationSend((u32, Option<String>)),
+    RevocationNotification(Revoke),
     Unknown
 }
 
 impl From<A2AMessage> for CredentialIssuanceMessage {
     fn from(msg: A2AMessage) -> Self {
+        let msg = match IssuanceProtocol::decode(msg) {
+            Ok(msg) => msg,
+            Err(_) => return CredentialIssuanceMessage::Unknown
+        };
+
         match msg {
             A2AMessage::CredentialProposal(proposal) => {
                 CredentialIssuanceMessage::CredentialProposal(proposal)
@@ -42,6 +53,9 @@ impl From<A2AMessage> for CredentialIssuanceMessage {
             A2AMessage::CommonProblemReport(report) => {
                 CredentialIssuanceMessage::ProblemReport(report)
             },
+            A2AMessage::RevocationNotification(revoke) => {
+                CredentialIssuanceMessage::RevocationNotification(revoke)
+            },
             _ => {
                 CredentialIssuanceMessage::Unknown
             }
@@ -2,21 +2,34 @@ pub mod issuer;
 pub mod states;
 pub mod messages;
 pub mod holder;
+pub mod protocol;
+pub mod consent;
+pub mod revocation;
 
 use error::prelude::*;
 use v3::messages::a2a::A2AMessage;
 use v3::handlers::issuance::issuer::IssuerSM;
 use v3::handlers::issuance::messages::CredentialIssuanceMessage;
 use v3::handlers::issuance::holder::HolderSM;
+use v3::handlers::issuance::protocol::IssuanceProtocol;
+use v3::messages::issuance::{CredentialPreviewData, DisplayHints};
 use v3::messages::issuance::credential::Credential;
 use v3::messages::issuance::credential_offer::CredentialOffer;
+use v3::messages::issuance::credential_proposal::CredentialProposal;
+use v3::messages::issuance::consent::ConsentTemplate;
 use connection;
+use settings::AutoAcceptOffers;
+use api::VcxStateType;
+use issuer_credential::DerivationLink;
+use std::collections::HashMap;
 
 // Issuer
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Issuer {
-    issuer_sm: IssuerSM
+    issuer_sm: IssuerSM,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    derived_from: Option<DerivationLink>,
 }
 
 impl Issuer {
@@ -27,13 +40,35 @@ impl Issuer {
         let rev_reg_id = ::credential_def::get_rev_reg_id(cred_def_handle)?;
         let tails_file = ::credential_def::get_tails_file(cred_def_handle)?;
         let issuer_sm = IssuerSM::new(&cred_def_id, credential_data, rev_reg_id, tails_file, source_id);
-        Ok(Issuer { issuer_sm })
+        Ok(Issuer { issuer_sm, derived_from: None })
     }
 
     pub fn send_credential_offer(&mut self, connection_handle: u32) -> VcxResult<()> {
         self.step(CredentialIssuanceMessage::CredentialInit(connection_handle))
     }
 
+    pub fn get_credential_proposal(&self) -> VcxResult<CredentialProposal> {
+        self.issuer_sm.get_credential_proposal()
+    }
+
+    // Answers the received Credential Proposal with the offer of updated credential data
+    pub fn send_counter_offer(&mut self, credential_data: &str) -> VcxResult<()> {
+        self.step(CredentialIssuanceMessage::CredentialCounterOfferSend(credential_data.to_string()))
+    }
+
+    // Sets display hints as JSON map `attribute name -> {label, order, group, locale}`
+    pub fn set_display_hints(&mut self, display_hints: &str) -> VcxResult<()> {
+        let display_hints: HashMap<String, DisplayHints> = ::serde_json::from_str(display_hints)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Display Hints: {:?}", err)))?;
+        self.issuer_sm.set_display_hints(display_hints)
+    }
+
+    // Sets consent template as JSON `{purpose, data_categories, retention}`
+    pub fn set_consent_template(&mut self, consent_template: &str) -> VcxResult<()> {
+        let consent_template = ConsentTemplate::from_json(consent_template)?;
+        self.issuer_sm.set_consent_template(consent_template)
+    }
+
     pub fn send_credential(&mut self, _connection_handle: u32) -> VcxResult<()> { // TODO: should use connection_handle
         self.step(CredentialIssuanceMessage::CredentialSend())
     }
@@ -46,10 +81,31 @@ impl Issuer {
         Ok(self.issuer_sm.get_source_id())
     }
 
+    pub fn get_thread_id(&self) -> String {
+        self.issuer_sm.thread_id()
+    }
+
+    pub fn get_credential_data(&self) -> VcxResult<String> {
+        self.issuer_sm.get_credential_data()
+    }
+
+    pub fn get_derived_from(&self) -> Option<&DerivationLink> {
+        self.derived_from.as_ref()
+    }
+
+    pub fn set_derived_from(&mut self, derived_from: Option<DerivationLink>) {
+        self.derived_from = derived_from;
+    }
+
     pub fn revoke_credential(&self) -> VcxResult<()> {
         self.issuer_sm.revoke()
     }
 
+    // Notifies Holder that the credential is revoked so it does not need to check the ledger
+    pub fn send_revocation_notification(&mut self, connection_handle: u32, comment: Option<String>) -> VcxResult<()> {
+        self.step(CredentialIssuanceMessage::RevocationNotificationSend((connection_handle, comment)))
+    }
+
     pub fn update_status(&mut self, msg: Option<String>) -> VcxResult<()> {
         match msg {
             Some(msg) => {
@@ -69,6 +125,12 @@ impl Issuer {
         Ok(self.issuer_sm.credential_status())
     }
 
+    pub fn get_problem_report(&self) -> VcxResult<String> {
+        self.issuer_sm.get_problem_report()
+            .map(|problem_report| json!(problem_report.to_a2a_message()).to_string())
+            .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "No Problem Report was sent or received"))
+    }
+
     pub fn step(&mut self, message: CredentialIssuanceMessage) -> VcxResult<()> {
         self.issuer_sm = self.issuer_sm.clone().handle_message(message)?;
         Ok(())
@@ -95,6 +157,19 @@ impl Holder {
         self.step(CredentialIssuanceMessage::CredentialRequestSend(connection_handle))
     }
 
+    // Answers the received Credential Offer with the proposal of other terms
+    pub fn send_proposal(&mut self, connection_handle: u32, proposal: CredentialProposal) -> VcxResult<()> {
+        self.step(CredentialIssuanceMessage::CredentialProposalSend((connection_handle, proposal)))
+    }
+
+    pub fn get_credential_offer(&self) -> VcxResult<CredentialOffer> {
+        self.holder_sm.get_credential_offer()
+    }
+
+    pub fn get_credential_preview(&self) -> VcxResult<CredentialPreviewData> {
+        self.holder_sm.get_credential_preview()
+    }
+
     pub fn update_state(&mut self, msg: Option<String>) -> VcxResult<()> {
         match msg {
             Some(msg) => {
@@ -104,12 +179,33 @@ impl Holder {
                 self.step(message.into())
             }
             None => {
+                let connection_handle = self.holder_sm.get_connection_handle();
+                let offer_received = VcxStateType::VcxStateRequestReceived as u32;
+                let state = self.holder_sm.state();
+
                 self.holder_sm = self.holder_sm.clone().update_state()?;
+
+                // Issuer answered our proposal with a new Credential Offer
+                if state != offer_received && self.holder_sm.state() == offer_received {
+                    self.auto_accept_offer(connection_handle)?;
+                }
+
                 Ok(())
             }
         }
     }
 
+    // Sends Credential Request if the auto-accept policy of the connection allows it. Returns whether it was sent.
+    pub fn auto_accept_offer(&mut self, connection_handle: u32) -> VcxResult<bool> {
+        if connection::get_auto_accept(connection_handle)?.credential_offers() != AutoAcceptOffers::Always {
+            return Ok(false);
+        }
+
+        debug!("Holder::auto_accept_offer: sending Credential Request over connection {}", connection_handle);
+        self.send_request(connection_handle)?;
+        Ok(true)
+    }
+
     pub fn get_status(&self) -> u32 {
         self.holder_sm.state()
     }
@@ -118,6 +214,10 @@ impl Holder {
         self.holder_sm.get_source_id()
     }
 
+    pub fn get_thread_id(&self) -> String {
+        self.holder_sm.thread_id()
+    }
+
     pub fn get_credential(&self) -> VcxResult<(String, Credential)> {
         self.holder_sm.get_credential()
     }
@@ -130,6 +230,12 @@ impl Holder {
         Ok(self.holder_sm.credential_status())
     }
 
+    pub fn get_problem_report(&self) -> VcxResult<String> {
+        self.holder_sm.get_problem_report()
+            .map(|problem_report| json!(problem_report.to_a2a_message()).to_string())
+            .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "No Problem Report was sent or received"))
+    }
+
     pub fn step(&mut self, message: CredentialIssuanceMessage) -> VcxResult<()> {
         self.holder_sm = self.holder_sm.clone().handle_message(message)?;
         Ok(())
@@ -138,7 +244,7 @@ impl Holder {
     pub fn get_credential_offer_message(connection_handle: u32, msg_id: &str) -> VcxResult<CredentialOffer> {
         let message = connection::get_message_by_id(connection_handle, msg_id.to_string())?;
 
-        let credential_offer: CredentialOffer = match message {
+        let credential_offer: CredentialOffer = match IssuanceProtocol::decode(message)? {
             A2AMessage::CredentialOffer(credential_offer) => credential_offer,
             msg => {
                 return Err(VcxError::from_msg(VcxErrorKind::InvalidMessages,
@@ -154,7 +260,7 @@ impl Holder {
         let msgs: Vec<CredentialOffer> = messages
             .into_iter()
             .filter_map(|(_, a2a_message)| {
-                match a2a_message {
+                match IssuanceProtocol::decode(a2a_message).ok()? {
                     A2AMessage::CredentialOffer(credential_offer) => {
                         Some(credential_offer)
                     }
@@ -166,3 +272,28 @@ impl Holder {
         Ok(msgs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use utils::devsetup::SetupAriesMocks;
+    use v3::handlers::connection::tests::mock_connection;
+    use v3::messages::issuance::credential_offer::tests::_credential_offer;
+
+    #[test]
+    fn test_holder_auto_accept_offer_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let connection_handle = mock_connection();
+        let mut holder = Holder::create(_credential_offer(), "test_holder_auto_accept_offer_works").unwrap();
+
+        assert!(!holder.auto_accept_offer(connection_handle).unwrap());
+        assert_eq!(VcxStateType::VcxStateRequestReceived as u32, holder.get_status());
+
+        connection::set_auto_accept(connection_handle, r#"{"credential_offers":"always"}"#).unwrap();
+
+        assert!(holder.auto_accept_offer(connection_handle).unwrap());
+        assert_eq!(VcxStateType::VcxStateOfferSent as u32, holder.get_status());
+    }
+}
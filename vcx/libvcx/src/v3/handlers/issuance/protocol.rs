@@ -0,0 +1,117 @@
+use error::prelude::*;
+use v3::messages::a2a::A2AMessage;
+use v3::messages::a2a::message_family::MessageFamilies;
+use v3::messages::discovery::disclose::ProtocolDescriptor;
+use v3::messages::issuance_v2::credential_proposal::CredentialProposalV2;
+use connection;
+
+// Version of issue-credential protocol used for the interaction.
+// State machines operate on 1.0 messages, 2.0 ones are converted when sent or received.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum IssuanceProtocol {
+    V1,
+    V2,
+}
+
+impl Default for IssuanceProtocol {
+    fn default() -> IssuanceProtocol {
+        IssuanceProtocol::V1
+    }
+}
+
+impl IssuanceProtocol {
+    // 2.0 is used only if the remote side disclosed its support via Discover Features
+    pub fn for_connection(connection_handle: u32) -> IssuanceProtocol {
+        match connection::get_remote_protocols(connection_handle) {
+            Ok(Some(protocols)) => IssuanceProtocol::for_protocols(&protocols),
+            _ => IssuanceProtocol::V1
+        }
+    }
+
+    fn for_protocols(protocols: &[ProtocolDescriptor]) -> IssuanceProtocol {
+        let family = MessageFamilies::CredentialIssuanceV2;
+        let suffix = format!("/{}/{}", family.to_string(), family.version());
+
+        // pid prefix depends on the agent: `did:sov:...;spec` or `https://didcomm.org`
+        if protocols.iter().any(|protocol| protocol.pid.ends_with(&suffix)) {
+            IssuanceProtocol::V2
+        } else {
+            IssuanceProtocol::V1
+        }
+    }
+
+    pub fn for_message(message: &A2AMessage) -> IssuanceProtocol {
+        match message {
+            A2AMessage::CredentialProposalV2(_) |
+            A2AMessage::CredentialOfferV2(_) |
+            A2AMessage::CredentialRequestV2(_) |
+            A2AMessage::CredentialV2(_) |
+            A2AMessage::CredentialAckV2(_) => IssuanceProtocol::V2,
+            _ => IssuanceProtocol::V1
+        }
+    }
+
+    // Converts 1.0 message produced by state machine into the message of this protocol version
+    pub fn encode(&self, message: A2AMessage) -> VcxResult<A2AMessage> {
+        match self {
+            IssuanceProtocol::V1 => Ok(message),
+            IssuanceProtocol::V2 => match message {
+                A2AMessage::CredentialProposal(proposal) => Ok(A2AMessage::CredentialProposalV2(CredentialProposalV2::from_v1(proposal)?)),
+                A2AMessage::CredentialOffer(offer) => Ok(A2AMessage::CredentialOfferV2(offer.into())),
+                A2AMessage::CredentialRequest(request) => Ok(A2AMessage::CredentialRequestV2(request.into())),
+                A2AMessage::Credential(credential) => Ok(A2AMessage::CredentialV2(credential.into())),
+                A2AMessage::CredentialAck(ack) => Ok(A2AMessage::CredentialAckV2(ack)),
+                message => Ok(message)
+            }
+        }
+    }
+
+    // Converts received 2.0 message into 1.0 one handled by state machines
+    pub fn decode(message: A2AMessage) -> VcxResult<A2AMessage> {
+        match message {
+            A2AMessage::CredentialProposalV2(proposal) => Ok(A2AMessage::CredentialProposal(proposal.to_v1()?)),
+            A2AMessage::CredentialOfferV2(offer) => Ok(A2AMessage::CredentialOffer(offer.to_v1()?)),
+            A2AMessage::CredentialRequestV2(request) => Ok(A2AMessage::CredentialRequest(request.to_v1()?)),
+            A2AMessage::CredentialV2(credential) => Ok(A2AMessage::Credential(credential.to_v1()?)),
+            A2AMessage::CredentialAckV2(ack) => Ok(A2AMessage::CredentialAck(ack)),
+            message => Ok(message)
+        }
+    }
+
+    pub fn send_message(&self, connection_handle: u32, message: A2AMessage) -> VcxResult<()> {
+        connection::send_message(connection_handle, self.encode(message)?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::issuance::credential_offer::tests::_credential_offer;
+    use v3::messages::issuance::credential::tests::_credential;
+
+    #[test]
+    fn test_issuance_protocol_for_protocols_works() {
+        assert_eq!(IssuanceProtocol::V1, IssuanceProtocol::for_protocols(&[
+            ProtocolDescriptor { pid: MessageFamilies::CredentialIssuance.id(), roles: None }
+        ]));
+        assert_eq!(IssuanceProtocol::V2, IssuanceProtocol::for_protocols(&[
+            ProtocolDescriptor { pid: MessageFamilies::CredentialIssuance.id(), roles: None },
+            ProtocolDescriptor { pid: MessageFamilies::CredentialIssuanceV2.id(), roles: None },
+        ]));
+        assert_eq!(IssuanceProtocol::V2, IssuanceProtocol::for_protocols(&[
+            ProtocolDescriptor { pid: String::from("https://didcomm.org/issue-credential/2.0"), roles: None }
+        ]));
+    }
+
+    #[test]
+    fn test_issuance_protocol_encode_decode_works() {
+        let message = IssuanceProtocol::V2.encode(_credential_offer().to_a2a_message()).unwrap();
+        assert_match!(A2AMessage::CredentialOfferV2(_), message);
+        assert_eq!(IssuanceProtocol::V2, IssuanceProtocol::for_message(&message));
+        assert_eq!(_credential_offer().to_a2a_message(), IssuanceProtocol::decode(message).unwrap());
+
+        let message = IssuanceProtocol::V1.encode(_credential().to_a2a_message()).unwrap();
+        assert_eq!(_credential().to_a2a_message(), message);
+        assert_eq!(IssuanceProtocol::V1, IssuanceProtocol::for_message(&message));
+    }
+}
@@ -0,0 +1,53 @@
+use error::prelude::*;
+use utils::libindy::wallet::{add_record, get_record, update_record_value};
+use v3::messages::revocation_notification::revoke::Revoke;
+
+const REVOKED_CREDENTIAL_RECORD_TYPE: &str = "VcxRevokedCredential";
+const RECORD_OPTIONS: &str = r#"{"retrieveType":false,"retrieveValue":true,"retrieveTags":false}"#;
+
+// Record is keyed by the id of the stored credential and tagged by the thread of its issuance
+pub fn store_revocation(cred_id: &str, revoke: &Revoke) -> VcxResult<()> {
+    trace!("revocation::store_revocation >>> cred_id: {}, thread_id: {}", cred_id, revoke.thread_id);
+
+    let value = json!({
+        "thread_id": revoke.thread_id,
+        "comment": revoke.comment,
+    }).to_string();
+    let tags = json!({
+        "thread_id": revoke.thread_id,
+    }).to_string();
+
+    update_record_value(REVOKED_CREDENTIAL_RECORD_TYPE, cred_id, &value)
+        .or(add_record(REVOKED_CREDENTIAL_RECORD_TYPE, cred_id, &value, Some(&tags)))
+}
+
+pub fn is_revoked(cred_id: &str) -> VcxResult<bool> {
+    trace!("revocation::is_revoked >>> cred_id: {}", cred_id);
+
+    match get_record(REVOKED_CREDENTIAL_RECORD_TYPE, cred_id, RECORD_OPTIONS) {
+        Ok(_) => Ok(true),
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => Ok(false),
+        Err(err) => Err(err)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use utils::devsetup::SetupLibraryWallet;
+    use v3::messages::revocation_notification::revoke::tests::_revoke;
+
+    #[test]
+    fn test_store_revocation_works() {
+        let _setup = SetupLibraryWallet::init();
+
+        assert!(!is_revoked("cred_id").unwrap());
+
+        store_revocation("cred_id", &_revoke()).unwrap();
+        assert!(is_revoked("cred_id").unwrap());
+        assert!(!is_revoked("other").unwrap());
+
+        // repeated notification does not fail
+        store_revocation("cred_id", &_revoke()).unwrap();
+    }
+}
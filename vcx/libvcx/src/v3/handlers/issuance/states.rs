@@ -1,6 +1,8 @@
 use v3::messages::a2a::MessageId;
 use v3::messages::issuance::credential_request::CredentialRequest;
+use v3::messages::issuance::CredentialPreviewData;
 use v3::messages::issuance::credential_offer::CredentialOffer;
+use v3::messages::issuance::credential_proposal::CredentialProposal;
 use v3::messages::issuance::credential::Credential;
 use v3::messages::status::Status;
 use v3::messages::error::ProblemReport;
@@ -8,13 +10,17 @@ use v3::messages::error::ProblemReport;
 // Possible Transitions:
 // Initial -> OfferSent
 // Initial -> Finished
+// OfferSent -> ProposalReceived
 // OfferSent -> CredentialSent
 // OfferSent -> Finished
+// ProposalReceived -> OfferSent (counter-offer)
+// ProposalReceived -> Finished
 // CredentialSent -> Finished
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum IssuerState {
     Initial(InitialState),
     OfferSent(OfferSentState),
+    ProposalReceived(ProposalReceivedState),
     RequestReceived(RequestReceivedState),
     CredentialSent(CredentialSentState),
     Finished(FinishedState)
@@ -25,6 +31,7 @@ impl IssuerState {
         match self {
             IssuerState::Initial(_) => 0,
             IssuerState::OfferSent(state) => state.connection_handle,
+            IssuerState::ProposalReceived(state) => state.connection_handle,
             IssuerState::RequestReceived(state) => state.connection_handle,
             IssuerState::CredentialSent(state) => state.connection_handle,
             IssuerState::Finished(_) => 0
@@ -35,6 +42,7 @@ impl IssuerState {
         match self {
             IssuerState::Initial(_) => String::new(),
             IssuerState::OfferSent(state) => state.thread_id.clone(),
+            IssuerState::ProposalReceived(state) => state.thread_id.clone(),
             IssuerState::RequestReceived(state) => state.thread_id.clone(),
             IssuerState::CredentialSent(state) => state.thread_id.clone(),
             IssuerState::Finished(state) => state.thread_id.clone(),
@@ -71,6 +79,17 @@ pub struct OfferSentState {
     pub thread_id: String
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProposalReceivedState {
+    pub offer: String,
+    pub cred_data: String,
+    pub rev_reg_id: Option<String>,
+    pub tails_file: Option<String>,
+    pub connection_handle: u32,
+    pub proposal: CredentialProposal,
+    pub thread_id: String
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RequestReceivedState {
     pub offer: String,
@@ -92,6 +111,10 @@ pub struct RevocationInfoV1 {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CredentialSentState {
     pub connection_handle: u32,
+    // Attribute values of issued credential are kept only in memory (for derived credentials),
+    // so they don't get into serialized state
+    #[serde(default, skip_serializing)]
+    pub cred_data: Option<String>,
     pub revocation_info_v1: Option<RevocationInfoV1>,
     pub thread_id: String
 }
@@ -99,6 +122,8 @@ pub struct CredentialSentState {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FinishedState {
     pub cred_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub cred_data: Option<String>,
     pub thread_id: String,
     pub revocation_info_v1: Option<RevocationInfoV1>,
     pub status: Status
@@ -119,10 +144,11 @@ impl From<(InitialState, String, u32, MessageId)> for OfferSentState {
 }
 
 impl From<InitialState> for FinishedState {
-    fn from(_state: InitialState) -> Self {
+    fn from(state: InitialState) -> Self {
         trace!("SM is now in Finished state");
         FinishedState {
             cred_id: None,
+            cred_data: Some(state.credential_json),
             thread_id: String::new(),
             revocation_info_v1: None,
             status: Status::Undefined,
@@ -130,6 +156,52 @@ impl From<InitialState> for FinishedState {
     }
 }
 
+impl From<(OfferSentState, CredentialProposal)> for ProposalReceivedState {
+    fn from((state, proposal): (OfferSentState, CredentialProposal)) -> Self {
+        trace!("SM is now in ProposalReceived state");
+        ProposalReceivedState {
+            offer: state.offer,
+            cred_data: state.cred_data,
+            rev_reg_id: state.rev_reg_id,
+            tails_file: state.tails_file,
+            connection_handle: state.connection_handle,
+            proposal,
+            thread_id: state.thread_id,
+        }
+    }
+}
+
+impl From<(ProposalReceivedState, String)> for OfferSentState {
+    fn from((state, cred_data): (ProposalReceivedState, String)) -> Self {
+        trace!("SM is now in OfferSent state");
+        OfferSentState {
+            offer: state.offer,
+            cred_data,
+            rev_reg_id: state.rev_reg_id,
+            tails_file: state.tails_file,
+            connection_handle: state.connection_handle,
+            thread_id: state.thread_id,
+        }
+    }
+}
+
+impl From<(ProposalReceivedState, ProblemReport)> for FinishedState {
+    fn from((state, err): (ProposalReceivedState, ProblemReport)) -> Self {
+        trace!("SM is now in Finished state");
+        FinishedState {
+            cred_id: None,
+            cred_data: Some(state.cred_data),
+            thread_id: state.thread_id,
+            revocation_info_v1: Some(RevocationInfoV1 {
+                cred_rev_id: None,
+                rev_reg_id: state.rev_reg_id,
+                tails_file: state.tails_file,
+            }),
+            status: Status::Failed(err),
+        }
+    }
+}
+
 impl From<(OfferSentState, CredentialRequest)> for RequestReceivedState {
     fn from((state, request): (OfferSentState, CredentialRequest)) -> Self {
         trace!("SM is now in Request Received state");
@@ -150,6 +222,7 @@ impl From<(RequestReceivedState, MessageId)> for CredentialSentState {
         trace!("SM is now in CredentialSent state");
         CredentialSentState {
             connection_handle: state.connection_handle,
+            cred_data: Some(state.cred_data),
             revocation_info_v1: Some(RevocationInfoV1 {
                 cred_rev_id: None,
                 rev_reg_id: state.rev_reg_id,
@@ -165,6 +238,7 @@ impl From<OfferSentState> for FinishedState {
         trace!("SM is now in Finished state");
         FinishedState {
             cred_id: None,
+            cred_data: Some(state.cred_data),
             thread_id: state.thread_id,
             revocation_info_v1: Some(RevocationInfoV1 {
                 cred_rev_id: None,
@@ -181,6 +255,7 @@ impl From<(OfferSentState, ProblemReport)> for FinishedState {
         trace!("SM is now in Finished state");
         FinishedState {
             cred_id: None,
+            cred_data: Some(state.cred_data),
             thread_id: state.thread_id,
             revocation_info_v1: Some(RevocationInfoV1 {
                 cred_rev_id: None,
@@ -197,6 +272,7 @@ impl From<(RequestReceivedState, Option<String>)> for FinishedState {
         trace!("SM is now in Finished state");
         FinishedState {
             cred_id: None,
+            cred_data: Some(state.cred_data),
             thread_id: state.thread_id,
             revocation_info_v1: Some(RevocationInfoV1 {
                 cred_rev_id: cred_rev_id,
@@ -213,6 +289,7 @@ impl From<(RequestReceivedState, ProblemReport)> for FinishedState {
         trace!("SM is now in Finished state");
         FinishedState {
             cred_id: None,
+            cred_data: Some(state.cred_data),
             thread_id: state.thread_id,
             revocation_info_v1: Some(RevocationInfoV1 {
                 cred_rev_id: None,
@@ -229,6 +306,7 @@ impl From<CredentialSentState> for FinishedState {
         trace!("SM is now in Finished state");
         FinishedState {
             cred_id: None,
+            cred_data: state.cred_data,
             thread_id: state.thread_id,
             revocation_info_v1: state.revocation_info_v1,
             status: Status::Success,
@@ -236,9 +314,17 @@ impl From<CredentialSentState> for FinishedState {
     }
 }
 
+// Possible Transitions:
+// OfferReceived -> RequestSent
+// OfferReceived -> ProposalSent
+// OfferReceived -> Finished
+// ProposalSent -> OfferReceived (counter-offer)
+// ProposalSent -> Finished
+// RequestSent -> Finished
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum HolderState {
     OfferReceived(OfferReceivedState),
+    ProposalSent(ProposalSentState),
     RequestSent(RequestSentState),
     Finished(FinishedHolderState)
 }
@@ -247,8 +333,9 @@ impl HolderState {
     pub fn get_connection_handle(&self) -> u32 {
         match self {
             HolderState::OfferReceived(_) => 0,
+            HolderState::ProposalSent(state) => state.connection_handle,
             HolderState::RequestSent(state) => state.connection_handle,
-            HolderState::Finished(_) => 0
+            HolderState::Finished(state) => state.connection_handle.unwrap_or(0)
         }
     }
 }
@@ -257,6 +344,15 @@ impl HolderState {
 pub struct RequestSentState {
     pub req_meta: String,
     pub cred_def_json: String,
+    pub connection_handle: u32,
+    #[serde(default)]
+    pub credential_preview: Option<CredentialPreviewData>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProposalSentState {
+    pub offer: CredentialOffer,
+    pub proposal: CredentialProposal,
     pub connection_handle: u32
 }
 
@@ -279,52 +375,105 @@ pub struct FinishedHolderState {
     pub cred_id: Option<String>,
     pub credential: Option<Credential>,
     pub status: Status,
-    pub rev_reg_def_json: Option<String>
+    pub rev_reg_def_json: Option<String>,
+    // Preview of the offer the credential was issued for, kept for rendering
+    #[serde(default)]
+    pub credential_preview: Option<CredentialPreviewData>,
+    // Connection the credential was issued over, Issuer notifies about its revocation there
+    #[serde(default)]
+    pub connection_handle: Option<u32>,
+    #[serde(default)]
+    pub revoked: bool,
 }
 
 impl From<(OfferReceivedState, String, String, u32)> for RequestSentState {
-    fn from((_state, req_meta, cred_def_json, connection_handle): (OfferReceivedState, String, String, u32)) -> Self {
+    fn from((state, req_meta, cred_def_json, connection_handle): (OfferReceivedState, String, String, u32)) -> Self {
         trace!("SM is now in RequestSent state");
         RequestSentState {
             req_meta,
             cred_def_json,
             connection_handle,
+            credential_preview: Some(state.offer.credential_preview),
+        }
+    }
+}
+
+impl From<(OfferReceivedState, CredentialProposal, u32)> for ProposalSentState {
+    fn from((state, proposal, connection_handle): (OfferReceivedState, CredentialProposal, u32)) -> Self {
+        trace!("SM is now in ProposalSent state");
+        ProposalSentState {
+            offer: state.offer,
+            proposal,
+            connection_handle,
+        }
+    }
+}
+
+impl From<(ProposalSentState, CredentialOffer)> for OfferReceivedState {
+    fn from((_state, offer): (ProposalSentState, CredentialOffer)) -> Self {
+        trace!("SM is now in OfferReceived state");
+        OfferReceivedState {
+            offer,
         }
     }
 }
 
 impl From<(RequestSentState, String, Credential, Option<String>)> for FinishedHolderState {
-    fn from((_, cred_id, credential, rev_reg_def_json): (RequestSentState, String, Credential, Option<String>)) -> Self {
+    fn from((state, cred_id, credential, rev_reg_def_json): (RequestSentState, String, Credential, Option<String>)) -> Self {
         trace!("SM is now in Finished state");
         FinishedHolderState {
             cred_id: Some(cred_id),
             credential: Some(credential),
             status: Status::Success,
-            rev_reg_def_json: rev_reg_def_json
+            rev_reg_def_json: rev_reg_def_json,
+            credential_preview: state.credential_preview,
+            connection_handle: Some(state.connection_handle),
+            revoked: false,
         }
     }
 }
 
 impl From<(RequestSentState, ProblemReport)> for FinishedHolderState {
-    fn from((_, problem_report): (RequestSentState, ProblemReport)) -> Self {
+    fn from((state, problem_report): (RequestSentState, ProblemReport)) -> Self {
         trace!("SM is now in Finished state");
         FinishedHolderState {
             cred_id: None,
             credential: None,
             status: Status::Failed(problem_report),
-            rev_reg_def_json: None
+            rev_reg_def_json: None,
+            credential_preview: state.credential_preview,
+            connection_handle: None,
+            revoked: false,
         }
     }
 }
 
 impl From<(OfferReceivedState, ProblemReport)> for FinishedHolderState {
-    fn from((_state, problem_report): (OfferReceivedState, ProblemReport)) -> Self {
+    fn from((state, problem_report): (OfferReceivedState, ProblemReport)) -> Self {
+        trace!("SM is now in Finished state");
+        FinishedHolderState {
+            cred_id: None,
+            credential: None,
+            status: Status::Failed(problem_report),
+            rev_reg_def_json: None,
+            credential_preview: Some(state.offer.credential_preview),
+            connection_handle: None,
+            revoked: false,
+        }
+    }
+}
+
+impl From<(ProposalSentState, ProblemReport)> for FinishedHolderState {
+    fn from((state, problem_report): (ProposalSentState, ProblemReport)) -> Self {
         trace!("SM is now in Finished state");
         FinishedHolderState {
             cred_id: None,
             credential: None,
             status: Status::Failed(problem_report),
-            rev_reg_def_json: None
+            rev_reg_def_json: None,
+            credential_preview: Some(state.offer.credential_preview),
+            connection_handle: None,
+            revoked: false,
         }
     }
 }
@@ -0,0 +1,33 @@
+use v3::messages::a2a::A2AMessage;
+use v3::messages::mediation::mediate_grant::MediateGrant;
+use v3::messages::mediation::mediate_deny::MediateDeny;
+use v3::messages::mediation::keylist_update_response::KeylistUpdateResponse;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MediationMessages {
+    MediateGrantReceived(MediateGrant),
+    MediateDenyReceived(MediateDeny),
+    AddKey(String),
+    RemoveKey(String),
+    KeylistUpdateResponseReceived(KeylistUpdateResponse),
+    Unknown
+}
+
+impl From<A2AMessage> for MediationMessages {
+    fn from(msg: A2AMessage) -> Self {
+        match msg {
+            A2AMessage::MediateGrant(grant) => {
+                MediationMessages::MediateGrantReceived(grant)
+            }
+            A2AMessage::MediateDeny(deny) => {
+                MediationMessages::MediateDenyReceived(deny)
+            }
+            A2AMessage::KeylistUpdateResponse(response) => {
+                MediationMessages::KeylistUpdateResponseReceived(response)
+            }
+            _ => {
+                MediationMessages::Unknown
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod states;
+pub mod messages;
+pub mod pickup;
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use v3::handlers::connection::agent::AgentInfo;
+use v3::messages::a2a::A2AMessage;
+use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::pickup::status_request::StatusRequest;
+use v3::messages::pickup::delivery_request::DeliveryRequest;
+use v3::messages::pickup::delivery::Delivery;
+use v3::messages::pickup::messages_received::MessagesReceived;
+use v3::utils::encryption_envelope::EncryptionEnvelope;
+
+use error::prelude::*;
+
+// Recipient side of Pickup protocol.
+// Requests are sent over the connection with the mediator and answered within the same HTTP exchange (return route),
+// so the agent doesn't need an endpoint reachable by the mediator.
+
+// Messages exceeding the limit are delivered on next poll
+pub const DELIVERY_LIMIT: u32 = 100;
+
+pub fn status(agent_info: &AgentInfo, did_doc: &DidDoc, recipient_key: Option<&str>) -> VcxResult<u32> {
+    trace!("pickup::status >>> recipient_key: {:?}", recipient_key);
+
+    let request = StatusRequest::create()
+        .set_recipient_key(recipient_key.map(String::from));
+
+    match agent_info.send_message_with_return_route(&request.to_a2a_message(), did_doc)? {
+        Some(A2AMessage::PickupStatus(status)) => Ok(status.message_count),
+        response => Err(VcxError::from_msg(VcxErrorKind::InvalidHttpResponse, format!("Mediator didn't answer with Status message: {:?}", response)))
+    }
+}
+
+// Delivered messages stay queued by the mediator until `messages_received` is sent.
+// Messages which can't be opened are skipped and removed from the mediator queue right away,
+// so they don't take the place of valid messages in next deliveries.
+// `sender_did_doc` is DIDDoc of the connection the messages are sent over, not of the mediator.
+pub fn deliver(agent_info: &AgentInfo, did_doc: &DidDoc, recipient_key: &str, sender_did_doc: Option<&DidDoc>) -> VcxResult<HashMap<String, A2AMessage>> {
+    trace!("pickup::deliver >>> recipient_key: {:?}", recipient_key);
+
+    let request = DeliveryRequest::create()
+        .set_limit(DELIVERY_LIMIT)
+        .set_recipient_key(Some(recipient_key.to_string()));
+
+    match agent_info.send_message_with_return_route(&request.to_a2a_message(), did_doc)? {
+        Some(A2AMessage::Delivery(delivery)) => {
+            let (messages, rejected) = open_delivery(delivery, sender_did_doc);
+
+            if !rejected.is_empty() {
+                if let Err(err) = messages_received(agent_info, did_doc, rejected) {
+                    warn!("pickup::deliver: cannot remove skipped messages from the mediator queue: {}", err);
+                }
+            }
+
+            Ok(messages)
+        }
+        // Mediator answers with Status if there is nothing to deliver
+        Some(A2AMessage::PickupStatus(_)) | None => Ok(HashMap::new()),
+        response => Err(VcxError::from_msg(VcxErrorKind::InvalidHttpResponse, format!("Mediator didn't answer with Delivery message: {:?}", response)))
+    }
+}
+
+pub fn messages_received(agent_info: &AgentInfo, did_doc: &DidDoc, message_ids: Vec<String>) -> VcxResult<()> {
+    trace!("pickup::messages_received >>> message_ids: {:?}", message_ids);
+
+    let message = MessagesReceived::create()
+        .set_message_id_list(message_ids);
+
+    agent_info.send_message(&message.to_a2a_message(), did_doc)
+}
+
+// Returns opened messages and ids of messages which can't be opened
+fn open_delivery(delivery: Delivery, sender_did_doc: Option<&DidDoc>) -> (HashMap<String, A2AMessage>, Vec<String>) {
+    let mut messages = HashMap::new();
+    let mut rejected = Vec::new();
+
+    for message in delivery.messages {
+        match message.payload().and_then(|payload| EncryptionEnvelope::open_from(payload, sender_did_doc)) {
+            Ok(opened) => { messages.insert(message.id, opened); }
+            Err(err) => {
+                warn!("pickup::deliver: message {} is skipped: {}", message.id, err);
+                rejected.push(message.id);
+            }
+        }
+    }
+
+    (messages, rejected)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use utils::devsetup::SetupAriesMocks;
+    use utils::httpclient::AgencyMock;
+    use v3::messages::ack::tests::_ack;
+    use v3::messages::connection::did_doc::tests::_did_doc;
+    use v3::messages::mediation::keylist_update::tests::_key;
+    use v3::messages::pickup::status::Status;
+    use v3::messages::pickup::delivery::DeliveredMessage;
+
+    // Envelopes are not encrypted in mocked mode
+    pub fn _envelope(message: &A2AMessage) -> Vec<u8> {
+        json!({"message": json!(message).to_string()}).to_string().into_bytes()
+    }
+
+    #[test]
+    fn test_pickup_status_works() {
+        let _setup = SetupAriesMocks::init();
+
+        AgencyMock::set_next_response(_envelope(&Status::create().set_message_count(3).to_a2a_message()));
+
+        assert_eq!(3, status(&AgentInfo::default(), &_did_doc(), Some(&_key())).unwrap());
+    }
+
+    #[test]
+    fn test_pickup_status_fails_for_no_response() {
+        let _setup = SetupAriesMocks::init();
+
+        assert_eq!(status(&AgentInfo::default(), &_did_doc(), None).unwrap_err().kind(), VcxErrorKind::InvalidHttpResponse);
+    }
+
+    #[test]
+    fn test_pickup_deliver_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let delivery = Delivery::create()
+            .add_message(DeliveredMessage::new("message_1", &_envelope(&_ack().to_a2a_message())));
+        AgencyMock::set_next_response(_envelope(&delivery.to_a2a_message()));
+
+        let messages = deliver(&AgentInfo::default(), &_did_doc(), &_key(), None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(_ack().to_a2a_message(), messages["message_1"]);
+    }
+
+    #[test]
+    fn test_pickup_deliver_skips_invalid_messages() {
+        let _setup = SetupAriesMocks::init();
+
+        let delivery = Delivery::create()
+            .add_message(DeliveredMessage::new("message_1", b"invalid"))
+            .add_message(DeliveredMessage::new("message_2", &_envelope(&_ack().to_a2a_message())));
+        AgencyMock::set_next_response(_envelope(&delivery.to_a2a_message()));
+
+        let messages = deliver(&AgentInfo::default(), &_did_doc(), &_key(), None).unwrap();
+
+        assert_eq!(1, messages.len());
+        assert_eq!(_ack().to_a2a_message(), messages["message_2"]);
+    }
+
+    #[test]
+    fn test_pickup_deliver_works_for_empty_queue() {
+        let _setup = SetupAriesMocks::init();
+
+        AgencyMock::set_next_response(_envelope(&Status::create().to_a2a_message()));
+        assert!(deliver(&AgentInfo::default(), &_did_doc(), &_key(), None).unwrap().is_empty());
+
+        assert!(deliver(&AgentInfo::default(), &_did_doc(), &_key(), None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pickup_messages_received_works() {
+        let _setup = SetupAriesMocks::init();
+
+        messages_received(&AgentInfo::default(), &_did_doc(), vec![String::from("message_1")]).unwrap();
+    }
+}
@@ -0,0 +1,357 @@
+use api::VcxStateType;
+
+use v3::handlers::connection::agent::AgentInfo;
+use v3::handlers::mediation::messages::MediationMessages;
+use v3::messages::a2a::A2AMessage;
+use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::mediation::mediate_request::MediateRequest;
+use v3::messages::mediation::mediate_grant::MediateGrant;
+use v3::messages::mediation::keylist_update::{KeylistUpdate, KeylistUpdateAction};
+use v3::messages::mediation::keylist_update_response::KeylistUpdateResponse;
+
+use std::collections::HashMap;
+
+use error::prelude::*;
+
+// Recipient side of Mediator Coordination protocol.
+// Runs over established connection with the mediator, so pairwise agent and DIDDoc of the mediator are kept here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediationSM {
+    agent_info: AgentInfo,
+    did_doc: DidDoc,
+    state: MediationState,
+}
+
+/// Transitions of Mediation state
+/// Requested -> Granted, Denied
+/// Granted
+/// Denied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MediationState {
+    Requested(RequestedState),
+    Granted(GrantedState),
+    Denied(DeniedState),
+}
+
+impl MediationState {
+    pub fn code(&self) -> u32 {
+        match self {
+            MediationState::Requested(_) => VcxStateType::VcxStateOfferSent as u32,
+            MediationState::Granted(_) => VcxStateType::VcxStateAccepted as u32,
+            MediationState::Denied(_) => VcxStateType::VcxStateRejected as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestedState {
+    request: MediateRequest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantedState {
+    route: MediatorRoute,
+    // Recipient keys registered with the mediator
+    keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeniedState {}
+
+// Endpoint and routing keys put into DIDDocs of connections which receive messages through the mediator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediatorRoute {
+    pub endpoint: String,
+    pub routing_keys: Vec<String>,
+}
+
+impl From<(RequestedState, MediateGrant)> for GrantedState {
+    fn from((_state, grant): (RequestedState, MediateGrant)) -> GrantedState {
+        trace!("MediationSM: transit state from RequestedState to GrantedState");
+        GrantedState { route: MediatorRoute { endpoint: grant.endpoint, routing_keys: grant.routing_keys }, keys: Vec::new() }
+    }
+}
+
+impl GrantedState {
+    fn handle_keylist_update(mut self, update: KeylistUpdate, agent_info: &AgentInfo, did_doc: &DidDoc) -> VcxResult<GrantedState> {
+        agent_info.send_message(&update.to_a2a_message(), did_doc)?;
+
+        for item in update.updates {
+            match item.action {
+                KeylistUpdateAction::Add => self.keys.push(item.recipient_key),
+                KeylistUpdateAction::Remove => self.keys.retain(|key| *key != item.recipient_key),
+            }
+        }
+
+        Ok(self)
+    }
+
+    // Keys are considered registered once the update is sent, the response only reverts rejected additions
+    fn handle_keylist_update_response(mut self, response: KeylistUpdateResponse) -> GrantedState {
+        for updated in response.updated {
+            if updated.is_applied() {
+                continue;
+            }
+
+            warn!("Mediator didn't apply keylist update: {:?}", updated);
+
+            if updated.action == KeylistUpdateAction::Add {
+                self.keys.retain(|key| *key != updated.recipient_key);
+            }
+        }
+
+        self
+    }
+}
+
+impl MediationSM {
+    pub fn request(agent_info: AgentInfo, did_doc: DidDoc) -> VcxResult<MediationSM> {
+        trace!("MediationSM::request >>>");
+
+        let request = MediateRequest::create();
+
+        agent_info.send_message(&request.to_a2a_message(), &did_doc)?;
+
+        Ok(MediationSM {
+            agent_info,
+            did_doc,
+            state: MediationState::Requested(RequestedState { request }),
+        })
+    }
+
+    pub fn state(&self) -> u32 {
+        self.state.code()
+    }
+
+    pub fn agent_info(&self) -> &AgentInfo {
+        &self.agent_info
+    }
+
+    pub fn did_doc(&self) -> &DidDoc {
+        &self.did_doc
+    }
+
+    pub fn route(&self) -> Option<&MediatorRoute> {
+        match self.state {
+            MediationState::Granted(ref state) => Some(&state.route),
+            _ => None
+        }
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        match self.state {
+            MediationState::Granted(ref state) => state.keys.clone(),
+            _ => Vec::new()
+        }
+    }
+
+    pub fn find_message_to_handle(&self, messages: HashMap<String, A2AMessage>) -> Option<(String, A2AMessage)> {
+        trace!("MediationSM::find_message_to_handle >>> messages: {:?}", messages);
+
+        for (uid, message) in messages {
+            match self.state {
+                MediationState::Requested(ref state) => {
+                    match message {
+                        A2AMessage::MediateGrant(ref grant) if grant.from_thread(&state.request.id.0) => {
+                            debug!("Recipient received MediateGrant message");
+                            return Some((uid, message));
+                        }
+                        A2AMessage::MediateDeny(ref deny) if deny.from_thread(&state.request.id.0) => {
+                            debug!("Recipient received MediateDeny message");
+                            return Some((uid, message));
+                        }
+                        _ => {}
+                    }
+                }
+                MediationState::Granted(_) => {
+                    match message {
+                        response @ A2AMessage::KeylistUpdateResponse(_) => {
+                            debug!("Recipient received KeylistUpdateResponse message");
+                            return Some((uid, response));
+                        }
+                        _ => {}
+                    }
+                }
+                MediationState::Denied(_) => {}
+            }
+        }
+
+        None
+    }
+
+    pub fn step(self, message: MediationMessages) -> VcxResult<MediationSM> {
+        trace!("MediationSM::step >>> message: {:?}", message);
+
+        let MediationSM { agent_info, did_doc, state } = self;
+
+        let state = match state {
+            MediationState::Requested(state) => {
+                match message {
+                    MediationMessages::MediateGrantReceived(grant) => {
+                        MediationState::Granted((state, grant).into())
+                    }
+                    MediationMessages::MediateDenyReceived(_) => {
+                        MediationState::Denied(DeniedState {})
+                    }
+                    _ => {
+                        MediationState::Requested(state)
+                    }
+                }
+            }
+            MediationState::Granted(state) => {
+                match message {
+                    MediationMessages::AddKey(key) => {
+                        MediationState::Granted(state.handle_keylist_update(KeylistUpdate::create().add_key(&key), &agent_info, &did_doc)?)
+                    }
+                    MediationMessages::RemoveKey(key) => {
+                        MediationState::Granted(state.handle_keylist_update(KeylistUpdate::create().remove_key(&key), &agent_info, &did_doc)?)
+                    }
+                    MediationMessages::KeylistUpdateResponseReceived(response) => {
+                        MediationState::Granted(state.handle_keylist_update_response(response))
+                    }
+                    _ => {
+                        MediationState::Granted(state)
+                    }
+                }
+            }
+            MediationState::Denied(state) => {
+                MediationState::Denied(state)
+            }
+        };
+
+        Ok(MediationSM { agent_info, did_doc, state })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use utils::devsetup::SetupAriesMocks;
+    use v3::messages::connection::did_doc::tests::{_did_doc, _service_endpoint};
+    use v3::messages::mediation::mediate_grant::tests::_routing_keys;
+    use v3::messages::mediation::mediate_deny::MediateDeny;
+    use v3::messages::mediation::keylist_update::tests::_key;
+    use v3::messages::mediation::keylist_update_response::{KeylistUpdated, KeylistUpdateResult};
+
+    pub fn _mediation_sm() -> MediationSM {
+        MediationSM::request(AgentInfo::default(), _did_doc()).unwrap()
+    }
+
+    impl MediationSM {
+        fn request_id(&self) -> String {
+            match self.state {
+                MediationState::Requested(ref state) => state.request.id.0.clone(),
+                _ => panic!("Mediation is not requested")
+            }
+        }
+
+        pub fn to_granted_state(self) -> MediationSM {
+            let grant = MediateGrant::create()
+                .set_endpoint(_service_endpoint())
+                .set_routing_keys(_routing_keys())
+                .set_thread_id(&self.request_id());
+
+            self.step(MediationMessages::MediateGrantReceived(grant)).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_mediation_request_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let mediation_sm = _mediation_sm();
+
+        assert_match!(MediationState::Requested(_), mediation_sm.state);
+        assert_eq!(VcxStateType::VcxStateOfferSent as u32, mediation_sm.state());
+        assert!(mediation_sm.route().is_none());
+    }
+
+    #[test]
+    fn test_mediation_handle_grant_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let mediation_sm = _mediation_sm().to_granted_state();
+
+        assert_match!(MediationState::Granted(_), mediation_sm.state);
+        assert_eq!(Some(&MediatorRoute { endpoint: _service_endpoint(), routing_keys: _routing_keys() }), mediation_sm.route());
+    }
+
+    #[test]
+    fn test_mediation_handle_deny_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let mediation_sm = _mediation_sm();
+        let deny = MediateDeny::create().set_thread_id(&mediation_sm.request_id());
+
+        let mediation_sm = mediation_sm.step(MediationMessages::MediateDenyReceived(deny)).unwrap();
+
+        assert_match!(MediationState::Denied(_), mediation_sm.state);
+        assert_eq!(VcxStateType::VcxStateRejected as u32, mediation_sm.state());
+        assert!(mediation_sm.route().is_none());
+    }
+
+    #[test]
+    fn test_mediation_keylist_update_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let mut mediation_sm = _mediation_sm().to_granted_state();
+
+        mediation_sm = mediation_sm.step(MediationMessages::AddKey(_key())).unwrap();
+        assert_eq!(vec![_key()], mediation_sm.keys());
+
+        mediation_sm = mediation_sm.step(MediationMessages::RemoveKey(_key())).unwrap();
+        assert!(mediation_sm.keys().is_empty());
+    }
+
+    #[test]
+    fn test_mediation_keylist_update_response_reverts_rejected_key() {
+        let _setup = SetupAriesMocks::init();
+
+        let mut mediation_sm = _mediation_sm().to_granted_state();
+        mediation_sm = mediation_sm.step(MediationMessages::AddKey(_key())).unwrap();
+
+        let response = KeylistUpdateResponse {
+            updated: vec![KeylistUpdated { recipient_key: _key(), action: KeylistUpdateAction::Add, result: KeylistUpdateResult::ServerError }],
+            ..KeylistUpdateResponse::create()
+        };
+
+        mediation_sm = mediation_sm.step(MediationMessages::KeylistUpdateResponseReceived(response)).unwrap();
+        assert!(mediation_sm.keys().is_empty());
+    }
+
+    #[test]
+    fn test_mediation_keys_are_not_added_before_grant() {
+        let _setup = SetupAriesMocks::init();
+
+        let mediation_sm = _mediation_sm().step(MediationMessages::AddKey(_key())).unwrap();
+
+        assert_match!(MediationState::Requested(_), mediation_sm.state);
+        assert!(mediation_sm.keys().is_empty());
+    }
+
+    #[test]
+    fn test_find_message_to_handle_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let mediation_sm = _mediation_sm();
+
+        let foreign_grant = MediateGrant::create().set_thread_id("other");
+        let grant = MediateGrant::create().set_thread_id(&mediation_sm.request_id());
+
+        let messages = map!(
+            "key_1".to_string() => A2AMessage::MediateGrant(foreign_grant),
+            "key_2".to_string() => A2AMessage::MediateGrant(grant)
+        );
+
+        let (uid, message) = mediation_sm.find_message_to_handle(messages).unwrap();
+        assert_eq!("key_2", uid);
+        assert_match!(A2AMessage::MediateGrant(_), message);
+
+        let mediation_sm = mediation_sm.to_granted_state();
+        let messages = map!(
+            "key_1".to_string() => A2AMessage::MediateGrant(MediateGrant::create())
+        );
+        assert!(mediation_sm.find_message_to_handle(messages).is_none());
+    }
+}
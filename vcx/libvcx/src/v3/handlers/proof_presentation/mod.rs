@@ -1,2 +1,3 @@
 pub mod prover;
-pub mod verifier;
\ No newline at end of file
+pub mod verifier;
+pub mod protocol;
\ No newline at end of file
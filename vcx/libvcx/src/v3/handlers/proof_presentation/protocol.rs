@@ -0,0 +1,120 @@
+use error::prelude::*;
+use v3::messages::a2a::A2AMessage;
+use v3::messages::a2a::message_family::MessageFamilies;
+use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::discovery::disclose::ProtocolDescriptor;
+use v3::messages::proof_presentation::v2::presentation_proposal::PresentationProposalV2;
+use connection;
+
+// Version of present-proof protocol used for the interaction.
+// State machines operate on 1.0 messages, 2.0 ones are converted when sent or received.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PresentationProtocol {
+    V1,
+    V2,
+}
+
+impl Default for PresentationProtocol {
+    fn default() -> PresentationProtocol {
+        PresentationProtocol::V1
+    }
+}
+
+impl PresentationProtocol {
+    // 2.0 is used only if the remote side disclosed its support via Discover Features
+    pub fn for_connection(connection_handle: u32) -> PresentationProtocol {
+        match connection::get_remote_protocols(connection_handle) {
+            Ok(Some(protocols)) => PresentationProtocol::for_protocols(&protocols),
+            _ => PresentationProtocol::V1
+        }
+    }
+
+    fn for_protocols(protocols: &[ProtocolDescriptor]) -> PresentationProtocol {
+        let family = MessageFamilies::PresentProofV2;
+        let suffix = format!("/{}/{}", family.to_string(), family.version());
+
+        if protocols.iter().any(|protocol| protocol.pid.ends_with(&suffix)) {
+            PresentationProtocol::V2
+        } else {
+            PresentationProtocol::V1
+        }
+    }
+
+    pub fn for_message(message: &A2AMessage) -> PresentationProtocol {
+        match message {
+            A2AMessage::PresentationProposalV2(_) |
+            A2AMessage::PresentationRequestV2(_) |
+            A2AMessage::PresentationV2(_) |
+            A2AMessage::PresentationAckV2(_) => PresentationProtocol::V2,
+            _ => PresentationProtocol::V1
+        }
+    }
+
+    // Converts 1.0 message produced by state machine into the message of this protocol version
+    pub fn encode(&self, message: A2AMessage) -> VcxResult<A2AMessage> {
+        match self {
+            PresentationProtocol::V1 => Ok(message),
+            PresentationProtocol::V2 => match message {
+                A2AMessage::PresentationProposal(proposal) => Ok(A2AMessage::PresentationProposalV2(PresentationProposalV2::from_v1(proposal)?)),
+                A2AMessage::PresentationRequest(request) => Ok(A2AMessage::PresentationRequestV2(request.into())),
+                A2AMessage::Presentation(presentation) => Ok(A2AMessage::PresentationV2(presentation.into())),
+                A2AMessage::PresentationAck(ack) => Ok(A2AMessage::PresentationAckV2(ack)),
+                message => Ok(message)
+            }
+        }
+    }
+
+    // Converts received 2.0 message into 1.0 one handled by state machines.
+    // Fails if the message doesn't contain attachments of libindy format.
+    pub fn decode(message: A2AMessage) -> VcxResult<A2AMessage> {
+        match message {
+            A2AMessage::PresentationProposalV2(proposal) => Ok(A2AMessage::PresentationProposal(proposal.to_v1()?)),
+            A2AMessage::PresentationRequestV2(request) => Ok(A2AMessage::PresentationRequest(request.to_v1()?)),
+            A2AMessage::PresentationV2(presentation) => Ok(A2AMessage::Presentation(presentation.to_v1()?)),
+            A2AMessage::PresentationAckV2(ack) => Ok(A2AMessage::PresentationAck(ack)),
+            message => Ok(message)
+        }
+    }
+
+    pub fn send_message(&self, connection_handle: u32, message: A2AMessage) -> VcxResult<()> {
+        connection::send_message(connection_handle, self.encode(message)?)
+    }
+
+    pub fn send_message_to_self_endpoint(&self, message: A2AMessage, did_doc: &DidDoc) -> VcxResult<()> {
+        connection::send_message_to_self_endpoint(self.encode(message)?, did_doc)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::proof_presentation::presentation_request::tests::_presentation_request;
+    use v3::messages::proof_presentation::presentation::tests::_presentation;
+
+    #[test]
+    fn test_presentation_protocol_for_protocols_works() {
+        assert_eq!(PresentationProtocol::V1, PresentationProtocol::for_protocols(&[
+            ProtocolDescriptor { pid: MessageFamilies::PresentProof.id(), roles: None },
+            ProtocolDescriptor { pid: MessageFamilies::CredentialIssuanceV2.id(), roles: None },
+        ]));
+        assert_eq!(PresentationProtocol::V2, PresentationProtocol::for_protocols(&[
+            ProtocolDescriptor { pid: MessageFamilies::PresentProof.id(), roles: None },
+            ProtocolDescriptor { pid: MessageFamilies::PresentProofV2.id(), roles: None },
+        ]));
+        assert_eq!(PresentationProtocol::V2, PresentationProtocol::for_protocols(&[
+            ProtocolDescriptor { pid: String::from("https://didcomm.org/present-proof/2.0"), roles: None }
+        ]));
+    }
+
+    #[test]
+    fn test_presentation_protocol_encode_decode_works() {
+        let message = PresentationProtocol::V2.encode(_presentation_request().to_a2a_message()).unwrap();
+        assert_match!(A2AMessage::PresentationRequestV2(_), message);
+        assert_eq!(PresentationProtocol::V2, PresentationProtocol::for_message(&message));
+        assert_eq!(_presentation_request().to_a2a_message(), PresentationProtocol::decode(message).unwrap());
+
+        let message = PresentationProtocol::V1.encode(_presentation().to_a2a_message()).unwrap();
+        assert_eq!(_presentation().to_a2a_message(), message);
+        assert_eq!(PresentationProtocol::V1, PresentationProtocol::for_message(&message));
+    }
+}
@@ -0,0 +1,218 @@
+use std::ffi::CString;
+use std::sync::Mutex;
+
+use libc::c_char;
+use serde_json::{Map, Value};
+
+use error::prelude::*;
+use settings::CredentialSelectionStrategy;
+
+// Receives requested referent and JSON array of credentials matching it.
+// Returns index of the chosen credential or negative value to refuse automatic presentation.
+pub type CredentialSelectionHandler = extern fn(referent: *const c_char, credentials: *const c_char) -> i32;
+
+lazy_static! {
+    static ref SELECTION_HANDLER: Mutex<Option<CredentialSelectionHandler>> = Default::default();
+}
+
+pub fn set_selection_handler(handler: Option<CredentialSelectionHandler>) -> VcxResult<()> {
+    let mut selection_handler = SELECTION_HANDLER.lock()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock credential selection handler: {:?}", err)))?;
+
+    *selection_handler = handler;
+
+    Ok(())
+}
+
+fn _selection_handler() -> VcxResult<Option<CredentialSelectionHandler>> {
+    SELECTION_HANDLER.lock()
+        .map(|handler| *handler)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidState, format!("Cannot lock credential selection handler: {:?}", err)))
+}
+
+// Chooses one credential for every requested attribute and predicate of retrieved credentials JSON
+// (as returned by `Prover::retrieve_credentials`). Result can be passed to `Prover::generate_presentation`.
+// Returns `None` if some referent is not matched by any credential or the strategy refused to choose.
+pub fn select_credentials(credentials: &str, strategy: &CredentialSelectionStrategy) -> VcxResult<Option<String>> {
+    trace!("select_credentials >>> credentials: {}, strategy: {:?}", credentials, strategy);
+
+    let credentials: Value = ::serde_json::from_str(credentials)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize retrieved credentials: {:?}", err)))?;
+
+    let mut selected = Map::new();
+
+    for kind in &["attrs", "predicates"] {
+        if let Some(referents) = credentials[kind].as_object() {
+            for (referent, matches) in referents {
+                let matches = match matches.as_array() {
+                    Some(matches) if !matches.is_empty() => matches,
+                    _ => return Ok(None)
+                };
+
+                match _select(referent, matches, strategy)? {
+                    Some(credential) => { selected.insert(referent.to_string(), json!({"credential": credential})); }
+                    None => return Ok(None)
+                }
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(json!({"attrs": selected}).to_string()))
+}
+
+fn _select<'a>(referent: &str, matches: &'a [Value], strategy: &CredentialSelectionStrategy) -> VcxResult<Option<&'a Value>> {
+    if matches.len() == 1 {
+        return Ok(matches.first());
+    }
+
+    let selected = match strategy {
+        CredentialSelectionStrategy::FirstMatch => matches.first(),
+        CredentialSelectionStrategy::Newest { attribute } =>
+            matches.iter().max_by_key(|credential| _attribute_value(credential, attribute).unwrap_or(i64::min_value())),
+        CredentialSelectionStrategy::EarliestExpiry { attribute } =>
+            matches.iter().min_by_key(|credential| _attribute_value(credential, attribute).unwrap_or(i64::max_value())),
+        CredentialSelectionStrategy::CredDefPreference { cred_def_ids } =>
+            cred_def_ids.iter()
+                .filter_map(|cred_def_id| matches.iter().find(|credential| credential["cred_info"]["cred_def_id"].as_str() == Some(cred_def_id)))
+                .next()
+                .or_else(|| matches.first()),
+        CredentialSelectionStrategy::Callback => _select_with_handler(referent, matches)?
+    };
+
+    Ok(selected)
+}
+
+fn _attribute_value(credential: &Value, attribute: &str) -> Option<i64> {
+    credential["cred_info"]["attrs"][attribute].as_str()
+        .and_then(|value| value.parse().ok())
+}
+
+fn _select_with_handler<'a>(referent: &str, matches: &'a [Value]) -> VcxResult<Option<&'a Value>> {
+    let handler = _selection_handler()?
+        .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "Credential selection handler is not set"))?;
+
+    let referent = CString::new(referent)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Invalid referent: {:?}", err)))?;
+    let credentials = CString::new(json!(matches).to_string())
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Invalid credentials: {:?}", err)))?;
+
+    let index = handler(referent.as_ptr(), credentials.as_ptr());
+
+    if index < 0 {
+        return Ok(None);
+    }
+
+    matches.get(index as usize)
+        .map(Some)
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidOption, format!("Credential selection handler returned invalid index: {}", index)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use utils::constants::CREDS_FROM_PROOF_REQ;
+
+    fn _credential(referent: &str, cred_def_id: &str, issued_at: &str) -> Value {
+        json!({
+            "cred_info": {
+                "referent": referent,
+                "attrs": {"name": "Alex", "issued_at": issued_at},
+                "cred_def_id": cred_def_id
+            },
+            "interval": null
+        })
+    }
+
+    fn _credentials() -> String {
+        json!({
+            "attrs": {
+                "name": [
+                    _credential("1", "cred_def_1", "100"),
+                    _credential("2", "cred_def_2", "300"),
+                    _credential("3", "cred_def_3", "200"),
+                ]
+            },
+            "predicates": {}
+        }).to_string()
+    }
+
+    fn _selected_referent(selected: Option<String>) -> String {
+        let selected: Value = ::serde_json::from_str(&selected.unwrap()).unwrap();
+        selected["attrs"]["name"]["credential"]["cred_info"]["referent"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_select_credentials_works_for_first_match() {
+        let selected = select_credentials(&_credentials(), &CredentialSelectionStrategy::FirstMatch).unwrap();
+        assert_eq!("1", _selected_referent(selected));
+
+        let selected: Value = ::serde_json::from_str(&select_credentials(CREDS_FROM_PROOF_REQ, &CredentialSelectionStrategy::FirstMatch).unwrap().unwrap()).unwrap();
+        let retrieved: Value = ::serde_json::from_str(CREDS_FROM_PROOF_REQ).unwrap();
+        assert_eq!(retrieved["attrs"]["height_1"][0], selected["attrs"]["height_1"]["credential"]);
+        assert_eq!(retrieved["attrs"]["zip_2"][0], selected["attrs"]["zip_2"]["credential"]);
+    }
+
+    #[test]
+    fn test_select_credentials_works_for_newest_and_earliest_expiry() {
+        let strategy = CredentialSelectionStrategy::Newest { attribute: "issued_at".to_string() };
+        assert_eq!("2", _selected_referent(select_credentials(&_credentials(), &strategy).unwrap()));
+
+        let strategy = CredentialSelectionStrategy::EarliestExpiry { attribute: "issued_at".to_string() };
+        assert_eq!("1", _selected_referent(select_credentials(&_credentials(), &strategy).unwrap()));
+    }
+
+    #[test]
+    fn test_select_credentials_works_for_cred_def_preference() {
+        let strategy = CredentialSelectionStrategy::CredDefPreference { cred_def_ids: vec!["unknown".to_string(), "cred_def_3".to_string(), "cred_def_2".to_string()] };
+        assert_eq!("3", _selected_referent(select_credentials(&_credentials(), &strategy).unwrap()));
+
+        let strategy = CredentialSelectionStrategy::CredDefPreference { cred_def_ids: vec!["unknown".to_string()] };
+        assert_eq!("1", _selected_referent(select_credentials(&_credentials(), &strategy).unwrap()));
+    }
+
+    extern fn _choose_newest_handler(referent: *const c_char, credentials: *const c_char) -> i32 {
+        let referent = unsafe { CStr::from_ptr(referent) }.to_str().unwrap();
+        let credentials: Value = ::serde_json::from_str(unsafe { CStr::from_ptr(credentials) }.to_str().unwrap()).unwrap();
+
+        if referent != "name" { return -1; }
+
+        credentials.as_array().unwrap().iter()
+            .position(|credential| credential["cred_info"]["attrs"]["issued_at"] == "300")
+            .map(|index| index as i32)
+            .unwrap_or(-1)
+    }
+
+    extern fn _refuse_handler(_referent: *const c_char, _credentials: *const c_char) -> i32 {
+        -1
+    }
+
+    #[test]
+    fn test_select_credentials_works_for_callback() {
+        set_selection_handler(None).unwrap();
+        assert_eq!(select_credentials(&_credentials(), &CredentialSelectionStrategy::Callback).unwrap_err().kind(), VcxErrorKind::NotReady);
+
+        set_selection_handler(Some(_choose_newest_handler)).unwrap();
+        assert_eq!("2", _selected_referent(select_credentials(&_credentials(), &CredentialSelectionStrategy::Callback).unwrap()));
+
+        set_selection_handler(Some(_refuse_handler)).unwrap();
+        assert_eq!(None, select_credentials(&_credentials(), &CredentialSelectionStrategy::Callback).unwrap());
+
+        set_selection_handler(None).unwrap();
+    }
+
+    #[test]
+    fn test_select_credentials_works_for_missing_credentials() {
+        let credential = _credential("1", "cred_def_1", "100");
+
+        let missing = json!({"attrs": {"name": [credential]}, "predicates": {"age": []}});
+        assert_eq!(None, select_credentials(&missing.to_string(), &CredentialSelectionStrategy::FirstMatch).unwrap());
+
+        assert_eq!(None, select_credentials(r#"{"attrs":{},"predicates":{}}"#, &CredentialSelectionStrategy::FirstMatch).unwrap());
+        assert_eq!(select_credentials("invalid", &CredentialSelectionStrategy::FirstMatch).unwrap_err().kind(), VcxErrorKind::InvalidJson);
+    }
+}
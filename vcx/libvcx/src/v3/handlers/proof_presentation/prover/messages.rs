@@ -3,6 +3,7 @@ use v3::messages::proof_presentation::presentation_ack::PresentationAck;
 use v3::messages::proof_presentation::presentation_proposal::PresentationPreview;
 use v3::messages::error::ProblemReport;
 use v3::messages::a2a::A2AMessage;
+use v3::handlers::proof_presentation::protocol::PresentationProtocol;
 use v3::messages::proof_presentation::presentation::Presentation;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -20,6 +21,11 @@ pub enum ProverMessages {
 
 impl From<A2AMessage> for ProverMessages {
     fn from(msg: A2AMessage) -> Self {
+        let msg = match PresentationProtocol::decode(msg) {
+            Ok(msg) => msg,
+            Err(_) => return ProverMessages::Unknown
+        };
+
         match msg {
             A2AMessage::Ack(ack) | A2AMessage::PresentationAck(ack) => {
                 ProverMessages::PresentationAckReceived(ack)
@@ -1,3 +1,4 @@
 pub mod states;
 pub mod prover;
 pub mod messages;
+pub mod credential_selection;
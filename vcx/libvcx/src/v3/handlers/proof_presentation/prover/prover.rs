@@ -1,9 +1,12 @@
 use error::prelude::*;
 use utils::libindy::anoncreds;
 use std::convert::TryInto;
+use serde_json::{Map, Value};
 
 use v3::handlers::proof_presentation::prover::states::ProverSM;
 use v3::handlers::proof_presentation::prover::messages::ProverMessages;
+use v3::handlers::proof_presentation::prover::credential_selection::select_credentials;
+use v3::handlers::proof_presentation::protocol::PresentationProtocol;
 use v3::messages::a2a::A2AMessage;
 use v3::messages::proof_presentation::presentation_proposal::PresentationPreview;
 use v3::messages::proof_presentation::presentation_request::PresentationRequest;
@@ -33,6 +36,13 @@ impl Prover {
         self.prover_sm.presentation_status()
     }
 
+    pub fn get_problem_report(&self) -> VcxResult<String> {
+        trace!("Prover::get_problem_report >>>");
+        self.prover_sm.get_problem_report()
+            .map(|problem_report| json!(problem_report.to_a2a_message()).to_string())
+            .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "No Problem Report was sent or received"))
+    }
+
     pub fn retrieve_credentials(&self) -> VcxResult<String> {
         trace!("Prover::retrieve_credentials >>>");
         let presentation_request = self.prover_sm.presentation_request().request_presentations_attach.content()?;
@@ -70,6 +80,39 @@ impl Prover {
         self.step(ProverMessages::SendPresentation(connection_handle))
     }
 
+    // Sends Presentation without user involvement if every requested attribute and predicate is matched by exactly one credential.
+    // Returns `false` if there is no such choice of credentials.
+    pub fn present_single_match(&mut self, connection_handle: u32) -> VcxResult<bool> {
+        trace!("Prover::present_single_match >>> connection_handle: {}", connection_handle);
+
+        let credentials = match select_single_match(&self.retrieve_credentials()?)? {
+            Some(credentials) => credentials,
+            None => return Ok(false)
+        };
+
+        self.generate_presentation(credentials, String::from("{}"))?;
+        self.send_presentation(connection_handle)?;
+
+        Ok(true)
+    }
+
+    // Sends Presentation without user involvement choosing credentials with configured `credential_selection_strategy`
+    // when several of them match a requested attribute or predicate.
+    // Returns `false` if some of them is not matched or the strategy refused to choose.
+    pub fn present_selected(&mut self, connection_handle: u32) -> VcxResult<bool> {
+        trace!("Prover::present_selected >>> connection_handle: {}", connection_handle);
+
+        let credentials = match select_credentials(&self.retrieve_credentials()?, &settings::get_credential_selection_strategy())? {
+            Some(credentials) => credentials,
+            None => return Ok(false)
+        };
+
+        self.generate_presentation(credentials, String::from("{}"))?;
+        self.send_presentation(connection_handle)?;
+
+        Ok(true)
+    }
+
     pub fn update_state(&mut self, message: Option<&str>) -> VcxResult<()> {
         trace!("Prover::update_state >>> message: {:?}", message);
 
@@ -111,7 +154,7 @@ impl Prover {
 
         let message = connection::get_message_by_id(connection_handle, msg_id.to_string())?;
 
-        let presentation_request: PresentationRequest = match message {
+        let presentation_request: PresentationRequest = match PresentationProtocol::decode(message)? {
             A2AMessage::PresentationRequest(presentation_request) => presentation_request,
             msg => {
                 return Err(VcxError::from_msg(VcxErrorKind::InvalidMessages,
@@ -129,7 +172,7 @@ impl Prover {
             connection::get_messages(connection_handle)?
                 .into_iter()
                 .filter_map(|(_, message)| {
-                    match message {
+                    match PresentationProtocol::decode(message).ok()? {
                         A2AMessage::PresentationRequest(presentation_request) => {
                             Some(presentation_request)
                         }
@@ -143,6 +186,8 @@ impl Prover {
 
     pub fn get_source_id(&self) -> String { self.prover_sm.source_id() }
 
+    pub fn get_thread_id(&self) -> String { self.prover_sm.thread_id() }
+
     pub fn step(&mut self, message: ProverMessages) -> VcxResult<()> {
         self.prover_sm = self.prover_sm.clone().step(message)?;
         Ok(())
@@ -168,4 +213,59 @@ impl Prover {
             }
         }
     }
-}
\ No newline at end of file
+}
+// Selected credentials in the form `generate_presentation` expects, taken from the result of `retrieve_credentials`
+fn select_single_match(credentials: &str) -> VcxResult<Option<String>> {
+    let credentials: Value = ::serde_json::from_str(credentials)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize retrieved credentials: {:?}", err)))?;
+
+    let mut selected = Map::new();
+
+    for kind in &["attrs", "predicates"] {
+        if let Some(referents) = credentials[kind].as_object() {
+            for (referent, matches) in referents {
+                match matches.as_array() {
+                    Some(matches) if matches.len() == 1 => {
+                        selected.insert(referent.to_string(), json!({"credential": matches[0]}));
+                    }
+                    _ => return Ok(None)
+                }
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(json!({"attrs": selected}).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::constants::CREDS_FROM_PROOF_REQ;
+
+    #[test]
+    fn test_select_single_match_works() {
+        let selected: Value = ::serde_json::from_str(&select_single_match(CREDS_FROM_PROOF_REQ).unwrap().unwrap()).unwrap();
+
+        let retrieved: Value = ::serde_json::from_str(CREDS_FROM_PROOF_REQ).unwrap();
+        assert_eq!(retrieved["attrs"]["height_1"][0], selected["attrs"]["height_1"]["credential"]);
+        assert_eq!(retrieved["attrs"]["zip_2"][0], selected["attrs"]["zip_2"]["credential"]);
+    }
+
+    #[test]
+    fn test_select_single_match_works_for_ambiguous_or_missing_credentials() {
+        let credential = json!({"cred_info": {"referent": "1"}, "interval": null});
+
+        let ambiguous = json!({"attrs": {"name": [credential, credential]}, "predicates": {}});
+        assert_eq!(None, select_single_match(&ambiguous.to_string()).unwrap());
+
+        let missing = json!({"attrs": {"name": [credential]}, "predicates": {"age": []}});
+        assert_eq!(None, select_single_match(&missing.to_string()).unwrap());
+
+        assert_eq!(None, select_single_match(r#"{"attrs":{},"predicates":{}}"#).unwrap());
+        assert_eq!(select_single_match("invalid").unwrap_err().kind(), VcxErrorKind::InvalidJson);
+    }
+}
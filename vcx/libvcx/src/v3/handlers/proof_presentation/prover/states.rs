@@ -1,17 +1,20 @@
 use api::VcxStateType;
 
-use connection;
 use v3::handlers::proof_presentation::prover::messages::ProverMessages;
+use v3::handlers::proof_presentation::protocol::PresentationProtocol;
 use v3::messages::a2a::A2AMessage;
+use v3::messages::attachment::AttachmentId;
 use v3::messages::proof_presentation::presentation_request::PresentationRequest;
 use v3::messages::proof_presentation::presentation_proposal::{PresentationProposal, PresentationPreview};
 use v3::messages::proof_presentation::presentation::Presentation;
 use v3::messages::proof_presentation::presentation_ack::PresentationAck;
-use v3::messages::error::ProblemReport;
+use v3::messages::error::{ProblemReport, Impact, codes};
 use v3::messages::status::Status;
 
 use std::collections::HashMap;
 use disclosed_proof::DisclosedProof;
+use settings;
+use utils::libindy::anoncreds;
 
 use error::prelude::*;
 
@@ -22,11 +25,18 @@ pub struct ProverSM {
     source_id: String,
     thread_id: String,
     state: ProverState,
+    #[serde(default)]
+    protocol: PresentationProtocol,
 }
 
 impl ProverSM {
     pub fn new(presentation_request: PresentationRequest, source_id: String) -> ProverSM {
-        ProverSM { source_id, thread_id: presentation_request.id.0.clone(), state: ProverState::Initiated(InitialState { presentation_request }) }
+        ProverSM {
+            source_id,
+            thread_id: presentation_request.id.0.clone(),
+            state: ProverState::Initiated(InitialState { presentation_request }),
+            protocol: PresentationProtocol::default(),
+        }
     }
 }
 
@@ -137,7 +147,7 @@ impl From<(PresentationPreparationFailedState, u32)> for FinishedState {
         trace!("transit state from PresentationPreparationFailedState to FinishedState");
         FinishedState {
             presentation_request: state.presentation_request,
-            presentation: Presentation::create(),
+            presentation: Presentation::default(),
             connection_handle,
             status: Status::Failed(state.problem_report),
         }
@@ -174,6 +184,25 @@ impl InitialState {
                                             self_attested_attrs,
                                             &self.presentation_request.request_presentations_attach.content()?)
     }
+
+    // Presentation Definition is attached to the request translated from DIF Presentation Exchange one
+    fn build_presentation_submission(&self, proof: &str) -> VcxResult<Option<::serde_json::Value>> {
+        let definition_attach = self.presentation_request.request_presentations_attach.select(&[AttachmentId::PresentationDefinition]);
+
+        if definition_attach.get().is_none() {
+            return Ok(None);
+        }
+
+        let definition: ::serde_json::Value = ::serde_json::from_str(&definition_attach.content()?)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Presentation Definition attachment: {:?}", err)))?;
+
+        let submission = anoncreds::libindy_verifier_create_presentation_submission(&definition["presentation_definition"].to_string(), proof)?;
+
+        let submission = ::serde_json::from_str(&submission)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Presentation Submission: {:?}", err)))?;
+
+        Ok(Some(submission))
+    }
 }
 
 impl ProverSM {
@@ -181,6 +210,14 @@ impl ProverSM {
         trace!("Prover::find_message_to_handle >>> messages: {:?}", messages);
 
         for (uid, message) in messages {
+            let message = match PresentationProtocol::decode(message) {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!("Prover::find_message_to_handle: skipping message {}: {}", uid, err);
+                    continue;
+                }
+            };
+
             match self.state {
                 ProverState::Initiated(_) => {
                     match message {
@@ -223,7 +260,7 @@ impl ProverSM {
     pub fn step(self, message: ProverMessages) -> VcxResult<ProverSM> {
         trace!("ProverSM::step >>> message: {:?}", message);
 
-        let ProverSM { source_id, state, thread_id } = self;
+        let ProverSM { source_id, state, thread_id, mut protocol } = self;
 
         let state = match state {
             ProverState::Initiated(state) => {
@@ -233,18 +270,25 @@ impl ProverSM {
                         ProverState::PresentationPrepared((state, presentation).into())
                     }
                     ProverMessages::PreparePresentation((credentials, self_attested_attrs)) => {
-                        match state.build_presentation(&credentials, &self_attested_attrs) {
-                            Ok(presentation) => {
-                                let presentation = Presentation::create()
-                                    .ask_for_ack()
-                                    .set_thread_id(&thread_id)
-                                    .set_presentations_attach(presentation)?;
+                        let presentation = state.build_presentation(&credentials, &self_attested_attrs)
+                            .and_then(|proof| Ok((state.build_presentation_submission(&proof)?, proof)));
+
+                        match presentation {
+                            Ok((submission, proof)) => {
+                                let mut presentation = Presentation::build(proof, &thread_id)?
+                                    .ask_for_ack();
+
+                                if let Some(submission) = submission {
+                                    presentation = presentation.set_presentation_submission(submission)?;
+                                }
 
                                 ProverState::PresentationPrepared((state, presentation).into())
                             }
                             Err(err) => {
                                 let problem_report =
                                     ProblemReport::create()
+                                        .set_description(codes::MESSAGE_PROCESSING_ERROR)
+                                        .set_impact(Impact::Thread)
                                         .set_comment(err.to_string())
                                         .set_thread_id(&thread_id);
 
@@ -253,11 +297,13 @@ impl ProverSM {
                         }
                     }
                     ProverMessages::RejectPresentationRequest((connection_handle, reason)) => {
-                        Self::_handle_reject_presentation_request(connection_handle, &reason, &state.presentation_request, &thread_id)?;
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+                        Self::_handle_reject_presentation_request(connection_handle, &reason, &state.presentation_request, &thread_id, protocol)?;
                         ProverState::Finished(state.into())
                     }
                     ProverMessages::ProposePresentation((connection_handle, preview)) => {
-                        Self::_handle_presentation_proposal(connection_handle, preview, &state.presentation_request, &thread_id)?;
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+                        Self::_handle_presentation_proposal(connection_handle, preview, &state.presentation_request, &thread_id, protocol)?;
                         ProverState::Finished(state.into())
                     }
                     _ => {
@@ -268,23 +314,27 @@ impl ProverSM {
             ProverState::PresentationPrepared(state) => {
                 match message {
                     ProverMessages::SendPresentation(connection_handle) => {
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+
                         match state.presentation_request.service.clone() {
                             None => {
-                                connection::send_message(connection_handle, state.presentation.to_a2a_message())?;
+                                protocol.send_message(connection_handle, state.presentation.to_a2a_message())?;
                                 ProverState::PresentationSent((state, connection_handle).into())
                             }
                             Some(service) => {
-                                connection::send_message_to_self_endpoint(state.presentation.to_a2a_message(), &service.into())?;
+                                protocol.send_message_to_self_endpoint(state.presentation.to_a2a_message(), &service.into())?;
                                 ProverState::Finished(state.into())
                             }
                         }
                     }
                     ProverMessages::RejectPresentationRequest((connection_handle, reason)) => {
-                        Self::_handle_reject_presentation_request(connection_handle, &reason, &state.presentation_request, &thread_id)?;
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+                        Self::_handle_reject_presentation_request(connection_handle, &reason, &state.presentation_request, &thread_id, protocol)?;
                         ProverState::Finished(state.into())
                     }
                     ProverMessages::ProposePresentation((connection_handle, preview)) => {
-                        Self::_handle_presentation_proposal(connection_handle, preview, &state.presentation_request, &thread_id)?;
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+                        Self::_handle_presentation_proposal(connection_handle, preview, &state.presentation_request, &thread_id, protocol)?;
                         ProverState::Finished(state.into())
                     }
                     _ => {
@@ -295,12 +345,14 @@ impl ProverSM {
             ProverState::PresentationPreparationFailed(state) => {
                 match message {
                     ProverMessages::SendPresentation(connection_handle) => {
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+
                         match state.presentation_request.service.clone() {
                             None => {
-                                connection::send_message(connection_handle, state.problem_report.to_a2a_message())?;
+                                protocol.send_message(connection_handle, state.problem_report.to_a2a_message())?;
                             }
                             Some(service) => {
-                                connection::send_message_to_self_endpoint(state.problem_report.to_a2a_message(), &service.into())?;
+                                protocol.send_message_to_self_endpoint(state.problem_report.to_a2a_message(), &service.into())?;
                             }
                         }
 
@@ -330,37 +382,71 @@ impl ProverSM {
             ProverState::Finished(state) => ProverState::Finished(state)
         };
 
-        Ok(ProverSM { source_id, state, thread_id })
+        Ok(ProverSM { source_id, state, thread_id, protocol })
     }
 
-    fn _handle_reject_presentation_request(connection_handle: u32, reason: &str, presentation_request: &PresentationRequest, thread_id: &str) -> VcxResult<()> {
+    fn _handle_reject_presentation_request(connection_handle: u32, reason: &str, presentation_request: &PresentationRequest, thread_id: &str, protocol: PresentationProtocol) -> VcxResult<()> {
         let problem_report = ProblemReport::create()
+            .set_description(codes::REQUEST_NOT_ACCEPTED)
+            .set_impact(Impact::Thread)
             .set_comment(reason.to_string())
             .set_thread_id(thread_id);
 
         match presentation_request.service.clone() {
-            None => connection::send_message(connection_handle, problem_report.to_a2a_message())?,
-            Some(service) => connection::send_message_to_self_endpoint(problem_report.to_a2a_message(), &service.into())?
+            None => protocol.send_message(connection_handle, problem_report.to_a2a_message())?,
+            Some(service) => protocol.send_message_to_self_endpoint(problem_report.to_a2a_message(), &service.into())?
         }
 
         Ok(())
     }
 
-    fn _handle_presentation_proposal(connection_handle: u32, preview: PresentationPreview, presentation_request: &PresentationRequest, thread_id: &str) -> VcxResult<()> {
+    fn _handle_presentation_proposal(connection_handle: u32, preview: PresentationPreview, presentation_request: &PresentationRequest, thread_id: &str, protocol: PresentationProtocol) -> VcxResult<()> {
         let proposal = PresentationProposal::create()
             .set_presentation_preview(preview)
+            .set_non_revocable_referents(Self::_non_revocable_referents(presentation_request))
             .set_thread_id(thread_id);
 
         match presentation_request.service.clone() {
-            None => connection::send_message(connection_handle, proposal.to_a2a_message())?,
-            Some(service) => connection::send_message_to_self_endpoint(proposal.to_a2a_message(), &service.into())?
+            None => protocol.send_message(connection_handle, proposal.to_a2a_message())?,
+            Some(service) => protocol.send_message_to_self_endpoint(proposal.to_a2a_message(), &service.into())?
         }
 
         Ok(())
     }
 
+    // Referents of the requested non-revocation interval which can be satisfied only by credentials without revocation support.
+    // Failure to look them up isn't fatal: proposal is sent without them.
+    fn _non_revocable_referents(presentation_request: &PresentationRequest) -> Vec<String> {
+        if settings::indy_mocks_enabled() { return Vec::new(); }
+
+        let proof_request: ::serde_json::Value = match presentation_request.request_presentations_attach.content()
+            .and_then(|content| ::serde_json::from_str(&content)
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Proof Request: {:?}", err)))) {
+            Ok(proof_request) => proof_request,
+            Err(err) => {
+                warn!("ProverSM::_non_revocable_referents: {}", err);
+                return Vec::new();
+            }
+        };
+
+        let referents = _referents_with_non_revoked_interval(&proof_request);
+        if referents.is_empty() { return referents; }
+
+        match anoncreds::libindy_prover_get_credentials_for_proof_req(&proof_request.to_string())
+            .and_then(|credentials| ::serde_json::from_str(&credentials)
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize credentials: {:?}", err)))) {
+            Ok(credentials) => _referents_without_revocation_support(&referents, &credentials),
+            Err(err) => {
+                warn!("ProverSM::_non_revocable_referents: cannot get credentials for proof request: {}", err);
+                Vec::new()
+            }
+        }
+    }
+
     pub fn source_id(&self) -> String { self.source_id.clone() }
 
+    pub fn thread_id(&self) -> String { self.thread_id.clone() }
+
     pub fn state(&self) -> u32 {
         match self.state {
             ProverState::Initiated(_) => VcxStateType::VcxStateRequestReceived as u32,
@@ -393,6 +479,15 @@ impl ProverSM {
         }
     }
 
+    // Problem Report either received from Verifier or prepared on failure
+    pub fn get_problem_report(&self) -> Option<ProblemReport> {
+        match self.state {
+            ProverState::PresentationPreparationFailed(ref state) => Some(state.problem_report.clone()),
+            ProverState::Finished(ref state) => state.status.problem_report().cloned(),
+            _ => None
+        }
+    }
+
     pub fn connection_handle(&self) -> VcxResult<u32> {
         match self.state {
             ProverState::Initiated(_) => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Connection handle isn't set")),
@@ -424,6 +519,33 @@ impl ProverSM {
     }
 }
 
+fn _referents_with_non_revoked_interval(proof_request: &::serde_json::Value) -> Vec<String> {
+    let requested_interval = |item: &::serde_json::Value| {
+        let interval = if item["non_revoked"].is_null() { &proof_request["non_revoked"] } else { &item["non_revoked"] };
+        !interval["from"].is_null() || !interval["to"].is_null()
+    };
+
+    ["requested_attributes", "requested_predicates"].iter()
+        .filter_map(|key| proof_request[key].as_object())
+        .flat_map(|items| items.iter())
+        .filter(|&(_, item)| requested_interval(item))
+        .map(|(referent, _)| referent.to_string())
+        .collect()
+}
+
+fn _referents_without_revocation_support(referents: &[String], credentials: &::serde_json::Value) -> Vec<String> {
+    referents.iter()
+        .filter(|referent| {
+            match credentials["attrs"][referent.as_str()].as_array() {
+                Some(credentials) => !credentials.is_empty() &&
+                    credentials.iter().all(|credential| credential["cred_info"]["rev_reg_id"].is_null()),
+                None => false
+            }
+        })
+        .cloned()
+        .collect()
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
@@ -770,6 +892,18 @@ pub mod test {
                 assert_match!(A2AMessage::PresentationAck(_), message);
             }
 
+            // Ack 2.0
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::PresentationProposal(_presentation_proposal()),
+                    "key_2".to_string() => A2AMessage::PresentationAckV2(_ack())
+                );
+
+                let (uid, message) = prover.find_message_to_handle(messages).unwrap();
+                assert_eq!("key_2", uid);
+                assert_match!(A2AMessage::PresentationAck(_), message);
+            }
+
             // Problem Report
             {
                 let messages = map!(
@@ -840,4 +974,40 @@ pub mod test {
             assert_eq!(VcxStateType::VcxStateAccepted as u32, _prover_sm().to_finished_state().state());
         }
     }
-}
\ No newline at end of file
+
+    mod non_revocable_referents {
+        use super::*;
+
+        #[test]
+        fn test_referents_with_non_revoked_interval_works() {
+            let proof_request = json!({
+                "requested_attributes": {
+                    "attribute_0": {"name": "name"},
+                    "attribute_1": {"name": "age", "non_revoked": {"to": 100}}
+                },
+                "requested_predicates": {
+                    "predicate_0": {"name": "height", "p_type": ">=", "p_value": 180}
+                }
+            });
+            assert_eq!(vec!["attribute_1"], _referents_with_non_revoked_interval(&proof_request));
+
+            let mut proof_request = proof_request;
+            proof_request["non_revoked"] = json!({"from": 10, "to": 100});
+            assert_eq!(vec!["attribute_0", "attribute_1", "predicate_0"], _referents_with_non_revoked_interval(&proof_request));
+        }
+
+        #[test]
+        fn test_referents_without_revocation_support_works() {
+            let referents = vec![String::from("attribute_0"), String::from("attribute_1"), String::from("predicate_0")];
+            let credentials = json!({
+                "attrs": {
+                    "attribute_0": [{"cred_info": {"referent": "1", "rev_reg_id": null}}],
+                    "attribute_1": [{"cred_info": {"referent": "1", "rev_reg_id": null}}, {"cred_info": {"referent": "2", "rev_reg_id": "rev_reg_id"}}],
+                    "predicate_0": []
+                }
+            });
+
+            assert_eq!(vec!["attribute_0"], _referents_without_revocation_support(&referents, &credentials));
+        }
+    }
+}
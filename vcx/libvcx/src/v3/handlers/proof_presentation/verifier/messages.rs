@@ -2,6 +2,7 @@ use v3::messages::proof_presentation::presentation_proposal::PresentationProposa
 use v3::messages::proof_presentation::presentation::Presentation;
 use v3::messages::error::ProblemReport;
 use v3::messages::a2a::A2AMessage;
+use v3::handlers::proof_presentation::protocol::PresentationProtocol;
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub enum VerifierMessages {
@@ -14,6 +15,11 @@ pub enum VerifierMessages {
 
 impl From<A2AMessage> for VerifierMessages {
     fn from(msg: A2AMessage) -> Self {
+        let msg = match PresentationProtocol::decode(msg) {
+            Ok(msg) => msg,
+            Err(_) => return VerifierMessages::Unknown
+        };
+
         match msg {
             A2AMessage::Presentation(presentation) => {
                 VerifierMessages::VerifyPresentation(presentation)
@@ -2,12 +2,12 @@ use std::collections::HashMap;
 
 use api::VcxStateType;
 use connection::{get_pw_did, get_their_pw_verkey};
-use connection;
 use error::prelude::*;
 use proof::Proof;
+use v3::handlers::proof_presentation::protocol::PresentationProtocol;
 use v3::handlers::proof_presentation::verifier::messages::VerifierMessages;
 use v3::messages::a2a::A2AMessage;
-use v3::messages::error::ProblemReport;
+use v3::messages::error::{ProblemReport, Impact, codes};
 use v3::messages::proof_presentation::presentation::Presentation;
 use v3::messages::proof_presentation::presentation_ack::PresentationAck;
 use v3::messages::proof_presentation::presentation_request::{PresentationRequest, PresentationRequestData};
@@ -17,11 +17,26 @@ use v3::messages::status::Status;
 pub struct VerifierSM {
     source_id: String,
     state: VerifierState,
+    #[serde(default)]
+    protocol: PresentationProtocol,
+    // referents for which credentials without revocation support are accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    non_revocable_referents: Vec<String>,
 }
 
 impl VerifierSM {
     pub fn new(presentation_request: PresentationRequestData, source_id: String) -> VerifierSM {
-        VerifierSM { source_id, state: VerifierState::Initiated(InitialState { presentation_request_data: presentation_request }) }
+        VerifierSM {
+            source_id,
+            state: VerifierState::Initiated(InitialState { presentation_request_data: presentation_request }),
+            protocol: PresentationProtocol::default(),
+            non_revocable_referents: Vec::new(),
+        }
+    }
+
+    pub fn set_non_revocable_referents(mut self, non_revocable_referents: Vec<String>) -> VerifierSM {
+        self.non_revocable_referents = non_revocable_referents;
+        self
     }
 }
 
@@ -97,9 +112,10 @@ impl From<(PresentationRequestSentState, ProblemReport)> for FinishedState {
 
 
 impl PresentationRequestSentState {
-    fn verify_presentation(&self, presentation: &Presentation) -> VcxResult<()> {
-        let valid = Proof::validate_indy_proof(&presentation.presentations_attach.content()?,
-                                               &self.presentation_request.request_presentations_attach.content()?)?;
+    fn verify_presentation(&self, presentation: &Presentation, protocol: PresentationProtocol, non_revocable_referents: &[String]) -> VcxResult<()> {
+        let valid = Proof::validate_indy_proof_accepting_non_revocable(&presentation.presentations_attach.content()?,
+                                                                       &self.presentation_request.request_presentations_attach.content()?,
+                                                                       non_revocable_referents)?;
 
         if !valid {
             return Err(VcxError::from_msg(VcxErrorKind::InvalidProof, "Presentation verification failed"));
@@ -107,7 +123,7 @@ impl PresentationRequestSentState {
 
         if presentation.please_ack.is_some() {
             let ack = PresentationAck::create().set_thread_id(&self.presentation_request.id.0);
-            connection::send_message(self.connection_handle, A2AMessage::PresentationAck(ack))?;
+            protocol.send_message(self.connection_handle, A2AMessage::PresentationAck(ack))?;
         }
 
         Ok(())
@@ -119,6 +135,14 @@ impl VerifierSM {
         trace!("VerifierSM::find_message_to_handle >>> messages: {:?}", messages);
 
         for (uid, message) in messages {
+            let message = match PresentationProtocol::decode(message) {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!("VerifierSM::find_message_to_handle: skipping message {}: {}", uid, err);
+                    continue;
+                }
+            };
+
             match self.state {
                 VerifierState::Initiated(_) => {
                     // do not process message
@@ -155,7 +179,7 @@ impl VerifierSM {
     pub fn step(self, message: VerifierMessages) -> VcxResult<VerifierSM> {
         trace!("VerifierSM::step >>> message: {:?}", message);
 
-        let VerifierSM { source_id, state } = self;
+        let VerifierSM { source_id, state, mut protocol, non_revocable_referents } = self;
 
         let state = match state {
             VerifierState::Initiated(state) => {
@@ -172,11 +196,11 @@ impl VerifierSM {
                                             ::settings::get_config_value(::settings::CONFIG_INSTITUTION_NAME)?, presentation_request.name);
 
                         let presentation_request =
-                            PresentationRequest::create()
-                                .set_comment(title)
-                                .set_request_presentations_attach(&presentation_request)?;
+                            PresentationRequest::build(&presentation_request)?
+                                .set_comment(title);
 
-                        connection::send_message(connection_handle, presentation_request.to_a2a_message())?;
+                        protocol = PresentationProtocol::for_connection(connection_handle);
+                        protocol.send_message(connection_handle, presentation_request.to_a2a_message())?;
                         VerifierState::PresentationRequestSent((state, presentation_request, connection_handle).into())
                     }
                     _ => {
@@ -187,17 +211,19 @@ impl VerifierSM {
             VerifierState::PresentationRequestSent(state) => {
                 match message {
                     VerifierMessages::VerifyPresentation(presentation) => {
-                        match state.verify_presentation(&presentation) {
+                        match state.verify_presentation(&presentation, protocol, &non_revocable_referents) {
                             Ok(()) => {
                                 VerifierState::Finished((state, presentation, RevocationStatus::NonRevoked).into())
                             }
                             Err(err) => {
                                 let problem_report =
                                     ProblemReport::create()
+                                        .set_description(codes::MESSAGE_PROCESSING_ERROR)
+                                        .set_impact(Impact::Thread)
                                         .set_comment(err.to_string())
                                         .set_thread_id(&state.presentation_request.id.0);
 
-                                connection::send_message(state.connection_handle, problem_report.to_a2a_message())?;
+                                protocol.send_message(state.connection_handle, problem_report.to_a2a_message())?;
                                 match err.kind() {
                                     VcxErrorKind::InvalidProof => {
                                         VerifierState::Finished((state, presentation, RevocationStatus::Revoked).into())
@@ -210,13 +236,20 @@ impl VerifierSM {
                     VerifierMessages::PresentationRejectReceived(problem_report) => {
                         VerifierState::Finished((state, problem_report).into())
                     }
+                    VerifierMessages::PresentationProposalReceived(ref proposal) if proposal.accepted_by(&non_revocable_referents) => {
+                        // the only negotiation supported: prover lacks revocation support for referents verifier agreed to accept
+                        protocol.send_message(state.connection_handle, state.presentation_request.to_a2a_message())?;
+                        VerifierState::PresentationRequestSent(state)
+                    }
                     VerifierMessages::PresentationProposalReceived(_) => { // TODO: handle Presentation Proposal
                         let problem_report =
                             ProblemReport::create()
+                                .set_description(codes::REQUEST_NOT_ACCEPTED)
+                                .set_impact(Impact::Thread)
                                 .set_comment(String::from("PresentationProposal is not supported"))
                                 .set_thread_id(&state.presentation_request.id.0);
 
-                        connection::send_message(state.connection_handle, problem_report.to_a2a_message())?;
+                        protocol.send_message(state.connection_handle, problem_report.to_a2a_message())?;
                         VerifierState::Finished((state, problem_report).into())
                     }
                     _ => {
@@ -227,7 +260,7 @@ impl VerifierSM {
             VerifierState::Finished(state) => VerifierState::Finished(state)
         };
 
-        Ok(VerifierSM { source_id, state })
+        Ok(VerifierSM { source_id, state, protocol, non_revocable_referents })
     }
 
     pub fn source_id(&self) -> String { self.source_id.clone() }
@@ -264,8 +297,7 @@ impl VerifierSM {
                             Some(RevocationStatus::NonRevoked) => Status::Success.code(),
                             None => Status::Success.code(), // for backward compatibility
                             Some(RevocationStatus::Revoked) => {
-                                let problem_report = ProblemReport::create().set_comment(String::from("Revoked credential was used."));
-                                Status::Failed(problem_report).code()
+                                Status::Failed(_revoked_problem_report()).code()
                             },
                         }
                     }
@@ -278,6 +310,19 @@ impl VerifierSM {
         }
     }
 
+    // Problem Report either received from Prover or sent on failure
+    pub fn get_problem_report(&self) -> Option<ProblemReport> {
+        match self.state {
+            VerifierState::Finished(ref state) => {
+                match state.revocation_status {
+                    Some(RevocationStatus::Revoked) => Some(_revoked_problem_report()),
+                    _ => state.status.problem_report().cloned()
+                }
+            }
+            _ => None
+        }
+    }
+
     pub fn connection_handle(&self) -> VcxResult<u32> {
         match self.state {
             VerifierState::Initiated(_) => Err(VcxError::from_msg(VcxErrorKind::NotReady, "Connection handle isn't set")),
@@ -297,7 +342,7 @@ impl VerifierSM {
     pub fn presentation_request(&self) -> VcxResult<PresentationRequest> {
         match self.state {
             VerifierState::Initiated(ref state) => {
-                PresentationRequest::create().set_request_presentations_attach(&state.presentation_request_data)
+                PresentationRequest::build(&state.presentation_request_data)
             }
             VerifierState::PresentationRequestSent(ref state) => Ok(state.presentation_request.clone()),
             VerifierState::Finished(ref state) => Ok(state.presentation_request.clone()),
@@ -316,6 +361,12 @@ impl VerifierSM {
     }
 }
 
+fn _revoked_problem_report() -> ProblemReport {
+    ProblemReport::create()
+        .set_description(codes::CREDENTIAL_REVOKED)
+        .set_comment(String::from("Revoked credential was used."))
+}
+
 #[cfg(test)]
 pub mod test {
     use utils::devsetup::SetupAriesMocks;
@@ -446,6 +497,22 @@ pub mod test {
             assert_eq!(Status::Failed(_problem_report()).code(), verifier_sm.presentation_status());
         }
 
+        #[test]
+        fn test_prover_handle_presentation_proposal_message_for_accepted_non_revocable_referents() {
+            let _setup = SetupAriesMocks::init();
+
+            let mut verifier_sm = _verifier_sm().set_non_revocable_referents(vec![String::from("attribute_0")]);
+            verifier_sm = verifier_sm.step(VerifierMessages::SendPresentationRequest(mock_connection())).unwrap();
+
+            let proposal = _presentation_proposal().set_non_revocable_referents(vec![String::from("attribute_0")]);
+            verifier_sm = verifier_sm.step(VerifierMessages::PresentationProposalReceived(proposal)).unwrap();
+            assert_match!(VerifierState::PresentationRequestSent(_), verifier_sm.state);
+
+            let proposal = _presentation_proposal().set_non_revocable_referents(vec![String::from("attribute_1")]);
+            verifier_sm = verifier_sm.step(VerifierMessages::PresentationProposalReceived(proposal)).unwrap();
+            assert_match!(VerifierState::Finished(_), verifier_sm.state);
+        }
+
         #[test]
         fn test_prover_handle_presentation_reject_message_from_presentation_request_sent_state() {
             let _setup = SetupAriesMocks::init();
@@ -527,6 +594,18 @@ pub mod test {
                 assert_match!(A2AMessage::Presentation(_), message);
             }
 
+            // Presentation 2.0
+            {
+                let messages = map!(
+                    "key_1".to_string() => A2AMessage::PresentationRequest(_presentation_request()),
+                    "key_2".to_string() => A2AMessage::PresentationV2(_presentation().into())
+                );
+
+                let (uid, message) = verifier.find_message_to_handle(messages).unwrap();
+                assert_eq!("key_2", uid);
+                assert_eq!(A2AMessage::Presentation(_presentation()), message);
+            }
+
             // Presentation Proposal
             {
                 let messages = map!(
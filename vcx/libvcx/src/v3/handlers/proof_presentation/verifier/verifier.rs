@@ -16,6 +16,12 @@ pub struct Verifier {
     verifier_sm: VerifierSM
 }
 
+#[derive(Deserialize, Debug, Default)]
+struct RevocationDetails {
+    #[serde(default)]
+    accept_non_revocable: Vec<String>,
+}
+
 impl Verifier {
     pub fn create(source_id: String,
                   requested_attrs: String,
@@ -30,16 +36,22 @@ impl Verifier {
                 .set_name(name)
                 .set_requested_attributes(requested_attrs)?
                 .set_requested_predicates(requested_predicates)?
-                .set_not_revoked_interval(revocation_details)?
+                .set_not_revoked_interval(revocation_details.clone())?
                 .set_nonce()?;
 
+        let revocation_details: RevocationDetails = ::serde_json::from_str(&revocation_details)
+            .map_err(|_| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Invalid Revocation Interval: {:?}", revocation_details)))?;
+
         Ok(Verifier {
-            verifier_sm: VerifierSM::new(presentation_request, source_id),
+            verifier_sm: VerifierSM::new(presentation_request, source_id)
+                .set_non_revocable_referents(revocation_details.accept_non_revocable),
         })
     }
 
     pub fn get_source_id(&self) -> String { self.verifier_sm.source_id() }
 
+    pub fn get_thread_id(&self) -> String { self.verifier_sm.thread_id() }
+
     pub fn state(&self) -> u32 {
         trace!("Verifier::state >>>");
         self.verifier_sm.state()
@@ -50,6 +62,13 @@ impl Verifier {
         self.verifier_sm.presentation_status()
     }
 
+    pub fn get_problem_report(&self) -> VcxResult<String> {
+        trace!("Verifier::get_problem_report >>>");
+        self.verifier_sm.get_problem_report()
+            .map(|problem_report| json!(problem_report.to_a2a_message()).to_string())
+            .ok_or(VcxError::from_msg(VcxErrorKind::NotReady, "No Problem Report was sent or received"))
+    }
+
     pub fn update_state(&mut self, message: Option<&str>) -> VcxResult<()> {
         trace!("Verifier::update_state >>> message: {:?}", message);
 
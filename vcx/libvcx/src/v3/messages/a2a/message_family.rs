@@ -7,11 +7,18 @@ pub enum MessageFamilies {
     Notification,
     Signature,
     CredentialIssuance,
+    CredentialIssuanceV2,
     ReportProblem,
     PresentProof,
+    PresentProofV2,
     TrustPing,
     DiscoveryFeatures,
     Basicmessage,
+    OutOfBand,
+    DidExchange,
+    CoordinateMediation,
+    MessagePickup,
+    RevocationNotification,
     Unknown(String)
 }
 
@@ -25,11 +32,18 @@ impl MessageFamilies {
             MessageFamilies::Notification => "1.0",
             MessageFamilies::Signature => "1.0",
             MessageFamilies::CredentialIssuance => "1.0",
+            MessageFamilies::CredentialIssuanceV2 => "2.0",
             MessageFamilies::ReportProblem => "1.0",
             MessageFamilies::PresentProof => "1.0",
+            MessageFamilies::PresentProofV2 => "2.0",
             MessageFamilies::TrustPing => "1.0",
             MessageFamilies::DiscoveryFeatures => "1.0",
             MessageFamilies::Basicmessage => "1.0",
+            MessageFamilies::OutOfBand => "1.0",
+            MessageFamilies::DidExchange => "1.0",
+            MessageFamilies::CoordinateMediation => "1.0",
+            MessageFamilies::MessagePickup => "2.0",
+            MessageFamilies::RevocationNotification => "1.0",
             MessageFamilies::Unknown(_) => "1.0"
         }
     }
@@ -45,16 +59,36 @@ impl MessageFamilies {
             MessageFamilies::Notification => None,
             MessageFamilies::Signature => None,
             MessageFamilies::CredentialIssuance => Some((Actors::Issuer, Actors::Holder)),
+            MessageFamilies::CredentialIssuanceV2 => Some((Actors::Issuer, Actors::Holder)),
             MessageFamilies::ReportProblem => None,
             MessageFamilies::PresentProof => Some((Actors::Prover, Actors::Verifier)),
+            MessageFamilies::PresentProofV2 => Some((Actors::Prover, Actors::Verifier)),
             MessageFamilies::TrustPing => Some((Actors::Sender, Actors::Receiver)),
             MessageFamilies::DiscoveryFeatures => Some((Actors::Sender, Actors::Receiver)),
             MessageFamilies::Basicmessage => Some((Actors::Sender, Actors::Receiver)),
+            MessageFamilies::OutOfBand => Some((Actors::Inviter, Actors::Invitee)),
+            MessageFamilies::DidExchange => Some((Actors::Inviter, Actors::Invitee)),
+            MessageFamilies::CoordinateMediation => Some((Actors::Mediator, Actors::Recipient)),
+            MessageFamilies::MessagePickup => Some((Actors::Mediator, Actors::Recipient)),
+            MessageFamilies::RevocationNotification => Some((Actors::Issuer, Actors::Holder)),
             MessageFamilies::Unknown(_) => None
         }
     }
 }
 
+impl MessageFamilies {
+    // Families sharing the name are told apart by the major version
+    pub fn from_versioned(family: String, version: &str) -> MessageFamilies {
+        let major = version.split('.').next().unwrap_or_default();
+
+        match (family.as_str(), major) {
+            ("issue-credential", "2") => MessageFamilies::CredentialIssuanceV2,
+            ("present-proof", "2") => MessageFamilies::PresentProofV2,
+            _ => MessageFamilies::from(family)
+        }
+    }
+}
+
 impl From<String> for MessageFamilies {
     fn from(family: String) -> Self {
         match family.as_str() {
@@ -68,6 +102,11 @@ impl From<String> for MessageFamilies {
             "trust_ping" => MessageFamilies::TrustPing,
             "discover-features" => MessageFamilies::DiscoveryFeatures,
             "basicmessage" => MessageFamilies::Basicmessage,
+            "out-of-band" => MessageFamilies::OutOfBand,
+            "didexchange" => MessageFamilies::DidExchange,
+            "coordinate-mediation" => MessageFamilies::CoordinateMediation,
+            "messagepickup" => MessageFamilies::MessagePickup,
+            "revocation_notification" => MessageFamilies::RevocationNotification,
             family @ _ => MessageFamilies::Unknown(family.to_string())
         }
     }
@@ -81,11 +120,18 @@ impl ::std::string::ToString for MessageFamilies {
             MessageFamilies::Notification => "notification".to_string(),
             MessageFamilies::Signature => "signature".to_string(),
             MessageFamilies::CredentialIssuance => "issue-credential".to_string(),
+            MessageFamilies::CredentialIssuanceV2 => "issue-credential".to_string(),
             MessageFamilies::ReportProblem => "report-problem".to_string(),
             MessageFamilies::PresentProof => "present-proof".to_string(),
+            MessageFamilies::PresentProofV2 => "present-proof".to_string(),
             MessageFamilies::TrustPing => "trust_ping".to_string(),
             MessageFamilies::DiscoveryFeatures => "discover-features".to_string(),
             MessageFamilies::Basicmessage => "basicmessage".to_string(),
+            MessageFamilies::OutOfBand => "out-of-band".to_string(),
+            MessageFamilies::DidExchange => "didexchange".to_string(),
+            MessageFamilies::CoordinateMediation => "coordinate-mediation".to_string(),
+            MessageFamilies::MessagePickup => "messagepickup".to_string(),
+            MessageFamilies::RevocationNotification => "revocation_notification".to_string(),
             MessageFamilies::Unknown(family) => family.to_string()
         }
     }
@@ -33,7 +33,7 @@ impl<'de> Deserialize<'de> for MessageType {
                 let (did, family, version, type_) = parse_message_type(type_).map_err(de::Error::custom)?;
                 Ok(MessageType {
                     did,
-                    family: MessageFamilies::from(family),
+                    family: MessageFamilies::from_versioned(family, &version),
                     version,
                     type_,
                 })
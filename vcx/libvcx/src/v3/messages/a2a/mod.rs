@@ -23,15 +23,46 @@ use v3::messages::issuance::credential_offer::CredentialOffer;
 use v3::messages::issuance::credential_request::CredentialRequest;
 use v3::messages::issuance::credential::Credential;
 
+use v3::messages::issuance_v2::credential_proposal::CredentialProposalV2;
+use v3::messages::issuance_v2::credential_offer::CredentialOfferV2;
+use v3::messages::issuance_v2::credential_request::CredentialRequestV2;
+use v3::messages::issuance_v2::credential::CredentialV2;
+
 use v3::messages::proof_presentation::presentation_proposal::PresentationProposal;
 use v3::messages::proof_presentation::presentation_request::PresentationRequest;
 use v3::messages::proof_presentation::presentation::Presentation;
 
+use v3::messages::proof_presentation::v2::presentation_proposal::PresentationProposalV2;
+use v3::messages::proof_presentation::v2::presentation_request::PresentationRequestV2;
+use v3::messages::proof_presentation::v2::presentation::PresentationV2;
+
 use v3::messages::discovery::query::Query;
 use v3::messages::discovery::disclose::Disclose;
 
 use v3::messages::basic_message::message::BasicMessage;
 
+use v3::messages::out_of_band::invitation::Invitation as OutOfBandInvitation;
+use v3::messages::out_of_band::handshake_reuse::HandshakeReuse;
+use v3::messages::out_of_band::handshake_reuse_accepted::HandshakeReuseAccepted;
+
+use v3::messages::didexchange::request::Request as DidExchangeRequest;
+use v3::messages::didexchange::response::Response as DidExchangeResponse;
+use v3::messages::didexchange::complete::Complete as DidExchangeComplete;
+
+use v3::messages::mediation::mediate_request::MediateRequest;
+use v3::messages::mediation::mediate_grant::MediateGrant;
+use v3::messages::mediation::mediate_deny::MediateDeny;
+use v3::messages::mediation::keylist_update::KeylistUpdate;
+use v3::messages::mediation::keylist_update_response::KeylistUpdateResponse;
+
+use v3::messages::pickup::status_request::StatusRequest as PickupStatusRequest;
+use v3::messages::pickup::status::Status as PickupStatus;
+use v3::messages::pickup::delivery_request::DeliveryRequest;
+use v3::messages::pickup::delivery::Delivery;
+use v3::messages::pickup::messages_received::MessagesReceived;
+
+use v3::messages::revocation_notification::revoke::Revoke;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum A2AMessage {
     /// routing
@@ -58,12 +89,25 @@ pub enum A2AMessage {
     Credential(Credential),
     CredentialAck(Ack),
 
+    /// credential issuance 2.0
+    CredentialProposalV2(CredentialProposalV2),
+    CredentialOfferV2(CredentialOfferV2),
+    CredentialRequestV2(CredentialRequestV2),
+    CredentialV2(CredentialV2),
+    CredentialAckV2(Ack),
+
     /// proof presentation
     PresentationProposal(PresentationProposal),
     PresentationRequest(PresentationRequest),
     Presentation(Presentation),
     PresentationAck(Ack),
 
+    /// proof presentation 2.0
+    PresentationProposalV2(PresentationProposalV2),
+    PresentationRequestV2(PresentationRequestV2),
+    PresentationV2(PresentationV2),
+    PresentationAckV2(Ack),
+
     /// discovery features
     Query(Query),
     Disclose(Disclose),
@@ -71,6 +115,33 @@ pub enum A2AMessage {
     /// basic message
     BasicMessage(BasicMessage),
 
+    /// out-of-band
+    OutOfBandInvitation(OutOfBandInvitation),
+    HandshakeReuse(HandshakeReuse),
+    HandshakeReuseAccepted(HandshakeReuseAccepted),
+
+    /// didexchange
+    DidExchangeRequest(DidExchangeRequest),
+    DidExchangeResponse(DidExchangeResponse),
+    DidExchangeComplete(DidExchangeComplete),
+
+    /// coordinate-mediation
+    MediateRequest(MediateRequest),
+    MediateGrant(MediateGrant),
+    MediateDeny(MediateDeny),
+    KeylistUpdate(KeylistUpdate),
+    KeylistUpdateResponse(KeylistUpdateResponse),
+
+    /// message pickup
+    PickupStatusRequest(PickupStatusRequest),
+    PickupStatus(PickupStatus),
+    DeliveryRequest(DeliveryRequest),
+    Delivery(Delivery),
+    MessagesReceived(MessagesReceived),
+
+    /// revocation notification
+    RevocationNotification(Revoke),
+
     /// Any Raw Message
     Generic(Value),
 }
@@ -155,6 +226,31 @@ impl<'de> Deserialize<'de> for A2AMessage {
                     .map(|msg| A2AMessage::CredentialAck(msg))
                     .map_err(de::Error::custom)
             }
+            (MessageFamilies::CredentialIssuanceV2, A2AMessage::CREDENTIAL) => {
+                CredentialV2::deserialize(value)
+                    .map(|msg| A2AMessage::CredentialV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CredentialIssuanceV2, A2AMessage::PROPOSE_CREDENTIAL) => {
+                CredentialProposalV2::deserialize(value)
+                    .map(|msg| A2AMessage::CredentialProposalV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CredentialIssuanceV2, A2AMessage::CREDENTIAL_OFFER) => {
+                CredentialOfferV2::deserialize(value)
+                    .map(|msg| A2AMessage::CredentialOfferV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CredentialIssuanceV2, A2AMessage::REQUEST_CREDENTIAL) => {
+                CredentialRequestV2::deserialize(value)
+                    .map(|msg| A2AMessage::CredentialRequestV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CredentialIssuanceV2, A2AMessage::ACK) => {
+                Ack::deserialize(value)
+                    .map(|msg| A2AMessage::CredentialAckV2(msg))
+                    .map_err(de::Error::custom)
+            }
             (MessageFamilies::PresentProof, A2AMessage::PROPOSE_PRESENTATION) => {
                 PresentationProposal::deserialize(value)
                     .map(|msg| A2AMessage::PresentationProposal(msg))
@@ -175,6 +271,26 @@ impl<'de> Deserialize<'de> for A2AMessage {
                     .map(|msg| A2AMessage::PresentationAck(msg))
                     .map_err(de::Error::custom)
             }
+            (MessageFamilies::PresentProofV2, A2AMessage::PROPOSE_PRESENTATION) => {
+                PresentationProposalV2::deserialize(value)
+                    .map(|msg| A2AMessage::PresentationProposalV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::PresentProofV2, A2AMessage::REQUEST_PRESENTATION) => {
+                PresentationRequestV2::deserialize(value)
+                    .map(|msg| A2AMessage::PresentationRequestV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::PresentProofV2, A2AMessage::PRESENTATION) => {
+                PresentationV2::deserialize(value)
+                    .map(|msg| A2AMessage::PresentationV2(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::PresentProofV2, A2AMessage::ACK) => {
+                Ack::deserialize(value)
+                    .map(|msg| A2AMessage::PresentationAckV2(msg))
+                    .map_err(de::Error::custom)
+            }
             (MessageFamilies::DiscoveryFeatures, A2AMessage::QUERY) => {
                 Query::deserialize(value)
                     .map(|msg| A2AMessage::Query(msg))
@@ -190,6 +306,97 @@ impl<'de> Deserialize<'de> for A2AMessage {
                     .map(|msg| A2AMessage::BasicMessage(msg))
                     .map_err(de::Error::custom)
             }
+            (MessageFamilies::OutOfBand, A2AMessage::OUT_OF_BAND_INVITATION) => {
+                OutOfBandInvitation::deserialize(value)
+                    .map(|msg| A2AMessage::OutOfBandInvitation(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::OutOfBand, A2AMessage::HANDSHAKE_REUSE) => {
+                HandshakeReuse::deserialize(value)
+                    .map(|msg| A2AMessage::HandshakeReuse(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::OutOfBand, A2AMessage::HANDSHAKE_REUSE_ACCEPTED) => {
+                HandshakeReuseAccepted::deserialize(value)
+                    .map(|msg| A2AMessage::HandshakeReuseAccepted(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::DidExchange, A2AMessage::CONNECTION_REQUEST) => {
+                DidExchangeRequest::deserialize(value)
+                    .map(|msg| A2AMessage::DidExchangeRequest(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::DidExchange, A2AMessage::CONNECTION_RESPONSE) => {
+                DidExchangeResponse::deserialize(value)
+                    .map(|msg| A2AMessage::DidExchangeResponse(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::DidExchange, A2AMessage::DID_EXCHANGE_COMPLETE) => {
+                DidExchangeComplete::deserialize(value)
+                    .map(|msg| A2AMessage::DidExchangeComplete(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CoordinateMediation, A2AMessage::MEDIATE_REQUEST) => {
+                MediateRequest::deserialize(value)
+                    .map(|msg| A2AMessage::MediateRequest(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CoordinateMediation, A2AMessage::MEDIATE_GRANT) => {
+                MediateGrant::deserialize(value)
+                    .map(|msg| A2AMessage::MediateGrant(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CoordinateMediation, A2AMessage::MEDIATE_DENY) => {
+                MediateDeny::deserialize(value)
+                    .map(|msg| A2AMessage::MediateDeny(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CoordinateMediation, A2AMessage::KEYLIST_UPDATE) => {
+                KeylistUpdate::deserialize(value)
+                    .map(|msg| A2AMessage::KeylistUpdate(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::CoordinateMediation, A2AMessage::KEYLIST_UPDATE_RESPONSE) => {
+                KeylistUpdateResponse::deserialize(value)
+                    .map(|msg| A2AMessage::KeylistUpdateResponse(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::MessagePickup, A2AMessage::PICKUP_STATUS_REQUEST) => {
+                PickupStatusRequest::deserialize(value)
+                    .map(|msg| A2AMessage::PickupStatusRequest(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::MessagePickup, A2AMessage::PICKUP_STATUS) => {
+                PickupStatus::deserialize(value)
+                    .map(|msg| A2AMessage::PickupStatus(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::MessagePickup, A2AMessage::DELIVERY_REQUEST) => {
+                DeliveryRequest::deserialize(value)
+                    .map(|msg| A2AMessage::DeliveryRequest(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::MessagePickup, A2AMessage::DELIVERY) => {
+                Delivery::deserialize(value)
+                    .map(|msg| A2AMessage::Delivery(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::MessagePickup, A2AMessage::MESSAGES_RECEIVED) => {
+                MessagesReceived::deserialize(value)
+                    .map(|msg| A2AMessage::MessagesReceived(msg))
+                    .map_err(de::Error::custom)
+            }
+            (MessageFamilies::RevocationNotification, A2AMessage::REVOKE) => {
+                Revoke::deserialize(value)
+                    .map(|msg| A2AMessage::RevocationNotification(msg))
+                    .map_err(de::Error::custom)
+            }
+            // Problem Reports of both handshake protocols share the structure
+            (MessageFamilies::DidExchange, A2AMessage::CONNECTION_PROBLEM_REPORT) => {
+                ConnectionProblemReport::deserialize(value)
+                    .map(|msg| A2AMessage::ConnectionProblemReport(msg))
+                    .map_err(de::Error::custom)
+            }
             (_, other_type) => {
                 warn!("Unexpected @type field structure: {}", other_type);
                 Ok(A2AMessage::Generic(value))
@@ -222,13 +429,39 @@ impl Serialize for A2AMessage {
             A2AMessage::CredentialProposal(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuance, A2AMessage::PROPOSE_CREDENTIAL),
             A2AMessage::CredentialRequest(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuance, A2AMessage::REQUEST_CREDENTIAL),
             A2AMessage::CredentialAck(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuance, A2AMessage::ACK),
+            A2AMessage::CredentialOfferV2(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuanceV2, A2AMessage::CREDENTIAL_OFFER),
+            A2AMessage::CredentialV2(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuanceV2, A2AMessage::CREDENTIAL),
+            A2AMessage::CredentialProposalV2(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuanceV2, A2AMessage::PROPOSE_CREDENTIAL),
+            A2AMessage::CredentialRequestV2(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuanceV2, A2AMessage::REQUEST_CREDENTIAL),
+            A2AMessage::CredentialAckV2(msg) => set_a2a_message_type(msg, MessageFamilies::CredentialIssuanceV2, A2AMessage::ACK),
             A2AMessage::PresentationProposal(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProof, A2AMessage::PROPOSE_PRESENTATION),
             A2AMessage::PresentationRequest(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProof, A2AMessage::REQUEST_PRESENTATION),
             A2AMessage::Presentation(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProof, A2AMessage::PRESENTATION),
             A2AMessage::PresentationAck(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProof, A2AMessage::ACK),
+            A2AMessage::PresentationProposalV2(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProofV2, A2AMessage::PROPOSE_PRESENTATION),
+            A2AMessage::PresentationRequestV2(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProofV2, A2AMessage::REQUEST_PRESENTATION),
+            A2AMessage::PresentationV2(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProofV2, A2AMessage::PRESENTATION),
+            A2AMessage::PresentationAckV2(msg) => set_a2a_message_type(msg, MessageFamilies::PresentProofV2, A2AMessage::ACK),
             A2AMessage::Query(msg) => set_a2a_message_type(msg, MessageFamilies::DiscoveryFeatures, A2AMessage::QUERY),
             A2AMessage::Disclose(msg) => set_a2a_message_type(msg, MessageFamilies::DiscoveryFeatures, A2AMessage::DISCLOSE),
             A2AMessage::BasicMessage(msg) => set_a2a_message_type(msg, MessageFamilies::Basicmessage, A2AMessage::BASIC_MESSAGE),
+            A2AMessage::OutOfBandInvitation(msg) => set_a2a_message_type(msg, MessageFamilies::OutOfBand, A2AMessage::OUT_OF_BAND_INVITATION),
+            A2AMessage::HandshakeReuse(msg) => set_a2a_message_type(msg, MessageFamilies::OutOfBand, A2AMessage::HANDSHAKE_REUSE),
+            A2AMessage::HandshakeReuseAccepted(msg) => set_a2a_message_type(msg, MessageFamilies::OutOfBand, A2AMessage::HANDSHAKE_REUSE_ACCEPTED),
+            A2AMessage::DidExchangeRequest(msg) => set_a2a_message_type(msg, MessageFamilies::DidExchange, A2AMessage::CONNECTION_REQUEST),
+            A2AMessage::DidExchangeResponse(msg) => set_a2a_message_type(msg, MessageFamilies::DidExchange, A2AMessage::CONNECTION_RESPONSE),
+            A2AMessage::DidExchangeComplete(msg) => set_a2a_message_type(msg, MessageFamilies::DidExchange, A2AMessage::DID_EXCHANGE_COMPLETE),
+            A2AMessage::MediateRequest(msg) => set_a2a_message_type(msg, MessageFamilies::CoordinateMediation, A2AMessage::MEDIATE_REQUEST),
+            A2AMessage::MediateGrant(msg) => set_a2a_message_type(msg, MessageFamilies::CoordinateMediation, A2AMessage::MEDIATE_GRANT),
+            A2AMessage::MediateDeny(msg) => set_a2a_message_type(msg, MessageFamilies::CoordinateMediation, A2AMessage::MEDIATE_DENY),
+            A2AMessage::KeylistUpdate(msg) => set_a2a_message_type(msg, MessageFamilies::CoordinateMediation, A2AMessage::KEYLIST_UPDATE),
+            A2AMessage::KeylistUpdateResponse(msg) => set_a2a_message_type(msg, MessageFamilies::CoordinateMediation, A2AMessage::KEYLIST_UPDATE_RESPONSE),
+            A2AMessage::PickupStatusRequest(msg) => set_a2a_message_type(msg, MessageFamilies::MessagePickup, A2AMessage::PICKUP_STATUS_REQUEST),
+            A2AMessage::PickupStatus(msg) => set_a2a_message_type(msg, MessageFamilies::MessagePickup, A2AMessage::PICKUP_STATUS),
+            A2AMessage::DeliveryRequest(msg) => set_a2a_message_type(msg, MessageFamilies::MessagePickup, A2AMessage::DELIVERY_REQUEST),
+            A2AMessage::Delivery(msg) => set_a2a_message_type(msg, MessageFamilies::MessagePickup, A2AMessage::DELIVERY),
+            A2AMessage::MessagesReceived(msg) => set_a2a_message_type(msg, MessageFamilies::MessagePickup, A2AMessage::MESSAGES_RECEIVED),
+            A2AMessage::RevocationNotification(msg) => set_a2a_message_type(msg, MessageFamilies::RevocationNotification, A2AMessage::REVOKE),
             A2AMessage::Generic(msg) => Ok(msg.clone())
         }.map_err(ser::Error::custom)?;
 
@@ -283,6 +516,21 @@ impl A2AMessage {
     const QUERY: &'static str = "query";
     const DISCLOSE: &'static str = "disclose";
     const BASIC_MESSAGE: &'static str = "message";
+    const OUT_OF_BAND_INVITATION: &'static str = "invitation";
+    const HANDSHAKE_REUSE: &'static str = "handshake-reuse";
+    const HANDSHAKE_REUSE_ACCEPTED: &'static str = "handshake-reuse-accepted";
+    const DID_EXCHANGE_COMPLETE: &'static str = "complete";
+    const MEDIATE_REQUEST: &'static str = "mediate-request";
+    const MEDIATE_GRANT: &'static str = "mediate-grant";
+    const MEDIATE_DENY: &'static str = "mediate-deny";
+    const KEYLIST_UPDATE: &'static str = "keylist-update";
+    const KEYLIST_UPDATE_RESPONSE: &'static str = "keylist-update-response";
+    const PICKUP_STATUS_REQUEST: &'static str = "status-request";
+    const PICKUP_STATUS: &'static str = "status";
+    const DELIVERY_REQUEST: &'static str = "delivery-request";
+    const DELIVERY: &'static str = "delivery";
+    const MESSAGES_RECEIVED: &'static str = "messages-received";
+    const REVOKE: &'static str = "revoke";
 }
 
 #[macro_export]
@@ -21,10 +21,21 @@ impl ProtocolRegistry {
                 family @ MessageFamilies::Notification |
                 family @ MessageFamilies::Connections |
                 family @ MessageFamilies::CredentialIssuance |
+                family @ MessageFamilies::CredentialIssuanceV2 |
                 family @ MessageFamilies::PresentProof |
+                family @ MessageFamilies::PresentProofV2 |
                 family @ MessageFamilies::TrustPing |
                 family @ MessageFamilies::Basicmessage |
+                family @ MessageFamilies::OutOfBand |
+                family @ MessageFamilies::DidExchange |
+                family @ MessageFamilies::RevocationNotification |
                 family @ MessageFamilies::DiscoveryFeatures => registry.add_protocol(&actors, family),
+                // Only the recipient side of mediation is implemented
+                family @ MessageFamilies::CoordinateMediation |
+                family @ MessageFamilies::MessagePickup => {
+                    let actors = actors.iter().cloned().filter(|actor| *actor != Actors::Mediator).collect();
+                    registry.add_protocol(&actors, family)
+                }
                 MessageFamilies::Signature => {}
                 MessageFamilies::Unknown(_) => {}
             }
@@ -59,7 +70,7 @@ impl ProtocolRegistry {
         match query {
             Some(query_) if query_ == "*" => self.protocols.clone(),
             Some(query_) => {
-                match Regex::new(query_) {
+                match Regex::new(&_query_to_regex(query_)) {
                     Ok(re) => self.protocols.iter().filter(|protocol| re.is_match(&protocol.pid)).cloned().collect(),
                     Err(_) => vec![]
                 }
@@ -73,6 +84,11 @@ impl ProtocolRegistry {
     }
 }
 
+// RFC 0031 query is a protocol identifier prefix where `*` stands for any sequence of characters
+fn _query_to_regex(query: &str) -> String {
+    format!("^{}", ::regex::escape(query).replace("\\*", ".*"))
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -132,6 +148,33 @@ pub mod tests {
         assert_eq!(expected_protocols, protocols);
     }
 
+    #[test]
+    fn test_get_protocols_for_query_works_for_wildcard_in_the_middle() {
+        let _setup = SetupEmpty::init();
+
+        let registry: ProtocolRegistry = _protocol_registry();
+
+        let protocols = registry.get_protocols_for_query(Some("protocol_*_test"));
+
+        let expected_protocols = vec![
+            ProtocolDescriptor { pid: "protocol_1.0_test".to_string(), roles: None },
+        ];
+        assert_eq!(expected_protocols, protocols);
+    }
+
+    #[test]
+    fn test_get_protocols_for_query_treats_regex_characters_literally() {
+        let _setup = SetupEmpty::init();
+
+        let registry: ProtocolRegistry = _protocol_registry();
+
+        let protocols = registry.get_protocols_for_query(Some("protocol_1.0_tes."));
+        assert!(protocols.is_empty());
+
+        let protocols = registry.get_protocols_for_query(Some("protocol?1.0"));
+        assert!(protocols.is_empty());
+    }
+
     #[test]
     fn test_get_protocols_for_query_works_for_exact_protocol() {
         let _setup = SetupEmpty::init();
@@ -1,7 +1,14 @@
+extern crate rust_base58;
+
 use std::str::from_utf8;
+use self::rust_base58::{FromBase58, ToBase58};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json;
+use serde_json::Value;
+use openssl::sha::sha256;
 
 use error::{VcxResult, VcxError, VcxErrorKind};
+use utils::libindy::crypto;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct Attachments(pub Vec<Attachment>);
@@ -29,6 +36,28 @@ impl Attachments {
         self.add_json_attachment(id, json, AttachmentEncoding::Base64)
     }
 
+    pub fn ids(&self) -> Vec<AttachmentId> {
+        self.0.iter()
+            .filter_map(|attachment| match attachment {
+                Attachment::JSON(ref json) => Some(json.id().clone()),
+                Attachment::Blank => None
+            })
+            .collect()
+    }
+
+    // Attachments with given ids only, in the original order
+    pub fn select(&self, ids: &[AttachmentId]) -> Attachments {
+        Attachments(
+            self.0.iter()
+                .filter(|attachment| match attachment {
+                    Attachment::JSON(ref json) => ids.contains(json.id()),
+                    Attachment::Blank => false
+                })
+                .cloned()
+                .collect()
+        )
+    }
+
     pub fn content(&self) -> VcxResult<String> {
         match self.get() {
             Some(Attachment::JSON(ref attach)) => attach.get_data(),
@@ -37,6 +66,38 @@ impl Attachments {
     }
 }
 
+// Format of the attachment, used by 2.0 protocols to tell apart the same object in different formats (Aries RFC 0434)
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct AttachmentFormat {
+    pub attach_id: AttachmentId,
+    pub format: String,
+}
+
+impl AttachmentFormat {
+    pub fn for_attachments(attachments: &Attachments, format: &str) -> Vec<AttachmentFormat> {
+        attachments.ids()
+            .into_iter()
+            .map(|attach_id| AttachmentFormat { attach_id, format: format.to_string() })
+            .collect()
+    }
+}
+
+// Only attachments of the given format are selected, attachments of other formats are skipped.
+pub fn select_attachments(formats: &[AttachmentFormat], attachments: &Attachments, format: &str) -> VcxResult<Attachments> {
+    let ids: Vec<AttachmentId> = formats.iter()
+        .filter(|attachment_format| attachment_format.format == format)
+        .map(|attachment_format| attachment_format.attach_id.clone())
+        .collect();
+
+    let selected = attachments.select(&ids);
+
+    if selected.0.is_empty() {
+        return Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, format!("Message doesn't contain attachment of supported format: {}", format)));
+    }
+
+    Ok(selected)
+}
+
 #[serde(tag = "mime-type")]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Attachment {
@@ -49,49 +110,149 @@ pub enum Attachment {
 pub struct Json {
     #[serde(rename = "@id")]
     id: AttachmentId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    byte_count: Option<u64>,
     data: AttachmentData,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AttachmentId {
-    #[serde(rename = "libindy-cred-offer-0")]
     CredentialOffer,
-    #[serde(rename = "libindy-cred-request-0")]
     CredentialRequest,
-    #[serde(rename = "libindy-cred-0")]
     Credential,
-    #[serde(rename = "libindy-request-presentation-0")]
     PresentationRequest,
-    #[serde(rename = "libindy-presentation-0")]
-    Presentation
+    Presentation,
+    CredentialFilter,
+    PresentationProposal,
+    PresentationDefinition,
+    PresentationSubmission,
+    // Other agents may use arbitrary ids
+    Other(String),
+}
+
+impl AttachmentId {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AttachmentId::CredentialOffer => "libindy-cred-offer-0",
+            AttachmentId::CredentialRequest => "libindy-cred-request-0",
+            AttachmentId::Credential => "libindy-cred-0",
+            AttachmentId::PresentationRequest => "libindy-request-presentation-0",
+            AttachmentId::Presentation => "libindy-presentation-0",
+            AttachmentId::CredentialFilter => "libindy-cred-filter-0",
+            AttachmentId::PresentationProposal => "libindy-presentation-proposal-0",
+            AttachmentId::PresentationDefinition => "dif-presentation-definition-0",
+            AttachmentId::PresentationSubmission => "dif-presentation-submission-0",
+            AttachmentId::Other(id) => id.as_str(),
+        }
+    }
+}
+
+impl Serialize for AttachmentId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        Value::String(self.as_str().to_string()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AttachmentId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let value = Value::deserialize(deserializer).map_err(de::Error::custom)?;
+        match value.as_str() {
+            Some("libindy-cred-offer-0") => Ok(AttachmentId::CredentialOffer),
+            Some("libindy-cred-request-0") => Ok(AttachmentId::CredentialRequest),
+            Some("libindy-cred-0") => Ok(AttachmentId::Credential),
+            Some("libindy-request-presentation-0") => Ok(AttachmentId::PresentationRequest),
+            Some("libindy-presentation-0") => Ok(AttachmentId::Presentation),
+            Some("libindy-cred-filter-0") => Ok(AttachmentId::CredentialFilter),
+            Some("libindy-presentation-proposal-0") => Ok(AttachmentId::PresentationProposal),
+            Some("dif-presentation-definition-0") => Ok(AttachmentId::PresentationDefinition),
+            Some("dif-presentation-submission-0") => Ok(AttachmentId::PresentationSubmission),
+            Some(id) => Ok(AttachmentId::Other(id.to_string())),
+            None => Err(de::Error::custom("Unexpected attachment id."))
+        }
+    }
 }
 
 impl Json {
     pub fn new(id: AttachmentId, json: serde_json::Value, encoding: AttachmentEncoding) -> VcxResult<Json> {
+        let content = Json::_content(json)?;
+
         let data: AttachmentData = match encoding {
-            AttachmentEncoding::Base64 => {
-                AttachmentData::Base64(
-                    base64::encode(&
-                        match json {
-                            ::serde_json::Value::Object(obj) => {
-                                serde_json::to_string(&obj)
-                                    .map_err(|_| VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid Attachment Json".to_string()))?
-                            }
-                            ::serde_json::Value::String(str) => str,
-                            val => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Unsupported Json value: {:?}", val)))
-                        }
-                    )
-                )
-            }
+            AttachmentEncoding::Base64 => AttachmentData::base64(content.as_bytes())
         };
+
         Ok(Json {
             id,
+            byte_count: Some(content.len() as u64),
             data,
         })
     }
 
+    // Content signed with Ed25519 key as detached JWS (Aries RFC 0017)
+    pub fn new_signed(id: AttachmentId, json: serde_json::Value, verkey: &str) -> VcxResult<Json> {
+        let content = Json::_content(json)?;
+
+        let mut data = AttachmentData {
+            base64: Some(base64::encode_config(content.as_bytes(), base64::URL_SAFE_NO_PAD)),
+            ..AttachmentData::default()
+        };
+        data.sign(verkey)?;
+
+        Ok(Json {
+            id,
+            byte_count: Some(content.len() as u64),
+            data,
+        })
+    }
+
+    fn _content(json: serde_json::Value) -> VcxResult<String> {
+        match json {
+            ::serde_json::Value::Object(obj) => {
+                ::utils::json::to_signed_string(&obj)
+                    .map_err(|_| VcxError::from_msg(VcxErrorKind::InvalidJson, "Invalid Attachment Json".to_string()))
+            }
+            ::serde_json::Value::String(str) => Ok(str),
+            val => Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Unsupported Json value: {:?}", val)))
+        }
+    }
+
+    // Attachment content is hosted elsewhere. RFC 0017 requires sha256 for linked content.
+    pub fn from_links(id: AttachmentId, links: Vec<String>, sha256: &str, byte_count: Option<u64>) -> Json {
+        Json {
+            id,
+            byte_count,
+            data: AttachmentData {
+                links,
+                sha256: Some(sha256.to_string()),
+                ..AttachmentData::default()
+            },
+        }
+    }
+
+    pub fn id(&self) -> &AttachmentId {
+        &self.id
+    }
+
+    pub fn links(&self) -> &[String] {
+        &self.data.links
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.data.jws.is_some()
+    }
+
+    pub fn verify_signature(&self, verkey: &str) -> VcxResult<()> {
+        self.data.verify(verkey)
+    }
+
     pub fn get_data(&self) -> VcxResult<String> {
         let data = self.data.get_bytes()?;
+
+        if let Some(byte_count) = self.byte_count {
+            if data.len() as u64 != byte_count {
+                return Err(VcxError::from_msg(VcxErrorKind::IOError, format!("Attachment size {} doesn't match byte_count {}", data.len(), byte_count)));
+            }
+        }
+
         from_utf8(data.as_slice())
             .map(|s| s.to_string())
             .map_err(|_| VcxError::from_msg(VcxErrorKind::IOError, "Wrong bytes in attachment".to_string()))
@@ -103,30 +264,181 @@ pub enum AttachmentEncoding {
     Base64
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct AttachmentData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    // Hex encoded sha256 of the content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jws: Option<AttachmentSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentSignature {
+    pub header: JwsHeader,
+    pub protected: String,
+    pub signature: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum AttachmentData {
-    #[serde(rename = "base64")]
-    Base64(String)
+pub struct JwsHeader {
+    pub kid: String,
 }
 
+const JWS_ALGORITHM: &str = "EdDSA";
+
 impl AttachmentData {
+    pub fn base64(bytes: &[u8]) -> AttachmentData {
+        AttachmentData {
+            base64: Some(base64::encode(bytes)),
+            ..AttachmentData::default()
+        }
+    }
+
     pub fn get_bytes(&self) -> VcxResult<Vec<u8>> {
-        match self {
-            AttachmentData::Base64(s) => {
-                base64::decode(s).map_err(|_| VcxError::from_msg(VcxErrorKind::IOError, "Wrong bytes in attachment"))
+        let bytes = match (&self.base64, &self.json) {
+            (&Some(ref s), _) => _decode_base64(s)?,
+            (&None, &Some(ref json)) => json.to_string().into_bytes(),
+            (&None, &None) if !self.links.is_empty() =>
+                return Err(VcxError::from_msg(VcxErrorKind::IOError, format!("Attachment content is available by links only: {:?}", self.links))),
+            (&None, &None) => return Err(VcxError::from_msg(VcxErrorKind::IOError, "Attachment data is empty")),
+        };
+
+        if let Some(ref expected) = self.sha256 {
+            let actual: String = sha256(&bytes).iter().map(|b| format!("{:02x}", b)).collect();
+            if actual != expected.to_lowercase() {
+                return Err(VcxError::from_msg(VcxErrorKind::IOError, "Attachment content doesn't match sha256"));
             }
         }
+
+        Ok(bytes)
+    }
+
+    // Signing input is built of unpadded base64url payload, so it doesn't depend on encoding of the sender
+    fn _signing_input(&self, protected: &str) -> VcxResult<String> {
+        let payload = self.base64.as_ref()
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Only base64 encoded attachment can be signed"))?;
+
+        let payload = payload.replace('+', "-").replace('/', "_").replace('=', "");
+
+        Ok(format!("{}.{}", protected, payload))
+    }
+
+    pub fn sign(&mut self, verkey: &str) -> VcxResult<()> {
+        let kid = _did_key(verkey)?;
+
+        let protected = json!({
+            "alg": JWS_ALGORITHM,
+            "kid": kid,
+            "jwk": {
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": base64::encode_config(&_decode_verkey(verkey)?, base64::URL_SAFE_NO_PAD),
+                "kid": kid,
+            }
+        });
+        let protected = base64::encode_config(protected.to_string().as_bytes(), base64::URL_SAFE_NO_PAD);
+
+        let signature = crypto::sign(verkey, self._signing_input(&protected)?.as_bytes())?;
+
+        self.jws = Some(AttachmentSignature {
+            header: JwsHeader { kid },
+            protected,
+            signature: base64::encode_config(&signature, base64::URL_SAFE_NO_PAD),
+        });
+
+        Ok(())
+    }
+
+    // Attachment must be signed by the given key
+    pub fn verify(&self, verkey: &str) -> VcxResult<()> {
+        let jws = self.jws.as_ref()
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment is not signed"))?;
+
+        let protected: Value = ::serde_json::from_slice(&_decode_base64(&jws.protected)?)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize JWS protected header: {:?}", err)))?;
+
+        if protected["alg"].as_str() != Some(JWS_ALGORITHM) {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Unsupported JWS algorithm: {:?}", protected["alg"])));
+        }
+
+        let signer = match protected["jwk"]["x"].as_str() {
+            Some(x) => _decode_base64(x)?.to_base58(),
+            None => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "JWS protected header doesn't contain signer key"))
+        };
+
+        if signer != verkey {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Attachment is signed by unexpected key: {}", signer)));
+        }
+
+        let signature = _decode_base64(&jws.signature)?;
+
+        if !crypto::verify(verkey, self._signing_input(&jws.protected)?.as_bytes(), &signature)? {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Attachment signature is invalid"));
+        }
+
+        Ok(())
     }
 }
 
+// Other agents use both standard and url-safe alphabets, with or without padding
+fn _decode_base64(data: &str) -> VcxResult<Vec<u8>> {
+    base64::decode(data)
+        .or_else(|_| base64::decode_config(data, base64::URL_SAFE))
+        .or_else(|_| base64::decode_config(data, base64::URL_SAFE_NO_PAD))
+        .map_err(|_| VcxError::from_msg(VcxErrorKind::IOError, "Wrong bytes in attachment"))
+}
+
+fn _decode_verkey(verkey: &str) -> VcxResult<Vec<u8>> {
+    verkey.from_base58()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidVerkey, format!("Cannot decode verkey {}: {:?}", verkey, err)))
+}
+
+// did:key of Ed25519 key: multicodec prefix 0xed01, base58btc multibase
+fn _did_key(verkey: &str) -> VcxResult<String> {
+    let mut bytes = vec![0xed, 0x01];
+    bytes.extend(_decode_verkey(verkey)?);
+    Ok(format!("did:key:z{}", bytes.to_base58()))
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use utils::libindy::tests::test_setup;
 
     fn _json() -> serde_json::Value {
         json!({"field": "value"})
     }
 
+    #[test]
+    fn test_select_attachments_works() {
+        let mut attachments = Attachments::new();
+        attachments.add_json_attachment(AttachmentId::CredentialOffer, json!({"offer": "indy"}), AttachmentEncoding::Base64).unwrap();
+        attachments.add_json_attachment(AttachmentId::Other(String::from("ld-proof")), json!({"offer": "ld"}), AttachmentEncoding::Base64).unwrap();
+
+        let formats = vec![
+            AttachmentFormat { attach_id: AttachmentId::Other(String::from("ld-proof")), format: String::from("aries/ld-proof-vc-detail@v1.0") },
+            AttachmentFormat { attach_id: AttachmentId::CredentialOffer, format: String::from("hlindy/cred-abstract@v2.0") },
+        ];
+
+        let selected = select_attachments(&formats, &attachments, "hlindy/cred-abstract@v2.0").unwrap();
+        assert_eq!(json!({"offer": "indy"}).to_string(), selected.content().unwrap());
+
+        assert!(select_attachments(&formats, &attachments, "hlindy/cred@v2.0").is_err());
+    }
+
+    #[test]
+    fn test_attachment_format_serialization_works() {
+        let format = AttachmentFormat { attach_id: AttachmentId::CredentialOffer, format: String::from("hlindy/cred-abstract@v2.0") };
+        assert_eq!(json!({"attach_id": "libindy-cred-offer-0", "format": "hlindy/cred-abstract@v2.0"}), ::serde_json::to_value(&format).unwrap());
+    }
+
     #[test]
     fn test_create_json_attachment_works() {
         let json_attachment: Json = Json::new(AttachmentId::Credential, _json(), AttachmentEncoding::Base64).unwrap();
@@ -134,6 +446,57 @@ pub mod tests {
         assert_eq!(_json().to_string(), json_attachment.get_data().unwrap());
     }
 
+    #[test]
+    fn test_json_attachment_serialization_works() {
+        let json_attachment: Json = Json::new(AttachmentId::CredentialOffer, _json(), AttachmentEncoding::Base64).unwrap();
+
+        let expected = json!({
+            "mime-type": "application/json",
+            "@id": "libindy-cred-offer-0",
+            "byte_count": 17,
+            "data": {"base64": base64::encode(&_json().to_string())}
+        });
+        assert_eq!(expected, ::serde_json::to_value(&Attachment::JSON(json_attachment)).unwrap());
+    }
+
+    #[test]
+    fn test_json_attachment_deserialization_works_for_other_agents() {
+        let attachment: Attachment = ::serde_json::from_value(json!({
+            "mime-type": "application/json",
+            "@id": "0",
+            "data": {"json": _json()}
+        })).unwrap();
+
+        match attachment {
+            Attachment::JSON(ref json) => {
+                assert_eq!(&AttachmentId::Other(String::from("0")), json.id());
+                assert_eq!(_json().to_string(), json.get_data().unwrap());
+            }
+            _ => panic!("Unexpected attachment")
+        }
+    }
+
+    #[test]
+    fn test_attachment_data_verifies_sha256() {
+        let content = _json().to_string();
+        let hash: String = sha256(content.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect();
+
+        let mut data = AttachmentData::base64(content.as_bytes());
+        data.sha256 = Some(hash);
+        assert_eq!(content.into_bytes(), data.get_bytes().unwrap());
+
+        data.sha256 = Some(String::from("00"));
+        assert!(data.get_bytes().is_err());
+    }
+
+    #[test]
+    fn test_json_attachment_from_links_works() {
+        let json = Json::from_links(AttachmentId::Credential, vec![String::from("https://example.com/cred")], "00", None);
+
+        assert_eq!(&[String::from("https://example.com/cred")], json.links());
+        assert!(json.get_data().is_err());
+    }
+
     #[test]
     fn test_attachments_works() {
         {
@@ -153,4 +516,34 @@ pub mod tests {
             assert_eq!(_json().to_string(), attachments.content().unwrap());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_did_key_works() {
+        // did:key test vector for Ed25519 key
+        assert_eq!("did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH",
+                   _did_key("B12NYF8RrR3h41TDCTJojY59usg3mbtbjnFs7Eud1Y6u").unwrap());
+    }
+
+    #[test]
+    fn test_signed_json_attachment_works() {
+        let setup = test_setup::key();
+
+        let json = Json::new_signed(AttachmentId::Other(String::from("did_doc")), _json(), &setup.key).unwrap();
+
+        assert!(json.is_signed());
+        assert_eq!(_json().to_string(), json.get_data().unwrap());
+        json.verify_signature(&setup.key).unwrap();
+    }
+
+    #[test]
+    fn test_signed_json_attachment_verify_fails_for_other_key() {
+        let setup = test_setup::key();
+        let other_key = crypto::create_key(None).unwrap();
+
+        let json = Json::new_signed(AttachmentId::Other(String::from("did_doc")), _json(), &setup.key).unwrap();
+        assert!(json.verify_signature(&other_key).is_err());
+
+        let unsigned = Json::new(AttachmentId::Other(String::from("did_doc")), _json(), AttachmentEncoding::Base64).unwrap();
+        assert!(unsigned.verify_signature(&setup.key).is_err());
+    }
+}
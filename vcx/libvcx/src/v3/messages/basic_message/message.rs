@@ -1,5 +1,6 @@
 use v3::messages::a2a::{MessageId, A2AMessage};
 use v3::messages::localization::Localization;
+use v3::messages::ack::{Ack, PleaseAck};
 use chrono::prelude::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -11,6 +12,9 @@ pub struct BasicMessage {
     #[serde(rename = "~l10n")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub l10n: Option<Localization>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
 }
 
 impl BasicMessage {
@@ -33,11 +37,18 @@ impl BasicMessage {
         self
     }
 
+    // Delivery receipt for the message
+    pub fn create_ack(&self) -> Ack {
+        Ack::create().set_thread_id(&self.id.0)
+    }
+
     pub fn to_a2a_message(&self) -> A2AMessage {
         A2AMessage::BasicMessage(self.clone()) // TODO: THINK how to avoid clone
     }
 }
 
+please_ack!(BasicMessage);
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -54,4 +65,17 @@ pub mod tests {
             .set_default_localization();
         assert_eq!(_content(), basic_message.content);
     }
+
+    #[test]
+    fn test_basic_message_with_please_ack_works() {
+        let basic_message: BasicMessage = BasicMessage::create()
+            .set_content(_content())
+            .ask_for_ack();
+
+        let json = ::serde_json::to_value(&basic_message.to_a2a_message()).unwrap();
+        assert_eq!(json!({}), json["~please_ack"]);
+
+        let ack = basic_message.create_ack();
+        assert!(ack.from_thread(&basic_message.id.0));
+    }
 }
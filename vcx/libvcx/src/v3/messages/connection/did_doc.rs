@@ -3,6 +3,7 @@ use v3::messages::connection::invite::Invitation;
 use error::prelude::*;
 use url::Url;
 use messages::validation::validate_verkey;
+use openssl::x509::X509;
 
 pub const CONTEXT: &str = "https://w3id.org/did/v1";
 pub const KEY_TYPE: &str = "Ed25519VerificationKey2018";
@@ -57,6 +58,47 @@ pub struct Service {
     pub routing_keys: Vec<String>,
     #[serde(rename = "serviceEndpoint")]
     pub service_endpoint: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConstraints>,
+}
+
+// Constraints on the TLS certificate presented by a service endpoint.
+// Pins are base64 encoded SHA-256 digests of a certificate SubjectPublicKeyInfo (as in HPKP),
+// at least one certificate of the presented chain must match one of the pins.
+// If CA certificate is set the endpoint certificate must be issued by it instead of any system trusted CA.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct TlsConstraints {
+    #[serde(default)]
+    #[serde(rename = "spkiSha256Pins")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub spki_sha256_pins: Vec<String>,
+    #[serde(default)]
+    #[serde(rename = "caCertificate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_certificate: Option<String>,
+}
+
+impl TlsConstraints {
+    pub fn validate(&self) -> VcxResult<()> {
+        if self.spki_sha256_pins.is_empty() && self.ca_certificate.is_none() {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "TLS constraints validation failed: neither pins nor CA certificate are set"));
+        }
+
+        for pin in self.spki_sha256_pins.iter() {
+            match ::base64::decode(pin) {
+                Ok(ref digest) if digest.len() == 32 => {}
+                _ => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("TLS constraints validation failed: Invalid SHA-256 pin: {:?}", pin)))
+            }
+        }
+
+        if let Some(ref ca_certificate) = self.ca_certificate {
+            X509::from_pem(ca_certificate.as_bytes())
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("TLS constraints validation failed: Invalid CA certificate, err: {:?}", err)))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for DidDoc {
@@ -164,6 +206,13 @@ impl DidDoc {
                 .iter()
                 .map(|key| self.validate_routing_key(key))
                 .collect::<VcxResult<()>>()?;
+
+            if let Some(ref tls) = service.tls {
+                if !service.service_endpoint.starts_with("https://") {
+                    return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("DIDDoc validation failed: TLS constraints are set for not https endpoint {:?}", service.service_endpoint)));
+                }
+                tls.validate()?;
+            }
         }
 
         Ok(())
@@ -253,6 +302,10 @@ impl DidDoc {
         }
     }
 
+    pub fn get_tls_constraints(&self) -> Option<TlsConstraints> {
+        self.service.get(0).and_then(|service| service.tls.clone())
+    }
+
     fn key_for_reference(&self, key_reference: &str) -> String {
         let id = DidDoc::_parse_key_reference(key_reference);
 
@@ -285,6 +338,7 @@ impl Default for Service {
             service_endpoint: String::new(),
             recipient_keys: Vec::new(),
             routing_keys: Vec::new(),
+            tls: None,
         }
     }
 }
@@ -303,10 +357,8 @@ impl From<DidDoc> for Invitation {
     fn from(did_doc: DidDoc) -> Invitation {
         let (recipient_keys, routing_keys) = did_doc.resolve_keys();
 
-        Invitation::create()
+        Invitation::build(String::new(), recipient_keys, did_doc.get_endpoint())
             .set_id(did_doc.id.clone())
-            .set_service_endpoint(did_doc.get_endpoint())
-            .set_recipient_keys(recipient_keys)
             .set_routing_keys(routing_keys)
     }
 }
@@ -515,4 +567,38 @@ pub mod tests {
 
         assert_eq!(did_doc, DidDoc::from(_invitation()))
     }
+
+    fn _did_doc_with_tls(service_endpoint: &str, pin: &str) -> DidDoc {
+        let mut did_doc = _did_doc();
+        did_doc.service[0].service_endpoint = service_endpoint.to_string();
+        did_doc.service[0].tls = Some(TlsConstraints { spki_sha256_pins: vec![pin.to_string()], ca_certificate: None });
+        did_doc
+    }
+
+    #[test]
+    fn test_did_doc_validate_works_for_tls_constraints() {
+        let did_doc = _did_doc_with_tls("https://localhost:8080", "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+        did_doc.validate().unwrap();
+        assert_eq!(did_doc.service[0].tls, did_doc.get_tls_constraints());
+    }
+
+    #[test]
+    fn test_did_doc_validate_fails_for_invalid_tls_constraints() {
+        let did_doc = _did_doc_with_tls("http://localhost:8080", "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+        assert_eq!(VcxErrorKind::InvalidJson, did_doc.validate().unwrap_err().kind());
+
+        let did_doc = _did_doc_with_tls("https://localhost:8080", "invalid");
+        assert_eq!(VcxErrorKind::InvalidJson, did_doc.validate().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_did_doc_serialization_skips_empty_tls_constraints() {
+        let did_doc_json = ::serde_json::to_value(&_did_doc()).unwrap();
+        assert!(did_doc_json["service"][0].get("tls").is_none());
+
+        let did_doc = _did_doc_with_tls("https://localhost:8080", "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+        let did_doc_json = ::serde_json::to_value(&did_doc).unwrap();
+        assert_eq!(json!({"spkiSha256Pins": ["47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="]}), did_doc_json["service"][0]["tls"]);
+        assert_eq!(did_doc, ::serde_json::from_value(did_doc_json).unwrap());
+    }
 }
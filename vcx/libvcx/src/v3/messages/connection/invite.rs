@@ -12,14 +12,26 @@ pub struct Invitation {
     pub routing_keys: Vec<String>,
     #[serde(rename = "serviceEndpoint")]
     pub service_endpoint: String,
+    // Public DID of the inviter. Lets the invitee recognize the inviter it is already connected with.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did: Option<String>,
 }
 
 impl Invitation {
-    pub fn create() -> Invitation {
+    fn create() -> Invitation {
         Invitation::default()
     }
 
-    pub fn set_label(mut self, label: String) -> Invitation {
+    // Invitation with all required parts, routing keys are optional.
+    pub fn build(label: String, recipient_keys: Vec<String>, service_endpoint: String) -> Invitation {
+        Invitation::create()
+            .set_label(label)
+            .set_recipient_keys(recipient_keys)
+            .set_service_endpoint(service_endpoint)
+    }
+
+    fn set_label(mut self, label: String) -> Invitation {
         self.label = label;
         self
     }
@@ -29,12 +41,12 @@ impl Invitation {
         self
     }
 
-    pub fn set_service_endpoint(mut self, service_endpoint: String) -> Invitation {
+    fn set_service_endpoint(mut self, service_endpoint: String) -> Invitation {
         self.service_endpoint = service_endpoint;
         self
     }
 
-    pub fn set_recipient_keys(mut self, recipient_keys: Vec<String>) -> Invitation {
+    fn set_recipient_keys(mut self, recipient_keys: Vec<String>) -> Invitation {
         self.recipient_keys = recipient_keys;
         self
     }
@@ -43,6 +55,11 @@ impl Invitation {
         self.routing_keys = routing_keys;
         self
     }
+
+    pub fn set_public_did(mut self, did: Option<String>) -> Invitation {
+        self.did = did;
+        self
+    }
 }
 
 a2a_message!(Invitation, ConnectionInvitation);
@@ -59,9 +76,14 @@ pub mod tests {
             recipient_keys: _recipient_keys(),
             routing_keys: _routing_keys(),
             service_endpoint: _service_endpoint(),
+            did: None,
         }
     }
 
+    pub fn _public_did() -> String {
+        String::from("did:sov:LjgpST2rjsoxYegQDRm7EL")
+    }
+
     pub fn _invitation_json() -> String {
         ::serde_json::to_string(&_invitation().to_a2a_message()).unwrap()
     }
@@ -76,4 +98,23 @@ pub mod tests {
 
         assert_eq!(_invitation(), invitation);
     }
+
+    #[test]
+    fn test_invitation_typed_build_works() {
+        let invitation = Invitation::build(_label(), _recipient_keys(), _service_endpoint())
+            .set_routing_keys(_routing_keys());
+
+        assert_eq!(_invitation(), invitation);
+    }
+
+    #[test]
+    fn test_invitation_with_public_did_works() {
+        let invitation = _invitation().set_public_did(Some(_public_did()));
+
+        let json = ::serde_json::to_value(&invitation.to_a2a_message()).unwrap();
+        assert_eq!(json!(_public_did()), json["did"]);
+
+        let json = ::serde_json::to_value(&_invitation().to_a2a_message()).unwrap();
+        assert!(json.get("did").is_none());
+    }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use error::prelude::*;
 use utils::libindy::crypto;
+use utils::json;
 use base64;
 use time;
 
@@ -74,7 +75,7 @@ impl Response {
     }
 
     pub fn encode(&self, key: &str) -> VcxResult<SignedResponse> {
-        let connection_data = json!(self.connection).to_string();
+        let connection_data = json::to_signed_string(&self.connection)?;
 
         let now: u64 = time::get_time().sec as u64;
 
@@ -108,6 +109,7 @@ impl Response {
 
 please_ack!(Response);
 threadlike!(Response);
+threaded!(SignedResponse);
 
 impl SignedResponse {
     pub fn decode(self, key: &str) -> VcxResult<Response> {
@@ -125,6 +127,11 @@ impl SignedResponse {
 
         let sig_data = &sig_data[8..];
 
+        let connection_data = ::std::str::from_utf8(sig_data)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot decode ConnectionResponse: {:?}", err)))?;
+
+        json::check_signed_json(connection_data)?;
+
         let connection: ConnectionData = ::serde_json::from_slice(&sig_data)
             .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, err.to_string()))?;
 
@@ -215,4 +222,15 @@ pub mod tests {
         let signed_response: SignedResponse = _response().encode(&setup.key).unwrap();
         assert_eq!(_response(), signed_response.decode(&setup.key).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_response_encode_signs_canonical_json() {
+        let setup = test_setup::key();
+        let signed_response: SignedResponse = _response().encode(&setup.key).unwrap();
+
+        let sig_data = base64::decode_config(&signed_response.connection_sig.sig_data.as_bytes(), base64::URL_SAFE).unwrap();
+        let connection_data = ::std::str::from_utf8(&sig_data[8..]).unwrap();
+
+        assert_eq!(json::to_canonical_string(&_response().connection).unwrap(), connection_data);
+    }
+}
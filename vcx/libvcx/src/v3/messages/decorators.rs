@@ -0,0 +1,195 @@
+use serde_json::Value;
+
+use error::prelude::*;
+use v3::messages::a2a::A2AMessage;
+
+pub use messages::thread::Thread;
+pub use v3::messages::timing::Timing;
+pub use v3::messages::ack::PleaseAck;
+
+// Transport decorator (Aries RFC 0092). Asks the recipient to use the inbound connection for responses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Transport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_route: Option<ReturnRoute>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_route_thread: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ReturnRoute {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "all")]
+    All,
+    #[serde(rename = "thread")]
+    Thread,
+}
+
+impl Transport {
+    pub fn return_route(return_route: ReturnRoute) -> Transport {
+        Transport { return_route: Some(return_route), return_route_thread: None }
+    }
+
+    pub fn return_route_for_thread(thread_id: &str) -> Transport {
+        Transport { return_route: Some(ReturnRoute::Thread), return_route_thread: Some(thread_id.to_string()) }
+    }
+}
+
+// Thread access implemented by every threaded protocol message (see `threaded!` and `optionally_threaded!`)
+pub trait Threaded {
+    fn thread(&self) -> Option<&Thread>;
+
+    fn thread_id(&self) -> Option<&str> {
+        self.thread().and_then(|thread| thread.thid.as_ref()).map(String::as_str)
+    }
+
+    fn parent_thread_id(&self) -> Option<&str> {
+        self.thread().and_then(|thread| thread.pthid.as_ref()).map(String::as_str)
+    }
+}
+
+#[macro_export]
+macro_rules! threaded (($type:ident) => (
+    impl ::v3::messages::decorators::Threaded for $type {
+        fn thread(&self) -> Option<&::messages::thread::Thread> {
+            Some(&self.thread)
+        }
+    }
+));
+
+#[macro_export]
+macro_rules! optionally_threaded (($type:ident) => (
+    impl ::v3::messages::decorators::Threaded for $type {
+        fn thread(&self) -> Option<&::messages::thread::Thread> {
+            self.thread.as_ref()
+        }
+    }
+));
+
+// Decorators of an arbitrary message, including messages which are not typed in VCX
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Decorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
+    #[serde(rename = "~transport")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
+}
+
+impl Decorators {
+    pub fn from_message(message: &A2AMessage) -> VcxResult<Decorators> {
+        ::serde_json::to_value(message)
+            .and_then(::serde_json::from_value)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot parse message decorators: {:?}", err)))
+    }
+
+    // Decorators set here override the ones the message already has.
+    // Result is kept as generic message as typed messages may not define all decorators.
+    pub fn attach(&self, message: &A2AMessage) -> VcxResult<A2AMessage> {
+        let mut message = ::serde_json::to_value(message)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize message: {:?}", err)))?;
+
+        let decorators = ::serde_json::to_value(self)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize message decorators: {:?}", err)))?;
+
+        match (message.as_object_mut(), decorators) {
+            (Some(message), Value::Object(decorators)) => message.extend(decorators),
+            _ => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Message must be a JSON object"))
+        }
+
+        Ok(A2AMessage::Generic(message))
+    }
+
+    pub fn set_thread(mut self, thread: Thread) -> Decorators {
+        self.thread = Some(thread);
+        self
+    }
+
+    pub fn set_timing(mut self, timing: Timing) -> Decorators {
+        self.timing = Some(timing);
+        self
+    }
+
+    pub fn ask_for_ack(mut self) -> Decorators {
+        self.please_ack = Some(PleaseAck {});
+        self
+    }
+
+    pub fn set_transport(mut self, transport: Transport) -> Decorators {
+        self.transport = Some(transport);
+        self
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::ack::tests::_ack;
+    use v3::messages::basic_message::message::BasicMessage;
+    use v3::messages::trust_ping::ping::Ping;
+
+    #[test]
+    fn test_transport_serialization_works() {
+        assert_eq!(json!({"return_route": "all"}), json!(Transport::return_route(ReturnRoute::All)));
+        assert_eq!(json!({"return_route": "thread", "return_route_thread": "thid"}), json!(Transport::return_route_for_thread("thid")));
+    }
+
+    #[test]
+    fn test_decorators_from_message_works() {
+        let decorators = Decorators::from_message(&_ack().to_a2a_message()).unwrap();
+
+        assert_eq!(Some(_ack().thread), decorators.thread);
+        assert!(decorators.timing.is_none());
+        assert!(decorators.please_ack.is_none());
+        assert!(decorators.transport.is_none());
+    }
+
+    #[test]
+    fn test_decorators_attach_works_for_typed_message() {
+        let message = BasicMessage::create().set_content(String::from("content")).to_a2a_message();
+
+        let decorated = Decorators::default()
+            .ask_for_ack()
+            .set_transport(Transport::return_route(ReturnRoute::All))
+            .attach(&message)
+            .unwrap();
+
+        let json = ::serde_json::to_value(&decorated).unwrap();
+        assert_eq!(json!("content"), json["content"]);
+
+        let decorators = Decorators::from_message(&decorated).unwrap();
+        assert_eq!(Some(PleaseAck {}), decorators.please_ack);
+        assert_eq!(Some(Transport::return_route(ReturnRoute::All)), decorators.transport);
+
+        match ::serde_json::from_value::<A2AMessage>(json).unwrap() {
+            A2AMessage::BasicMessage(basic_message) => assert!(basic_message.please_ack.is_some()),
+            other => panic!("Unexpected message: {:?}", other)
+        }
+    }
+
+    #[test]
+    fn test_decorators_attach_works_for_generic_message() {
+        let message = A2AMessage::Generic(json!({"@type": "did:sov:123;spec/custom/1.0/message", "@id": "id"}));
+
+        let thread = Thread::new().set_thid(String::from("thid")).set_pthid(String::from("pthid"));
+        let decorated = Decorators::default().set_thread(thread.clone()).attach(&message).unwrap();
+
+        assert_eq!(Some(thread), Decorators::from_message(&decorated).unwrap().thread);
+    }
+
+    #[test]
+    fn test_threaded_works() {
+        assert_eq!(Some("test_id"), _ack().thread_id());
+
+        assert_eq!(None, Ping::create().thread_id());
+        assert_eq!(Some("thid"), Ping::create().set_thread_id(String::from("thid")).thread_id());
+    }
+}
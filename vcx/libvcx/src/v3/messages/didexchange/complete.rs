@@ -0,0 +1,51 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{A2AMessage, MessageId};
+
+// Confirms the response. Threaded to the request and to the invitation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Complete {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+}
+
+impl Complete {
+    pub fn create() -> Complete {
+        Complete::default()
+    }
+}
+
+threadlike!(Complete);
+a2a_message!(Complete, DidExchangeComplete);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::didexchange::request::tests::_invitation_id;
+
+    pub fn _complete() -> Complete {
+        Complete {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0).set_pthid(_invitation_id()),
+        }
+    }
+
+    #[test]
+    fn test_complete_build_works() {
+        let complete = Complete::create()
+            .set_thread_id(&MessageId::id().0)
+            .set_parent_thread_id(&_invitation_id());
+
+        assert_eq!(_complete(), complete);
+    }
+
+    #[test]
+    fn test_complete_serialization_works() {
+        let json = ::serde_json::to_value(&_complete().to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/didexchange/1.0/complete"), json["@type"]);
+
+        let message: A2AMessage = ::serde_json::from_value(json).unwrap();
+        assert_eq!(_complete().to_a2a_message(), message);
+    }
+}
@@ -0,0 +1,72 @@
+pub mod request;
+pub mod response;
+pub mod complete;
+
+use v3::messages::attachment::{Attachments, Attachment, AttachmentId, AttachmentEncoding, Json};
+use v3::messages::connection::did_doc::DidDoc;
+use v3::utils::did_method;
+use error::prelude::*;
+
+const DID_DOC_ATTACHMENT_ID: &str = "did_doc";
+const DID_ROTATE_ATTACHMENT_ID: &str = "did_rotate";
+
+// DIDDoc is attached as base64 encoded JSON. Signed attachment proves that DIDDoc is sent by the owner of the key.
+fn attach_did_doc(did_doc: &DidDoc, verkey: Option<&str>) -> VcxResult<Attachments> {
+    let id = AttachmentId::Other(DID_DOC_ATTACHMENT_ID.to_string());
+
+    let json = match verkey {
+        Some(verkey) => Json::new_signed(id, json!(did_doc), verkey)?,
+        None => Json::new(id, json!(did_doc), AttachmentEncoding::Base64)?,
+    };
+
+    let mut attachments = Attachments::new();
+    attachments.add(Attachment::JSON(json));
+    Ok(attachments)
+}
+
+// DID signed by the key of the invitation (Aries RFC 0023 `did_rotate~attach`).
+// It binds a DIDDoc resolved from `did:peer` or `did:key` DID to the invitation.
+fn attach_did_rotate(did: &str, verkey: &str) -> VcxResult<Attachments> {
+    let json = Json::new_signed(AttachmentId::Other(DID_ROTATE_ATTACHMENT_ID.to_string()), json!(did), verkey)?;
+
+    let mut attachments = Attachments::new();
+    attachments.add(Attachment::JSON(json));
+    Ok(attachments)
+}
+
+fn verify_did_rotate(did_rotate: Option<&Attachments>, did: &str, verkey: &str) -> VcxResult<()> {
+    let json = match did_rotate.and_then(Attachments::get) {
+        Some(Attachment::JSON(ref json)) => json,
+        _ => return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Either DIDDoc or DID must be attached signed by the key of the invitation"))
+    };
+
+    json.verify_signature(verkey)?;
+
+    if json.get_data()? != did {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Signed DID doesn't match DID {:?}", did)));
+    }
+
+    Ok(())
+}
+
+// Signature is checked if the key is given: either DIDDoc attachment or DID in `did_rotate` attachment must be signed.
+// DIDDoc of `did:peer` and `did:key` DIDs may be omitted, it is resolved from the DID then.
+fn did_doc_from_attachments(attachments: &Attachments, did: &str, verkey: Option<&str>, did_rotate: Option<&Attachments>) -> VcxResult<DidDoc> {
+    let json = match attachments.get() {
+        Some(Attachment::JSON(ref json)) => json,
+        _ if did_method::is_resolvable(did) => {
+            if let Some(verkey) = verkey {
+                verify_did_rotate(did_rotate, did, verkey)?;
+            }
+            return did_method::resolve(did);
+        }
+        _ => return Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "DIDDoc must be attached: resolving of unqualified DIDs is not supported"))
+    };
+
+    if let Some(verkey) = verkey {
+        json.verify_signature(verkey)?;
+    }
+
+    ::serde_json::from_str(&json.get_data()?)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize attached DIDDoc: {:?}", err)))
+}
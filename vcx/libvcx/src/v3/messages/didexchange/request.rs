@@ -0,0 +1,151 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{A2AMessage, MessageId};
+use v3::messages::attachment::Attachments;
+use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::connection::request::{Request as ConnectionRequest, ConnectionData};
+use v3::messages::didexchange::{attach_did_doc, did_doc_from_attachments};
+use error::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Request {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    pub did: String,
+    #[serde(default)]
+    #[serde(rename = "did_doc~attach")]
+    pub did_doc_attach: Attachments,
+}
+
+impl Request {
+    pub fn create() -> Request {
+        let request = Request::default();
+        let thid = request.id.0.clone();
+        request.set_thread_id(&thid)
+    }
+
+    pub fn set_label(mut self, label: String) -> Request {
+        self.label = label;
+        self
+    }
+
+    pub fn set_did(mut self, did: String) -> Request {
+        self.did = did;
+        self
+    }
+
+    pub fn set_did_doc(mut self, did_doc: &DidDoc) -> VcxResult<Request> {
+        self.did_doc_attach = attach_did_doc(did_doc, None)?;
+        Ok(self)
+    }
+
+    // Id of the invitation the request answers
+    pub fn set_invitation_id(self, invitation_id: &str) -> Request {
+        self.set_parent_thread_id(invitation_id)
+    }
+
+    pub fn did_doc(&self) -> VcxResult<DidDoc> {
+        did_doc_from_attachments(&self.did_doc_attach, &self.did, None, None)
+    }
+
+    // Both protocols are handled the same way after the DIDDoc is extracted.
+    // Thread id is used as request id, so the response is threaded correctly when they differ.
+    pub fn to_connection_request(&self) -> VcxResult<ConnectionRequest> {
+        Ok(ConnectionRequest {
+            id: MessageId(self.thread.thid.clone().unwrap_or(self.id.0.clone())),
+            label: self.label.clone(),
+            connection: ConnectionData {
+                did: self.did.clone(),
+                did_doc: self.did_doc()?,
+            },
+        })
+    }
+}
+
+threadlike!(Request);
+a2a_message!(Request, DidExchangeRequest);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::connection::did_doc::tests::*;
+
+    fn _did() -> String {
+        String::from("VsKV7grR1BUE29mG2Fm2kX")
+    }
+
+    pub fn _invitation_id() -> String {
+        String::from("invitation_id")
+    }
+
+    pub fn _didexchange_request() -> Request {
+        Request {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0).set_pthid(_invitation_id()),
+            label: _label(),
+            goal_code: None,
+            goal: None,
+            did: _did(),
+            did_doc_attach: attach_did_doc(&_did_doc(), None).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_didexchange_request_build_works() {
+        let request = Request::create()
+            .set_label(_label())
+            .set_did(_did())
+            .set_invitation_id(&_invitation_id())
+            .set_did_doc(&_did_doc()).unwrap();
+
+        assert_eq!(_didexchange_request(), request);
+        assert_eq!(_did_doc(), request.did_doc().unwrap());
+    }
+
+    #[test]
+    fn test_didexchange_request_serialization_works() {
+        let json = ::serde_json::to_value(&_didexchange_request().to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/didexchange/1.0/request"), json["@type"]);
+        assert_eq!(json!("application/json"), json["did_doc~attach"][0]["mime-type"]);
+
+        let message: A2AMessage = ::serde_json::from_value(json).unwrap();
+        assert_eq!(_didexchange_request().to_a2a_message(), message);
+    }
+
+    #[test]
+    fn test_didexchange_request_to_connection_request_works() {
+        let request = _didexchange_request().to_connection_request().unwrap();
+
+        assert_eq!(MessageId::id(), request.id);
+        assert_eq!(_label(), request.label);
+        assert_eq!(_did(), request.connection.did);
+        assert_eq!(_did_doc(), request.connection.did_doc);
+    }
+
+    #[test]
+    fn test_didexchange_request_fails_for_missing_did_doc() {
+        let mut request = _didexchange_request();
+        request.did_doc_attach = Attachments::new();
+
+        assert_eq!(VcxErrorKind::ActionNotSupported, request.to_connection_request().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_didexchange_request_resolves_did_doc_of_peer_did() {
+        let did = ::v3::utils::did_method::peer_did_numalgo_2(&_recipient_keys()[0], &_service_endpoint(), &_routing_keys()).unwrap();
+
+        let mut request = _didexchange_request().set_did(did.clone());
+        request.did_doc_attach = Attachments::new();
+
+        let request = request.to_connection_request().unwrap();
+        assert_eq!(did, request.connection.did_doc.id);
+        assert_eq!(_recipient_keys(), request.connection.did_doc.recipient_keys());
+        assert_eq!(_routing_keys(), request.connection.did_doc.routing_keys());
+    }
+}
@@ -0,0 +1,159 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{A2AMessage, MessageId};
+use v3::messages::attachment::Attachments;
+use v3::messages::connection::did_doc::DidDoc;
+use v3::messages::connection::response::{Response as ConnectionResponse, ConnectionData};
+use v3::messages::didexchange::{attach_did_doc, attach_did_rotate, did_doc_from_attachments};
+use v3::utils::did_method;
+use error::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Response {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    pub did: String,
+    #[serde(default)]
+    #[serde(rename = "did_doc~attach")]
+    pub did_doc_attach: Attachments,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "did_rotate~attach")]
+    pub did_rotate_attach: Option<Attachments>,
+}
+
+impl Response {
+    pub fn create() -> Response {
+        Response::default()
+    }
+
+    pub fn set_did(mut self, did: String) -> Response {
+        self.did = did;
+        self
+    }
+
+    // DIDDoc of the response is signed by the key of the invitation
+    pub fn set_did_doc(mut self, did_doc: &DidDoc, invitation_verkey: &str) -> VcxResult<Response> {
+        self.did_doc_attach = attach_did_doc(did_doc, Some(invitation_verkey))?;
+        Ok(self)
+    }
+
+    // DID of the response is signed by the key of the invitation, so DIDDoc may be omitted for resolvable DIDs
+    pub fn set_did_rotate(mut self, invitation_verkey: &str) -> VcxResult<Response> {
+        self.did_rotate_attach = Some(attach_did_rotate(&self.did, invitation_verkey)?);
+        Ok(self)
+    }
+
+    pub fn did_doc(&self, invitation_verkey: &str) -> VcxResult<DidDoc> {
+        did_doc_from_attachments(&self.did_doc_attach, &self.did, Some(invitation_verkey), self.did_rotate_attach.as_ref())
+    }
+
+    pub fn from_connection_response(response: &ConnectionResponse, invitation_verkey: &str) -> VcxResult<Response> {
+        let response = Response {
+            id: response.id.clone(),
+            thread: response.thread.clone(),
+            ..Response::default()
+        }
+            .set_did(response.connection.did.clone())
+            .set_did_doc(&response.connection.did_doc, invitation_verkey)?;
+
+        if did_method::is_resolvable(&response.did) {
+            return response.set_did_rotate(invitation_verkey);
+        }
+
+        Ok(response)
+    }
+
+    pub fn to_connection_response(&self, invitation_verkey: &str) -> VcxResult<ConnectionResponse> {
+        Ok(ConnectionResponse {
+            id: self.id.clone(),
+            thread: self.thread.clone(),
+            connection: ConnectionData {
+                did: self.did.clone(),
+                did_doc: self.did_doc(invitation_verkey)?,
+            },
+            please_ack: None,
+        })
+    }
+}
+
+threadlike!(Response);
+a2a_message!(Response, DidExchangeResponse);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::connection::did_doc::tests::*;
+    use v3::messages::connection::response::tests::{_response, _thread};
+    use utils::libindy::tests::test_setup;
+    use utils::libindy::crypto::create_key;
+
+    fn _did() -> String {
+        String::from("VsKV7grR1BUE29mG2Fm2kX")
+    }
+
+    #[test]
+    fn test_didexchange_response_build_works() {
+        let setup = test_setup::key();
+
+        let response = Response::create()
+            .set_did(_did())
+            .set_thread_id(&_thread().thid.unwrap())
+            .set_did_doc(&_did_doc(), &setup.key).unwrap();
+
+        assert_eq!(_did(), response.did);
+        assert_eq!(_thread(), response.thread);
+        assert_eq!(_did_doc(), response.did_doc(&setup.key).unwrap());
+    }
+
+    #[test]
+    fn test_didexchange_response_serialization_works() {
+        let setup = test_setup::key();
+
+        let response = Response::from_connection_response(&_response(), &setup.key).unwrap();
+
+        let json = ::serde_json::to_value(&response.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/didexchange/1.0/response"), json["@type"]);
+        assert!(json["did_doc~attach"][0]["data"]["jws"].is_object());
+
+        let message: A2AMessage = ::serde_json::from_value(json).unwrap();
+        assert_eq!(response.to_a2a_message(), message);
+    }
+
+    #[test]
+    fn test_didexchange_response_to_connection_response_works() {
+        let setup = test_setup::key();
+
+        let response = Response::from_connection_response(&_response(), &setup.key).unwrap();
+        assert_eq!(_response(), response.to_connection_response(&setup.key).unwrap());
+    }
+
+    #[test]
+    fn test_didexchange_response_fails_for_other_signer() {
+        let setup = test_setup::key();
+        let other_key = create_key(None).unwrap();
+
+        let response = Response::from_connection_response(&_response(), &setup.key).unwrap();
+        assert!(response.to_connection_response(&other_key).is_err());
+    }
+
+    #[test]
+    fn test_didexchange_response_fails_without_signature_for_resolvable_did() {
+        let setup = test_setup::key();
+        let did = did_method::did_key(&create_key(None).unwrap()).unwrap();
+
+        let response = Response::create()
+            .set_did(did)
+            .set_thread_id(&_thread().thid.unwrap());
+        assert!(response.did_doc(&setup.key).is_err());
+
+        let response = response.set_did_rotate(&setup.key).unwrap();
+        assert!(response.did_doc(&setup.key).is_ok());
+
+        let other_key = create_key(None).unwrap();
+        assert!(response.did_doc(&other_key).is_err());
+
+        let response = Response { did: did_method::did_key(&other_key).unwrap(), ..response };
+        assert!(response.did_doc(&setup.key).is_err());
+    }
+}
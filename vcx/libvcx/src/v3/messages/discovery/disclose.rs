@@ -42,6 +42,8 @@ impl Disclose {
     }
 }
 
+threaded!(Disclose);
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
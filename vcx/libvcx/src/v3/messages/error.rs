@@ -31,7 +31,9 @@ pub struct ProblemReport {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub problem_items: Option<HashMap<String, String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub comment: Option<String>
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<String>,
 }
 
 impl ProblemReport {
@@ -39,10 +41,10 @@ impl ProblemReport {
         ProblemReport::default()
     }
 
-    pub fn set_description(mut self, code: u32) -> Self {
+    pub fn set_description(mut self, code: &str) -> Self {
         self.description = Some(Description {
             en: None,
-            code
+            code: code.to_string()
         });
         self
     }
@@ -51,6 +53,28 @@ impl ProblemReport {
         self.comment = Some(comment);
         self
     }
+
+    pub fn set_explain(mut self, explain: String) -> Self {
+        self.explain = Some(explain);
+        self
+    }
+
+    pub fn set_impact(mut self, impact: Impact) -> Self {
+        self.impact = Some(impact);
+        self
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.description.as_ref().map(|description| description.code.as_str())
+    }
+
+    // Human readable explanation: agents fill either `explain`, `comment` or description in `en`
+    pub fn explanation(&self) -> Option<&str> {
+        self.explain.as_ref()
+            .or(self.comment.as_ref())
+            .or(self.description.as_ref().and_then(|description| description.en.as_ref()))
+            .map(String::as_str)
+    }
 }
 
 threadlike!(ProblemReport);
@@ -60,11 +84,30 @@ a2a_message!(ProblemReport, CommonProblemReport);
 pub struct Description {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub en: Option<String>,
-    pub code: u32,
+    #[serde(deserialize_with = "deserialize_code")]
+    pub code: String,
+}
+
+// Codes are strings by RFC 0035, but numeric codes were sent by previous versions of VCX
+fn deserialize_code<'de, D>(deserializer: D) -> Result<String, D::Error> where D: ::serde::Deserializer<'de> {
+    use serde::Deserialize;
+
+    match ::serde_json::Value::deserialize(deserializer)? {
+        ::serde_json::Value::String(code) => Ok(code),
+        ::serde_json::Value::Number(code) => Ok(code.to_string()),
+        code => Err(::serde::de::Error::custom(format!("Invalid problem code: {}", code)))
+    }
+}
+
+// Codes of problems detected by VCX state machines
+pub mod codes {
+    pub const MESSAGE_PROCESSING_ERROR: &str = "message_processing_error";
+    pub const REQUEST_PROCESSING_ERROR: &str = "request_processing_error";
+    pub const REQUEST_NOT_ACCEPTED: &str = "request_not_accepted";
+    pub const CREDENTIAL_REVOKED: &str = "credential_revoked";
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(untagged)]
 pub enum WhoRetries {
     #[serde(rename = "me")]
     Me,
@@ -89,7 +132,6 @@ pub struct FixHint {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(untagged)]
 pub enum Impact {
     #[serde(rename = "message")]
     Message,
@@ -104,7 +146,7 @@ pub mod tests {
     use super::*;
     use v3::messages::connection::response::tests::*;
 
-    fn _code() -> u32 { 0 }
+    fn _code() -> String { String::from(codes::REQUEST_PROCESSING_ERROR) }
 
     fn _comment() -> String {
         String::from("test comment")
@@ -124,6 +166,7 @@ pub mod tests {
             location: None,
             problem_items: None,
             comment: Some(_comment()),
+            explain: None,
         }
     }
 
@@ -132,8 +175,35 @@ pub mod tests {
         let report: ProblemReport = ProblemReport::default()
             .set_comment(_comment())
             .set_thread_id(&_thread_id())
-            .set_description(_code());
+            .set_description(&_code());
 
         assert_eq!(_problem_report(), report);
     }
+
+    #[test]
+    fn test_problem_report_deserialization_works() {
+        let report: ProblemReport = ::serde_json::from_value(json!({
+            "@id": "testid",
+            "~thread": {"thid": "thread"},
+            "description": {"en": "Credential is revoked", "code": "revoked"},
+            "impact": "thread",
+            "who_retries": "none"
+        })).unwrap();
+
+        assert_eq!(Some("revoked"), report.code());
+        assert_eq!(Some("Credential is revoked"), report.explanation());
+        assert_eq!(Some(Impact::Thread), report.impact);
+        assert_eq!(json!("thread"), ::serde_json::to_value(&report).unwrap()["impact"]);
+    }
+
+    #[test]
+    fn test_problem_report_deserialization_works_for_numeric_code() {
+        let report: ProblemReport = ::serde_json::from_value(json!({
+            "@id": "testid",
+            "~thread": {"thid": "thread"},
+            "description": {"code": 0}
+        })).unwrap();
+
+        assert_eq!(Some("0"), report.code());
+    }
 }
\ No newline at end of file
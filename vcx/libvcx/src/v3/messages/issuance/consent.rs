@@ -0,0 +1,194 @@
+use error::{VcxResult, VcxError, VcxErrorKind};
+use utils::json;
+use utils::libindy::crypto;
+
+// Terms of data processing the Issuer asks the Holder to consent to. Is sent within Credential Offer as `~consent` decorator.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ConsentTemplate {
+    pub purpose: String,
+    pub data_categories: Vec<String>,
+    // ISO 8601 duration (e.g. `P1Y`) the data will be kept for
+    pub retention: String,
+}
+
+impl ConsentTemplate {
+    pub fn create(purpose: &str, data_categories: Vec<String>, retention: &str) -> VcxResult<ConsentTemplate> {
+        ConsentTemplate {
+            purpose: purpose.to_string(),
+            data_categories,
+            retention: retention.to_string(),
+        }.validate()
+    }
+
+    pub fn from_json(template: &str) -> VcxResult<ConsentTemplate> {
+        let template: ConsentTemplate = ::serde_json::from_str(template)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Consent Template: {:?}", err)))?;
+        template.validate()
+    }
+
+    fn validate(self) -> VcxResult<ConsentTemplate> {
+        if self.purpose.is_empty() || self.retention.is_empty() || self.data_categories.is_empty() {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidOption, "Consent Template must contain purpose, data categories and retention"));
+        }
+        Ok(self)
+    }
+}
+
+// Consent receipt signed by the Holder. Is sent within Credential Request as `~consent_receipt` decorator.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ConsentReceipt {
+    #[serde(flatten)]
+    pub data: ConsentReceiptData,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ConsentReceiptData {
+    pub receipt_id: String,
+    #[serde(flatten)]
+    pub template: ConsentTemplate,
+    pub thread_id: String,
+    pub holder_verkey: String,
+    pub consent_timestamp: i64,
+}
+
+impl ConsentReceipt {
+    pub fn sign(template: &ConsentTemplate, thread_id: &str, holder_verkey: &str) -> VcxResult<ConsentReceipt> {
+        trace!("ConsentReceipt::sign >>> template: {:?}, thread_id: {:?}", template, thread_id);
+
+        let data = ConsentReceiptData {
+            receipt_id: ::utils::uuid::uuid(),
+            template: template.clone(),
+            thread_id: thread_id.to_string(),
+            holder_verkey: holder_verkey.to_string(),
+            consent_timestamp: ::time::get_time().sec,
+        };
+
+        let signature = crypto::sign(holder_verkey, data.signed_bytes()?.as_slice())?;
+
+        Ok(ConsentReceipt { data, signature: ::base64::encode(&signature) })
+    }
+
+    // Checks that receipt is signed by the expected Holder for the terms and thread offered by Issuer
+    pub fn verify(&self, template: &ConsentTemplate, thread_id: &str, holder_verkey: &str) -> VcxResult<()> {
+        trace!("ConsentReceipt::verify >>> template: {:?}, thread_id: {:?}", template, thread_id);
+
+        if &self.data.template != template {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, "Consent Receipt doesn't match the offered Consent Template"));
+        }
+
+        if self.data.thread_id != thread_id {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, "Consent Receipt is issued for another thread"));
+        }
+
+        if self.data.holder_verkey != holder_verkey {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, "Consent Receipt is signed by another key than the one of connection"));
+        }
+
+        let signature = ::base64::decode(&self.signature)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, format!("Cannot decode Consent Receipt signature: {:?}", err)))?;
+
+        if !crypto::verify(holder_verkey, self.data.signed_bytes()?.as_slice(), &signature)? {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidCredentialRequest, "Consent Receipt signature is invalid"));
+        }
+
+        Ok(())
+    }
+}
+
+impl ConsentReceiptData {
+    // Signature is checked against data parsed from the message, so both sides must serialize it the same way
+    fn signed_bytes(&self) -> VcxResult<Vec<u8>> {
+        json::to_canonical_string(self).map(String::into_bytes)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use utils::devsetup::SetupAriesMocks;
+    use v3::messages::issuance::credential_offer::tests::thread_id;
+
+    pub fn _consent_template() -> ConsentTemplate {
+        ConsentTemplate {
+            purpose: String::from("Issuance of the employee badge"),
+            data_categories: vec![String::from("name"), String::from("date of birth")],
+            retention: String::from("P1Y"),
+        }
+    }
+
+    pub fn _holder_verkey() -> String {
+        String::from("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL")
+    }
+
+    pub fn _consent_receipt_data() -> ConsentReceiptData {
+        ConsentReceiptData {
+            receipt_id: String::from("receipt_id"),
+            template: _consent_template(),
+            thread_id: thread_id(),
+            holder_verkey: _holder_verkey(),
+            consent_timestamp: 1600000000,
+        }
+    }
+
+    pub fn _consent_receipt() -> ConsentReceipt {
+        ConsentReceipt::sign(&_consent_template(), &thread_id(), &_holder_verkey()).unwrap()
+    }
+
+    #[test]
+    fn test_consent_template_from_json_works() {
+        let template = ConsentTemplate::from_json(&json!(_consent_template()).to_string()).unwrap();
+        assert_eq!(_consent_template(), template);
+
+        let err = ConsentTemplate::from_json(r#"{"purpose":"","data_categories":[],"retention":"P1Y"}"#).unwrap_err();
+        assert_eq!(VcxErrorKind::InvalidOption, err.kind());
+
+        let err = ConsentTemplate::from_json("{}").unwrap_err();
+        assert_eq!(VcxErrorKind::InvalidJson, err.kind());
+    }
+
+    #[test]
+    fn test_consent_receipt_serialization_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let receipt = _consent_receipt();
+
+        let json = ::serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json!(_consent_template().purpose), json["purpose"]);
+        assert_eq!(json!(_holder_verkey()), json["holder_verkey"]);
+        assert!(json["signature"].is_string());
+
+        let deserialized: ConsentReceipt = ::serde_json::from_value(json).unwrap();
+        assert_eq!(receipt, deserialized);
+    }
+
+    #[test]
+    fn test_consent_receipt_signed_bytes_are_canonical() {
+        let expected = format!(r#"{{"consent_timestamp":1600000000,"data_categories":["name","date of birth"],"holder_verkey":"{}","purpose":"Issuance of the employee badge","receipt_id":"receipt_id","retention":"P1Y","thread_id":"{}"}}"#,
+                               _holder_verkey(), thread_id());
+
+        assert_eq!(expected.into_bytes(), _consent_receipt_data().signed_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_consent_receipt_verify_works() {
+        let _setup = SetupAriesMocks::init();
+
+        let receipt = _consent_receipt();
+        receipt.verify(&_consent_template(), &thread_id(), &_holder_verkey()).unwrap();
+    }
+
+    #[test]
+    fn test_consent_receipt_verify_fails_for_other_terms() {
+        let _setup = SetupAriesMocks::init();
+
+        let receipt = _consent_receipt();
+
+        let mut template = _consent_template();
+        template.retention = String::from("P10Y");
+        assert_eq!(VcxErrorKind::InvalidCredentialRequest, receipt.verify(&template, &thread_id(), &_holder_verkey()).unwrap_err().kind());
+
+        assert_eq!(VcxErrorKind::InvalidCredentialRequest, receipt.verify(&_consent_template(), "other", &_holder_verkey()).unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidCredentialRequest, receipt.verify(&_consent_template(), &thread_id(), "other").unwrap_err().kind());
+    }
+}
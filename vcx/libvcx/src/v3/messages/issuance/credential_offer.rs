@@ -1,7 +1,9 @@
 use v3::messages::a2a::{MessageId, A2AMessage};
 use v3::messages::issuance::CredentialPreviewData;
+use v3::messages::issuance::consent::ConsentTemplate;
 use v3::messages::attachment::{Attachments, AttachmentId};
 use v3::messages::mime_type::MimeType;
+use v3::messages::timing::Timing;
 use error::{VcxError, VcxResult, VcxErrorKind};
 use messages::thread::Thread;
 use issuer_credential::CredentialOffer as CredentialOfferV1;
@@ -19,14 +21,33 @@ pub struct CredentialOffer {
     pub offers_attach: Attachments,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "~thread")]
-    pub thread: Option<Thread>
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(rename = "~consent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent: Option<ConsentTemplate>,
 }
 
 impl CredentialOffer {
-    pub fn create() -> Self {
+    fn create() -> Self {
         CredentialOffer::default()
     }
 
+    // Offer with all required parts. The offer starts a new thread, so thread id is passed only for counter-offers.
+    pub fn build(libindy_offer: &str, credential_preview: CredentialPreviewData, thread_id: Option<&str>) -> VcxResult<CredentialOffer> {
+        let offer = CredentialOffer::create()
+            .set_offers_attach(libindy_offer)?
+            .set_credential_preview_data(credential_preview)?
+            .set_out_time();
+
+        Ok(match thread_id {
+            Some(thread_id) => offer.set_thread_id(thread_id),
+            None => offer
+        })
+    }
+
     pub fn set_id(mut self, id: String) -> Self {
         self.id = MessageId(id);
         self
@@ -37,12 +58,12 @@ impl CredentialOffer {
         self
     }
 
-    pub fn set_offers_attach(mut self, credential_offer: &str) -> VcxResult<CredentialOffer> {
+    fn set_offers_attach(mut self, credential_offer: &str) -> VcxResult<CredentialOffer> {
         self.offers_attach.add_base64_encoded_json_attachment(AttachmentId::CredentialOffer, ::serde_json::Value::String(credential_offer.to_string()))?;
         Ok(self)
     }
 
-    pub fn set_credential_preview_data(mut self, credential_preview: CredentialPreviewData) -> VcxResult<CredentialOffer> {
+    fn set_credential_preview_data(mut self, credential_preview: CredentialPreviewData) -> VcxResult<CredentialOffer> {
         self.credential_preview = credential_preview;
         Ok(self)
     }
@@ -56,8 +77,19 @@ impl CredentialOffer {
         self.thread = Some(Thread::new().set_thid(id.to_string()));
         self
     }
+
+    pub fn set_consent(mut self, consent: Option<ConsentTemplate>) -> Self {
+        self.consent = consent;
+        self
+    }
+
+    pub fn from_thread(&self, id: &str) -> bool {
+        self.thread.as_ref().map(|thread| thread.is_reply(id)).unwrap_or(false)
+    }
 }
 
+timed!(CredentialOffer);
+optionally_threaded!(CredentialOffer);
 a2a_message!(CredentialOffer);
 
 impl TryInto<CredentialOffer> for CredentialOfferV1 {
@@ -152,6 +184,8 @@ pub mod tests {
             credential_preview: _preview_data(),
             offers_attach: attachment,
             thread: Some(_thread()),
+            timing: None,
+            consent: None,
         }
     }
 
@@ -165,4 +199,17 @@ pub mod tests {
 
         assert_eq!(_credential_offer(), credential_offer);
     }
+
+    #[test]
+    fn test_credential_offer_typed_build_works() {
+        let credential_offer = CredentialOffer::build(&_attachment().to_string(), _preview_data(), None).unwrap();
+
+        assert_eq!(_credential_offer().offers_attach, credential_offer.offers_attach);
+        assert_eq!(_preview_data(), credential_offer.credential_preview);
+        assert!(credential_offer.thread.is_none());
+        assert!(credential_offer.timing.unwrap().out_time.is_some());
+
+        let counter_offer = CredentialOffer::build(&_attachment().to_string(), _preview_data(), Some(&_thread_id())).unwrap();
+        assert_eq!(Some(_thread()), counter_offer.thread);
+    }
 }
\ No newline at end of file
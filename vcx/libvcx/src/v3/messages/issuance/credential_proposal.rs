@@ -43,12 +43,21 @@ impl CredentialProposal {
         Ok(self)
     }
 
+    pub fn add_credential_preview_value(self, name: &str, value: &str) -> VcxResult<CredentialProposal> {
+        self.add_credential_preview_data(name, value, MimeType::Plain)
+    }
+
     pub fn set_thread_id(mut self, id: &str) -> Self {
         self.thread = Some(Thread::new().set_thid(id.to_string()));
         self
     }
+
+    pub fn from_thread(&self, id: &str) -> bool {
+        self.thread.as_ref().map(|thread| thread.is_reply(id)).unwrap_or(false)
+    }
 }
 
+optionally_threaded!(CredentialProposal);
 a2a_message!(CredentialProposal);
 
 #[cfg(test)]
@@ -99,4 +108,20 @@ pub mod tests {
 
         assert_eq!(_credential_proposal(), credential_proposal);
     }
+
+    #[test]
+    fn test_credential_proposal_build_works_for_plain_preview_value() {
+        let (name, value) = _value();
+
+        let credential_proposal: CredentialProposal = CredentialProposal::create()
+            .set_comment(_comment())
+            .set_thread_id(&thread_id())
+            .set_cred_def_id(_cred_def_id())
+            .set_schema_id(_schema_id())
+            .add_credential_preview_value(name, value).unwrap();
+
+        assert_eq!(_credential_proposal(), credential_proposal);
+        assert!(credential_proposal.from_thread(&thread_id()));
+        assert!(!credential_proposal.from_thread("other"));
+    }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 use v3::messages::a2a::{MessageId, A2AMessage};
 use v3::messages::attachment::{Attachments, AttachmentId};
+use v3::messages::timing::Timing;
+use v3::messages::issuance::consent::ConsentReceipt;
 use error::VcxResult;
 use messages::thread::Thread;
 
@@ -12,26 +14,46 @@ pub struct CredentialRequest {
     #[serde(rename = "requests~attach")]
     pub requests_attach: Attachments,
     #[serde(rename = "~thread")]
-    pub thread: Thread
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(rename = "~consent_receipt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent_receipt: Option<ConsentReceipt>,
 }
 
 impl CredentialRequest {
-    pub fn create() -> Self {
+    fn create() -> Self {
         CredentialRequest::default()
     }
 
+    // Request is always a reply to the offer, so thread id is required.
+    pub fn build(libindy_request: String, thread_id: &str) -> VcxResult<CredentialRequest> {
+        Ok(CredentialRequest::create()
+            .set_requests_attach(libindy_request)?
+            .set_thread_id(thread_id)
+            .set_out_time())
+    }
+
     pub fn set_comment(mut self, comment: String) -> Self {
         self.comment = Some(comment);
         self
     }
 
-    pub fn set_requests_attach(mut self, credential_request: String) -> VcxResult<CredentialRequest> {
+    pub fn set_consent_receipt(mut self, consent_receipt: ConsentReceipt) -> Self {
+        self.consent_receipt = Some(consent_receipt);
+        self
+    }
+
+    fn set_requests_attach(mut self, credential_request: String) -> VcxResult<CredentialRequest> {
         self.requests_attach.add_base64_encoded_json_attachment(AttachmentId::CredentialRequest, ::serde_json::Value::String(credential_request))?;
         Ok(self)
     }
 }
 
 threadlike!(CredentialRequest);
+timed!(CredentialRequest);
 a2a_message!(CredentialRequest);
 
 #[cfg(test)]
@@ -59,6 +81,8 @@ pub mod tests {
             comment: Some(_comment()),
             requests_attach: attachment,
             thread: thread(),
+            timing: None,
+            consent_receipt: None,
         }
     }
 
@@ -71,4 +95,13 @@ pub mod tests {
 
         assert_eq!(_credential_request(), credential_request);
     }
+
+    #[test]
+    fn test_credential_request_typed_build_works() {
+        let credential_request = CredentialRequest::build(_attachment().to_string(), &thread_id()).unwrap();
+
+        assert_eq!(_credential_request().requests_attach, credential_request.requests_attach);
+        assert!(credential_request.from_thread(&thread_id()));
+        assert!(credential_request.timing.unwrap().out_time.is_some());
+    }
 }
\ No newline at end of file
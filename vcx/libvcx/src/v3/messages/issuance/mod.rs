@@ -1,13 +1,15 @@
 use v3::messages::a2a::message_type::MessageType;
 use v3::messages::a2a::message_family::MessageFamilies;
 use v3::messages::mime_type::MimeType;
-use error::VcxResult;
+use std::collections::HashMap;
+use error::{VcxResult, VcxError, VcxErrorKind};
 
 pub mod credential;
 pub mod credential_offer;
 pub mod credential_proposal;
 pub mod credential_request;
 pub mod credential_ack;
+pub mod consent;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct CredentialPreviewData {
@@ -22,18 +24,92 @@ impl CredentialPreviewData {
     }
 
     pub fn add_value(mut self, name: &str, value: &str, mime_type: MimeType) -> VcxResult<CredentialPreviewData> {
-        let data_value = match mime_type {
-            MimeType::Plain => {
-                CredentialValue {
-                    name: name.to_string(),
-                    value: value.to_string(),
-                    _type: None,
-                }
+        let _type = match mime_type {
+            MimeType::Plain => None,
+            MimeType::Json => {
+                ::serde_json::from_str::<::serde_json::Value>(value)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Value of attribute {} isn't valid JSON: {:?}", name, err)))?;
+                Some(mime_type)
             }
+            MimeType::Png => {
+                ::base64::decode(value)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Value of attribute {} isn't valid base64: {:?}", name, err)))?;
+                Some(mime_type)
+            }
+            MimeType::Other(_) => Some(mime_type),
         };
-        self.attributes.push(data_value);
+
+        self.attributes.push(CredentialValue {
+            name: name.to_string(),
+            value: value.to_string(),
+            _type,
+            display: None,
+        });
         Ok(self)
     }
+
+    pub fn add_json_value(self, name: &str, value: &::serde_json::Value) -> VcxResult<CredentialPreviewData> {
+        self.add_value(name, &value.to_string(), MimeType::Json)
+    }
+
+    // Binary values are base64 encoded
+    pub fn add_binary_value(self, name: &str, value: &[u8], mime_type: MimeType) -> VcxResult<CredentialPreviewData> {
+        if mime_type == MimeType::Plain || mime_type == MimeType::Json {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Mime type {} isn't binary", mime_type.as_str())));
+        }
+        self.add_value(name, &::base64::encode(value), mime_type)
+    }
+
+    pub fn set_display(mut self, name: &str, display: DisplayHints) -> VcxResult<CredentialPreviewData> {
+        {
+            let value = self.attributes.iter_mut()
+                .find(|value| value.name == name)
+                .ok_or_else(|| VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Credential preview doesn't contain attribute: {}", name)))?;
+
+            value.display = Some(display);
+        }
+        Ok(self)
+    }
+
+    // Hints for attributes missing in the preview are ignored.
+    pub fn set_display_hints(mut self, hints: &HashMap<String, DisplayHints>) -> CredentialPreviewData {
+        for value in self.attributes.iter_mut() {
+            if let Some(display) = hints.get(&value.name) {
+                value.display = Some(display.clone());
+            }
+        }
+        self
+    }
+
+    pub fn get_display(&self, name: &str) -> Option<&DisplayHints> {
+        self.attributes.iter()
+            .find(|value| value.name == name)
+            .and_then(|value| value.display.as_ref())
+    }
+
+    // Attributes with order go first (ascending), others keep the order of the preview.
+    pub fn ordered_attributes(&self) -> Vec<&CredentialValue> {
+        let mut attributes: Vec<&CredentialValue> = self.attributes.iter().collect();
+        attributes.sort_by_key(|value| value.display.as_ref().and_then(|display| display.order).unwrap_or(u32::max_value()));
+        attributes
+    }
+
+    // Ordered attributes grouped by the group hint. Attributes without group are collected under the empty name.
+    // Groups follow the position of their first attribute.
+    pub fn grouped_attributes(&self) -> Vec<(String, Vec<&CredentialValue>)> {
+        let mut groups: Vec<(String, Vec<&CredentialValue>)> = Vec::new();
+
+        for value in self.ordered_attributes() {
+            let group = value.display.as_ref().and_then(|display| display.group.clone()).unwrap_or_default();
+
+            match groups.iter().position(|&(ref name, _)| *name == group) {
+                Some(index) => groups[index].1.push(value),
+                None => groups.push((group, vec![value])),
+            }
+        }
+
+        groups
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
@@ -43,6 +119,39 @@ pub struct CredentialValue {
     #[serde(rename = "mime-type")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub _type: Option<MimeType>,
+    #[serde(rename = "~display")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayHints>,
+}
+
+// Rendering hints for wallet UIs. Agents not aware of them ignore the decorator.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct DisplayHints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+impl CredentialValue {
+    pub fn mime_type(&self) -> MimeType {
+        self._type.clone().unwrap_or_default()
+    }
+
+    // Decodes base64 value of binary attribute
+    pub fn binary_value(&self) -> VcxResult<Vec<u8>> {
+        match self.mime_type() {
+            MimeType::Plain | MimeType::Json =>
+                Err(VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Attribute {} isn't binary", self.name))),
+            _ => ::base64::decode(&self.value)
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidAttributesStructure, format!("Value of attribute {} isn't valid base64: {:?}", self.name, err)))
+        }
+    }
 }
 
 impl Default for CredentialPreviewData {
@@ -56,6 +165,7 @@ impl Default for CredentialPreviewData {
 
 #[cfg(test)]
 pub mod test {
+    use super::*;
     use v3::messages::ack;
     use v3::messages::error;
     use v3::messages::issuance::credential_offer::tests::_credential_offer;
@@ -67,4 +177,96 @@ pub mod test {
     pub fn _problem_report() -> error::ProblemReport {
         error::tests::_problem_report().set_thread_id(&_credential_offer().id.0)
     }
-}
\ No newline at end of file
+
+    fn _display(label: &str, order: Option<u32>, group: Option<&str>) -> DisplayHints {
+        DisplayHints {
+            label: Some(label.to_string()),
+            order,
+            group: group.map(String::from),
+            locale: Some(String::from("en")),
+        }
+    }
+
+    fn _preview_with_display() -> CredentialPreviewData {
+        CredentialPreviewData::new()
+            .add_value("name", "Alice", MimeType::Plain).unwrap()
+            .add_value("street", "Main st", MimeType::Plain).unwrap()
+            .add_value("age", "25", MimeType::Plain).unwrap()
+            .add_value("city", "Dublin", MimeType::Plain).unwrap()
+            .set_display("name", _display("Full Name", Some(1), None)).unwrap()
+            .set_display("street", _display("Street", Some(3), Some("Address"))).unwrap()
+            .set_display("city", _display("City", Some(2), Some("Address"))).unwrap()
+    }
+
+    #[test]
+    fn test_credential_preview_add_value_works_for_mime_types() {
+        let preview = CredentialPreviewData::new()
+            .add_value("name", "Alice", MimeType::Plain).unwrap()
+            .add_json_value("address", &json!({"city": "Dublin"})).unwrap()
+            .add_binary_value("photo", &[1, 2, 3], MimeType::Png).unwrap()
+            .add_binary_value("scan", &[4, 5], MimeType::Other(String::from("application/pdf"))).unwrap();
+
+        let json = ::serde_json::to_value(&preview).unwrap();
+        assert!(json["attributes"][0].get("mime-type").is_none());
+        assert_eq!(json!("application/json"), json["attributes"][1]["mime-type"]);
+        assert_eq!(json!("image/png"), json["attributes"][2]["mime-type"]);
+        assert_eq!(json!("application/pdf"), json["attributes"][3]["mime-type"]);
+
+        assert_eq!(MimeType::Plain, preview.attributes[0].mime_type());
+        assert_eq!(vec![1, 2, 3], preview.attributes[2].binary_value().unwrap());
+        assert!(preview.attributes[1].binary_value().is_err());
+
+        let deserialized: CredentialPreviewData = ::serde_json::from_value(json).unwrap();
+        assert_eq!(preview, deserialized);
+    }
+
+    #[test]
+    fn test_credential_preview_add_value_fails_for_invalid_values() {
+        assert!(CredentialPreviewData::new().add_value("address", "{", MimeType::Json).is_err());
+        assert!(CredentialPreviewData::new().add_value("photo", "not base64!", MimeType::Png).is_err());
+        assert!(CredentialPreviewData::new().add_binary_value("name", &[1], MimeType::Plain).is_err());
+    }
+
+    #[test]
+    fn test_credential_preview_display_works() {
+        let preview = _preview_with_display();
+
+        assert_eq!(Some(&_display("Full Name", Some(1), None)), preview.get_display("name"));
+        assert_eq!(None, preview.get_display("age"));
+        assert!(CredentialPreviewData::new().set_display("name", DisplayHints::default()).is_err());
+    }
+
+    #[test]
+    fn test_credential_preview_display_is_serialized_as_decorator() {
+        let preview = _preview_with_display();
+
+        let json = ::serde_json::to_value(&preview).unwrap();
+        assert_eq!(json!({"label": "Full Name", "order": 1, "locale": "en"}), json["attributes"][0]["~display"]);
+        assert!(json["attributes"][2].get("~display").is_none());
+
+        let deserialized: CredentialPreviewData = ::serde_json::from_value(json).unwrap();
+        assert_eq!(preview, deserialized);
+    }
+
+    #[test]
+    fn test_credential_preview_ordered_attributes_works() {
+        let preview = _preview_with_display();
+
+        let names: Vec<&str> = preview.ordered_attributes().iter().map(|value| value.name.as_str()).collect();
+        assert_eq!(vec!["name", "city", "street", "age"], names);
+    }
+
+    #[test]
+    fn test_credential_preview_grouped_attributes_works() {
+        let preview = _preview_with_display();
+
+        let groups: Vec<(String, Vec<&str>)> = preview.grouped_attributes().into_iter()
+            .map(|(group, values)| (group, values.iter().map(|value| value.name.as_str()).collect()))
+            .collect();
+
+        assert_eq!(vec![
+            (String::new(), vec!["name", "age"]),
+            (String::from("Address"), vec!["city", "street"]),
+        ], groups);
+    }
+}
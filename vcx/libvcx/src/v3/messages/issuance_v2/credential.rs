@@ -0,0 +1,73 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentFormat, select_attachments};
+use v3::messages::ack::PleaseAck;
+use v3::messages::issuance::credential::Credential;
+use v3::messages::issuance_v2::INDY_CRED_FORMAT;
+use error::VcxResult;
+use messages::thread::Thread;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CredentialV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_id: Option<String>,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "credentials~attach")]
+    pub credentials_attach: Attachments,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
+}
+
+impl CredentialV2 {
+    pub fn to_v1(self) -> VcxResult<Credential> {
+        Ok(Credential {
+            id: self.id,
+            comment: self.comment,
+            credentials_attach: select_attachments(&self.formats, &self.credentials_attach, INDY_CRED_FORMAT)?,
+            thread: self.thread,
+            please_ack: self.please_ack,
+        })
+    }
+}
+
+impl From<Credential> for CredentialV2 {
+    fn from(credential: Credential) -> CredentialV2 {
+        CredentialV2 {
+            id: credential.id,
+            comment: credential.comment,
+            replacement_id: None,
+            formats: AttachmentFormat::for_attachments(&credential.credentials_attach, INDY_CRED_FORMAT),
+            credentials_attach: credential.credentials_attach,
+            thread: credential.thread,
+            please_ack: credential.please_ack,
+        }
+    }
+}
+
+please_ack!(CredentialV2);
+threaded!(CredentialV2);
+a2a_message!(CredentialV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::issuance::credential::tests::_credential;
+
+    #[test]
+    fn test_credential_v2_conversion_works() {
+        let credential_v2 = CredentialV2::from(_credential()).ask_for_ack();
+
+        let json = ::serde_json::to_value(&credential_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/issue-credential/2.0/issue-credential"), json["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-cred-0", "format": INDY_CRED_FORMAT}]), json["formats"]);
+        assert_eq!(json!({}), json["~please_ack"]);
+
+        assert_eq!(_credential().ask_for_ack(), credential_v2.to_v1().unwrap());
+    }
+}
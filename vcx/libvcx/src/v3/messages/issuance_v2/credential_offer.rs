@@ -0,0 +1,91 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentFormat, select_attachments};
+use v3::messages::issuance::CredentialPreviewData;
+use v3::messages::issuance::credential_offer::CredentialOffer;
+use v3::messages::issuance::consent::ConsentTemplate;
+use v3::messages::issuance_v2::{INDY_CRED_ABSTRACT_FORMAT, credential_preview_v1, credential_preview_v2};
+use v3::messages::timing::Timing;
+use error::VcxResult;
+use messages::thread::Thread;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CredentialOfferV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_id: Option<String>,
+    pub credential_preview: CredentialPreviewData,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "offers~attach")]
+    pub offers_attach: Attachments,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "~thread")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(rename = "~consent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent: Option<ConsentTemplate>,
+}
+
+impl CredentialOfferV2 {
+    pub fn to_v1(self) -> VcxResult<CredentialOffer> {
+        Ok(CredentialOffer {
+            id: self.id,
+            comment: self.comment,
+            credential_preview: credential_preview_v1(self.credential_preview),
+            offers_attach: select_attachments(&self.formats, &self.offers_attach, INDY_CRED_ABSTRACT_FORMAT)?,
+            thread: self.thread,
+            timing: self.timing,
+            consent: self.consent,
+        })
+    }
+}
+
+impl From<CredentialOffer> for CredentialOfferV2 {
+    fn from(offer: CredentialOffer) -> CredentialOfferV2 {
+        CredentialOfferV2 {
+            id: offer.id,
+            comment: offer.comment,
+            replacement_id: None,
+            credential_preview: credential_preview_v2(offer.credential_preview),
+            formats: AttachmentFormat::for_attachments(&offer.offers_attach, INDY_CRED_ABSTRACT_FORMAT),
+            offers_attach: offer.offers_attach,
+            thread: offer.thread,
+            timing: offer.timing,
+            consent: offer.consent,
+        }
+    }
+}
+
+optionally_threaded!(CredentialOfferV2);
+a2a_message!(CredentialOfferV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::issuance::credential_offer::tests::_credential_offer;
+
+    #[test]
+    fn test_credential_offer_v2_conversion_works() {
+        let offer_v2 = CredentialOfferV2::from(_credential_offer());
+
+        let json = ::serde_json::to_value(&offer_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/issue-credential/2.0/offer-credential"), json["@type"]);
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/issue-credential/2.0/credential-preview"), json["credential_preview"]["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-cred-offer-0", "format": INDY_CRED_ABSTRACT_FORMAT}]), json["formats"]);
+
+        assert_eq!(_credential_offer(), offer_v2.to_v1().unwrap());
+    }
+
+    #[test]
+    fn test_credential_offer_v2_to_v1_fails_for_unsupported_format() {
+        let mut offer_v2 = CredentialOfferV2::from(_credential_offer());
+        offer_v2.formats[0].format = String::from("aries/ld-proof-vc-detail@v1.0");
+
+        assert!(offer_v2.to_v1().is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentId, AttachmentEncoding, AttachmentFormat, select_attachments};
+use v3::messages::issuance::CredentialPreviewData;
+use v3::messages::issuance::credential_proposal::CredentialProposal;
+use v3::messages::issuance_v2::{INDY_CRED_FILTER_FORMAT, credential_preview_v1, credential_preview_v2};
+use error::{VcxResult, VcxError, VcxErrorKind};
+use messages::thread::Thread;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CredentialProposalV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub credential_preview: CredentialPreviewData,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "filters~attach")]
+    pub filters_attach: Attachments,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "~thread")]
+    pub thread: Option<Thread>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+struct CredentialFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cred_def_id: Option<String>,
+}
+
+impl CredentialProposalV2 {
+    pub fn to_v1(self) -> VcxResult<CredentialProposal> {
+        let filter = select_attachments(&self.formats, &self.filters_attach, INDY_CRED_FILTER_FORMAT)?.content()?;
+
+        let filter: CredentialFilter = ::serde_json::from_str(&filter)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Indy Credential Filter: {:?}", err)))?;
+
+        Ok(CredentialProposal {
+            id: self.id,
+            comment: self.comment,
+            credential_proposal: credential_preview_v1(self.credential_preview),
+            schema_id: filter.schema_id.unwrap_or_default(),
+            cred_def_id: filter.cred_def_id.unwrap_or_default(),
+            thread: self.thread,
+        })
+    }
+
+    pub fn from_v1(proposal: CredentialProposal) -> VcxResult<CredentialProposalV2> {
+        let filter = CredentialFilter {
+            schema_id: Some(proposal.schema_id).filter(|schema_id| !schema_id.is_empty()),
+            cred_def_id: Some(proposal.cred_def_id).filter(|cred_def_id| !cred_def_id.is_empty()),
+        };
+
+        let mut filters_attach = Attachments::new();
+        filters_attach.add_json_attachment(AttachmentId::CredentialFilter, json!(filter), AttachmentEncoding::Base64)?;
+
+        Ok(CredentialProposalV2 {
+            id: proposal.id,
+            comment: proposal.comment,
+            credential_preview: credential_preview_v2(proposal.credential_proposal),
+            formats: AttachmentFormat::for_attachments(&filters_attach, INDY_CRED_FILTER_FORMAT),
+            filters_attach,
+            thread: proposal.thread,
+        })
+    }
+}
+
+optionally_threaded!(CredentialProposalV2);
+a2a_message!(CredentialProposalV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::issuance::credential_proposal::tests::_credential_proposal;
+
+    #[test]
+    fn test_credential_proposal_v2_conversion_works() {
+        let proposal_v2 = CredentialProposalV2::from_v1(_credential_proposal()).unwrap();
+
+        let json = ::serde_json::to_value(&proposal_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/issue-credential/2.0/propose-credential"), json["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-cred-filter-0", "format": INDY_CRED_FILTER_FORMAT}]), json["formats"]);
+        assert_eq!(json!({"schema_id": "schema:id", "cred_def_id": "cred_def_id:id"}).to_string(), proposal_v2.filters_attach.content().unwrap());
+
+        assert_eq!(_credential_proposal(), proposal_v2.to_v1().unwrap());
+    }
+}
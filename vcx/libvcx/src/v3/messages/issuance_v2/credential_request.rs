@@ -0,0 +1,74 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentFormat, select_attachments};
+use v3::messages::issuance::credential_request::CredentialRequest;
+use v3::messages::issuance::consent::ConsentReceipt;
+use v3::messages::issuance_v2::INDY_CRED_REQUEST_FORMAT;
+use v3::messages::timing::Timing;
+use error::VcxResult;
+use messages::thread::Thread;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct CredentialRequestV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "requests~attach")]
+    pub requests_attach: Attachments,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+    #[serde(rename = "~consent_receipt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consent_receipt: Option<ConsentReceipt>,
+}
+
+impl CredentialRequestV2 {
+    pub fn to_v1(self) -> VcxResult<CredentialRequest> {
+        Ok(CredentialRequest {
+            id: self.id,
+            comment: self.comment,
+            requests_attach: select_attachments(&self.formats, &self.requests_attach, INDY_CRED_REQUEST_FORMAT)?,
+            thread: self.thread,
+            timing: self.timing,
+            consent_receipt: self.consent_receipt,
+        })
+    }
+}
+
+impl From<CredentialRequest> for CredentialRequestV2 {
+    fn from(request: CredentialRequest) -> CredentialRequestV2 {
+        CredentialRequestV2 {
+            id: request.id,
+            comment: request.comment,
+            formats: AttachmentFormat::for_attachments(&request.requests_attach, INDY_CRED_REQUEST_FORMAT),
+            requests_attach: request.requests_attach,
+            thread: request.thread,
+            timing: request.timing,
+            consent_receipt: request.consent_receipt,
+        }
+    }
+}
+
+threaded!(CredentialRequestV2);
+a2a_message!(CredentialRequestV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::issuance::credential_request::tests::_credential_request;
+
+    #[test]
+    fn test_credential_request_v2_conversion_works() {
+        let request_v2 = CredentialRequestV2::from(_credential_request());
+
+        let json = ::serde_json::to_value(&request_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/issue-credential/2.0/request-credential"), json["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-cred-request-0", "format": INDY_CRED_REQUEST_FORMAT}]), json["formats"]);
+
+        assert_eq!(_credential_request(), request_v2.to_v1().unwrap());
+    }
+}
@@ -0,0 +1,28 @@
+use v3::messages::a2a::message_type::MessageType;
+use v3::messages::a2a::message_family::MessageFamilies;
+use v3::messages::issuance::CredentialPreviewData;
+
+pub mod credential_proposal;
+pub mod credential_offer;
+pub mod credential_request;
+pub mod credential;
+
+// Attachment formats of libindy objects (Aries RFC 0592)
+pub const INDY_CRED_FILTER_FORMAT: &str = "hlindy/cred-filter@v2.0";
+pub const INDY_CRED_ABSTRACT_FORMAT: &str = "hlindy/cred-abstract@v2.0";
+pub const INDY_CRED_REQUEST_FORMAT: &str = "hlindy/cred-req@v2.0";
+pub const INDY_CRED_FORMAT: &str = "hlindy/cred@v2.0";
+
+pub fn credential_preview_v2(credential_preview: CredentialPreviewData) -> CredentialPreviewData {
+    CredentialPreviewData {
+        _type: MessageType::build(MessageFamilies::CredentialIssuanceV2, "credential-preview"),
+        ..credential_preview
+    }
+}
+
+pub fn credential_preview_v1(credential_preview: CredentialPreviewData) -> CredentialPreviewData {
+    CredentialPreviewData {
+        _type: CredentialPreviewData::default()._type,
+        ..credential_preview
+    }
+}
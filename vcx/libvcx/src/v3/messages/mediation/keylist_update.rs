@@ -0,0 +1,69 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Registers or removes recipient keys messages are routed for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct KeylistUpdate {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    pub updates: Vec<KeylistUpdateItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeylistUpdateItem {
+    pub recipient_key: String,
+    pub action: KeylistUpdateAction,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum KeylistUpdateAction {
+    #[serde(rename = "add")]
+    Add,
+    #[serde(rename = "remove")]
+    Remove,
+}
+
+impl KeylistUpdate {
+    pub fn create() -> KeylistUpdate {
+        KeylistUpdate::default()
+    }
+
+    pub fn add_key(mut self, recipient_key: &str) -> KeylistUpdate {
+        self.updates.push(KeylistUpdateItem { recipient_key: recipient_key.to_string(), action: KeylistUpdateAction::Add });
+        self
+    }
+
+    pub fn remove_key(mut self, recipient_key: &str) -> KeylistUpdate {
+        self.updates.push(KeylistUpdateItem { recipient_key: recipient_key.to_string(), action: KeylistUpdateAction::Remove });
+        self
+    }
+}
+
+a2a_message!(KeylistUpdate);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _key() -> String {
+        String::from("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL")
+    }
+
+    pub fn _keylist_update() -> KeylistUpdate {
+        KeylistUpdate {
+            id: MessageId::id(),
+            updates: vec![KeylistUpdateItem { recipient_key: _key(), action: KeylistUpdateAction::Add }],
+        }
+    }
+
+    #[test]
+    fn test_keylist_update_build_works() {
+        assert_eq!(_keylist_update(), KeylistUpdate::create().add_key(&_key()));
+    }
+
+    #[test]
+    fn test_keylist_update_serialization_works() {
+        let json = ::serde_json::to_value(&_keylist_update().to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/coordinate-mediation/1.0/keylist-update"), json["@type"]);
+        assert_eq!(json!([{"recipient_key": _key(), "action": "add"}]), json["updates"]);
+    }
+}
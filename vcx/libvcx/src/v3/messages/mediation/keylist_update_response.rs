@@ -0,0 +1,89 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::mediation::keylist_update::{KeylistUpdate, KeylistUpdateAction};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct KeylistUpdateResponse {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    pub updated: Vec<KeylistUpdated>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KeylistUpdated {
+    pub recipient_key: String,
+    pub action: KeylistUpdateAction,
+    pub result: KeylistUpdateResult,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum KeylistUpdateResult {
+    #[serde(rename = "client_error")]
+    ClientError,
+    #[serde(rename = "server_error")]
+    ServerError,
+    #[serde(rename = "no_change")]
+    NoChange,
+    #[serde(rename = "success")]
+    Success,
+}
+
+impl KeylistUpdateResponse {
+    pub fn create() -> KeylistUpdateResponse {
+        KeylistUpdateResponse::default()
+    }
+
+    // Answers every update of the request with the same result
+    pub fn for_update(update: &KeylistUpdate, result: KeylistUpdateResult) -> KeylistUpdateResponse {
+        KeylistUpdateResponse {
+            updated: update.updates.iter()
+                .map(|item| KeylistUpdated { recipient_key: item.recipient_key.clone(), action: item.action, result })
+                .collect(),
+            ..KeylistUpdateResponse::create()
+        }.set_thread_id(&update.id.0)
+    }
+}
+
+impl KeylistUpdated {
+    // Key is in the requested state on the mediator side
+    pub fn is_applied(&self) -> bool {
+        self.result == KeylistUpdateResult::Success || self.result == KeylistUpdateResult::NoChange
+    }
+}
+
+threadlike!(KeylistUpdateResponse);
+a2a_message!(KeylistUpdateResponse);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::mediation::keylist_update::tests::{_key, _keylist_update};
+
+    pub fn _keylist_update_response() -> KeylistUpdateResponse {
+        KeylistUpdateResponse {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0),
+            updated: vec![KeylistUpdated { recipient_key: _key(), action: KeylistUpdateAction::Add, result: KeylistUpdateResult::Success }],
+        }
+    }
+
+    #[test]
+    fn test_keylist_update_response_build_works() {
+        assert_eq!(_keylist_update_response(), KeylistUpdateResponse::for_update(&_keylist_update(), KeylistUpdateResult::Success));
+    }
+
+    #[test]
+    fn test_keylist_update_response_deserialization_works() {
+        let message: A2AMessage = ::serde_json::from_value(json!({
+            "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/coordinate-mediation/1.0/keylist-update-response",
+            "@id": "testid",
+            "~thread": {"thid": "testid"},
+            "updated": [{"recipient_key": _key(), "action": "add", "result": "success"}]
+        })).unwrap();
+
+        assert_eq!(_keylist_update_response().to_a2a_message(), message);
+        assert!(_keylist_update_response().updated[0].is_applied());
+    }
+}
@@ -0,0 +1,36 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MediateDeny {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+}
+
+impl MediateDeny {
+    pub fn create() -> MediateDeny {
+        MediateDeny::default()
+    }
+}
+
+threadlike!(MediateDeny);
+a2a_message!(MediateDeny);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _mediate_deny() -> MediateDeny {
+        MediateDeny {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0),
+        }
+    }
+
+    #[test]
+    fn test_mediate_deny_build_works() {
+        assert_eq!(_mediate_deny(), MediateDeny::create().set_thread_id(&MessageId::id().0));
+    }
+}
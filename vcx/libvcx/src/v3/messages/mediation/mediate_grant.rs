@@ -0,0 +1,75 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Endpoint and routing keys the recipient must put into its DIDDocs to receive messages through the mediator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MediateGrant {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    pub endpoint: String,
+    #[serde(default)]
+    pub routing_keys: Vec<String>,
+}
+
+impl MediateGrant {
+    pub fn create() -> MediateGrant {
+        MediateGrant::default()
+    }
+
+    pub fn set_endpoint(mut self, endpoint: String) -> MediateGrant {
+        self.endpoint = endpoint;
+        self
+    }
+
+    pub fn set_routing_keys(mut self, routing_keys: Vec<String>) -> MediateGrant {
+        self.routing_keys = routing_keys;
+        self
+    }
+}
+
+threadlike!(MediateGrant);
+a2a_message!(MediateGrant);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::connection::did_doc::tests::_service_endpoint;
+
+    pub fn _routing_keys() -> Vec<String> {
+        vec![String::from("3LYuxJBJkngDbvJj4zjx13DBUdZ2P96eNybwd2n9L9AU")]
+    }
+
+    pub fn _mediate_grant() -> MediateGrant {
+        MediateGrant {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0),
+            endpoint: _service_endpoint(),
+            routing_keys: _routing_keys(),
+        }
+    }
+
+    #[test]
+    fn test_mediate_grant_build_works() {
+        let grant = MediateGrant::create()
+            .set_endpoint(_service_endpoint())
+            .set_routing_keys(_routing_keys())
+            .set_thread_id(&MessageId::id().0);
+
+        assert_eq!(_mediate_grant(), grant);
+    }
+
+    #[test]
+    fn test_mediate_grant_deserialization_works() {
+        let message: A2AMessage = ::serde_json::from_value(json!({
+            "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/coordinate-mediation/1.0/mediate-grant",
+            "@id": "testid",
+            "~thread": {"thid": "testid"},
+            "endpoint": _service_endpoint(),
+            "routing_keys": _routing_keys()
+        })).unwrap();
+
+        assert_eq!(_mediate_grant().to_a2a_message(), message);
+    }
+}
@@ -0,0 +1,36 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Asks the mediator for permission to route messages of the recipient
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MediateRequest {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+}
+
+impl MediateRequest {
+    pub fn create() -> MediateRequest {
+        MediateRequest::default()
+    }
+}
+
+a2a_message!(MediateRequest);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _mediate_request() -> MediateRequest {
+        MediateRequest {
+            id: MessageId::id(),
+        }
+    }
+
+    #[test]
+    fn test_mediate_request_serialization_works() {
+        let json = ::serde_json::to_value(&_mediate_request().to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/coordinate-mediation/1.0/mediate-request"), json["@type"]);
+
+        let message: A2AMessage = ::serde_json::from_value(json).unwrap();
+        assert_eq!(_mediate_request().to_a2a_message(), message);
+    }
+}
@@ -1,7 +1,33 @@
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum MimeType {
-    #[serde(rename = "text/plain")]
-    Plain
+    Plain,
+    Json,
+    // Value is base64 encoded image
+    Png,
+    Other(String),
+}
+
+impl MimeType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MimeType::Plain => "text/plain",
+            MimeType::Json => "application/json",
+            MimeType::Png => "image/png",
+            MimeType::Other(mime_type) => mime_type.as_str(),
+        }
+    }
+
+    pub fn from_str(mime_type: &str) -> MimeType {
+        match mime_type {
+            "text/plain" => MimeType::Plain,
+            "application/json" => MimeType::Json,
+            "image/png" => MimeType::Png,
+            mime_type => MimeType::Other(mime_type.to_string()),
+        }
+    }
 }
 
 impl Default for MimeType {
@@ -9,3 +35,35 @@ impl Default for MimeType {
         MimeType::Plain
     }
 }
+
+impl Serialize for MimeType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        Value::String(self.as_str().to_string()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MimeType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let value = Value::deserialize(deserializer).map_err(de::Error::custom)?;
+        match value.as_str() {
+            Some(mime_type) => Ok(MimeType::from_str(mime_type)),
+            None => Err(de::Error::custom("Unexpected mime type."))
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_type_serialization_works() {
+        assert_eq!(json!("text/plain"), ::serde_json::to_value(&MimeType::Plain).unwrap());
+        assert_eq!(json!("image/png"), ::serde_json::to_value(&MimeType::Png).unwrap());
+        assert_eq!(json!("application/pdf"), ::serde_json::to_value(&MimeType::Other(String::from("application/pdf"))).unwrap());
+
+        assert_eq!(MimeType::Json, ::serde_json::from_value(json!("application/json")).unwrap());
+        assert_eq!(MimeType::Other(String::from("application/pdf")), ::serde_json::from_value(json!("application/pdf")).unwrap());
+        assert!(::serde_json::from_value::<MimeType>(json!(1)).is_err());
+    }
+}
@@ -1,7 +1,11 @@
 #[macro_use]
+pub mod decorators;
+#[macro_use]
 pub mod a2a;
 #[macro_use]
 pub mod ack;
+#[macro_use]
+pub mod timing;
 pub mod connection;
 pub mod error;
 pub mod forward;
@@ -9,8 +13,14 @@ pub mod attachment;
 pub mod mime_type;
 pub mod status;
 pub mod issuance;
+pub mod issuance_v2;
 pub mod proof_presentation;
 pub mod discovery;
 pub mod trust_ping;
 pub mod basic_message;
+pub mod out_of_band;
+pub mod didexchange;
+pub mod mediation;
+pub mod pickup;
+pub mod revocation_notification;
 pub mod localization;
\ No newline at end of file
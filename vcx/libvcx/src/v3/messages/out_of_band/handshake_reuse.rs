@@ -0,0 +1,56 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Sent over existing connection instead of performing handshake offered by Out-of-Band invitation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct HandshakeReuse {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+}
+
+impl HandshakeReuse {
+    pub fn create() -> HandshakeReuse {
+        let reuse = HandshakeReuse::default();
+        let thid = reuse.id.0.clone();
+        reuse.set_thread_id(&thid)
+    }
+
+    // Id of Out-of-Band invitation being answered
+    pub fn set_invitation_id(mut self, invitation_id: &str) -> HandshakeReuse {
+        self.thread.pthid = Some(invitation_id.to_string());
+        self
+    }
+
+    pub fn invitation_id(&self) -> Option<&String> {
+        self.thread.pthid.as_ref()
+    }
+}
+
+threadlike!(HandshakeReuse);
+a2a_message!(HandshakeReuse);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _invitation_id() -> String {
+        String::from("invitation_id")
+    }
+
+    pub fn _handshake_reuse() -> HandshakeReuse {
+        HandshakeReuse {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0),
+        }.set_invitation_id(&_invitation_id())
+    }
+
+    #[test]
+    fn test_handshake_reuse_build_works() {
+        let reuse = HandshakeReuse::create().set_invitation_id(&_invitation_id());
+
+        assert_eq!(_handshake_reuse(), reuse);
+        assert_eq!(Some(&_invitation_id()), reuse.invitation_id());
+    }
+}
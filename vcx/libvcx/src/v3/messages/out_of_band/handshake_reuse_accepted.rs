@@ -0,0 +1,49 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::out_of_band::handshake_reuse::HandshakeReuse;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct HandshakeReuseAccepted {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+}
+
+impl HandshakeReuseAccepted {
+    pub fn create() -> HandshakeReuseAccepted {
+        HandshakeReuseAccepted::default()
+    }
+
+    // Shares the thread of the reuse message including the invitation id
+    pub fn for_reuse(reuse: &HandshakeReuse) -> HandshakeReuseAccepted {
+        let mut accepted = HandshakeReuseAccepted::create().set_thread_id(&reuse.id.0);
+        accepted.thread.pthid = reuse.thread.pthid.clone();
+        accepted
+    }
+}
+
+threadlike!(HandshakeReuseAccepted);
+a2a_message!(HandshakeReuseAccepted);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::out_of_band::handshake_reuse::tests::{_handshake_reuse, _invitation_id};
+
+    pub fn _handshake_reuse_accepted() -> HandshakeReuseAccepted {
+        let mut thread = Thread::new().set_thid(MessageId::id().0);
+        thread.pthid = Some(_invitation_id());
+
+        HandshakeReuseAccepted {
+            id: MessageId::id(),
+            thread,
+        }
+    }
+
+    #[test]
+    fn test_handshake_reuse_accepted_build_works() {
+        assert_eq!(_handshake_reuse_accepted(), HandshakeReuseAccepted::for_reuse(&_handshake_reuse()));
+        assert!(_handshake_reuse_accepted().from_thread(&_handshake_reuse().id.0));
+    }
+}
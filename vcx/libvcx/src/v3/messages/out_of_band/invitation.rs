@@ -0,0 +1,274 @@
+use v3::messages::a2a::{A2AMessage, MessageId};
+use v3::messages::a2a::message_family::MessageFamilies;
+use v3::messages::attachment::{Attachments, Attachment, AttachmentId};
+use v3::messages::connection::invite::Invitation as ConnectionInvitation;
+use error::prelude::*;
+
+// Out-of-band invitation (Aries RFC 0434): connection invitation and/or requests which can be handled without connection.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
+pub struct Invitation {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub handshake_protocols: Vec<String>,
+    #[serde(default)]
+    #[serde(rename = "requests~attach")]
+    pub requests_attach: Attachments,
+    pub services: Vec<ServiceResolvable>,
+}
+
+// Service is either given inline or as a public DID resolvable on the ledger
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum ServiceResolvable {
+    Service(Service),
+    Did(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Service {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub recipient_keys: Vec<String>,
+    #[serde(default)]
+    pub routing_keys: Vec<String>,
+    pub service_endpoint: String,
+}
+
+impl Service {
+    const INLINE_ID: &'static str = "#inline";
+    const TYPE: &'static str = "did-communication";
+
+    pub fn build(recipient_keys: Vec<String>, routing_keys: Vec<String>, service_endpoint: String) -> Service {
+        Service {
+            id: Service::INLINE_ID.to_string(),
+            type_: Service::TYPE.to_string(),
+            recipient_keys,
+            routing_keys,
+            service_endpoint,
+        }
+    }
+}
+
+impl Invitation {
+    pub fn create() -> Invitation {
+        Invitation::default()
+    }
+
+    pub fn set_label(mut self, label: String) -> Invitation {
+        self.label = Some(label);
+        self
+    }
+
+    pub fn set_goal_code(mut self, goal_code: Option<String>) -> Invitation {
+        self.goal_code = goal_code;
+        self
+    }
+
+    pub fn set_goal(mut self, goal: Option<String>) -> Invitation {
+        self.goal = goal;
+        self
+    }
+
+    pub fn add_handshake_protocol(mut self, family: MessageFamilies) -> Invitation {
+        self.handshake_protocols.push(family.id());
+        self
+    }
+
+    pub fn add_service(mut self, service: Service) -> Invitation {
+        self.services.push(ServiceResolvable::Service(service));
+        self
+    }
+
+    pub fn add_service_did(mut self, did: String) -> Invitation {
+        self.services.push(ServiceResolvable::Did(did));
+        self
+    }
+
+    pub fn add_request(mut self, request: &A2AMessage) -> VcxResult<Invitation> {
+        let id = AttachmentId::Other(format!("request-{}", self.requests_attach.0.len()));
+        self.requests_attach.add_base64_encoded_json_attachment(id, json!(request))?;
+        Ok(self)
+    }
+
+    // Protocol ids are compared without the prefix, so both `did:sov:` and `https://didcomm.org/` forms are accepted
+    pub fn supports_handshake(&self, family: &MessageFamilies) -> bool {
+        let suffix = format!("/{}/{}", family.to_string(), family.version());
+        self.handshake_protocols.iter().any(|protocol| protocol.ends_with(&suffix))
+    }
+
+    // Handshake protocols are listed in order of preference of the inviter
+    pub fn handshake_protocol(&self) -> Option<MessageFamilies> {
+        self.handshake_protocols.iter()
+            .filter_map(|protocol| {
+                vec![MessageFamilies::Connections, MessageFamilies::DidExchange].into_iter()
+                    .find(|family| protocol.ends_with(&format!("/{}/{}", family.to_string(), family.version())))
+            })
+            .next()
+    }
+
+    pub fn requests(&self) -> VcxResult<Vec<A2AMessage>> {
+        self.requests_attach.0.iter()
+            .filter_map(|attachment| match attachment {
+                Attachment::JSON(ref json) => Some(json.get_data()),
+                Attachment::Blank => None
+            })
+            .map(|data| {
+                ::serde_json::from_str::<A2AMessage>(&data?)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Out-of-Band request: {:?}", err)))
+            })
+            .collect()
+    }
+
+    pub fn inline_service(&self) -> Option<&Service> {
+        self.services.iter()
+            .filter_map(|service| match service {
+                ServiceResolvable::Service(ref service) => Some(service),
+                ServiceResolvable::Did(_) => None
+            })
+            .next()
+    }
+
+    pub fn public_did(&self) -> Option<&str> {
+        self.services.iter()
+            .filter_map(|service| match service {
+                ServiceResolvable::Did(ref did) => Some(did.as_str()),
+                ServiceResolvable::Service(_) => None
+            })
+            .next()
+    }
+
+    // Connection invitation to perform handshake with. Resolving of public DIDs isn't supported.
+    pub fn to_connection_invitation(&self) -> VcxResult<ConnectionInvitation> {
+        if self.handshake_protocol().is_none() {
+            return Err(VcxError::from_msg(VcxErrorKind::ActionNotSupported, format!("Out-of-Band invitation doesn't offer supported handshake protocol: {:?}", self.handshake_protocols)));
+        }
+
+        let service = self.inline_service()
+            .ok_or(VcxError::from_msg(VcxErrorKind::ActionNotSupported, "Out-of-Band invitation doesn't contain inline service"))?;
+
+        Ok(ConnectionInvitation::build(self.label.clone().unwrap_or_default(), service.recipient_keys.clone(), service.service_endpoint.clone())
+            .set_id(self.id.0.clone())
+            .set_routing_keys(service.routing_keys.clone())
+            .set_public_did(self.public_did().map(String::from)))
+    }
+}
+
+impl From<ConnectionInvitation> for Invitation {
+    fn from(invitation: ConnectionInvitation) -> Invitation {
+        let mut services = vec![ServiceResolvable::Service(Service::build(invitation.recipient_keys, invitation.routing_keys, invitation.service_endpoint))];
+        services.extend(invitation.did.map(ServiceResolvable::Did));
+
+        Invitation {
+            id: invitation.id,
+            label: Some(invitation.label),
+            handshake_protocols: vec![MessageFamilies::Connections.id(), MessageFamilies::DidExchange.id()],
+            services,
+            ..Invitation::default()
+        }
+    }
+}
+
+a2a_message!(Invitation, OutOfBandInvitation);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::connection::invite::tests::_invitation;
+    use v3::messages::connection::did_doc::tests::*;
+    use v3::messages::trust_ping::ping::tests::_ping;
+
+    pub fn _oob_invitation() -> Invitation {
+        Invitation {
+            id: MessageId::id(),
+            label: Some(_label()),
+            goal_code: None,
+            goal: None,
+            handshake_protocols: vec![MessageFamilies::Connections.id(), MessageFamilies::DidExchange.id()],
+            requests_attach: Attachments::new(),
+            services: vec![ServiceResolvable::Service(Service::build(_recipient_keys(), _routing_keys(), _service_endpoint()))],
+        }
+    }
+
+    #[test]
+    fn test_oob_invitation_build_works() {
+        let invitation = Invitation::create()
+            .set_label(_label())
+            .add_handshake_protocol(MessageFamilies::Connections)
+            .add_handshake_protocol(MessageFamilies::DidExchange)
+            .add_service(Service::build(_recipient_keys(), _routing_keys(), _service_endpoint()));
+
+        assert_eq!(_oob_invitation(), invitation);
+        assert_eq!(_oob_invitation(), Invitation::from(_invitation()));
+    }
+
+    #[test]
+    fn test_oob_invitation_serialization_works() {
+        let json = ::serde_json::to_value(&_oob_invitation().to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/out-of-band/1.0/invitation"), json["@type"]);
+        assert_eq!(json!("#inline"), json["services"][0]["id"]);
+        assert_eq!(json!("did-communication"), json["services"][0]["type"]);
+
+        let message: A2AMessage = ::serde_json::from_value(json).unwrap();
+        assert_eq!(_oob_invitation().to_a2a_message(), message);
+    }
+
+    #[test]
+    fn test_oob_invitation_deserialization_works_for_did_service() {
+        let invitation: Invitation = ::serde_json::from_value(json!({
+            "@id": "testid",
+            "handshake_protocols": ["https://didcomm.org/connections/1.0"],
+            "services": ["did:sov:LjgpST2rjsoxYegQDRm7EL"]
+        })).unwrap();
+
+        assert!(invitation.supports_handshake(&MessageFamilies::Connections));
+        assert_eq!(vec![ServiceResolvable::Did(String::from("did:sov:LjgpST2rjsoxYegQDRm7EL"))], invitation.services);
+        assert_eq!(VcxErrorKind::ActionNotSupported, invitation.to_connection_invitation().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_oob_invitation_to_connection_invitation_works() {
+        assert_eq!(_invitation(), _oob_invitation().to_connection_invitation().unwrap());
+
+        let mut invitation = _oob_invitation();
+        invitation.handshake_protocols = vec![];
+        assert_eq!(VcxErrorKind::ActionNotSupported, invitation.to_connection_invitation().unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_oob_invitation_public_did_works() {
+        assert_eq!(None, _oob_invitation().public_did());
+
+        let invitation = _oob_invitation().add_service_did(String::from("did:sov:LjgpST2rjsoxYegQDRm7EL"));
+        assert_eq!(Some("did:sov:LjgpST2rjsoxYegQDRm7EL"), invitation.public_did());
+        assert_eq!(Some(String::from("did:sov:LjgpST2rjsoxYegQDRm7EL")), invitation.to_connection_invitation().unwrap().did);
+    }
+
+    #[test]
+    fn test_oob_invitation_handshake_protocol_works() {
+        assert_eq!(Some(MessageFamilies::Connections), _oob_invitation().handshake_protocol());
+
+        let mut invitation = _oob_invitation();
+        invitation.handshake_protocols = vec![String::from("https://didcomm.org/didexchange/1.0"), MessageFamilies::Connections.id()];
+        assert_eq!(Some(MessageFamilies::DidExchange), invitation.handshake_protocol());
+        assert_eq!(_invitation(), invitation.to_connection_invitation().unwrap());
+
+        invitation.handshake_protocols = vec![String::from("https://didcomm.org/didexchange/2.0")];
+        assert_eq!(None, invitation.handshake_protocol());
+    }
+
+    #[test]
+    fn test_oob_invitation_requests_works() {
+        let invitation = _oob_invitation().add_request(&_ping().to_a2a_message()).unwrap();
+        assert_eq!(vec![_ping().to_a2a_message()], invitation.requests().unwrap());
+    }
+}
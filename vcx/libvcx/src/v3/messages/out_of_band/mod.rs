@@ -0,0 +1,3 @@
+pub mod invitation;
+pub mod handshake_reuse;
+pub mod handshake_reuse_accepted;
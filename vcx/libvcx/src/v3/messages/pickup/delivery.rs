@@ -0,0 +1,104 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::AttachmentData;
+use error::prelude::*;
+
+// Queued messages, each attached as packed DIDComm message identified by the attachment id
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Delivery {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(default)]
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_key: Option<String>,
+    #[serde(rename = "~attach")]
+    pub messages: Vec<DeliveredMessage>,
+}
+
+// Delivered messages are not typed by mime-type, so generic attachments don't fit them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeliveredMessage {
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub data: AttachmentData,
+}
+
+impl DeliveredMessage {
+    pub fn new(id: &str, payload: &[u8]) -> DeliveredMessage {
+        DeliveredMessage { id: id.to_string(), data: AttachmentData::base64(payload) }
+    }
+
+    pub fn payload(&self) -> VcxResult<Vec<u8>> {
+        self.data.get_bytes()
+    }
+}
+
+impl Delivery {
+    pub fn create() -> Delivery {
+        Delivery::default()
+    }
+
+    pub fn add_message(mut self, message: DeliveredMessage) -> Delivery {
+        self.messages.push(message);
+        self
+    }
+}
+
+threadlike!(Delivery);
+a2a_message!(Delivery);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _payload() -> Vec<u8> {
+        br#"{"protected":"eyJlbmMiOiJ4Y2hhY2hhMjBwb2x5MTMwNV9pZXRmIn0","iv":"aXY","ciphertext":"Y2lwaGVy","tag":"dGFn"}"#.to_vec()
+    }
+
+    pub fn _delivery() -> Delivery {
+        Delivery {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0),
+            recipient_key: None,
+            messages: vec![DeliveredMessage::new("message_1", &_payload())],
+        }
+    }
+
+    #[test]
+    fn test_delivery_build_works() {
+        let delivery = Delivery::create()
+            .add_message(DeliveredMessage::new("message_1", &_payload()))
+            .set_thread_id(&MessageId::id().0);
+
+        assert_eq!(_delivery(), delivery);
+        assert_eq!(_payload(), delivery.messages[0].payload().unwrap());
+    }
+
+    #[test]
+    fn test_delivery_deserialization_works() {
+        let message: A2AMessage = ::serde_json::from_value(json!({
+            "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/messagepickup/2.0/delivery",
+            "@id": "testid",
+            "~thread": {"thid": "testid"},
+            "~attach": [{
+                "@id": "message_1",
+                "data": {"base64": ::base64::encode(&_payload())}
+            }]
+        })).unwrap();
+
+        assert_eq!(_delivery().to_a2a_message(), message);
+    }
+
+    #[test]
+    fn test_delivered_message_works_for_json_data() {
+        let message: DeliveredMessage = ::serde_json::from_value(json!({
+            "@id": "message_1",
+            "data": {"json": {"ciphertext": "Y2lwaGVy"}}
+        })).unwrap();
+
+        assert_eq!(br#"{"ciphertext":"Y2lwaGVy"}"#.to_vec(), message.payload().unwrap());
+    }
+}
@@ -0,0 +1,61 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Asks the mediator to deliver up to `limit` queued messages in the response
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct DeliveryRequest {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_key: Option<String>,
+}
+
+impl DeliveryRequest {
+    pub fn create() -> DeliveryRequest {
+        DeliveryRequest::default()
+    }
+
+    pub fn set_limit(mut self, limit: u32) -> DeliveryRequest {
+        self.limit = limit;
+        self
+    }
+
+    pub fn set_recipient_key(mut self, recipient_key: Option<String>) -> DeliveryRequest {
+        self.recipient_key = recipient_key;
+        self
+    }
+}
+
+a2a_message!(DeliveryRequest);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::mediation::keylist_update::tests::_key;
+
+    pub fn _delivery_request() -> DeliveryRequest {
+        DeliveryRequest {
+            id: MessageId::id(),
+            limit: 10,
+            recipient_key: Some(_key()),
+        }
+    }
+
+    #[test]
+    fn test_delivery_request_build_works() {
+        let request = DeliveryRequest::create()
+            .set_limit(10)
+            .set_recipient_key(Some(_key()));
+
+        assert_eq!(_delivery_request(), request);
+    }
+
+    #[test]
+    fn test_delivery_request_serialization_works() {
+        let json = ::serde_json::to_value(&_delivery_request().to_a2a_message()).unwrap();
+
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/messagepickup/2.0/delivery-request"), json["@type"]);
+        assert_eq!(json!(10), json["limit"]);
+        assert_eq!(json!(_key()), json["recipient_key"]);
+    }
+}
@@ -0,0 +1,47 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Lets the mediator remove delivered messages from the queue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct MessagesReceived {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    pub message_id_list: Vec<String>,
+}
+
+impl MessagesReceived {
+    pub fn create() -> MessagesReceived {
+        MessagesReceived::default()
+    }
+
+    pub fn set_message_id_list(mut self, message_id_list: Vec<String>) -> MessagesReceived {
+        self.message_id_list = message_id_list;
+        self
+    }
+}
+
+a2a_message!(MessagesReceived);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _messages_received() -> MessagesReceived {
+        MessagesReceived {
+            id: MessageId::id(),
+            message_id_list: vec![String::from("message_1")],
+        }
+    }
+
+    #[test]
+    fn test_messages_received_build_works() {
+        assert_eq!(_messages_received(), MessagesReceived::create().set_message_id_list(vec![String::from("message_1")]));
+    }
+
+    #[test]
+    fn test_messages_received_serialization_works() {
+        let json = ::serde_json::to_value(&_messages_received().to_a2a_message()).unwrap();
+
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/messagepickup/2.0/messages-received"), json["@type"]);
+        assert_eq!(json!(["message_1"]), json["message_id_list"]);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod status_request;
+pub mod status;
+pub mod delivery_request;
+pub mod delivery;
+pub mod messages_received;
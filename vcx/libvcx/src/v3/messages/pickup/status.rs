@@ -0,0 +1,64 @@
+use messages::thread::Thread;
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Status {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(default)]
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    pub message_count: u32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_key: Option<String>,
+}
+
+impl Status {
+    pub fn create() -> Status {
+        Status::default()
+    }
+
+    pub fn set_message_count(mut self, message_count: u32) -> Status {
+        self.message_count = message_count;
+        self
+    }
+}
+
+threadlike!(Status);
+a2a_message!(Status, PickupStatus);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _status() -> Status {
+        Status {
+            id: MessageId::id(),
+            thread: Thread::new().set_thid(MessageId::id().0),
+            message_count: 2,
+            recipient_key: None,
+        }
+    }
+
+    #[test]
+    fn test_status_build_works() {
+        let status = Status::create()
+            .set_message_count(2)
+            .set_thread_id(&MessageId::id().0);
+
+        assert_eq!(_status(), status);
+    }
+
+    #[test]
+    fn test_status_deserialization_works() {
+        let message: A2AMessage = ::serde_json::from_value(json!({
+            "@type": "did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/messagepickup/2.0/status",
+            "@id": "testid",
+            "~thread": {"thid": "testid"},
+            "message_count": 2
+        })).unwrap();
+
+        assert_eq!(_status().to_a2a_message(), message);
+    }
+}
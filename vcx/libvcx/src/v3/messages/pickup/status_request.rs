@@ -0,0 +1,49 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+
+// Asks the mediator how many messages are queued, optionally for a single recipient key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct StatusRequest {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_key: Option<String>,
+}
+
+impl StatusRequest {
+    pub fn create() -> StatusRequest {
+        StatusRequest::default()
+    }
+
+    pub fn set_recipient_key(mut self, recipient_key: Option<String>) -> StatusRequest {
+        self.recipient_key = recipient_key;
+        self
+    }
+}
+
+a2a_message!(StatusRequest, PickupStatusRequest);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::mediation::keylist_update::tests::_key;
+
+    pub fn _status_request() -> StatusRequest {
+        StatusRequest {
+            id: MessageId::id(),
+            recipient_key: Some(_key()),
+        }
+    }
+
+    #[test]
+    fn test_status_request_build_works() {
+        assert_eq!(_status_request(), StatusRequest::create().set_recipient_key(Some(_key())));
+    }
+
+    #[test]
+    fn test_status_request_serialization_works() {
+        let json = ::serde_json::to_value(&StatusRequest::create().to_a2a_message()).unwrap();
+
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/messagepickup/2.0/status-request"), json["@type"]);
+        assert!(json.get("recipient_key").is_none());
+    }
+}
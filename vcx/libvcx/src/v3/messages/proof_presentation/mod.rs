@@ -2,6 +2,7 @@ pub mod presentation_proposal;
 pub mod presentation_request;
 pub mod presentation;
 pub mod presentation_ack;
+pub mod v2;
 
 #[cfg(test)]
 pub mod test {
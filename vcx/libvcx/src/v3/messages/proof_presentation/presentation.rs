@@ -1,6 +1,7 @@
 use v3::messages::a2a::{MessageId, A2AMessage};
 use v3::messages::attachment::{Attachments, AttachmentId};
 use v3::messages::ack::PleaseAck;
+use v3::messages::timing::Timing;
 use messages::thread::Thread;
 use messages::proofs::proof_message::ProofMessage;
 use std::convert::TryInto;
@@ -19,27 +20,45 @@ pub struct Presentation {
     pub thread: Thread,
     #[serde(rename = "~please_ack")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub please_ack: Option<PleaseAck>
+    pub please_ack: Option<PleaseAck>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
 }
 
 impl Presentation {
-    pub fn create() -> Self {
+    fn create() -> Self {
         Presentation::default()
     }
 
+    // Presentation is always a reply to the presentation request, so thread id is required.
+    pub fn build(libindy_proof: String, thread_id: &str) -> VcxResult<Presentation> {
+        Ok(Presentation::create()
+            .set_presentations_attach(libindy_proof)?
+            .set_thread_id(thread_id)
+            .set_out_time())
+    }
+
     pub fn set_comment(mut self, comment: String) -> Self {
         self.comment = Some(comment);
         self
     }
 
-    pub fn set_presentations_attach(mut self, presentations: String) -> VcxResult<Presentation> {
+    fn set_presentations_attach(mut self, presentations: String) -> VcxResult<Presentation> {
         self.presentations_attach.add_base64_encoded_json_attachment(AttachmentId::Presentation,::serde_json::Value::String(presentations))?;
         Ok(self)
     }
+
+    // DIF Presentation Submission describing the libindy proof, sent along with it in present-proof 2.0
+    pub fn set_presentation_submission(mut self, submission: ::serde_json::Value) -> VcxResult<Presentation> {
+        self.presentations_attach.add_base64_encoded_json_attachment(AttachmentId::PresentationSubmission, json!({"presentation_submission": submission}))?;
+        Ok(self)
+    }
 }
 
 please_ack!(Presentation);
 threadlike!(Presentation);
+timed!(Presentation);
 a2a_message!(Presentation);
 
 impl TryInto<Presentation> for ProofMessage {
@@ -87,6 +106,7 @@ pub mod tests {
             presentations_attach: attachment,
             thread: thread(),
             please_ack: Some(PleaseAck {}),
+            timing: None,
         }
     }
 
@@ -100,4 +120,14 @@ pub mod tests {
 
         assert_eq!(_presentation(), presentation);
     }
+
+    #[test]
+    fn test_presentation_typed_build_works() {
+        let presentation = Presentation::build(_attachment().to_string(), &thread_id()).unwrap();
+
+        assert_eq!(_presentation().presentations_attach, presentation.presentations_attach);
+        assert!(presentation.from_thread(&thread_id()));
+        assert!(presentation.please_ack.is_none());
+        assert!(presentation.timing.unwrap().out_time.is_some());
+    }
 }
@@ -11,6 +11,9 @@ pub struct PresentationProposal {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
     pub presentation_proposal: PresentationPreview,
+    // referents of the requested non-revocation interval the prover can satisfy only with credentials without revocation support
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub non_revocable_referents: Vec<String>,
     #[serde(rename = "~thread")]
     pub thread: Thread,
 }
@@ -43,7 +46,7 @@ pub struct Predicate {
     pub filter: Option<Vec<::serde_json::Value>>,
 }
 
-fn default_presentation_preview_type() -> MessageType {
+pub fn default_presentation_preview_type() -> MessageType {
     MessageType::build(MessageFamilies::CredentialIssuance, "presentation-preview")
 }
 impl PresentationProposal {
@@ -60,6 +63,16 @@ impl PresentationProposal {
         self.presentation_proposal = presentation_preview;
         self
     }
+
+    pub fn set_non_revocable_referents(mut self, non_revocable_referents: Vec<String>) -> PresentationProposal {
+        self.non_revocable_referents = non_revocable_referents;
+        self
+    }
+
+    pub fn accepted_by(&self, accepted_referents: &[String]) -> bool {
+        !self.non_revocable_referents.is_empty() &&
+            self.non_revocable_referents.iter().all(|referent| accepted_referents.contains(referent))
+    }
 }
 
 threadlike!(PresentationProposal);
@@ -98,6 +111,7 @@ pub mod tests {
             comment: Some(_comment()),
             thread: thread(),
             presentation_proposal: _presentation_preview(),
+            non_revocable_referents: vec![],
         }
     }
 
@@ -110,4 +124,18 @@ pub mod tests {
 
         assert_eq!(_presentation_proposal(), presentation_proposal);
     }
+
+    #[test]
+    fn test_presentation_proposal_accepted_by_works() {
+        let referents = vec![String::from("attribute_0"), String::from("predicate_0")];
+
+        assert!(!_presentation_proposal().accepted_by(&referents));
+
+        let proposal = _presentation_proposal().set_non_revocable_referents(vec![String::from("attribute_0")]);
+        assert!(proposal.accepted_by(&referents));
+        assert!(!proposal.accepted_by(&[]));
+
+        let proposal = _presentation_proposal().set_non_revocable_referents(vec![String::from("attribute_1")]);
+        assert!(!proposal.accepted_by(&referents));
+    }
 }
@@ -1,6 +1,7 @@
 use v3::messages::a2a::{MessageId, A2AMessage};
 use v3::messages::attachment::{Attachments, AttachmentId};
 use v3::messages::connection::service::Service;
+use v3::messages::timing::Timing;
 use error::prelude::*;
 use std::convert::TryInto;
 
@@ -17,13 +18,23 @@ pub struct PresentationRequest {
     #[serde(rename = "~service")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service: Option<Service>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
 }
 
 impl PresentationRequest {
-    pub fn create() -> Self {
+    fn create() -> Self {
         PresentationRequest::default()
     }
 
+    // Presentation request starts a new thread, its id is used as thread id by the prover.
+    pub fn build(request_presentations: &PresentationRequestData) -> VcxResult<PresentationRequest> {
+        Ok(PresentationRequest::create()
+            .set_request_presentations_attach(request_presentations)?
+            .set_out_time())
+    }
+
     pub fn set_id(mut self, id: String) -> Self {
         self.id = MessageId(id);
         self
@@ -34,7 +45,7 @@ impl PresentationRequest {
         self
     }
 
-    pub fn set_request_presentations_attach(mut self, request_presentations: &PresentationRequestData) -> VcxResult<PresentationRequest> {
+    fn set_request_presentations_attach(mut self, request_presentations: &PresentationRequestData) -> VcxResult<PresentationRequest> {
         self.request_presentations_attach.add_base64_encoded_json_attachment(AttachmentId::PresentationRequest, json!(request_presentations))?;
         Ok(self)
     }
@@ -50,6 +61,7 @@ impl PresentationRequest {
     }
 }
 
+timed!(PresentationRequest);
 a2a_message!(PresentationRequest);
 
 impl TryInto<PresentationRequest> for ProofRequestMessage {
@@ -121,6 +133,7 @@ pub mod tests {
             comment: Some(_comment()),
             request_presentations_attach: _attachment(),
             service: None,
+            timing: None,
         }
     }
 
@@ -130,6 +143,7 @@ pub mod tests {
             comment: Some(_comment()),
             request_presentations_attach: _attachment(),
             service: Some(_service()),
+            timing: None,
         }
     }
 
@@ -151,4 +165,12 @@ pub mod tests {
 
         assert_eq!(_presentation_request_with_service(), presentation_request);
     }
+
+    #[test]
+    fn test_presentation_request_typed_build_works() {
+        let presentation_request = PresentationRequest::build(&_presentation_request_data()).unwrap();
+
+        assert_eq!(_presentation_request().request_presentations_attach, presentation_request.request_presentations_attach);
+        assert!(presentation_request.timing.unwrap().out_time.is_some());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,31 @@
+use v3::messages::attachment::{Attachments, AttachmentFormat, AttachmentId};
+
+pub mod presentation_proposal;
+pub mod presentation_request;
+pub mod presentation;
+
+// Attachment formats of libindy objects (Aries RFC 0592)
+pub const INDY_PROOF_REQUEST_FORMAT: &str = "hlindy/proof-req@v2.0";
+pub const INDY_PROOF_FORMAT: &str = "hlindy/proof@v2.0";
+
+// Attachment formats of DIF Presentation Exchange objects (Aries RFC 0510).
+// Other agents may send them side by side with libindy ones. Presentation Definition is translated into
+// libindy proof request if there is no libindy one, Presentation Submission is attached to the proof built for it.
+pub const DIF_PRESENTATION_DEFINITION_FORMAT: &str = "dif/presentation-exchange/definitions@v1.0";
+pub const DIF_PRESENTATION_SUBMISSION_FORMAT: &str = "dif/presentation-exchange/submission@v1.0";
+
+// DIF objects are attached under their own ids, all other attachments are of the given libindy format
+pub fn attachment_formats(attachments: &Attachments, indy_format: &str) -> Vec<AttachmentFormat> {
+    attachments.ids()
+        .into_iter()
+        .map(|attach_id| {
+            let format = match attach_id {
+                AttachmentId::PresentationDefinition => DIF_PRESENTATION_DEFINITION_FORMAT,
+                AttachmentId::PresentationSubmission => DIF_PRESENTATION_SUBMISSION_FORMAT,
+                _ => indy_format
+            };
+
+            AttachmentFormat { attach_id, format: format.to_string() }
+        })
+        .collect()
+}
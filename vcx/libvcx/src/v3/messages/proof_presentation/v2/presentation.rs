@@ -0,0 +1,92 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentFormat, select_attachments};
+use v3::messages::ack::PleaseAck;
+use v3::messages::proof_presentation::presentation::Presentation;
+use v3::messages::proof_presentation::v2::{INDY_PROOF_FORMAT, attachment_formats};
+use v3::messages::timing::Timing;
+use error::VcxResult;
+use messages::thread::Thread;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct PresentationV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "presentations~attach")]
+    pub presentations_attach: Attachments,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl PresentationV2 {
+    pub fn to_v1(self) -> VcxResult<Presentation> {
+        Ok(Presentation {
+            id: self.id,
+            comment: self.comment,
+            presentations_attach: select_attachments(&self.formats, &self.presentations_attach, INDY_PROOF_FORMAT)?,
+            thread: self.thread,
+            please_ack: self.please_ack,
+            timing: self.timing,
+        })
+    }
+}
+
+impl From<Presentation> for PresentationV2 {
+    fn from(presentation: Presentation) -> PresentationV2 {
+        PresentationV2 {
+            id: presentation.id,
+            comment: presentation.comment,
+            formats: attachment_formats(&presentation.presentations_attach, INDY_PROOF_FORMAT),
+            presentations_attach: presentation.presentations_attach,
+            thread: presentation.thread,
+            please_ack: presentation.please_ack,
+            timing: presentation.timing,
+        }
+    }
+}
+
+please_ack!(PresentationV2);
+threaded!(PresentationV2);
+a2a_message!(PresentationV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::proof_presentation::presentation::tests::_presentation;
+    use v3::messages::proof_presentation::v2::DIF_PRESENTATION_SUBMISSION_FORMAT;
+
+    #[test]
+    fn test_presentation_v2_conversion_works() {
+        let presentation_v2 = PresentationV2::from(_presentation());
+
+        let json = ::serde_json::to_value(&presentation_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/present-proof/2.0/presentation"), json["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-presentation-0", "format": INDY_PROOF_FORMAT}]), json["formats"]);
+        assert_eq!(json!({}), json["~please_ack"]);
+
+        assert_eq!(_presentation(), presentation_v2.to_v1().unwrap());
+    }
+
+    #[test]
+    fn test_presentation_v2_conversion_works_for_presentation_submission() {
+        let presentation = _presentation().set_presentation_submission(json!({"id": "submission"})).unwrap();
+
+        let presentation_v2 = PresentationV2::from(presentation);
+
+        let json = ::serde_json::to_value(&presentation_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!([
+            {"attach_id": "libindy-presentation-0", "format": INDY_PROOF_FORMAT},
+            {"attach_id": "dif-presentation-submission-0", "format": DIF_PRESENTATION_SUBMISSION_FORMAT}
+        ]), json["formats"]);
+
+        assert_eq!(_presentation(), presentation_v2.to_v1().unwrap());
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentId, AttachmentFormat, select_attachments};
+use v3::messages::proof_presentation::presentation_proposal::{PresentationProposal, PresentationPreview, Attribute, Predicate, default_presentation_preview_type};
+use v3::messages::proof_presentation::v2::INDY_PROOF_REQUEST_FORMAT;
+use error::{VcxResult, VcxError, VcxErrorKind};
+use messages::thread::Thread;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct PresentationProposalV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "proposals~attach")]
+    pub proposals_attach: Attachments,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub non_revocable_referents: Vec<String>,
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+}
+
+// Present-proof 2.0 has no presentation preview, libindy proposal is a proof request without nonce.
+// Attribute values and mime types can't be expressed by it and are dropped.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+struct ProofRequestProposal {
+    name: String,
+    version: String,
+    requested_attributes: BTreeMap<String, ProposedAttribute>,
+    requested_predicates: BTreeMap<String, ProposedPredicate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+struct ProposedAttribute {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restrictions: Option<Vec<::serde_json::Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+struct ProposedPredicate {
+    name: String,
+    p_type: String,
+    p_value: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restrictions: Option<Vec<::serde_json::Value>>,
+}
+
+// Explicit filter takes precedence over the credential definition id
+fn _restrictions(cred_def_id: Option<String>, filter: Option<Vec<::serde_json::Value>>) -> Option<Vec<::serde_json::Value>> {
+    filter.or_else(|| cred_def_id.map(|cred_def_id| vec![json!({"cred_def_id": cred_def_id})]))
+}
+
+fn _cred_def_id_and_filter(restrictions: Option<Vec<::serde_json::Value>>) -> (Option<String>, Option<Vec<::serde_json::Value>>) {
+    let cred_def_id = match restrictions {
+        Some(ref restrictions) if restrictions.len() == 1 => {
+            restrictions[0].as_object()
+                .filter(|restriction| restriction.len() == 1)
+                .and_then(|restriction| restriction.get("cred_def_id"))
+                .and_then(|cred_def_id| cred_def_id.as_str())
+                .map(String::from)
+        }
+        _ => None
+    };
+
+    match cred_def_id {
+        Some(cred_def_id) => (Some(cred_def_id), None),
+        None => (None, restrictions)
+    }
+}
+
+impl PresentationProposalV2 {
+    pub fn to_v1(self) -> VcxResult<PresentationProposal> {
+        let proposal = select_attachments(&self.formats, &self.proposals_attach, INDY_PROOF_REQUEST_FORMAT)?.content()?;
+
+        let proposal: ProofRequestProposal = ::serde_json::from_str(&proposal)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Indy Proof Request proposal: {:?}", err)))?;
+
+        let attributes = proposal.requested_attributes.into_iter()
+            .map(|(_, attribute)| {
+                let (cred_def_id, filter) = _cred_def_id_and_filter(attribute.restrictions);
+                Attribute { name: attribute.name, cred_def_id, mime_type: None, value: None, filter }
+            })
+            .collect();
+
+        let predicates = proposal.requested_predicates.into_iter()
+            .map(|(_, predicate)| {
+                let (cred_def_id, filter) = _cred_def_id_and_filter(predicate.restrictions);
+                Predicate { name: predicate.name, cred_def_id, predicate: predicate.p_type, threshold: predicate.p_value, filter }
+            })
+            .collect();
+
+        Ok(PresentationProposal {
+            id: self.id,
+            comment: self.comment,
+            presentation_proposal: PresentationPreview { _type: default_presentation_preview_type(), attributes, predicates },
+            non_revocable_referents: self.non_revocable_referents,
+            thread: self.thread,
+        })
+    }
+
+    pub fn from_v1(proposal: PresentationProposal) -> VcxResult<PresentationProposalV2> {
+        let preview = proposal.presentation_proposal;
+
+        let requested_attributes = preview.attributes.into_iter()
+            .enumerate()
+            .map(|(i, attribute)| {
+                (format!("attribute_{}", i), ProposedAttribute { name: attribute.name, restrictions: _restrictions(attribute.cred_def_id, attribute.filter) })
+            })
+            .collect();
+
+        let requested_predicates = preview.predicates.into_iter()
+            .enumerate()
+            .map(|(i, predicate)| {
+                (format!("predicate_{}", i), ProposedPredicate {
+                    name: predicate.name,
+                    p_type: predicate.predicate,
+                    p_value: predicate.threshold,
+                    restrictions: _restrictions(predicate.cred_def_id, predicate.filter),
+                })
+            })
+            .collect();
+
+        let proof_request = ProofRequestProposal {
+            name: String::from("proposal"),
+            version: String::from("1.0"),
+            requested_attributes,
+            requested_predicates,
+        };
+
+        let mut proposals_attach = Attachments::new();
+        proposals_attach.add_base64_encoded_json_attachment(AttachmentId::PresentationProposal, json!(proof_request))?;
+
+        Ok(PresentationProposalV2 {
+            id: proposal.id,
+            comment: proposal.comment,
+            formats: AttachmentFormat::for_attachments(&proposals_attach, INDY_PROOF_REQUEST_FORMAT),
+            proposals_attach,
+            non_revocable_referents: proposal.non_revocable_referents,
+            thread: proposal.thread,
+        })
+    }
+}
+
+threaded!(PresentationProposalV2);
+a2a_message!(PresentationProposalV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::proof_presentation::presentation_proposal::tests::_presentation_proposal;
+
+    #[test]
+    fn test_presentation_proposal_v2_conversion_works() {
+        let mut proposal = _presentation_proposal().set_non_revocable_referents(vec![String::from("attribute_0")]);
+        proposal.presentation_proposal.attributes.push(Attribute {
+            name: String::from("age"),
+            cred_def_id: Some(String::from("cred_def_id:id")),
+            mime_type: None,
+            value: None,
+            filter: None,
+        });
+        proposal.presentation_proposal.predicates.push(Predicate {
+            name: String::from("height"),
+            cred_def_id: None,
+            predicate: String::from(">="),
+            threshold: 180,
+            filter: Some(vec![json!({"schema_name": "gvt"})]),
+        });
+
+        let proposal_v2 = PresentationProposalV2::from_v1(proposal.clone()).unwrap();
+
+        let json = ::serde_json::to_value(&proposal_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/present-proof/2.0/propose-presentation"), json["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-presentation-proposal-0", "format": INDY_PROOF_REQUEST_FORMAT}]), json["formats"]);
+
+        let expected_proof_request = json!({
+            "name": "proposal",
+            "version": "1.0",
+            "requested_attributes": {
+                "attribute_0": {"name": "name"},
+                "attribute_1": {"name": "age", "restrictions": [{"cred_def_id": "cred_def_id:id"}]}
+            },
+            "requested_predicates": {
+                "predicate_0": {"name": "height", "p_type": ">=", "p_value": 180, "restrictions": [{"schema_name": "gvt"}]}
+            }
+        });
+        let proof_request: ::serde_json::Value = ::serde_json::from_str(&proposal_v2.proposals_attach.content().unwrap()).unwrap();
+        assert_eq!(expected_proof_request, proof_request);
+
+        let proposal_v1 = proposal_v2.to_v1().unwrap();
+        assert_eq!(proposal.id, proposal_v1.id);
+        assert_eq!(proposal.thread, proposal_v1.thread);
+        assert_eq!(proposal.presentation_proposal.attributes, proposal_v1.presentation_proposal.attributes);
+        assert_eq!(proposal.presentation_proposal.predicates, proposal_v1.presentation_proposal.predicates);
+        assert_eq!(proposal.non_revocable_referents, proposal_v1.non_revocable_referents);
+    }
+}
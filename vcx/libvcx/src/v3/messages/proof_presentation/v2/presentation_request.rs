@@ -0,0 +1,166 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::attachment::{Attachments, AttachmentFormat, AttachmentId, select_attachments};
+use v3::messages::connection::service::Service;
+use v3::messages::proof_presentation::presentation_request::PresentationRequest;
+use v3::messages::proof_presentation::v2::{INDY_PROOF_REQUEST_FORMAT, DIF_PRESENTATION_DEFINITION_FORMAT, attachment_formats};
+use v3::messages::timing::Timing;
+use utils::libindy::anoncreds;
+use error::prelude::*;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct PresentationRequestV2 {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub will_confirm: Option<bool>,
+    pub formats: Vec<AttachmentFormat>,
+    #[serde(rename = "request_presentations~attach")]
+    pub request_presentations_attach: Attachments,
+    #[serde(rename = "~service")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<Service>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl PresentationRequestV2 {
+    pub fn to_v1(self) -> VcxResult<PresentationRequest> {
+        let request_presentations_attach = match select_attachments(&self.formats, &self.request_presentations_attach, INDY_PROOF_REQUEST_FORMAT) {
+            Ok(attachments) => attachments,
+            Err(ref err) if err.kind() == VcxErrorKind::ActionNotSupported => self._translate_presentation_definition()?,
+            Err(err) => return Err(err)
+        };
+
+        Ok(PresentationRequest {
+            id: self.id,
+            comment: self.comment,
+            request_presentations_attach,
+            service: self.service,
+            timing: self.timing,
+        })
+    }
+
+    // libindy proof request goes first, the definition is kept to attach Presentation Submission to the proof
+    fn _translate_presentation_definition(&self) -> VcxResult<Attachments> {
+        let definition_attach = select_attachments(&self.formats, &self.request_presentations_attach, DIF_PRESENTATION_DEFINITION_FORMAT)?;
+
+        let definition: ::serde_json::Value = ::serde_json::from_str(&definition_attach.content()?)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Presentation Definition attachment: {:?}", err)))?;
+
+        let definition = &definition["presentation_definition"];
+
+        if !definition.is_object() {
+            return Err(VcxError::from_msg(VcxErrorKind::InvalidJson, "Presentation Definition attachment doesn't contain `presentation_definition`"));
+        }
+
+        let proof_request = anoncreds::libindy_verifier_create_proof_request_from_presentation_definition(&definition.to_string())?;
+
+        let proof_request: ::serde_json::Value = ::serde_json::from_str(&proof_request)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize Proof Request: {:?}", err)))?;
+
+        let mut attachments = Attachments::new();
+        attachments.add_base64_encoded_json_attachment(AttachmentId::PresentationRequest, proof_request)?;
+        attachments.add_base64_encoded_json_attachment(AttachmentId::PresentationDefinition, json!({"presentation_definition": definition}))?;
+
+        Ok(attachments)
+    }
+}
+
+impl From<PresentationRequest> for PresentationRequestV2 {
+    fn from(request: PresentationRequest) -> PresentationRequestV2 {
+        PresentationRequestV2 {
+            id: request.id,
+            comment: request.comment,
+            will_confirm: None,
+            formats: attachment_formats(&request.request_presentations_attach, INDY_PROOF_REQUEST_FORMAT),
+            request_presentations_attach: request.request_presentations_attach,
+            service: request.service,
+            timing: request.timing,
+        }
+    }
+}
+
+a2a_message!(PresentationRequestV2);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use v3::messages::proof_presentation::presentation_request::tests::{_presentation_request, _presentation_request_with_service};
+    use utils::devsetup::SetupDefaults;
+
+    fn _dif_attachment_id() -> AttachmentId {
+        AttachmentId::PresentationDefinition
+    }
+
+    fn _presentation_definition() -> ::serde_json::Value {
+        json!({
+            "id": "definition",
+            "input_descriptors": [{
+                "id": "age",
+                "constraints": {"fields": [{"path": ["$.credentialSubject.age"], "filter": {"type": "number", "minimum": 18}}]}
+            }]
+        })
+    }
+
+    fn _dif_only_request(definition: ::serde_json::Value) -> PresentationRequestV2 {
+        let mut request_v2 = PresentationRequestV2::from(_presentation_request());
+        request_v2.request_presentations_attach = Attachments::new();
+        request_v2.request_presentations_attach.add_base64_encoded_json_attachment(_dif_attachment_id(), json!({"presentation_definition": definition})).unwrap();
+        request_v2.formats = vec![AttachmentFormat { attach_id: _dif_attachment_id(), format: DIF_PRESENTATION_DEFINITION_FORMAT.to_string() }];
+        request_v2
+    }
+
+    #[test]
+    fn test_presentation_request_v2_conversion_works() {
+        let request_v2 = PresentationRequestV2::from(_presentation_request_with_service());
+
+        let json = ::serde_json::to_value(&request_v2.to_a2a_message()).unwrap();
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/present-proof/2.0/request-presentation"), json["@type"]);
+        assert_eq!(json!([{"attach_id": "libindy-request-presentation-0", "format": INDY_PROOF_REQUEST_FORMAT}]), json["formats"]);
+
+        assert_eq!(_presentation_request_with_service(), request_v2.to_v1().unwrap());
+    }
+
+    #[test]
+    fn test_presentation_request_v2_selects_indy_format() {
+        let mut request_v2 = PresentationRequestV2::from(_presentation_request());
+        request_v2.request_presentations_attach.add_base64_encoded_json_attachment(_dif_attachment_id(), json!({"presentation_definition": {}})).unwrap();
+        request_v2.formats.insert(0, AttachmentFormat { attach_id: _dif_attachment_id(), format: DIF_PRESENTATION_DEFINITION_FORMAT.to_string() });
+
+        assert_eq!(_presentation_request(), request_v2.to_v1().unwrap());
+    }
+
+    #[test]
+    fn test_presentation_request_v2_conversion_translates_dif_format_only() {
+        let _setup = SetupDefaults::init();
+
+        let request = _dif_only_request(_presentation_definition()).to_v1().unwrap();
+        assert_eq!(vec![AttachmentId::PresentationRequest, AttachmentId::PresentationDefinition], request.request_presentations_attach.ids());
+
+        let proof_request: ::serde_json::Value = ::serde_json::from_str(&request.request_presentations_attach.content().unwrap()).unwrap();
+        assert_eq!(json!("age"), proof_request["requested_predicates"]["age::0"]["name"]);
+
+        let request_v2 = PresentationRequestV2::from(request);
+        assert_eq!(DIF_PRESENTATION_DEFINITION_FORMAT, request_v2.formats[1].format);
+    }
+
+    #[test]
+    fn test_presentation_request_v2_conversion_fails_for_invalid_dif_format_only() {
+        let _setup = SetupDefaults::init();
+
+        let err = _dif_only_request(json!({})).to_v1().unwrap_err();
+        assert_eq!(VcxErrorKind::LibindyInvalidStructure, err.kind());
+    }
+
+    #[test]
+    fn test_presentation_request_v2_conversion_fails_for_unknown_format_only() {
+        let mut request_v2 = _dif_only_request(_presentation_definition());
+        request_v2.formats[0].format = String::from("unknown/format@v1.0");
+
+        let err = request_v2.to_v1().unwrap_err();
+        assert_eq!(VcxErrorKind::ActionNotSupported, err.kind());
+    }
+}
@@ -0,0 +1,78 @@
+use v3::messages::a2a::{MessageId, A2AMessage};
+use v3::messages::ack::PleaseAck;
+
+// Issuer notifies Holder that the credential issued within the thread is revoked
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Revoke {
+    #[serde(rename = "@id")]
+    pub id: MessageId,
+    // Thread of Credential Issuance the revoked credential was issued within
+    pub thread_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(rename = "~please_ack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub please_ack: Option<PleaseAck>,
+}
+
+impl Revoke {
+    pub fn create() -> Revoke {
+        Revoke::default()
+    }
+
+    pub fn set_credential_thread_id(mut self, thread_id: &str) -> Revoke {
+        self.thread_id = thread_id.to_string();
+        self
+    }
+
+    pub fn set_comment(mut self, comment: Option<String>) -> Revoke {
+        self.comment = comment;
+        self
+    }
+}
+
+please_ack!(Revoke);
+a2a_message!(Revoke, RevocationNotification);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn _credential_thread_id() -> String {
+        String::from("testid")
+    }
+
+    pub fn _comment() -> Option<String> {
+        Some(String::from("Credential is revoked"))
+    }
+
+    pub fn _revoke() -> Revoke {
+        Revoke {
+            id: MessageId::id(),
+            thread_id: _credential_thread_id(),
+            comment: _comment(),
+            please_ack: None,
+        }
+    }
+
+    #[test]
+    fn test_revoke_build_works() {
+        let revoke = Revoke::create()
+            .set_credential_thread_id(&_credential_thread_id())
+            .set_comment(_comment());
+
+        assert_eq!(_revoke(), revoke);
+        assert_eq!(Some(PleaseAck {}), revoke.ask_for_ack().please_ack);
+    }
+
+    #[test]
+    fn test_revoke_serialization_works() {
+        let json = ::serde_json::to_value(&_revoke().to_a2a_message()).unwrap();
+
+        assert_eq!(json!("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/revocation_notification/1.0/revoke"), json["@type"]);
+        assert_eq!(json!(_credential_thread_id()), json["thread_id"]);
+
+        let message: A2AMessage = ::serde_json::from_value(json).unwrap();
+        assert_eq!(_revoke().to_a2a_message(), message);
+    }
+}
@@ -20,4 +20,11 @@ impl Status {
             Status::Declined => 3
         }
     }
+
+    pub fn problem_report(&self) -> Option<&ProblemReport> {
+        match self {
+            Status::Failed(problem_report) => Some(problem_report),
+            _ => None
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,44 @@
+use chrono::prelude::*;
+
+// Timing decorator (Aries RFC 0032). Only the time of sending is set by VCX.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Timing {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_time: Option<String>,
+}
+
+impl Timing {
+    pub fn now() -> Timing {
+        Timing {
+            out_time: Some(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)),
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! timed (($type:ident) => (
+    impl $type {
+        pub fn set_out_time(mut self) -> $type {
+            self.timing = Some(Timing::now());
+            self
+        }
+    }
+));
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_now_works() {
+        let timing = Timing::now();
+
+        let out_time = timing.out_time.unwrap();
+        assert!(DateTime::parse_from_rfc3339(&out_time).is_ok());
+    }
+
+    #[test]
+    fn test_timing_is_omitted_when_empty() {
+        assert_eq!(json!({}), ::serde_json::to_value(Timing::default()).unwrap());
+    }
+}
@@ -35,6 +35,7 @@ impl Ping {
     }
 }
 
+optionally_threaded!(Ping);
 a2a_message!(Ping);
 
 #[cfg(test)]
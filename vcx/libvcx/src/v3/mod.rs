@@ -174,7 +174,7 @@ pub mod test {
         pub fn activate(&self) {
             ::settings::clear_config();
             ::settings::process_config_string(&self.config, false).unwrap();
-            set_wallet_handle(self.wallet_handle);
+            set_wallet_handle(self.wallet_handle).unwrap();
         }
 
         pub fn create_schema(&mut self) {
@@ -351,7 +351,7 @@ pub mod test {
         pub fn activate(&self) {
             ::settings::clear_config();
             ::settings::process_config_string(&self.config, false).unwrap();
-            set_wallet_handle(self.wallet_handle);
+            set_wallet_handle(self.wallet_handle).unwrap();
         }
 
         pub fn accept_invite(&mut self, invite: &str) {
@@ -0,0 +1,278 @@
+extern crate rust_base58;
+
+use self::rust_base58::{FromBase58, ToBase58};
+
+use error::prelude::*;
+use v3::messages::connection::did_doc::DidDoc;
+use v3::utils::didcomm_v2;
+
+// Multicodec prefixes (varint encoded) of the public keys used in `did:key` and `did:peer` identifiers
+const ED25519_PUB_PREFIX: [u8; 2] = [0xed, 0x01];
+const X25519_PUB_PREFIX: [u8; 2] = [0xec, 0x01];
+// Multibase prefix of base58btc encoding
+const MULTIBASE_BASE58_BTC: char = 'z';
+
+const DID_KEY_PREFIX: &str = "did:key:";
+const DID_PEER_PREFIX: &str = "did:peer:";
+
+// Purpose codes of `did:peer` numalgo 2 elements
+const PEER_PURPOSE_AUTHENTICATION: char = 'V';
+const PEER_PURPOSE_KEY_AGREEMENT: char = 'E';
+const PEER_PURPOSE_SERVICE: char = 'S';
+
+const PEER_SERVICE_TYPE: &str = "dm";
+const PEER_SERVICE_ACCEPT: &str = "didcomm/aip2;env=rfc19";
+
+// Method of DIDs created for the connection. Unqualified Indy DIDs are used by default.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DidMethod {
+    #[serde(rename = "unqualified")]
+    Unqualified,
+    #[serde(rename = "did:key")]
+    Key,
+    #[serde(rename = "did:peer:0")]
+    PeerNumalgo0,
+    #[serde(rename = "did:peer:2")]
+    PeerNumalgo2,
+}
+
+impl Default for DidMethod {
+    fn default() -> DidMethod {
+        DidMethod::Unqualified
+    }
+}
+
+impl DidMethod {
+    pub fn is_unqualified(&self) -> bool {
+        *self == DidMethod::Unqualified
+    }
+
+    // Builds DID of the method for the verkey. Numalgo 2 `did:peer` additionally encodes the service.
+    pub fn build_did(&self, unqualified_did: &str, verkey: &str, endpoint: &str, routing_keys: &[String]) -> VcxResult<String> {
+        match self {
+            DidMethod::Unqualified => Ok(unqualified_did.to_string()),
+            DidMethod::Key => did_key(verkey),
+            DidMethod::PeerNumalgo0 => peer_did_numalgo_0(verkey),
+            DidMethod::PeerNumalgo2 => peer_did_numalgo_2(verkey, endpoint, routing_keys),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PeerService {
+    #[serde(rename = "t")]
+    type_: String,
+    #[serde(rename = "s")]
+    endpoint: String,
+    #[serde(default)]
+    #[serde(rename = "r")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    routing_keys: Vec<String>,
+    #[serde(default)]
+    #[serde(rename = "a")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    accept: Vec<String>,
+}
+
+pub fn did_key(verkey: &str) -> VcxResult<String> {
+    Ok(format!("{}{}", DID_KEY_PREFIX, _encode_multibase_key(&ED25519_PUB_PREFIX, &_decode_verkey(verkey)?)))
+}
+
+pub fn peer_did_numalgo_0(verkey: &str) -> VcxResult<String> {
+    Ok(format!("{}0{}", DID_PEER_PREFIX, _encode_multibase_key(&ED25519_PUB_PREFIX, &_decode_verkey(verkey)?)))
+}
+
+// Routing keys are encoded as `did:key` references as other agents expect
+pub fn peer_did_numalgo_2(verkey: &str, endpoint: &str, routing_keys: &[String]) -> VcxResult<String> {
+    let key_agreement_key = _decode_verkey(&didcomm_v2::key_agreement_key_from_verkey(verkey)?)?;
+    let verkey = _decode_verkey(verkey)?;
+
+    let service = PeerService {
+        type_: PEER_SERVICE_TYPE.to_string(),
+        endpoint: endpoint.to_string(),
+        routing_keys: routing_keys.iter()
+            .map(|key| did_key(key).map(|did| format!("{}#{}", did, &did[DID_KEY_PREFIX.len()..])))
+            .collect::<VcxResult<Vec<String>>>()?,
+        accept: vec![PEER_SERVICE_ACCEPT.to_string()],
+    };
+
+    let service = ::serde_json::to_string(&service)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::SerializationError, format!("Cannot serialize did:peer service: {:?}", err)))?;
+
+    Ok(format!("{}2.{}{}.{}{}.{}{}",
+               DID_PEER_PREFIX,
+               PEER_PURPOSE_AUTHENTICATION, _encode_multibase_key(&ED25519_PUB_PREFIX, &verkey),
+               PEER_PURPOSE_KEY_AGREEMENT, _encode_multibase_key(&X25519_PUB_PREFIX, &key_agreement_key),
+               PEER_PURPOSE_SERVICE, ::base64::encode_config(service.as_bytes(), ::base64::URL_SAFE_NO_PAD)))
+}
+
+pub fn is_resolvable(did: &str) -> bool {
+    did.starts_with(DID_KEY_PREFIX) || did.starts_with(DID_PEER_PREFIX)
+}
+
+// Builds DIDDoc of the connection protocol from `did:key` or `did:peer` (numalgo 0 or 2) DID.
+// Only numalgo 2 DIDs contain the service endpoint, DIDDocs of other ones can't be used to send messages.
+pub fn resolve(did: &str) -> VcxResult<DidDoc> {
+    trace!("did_method::resolve >>> did: {:?}", did);
+
+    let (verkey, endpoint, routing_keys) = if did.starts_with(DID_KEY_PREFIX) {
+        (_decode_multibase_verkey(&did[DID_KEY_PREFIX.len()..])?, String::new(), Vec::new())
+    } else if did.starts_with(DID_PEER_PREFIX) {
+        let peer_did = &did[DID_PEER_PREFIX.len()..];
+
+        match peer_did.chars().next() {
+            Some('0') => (_decode_multibase_verkey(&peer_did[1..])?, String::new(), Vec::new()),
+            Some('2') => _resolve_peer_did_numalgo_2(&peer_did[1..])?,
+            _ => return Err(VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Unsupported did:peer numalgo: {:?}", did)))
+        }
+    } else {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Unsupported DID method: {:?}", did)));
+    };
+
+    let mut did_doc = DidDoc::default();
+    did_doc.set_id(did.to_string());
+    did_doc.set_service_endpoint(endpoint);
+    did_doc.set_keys(vec![verkey], routing_keys);
+    Ok(did_doc)
+}
+
+fn _resolve_peer_did_numalgo_2(elements: &str) -> VcxResult<(String, String, Vec<String>)> {
+    let mut verkey: Option<String> = None;
+    let mut service: Option<PeerService> = None;
+
+    for element in elements.split('.').filter(|element| !element.is_empty()) {
+        let mut chars = element.chars();
+        let purpose = chars.next();
+        let value = chars.as_str();
+
+        match purpose {
+            Some(PEER_PURPOSE_AUTHENTICATION) if verkey.is_none() => verkey = Some(_decode_multibase_verkey(value)?),
+            Some(PEER_PURPOSE_SERVICE) if service.is_none() => {
+                let json = ::base64::decode_config(value, ::base64::URL_SAFE_NO_PAD)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Cannot decode did:peer service: {:?}", err)))?;
+                service = Some(::serde_json::from_slice(&json)
+                    .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Cannot deserialize did:peer service: {:?}", err)))?);
+            }
+            // Key agreement keys are derived from the verkey when needed
+            _ => {}
+        }
+    }
+
+    let verkey = verkey
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidDid, "did:peer doesn't contain authentication key"))?;
+
+    let (endpoint, routing_keys) = match service {
+        Some(service) => {
+            let routing_keys = service.routing_keys.iter()
+                .map(|key| _routing_key(key))
+                .collect::<VcxResult<Vec<String>>>()?;
+            (service.endpoint, routing_keys)
+        }
+        None => (String::new(), Vec::new())
+    };
+
+    Ok((verkey, endpoint, routing_keys))
+}
+
+// Routing keys may be either `did:key` references or raw base58 verkeys
+fn _routing_key(key: &str) -> VcxResult<String> {
+    if key.starts_with(DID_KEY_PREFIX) {
+        let key = key[DID_KEY_PREFIX.len()..].split('#').next().unwrap_or_default();
+        _decode_multibase_verkey(key)
+    } else {
+        Ok(key.to_string())
+    }
+}
+
+fn _encode_multibase_key(prefix: &[u8], key: &[u8]) -> String {
+    let mut bytes = prefix.to_vec();
+    bytes.extend_from_slice(key);
+    format!("{}{}", MULTIBASE_BASE58_BTC, bytes.to_base58())
+}
+
+fn _decode_multibase_verkey(value: &str) -> VcxResult<String> {
+    if !value.starts_with(MULTIBASE_BASE58_BTC) {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Unsupported multibase encoding of key: {:?}", value)));
+    }
+
+    let bytes = value[1..].from_base58()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Cannot decode key {:?}: {:?}", value, err)))?;
+
+    if !bytes.starts_with(&ED25519_PUB_PREFIX) || bytes.len() != ED25519_PUB_PREFIX.len() + 32 {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidDid, format!("Key is not Ed25519 public key: {:?}", value)));
+    }
+
+    Ok(bytes[ED25519_PUB_PREFIX.len()..].to_base58())
+}
+
+fn _decode_verkey(verkey: &str) -> VcxResult<Vec<u8>> {
+    verkey.from_base58()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidVerkey, format!("Cannot decode verkey {:?}: {:?}", verkey, err)))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const VERKEY: &str = "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL";
+
+    fn _endpoint() -> String {
+        String::from("https://localhost:8080")
+    }
+
+    fn _routing_keys() -> Vec<String> {
+        vec![String::from("Hezce2UWMZ3wUhVkh2LfKSs8nDzWwzs2Win7EzNN3YaR")]
+    }
+
+    #[test]
+    fn test_did_key_works() {
+        let did = did_key(VERKEY).unwrap();
+        assert!(did.starts_with("did:key:z6Mk"));
+
+        let did_doc = resolve(&did).unwrap();
+        assert_eq!(did, did_doc.id);
+        assert_eq!(vec![VERKEY.to_string()], did_doc.recipient_keys());
+    }
+
+    #[test]
+    fn test_peer_did_numalgo_0_works() {
+        let did = peer_did_numalgo_0(VERKEY).unwrap();
+        assert!(did.starts_with("did:peer:0z6Mk"));
+
+        let did_doc = resolve(&did).unwrap();
+        assert_eq!(vec![VERKEY.to_string()], did_doc.recipient_keys());
+    }
+
+    #[test]
+    fn test_peer_did_numalgo_2_works() {
+        let did = peer_did_numalgo_2(VERKEY, &_endpoint(), &_routing_keys()).unwrap();
+        assert!(did.starts_with("did:peer:2.Vz6Mk"));
+        assert!(did.contains(".Ez6LS"));
+
+        let did_doc = resolve(&did).unwrap();
+        did_doc.validate().unwrap();
+        assert_eq!(did, did_doc.id);
+        assert_eq!(_endpoint(), did_doc.get_endpoint());
+        assert_eq!(vec![VERKEY.to_string()], did_doc.recipient_keys());
+        assert_eq!(_routing_keys(), did_doc.routing_keys());
+    }
+
+    #[test]
+    fn test_build_did_keeps_unqualified_did() {
+        assert_eq!("VsKV7grR1BUE29mG2Fm2kX", DidMethod::Unqualified.build_did("VsKV7grR1BUE29mG2Fm2kX", VERKEY, &_endpoint(), &[]).unwrap());
+        assert_eq!(did_key(VERKEY).unwrap(), DidMethod::Key.build_did("VsKV7grR1BUE29mG2Fm2kX", VERKEY, &_endpoint(), &[]).unwrap());
+    }
+
+    #[test]
+    fn test_did_method_deserialization_works() {
+        assert_eq!(DidMethod::PeerNumalgo2, ::serde_json::from_str::<DidMethod>(r#""did:peer:2""#).unwrap());
+        assert!(::serde_json::from_str::<DidMethod>(r#""did:web""#).is_err());
+    }
+
+    #[test]
+    fn test_resolve_fails_for_unsupported_dids() {
+        assert_eq!(VcxErrorKind::InvalidDid, resolve("VsKV7grR1BUE29mG2Fm2kX").unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidDid, resolve("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa").unwrap_err().kind());
+        assert_eq!(VcxErrorKind::InvalidDid, resolve("did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc").unwrap_err().kind());
+    }
+}
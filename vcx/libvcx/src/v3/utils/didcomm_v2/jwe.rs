@@ -0,0 +1,493 @@
+use openssl::aes::{AesKey, wrap_key, unwrap_key};
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkey::PKey;
+use openssl::rand::rand_bytes;
+use openssl::sha::sha256;
+use openssl::sign::Signer;
+use openssl::symm::{self, Cipher};
+
+use std::fmt::Debug;
+
+use error::prelude::*;
+use v3::utils::didcomm_v2::x25519;
+
+pub const ANONCRYPT_ALG: &str = "ECDH-ES+A256KW";
+pub const AUTHCRYPT_ALG: &str = "ECDH-1PU+A256KW";
+const ENCRYPTED_MESSAGE_TYPE: &str = "application/didcomm-encrypted+json";
+const KEY_WRAPPING_KEY_LENGTH: usize = 256;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ContentEncryption {
+    #[serde(rename = "A256GCM")]
+    A256Gcm,
+    #[serde(rename = "A256CBC-HS512")]
+    A256CbcHs512,
+}
+
+impl Default for ContentEncryption {
+    fn default() -> ContentEncryption {
+        ContentEncryption::A256CbcHs512
+    }
+}
+
+// JWE in General JSON Serialization as DIDComm v2 encrypted message
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Jwe {
+    pub protected: String,
+    pub recipients: Vec<JweRecipient>,
+    pub iv: String,
+    pub ciphertext: String,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JweRecipient {
+    pub header: RecipientHeader,
+    pub encrypted_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecipientHeader {
+    pub kid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProtectedHeader {
+    pub typ: String,
+    pub alg: String,
+    pub enc: ContentEncryption,
+    pub epk: EphemeralKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apu: Option<String>,
+    pub apv: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EphemeralKey {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+}
+
+// X25519 key agreement key of the recipient
+pub struct Recipient {
+    pub kid: String,
+    pub public: Vec<u8>,
+}
+
+// X25519 key agreement key of the sender for authenticated encryption (ECDH-1PU).
+// Static secret isn't exposed, `diffie_hellman` computes shared secret with the given public key.
+pub struct Sender<'a> {
+    pub kid: String,
+    pub diffie_hellman: &'a dyn Fn(&[u8]) -> VcxResult<Vec<u8>>,
+}
+
+impl Jwe {
+    pub fn protected_header(&self) -> VcxResult<ProtectedHeader> {
+        let header = _decode(&self.protected)?;
+        ::serde_json::from_slice(&header)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize JWE protected header: {:?}", err)))
+    }
+}
+
+pub fn encrypt(plaintext: &[u8], recipients: &[Recipient], sender: Option<&Sender>, enc: ContentEncryption) -> VcxResult<Jwe> {
+    trace!("jwe::encrypt >>> recipients: {:?}, sender: {:?}, enc: {:?}",
+           recipients.iter().map(|recipient| recipient.kid.as_str()).collect::<Vec<&str>>(), sender.map(|sender| sender.kid.as_str()), enc);
+
+    if recipients.is_empty() {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidLibindyParam, "JWE must have at least one recipient"));
+    }
+
+    // ECDH-1PU with key wrapping binds the key to the content via authentication tag which only AES-CBC-HMAC provides
+    if sender.is_some() && enc != ContentEncryption::A256CbcHs512 {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidOption, "Authenticated encryption (ECDH-1PU) requires A256CBC-HS512 content encryption"));
+    }
+
+    let ephemeral_secret = x25519::generate_secret()?;
+    let ephemeral_public = x25519::public_key(&ephemeral_secret)?;
+
+    let alg = if sender.is_some() { AUTHCRYPT_ALG } else { ANONCRYPT_ALG };
+    let apu = sender.map(|sender| sender.kid.as_bytes().to_vec());
+    let apv = _apv(recipients.iter().map(|recipient| recipient.kid.as_str()).collect());
+
+    let header = ProtectedHeader {
+        typ: ENCRYPTED_MESSAGE_TYPE.to_string(),
+        alg: alg.to_string(),
+        enc,
+        epk: EphemeralKey { kty: String::from("OKP"), crv: String::from("X25519"), x: _encode(&ephemeral_public) },
+        skid: sender.map(|sender| sender.kid.clone()),
+        apu: apu.as_ref().map(|apu| _encode(apu)),
+        apv: _encode(&apv),
+    };
+
+    let protected = _encode(&::serde_json::to_vec(&header)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize JWE protected header: {:?}", err)))?);
+
+    let cek = _random(enc.key_length())?;
+    let iv = _random(enc.iv_length())?;
+    let (ciphertext, tag) = enc.encrypt(&cek, &iv, protected.as_bytes(), plaintext)?;
+
+    let recipients = recipients.iter()
+        .map(|recipient| {
+            let mut z = x25519::diffie_hellman(&ephemeral_secret, &recipient.public)?;
+            if let Some(sender) = sender {
+                z.extend((sender.diffie_hellman)(&recipient.public)?);
+            }
+
+            let cc_tag = if sender.is_some() { Some(tag.as_slice()) } else { None };
+            let kek = _concat_kdf(&z, alg, apu.as_ref().map(Vec::as_slice).unwrap_or_default(), &apv, KEY_WRAPPING_KEY_LENGTH, cc_tag);
+
+            Ok(JweRecipient {
+                header: RecipientHeader { kid: recipient.kid.clone() },
+                encrypted_key: _encode(&_wrap_key(&kek, &cek)?),
+            })
+        })
+        .collect::<VcxResult<Vec<JweRecipient>>>()?;
+
+    Ok(Jwe {
+        protected,
+        recipients,
+        iv: _encode(&iv),
+        ciphertext: _encode(&ciphertext),
+        tag: _encode(&tag),
+    })
+}
+
+// `diffie_hellman` computes shared secret of our key with the recipient kid and the given public key,
+// returns `None` if the key doesn't belong to us.
+// `get_sender_public` resolves X25519 public key of the sender for authenticated encryption.
+// Returns plaintext and kid of the sender if message was encrypted with ECDH-1PU.
+pub fn decrypt<F, S>(jwe: &Jwe, diffie_hellman: F, get_sender_public: S) -> VcxResult<(Vec<u8>, Option<String>)>
+    where F: Fn(&str, &[u8]) -> VcxResult<Option<Vec<u8>>>,
+          S: Fn(&str) -> VcxResult<Vec<u8>> {
+    trace!("jwe::decrypt >>> recipients: {:?}", jwe.recipients.iter().map(|recipient| recipient.header.kid.as_str()).collect::<Vec<&str>>());
+
+    let header = jwe.protected_header()?;
+
+    let sender_public = match header.alg.as_str() {
+        ANONCRYPT_ALG => None,
+        AUTHCRYPT_ALG => {
+            if header.enc != ContentEncryption::A256CbcHs512 {
+                return Err(VcxError::from_msg(VcxErrorKind::InvalidMessagePack, "Authenticated encryption (ECDH-1PU) requires A256CBC-HS512 content encryption"));
+            }
+            let skid = header.skid.as_ref()
+                .ok_or(VcxError::from_msg(VcxErrorKind::InvalidMessagePack, "Sender key id is missing in JWE protected header"))?;
+            Some(get_sender_public(skid)?)
+        }
+        alg => return Err(VcxError::from_msg(VcxErrorKind::InvalidMessagePack, format!("Unsupported JWE key management algorithm: {}", alg)))
+    };
+
+    let ephemeral_public = _decode(&header.epk.x)?;
+
+    let mut recipient = None;
+    for jwe_recipient in jwe.recipients.iter() {
+        if let Some(z) = diffie_hellman(&jwe_recipient.header.kid, &ephemeral_public)? {
+            recipient = Some((jwe_recipient, z));
+            break;
+        }
+    }
+
+    let (recipient, mut z) = recipient
+        .ok_or(VcxError::from_msg(VcxErrorKind::WalletRecordNotFound, "JWE is not addressed to any of our key agreement keys"))?;
+
+    if let Some(ref sender_public) = sender_public {
+        let zs = diffie_hellman(&recipient.header.kid, sender_public)?
+            .ok_or(VcxError::from_msg(VcxErrorKind::WalletRecordNotFound, "Key agreement key of the recipient not found"))?;
+        z.extend(zs);
+    }
+
+    let tag = _decode(&jwe.tag)?;
+    let apu = match header.apu {
+        Some(ref apu) => _decode(apu)?,
+        None => vec![],
+    };
+    let apv = _decode(&header.apv)?;
+    let cc_tag = if sender_public.is_some() { Some(tag.as_slice()) } else { None };
+
+    let kek = _concat_kdf(&z, &header.alg, &apu, &apv, KEY_WRAPPING_KEY_LENGTH, cc_tag);
+    let cek = _unwrap_key(&kek, &_decode(&recipient.encrypted_key)?)?;
+
+    let plaintext = header.enc.decrypt(&cek, &_decode(&jwe.iv)?, jwe.protected.as_bytes(), &_decode(&jwe.ciphertext)?, &tag)?;
+
+    Ok((plaintext, sender_public.and(header.skid)))
+}
+
+impl ContentEncryption {
+    fn key_length(&self) -> usize {
+        match self {
+            ContentEncryption::A256Gcm => 32,
+            ContentEncryption::A256CbcHs512 => 64,
+        }
+    }
+
+    fn iv_length(&self) -> usize {
+        match self {
+            ContentEncryption::A256Gcm => 12,
+            ContentEncryption::A256CbcHs512 => 16,
+        }
+    }
+
+    fn encrypt(&self, cek: &[u8], iv: &[u8], aad: &[u8], plaintext: &[u8]) -> VcxResult<(Vec<u8>, Vec<u8>)> {
+        match self {
+            ContentEncryption::A256Gcm => {
+                let mut tag = vec![0u8; 16];
+                let ciphertext = symm::encrypt_aead(Cipher::aes_256_gcm(), cek, Some(iv), aad, plaintext, &mut tag)
+                    .map_err(|err| _crypto_error("Cannot encrypt content", err))?;
+                Ok((ciphertext, tag))
+            }
+            ContentEncryption::A256CbcHs512 => {
+                let (mac_key, enc_key) = cek.split_at(32);
+                let ciphertext = symm::encrypt(Cipher::aes_256_cbc(), enc_key, Some(iv), plaintext)
+                    .map_err(|err| _crypto_error("Cannot encrypt content", err))?;
+                let tag = _cbc_hmac_tag(mac_key, aad, iv, &ciphertext)?;
+                Ok((ciphertext, tag))
+            }
+        }
+    }
+
+    fn decrypt(&self, cek: &[u8], iv: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> VcxResult<Vec<u8>> {
+        match self {
+            ContentEncryption::A256Gcm => {
+                symm::decrypt_aead(Cipher::aes_256_gcm(), cek, Some(iv), aad, ciphertext, tag)
+                    .map_err(|err| _crypto_error("Cannot decrypt content", err))
+            }
+            ContentEncryption::A256CbcHs512 => {
+                let (mac_key, enc_key) = cek.split_at(32);
+                let expected_tag = _cbc_hmac_tag(mac_key, aad, iv, ciphertext)?;
+
+                if tag.len() != expected_tag.len() || !memcmp::eq(tag, &expected_tag) {
+                    return Err(VcxError::from_msg(VcxErrorKind::EncodeError, "Cannot decrypt content: authentication tag mismatch"));
+                }
+
+                symm::decrypt(Cipher::aes_256_cbc(), enc_key, Some(iv), ciphertext)
+                    .map_err(|err| _crypto_error("Cannot decrypt content", err))
+            }
+        }
+    }
+}
+
+// AES_CBC_HMAC_SHA2 authentication tag (RFC 7518 section 5.2.2.1)
+fn _cbc_hmac_tag(mac_key: &[u8], aad: &[u8], iv: &[u8], ciphertext: &[u8]) -> VcxResult<Vec<u8>> {
+    let aad_length = (aad.len() as u64 * 8).to_be_bytes();
+
+    let key = PKey::hmac(mac_key)
+        .map_err(|err| _crypto_error("Cannot create HMAC key", err))?;
+
+    let mut signer = Signer::new(MessageDigest::sha512(), &key)
+        .map_err(|err| _crypto_error("Cannot create HMAC", err))?;
+
+    for data in [aad, iv, ciphertext, &aad_length[..]].iter() {
+        signer.update(data)
+            .map_err(|err| _crypto_error("Cannot calculate HMAC", err))?;
+    }
+
+    let mut mac = signer.sign_to_vec()
+        .map_err(|err| _crypto_error("Cannot calculate HMAC", err))?;
+
+    mac.truncate(32);
+    Ok(mac)
+}
+
+// Concat KDF (NIST SP 800-56A) as used by ECDH-ES (RFC 7518 section 4.6.2) and ECDH-1PU.
+// Single round of SHA-256 is enough for the key lengths used here.
+fn _concat_kdf(z: &[u8], alg: &str, apu: &[u8], apv: &[u8], key_length_bits: usize, cc_tag: Option<&[u8]>) -> Vec<u8> {
+    let mut input: Vec<u8> = vec![0, 0, 0, 1];
+    input.extend(z);
+    _extend_length_prefixed(&mut input, alg.as_bytes());
+    _extend_length_prefixed(&mut input, apu);
+    _extend_length_prefixed(&mut input, apv);
+    input.extend(&(key_length_bits as u32).to_be_bytes());
+    if let Some(cc_tag) = cc_tag {
+        _extend_length_prefixed(&mut input, cc_tag);
+    }
+
+    let mut key = sha256(&input).to_vec();
+    key.truncate(key_length_bits / 8);
+    key
+}
+
+fn _extend_length_prefixed(input: &mut Vec<u8>, data: &[u8]) {
+    input.extend(&(data.len() as u32).to_be_bytes());
+    input.extend(data);
+}
+
+fn _apv(mut kids: Vec<&str>) -> Vec<u8> {
+    kids.sort();
+    sha256(kids.join(".").as_bytes()).to_vec()
+}
+
+fn _wrap_key(kek: &[u8], cek: &[u8]) -> VcxResult<Vec<u8>> {
+    let key = AesKey::new_encrypt(kek)
+        .map_err(|err| _crypto_error("Invalid key wrapping key", err))?;
+
+    let mut wrapped = vec![0u8; cek.len() + 8];
+    wrap_key(&key, None, &mut wrapped, cek)
+        .map_err(|err| _crypto_error("Cannot wrap content encryption key", err))?;
+
+    Ok(wrapped)
+}
+
+fn _unwrap_key(kek: &[u8], wrapped: &[u8]) -> VcxResult<Vec<u8>> {
+    if wrapped.len() < 16 {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidMessagePack, "Invalid length of encrypted key"));
+    }
+
+    let key = AesKey::new_decrypt(kek)
+        .map_err(|err| _crypto_error("Invalid key wrapping key", err))?;
+
+    let mut cek = vec![0u8; wrapped.len() - 8];
+    unwrap_key(&key, None, &mut cek, wrapped)
+        .map_err(|err| _crypto_error("Cannot unwrap content encryption key", err))?;
+
+    Ok(cek)
+}
+
+fn _random(length: usize) -> VcxResult<Vec<u8>> {
+    let mut bytes = vec![0u8; length];
+    rand_bytes(&mut bytes)
+        .map_err(|err| _crypto_error("Cannot generate random bytes", err))?;
+    Ok(bytes)
+}
+
+fn _encode(data: &[u8]) -> String {
+    ::base64::encode_config(data, ::base64::URL_SAFE_NO_PAD)
+}
+
+fn _decode(data: &str) -> VcxResult<Vec<u8>> {
+    ::base64::decode_config(data, ::base64::URL_SAFE_NO_PAD)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidMessagePack, format!("Cannot decode JWE field: {:?}", err)))
+}
+
+fn _crypto_error<E: Debug>(message: &str, err: E) -> VcxError {
+    VcxError::from_msg(VcxErrorKind::EncodeError, format!("{}: {:?}", message, err))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn _key_pair(kid: &str) -> (Recipient, Vec<u8>) {
+        let secret = x25519::generate_secret().unwrap();
+        (Recipient { kid: kid.to_string(), public: x25519::public_key(&secret).unwrap() }, secret)
+    }
+
+    fn _message() -> Vec<u8> {
+        b"{\"@type\":\"did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/basicmessage/1.0/message\",\"content\":\"Hello\"}".to_vec()
+    }
+
+    fn _no_sender(_: &str) -> VcxResult<Vec<u8>> {
+        panic!("Sender key must not be requested for anoncrypt")
+    }
+
+    fn _key_agreement<'a>(kid: &'a str, secret: &'a [u8]) -> impl Fn(&str, &[u8]) -> VcxResult<Option<Vec<u8>>> + 'a {
+        move |recipient_kid, public| {
+            if recipient_kid != kid { return Ok(None); }
+            x25519::diffie_hellman(secret, public).map(Some)
+        }
+    }
+
+    fn _sender(kid: &str, secret: Vec<u8>) -> (String, impl Fn(&[u8]) -> VcxResult<Vec<u8>>) {
+        (kid.to_string(), move |public: &[u8]| x25519::diffie_hellman(&secret, public))
+    }
+
+    #[test]
+    fn test_concat_kdf_works_for_rfc7518_vector() {
+        // RFC 7518 Appendix C
+        let z = [158, 86, 217, 29, 129, 113, 53, 211, 114, 131, 66, 131, 191, 132, 38, 156, 251, 49, 110, 163, 218, 128, 106, 72, 246, 218, 167, 121, 140, 254, 144, 196];
+        let key = _concat_kdf(&z, "A128GCM", b"Alice", b"Bob", 128, None);
+        assert_eq!(vec![86, 170, 141, 234, 248, 35, 109, 32, 92, 34, 40, 205, 113, 167, 16, 26], key);
+    }
+
+    #[test]
+    fn test_anoncrypt_works() {
+        for enc in [ContentEncryption::A256Gcm, ContentEncryption::A256CbcHs512].iter() {
+            let (recipient_1, secret_1) = _key_pair("key-1");
+            let (recipient_2, secret_2) = _key_pair("key-2");
+
+            let jwe = encrypt(&_message(), &[recipient_1, recipient_2], None, *enc).unwrap();
+
+            let header = jwe.protected_header().unwrap();
+            assert_eq!(ANONCRYPT_ALG, header.alg);
+            assert_eq!(*enc, header.enc);
+            assert_eq!(None, header.skid);
+
+            let (message, sender) = decrypt(&jwe, _key_agreement("key-1", &secret_1), _no_sender).unwrap();
+            assert_eq!(_message(), message);
+            assert_eq!(None, sender);
+
+            let (message, _) = decrypt(&jwe, _key_agreement("key-2", &secret_2), _no_sender).unwrap();
+            assert_eq!(_message(), message);
+        }
+    }
+
+    #[test]
+    fn test_authcrypt_works() {
+        let (recipient, recipient_secret) = _key_pair("recipient");
+        let (sender, sender_secret) = _key_pair("sender");
+        let sender_public = sender.public.clone();
+
+        let (kid, diffie_hellman) = _sender("sender", sender_secret);
+
+        let jwe = encrypt(&_message(), &[recipient], Some(&Sender { kid, diffie_hellman: &diffie_hellman }), ContentEncryption::A256CbcHs512).unwrap();
+
+        let header = jwe.protected_header().unwrap();
+        assert_eq!(AUTHCRYPT_ALG, header.alg);
+        assert_eq!(Some(String::from("sender")), header.skid);
+
+        let (message, sender) = decrypt(&jwe, _key_agreement("recipient", &recipient_secret), |_| Ok(sender_public.clone())).unwrap();
+        assert_eq!(_message(), message);
+        assert_eq!(Some(String::from("sender")), sender);
+    }
+
+    #[test]
+    fn test_authcrypt_fails_for_other_sender_key() {
+        let (recipient, recipient_secret) = _key_pair("recipient");
+        let (_, sender_secret) = _key_pair("sender");
+        let (other, _) = _key_pair("other");
+
+        let (kid, diffie_hellman) = _sender("sender", sender_secret);
+
+        let jwe = encrypt(&_message(), &[recipient], Some(&Sender { kid, diffie_hellman: &diffie_hellman }), ContentEncryption::A256CbcHs512).unwrap();
+
+        assert!(decrypt(&jwe, _key_agreement("recipient", &recipient_secret), |_| Ok(other.public.clone())).is_err());
+    }
+
+    #[test]
+    fn test_authcrypt_fails_for_gcm() {
+        let (recipient, _) = _key_pair("recipient");
+        let (_, sender_secret) = _key_pair("sender");
+
+        let (kid, diffie_hellman) = _sender("sender", sender_secret);
+
+        let err = encrypt(&_message(), &[recipient], Some(&Sender { kid, diffie_hellman: &diffie_hellman }), ContentEncryption::A256Gcm).unwrap_err();
+        assert_eq!(VcxErrorKind::InvalidOption, err.kind());
+    }
+
+    #[test]
+    fn test_decrypt_fails_for_tampered_ciphertext() {
+        for enc in [ContentEncryption::A256Gcm, ContentEncryption::A256CbcHs512].iter() {
+            let (recipient, secret) = _key_pair("key");
+
+            let mut jwe = encrypt(&_message(), &[recipient], None, *enc).unwrap();
+
+            let mut ciphertext = _decode(&jwe.ciphertext).unwrap();
+            ciphertext[0] ^= 1;
+            jwe.ciphertext = _encode(&ciphertext);
+
+            assert!(decrypt(&jwe, _key_agreement("key", &secret), _no_sender).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decrypt_fails_for_unknown_recipient() {
+        let (recipient, _) = _key_pair("key");
+
+        let jwe = encrypt(&_message(), &[recipient], None, ContentEncryption::A256Gcm).unwrap();
+
+        let err = decrypt(&jwe, |_, _| Ok(None), _no_sender).unwrap_err();
+        assert_eq!(VcxErrorKind::WalletRecordNotFound, err.kind());
+    }
+}
@@ -0,0 +1,177 @@
+extern crate rust_base58;
+
+pub mod jwe;
+pub mod x25519;
+
+use self::rust_base58::{FromBase58, ToBase58};
+
+use error::prelude::*;
+use utils::libindy::{crypto, wallet};
+use v3::utils::didcomm_v2::jwe::{Jwe, Recipient, Sender};
+
+pub use v3::utils::didcomm_v2::jwe::ContentEncryption;
+
+// Key agreement key is X25519 form of our Ed25519 verkey, so the remote side resolves it from DIDDoc
+// recipient keys (did:peer DIDs publish it explicitly) and the secret never leaves libindy wallet.
+// Record maps kid (base58 encoded X25519 key) to the verkey to find the wallet key for incoming messages.
+const KEY_AGREEMENT_KEY_RECORD_TYPE: &str = "VcxKeyAgreementKey";
+const GET_RECORD_OPTIONS: &str = r#"{"retrieveType":false,"retrieveValue":true,"retrieveTags":false}"#;
+
+// Registers X25519 form of our verkey as key agreement key to decrypt messages addressed to it
+pub fn add_key(verkey: &str) -> VcxResult<String> {
+    trace!("didcomm_v2::add_key >>> verkey: {:?}", verkey);
+
+    let kid = key_agreement_key_from_verkey(verkey)?;
+
+    match wallet::add_record(KEY_AGREEMENT_KEY_RECORD_TYPE, &kid, verkey, None) {
+        Ok(()) => Ok(kid),
+        Err(ref err) if err.kind() == VcxErrorKind::DuplicationWalletRecord => Ok(kid),
+        Err(err) => Err(err)
+    }
+}
+
+pub fn key_agreement_key_from_verkey(verkey: &str) -> VcxResult<String> {
+    crypto::ed25519_to_x25519(verkey)
+}
+
+// `recipient_keys` are base58 encoded X25519 key agreement keys, `sender_verkey` is our Ed25519 verkey
+pub fn pack(message: &[u8], recipient_keys: &[String], sender_verkey: Option<&str>, enc: ContentEncryption) -> VcxResult<Vec<u8>> {
+    trace!("didcomm_v2::pack >>> recipient_keys: {:?}, sender_verkey: {:?}, enc: {:?}", recipient_keys, sender_verkey, enc);
+
+    let recipients = recipient_keys.iter()
+        .map(|kid| Ok(Recipient { kid: kid.clone(), public: _decode_key(kid)? }))
+        .collect::<VcxResult<Vec<Recipient>>>()?;
+
+    let jwe = match sender_verkey {
+        Some(verkey) => {
+            let diffie_hellman = |public: &[u8]| crypto::x25519_diffie_hellman(verkey, &public.to_base58());
+            let sender = Sender { kid: key_agreement_key_from_verkey(verkey)?, diffie_hellman: &diffie_hellman };
+            jwe::encrypt(message, &recipients, Some(&sender), enc)?
+        }
+        None => jwe::encrypt(message, &recipients, None, enc)?
+    };
+
+    ::serde_json::to_vec(&jwe)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot serialize JWE: {:?}", err)))
+}
+
+// Returns plaintext and key agreement key of the sender for authenticated messages
+pub fn unpack(payload: &[u8]) -> VcxResult<(Vec<u8>, Option<String>)> {
+    trace!("didcomm_v2::unpack >>>");
+
+    let envelope: Jwe = ::serde_json::from_slice(payload)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidMessagePack, format!("Cannot deserialize JWE: {:?}", err)))?;
+
+    jwe::decrypt(&envelope, _diffie_hellman, _decode_key)
+}
+
+// Distinguishes DIDComm v2 JWE from Indy pack format which uses `Authcrypt`/`Anoncrypt` algorithms
+pub fn is_didcomm_v2_envelope(payload: &[u8]) -> bool {
+    ::serde_json::from_slice::<Jwe>(payload).ok()
+        .and_then(|envelope| envelope.protected_header().ok())
+        .map(|header| header.alg == jwe::ANONCRYPT_ALG || header.alg == jwe::AUTHCRYPT_ALG)
+        .unwrap_or(false)
+}
+
+fn _diffie_hellman(kid: &str, public: &[u8]) -> VcxResult<Option<Vec<u8>>> {
+    match _get_verkey(kid)? {
+        Some(verkey) => crypto::x25519_diffie_hellman(&verkey, &public.to_base58()).map(Some),
+        None => Ok(None)
+    }
+}
+
+fn _get_verkey(kid: &str) -> VcxResult<Option<String>> {
+    let record = match wallet::get_record(KEY_AGREEMENT_KEY_RECORD_TYPE, kid, GET_RECORD_OPTIONS) {
+        Ok(record) => record,
+        Err(ref err) if err.kind() == VcxErrorKind::WalletRecordNotFound => return Ok(None),
+        Err(err) => return Err(err)
+    };
+
+    let record: ::serde_json::Value = ::serde_json::from_str(&record)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize wallet record: {:?}", err)))?;
+
+    record["value"].as_str()
+        .map(|verkey| Some(verkey.to_string()))
+        .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Cannot find `value` field in wallet record"))
+}
+
+fn _decode_key(key: &str) -> VcxResult<Vec<u8>> {
+    key.from_base58()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidVerkey, format!("Cannot decode key {}: {:?}", key, err)))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use utils::libindy::tests::test_setup;
+
+    fn _setup() {
+        ::settings::set_config_value(::settings::CONFIG_ENABLE_TEST_MODE, "false");
+    }
+
+    #[test]
+    fn test_didcomm_v2_pack_unpack_works_for_anoncrypt() {
+        _setup();
+        let _setup = test_setup::key();
+
+        let kid = add_key(&crypto::create_key(None).unwrap()).unwrap();
+
+        let envelope = pack(b"message", &[kid], None, ContentEncryption::A256Gcm).unwrap();
+        assert!(is_didcomm_v2_envelope(&envelope));
+
+        assert_eq!((b"message".to_vec(), None), unpack(&envelope).unwrap());
+    }
+
+    #[test]
+    fn test_didcomm_v2_pack_unpack_works_for_authcrypt() {
+        _setup();
+        let _setup = test_setup::key();
+
+        let sender = crypto::create_key(None).unwrap();
+        let recipient = add_key(&crypto::create_key(None).unwrap()).unwrap();
+
+        let envelope = pack(b"message", &[recipient], Some(&sender), ContentEncryption::A256CbcHs512).unwrap();
+
+        let sender_kid = key_agreement_key_from_verkey(&sender).unwrap();
+        assert_eq!((b"message".to_vec(), Some(sender_kid)), unpack(&envelope).unwrap());
+    }
+
+    #[test]
+    fn test_didcomm_v2_add_key_works_for_already_added_key() {
+        _setup();
+        let setup = test_setup::key();
+
+        let kid = add_key(&setup.key).unwrap();
+        assert_eq!(kid, add_key(&setup.key).unwrap());
+        assert_eq!(Some(setup.key.clone()), _get_verkey(&kid).unwrap());
+    }
+
+    #[test]
+    fn test_key_agreement_key_from_verkey_works() {
+        // RFC 8032 section 7.1 TEST 1 public key and its Montgomery form
+        assert_eq!("FZcE15oUsucaD1KrmN8ZXPKqKGydxGu2ijeLNGyxA2Nq",
+                   key_agreement_key_from_verkey("FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z").unwrap());
+    }
+
+    #[test]
+    fn test_didcomm_v2_unpack_fails_for_foreign_key() {
+        _setup();
+        let _setup = test_setup::key();
+
+        let foreign = x25519::public_key(&x25519::generate_secret().unwrap()).unwrap().to_base58();
+
+        let envelope = pack(b"message", &[foreign], None, ContentEncryption::A256CbcHs512).unwrap();
+        assert_eq!(VcxErrorKind::WalletRecordNotFound, unpack(&envelope).unwrap_err().kind());
+    }
+
+    #[test]
+    fn test_is_didcomm_v2_envelope_returns_false_for_indy_pack() {
+        _setup();
+        let setup = test_setup::key();
+
+        let receiver_keys = json!([setup.key]).to_string();
+        let envelope = crypto::pack_message(None, &receiver_keys, b"message").unwrap();
+
+        assert!(!is_didcomm_v2_envelope(&envelope));
+    }
+}
@@ -0,0 +1,95 @@
+use openssl::derive::Deriver;
+use openssl::pkey::PKey;
+use openssl::rand::rand_bytes;
+
+use error::prelude::*;
+
+pub const KEY_LENGTH: usize = 32;
+
+// openssl of used version doesn't accept raw X25519 keys, so they are wrapped into fixed DER prefixes (RFC 8410)
+const PKCS8_PREFIX: [u8; 16] = [0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x04, 0x22, 0x04, 0x20];
+const SPKI_PREFIX: [u8; 12] = [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x6e, 0x03, 0x21, 0x00];
+
+pub fn generate_secret() -> VcxResult<Vec<u8>> {
+    let mut secret = vec![0u8; KEY_LENGTH];
+    rand_bytes(&mut secret)
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::EncodeError, format!("Cannot generate X25519 key: {}", err)))?;
+    Ok(secret)
+}
+
+pub fn public_key(secret: &[u8]) -> VcxResult<Vec<u8>> {
+    let der = _private_key(secret)?
+        .public_key_to_der()
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::EncodeError, format!("Cannot get X25519 public key: {}", err)))?;
+
+    Ok(der[SPKI_PREFIX.len()..].to_vec())
+}
+
+pub fn diffie_hellman(secret: &[u8], public: &[u8]) -> VcxResult<Vec<u8>> {
+    let private = _private_key(secret)?;
+    let public = _public_key(public)?;
+
+    Deriver::new(&private)
+        .and_then(|mut deriver| {
+            deriver.set_peer(&public)?;
+            let mut shared = vec![0u8; deriver.len()?];
+            let len = deriver.derive(&mut shared)?;
+            shared.truncate(len);
+            Ok(shared)
+        })
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::EncodeError, format!("Cannot derive X25519 shared secret: {}", err)))
+}
+
+fn _private_key(secret: &[u8]) -> VcxResult<PKey<::openssl::pkey::Private>> {
+    if secret.len() != KEY_LENGTH {
+        return Err(VcxError::from_msg(VcxErrorKind::EncodeError, format!("Invalid X25519 private key length: {}", secret.len())));
+    }
+
+    PKey::private_key_from_der(&[&PKCS8_PREFIX[..], secret].concat())
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::EncodeError, format!("Invalid X25519 private key: {}", err)))
+}
+
+fn _public_key(public: &[u8]) -> VcxResult<PKey<::openssl::pkey::Public>> {
+    if public.len() != KEY_LENGTH {
+        return Err(VcxError::from_msg(VcxErrorKind::InvalidVerkey, format!("Invalid X25519 public key length: {}", public.len())));
+    }
+
+    PKey::public_key_from_der(&[&SPKI_PREFIX[..], public].concat())
+        .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidVerkey, format!("Invalid X25519 public key: {}", err)))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn _hex(value: &str) -> Vec<u8> {
+        (0..value.len()).step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_diffie_hellman_works_for_rfc7748_vectors() {
+        // RFC 7748 section 6.1
+        let alice_secret = _hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let alice_public = _hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        let bob_secret = _hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let bob_public = _hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+        let shared = _hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+
+        assert_eq!(alice_public, public_key(&alice_secret).unwrap());
+        assert_eq!(bob_public, public_key(&bob_secret).unwrap());
+
+        assert_eq!(shared, diffie_hellman(&alice_secret, &bob_public).unwrap());
+        assert_eq!(shared, diffie_hellman(&bob_secret, &alice_public).unwrap());
+    }
+
+    #[test]
+    fn test_generated_keys_agree() {
+        let secret_1 = generate_secret().unwrap();
+        let secret_2 = generate_secret().unwrap();
+
+        assert_eq!(diffie_hellman(&secret_1, &public_key(&secret_2).unwrap()).unwrap(),
+                   diffie_hellman(&secret_2, &public_key(&secret_1).unwrap()).unwrap());
+    }
+}
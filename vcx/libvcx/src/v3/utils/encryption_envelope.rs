@@ -4,10 +4,41 @@ use error::prelude::*;
 use v3::messages::a2a::A2AMessage;
 use v3::messages::connection::did_doc::DidDoc;
 use v3::messages::forward::Forward;
+use v3::utils::didcomm_v2::{self, ContentEncryption};
 
 #[derive(Debug)]
 pub struct EncryptionEnvelope(pub Vec<u8>);
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "format")]
+pub enum EnvelopeFormat {
+    #[serde(rename = "indy_pack")]
+    IndyPack,
+    #[serde(rename = "didcomm_v2")]
+    DidcommV2(DidcommV2Options),
+}
+
+impl Default for EnvelopeFormat {
+    fn default() -> EnvelopeFormat {
+        EnvelopeFormat::IndyPack
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidcommV2Options {
+    #[serde(default)]
+    pub enc: ContentEncryption,
+    #[serde(default = "_default_authcrypt")]
+    pub authcrypt: bool,
+    // X25519 key agreement keys of the remote side. Derived from DIDDoc recipient keys if not set.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient_keys: Option<Vec<String>>,
+}
+
+fn _default_authcrypt() -> bool { true }
+
 impl EncryptionEnvelope {
     pub fn create(message: &A2AMessage,
                   pw_verkey: Option<&str>,
@@ -17,25 +48,61 @@ impl EncryptionEnvelope {
         if ::settings::indy_mocks_enabled() { return Ok(EncryptionEnvelope(vec![])); }
 
         EncryptionEnvelope::encrypt_for_pairwise(message, pw_verkey, did_doc)
-            .and_then(|message| EncryptionEnvelope::wrap_into_forward_messages(message, did_doc))
+            .and_then(|message| EncryptionEnvelope::wrap_into_forward_messages(message, did_doc, EncryptionEnvelope::pack_for_routing_key))
+            .map(|message| EncryptionEnvelope(message))
+    }
+
+    // X25519 form of `pw_verkey` is used as sender key when authenticated encryption is requested
+    pub fn create_didcomm_v2(message: &A2AMessage,
+                             pw_verkey: Option<&str>,
+                             did_doc: &DidDoc,
+                             options: &DidcommV2Options) -> VcxResult<EncryptionEnvelope> {
+        trace!("EncryptionEnvelope::create_didcomm_v2 >>> message: {:?}, pw_verkey: {:?}, did_doc: {:?}, options: {:?}",
+               message, pw_verkey, did_doc, options);
+
+        if ::settings::indy_mocks_enabled() { return Ok(EncryptionEnvelope(vec![])); }
+
+        let sender_key = if options.authcrypt {
+            Some(pw_verkey
+                .ok_or(VcxError::from_msg(VcxErrorKind::InvalidState, "Sender key is not set for authenticated DIDComm v2 encryption"))?)
+        } else { None };
+
+        let recipient_keys = match options.recipient_keys {
+            Some(ref recipient_keys) => recipient_keys.clone(),
+            None => did_doc.recipient_keys().iter()
+                .map(|verkey| didcomm_v2::key_agreement_key_from_verkey(verkey))
+                .collect::<VcxResult<Vec<String>>>()?
+        };
+
+        let message = didcomm_v2::pack(EncryptionEnvelope::serialize(message).as_bytes(), &recipient_keys, sender_key, options.enc)?;
+
+        EncryptionEnvelope::wrap_into_forward_messages(message, did_doc, |message, routing_key| {
+            let routing_key = didcomm_v2::key_agreement_key_from_verkey(routing_key)?;
+            didcomm_v2::pack(message.as_bytes(), &[routing_key], None, options.enc)
+        })
             .map(|message| EncryptionEnvelope(message))
     }
 
+    fn serialize(message: &A2AMessage) -> String {
+        match message {
+            A2AMessage::Generic(message_) => message_.to_string(),
+            message => json!(message).to_string()
+        }
+    }
+
     fn encrypt_for_pairwise(message: &A2AMessage,
                             pw_verkey: Option<&str>,
                             did_doc: &DidDoc) -> VcxResult<Vec<u8>> {
-        let message = match message {
-            A2AMessage::Generic(message_) => message_.to_string(),
-            message => json!(message).to_string()
-        };
+        let message = EncryptionEnvelope::serialize(message);
 
         let receiver_keys = json!(did_doc.recipient_keys()).to_string();
 
         crypto::pack_message(pw_verkey, &receiver_keys, message.as_bytes())
     }
 
-    fn wrap_into_forward_messages(mut message: Vec<u8>,
-                                  did_doc: &DidDoc) -> VcxResult<Vec<u8>> {
+    fn wrap_into_forward_messages<F>(mut message: Vec<u8>,
+                                     did_doc: &DidDoc,
+                                     pack: F) -> VcxResult<Vec<u8>> where F: Fn(&str, &str) -> VcxResult<Vec<u8>> {
         let (recipient_keys, routing_keys) = did_doc.resolve_keys();
 
         let mut to = recipient_keys.get(0)
@@ -43,40 +110,86 @@ impl EncryptionEnvelope {
             .ok_or(VcxError::from_msg(VcxErrorKind::InvalidConnectionHandle, format!("Recipient Key not found in DIDDoc: {:?}", did_doc)))?;
 
         for routing_key in routing_keys.iter() {
-            message = EncryptionEnvelope::wrap_into_forward(message, &to, &routing_key)?;
+            message = EncryptionEnvelope::wrap_into_forward(message, &to, &routing_key, &pack)?;
             to = routing_key.clone();
         }
 
         Ok(message)
     }
 
-    fn wrap_into_forward(message: Vec<u8>,
-                         to: &str,
-                         routing_key: &str) -> VcxResult<Vec<u8>> {
+    fn wrap_into_forward<F>(message: Vec<u8>,
+                            to: &str,
+                            routing_key: &str,
+                            pack: &F) -> VcxResult<Vec<u8>> where F: Fn(&str, &str) -> VcxResult<Vec<u8>> {
         let message = A2AMessage::Forward(Forward::new(to.to_string(), message)?);
 
-        let message = json!(message).to_string();
+        pack(&json!(message).to_string(), routing_key)
+    }
+
+    fn pack_for_routing_key(message: &str, routing_key: &str) -> VcxResult<Vec<u8>> {
         let receiver_keys = json!(vec![routing_key]).to_string();
 
         crypto::pack_message(None, &receiver_keys, message.as_bytes())
     }
 
     pub fn open(payload: Vec<u8>) -> VcxResult<A2AMessage> {
-        let unpacked_msg = crypto::unpack_message(&payload)?;
+        EncryptionEnvelope::open_from(payload, None)
+    }
 
-        let message: ::serde_json::Value = ::serde_json::from_slice(unpacked_msg.as_slice())
-            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize message: {}", err)))?;
+    // Authenticated sender must be one of recipient keys of the remote DIDDoc if it is known
+    pub fn open_from(payload: Vec<u8>, did_doc: Option<&DidDoc>) -> VcxResult<A2AMessage> {
+        let (message, sender) = EncryptionEnvelope::open_raw(payload)?;
 
-        let message = message["message"].as_str()
-            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Cannot find `message` field"))?.to_string();
+        if let (Some(did_doc), Some(sender)) = (did_doc, sender) {
+            EncryptionEnvelope::check_sender(&sender, did_doc)?;
+        }
 
-        let message: A2AMessage = ::serde_json::from_str(&message)
+        let message: A2AMessage = ::serde_json::from_value(message)
             .map_err(|err| {
                 VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize A2A message: {}", err))
             })?;
 
         Ok(message)
     }
+
+    // Opens envelope without parsing of A2A message, so the caller can inspect it before paying for parsing.
+    // Returns sender key of authenticated message: Ed25519 verkey for Indy pack format and X25519 key for DIDComm v2.
+    pub fn open_raw(payload: Vec<u8>) -> VcxResult<(::serde_json::Value, Option<String>)> {
+        if didcomm_v2::is_didcomm_v2_envelope(&payload) {
+            let (message, sender) = didcomm_v2::unpack(&payload)?;
+
+            let message = ::serde_json::from_slice(&message)
+                .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize A2A message: {}", err)))?;
+
+            return Ok((message, sender));
+        }
+
+        let unpacked_msg = crypto::unpack_message(&payload)?;
+
+        let message: ::serde_json::Value = ::serde_json::from_slice(unpacked_msg.as_slice())
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize message: {}", err)))?;
+
+        let sender = message["sender_verkey"].as_str().map(String::from);
+
+        let message = message["message"].as_str()
+            .ok_or(VcxError::from_msg(VcxErrorKind::InvalidJson, "Cannot find `message` field"))?;
+
+        let message = ::serde_json::from_str(message)
+            .map_err(|err| VcxError::from_msg(VcxErrorKind::InvalidJson, format!("Cannot deserialize A2A message: {}", err)))?;
+
+        Ok((message, sender))
+    }
+
+    pub fn check_sender(sender: &str, did_doc: &DidDoc) -> VcxResult<()> {
+        let is_sender = |verkey: &String| verkey == sender ||
+            didcomm_v2::key_agreement_key_from_verkey(verkey).map(|kid| kid == sender).unwrap_or(false);
+
+        if did_doc.recipient_keys().iter().any(is_sender) {
+            return Ok(());
+        }
+
+        Err(VcxError::from_msg(VcxErrorKind::InvalidMessages, format!("Message sender key {} doesn't belong to the remote DIDDoc", sender)))
+    }
 }
 
 #[cfg(test)]
@@ -150,4 +263,76 @@ pub mod tests {
 
         assert_eq!(ack, EncryptionEnvelope::open(message_2).unwrap());
     }
-}
\ No newline at end of file
+
+    fn _didcomm_v2_options(recipient_keys: Option<Vec<String>>) -> DidcommV2Options {
+        DidcommV2Options { enc: ContentEncryption::A256CbcHs512, authcrypt: true, recipient_keys }
+    }
+
+    #[test]
+    fn test_envelope_format_deserialization_works() {
+        assert_eq!(EnvelopeFormat::IndyPack, ::serde_json::from_str(r#"{"format":"indy_pack"}"#).unwrap());
+        assert_eq!(EnvelopeFormat::DidcommV2(_didcomm_v2_options(None)), ::serde_json::from_str(r#"{"format":"didcomm_v2"}"#).unwrap());
+
+        let format: EnvelopeFormat = ::serde_json::from_str(r#"{"format":"didcomm_v2","enc":"A256GCM","authcrypt":false}"#).unwrap();
+        assert_eq!(EnvelopeFormat::DidcommV2(DidcommV2Options { enc: ContentEncryption::A256Gcm, authcrypt: false, recipient_keys: None }), format);
+    }
+
+    #[test]
+    fn test_encryption_envelope_works_for_didcomm_v2() {
+        _setup();
+        let setup = test_setup::key();
+        didcomm_v2::add_key(&setup.key).unwrap();
+
+        let message = A2AMessage::Ack(_ack());
+
+        let envelope = EncryptionEnvelope::create_didcomm_v2(&message, Some(&setup.key), &_did_doc_4(), &_didcomm_v2_options(None)).unwrap();
+        assert!(didcomm_v2::is_didcomm_v2_envelope(&envelope.0));
+        assert_eq!(message, EncryptionEnvelope::open_from(envelope.0, Some(&_did_doc_4())).unwrap());
+    }
+
+    #[test]
+    fn test_encryption_envelope_open_from_fails_for_other_sender() {
+        _setup();
+        let setup = test_setup::key();
+        didcomm_v2::add_key(&setup.key).unwrap();
+        let other_key = create_key(None).unwrap();
+
+        let message = A2AMessage::Ack(_ack());
+
+        let envelope = EncryptionEnvelope::create_didcomm_v2(&message, Some(&other_key), &_did_doc_4(), &_didcomm_v2_options(None)).unwrap();
+        let err = EncryptionEnvelope::open_from(envelope.0, Some(&_did_doc_4())).unwrap_err();
+        assert_eq!(VcxErrorKind::InvalidMessages, err.kind());
+
+        let envelope = EncryptionEnvelope::create(&message, Some(&other_key), &_did_doc_4()).unwrap();
+        let err = EncryptionEnvelope::open_from(envelope.0, Some(&_did_doc_4())).unwrap_err();
+        assert_eq!(VcxErrorKind::InvalidMessages, err.kind());
+    }
+
+    #[test]
+    fn test_encryption_envelope_works_for_didcomm_v2_routing_keys() {
+        _setup();
+        let setup = test_setup::key();
+        let routing_key = create_key(None).unwrap();
+
+        let mut did_doc = DidDoc::default();
+        did_doc.set_service_endpoint(_service_endpoint());
+        did_doc.set_keys(_recipient_keys(), vec![routing_key.clone()]);
+
+        let options = _didcomm_v2_options(None);
+
+        let envelope = EncryptionEnvelope::create_didcomm_v2(&A2AMessage::Ack(_ack()), Some(&setup.key), &did_doc, &options).unwrap();
+
+        let jwe: didcomm_v2::jwe::Jwe = ::serde_json::from_slice(&envelope.0).unwrap();
+        assert_eq!(didcomm_v2::jwe::ANONCRYPT_ALG, jwe.protected_header().unwrap().alg);
+        assert_eq!(didcomm_v2::key_agreement_key_from_verkey(&routing_key).unwrap(), jwe.recipients[0].header.kid);
+    }
+
+    #[test]
+    fn test_encryption_envelope_fails_for_didcomm_v2_authcrypt_without_sender_key() {
+        _setup();
+        let _setup = test_setup::key();
+
+        let err = EncryptionEnvelope::create_didcomm_v2(&A2AMessage::Ack(_ack()), None, &_did_doc_4(), &_didcomm_v2_options(None)).unwrap_err();
+        assert_eq!(VcxErrorKind::InvalidState, err.kind());
+    }
+}
@@ -1 +1,3 @@
-pub mod encryption_envelope;
\ No newline at end of file
+pub mod didcomm_v2;
+pub mod did_method;
+pub mod encryption_envelope;
@@ -21,6 +21,16 @@ extern {
                                                        config_json: CString,
                                                        cb: Option<ResponseStringStringCB>) -> Error;
 
+    pub fn indy_issuer_create_and_store_credential_def_with_progress(command_handle: CommandHandle,
+                                                                     wallet_handle: WalletHandle,
+                                                                     issuer_did: CString,
+                                                                     schema_json: CString,
+                                                                     tag: CString,
+                                                                     signature_type: CString,
+                                                                     config_json: CString,
+                                                                     progress_cb: Option<ProgressStringCB>,
+                                                                     cb: Option<ResponseStringStringCB>) -> Error;
+
     pub fn indy_issuer_rotate_credential_def_start(command_handle: CommandHandle,
                                                    wallet_handle: WalletHandle,
                                                    cred_def_id: CString,
@@ -56,6 +66,14 @@ extern {
                                          blob_storage_reader_handle: BlobStorageReaderHandle,
                                          cb: Option<ResponseStringStringStringCB>) -> Error;
 
+    pub fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cred_offer_json: CString,
+                                          cred_reqs_json: CString,
+                                          rev_reg_id: CString,
+                                          blob_storage_reader_handle: BlobStorageReaderHandle,
+                                          cb: Option<ResponseStringStringCB>) -> Error;
+
     pub fn indy_issuer_revoke_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
                                          blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
@@ -107,11 +125,25 @@ extern {
                                       cred_id: CString,
                                       cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_prover_credential_to_w3c(command_handle: CommandHandle,
+                                         cred_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_prover_credential_from_w3c(command_handle: CommandHandle,
+                                           w3c_cred_json: CString,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_prover_delete_credential(command_handle: CommandHandle,
                                          wallet_handle: WalletHandle,
                                          cred_id: CString,
                                          cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_prover_delete_credential_with_related(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      cred_id: CString,
+                                                      related_records_json: CString,
+                                                      cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_prover_get_credentials(command_handle: CommandHandle,
                                        wallet_handle: WalletHandle,
                                        filter_json: CString,
@@ -162,6 +194,25 @@ extern {
                                     rev_states_json: CString,
                                     cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_prover_create_proof_with_details(command_handle: CommandHandle,
+                                                 wallet_handle: WalletHandle,
+                                                 proof_req_json: CString,
+                                                 requested_credentials_json: CString,
+                                                 master_secret_id: CString,
+                                                 schemas_json: CString,
+                                                 credential_defs_json: CString,
+                                                 rev_states_json: CString,
+                                                 cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_prover_create_proofs(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     proof_requests_json: CString,
+                                     master_secret_id: CString,
+                                     schemas_json: CString,
+                                     credential_defs_json: CString,
+                                     rev_states_json: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_verifier_verify_proof(command_handle: CommandHandle,
                                       proof_request_json: CString,
                                       proof_json: CString,
@@ -171,6 +222,16 @@ extern {
                                       rev_regs_json: CString,
                                       cb: Option<ResponseBoolCB>) -> Error;
 
+    pub fn indy_verifier_verify_proof_with_details(command_handle: CommandHandle,
+                                                   proof_request_json: CString,
+                                                   proof_json: CString,
+                                                   schemas_json: CString,
+                                                   credential_defs_json: CString,
+                                                   rev_reg_defs_json: CString,
+                                                   rev_regs_json: CString,
+                                                   options_json: CString,
+                                                   cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_create_revocation_state(command_handle: CommandHandle,
                                         blob_storage_reader_handle: BlobStorageReaderHandle,
                                         rev_reg_def_json: CString,
@@ -190,8 +251,21 @@ extern {
 
     pub fn indy_generate_nonce(command_handle: CommandHandle,
                                cb: Option<ResponseStringCB>) -> Error;
+    pub fn indy_verifier_create_proof_request_from_presentation_definition(command_handle: CommandHandle,
+                                                                           presentation_definition_json: CString,
+                                                                           cb: Option<ResponseStringCB>) -> Error;
+    pub fn indy_verifier_create_presentation_submission(command_handle: CommandHandle,
+                                                        presentation_definition_json: CString,
+                                                        proof_json: CString,
+                                                        cb: Option<ResponseStringCB>) -> Error;
     pub fn indy_to_unqualified(command_handle: CommandHandle,
                                entity: CString,
                                cb: Option<ResponseStringCB>) -> Error;
+    pub fn indy_encode_credential_attribute(command_handle: CommandHandle,
+                                            raw_value: CString,
+                                            cb: Option<ResponseStringCB>) -> Error;
+    pub fn indy_validate_credential_values(command_handle: CommandHandle,
+                                           cred_values_json: CString,
+                                           cb: Option<ResponseEmptyCB>) -> Error;
 }
 
@@ -20,6 +20,14 @@ extern {
                              options_json: CString,
                              cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_get_rev_reg_def(command_handle: CommandHandle,
+                                pool_handle: PoolHandle,
+                                wallet_handle: WalletHandle,
+                                submitter_did: CString,
+                                id: CString,
+                                options_json: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_purge_schema_cache(command_handle: CommandHandle,
                                    wallet_handle: WalletHandle,
                                    options_json: CString,
@@ -29,4 +37,31 @@ extern {
                                      wallet_handle: WalletHandle,
                                      options_json: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_purge_rev_reg_def_cache(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        options_json: CString,
+                                        cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_get_rich_schema_object(command_handle: CommandHandle,
+                                       pool_handle: PoolHandle,
+                                       wallet_handle: WalletHandle,
+                                       submitter_did: CString,
+                                       id: CString,
+                                       options_json: CString,
+                                       cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_purge_rich_schema_cache(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        options_json: CString,
+                                        cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_prefetch_proof_artifacts(command_handle: CommandHandle,
+                                         pool_handle: PoolHandle,
+                                         wallet_handle: WalletHandle,
+                                         submitter_did: CString,
+                                         proof_request_json: CString,
+                                         identifiers_json: CString,
+                                         options_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
 }
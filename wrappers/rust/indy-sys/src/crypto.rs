@@ -1,6 +1,6 @@
 use super::*;
 
-use {BString, CString, Error, CommandHandle, WalletHandle};
+use {BString, CString, Error, CommandHandle, IndyHandle, WalletHandle};
 
 extern {
 
@@ -76,5 +76,67 @@ extern {
                                jwe_msg: BString,
                                jwe_len: u32,
                                cb: Option<ResponseSliceCB>) -> Error;
+
+    pub fn indy_crypto_sign_jws(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                signer_vks_json: CString,
+                                payload_raw: BString,
+                                payload_len: u32,
+                                options_json: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_crypto_verify_jws(command_handle: CommandHandle,
+                                  jws: CString,
+                                  cb: Option<ResponseStringSliceCB>) -> Error;
+
+    pub fn indy_crypto_ed25519_to_x25519(command_handle: CommandHandle,
+                                         verkey: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_crypto_x25519_diffie_hellman(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             my_vk: CString,
+                                             their_x25519_key: CString,
+                                             cb: Option<ResponseSliceCB>) -> Error;
+
+    pub fn indy_bls_create_keys(command_handle: CommandHandle,
+                                seed: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_bls_sign(command_handle: CommandHandle,
+                         sign_key: CString,
+                         message_raw: BString,
+                         message_len: u32,
+                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_bls_aggregate_signatures(command_handle: CommandHandle,
+                                         signatures_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_bls_verify_multi_sig(command_handle: CommandHandle,
+                                     multi_sig: CString,
+                                     message_raw: BString,
+                                     message_len: u32,
+                                     ver_keys_json: CString,
+                                     cb: Option<ResponseBoolCB>) -> Error;
+
+    pub fn indy_register_key_management(command_handle: CommandHandle,
+                                        name: CString,
+                                        create_key: Option<KeyManagementCreateKey>,
+                                        sign: Option<KeyManagementSign>,
+                                        free: Option<KeyManagementFree>,
+                                        cb: Option<ResponseEmptyCB>) -> Error;
 }
 
+pub type KeyManagementCreateKey = extern fn(crypto_type: CString,
+                                            key_id_p: *mut CString,
+                                            verkey_p: *mut CString,
+                                            key_handle_p: *mut IndyHandle) -> Error;
+pub type KeyManagementSign = extern fn(key_id: CString,
+                                       crypto_type: CString,
+                                       message_raw: BString,
+                                       message_len: u32,
+                                       signature_raw_p: *mut BString,
+                                       signature_len_p: *mut u32,
+                                       signature_handle_p: *mut IndyHandle) -> Error;
+pub type KeyManagementFree = extern fn(handle: IndyHandle) -> Error;
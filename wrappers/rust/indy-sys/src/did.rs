@@ -19,6 +19,13 @@ extern {
                                    did: CString,
                                    cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_rotate_did_key(command_handle: CommandHandle,
+                               wallet_handle: WalletHandle,
+                               pool_handle: PoolHandle,
+                               did: CString,
+                               key_info: CString,
+                               cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_store_their_did(command_handle: CommandHandle,
                                 wallet_handle: WalletHandle,
                                 identity_json: CString,
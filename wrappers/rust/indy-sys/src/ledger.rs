@@ -22,6 +22,12 @@ extern {
                               timeout: Timeout,
                               cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_submit_read_request(command_handle: CommandHandle,
+                                    pool_handle: PoolHandle,
+                                    request_json: CString,
+                                    options_json: CString,
+                                    cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_sign_request(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
                              submitter_did: CString,
@@ -110,6 +116,10 @@ extern {
                                                  submitter_did: CString,
                                                  cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_parse_get_validator_info_response(command_handle: CommandHandle,
+                                                  get_validator_info_response: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_build_get_txn_request(command_handle: CommandHandle,
                                       submitter_did: CString,
                                       ledger_type: CString,
@@ -128,6 +138,10 @@ extern {
                                            datetime: CString,
                                            cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_parse_pool_restart_response(command_handle: CommandHandle,
+                                            pool_restart_response: CString,
+                                            cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_build_pool_upgrade_request(command_handle: CommandHandle,
                                            submitter_did: CString,
                                            name: CString,
@@ -258,11 +272,38 @@ extern {
                                                                   time_of_acceptance: u64,
                                                                   cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_set_txn_author_agreement_acceptance(command_handle: CommandHandle,
+                                                    text: CString,
+                                                    version: CString,
+                                                    hash: CString,
+                                                    acc_mech_type: CString,
+                                                    time_of_acceptance: u64,
+                                                    cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_get_active_txn_author_agreement(command_handle: CommandHandle,
+                                                pool_handle: PoolHandle,
+                                                submitter_did: CString,
+                                                cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_append_request_endorser(command_handle: CommandHandle,
                                         request_json: CString,
                                         endorser_did: CString,
                                         cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_sign_request_for_endorser(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          author_did: CString,
+                                          request_json: CString,
+                                          endorser_did: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_endorse_and_submit_request(command_handle: CommandHandle,
+                                           pool_handle: PoolHandle,
+                                           wallet_handle: WalletHandle,
+                                           endorser_did: CString,
+                                           request_json: CString,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_build_ledgers_freeze_request(command_handle: CommandHandle,
                                        submitter_did: CString,
                                        ledgers_ids: CString,
@@ -271,6 +312,31 @@ extern {
     pub fn indy_build_get_frozen_ledgers_request(command_handle: CommandHandle,
                                         submitter_did: CString,
                                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_parse_get_frozen_ledgers_response(command_handle: CommandHandle,
+                                                  get_frozen_ledgers_response: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_build_rich_schema_request(command_handle: CommandHandle,
+                                          submitter_did: CString,
+                                          data: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_build_get_rich_schema_object_by_id_request(command_handle: CommandHandle,
+                                                           submitter_did: CString,
+                                                           id: CString,
+                                                           cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_build_get_rich_schema_object_by_metadata_request(command_handle: CommandHandle,
+                                                                 submitter_did: CString,
+                                                                 rs_type: CString,
+                                                                 rs_name: CString,
+                                                                 rs_version: CString,
+                                                                 cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_parse_get_rich_schema_object_response(command_handle: CommandHandle,
+                                                      get_rich_schema_object_response: CString,
+                                                      cb: Option<ResponseStringStringCB>) -> Error;
 }
 
 pub type CustomTransactionParser = extern fn(reply_from_node: CString, parsed_sp: *mut CString) -> Error;
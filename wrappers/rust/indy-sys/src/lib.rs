@@ -62,9 +62,16 @@ pub type ResponseSliceCB = extern fn(xcommand_handle: CommandHandle, err: Error,
 pub type ResponseStringSliceCB = extern fn(xcommand_handle: CommandHandle, err: Error, str1: CString, raw: BString, len: u32);
 pub type ResponseStringStringU64CB = extern fn(xcommand_handle: CommandHandle, err: Error, arg1: CString, arg2: CString, arg3: u64);
 pub type ResponseStringI64CB = extern fn(xcommand_handle: CommandHandle, err: Error, arg1: CString, arg3: i64);
+pub type ProgressStringCB = extern fn(xcommand_handle: CommandHandle, str1: CString);
 
 extern {
     pub fn indy_set_runtime_config(config: CString) -> Error;
 
+    pub fn indy_cancel_command(command_handle: CommandHandle) -> Error;
+
+    pub fn indy_set_command_timeout(command_handle: CommandHandle, timeout_ms: u32) -> Error;
+
     pub fn indy_get_current_error(error_json_p: *mut CString);
+
+    pub fn indy_get_features() -> CString;
 }
\ No newline at end of file
@@ -21,6 +21,8 @@ extern {
                            enabled_cb_p: *mut Option<EnabledCB>,
                            log_cb_p: *mut Option<LogCB>,
                            flush_cb_p: *mut Option<FlushCB>) -> Error;
+
+    pub fn indy_set_correlation_id(correlation_id: CString) -> Error;
 }
 
 pub type EnabledCB = extern fn(context: *const CVoid,
@@ -46,6 +46,21 @@ extern {
                                      id: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_add_wallet_records(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   records_json: CString,
+                                   cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_delete_wallet_records(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      record_ids_json: CString,
+                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_with_wallet_records(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    operations_json: CString,
+                                    cb: Option<ResponseEmptyCB>) -> Error;
+
     pub fn indy_get_wallet_record(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
                                   type_: CString,
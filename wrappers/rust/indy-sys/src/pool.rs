@@ -32,5 +32,19 @@ extern {
     pub fn indy_set_protocol_version(command_handle: CommandHandle,
                                      protocol_version: usize,
                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_get_pool_handle_for_network(command_handle: CommandHandle,
+                                            network: CString,
+                                            cb: Option<ResponseI32CB>) -> Error;
+
+    pub fn indy_validate_pool_genesis_txns(command_handle: CommandHandle,
+                                           genesis_txn: CString,
+                                           options_json: CString,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_diff_pool_genesis_txns(command_handle: CommandHandle,
+                                       config_name: CString,
+                                       genesis_txn: CString,
+                                       cb: Option<ResponseStringCB>) -> Error;
 }
 
@@ -32,6 +32,24 @@ extern {
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_register_wallet_key_wrapper(command_handle: CommandHandle,
+                                            type_: CString,
+                                            wrap_key: Option<WalletKeyWrap>,
+                                            unwrap_key: Option<WalletKeyUnwrap>,
+                                            free_key: Option<WalletKeyFree>,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_register_wallet_storage_batch(command_handle: CommandHandle,
+                                              type_: CString,
+                                              batch_records: Option<WalletBatchRecords>,
+                                              cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_register_wallet_storage_search_chunk(command_handle: CommandHandle,
+                                                     type_: CString,
+                                                     fetch_search_next_records: Option<WalletFetchSearchNextRecords>,
+                                                     free_search_records: Option<WalletFreeSearchRecords>,
+                                                     cb: Option<ResponseEmptyCB>) -> Error;
+
     pub fn indy_create_wallet(command_handle: CommandHandle,
                               config: CString,
                               credentials: CString,
@@ -47,12 +65,31 @@ extern {
                               export_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_start_wallet_reencryption(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          reencryption_config: CString,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_reencrypt_wallet_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         batch_size: usize,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_get_wallet_reencryption_status(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_import_wallet(command_handle: CommandHandle,
                               config: CString,
                               credentials: CString,
                               import_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_import_wallet_records(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      import_config: CString,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_close_wallet(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
                              cb: Option<ResponseEmptyCB>) -> Error;
@@ -65,6 +102,15 @@ extern {
     pub fn indy_generate_wallet_key(command_handle: CommandHandle,
                                     config: CString,
                                     cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_generate_mnemonic(command_handle: CommandHandle,
+                                  config: CString,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_split_wallet_key(command_handle: CommandHandle,
+                                 key: CString,
+                                 config: CString,
+                                 cb: Option<ResponseStringCB>) -> Error;
 }
 
 pub type WalletCreate = extern fn(name: CString,
@@ -147,3 +193,21 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: StorageHandle,
                                                  record_handle_p: *mut RecordHandle) -> Error;
 pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                       search_handle: SearchHandle) -> Error;
+pub type WalletBatchRecords = extern fn(storage_handle: StorageHandle,
+                                        operations_json: CString) -> Error;
+pub type WalletFetchSearchNextRecords = extern fn(storage_handle: StorageHandle,
+                                                  search_handle: SearchHandle,
+                                                  count: usize,
+                                                  records_json_p: *mut CString,
+                                                  records_handle_p: *mut IndyHandle) -> Error;
+pub type WalletFreeSearchRecords = extern fn(storage_handle: StorageHandle,
+                                             records_handle: IndyHandle) -> Error;
+pub type WalletKeyWrap = extern fn(key_id: CString,
+                                   key: CString,
+                                   wrapped_key_p: *mut CString,
+                                   wrapped_key_handle_p: *mut IndyHandle) -> Error;
+pub type WalletKeyUnwrap = extern fn(key_id: CString,
+                                     wrapped_key: CString,
+                                     key_p: *mut CString,
+                                     key_handle_p: *mut IndyHandle) -> Error;
+pub type WalletKeyFree = extern fn(key_handle: IndyHandle) -> Error;
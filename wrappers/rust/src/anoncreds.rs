@@ -1,14 +1,19 @@
 use {ErrorCode, IndyError};
 
-use std::ffi::CString;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::ptr::null;
+use std::sync::Mutex;
+
+use libc::c_char;
 
 use futures::Future;
 
 use utils::callbacks::{ClosureHandler, ResultHandler};
 
 use ffi::anoncreds;
-use ffi::{ResponseStringStringCB,
+use ffi::{ProgressStringCB,
+          ResponseStringStringCB,
           ResponseI32UsizeCB,
           ResponseStringStringStringCB,
           ResponseStringCB,
@@ -139,6 +144,68 @@ fn _issuer_create_and_store_credential_def(command_handle: CommandHandle, wallet
     })
 }
 
+lazy_static! {
+    static ref CRED_DEF_PROGRESS_CALLBACKS: Mutex<HashMap<CommandHandle, Box<dyn Fn(&str) + Send>>> = Default::default();
+}
+
+/// The same as `issuer_create_and_store_credential_def` but also reports progress of the operation.
+///
+/// Keys generation is performed on a dedicated thread pool (see `cred_def_thread_pool_size` of `set_runtime_config`),
+/// so other operations are not blocked meanwhile.
+///
+/// # Arguments
+/// * `progress` - called on each stage of the operation with the stage name:
+///     - "queued" - waiting for a free worker of the credential definition thread pool
+///     - "generating" - keys are being generated
+///     - "storing" - generated keys are being stored in the wallet
+///
+/// See `issuer_create_and_store_credential_def` for the other arguments and the result.
+pub fn issuer_create_and_store_credential_def_with_progress(wallet_handle: WalletHandle, issuer_did: &str, schema_json: &str, tag: &str, signature_type: Option<&str>, config_json: &str,
+                                                            progress: Box<dyn Fn(&str) + Send>) -> Box<dyn Future<Item=(String, String), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    CRED_DEF_PROGRESS_CALLBACKS.lock().unwrap().insert(command_handle, progress);
+
+    let err = _issuer_create_and_store_credential_def_with_progress(command_handle, wallet_handle, issuer_did, schema_json, tag, signature_type, config_json, Some(_cred_def_progress_cb), cb);
+
+    Box::new(ResultHandler::str_str(command_handle, err, receiver)
+        .then(move |res| {
+            CRED_DEF_PROGRESS_CALLBACKS.lock().unwrap().remove(&command_handle);
+            res
+        }))
+}
+
+extern fn _cred_def_progress_cb(command_handle: CommandHandle, stage: *const c_char) {
+    let stage = unsafe { CStr::from_ptr(stage).to_str().unwrap() };
+
+    if let Some(progress) = CRED_DEF_PROGRESS_CALLBACKS.lock().unwrap().get(&command_handle) {
+        progress(stage);
+    }
+}
+
+fn _issuer_create_and_store_credential_def_with_progress(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, schema_json: &str, tag: &str, signature_type: Option<&str>, config_json: &str,
+                                                         progress_cb: Option<ProgressStringCB>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    let issuer_did = c_str!(issuer_did);
+    let schema_json = c_str!(schema_json);
+    let tag = c_str!(tag);
+    let signature_type_str = opt_c_str!(signature_type);
+    let config_json = c_str!(config_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_and_store_credential_def_with_progress(
+            command_handle,
+            wallet_handle,
+            issuer_did.as_ptr(),
+            schema_json.as_ptr(),
+            tag.as_ptr(),
+            opt_c_ptr!(signature_type, signature_type_str),
+            config_json.as_ptr(),
+            progress_cb,
+            cb
+        )
+    })
+}
+
 /// Generate temporary credential definitional keys for an existing one (owned by the caller of the library).
 ///
 /// Use `issuer_rotate_credential_def_apply` function to set generated temporary keys as the main.
@@ -406,6 +473,60 @@ fn _issuer_create_credential(
     })
 }
 
+/// Issue Credentials for a batch of Cred Requests created for the same Cred Offer.
+///
+/// Revocation registry is updated once and deltas of all credentials are merged into one,
+/// so the whole batch requires a single REVOC_REG_ENTRY transaction.
+/// If issuance of any credential fails, no credentials are issued.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_offer_json`: a cred offer created by create_credential_offer
+/// * `cred_reqs_json`: credential requests with values of credentials
+///     [{
+///         "cred_req": <credential request created by prover_create_credential_req>,
+///         "cred_values": <credential values, see issuer_create_credential>
+///     }]
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `creds_json`: issued credentials in the order of requests
+///     [{
+///         "cred": <credential json, see issuer_create_credential>,
+///         "cred_revoc_id": Optional<string>
+///     }]
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all newly issued credentials
+pub fn issuer_create_credentials(wallet_handle: WalletHandle,
+                                 cred_offer_json: &str,
+                                 cred_reqs_json: &str,
+                                 rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string();
+
+    let err = _issuer_create_credentials(command_handle, wallet_handle, cred_offer_json, cred_reqs_json, rev_reg_id, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credentials(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    cred_offer_json: &str,
+    cred_reqs_json: &str,
+    rev_reg_id: Option<&str>,
+    blob_storage_reader_handle: BlobStorageReaderHandle,
+    cb: Option<ResponseStringStringCB>
+) -> ErrorCode {
+    let cred_offer_json = c_str!(cred_offer_json);
+    let cred_reqs_json = c_str!(cred_reqs_json);
+    let rev_reg_id_str = opt_c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credentials(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_reqs_json.as_ptr(), opt_c_ptr!(rev_reg_id, rev_reg_id_str), blob_storage_reader_handle, cb)
+    })
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -527,6 +648,71 @@ fn _prover_get_credential(command_handle: CommandHandle, wallet_handle: WalletHa
     })
 }
 
+/// Wraps an issued credential as W3C Verifiable Credential.
+/// Raw attribute values become `credentialSubject`, CL signature and encoded values are kept in `proof`
+/// so the credential can be converted back with `prover_credential_from_w3c`.
+///
+/// # Arguments
+/// * `cred_json`: credential json received from issuer
+///
+/// # Returns
+/// * `w3c_cred_json` - {
+///     "@context": ["https://www.w3.org/2018/credentials/v1"],
+///     "type": ["VerifiableCredential", "IndyCredential"],
+///     "issuer": string, // fully qualified DID of the issuer
+///     "issuanceDate": string, // time of conversion
+///     "credentialSchema": {"id": string, "type": "IndyCredentialSchema"},
+///     "credentialSubject": {"attr1" : "raw_value1", "attr2" : "raw_value2"},
+///     "proof": {
+///         "type": "CLSignature2019",
+///         "credDefId": string,
+///         "revRegId": Optional<string>,
+///         "encoding": {"attr1" : "encoded_value1", "attr2" : "encoded_value2"},
+///         "signature": <credential signature>,
+///         "signatureCorrectnessProof": <credential signature correctness proof>,
+///         "revReg": Optional<revocation registry>,
+///         "witness": Optional<witness>
+///     }
+/// }
+pub fn prover_credential_to_w3c(cred_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_credential_to_w3c(command_handle, cred_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_credential_to_w3c(command_handle: CommandHandle, cred_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_json = c_str!(cred_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_credential_to_w3c(command_handle, cred_json.as_ptr(), cb)
+    })
+}
+
+/// Converts W3C Verifiable Credential created by `prover_credential_to_w3c` back to the credential.
+///
+/// # Arguments
+/// * `w3c_cred_json`: W3C Verifiable Credential json
+///
+/// # Returns
+/// * `cred_json` - credential json which can be stored with `prover_store_credential`
+pub fn prover_credential_from_w3c(w3c_cred_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_credential_from_w3c(command_handle, w3c_cred_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_credential_from_w3c(command_handle: CommandHandle, w3c_cred_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let w3c_cred_json = c_str!(w3c_cred_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_credential_from_w3c(command_handle, w3c_cred_json.as_ptr(), cb)
+    })
+}
+
 /// Deletes credential by given id.
 ///
 /// # Arguments
@@ -548,6 +734,33 @@ fn _prover_delete_credential(command_handle: CommandHandle, wallet_handle: Walle
     })
 }
 
+/// Deletes credential by given id together with related non-secret records in a single wallet transaction.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_id`: Identifier by which requested credential is stored in the wallet
+/// * `related_records_json`: json array of non-secret records to delete with the credential, missing records are skipped
+///     [{"type": string, "id": string}]
+///
+/// # Returns
+/// * `deleted_credential_json` - info of the deleted credential in the same format as `prover_get_credential` returns
+pub fn prover_delete_credential_with_related(wallet_handle: WalletHandle, cred_id: &str, related_records_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_delete_credential_with_related(command_handle, wallet_handle, cred_id, related_records_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_delete_credential_with_related(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, related_records_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_id = c_str!(cred_id);
+    let related_records_json = c_str!(related_records_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_delete_credential_with_related(command_handle, wallet_handle, cred_id.as_ptr(), related_records_json.as_ptr(), cb)
+    })
+}
+
 /// Creates a credential request for the given credential offer.
 ///
 /// The method creates a blinded master secret for a master secret identified by a provided name.
@@ -877,8 +1090,10 @@ fn _prover_close_credentials_search(command_handle: CommandHandle, search_handle
 /// `predicate_info`: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (Currently ">=" only)
-///         "p_value": int predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": int predicate value (for all predicate types except "RANGE")
+///         "from": int inclusive lower bound (for "RANGE" only)
+///         "to": int inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<filter_json>, // see above
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -979,8 +1194,10 @@ fn _prover_get_credentials_for_proof_req(command_handle: CommandHandle, wallet_h
 /// `predicate_info`: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (Currently ">=" only)
-///         "p_value": int predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": int predicate value (for all predicate types except "RANGE")
+///         "from": int inclusive lower bound (for "RANGE" only)
+///         "to": int inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<filter_json>, // see above
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1170,8 +1387,10 @@ fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (Currently >= only)
-///         "p_value": predicate value
+///         "p_type": predicate type (">=", ">", "<=", "<", "RANGE")
+///         "p_value": predicate value (for all predicate types except "RANGE")
+///         "from": inclusive lower bound (for "RANGE" only)
+///         "to": inclusive upper bound (for "RANGE" only)
 ///         "restrictions": Optional<wql query>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1235,6 +1454,74 @@ fn _prover_create_proof(command_handle: CommandHandle, wallet_handle: WalletHand
     })
 }
 
+/// Creates a proof like `prover_create_proof` and returns it together with proof telemetry.
+///
+/// # Arguments
+/// * `wallet_handle`, `proof_req_json`, `requested_credentials_json`, `master_secret_id`, `schemas_json`,
+///   `credential_defs_json`, `rev_states_json`: see `prover_create_proof`
+///
+/// # Returns
+/// * `result_json`: proof creation result json
+///     {
+///         "proof": <proof>, // proof json returned by `prover_create_proof`, only this part is sent to the verifier
+///         "telemetry": Optional<object>, // present if "anoncreds_telemetry" runtime config flag is enabled
+///     }
+pub fn prover_create_proof_with_details(wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str, master_secret_id: &str, schemas_json: &str, credential_defs_json: &str, rev_states_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_create_proof_with_details(command_handle, wallet_handle, proof_req_json, requested_credentials_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_create_proof_with_details(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str, master_secret_id: &str, schemas_json: &str, credential_defs_json: &str, rev_states_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_req_json = c_str!(proof_req_json);
+    let requested_credentials_json = c_str!(requested_credentials_json);
+    let master_secret_id = c_str!(master_secret_id);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_states_json = c_str!(rev_states_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_create_proof_with_details(command_handle, wallet_handle, proof_req_json.as_ptr(), requested_credentials_json.as_ptr(), master_secret_id.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_states_json.as_ptr(), cb)
+    })
+}
+
+/// Creates proofs for several proof requests at once.
+/// Master secret and credentials are read from the wallet once and proofs are built in parallel.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by open_wallet).
+/// * `proof_requests_json` - list of proof requests with credentials to use for each of them
+///     [{"proof_request": <proof_req_json>, "requested_credentials": <requested_credentials_json>}, ...]
+///     (see `prover_create_proof`)
+/// * `master_secret_id` - the id of the master secret stored in the wallet
+/// * `schemas_json` - all schemas participating in the proof requests
+/// * `credential_defs_json` - all credential definitions participating in the proof requests
+/// * `rev_states_json` - all revocation states participating in the proof requests
+///
+/// # Returns
+/// List of proof jsons in the same order as proof requests (see `prover_create_proof`)
+pub fn prover_create_proofs(wallet_handle: WalletHandle, proof_requests_json: &str, master_secret_id: &str, schemas_json: &str, credential_defs_json: &str, rev_states_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_create_proofs(command_handle, wallet_handle, proof_requests_json, master_secret_id, schemas_json, credential_defs_json, rev_states_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_create_proofs(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_requests_json: &str, master_secret_id: &str, schemas_json: &str, credential_defs_json: &str, rev_states_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_requests_json = c_str!(proof_requests_json);
+    let master_secret_id = c_str!(master_secret_id);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_states_json = c_str!(rev_states_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_create_proofs(command_handle, wallet_handle, proof_requests_json.as_ptr(), master_secret_id.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_states_json.as_ptr(), cb)
+    })
+}
+
 
 /// Verifies a proof (of multiple credential).
 /// All required schemas, public keys and revocation registries must be provided.
@@ -1345,6 +1632,56 @@ fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &st
     })
 }
 
+/// Verifies a proof like `verifier_verify_proof` but reports the result of every check
+/// instead of failing on the first one.
+///
+/// # Arguments
+/// * `proof_request_json`, `proof_json`, `schemas_json`, `credential_defs_json`, `rev_reg_defs_json`, `rev_regs_json`:
+///     see `verifier_verify_proof`
+/// * `options_json`: (optional) verification options
+///     {
+///         "revocation_unavailable": Optional<string>, // "fail" (default) or "skip" - return result flagged with
+///                                                     // "revocation_unchecked" if revocation registries are not provided
+///         "attribute_encoding": Optional<string>, // "warn" (default) or "fail" - fail the check for revealed attributes
+///                                                 // whose encoded values don't match canonical encoding of raw values
+///     }
+///
+/// # Returns
+/// * `result_json`: verification result json
+///     {
+///         "valid": bool, // true if all checks and cryptographic verification passed
+///         "crypto_valid": Optional<bool>, // null if cryptographic verification was skipped because of failed checks
+///         "referents": {"<referent>": {"valid": bool, "failures": Optional<[{"check": string, "message": string}]>}},
+///         "revealed_attrs": {"<attr_referent>": <raw value>},
+///         "revealed_attr_groups": {"<attr_referent>": {"<attr_name>": <raw value>}},
+///         "revocation_unchecked": bool,
+///         "unavailable_rev_reg_ids": Optional<[string]>,
+///         "revocation_unchecked_referents": Optional<[string]>,
+///         "non_canonical_attrs": Optional<[string]>,
+///         "telemetry": Optional<object>,
+///     }
+pub fn verifier_verify_proof_with_details(proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_verify_proof_with_details(command_handle, proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_verify_proof_with_details(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_reg_defs_json = c_str!(rev_reg_defs_json);
+    let rev_regs_json = c_str!(rev_regs_json);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_details(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), opt_c_ptr!(options_json, options_json_str), cb)
+    })
+}
+
 
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
@@ -1457,6 +1794,55 @@ fn _generate_nonce(command_handle: CommandHandle, cb: Option<ResponseStringCB>)
     })
 }
 
+/// Creates a proof request from DIF Presentation Definition.
+///
+/// # Arguments
+/// * `presentation_definition_json`: DIF presentation definition json
+///
+/// # Returns
+/// * `proof_request_json`: proof request json with generated nonce
+pub fn verifier_create_proof_request_from_presentation_definition(presentation_definition_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_create_proof_request_from_presentation_definition(command_handle, presentation_definition_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_create_proof_request_from_presentation_definition(command_handle: CommandHandle, presentation_definition_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let presentation_definition_json = c_str!(presentation_definition_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_create_proof_request_from_presentation_definition(command_handle, presentation_definition_json.as_ptr(), cb)
+    })
+}
+
+/// Maps a proof created for the proof request of `verifier_create_proof_request_from_presentation_definition`
+/// back to DIF Presentation Submission.
+///
+/// # Arguments
+/// * `presentation_definition_json`: DIF presentation definition json
+/// * `proof_json`: proof json
+///
+/// # Returns
+/// * `presentation_submission_json`: DIF presentation submission json
+pub fn verifier_create_presentation_submission(presentation_definition_json: &str, proof_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_create_presentation_submission(command_handle, presentation_definition_json, proof_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_create_presentation_submission(command_handle: CommandHandle, presentation_definition_json: &str, proof_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let presentation_definition_json = c_str!(presentation_definition_json);
+    let proof_json = c_str!(proof_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_create_presentation_submission(command_handle, presentation_definition_json.as_ptr(), proof_json.as_ptr(), cb)
+    })
+}
+
 /// Get unqualified form (short form without method) of a fully qualified entity like DID.
 ///
 /// This function should be used to the proper casting of fully qualified entity to unqualified form in the following cases:
@@ -1494,3 +1880,52 @@ fn _to_unqualified(command_handle: CommandHandle, entity: &str, cb: Option<Respo
         anoncreds::indy_to_unqualified(command_handle, entity.as_ptr(), cb)
     })
 }
+
+/// Encode a raw credential attribute value the canonical way.
+///
+/// Values that are 32-bit integers are encoded as themselves, so they can be used in predicates.
+/// All other values are encoded as decimal representation of big-endian SHA-256 digest of their UTF-8 bytes.
+///
+/// # Arguments
+/// * `raw_value`: raw attribute value
+///
+/// # Returns
+/// * `encoded`: canonical encoding of the raw value
+pub fn encode_credential_attribute(raw_value: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _encode_credential_attribute(command_handle, raw_value, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _encode_credential_attribute(command_handle: CommandHandle, raw_value: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let raw_value = c_str!(raw_value);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_encode_credential_attribute(command_handle, raw_value.as_ptr(), cb)
+    })
+}
+
+/// Check that all values of credential attributes are encoded the canonical way (see `encode_credential_attribute`).
+///
+/// # Arguments
+/// * `cred_values_json`: credential values in the format accepted by `issuer_create_credential`
+///
+/// # Errors
+/// * `CommonInvalidStructure` - some values are not encoded canonically
+pub fn validate_credential_values(cred_values_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _validate_credential_values(command_handle, cred_values_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _validate_credential_values(command_handle: CommandHandle, cred_values_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let cred_values_json = c_str!(cred_values_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_validate_credential_values(command_handle, cred_values_json.as_ptr(), cb)
+    })
+}
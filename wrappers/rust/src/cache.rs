@@ -128,6 +128,56 @@ pub fn _get_cred_def(command_handle: CommandHandle,
     )
 }
 
+/// Gets revocation registry definition json data for specified revocation registry definition id.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `id` - identifier of revocation registry definition.
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, `ledger_cache_ttl` runtime config by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// # Returns
+/// Revocation registry definition json.
+pub fn get_rev_reg_def(pool_handle: PoolHandle,
+                       wallet_handle: WalletHandle,
+                       submitter_did: &str,
+                       id: &str,
+                       options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_rev_reg_def(command_handle, pool_handle, wallet_handle, submitter_did, id, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_rev_reg_def(command_handle: CommandHandle,
+                    pool_handle: PoolHandle,
+                    wallet_handle: WalletHandle,
+                    submitter_did: &str,
+                    id: &str,
+                    options_json: &str,
+                    cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let id = c_str!(id);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_get_rev_reg_def(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), id.as_ptr(), options_json.as_ptr(), cb)
+        }
+    )
+}
+
 /// Purge schema cache.
 ///
 /// EXPERIMENTAL
@@ -153,6 +203,80 @@ fn _purge_schema_cache(command_handle: CommandHandle, wallet_handle: WalletHandl
     ErrorCode::from(unsafe { cache::indy_purge_schema_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
 }
 
+/// Get json data of an object of the rich schema family for specified object id.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `id` - DID URL identifying the object.
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// # Returns
+/// Rich schema object json (see `ledger::build_rich_schema_request`).
+pub fn get_rich_schema_object(pool_handle: PoolHandle,
+                              wallet_handle: WalletHandle,
+                              submitter_did: &str,
+                              id: &str,
+                              options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_rich_schema_object(command_handle, pool_handle, wallet_handle, submitter_did, id, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_rich_schema_object(command_handle: CommandHandle,
+                           pool_handle: PoolHandle,
+                           wallet_handle: WalletHandle,
+                           submitter_did: &str,
+                           id: &str,
+                           options_json: &str,
+                           cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let id = c_str!(id);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_get_rich_schema_object(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), id.as_ptr(), options_json.as_ptr(), cb)
+        }
+    )
+}
+
+/// Purge cache of rich schema family objects.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_rich_schema_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_rich_schema_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_rich_schema_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_rich_schema_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
+
 /// Purge credential definition cache.
 ///
 /// EXPERIMENTAL
@@ -176,4 +300,100 @@ fn _purge_cred_def_cache(command_handle: CommandHandle, wallet_handle: WalletHan
     let options_json = c_str!(options_json);
 
     ErrorCode::from(unsafe { cache::indy_purge_cred_def_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
-}
\ No newline at end of file
+}
+
+/// Purge revocation registry definition cache.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_rev_reg_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_rev_reg_def_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_rev_reg_def_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_rev_reg_def_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
+/// Collect all ledger artifacts needed to create or verify a proof for the proof request
+/// and fetch them from the ledger concurrently in one pass.
+/// Schemas, credential definitions and revocation registry definitions are taken from the cache if present
+/// and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `proof_request_json` - proof request json (see prover_create_proof).
+/// * `identifiers_json` - list of identifiers of credentials selected for the proof (prover) or of the proof (verifier).
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+///  Options are applied to schemas, credential definitions and revocation registry definitions.
+///  States and deltas of revocation registries are always fetched from the ledger.
+/// # Returns
+/// Artifacts json.
+/// {
+///     "schemas": { <schema_id>: <schema_json>, ... },
+///     "cred_defs": { <cred_def_id>: <cred_def_json>, ... },
+///     "rev_reg_defs": { <rev_reg_id>: <rev_reg_def_json>, ... },
+///     "rev_regs": { <rev_reg_id>: { <timestamp>: <rev_reg_json>, ... }, ... },
+///     "rev_reg_deltas": [{
+///         "rev_reg_id": revocation registry id,
+///         "from": (optional) start of the requested interval,
+///         "to": end of the requested interval,
+///         "timestamp": time of the delta as returned by the ledger,
+///         "delta": <rev_reg_delta_json>
+///     }, ...]
+/// }
+/// schemas, cred_defs and rev_regs can be passed to indy_verifier_verify_proof as is,
+/// schemas and cred_defs to indy_prover_create_proof, rev_reg_defs and rev_reg_deltas are
+/// the inputs for indy_create_revocation_state.
+pub fn prefetch_proof_artifacts(pool_handle: PoolHandle,
+                                wallet_handle: WalletHandle,
+                                submitter_did: &str,
+                                proof_request_json: &str,
+                                identifiers_json: &str,
+                                options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prefetch_proof_artifacts(command_handle, pool_handle, wallet_handle, submitter_did, proof_request_json, identifiers_json, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prefetch_proof_artifacts(command_handle: CommandHandle,
+                             pool_handle: PoolHandle,
+                             wallet_handle: WalletHandle,
+                             submitter_did: &str,
+                             proof_request_json: &str,
+                             identifiers_json: &str,
+                             options_json: &str,
+                             cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let proof_request_json = c_str!(proof_request_json);
+    let identifiers_json = c_str!(identifiers_json);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_prefetch_proof_artifacts(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), proof_request_json.as_ptr(), identifiers_json.as_ptr(), options_json.as_ptr(), cb)
+        }
+    )
+}
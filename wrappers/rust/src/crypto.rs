@@ -24,7 +24,10 @@ use {WalletHandle, CommandHandle};
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); 'ed25519' or 'secp256k1'.
+///                            // secp256k1 keys can be used for signing only.
+///     "kms": string, (optional) Name of key management registered with `register_key_management`.
+///                    The private key is kept in external KMS and can be used for signing only.
 /// }
 /// # Returns
 /// verkey of generated key pair, also used as key identifier
@@ -344,3 +347,222 @@ fn _unpack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, j
     })
 }
 
+
+/// Signs a payload and returns RFC 7515 JSON Web Signature
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `signer_vks_json` - list of verkeys (key ids) of the signers
+/// * `payload` - the data to be signed
+/// * `options_json` - Optional signing options: {"format": `compact` (default, single signer only) or `general`}
+/// # Returns
+/// JWS in the requested serialization
+pub fn sign_jws(wallet_handle: WalletHandle, signer_vks_json: &str, payload: &[u8], options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _sign_jws(command_handle, wallet_handle, signer_vks_json, payload, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _sign_jws(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vks_json: &str, payload: &[u8], options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signer_vks_json = c_str!(signer_vks_json);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_sign_jws(command_handle, wallet_handle, signer_vks_json.as_ptr(),
+                                     payload.as_ptr() as *const u8, payload.len() as u32,
+                                     opt_c_ptr!(options_json, options_json_str), cb)
+    })
+}
+
+/// Verifies RFC 7515 JSON Web Signature in compact, general or flattened JSON serialization
+/// # Arguments
+/// * `jws` - JWS to verify
+/// # Returns
+/// list of verkeys of the signers as json and the decoded payload
+pub fn verify_jws(jws: &str) -> Box<dyn Future<Item=(String, Vec<u8>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_slice();
+
+    let err = _verify_jws(command_handle, jws, cb);
+
+    ResultHandler::str_slice(command_handle, err, receiver)
+}
+
+fn _verify_jws(command_handle: CommandHandle, jws: &str, cb: Option<ResponseStringSliceCB>) -> ErrorCode {
+    let jws = c_str!(jws);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_jws(command_handle, jws.as_ptr(), cb)
+    })
+}
+
+/// Converts Ed25519 verkey to X25519 public key of the same key pair
+/// # Arguments
+/// * `verkey` - full Ed25519 verkey
+/// # Returns
+/// base58 encoded X25519 public key
+pub fn ed25519_to_x25519(verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _ed25519_to_x25519(command_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _ed25519_to_x25519(command_handle: CommandHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_ed25519_to_x25519(command_handle, verkey.as_ptr(), cb)
+    })
+}
+
+/// Computes X25519 Diffie-Hellman shared secret of the X25519 form of a wallet key and a public X25519 key
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `my_vk` - id (verkey) of Ed25519 key in the wallet
+/// * `their_x25519_key` - base58 encoded X25519 public key of the other party
+/// # Returns
+/// raw shared secret
+pub fn x25519_diffie_hellman(wallet_handle: WalletHandle, my_vk: &str, their_x25519_key: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _x25519_diffie_hellman(command_handle, wallet_handle, my_vk, their_x25519_key, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _x25519_diffie_hellman(command_handle: CommandHandle, wallet_handle: WalletHandle, my_vk: &str, their_x25519_key: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let my_vk = c_str!(my_vk);
+    let their_x25519_key = c_str!(their_x25519_key);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_x25519_diffie_hellman(command_handle, wallet_handle, my_vk.as_ptr(), their_x25519_key.as_ptr(), cb)
+    })
+}
+
+/// Generates BLS12-381 key pair. Keys are not stored in the wallet.
+/// # Arguments
+/// * `seed` - Optional seed that allows deterministic key creation
+/// # Returns
+/// keys json: {"sign_key": string, "ver_key": string, "pop": string} - base58 encoded keys and proof of possession
+pub fn bls_create_keys(seed: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _bls_create_keys(command_handle, seed, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _bls_create_keys(command_handle: CommandHandle, seed: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let seed_str = opt_c_str!(seed);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_bls_create_keys(command_handle, opt_c_ptr!(seed, seed_str), cb)
+    })
+}
+
+/// Signs a message with BLS sign key
+/// # Arguments
+/// * `sign_key` - base58 encoded BLS sign key
+/// * `message` - the data to be signed
+/// # Returns
+/// base58 encoded BLS signature
+pub fn bls_sign(sign_key: &str, message: &[u8]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _bls_sign(command_handle, sign_key, message, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _bls_sign(command_handle: CommandHandle, sign_key: &str, message: &[u8], cb: Option<ResponseStringCB>) -> ErrorCode {
+    let sign_key = c_str!(sign_key);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_bls_sign(command_handle, sign_key.as_ptr(),
+                              message.as_ptr() as *const u8, message.len() as u32, cb)
+    })
+}
+
+/// Aggregates BLS signatures of the same message into multi-signature
+/// # Arguments
+/// * `signatures_json` - list of base58 encoded BLS signatures
+/// # Returns
+/// base58 encoded BLS multi-signature
+pub fn bls_aggregate_signatures(signatures_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _bls_aggregate_signatures(command_handle, signatures_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _bls_aggregate_signatures(command_handle: CommandHandle, signatures_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signatures_json = c_str!(signatures_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_bls_aggregate_signatures(command_handle, signatures_json.as_ptr(), cb)
+    })
+}
+
+/// Verifies BLS multi-signature of a message, e.g. the one of ledger state proof
+/// # Arguments
+/// * `multi_sig` - base58 encoded BLS multi-signature
+/// * `message` - the data that was signed
+/// * `ver_keys_json` - list of base58 encoded BLS verkeys of all the signers
+/// # Returns
+/// true if multi-signature is valid, false otherwise
+pub fn bls_verify_multi_sig(multi_sig: &str, message: &[u8], ver_keys_json: &str) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _bls_verify_multi_sig(command_handle, multi_sig, message, ver_keys_json, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _bls_verify_multi_sig(command_handle: CommandHandle, multi_sig: &str, message: &[u8], ver_keys_json: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let multi_sig = c_str!(multi_sig);
+    let ver_keys_json = c_str!(ver_keys_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_bls_verify_multi_sig(command_handle, multi_sig.as_ptr(),
+                                          message.as_ptr() as *const u8, message.len() as u32,
+                                          ver_keys_json.as_ptr(), cb)
+    })
+}
+
+/// Register key management implementation.
+///
+/// Key management keeps private keys in external KMS (PKCS#11 HSM or mobile secure enclave for example)
+/// instead of the wallet. Keys are created in KMS with `kms` param of `create_key`,
+/// signing with such keys is delegated to KMS.
+///
+/// # Arguments
+/// * `name` - Key management name.
+/// * `create_key` - KeyManagement create key operation handler
+/// * `sign` - KeyManagement sign operation handler
+/// * `free` - Handler that allows to de-allocate data allocated in caller code
+pub fn register_key_management(name: &str,
+                               create_key: Option<crypto::KeyManagementCreateKey>,
+                               sign: Option<crypto::KeyManagementSign>,
+                               free: Option<crypto::KeyManagementFree>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_key_management(command_handle, name, create_key, sign, free, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_key_management(command_handle: CommandHandle,
+                            name: &str,
+                            create_key: Option<crypto::KeyManagementCreateKey>,
+                            sign: Option<crypto::KeyManagementSign>,
+                            free: Option<crypto::KeyManagementFree>,
+                            cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let name = c_str!(name);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_register_key_management(command_handle, name.as_ptr(), create_key, sign, free, cb)
+    })
+}
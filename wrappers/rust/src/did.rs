@@ -31,10 +31,17 @@ use {CommandHandle, WalletHandle, PoolHandle};
 ///             if provided, then keys will be replaced - key rotation use case)
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
+///     "mnemonic": object, (optional) BIP39 mnemonic for deterministic key creation instead of seed.
+///         {
+///             "phrase": string, mnemonic generated by `wallet::generate_mnemonic`
+///             "passphrase": string, (optional) BIP39 passphrase, empty by default
+///             "index": int, (optional) index of the DID, 0 by default
+///         }
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
 ///               currently only 'ed25519' value is supported for this field)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///                    Use `indy:<namespace>` to create a DID bound to the network with this namespace (`did:indy:<namespace>:<id>`).
 /// }
 ///
 /// # Returns
@@ -106,6 +113,45 @@ fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandl
     ErrorCode::from(unsafe { did::indy_replace_keys_apply(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
 }
 
+/// Rotates keys of an existing DID (owned by the caller of the library) in the wallet and on the ledger at once.
+///
+/// Generates temporary keys for the DID, writes NYM with the new verkey signed by the current key
+/// and applies the new keys in the wallet when the transaction is written.
+/// Their DIDs and endpoints stored in the wallet with the old verkey are updated to the new one.
+/// If the ledger rejects the transaction the wallet stays unchanged.
+/// If the ledger doesn't reply in time the temporary keys are kept to be applied with `replace_keys_apply`.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `tgt_did` - DID to rotate keys.
+/// * `identity_json` - Identity information as json.
+/// # Example
+/// * `identity_json`-
+/// {
+///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
+///                                Can be UTF-8, base64 or hex string.
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               currently only 'ed25519' value is supported for this field)
+/// }
+///
+/// # Returns
+/// * `verkey` - The new verification key of the DID
+pub fn rotate_key(wallet_handle: WalletHandle, pool_handle: PoolHandle, tgt_did: &str, identity_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _rotate_key(command_handle, wallet_handle, pool_handle, tgt_did, identity_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _rotate_key(command_handle: CommandHandle, wallet_handle: WalletHandle, pool_handle: PoolHandle, tgt_did: &str, identity_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+    let identity_json = c_str!(identity_json);
+
+    ErrorCode::from(unsafe { did::indy_rotate_did_key(command_handle, wallet_handle, pool_handle, tgt_did.as_ptr(), identity_json.as_ptr(), cb) })
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
 /// Updates DID associated verkey in case DID already exists in the Wallet.
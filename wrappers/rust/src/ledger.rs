@@ -7,13 +7,16 @@ use std::ptr::null;
 use futures::Future;
 
 use ffi::ledger;
-use ffi::{ResponseStringCB,
+use ffi::{ResponseEmptyCB,
+          ResponseStringCB,
           ResponseStringStringCB,
           ResponseStringStringU64CB};
 
 use utils::callbacks::{ClosureHandler, ResultHandler};
 use {WalletHandle, CommandHandle, PoolHandle};
 
+pub mod auth_rule;
+
 /// Signs and submits request message to validator pool.
 ///
 /// Adds submitter information to passed request json, signs it with submitter
@@ -22,6 +25,7 @@ use {WalletHandle, CommandHandle, PoolHandle};
 ///
 /// # Arguments
 /// * `pool_handle` - pool handle (created by Pool::open_ledger).
+///                   Pass 0 to route the request to the pool opened for the network namespace of `did:indy:<namespace>:<id>` submitter DID.
 /// * `wallet_handle` - wallet handle (created by Wallet::open).
 /// * `submitter_did` - Id of Identity stored in secured Wallet.
 /// * `request_json` - Request data json.
@@ -91,6 +95,42 @@ fn _submit_action(command_handle: CommandHandle, pool_handle: PoolHandle, reques
     })
 }
 
+/// Send read request to validator pool collecting matching replies of several nodes.
+///
+/// The request is sent to several nodes at once and the result is returned as soon as
+/// a quorum of them replied with the same data or one of them replied with valid state proof.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `request_json` - Request data json.
+/// * `options_json` - (Optional) Options of the read:
+///     {
+///         "quorum": Optional<int> - number of matching replies required to accept the result (f+1 by default),
+///                                       less than f+1 is accepted only with "require_state_proof",
+///         "fan_out": Optional<int> - number of nodes the request is sent to at once (`number_read_nodes` of the pool by default),
+///         "timeout": Optional<int> - time to wait for acknowledgement of a node in seconds (`timeout` of the pool by default),
+///         "require_state_proof": Optional<bool> - accept only a reply with valid state proof, matching replies without it are not enough (false by default)
+///     }
+///
+/// # Returns
+/// Request result as json.
+pub fn submit_read_request(pool_handle: PoolHandle, request_json: &str, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _submit_read_request(command_handle, pool_handle, request_json, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _submit_read_request(command_handle: CommandHandle, pool_handle: PoolHandle, request_json: &str, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let request_json = c_str!(request_json);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_submit_read_request(command_handle, pool_handle, request_json.as_ptr(), opt_c_ptr!(options_json, options_json_str), cb)
+    })
+}
+
 /// Signs request message.
 ///
 /// Adds submitter information to passed request json, signs it with submitter
@@ -610,6 +650,37 @@ fn _build_get_validator_info_request(command_handle: CommandHandle, submitter_di
     })
 }
 
+/// Parse a GET_VALIDATOR_INFO action response to get validator info of every node of the pool.
+///
+/// # Arguments
+/// * `get_validator_info_response` - response of GET_VALIDATOR_INFO action (result of `submit_action`).
+///
+/// # Returns
+/// Results of the action aggregated per node as json.
+/// {
+///     <node_alias>: {
+///         "status": String - "REPLY", "REQNACK", "REJECT" or "timeout",
+///         "data": Optional<object> - validator info reported by the node (present for "REPLY" status only),
+///         "reason": Optional<string> - reason of request rejection (present for "REQNACK" and "REJECT" statuses only),
+///     },
+///     ...
+/// }
+pub fn parse_get_validator_info_response(get_validator_info_response: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _parse_get_validator_info_response(command_handle, get_validator_info_response, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _parse_get_validator_info_response(command_handle: CommandHandle, get_validator_info_response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let get_validator_info_response = c_str!(get_validator_info_response);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_parse_get_validator_info_response(command_handle, get_validator_info_response.as_ptr(), cb)
+    })
+}
+
 /// Builds a POOL_CONFIG request. Request to change Pool's configuration.
 ///
 /// # Arguments
@@ -668,6 +739,37 @@ fn _build_pool_restart_request(command_handle: CommandHandle, submitter_did: &st
     })
 }
 
+/// Parse a POOL_RESTART action response to get the result of the restart command for every node of the pool.
+///
+/// # Arguments
+/// * `pool_restart_response` - response of POOL_RESTART action (result of `submit_action`).
+///
+/// # Returns
+/// Results of the action aggregated per node as json.
+/// {
+///     <node_alias>: {
+///         "status": String - "REPLY", "REQNACK", "REJECT" or "timeout",
+///         "data": Optional<object> - restart command result reported by the node (present for "REPLY" status only),
+///         "reason": Optional<string> - reason of request rejection (present for "REQNACK" and "REJECT" statuses only),
+///     },
+///     ...
+/// }
+pub fn parse_pool_restart_response(pool_restart_response: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _parse_pool_restart_response(command_handle, pool_restart_response, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _parse_pool_restart_response(command_handle: CommandHandle, pool_restart_response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let pool_restart_response = c_str!(pool_restart_response);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_parse_pool_restart_response(command_handle, pool_restart_response.as_ptr(), cb)
+    })
+}
+
 /// Builds a POOL_UPGRADE request. Request to upgrade the Pool (sent by Trustee).
 /// It upgrades the specified Nodes (either all nodes in the Pool, or some specific ones).
 ///
@@ -1065,6 +1167,7 @@ fn _get_response_metadata(command_handle: CommandHandle, response: &str, cb: Opt
 ///         'constraint_id': <"AND" or "OR">
 ///         'auth_constraints': [<constraint_1>, <constraint_2>]
 ///     }
+/// Use `auth_rule::build_auth_rule_request` to build the request from the typed `auth_rule::Constraint`.
 ///
 /// # Returns
 /// Request result as json.
@@ -1473,6 +1576,100 @@ fn _append_txn_author_agreement_acceptance_to_request(command_handle: CommandHan
     })
 }
 
+/// Record transaction author agreement acceptance for the requests built by libindy.
+/// Once recorded, acceptance data is appended to every domain ledger write request
+/// (NYM, ATTRIB, SCHEMA, CRED_DEF, REVOC_REG_DEF, REVOC_REG_ENTRY) built by `build_*_request` functions,
+/// so `append_txn_author_agreement_acceptance_to_request` doesn't have to be called for them.
+///
+/// # Arguments
+/// * `text` and `version`: (optional) raw data about TAA from ledger.
+///     These parameters should be passed together.
+///     These parameters are required if taa_digest parameter is omitted.
+/// * `taa_digest`: (optional) digest on text and version.
+///     Digest is sha256 hash calculated on concatenated strings: version || text.
+///     This parameter is required if text and version parameters are omitted.
+/// * `mechanism`: (optional) mechanism how user has accepted the TAA.
+///     Pass None to remove recorded acceptance, other parameters are ignored in this case.
+/// * `time`: UTC timestamp when user has accepted the TAA. Note that the time portion will be discarded to avoid a privacy risk.
+pub fn set_txn_author_agreement_acceptance(text: Option<&str>,
+                                           version: Option<&str>,
+                                           taa_digest: Option<&str>,
+                                           mechanism: Option<&str>,
+                                           time: u64) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_txn_author_agreement_acceptance(command_handle, text, version, taa_digest, mechanism, time, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_txn_author_agreement_acceptance(command_handle: CommandHandle,
+                                        text: Option<&str>,
+                                        version: Option<&str>,
+                                        taa_digest: Option<&str>,
+                                        mechanism: Option<&str>,
+                                        time: u64,
+                                        cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let text_str = opt_c_str!(text);
+    let version_str = opt_c_str!(version);
+    let taa_digest_str = opt_c_str!(taa_digest);
+    let mechanism_str = opt_c_str!(mechanism);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_set_txn_author_agreement_acceptance(command_handle,
+                                                         opt_c_ptr!(text, text_str),
+                                                         opt_c_ptr!(version, version_str),
+                                                         opt_c_ptr!(taa_digest, taa_digest_str),
+                                                         opt_c_ptr!(mechanism, mechanism_str),
+                                                         time,
+                                                         cb)
+    })
+}
+
+/// Get transaction author agreement and acceptance mechanisms which are active on the Ledger.
+///
+/// # Arguments
+/// * `pool_handle`: pool handle (created by open_pool_ledger).
+/// * `submitter_did`: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+///
+/// # Returns
+/// {
+///     "taa": Optional<object> - active transaction author agreement, null if it isn't set on the Ledger
+///     {
+///         "text": string - text of TAA,
+///         "version": string - version of TAA,
+///         "digest": string - digest to use for acceptance,
+///         "ratification_ts": Optional<int> - time of TAA ratification
+///     },
+///     "aml": Optional<object> - latest acceptance mechanisms, null if they aren't set on the Ledger
+///     {
+///         "aml": {<label>: <description>} - acceptance mechanisms allowed for TAA acceptance,
+///         "version": string - version of acceptance mechanisms,
+///         "amlContext": Optional<string> - context information about acceptance mechanisms
+///     }
+/// }
+pub fn get_active_txn_author_agreement(pool_handle: PoolHandle, submitter_did: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_active_txn_author_agreement(command_handle, pool_handle, submitter_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_active_txn_author_agreement(command_handle: CommandHandle,
+                                    pool_handle: PoolHandle,
+                                    submitter_did: Option<&str>,
+                                    cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did_str = opt_c_str!(submitter_did);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_get_active_txn_author_agreement(command_handle,
+                                                     pool_handle,
+                                                     opt_c_ptr!(submitter_did, submitter_did_str),
+                                                     cb)
+    })
+}
+
 /// Append Endorser to an existing request.
 ///
 /// An author of request still is a `DID` used as a `submitter_did` parameter for the building of the request.
@@ -1512,6 +1709,99 @@ fn _append_request_endorser(command_handle: CommandHandle,
                                              cb)
     })
 }
+
+/// Sign request by its author for the Endorser.
+///
+/// Appends the Endorser to the request and multi signs it with the author key (see `multi_sign_request`),
+/// so the output can be transported to the Endorser who adds their signature and submits it
+/// (see `endorse_and_submit_request`).
+///
+/// More about Transaction Endorser: https://github.com/hyperledger/indy-node/blob/master/design/transaction_endorser.md
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `author_did`: DID of the transaction author used to build the request. Must be stored in the wallet.
+/// * `request_json`: original request data json.
+/// * `endorser_did`: DID of the Endorser that will submit the transaction.
+///                   The Endorser's DID must be present on the ledger.
+///
+/// # Returns
+/// Request json signed by the author and ready to be passed to the Endorser.
+pub fn sign_request_for_endorser(wallet_handle: WalletHandle,
+                                 author_did: &str,
+                                 request_json: &str,
+                                 endorser_did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _sign_request_for_endorser(command_handle, wallet_handle, author_did, request_json, endorser_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _sign_request_for_endorser(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              author_did: &str,
+                              request_json: &str,
+                              endorser_did: &str,
+                              cb: Option<ResponseStringCB>) -> ErrorCode {
+    let author_did = c_str!(author_did);
+    let request_json = c_str!(request_json);
+    let endorser_did = c_str!(endorser_did);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_sign_request_for_endorser(command_handle,
+                                               wallet_handle,
+                                               author_did.as_ptr(),
+                                               request_json.as_ptr(),
+                                               endorser_did.as_ptr(),
+                                               cb)
+    })
+}
+
+/// Endorse request signed by its author and send it to validator pool.
+///
+/// Checks that the request names the Endorser and is multi signed by its author (see `sign_request_for_endorser`),
+/// adds the Endorser signature and sends the request to validator pool.
+///
+/// # Arguments
+/// * `pool_handle`: pool handle (created by Pool::open_ledger).
+///                  Pass 0 to route the request to the pool opened for the network namespace of `did:indy:<namespace>:<id>` endorser DID.
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `endorser_did`: DID of the Endorser named in the request. Must be stored in the wallet.
+/// * `request_json`: request json signed by the author.
+///
+/// # Returns
+/// Request result as json.
+pub fn endorse_and_submit_request(pool_handle: PoolHandle,
+                                  wallet_handle: WalletHandle,
+                                  endorser_did: &str,
+                                  request_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _endorse_and_submit_request(command_handle, pool_handle, wallet_handle, endorser_did, request_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _endorse_and_submit_request(command_handle: CommandHandle,
+                               pool_handle: PoolHandle,
+                               wallet_handle: WalletHandle,
+                               endorser_did: &str,
+                               request_json: &str,
+                               cb: Option<ResponseStringCB>) -> ErrorCode {
+    let endorser_did = c_str!(endorser_did);
+    let request_json = c_str!(request_json);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_endorse_and_submit_request(command_handle,
+                                                pool_handle,
+                                                wallet_handle,
+                                                endorser_did.as_ptr(),
+                                                request_json.as_ptr(),
+                                                cb)
+    })
+}
+
 /// Request to freeze list of ledgers.
 ///
 /// # Arguments
@@ -1572,4 +1862,146 @@ fn _build_get_frozen_ledgers_request(command_handle: CommandHandle, submitter_di
                                              submitter_did.as_ptr(),
                                              cb)
     })
-}
\ No newline at end of file
+}
+
+/// Parse a GET_FROZEN_LEDGERS response to get the list of frozen ledgers.
+///
+/// # Arguments
+/// * `get_frozen_ledgers_response` - response of GET_FROZEN_LEDGERS request.
+///
+/// # Returns
+/// Frozen ledgers as json (empty object if there are no frozen ledgers).
+/// {
+///     <ledger_id>: {
+///         "ledger": String - Ledger root hash,
+///         "state": String - State root hash,
+///         "seq_no": u64 - the latest transaction seqNo for particular Node,
+///     },
+///     ...
+/// }
+pub fn parse_get_frozen_ledgers_response(get_frozen_ledgers_response: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _parse_get_frozen_ledgers_response(command_handle, get_frozen_ledgers_response, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _parse_get_frozen_ledgers_response(command_handle: CommandHandle, get_frozen_ledgers_response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let get_frozen_ledgers_response = c_str!(get_frozen_ledgers_response);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_parse_get_frozen_ledgers_response(command_handle, get_frozen_ledgers_response.as_ptr(), cb)
+    })
+}
+
+/// Builds a request to add an object of the rich schema family:
+/// JSON-LD context (SET_CONTEXT), rich schema (RICH_SCHEMA), mapping (MAPPING) or encoding (ENCODING).
+///
+/// # Arguments
+/// * `submitter_did` - Identifier (DID) of the transaction author as base58-encoded string.
+/// * `data` - rich schema object json
+/// {
+///     id: string - DID URL identifying the object
+///     content: string - JSON-LD content of the object serialized as string.
+///     rsName: string - name of the object
+///     rsVersion: string - version of the object
+///     rsType: string - type of the object: "ctx", "sch", "map" or "enc"
+///     ver: string - version of the object json
+/// }
+///
+/// # Returns
+/// Request result as json.
+pub fn build_rich_schema_request(submitter_did: &str, data: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _build_rich_schema_request(command_handle, submitter_did, data, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _build_rich_schema_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let data = c_str!(data);
+
+    ErrorCode::from(unsafe { ledger::indy_build_rich_schema_request(command_handle, submitter_did.as_ptr(), data.as_ptr(), cb) })
+}
+
+/// Builds a GET_RICH_SCHEMA_OBJECT_BY_ID request. Request to get an object of the rich schema family by its id.
+///
+/// # Arguments
+/// * `submitter_did` - (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// * `id` - DID URL identifying the object
+///
+/// # Returns
+/// Request result as json.
+pub fn build_get_rich_schema_object_by_id_request(submitter_did: Option<&str>, id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _build_get_rich_schema_object_by_id_request(command_handle, submitter_did, id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _build_get_rich_schema_object_by_id_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did_str = opt_c_str!(submitter_did);
+    let id = c_str!(id);
+
+    ErrorCode::from(unsafe { ledger::indy_build_get_rich_schema_object_by_id_request(command_handle, opt_c_ptr!(submitter_did, submitter_did_str), id.as_ptr(), cb) })
+}
+
+/// Builds a GET_RICH_SCHEMA_OBJECT_BY_METADATA request. Request to get an object of the rich schema family
+/// by its type, name and version.
+///
+/// # Arguments
+/// * `submitter_did` - (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// * `rs_type` - type of the object: "ctx", "sch", "map" or "enc"
+/// * `rs_name` - name of the object
+/// * `rs_version` - version of the object
+///
+/// # Returns
+/// Request result as json.
+pub fn build_get_rich_schema_object_by_metadata_request(submitter_did: Option<&str>, rs_type: &str, rs_name: &str, rs_version: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _build_get_rich_schema_object_by_metadata_request(command_handle, submitter_did, rs_type, rs_name, rs_version, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _build_get_rich_schema_object_by_metadata_request(command_handle: CommandHandle, submitter_did: Option<&str>, rs_type: &str, rs_name: &str, rs_version: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did_str = opt_c_str!(submitter_did);
+    let rs_type = c_str!(rs_type);
+    let rs_name = c_str!(rs_name);
+    let rs_version = c_str!(rs_version);
+
+    ErrorCode::from(unsafe {
+        ledger::indy_build_get_rich_schema_object_by_metadata_request(command_handle,
+                                                                      opt_c_ptr!(submitter_did, submitter_did_str),
+                                                                      rs_type.as_ptr(),
+                                                                      rs_name.as_ptr(),
+                                                                      rs_version.as_ptr(),
+                                                                      cb)
+    })
+}
+
+/// Parse a GET_RICH_SCHEMA_OBJECT_BY_ID or GET_RICH_SCHEMA_OBJECT_BY_METADATA response.
+///
+/// # Arguments
+/// * `get_rich_schema_object_response` - response of GET_RICH_SCHEMA_OBJECT_BY_ID or GET_RICH_SCHEMA_OBJECT_BY_METADATA request.
+///
+/// # Returns
+/// Object id and object json (see `build_rich_schema_request`).
+pub fn parse_get_rich_schema_object_response(get_rich_schema_object_response: &str) -> Box<dyn Future<Item=(String, String), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = _parse_get_rich_schema_object_response(command_handle, get_rich_schema_object_response, cb);
+
+    ResultHandler::str_str(command_handle, err, receiver)
+}
+
+fn _parse_get_rich_schema_object_response(command_handle: CommandHandle, get_rich_schema_object_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    let get_rich_schema_object_response = c_str!(get_rich_schema_object_response);
+
+    ErrorCode::from(unsafe { ledger::indy_parse_get_rich_schema_object_response(command_handle, get_rich_schema_object_response.as_ptr(), cb) })
+}
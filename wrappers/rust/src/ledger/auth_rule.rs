@@ -0,0 +1,200 @@
+//! Typed model of ledger authentication rules.
+//!
+//! Lets callers build AUTH_RULE, AUTH_RULES and GET_AUTH_RULE requests from Rust values
+//! instead of constructing raw constraint JSON by hand.
+
+use serde_json;
+use serde_json::{json, Value};
+
+use futures::Future;
+
+use {ErrorCode, IndyError};
+
+/// Type of an action for which authentication rules are applied.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AuthAction {
+    #[serde(rename = "ADD")]
+    Add,
+    #[serde(rename = "EDIT")]
+    Edit,
+}
+
+impl AuthAction {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            AuthAction::Add => "ADD",
+            AuthAction::Edit => "EDIT",
+        }
+    }
+}
+
+/// Set of constraints required for execution of an action.
+///
+/// `RoleConstraint` is the final constraint, `AndConstraint` and `OrConstraint` combine
+/// multiple constraints, `ForbiddenConstraint` means that the action is forbidden.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "constraint_id")]
+pub enum Constraint {
+    #[serde(rename = "OR")]
+    OrConstraint(CombinationConstraint),
+    #[serde(rename = "AND")]
+    AndConstraint(CombinationConstraint),
+    #[serde(rename = "ROLE")]
+    RoleConstraint(RoleConstraint),
+    #[serde(rename = "FORBIDDEN")]
+    ForbiddenConstraint(ForbiddenConstraint),
+}
+
+impl Constraint {
+    /// All of the given constraints must be met.
+    pub fn and(auth_constraints: Vec<Constraint>) -> Constraint {
+        Constraint::AndConstraint(CombinationConstraint { auth_constraints })
+    }
+
+    /// Any of the given constraints must be met.
+    pub fn or(auth_constraints: Vec<Constraint>) -> Constraint {
+        Constraint::OrConstraint(CombinationConstraint { auth_constraints })
+    }
+
+    /// The action is forbidden.
+    pub fn forbidden() -> Constraint {
+        Constraint::ForbiddenConstraint(ForbiddenConstraint {})
+    }
+
+    /// Constraint in the JSON format accepted by `ledger::build_auth_rule_request`.
+    pub fn to_json(&self) -> String {
+        json!(self).to_string()
+    }
+}
+
+impl From<RoleConstraint> for Constraint {
+    fn from(constraint: RoleConstraint) -> Constraint {
+        Constraint::RoleConstraint(constraint)
+    }
+}
+
+/// The final constraint.
+///
+/// * `sig_count` - the number of signatures required to execute the action.
+/// * `role` - the role which the user must have to execute the action, None or "*" for any role.
+/// * `metadata` - additional parameters of the constraint (contains transaction FEE cost).
+/// * `need_to_be_owner` - if the user must be an owner of the transaction (false by default).
+/// * `off_ledger_signature` - allow signature of a DID unknown for the ledger (false by default).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RoleConstraint {
+    pub sig_count: u32,
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+    #[serde(default)]
+    pub need_to_be_owner: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub off_ledger_signature: bool,
+}
+
+impl RoleConstraint {
+    pub fn new(role: Option<&str>, sig_count: u32) -> RoleConstraint {
+        RoleConstraint {
+            sig_count,
+            role: role.map(String::from),
+            metadata: None,
+            need_to_be_owner: false,
+            off_ledger_signature: false,
+        }
+    }
+
+    pub fn need_to_be_owner(mut self, need_to_be_owner: bool) -> RoleConstraint {
+        self.need_to_be_owner = need_to_be_owner;
+        self
+    }
+
+    pub fn off_ledger_signature(mut self, off_ledger_signature: bool) -> RoleConstraint {
+        self.off_ledger_signature = off_ledger_signature;
+        self
+    }
+
+    pub fn metadata(mut self, metadata: Value) -> RoleConstraint {
+        self.metadata = Some(metadata);
+        self
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Combination of multiple constraints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CombinationConstraint {
+    pub auth_constraints: Vec<Constraint>,
+}
+
+/// The forbidden constraint means that action is forbidden.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ForbiddenConstraint {}
+
+/// Authentication rule of a ledger transaction as used in AUTH_RULES request and GET_AUTH_RULE response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuthRule {
+    pub auth_type: String,
+    pub auth_action: AuthAction,
+    pub field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub constraint: Constraint,
+}
+
+/// Builds a AUTH_RULE request for the typed constraint.
+///
+/// See `ledger::build_auth_rule_request` for the description of the arguments.
+pub fn build_auth_rule_request(submitter_did: &str, txn_type: &str, action: AuthAction, field: &str,
+                               old_value: Option<&str>, new_value: Option<&str>, constraint: &Constraint) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    super::build_auth_rule_request(submitter_did, txn_type, action.as_str(), field, old_value, new_value, &constraint.to_json())
+}
+
+/// Builds a AUTH_RULES request for the typed list of rules.
+///
+/// See `ledger::build_auth_rules_request` for the description of the arguments.
+pub fn build_auth_rules_request(submitter_did: &str, rules: &[AuthRule]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    super::build_auth_rules_request(submitter_did, &json!(rules).to_string())
+}
+
+/// Builds a GET_AUTH_RULE request for the typed action.
+///
+/// See `ledger::build_get_auth_rule_request` for the description of the arguments.
+pub fn build_get_auth_rule_request(submitter_did: Option<&str>, txn_type: Option<&str>, action: Option<AuthAction>, field: Option<&str>,
+                                   old_value: Option<&str>, new_value: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    super::build_get_auth_rule_request(submitter_did, txn_type, action.as_ref().map(AuthAction::as_str), field, old_value, new_value)
+}
+
+/// Parse a GET_AUTH_RULE response to get the list of authentication rules.
+///
+/// # Arguments
+/// * `get_auth_rule_response`: response of GET_AUTH_RULE request.
+///
+/// # Returns
+/// Authentication rules returned by the ledger.
+pub fn parse_get_auth_rule_response(get_auth_rule_response: &str) -> Result<Vec<AuthRule>, IndyError> {
+    let response: Value = serde_json::from_str(get_auth_rule_response)
+        .map_err(|err| _invalid_response(format!("Response is invalid json: {}", err)))?;
+
+    if response["op"] != json!("REPLY") {
+        return Err(_invalid_response(format!("Transaction has been failed: {:?}", response["reason"])));
+    }
+
+    serde_json::from_value(response["result"]["data"].clone())
+        .map_err(|err| _invalid_response(format!("Cannot parse GET_AUTH_RULE response: {}", err)))
+}
+
+fn _invalid_response(message: String) -> IndyError {
+    IndyError {
+        error_code: ErrorCode::LedgerInvalidTransaction,
+        message,
+        indy_backtrace: None,
+        indy_context: Vec::new(),
+        vendor_code: None,
+    }
+}
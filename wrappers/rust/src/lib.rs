@@ -65,6 +65,12 @@ pub use ffi::{
 /// # Arguments
 /// * `config` - {
 ///     "crypto_thread_pool_size": <int> - size of thread pool for the most expensive crypto operations. (4 by default)
+///     "cred_def_thread_pool_size": <int> - size of dedicated thread pool for credential definition keys generation. (2 by default)
+///     "command_queue_size": <int> - max number of commands waiting for execution. (0 by default - the queue is unbounded)
+///         Calls made when the queue is full are rejected with `ErrorCode::CommonBusy` and can be retried later.
+///     "command_workers": <int> - number of threads executing commands. (1 by default, at most 3 are used)
+///         Wallet dependent commands always run on the same thread, pool and blob storage commands get own threads if available.
+///         Must be set before invocation of any other API functions.
 /// }
 pub fn set_runtime_config(config: &str) -> ErrorCode {
     let config = c_str!(config);
@@ -74,6 +80,41 @@ pub fn set_runtime_config(config: &str) -> ErrorCode {
     })
 }
 
+/// Cancel long-running command.
+///
+/// Callback of the command is called with `ErrorCode::CommonCancelled` and the result of the command is dropped.
+/// The work of the command isn't stopped, e.g. a pool request is still sent to the ledger.
+/// Supported commands: pool requests, proof generation and wallet search.
+///
+/// # Arguments
+/// * `command_handle` - command handle passed to libindy call of the command
+pub fn cancel_command(command_handle: CommandHandle) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        ffi::indy_cancel_command(command_handle)
+    })
+}
+
+/// Set timeout of long-running command. The command is cancelled if it isn't completed in time.
+///
+/// # Arguments
+/// * `command_handle` - command handle passed to libindy call of the command
+/// * `timeout_ms` - timeout in milliseconds
+pub fn set_command_timeout(command_handle: CommandHandle, timeout_ms: u32) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        ffi::indy_set_command_timeout(command_handle, timeout_ms)
+    })
+}
+
+/// Get optional subsystems of libindy: whether they are compiled in and enabled.
+///
+/// # Returns
+/// Map in the JSON format. Where keys are names of subsystems and values are
+/// `{"compiled": <bool>, "enabled": <bool>}`
+pub fn get_features() -> String {
+    let features_json_p = unsafe { ffi::indy_get_features() };
+    opt_rust_str!(features_json_p).unwrap_or_else(|| String::from("{}"))
+}
+
 #[derive(Fail, Debug, PartialEq, Copy, Clone, FromPrimitive, ToPrimitive)]
 #[repr(i32)]
 #[allow(dead_code)]
@@ -179,6 +220,12 @@ pub enum ErrorCode
     // Caller passed invalid value as param 27 (null, invalid json and etc..)
     #[fail(display = "CommonInvalidParam27")]
     CommonInvalidParam27 = 129,
+    // Command queue of the library is full. The call can be retried later
+    #[fail(display = "CommonBusy")]
+    CommonBusy = 130,
+    // Command was cancelled with indy_cancel_command or its timeout expired
+    #[fail(display = "CommonCancelled")]
+    CommonCancelled = 131,
     // Wallet errors
     // Caller passed invalid wallet handle
     #[fail(display = "WalletInvalidHandle")]
@@ -336,7 +383,9 @@ impl Into<i32> for ErrorCode {
 pub struct IndyError {
     pub error_code: ErrorCode,
     pub message: String,
-    pub indy_backtrace: Option<String>
+    pub indy_backtrace: Option<String>,
+    pub indy_context: Vec<String>,
+    pub vendor_code: Option<i32>
 }
 
 impl Fail for IndyError {
@@ -368,6 +417,8 @@ impl IndyError {
                     error_code: ErrorCode::CommonInvalidState,
                     message: String::from("Invalid ErrorMessage pointer"),
                     indy_backtrace: None,
+                    indy_context: Vec::new(),
+                    vendor_code: None,
                 };
             }
         };
@@ -377,11 +428,15 @@ impl IndyError {
                 error_code,
                 message: error.message,
                 indy_backtrace: error.backtrace,
+                indy_context: error.extra.context,
+                vendor_code: error.extra.vendor_code,
             },
             Err(err) => IndyError {
                 error_code: ErrorCode::CommonInvalidState,
                 message: err.to_string(),
                 indy_backtrace: None,
+                indy_context: Vec::new(),
+                vendor_code: None,
             }
         }
     }
@@ -390,5 +445,14 @@ impl IndyError {
 #[derive(Deserialize)]
 struct ErrorDetails {
     message: String,
-    backtrace: Option<String>
+    backtrace: Option<String>,
+    #[serde(default)]
+    extra: ErrorExtra
+}
+
+#[derive(Deserialize, Default)]
+struct ErrorExtra {
+    #[serde(default)]
+    context: Vec<String>,
+    vendor_code: Option<i32>
 }
\ No newline at end of file
@@ -32,6 +32,25 @@ pub fn set_default_logger(pattern: Option<&str>) -> Result<(), IndyError> {
     }
 }
 
+/// Set correlation id for the subsequent Libindy calls from the current thread.
+///
+/// Correlation id prefixes log messages of the commands started by these calls.
+///
+/// # Arguments
+/// * `correlation_id` - (optional) correlation id to use. `None` restores generation of unique ids per call.
+pub fn set_correlation_id(correlation_id: Option<&str>) -> Result<(), IndyError> {
+    let correlation_id_str = opt_c_str!(correlation_id);
+
+    let res = ErrorCode::from(unsafe {
+        logger::indy_set_correlation_id(opt_c_ptr!(correlation_id, correlation_id_str))
+    });
+
+    match res {
+        ErrorCode::Success => Ok(()),
+        err => Err(IndyError::new(err))
+    }
+}
+
 /// Set application logger implementation to Libindy.
 ///
 /// # Arguments
@@ -44,6 +63,8 @@ pub fn set_logger(logger: &'static dyn Log) -> Result<(), IndyError> {
                     error_code: ErrorCode::CommonInvalidState,
                     message: "Logger is already set".to_string(),
                     indy_backtrace: None,
+                    indy_context: Vec::new(),
+                    vendor_code: None,
                 });
             }
             LOGGER = Some(Box::new(logger));
@@ -58,6 +58,13 @@ fn _create_pool_ledger_config(command_handle: CommandHandle, pool_name: &str, po
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
 ///         By default Libindy sends a read requests to 2 nodes in the pool.
 ///         If response isn't received or `state proof` is invalid Libindy sends the request again but to 2 (`number_read_nodes`) * 2 = 4 nodes and so far until completion.
+///     "namespace": string (optional) - namespace of the network (example: sovrin:staging).
+///         Several pools can be opened at once but only one of them can use a namespace.
+///         Requests signed by `did:indy:<namespace>:<id>` DIDs are routed to this pool automatically (look at `ledger::sign_and_submit_request`).
+///     "mock": bool (optional) - use in-process mock ledger instead of connecting to pool nodes (false by default).
+///         NYM, ATTRIB, SCHEMA and CRED_DEF transactions are kept in memory until the pool is closed
+///         and can be read back with the corresponding GET requests. Other requests are rejected.
+///         Pool ledger configuration isn't required to be created for the mock ledger.
 /// }
 ///
 /// # Returns
@@ -165,3 +172,87 @@ fn _set_protocol_version(command_handle: CommandHandle, protocol_version: usize,
       pool::indy_set_protocol_version(command_handle, protocol_version, cb)
     })
 }
+
+/// Returns the handle of the opened pool bound to the network namespace.
+///
+/// # Arguments
+/// * `network` - namespace of the network (example: sovrin:staging)
+///     or DID qualified with the network namespace (example: did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e).
+///
+/// # Returns
+/// Handle to opened pool to use in methods that require pool connection.
+pub fn get_pool_handle_for_network(network: &str) -> Box<dyn Future<Item=PoolHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _get_pool_handle_for_network(command_handle, network, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _get_pool_handle_for_network(command_handle: CommandHandle, network: &str, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let network = c_str!(network);
+
+    ErrorCode::from(unsafe { pool::indy_get_pool_handle_for_network(command_handle, network.as_ptr(), cb) })
+}
+
+/// Validates a genesis transactions file before it is used to create or open a pool ledger.
+///
+/// # Arguments
+/// * `genesis_txn` - path to genesis transactions file.
+/// * `options_json` - (optional) validation options json.
+/// {
+///     "check_endpoints": bool (optional) - try to connect to client endpoint of every validator node (false by default).
+///     "endpoint_timeout": int (optional) - time to wait for connection with a node (in sec).
+/// }
+///
+/// # Returns
+/// Validation report json.
+/// {
+///     "valid": bool - whether the pool can be opened with these genesis transactions,
+///     "txns_count": int - number of transactions in the file,
+///     "nodes_count": int - number of nodes defined by transactions,
+///     "issues": [{"kind": string, "line": int (optional), "node": string (optional), "message": string}]
+/// }
+pub fn validate_pool_genesis_txns(genesis_txn: &str, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _validate_pool_genesis_txns(command_handle, genesis_txn, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _validate_pool_genesis_txns(command_handle: CommandHandle, genesis_txn: &str, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let genesis_txn = c_str!(genesis_txn);
+    let options_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe { pool::indy_validate_pool_genesis_txns(command_handle, genesis_txn.as_ptr(), opt_c_ptr!(options_json, options_str), cb) })
+}
+
+/// Compares nodes defined by a genesis transactions file with the current nodes of created pool ledger configuration.
+///
+/// # Arguments
+/// * `config_name` - Name of the pool ledger configuration.
+/// * `genesis_txn` - path to genesis transactions file.
+///
+/// # Returns
+/// Difference json.
+/// {
+///     "added": [{"alias": string, "dest": string}] - nodes defined only by the genesis transactions file,
+///     "removed": [{"alias": string, "dest": string}] - nodes defined only by the pool ledger configuration,
+///     "changed": [{"alias": string, "dest": string, "fields": [string]}] - nodes with different data,
+///     "unchanged": int - number of nodes with the same data,
+/// }
+pub fn diff_pool_genesis_txns(config_name: &str, genesis_txn: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _diff_pool_genesis_txns(command_handle, config_name, genesis_txn, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _diff_pool_genesis_txns(command_handle: CommandHandle, config_name: &str, genesis_txn: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let config_name = c_str!(config_name);
+    let genesis_txn = c_str!(genesis_txn);
+
+    ErrorCode::from(unsafe { pool::indy_diff_pool_genesis_txns(command_handle, config_name.as_ptr(), genesis_txn.as_ptr(), cb) })
+}
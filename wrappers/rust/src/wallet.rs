@@ -148,6 +148,99 @@ fn _register_storage(command_handle: CommandHandle,
     })
 }
 
+/// Registers wallet key wrapper implementation.
+///
+/// Key wrapper allows to seal wallet export with the transit key of external KMS (HashiCorp Vault transit engine for example)
+/// instead of passphrase. See `sealed` param of `export_wallet`.
+///
+/// # Arguments
+/// * `xtype` - Key wrapper name.
+/// * `wrap_key` - KeyWrapper wrap key operation handler
+/// * `unwrap_key` - KeyWrapper unwrap key operation handler
+/// * `free_key` - Handler that allows to de-allocate keys allocated in caller code
+pub fn register_wallet_key_wrapper(xtype: &str,
+                                   wrap_key: Option<wallet::WalletKeyWrap>,
+                                   unwrap_key: Option<wallet::WalletKeyUnwrap>,
+                                   free_key: Option<wallet::WalletKeyFree>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_key_wrapper(command_handle, xtype, wrap_key, unwrap_key, free_key, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_key_wrapper(command_handle: CommandHandle,
+                         xtype: &str,
+                         wrap_key: Option<wallet::WalletKeyWrap>,
+                         unwrap_key: Option<wallet::WalletKeyUnwrap>,
+                         free_key: Option<wallet::WalletKeyFree>,
+                         cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_register_wallet_key_wrapper(command_handle, xtype.as_ptr(), wrap_key, unwrap_key, free_key, cb)
+    })
+}
+
+/// Registers atomic batch handler for the custom wallet storage registered with `register_wallet_storage`.
+///
+/// Batch handler allows to apply atomic record operations (see `with_wallet_records`)
+/// in a single storage transaction. Without it such operations fail for the custom storage.
+///
+/// # Arguments
+/// * `xtype` - Storage type name (must be registered already).
+/// * `batch_records` - WalletType batch records operation handler
+pub fn register_wallet_storage_batch(xtype: &str,
+                                     batch_records: Option<wallet::WalletBatchRecords>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_storage_batch(command_handle, xtype, batch_records, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_storage_batch(command_handle: CommandHandle,
+                           xtype: &str,
+                           batch_records: Option<wallet::WalletBatchRecords>,
+                           cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_register_wallet_storage_batch(command_handle, xtype.as_ptr(), batch_records, cb)
+    })
+}
+
+/// Registers chunked search handlers for the custom wallet storage registered with `register_wallet_storage`.
+///
+/// Chunked search handlers allow to fetch search results by chunks of records with a single call
+/// instead of fetching and reading every record separately.
+///
+/// # Arguments
+/// * `xtype` - Storage type name (must be registered already).
+/// * `fetch_search_next_records` - WalletType fetch search next records operation handler
+/// * `free_search_records` - WalletType free search records operation handler
+pub fn register_wallet_storage_search_chunk(xtype: &str,
+                                            fetch_search_next_records: Option<wallet::WalletFetchSearchNextRecords>,
+                                            free_search_records: Option<wallet::WalletFreeSearchRecords>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_storage_search_chunk(command_handle, xtype, fetch_search_next_records, free_search_records, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_storage_search_chunk(command_handle: CommandHandle,
+                                  xtype: &str,
+                                  fetch_search_next_records: Option<wallet::WalletFetchSearchNextRecords>,
+                                  free_search_records: Option<wallet::WalletFreeSearchRecords>,
+                                  cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_register_wallet_storage_search_chunk(command_handle, xtype.as_ptr(), fetch_search_next_records, free_search_records, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name.
 ///
 /// # Arguments
@@ -216,6 +309,16 @@ fn _open_wallet(command_handle: CommandHandle, config: &str, credentials: &str,
 ///   {
 ///     "path": path of the file that contains exported wallet content
 ///     "key": passphrase used to derive export key
+///     "sealed": optional<object> seal export with the transit key of external KMS instead of key
+///         {
+///             "key_wrapper": name of key wrapper registered with `register_wallet_key_wrapper`
+///             "key_id": identifier of the transit key in KMS
+///         }
+///     "filter": optional<object> export only records of listed types matching optional WQL query
+///         {
+///             "types": array of record types
+///             "query": optional WQL query
+///         }
 ///   }
 pub fn export_wallet(wallet_handle: WalletHandle, export_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -233,6 +336,80 @@ fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, ex
     })
 }
 
+/// Starts re-encryption of wallet records under a new master key.
+///
+/// Records are re-encrypted in batches by `reencrypt_wallet_records`, the wallet remains available
+/// for other operations between batches. The new key has to be used for opening of the wallet
+/// after re-encryption is finished.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by `open_wallet`
+/// * `reencryption_config` - Re-encryption configuration json.
+///   {
+///       "key": string, Current key or passphrase of the wallet.
+///       "new_key": string, Key or passphrase used for derivation of the new wallet master key.
+///       "key_derivation_method": optional<string> Algorithm used for current key derivation (ARGON2I_MOD by default).
+///       "new_key_derivation_method": optional<string> Algorithm to use for new key derivation (ARGON2I_MOD by default).
+///   }
+pub fn start_wallet_reencryption(wallet_handle: WalletHandle, reencryption_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _start_wallet_reencryption(command_handle, wallet_handle, reencryption_config, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _start_wallet_reencryption(command_handle: CommandHandle, wallet_handle: WalletHandle, reencryption_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let reencryption_config = c_str!(reencryption_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_start_wallet_reencryption(command_handle, wallet_handle, reencryption_config.as_ptr(), cb)
+    })
+}
+
+/// Re-encrypts the next batch of wallet records under the new master key.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by `open_wallet`
+/// * `batch_size` - max number of records to re-encrypt
+///
+/// # Returns
+/// progress json: {"reencrypted": int, "finished": bool}
+pub fn reencrypt_wallet_records(wallet_handle: WalletHandle, batch_size: usize) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _reencrypt_wallet_records(command_handle, wallet_handle, batch_size, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _reencrypt_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, batch_size: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+      wallet::indy_reencrypt_wallet_records(command_handle, wallet_handle, batch_size, cb)
+    })
+}
+
+/// Verifies wallet records can be decrypted and reports progress of re-encryption.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by `open_wallet`
+///
+/// # Returns
+/// status json: {"inProgress": bool, "total": int, "remaining": int, "undecryptable": int}
+pub fn get_wallet_reencryption_status(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_wallet_reencryption_status(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_wallet_reencryption_status(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+      wallet::indy_get_wallet_reencryption_status(command_handle, wallet_handle, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name and then imports its content
 /// according to fields provided in import_config
 /// This can be seen as an create call with additional content import
@@ -274,6 +451,35 @@ fn _import_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Imports records of exported wallet into opened wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by `open_wallet`
+/// * `import_config` - JSON containing settings for input operation.
+///   {
+///     "path": path of the file that contains exported wallet content
+///     "key": passphrase used to derive export key
+///     "conflict_policy": optional<string> skip, overwrite or fail (default) for records already present in the wallet
+///   }
+///
+/// # Returns
+/// import result json: {"added": int, "skipped": int, "overwritten": int}
+pub fn import_wallet_records(wallet_handle: WalletHandle, import_config: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _import_wallet_records(command_handle, wallet_handle, import_config, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _import_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, import_config: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_import_wallet_records(command_handle, wallet_handle, import_config.as_ptr(), cb)
+    })
+}
+
 /// Deletes created wallet.
 pub fn delete_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -499,6 +705,76 @@ fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHan
     })
 }
 
+/// Create a list of new non-secret records in the wallet atomically
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `records_json` - json array of records to add:
+///   [{"type": <str>, "id": <str>, "value": <str>, "tags": (optional) <object>}]
+pub fn add_wallet_records(wallet_handle: WalletHandle, records_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _add_wallet_records(command_handle, wallet_handle, records_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _add_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, records_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let records_json = c_str!(records_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_add_wallet_records(command_handle, wallet_handle, records_json.as_ptr(), cb)
+    })
+}
+
+/// Delete a list of existing wallet records atomically
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `record_ids_json` - json array of ids of records to delete:
+///   [{"type": <str>, "id": <str>}]
+pub fn delete_wallet_records(wallet_handle: WalletHandle, record_ids_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _delete_wallet_records(command_handle, wallet_handle, record_ids_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _delete_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, record_ids_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let record_ids_json = c_str!(record_ids_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_delete_wallet_records(command_handle, wallet_handle, record_ids_json.as_ptr(), cb)
+    })
+}
+
+/// Apply a list of add/update/delete operations to wallet records atomically
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `operations_json` - json array of operations applied in the given order:
+///   [
+///     {"op": "add", "type": <str>, "id": <str>, "value": <str>, "tags": (optional) <object>},
+///     {"op": "update", "type": <str>, "id": <str>, "value": (optional) <str>, "tags": (optional) <object>},
+///     {"op": "delete", "type": <str>, "id": <str>},
+///   ]
+pub fn with_wallet_records(wallet_handle: WalletHandle, operations_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _with_wallet_records(command_handle, wallet_handle, operations_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _with_wallet_records(command_handle: CommandHandle, wallet_handle: WalletHandle, operations_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let operations_json = c_str!(operations_json);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_with_wallet_records(command_handle, wallet_handle, operations_json.as_ptr(), cb)
+    })
+}
+
 /// Get an wallet record by id
 ///
 /// # Arguments
@@ -651,6 +927,12 @@ fn _default_credentials(credentials: Option<&str>) -> CString {
 /// {
 ///   "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                              Can be UTF-8, base64 or hex string.
+///   "mnemonic": object, (optional) BIP39 mnemonic for deterministic key creation instead of seed.
+///       {
+///           "phrase": string, mnemonic generated by `generate_mnemonic`
+///           "passphrase": string, (optional) BIP39 passphrase, empty by default
+///           "index": int, (optional) index of the key, 0 by default
+///       }
 /// }
 ///
 /// # Returns
@@ -668,3 +950,59 @@ fn _generate_wallet_key(command_handle: CommandHandle, config: Option<&str>, cb:
 
     ErrorCode::from(unsafe { wallet::indy_generate_wallet_key(command_handle, config.as_ptr(), cb) })
 }
+
+/// Generate BIP39 mnemonic.
+/// Mnemonic is a human readable backup of the wallet key and DIDs derived from it.
+///
+/// # Arguments
+/// * `config` - (optional) mnemonic configuration json.
+/// {
+///   "word_count": int, (optional) Number of words: 12, 15, 18, 21 or 24 (default).
+/// }
+///
+/// # Returns
+/// mnemonic phrase
+pub fn generate_mnemonic(config: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _generate_mnemonic(command_handle, config, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _generate_mnemonic(command_handle: CommandHandle, config: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let config = opt_c_str_json!(config);
+
+    ErrorCode::from(unsafe { wallet::indy_generate_mnemonic(command_handle, config.as_ptr(), cb) })
+}
+
+/// Split wallet key into shares for social recovery of the wallet.
+///
+/// Any `threshold` of shares are enough to reconstruct the key. Each share is encrypted for its recipient
+/// as in `crypto::anon_crypt`. Shares decrypted with `crypto::anon_decrypt` can be passed as "key_shares"
+/// of credentials to `open_wallet` and `import_wallet` or of import config to `import_wallet`.
+///
+/// # Arguments
+/// * `key` - wallet key, passphrase or export key to split.
+/// * `config` - key shares configuration json.
+/// {
+///   "threshold": int, Number of shares required to reconstruct the key. Must be at least 2.
+///   "recipients": array<string>, Verkeys of share holders.
+/// }
+///
+/// # Returns
+/// json array of base58 encrypted shares in the order of recipients.
+pub fn split_wallet_key(key: &str, config: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _split_wallet_key(command_handle, key, config, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _split_wallet_key(command_handle: CommandHandle, key: &str, config: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let key = c_str!(key);
+    let config = c_str!(config);
+
+    ErrorCode::from(unsafe { wallet::indy_split_wallet_key(command_handle, key.as_ptr(), config.as_ptr(), cb) })
+}
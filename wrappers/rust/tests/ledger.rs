@@ -615,6 +615,27 @@ mod test_build_get_frozen_ledgers_request {
     }
 }
 
+#[cfg(test)]
+mod test_parse_get_frozen_ledgers_response {
+    use super::*;
+
+    #[test]
+    pub fn parse_get_frozen_ledgers_response_success() {
+        let response = json!({
+            "op": "REPLY",
+            "result": {
+                "type": "10",
+                "data": {"909": {"ledger": "ledger_root_hash", "state": "state_root_hash", "seq_no": 10}}
+            }
+        }).to_string();
+
+        let frozen_ledgers = ledger::parse_get_frozen_ledgers_response(&response).wait().unwrap();
+        let frozen_ledgers: serde_json::Value = serde_json::from_str(&frozen_ledgers).unwrap();
+
+        assert_eq!(json!({"909": {"ledger": "ledger_root_hash", "state": "state_root_hash", "seq_no": 10}}), frozen_ledgers);
+    }
+}
+
 #[cfg(test)]
 mod test_build_get_cred_def_request {}
 
@@ -624,6 +645,24 @@ mod test_build_node_request {}
 #[cfg(test)]
 mod test_build_get_validator_info_request {}
 
+#[cfg(test)]
+mod test_parse_get_validator_info_response {
+    use super::*;
+
+    #[test]
+    pub fn parse_get_validator_info_response_success() {
+        let response = json!({
+            "Node1": json!({"op": "REPLY", "result": {"type": "119", "data": {"alias": "Node1"}}}).to_string(),
+            "Node2": "timeout",
+        }).to_string();
+
+        let results = ledger::parse_get_validator_info_response(&response).wait().unwrap();
+        let results: serde_json::Value = serde_json::from_str(&results).unwrap();
+
+        assert_eq!(json!({"Node1": {"status": "REPLY", "data": {"alias": "Node1"}}, "Node2": {"status": "timeout"}}), results);
+    }
+}
+
 #[cfg(test)]
 mod test_build_pool_config_request {}
 
@@ -663,6 +702,7 @@ mod test_register_transaction_parser_for_sp {}
 #[cfg(test)]
 mod test_build_auth_rule_request {
     use super::*;
+    use indy::ledger::auth_rule::{self, AuthAction, AuthRule, Constraint, RoleConstraint};
 
     const DID: &str = "VsKV7grR1BUE29mG2Fm2kX";
     const NYM_AUTH_TYPE: &str = "1";
@@ -708,4 +748,102 @@ mod test_build_auth_rule_request {
                                                                          None,
                                                                          None).wait().unwrap();
     }
+
+    #[test]
+    pub fn build_auth_rule_request_success_for_typed_constraint() {
+        let constraint = Constraint::or(vec![
+            RoleConstraint::new(Some("0"), 1).into(),
+            Constraint::and(vec![
+                RoleConstraint::new(Some("2"), 2).metadata(json!({"fees": "1"})).into(),
+                RoleConstraint::new(None, 1).need_to_be_owner(true).into(),
+            ]),
+        ]);
+
+        let request = auth_rule::build_auth_rule_request(DID,
+                                                         NYM_AUTH_TYPE,
+                                                         AuthAction::Add,
+                                                         FIELD,
+                                                         None,
+                                                         Some(NEW_VALUE),
+                                                         &constraint).wait().unwrap();
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+        let expected_constraint = json!({
+            "constraint_id": "OR",
+            "auth_constraints": [
+                {"constraint_id": "ROLE", "role": "0", "sig_count": 1, "need_to_be_owner": false},
+                {
+                    "constraint_id": "AND",
+                    "auth_constraints": [
+                        {"constraint_id": "ROLE", "role": "2", "sig_count": 2, "need_to_be_owner": false, "metadata": {"fees": "1"}},
+                        {"constraint_id": "ROLE", "role": null, "sig_count": 1, "need_to_be_owner": true}
+                    ]
+                }
+            ]
+        });
+        assert_eq!(request["operation"]["auth_action"], json!(ADD_AUTH_ACTION));
+        assert_eq!(request["operation"]["constraint"], expected_constraint);
+    }
+
+    #[test]
+    pub fn build_auth_rules_request_success_for_typed_rules() {
+        let rules = vec![
+            AuthRule {
+                auth_type: NYM_AUTH_TYPE.to_string(),
+                auth_action: AuthAction::Edit,
+                field: FIELD.to_string(),
+                old_value: Some(OLD_VALUE.to_string()),
+                new_value: Some(NEW_VALUE.to_string()),
+                constraint: Constraint::forbidden(),
+            }
+        ];
+
+        let request = auth_rule::build_auth_rules_request(DID, &rules).wait().unwrap();
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+        assert_eq!(request["operation"]["rules"][0]["auth_action"], json!("EDIT"));
+        assert_eq!(request["operation"]["rules"][0]["constraint"], json!({"constraint_id": "FORBIDDEN"}));
+    }
+
+    #[test]
+    pub fn build_get_auth_rule_request_success_for_typed_action() {
+        let request = auth_rule::build_get_auth_rule_request(Some(DID),
+                                                             Some(NYM_AUTH_TYPE),
+                                                             Some(AuthAction::Add),
+                                                             Some(FIELD),
+                                                             None,
+                                                             Some(NEW_VALUE)).wait().unwrap();
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+
+        assert_eq!(request["operation"]["auth_action"], json!(ADD_AUTH_ACTION));
+    }
+
+    #[test]
+    pub fn parse_get_auth_rule_response_success() {
+        let response = json!({
+            "op": "REPLY",
+            "result": {
+                "type": "121",
+                "data": [{
+                    "auth_type": NYM_AUTH_TYPE,
+                    "auth_action": ADD_AUTH_ACTION,
+                    "field": FIELD,
+                    "new_value": NEW_VALUE,
+                    "constraint": serde_json::from_str::<serde_json::Value>(ROLE_CONSTRAINT).unwrap()
+                }]
+            }
+        }).to_string();
+
+        let rules = auth_rule::parse_get_auth_rule_response(&response).unwrap();
+
+        let expected_rule = AuthRule {
+            auth_type: NYM_AUTH_TYPE.to_string(),
+            auth_action: AuthAction::Add,
+            field: FIELD.to_string(),
+            old_value: None,
+            new_value: Some(NEW_VALUE.to_string()),
+            constraint: RoleConstraint::new(Some("0"), 1).metadata(json!({})).into(),
+        };
+        assert_eq!(vec![expected_rule], rules);
+    }
 }
\ No newline at end of file